@@ -1,8 +1,9 @@
 use std::collections::HashMap;
+use std::time::Duration;
 
 use serde::Deserialize;
-use tokio::sync::mpsc;
 
+use breakpoint_core::event_source::{EventSource, SourceFuture, SourceHealth};
 use breakpoint_core::events::{Event, EventType, Priority};
 
 use crate::agent_detect::AgentDetector;
@@ -17,6 +18,8 @@ pub struct GitHubPoller {
     active_runs: HashMap<u64, RunState>,
     /// Rolling stats.
     stats: PollerStats,
+    /// Repos that failed during the last poll.
+    failed_repos: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -87,62 +90,63 @@ impl GitHubPoller {
             agent_detector,
             active_runs: HashMap::new(),
             stats: PollerStats::default(),
+            failed_repos: 0,
         }
     }
 
-    /// Run the poller loop, sending events through the channel.
-    pub async fn run(mut self, tx: mpsc::UnboundedSender<Event>) {
-        let interval = std::time::Duration::from_secs(self.config.poll_interval_secs);
-        loop {
-            for repo in &self.config.repos.clone() {
-                if let Err(e) = self.poll_repo(repo, &tx).await {
-                    tracing::warn!(repo, error = %e, "Failed to poll repo");
-                }
+    /// Poll every configured repo once. Fails only if every repo failed.
+    async fn poll_all(&mut self) -> Result<Vec<Event>, String> {
+        let mut events = Vec::new();
+        let mut last_error = None;
+        self.failed_repos = 0;
+        for repo in &self.config.repos.clone() {
+            if let Err(e) = self.poll_repo(repo, &mut events).await {
+                tracing::warn!(repo, error = %e, "Failed to poll repo");
+                self.failed_repos += 1;
+                last_error = Some(format!("{repo}: {e}"));
             }
-
-            // Prune stale active_runs older than 24h
-            let prune_cutoff =
-                std::time::Instant::now() - std::time::Duration::from_secs(24 * 3600);
-            self.active_runs.retain(|_, r| r.first_seen > prune_cutoff);
-            self.stats.prune();
-
-            // Emit aggregate ticker event
-            let active_count = self
-                .active_runs
-                .values()
-                .filter(|r| r.status != "completed")
-                .count();
-            let aggregate = Event {
-                id: format!("gh-agg-{}", uuid_simple()),
-                event_type: EventType::Custom,
-                source: "github-actions".to_string(),
-                priority: Priority::Ambient,
-                title: format!(
-                    "CI: {:.0}% pass rate, {} active runs",
-                    self.stats.pass_rate(),
-                    active_count
-                ),
-                body: None,
-                timestamp: breakpoint_core::time::timestamp_now(),
-                url: None,
-                actor: None,
-                tags: vec!["aggregate".to_string()],
-                action_required: false,
-                group_key: Some("github:ci-aggregate".to_string()),
-                expires_at: None,
-                metadata: HashMap::new(),
-            };
-            let _ = tx.send(aggregate);
-
-            tokio::time::sleep(interval).await;
         }
+        if let Some(e) = last_error
+            && self.failed_repos == self.config.repos.len()
+        {
+            return Err(e);
+        }
+
+        // Prune stale active_runs older than 24h
+        let prune_cutoff = std::time::Instant::now() - std::time::Duration::from_secs(24 * 3600);
+        self.active_runs.retain(|_, r| r.first_seen > prune_cutoff);
+        self.stats.prune();
+
+        // Emit aggregate ticker event
+        let active_count = self
+            .active_runs
+            .values()
+            .filter(|r| r.status != "completed")
+            .count();
+        events.push(Event {
+            id: format!("gh-agg-{}", uuid_simple()),
+            event_type: EventType::Custom,
+            source: "github-actions".to_string(),
+            priority: Priority::Ambient,
+            title: format!(
+                "CI: {:.0}% pass rate, {} active runs",
+                self.stats.pass_rate(),
+                active_count
+            ),
+            body: None,
+            timestamp: breakpoint_core::time::timestamp_now(),
+            url: None,
+            actor: None,
+            tags: vec!["aggregate".to_string()],
+            action_required: false,
+            group_key: Some("github:ci-aggregate".to_string()),
+            expires_at: None,
+            metadata: HashMap::new(),
+        });
+        Ok(events)
     }
 
-    async fn poll_repo(
-        &mut self,
-        repo: &str,
-        tx: &mpsc::UnboundedSender<Event>,
-    ) -> Result<(), String> {
+    async fn poll_repo(&mut self, repo: &str, events: &mut Vec<Event>) -> Result<(), String> {
         let url = format!(
             "https://api.github.com/repos/{repo}/actions/runs?per_page=20&status=in_progress"
         );
@@ -193,7 +197,7 @@ impl GitHubPoller {
                     expires_at: None,
                     metadata,
                 };
-                let _ = tx.send(event);
+                events.push(event);
             }
 
             self.active_runs.insert(
@@ -266,7 +270,7 @@ impl GitHubPoller {
                         expires_at: None,
                         metadata,
                     };
-                    let _ = tx.send(event);
+                    events.push(event);
                 }
             }
         }
@@ -275,6 +279,49 @@ impl GitHubPoller {
     }
 }
 
+impl EventSource for GitHubPoller {
+    fn name(&self) -> &str {
+        "github-actions"
+    }
+
+    fn config_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "required": ["token", "repos"],
+            "properties": {
+                "token": { "type": "string", "description": "GitHub personal access token" },
+                "repos": {
+                    "type": "array",
+                    "items": { "type": "string", "pattern": "^[^/]+/[^/]+$" },
+                    "description": "Repositories in owner/repo format"
+                },
+                "poll_interval_secs": { "type": "integer", "minimum": 1, "default": 30 },
+                "agent_patterns": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Glob patterns for agent/bot actors"
+                }
+            }
+        })
+    }
+
+    fn poll_interval(&self) -> Duration {
+        Duration::from_secs(self.config.poll_interval_secs)
+    }
+
+    fn poll(&mut self) -> SourceFuture<'_, Result<Vec<Event>, String>> {
+        Box::pin(self.poll_all())
+    }
+
+    fn health(&self) -> SourceHealth {
+        if self.failed_repos > 0 {
+            SourceHealth::Degraded
+        } else {
+            SourceHealth::Healthy
+        }
+    }
+}
+
 fn uuid_simple() -> String {
     uuid::Uuid::new_v4().to_string()[..8].to_string()
 }
@@ -307,5 +354,7 @@ mod tests {
         };
         let poller = GitHubPoller::new(config);
         assert!(poller.active_runs.is_empty());
+        assert_eq!(poller.name(), "github-actions");
+        assert_eq!(poller.health(), SourceHealth::Healthy);
     }
 }
@@ -0,0 +1,166 @@
+//! A minimal external bot for the bot API: joins a room with a bot token
+//! and plays mini-golf by putting straight at the cup.
+//!
+//! ```text
+//! breakpoint-bot-example ws://localhost:8080/ws ABCD-1234 <bot-token> [name]
+//! ```
+//!
+//! Everything it knows comes off the wire: `GameState` carries the game's
+//! own serialized state (`GolfState` here, decoded with the game crate),
+//! and each input goes back as a `BotAck` naming the tick it answers.
+
+use futures::{SinkExt, StreamExt};
+use tokio_tungstenite::tungstenite::Message;
+
+use breakpoint_core::game_trait::{GameId, PlayerId};
+use breakpoint_core::net::messages::{BotAckMsg, ClientMessage, JoinRoomMsg, ServerMessage};
+use breakpoint_core::net::protocol::{
+    PROTOCOL_VERSION, decode_server_message, encode_client_message,
+};
+use breakpoint_core::player::PlayerColor;
+use breakpoint_golf::course::all_courses;
+use breakpoint_golf::{GolfInput, GolfState};
+
+/// Distance the bot putts at full power.
+const FULL_POWER_DISTANCE: f32 = 20.0;
+
+#[tokio::main]
+async fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let [_, url, room_code, token, rest @ ..] = args.as_slice() else {
+        eprintln!("usage: breakpoint-bot-example <ws-url> <room-code> <bot-token> [name]");
+        std::process::exit(2);
+    };
+    let name = rest.first().map_or("PuttBot", String::as_str);
+
+    let (mut ws, _) = match tokio_tungstenite::connect_async(url.as_str()).await {
+        Ok(conn) => conn,
+        Err(e) => {
+            eprintln!("Failed to connect to {url}: {e}");
+            std::process::exit(1);
+        },
+    };
+    let join = ClientMessage::JoinRoom(JoinRoomMsg {
+        room_code: room_code.clone(),
+        player_name: name.to_string(),
+        player_color: PlayerColor::PALETTE[3],
+        protocol_version: PROTOCOL_VERSION,
+        session_token: None,
+        identity_key: None,
+        accepts_batches: false,
+        room_alias: None,
+        bot_token: Some(token.clone()),
+        tutorial: None,
+    });
+    let Ok(data) = encode_client_message(&join) else {
+        return;
+    };
+    if ws.send(Message::Binary(data.into())).await.is_err() {
+        return;
+    }
+
+    let mut bot = PuttBot::default();
+    while let Some(Ok(frame)) = ws.next().await {
+        let Message::Binary(data) = frame else {
+            continue;
+        };
+        let Ok(msg) = decode_server_message(&data) else {
+            continue;
+        };
+        match msg {
+            ServerMessage::JoinRoomResponse(resp) if !resp.success => {
+                eprintln!("Join refused: {:?}", resp.error);
+                std::process::exit(1);
+            },
+            ServerMessage::JoinRoomResponse(resp) => {
+                bot.player_id = resp.player_id;
+                println!("Joined {room_code} as player {:?}", resp.player_id);
+            },
+            ServerMessage::GameStart(start) => {
+                bot.playing = GameId::from_str_opt(&start.game_name) == Some(GameId::Golf);
+                if !bot.playing {
+                    println!("Sitting out {}: this bot only plays golf", start.game_name);
+                }
+            },
+            ServerMessage::GameState(gs) if bot.playing => {
+                let Ok(state) = rmp_serde::from_slice::<GolfState>(&gs.state_data) else {
+                    continue;
+                };
+                let Some(input) = bot.decide(&state) else {
+                    continue;
+                };
+                let ack = ClientMessage::BotAck(BotAckMsg {
+                    tick: gs.tick,
+                    input_data: rmp_serde::to_vec(&input).unwrap_or_default(),
+                });
+                if let Ok(data) = encode_client_message(&ack)
+                    && ws.send(Message::Binary(data.into())).await.is_err()
+                {
+                    break;
+                }
+            },
+            ServerMessage::GameEnd(end) => {
+                println!("Match over: {:?}", end.final_scores);
+                break;
+            },
+            _ => {},
+        }
+    }
+}
+
+#[derive(Default)]
+struct PuttBot {
+    player_id: Option<PlayerId>,
+    playing: bool,
+    /// Strokes taken this hole, so each is sent once: acks trail the
+    /// simulation, and the ball still looks stopped for a tick or two.
+    strokes_sent: u32,
+    voted_skip: bool,
+}
+
+impl PuttBot {
+    /// The input for this state, if the bot wants to act on it.
+    fn decide(&mut self, state: &GolfState) -> Option<GolfInput> {
+        let me = self.player_id?;
+        let ball = state.balls.get(&me)?;
+        let strokes = state.strokes.get(&me).copied().unwrap_or(0);
+        if strokes < self.strokes_sent {
+            // A new hole started
+            self.strokes_sent = strokes;
+            self.voted_skip = false;
+        }
+
+        let mut input = GolfInput {
+            aim_angle: 0.0,
+            power: 0.0,
+            stroke: false,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
+        };
+        if state.intro_remaining > 0.0 {
+            if self.voted_skip {
+                return None;
+            }
+            self.voted_skip = true;
+            input.skip_intro = true;
+            return Some(input);
+        }
+        if ball.is_sunk || !ball.is_stopped() || strokes < self.strokes_sent {
+            return None;
+        }
+
+        // Built-in courses only; the hint covers holes out of sight
+        let course = all_courses().into_iter().nth(state.course_index as usize)?;
+        let to_hole = course.hole_position - ball.position;
+        input.aim_angle = match state.shot_hints.get(&me) {
+            Some(hint) => hint.aim_angle,
+            None => to_hole.z.atan2(to_hole.x),
+        };
+        input.power = (ball.distance_to_hole(&course) / FULL_POWER_DISTANCE).clamp(0.15, 1.0);
+        input.stroke = true;
+        self.strokes_sent = strokes + 1;
+        Some(input)
+    }
+}
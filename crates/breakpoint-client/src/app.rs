@@ -1,23 +1,51 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use glam::{Vec2, Vec4};
+use glam::Vec4;
 use serde::{Deserialize, Serialize};
 
+use breakpoint_core::announcement::Announcement;
 use breakpoint_core::game_trait::{BreakpointGame, GameConfig, GameId, PlayerId, PlayerScore};
-use breakpoint_core::net::messages::MessageType;
-use breakpoint_core::net::protocol::{decode_message_type, decode_server_message};
-use breakpoint_core::player::Player;
-
+use breakpoint_core::heatmap::RoundHeatmap;
+use breakpoint_core::i18n::{LocalizedText, TextKey};
+use breakpoint_core::input_replay::{
+    INPUT_REPLAY_MAGIC, ReplayPlayer, decode_input_replay, encode_input_replay,
+};
+use breakpoint_core::lobby_toy::{TargetClick, TargetPracticeState};
+use breakpoint_core::net::e2e::{self, RoomCipher};
+use breakpoint_core::net::messages::{
+    AddLocalPlayerMsg, AdvanceRoundMsg, ClientMessage, ClockSyncMsg, CourseCandidate,
+    DraftedPlayer, FriendInviteMsg, IntermissionMsg, JoinQueueUpdateMsg, JoinRoomMsg,
+    LobbyToyInputMsg, MessageType, RelayMessage, RelayToPeerMsg, RemoveLocalPlayerMsg,
+    RerollNameMsg, ReshapeRoomMsg, RoomRekeyMsg, RoomReshape, RoomTransferMsg, ServerMessage,
+    SocialUpdateMsg, StandingsMsg,
+};
+use breakpoint_core::net::protocol::{
+    PROTOCOL_VERSION, decode_message_type, decode_relay_message, decode_server_message,
+    encode_client_message, encode_relay_message, encode_server_message, split_batch,
+};
+use breakpoint_core::overlay::config::{OverlayConfigMsg, OverlayRoomConfig};
+use breakpoint_core::player::{Player, PlayerColor};
+use breakpoint_core::room_export::RoomExport;
+use breakpoint_core::round_phase::{RoundPacing, RoundPhase};
+use breakpoint_core::tutorial::TutorialChecklist;
+use breakpoint_core::victory::VictorySummary;
+
+use crate::assets::AssetLoader;
 use crate::audio::{AudioEvent, AudioEventQueue, AudioManager, AudioSettings};
 use crate::bridge;
 use crate::camera_gl::{Camera, CameraMode};
+use crate::clock_sync::ClockSync;
 use crate::effects::{ScreenFlash, ScreenShake};
-use crate::game::{GameRegistry, read_game_state};
+use crate::game::{GameRegistry, player_position, read_game_state};
+use crate::hot_seat::HotSeat;
 use crate::input::InputState;
+use crate::local_host::{LOCAL_PEER, LocalHost, OFFLINE_ROOM_CODE, Recipient};
 use crate::net_client::WsClient;
+use crate::net_diag::NetDiagnostics;
 use crate::overlay::{OverlayEventQueue, OverlayNetEvent, OverlayState};
 use crate::particles::ParticleSystem;
 use crate::renderer::Renderer;
+use crate::replay_viewer::ReplayPlayback;
 use crate::scene::Scene;
 use crate::theme::Theme;
 
@@ -26,9 +54,13 @@ use crate::theme::Theme;
 pub enum AppState {
     #[default]
     Lobby,
+    /// A match has started but the game's assets are still downloading.
+    Loading,
     InGame,
     BetweenRounds,
     GameOver,
+    /// Watching a `.bpreplay` file offline.
+    Replay,
 }
 
 /// Lobby state.
@@ -42,22 +74,71 @@ pub struct LobbyState {
     pub players: Vec<Player>,
     pub connected: bool,
     pub is_spectator: bool,
-    pub error_message: Option<String>,
+    pub error_message: Option<LocalizedText>,
     pub ws_url: String,
     pub selected_game: GameId,
     pub join_code_input: String,
-    pub status_message: Option<String>,
+    pub status_message: Option<LocalizedText>,
     /// Session token from the server for reconnection support.
     pub session_token: Option<String>,
     /// Per-game custom settings set in the lobby UI.
     pub game_settings: HashMap<String, serde_json::Value>,
+    /// Identity secret sent on join so the server can track friends.
+    pub identity_key: String,
+    /// Friends list and recent co-players from the server.
+    pub social: Option<SocialUpdateMsg>,
+    /// Received room invites, newest last.
+    pub invites: Vec<FriendInviteMsg>,
+    /// Tutorial checklists from the server, one per game.
+    pub tutorials: Vec<TutorialChecklist>,
+    /// Active MOTD and operator announcements, most severe first.
+    pub announcements: Vec<Announcement>,
+    /// Players waiting to enter the current room.
+    pub join_queue: Option<JoinQueueUpdateMsg>,
+    /// The room's overlay settings, including which alerts reach it.
+    pub overlay_config: OverlayRoomConfig,
+    /// Round pacing the leader sends with the next game start.
+    pub pacing: RoundPacing,
+    /// Start the season's featured game, with its modifiers, instead of
+    /// the selected one.
+    pub featured: bool,
+    /// Shared secret for an end-to-end encrypted relay room: generated
+    /// when hosting (and replaced on each key rotation), entered when
+    /// joining.
+    pub room_secret: Option<String>,
+    /// Vanity alias requested for the room this player created, shown
+    /// beside its code.
+    pub room_alias: Option<String>,
+    /// The room's target practice, shared while it waits in the lobby.
+    pub toy: Option<TargetPracticeState>,
+    /// Final scores summed over the matches played in this room, carried
+    /// over on merges and splits.
+    pub session_scores: HashMap<PlayerId, i32>,
+    /// Games a scheduled session lined up for this room, next first.
+    pub playlist: Vec<GameId>,
+    /// An open offer to move to another room after a merge or split.
+    pub transfer_offer: Option<TransferOffer>,
+}
+
+/// A move to another room offered by the server, until the player
+/// accepts, declines, or it lapses.
+pub struct TransferOffer {
+    pub offer: RoomTransferMsg,
+    /// Timestamp (ms) when the offer lapses.
+    pub expires_at: f64,
 }
 
+/// Most invites kept for display; older ones are dropped.
+const MAX_SHOWN_INVITES: usize = 5;
+
 /// Active game instance.
 pub struct ActiveGame {
     pub game: Box<dyn BreakpointGame>,
     pub game_id: GameId,
     pub tick: u32,
+    /// Server clock when `tick` was simulated; 0 before the first snapshot.
+    /// [`App::server_now`] gives the server clock to measure it against.
+    pub server_time_ms: u64,
     pub tick_accumulator: f32,
 }
 
@@ -75,6 +156,8 @@ pub struct RoundTracker {
     pub cumulative_scores: HashMap<PlayerId, i32>,
     /// Per-round score history: round_scores[i] = scores for round i+1.
     pub round_scores: Vec<HashMap<PlayerId, i32>>,
+    /// Players whose latest score was reported as disconnected.
+    pub disconnected: HashSet<PlayerId>,
 }
 
 impl RoundTracker {
@@ -84,6 +167,7 @@ impl RoundTracker {
             total_rounds,
             cumulative_scores: HashMap::new(),
             round_scores: Vec::new(),
+            disconnected: HashSet::new(),
         }
     }
 
@@ -92,6 +176,11 @@ impl RoundTracker {
         for s in scores {
             *self.cumulative_scores.entry(s.player_id).or_insert(0) += s.score;
             round_map.insert(s.player_id, s.score);
+            if s.disconnected {
+                self.disconnected.insert(s.player_id);
+            } else {
+                self.disconnected.remove(&s.player_id);
+            }
         }
         self.round_scores.push(round_map);
     }
@@ -111,6 +200,43 @@ pub struct ReconnectInfo {
     pub session_token: String,
 }
 
+/// A pre-round course vote, from the moment it opens until shortly after
+/// the winner is announced.
+pub struct CourseVoteView {
+    pub round: u8,
+    pub candidates: Vec<CourseCandidate>,
+    pub votes: Vec<u8>,
+    /// Timestamp (ms) when voting closes.
+    pub end_time: f64,
+    pub winner: Option<u8>,
+    /// Timestamp (ms) when the winner was announced.
+    pub closed_at: Option<f64>,
+    /// The local player's ballot.
+    pub my_choice: Option<u8>,
+}
+
+/// A pre-match captain draft, from the moment it opens until shortly after
+/// the teams are settled.
+pub struct TeamDraftView {
+    pub captains: Vec<PlayerId>,
+    pub picks: Vec<DraftedPlayer>,
+    pub pool: Vec<PlayerId>,
+    /// The team picking now; `None` once the draft is over.
+    pub turn: Option<u8>,
+    /// Timestamp (ms) when the pick clock runs out.
+    pub end_time: f64,
+    /// Timestamp (ms) when the draft closed.
+    pub closed_at: Option<f64>,
+}
+
+impl TeamDraftView {
+    /// The captain picking now.
+    pub fn captain(&self) -> Option<PlayerId> {
+        self.turn
+            .and_then(|team| self.captains.get(team as usize).copied())
+    }
+}
+
 const MAX_RECONNECT_ATTEMPTS: u32 = 10;
 
 fn reconnect_delay(attempt: u32) -> f64 {
@@ -133,6 +259,8 @@ pub struct App {
     pub audio_manager: AudioManager,
     pub audio_events: AudioEventQueue,
     pub audio_settings: AudioSettings,
+    /// Golf swings by dragging back from the ball instead of clicking a spot.
+    pub golf_drag_aim: bool,
     pub theme: Theme,
     pub lobby: LobbyState,
     pub game: Option<ActiveGame>,
@@ -140,7 +268,28 @@ pub struct App {
     pub overlay: OverlayState,
     pub overlay_queue: OverlayEventQueue,
     pub round_tracker: Option<RoundTracker>,
+    /// Heatmap sent with the last `RoundEnd`, shown between rounds.
+    pub round_heatmap: Option<RoundHeatmap>,
+    /// Announcer summary from the last `RoundEnd` or `GameEnd`.
+    pub victory: Option<VictorySummary>,
+    /// Loaded replay while in [`AppState::Replay`].
+    pub replay: Option<ReplayPlayback>,
+    /// Recorded match from the last `GameEnd`, for the download link.
+    pub last_match_id: Option<String>,
+    /// Room this client hosts itself, for LAN or offline play.
+    pub local_host: Option<LocalHost>,
+    /// Network diagnostics panel data (F4).
+    pub net_diag: NetDiagnostics,
+    /// State diff panel data (F7).
+    #[cfg(feature = "state-diff")]
+    pub state_inspector: crate::state_inspector::StateInspector,
+    /// The server's clock, measured with `ClockSync` probes.
+    pub clock: ClockSync,
     pub registry: GameRegistry,
+    /// Per-game assets, fetched when a game is first picked.
+    pub assets: AssetLoader,
+    /// Hot-seat guests sharing this keyboard.
+    pub hot_seat: HotSeat,
     pub screen_shake: ScreenShake,
     pub screen_flash: ScreenFlash,
     pub particle_system: ParticleSystem,
@@ -151,15 +300,37 @@ pub struct App {
     prev_enemy_alive: Vec<(u16, bool)>,
     /// Previous frame powerup collected states (for detecting pickups).
     prev_powerup_collected: Vec<bool>,
+    /// Golf highlights already celebrated on the current hole.
+    prev_golf_highlights: usize,
+    /// Number of the next power-up feed event to play.
+    next_powerup_seq: u32,
+    /// Whether the local golf ball was resting with the cup out of sight.
+    prev_golf_blind: bool,
     pub was_connected: bool,
     pub reconnect_info: Option<ReconnectInfo>,
     /// Timestamp (ms) when between-round countdown expires.
     pub between_round_end_time: Option<f64>,
+    /// Length (s) of the current between-round countdown, for its progress bar.
+    pub between_round_total_secs: f32,
+    /// Latest intermission announcement while between rounds.
+    pub intermission: Option<IntermissionMsg>,
+    /// Current round phase as announced by the server.
+    pub round_phase: RoundPhase,
+    /// Timestamp (ms) when the current timed round phase ends.
+    pub round_phase_end_time: Option<f64>,
+    /// Latest standings for the hold-to-view scoreboard.
+    pub standings: Option<StandingsMsg>,
     /// Timestamp (ms) when game-over was entered (for auto-return countdown).
     pub game_over_timestamp: Option<f64>,
+    /// Open or just-closed platformer course vote.
+    pub course_vote: Option<CourseVoteView>,
+    /// Open or just-closed team draft.
+    pub team_draft: Option<TeamDraftView>,
     pub(crate) prev_timestamp: f64,
     /// Tracks local player alive state for Tron crash audio detection.
     prev_local_alive: bool,
+    /// Whether the last Tron state was in sudden-death overtime.
+    prev_tron_overtime: bool,
     /// Frame counter for throttling continuous audio (e.g. Tron grind).
     audio_frame_counter: u32,
     /// Timestamp (ms) of the last JS bridge push. Throttled to 10 Hz.
@@ -171,10 +342,12 @@ pub struct App {
 impl App {
     pub fn new(renderer: Renderer) -> Self {
         let theme = Theme::load();
-        let mut lobby = LobbyState {
-            player_name: format!("Player{}", fastrand::u16(..1000)),
-            ..Default::default()
-        };
+        // Until the player types a name, the room generates one
+        let mut lobby = LobbyState::default();
+        // Golf course flyover is on unless the host turns it off
+        lobby
+            .game_settings
+            .insert("course_intro".to_string(), serde_json::json!(true));
 
         // Determine WebSocket URL
         #[cfg(target_family = "wasm")]
@@ -219,24 +392,9 @@ impl App {
             }
         }
 
-        // Load audio settings from localStorage
-        let mut audio_settings = AudioSettings::default();
-        crate::storage::with_local_storage(|storage| {
-            if let Ok(Some(val)) = storage.get_item("audio_muted") {
-                audio_settings.muted = val == "true";
-            }
-            if let Ok(Some(val)) = storage.get_item("audio_master_volume")
-                && let Ok(v) = val.parse::<f32>()
-            {
-                audio_settings.muted = false;
-                audio_settings.master_volume = v.clamp(0.0, 1.0);
-            }
-            if let Ok(Some(val)) = storage.get_item("audio_music_volume")
-                && let Ok(v) = val.parse::<f32>()
-            {
-                audio_settings.music_volume = v.clamp(0.0, 1.0);
-            }
-        });
+        lobby.identity_key = crate::storage::load_identity_key();
+
+        let audio_settings = crate::storage::load_audio_settings();
 
         let registry = crate::game::create_registry();
 
@@ -250,6 +408,7 @@ impl App {
             audio_manager: AudioManager::new(),
             audio_events: AudioEventQueue::default(),
             audio_settings,
+            golf_drag_aim: crate::storage::load_golf_drag_aim(),
             theme,
             lobby,
             game: None,
@@ -257,7 +416,18 @@ impl App {
             overlay: OverlayState::new(),
             overlay_queue: OverlayEventQueue::default(),
             round_tracker: None,
+            round_heatmap: None,
+            victory: None,
+            replay: None,
+            last_match_id: None,
+            local_host: None,
+            net_diag: NetDiagnostics::default(),
+            #[cfg(feature = "state-diff")]
+            state_inspector: Default::default(),
+            clock: ClockSync::default(),
             registry,
+            assets: AssetLoader::default(),
+            hot_seat: HotSeat::default(),
             screen_shake: ScreenShake::default(),
             screen_flash: ScreenFlash::default(),
             particle_system: ParticleSystem::new(),
@@ -265,12 +435,23 @@ impl App {
             prev_player_hp: HashMap::new(),
             prev_enemy_alive: Vec::new(),
             prev_powerup_collected: Vec::new(),
+            prev_golf_highlights: 0,
+            next_powerup_seq: 0,
+            prev_golf_blind: false,
             was_connected: false,
             reconnect_info: None,
             between_round_end_time: None,
+            between_round_total_secs: 0.0,
+            intermission: None,
+            round_phase: RoundPhase::Playing,
+            round_phase_end_time: None,
+            standings: None,
             game_over_timestamp: None,
+            course_vote: None,
+            team_draft: None,
             prev_timestamp: 0.0,
             prev_local_alive: true,
+            prev_tron_overtime: false,
             audio_frame_counter: 0,
             last_bridge_push: 0.0,
             prev_bridge_state: AppState::Lobby,
@@ -302,8 +483,10 @@ impl App {
         // Process network messages
         {
             breakpoint_core::profile!("network");
+            self.drive_local_host(dt);
             self.process_network(timestamp);
         }
+        crate::telemetry::flush_if_due(timestamp);
 
         // Process overlay events
         {
@@ -317,10 +500,19 @@ impl App {
             breakpoint_core::profile!("game_update");
             match self.state {
                 AppState::Lobby => {},
+                AppState::Loading => {
+                    if self.assets.is_ready(self.lobby.selected_game) {
+                        self.transition_to(AppState::InGame);
+                    }
+                },
                 AppState::InGame => {
                     self.update_game(dt);
                 },
                 AppState::BetweenRounds => {},
+                AppState::Replay => {
+                    self.update_replay(dt);
+                    self.update_game(dt);
+                },
                 AppState::GameOver => {
                     // Auto-return to lobby after 30s
                     if let Some(start) = self.game_over_timestamp {
@@ -451,6 +643,9 @@ impl App {
         #[cfg(feature = "profiling")]
         bridge::push_profile_data();
 
+        #[cfg(feature = "state-diff")]
+        bridge::push_state_diff(&mut self.state_inspector);
+
         // End frame
         self.input.end_frame();
     }
@@ -479,6 +674,7 @@ impl App {
                     self.state = AppState::Lobby;
                     // Clean up game state (same as transition_to Lobby body)
                     if old_state == AppState::InGame
+                        || old_state == AppState::Loading
                         || old_state == AppState::BetweenRounds
                         || old_state == AppState::GameOver
                     {
@@ -499,30 +695,120 @@ impl App {
         // Drive reconnection attempts
         self.drive_reconnection(timestamp);
 
-        let messages = self.ws.drain_messages();
+        let (sent, sent_bytes) = self.ws.take_sent();
+        self.net_diag.record_sent(sent, sent_bytes, timestamp);
+
+        // Batch frames are unpacked here, so everything below sees single messages
+        let mut messages = Vec::new();
+        for data in self.ws.drain_messages() {
+            // A joined encrypted room drops anything the host didn't seal
+            let data = if e2e::is_sealed(&data) {
+                match self.ws.open(&data) {
+                    Ok(data) => data,
+                    Err(e) => {
+                        crate::telemetry::decode_failed("sealed message", data.len(), &e);
+                        continue;
+                    },
+                }
+            } else if self.ws.is_encrypted() && self.local_host.is_none() {
+                continue;
+            } else {
+                data
+            };
+            // Key changes apply before the messages sealed with the new key
+            if data.first() == Some(&(MessageType::RoomRekey as u8)) {
+                if self.local_host.is_none() {
+                    self.apply_room_rekey(&data);
+                }
+                continue;
+            }
+            if data.first() != Some(&(MessageType::Batch as u8)) {
+                messages.push(data);
+                continue;
+            }
+            match split_batch(&data) {
+                Ok(frames) => messages.extend(frames.into_iter().map(<[u8]>::to_vec)),
+                Err(e) => crate::telemetry::decode_failed("batch", data.len(), &e),
+            }
+        }
         for data in messages {
             let msg_type = match decode_message_type(&data) {
                 Ok(t) => t,
                 Err(e) => {
-                    crate::diag::console_warn!(
-                        "Failed to decode message type ({} bytes): {e}",
-                        data.len()
-                    );
+                    crate::telemetry::decode_failed("message type", data.len(), &e);
                     continue;
                 },
             };
+            self.net_diag
+                .record_message(msg_type, data.len(), timestamp);
+
+            // Relay envelopes carry the remote players of a locally hosted room
+            if self.local_host.is_some()
+                && matches!(
+                    msg_type,
+                    MessageType::RelayRoomCreated
+                        | MessageType::RelayFromPeer
+                        | MessageType::RelayPeerLeft
+                )
+            {
+                self.process_relay_message(&data);
+                continue;
+            }
+
+            // Friends, invites, tutorial checklists, announcements, the
+            // join queue, room overlay settings, and transfer offers are
+            // relevant in every state
+            if matches!(
+                msg_type,
+                MessageType::SocialUpdate
+                    | MessageType::FriendInvite
+                    | MessageType::TutorialProgress
+                    | MessageType::Announcements
+                    | MessageType::JoinQueueUpdate
+                    | MessageType::OverlayConfig
+                    | MessageType::RoomTransfer
+            ) {
+                self.process_social_message(&data);
+                continue;
+            }
+
+            if msg_type == MessageType::ClockSyncReply {
+                if let Ok(ServerMessage::ClockSyncReply(reply)) = decode_server_message(&data) {
+                    self.clock
+                        .record_reply(reply.client_time, reply.server_time_ms, timestamp);
+                }
+                continue;
+            }
+
+            // Course votes open in the lobby and between rounds
+            if msg_type == MessageType::CourseVote {
+                self.process_course_vote(&data);
+                continue;
+            }
+            if msg_type == MessageType::TeamDraft {
+                self.process_team_draft(&data);
+                continue;
+            }
 
             match self.state {
                 AppState::Lobby => self.process_lobby_message(&data, msg_type),
-                AppState::InGame => self.process_game_message(&data, msg_type),
+                AppState::Loading | AppState::InGame => {
+                    self.process_game_message(&data, msg_type);
+                },
                 AppState::BetweenRounds => {
                     self.process_between_rounds_message(&data, msg_type);
                 },
                 AppState::GameOver => {
                     self.process_game_over_message(&data, msg_type);
                 },
+                AppState::Replay => {},
             }
         }
+
+        // Keep measuring the server's clock while in a room
+        if self.lobby.connected && self.clock.take_probe(timestamp) {
+            self.send_clock_sync(timestamp);
+        }
     }
 
     /// Attempt reconnection on schedule.
@@ -579,22 +865,238 @@ impl App {
         if should_give_up {
             self.reconnect_info = None;
             bridge::show_disconnect_banner(MAX_RECONNECT_ATTEMPTS, MAX_RECONNECT_ATTEMPTS, 0.0);
-            self.lobby.error_message = Some("Connection lost. Please rejoin.".to_string());
+            self.lobby.error_message = Some(TextKey::ConnectionLostRejoin.into());
             self.lobby.status_message = self.lobby.error_message.clone();
             return;
         }
 
-        if should_send_join && let Some(recon) = self.reconnect_info.take() {
-            self.send_join_room(&recon.room_code, &recon.player_name, recon.color_index);
+        if should_send_join && let Some(ref recon) = self.reconnect_info {
+            let (code, name) = (recon.room_code.clone(), recon.player_name.clone());
+            // The join carries the session token from reconnect_info, so it
+            // is cleared only once the join is sent
+            self.send_join_room(&code, &name, recon.color_index);
+            self.reconnect_info = None;
+        }
+    }
+
+    /// Host a room in this client. With a relay URL, remote players join
+    /// through the relay using the code it assigns, and with `encrypted`
+    /// their traffic is sealed with a room secret the relay never sees;
+    /// without a URL the room is offline, for playing against bots.
+    pub fn host_local(
+        &mut self,
+        relay_url: Option<&str>,
+        encrypted: bool,
+        alias: Option<&str>,
+    ) -> Result<(), LocalizedText> {
+        if self.lobby.connected || self.local_host.is_some() {
+            return Err(TextKey::AlreadyInRoomCreate.into());
+        }
+        self.lobby.is_leader = true;
+        self.ws.set_cipher(None);
+        self.lobby.room_secret = (relay_url.is_some() && encrypted).then(e2e::generate_secret);
+        // Encrypted rooms are joined by code, since the room key is bound
+        // to it, and offline rooms can't be joined at all
+        let alias = alias
+            .map(|a| a.trim().to_ascii_uppercase())
+            .filter(|a| !a.is_empty() && relay_url.is_some() && !encrypted);
+        self.lobby.room_alias = alias.clone();
+        match relay_url {
+            Some(url) => {
+                self.ws
+                    .connect(url)
+                    .map_err(|e| LocalizedText::new(TextKey::ConnectionFailed).with("reason", e))?;
+                // An empty code asks the relay for a new room; the local
+                // join waits for the code it sends back
+                let mut host = LocalHost::new(String::new());
+                host.set_room_alias(alias.clone());
+                self.local_host = Some(host);
+                self.ws.set_loopback(true);
+                // Encrypted rooms keep even the host's name from the relay
+                let player_name = if encrypted {
+                    String::new()
+                } else {
+                    self.lobby.player_name.clone()
+                };
+                self.send_relay_join(&ClientMessage::JoinRoom(JoinRoomMsg {
+                    room_code: String::new(),
+                    player_name,
+                    player_color: PlayerColor::default(),
+                    protocol_version: PROTOCOL_VERSION,
+                    session_token: None,
+                    identity_key: None,
+                    accepts_batches: true,
+                    room_alias: alias,
+                    bot_token: None,
+                    tutorial: None,
+                }));
+                self.lobby.status_message = Some(TextKey::ConnectingRelay.into());
+            },
+            None => {
+                self.local_host = Some(LocalHost::new(OFFLINE_ROOM_CODE.to_string()));
+                self.ws.set_loopback(true);
+                let (name, color) = (self.lobby.player_name.clone(), self.lobby.color_index);
+                self.send_join_room(OFFLINE_ROOM_CODE, &name, color);
+                self.lobby.status_message = Some(TextKey::CreatingRoom.into());
+            },
+        }
+        Ok(())
+    }
+
+    fn send_relay_join(&self, msg: &ClientMessage) {
+        match encode_client_message(msg) {
+            Ok(data) => {
+                if let Err(e) = self.ws.send_raw(&data) {
+                    crate::diag::console_warn!("Failed to send JoinRoom (relay): {e}");
+                }
+            },
+            Err(e) => crate::diag::console_warn!("Failed to encode JoinRoom (relay): {e}"),
+        }
+    }
+
+    /// Run the locally hosted room: hand it this client's messages, advance
+    /// the game, and deliver what it sends.
+    fn drive_local_host(&mut self, dt: f32) {
+        let Some(ref mut host) = self.local_host else {
+            return;
+        };
+
+        // Keep playing if the relay goes away, without the remote players
+        if self.was_connected && !self.ws.is_connected() && self.ws.has_connection() {
+            self.ws.disconnect();
+            self.was_connected = false;
+            for peer in host.remote_peers() {
+                host.peer_left(peer);
+            }
+            bridge::hide_disconnect_banner();
+            self.lobby.status_message = Some(TextKey::RelayConnectionLost.into());
+        }
+
+        host.set_clock(self.prev_timestamp);
+        for data in self.ws.take_loopback() {
+            host.handle_message(LOCAL_PEER, &data, &self.registry);
+        }
+        host.update(dt);
+
+        for (to, data) in host.take_outbox() {
+            let sealed = match to {
+                Recipient::Peer(LOCAL_PEER) => Ok(Vec::new()),
+                _ => self.ws.seal(&data),
+            };
+            let sealed = match sealed {
+                Ok(sealed) => sealed,
+                Err(e) => {
+                    crate::diag::console_warn!("Failed to seal host message: {e}");
+                    continue;
+                },
+            };
+            match to {
+                Recipient::Peer(LOCAL_PEER) => self.ws.inject(data),
+                Recipient::Peer(peer_id) => {
+                    let msg = RelayMessage::ToPeer(RelayToPeerMsg {
+                        peer_id,
+                        data: sealed,
+                    });
+                    match encode_relay_message(&msg) {
+                        Ok(envelope) => {
+                            if let Err(e) = self.ws.send_raw(&envelope) {
+                                crate::diag::console_warn!("Failed to send to peer {peer_id}: {e}");
+                            }
+                        },
+                        Err(e) => crate::diag::console_warn!("Failed to encode RelayToPeer: {e}"),
+                    }
+                },
+                Recipient::All => {
+                    if self.ws.is_connected()
+                        && let Err(e) = self.ws.send_raw(&sealed)
+                    {
+                        crate::diag::console_warn!("Failed to broadcast to relay: {e}");
+                    }
+                    self.ws.inject(data);
+                },
+            }
+        }
+    }
+
+    fn process_relay_message(&mut self, data: &[u8]) {
+        let Some(ref mut host) = self.local_host else {
+            return;
+        };
+        match decode_relay_message(data) {
+            Ok(RelayMessage::RoomCreated(msg)) => {
+                host.set_room_code(msg.room_code.clone());
+                if let Some(ref secret) = self.lobby.room_secret {
+                    self.ws
+                        .set_cipher(Some(RoomCipher::new(&msg.room_code, secret)));
+                }
+                let (name, color) = (self.lobby.player_name.clone(), self.lobby.color_index);
+                self.send_join_room(&msg.room_code, &name, color);
+            },
+            // In an encrypted room only sealed messages count; the plaintext
+            // join a peer sends to find the room is for the relay
+            Ok(RelayMessage::FromPeer(msg)) if self.ws.is_encrypted() => {
+                if !e2e::is_sealed(&msg.data) {
+                    return;
+                }
+                match self.ws.open(&msg.data) {
+                    Ok(data) => host.handle_message(msg.peer_id, &data, &self.registry),
+                    Err(e) => crate::telemetry::decode_failed("peer message", msg.data.len(), &e),
+                }
+            },
+            Ok(RelayMessage::FromPeer(msg)) => {
+                host.handle_message(msg.peer_id, &msg.data, &self.registry);
+            },
+            Ok(RelayMessage::PeerLeft(msg)) => {
+                host.peer_left(msg.peer_id);
+                self.rotate_room_key();
+            },
+            Ok(RelayMessage::ToPeer(_)) => {},
+            Err(e) => crate::telemetry::decode_failed("relay message", data.len(), &e),
+        }
+    }
+
+    /// Replace the key of an encrypted relay room after a player leaves.
+    /// The new secret goes to the remaining peers sealed under the old
+    /// key, and is the one new players join with. A departed player who
+    /// can still see the relayed traffic can open the rekey, so this only
+    /// keeps out those who can't.
+    fn rotate_room_key(&mut self) {
+        if !self.ws.is_encrypted() {
+            return;
+        }
+        let secret = e2e::generate_secret();
+        let epoch = self.ws.key_epoch() + 1;
+        let msg = ServerMessage::RoomRekey(RoomRekeyMsg {
+            epoch,
+            secret: secret.clone(),
+        });
+        match encode_server_message(&msg).and_then(|data| self.ws.seal(&data)) {
+            Ok(data) => {
+                if let Err(e) = self.ws.send_raw(&data) {
+                    crate::diag::console_warn!("Failed to send RoomRekey: {e}");
+                }
+            },
+            Err(e) => crate::diag::console_warn!("Failed to encode RoomRekey: {e}"),
+        }
+        self.ws.rotate_key(epoch, &secret);
+        self.lobby.room_secret = Some(secret);
+    }
+
+    /// Adopt the host's new room key.
+    fn apply_room_rekey(&mut self, data: &[u8]) {
+        match decode_server_message(data) {
+            Ok(ServerMessage::RoomRekey(msg)) => {
+                if self.ws.rotate_key(msg.epoch, &msg.secret) {
+                    self.lobby.room_secret = Some(msg.secret);
+                }
+            },
+            Ok(_) => {},
+            Err(e) => crate::telemetry::decode_failed("RoomRekey", data.len(), &e),
         }
     }
 
     /// Send a JoinRoom message (used for both initial join and reconnection).
     pub fn send_join_room(&self, room_code: &str, player_name: &str, color_index: usize) {
-        use breakpoint_core::net::messages::{ClientMessage, JoinRoomMsg};
-        use breakpoint_core::net::protocol::{PROTOCOL_VERSION, encode_client_message};
-        use breakpoint_core::player::PlayerColor;
-
         let color = PlayerColor::PALETTE[color_index % PlayerColor::PALETTE.len()];
         let session_token = self
             .reconnect_info
@@ -606,7 +1108,36 @@ impl App {
             player_color: color,
             protocol_version: PROTOCOL_VERSION,
             session_token,
+            identity_key: Some(self.lobby.identity_key.clone()),
+            accepts_batches: true,
+            room_alias: None,
+            bot_token: None,
+            tutorial: None,
         });
+        // Joining an encrypted relay room: the relay only needs the room
+        // code, so it gets a join with nothing else and the real one is sealed
+        if !self.ws.is_loopback()
+            && let Some(ref secret) = self.lobby.room_secret
+        {
+            let routing = ClientMessage::JoinRoom(JoinRoomMsg {
+                room_code: room_code.to_string(),
+                player_name: String::new(),
+                player_color: PlayerColor::default(),
+                protocol_version: PROTOCOL_VERSION,
+                session_token: None,
+                identity_key: None,
+                accepts_batches: false,
+                room_alias: None,
+                bot_token: None,
+                tutorial: None,
+            });
+            self.send_relay_join(&routing);
+            if !self.ws.is_encrypted() {
+                self.ws.set_cipher(Some(RoomCipher::new(room_code, secret)));
+            }
+        } else if !self.ws.is_loopback() {
+            self.ws.set_cipher(None);
+        }
         match encode_client_message(&msg) {
             Ok(data) => {
                 if let Err(e) = self.ws.send(&data) {
@@ -617,15 +1148,308 @@ impl App {
         }
     }
 
+    /// Ask the server for a new room, led by this player. With `tutorial`
+    /// set, the server starts that game's tutorial in it straight away.
+    pub fn create_room(&mut self, tutorial: Option<GameId>) {
+        if self.lobby.connected {
+            self.lobby.status_message = Some(TextKey::AlreadyInRoomCreate.into());
+            return;
+        }
+        if !self.ws.has_connection() {
+            let url = self.lobby.ws_url.clone();
+            if let Err(e) = self.ws.connect(&url) {
+                self.lobby.status_message =
+                    Some(LocalizedText::new(TextKey::ConnectionFailed).with("reason", e));
+                return;
+            }
+        }
+        self.lobby.is_leader = true;
+        let color = PlayerColor::PALETTE[self.lobby.color_index % PlayerColor::PALETTE.len()];
+        let msg = ClientMessage::JoinRoom(JoinRoomMsg {
+            room_code: String::new(),
+            player_name: self.lobby.player_name.clone(),
+            player_color: color,
+            protocol_version: PROTOCOL_VERSION,
+            session_token: None,
+            identity_key: Some(self.lobby.identity_key.clone()),
+            accepts_batches: true,
+            room_alias: self.lobby.room_alias.clone(),
+            bot_token: None,
+            tutorial: tutorial.map(|game| game.to_string()),
+        });
+        match encode_client_message(&msg) {
+            Ok(data) => {
+                if let Err(e) = self.ws.send(&data) {
+                    crate::diag::console_warn!("Failed to send JoinRoom (create): {e}");
+                }
+            },
+            Err(e) => crate::diag::console_warn!("Failed to encode JoinRoom (create): {e}"),
+        }
+        self.lobby.status_message = Some(TextKey::CreatingRoom.into());
+    }
+
+    /// Seat another player at this keyboard. Guests are named after the
+    /// first player with their seat number and take the next palette color.
+    pub fn send_add_local_player(&self) {
+        let Some(seat) = self.hot_seat.next_seat_number() else {
+            return;
+        };
+        let base: String = self.lobby.player_name.trim().chars().take(24).collect();
+        let color_index = self.lobby.color_index + seat - 1;
+        let msg = ClientMessage::AddLocalPlayer(AddLocalPlayerMsg {
+            player_name: format!("{base} (P{seat})").trim().to_string(),
+            player_color: PlayerColor::PALETTE[color_index % PlayerColor::PALETTE.len()],
+        });
+        match encode_client_message(&msg) {
+            Ok(data) => {
+                if let Err(e) = self.ws.send(&data) {
+                    crate::diag::console_warn!("Failed to send AddLocalPlayer: {e}");
+                }
+            },
+            Err(e) => crate::diag::console_warn!("Failed to encode AddLocalPlayer: {e}"),
+        }
+    }
+
+    /// Remove one of this keyboard's hot-seat guests.
+    pub fn send_remove_local_player(&self, player_id: PlayerId) {
+        let msg = ClientMessage::RemoveLocalPlayer(RemoveLocalPlayerMsg { player_id });
+        match encode_client_message(&msg) {
+            Ok(data) => {
+                if let Err(e) = self.ws.send(&data) {
+                    crate::diag::console_warn!("Failed to send RemoveLocalPlayer: {e}");
+                }
+            },
+            Err(e) => crate::diag::console_warn!("Failed to encode RemoveLocalPlayer: {e}"),
+        }
+    }
+
+    /// Click the lobby target at (`x`, `y`), from 0 to 1 across and down
+    /// the play area.
+    /// Ask for the next generated name for ourselves or a hot-seat guest.
+    pub fn send_reroll_name(&self, player_id: PlayerId) {
+        let msg = ClientMessage::RerollName(RerollNameMsg { player_id });
+        match encode_client_message(&msg) {
+            Ok(data) => {
+                if let Err(e) = self.ws.send(&data) {
+                    crate::diag::console_warn!("Failed to send RerollName: {e}");
+                }
+            },
+            Err(e) => crate::diag::console_warn!("Failed to encode RerollName: {e}"),
+        }
+    }
+
+    fn send_clock_sync(&self, now: f64) {
+        let msg = ClientMessage::ClockSync(ClockSyncMsg {
+            client_time: now,
+            rtt_ms: self.clock.rtt_ms().map(|rtt| rtt.round() as u32),
+        });
+        match encode_client_message(&msg) {
+            Ok(data) => {
+                if let Err(e) = self.ws.send(&data) {
+                    crate::diag::console_warn!("Failed to send ClockSync: {e}");
+                }
+            },
+            Err(e) => crate::diag::console_warn!("Failed to encode ClockSync: {e}"),
+        }
+    }
+
+    /// The server's clock at local frame time `now`, once measured.
+    /// Comparable with snapshots' `server_time_ms`.
+    pub fn server_now(&self, now: f64) -> Option<f64> {
+        self.clock.server_now(now)
+    }
+
+    pub fn send_lobby_toy_click(&self, x: f32, y: f32) {
+        let Ok(input_data) = rmp_serde::to_vec(&TargetClick { x, y }) else {
+            return;
+        };
+        let msg = ClientMessage::LobbyToyInput(LobbyToyInputMsg { input_data });
+        match encode_client_message(&msg) {
+            Ok(data) => {
+                if let Err(e) = self.ws.send(&data) {
+                    crate::diag::console_warn!("Failed to send LobbyToyInput: {e}");
+                }
+            },
+            Err(e) => crate::diag::console_warn!("Failed to encode LobbyToyInput: {e}"),
+        }
+    }
+
+    fn process_social_message(&mut self, data: &[u8]) {
+        use breakpoint_core::net::messages::ServerMessage;
+
+        match decode_server_message(data) {
+            Ok(ServerMessage::SocialUpdate(update)) => {
+                self.lobby.social = Some(update);
+            },
+            Ok(ServerMessage::FriendInvite(invite)) => {
+                let invites = &mut self.lobby.invites;
+                invites.retain(|i| i.from_id != invite.from_id);
+                invites.push(invite);
+                if invites.len() > MAX_SHOWN_INVITES {
+                    invites.remove(0);
+                }
+            },
+            Ok(ServerMessage::TutorialProgress(msg)) => {
+                for checklist in msg.checklists {
+                    let tutorials = &mut self.lobby.tutorials;
+                    match tutorials.iter_mut().find(|c| c.game == checklist.game) {
+                        Some(shown) => *shown = checklist,
+                        None => tutorials.push(checklist),
+                    }
+                }
+            },
+            Ok(ServerMessage::Announcements(msg)) => {
+                self.lobby.announcements = msg.announcements;
+            },
+            Ok(ServerMessage::JoinQueueUpdate(msg)) => {
+                self.lobby.join_queue = Some(msg);
+            },
+            Ok(ServerMessage::OverlayConfig(msg)) => {
+                self.lobby.overlay_config = msg.room_config;
+            },
+            Ok(ServerMessage::RoomTransfer(offer)) => {
+                self.audio_events.push(AudioEvent::NoticeChime);
+                let expires_at = self.prev_timestamp + f64::from(offer.expires_in_secs) * 1000.0;
+                self.lobby.transfer_offer = Some(TransferOffer { offer, expires_at });
+            },
+            Ok(_) => {},
+            Err(e) => {
+                crate::telemetry::decode_failed("social message", data.len(), &e);
+            },
+        }
+    }
+
+    fn process_course_vote(&mut self, data: &[u8]) {
+        use breakpoint_core::net::messages::ServerMessage;
+
+        let msg = match decode_server_message(data) {
+            Ok(ServerMessage::CourseVote(msg)) => msg,
+            Ok(_) => return,
+            Err(e) => {
+                crate::telemetry::decode_failed("CourseVote", data.len(), &e);
+                return;
+            },
+        };
+        let end_time = self.prev_timestamp + msg.remaining_secs as f64 * 1000.0;
+        // The opening message carries the candidates; later ones only tally
+        if !msg.candidates.is_empty() {
+            self.audio_events.push(AudioEvent::NoticeChime);
+            self.course_vote = Some(CourseVoteView {
+                round: msg.round,
+                candidates: msg.candidates,
+                votes: msg.votes,
+                end_time,
+                winner: None,
+                closed_at: None,
+                my_choice: None,
+            });
+            return;
+        }
+        let Some(ref mut vote) = self.course_vote else {
+            return;
+        };
+        if vote.round != msg.round {
+            return;
+        }
+        vote.votes = msg.votes;
+        vote.end_time = end_time;
+        if msg.winner.is_some() {
+            vote.winner = msg.winner;
+            vote.closed_at = Some(self.prev_timestamp);
+        }
+    }
+
+    /// Send the local player's ballot in the open course vote.
+    pub fn cast_course_vote(&mut self, choice: u8) {
+        use breakpoint_core::net::messages::CastCourseVoteMsg;
+
+        let Some(ref mut vote) = self.course_vote else {
+            return;
+        };
+        if vote.winner.is_some() || choice as usize >= vote.candidates.len() {
+            return;
+        }
+        vote.my_choice = Some(choice);
+        let msg = ClientMessage::CastCourseVote(CastCourseVoteMsg {
+            round: vote.round,
+            choice,
+        });
+        match encode_client_message(&msg) {
+            Ok(data) => {
+                if let Err(e) = self.ws.send(&data) {
+                    crate::diag::console_warn!("Failed to send CastCourseVote: {e}");
+                }
+            },
+            Err(e) => crate::diag::console_warn!("Failed to encode CastCourseVote: {e}"),
+        }
+    }
+
+    fn process_team_draft(&mut self, data: &[u8]) {
+        let msg = match decode_server_message(data) {
+            Ok(ServerMessage::TeamDraft(msg)) => msg,
+            Ok(_) => return,
+            Err(e) => {
+                crate::telemetry::decode_failed("TeamDraft", data.len(), &e);
+                return;
+            },
+        };
+        let was_my_turn = self.is_my_draft_turn();
+        if self.team_draft.is_none() {
+            self.audio_events.push(AudioEvent::NoticeChime);
+        }
+        self.team_draft = Some(TeamDraftView {
+            captains: msg.captains,
+            picks: msg.picks,
+            pool: msg.pool,
+            turn: msg.turn,
+            end_time: self.prev_timestamp + msg.remaining_secs as f64 * 1000.0,
+            closed_at: msg.turn.is_none().then_some(self.prev_timestamp),
+        });
+        if !was_my_turn && self.is_my_draft_turn() {
+            self.audio_events.push(AudioEvent::NoticeChime);
+        }
+    }
+
+    /// Whether the local player is the captain picking now.
+    pub fn is_my_draft_turn(&self) -> bool {
+        self.team_draft
+            .as_ref()
+            .and_then(TeamDraftView::captain)
+            .is_some_and(|id| self.lobby.local_player_id == Some(id))
+    }
+
+    /// Draft `player_id` onto the local captain's team.
+    pub fn draft_pick(&mut self, player_id: PlayerId) {
+        use breakpoint_core::net::messages::DraftPickMsg;
+
+        let in_pool = self
+            .team_draft
+            .as_ref()
+            .is_some_and(|draft| draft.pool.contains(&player_id));
+        if !in_pool || !self.is_my_draft_turn() {
+            return;
+        }
+        let msg = ClientMessage::DraftPick(DraftPickMsg { player_id });
+        match encode_client_message(&msg) {
+            Ok(data) => {
+                if let Err(e) = self.ws.send(&data) {
+                    crate::diag::console_warn!("Failed to send DraftPick: {e}");
+                }
+            },
+            Err(e) => crate::diag::console_warn!("Failed to encode DraftPick: {e}"),
+        }
+    }
+
     fn process_lobby_message(&mut self, data: &[u8], msg_type: MessageType) {
         use breakpoint_core::net::messages::ServerMessage;
 
         let msg = match decode_server_message(data) {
             Ok(m) => m,
             Err(e) => {
-                crate::diag::console_warn!(
-                    "Failed to decode lobby message ({msg_type:?}, {} bytes): {e}",
-                    data.len()
+                crate::telemetry::decode_failed(
+                    &format!("lobby message {msg_type:?}"),
+                    data.len(),
+                    &e,
                 );
                 return;
             },
@@ -634,46 +1458,89 @@ impl App {
         match msg {
             ServerMessage::JoinRoomResponse(resp) => {
                 if resp.success {
+                    // Measure the new connection's clock from scratch
+                    self.clock.reset();
                     self.lobby.local_player_id = resp.player_id;
                     if let Some(code) = &resp.room_code {
                         self.lobby.room_code = code.clone();
                     }
                     self.lobby.session_token = resp.session_token;
                     self.lobby.connected = true;
+                    // Customized settings follow from the server
+                    self.lobby.overlay_config = OverlayRoomConfig::default();
+                    self.lobby.toy = resp
+                        .lobby_toy
+                        .and_then(|data| rmp_serde::from_slice(&data).ok());
                     self.lobby.error_message = None;
                     self.overlay.local_player_id = resp.player_id;
 
-                    if self.lobby.is_leader {
-                        self.lobby.status_message = Some(
-                            "Room created! Click Start Game, or share the code with friends."
-                                .to_string(),
-                        );
+                    if self.local_host.is_some() && !self.ws.has_connection() {
+                        self.lobby.status_message = Some(TextKey::OfflineReady.into());
+                    } else if self.lobby.is_leader {
+                        self.lobby.status_message = Some(TextKey::RoomCreated.into());
                     } else {
-                        self.lobby.status_message =
-                            Some("Joined! Waiting for leader to start...".to_string());
+                        self.lobby.status_message = Some(TextKey::JoinedWaiting.into());
                     }
 
                     if let Some(room_state) = resp.room_state
                         && room_state != breakpoint_core::room::RoomState::Lobby
                     {
                         self.lobby.is_spectator = true;
-                        self.transition_to(AppState::InGame);
+                        self.enter_game();
                     }
                 } else {
                     self.lobby.error_message = resp.error.clone();
                     self.lobby.status_message = resp.error;
                 }
             },
+            ServerMessage::JoinQueued(q) => {
+                let key = if q.room_code.is_empty() {
+                    TextKey::RoomQueued
+                } else if q.awaiting_approval {
+                    TextKey::JoinAwaitingApproval
+                } else {
+                    TextKey::JoinQueued
+                };
+                self.lobby.status_message = Some(
+                    LocalizedText::new(key)
+                        .with("position", q.position)
+                        .with("total", q.queue_len),
+                );
+                self.lobby.error_message = None;
+            },
             ServerMessage::PlayerList(pl) => {
                 self.lobby.players = pl.players.clone();
+                self.lobby.session_scores = pl.session_scores;
+                let playlist: Vec<GameId> = pl
+                    .playlist
+                    .iter()
+                    .filter_map(|g| GameId::from_str_opt(g))
+                    .collect();
+                // A scheduled session's next game comes up selected
+                if playlist != self.lobby.playlist
+                    && let Some(&next) = playlist.first()
+                {
+                    self.lobby.selected_game = next;
+                }
+                self.lobby.playlist = playlist;
+                self.hot_seat
+                    .sync(&self.lobby.players, self.lobby.local_player_id);
                 if let Some(my_id) = self.lobby.local_player_id {
                     self.lobby.is_leader = pl.leader_id == my_id;
+                    // Adopt a generated or re-rolled name as our own
+                    if let Some(me) = pl.players.iter().find(|p| p.id == my_id) {
+                        self.lobby.player_name.clone_from(&me.display_name);
+                    }
                 }
                 self.lobby.connected = true;
             },
+            ServerMessage::LobbyToyState(toy) => match rmp_serde::from_slice(&toy.state_data) {
+                Ok(state) => self.lobby.toy = Some(state),
+                Err(e) => crate::telemetry::decode_failed("LobbyToyState", data.len(), &e),
+            },
             ServerMessage::GameStart(gs) => {
                 self.lobby.selected_game = GameId::from_str_opt(&gs.game_name).unwrap_or_default();
-                self.transition_to(AppState::InGame);
+                self.enter_game();
             },
             ServerMessage::AlertEvent(ae) => {
                 self.overlay_queue
@@ -699,19 +1566,33 @@ impl App {
 
         match msg_type {
             MessageType::GameState => {
-                // Fast decode: [type_byte | tick_le32 | raw_state_data]
+                // Fast decode: [type_byte | tick_le32 | server_time_ms_le64 | raw_state_data]
                 match breakpoint_core::net::protocol::decode_game_state_fast(data) {
-                    Ok((tick, state_data)) => {
+                    Ok((tick, server_time_ms, state_data)) => {
                         if let Some(ref mut active) = self.game {
                             active.game.apply_state(state_data);
                             active.tick = tick;
+                            active.server_time_ms = server_time_ms;
+                            #[cfg(feature = "state-diff")]
+                            self.state_inspector
+                                .record(active.game.as_ref(), tick, state_data);
+                            let now = self.prev_timestamp;
+                            self.net_diag.record_snapshot(
+                                tick,
+                                server_time_ms,
+                                data.len(),
+                                active.game.tick_rate(),
+                                now,
+                            );
+                            if let Some(ref role) = self.network_role
+                                && let Some(pos) = player_position(active, role.local_player_id)
+                            {
+                                self.net_diag.record_local_position(pos, now);
+                            }
                         }
                     },
                     Err(e) => {
-                        crate::diag::console_warn!(
-                            "Failed to decode GameState ({} bytes): {e}",
-                            data.len()
-                        );
+                        crate::telemetry::decode_failed("GameState", data.len(), &e);
                     },
                 }
             },
@@ -723,62 +1604,107 @@ impl App {
                         .map(|s| PlayerScore {
                             player_id: s.player_id,
                             score: s.score,
+                            disconnected: s.disconnected,
                         })
                         .collect();
                     if let Some(ref mut tracker) = self.round_tracker {
                         tracker.record_round(&scores);
                     }
+                    self.round_heatmap = re.heatmap;
+                    self.victory = re.victory;
                     if re.between_round_secs > 0 {
                         self.between_round_end_time =
                             Some(self.prev_timestamp + (re.between_round_secs as f64 * 1000.0));
+                        self.between_round_total_secs = f32::from(re.between_round_secs);
+                    }
+                    self.audio_events.push(AudioEvent::NoticeChime);
+                    self.transition_to(AppState::BetweenRounds);
+                },
+                Err(e) => {
+                    crate::telemetry::decode_failed("RoundEnd", data.len(), &e);
+                },
+                _ => {},
+            },
+            MessageType::GameEnd => match decode_server_message(data) {
+                Ok(ServerMessage::GameEnd(ge)) => {
+                    let scores: Vec<PlayerScore> = ge
+                        .final_scores
+                        .iter()
+                        .map(|s| PlayerScore {
+                            player_id: s.player_id,
+                            score: s.score,
+                            disconnected: s.disconnected,
+                        })
+                        .collect();
+                    if let Some(ref mut tracker) = self.round_tracker {
+                        tracker.record_round(&scores);
+                    }
+                    self.last_match_id = ge.match_id;
+                    self.victory = ge.victory;
+                    self.game_over_timestamp = Some(self.prev_timestamp);
+                    self.audio_events.push(AudioEvent::UrgentAttention);
+                    self.transition_to(AppState::GameOver);
+                },
+                Err(e) => {
+                    crate::telemetry::decode_failed("GameEnd", data.len(), &e);
+                },
+                _ => {},
+            },
+            MessageType::RoundPhase => match decode_server_message(data) {
+                Ok(ServerMessage::RoundPhase(rp)) => {
+                    if rp.phase == RoundPhase::Countdown {
+                        self.audio_events.push(AudioEvent::NoticeChime);
+                    }
+                    self.round_phase = rp.phase;
+                    self.round_phase_end_time = (rp.remaining_secs > 0.0)
+                        .then_some(self.prev_timestamp + rp.remaining_secs as f64 * 1000.0);
+                },
+                Err(e) => {
+                    crate::telemetry::decode_failed("RoundPhase", data.len(), &e);
+                },
+                _ => {},
+            },
+            MessageType::Standings => match decode_server_message(data) {
+                Ok(ServerMessage::Standings(standings)) => {
+                    self.standings = Some(standings);
+                },
+                Err(e) => {
+                    crate::telemetry::decode_failed("Standings", data.len(), &e);
+                },
+                _ => {},
+            },
+            MessageType::CourseUpdate => match decode_server_message(data) {
+                Ok(ServerMessage::CourseUpdate(cu)) => {
+                    if let Some(ref mut active) = self.game {
+                        active.game.apply_course_data(&cu.data);
                     }
-                    self.audio_events.push(AudioEvent::NoticeChime);
-                    self.transition_to(AppState::BetweenRounds);
                 },
                 Err(e) => {
-                    crate::diag::console_warn!(
-                        "Failed to decode RoundEnd ({} bytes): {e}",
-                        data.len()
-                    );
+                    crate::telemetry::decode_failed("CourseUpdate", data.len(), &e);
                 },
                 _ => {},
             },
-            MessageType::GameEnd => match decode_server_message(data) {
-                Ok(ServerMessage::GameEnd(ge)) => {
-                    let scores: Vec<PlayerScore> = ge
-                        .final_scores
-                        .iter()
-                        .map(|s| PlayerScore {
-                            player_id: s.player_id,
-                            score: s.score,
-                        })
-                        .collect();
-                    if let Some(ref mut tracker) = self.round_tracker {
-                        tracker.record_round(&scores);
+            MessageType::GameEffects => match decode_server_message(data) {
+                Ok(ServerMessage::GameEffects(effects)) => {
+                    if let Some(ref mut active) = self.game {
+                        active.game.apply_effects(&effects.data);
                     }
-                    self.game_over_timestamp = Some(self.prev_timestamp);
-                    self.audio_events.push(AudioEvent::UrgentAttention);
-                    self.transition_to(AppState::GameOver);
                 },
                 Err(e) => {
-                    crate::diag::console_warn!(
-                        "Failed to decode GameEnd ({} bytes): {e}",
-                        data.len()
-                    );
+                    crate::telemetry::decode_failed("GameEffects", data.len(), &e);
                 },
                 _ => {},
             },
-            MessageType::CourseUpdate => match decode_server_message(data) {
-                Ok(ServerMessage::CourseUpdate(cu)) => {
+            MessageType::PrivateState => match decode_server_message(data) {
+                Ok(ServerMessage::PrivateState(private)) => {
                     if let Some(ref mut active) = self.game {
-                        active.game.apply_course_data(&cu.data);
+                        active
+                            .game
+                            .apply_private_state(private.player_id, &private.data);
                     }
                 },
                 Err(e) => {
-                    crate::diag::console_warn!(
-                        "Failed to decode CourseUpdate ({} bytes): {e}",
-                        data.len()
-                    );
+                    crate::telemetry::decode_failed("PrivateState", data.len(), &e);
                 },
                 _ => {},
             },
@@ -799,10 +1725,7 @@ impl App {
                         .push(OverlayNetEvent::AlertReceived(Box::new(ae.event)));
                 },
                 Err(e) => {
-                    crate::diag::console_warn!(
-                        "Failed to decode AlertEvent ({} bytes): {e}",
-                        data.len()
-                    );
+                    crate::telemetry::decode_failed("AlertEvent", data.len(), &e);
                 },
                 _ => {},
             },
@@ -814,10 +1737,7 @@ impl App {
                     });
                 },
                 Err(e) => {
-                    crate::diag::console_warn!(
-                        "Failed to decode AlertClaimed ({} bytes): {e}",
-                        data.len()
-                    );
+                    crate::telemetry::decode_failed("AlertClaimed", data.len(), &e);
                 },
                 _ => {},
             },
@@ -828,10 +1748,7 @@ impl App {
                     });
                 },
                 Err(e) => {
-                    crate::diag::console_warn!(
-                        "Failed to decode AlertDismissed ({} bytes): {e}",
-                        data.len()
-                    );
+                    crate::telemetry::decode_failed("AlertDismissed", data.len(), &e);
                 },
                 _ => {},
             },
@@ -865,6 +1782,11 @@ impl App {
             MessageType::GameEnd => {
                 self.process_game_message(data, msg_type);
             },
+            MessageType::Intermission => {
+                if let Ok(ServerMessage::Intermission(msg)) = decode_server_message(data) {
+                    self.apply_intermission(msg);
+                }
+            },
             MessageType::PlayerList => {
                 self.process_lobby_message(data, msg_type);
             },
@@ -874,6 +1796,179 @@ impl App {
         }
     }
 
+    /// Restart the between-round countdown from an intermission update; no
+    /// countdown while the room waits for the leader.
+    fn apply_intermission(&mut self, msg: IntermissionMsg) {
+        self.between_round_end_time = msg
+            .remaining_secs
+            .map(|secs| self.prev_timestamp + f64::from(secs) * 1000.0);
+        if let Some(secs) = msg.remaining_secs {
+            self.between_round_total_secs = secs;
+        }
+        self.intermission = Some(msg);
+    }
+
+    /// Take back the local player's last golf stroke, for the HUD's undo
+    /// button. The host ignores it once the undo window has closed.
+    #[cfg(feature = "golf")]
+    pub fn send_golf_undo(&mut self) {
+        let (Some(active), Some(role)) = (self.game.as_mut(), self.network_role.as_ref()) else {
+            return;
+        };
+        if active.game_id != GameId::Golf {
+            return;
+        }
+        let input = crate::game::golf_input::undo_input();
+        crate::game::send_player_input(&input, active, role, &self.ws);
+    }
+
+    /// Ask the host's caddie for a stroke suggestion, for the HUD's caddie
+    /// button. The host ignores it once the hint budget is spent.
+    #[cfg(feature = "golf")]
+    pub fn send_golf_caddie(&mut self) {
+        let (Some(active), Some(role)) = (self.game.as_mut(), self.network_role.as_ref()) else {
+            return;
+        };
+        if active.game_id != GameId::Golf {
+            return;
+        }
+        let input = crate::game::golf_input::caddie_input();
+        crate::game::send_player_input(&input, active, role, &self.ws);
+    }
+
+    /// Take up the open transfer offer: leave this room and join the one it
+    /// points at, through the reconnect path with the offer's token as the
+    /// session token. A lapsed offer is just dropped.
+    pub fn accept_room_transfer(&mut self) {
+        let Some(TransferOffer { offer, expires_at }) = self.lobby.transfer_offer.take() else {
+            return;
+        };
+        if self.prev_timestamp > expires_at || self.local_host.is_some() {
+            return;
+        }
+        self.ws.disconnect();
+        self.was_connected = false;
+        self.lobby.connected = false;
+        self.lobby.join_queue = None;
+        self.lobby.status_message =
+            Some(LocalizedText::new(TextKey::JoiningRoom).with("code", offer.room_code.as_str()));
+        self.reconnect_info = Some(ReconnectInfo {
+            attempt: 0,
+            next_attempt_at: self.prev_timestamp,
+            room_code: offer.room_code,
+            player_name: self.lobby.player_name.clone(),
+            color_index: self.lobby.color_index,
+            session_token: offer.transfer_token,
+        });
+    }
+
+    pub fn decline_room_transfer(&mut self) {
+        self.lobby.transfer_offer = None;
+    }
+
+    /// Ask the server to merge this lobby into another room or split it
+    /// in two. Only the leader's request counts.
+    pub fn send_reshape_room(&self, action: RoomReshape) {
+        if !self.lobby.is_leader {
+            return;
+        }
+        let msg = ClientMessage::ReshapeRoom(ReshapeRoomMsg { action });
+        match encode_client_message(&msg) {
+            Ok(data) => {
+                if let Err(e) = self.ws.send(&data) {
+                    crate::diag::console_warn!("Failed to send ReshapeRoom: {e}");
+                }
+            },
+            Err(e) => crate::diag::console_warn!("Failed to encode ReshapeRoom: {e}"),
+        }
+    }
+
+    /// Ask the host to start the next round now, or the match during a
+    /// warmup. Only the leader's request counts, and the results still stay
+    /// up for their minimum time.
+    pub fn send_advance_round(&self) {
+        if !self.lobby.is_leader {
+            return;
+        }
+        // Round 0 is the warmup
+        let round = if self.round_phase == RoundPhase::Warmup {
+            0
+        } else if let Some(ref intermission) = self.intermission {
+            intermission.round
+        } else {
+            return;
+        };
+        let msg = ClientMessage::AdvanceRound(AdvanceRoundMsg { round });
+        match encode_client_message(&msg) {
+            Ok(data) => {
+                if let Err(e) = self.ws.send(&data) {
+                    crate::diag::console_warn!("Failed to send AdvanceRound: {e}");
+                }
+            },
+            Err(e) => crate::diag::console_warn!("Failed to encode AdvanceRound: {e}"),
+        }
+    }
+
+    /// Change live settings in the running round, given as JSON (e.g.
+    /// `{"extend_round_secs":60}`). Only the leader's changes count.
+    pub fn send_config_change(&self, json: &str) {
+        use breakpoint_core::game_trait::GameConfigDelta;
+        use breakpoint_core::net::messages::ChangeGameConfigMsg;
+
+        if !self.lobby.is_leader {
+            crate::diag::console_warn!("Live settings are for the room leader");
+            return;
+        }
+        let changes = match serde_json::from_str(json) {
+            Ok(changes) => changes,
+            Err(e) => {
+                crate::diag::console_warn!("Invalid settings change: {e}");
+                return;
+            },
+        };
+        let msg = ClientMessage::ChangeGameConfig(ChangeGameConfigMsg {
+            delta: GameConfigDelta { changes },
+        });
+        match encode_client_message(&msg) {
+            Ok(data) => {
+                if let Err(e) = self.ws.send(&data) {
+                    crate::diag::console_warn!("Failed to send ChangeGameConfig: {e}");
+                }
+            },
+            Err(e) => crate::diag::console_warn!("Failed to encode ChangeGameConfig: {e}"),
+        }
+    }
+
+    /// Send a host debug command, given as JSON (e.g.
+    /// `{"SetRoundTimer":{"elapsed_secs":170}}`), from the browser console.
+    /// Only the leader's commands count, and only on servers that allow them.
+    #[cfg(feature = "debug-commands")]
+    pub fn send_debug_command(&self, json: &str) {
+        use breakpoint_core::debug_command::DebugCommand;
+        use breakpoint_core::net::messages::DebugCommandMsg;
+
+        if !self.lobby.is_leader {
+            crate::diag::console_warn!("Debug commands are for the room leader");
+            return;
+        }
+        let command: DebugCommand = match serde_json::from_str(json) {
+            Ok(command) => command,
+            Err(e) => {
+                crate::diag::console_warn!("Invalid debug command: {e}");
+                return;
+            },
+        };
+        let msg = ClientMessage::DebugCommand(DebugCommandMsg { command });
+        match encode_client_message(&msg) {
+            Ok(data) => {
+                if let Err(e) = self.ws.send(&data) {
+                    crate::diag::console_warn!("Failed to send DebugCommand: {e}");
+                }
+            },
+            Err(e) => crate::diag::console_warn!("Failed to encode DebugCommand: {e}"),
+        }
+    }
+
     fn process_game_over_message(&mut self, data: &[u8], msg_type: MessageType) {
         use breakpoint_core::net::messages::ServerMessage;
 
@@ -887,7 +1982,7 @@ impl App {
                 if let Ok(ServerMessage::GameStart(gs)) = decode_server_message(data) {
                     self.lobby.selected_game =
                         GameId::from_str_opt(&gs.game_name).unwrap_or_default();
-                    self.transition_to(AppState::InGame);
+                    self.enter_game();
                 }
             },
             _ => {
@@ -899,11 +1994,6 @@ impl App {
     fn update_game(&mut self, dt: f32) {
         self.audio_frame_counter = self.audio_frame_counter.wrapping_add(1);
 
-        let game_id = match self.game {
-            Some(ref g) => g.game_id,
-            None => return,
-        };
-
         let Some(ref active) = self.game else {
             return;
         };
@@ -912,8 +2002,62 @@ impl App {
         match active.game_id {
             #[cfg(feature = "golf")]
             GameId::Golf => {
-                if let Some(ref role) = self.network_role
-                    && let Some(s) = read_game_state::<breakpoint_golf::GolfState>(active)
+                let golf_state = read_game_state::<breakpoint_golf::GolfState>(active);
+                let flyover = golf_state.as_ref().and_then(|s| {
+                    let preview = active
+                        .game
+                        .as_any()
+                        .downcast_ref::<breakpoint_golf::MiniGolf>()?
+                        .preview()?;
+                    if s.intro_remaining <= 0.0 || preview.duration_secs <= 0.0 {
+                        return None;
+                    }
+                    preview.sample(1.0 - s.intro_remaining / preview.duration_secs)
+                });
+                // Celebrate hole-in-ones, chip-ins, and birdie streaks
+                if let Some(ref s) = golf_state {
+                    use breakpoint_core::game_trait::GameEvent;
+
+                    if s.highlights.len() < self.prev_golf_highlights {
+                        self.prev_golf_highlights = 0;
+                    }
+                    let local_id = self.network_role.as_ref().map(|r| r.local_player_id);
+                    for highlight in &s.highlights[self.prev_golf_highlights..] {
+                        self.audio_events.push(AudioEvent::GolfBallSink);
+                        let (player_id, color) = match *highlight {
+                            GameEvent::HoleInOne { player_id } => {
+                                (player_id, Vec4::new(1.0, 0.85, 0.2, 0.45))
+                            },
+                            GameEvent::ChipIn { player_id, .. }
+                            | GameEvent::BirdieStreak { player_id, .. } => {
+                                (player_id, Vec4::new(0.4, 1.0, 0.6, 0.3))
+                            },
+                            _ => continue,
+                        };
+                        if Some(player_id) == local_id {
+                            self.screen_flash.trigger(color, 0.4);
+                            self.screen_shake.trigger(0.2, 0.25);
+                        }
+                    }
+                    self.prev_golf_highlights = s.highlights.len();
+
+                    // Distinct cue when the ball stops with the cup hidden
+                    let blind = local_id
+                        .and_then(|id| s.shot_hints.get(&id))
+                        .is_some_and(|h| !h.hole_visible);
+                    if blind && !self.prev_golf_blind {
+                        self.audio_events.push(AudioEvent::GolfBlindShot);
+                    }
+                    self.prev_golf_blind = blind;
+                }
+                if let Some(pose) = flyover {
+                    let v = |p: breakpoint_golf::course::Vec3| glam::Vec3::new(p.x, p.y, p.z);
+                    self.camera.set_mode(CameraMode::GolfFlyover {
+                        position: v(pose.position),
+                        look_at: v(pose.look_at),
+                    });
+                } else if let Some(ref role) = self.network_role
+                    && let Some(ref s) = golf_state
                     && let Some(b) = s.balls.get(&role.local_player_id)
                 {
                     self.camera.set_mode(CameraMode::GolfFollow {
@@ -931,7 +2075,7 @@ impl App {
                     && let Some(p) = racer.state().players.get(&role.local_player_id)
                 {
                     self.camera.set_mode(CameraMode::PlatformerFollow {
-                        player_pos: Vec2::new(p.x, p.y),
+                        player_pos: glam::Vec2::new(p.x, p.y),
                     });
                 }
             },
@@ -941,8 +2085,18 @@ impl App {
             },
             #[cfg(feature = "tron")]
             GameId::Tron => {
+                let state = read_game_state::<breakpoint_tron::TronState>(active);
+                let follow = |c: &breakpoint_tron::CycleState| CameraMode::TronFollow {
+                    cycle_pos: glam::Vec3::new(c.x, 0.0, c.z),
+                    direction: match c.direction {
+                        breakpoint_tron::Direction::North => [0.0, -1.0],
+                        breakpoint_tron::Direction::South => [0.0, 1.0],
+                        breakpoint_tron::Direction::East => [1.0, 0.0],
+                        breakpoint_tron::Direction::West => [-1.0, 0.0],
+                    },
+                };
                 if let Some(ref role) = self.network_role
-                    && let Some(s) = read_game_state::<breakpoint_tron::TronState>(active)
+                    && let Some(ref s) = state
                     && let Some(c) = s.players.get(&role.local_player_id)
                 {
                     // Tron crash audio: detect alive -> dead transition
@@ -952,6 +2106,13 @@ impl App {
                     }
                     self.prev_local_alive = c.alive;
 
+                    // Sudden death: flash as the riders respawn
+                    if s.overtime && !self.prev_tron_overtime {
+                        self.screen_flash
+                            .trigger(Vec4::new(1.0, 0.8, 0.2, 0.35), 0.5);
+                    }
+                    self.prev_tron_overtime = s.overtime;
+
                     // Tron grind audio: emit every ~10 frames when speed
                     // exceeds base (50.0)
                     if c.alive && c.speed > 50.0 && self.audio_frame_counter.is_multiple_of(10) {
@@ -967,33 +2128,85 @@ impl App {
                     }
 
                     if c.alive {
-                        let dir = match c.direction {
-                            breakpoint_tron::Direction::North => [0.0, -1.0],
-                            breakpoint_tron::Direction::South => [0.0, 1.0],
-                            breakpoint_tron::Direction::East => [1.0, 0.0],
-                            breakpoint_tron::Direction::West => [-1.0, 0.0],
-                        };
-                        self.camera.set_mode(CameraMode::TronFollow {
-                            cycle_pos: glam::Vec3::new(c.x, 0.0, c.z),
-                            direction: dir,
-                        });
+                        self.camera.set_mode(follow(c));
                     }
+                } else if let Some(ref s) = state
+                    && let Some(c) = s
+                        .spectator
+                        .as_ref()
+                        .and_then(|cues| cues.focus)
+                        .and_then(|pid| s.players.get(&pid))
+                    && c.alive
+                {
+                    // Spectators ride along with whoever the cues point at
+                    self.camera.set_mode(follow(c));
                 }
             },
             #[allow(unreachable_patterns)]
             _ => {},
         }
 
-        // Game-specific input and rendering
-        self.update_game_input();
+        // Game-specific input and rendering. The server drops inputs outside
+        // the playing phase, so don't send them.
+        if self.state == AppState::InGame && self.round_phase.accepts_input() {
+            self.update_game_input(dt);
+        }
         self.sync_game_scene(dt);
 
         // Detect platformer state changes for VFX (outside the `ref active` borrow)
         #[cfg(feature = "platformer")]
-        if game_id == GameId::Platformer {
+        if self
+            .game
+            .as_ref()
+            .is_some_and(|g| g.game_id == GameId::Platformer)
+        {
             self.detect_platformer_events();
             self.update_platformer_weather();
         }
+        self.play_powerup_feed();
+    }
+
+    /// Play cues for the local player's power-up pickups and expiries.
+    #[allow(unused_variables)]
+    fn play_powerup_feed(&mut self) {
+        use breakpoint_core::game_trait::GameEvent;
+        use breakpoint_core::powerup::PowerUpFeed;
+
+        let Some(ref active) = self.game else {
+            return;
+        };
+        let any = active.game.as_any();
+        let feed: Option<&PowerUpFeed> = match active.game_id {
+            #[cfg(feature = "lasertag")]
+            GameId::LaserTag => any
+                .downcast_ref::<breakpoint_lasertag::LaserTagArena>()
+                .map(|g| &g.state().powerup_feed),
+            #[cfg(feature = "platformer")]
+            GameId::Platformer => any
+                .downcast_ref::<breakpoint_platformer::PlatformRacer>()
+                .map(|g| &g.state().powerup_feed),
+            _ => None,
+        };
+        let Some(feed) = feed else {
+            return;
+        };
+        // Each round numbers its events from 0 again
+        if feed.next_seq < self.next_powerup_seq {
+            self.next_powerup_seq = 0;
+        }
+        let local_id = self.network_role.as_ref().map(|r| r.local_player_id);
+        for event in feed.since(self.next_powerup_seq) {
+            match event {
+                GameEvent::PowerupCollected { player_id, .. } if Some(*player_id) == local_id => {
+                    self.audio_events.push(AudioEvent::PowerUpCollected);
+                },
+                GameEvent::PowerupExpired { player_id, .. } if Some(*player_id) == local_id => {
+                    self.audio_events.push(AudioEvent::PowerUpExpired);
+                },
+                _ => {},
+            }
+        }
+        self.next_powerup_seq = feed.next_seq;
     }
 
     /// Detect HP changes and enemy kills in the platformer for particle/audio effects.
@@ -1162,6 +2375,7 @@ impl App {
     }
 
     /// Check for powerup collection events and emit colored burst particles.
+    /// The pickup sound comes from the power-up feed.
     #[cfg(feature = "platformer")]
     fn detect_powerup_collections(
         &mut self,
@@ -1190,6 +2404,7 @@ impl App {
                         PowerUpKind::ArmorUp => Vec4::new(0.6, 0.6, 0.6, 1.0),
                         PowerUpKind::Invincibility => Vec4::new(1.0, 0.85, 0.2, 1.0),
                         PowerUpKind::WhipExtend => Vec4::new(1.0, 0.5, 0.1, 1.0),
+                        PowerUpKind::Banana => Vec4::new(1.0, 0.9, 0.3, 1.0),
                     };
                     self.particle_system.emit(
                         ParticleEffect::GenericBurst { color, count: 8 },
@@ -1197,7 +2412,6 @@ impl App {
                         pu.y,
                         sheet,
                     );
-                    self.audio_events.push(AudioEvent::PlatformerPowerUp);
                 }
             }
         }
@@ -1205,21 +2419,25 @@ impl App {
         self.prev_powerup_collected = current;
     }
 
-    fn update_game_input(&mut self) {
+    fn update_game_input(&mut self, dt: f32) {
         let Some(ref mut active) = self.game else {
             return;
         };
         let Some(ref role) = self.network_role else {
             return;
         };
+        // With guests at the keyboard the first player gives up their keys
+        let primary_input = self.hot_seat.primary_input(&self.input);
+        let input = primary_input.as_ref().unwrap_or(&self.input);
 
         match active.game_id {
             #[cfg(feature = "golf")]
             GameId::Golf => {
                 let stroke_sent = crate::game::golf_input::process_golf_input(
-                    &self.input,
+                    input,
                     &self.camera,
                     &self.renderer,
+                    self.golf_drag_aim,
                     active,
                     role,
                     &self.ws,
@@ -1231,33 +2449,28 @@ impl App {
             },
             #[cfg(feature = "platformer")]
             GameId::Platformer => {
-                let jump_pressed = self.input.is_key_just_pressed("Space")
-                    || self.input.is_key_just_pressed("ArrowUp")
-                    || self.input.is_key_just_pressed("KeyW");
+                let jump_pressed = input.is_key_just_pressed("Space")
+                    || input.is_key_just_pressed("ArrowUp")
+                    || input.is_key_just_pressed("KeyW");
                 if jump_pressed {
                     self.audio_events.push(AudioEvent::PlatformerJump);
                 }
-                let powerup_pressed = self.input.is_key_just_pressed("KeyE");
+                let powerup_pressed = input.is_key_just_pressed("KeyE");
                 if powerup_pressed {
                     self.audio_events.push(AudioEvent::PlatformerPowerUp);
                 }
                 crate::game::platformer_input::process_platformer_input(
-                    &self.input,
-                    active,
-                    role,
-                    &self.ws,
+                    input, active, role, &self.ws,
                 );
             },
             #[cfg(feature = "lasertag")]
             GameId::LaserTag => {
-                let fire = self
-                    .input
-                    .is_mouse_just_pressed(crate::input::MouseButton::Left);
+                let fire = input.is_mouse_just_pressed(crate::input::MouseButton::Left);
                 if fire {
                     self.audio_events.push(AudioEvent::LaserFire);
                 }
                 crate::game::lasertag_input::process_lasertag_input(
-                    &self.input,
+                    input,
                     &self.camera,
                     &self.renderer,
                     active,
@@ -1267,11 +2480,58 @@ impl App {
             },
             #[cfg(feature = "tron")]
             GameId::Tron => {
-                crate::game::tron_input::process_tron_input(&self.input, active, role, &self.ws);
+                crate::game::tron_input::process_tron_input(input, active, role, &self.ws);
             },
             #[allow(unreachable_patterns)]
             _ => {},
         }
+
+        for seat in self.hot_seat.seats_mut() {
+            match active.game_id {
+                #[cfg(feature = "golf")]
+                GameId::Golf => {
+                    let stroke_sent = crate::game::golf_input::process_golf_seat_input(
+                        seat,
+                        &self.input,
+                        dt,
+                        active,
+                        &self.ws,
+                    );
+                    if stroke_sent {
+                        self.audio_events.push(AudioEvent::GolfStroke);
+                    }
+                },
+                #[cfg(feature = "platformer")]
+                GameId::Platformer => {
+                    crate::game::platformer_input::process_platformer_seat_input(
+                        seat,
+                        &self.input,
+                        active,
+                        &self.ws,
+                    );
+                },
+                #[cfg(feature = "lasertag")]
+                GameId::LaserTag => {
+                    crate::game::lasertag_input::process_lasertag_seat_input(
+                        seat,
+                        &self.input,
+                        active,
+                        &self.ws,
+                    );
+                },
+                #[cfg(feature = "tron")]
+                GameId::Tron => {
+                    crate::game::tron_input::process_tron_seat_input(
+                        seat,
+                        &self.input,
+                        active,
+                        &self.ws,
+                    );
+                },
+                #[allow(unreachable_patterns)]
+                _ => {},
+            }
+        }
     }
 
     fn sync_game_scene(&mut self, dt: f32) {
@@ -1290,7 +2550,9 @@ impl App {
                     &self.input,
                     &self.camera,
                     &self.renderer,
+                    self.golf_drag_aim,
                     self.network_role.as_ref(),
+                    self.hot_seat.seats(),
                 );
             },
             #[cfg(feature = "platformer")]
@@ -1318,6 +2580,7 @@ impl App {
                     active,
                     &self.theme,
                     dt,
+                    &self.lobby.players,
                 );
             },
             #[cfg(feature = "tron")]
@@ -1329,6 +2592,7 @@ impl App {
                     &self.theme,
                     dt,
                     local_id,
+                    &self.lobby.players,
                 );
             },
             #[allow(unreachable_patterns)]
@@ -1336,36 +2600,242 @@ impl App {
         }
     }
 
+    /// Start playing the selected game, waiting in `Loading` first if its
+    /// assets have not all arrived.
+    fn enter_game(&mut self) {
+        let game_id = self.lobby.selected_game;
+        self.assets.request(game_id);
+        if self.assets.is_ready(game_id) {
+            self.transition_to(AppState::InGame);
+        } else {
+            self.transition_to(AppState::Loading);
+        }
+    }
+
     /// Transition to a new app state.
     pub fn transition_to(&mut self, new_state: AppState) {
         let old_state = self.state;
         self.state = new_state;
 
         match (old_state, new_state) {
-            (AppState::Lobby, AppState::InGame) => {
+            (AppState::Lobby, AppState::InGame | AppState::Loading) => {
                 self.setup_game();
             },
-            (AppState::BetweenRounds, AppState::InGame)
-            | (AppState::GameOver, AppState::InGame) => {
+            // Set up when loading began
+            (AppState::Loading, AppState::InGame) => {},
+            (
+                AppState::BetweenRounds | AppState::GameOver,
+                AppState::InGame | AppState::Loading,
+            ) => {
                 // New round or new game — clear scene for fresh render
                 self.scene.clear();
                 self.between_round_end_time = None;
+                self.intermission = None;
                 self.game_over_timestamp = None;
+                self.round_phase = RoundPhase::Playing;
+                self.round_phase_end_time = None;
                 // Re-init game instance if needed (e.g., starting fresh from GameOver)
                 if self.game.is_none() {
                     self.setup_game();
                 }
             },
+            (_, AppState::Replay) => {
+                self.scene.clear();
+                self.round_phase = RoundPhase::Playing;
+                self.round_phase_end_time = None;
+            },
             (AppState::InGame, AppState::Lobby) | (_, AppState::Lobby) => {
                 self.scene.clear();
                 self.game = None;
                 self.network_role = None;
                 self.round_tracker = None;
+                self.standings = None;
+                self.replay = None;
                 self.between_round_end_time = None;
+                self.intermission = None;
                 self.game_over_timestamp = None;
+                self.course_vote = None;
+                self.team_draft = None;
             },
             _ => {},
         }
+        crate::telemetry::set_context(format!("{new_state:?}/{}", self.lobby.selected_game));
+    }
+
+    /// Open a `.bpreplay` file, or re-run a `.bpinputs` recording, and start
+    /// watching it. Only from the lobby, outside a room, since playback
+    /// replaces the active game.
+    pub fn load_replay(&mut self, data: &[u8]) -> Result<(), LocalizedText> {
+        if self.state != AppState::Lobby || self.lobby.connected {
+            return Err(TextKey::ReplayLeaveRoom.into());
+        }
+        let invalid =
+            |reason: String| LocalizedText::new(TextKey::ReplayInvalid).with("reason", reason);
+        let unavailable = |game_id: GameId| format!("{game_id} is not available in this build");
+        let playback = if data.starts_with(INPUT_REPLAY_MAGIC) {
+            let inputs = decode_input_replay(data).map_err(|e| invalid(e.to_string()))?;
+            let Some(game) = self.registry.create(inputs.game) else {
+                return Err(invalid(unavailable(inputs.game)));
+            };
+            ReplayPlayback::new(ReplayPlayer::new(inputs, game).into_match_replay())
+                .map_err(invalid)?
+        } else {
+            ReplayPlayback::load(data).map_err(invalid)?
+        };
+        let game_id = playback.replay.metadata.game;
+        if self.registry.create(game_id).is_none() {
+            return Err(invalid(unavailable(game_id)));
+        }
+        self.assets.request(game_id);
+        self.replay = Some(playback);
+        self.lobby.error_message = None;
+        self.lobby.status_message = None;
+        self.transition_to(AppState::Replay);
+        Ok(())
+    }
+
+    /// The locally hosted room's last recorded match, as `.bpinputs` file
+    /// bytes.
+    pub fn export_input_replay(&self) -> Option<Vec<u8>> {
+        let replay = self.local_host.as_ref()?.input_replay()?;
+        match encode_input_replay(replay) {
+            Ok(data) => Some(data),
+            Err(e) => {
+                crate::diag::console_warn!("Failed to encode input replay: {e}");
+                None
+            },
+        }
+    }
+
+    /// The lobby's game, settings, pacing, and alert filter, for sharing.
+    pub fn export_room_config(&self) -> RoomExport {
+        RoomExport::new(
+            self.lobby.selected_game,
+            self.lobby.game_settings.clone(),
+            self.lobby.pacing,
+            self.lobby.overlay_config.clone(),
+        )
+    }
+
+    /// Apply a shared room config to this lobby. The game, settings and
+    /// pacing go out with the next game start; the alert filter is sent to
+    /// the server right away. Only the leader of a room still in its lobby
+    /// can import, since the server keeps the filter per room.
+    pub fn import_room_config(&mut self, text: &str) -> Result<(), LocalizedText> {
+        if self.state != AppState::Lobby || !self.lobby.connected || !self.lobby.is_leader {
+            return Err(TextKey::RoomConfigLeaderOnly.into());
+        }
+        let invalid =
+            |reason: String| LocalizedText::new(TextKey::RoomConfigInvalid).with("reason", reason);
+        let export = RoomExport::from_json(text).map_err(|e| invalid(e.to_string()))?;
+        let Some(game_id) = export.game_id() else {
+            return Err(invalid(format!("unknown game \"{}\"", export.game)));
+        };
+        if self.registry.create(game_id).is_none() {
+            return Err(invalid(format!("{game_id} is not available in this build")));
+        }
+        self.registry
+            .validate_settings(game_id, &export.settings)
+            .map_err(|e| invalid(e.to_string()))?;
+
+        self.lobby.selected_game = game_id;
+        self.assets.request(game_id);
+        self.lobby.game_settings = export.settings;
+        self.lobby.pacing = export.pacing;
+        let msg = ClientMessage::OverlayConfig(OverlayConfigMsg {
+            room_config: export.overlay,
+        });
+        match encode_client_message(&msg) {
+            Ok(data) => {
+                if let Err(e) = self.ws.send(&data) {
+                    crate::diag::console_warn!("Failed to send OverlayConfig: {e}");
+                }
+            },
+            Err(e) => crate::diag::console_warn!("Failed to encode OverlayConfig: {e}"),
+        }
+        self.lobby.error_message = None;
+        self.lobby.status_message = Some(TextKey::RoomConfigImported.into());
+        Ok(())
+    }
+
+    /// Advance replay playback and apply the frames that came due.
+    fn update_replay(&mut self, dt: f32) {
+        let Some(ref mut playback) = self.replay else {
+            return;
+        };
+        let step = playback.advance(f64::from(dt));
+        if let Some(round_index) = step.new_round {
+            self.start_replay_round(round_index);
+        }
+
+        let (Some(playback), Some(active)) = (self.replay.as_ref(), self.game.as_mut()) else {
+            return;
+        };
+        let frames = &playback.current_round().frames[step.frames];
+        for frame in frames {
+            if let Some(ref course) = frame.course {
+                active.game.apply_course_data(course);
+            }
+        }
+        // Each frame is a full snapshot; only the newest matters
+        if let Some(frame) = frames.last() {
+            active.game.apply_state(&frame.state);
+            active.tick = frame.tick;
+            active.server_time_ms = frame.server_time_ms;
+        }
+    }
+
+    /// Re-create the game for a replay round, as a spectator following the
+    /// first human player.
+    fn start_replay_round(&mut self, round_index: usize) {
+        let Some(ref playback) = self.replay else {
+            return;
+        };
+        let meta = &playback.replay.metadata;
+        let total_rounds = playback.replay.rounds.len() as u8;
+        let round = playback.current_round().round;
+        let Some(mut game) = self.registry.create(meta.game) else {
+            return;
+        };
+        game.init(
+            &meta.players,
+            &GameConfig::builder()
+                .round_count(total_rounds)
+                .custom(meta.custom.clone())
+                .setting("hole_index", round.saturating_sub(1))
+                .build(),
+        );
+        let followed = meta
+            .players
+            .iter()
+            .find(|p| !p.is_bot)
+            .or(meta.players.first())
+            .map_or(0, |p| p.id);
+        let game_id = meta.game;
+
+        let tracker = self
+            .round_tracker
+            .get_or_insert_with(|| RoundTracker::new(total_rounds));
+        if round_index > 0 {
+            tracker.record_round(&playback.replay.rounds[round_index - 1].scores);
+        }
+        tracker.current_round = round;
+
+        self.game = Some(ActiveGame {
+            game,
+            game_id,
+            tick: 0,
+            server_time_ms: 0,
+            tick_accumulator: 0.0,
+        });
+        self.network_role = Some(NetworkRole {
+            is_leader: false,
+            local_player_id: followed,
+            is_spectator: true,
+        });
+        self.prev_local_alive = true;
+        self.prev_tron_overtime = false;
+        self.scene.clear();
     }
 
     fn setup_game(&mut self) {
@@ -1380,11 +2850,7 @@ impl App {
         };
 
         let round_count = game.round_count_hint();
-        let config = GameConfig {
-            round_count,
-            round_duration: std::time::Duration::from_secs(90),
-            custom: HashMap::new(),
-        };
+        let config = GameConfig::builder().round_count(round_count).build();
         game.init(&self.lobby.players, &config);
 
         let local_player_id = self.lobby.local_player_id.unwrap_or(0);
@@ -1393,21 +2859,62 @@ impl App {
             game,
             game_id,
             tick: 0,
+            server_time_ms: 0,
             tick_accumulator: 0.0,
         });
+        self.net_diag.reset_game();
+        #[cfg(feature = "state-diff")]
+        self.state_inspector.reset_game();
         self.network_role = Some(NetworkRole {
             is_leader: self.lobby.is_leader,
             local_player_id,
             is_spectator: self.lobby.is_spectator,
         });
         self.round_tracker = Some(RoundTracker::new(round_count));
+        self.standings = None;
+        self.round_heatmap = None;
+        self.victory = None;
         self.prev_local_alive = true;
+        self.prev_tron_overtime = false;
         self.scene.clear();
     }
 }
 
 // ── requestAnimationFrame loop ─────────────────────────────────
 
+/// Fetch one game texture and upload it once decoded. A failed fetch is
+/// recorded too, so `Loading` never waits on it forever.
+#[cfg(target_family = "wasm")]
+fn load_game_asset(app: &std::rc::Rc<std::cell::RefCell<App>>, spec: crate::assets::AssetSpec) {
+    use std::rc::Rc;
+
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen::closure::Closure;
+
+    let Ok(img) = web_sys::HtmlImageElement::new() else {
+        app.borrow_mut().assets.finish(spec.url, false);
+        return;
+    };
+    let app_loaded = Rc::clone(app);
+    let img_loaded = img.clone();
+    let onload = Closure::<dyn FnMut()>::new(move || {
+        let mut app = app_loaded.borrow_mut();
+        app.renderer
+            .load_texture_with_wrap(spec.texture_id, &img_loaded, spec.repeat);
+        app.assets.finish(spec.url, true);
+    });
+    let app_failed = Rc::clone(app);
+    let onerror = Closure::<dyn FnMut()>::new(move || {
+        web_sys::console::warn_1(&format!("Failed to load {}", spec.url).into());
+        app_failed.borrow_mut().assets.finish(spec.url, false);
+    });
+    img.set_onload(Some(onload.as_ref().unchecked_ref()));
+    img.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+    onload.forget();
+    onerror.forget();
+    img.set_src(spec.url);
+}
+
 #[cfg(target_family = "wasm")]
 pub fn run() {
     use std::cell::RefCell;
@@ -1428,37 +2935,6 @@ pub fn run() {
 
     let app = Rc::new(RefCell::new(App::new(renderer)));
 
-    // Load platformer sprite atlas (ID 0, clamp-to-edge)
-    {
-        let app_atlas = Rc::clone(&app);
-        let img = web_sys::HtmlImageElement::new().unwrap();
-        let img_clone = img.clone();
-        let onload = wasm_bindgen::closure::Closure::<dyn FnMut()>::new(move || {
-            app_atlas.borrow_mut().renderer.load_texture(0, &img_clone);
-            web_sys::console::log_1(&"Platformer atlas loaded".into());
-        });
-        img.set_onload(Some(onload.as_ref().unchecked_ref()));
-        onload.forget();
-        img.set_src("assets/sprites/platformer_atlas.png");
-    }
-
-    // Load parallax background texture (ID 1, repeat wrapping)
-    {
-        let app_bg = Rc::clone(&app);
-        let img = web_sys::HtmlImageElement::new().unwrap();
-        let img_clone = img.clone();
-        let onload = wasm_bindgen::closure::Closure::<dyn FnMut()>::new(move || {
-            app_bg
-                .borrow_mut()
-                .renderer
-                .load_texture_with_wrap(1, &img_clone, true);
-            web_sys::console::log_1(&"Parallax background loaded".into());
-        });
-        img.set_onload(Some(onload.as_ref().unchecked_ref()));
-        onload.forget();
-        img.set_src("assets/sprites/platformer_bg.png");
-    }
-
     // Attach input listeners
     bridge::attach_input_listeners(&app);
     // Attach JS→Rust bridge callbacks
@@ -1474,6 +2950,12 @@ pub fn run() {
         move |timestamp: f64| {
             app_loop.borrow_mut().frame(timestamp);
 
+            // Fetch game assets requested this frame
+            let queued = app_loop.borrow_mut().assets.take_queued();
+            for spec in queued {
+                load_game_asset(&app_loop, spec);
+            }
+
             // Schedule next frame
             if let Some(window) = web_sys::window() {
                 let _ = window
@@ -1511,10 +2993,12 @@ mod tests {
             PlayerScore {
                 player_id: 1,
                 score: 5,
+                disconnected: false,
             },
             PlayerScore {
                 player_id: 2,
                 score: 3,
+                disconnected: false,
             },
         ]);
         assert_eq!(tracker.cumulative_scores[&1], 5);
@@ -1524,14 +3008,24 @@ mod tests {
             PlayerScore {
                 player_id: 1,
                 score: 2,
+                disconnected: false,
             },
             PlayerScore {
                 player_id: 2,
                 score: 7,
+                disconnected: false,
             },
         ]);
         assert_eq!(tracker.cumulative_scores[&1], 7);
         assert_eq!(tracker.cumulative_scores[&2], 10);
+
+        tracker.record_round(&[PlayerScore {
+            player_id: 2,
+            score: 1,
+            disconnected: true,
+        }]);
+        assert_eq!(tracker.cumulative_scores[&2], 11);
+        assert!(tracker.disconnected.contains(&2));
     }
 
     #[test]
@@ -0,0 +1,135 @@
+//! Per-game assets fetched on demand, so the first load is only the lobby
+//! and core client. A game's textures are requested when it is picked in
+//! the lobby or a match of it starts; the app waits in
+//! [`AppState::Loading`](crate::app::AppState::Loading) until they settle.
+
+use std::collections::HashMap;
+
+use breakpoint_core::game_trait::GameId;
+
+/// One texture a game needs before it can render.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssetSpec {
+    pub url: &'static str,
+    pub texture_id: u8,
+    /// Tile with `REPEAT` wrapping instead of clamping to the edge.
+    pub repeat: bool,
+}
+
+const PLATFORMER_ASSETS: &[AssetSpec] = &[
+    AssetSpec {
+        url: "assets/sprites/platformer_atlas.png",
+        texture_id: 0,
+        repeat: false,
+    },
+    AssetSpec {
+        url: "assets/sprites/platformer_bg.png",
+        texture_id: 1,
+        repeat: true,
+    },
+];
+
+/// Assets `game` loads before play starts. Games drawn from geometry
+/// alone need none.
+pub fn game_assets(game: GameId) -> &'static [AssetSpec] {
+    match game {
+        GameId::Platformer => PLATFORMER_ASSETS,
+        _ => &[],
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LoadStatus {
+    Pending,
+    Loaded,
+    /// The fetch failed; the game renders without it rather than waiting.
+    Failed,
+}
+
+/// Tracks which game assets have been requested and how far they got.
+/// The frame loop starts the actual fetches from [`take_queued`].
+///
+/// [`take_queued`]: AssetLoader::take_queued
+#[derive(Debug, Default)]
+pub struct AssetLoader {
+    status: HashMap<&'static str, LoadStatus>,
+    queued: Vec<AssetSpec>,
+}
+
+impl AssetLoader {
+    /// Queue every asset of `game` not already requested.
+    pub fn request(&mut self, game: GameId) {
+        for spec in game_assets(game) {
+            if !self.status.contains_key(spec.url) {
+                self.status.insert(spec.url, LoadStatus::Pending);
+                self.queued.push(*spec);
+            }
+        }
+    }
+
+    /// Requested assets whose fetch has not been started yet.
+    pub fn take_queued(&mut self) -> Vec<AssetSpec> {
+        std::mem::take(&mut self.queued)
+    }
+
+    /// Record the outcome of a fetch.
+    pub fn finish(&mut self, url: &'static str, ok: bool) {
+        let status = if ok {
+            LoadStatus::Loaded
+        } else {
+            LoadStatus::Failed
+        };
+        self.status.insert(url, status);
+    }
+
+    /// `(settled, total)` assets of `game`; failed fetches count as settled.
+    pub fn progress(&self, game: GameId) -> (usize, usize) {
+        let assets = game_assets(game);
+        let settled = assets
+            .iter()
+            .filter(|spec| {
+                matches!(
+                    self.status.get(spec.url),
+                    Some(LoadStatus::Loaded | LoadStatus::Failed)
+                )
+            })
+            .count();
+        (settled, assets.len())
+    }
+
+    /// Whether `game` can start without waiting on any fetch.
+    pub fn is_ready(&self, game: GameId) -> bool {
+        let (settled, total) = self.progress(game);
+        settled == total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_queues_each_asset_once() {
+        let mut loader = AssetLoader::default();
+        assert!(
+            loader.is_ready(GameId::Golf),
+            "Golf has no assets to wait on"
+        );
+        assert!(!loader.is_ready(GameId::Platformer));
+
+        loader.request(GameId::Platformer);
+        loader.request(GameId::Platformer);
+        let queued = loader.take_queued();
+        assert_eq!(queued.len(), 2);
+        assert!(loader.take_queued().is_empty());
+        assert_eq!(loader.progress(GameId::Platformer), (0, 2));
+
+        loader.finish(queued[0].url, true);
+        loader.finish(queued[1].url, false);
+        assert_eq!(loader.progress(GameId::Platformer), (2, 2));
+        assert!(
+            loader.is_ready(GameId::Platformer),
+            "Failures do not block play"
+        );
+    }
+}
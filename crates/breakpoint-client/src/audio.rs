@@ -6,8 +6,11 @@ pub enum AudioEvent {
     CriticalAlert,
     GolfStroke,
     GolfBallSink,
+    GolfBlindShot,
     PlatformerJump,
     PlatformerPowerUp,
+    PowerUpCollected,
+    PowerUpExpired,
     PlatformerFinish,
     PlatformerAttack,
     PlatformerHit,
@@ -46,10 +49,15 @@ impl AudioEventQueue {
                 AudioEvent::CriticalAlert => (220.0, 0.4, WaveType::Square, SoundCategory::Overlay),
                 AudioEvent::GolfStroke => (250.0, 0.1, WaveType::Sine, SoundCategory::Game),
                 AudioEvent::GolfBallSink => (520.0, 0.3, WaveType::Sine, SoundCategory::Game),
+                AudioEvent::GolfBlindShot => (196.0, 0.25, WaveType::Triangle, SoundCategory::Game),
                 AudioEvent::PlatformerJump => {
                     (330.0, 0.08, WaveType::Triangle, SoundCategory::Game)
                 },
                 AudioEvent::PlatformerPowerUp => (440.0, 0.2, WaveType::Sine, SoundCategory::Game),
+                AudioEvent::PowerUpCollected => (660.0, 0.2, WaveType::Sine, SoundCategory::Game),
+                AudioEvent::PowerUpExpired => {
+                    (260.0, 0.15, WaveType::Triangle, SoundCategory::Game)
+                },
                 AudioEvent::PlatformerFinish => {
                     (520.0, 0.5, WaveType::Triangle, SoundCategory::Game)
                 },
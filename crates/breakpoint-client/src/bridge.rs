@@ -9,13 +9,24 @@ pub fn push_ui_state(app: &App) {
     {
         let state = serde_json::json!({
             "appState": format!("{:?}", app.state),
+            "loading": (app.state == crate::app::AppState::Loading).then(|| {
+                let (loaded, total) = app.assets.progress(app.lobby.selected_game);
+                serde_json::json!({
+                    "game": app.lobby.selected_game.to_string(),
+                    "loaded": loaded,
+                    "total": total,
+                })
+            }),
             "lobby": {
                 "playerName": app.lobby.player_name,
                 "roomCode": app.lobby.room_code,
+                "roomSecret": app.lobby.room_secret,
+                "roomAlias": app.lobby.room_alias,
                 "connected": app.lobby.connected,
                 "isLeader": app.lobby.is_leader,
                 "isSpectator": app.lobby.is_spectator,
                 "selectedGame": app.lobby.selected_game.to_string(),
+                "playlist": app.lobby.playlist.iter().map(|g| g.as_str()).collect::<Vec<_>>(),
                 "joinCodeInput": app.lobby.join_code_input,
                 "statusMessage": app.lobby.status_message,
                 "errorMessage": app.lobby.error_message,
@@ -25,8 +36,83 @@ pub fn push_ui_state(app: &App) {
                         "name": p.display_name,
                         "isLeader": p.is_leader,
                         "isBot": p.is_bot,
+                        "externalBot": p.external_bot,
+                        "botDifficulty": p.bot_difficulty,
+                        "hotSeatOf": p.hot_seat_of,
+                        "seat": app.hot_seat.seat_number(p.id),
+                        "sessionScore": app.lobby.session_scores.get(&p.id),
+                    })
+                }).collect::<Vec<_>>(),
+                "localPlayerId": app.lobby.local_player_id,
+                "canAddLocalPlayer": !app.lobby.is_spectator
+                    && app.hot_seat.next_seat_number().is_some(),
+                "social": app.lobby.social.as_ref().map(|s| {
+                    let entry = |e: &breakpoint_core::net::messages::SocialEntry| {
+                        serde_json::json!({
+                            "id": e.id,
+                            "name": e.name,
+                            "online": e.online,
+                            "lastPlayed": e.last_played,
+                            "roomCode": e.room_code,
+                            "game": e.game,
+                        })
+                    };
+                    serde_json::json!({
+                        "yourId": s.your_id,
+                        "friends": s.friends.iter().map(entry).collect::<Vec<_>>(),
+                        "recent": s.recent.iter().map(entry).collect::<Vec<_>>(),
+                        "sharingPresence": s.sharing_presence,
+                    })
+                }),
+                "tutorials": app.lobby.tutorials,
+                "invites": app.lobby.invites.iter().map(|i| {
+                    serde_json::json!({
+                        "fromId": i.from_id,
+                        "fromName": i.from_name,
+                        "roomCode": i.room_code,
+                        "sentAt": i.sent_at,
                     })
                 }).collect::<Vec<_>>(),
+                "announcements": app.lobby.announcements.iter().map(|a| {
+                    serde_json::json!({
+                        "id": a.id,
+                        "message": a.message,
+                        "severity": a.severity,
+                    })
+                }).collect::<Vec<_>>(),
+                "joinQueue": app.lobby.join_queue.as_ref().map(|q| {
+                    serde_json::json!({
+                        "requireApproval": q.require_approval,
+                        "entries": q.entries.iter().map(|e| {
+                            serde_json::json!({
+                                "ticket": e.ticket,
+                                "name": e.display_name,
+                                "approved": e.approved,
+                            })
+                        }).collect::<Vec<_>>(),
+                    })
+                }),
+                "transferOffer": app.lobby.transfer_offer.as_ref()
+                    .filter(|t| t.expires_at > app.prev_timestamp)
+                    .map(|t| {
+                        serde_json::json!({
+                            "roomCode": t.offer.room_code,
+                            "reason": t.offer.reason,
+                            "secsLeft": ((t.expires_at - app.prev_timestamp) / 1000.0).ceil(),
+                        })
+                    }),
+                "gameSettings": app.lobby.game_settings,
+                "pacing": {
+                    "intermissionSecs": app.lobby.pacing.intermission_secs,
+                    "autoAdvance": app.lobby.pacing.auto_advance,
+                    "minResultsSecs": app.lobby.pacing.min_results_secs,
+                },
+                "eventFilter": {
+                    "sources": app.lobby.overlay_config.enabled_sources,
+                    "tags": app.lobby.overlay_config.event_tags,
+                    "types": app.lobby.overlay_config.event_types,
+                    "minPriority": app.lobby.overlay_config.min_priority,
+                },
             },
             "overlay": {
                 "tickerText": app.overlay.ticker.display_text(),
@@ -56,19 +142,56 @@ pub fn push_ui_state(app: &App) {
                     "totalRounds": rt.total_rounds,
                     "scores": rt.cumulative_scores,
                     "roundScoresHistory": rt.round_scores,
+                    "disconnected": rt.disconnected,
+                })
+            }),
+            "replay": app.replay.as_ref().map(|r| {
+                serde_json::json!({
+                    "gameId": r.replay.metadata.game.to_string(),
+                    "round": r.current_round().round,
+                    "totalRounds": r.replay.rounds.len(),
+                    "progress": r.round_progress(),
+                    "paused": r.paused,
+                    "finished": r.finished,
                 })
             }),
+            "replayUrl": app.last_match_id.as_ref().map(|id| {
+                format!("api/v1/public/history/{id}/replay")
+            }),
+            "inputReplay": app
+                .local_host
+                .as_ref()
+                .is_some_and(|host| host.input_replay().is_some()),
             "connected": app.ws.is_connected(),
+            "netDiag": app.net_diag.visible.then(|| app.net_diag.report(app.prev_timestamp, &app.clock)),
             "muted": app.audio_settings.muted,
+            "telemetryOptIn": crate::telemetry::is_enabled(),
             "musicVolume": app.audio_settings.master_volume * app.audio_settings.music_volume,
             "golfHud": build_golf_hud(app),
             "platformerHud": build_platformer_hud(app),
             "lasertagHud": build_lasertag_hud(app),
+            "roundHeatmap": build_round_heatmap(app),
+            "victory": build_victory(app),
+            "lobbyToy": build_lobby_toy(app),
             "tronHud": build_tron_hud(app),
+            "scoreboard": build_scoreboard(app),
+            "roundPhase": {
+                "phase": format!("{:?}", app.round_phase),
+                "remaining": app.round_phase_end_time.map(|end| {
+                    ((end - app.prev_timestamp) / 1000.0).max(0.0)
+                }),
+            },
             "betweenRoundCountdown": app.between_round_end_time.map(|end| {
                 let remaining = (end - app.prev_timestamp) / 1000.0;
                 if remaining > 0.0 { remaining } else { 0.0 }
             }),
+            "betweenRoundTotal": app.between_round_total_secs,
+            "waitingForHost": app
+                .intermission
+                .as_ref()
+                .is_some_and(|i| i.remaining_secs.is_none()),
+            "courseVote": build_course_vote(app),
+            "teamDraft": build_team_draft(app),
             "gameOverCountdown": app.game_over_timestamp.map(|start| {
                 let elapsed = (app.prev_timestamp - start) / 1000.0;
                 let remaining = 30.0 - elapsed;
@@ -89,8 +212,102 @@ pub fn push_ui_state(app: &App) {
     let _ = app;
 }
 
-/// Build Golf HUD data (hole/par/strokes/sunk indicators).
+/// How long the winning course stays on screen after the vote closes.
+#[cfg(target_family = "wasm")]
+const COURSE_VOTE_LINGER_MS: f64 = 2500.0;
+
+/// Build the course vote overlay: thumbnails, tallies, and the winner.
+#[cfg(target_family = "wasm")]
+fn build_course_vote(app: &App) -> serde_json::Value {
+    let Some(ref vote) = app.course_vote else {
+        return serde_json::Value::Null;
+    };
+    if vote
+        .closed_at
+        .is_some_and(|at| app.prev_timestamp - at > COURSE_VOTE_LINGER_MS)
+    {
+        return serde_json::Value::Null;
+    }
+    let candidates: Vec<serde_json::Value> = vote
+        .candidates
+        .iter()
+        .map(|c| {
+            serde_json::json!({
+                "cols": c.cols,
+                "rows": c.rows,
+                "cells": c.cells,
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "round": vote.round,
+        "candidates": candidates,
+        "votes": vote.votes,
+        "remaining": ((vote.end_time - app.prev_timestamp) / 1000.0).max(0.0),
+        "winner": vote.winner,
+        "myChoice": vote.my_choice,
+    })
+}
+
+/// How long the drafted teams stay on screen after the draft closes.
+#[cfg(target_family = "wasm")]
+const TEAM_DRAFT_LINGER_MS: f64 = 2500.0;
+
+/// Build the team draft overlay: each team's captain and picks, the players
+/// still up for grabs, and whose pick it is.
 #[cfg(target_family = "wasm")]
+fn build_team_draft(app: &App) -> serde_json::Value {
+    let Some(ref draft) = app.team_draft else {
+        return serde_json::Value::Null;
+    };
+    if draft
+        .closed_at
+        .is_some_and(|at| app.prev_timestamp - at > TEAM_DRAFT_LINGER_MS)
+    {
+        return serde_json::Value::Null;
+    }
+    let name = |id: u64| {
+        app.lobby
+            .players
+            .iter()
+            .find(|p| p.id == id)
+            .map(|p| p.display_name.clone())
+            .unwrap_or_else(|| format!("Player {id}"))
+    };
+    let teams: Vec<serde_json::Value> = draft
+        .captains
+        .iter()
+        .enumerate()
+        .map(|(team, &captain)| {
+            let members: Vec<String> = draft
+                .picks
+                .iter()
+                .filter(|p| p.team as usize == team && p.player_id != captain)
+                .map(|p| name(p.player_id))
+                .collect();
+            serde_json::json!({
+                "captain": name(captain),
+                "members": members,
+            })
+        })
+        .collect();
+    let pool: Vec<serde_json::Value> = draft
+        .pool
+        .iter()
+        .map(|&id| serde_json::json!({ "id": id, "name": name(id) }))
+        .collect();
+    serde_json::json!({
+        "teams": teams,
+        "pool": pool,
+        "turn": draft.turn,
+        "picker": draft.captain().map(name),
+        "myTurn": app.is_my_draft_turn(),
+        "remaining": ((draft.end_time - app.prev_timestamp) / 1000.0).max(0.0),
+    })
+}
+
+/// Build Golf HUD data (hole/par/strokes/sunk indicators).
+#[cfg(all(target_family = "wasm", feature = "golf"))]
 fn build_golf_hud(app: &App) -> serde_json::Value {
     use breakpoint_core::game_trait::GameId;
 
@@ -111,10 +328,34 @@ fn build_golf_hud(app: &App) -> serde_json::Value {
         return serde_json::Value::Null;
     };
 
+    // Prefer the server's course preview; it reflects custom course dirs
+    let preview = active
+        .game
+        .as_any()
+        .downcast_ref::<breakpoint_golf::MiniGolf>()
+        .and_then(|g| g.preview())
+        .filter(|p| p.course_index == state.course_index);
     let courses = breakpoint_golf::course::all_courses();
     let course = courses.get(state.course_index as usize);
-    let par = course.map(|c| c.par).unwrap_or(3);
-    let hole_name = course.map(|c| c.name.as_str()).unwrap_or("Hole");
+    let par = preview
+        .map(|p| p.par)
+        .or(course.map(|c| c.par))
+        .unwrap_or(3);
+    let hole_name = preview
+        .map(|p| p.name.as_str())
+        .or(course.map(|c| c.name.as_str()))
+        .unwrap_or("Hole");
+    let hole_length = preview.map(|p| p.hole_length.round());
+
+    let intro = (state.intro_remaining > 0.0).then(|| {
+        let local_id = app.network_role.as_ref().map(|r| r.local_player_id);
+        serde_json::json!({
+            "remaining": state.intro_remaining,
+            "votes": state.intro_skip_votes.len(),
+            "players": state.balls.len(),
+            "voted": local_id.is_some_and(|id| state.intro_skip_votes.contains(&id)),
+        })
+    });
 
     let players_json: Vec<serde_json::Value> = app
         .lobby
@@ -128,12 +369,21 @@ fn build_golf_hud(app: &App) -> serde_json::Value {
                 .iter()
                 .position(|&id| id == p.id)
                 .map(|i| i + 1);
+            // Closest-to-the-pin shows how far each played ball finished
+            let distance = state
+                .closest_to_pin
+                .then(|| state.balls.get(&p.id))
+                .flatten()
+                .filter(|b| strokes > 0 && !b.is_sunk)
+                .zip(course)
+                .map(|(b, c)| (b.distance_to_hole(c) * 10.0).round() / 10.0);
             serde_json::json!({
                 "id": p.id,
                 "name": p.display_name,
                 "strokes": strokes,
                 "isSunk": is_sunk,
                 "sunkRank": sunk_rank,
+                "distance": distance,
             })
         })
         .collect();
@@ -142,19 +392,37 @@ fn build_golf_hud(app: &App) -> serde_json::Value {
         "holeIndex": state.course_index,
         "holeName": hole_name,
         "par": par,
+        "holeLength": hole_length,
+        "closestToPin": state.closest_to_pin,
+        "night": state.night,
+        "intro": intro,
+        "undoRemaining": app
+            .network_role
+            .as_ref()
+            .and_then(|r| state.undo_windows.get(&r.local_player_id)),
+        "caddieLeft": app
+            .network_role
+            .as_ref()
+            .and_then(|r| state.caddie_left.get(&r.local_player_id)),
+        "caddieShowing": app
+            .network_role
+            .as_ref()
+            .is_some_and(|r| {
+                crate::game::golf_input::caddie_hint(active, r.local_player_id).is_some()
+            }),
         "players": players_json,
         "roundTimer": state.round_timer,
     })
 }
 
-#[cfg(not(target_family = "wasm"))]
+#[cfg(not(all(target_family = "wasm", feature = "golf")))]
 #[allow(dead_code)]
 fn build_golf_hud(_app: &App) -> serde_json::Value {
     serde_json::Value::Null
 }
 
-/// Build Platformer HUD data (rankings, mode, hazard, eliminations).
-#[cfg(target_family = "wasm")]
+/// Build Platformer HUD data (rankings, mode, hazard, co-op, eliminations).
+#[cfg(all(target_family = "wasm", feature = "platformer"))]
 fn build_platformer_hud(app: &App) -> serde_json::Value {
     use breakpoint_core::game_trait::GameId;
 
@@ -232,6 +500,34 @@ fn build_platformer_hud(app: &App) -> serde_json::Value {
         })
         .unwrap_or((0.0, 0.0));
 
+    // Held banana and trap trips
+    let local_holds_banana = local_id
+        .and_then(|id| state.active_powerups.get(&id))
+        .is_some_and(|pups| {
+            pups.iter()
+                .any(|p| p.kind == breakpoint_platformer::powerups::PowerUpKind::Banana)
+        });
+    let local_stumbling = local_ps.is_some_and(|s| s.stumble_timer > 0.0);
+
+    // Survival hazard
+    let alive_count = state.players.values().filter(|p| !p.eliminated).count();
+    let (mode, hazard_speed, hazard_paused) = match state.hazard {
+        Some(ref h) => ("Survival", h.speed, h.pause_remaining > 0.0),
+        None if state.coop.is_some() => ("Coop", 0.0, false),
+        None => ("Race", 0.0, false),
+    };
+
+    // Co-op shared lives and local revive progress (0..1)
+    let coop_lives = state.coop.as_ref().map(|c| c.lives).unwrap_or(0);
+    let local_down = local_id.and_then(|id| state.coop.as_ref()?.downed.get(&id));
+    let local_downed = local_down.is_some();
+    let local_revive_progress = local_down
+        .map(|d| {
+            let revive_secs = racer.config().coop.revive_secs.max(0.01);
+            (d.revive_progress / revive_secs).min(1.0)
+        })
+        .unwrap_or(0.0);
+
     // Checkpoint progress
     let local_checkpoint = local_ps.map(|s| s.last_checkpoint_id).unwrap_or(0);
     let total_checkpoints = state.course.checkpoint_positions.len();
@@ -265,7 +561,15 @@ fn build_platformer_hud(app: &App) -> serde_json::Value {
         .unwrap_or_default();
 
     serde_json::json!({
-        "mode": "Race",
+        "mode": mode,
+        "hazardSpeed": hazard_speed,
+        "hazardPaused": hazard_paused,
+        "aliveCount": alive_count,
+        "coopLives": coop_lives,
+        "localDowned": local_downed,
+        "localReviveProgress": local_revive_progress,
+        "localHoldsBanana": local_holds_banana,
+        "localStumbling": local_stumbling,
         "players": players_json,
         "enemyCount": state.enemies.iter().filter(|e| e.alive).count(),
         "finishCount": state.finish_order.len(),
@@ -287,7 +591,7 @@ fn build_platformer_hud(app: &App) -> serde_json::Value {
 
 /// Cached minimap grid data (rooms, connections, grid dimensions).
 /// Only rebuilt when `course_version` changes (wall breaks are rare).
-#[cfg(target_family = "wasm")]
+#[cfg(all(target_family = "wasm", feature = "platformer"))]
 struct MinimapCache {
     course_version: u32,
     grid_cols: u32,
@@ -298,7 +602,7 @@ struct MinimapCache {
     connections: Vec<serde_json::Value>,
 }
 
-#[cfg(target_family = "wasm")]
+#[cfg(all(target_family = "wasm", feature = "platformer"))]
 fn minimap_cache() -> &'static std::sync::Mutex<Option<MinimapCache>> {
     static CACHE: std::sync::OnceLock<std::sync::Mutex<Option<MinimapCache>>> =
         std::sync::OnceLock::new();
@@ -308,7 +612,7 @@ fn minimap_cache() -> &'static std::sync::Mutex<Option<MinimapCache>> {
 /// Build compact minimap data for the 2D labyrinth.
 /// Sends room grid positions, connections, themes, and player locations.
 /// Grid data is cached and only rebuilt when the course version changes.
-#[cfg(target_family = "wasm")]
+#[cfg(all(target_family = "wasm", feature = "platformer"))]
 fn build_platformer_minimap(
     state: &breakpoint_platformer::PlatformerState,
     lobby_players: &[breakpoint_core::player::Player],
@@ -435,14 +739,14 @@ fn build_platformer_minimap(
     })
 }
 
-#[cfg(not(target_family = "wasm"))]
+#[cfg(not(all(target_family = "wasm", feature = "platformer")))]
 #[allow(dead_code)]
 fn build_platformer_hud(_app: &App) -> serde_json::Value {
     serde_json::Value::Null
 }
 
 /// Build LaserTag HUD data (scores, team scores, power-ups, stun).
-#[cfg(target_family = "wasm")]
+#[cfg(all(target_family = "wasm", feature = "lasertag"))]
 fn build_lasertag_hud(app: &App) -> serde_json::Value {
     use breakpoint_core::game_trait::GameId;
 
@@ -478,6 +782,7 @@ fn build_lasertag_hud(app: &App) -> serde_json::Value {
         .iter()
         .map(|p| {
             let tags = state.tags_scored.get(&p.id).copied().unwrap_or(0);
+            let assists = state.assists.get(&p.id).copied().unwrap_or(0);
             let ps = state.players.get(&p.id);
             let stunned = ps.map(|s| s.stun_remaining > 0.0).unwrap_or(false);
             let team = state.teams.get(&p.id).copied();
@@ -486,6 +791,7 @@ fn build_lasertag_hud(app: &App) -> serde_json::Value {
                 "id": p.id,
                 "name": p.display_name,
                 "tags": tags,
+                "assists": assists,
                 "stunned": stunned,
                 "team": team,
                 "isLocal": is_local,
@@ -503,10 +809,10 @@ fn build_lasertag_hud(app: &App) -> serde_json::Value {
         }
     }
 
-    let local_stun = local_id
-        .and_then(|id| state.players.get(&id))
-        .map(|s| s.stun_remaining)
-        .unwrap_or(0.0);
+    let local_ps = local_id.and_then(|id| state.players.get(&id));
+    let local_stun = local_ps.map(|s| s.stun_remaining).unwrap_or(0.0);
+    let local_energy = local_ps.map(|s| s.energy.current).unwrap_or(0.0);
+    let local_overheated = local_ps.is_some_and(|s| s.energy.is_overheated());
 
     serde_json::json!({
         "teamMode": team_mode_str,
@@ -514,17 +820,199 @@ fn build_lasertag_hud(app: &App) -> serde_json::Value {
         "teamScores": team_scores,
         "localStunRemaining": local_stun,
         "roundTimer": state.round_timer,
+        "energyMax": state.energy_max,
+        "localEnergy": local_energy,
+        "localOverheated": local_overheated,
     })
 }
 
-#[cfg(not(target_family = "wasm"))]
+#[cfg(not(all(target_family = "wasm", feature = "lasertag")))]
 #[allow(dead_code)]
 fn build_lasertag_hud(_app: &App) -> serde_json::Value {
     serde_json::Value::Null
 }
 
-/// Build Tron HUD data (player name positions, minimap walls, gauges).
+/// Build the between-rounds heatmap overlay, with the laser tag arena's
+/// walls when the game's state is still loaded.
+#[cfg(target_family = "wasm")]
+fn build_round_heatmap(app: &App) -> serde_json::Value {
+    use crate::app::AppState;
+
+    if app.state != AppState::BetweenRounds {
+        return serde_json::Value::Null;
+    }
+    let Some(ref map) = app.round_heatmap else {
+        return serde_json::Value::Null;
+    };
+    #[cfg(not(feature = "lasertag"))]
+    let walls: Vec<[f32; 4]> = Vec::new();
+    #[cfg(feature = "lasertag")]
+    let walls: Vec<[f32; 4]> = app
+        .game
+        .as_ref()
+        .filter(|g| g.game_id == breakpoint_core::game_trait::GameId::LaserTag)
+        .and_then(crate::game::read_game_state::<breakpoint_lasertag::LaserTagState>)
+        .map(|s| {
+            s.arena_walls
+                .iter()
+                .map(|w| [w.ax, w.az, w.bx, w.bz])
+                .collect()
+        })
+        .unwrap_or_default();
+
+    serde_json::json!({
+        "width": map.width,
+        "depth": map.depth,
+        "cols": map.cols,
+        "rows": map.rows,
+        "presence": map.presence,
+        "tags": map.tags,
+        "walls": walls,
+    })
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[allow(dead_code)]
+fn build_round_heatmap(_app: &App) -> serde_json::Value {
+    serde_json::Value::Null
+}
+
+/// Build the round announcer banner: the game's summary with the
+/// winners' names filled in as `{winners}`.
+#[cfg(target_family = "wasm")]
+fn build_victory(app: &App) -> serde_json::Value {
+    use crate::app::AppState;
+
+    if !matches!(app.state, AppState::BetweenRounds | AppState::GameOver) {
+        return serde_json::Value::Null;
+    }
+    let Some(ref victory) = app.victory else {
+        return serde_json::Value::Null;
+    };
+    let winners: Vec<String> = victory
+        .winner_ids
+        .iter()
+        .map(|id| {
+            app.lobby
+                .players
+                .iter()
+                .find(|p| p.id == *id)
+                .map(|p| p.display_name.clone())
+                .unwrap_or_else(|| format!("Player {id}"))
+        })
+        .collect();
+    let message = victory.message.clone().with("winners", winners.join(", "));
+
+    serde_json::json!({
+        "message": message,
+        "winnerIds": victory.winner_ids,
+        "keyStat": victory.key_stat,
+    })
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[allow(dead_code)]
+fn build_victory(_app: &App) -> serde_json::Value {
+    serde_json::Value::Null
+}
+
+/// Build the hold-to-view scoreboard from the latest standings, while the
+/// scoreboard key is held in a game. `waiting` until the first standings
+/// of the round arrive.
+#[cfg(target_family = "wasm")]
+fn build_scoreboard(app: &App) -> serde_json::Value {
+    if app.state != crate::app::AppState::InGame || !app.input.is_key_down(SCOREBOARD_KEY) {
+        return serde_json::Value::Null;
+    }
+    let Some(ref standings) = app.standings else {
+        return serde_json::json!({ "waiting": true });
+    };
+    let local_id = app.network_role.as_ref().map(|r| r.local_player_id);
+    let name = |id: u64| {
+        app.lobby
+            .players
+            .iter()
+            .find(|p| p.id == id)
+            .map(|p| p.display_name.clone())
+            .unwrap_or_else(|| format!("Player {id}"))
+    };
+    serde_json::json!({
+        "round": standings.round,
+        "roundCount": standings.round_count,
+        "players": standings.players.iter().map(|e| {
+            serde_json::json!({
+                "id": e.player_id,
+                "name": name(e.player_id),
+                "roundScore": e.round_score,
+                "matchScore": e.match_score,
+                "team": e.team,
+                "ping": e.ping_ms,
+                "isLocal": local_id == Some(e.player_id),
+            })
+        }).collect::<Vec<_>>(),
+        "teams": standings.teams.iter().map(|t| {
+            serde_json::json!({ "team": t.team, "score": t.score })
+        }).collect::<Vec<_>>(),
+    })
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[allow(dead_code)]
+fn build_scoreboard(_app: &App) -> serde_json::Value {
+    serde_json::Value::Null
+}
+
+/// Key held to show the scoreboard.
+#[cfg(target_family = "wasm")]
+const SCOREBOARD_KEY: &str = "Tab";
+
+/// Build the lobby target and its hit board, leaders first. Only shown
+/// while the room waits in its lobby.
 #[cfg(target_family = "wasm")]
+fn build_lobby_toy(app: &App) -> serde_json::Value {
+    use crate::app::AppState;
+
+    if app.state != AppState::Lobby || !app.lobby.connected {
+        return serde_json::Value::Null;
+    }
+    let Some(ref toy) = app.lobby.toy else {
+        return serde_json::Value::Null;
+    };
+    let mut board: Vec<(&str, u32)> = app
+        .lobby
+        .players
+        .iter()
+        .filter_map(|p| {
+            let hits = toy.hits.get(&p.id).copied().filter(|&h| h > 0)?;
+            Some((p.display_name.as_str(), hits))
+        })
+        .collect();
+    board.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    let my_hits = app
+        .lobby
+        .local_player_id
+        .and_then(|id| toy.hits.get(&id).copied())
+        .unwrap_or(0);
+
+    serde_json::json!({
+        "x": toy.target_x,
+        "y": toy.target_y,
+        "radius": toy.radius,
+        "myHits": my_hits,
+        "board": board.iter().map(|(name, hits)| {
+            serde_json::json!({ "name": name, "hits": hits })
+        }).collect::<Vec<_>>(),
+    })
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[allow(dead_code)]
+fn build_lobby_toy(_app: &App) -> serde_json::Value {
+    serde_json::Value::Null
+}
+
+/// Build Tron HUD data (player name positions, minimap walls, gauges).
+#[cfg(all(target_family = "wasm", feature = "tron"))]
 fn build_tron_hud(app: &App) -> serde_json::Value {
     {
         use breakpoint_core::game_trait::GameId;
@@ -628,11 +1116,13 @@ fn build_tron_hud(app: &App) -> serde_json::Value {
             "arenaDepth": state.arena_depth,
             "minimapWalls": minimap_walls,
             "minimapCycles": minimap_cycles,
+            "overtime": state.overtime,
+            "draw": !state.draw.is_empty(),
         })
     }
 }
 
-#[cfg(not(target_family = "wasm"))]
+#[cfg(not(all(target_family = "wasm", feature = "tron")))]
 #[allow(dead_code)]
 fn build_tron_hud(_app: &App) -> serde_json::Value {
     serde_json::Value::Null
@@ -687,6 +1177,21 @@ pub fn push_profile_data() {
 #[cfg(all(not(target_family = "wasm"), feature = "profiling"))]
 pub fn push_profile_data() {}
 
+/// Push the state diff panel to the JS overlay when it changed; `null`
+/// hides it.
+#[cfg(all(target_family = "wasm", feature = "state-diff"))]
+pub fn push_state_diff(inspector: &mut crate::state_inspector::StateInspector) {
+    let Some(report) = inspector.take_update() else {
+        return;
+    };
+    if let Ok(json_str) = serde_json::to_string(&report) {
+        call_window_fn("_breakpointStateDiffUpdate", Some(&json_str));
+    }
+}
+
+#[cfg(all(not(target_family = "wasm"), feature = "state-diff"))]
+pub fn push_state_diff(_inspector: &mut crate::state_inspector::StateInspector) {}
+
 /// Show fatal error overlay via JS (WebGL2 failure, unrecoverable errors).
 #[cfg(target_family = "wasm")]
 pub fn show_fatal_error(msg: &str) {
@@ -880,9 +1385,15 @@ pub fn attach_ui_callbacks(app: &std::rc::Rc<std::cell::RefCell<App>>) {
     use wasm_bindgen::closure::Closure;
 
     use breakpoint_core::game_trait::GameId;
-    use breakpoint_core::net::messages::{ClientMessage, JoinRoomMsg, RequestGameStartMsg};
+    use breakpoint_core::i18n::{LocalizedText, TextKey};
+    use breakpoint_core::net::messages::{
+        ClientMessage, JoinQueueAction, JoinRoomMsg, ManageJoinQueueMsg, RequestGameStartMsg,
+        RoomReshape,
+    };
     use breakpoint_core::net::protocol::{PROTOCOL_VERSION, encode_client_message};
+    use breakpoint_core::overlay::config::OverlayConfigMsg;
     use breakpoint_core::player::PlayerColor;
+    use breakpoint_core::round_phase::RoundPacing;
 
     use crate::app::AppState;
 
@@ -909,41 +1420,18 @@ pub fn attach_ui_callbacks(app: &std::rc::Rc<std::cell::RefCell<App>>) {
         closure.forget();
     }
 
-    // ui_create_room
+    // ui_create_room(alias) — an empty alias creates a room known only by its code
     {
         let app = Rc::clone(app);
-        let closure = Closure::<dyn FnMut()>::new(move || {
+        let closure = Closure::<dyn FnMut(String)>::new(move |alias: String| {
             let mut app = app.borrow_mut();
             if app.lobby.connected {
-                app.lobby.status_message =
-                    Some("Already in a room. Refresh to create a new one.".to_string());
+                app.lobby.status_message = Some(TextKey::AlreadyInRoomCreate.into());
                 return;
             }
-            if !app.ws.has_connection() {
-                let url = app.lobby.ws_url.clone();
-                if let Err(e) = app.ws.connect(&url) {
-                    app.lobby.status_message = Some(format!("Connection failed: {e}"));
-                    return;
-                }
-            }
-            app.lobby.is_leader = true;
-            let color = PlayerColor::PALETTE[app.lobby.color_index % PlayerColor::PALETTE.len()];
-            let msg = ClientMessage::JoinRoom(JoinRoomMsg {
-                room_code: String::new(),
-                player_name: app.lobby.player_name.clone(),
-                player_color: color,
-                protocol_version: PROTOCOL_VERSION,
-                session_token: None,
-            });
-            match encode_client_message(&msg) {
-                Ok(data) => {
-                    if let Err(e) = app.ws.send(&data) {
-                        crate::diag::console_warn!("Failed to send JoinRoom (create): {e}");
-                    }
-                },
-                Err(e) => crate::diag::console_warn!("Failed to encode JoinRoom (create): {e}"),
-            }
-            app.lobby.status_message = Some("Creating room...".to_string());
+            let alias = alias.trim().to_ascii_uppercase();
+            app.lobby.room_alias = (!alias.is_empty()).then_some(alias);
+            app.create_room(None);
         });
         let _ = js_sys::Reflect::set(
             &window,
@@ -953,30 +1441,54 @@ pub fn attach_ui_callbacks(app: &std::rc::Rc<std::cell::RefCell<App>>) {
         closure.forget();
     }
 
+    // ui_start_tutorial(game) — a solo room that starts the game's tutorial
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut(String)>::new(move |game: String| {
+            let mut app = app.borrow_mut();
+            if app.lobby.connected {
+                app.lobby.status_message = Some(TextKey::AlreadyInRoomCreate.into());
+                return;
+            }
+            let Some(game_id) = GameId::from_str_opt(&game) else {
+                crate::diag::console_warn!("Unknown tutorial game: {game}");
+                return;
+            };
+            app.lobby.room_alias = None;
+            app.create_room(Some(game_id));
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpStartTutorial".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
     // ui_join_room(code)
     {
         let app = Rc::clone(app);
         let closure = Closure::<dyn FnMut(String)>::new(move |code: String| {
             let mut app = app.borrow_mut();
             if app.lobby.connected {
-                app.lobby.status_message =
-                    Some("Already in a room. Refresh to join a new one.".to_string());
+                app.lobby.status_message = Some(TextKey::AlreadyInRoomJoin.into());
                 return;
             }
             let code = code.trim().to_uppercase();
             if code.is_empty() {
-                app.lobby.status_message =
-                    Some("Type a room code first (e.g. ABCD-1234)".to_string());
+                app.lobby.status_message = Some(TextKey::EnterRoomCode.into());
                 return;
             }
             if !app.ws.has_connection() {
                 let url = app.lobby.ws_url.clone();
                 if let Err(e) = app.ws.connect(&url) {
-                    app.lobby.status_message = Some(format!("Connection failed: {e}"));
+                    app.lobby.status_message =
+                        Some(LocalizedText::new(TextKey::ConnectionFailed).with("reason", e));
                     return;
                 }
             }
             app.lobby.is_leader = false;
+            app.lobby.room_alias = None;
             let color = PlayerColor::PALETTE[app.lobby.color_index % PlayerColor::PALETTE.len()];
             let msg = ClientMessage::JoinRoom(JoinRoomMsg {
                 room_code: code.clone(),
@@ -984,6 +1496,11 @@ pub fn attach_ui_callbacks(app: &std::rc::Rc<std::cell::RefCell<App>>) {
                 player_color: color,
                 protocol_version: PROTOCOL_VERSION,
                 session_token: None,
+                identity_key: Some(app.lobby.identity_key.clone()),
+                accepts_batches: true,
+                room_alias: None,
+                bot_token: None,
+                tutorial: None,
             });
             match encode_client_message(&msg) {
                 Ok(data) => {
@@ -993,7 +1510,8 @@ pub fn attach_ui_callbacks(app: &std::rc::Rc<std::cell::RefCell<App>>) {
                 },
                 Err(e) => crate::diag::console_warn!("Failed to encode JoinRoom (join): {e}"),
             }
-            app.lobby.status_message = Some(format!("Joining room {code}..."));
+            app.lobby.status_message =
+                Some(LocalizedText::new(TextKey::JoiningRoom).with("code", &code));
         });
         let _ = js_sys::Reflect::set(
             &window,
@@ -1003,6 +1521,49 @@ pub fn attach_ui_callbacks(app: &std::rc::Rc<std::cell::RefCell<App>>) {
         closure.forget();
     }
 
+    // ui_host_local(relay_url, encrypted, alias) — an empty URL hosts offline
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut(String, bool, String)>::new(
+            move |url: String, encrypted: bool, alias: String| {
+                let mut app = app.borrow_mut();
+                let url = url.trim();
+                let relay_url = (!url.is_empty()).then_some(url);
+                if let Err(e) = app.host_local(relay_url, encrypted, Some(&alias)) {
+                    app.lobby.status_message = Some(e);
+                }
+            },
+        );
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpHostLocal".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_use_relay(url, secret) — later joins go through a LAN relay,
+    // end-to-end encrypted when the host's room secret is given
+    {
+        let app = Rc::clone(app);
+        let closure =
+            Closure::<dyn FnMut(String, String)>::new(move |url: String, secret: String| {
+                let mut app = app.borrow_mut();
+                let url = url.trim();
+                if !url.is_empty() && !app.lobby.connected {
+                    app.lobby.ws_url = url.to_string();
+                    let secret = secret.trim();
+                    app.lobby.room_secret = (!secret.is_empty()).then(|| secret.to_string());
+                }
+            });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpUseRelay".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
     // ui_start_game
     {
         let app = Rc::clone(app);
@@ -1012,6 +1573,9 @@ pub fn attach_ui_callbacks(app: &std::rc::Rc<std::cell::RefCell<App>>) {
                 let msg = ClientMessage::RequestGameStart(RequestGameStartMsg {
                     game_name: app.lobby.selected_game.to_string(),
                     custom: app.lobby.game_settings.clone(),
+                    win_condition: None,
+                    pacing: Some(app.lobby.pacing),
+                    featured: app.lobby.featured,
                 });
                 match encode_client_message(&msg) {
                     Ok(data) => {
@@ -1037,6 +1601,9 @@ pub fn attach_ui_callbacks(app: &std::rc::Rc<std::cell::RefCell<App>>) {
         let closure = Closure::<dyn FnMut(String)>::new(move |name: String| {
             let mut app = app.borrow_mut();
             app.lobby.selected_game = GameId::from_str_opt(&name).unwrap_or_default();
+            // Start fetching the game's assets while the lobby fills
+            let game = app.lobby.selected_game;
+            app.assets.request(game);
         });
         let _ = js_sys::Reflect::set(
             &window,
@@ -1046,19 +1613,186 @@ pub fn attach_ui_callbacks(app: &std::rc::Rc<std::cell::RefCell<App>>) {
         closure.forget();
     }
 
-    // ui_set_game_setting(key, value_json)
+    // ui_set_pacing(intermission_secs, auto_advance, min_results_secs)
     {
         let app = Rc::clone(app);
-        let closure =
-            Closure::<dyn FnMut(String, String)>::new(move |key: String, value_json: String| {
-                let mut app = app.borrow_mut();
-                if let Ok(val) = serde_json::from_str::<serde_json::Value>(&value_json) {
-                    app.lobby.game_settings.insert(key, val);
+        let closure = Closure::<dyn FnMut(f64, bool, f64)>::new(
+            move |intermission: f64, auto_advance: bool, min_results: f64| {
+                app.borrow_mut().lobby.pacing = RoundPacing {
+                    intermission_secs: intermission as u16,
+                    auto_advance,
+                    min_results_secs: min_results as u16,
                 }
-            });
+                .clamped();
+            },
+        );
         let _ = js_sys::Reflect::set(
             &window,
-            &"_bpSetGameSetting".into(),
+            &"_bpSetPacing".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_set_featured(featured)
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut(bool)>::new(move |featured: bool| {
+            app.borrow_mut().lobby.featured = featured;
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpSetFeatured".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_accept_transfer
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut()>::new(move || {
+            app.borrow_mut().accept_room_transfer();
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpAcceptTransfer".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_decline_transfer
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut()>::new(move || {
+            app.borrow_mut().decline_room_transfer();
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpDeclineTransfer".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_merge_room(code)
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut(String)>::new(move |code: String| {
+            let code = code.trim().to_uppercase();
+            if !code.is_empty() {
+                app.borrow().send_reshape_room(RoomReshape::MergeInto(code));
+            }
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpMergeRoom".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_split_room
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut()>::new(move || {
+            app.borrow().send_reshape_room(RoomReshape::Split);
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpSplitRoom".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_advance_round
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut()>::new(move || {
+            app.borrow().send_advance_round();
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpAdvanceRound".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_config_change
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut(String)>::new(move |json: String| {
+            app.borrow().send_config_change(&json);
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpConfigChange".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_debug_command
+    #[cfg(feature = "debug-commands")]
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut(String)>::new(move |json: String| {
+            app.borrow().send_debug_command(&json);
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpDebug".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_golf_undo
+    #[cfg(feature = "golf")]
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut()>::new(move || {
+            app.borrow_mut().send_golf_undo();
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpGolfUndo".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_golf_caddie
+    #[cfg(feature = "golf")]
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut()>::new(move || {
+            app.borrow_mut().send_golf_caddie();
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpGolfCaddie".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_set_game_setting(key, value_json)
+    {
+        let app = Rc::clone(app);
+        let closure =
+            Closure::<dyn FnMut(String, String)>::new(move |key: String, value_json: String| {
+                let mut app = app.borrow_mut();
+                if let Ok(val) = serde_json::from_str::<serde_json::Value>(&value_json) {
+                    app.lobby.game_settings.insert(key, val);
+                }
+            });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpSetGameSetting".into(),
             closure.as_ref().unchecked_ref(),
         );
         closure.forget();
@@ -1104,6 +1838,157 @@ pub fn attach_ui_callbacks(app: &std::rc::Rc<std::cell::RefCell<App>>) {
         closure.forget();
     }
 
+    // ui_reload_settings — re-read localStorage after settings sync
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut()>::new(move || {
+            let mut app = app.borrow_mut();
+            app.audio_settings = crate::storage::load_audio_settings();
+            app.golf_drag_aim = crate::storage::load_golf_drag_aim();
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpReloadSettings".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_toggle_state_diff / ui_pin_state_diff
+    #[cfg(feature = "state-diff")]
+    {
+        let toggle_app = Rc::clone(app);
+        let toggle = Closure::<dyn FnMut()>::new(move || {
+            toggle_app.borrow_mut().state_inspector.toggle();
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpToggleStateDiff".into(),
+            toggle.as_ref().unchecked_ref(),
+        );
+        toggle.forget();
+
+        let pin_app = Rc::clone(app);
+        let pin = Closure::<dyn FnMut()>::new(move || {
+            pin_app.borrow_mut().state_inspector.toggle_pin();
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpPinStateDiff".into(),
+            pin.as_ref().unchecked_ref(),
+        );
+        pin.forget();
+    }
+
+    // ui_toggle_net_diag
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut()>::new(move || {
+            app.borrow_mut().net_diag.toggle();
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpToggleNetDiag".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_set_telemetry
+    {
+        let closure = Closure::<dyn FnMut(bool)>::new(crate::telemetry::set_enabled);
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpSetTelemetry".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_load_replay(bytes)
+    {
+        let app = Rc::clone(app);
+        let closure =
+            Closure::<dyn FnMut(js_sys::Uint8Array)>::new(move |bytes: js_sys::Uint8Array| {
+                let mut app = app.borrow_mut();
+                if let Err(msg) = app.load_replay(&bytes.to_vec()) {
+                    app.lobby.error_message = Some(msg.clone());
+                    app.lobby.status_message = Some(msg);
+                }
+            });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpLoadReplay".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_export_input_replay() -> .bpinputs bytes, or null
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut() -> wasm_bindgen::JsValue>::new(move || {
+            match app.borrow().export_input_replay() {
+                Some(data) => js_sys::Uint8Array::from(data.as_slice()).into(),
+                None => wasm_bindgen::JsValue::NULL,
+            }
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpExportInputReplay".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_export_room_config() -> JSON text
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut() -> String>::new(move || {
+            app.borrow().export_room_config().to_json()
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpExportRoomConfig".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_import_room_config(json)
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut(String)>::new(move |text: String| {
+            let mut app = app.borrow_mut();
+            if let Err(msg) = app.import_room_config(&text) {
+                app.lobby.error_message = Some(msg.clone());
+                app.lobby.status_message = Some(msg);
+            }
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpImportRoomConfig".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_toggle_replay_pause
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut()>::new(move || {
+            if let Some(ref mut playback) = app.borrow_mut().replay {
+                playback.paused = !playback.paused;
+            }
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpToggleReplayPause".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
     // ui_return_to_lobby
     {
         let app = Rc::clone(app);
@@ -1145,7 +2030,7 @@ pub fn attach_ui_callbacks(app: &std::rc::Rc<std::cell::RefCell<App>>) {
         let app = Rc::clone(app);
         let closure = Closure::<dyn FnMut()>::new(move || {
             let app = app.borrow();
-            let msg = ClientMessage::AddBot(breakpoint_core::net::messages::AddBotMsg {});
+            let msg = ClientMessage::AddBot(breakpoint_core::net::messages::AddBotMsg::default());
             match encode_client_message(&msg) {
                 Ok(data) => {
                     if let Err(e) = app.ws.send(&data) {
@@ -1187,6 +2072,325 @@ pub fn attach_ui_callbacks(app: &std::rc::Rc<std::cell::RefCell<App>>) {
         );
         closure.forget();
     }
+
+    // ui_add_local_player
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut()>::new(move || {
+            app.borrow().send_add_local_player();
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpAddLocalPlayer".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_reroll_name(player_id)
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut(f64)>::new(move |player_id: f64| {
+            app.borrow().send_reroll_name(player_id as u64);
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpRerollName".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_lobby_toy_click(x, y)
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut(f64, f64)>::new(move |x: f64, y: f64| {
+            app.borrow().send_lobby_toy_click(x as f32, y as f32);
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpLobbyToyClick".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_remove_local_player(player_id)
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut(f64)>::new(move |player_id: f64| {
+            app.borrow().send_remove_local_player(player_id as u64);
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpRemoveLocalPlayer".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_set_bot_difficulty(player_id, difficulty)
+    {
+        let app = Rc::clone(app);
+        let closure =
+            Closure::<dyn FnMut(f64, String)>::new(move |player_id: f64, difficulty: String| {
+                let Ok(difficulty) = serde_json::from_value(serde_json::Value::String(difficulty))
+                else {
+                    return;
+                };
+                let app = app.borrow();
+                let msg = ClientMessage::SetBotDifficulty(
+                    breakpoint_core::net::messages::SetBotDifficultyMsg {
+                        player_id: player_id as u64,
+                        difficulty,
+                    },
+                );
+                match encode_client_message(&msg) {
+                    Ok(data) => {
+                        if let Err(e) = app.ws.send(&data) {
+                            crate::diag::console_warn!("Failed to send SetBotDifficulty: {e}");
+                        }
+                    },
+                    Err(e) => crate::diag::console_warn!("Failed to encode SetBotDifficulty: {e}"),
+                }
+            });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpSetBotDifficulty".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_draft_pick(player_id)
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut(f64)>::new(move |player_id: f64| {
+            app.borrow_mut().draft_pick(player_id as u64);
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpDraftPick".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_cast_course_vote(choice)
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut(f64)>::new(move |choice: f64| {
+            app.borrow_mut().cast_course_vote(choice as u8);
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpCastCourseVote".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_set_join_approval(required)
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut(bool)>::new(move |required: bool| {
+            let app = app.borrow();
+            let msg = ClientMessage::ManageJoinQueue(ManageJoinQueueMsg {
+                action: JoinQueueAction::RequireApproval(required),
+            });
+            match encode_client_message(&msg) {
+                Ok(data) => {
+                    if let Err(e) = app.ws.send(&data) {
+                        crate::diag::console_warn!("Failed to send ManageJoinQueue: {e}");
+                    }
+                },
+                Err(e) => crate::diag::console_warn!("Failed to encode ManageJoinQueue: {e}"),
+            }
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpSetJoinApproval".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_set_event_filter(json) -- {sources, tags, types, minPriority}.
+    // Unknown event types are dropped; the server validates the rest.
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut(String)>::new(move |json: String| {
+            let app = app.borrow();
+            let filter: serde_json::Value = match serde_json::from_str(&json) {
+                Ok(v) => v,
+                Err(e) => {
+                    crate::diag::console_warn!("Invalid event filter: {e}");
+                    return;
+                },
+            };
+            let strings = |key: &str| -> Vec<String> {
+                filter[key]
+                    .as_array()
+                    .map(|items| {
+                        items
+                            .iter()
+                            .filter_map(|v| v.as_str())
+                            .map(|s| s.trim().to_string())
+                            .filter(|s| !s.is_empty())
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            };
+            let mut room_config = app.lobby.overlay_config.clone();
+            room_config.enabled_sources = strings("sources");
+            room_config.event_tags = strings("tags");
+            room_config.event_types = strings("types")
+                .into_iter()
+                .filter_map(|t| serde_json::from_value(serde_json::Value::String(t)).ok())
+                .collect();
+            room_config.min_priority =
+                serde_json::from_value(filter["minPriority"].clone()).unwrap_or_default();
+            let msg = ClientMessage::OverlayConfig(OverlayConfigMsg { room_config });
+            match encode_client_message(&msg) {
+                Ok(data) => {
+                    if let Err(e) = app.ws.send(&data) {
+                        crate::diag::console_warn!("Failed to send OverlayConfig: {e}");
+                    }
+                },
+                Err(e) => crate::diag::console_warn!("Failed to encode OverlayConfig: {e}"),
+            }
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpSetEventFilter".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_answer_join(ticket, admit)
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut(f64, bool)>::new(move |ticket: f64, admit: bool| {
+            let app = app.borrow();
+            let ticket = ticket as u64;
+            let action = if admit {
+                JoinQueueAction::Admit(ticket)
+            } else {
+                JoinQueueAction::Deny(ticket)
+            };
+            let msg = ClientMessage::ManageJoinQueue(ManageJoinQueueMsg { action });
+            match encode_client_message(&msg) {
+                Ok(data) => {
+                    if let Err(e) = app.ws.send(&data) {
+                        crate::diag::console_warn!("Failed to send ManageJoinQueue: {e}");
+                    }
+                },
+                Err(e) => crate::diag::console_warn!("Failed to encode ManageJoinQueue: {e}"),
+            }
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpAnswerJoin".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_set_friend(friend_id, is_friend)
+    {
+        let app = Rc::clone(app);
+        let closure =
+            Closure::<dyn FnMut(String, bool)>::new(move |friend_id: String, is_friend: bool| {
+                let app = app.borrow();
+                let msg = ClientMessage::SetFriend(breakpoint_core::net::messages::SetFriendMsg {
+                    friend_id,
+                    is_friend,
+                });
+                match encode_client_message(&msg) {
+                    Ok(data) => {
+                        if let Err(e) = app.ws.send(&data) {
+                            crate::diag::console_warn!("Failed to send SetFriend: {e}");
+                        }
+                    },
+                    Err(e) => crate::diag::console_warn!("Failed to encode SetFriend: {e}"),
+                }
+            });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpSetFriend".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_invite_friend(friend_id)
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut(String)>::new(move |friend_id: String| {
+            let app = app.borrow();
+            let msg =
+                ClientMessage::InviteFriend(breakpoint_core::net::messages::InviteFriendMsg {
+                    friend_id,
+                });
+            match encode_client_message(&msg) {
+                Ok(data) => {
+                    if let Err(e) = app.ws.send(&data) {
+                        crate::diag::console_warn!("Failed to send InviteFriend: {e}");
+                    }
+                },
+                Err(e) => crate::diag::console_warn!("Failed to encode InviteFriend: {e}"),
+            }
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpInviteFriend".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_set_presence_sharing(share)
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut(bool)>::new(move |share: bool| {
+            let app = app.borrow();
+            let msg = ClientMessage::SetPresenceSharing(
+                breakpoint_core::net::messages::SetPresenceSharingMsg { share },
+            );
+            match encode_client_message(&msg) {
+                Ok(data) => {
+                    if let Err(e) = app.ws.send(&data) {
+                        crate::diag::console_warn!("Failed to send SetPresenceSharing: {e}");
+                    }
+                },
+                Err(e) => crate::diag::console_warn!("Failed to encode SetPresenceSharing: {e}"),
+            }
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpSetPresenceSharing".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
+
+    // ui_dismiss_invite(from_id)
+    {
+        let app = Rc::clone(app);
+        let closure = Closure::<dyn FnMut(String)>::new(move |from_id: String| {
+            app.borrow_mut()
+                .lobby
+                .invites
+                .retain(|i| i.from_id != from_id);
+        });
+        let _ = js_sys::Reflect::set(
+            &window,
+            &"_bpDismissInvite".into(),
+            closure.as_ref().unchecked_ref(),
+        );
+        closure.forget();
+    }
 }
 
 #[cfg(not(target_family = "wasm"))]
@@ -14,6 +14,8 @@ pub enum CameraMode {
         cycle_pos: Vec3,
         direction: [f32; 2],
     },
+    /// Golf: course flyover intro, posed along the preview spline.
+    GolfFlyover { position: Vec3, look_at: Vec3 },
     /// Overview of the course (golf fallback).
     GolfOverview {
         center_x: f32,
@@ -85,6 +87,13 @@ impl Camera {
                 self.target = self.target.lerp(look_at, lerp_factor);
                 self.up = Vec3::Y;
             },
+            CameraMode::GolfFlyover { position, look_at } => {
+                // Poses arrive at the server tick rate; a fast lerp hides the steps
+                let flyover_lerp = (10.0 * dt).min(1.0);
+                self.position = self.position.lerp(position, flyover_lerp);
+                self.target = self.target.lerp(look_at, flyover_lerp);
+                self.up = Vec3::Y;
+            },
             CameraMode::GolfOverview {
                 center_x,
                 center_z,
@@ -0,0 +1,127 @@
+//! Estimates the server's clock on the client's frame clock. After joining,
+//! the client sends a short burst of `ClockSync` probes, then one every
+//! [`RESYNC_MS`]. Each reply gives a round trip and an offset; the offset
+//! from the fastest recent round trip is trusted, since it had the least
+//! room for queueing to skew it. Snapshot timestamps, the network panel,
+//! and anything else timed against the server read the server's clock
+//! through here.
+
+/// Probes sent back to back after joining.
+pub const BURST_PROBES: usize = 5;
+
+/// Interval between probes once the burst is done.
+pub const RESYNC_MS: f64 = 30_000.0;
+
+/// A probe unanswered this long is given up on.
+const PROBE_TIMEOUT_MS: f64 = 3_000.0;
+
+/// Recent samples kept.
+const MAX_SAMPLES: usize = 8;
+
+#[derive(Debug, Clone, Copy)]
+struct ClockSample {
+    rtt: f64,
+    /// Server clock minus local clock.
+    offset: f64,
+}
+
+#[derive(Debug, Default)]
+pub struct ClockSync {
+    samples: Vec<ClockSample>,
+    /// Local time of the unanswered probe.
+    pending: Option<f64>,
+    last_probe: f64,
+}
+
+impl ClockSync {
+    /// Forget the estimate, for a new connection.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Whether to send a probe at `now`; if so, it counts as sent.
+    pub fn take_probe(&mut self, now: f64) -> bool {
+        if self
+            .pending
+            .is_some_and(|sent| now - sent < PROBE_TIMEOUT_MS)
+        {
+            return false;
+        }
+        // A timed-out probe is replaced straight away
+        let due = self.pending.is_some()
+            || self.samples.len() < BURST_PROBES
+            || now - self.last_probe >= RESYNC_MS;
+        if due {
+            self.pending = Some(now);
+            self.last_probe = now;
+        }
+        due
+    }
+
+    /// Record a reply to the probe sent at `client_time`, received at `now`.
+    pub fn record_reply(&mut self, client_time: f64, server_time_ms: u64, now: f64) {
+        let rtt = now - client_time;
+        if rtt < 0.0 {
+            return;
+        }
+        self.pending = None;
+        if self.samples.len() == MAX_SAMPLES {
+            self.samples.remove(0);
+        }
+        self.samples.push(ClockSample {
+            rtt,
+            offset: server_time_ms as f64 + rtt / 2.0 - now,
+        });
+    }
+
+    /// The latest round trip, in ms.
+    pub fn rtt_ms(&self) -> Option<f64> {
+        self.samples.last().map(|s| s.rtt)
+    }
+
+    /// Server clock minus local clock, in ms.
+    pub fn offset_ms(&self) -> Option<f64> {
+        self.samples
+            .iter()
+            .min_by(|a, b| a.rtt.total_cmp(&b.rtt))
+            .map(|s| s.offset)
+    }
+
+    /// The server's clock at local time `now`.
+    pub fn server_now(&self, now: f64) -> Option<f64> {
+        self.offset_ms().map(|offset| now + offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn burst_then_periodic_probes() {
+        let mut clock = ClockSync::default();
+        assert!(clock.take_probe(0.0));
+        assert!(!clock.take_probe(10.0), "One probe in flight");
+        for i in 0..BURST_PROBES {
+            let sent = i as f64 * 100.0;
+            clock.record_reply(sent, 5_000, sent + 40.0);
+            assert_eq!(clock.take_probe(sent + 100.0), i + 1 < BURST_PROBES);
+        }
+        assert!(clock.take_probe(400.0 + RESYNC_MS));
+        // A lost probe is retried after the timeout
+        assert!(!clock.take_probe(400.0 + RESYNC_MS + 100.0));
+        assert!(clock.take_probe(400.0 + RESYNC_MS + PROBE_TIMEOUT_MS));
+    }
+
+    #[test]
+    fn offset_comes_from_the_fastest_round_trip() {
+        let mut clock = ClockSync::default();
+        assert_eq!(clock.server_now(0.0), None);
+        // Server is 1000 ms ahead; the slow reply sat in a queue on the way back
+        clock.record_reply(0.0, 1_010, 20.0);
+        clock.record_reply(100.0, 1_110, 300.0);
+        assert_eq!(clock.offset_ms(), Some(1_000.0));
+        assert_eq!(clock.rtt_ms(), Some(200.0));
+        assert_eq!(clock.server_now(500.0), Some(1_500.0));
+    }
+}
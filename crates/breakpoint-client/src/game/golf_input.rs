@@ -1,20 +1,100 @@
 use glam::Vec2;
 
+use breakpoint_core::game_trait::PlayerId;
+use breakpoint_core::math::ScreenAim;
 use breakpoint_golf::GolfInput;
 
 use crate::app::{ActiveGame, NetworkRole};
 use crate::camera_gl::Camera;
-use crate::game::{read_game_state, send_player_input};
+use crate::game::{read_game_state, send_input_as, send_player_input};
+use crate::hot_seat::Seat;
 use crate::input::{InputState, MouseButton};
 use crate::net_client::WsClient;
 use crate::renderer::Renderer;
 
+/// Cursor distance from the ball that swings at full power.
+pub const FULL_POWER_AIM_DISTANCE: f32 = 15.0;
+
+/// Screen drag (CSS pixels) that swings at full power in drag-to-aim mode.
+pub const FULL_POWER_DRAG_PX: f32 = 200.0;
+
+/// Shorter drags are treated as taps and don't swing.
+const MIN_DRAG_PX: f32 = 8.0;
+
+/// Input asking the host to take back the sender's last stroke.
+pub fn undo_input() -> GolfInput {
+    GolfInput {
+        aim_angle: 0.0,
+        power: 0.0,
+        stroke: false,
+        skip_intro: false,
+        undo: true,
+        caddie: false,
+        screen_aim: None,
+    }
+}
+
+/// Input asking the host's caddie for a stroke suggestion.
+pub fn caddie_input() -> GolfInput {
+    GolfInput {
+        aim_angle: 0.0,
+        power: 0.0,
+        stroke: false,
+        skip_intro: false,
+        undo: false,
+        caddie: true,
+        screen_aim: None,
+    }
+}
+
+/// A drag-to-aim swing: pulling back from the press point shoots the other
+/// way, like a slingshot. The host turns the drag into a world direction
+/// with the camera yaw, so it aims the same way in every camera mode.
+pub fn drag_stroke(drag: Vec2, camera: &Camera) -> Option<GolfInput> {
+    let len = drag.length();
+    if len < MIN_DRAG_PX {
+        return None;
+    }
+    let camera_yaw =
+        breakpoint_core::math::camera_yaw(to_core(camera.position), to_core(camera.target));
+    Some(GolfInput {
+        aim_angle: 0.0,
+        power: (len / FULL_POWER_DRAG_PX).min(1.0),
+        stroke: true,
+        skip_intro: false,
+        undo: false,
+        caddie: false,
+        screen_aim: Some(ScreenAim {
+            drag: breakpoint_core::math::Vec2::new(-drag.x, -drag.y),
+            camera_yaw,
+        }),
+    })
+}
+
+fn to_core(v: glam::Vec3) -> breakpoint_core::math::Vec3 {
+    breakpoint_core::math::Vec3::new(v.x, v.y, v.z)
+}
+
 /// Process golf input: mouse hold for power, aim via cursor_to_ground, release to fire.
+/// With `drag_aim` the swing is a pull-back drag instead (see [`drag_stroke`]).
 /// Returns `true` if a stroke was sent this frame.
+/// The caddie hint the host sent `player_id` privately, if one is showing.
+pub fn caddie_hint(
+    active: &ActiveGame,
+    player_id: PlayerId,
+) -> Option<breakpoint_golf::caddie::CaddieHint> {
+    let golf = active
+        .game
+        .as_any()
+        .downcast_ref::<breakpoint_golf::MiniGolf>()?;
+    golf.caddie_hint(player_id).copied()
+}
+
 pub fn process_golf_input(
     input: &InputState,
     camera: &Camera,
     renderer: &Renderer,
+    drag_aim: bool,
     active: &mut ActiveGame,
     role: &NetworkRole,
     ws: &WsClient,
@@ -29,10 +109,34 @@ pub fn process_golf_input(
         return false;
     }
 
+    // During the course flyover, Space/Enter votes to skip it
+    if state.intro_remaining > 0.0 {
+        let pressed = input.is_key_just_pressed("Space") || input.is_key_just_pressed("Enter");
+        if pressed && !state.intro_skip_votes.contains(&role.local_player_id) {
+            let vote = GolfInput {
+                aim_angle: 0.0,
+                power: 0.0,
+                stroke: false,
+                skip_intro: true,
+                undo: false,
+                caddie: false,
+                screen_aim: None,
+            };
+            send_player_input(&vote, active, role, ws);
+        }
+        return false;
+    }
+
     let Some(ball) = state.balls.get(&role.local_player_id) else {
         return false;
     };
 
+    // Z takes back a misclicked stroke while its undo window is open
+    if input.is_key_just_pressed("KeyZ") && state.undo_windows.contains_key(&role.local_player_id) {
+        send_player_input(&undo_input(), active, role, ws);
+        return false;
+    }
+
     // Don't allow input if ball is still moving
     let vel_sq = ball.velocity.x * ball.velocity.x
         + ball.velocity.y * ball.velocity.y
@@ -41,6 +145,28 @@ pub fn process_golf_input(
         return false;
     }
 
+    // C asks the caddie while the lobby's hint budget lasts
+    if input.is_key_just_pressed("KeyC")
+        && caddie_hint(active, role.local_player_id).is_none()
+        && state
+            .caddie_left
+            .get(&role.local_player_id)
+            .is_some_and(|&n| n > 0)
+    {
+        send_player_input(&caddie_input(), active, role, ws);
+        return false;
+    }
+
+    if drag_aim {
+        if input.is_mouse_just_released(MouseButton::Left)
+            && let Some(golf_input) = input.drag_delta().and_then(|d| drag_stroke(d, camera))
+        {
+            send_player_input(&golf_input, active, role, ws);
+            return true;
+        }
+        return false;
+    }
+
     let (vw, vh) = renderer.viewport_size();
     let viewport = Vec2::new(vw, vh);
 
@@ -53,11 +179,15 @@ pub fn process_golf_input(
             if len > 0.1 {
                 let aim_angle = dz.atan2(dx);
                 // Power based on distance (clamped 0..1)
-                let power = (len / 15.0).min(1.0);
+                let power = (len / FULL_POWER_AIM_DISTANCE).min(1.0);
                 let golf_input = GolfInput {
                     aim_angle,
                     power,
                     stroke: true,
+                    skip_intro: false,
+                    undo: false,
+                    caddie: false,
+                    screen_aim: None,
                 };
                 send_player_input(&golf_input, active, role, ws);
                 return true;
@@ -66,3 +196,106 @@ pub fn process_golf_input(
     }
     false
 }
+
+/// Radians per second a guest's aim turns while left/right is held.
+const SEAT_AIM_SPEED: f32 = 1.8;
+/// Seconds of holding action to reach full power.
+const SEAT_CHARGE_SECS: f32 = 1.5;
+
+/// Process a hot-seat guest's golf input: left/right turn the aim, holding
+/// action builds power and releasing it swings. Action also votes to skip
+/// the flyover, and alt takes back a stroke while undo is open. Returns `true` if a stroke was sent this frame.
+pub fn process_golf_seat_input(
+    seat: &mut Seat,
+    input: &InputState,
+    dt: f32,
+    active: &mut ActiveGame,
+    ws: &WsClient,
+) -> bool {
+    let state: Option<breakpoint_golf::GolfState> = read_game_state(active);
+    let Some(state) = state else {
+        return false;
+    };
+    if state.round_complete {
+        return false;
+    }
+
+    if state.intro_remaining > 0.0 {
+        if input.is_key_just_pressed(seat.keys.action)
+            && !state.intro_skip_votes.contains(&seat.player_id)
+        {
+            let vote = GolfInput {
+                aim_angle: 0.0,
+                power: 0.0,
+                stroke: false,
+                skip_intro: true,
+                undo: false,
+                caddie: false,
+                screen_aim: None,
+            };
+            send_input_as(seat.player_id, &vote, active, ws);
+        }
+        return false;
+    }
+
+    let Some(ball) = state.balls.get(&seat.player_id) else {
+        return false;
+    };
+    if input.is_key_just_pressed(seat.keys.alt) && state.undo_windows.contains_key(&seat.player_id)
+    {
+        send_input_as(seat.player_id, &undo_input(), active, ws);
+        return false;
+    }
+    let vel_sq = ball.velocity.x * ball.velocity.x
+        + ball.velocity.y * ball.velocity.y
+        + ball.velocity.z * ball.velocity.z;
+    if vel_sq > 0.01 || ball.is_sunk {
+        seat.charge = 0.0;
+        return false;
+    }
+
+    seat.aim -= seat.axis(input, seat.keys.left, seat.keys.right) * SEAT_AIM_SPEED * dt;
+    if input.is_key_down(seat.keys.action) {
+        seat.charge = (seat.charge + dt / SEAT_CHARGE_SECS).min(1.0);
+        return false;
+    }
+    if input.keys_just_released.contains(seat.keys.action) && seat.charge > 0.0 {
+        let golf_input = GolfInput {
+            aim_angle: seat.aim,
+            power: seat.charge,
+            stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
+        };
+        seat.charge = 0.0;
+        send_input_as(seat.player_id, &golf_input, active, ws);
+        return true;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pulling_back_shoots_away_from_the_camera() {
+        let mut camera = Camera::new();
+        camera.position = glam::Vec3::new(4.0, 15.0, 8.0);
+        camera.target = glam::Vec3::new(4.0, 0.0, 10.0);
+
+        // Dragging down the screen, toward the player, fires up it: +Z here
+        let stroke = drag_stroke(Vec2::new(0.0, 100.0), &camera).unwrap();
+        let angle = stroke.screen_aim.unwrap().world_angle().unwrap();
+        assert!((angle - std::f32::consts::FRAC_PI_2).abs() < 1e-4);
+        assert!((stroke.power - 0.5).abs() < 1e-5);
+
+        assert_eq!(
+            drag_stroke(Vec2::new(0.0, 900.0), &camera).unwrap().power,
+            1.0
+        );
+        assert!(drag_stroke(Vec2::new(3.0, 3.0), &camera).is_none(), "A tap");
+    }
+}
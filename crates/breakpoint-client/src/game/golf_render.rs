@@ -3,11 +3,28 @@ use glam::{Vec2, Vec3, Vec4};
 use crate::app::{ActiveGame, NetworkRole};
 use crate::camera_gl::Camera;
 use crate::game::read_game_state;
-use crate::input::InputState;
+use crate::hot_seat::Seat;
+use crate::input::{InputState, MouseButton};
 use crate::renderer::Renderer;
 use crate::scene::{MaterialType, MeshType, Scene, Transform};
 use crate::theme::{Theme, rgb_vec4};
 
+/// Amber, to stand apart from the aim line.
+const BLIND_SHOT_COLOR: Vec4 = Vec4::new(1.0, 0.7, 0.2, 1.0);
+/// Caddie suggestion ring.
+const CADDIE_COLOR: Vec4 = Vec4::new(0.4, 1.0, 0.8, 0.9);
+/// Swirl over gravity wells that pull balls in.
+const ATTRACTOR_COLOR: Vec4 = Vec4::new(0.55, 0.35, 1.0, 0.5);
+/// Swirl over gravity wells that push balls away.
+const REPULSOR_COLOR: Vec4 = Vec4::new(1.0, 0.45, 0.2, 0.5);
+/// How much of the ground color is left on night holes.
+const NIGHT_DIM: f32 = 0.12;
+/// Warm pool of light under each ball on night holes.
+const LANTERN_COLOR: Vec4 = Vec4::new(1.0, 0.85, 0.5, 0.12);
+
+/// Opacity of other players' balls when balls are ghosted.
+const GHOST_ALPHA: f32 = 0.35;
+
 /// Sync the 3D scene with the current golf game state.
 #[allow(clippy::too_many_arguments)]
 pub fn sync_golf_scene(
@@ -18,7 +35,9 @@ pub fn sync_golf_scene(
     input: &InputState,
     camera: &Camera,
     renderer: &Renderer,
+    drag_aim: bool,
     role: Option<&NetworkRole>,
+    seats: &[Seat],
 ) {
     let state: Option<breakpoint_golf::GolfState> = read_game_state(active);
     let Some(state) = state else {
@@ -34,21 +53,58 @@ pub fn sync_golf_scene(
         return;
     };
 
+    // Night holes only show what the balls' lanterns light
+    let lanterns: Vec<(Vec2, f32)> = state
+        .lanterns
+        .iter()
+        .filter_map(|(id, &radius)| {
+            let ball = state.balls.get(id)?;
+            Some((Vec2::new(ball.position.x, ball.position.z), radius))
+        })
+        .collect();
+    let night = state.night;
+    let ground_dim = if night { NIGHT_DIM } else { 1.0 };
+
     // Ground plane
     let ground_w = course.width;
     let ground_d = course.depth;
     scene.add(
         MeshType::Plane,
         MaterialType::Gradient {
-            start: rgb_vec4(&theme.golf.ground_color),
-            end: rgb_vec4(&theme.golf.dirt_color),
+            start: dim(rgb_vec4(&theme.golf.ground_color), ground_dim),
+            end: dim(rgb_vec4(&theme.golf.dirt_color), ground_dim),
         },
         Transform::from_xyz(ground_w / 2.0, 0.0, ground_d / 2.0)
             .with_scale(Vec3::new(ground_w, 1.0, ground_d)),
     );
 
+    // Lantern light pools
+    for &(center, radius) in &lanterns {
+        scene.add(
+            MeshType::Cylinder { segments: 24 },
+            MaterialType::Glow {
+                color: LANTERN_COLOR,
+                intensity: 1.0,
+            },
+            Transform::from_xyz(center.x, 0.02, center.y).with_scale(Vec3::new(
+                radius * 2.0,
+                0.02,
+                radius * 2.0,
+            )),
+        );
+    }
+
     // Walls
     for wall in &course.walls {
+        if night
+            && !segment_lit(
+                Vec2::new(wall.a.x, wall.a.z),
+                Vec2::new(wall.b.x, wall.b.z),
+                &lanterns,
+            )
+        {
+            continue;
+        }
         let ax = wall.a.x;
         let az = wall.a.z;
         let bx = wall.b.x;
@@ -72,6 +128,10 @@ pub fn sync_golf_scene(
 
     // Bumpers
     for bumper in &course.bumpers {
+        let center = Vec2::new(bumper.position.x, bumper.position.z);
+        if night && !point_lit(center, bumper.radius, &lanterns) {
+            continue;
+        }
         scene.add(
             MeshType::Cylinder { segments: 16 },
             MaterialType::Unlit {
@@ -85,37 +145,95 @@ pub fn sync_golf_scene(
         );
     }
 
-    // Hole
-    scene.add(
-        MeshType::Plane,
-        MaterialType::Ripple {
-            color: Vec4::new(0.03, 0.03, 0.03, 1.0),
-            ring_count: 8.0,
-            speed: 2.0,
-        },
-        Transform::from_xyz(course.hole_position.x, 0.01, course.hole_position.z)
-            .with_scale(Vec3::splat(1.0)),
-    );
+    // Gravity wells, from the round's course data when it came with some
+    // (custom courses the client doesn't have); rings flow inward over
+    // attractors and outward over repulsors
+    let preview = active
+        .game
+        .as_any()
+        .downcast_ref::<breakpoint_golf::MiniGolf>()
+        .and_then(|golf| golf.preview());
+    let wells = match preview {
+        Some(preview) if preview.course_index == state.course_index => &preview.gravity_wells,
+        _ => &course.gravity_wells,
+    };
+    for well in wells {
+        let center = Vec2::new(well.position.x, well.position.z);
+        if night && !point_lit(center, well.radius, &lanterns) {
+            continue;
+        }
+        let (color, speed) = if well.attracts() {
+            (ATTRACTOR_COLOR, -3.0)
+        } else {
+            (REPULSOR_COLOR, 3.0)
+        };
+        scene.add(
+            MeshType::Plane,
+            MaterialType::Ripple {
+                color,
+                ring_count: 12.0,
+                speed,
+            },
+            Transform::from_xyz(well.position.x, 0.005, well.position.z)
+                .with_scale(Vec3::splat(well.radius * 2.0)),
+        );
+    }
+
+    // Hole; at night the cup itself is only seen up close
+    let cup = Vec2::new(course.hole_position.x, course.hole_position.z);
+    if !night || point_lit(cup, 0.5, &lanterns) {
+        scene.add(
+            MeshType::Plane,
+            MaterialType::Ripple {
+                color: Vec4::new(0.03, 0.03, 0.03, 1.0),
+                ring_count: 8.0,
+                speed: 2.0,
+            },
+            Transform::from_xyz(course.hole_position.x, 0.01, course.hole_position.z)
+                .with_scale(Vec3::splat(1.0)),
+        );
+    }
 
-    // Flag
+    // Flag, lit up at night so there is something to aim for
+    let flag_color = rgb_vec4(&theme.golf.flag_color);
     scene.add(
         MeshType::Cylinder { segments: 16 },
-        MaterialType::Unlit {
-            color: rgb_vec4(&theme.golf.flag_color),
+        if night {
+            MaterialType::Glow {
+                color: flag_color,
+                intensity: 2.0,
+            }
+        } else {
+            MaterialType::Unlit { color: flag_color }
         },
         Transform::from_xyz(course.hole_position.x, 0.75, course.hole_position.z)
             .with_scale(Vec3::new(0.05, 1.5, 0.05)),
     );
 
     // Balls — use theme ball color since BallState doesn't have a color field
-    for ball in state.balls.values() {
+    let ghosted = state.ball_interaction.ghosted();
+    for (&pid, ball) in &state.balls {
         if ball.is_sunk {
             continue;
         }
-        let color = rgb_vec4(&theme.golf.ball_color);
+        let mut color = rgb_vec4(&theme.golf.ball_color);
+        // Ghosted holes show other players' balls see-through
+        let own = role.is_some_and(|r| r.local_player_id == pid)
+            || seats.iter().any(|s| s.player_id == pid);
+        if ghosted && !own {
+            color.w *= GHOST_ALPHA;
+        }
         scene.add(
             MeshType::Sphere { segments: 16 },
-            MaterialType::Unlit { color },
+            // Balls glow on night holes
+            if night {
+                MaterialType::Glow {
+                    color,
+                    intensity: 2.5,
+                }
+            } else {
+                MaterialType::Unlit { color }
+            },
             Transform::from_xyz(ball.position.x, ball.position.y.max(0.15), ball.position.z)
                 .with_scale(Vec3::splat(0.3)),
         );
@@ -138,7 +256,59 @@ pub fn sync_golf_scene(
         }
     }
 
-    // Aim indicator: draw dots from local player's ball toward cursor ground position
+    // Blind-shot indicator: chevron of dots along the host's suggested aim
+    if let Some(role) = role
+        && let Some(ball) = state.balls.get(&role.local_player_id)
+        && let Some(hint) = state.shot_hints.get(&role.local_player_id)
+        && !hint.hole_visible
+    {
+        let dir = Vec2::new(hint.aim_angle.cos(), hint.aim_angle.sin());
+        let side = Vec2::new(-dir.y, dir.x);
+        for i in 1..=4 {
+            let along = 0.8 + i as f32 * 0.45;
+            let alpha = 0.9 - i as f32 * 0.15;
+            for offset in [-0.25, 0.0, 0.25] {
+                // Wings trail behind the tip to form an arrowhead
+                let back = if offset == 0.0 { 0.0 } else { 0.2 };
+                let pos = Vec2::new(ball.position.x, ball.position.z)
+                    + dir * (along - back)
+                    + side * offset;
+                scene.add(
+                    MeshType::Sphere { segments: 8 },
+                    MaterialType::Glow {
+                        color: BLIND_SHOT_COLOR.with_w(alpha),
+                        intensity: 1.5,
+                    },
+                    Transform::from_xyz(pos.x, 0.2, pos.y).with_scale(Vec3::splat(0.1)),
+                );
+            }
+        }
+    }
+
+    // Caddie suggestion: a ring where the cursor goes to play the stroke
+    if let Some(role) = role
+        && let Some(ball) = state.balls.get(&role.local_player_id)
+        && let Some(hint) = crate::game::golf_input::caddie_hint(active, role.local_player_id)
+    {
+        let dir = Vec2::new(hint.aim_angle.cos(), hint.aim_angle.sin());
+        let target = Vec2::new(ball.position.x, ball.position.z)
+            + dir * hint.power * crate::game::golf_input::FULL_POWER_AIM_DISTANCE;
+        for i in 0..12 {
+            let a = i as f32 / 12.0 * std::f32::consts::TAU;
+            let pos = target + Vec2::new(a.cos(), a.sin()) * 0.5;
+            scene.add(
+                MeshType::Sphere { segments: 8 },
+                MaterialType::Glow {
+                    color: CADDIE_COLOR,
+                    intensity: 1.5,
+                },
+                Transform::from_xyz(pos.x, 0.2, pos.y).with_scale(Vec3::splat(0.08)),
+            );
+        }
+    }
+
+    // Aim indicator: draw dots from local player's ball toward cursor ground
+    // position, or along the pull-back drag in drag-to-aim mode
     if let Some(role) = role
         && let Some(ball) = state.balls.get(&role.local_player_id)
         && !ball.is_sunk
@@ -146,51 +316,120 @@ pub fn sync_golf_scene(
         let vel_sq = ball.velocity.x * ball.velocity.x
             + ball.velocity.y * ball.velocity.y
             + ball.velocity.z * ball.velocity.z;
-        if vel_sq <= 0.01 {
+        let ball_pos = Vec3::new(ball.position.x, 0.15, ball.position.z);
+        let aim = if vel_sq > 0.01 {
+            None
+        } else if drag_aim {
+            input
+                .drag_delta()
+                .filter(|_| input.is_mouse_down(MouseButton::Left))
+                .and_then(|drag| crate::game::golf_input::drag_stroke(drag, camera))
+                .and_then(|stroke| {
+                    let angle = stroke.screen_aim?.world_angle()?;
+                    let dist = stroke.power * crate::game::golf_input::FULL_POWER_AIM_DISTANCE;
+                    Some((angle.cos(), angle.sin(), dist))
+                })
+        } else {
             let (vw, vh) = renderer.viewport_size();
             let viewport = Vec2::new(vw, vh);
-            if let Some(ground_pos) = camera.screen_to_ground(input.cursor_position, viewport) {
-                let ball_pos = Vec3::new(ball.position.x, 0.15, ball.position.z);
-                let dx = ground_pos.x - ball_pos.x;
-                let dz = ground_pos.z - ball_pos.z;
-                let dist = (dx * dx + dz * dz).sqrt();
-                if dist > 0.5 {
-                    let dir_x = dx / dist;
-                    let dir_z = dz / dist;
-                    let aim_color = Vec4::new(
-                        theme.golf.aim_line_color[0],
-                        theme.golf.aim_line_color[1],
-                        theme.golf.aim_line_color[2],
-                        theme.golf.aim_line_color[3],
-                    );
-                    let dot_count = 8;
-                    let max_dist = dist.min(15.0);
-                    let spacing = max_dist / dot_count as f32;
-                    for i in 1..=dot_count {
-                        let t = i as f32 * spacing;
-                        let alpha_fade = 1.0 - (i as f32 / dot_count as f32) * 0.6;
-                        let dot_color = Vec4::new(
-                            aim_color.x,
-                            aim_color.y,
-                            aim_color.z,
-                            aim_color.w * alpha_fade,
-                        );
-                        scene.add(
-                            MeshType::Sphere { segments: 16 },
-                            MaterialType::Glow {
-                                color: dot_color,
-                                intensity: 1.2,
-                            },
-                            Transform::from_xyz(
-                                ball_pos.x + dir_x * t,
-                                0.15,
-                                ball_pos.z + dir_z * t,
-                            )
-                            .with_scale(Vec3::splat(0.12)),
-                        );
-                    }
-                }
+            camera
+                .screen_to_ground(input.cursor_position, viewport)
+                .and_then(|ground_pos| {
+                    let dx = ground_pos.x - ball_pos.x;
+                    let dz = ground_pos.z - ball_pos.z;
+                    let dist = (dx * dx + dz * dz).sqrt();
+                    (dist > 0.5).then(|| (dx / dist, dz / dist, dist))
+                })
+        };
+        if let Some((dir_x, dir_z, dist)) = aim {
+            let aim_color = Vec4::new(
+                theme.golf.aim_line_color[0],
+                theme.golf.aim_line_color[1],
+                theme.golf.aim_line_color[2],
+                theme.golf.aim_line_color[3],
+            );
+            let dot_count = 8;
+            let max_dist = dist.min(15.0);
+            let spacing = max_dist / dot_count as f32;
+            for i in 1..=dot_count {
+                let t = i as f32 * spacing;
+                let alpha_fade = 1.0 - (i as f32 / dot_count as f32) * 0.6;
+                let dot_color = Vec4::new(
+                    aim_color.x,
+                    aim_color.y,
+                    aim_color.z,
+                    aim_color.w * alpha_fade,
+                );
+                scene.add(
+                    MeshType::Sphere { segments: 16 },
+                    MaterialType::Glow {
+                        color: dot_color,
+                        intensity: 1.2,
+                    },
+                    Transform::from_xyz(ball_pos.x + dir_x * t, 0.15, ball_pos.z + dir_z * t)
+                        .with_scale(Vec3::splat(0.12)),
+                );
             }
         }
     }
+
+    // Hot-seat guests aim with keys: dots along their aim, longer as the
+    // swing charges
+    for seat in seats {
+        let Some(ball) = state.balls.get(&seat.player_id) else {
+            continue;
+        };
+        let vel_sq = ball.velocity.x * ball.velocity.x
+            + ball.velocity.y * ball.velocity.y
+            + ball.velocity.z * ball.velocity.z;
+        if ball.is_sunk || vel_sq > 0.01 {
+            continue;
+        }
+        let color = theme.golf.aim_line_color;
+        let dir = Vec2::new(seat.aim.cos(), seat.aim.sin());
+        let length = 1.5 + seat.charge * 13.5;
+        let dot_count = 8;
+        for i in 1..=dot_count {
+            let t = i as f32 * length / dot_count as f32;
+            let alpha_fade = 1.0 - (i as f32 / dot_count as f32) * 0.6;
+            scene.add(
+                MeshType::Sphere { segments: 16 },
+                MaterialType::Glow {
+                    color: Vec4::new(color[0], color[1], color[2], color[3] * alpha_fade),
+                    intensity: 1.2,
+                },
+                Transform::from_xyz(
+                    ball.position.x + dir.x * t,
+                    0.15,
+                    ball.position.z + dir.y * t,
+                )
+                .with_scale(Vec3::splat(0.12)),
+            );
+        }
+    }
+}
+
+fn dim(color: Vec4, factor: f32) -> Vec4 {
+    (color.truncate() * factor).extend(color.w)
+}
+
+/// Whether any lantern reaches within `radius` of `point`.
+fn point_lit(point: Vec2, radius: f32, lanterns: &[(Vec2, f32)]) -> bool {
+    lanterns
+        .iter()
+        .any(|&(center, reach)| center.distance(point) <= reach + radius)
+}
+
+/// Whether any lantern reaches some part of the segment `a`-`b`.
+fn segment_lit(a: Vec2, b: Vec2, lanterns: &[(Vec2, f32)]) -> bool {
+    let ab = b - a;
+    let len_sq = ab.length_squared();
+    lanterns.iter().any(|&(center, reach)| {
+        let t = if len_sq > 0.0 {
+            ((center - a).dot(ab) / len_sq).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        center.distance(a + ab * t) <= reach
+    })
 }
@@ -4,7 +4,8 @@ use breakpoint_lasertag::LaserTagInput;
 
 use crate::app::{ActiveGame, NetworkRole};
 use crate::camera_gl::Camera;
-use crate::game::{read_game_state, send_player_input};
+use crate::game::{read_game_state, send_input_as, send_player_input};
+use crate::hot_seat::Seat;
 use crate::input::{InputState, MouseButton};
 use crate::net_client::WsClient;
 use crate::renderer::Renderer;
@@ -62,3 +63,26 @@ pub fn process_lasertag_input(
     };
     send_player_input(&lt_input, active, role, ws);
 }
+
+/// Process a hot-seat guest's laser tag input. Guests have no mouse, so
+/// they aim where they last moved; action fires, alt uses the power-up.
+pub fn process_lasertag_seat_input(
+    seat: &mut Seat,
+    input: &InputState,
+    active: &mut ActiveGame,
+    ws: &WsClient,
+) {
+    let move_x = seat.axis(input, seat.keys.left, seat.keys.right);
+    let move_z = seat.axis(input, seat.keys.down, seat.keys.up);
+    if move_x != 0.0 || move_z != 0.0 {
+        seat.aim = move_z.atan2(move_x);
+    }
+    let lt_input = LaserTagInput {
+        move_x,
+        move_z,
+        aim_angle: seat.aim,
+        fire: input.is_key_just_pressed(seat.keys.action),
+        use_powerup: input.is_key_just_pressed(seat.keys.alt),
+    };
+    send_input_as(seat.player_id, &lt_input, active, ws);
+}
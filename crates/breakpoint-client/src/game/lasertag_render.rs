@@ -1,12 +1,21 @@
+use breakpoint_core::palette;
+use breakpoint_core::player::Player;
+use breakpoint_lasertag::trail::TRAIL_LIFETIME;
 use glam::{Vec3, Vec4};
 
 use crate::app::ActiveGame;
-use crate::game::read_game_state;
+use crate::game::{add_spectator_marker, read_game_state};
 use crate::scene::{MaterialType, MeshType, Scene, Transform};
-use crate::theme::{Theme, rgb_vec4};
+use crate::theme::{Theme, player_color_to_vec4, rgb_vec4};
 
 /// Sync the 3D scene with the current laser tag game state.
-pub fn sync_lasertag_scene(scene: &mut Scene, active: &ActiveGame, theme: &Theme, _dt: f32) {
+pub fn sync_lasertag_scene(
+    scene: &mut Scene,
+    active: &ActiveGame,
+    theme: &Theme,
+    _dt: f32,
+    players: &[Player],
+) {
     let state: Option<breakpoint_lasertag::LaserTagState> = read_game_state(active);
     let Some(state) = state else {
         return;
@@ -27,9 +36,10 @@ pub fn sync_lasertag_scene(scene: &mut Scene, active: &ActiveGame, theme: &Theme
             .with_scale(Vec3::new(arena_w, 1.0, arena_d)),
     );
 
-    // Arena walls
+    // Arena walls, and the moving ones where the round timer puts them
     let wall_height = 2.0;
-    for wall in &state.arena_walls {
+    let moving_walls = state.moving_walls.iter().map(|w| w.at(state.round_timer));
+    for wall in state.arena_walls.iter().cloned().chain(moving_walls) {
         let dx = wall.bx - wall.ax;
         let dz = wall.bz - wall.az;
         let len = (dx * dx + dz * dz).sqrt();
@@ -96,6 +106,9 @@ pub fn sync_lasertag_scene(scene: &mut Scene, active: &ActiveGame, theme: &Theme
             breakpoint_lasertag::powerups::LaserPowerUpKind::WideBeam => {
                 Vec4::new(0.2, 0.9, 0.3, 1.0)
             },
+            breakpoint_lasertag::powerups::LaserPowerUpKind::Turret => {
+                Vec4::new(1.0, 0.5, 0.1, 1.0)
+            },
         };
         scene.add(
             MeshType::Sphere { segments: 8 },
@@ -107,11 +120,42 @@ pub fn sync_lasertag_scene(scene: &mut Scene, active: &ActiveGame, theme: &Theme
         );
     }
 
-    // Players as cylinders
-    for player in state.players.values() {
+    // Deployed turrets: a squat base with a barrel along the current aim
+    for turret in &state.turrets {
+        scene.add(
+            MeshType::Cylinder { segments: 8 },
+            MaterialType::Unlit {
+                color: Vec4::new(0.35, 0.35, 0.4, 1.0),
+            },
+            Transform::from_xyz(turret.x, 0.3, turret.z).with_scale(Vec3::new(0.8, 0.6, 0.8)),
+        );
+        let (bx, bz) = (
+            turret.x + turret.angle.cos() * 0.5,
+            turret.z + turret.angle.sin() * 0.5,
+        );
+        scene.add(
+            MeshType::Cuboid,
+            MaterialType::Glow {
+                color: Vec4::new(1.0, 0.5, 0.1, 1.0),
+                intensity: 1.5,
+            },
+            Transform::from_xyz(bx, 0.7, bz)
+                .with_scale(Vec3::new(0.8, 0.15, 0.15))
+                .with_rotation(glam::Quat::from_rotation_y(-turret.angle)),
+        );
+    }
+
+    // Players as cylinders, in their room colors or, in team modes, a
+    // shade of their team's hue
+    let team_colors = palette::team_colors(&state.teams);
+    for (pid, player) in &state.players {
+        let base = team_colors
+            .get(pid)
+            .or_else(|| players.iter().find(|p| p.id == *pid).map(|p| &p.color))
+            .map_or(Vec4::new(0.3, 0.7, 0.9, 1.0), player_color_to_vec4);
         // Stunned players rendered dimmer
         let alpha = if player.is_stunned() { 0.4 } else { 1.0 };
-        let color = Vec4::new(0.3, 0.7, 0.9, alpha);
+        let color = base.with_w(alpha);
         scene.add(
             MeshType::Cylinder { segments: 12 },
             MaterialType::Unlit { color },
@@ -129,14 +173,22 @@ pub fn sync_lasertag_scene(scene: &mut Scene, active: &ActiveGame, theme: &Theme
                 Transform::from_xyz(player.x, 0.75, player.z).with_scale(Vec3::splat(2.0)),
             );
         }
-    }
 
-    // Laser trails
-    for trail in &state.laser_trails {
-        if trail.age > 0.3 {
-            continue;
+        if let Some(ref cues) = state.spectator {
+            add_spectator_marker(scene, cues, *pid, Vec3::new(player.x, 0.75, player.z), 0.5);
         }
-        let alpha = 1.0 - trail.age / 0.3;
+    }
+
+    // Laser trails, from shots sent once as effects
+    let Some(game) = active
+        .game
+        .as_any()
+        .downcast_ref::<breakpoint_lasertag::LaserTagArena>()
+    else {
+        return;
+    };
+    for trail in game.laser_trails() {
+        let alpha = 1.0 - trail.age / TRAIL_LIFETIME;
         let color = Vec4::new(1.0, 0.2, 0.2, alpha);
 
         for &(start_x, start_z, end_x, end_z) in &trail.segments {
@@ -17,9 +17,12 @@ pub mod tron_render;
 
 use std::collections::HashMap;
 
-use breakpoint_core::game_trait::{BreakpointGame, GameId};
+use breakpoint_core::game_settings::{CustomSettings, FromGameConfig, SettingsError};
+use breakpoint_core::game_trait::{BreakpointGame, GameId, PlayerId};
 use breakpoint_core::net::messages::PlayerInputMsg;
 use breakpoint_core::net::protocol::encode_client_message;
+#[cfg(any(feature = "lasertag", feature = "tron"))]
+use breakpoint_core::spectator::{HighlightKind, SpectatorCues};
 
 use crate::app::ActiveGame;
 use crate::app::NetworkRole;
@@ -28,10 +31,14 @@ use crate::net_client::WsClient;
 /// Factory function type: creates a new game instance.
 type GameFactory = fn() -> Box<dyn BreakpointGame>;
 
+/// Checks a game's lobby settings without creating the game.
+type SettingsValidator = fn(&CustomSettings) -> Result<(), SettingsError>;
+
 /// Registry mapping game IDs to factory functions.
 #[derive(Default)]
 pub struct GameRegistry {
     factories: HashMap<GameId, GameFactory>,
+    validators: HashMap<GameId, SettingsValidator>,
 }
 
 impl GameRegistry {
@@ -39,29 +46,69 @@ impl GameRegistry {
         self.factories.insert(game_id, factory);
     }
 
+    pub fn register_validator(&mut self, game_id: GameId, validator: SettingsValidator) {
+        self.validators.insert(game_id, validator);
+    }
+
     pub fn create(&self, game_id: GameId) -> Option<Box<dyn BreakpointGame>> {
         self.factories.get(&game_id).map(|f| f())
     }
+
+    /// Check imported lobby settings for `game_id`, as the server will
+    /// when the game starts. Games without a validator accept anything.
+    pub fn validate_settings(
+        &self,
+        game_id: GameId,
+        custom: &CustomSettings,
+    ) -> Result<(), SettingsError> {
+        self.validators
+            .get(&game_id)
+            .map_or(Ok(()), |validate| validate(custom))
+    }
 }
 
 /// Create a fully populated game registry.
 pub fn create_registry() -> GameRegistry {
     let mut registry = GameRegistry::default();
     #[cfg(feature = "golf")]
-    registry.register(GameId::Golf, || Box::new(breakpoint_golf::MiniGolf::new()));
+    {
+        registry.register(GameId::Golf, || Box::new(breakpoint_golf::MiniGolf::new()));
+        registry.register_validator(
+            GameId::Golf,
+            breakpoint_golf::settings::GolfSettings::validate,
+        );
+    }
     #[cfg(feature = "platformer")]
-    registry.register(GameId::Platformer, || {
-        Box::new(breakpoint_platformer::PlatformRacer::new())
-    });
+    {
+        registry.register(GameId::Platformer, || {
+            Box::new(breakpoint_platformer::PlatformRacer::new())
+        });
+        registry.register_validator(
+            GameId::Platformer,
+            breakpoint_platformer::settings::PlatformerSettings::validate,
+        );
+    }
     #[cfg(feature = "lasertag")]
-    registry.register(GameId::LaserTag, || {
-        Box::new(breakpoint_lasertag::LaserTagArena::new())
-    });
+    {
+        registry.register(GameId::LaserTag, || {
+            Box::new(breakpoint_lasertag::LaserTagArena::new())
+        });
+        registry.register_validator(
+            GameId::LaserTag,
+            breakpoint_lasertag::settings::LaserTagSettings::validate,
+        );
+    }
     #[cfg(feature = "tron")]
-    registry.register(
-        GameId::Tron,
-        || Box::new(breakpoint_tron::TronCycles::new()),
-    );
+    {
+        registry.register(
+            GameId::Tron,
+            || Box::new(breakpoint_tron::TronCycles::new()),
+        );
+        registry.register_validator(
+            GameId::Tron,
+            breakpoint_tron::settings::TronSettings::validate,
+        );
+    }
     registry
 }
 
@@ -71,10 +118,21 @@ pub fn send_player_input(
     active_game: &mut ActiveGame,
     network_role: &NetworkRole,
     ws_client: &WsClient,
+) {
+    send_input_as(network_role.local_player_id, input, active_game, ws_client);
+}
+
+/// Send input on behalf of `player_id`: the local player or one of its
+/// hot-seat guests.
+pub fn send_input_as(
+    player_id: PlayerId,
+    input: &impl serde::Serialize,
+    active_game: &mut ActiveGame,
+    ws_client: &WsClient,
 ) {
     if let Ok(data) = rmp_serde::to_vec(input) {
         let msg = breakpoint_core::net::messages::ClientMessage::PlayerInput(PlayerInputMsg {
-            player_id: network_role.local_player_id,
+            player_id,
             tick: active_game.tick,
             input_data: data,
         });
@@ -84,6 +142,50 @@ pub fn send_player_input(
     }
 }
 
+/// Mark a player for spectators: a glowing disc under the player the
+/// cues focus on, and a beacon above the leader (gold) or a player on a
+/// streak (orange). `size` is about the player's width in world units.
+#[cfg(any(feature = "lasertag", feature = "tron"))]
+pub fn add_spectator_marker(
+    scene: &mut crate::scene::Scene,
+    cues: &SpectatorCues,
+    player_id: PlayerId,
+    pos: glam::Vec3,
+    size: f32,
+) {
+    use glam::{Vec3, Vec4};
+
+    use crate::scene::{MaterialType, MeshType, Transform};
+
+    if cues.focus == Some(player_id) {
+        scene.add(
+            MeshType::Cylinder { segments: 16 },
+            MaterialType::Glow {
+                color: Vec4::new(1.0, 1.0, 1.0, 0.35),
+                intensity: 1.5,
+            },
+            Transform::from_xyz(pos.x, 0.02, pos.z).with_scale(Vec3::new(
+                size * 2.5,
+                0.02,
+                size * 2.5,
+            )),
+        );
+    }
+    let color = match cues.highlight_of(player_id) {
+        Some(HighlightKind::Leader) => Vec4::new(1.0, 0.85, 0.2, 1.0),
+        Some(HighlightKind::Streak { .. }) => Vec4::new(1.0, 0.45, 0.1, 1.0),
+        None => return,
+    };
+    scene.add(
+        MeshType::Sphere { segments: 8 },
+        MaterialType::Glow {
+            color,
+            intensity: 3.0,
+        },
+        Transform::from_xyz(pos.x, pos.y + size * 2.0, pos.z).with_scale(Vec3::splat(size * 0.5)),
+    );
+}
+
 /// Deserialize the current game state from the active game.
 /// Used by non-platformer games (golf, lasertag, tron) which have small states.
 /// Platformer uses zero-copy downcast via `as_any()` instead.
@@ -91,7 +193,42 @@ pub fn read_game_state<S: serde::de::DeserializeOwned>(active_game: &ActiveGame)
     rmp_serde::from_slice(&active_game.game.serialize_state()).ok()
 }
 
-#[cfg(test)]
+/// Ground-plane position of a player in the active game, for the network
+/// diagnostics panel's prediction error.
+#[allow(unused_variables)]
+pub fn player_position(active_game: &ActiveGame, player_id: PlayerId) -> Option<[f32; 2]> {
+    let any = active_game.game.as_any();
+    match active_game.game_id {
+        #[cfg(feature = "golf")]
+        GameId::Golf => {
+            let golf = any.downcast_ref::<breakpoint_golf::MiniGolf>()?;
+            let ball = golf.state().balls.get(&player_id)?;
+            Some([ball.position.x, ball.position.z])
+        },
+        #[cfg(feature = "platformer")]
+        GameId::Platformer => {
+            let racer = any.downcast_ref::<breakpoint_platformer::PlatformRacer>()?;
+            let p = racer.state().players.get(&player_id)?;
+            Some([p.x, p.y])
+        },
+        #[cfg(feature = "lasertag")]
+        GameId::LaserTag => {
+            let arena = any.downcast_ref::<breakpoint_lasertag::LaserTagArena>()?;
+            let p = arena.state().players.get(&player_id)?;
+            Some([p.x, p.z])
+        },
+        #[cfg(feature = "tron")]
+        GameId::Tron => {
+            let tron = any.downcast_ref::<breakpoint_tron::TronCycles>()?;
+            let c = tron.state().players.get(&player_id)?;
+            Some([c.x, c.z])
+        },
+        #[allow(unreachable_patterns)]
+        _ => None,
+    }
+}
+
+#[cfg(all(test, feature = "golf", feature = "platformer"))]
 mod tests {
     use super::*;
 
@@ -115,4 +252,17 @@ mod tests {
         assert!(registry.create(GameId::Golf).is_some());
         assert!(registry.create(GameId::Platformer).is_some());
     }
+
+    #[test]
+    fn imported_settings_are_checked_per_game() {
+        let registry = create_registry();
+        let bad: CustomSettings = [("caddie_hints".to_string(), serde_json::json!(99))].into();
+        let err = registry.validate_settings(GameId::Golf, &bad).unwrap_err();
+        assert_eq!(err.key, "caddie_hints");
+        assert!(
+            registry
+                .validate_settings(GameId::Golf, &CustomSettings::default())
+                .is_ok()
+        );
+    }
 }
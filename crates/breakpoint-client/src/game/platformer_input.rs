@@ -1,7 +1,8 @@
 use breakpoint_platformer::physics::PlatformerInput;
 
 use crate::app::{ActiveGame, NetworkRole};
-use crate::game::send_player_input;
+use crate::game::{send_input_as, send_player_input};
+use crate::hot_seat::Seat;
 use crate::input::InputState;
 use crate::net_client::WsClient;
 
@@ -34,3 +35,20 @@ pub fn process_platformer_input(
     };
     send_player_input(&plat_input, active, role, ws);
 }
+
+/// Process a hot-seat guest's platformer input: left/right to move, up to
+/// jump, action to attack, alt for the power-up.
+pub fn process_platformer_seat_input(
+    seat: &Seat,
+    input: &InputState,
+    active: &mut ActiveGame,
+    ws: &WsClient,
+) {
+    let plat_input = PlatformerInput {
+        move_dir: seat.axis(input, seat.keys.left, seat.keys.right),
+        jump: input.is_key_down(seat.keys.up),
+        use_powerup: input.is_key_just_pressed(seat.keys.alt),
+        attack: input.is_key_just_pressed(seat.keys.action),
+    };
+    send_input_as(seat.player_id, &plat_input, active, ws);
+}
@@ -33,9 +33,9 @@ const Z_WATER: f32 = -0.8;
 const Z_SHADOWS: f32 = -0.5;
 const Z_ENEMIES: f32 = 0.0;
 const Z_PLAYERS: f32 = 0.1;
+const Z_HAZARD: f32 = 0.3;
 const Z_EFFECTS: f32 = 0.5;
-/// Fog layer Z (used by weather system).
-pub const Z_FOG: f32 = 1.0;
+// Fog layer: crate::weather::Z_FOG (1.0)
 const Z_HUD: f32 = 2.0;
 
 /// Per-player visual state for squash/stretch animation.
@@ -467,20 +467,36 @@ fn stone_brick_region(
     sheet.get_or_default(name)
 }
 
-/// Map power-up kind to sprite name.
-fn powerup_sprite_name(kind: &breakpoint_platformer::powerups::PowerUpKind) -> &'static str {
+/// Map power-up kind to sprite name. Bananas have no sprite and are drawn
+/// by [`add_banana`].
+fn powerup_sprite_name(
+    kind: &breakpoint_platformer::powerups::PowerUpKind,
+) -> Option<&'static str> {
     use breakpoint_platformer::powerups::PowerUpKind;
     match kind {
-        PowerUpKind::HolyWater => "powerup_holy_water",
-        PowerUpKind::Crucifix => "powerup_crucifix",
-        PowerUpKind::SpeedBoots => "powerup_speed_boots",
-        PowerUpKind::DoubleJump => "powerup_double_jump",
-        PowerUpKind::ArmorUp => "powerup_armor",
-        PowerUpKind::Invincibility => "powerup_invincibility",
-        PowerUpKind::WhipExtend => "powerup_whip_extend",
+        PowerUpKind::HolyWater => Some("powerup_holy_water"),
+        PowerUpKind::Crucifix => Some("powerup_crucifix"),
+        PowerUpKind::SpeedBoots => Some("powerup_speed_boots"),
+        PowerUpKind::DoubleJump => Some("powerup_double_jump"),
+        PowerUpKind::ArmorUp => Some("powerup_armor"),
+        PowerUpKind::Invincibility => Some("powerup_invincibility"),
+        PowerUpKind::WhipExtend => Some("powerup_whip_extend"),
+        PowerUpKind::Banana => None,
     }
 }
 
+/// A banana as a glowing yellow blob, for pickups and dropped traps.
+fn add_banana(scene: &mut Scene, x: f32, y: f32, size: f32, intensity: f32) {
+    scene.add(
+        MeshType::Quad,
+        MaterialType::Glow {
+            color: Vec4::new(1.0, 0.88, 0.2, 0.9),
+            intensity,
+        },
+        Transform::from_xyz(x, y, Z_ENEMIES).with_scale(Vec3::new(size, size * 0.5, 1.0)),
+    );
+}
+
 /// Sync the scene with the current platformer game state using flat sprites.
 pub fn sync_platformer_scene(
     scene: &mut Scene,
@@ -591,8 +607,57 @@ pub fn sync_platformer_scene(
     // Render players
     render_players(scene, state, tile_size, white, time, dt);
 
-    // Render uncollected powerups
+    // Render uncollected powerups and dropped traps
     render_powerups(scene, state, tile_size, white);
+    render_traps(scene, state, tile_size, time);
+
+    // Survival hazard over everything it has swallowed
+    render_hazard(
+        scene,
+        state,
+        camera_x,
+        camera_y,
+        visible_half_x,
+        visible_half_y,
+    );
+}
+
+/// Render the survival-mode rising hazard as a lava sheet from the bottom
+/// of the view up to the hazard line. It glows hotter while rising.
+fn render_hazard(
+    scene: &mut Scene,
+    state: &breakpoint_platformer::PlatformerState,
+    camera_x: f32,
+    camera_y: f32,
+    visible_half_x: f32,
+    visible_half_y: f32,
+) {
+    let Some(ref hazard) = state.hazard else {
+        return;
+    };
+    let bottom = camera_y - visible_half_y;
+    let top = hazard.y.min(camera_y + visible_half_y);
+    if top <= bottom {
+        return;
+    }
+    let heat = if hazard.pause_remaining > 0.0 {
+        0.7
+    } else {
+        1.0
+    };
+    scene.add(
+        MeshType::Quad,
+        MaterialType::Water {
+            color: Vec4::new(0.95 * heat, 0.3 * heat, 0.05, 0.85),
+            depth: 0.8,
+            wave_speed: 1.5 + hazard.speed,
+        },
+        Transform::from_xyz(camera_x, (top + bottom) / 2.0, Z_HAZARD).with_scale(Vec3::new(
+            visible_half_x * 2.0,
+            top - bottom,
+            1.0,
+        )),
+    );
 }
 
 /// Per-room tile tint for atmospheric coloring of stone/brick surfaces.
@@ -1104,7 +1169,10 @@ fn render_powerups(
         if pu.collected {
             continue;
         }
-        let sprite_name = powerup_sprite_name(&pu.kind);
+        let Some(sprite_name) = powerup_sprite_name(&pu.kind) else {
+            add_banana(scene, pu.x, pu.y, tile_size * 0.8, 1.5);
+            continue;
+        };
         add_sprite(
             scene,
             sprite_name,
@@ -1117,6 +1185,27 @@ fn render_powerups(
     }
 }
 
+/// Render dropped banana traps at their owner's feet height, pulsing so
+/// they read as live hazards.
+fn render_traps(
+    scene: &mut Scene,
+    state: &breakpoint_platformer::PlatformerState,
+    tile_size: f32,
+    time: f32,
+) {
+    let feet = breakpoint_platformer::physics::PLAYER_HEIGHT / 2.0;
+    for trap in &state.traps {
+        let pulse = 1.0 + 0.4 * (time * 6.0).sin();
+        add_banana(
+            scene,
+            trap.x,
+            trap.y - feet + tile_size * 0.2,
+            tile_size * 0.7,
+            pulse,
+        );
+    }
+}
+
 /// Render god rays for Chapel rooms (stained glass light beams).
 fn render_godrays(
     scene: &mut Scene,
@@ -1,7 +1,8 @@
 use breakpoint_tron::{TronInput, TurnDirection};
 
 use crate::app::{ActiveGame, NetworkRole};
-use crate::game::send_player_input;
+use crate::game::{send_input_as, send_player_input};
+use crate::hot_seat::Seat;
 use crate::input::InputState;
 use crate::net_client::WsClient;
 
@@ -26,3 +27,21 @@ pub fn process_tron_input(
     let tron_input = TronInput { turn, brake };
     send_player_input(&tron_input, active, role, ws);
 }
+
+/// Process a hot-seat guest's tron input: left/right turn, down brakes.
+pub fn process_tron_seat_input(
+    seat: &Seat,
+    input: &InputState,
+    active: &mut ActiveGame,
+    ws: &WsClient,
+) {
+    let turn = if input.is_key_just_pressed(seat.keys.left) {
+        TurnDirection::Left
+    } else if input.is_key_just_pressed(seat.keys.right) {
+        TurnDirection::Right
+    } else {
+        TurnDirection::None
+    };
+    let brake = input.is_key_down(seat.keys.down);
+    send_input_as(seat.player_id, &TronInput { turn, brake }, active, ws);
+}
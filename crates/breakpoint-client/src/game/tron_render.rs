@@ -1,9 +1,12 @@
+use std::collections::HashMap;
+
+use breakpoint_core::player::Player;
 use glam::{Quat, Vec3, Vec4};
 
 use crate::app::ActiveGame;
-use crate::game::read_game_state;
+use crate::game::{add_spectator_marker, read_game_state};
 use crate::scene::{MaterialType, MeshType, Scene, Transform};
-use crate::theme::Theme;
+use crate::theme::{Theme, player_color_to_vec4};
 
 // --- Armagetron-style color palette ---
 
@@ -19,17 +22,11 @@ const WIN_ZONE_COLOR: Vec4 = Vec4::new(1.0, 0.85, 0.2, 0.7);
 /// Base speed threshold — cycles above this are grinding.
 const BASE_SPEED: f32 = 50.0;
 
-/// Player trail colors — vivid neon on black, inspired by Armagetron.
-const PLAYER_COLORS: [Vec4; 8] = [
-    Vec4::new(0.0, 0.85, 1.0, 1.0), // cyan (classic Tron blue)
-    Vec4::new(1.0, 0.8, 0.0, 1.0),  // gold/yellow (Armagetron default)
-    Vec4::new(0.1, 1.0, 0.2, 1.0),  // neon green
-    Vec4::new(1.0, 0.0, 0.6, 1.0),  // hot pink / magenta
-    Vec4::new(0.6, 0.3, 1.0, 1.0),  // electric purple
-    Vec4::new(1.0, 0.35, 0.0, 1.0), // bright orange
-    Vec4::new(0.0, 1.0, 0.7, 1.0),  // aquamarine
-    Vec4::new(1.0, 0.1, 0.1, 1.0),  // red
-];
+/// Danger map cells two riders can reach; more riders stack the alpha.
+const DANGER_COLOR: Vec4 = Vec4::new(1.0, 0.15, 0.1, 0.12);
+
+/// Trail color for a cycle whose owner is no longer in the room.
+const DEPARTED_COLOR: Vec4 = Vec4::new(0.7, 0.7, 0.7, 1.0);
 
 /// Sync the 3D scene with the current tron game state.
 pub fn sync_tron_scene(
@@ -38,6 +35,7 @@ pub fn sync_tron_scene(
     _theme: &Theme,
     _dt: f32,
     local_player_id: Option<u64>,
+    players: &[Player],
 ) {
     let state: Option<breakpoint_tron::TronState> = read_game_state(active);
     let Some(state) = state else {
@@ -94,6 +92,33 @@ pub fn sync_tron_scene(
         z += grid_spacing;
     }
 
+    // Likely collision zones. Only spectators are sent a danger map; the
+    // riding check keeps a local host's own player from seeing theirs.
+    let riding = local_player_id.is_some_and(|id| state.players.contains_key(&id));
+    if let Some(map) = state.danger_map.as_ref().filter(|_| !riding) {
+        for (i, &mask) in map.cells.iter().enumerate() {
+            let riders = mask.count_ones();
+            if riders < 2 {
+                continue;
+            }
+            let col = (i % map.cols as usize) as f32;
+            let row = (i / map.cols as usize) as f32;
+            let alpha = (DANGER_COLOR.w * (riders - 1) as f32).min(0.5);
+            scene.add(
+                MeshType::Plane,
+                MaterialType::Unlit {
+                    color: Vec4::new(DANGER_COLOR.x, DANGER_COLOR.y, DANGER_COLOR.z, alpha),
+                },
+                Transform::from_xyz(
+                    (col + 0.5) * map.cell_width,
+                    0.01,
+                    (row + 0.5) * map.cell_depth,
+                )
+                .with_scale(Vec3::new(map.cell_width, 1.0, map.cell_depth)),
+            );
+        }
+    }
+
     // Arena boundary walls — Glow shader for broad GPU compatibility
     let bwall_height = 8.0;
     let bwall_thickness = 0.5;
@@ -207,11 +232,13 @@ pub fn sync_tron_scene(
             .with_scale(Vec3::new(flame_depth, flame_height, arena_d)),
     );
 
-    // Build a player index for color mapping
-    let mut player_index: std::collections::HashMap<u64, usize> = std::collections::HashMap::new();
-    for (i, (&pid, _)) in state.players.iter().enumerate() {
-        player_index.insert(pid, i);
-    }
+    // Trails wear the room palette colors, which the server keeps distinct
+    // for colorblind players too.
+    let player_colors: HashMap<u64, Vec4> = players
+        .iter()
+        .map(|p| (p.id, player_color_to_vec4(&p.color)))
+        .collect();
+    let color_of = |pid: u64| player_colors.get(&pid).copied().unwrap_or(DEPARTED_COLOR);
 
     // Wall trail segments — TronWall shader (dim body + bright top edge).
     // Own walls: short, high intensity. Enemy walls: tall, dimmer.
@@ -235,9 +262,7 @@ pub fn sync_tron_scene(
         let cx = (wall.x1 + wall.x2) / 2.0;
         let cz = (wall.z1 + wall.z2) / 2.0;
 
-        let color_idx =
-            player_index.get(&wall.owner_id).copied().unwrap_or(0) % PLAYER_COLORS.len();
-        let color = PLAYER_COLORS[color_idx];
+        let color = color_of(wall.owner_id);
 
         let is_own = local_player_id == Some(wall.owner_id);
 
@@ -266,8 +291,7 @@ pub fn sync_tron_scene(
     // Crash explosion — glow sphere at dead cycle positions
     for (&pid, cycle) in &state.players {
         if !cycle.alive {
-            let color_idx = player_index.get(&pid).copied().unwrap_or(0) % PLAYER_COLORS.len();
-            let color = PLAYER_COLORS[color_idx];
+            let color = color_of(pid);
             scene.add(
                 MeshType::Sphere { segments: 12 },
                 MaterialType::Glow {
@@ -284,8 +308,7 @@ pub fn sync_tron_scene(
         if !cycle.alive {
             continue;
         }
-        let color_idx = player_index.get(&pid).copied().unwrap_or(0) % PLAYER_COLORS.len();
-        let color = PLAYER_COLORS[color_idx];
+        let color = color_of(pid);
 
         // Rotate the cycle body to face the direction of travel
         let rotation = match cycle.direction {
@@ -295,6 +318,10 @@ pub fn sync_tron_scene(
             breakpoint_tron::Direction::West => Quat::from_rotation_y(std::f32::consts::FRAC_PI_2),
         };
 
+        if let Some(ref cues) = state.spectator {
+            add_spectator_marker(scene, cues, pid, Vec3::new(cycle.x, 1.0, cycle.z), 2.0);
+        }
+
         // Elongated cycle body (sleeker, smaller)
         scene.add(
             MeshType::Cuboid,
@@ -0,0 +1,227 @@
+//! Hot-seat play: up to three guests share this client's keyboard, each on
+//! its own key cluster. Guests are room players added with
+//! `AddLocalPlayer`; their inputs go out on this client's connection
+//! tagged with their own player IDs, so games see independent players.
+
+use breakpoint_core::game_trait::PlayerId;
+use breakpoint_core::net::messages::MAX_LOCAL_PLAYERS;
+use breakpoint_core::player::Player;
+
+use crate::input::InputState;
+
+/// One seat's keys, as `KeyboardEvent.code` values.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SeatKeys {
+    pub up: &'static str,
+    pub down: &'static str,
+    pub left: &'static str,
+    pub right: &'static str,
+    /// Jump, fire, swing, or skip the golf flyover.
+    pub action: &'static str,
+    /// Use a held power-up.
+    pub alt: &'static str,
+}
+
+impl SeatKeys {
+    fn all(&self) -> [&'static str; 6] {
+        [
+            self.up,
+            self.down,
+            self.left,
+            self.right,
+            self.action,
+            self.alt,
+        ]
+    }
+}
+
+/// Key clusters for guests, in the order they were added. The first
+/// player keeps WASD, Space, E, F and the mouse.
+pub const SEAT_KEYS: [SeatKeys; MAX_LOCAL_PLAYERS] = [
+    SeatKeys {
+        up: "ArrowUp",
+        down: "ArrowDown",
+        left: "ArrowLeft",
+        right: "ArrowRight",
+        action: "Enter",
+        alt: "ShiftRight",
+    },
+    SeatKeys {
+        up: "KeyI",
+        down: "KeyK",
+        left: "KeyJ",
+        right: "KeyL",
+        action: "KeyO",
+        alt: "KeyU",
+    },
+    SeatKeys {
+        up: "Numpad8",
+        down: "Numpad5",
+        left: "Numpad4",
+        right: "Numpad6",
+        action: "Numpad0",
+        alt: "NumpadDecimal",
+    },
+];
+
+/// A guest's place at the keyboard, plus the aim it steers with keys in
+/// games the first player aims with the mouse.
+#[derive(Debug)]
+pub struct Seat {
+    pub player_id: PlayerId,
+    pub keys: &'static SeatKeys,
+    /// Aim angle in radians (0 = +X).
+    pub aim: f32,
+    /// Golf swing power built up while `action` is held, 0 to 1.
+    pub charge: f32,
+}
+
+impl Seat {
+    /// -1, 0, or 1 from a pair of opposing keys.
+    pub fn axis(&self, input: &InputState, negative: &str, positive: &str) -> f32 {
+        let mut v = 0.0;
+        if input.is_key_down(positive) {
+            v += 1.0;
+        }
+        if input.is_key_down(negative) {
+            v -= 1.0;
+        }
+        v
+    }
+}
+
+/// The guests sharing this client, kept in step with the room's players.
+#[derive(Debug, Default)]
+pub struct HotSeat {
+    seats: Vec<Seat>,
+}
+
+impl HotSeat {
+    /// Match seats to the guests of `local_id` in `players`. Guests keep
+    /// their seat (and aim) while they stay; new ones take the first free
+    /// key cluster.
+    pub fn sync(&mut self, players: &[Player], local_id: Option<PlayerId>) {
+        let guests: Vec<PlayerId> = players
+            .iter()
+            .filter(|p| local_id.is_some() && p.hot_seat_of == local_id)
+            .map(|p| p.id)
+            .collect();
+        self.seats.retain(|s| guests.contains(&s.player_id));
+        for id in guests {
+            if self.seats.iter().any(|s| s.player_id == id) {
+                continue;
+            }
+            let Some(keys) = SEAT_KEYS
+                .iter()
+                .find(|k| !self.seats.iter().any(|s| std::ptr::eq(s.keys, *k)))
+            else {
+                break;
+            };
+            self.seats.push(Seat {
+                player_id: id,
+                keys,
+                aim: 0.0,
+                charge: 0.0,
+            });
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seats.is_empty()
+    }
+
+    pub fn seats(&self) -> &[Seat] {
+        &self.seats
+    }
+
+    pub fn seats_mut(&mut self) -> &mut [Seat] {
+        &mut self.seats
+    }
+
+    /// Number (1-based, counting the first player) of a guest's seat, for
+    /// the lobby's key hints.
+    pub fn seat_number(&self, player_id: PlayerId) -> Option<usize> {
+        let index = SEAT_KEYS.iter().position(|k| {
+            self.seats
+                .iter()
+                .any(|s| s.player_id == player_id && std::ptr::eq(s.keys, k))
+        })?;
+        Some(index + 2)
+    }
+
+    /// Seat number the next guest will take, if a key cluster is free.
+    pub fn next_seat_number(&self) -> Option<usize> {
+        let index = SEAT_KEYS
+            .iter()
+            .position(|k| !self.seats.iter().any(|s| std::ptr::eq(s.keys, k)))?;
+        Some(index + 2)
+    }
+
+    /// The first player's input with every guest key removed, so the
+    /// arrow-key aliases don't also steer the first player. `None` when
+    /// there are no guests and the input can be used as is.
+    pub fn primary_input(&self, input: &InputState) -> Option<InputState> {
+        if self.seats.is_empty() {
+            return None;
+        }
+        let mut primary = input.clone();
+        for key in self.seats.iter().flat_map(|s| s.keys.all()) {
+            primary.keys_down.remove(key);
+            primary.keys_just_pressed.remove(key);
+            primary.keys_just_released.remove(key);
+        }
+        Some(primary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use breakpoint_core::player::PlayerColor;
+
+    fn make_players(n: usize) -> Vec<Player> {
+        (1..=n as PlayerId)
+            .map(|id| Player {
+                id,
+                display_name: format!("Player{id}"),
+                color: PlayerColor::default(),
+                is_leader: id == 1,
+                is_spectator: false,
+                is_bot: false,
+                bot_difficulty: Default::default(),
+                hot_seat_of: None,
+                external_bot: false,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn seats_follow_guests_and_strip_their_keys() {
+        let mut players = make_players(4);
+        players[1].hot_seat_of = Some(1);
+        players[3].hot_seat_of = Some(1);
+        let mut hot_seat = HotSeat::default();
+        hot_seat.sync(&players, Some(1));
+        assert_eq!(hot_seat.seat_number(2), Some(2));
+        assert_eq!(hot_seat.seat_number(4), Some(3));
+        assert_eq!(hot_seat.seat_number(3), None, "Other players have no seat");
+        assert_eq!(hot_seat.next_seat_number(), Some(4));
+
+        // A guest leaving frees its keys for the next one
+        players.remove(1);
+        players[1].hot_seat_of = Some(1);
+        hot_seat.sync(&players, Some(1));
+        assert_eq!(hot_seat.seat_number(3), Some(2));
+        assert_eq!(hot_seat.seat_number(4), Some(3));
+
+        let mut input = InputState::new();
+        input.on_key_down("ArrowUp".into());
+        input.on_key_down("KeyW".into());
+        let primary = hot_seat.primary_input(&input).unwrap();
+        assert!(primary.is_key_down("KeyW"));
+        assert!(!primary.is_key_down("ArrowUp"));
+
+        hot_seat.sync(&players, None);
+        assert!(hot_seat.primary_input(&input).is_none());
+    }
+}
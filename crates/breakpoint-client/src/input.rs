@@ -11,6 +11,7 @@ pub enum MouseButton {
 }
 
 /// Keyboard/mouse input state, updated each frame from web events.
+#[derive(Clone)]
 pub struct InputState {
     /// Keys currently held down.
     pub keys_down: HashSet<String>,
@@ -26,6 +27,9 @@ pub struct InputState {
     pub mouse_just_released: HashSet<MouseButton>,
     /// Cursor position in CSS pixels relative to canvas.
     pub cursor_position: Vec2,
+    /// Cursor position when the left button went down, until the frame it
+    /// is released. Drag gestures measure from here.
+    pub drag_origin: Option<Vec2>,
 }
 
 impl InputState {
@@ -38,6 +42,7 @@ impl InputState {
             mouse_just_pressed: HashSet::new(),
             mouse_just_released: HashSet::new(),
             cursor_position: Vec2::ZERO,
+            drag_origin: None,
         }
     }
 
@@ -58,6 +63,9 @@ impl InputState {
     pub fn on_mouse_down(&mut self, button: MouseButton) {
         self.mouse_buttons.insert(button);
         self.mouse_just_pressed.insert(button);
+        if button == MouseButton::Left {
+            self.drag_origin = Some(self.cursor_position);
+        }
     }
 
     /// Called on mouse button release.
@@ -96,8 +104,16 @@ impl InputState {
         self.mouse_just_released.contains(&button)
     }
 
+    /// How far the cursor has moved since the left button went down.
+    pub fn drag_delta(&self) -> Option<Vec2> {
+        self.drag_origin.map(|origin| self.cursor_position - origin)
+    }
+
     /// Clear per-frame state. Call at the end of each frame.
     pub fn end_frame(&mut self) {
+        if self.mouse_just_released.contains(&MouseButton::Left) {
+            self.drag_origin = None;
+        }
         self.keys_just_pressed.clear();
         self.keys_just_released.clear();
         self.mouse_just_pressed.clear();
@@ -152,6 +168,20 @@ mod tests {
         assert_eq!(input.cursor_position, Vec2::new(100.0, 200.0));
     }
 
+    #[test]
+    fn drag_measures_from_the_press() {
+        let mut input = InputState::new();
+        assert_eq!(input.drag_delta(), None);
+        input.on_mouse_move(10.0, 10.0);
+        input.on_mouse_down(MouseButton::Left);
+        input.on_mouse_move(40.0, 6.0);
+        input.on_mouse_up(MouseButton::Left);
+        // Still readable in the release frame
+        assert_eq!(input.drag_delta(), Some(Vec2::new(30.0, -4.0)));
+        input.end_frame();
+        assert_eq!(input.drag_delta(), None);
+    }
+
     #[test]
     fn duplicate_key_down_not_just_pressed_twice() {
         let mut input = InputState::new();
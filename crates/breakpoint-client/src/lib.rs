@@ -1,18 +1,27 @@
 pub mod app;
+pub mod assets;
 mod audio;
 mod bridge;
 mod camera_gl;
+pub mod clock_sync;
 mod diag;
 mod effects;
 pub mod game;
+pub mod hot_seat;
 mod input;
+pub mod local_host;
 pub mod net_client;
+pub mod net_diag;
 pub mod overlay;
 pub mod particles;
 mod renderer;
+pub mod replay_viewer;
 mod scene;
 pub mod sprite_atlas;
+#[cfg(feature = "state-diff")]
+pub mod state_inspector;
 mod storage;
+pub mod telemetry;
 pub mod theme;
 pub mod weather;
 
@@ -22,7 +31,7 @@ use wasm_bindgen::prelude::*;
 #[wasm_bindgen(start)]
 pub fn start() {
     #[cfg(target_family = "wasm")]
-    console_error_panic_hook::set_once();
+    telemetry::init();
 
     #[cfg(target_family = "wasm")]
     app::run();
@@ -0,0 +1,1392 @@
+//! Host-authoritative local play. One client runs the room and the game
+//! simulation itself, doing the jobs of the server's room manager and game
+//! loop, so a LAN party can play through a relay with no game server and a
+//! single player can play against bots offline. The host's own UI talks to
+//! it with the normal wire protocol, so the rest of the client works
+//! unchanged.
+
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use breakpoint_core::bot::{BotDifficulty, BotDriver};
+use breakpoint_core::game_trait::{
+    BreakpointGame, GameConfig, GameEvent, GameId, PlayerId, PlayerInputs,
+};
+use breakpoint_core::i18n::TextKey;
+use breakpoint_core::input_replay::{self, InputRecorder, InputReplay};
+use breakpoint_core::lobby_toy::TargetPractice;
+use breakpoint_core::names;
+use breakpoint_core::net::messages::{
+    ClientMessage, ClockSyncReplyMsg, CourseUpdateMsg, GameEffectsMsg, GameEndMsg, GameStartMsg,
+    IntermissionMsg, JoinRoomMsg, JoinRoomResponseMsg, LobbyToyStateMsg, MAX_LOCAL_PLAYERS,
+    PlayerListMsg, PlayerScoreEntry, PrivateStateMsg, RoundEndMsg, RoundPhaseMsg, ServerMessage,
+};
+use breakpoint_core::net::protocol::{
+    PROTOCOL_VERSION, decode_client_message, encode_game_state_fast, encode_server_message,
+    encode_version_mismatch,
+};
+use breakpoint_core::palette;
+use breakpoint_core::player::{Player, PlayerColor};
+use breakpoint_core::room::{RoomConfig, RoomState};
+use breakpoint_core::round_phase::{
+    self, Intermission, RoundPacing, RoundPhase, RoundPhaseMachine,
+};
+use breakpoint_core::scoreboard;
+use breakpoint_core::win_condition::{MatchTracker, WinCondition};
+
+use crate::game::GameRegistry;
+
+/// Peer ID of the hosting player's own UI. Relay peers are numbered from 1.
+pub const LOCAL_PEER: u64 = 0;
+
+/// Room code shown when playing offline, without a relay.
+pub const OFFLINE_ROOM_CODE: &str = "SOLO-0000";
+
+/// Ticks simulated per frame at most, so a stalled tab doesn't spiral.
+const MAX_TICKS_PER_FRAME: u32 = 4;
+
+/// Where a host message goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recipient {
+    All,
+    Peer(u64),
+}
+
+/// A game in progress, as run by the server's game loop.
+struct HostSession {
+    game: Box<dyn BreakpointGame>,
+    game_id: GameId,
+    custom: HashMap<String, serde_json::Value>,
+    /// The room config with micro-round timing applied, if requested.
+    config: RoomConfig,
+    round_count: u8,
+    current_round: u8,
+    tick: u32,
+    tick_accumulator: f32,
+    tracker: MatchTracker,
+    bots: BotDriver,
+    /// Seconds since the match started, for time-capped win conditions.
+    elapsed: f32,
+    input_buffer: HashMap<PlayerId, Vec<u8>>,
+    phase: RoundPhaseMachine,
+    phase_changed: bool,
+    /// The pause between rounds, while one is running.
+    between_rounds: Option<Intermission>,
+    state_buf: Vec<u8>,
+    /// Records the match's inputs when the lobby asked for a replay.
+    recorder: Option<InputRecorder>,
+}
+
+/// A room hosted by this client.
+pub struct LocalHost {
+    room_code: String,
+    /// Vanity alias relay peers may join with instead of the code.
+    room_alias: Option<String>,
+    config: RoomConfig,
+    players: Vec<Player>,
+    leader_id: PlayerId,
+    /// Player for each connected peer.
+    peers: HashMap<u64, PlayerId>,
+    next_player_id: PlayerId,
+    session: Option<HostSession>,
+    /// Target practice for players waiting in the lobby.
+    lobby_toy: TargetPractice,
+    /// How often each player has re-rolled their generated name.
+    name_rolls: HashMap<PlayerId, u32>,
+    /// Round trips peers reported in their clock probes, for the scoreboard.
+    pings: HashMap<PlayerId, u16>,
+    /// The host page's clock (ms), standing in for the server's in
+    /// snapshot timestamps and `ClockSync` replies.
+    clock_ms: f64,
+    outbox: Vec<(Recipient, Vec<u8>)>,
+    /// Inputs of the last match that recorded them.
+    input_replay: Option<InputReplay>,
+}
+
+impl LocalHost {
+    pub fn new(room_code: String) -> Self {
+        Self {
+            room_code,
+            room_alias: None,
+            config: RoomConfig::default(),
+            players: Vec::new(),
+            leader_id: 0,
+            peers: HashMap::new(),
+            next_player_id: 1,
+            session: None,
+            lobby_toy: TargetPractice::new(),
+            name_rolls: HashMap::new(),
+            pings: HashMap::new(),
+            clock_ms: 0.0,
+            outbox: Vec::new(),
+            input_replay: None,
+        }
+    }
+
+    /// The last recorded match, for exporting as a `.bpinputs` file.
+    pub fn input_replay(&self) -> Option<&InputReplay> {
+        self.input_replay.as_ref()
+    }
+
+    pub fn room_code(&self) -> &str {
+        &self.room_code
+    }
+
+    /// Adopt the code the relay assigned.
+    pub fn set_room_code(&mut self, room_code: String) {
+        self.room_code = room_code;
+    }
+
+    /// Accept joins addressed to `alias`, which the relay resolves to this
+    /// room but forwards as typed.
+    pub fn set_room_alias(&mut self, alias: Option<String>) {
+        self.room_alias = alias;
+    }
+
+    /// Set the host's clock to the current frame time.
+    pub fn set_clock(&mut self, now: f64) {
+        self.clock_ms = now;
+    }
+
+    fn is_addressed_to(&self, code: &str) -> bool {
+        code == self.room_code || self.room_alias.as_deref() == Some(&code.to_ascii_uppercase())
+    }
+
+    /// Relay peers with a player in the room.
+    pub fn remote_peers(&self) -> Vec<u64> {
+        self.peers
+            .keys()
+            .copied()
+            .filter(|&peer| peer != LOCAL_PEER)
+            .collect()
+    }
+
+    /// Encoded messages produced since the last call.
+    pub fn take_outbox(&mut self) -> Vec<(Recipient, Vec<u8>)> {
+        std::mem::take(&mut self.outbox)
+    }
+
+    fn room_state(&self) -> RoomState {
+        match self.session {
+            Some(ref s) if s.between_rounds.is_some() => RoomState::BetweenRounds,
+            Some(_) => RoomState::InGame,
+            None => RoomState::Lobby,
+        }
+    }
+
+    fn send(&mut self, to: Recipient, msg: &ServerMessage) {
+        match encode_server_message(msg) {
+            Ok(data) => self.outbox.push((to, data)),
+            Err(e) => crate::diag::console_warn!("Failed to encode {:?}: {e}", msg.message_type()),
+        }
+    }
+
+    fn broadcast_player_list(&mut self) {
+        let msg = ServerMessage::PlayerList(PlayerListMsg {
+            players: self.players.clone(),
+            leader_id: self.leader_id,
+            session_scores: Default::default(),
+            playlist: Vec::new(),
+        });
+        self.send(Recipient::All, &msg);
+    }
+
+    /// Handle a client message from `peer`.
+    pub fn handle_message(&mut self, peer: u64, data: &[u8], registry: &GameRegistry) {
+        let msg = match decode_client_message(data) {
+            Ok(msg) => msg,
+            Err(e) => {
+                crate::diag::console_warn!("Local host dropped message from peer {peer}: {e}");
+                return;
+            },
+        };
+        if let ClientMessage::JoinRoom(join) = msg {
+            self.join(peer, join);
+            return;
+        }
+        let Some(&player_id) = self.peers.get(&peer) else {
+            return;
+        };
+        let is_leader = player_id == self.leader_id;
+        match msg {
+            // Inputs are credited to the sender unless they name one of its
+            // hot-seat guests
+            ClientMessage::PlayerInput(input) => {
+                let target = if self.is_local_player_of(input.player_id, player_id) {
+                    input.player_id
+                } else {
+                    player_id
+                };
+                self.player_input(target, input.input_data);
+            },
+            ClientMessage::AddLocalPlayer(req) => {
+                self.add_local_player(player_id, req.player_name, req.player_color);
+            },
+            ClientMessage::RemoveLocalPlayer(req) => {
+                self.remove_local_player(req.player_id, player_id);
+            },
+            ClientMessage::RequestGameStart(req) if is_leader => {
+                self.start_game(
+                    &req.game_name,
+                    req.custom,
+                    req.win_condition,
+                    req.pacing,
+                    registry,
+                );
+            },
+            ClientMessage::AdvanceRound(req) if is_leader => self.advance_round(req.round),
+            ClientMessage::AddBot(req) if is_leader => self.add_bot(req.difficulty),
+            ClientMessage::RemoveBot(req) if is_leader => self.remove_bot(req.player_id),
+            ClientMessage::SetBotDifficulty(req) if is_leader => {
+                self.set_bot_difficulty(req.player_id, req.difficulty);
+            },
+            #[cfg(feature = "debug-commands")]
+            ClientMessage::DebugCommand(req) if is_leader => self.debug_command(&req.command),
+            ClientMessage::ChangeGameConfig(req) if is_leader => {
+                self.change_game_config(&req.delta)
+            },
+            ClientMessage::LobbyToyInput(req) => self.lobby_toy_input(player_id, &req.input_data),
+            ClientMessage::RerollName(req) => self.reroll_name(player_id, req.player_id),
+            ClientMessage::ClockSync(req) => {
+                if let Some(rtt_ms) = req.rtt_ms {
+                    self.record_ping(player_id, rtt_ms);
+                }
+                let reply = ServerMessage::ClockSyncReply(ClockSyncReplyMsg {
+                    client_time: req.client_time,
+                    server_time_ms: self.clock_ms as u64,
+                });
+                self.send(Recipient::Peer(peer), &reply);
+            },
+            ClientMessage::LeaveRoom(_) => self.peer_left(peer),
+            // Friends, alerts, and join queues need the server
+            _ => {},
+        }
+    }
+
+    fn join(&mut self, peer: u64, join: JoinRoomMsg) {
+        if self.peers.contains_key(&peer) {
+            return;
+        }
+        if join.protocol_version != 0 && join.protocol_version != PROTOCOL_VERSION {
+            match encode_version_mismatch(join.protocol_version) {
+                Ok(data) => self.outbox.push((Recipient::Peer(peer), data)),
+                Err(e) => crate::diag::console_warn!("Failed to encode version mismatch: {e}"),
+            }
+            return;
+        }
+        let error = if peer != LOCAL_PEER && !self.is_addressed_to(&join.room_code) {
+            Some(TextKey::RoomNotFound.into())
+        } else if self.players.len() >= self.config.max_players as usize {
+            Some(TextKey::RoomFull.into())
+        } else {
+            None
+        };
+        let name = join.player_name.trim().to_string();
+        let error = error.or_else(|| {
+            (name.len() > 32 || name.chars().any(char::is_control))
+                .then(|| TextKey::InvalidPlayerName.into())
+        });
+        if let Some(error) = error {
+            let msg = ServerMessage::JoinRoomResponse(JoinRoomResponseMsg {
+                success: false,
+                player_id: None,
+                room_code: None,
+                room_state: None,
+                error: Some(error),
+                session_token: None,
+                lobby_toy: None,
+            });
+            self.send(Recipient::Peer(peer), &msg);
+            return;
+        }
+
+        let player_id = self.next_player_id;
+        self.next_player_id += 1;
+        // The hosting player leads the room for as long as it exists
+        let is_leader = peer == LOCAL_PEER;
+        if is_leader {
+            self.leader_id = player_id;
+        }
+        let player = Player {
+            id: player_id,
+            display_name: self.name_or_generated(player_id, name),
+            color: palette::resolve(join.player_color, &palette::taken_colors(&self.players)),
+            is_leader,
+            is_spectator: self.session.is_some(),
+            is_bot: false,
+            bot_difficulty: Default::default(),
+            hot_seat_of: None,
+            external_bot: false,
+        };
+        if let Some(ref mut session) = self.session {
+            session.player_joined(&player);
+        }
+        self.players.push(player);
+        self.peers.insert(peer, player_id);
+
+        let response = ServerMessage::JoinRoomResponse(JoinRoomResponseMsg {
+            success: true,
+            player_id: Some(player_id),
+            room_code: Some(self.room_code.clone()),
+            room_state: Some(self.room_state()),
+            error: None,
+            session_token: None,
+            lobby_toy: self
+                .session
+                .is_none()
+                .then(|| self.lobby_toy.serialize_state()),
+        });
+        self.send(Recipient::Peer(peer), &response);
+        self.broadcast_player_list();
+    }
+
+    /// Apply a click to the lobby toy, sharing it when the click scored.
+    fn lobby_toy_input(&mut self, player_id: PlayerId, input: &[u8]) {
+        if self.session.is_some() {
+            return;
+        }
+        self.lobby_toy.apply_input(player_id, input);
+        // The toy only reacts to clicks, so it needs no clock
+        let events = self.lobby_toy.update(
+            0.0,
+            &PlayerInputs {
+                inputs: HashMap::new(),
+            },
+        );
+        if !events.is_empty() {
+            let msg = ServerMessage::LobbyToyState(LobbyToyStateMsg {
+                state_data: self.lobby_toy.serialize_state(),
+            });
+            self.send(Recipient::All, &msg);
+        }
+    }
+
+    /// Note a peer's round trip for its player and any hot-seat guests.
+    fn record_ping(&mut self, player_id: PlayerId, rtt_ms: u32) {
+        let rtt_ms = u16::try_from(rtt_ms).unwrap_or(u16::MAX);
+        for p in &self.players {
+            if p.id == player_id || p.hot_seat_of == Some(player_id) {
+                self.pings.insert(p.id, rtt_ms);
+            }
+        }
+    }
+
+    /// Remove the player on a peer that disconnected or left.
+    pub fn peer_left(&mut self, peer: u64) {
+        let Some(player_id) = self.peers.remove(&peer) else {
+            return;
+        };
+        // Hot-seat guests leave with the peer they share
+        let leaving: Vec<PlayerId> = self
+            .players
+            .iter()
+            .filter(|p| p.id == player_id || p.hot_seat_of == Some(player_id))
+            .map(|p| p.id)
+            .collect();
+        self.players.retain(|p| !leaving.contains(&p.id));
+        for &id in &leaving {
+            self.lobby_toy.player_left(id);
+            self.pings.remove(&id);
+        }
+        if let Some(ref mut session) = self.session {
+            for &id in &leaving {
+                session.player_left(id);
+            }
+        }
+        self.broadcast_player_list();
+    }
+
+    fn is_local_player_of(&self, guest_id: PlayerId, owner_id: PlayerId) -> bool {
+        self.players
+            .iter()
+            .any(|p| p.id == guest_id && p.hot_seat_of == Some(owner_id))
+    }
+
+    fn add_local_player(&mut self, owner_id: PlayerId, name: String, color: PlayerColor) {
+        let name = name.trim().to_string();
+        let guests = self
+            .players
+            .iter()
+            .filter(|p| p.hot_seat_of == Some(owner_id))
+            .count();
+        if self.session.is_some()
+            || self.players.len() >= self.config.max_players as usize
+            || guests >= MAX_LOCAL_PLAYERS
+            || name.len() > 32
+            || name.chars().any(char::is_control)
+        {
+            return;
+        }
+        let color = palette::resolve(color, &palette::taken_colors(&self.players));
+        self.players.push(Player {
+            id: self.next_player_id,
+            display_name: self.name_or_generated(self.next_player_id, name),
+            color,
+            is_leader: false,
+            is_spectator: false,
+            is_bot: false,
+            bot_difficulty: Default::default(),
+            hot_seat_of: Some(owner_id),
+            external_bot: false,
+        });
+        self.next_player_id += 1;
+        self.broadcast_player_list();
+    }
+
+    /// `name`, or a generated name no one in the room has when it is blank.
+    fn name_or_generated(&self, player_id: PlayerId, name: String) -> String {
+        if !name.is_empty() {
+            return name;
+        }
+        let roll = self.name_rolls.get(&player_id).copied().unwrap_or(0);
+        names::generate(
+            names::seed(&self.room_code, player_id, roll),
+            &names::taken_names(&self.players),
+        )
+    }
+
+    /// Give `player_id` (the requester or one of its guests) the next
+    /// generated name, in the lobby.
+    fn reroll_name(&mut self, requester: PlayerId, player_id: PlayerId) {
+        if self.session.is_some()
+            || (player_id != requester && !self.is_local_player_of(player_id, requester))
+        {
+            return;
+        }
+        *self.name_rolls.entry(player_id).or_insert(0) += 1;
+        let name = self.name_or_generated(player_id, String::new());
+        if let Some(player) = self.players.iter_mut().find(|p| p.id == player_id) {
+            player.display_name = name;
+            self.broadcast_player_list();
+        }
+    }
+
+    fn remove_local_player(&mut self, guest_id: PlayerId, owner_id: PlayerId) {
+        if !self.is_local_player_of(guest_id, owner_id) {
+            return;
+        }
+        self.players.retain(|p| p.id != guest_id);
+        if let Some(ref mut session) = self.session {
+            session.player_left(guest_id);
+        }
+        self.broadcast_player_list();
+    }
+
+    fn player_input(&mut self, player_id: PlayerId, input_data: Vec<u8>) {
+        let Some(ref mut session) = self.session else {
+            return;
+        };
+        // Inputs outside the playing phase are dropped
+        if session.between_rounds.is_some() || !session.phase.phase().accepts_input() {
+            return;
+        }
+        session.game.apply_input(player_id, &input_data);
+        if let Some(ref mut recorder) = session.recorder {
+            recorder.input(player_id, &input_data);
+        }
+        session.input_buffer.insert(player_id, input_data);
+    }
+
+    /// Apply a leader's debug command. Offline and LAN rooms have no server
+    /// config to consult, so building with the feature is enough.
+    #[cfg(feature = "debug-commands")]
+    fn debug_command(&mut self, command: &breakpoint_core::debug_command::DebugCommand) {
+        let Some(ref mut session) = self.session else {
+            return;
+        };
+        if session.between_rounds.is_some() {
+            return;
+        }
+        if let Err(e) = session.game.apply_debug(command) {
+            crate::diag::console_warn!("Debug command {command:?} failed: {e}");
+        }
+    }
+
+    /// Apply a leader's mid-round settings change to the running round.
+    fn change_game_config(&mut self, delta: &breakpoint_core::game_trait::GameConfigDelta) {
+        let Some(ref mut session) = self.session else {
+            return;
+        };
+        if session.between_rounds.is_some() {
+            return;
+        }
+        match session.game.apply_config_change(delta) {
+            Ok(()) => {
+                if let Some(ref mut recorder) = session.recorder {
+                    recorder.config_changed(delta);
+                }
+            },
+            Err(e) => crate::diag::console_warn!("Settings change failed: {e}"),
+        }
+    }
+
+    fn add_bot(&mut self, difficulty: BotDifficulty) {
+        if self.session.is_some() || self.players.len() >= self.config.max_players as usize {
+            return;
+        }
+        let bot_number = self.players.iter().filter(|p| p.is_bot).count() + 1;
+        let color = palette::most_distinct(&palette::taken_colors(&self.players));
+        self.players.push(Player {
+            id: self.next_player_id,
+            display_name: format!("Bot {bot_number}"),
+            color,
+            is_leader: false,
+            is_spectator: false,
+            is_bot: true,
+            bot_difficulty: difficulty,
+            hot_seat_of: None,
+            external_bot: false,
+        });
+        self.next_player_id += 1;
+        self.broadcast_player_list();
+    }
+
+    fn remove_bot(&mut self, bot_id: PlayerId) {
+        let before = self.players.len();
+        self.players.retain(|p| !(p.id == bot_id && p.is_bot));
+        if self.players.len() != before {
+            self.broadcast_player_list();
+        }
+    }
+
+    fn set_bot_difficulty(&mut self, bot_id: PlayerId, difficulty: BotDifficulty) {
+        if self.session.is_some() {
+            return;
+        }
+        if let Some(bot) = self.players.iter_mut().find(|p| p.id == bot_id && p.is_bot) {
+            bot.bot_difficulty = difficulty;
+            self.broadcast_player_list();
+        }
+    }
+
+    fn start_game(
+        &mut self,
+        game_name: &str,
+        mut custom: HashMap<String, serde_json::Value>,
+        win_condition: Option<WinCondition>,
+        pacing: Option<RoundPacing>,
+        registry: &GameRegistry,
+    ) {
+        if self.session.is_some() {
+            return;
+        }
+        if let Some(win_condition) = win_condition {
+            self.config.win_condition = win_condition;
+        }
+        if let Some(pacing) = pacing {
+            self.config.pacing = pacing.clamped();
+        }
+        let Some(game_id) = GameId::from_str_opt(game_name) else {
+            crate::diag::console_warn!("Local host: unknown game {game_name}");
+            return;
+        };
+        let Some(mut game) = registry.create(game_id) else {
+            crate::diag::console_warn!("Local host: {game_id} is not in this build");
+            return;
+        };
+        let config = match round_phase::micro_round_secs(&custom) {
+            Ok(secs) => self.config.with_micro_rounds(secs),
+            Err(e) => {
+                crate::diag::console_warn!("Local host: {e}");
+                return;
+            },
+        };
+        let phase_timings = config.phase_timings();
+        let round_count = game.round_count_for(&custom);
+        let mut recorder = input_replay::record_replay_requested(&custom)
+            .then(|| InputRecorder::start(game_id, game.tick_rate(), &mut custom));
+        let round_config = GameConfig::builder()
+            .round_count(round_count)
+            .round_duration(config.round_duration)
+            .custom(custom.clone())
+            .build();
+        game.init(&self.players, &round_config);
+        if let Some(ref mut recorder) = recorder {
+            recorder.begin_round(&self.players, &round_config);
+        }
+        self.input_replay = None;
+        self.session = Some(HostSession {
+            game,
+            game_id,
+            custom,
+            config,
+            round_count,
+            current_round: 1,
+            tick: 0,
+            tick_accumulator: 0.0,
+            tracker: MatchTracker::new(self.config.win_condition.clone(), round_count),
+            bots: BotDriver::new(&self.players),
+            elapsed: 0.0,
+            input_buffer: HashMap::new(),
+            phase: RoundPhaseMachine::new(phase_timings),
+            phase_changed: true,
+            between_rounds: None,
+            state_buf: Vec::with_capacity(512),
+            recorder,
+        });
+        self.send_game_start(game_id);
+    }
+
+    fn send_game_start(&mut self, game_id: GameId) {
+        let msg = ServerMessage::GameStart(GameStartMsg {
+            game_name: game_id.to_string(),
+            players: self.players.clone(),
+            leader_id: self.leader_id,
+        });
+        self.send(Recipient::All, &msg);
+    }
+
+    /// Advance the game by `dt` seconds of wall time.
+    pub fn update(&mut self, dt: f32) {
+        let Some(ref mut session) = self.session else {
+            return;
+        };
+        session.elapsed += dt;
+
+        if let Some(ref mut intermission) = session.between_rounds {
+            intermission.tick(dt);
+            if intermission.is_done() {
+                self.next_round();
+            }
+            return;
+        }
+
+        // Each peer's viewer: its player, or none for spectators
+        let views: Vec<(u64, Option<PlayerId>)> = self
+            .peers
+            .iter()
+            .map(|(&peer, &pid)| {
+                let playing = self.players.iter().any(|p| p.id == pid && !p.is_spectator);
+                (peer, playing.then_some(pid))
+            })
+            .collect();
+        // Each active player and the peer carrying their private state:
+        // their own, or their host's for a hot-seat guest
+        let private: Vec<(u64, PlayerId)> = self
+            .players
+            .iter()
+            .filter(|p| !p.is_spectator && !p.is_bot)
+            .filter_map(|p| {
+                let owner = p.hot_seat_of.unwrap_or(p.id);
+                let peer = self.peers.iter().find(|&(_, &pid)| pid == owner)?.0;
+                Some((*peer, p.id))
+            })
+            .collect();
+
+        let tick_dt = 1.0 / session.game.tick_rate();
+        session.tick_accumulator += dt;
+        let mut ticks = 0;
+        let mut round_over = false;
+        while session.tick_accumulator >= tick_dt && ticks < MAX_TICKS_PER_FRAME {
+            session.tick_accumulator -= tick_dt;
+            ticks += 1;
+            let now_ms = self.clock_ms as u64;
+            if session.tick(
+                tick_dt,
+                now_ms,
+                &views,
+                &private,
+                &self.pings,
+                &mut self.outbox,
+            ) {
+                round_over = true;
+                break;
+            }
+        }
+        session.tick_accumulator = session.tick_accumulator.min(tick_dt);
+        if round_over {
+            self.finish_round();
+        }
+    }
+
+    /// Score the round, then end the match or pause before the next round.
+    fn finish_round(&mut self) {
+        let Some(ref mut session) = self.session else {
+            return;
+        };
+        let results = session.game.round_results();
+        let victory = session.game.victory_summary();
+        session.tracker.record_round(&results);
+        session.bots.record_round(&results);
+
+        if session
+            .tracker
+            .is_over(Duration::from_secs_f32(session.elapsed))
+        {
+            let still_playing: HashSet<PlayerId> = results
+                .iter()
+                .filter(|s| !s.disconnected)
+                .map(|s| s.player_id)
+                .collect();
+            let final_scores = session
+                .tracker
+                .totals()
+                .iter()
+                .map(|(&player_id, &score)| PlayerScoreEntry {
+                    player_id,
+                    score,
+                    disconnected: !still_playing.contains(&player_id),
+                })
+                .collect();
+            if let Some(recorder) = session.recorder.take() {
+                self.input_replay = Some(recorder.finish());
+            }
+            self.session = None;
+            let end = ServerMessage::GameEnd(GameEndMsg {
+                final_scores,
+                match_id: None,
+                victory: Some(victory),
+            });
+            self.send(Recipient::All, &end);
+            for p in &mut self.players {
+                p.is_spectator = false;
+            }
+            self.broadcast_player_list();
+            return;
+        }
+
+        let intermission = Intermission::new(session.config.pacing);
+        let between_round_secs = intermission
+            .remaining()
+            .map_or(0, |secs| secs.ceil() as u16);
+        session.between_rounds = Some(intermission);
+        let end = ServerMessage::RoundEnd(RoundEndMsg {
+            round: session.current_round,
+            scores: results
+                .iter()
+                .map(|s| PlayerScoreEntry {
+                    player_id: s.player_id,
+                    score: s.score,
+                    disconnected: s.disconnected,
+                })
+                .collect(),
+            between_round_secs,
+            heatmap: session.game.round_heatmap(),
+            victory: Some(victory),
+        });
+        self.send(Recipient::All, &end);
+        self.send_intermission();
+    }
+
+    /// The leader skips the rest of the intermission after `round`.
+    fn advance_round(&mut self, round: u8) {
+        let Some(ref mut session) = self.session else {
+            return;
+        };
+        if session.current_round != round {
+            return;
+        }
+        if let Some(ref mut intermission) = session.between_rounds
+            && intermission.request_advance()
+        {
+            self.send_intermission();
+        }
+    }
+
+    fn send_intermission(&mut self) {
+        let Some(ref session) = self.session else {
+            return;
+        };
+        let Some(ref intermission) = session.between_rounds else {
+            return;
+        };
+        let msg = ServerMessage::Intermission(IntermissionMsg {
+            round: session.current_round,
+            remaining_secs: intermission.remaining(),
+        });
+        self.send(Recipient::All, &msg);
+    }
+
+    /// Re-init the game for the next round; spectators join in.
+    fn next_round(&mut self) {
+        for p in &mut self.players {
+            p.is_spectator = false;
+        }
+        let Some(ref mut session) = self.session else {
+            return;
+        };
+        session.current_round += 1;
+        session.tick = 0;
+        session.tick_accumulator = 0.0;
+        session.input_buffer.clear();
+        session.between_rounds = None;
+        let round_config = GameConfig::builder()
+            .round_count(session.round_count)
+            .round_duration(session.config.round_duration)
+            .custom(session.custom.clone())
+            .setting("hole_index", session.current_round - 1)
+            .build();
+        session.game.init(&self.players, &round_config);
+        if let Some(ref mut recorder) = session.recorder {
+            recorder.begin_round(&self.players, &round_config);
+        }
+        session.phase.restart();
+        session.phase_changed = true;
+        let game_id = session.game_id;
+        self.send_game_start(game_id);
+    }
+}
+
+impl HostSession {
+    fn player_joined(&mut self, player: &Player) {
+        self.game.player_joined(player);
+        if let Some(ref mut recorder) = self.recorder {
+            recorder.joined(player);
+        }
+    }
+
+    fn player_left(&mut self, player_id: PlayerId) {
+        self.game.player_left(player_id);
+        self.input_buffer.remove(&player_id);
+        if let Some(ref mut recorder) = self.recorder {
+            recorder.left(player_id);
+        }
+    }
+
+    /// Run one simulation tick and queue its broadcasts. Returns true once
+    /// the round reaches its results. Games with player views send each
+    /// peer in `views` its own state, games with a spectator view send it to
+    /// the peers watching, and private state goes to the peer
+    /// paired with each player in `private`.
+    fn tick(
+        &mut self,
+        dt: f32,
+        now_ms: u64,
+        views: &[(u64, Option<PlayerId>)],
+        private: &[(u64, PlayerId)],
+        pings: &HashMap<PlayerId, u16>,
+        outbox: &mut Vec<(Recipient, Vec<u8>)>,
+    ) -> bool {
+        if self.phase.tick(dt).is_some() {
+            self.phase_changed = true;
+        }
+        let playing = self.phase.phase() == RoundPhase::Playing;
+
+        #[cfg(feature = "tron")]
+        if playing && self.game_id == GameId::Tron && !self.bots.is_empty() {
+            apply_tron_bot_inputs(
+                &mut *self.game,
+                &mut self.bots,
+                &mut self.input_buffer,
+                self.recorder.as_mut(),
+            );
+        }
+
+        let inputs = PlayerInputs {
+            inputs: std::mem::take(&mut self.input_buffer),
+        };
+        self.tick += 1;
+        let events = if playing {
+            let events = self.game.update(dt, &inputs);
+            if let Some(ref mut recorder) = self.recorder {
+                recorder.tick();
+            }
+            events
+        } else {
+            Vec::new()
+        };
+
+        let player_views = self.game.has_player_views();
+        let spectator_view = views
+            .iter()
+            .any(|&(_, viewer)| viewer.is_none())
+            .then(|| self.game.spectator_view_state())
+            .flatten();
+        if player_views || spectator_view.is_some() {
+            for &(peer, viewer) in views {
+                match (viewer, &spectator_view) {
+                    (Some(pid), _) if player_views => {
+                        self.game.serialize_state_for_into(pid, &mut self.state_buf)
+                    },
+                    (None, Some(view)) => {
+                        self.state_buf.clear();
+                        self.state_buf.extend_from_slice(view);
+                    },
+                    _ => self.game.serialize_state_into(&mut self.state_buf),
+                }
+                match encode_game_state_fast(self.tick, now_ms, &self.state_buf) {
+                    Ok(data) => outbox.push((Recipient::Peer(peer), data)),
+                    Err(e) => crate::diag::console_warn!("Failed to encode GameState: {e}"),
+                }
+            }
+        } else {
+            self.game.serialize_state_into(&mut self.state_buf);
+            match encode_game_state_fast(self.tick, now_ms, &self.state_buf) {
+                Ok(data) => outbox.push((Recipient::All, data)),
+                Err(e) => crate::diag::console_warn!("Failed to encode GameState: {e}"),
+            }
+        }
+        for &(peer, player_id) in private {
+            let Some(data) = self.game.private_state_for(player_id) else {
+                continue;
+            };
+            let msg = ServerMessage::PrivateState(PrivateStateMsg {
+                tick: self.tick,
+                player_id,
+                data,
+            });
+            match encode_server_message(&msg) {
+                Ok(data) => outbox.push((Recipient::Peer(peer), data)),
+                Err(e) => crate::diag::console_warn!("Failed to encode PrivateState: {e}"),
+            }
+        }
+        if let Some(data) = self.game.course_data() {
+            let msg = ServerMessage::CourseUpdate(CourseUpdateMsg {
+                version: self.tick,
+                data,
+            });
+            match encode_server_message(&msg) {
+                Ok(data) => outbox.push((Recipient::All, data)),
+                Err(e) => crate::diag::console_warn!("Failed to encode CourseUpdate: {e}"),
+            }
+        }
+        if let Some(data) = self.game.take_effects() {
+            let msg = ServerMessage::GameEffects(GameEffectsMsg {
+                tick: self.tick,
+                data,
+            });
+            match encode_server_message(&msg) {
+                Ok(data) => outbox.push((Recipient::All, data)),
+                Err(e) => crate::diag::console_warn!("Failed to encode GameEffects: {e}"),
+            }
+        }
+
+        if playing
+            && self
+                .tick
+                .is_multiple_of(scoreboard::standings_every(self.game.tick_rate()))
+        {
+            let standings = scoreboard::standings(
+                &*self.game,
+                self.current_round,
+                self.round_count,
+                self.tracker.totals(),
+                pings,
+            );
+            match encode_server_message(&ServerMessage::Standings(standings)) {
+                Ok(data) => outbox.push((Recipient::All, data)),
+                Err(e) => crate::diag::console_warn!("Failed to encode Standings: {e}"),
+            }
+        }
+
+        // Hold the final frame before results
+        let round_complete = playing
+            && (events.iter().any(|e| matches!(e, GameEvent::RoundComplete))
+                || self.game.is_round_complete());
+        if round_complete && self.phase.finish().is_some() {
+            self.phase_changed = true;
+        }
+        if self.phase_changed {
+            self.phase_changed = false;
+            let msg = ServerMessage::RoundPhase(RoundPhaseMsg {
+                round: self.current_round,
+                phase: self.phase.phase(),
+                remaining_secs: self.phase.remaining(),
+            });
+            match encode_server_message(&msg) {
+                Ok(data) => outbox.push((Recipient::All, data)),
+                Err(e) => crate::diag::console_warn!("Failed to encode RoundPhase: {e}"),
+            }
+        }
+        self.phase.phase() == RoundPhase::Results
+    }
+}
+
+/// Generate inputs for Tron bots from the current state, as the server does.
+#[cfg(feature = "tron")]
+fn apply_tron_bot_inputs(
+    game: &mut dyn BreakpointGame,
+    bots: &mut BotDriver,
+    input_buffer: &mut HashMap<PlayerId, Vec<u8>>,
+    mut recorder: Option<&mut InputRecorder>,
+) {
+    let Ok(state) = rmp_serde::from_slice::<breakpoint_tron::TronState>(&game.serialize_state())
+    else {
+        return;
+    };
+    let config = breakpoint_tron::config::TronConfig::default();
+    let tick_rate = game.tick_rate();
+    for bot_id in bots.bot_ids() {
+        if !bots.ready(bot_id, tick_rate) {
+            continue;
+        }
+        let skill = bots.skill(bot_id);
+        let input = breakpoint_tron::bot::generate_bot_input(&state, bot_id, &config, &skill);
+        if let Ok(bytes) = rmp_serde::to_vec(&input) {
+            game.apply_input(bot_id, &bytes);
+            if let Some(recorder) = recorder.as_deref_mut() {
+                recorder.input(bot_id, &bytes);
+            }
+            input_buffer.insert(bot_id, bytes);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use breakpoint_core::net::messages::{
+        AddBotMsg, AddLocalPlayerMsg, AdvanceRoundMsg, ClockSyncMsg, PlayerInputMsg,
+        RequestGameStartMsg,
+    };
+    use breakpoint_core::net::protocol::{
+        decode_message_type, decode_server_message, encode_client_message,
+    };
+
+    use super::*;
+    use crate::game::create_registry;
+
+    fn join_msg(room_code: &str, name: &str) -> Vec<u8> {
+        encode_client_message(&ClientMessage::JoinRoom(JoinRoomMsg {
+            room_code: room_code.to_string(),
+            player_name: name.to_string(),
+            player_color: PlayerColor::default(),
+            protocol_version: PROTOCOL_VERSION,
+            session_token: None,
+            identity_key: None,
+            accepts_batches: false,
+            room_alias: None,
+            bot_token: None,
+            tutorial: None,
+        }))
+        .unwrap()
+    }
+
+    fn server_messages(host: &mut LocalHost) -> Vec<(Recipient, ServerMessage)> {
+        host.take_outbox()
+            .into_iter()
+            .map(|(to, data)| (to, decode_server_message(&data).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn host_leads_and_peers_join_by_code() {
+        let registry = create_registry();
+        let mut host = LocalHost::new("ABCD-1234".to_string());
+        host.handle_message(LOCAL_PEER, &join_msg("", "Host"), &registry);
+        host.handle_message(2, &join_msg("WXYZ-0000", "Lost"), &registry);
+        host.handle_message(3, &join_msg("ABCD-1234", "Guest"), &registry);
+
+        let msgs = server_messages(&mut host);
+        let responses: Vec<_> = msgs
+            .iter()
+            .filter_map(|(to, m)| match m {
+                ServerMessage::JoinRoomResponse(r) => Some((*to, r.success, r.player_id)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            responses,
+            vec![
+                (Recipient::Peer(LOCAL_PEER), true, Some(1)),
+                (Recipient::Peer(2), false, None),
+                (Recipient::Peer(3), true, Some(2)),
+            ]
+        );
+        let Some((Recipient::All, ServerMessage::PlayerList(list))) = msgs.last() else {
+            panic!("Expected a player list broadcast");
+        };
+        assert_eq!(list.leader_id, 1);
+        assert_eq!(list.players.len(), 2);
+        assert_eq!(host.remote_peers(), vec![3]);
+
+        host.peer_left(3);
+        assert!(host.remote_peers().is_empty());
+    }
+
+    #[test]
+    fn peers_may_join_by_the_room_alias() {
+        let registry = create_registry();
+        let mut host = LocalHost::new("ABCD-1234".to_string());
+        host.set_room_alias(Some("GAME-NIGHT".to_string()));
+        host.handle_message(LOCAL_PEER, &join_msg("", "Host"), &registry);
+        host.handle_message(2, &join_msg("game-night", "Guest"), &registry);
+        host.handle_message(3, &join_msg("GAME-DAY", "Lost"), &registry);
+        assert_eq!(host.remote_peers(), vec![2]);
+    }
+
+    #[test]
+    fn only_the_host_starts_games() {
+        let registry = create_registry();
+        let mut host = LocalHost::new(OFFLINE_ROOM_CODE.to_string());
+        host.handle_message(LOCAL_PEER, &join_msg("", "Host"), &registry);
+        host.handle_message(1, &join_msg(OFFLINE_ROOM_CODE, "Guest"), &registry);
+        let start = encode_client_message(&ClientMessage::RequestGameStart(RequestGameStartMsg {
+            game_name: "tron".to_string(),
+            custom: HashMap::new(),
+            win_condition: None,
+            pacing: None,
+            featured: false,
+        }))
+        .unwrap();
+        let add_bot = encode_client_message(&ClientMessage::AddBot(AddBotMsg::default())).unwrap();
+
+        host.handle_message(1, &add_bot, &registry);
+        host.handle_message(1, &start, &registry);
+        assert_eq!(host.room_state(), RoomState::Lobby);
+
+        host.handle_message(LOCAL_PEER, &add_bot, &registry);
+        host.handle_message(LOCAL_PEER, &start, &registry);
+        assert_eq!(host.room_state(), RoomState::InGame);
+        let msgs = server_messages(&mut host);
+        let Some((_, ServerMessage::GameStart(gs))) = msgs.last() else {
+            panic!("Expected GameStart");
+        };
+        assert_eq!(gs.players.len(), 3);
+        assert!(gs.players.iter().any(|p| p.is_bot));
+    }
+
+    #[test]
+    fn hot_seat_guests_join_and_leave_with_their_peer() {
+        let registry = create_registry();
+        let mut host = LocalHost::new("ABCD-1234".to_string());
+        host.handle_message(LOCAL_PEER, &join_msg("", "Host"), &registry);
+        host.handle_message(2, &join_msg("ABCD-1234", "Remote"), &registry);
+        let add_local = encode_client_message(&ClientMessage::AddLocalPlayer(AddLocalPlayerMsg {
+            player_name: "Couch".to_string(),
+            player_color: PlayerColor::default(),
+        }))
+        .unwrap();
+        for _ in 0..=MAX_LOCAL_PLAYERS {
+            host.handle_message(2, &add_local, &registry);
+        }
+        let guests: Vec<PlayerId> = host
+            .players
+            .iter()
+            .filter(|p| p.hot_seat_of == Some(2))
+            .map(|p| p.id)
+            .collect();
+        assert_eq!(guests.len(), MAX_LOCAL_PLAYERS);
+        assert!(host.is_local_player_of(guests[0], 2));
+        assert!(!host.is_local_player_of(guests[0], 1));
+
+        host.peer_left(2);
+        assert_eq!(host.players.len(), 1, "Guests leave with their peer");
+    }
+
+    #[test]
+    fn running_game_broadcasts_state_and_phases() {
+        let registry = create_registry();
+        let mut host = LocalHost::new(OFFLINE_ROOM_CODE.to_string());
+        host.handle_message(LOCAL_PEER, &join_msg("", "Host"), &registry);
+        let start = encode_client_message(&ClientMessage::RequestGameStart(RequestGameStartMsg {
+            game_name: "mini-golf".to_string(),
+            custom: HashMap::new(),
+            win_condition: None,
+            pacing: None,
+            featured: false,
+        }))
+        .unwrap();
+        host.handle_message(LOCAL_PEER, &start, &registry);
+        host.take_outbox();
+
+        // Inputs during the countdown are dropped
+        let input = encode_client_message(&ClientMessage::PlayerInput(PlayerInputMsg {
+            player_id: 99,
+            tick: 0,
+            input_data: vec![0x90],
+        }))
+        .unwrap();
+        host.handle_message(LOCAL_PEER, &input, &registry);
+        assert!(host.session.as_ref().unwrap().input_buffer.is_empty());
+
+        host.update(1.0);
+        let types: Vec<_> = host
+            .take_outbox()
+            .iter()
+            .map(|(_, data)| decode_message_type(data).unwrap())
+            .collect();
+        assert_eq!(types.len() as u32 - 2, MAX_TICKS_PER_FRAME, "{types:?}");
+        assert!(types.contains(&breakpoint_core::net::messages::MessageType::GameState));
+        assert!(types.contains(&breakpoint_core::net::messages::MessageType::RoundPhase));
+    }
+
+    #[test]
+    fn standings_carry_reported_pings() {
+        let registry = create_registry();
+        let mut host = LocalHost::new(OFFLINE_ROOM_CODE.to_string());
+        host.handle_message(LOCAL_PEER, &join_msg("", "Host"), &registry);
+        let probe = encode_client_message(&ClientMessage::ClockSync(ClockSyncMsg {
+            client_time: 0.0,
+            rtt_ms: Some(25),
+        }))
+        .unwrap();
+        host.handle_message(LOCAL_PEER, &probe, &registry);
+        let start = encode_client_message(&ClientMessage::RequestGameStart(RequestGameStartMsg {
+            game_name: "mini-golf".to_string(),
+            custom: HashMap::new(),
+            win_condition: None,
+            pacing: None,
+            featured: false,
+        }))
+        .unwrap();
+        host.handle_message(LOCAL_PEER, &start, &registry);
+
+        // Past the countdown, standings arrive once a second
+        let mut standings = Vec::new();
+        for _ in 0..100 {
+            host.update(0.1);
+            standings.extend(server_messages(&mut host).into_iter().filter_map(
+                |(_, msg)| match msg {
+                    ServerMessage::Standings(s) => Some(s),
+                    _ => None,
+                },
+            ));
+        }
+        assert!(standings.len() >= 5, "{}", standings.len());
+        let latest = standings.last().unwrap();
+        assert_eq!(latest.round, 1);
+        assert_eq!(latest.players.len(), 1);
+        assert_eq!(latest.players[0].ping_ms, Some(25));
+    }
+
+    #[test]
+    fn micro_rounds_restart_almost_at_once() {
+        let registry = create_registry();
+        let mut host = LocalHost::new(OFFLINE_ROOM_CODE.to_string());
+        host.handle_message(LOCAL_PEER, &join_msg("", "Host"), &registry);
+        let start = encode_client_message(&ClientMessage::RequestGameStart(RequestGameStartMsg {
+            game_name: "mini-golf".to_string(),
+            custom: HashMap::from([(
+                round_phase::MICRO_ROUNDS_SETTING.to_string(),
+                serde_json::json!(true),
+            )]),
+            win_condition: None,
+            pacing: None,
+            featured: false,
+        }))
+        .unwrap();
+        host.handle_message(LOCAL_PEER, &start, &registry);
+        let session = host.session.as_ref().unwrap();
+        assert_eq!(
+            session.config.round_duration.as_secs_f32(),
+            round_phase::MICRO_ROUND_DEFAULT_SECS
+        );
+        assert_eq!(host.config, RoomConfig::default(), "The room keeps its own");
+        host.take_outbox();
+
+        host.finish_round();
+        let msgs = server_messages(&mut host);
+        let Some((_, ServerMessage::Intermission(msg))) = msgs.last() else {
+            panic!("Expected Intermission, got {msgs:?}");
+        };
+        let secs = f32::from(round_phase::MICRO_ROUND_PACING.intermission_secs);
+        assert_eq!(msg.remaining_secs, Some(secs));
+        host.update(secs);
+        assert_eq!(host.room_state(), RoomState::InGame);
+    }
+
+    #[test]
+    fn leader_ends_a_wait_for_host_intermission() {
+        let registry = create_registry();
+        let mut host = LocalHost::new("ABCD-1234".to_string());
+        host.handle_message(LOCAL_PEER, &join_msg("", "Host"), &registry);
+        host.handle_message(2, &join_msg("ABCD-1234", "Remote"), &registry);
+        let start = encode_client_message(&ClientMessage::RequestGameStart(RequestGameStartMsg {
+            game_name: "mini-golf".to_string(),
+            custom: HashMap::new(),
+            win_condition: None,
+            pacing: Some(RoundPacing {
+                intermission_secs: 10,
+                auto_advance: false,
+                min_results_secs: 0,
+            }),
+            featured: false,
+        }))
+        .unwrap();
+        host.handle_message(LOCAL_PEER, &start, &registry);
+        host.take_outbox();
+
+        host.finish_round();
+        let msgs = server_messages(&mut host);
+        let Some((_, ServerMessage::Intermission(msg))) = msgs.last() else {
+            panic!("Expected Intermission, got {msgs:?}");
+        };
+        assert_eq!((msg.round, msg.remaining_secs), (1, None));
+        host.update(60.0);
+        assert_eq!(host.room_state(), RoomState::BetweenRounds);
+
+        let advance =
+            encode_client_message(&ClientMessage::AdvanceRound(AdvanceRoundMsg { round: 1 }))
+                .unwrap();
+        host.handle_message(2, &advance, &registry);
+        assert!(host.take_outbox().is_empty(), "Only the leader advances");
+        host.handle_message(LOCAL_PEER, &advance, &registry);
+        host.update(0.0);
+        assert_eq!(host.room_state(), RoomState::InGame);
+        let msgs = server_messages(&mut host);
+        assert!(matches!(
+            msgs.first(),
+            Some((_, ServerMessage::Intermission(msg))) if msg.remaining_secs == Some(0.0)
+        ));
+        assert!(matches!(
+            msgs.last(),
+            Some((_, ServerMessage::GameStart(_)))
+        ));
+    }
+
+    #[test]
+    fn recorded_matches_rerun_to_the_same_scores() {
+        let registry = create_registry();
+        let mut host = LocalHost::new(OFFLINE_ROOM_CODE.to_string());
+        host.handle_message(LOCAL_PEER, &join_msg("", "Host"), &registry);
+        let add_bot = encode_client_message(&ClientMessage::AddBot(AddBotMsg::default())).unwrap();
+        host.handle_message(LOCAL_PEER, &add_bot, &registry);
+        let start = encode_client_message(&ClientMessage::RequestGameStart(RequestGameStartMsg {
+            game_name: "tron".to_string(),
+            custom: HashMap::from([(
+                input_replay::RECORD_REPLAY_SETTING.to_string(),
+                serde_json::json!(true),
+            )]),
+            win_condition: None,
+            pacing: Some(RoundPacing {
+                intermission_secs: 0,
+                auto_advance: true,
+                min_results_secs: 0,
+            }),
+            featured: false,
+        }))
+        .unwrap();
+        host.handle_message(LOCAL_PEER, &start, &registry);
+        assert!(
+            host.session.as_ref().unwrap().custom.contains_key("seed"),
+            "Recorded matches are seeded"
+        );
+
+        let mut live_scores = Vec::new();
+        for _ in 0..100_000 {
+            host.update(0.05);
+            for (_, msg) in server_messages(&mut host) {
+                if let ServerMessage::RoundEnd(end) = msg {
+                    let mut scores: Vec<_> =
+                        end.scores.iter().map(|s| (s.player_id, s.score)).collect();
+                    scores.sort();
+                    live_scores.push(scores);
+                }
+            }
+            if host.session.is_none() {
+                break;
+            }
+        }
+        assert_eq!(host.room_state(), RoomState::Lobby, "The match ended");
+
+        let replay = host.input_replay().unwrap().clone();
+        assert!(
+            replay.rounds.iter().any(|r| !r.inputs.is_empty()),
+            "Bot inputs"
+        );
+        let game = registry.create(GameId::Tron).unwrap();
+        let watched = input_replay::ReplayPlayer::new(replay, game).into_match_replay();
+        // The last round is scored in GameEnd rather than RoundEnd
+        let rerun_scores: Vec<_> = watched
+            .rounds
+            .iter()
+            .map(|r| {
+                let mut scores: Vec<_> = r.scores.iter().map(|s| (s.player_id, s.score)).collect();
+                scores.sort();
+                scores
+            })
+            .collect();
+        assert!(!live_scores.is_empty());
+        assert_eq!(rerun_scores[..live_scores.len()], live_scores[..]);
+    }
+}
@@ -1,6 +1,9 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
+use breakpoint_core::net::e2e::RoomCipher;
+use breakpoint_core::net::protocol::ProtocolError;
+
 #[cfg(target_family = "wasm")]
 use wasm_bindgen::JsCast;
 #[cfg(target_family = "wasm")]
@@ -34,6 +37,14 @@ pub struct WsClient {
     connected: Rc<RefCell<bool>>,
     #[cfg(target_family = "wasm")]
     outbound_queue: Rc<RefCell<Vec<Vec<u8>>>>,
+    /// Messages for the in-page host while hosting locally. When set,
+    /// [`WsClient::send`] queues here instead of writing to the socket.
+    loopback: RefCell<Option<Vec<Vec<u8>>>>,
+    /// Messages and bytes sent since the last [`WsClient::take_sent`].
+    sent: Cell<(u32, usize)>,
+    /// End-to-end key for a relayed room. When set, [`WsClient::send`]
+    /// seals what it writes to the socket.
+    cipher: RefCell<Option<RoomCipher>>,
 }
 
 impl Default for WsClient {
@@ -53,6 +64,9 @@ impl WsClient {
             connected: Rc::new(RefCell::new(false)),
             #[cfg(target_family = "wasm")]
             outbound_queue: Rc::new(RefCell::new(Vec::new())),
+            loopback: RefCell::new(None),
+            sent: Cell::new((0, 0)),
+            cipher: RefCell::new(None),
         }
     }
 
@@ -127,6 +141,13 @@ impl WsClient {
         let onclose =
             Closure::<dyn FnMut(web_sys::CloseEvent)>::new(move |evt: web_sys::CloseEvent| {
                 *connected_close.borrow_mut() = false;
+                // 1000/1001 are normal closes and page navigation
+                if !evt.was_clean() || !matches!(evt.code(), 1000 | 1001) {
+                    crate::telemetry::record(
+                        breakpoint_core::client_report::ClientReportKind::Disconnect,
+                        format!("code={} reason='{}'", evt.code(), evt.reason()),
+                    );
+                }
                 web_sys::console::warn_1(
                     &format!(
                         "WebSocket closed: code={}, reason='{}'",
@@ -203,8 +224,62 @@ impl WsClient {
         *self.connected.borrow_mut() = false;
     }
 
-    #[cfg(target_family = "wasm")]
+    /// Send a client message: to the local host while hosting, otherwise
+    /// over the socket.
     pub fn send(&self, data: &[u8]) -> Result<(), String> {
+        let (count, bytes) = self.sent.get();
+        self.sent.set((count + 1, bytes + data.len()));
+        if let Some(queue) = self.loopback.borrow_mut().as_mut() {
+            queue.push(data.to_vec());
+            return Ok(());
+        }
+        let sealed = self.seal(data).map_err(|e| e.to_string())?;
+        self.send_raw(&sealed)
+    }
+
+    /// Encrypt relayed traffic end to end with `cipher` (or stop).
+    pub fn set_cipher(&self, cipher: Option<RoomCipher>) {
+        *self.cipher.borrow_mut() = cipher;
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.cipher.borrow().is_some()
+    }
+
+    /// Rotations of the room key so far.
+    pub fn key_epoch(&self) -> u32 {
+        self.cipher.borrow().as_ref().map_or(0, RoomCipher::epoch)
+    }
+
+    /// Switch the room key; see [`RoomCipher::rotate`].
+    pub fn rotate_key(&self, epoch: u32, secret: &str) -> bool {
+        self.cipher
+            .borrow_mut()
+            .as_mut()
+            .is_some_and(|c| c.rotate(epoch, secret))
+    }
+
+    /// `data` sealed with the room key, or as is without one.
+    pub fn seal(&self, data: &[u8]) -> Result<Vec<u8>, ProtocolError> {
+        match self.cipher.borrow().as_ref() {
+            Some(cipher) => cipher.seal(data),
+            None => Ok(data.to_vec()),
+        }
+    }
+
+    /// Open a sealed frame with the room key.
+    pub fn open(&self, sealed: &[u8]) -> Result<Vec<u8>, ProtocolError> {
+        match self.cipher.borrow().as_ref() {
+            Some(cipher) => cipher.open(sealed),
+            None => Err(ProtocolError::DeserializeError(
+                "sealed frame without a room secret".to_string(),
+            )),
+        }
+    }
+
+    /// Write to the socket, bypassing the loopback.
+    #[cfg(target_family = "wasm")]
+    pub fn send_raw(&self, data: &[u8]) -> Result<(), String> {
         if let Some(ws) = &self.ws {
             if *self.connected.borrow() {
                 ws.send_with_u8_array(data)
@@ -219,10 +294,38 @@ impl WsClient {
     }
 
     #[cfg(not(target_family = "wasm"))]
-    pub fn send(&self, _data: &[u8]) -> Result<(), String> {
+    pub fn send_raw(&self, _data: &[u8]) -> Result<(), String> {
         Ok(())
     }
 
+    /// Route [`WsClient::send`] to the loopback queue (or back to the socket).
+    pub fn set_loopback(&self, enabled: bool) {
+        *self.loopback.borrow_mut() = enabled.then(Vec::new);
+    }
+
+    pub fn is_loopback(&self) -> bool {
+        self.loopback.borrow().is_some()
+    }
+
+    /// Messages sent to the local host since the last call.
+    pub fn take_loopback(&self) -> Vec<Vec<u8>> {
+        self.loopback
+            .borrow_mut()
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// Deliver a message as if it came from the server.
+    pub fn inject(&self, data: Vec<u8>) {
+        self.buffer.borrow_mut().messages.push(data);
+    }
+
+    /// Outbound message and byte counts since the last call.
+    pub fn take_sent(&self) -> (u32, usize) {
+        self.sent.take()
+    }
+
     pub fn drain_messages(&self) -> Vec<Vec<u8>> {
         std::mem::take(&mut self.buffer.borrow_mut().messages)
     }
@@ -0,0 +1,284 @@
+//! Network diagnostics panel (toggled with F4). Breaks incoming traffic down
+//! by message type and tracks snapshot sizes, decode failures, how stale the
+//! rendered state is, and how far the local player strays from a
+//! dead-reckoned prediction between snapshots. Staleness is measured on the
+//! server's clock once [`ClockSync`] has an estimate.
+
+use breakpoint_core::net::messages::MessageType;
+use serde::Serialize;
+
+use crate::clock_sync::ClockSync;
+
+/// Rates are measured over windows of this length.
+const WINDOW_MS: f64 = 1000.0;
+/// Weight of the newest sample in the running averages.
+const AVG_WEIGHT: f32 = 0.1;
+
+/// Per-type counters for the current window, plus the last window's rates.
+struct TypeCounter {
+    msg_type: MessageType,
+    count: u32,
+    bytes: usize,
+    rate: f32,
+    bytes_per_sec: f32,
+}
+
+/// A timestamped local player position, in world units.
+#[derive(Clone, Copy)]
+struct PositionSample {
+    at: f64,
+    pos: [f32; 2],
+}
+
+/// Collected network instrumentation. Recording is cheap and always on so
+/// the panel has data as soon as it opens.
+#[derive(Default)]
+pub struct NetDiagnostics {
+    pub visible: bool,
+    window_start: f64,
+    types: Vec<TypeCounter>,
+    sent_count: u32,
+    sent_bytes: usize,
+    sent_rate: f32,
+    sent_bytes_per_sec: f32,
+    last_snapshot_bytes: usize,
+    avg_snapshot_bytes: f32,
+    max_snapshot_bytes: usize,
+    last_tick: Option<u32>,
+    last_tick_at: f64,
+    /// Server clock stamped on the last snapshot.
+    last_tick_server_ms: u64,
+    tick_rate: f32,
+    prev_sample: Option<PositionSample>,
+    last_sample: Option<PositionSample>,
+    last_error: f32,
+    avg_error: f32,
+    max_error: f32,
+}
+
+/// Panel contents sent to the UI.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetDiagReport {
+    /// `(type, messages/s, bytes/s)` for each type seen, busiest first.
+    pub rates: Vec<(String, f32, f32)>,
+    pub sent_rate: f32,
+    pub sent_bytes_per_sec: f32,
+    pub last_snapshot_bytes: usize,
+    pub avg_snapshot_bytes: f32,
+    pub max_snapshot_bytes: usize,
+    pub decode_failures: u32,
+    pub applied_tick: Option<u32>,
+    /// Server tick expected at render time, extrapolated from the last
+    /// snapshot's timestamp on the server clock (or its arrival, before the
+    /// clock is measured).
+    pub render_tick: Option<u32>,
+    /// Latest clock probe round trip, in ms.
+    pub rtt_ms: Option<f32>,
+    /// How old the last snapshot is on the server clock, in ms.
+    pub snapshot_age_ms: Option<f32>,
+    pub last_prediction_error: f32,
+    pub avg_prediction_error: f32,
+    pub max_prediction_error: f32,
+}
+
+impl NetDiagnostics {
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    /// Count an incoming message. `now` is the frame timestamp in ms.
+    pub fn record_message(&mut self, msg_type: MessageType, len: usize, now: f64) {
+        self.roll_window(now);
+        match self.types.iter_mut().find(|t| t.msg_type == msg_type) {
+            Some(t) => {
+                t.count += 1;
+                t.bytes += len;
+            },
+            None => self.types.push(TypeCounter {
+                msg_type,
+                count: 1,
+                bytes: len,
+                rate: 0.0,
+                bytes_per_sec: 0.0,
+            }),
+        }
+    }
+
+    /// Count outbound traffic reported by the socket since the last frame.
+    pub fn record_sent(&mut self, count: u32, bytes: usize, now: f64) {
+        self.roll_window(now);
+        self.sent_count += count;
+        self.sent_bytes += bytes;
+    }
+
+    /// Record an applied game state snapshot.
+    pub fn record_snapshot(
+        &mut self,
+        tick: u32,
+        server_time_ms: u64,
+        len: usize,
+        tick_rate: f32,
+        now: f64,
+    ) {
+        self.last_snapshot_bytes = len;
+        self.avg_snapshot_bytes = if self.avg_snapshot_bytes == 0.0 {
+            len as f32
+        } else {
+            self.avg_snapshot_bytes + (len as f32 - self.avg_snapshot_bytes) * AVG_WEIGHT
+        };
+        self.max_snapshot_bytes = self.max_snapshot_bytes.max(len);
+        self.last_tick = Some(tick);
+        self.last_tick_at = now;
+        self.last_tick_server_ms = server_time_ms;
+        self.tick_rate = tick_rate;
+    }
+
+    /// Record the local player's position from a freshly applied snapshot
+    /// and measure its distance from the position extrapolated from the two
+    /// snapshots before it.
+    pub fn record_local_position(&mut self, pos: [f32; 2], now: f64) {
+        if let (Some(a), Some(b)) = (self.prev_sample, self.last_sample) {
+            let span = (b.at - a.at) as f32;
+            if span > 0.0 {
+                let t = (now - b.at) as f32 / span;
+                let predicted = [
+                    b.pos[0] + (b.pos[0] - a.pos[0]) * t,
+                    b.pos[1] + (b.pos[1] - a.pos[1]) * t,
+                ];
+                let error =
+                    ((pos[0] - predicted[0]).powi(2) + (pos[1] - predicted[1]).powi(2)).sqrt();
+                self.last_error = error;
+                self.avg_error += (error - self.avg_error) * AVG_WEIGHT;
+                self.max_error = self.max_error.max(error);
+            }
+        }
+        self.prev_sample = self.last_sample;
+        self.last_sample = Some(PositionSample { at: now, pos });
+    }
+
+    /// Forget per-game tracking when a game starts or ends.
+    pub fn reset_game(&mut self) {
+        self.last_tick = None;
+        self.last_tick_server_ms = 0;
+        self.prev_sample = None;
+        self.last_sample = None;
+        self.last_error = 0.0;
+        self.avg_error = 0.0;
+        self.max_error = 0.0;
+        self.max_snapshot_bytes = 0;
+        self.avg_snapshot_bytes = 0.0;
+    }
+
+    pub fn report(&self, now: f64, clock: &ClockSync) -> NetDiagReport {
+        let snapshot_age_ms = clock
+            .server_now(now)
+            .filter(|_| self.last_tick.is_some() && self.last_tick_server_ms > 0)
+            .map(|server_now| (server_now - self.last_tick_server_ms as f64).max(0.0));
+        let since_snapshot_ms = snapshot_age_ms.unwrap_or((now - self.last_tick_at).max(0.0));
+        let mut types: Vec<&TypeCounter> = self.types.iter().filter(|t| t.rate > 0.0).collect();
+        types.sort_by(|a, b| b.rate.total_cmp(&a.rate));
+        NetDiagReport {
+            rates: types
+                .iter()
+                .map(|t| (format!("{:?}", t.msg_type), t.rate, t.bytes_per_sec))
+                .collect(),
+            sent_rate: self.sent_rate,
+            sent_bytes_per_sec: self.sent_bytes_per_sec,
+            last_snapshot_bytes: self.last_snapshot_bytes,
+            avg_snapshot_bytes: self.avg_snapshot_bytes,
+            max_snapshot_bytes: self.max_snapshot_bytes,
+            decode_failures: crate::telemetry::decode_failure_count(),
+            applied_tick: self.last_tick,
+            render_tick: self
+                .last_tick
+                .map(|tick| tick + ((since_snapshot_ms / 1000.0) as f32 * self.tick_rate) as u32),
+            rtt_ms: clock.rtt_ms().map(|rtt| rtt as f32),
+            snapshot_age_ms: snapshot_age_ms.map(|age| age as f32),
+            last_prediction_error: self.last_error,
+            avg_prediction_error: self.avg_error,
+            max_prediction_error: self.max_error,
+        }
+    }
+
+    /// Close the measurement window once it has run its length.
+    fn roll_window(&mut self, now: f64) {
+        let elapsed = now - self.window_start;
+        if elapsed < WINDOW_MS {
+            return;
+        }
+        let secs = (elapsed / 1000.0) as f32;
+        for t in &mut self.types {
+            t.rate = t.count as f32 / secs;
+            t.bytes_per_sec = t.bytes as f32 / secs;
+            t.count = 0;
+            t.bytes = 0;
+        }
+        self.sent_rate = self.sent_count as f32 / secs;
+        self.sent_bytes_per_sec = self.sent_bytes as f32 / secs;
+        self.sent_count = 0;
+        self.sent_bytes = 0;
+        self.window_start = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rates_are_per_type_and_per_window() {
+        let mut diag = NetDiagnostics::default();
+        for i in 0..20 {
+            diag.record_message(MessageType::GameState, 100, 10.0 + i as f64 * 50.0);
+        }
+        diag.record_message(MessageType::RoundPhase, 8, 500.0);
+        // Opening the next window publishes the previous one
+        diag.record_message(MessageType::GameState, 100, 1000.0);
+
+        let report = diag.report(1000.0, &ClockSync::default());
+        assert_eq!(report.rates[0].0, "GameState");
+        assert!((report.rates[0].1 - 20.0).abs() < 0.01);
+        assert!((report.rates[0].2 - 2000.0).abs() < 0.1);
+        assert_eq!(report.rates[1].0, "RoundPhase");
+    }
+
+    #[test]
+    fn render_tick_extrapolates_from_last_snapshot() {
+        let mut diag = NetDiagnostics::default();
+        diag.record_snapshot(100, 0, 512, 20.0, 1000.0);
+        let report = diag.report(1250.0, &ClockSync::default());
+        assert_eq!(report.applied_tick, Some(100));
+        assert_eq!(report.render_tick, Some(105));
+        assert_eq!(report.max_snapshot_bytes, 512);
+        assert_eq!(report.snapshot_age_ms, None);
+    }
+
+    #[test]
+    fn synced_clock_measures_from_the_server_timestamp() {
+        let mut clock = ClockSync::default();
+        // Server clock runs 50_000 ms ahead, 40 ms round trip
+        clock.record_reply(960.0, 50_980, 1000.0);
+        let mut diag = NetDiagnostics::default();
+        // Simulated at server 50_900, arrived at local 1000 (server 51_000)
+        diag.record_snapshot(100, 50_900, 512, 20.0, 1000.0);
+        let report = diag.report(1150.0, &clock);
+        assert_eq!(report.rtt_ms, Some(40.0));
+        assert_eq!(report.snapshot_age_ms, Some(250.0));
+        assert_eq!(report.render_tick, Some(105));
+    }
+
+    #[test]
+    fn prediction_error_measures_deviation_from_dead_reckoning() {
+        let mut diag = NetDiagnostics::default();
+        diag.record_local_position([0.0, 0.0], 0.0);
+        diag.record_local_position([1.0, 0.0], 100.0);
+        // Steady motion lands exactly where predicted
+        diag.record_local_position([2.0, 0.0], 200.0);
+        assert!(diag.last_error < 1e-5);
+        // A correction sideways shows up as error
+        diag.record_local_position([3.0, 4.0], 300.0);
+        assert!((diag.last_error - 4.0).abs() < 1e-4);
+        assert!((diag.max_error - 4.0).abs() < 1e-4);
+    }
+}
@@ -910,12 +910,6 @@ impl Renderer {
         Ok(())
     }
 
-    /// Load a texture atlas from an HtmlImageElement with NEAREST filtering.
-    #[cfg(target_family = "wasm")]
-    pub fn load_texture(&mut self, id: u8, img: &web_sys::HtmlImageElement) {
-        self.load_texture_with_wrap(id, img, false);
-    }
-
     /// Load a texture with NEAREST filtering and configurable wrapping.
     /// When `wrap_repeat` is true, uses GL::REPEAT for seamless tiling;
     /// otherwise uses CLAMP_TO_EDGE.
@@ -0,0 +1,210 @@
+//! Offline playback of `.bpreplay` match recordings, and of `.bpinputs`
+//! recordings once they are re-run into state frames.
+
+use std::ops::Range;
+
+use breakpoint_core::replay::{MatchReplay, RoundReplay, decode_replay};
+
+/// How long the last frame of a round stays up before the next round.
+const ROUND_HOLD_SECS: f64 = 2.0;
+
+/// Playback position in a loaded replay, advanced in real time.
+pub struct ReplayPlayback {
+    pub replay: MatchReplay,
+    round_index: usize,
+    /// Next frame of the current round to apply.
+    next_frame: usize,
+    /// Seconds since the current round's first frame.
+    round_clock: f64,
+    started: bool,
+    pub paused: bool,
+    pub finished: bool,
+}
+
+/// What to apply after [`ReplayPlayback::advance`]: re-init the game when
+/// a round starts, then apply the current round's `frames` in order.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PlaybackStep {
+    pub new_round: Option<usize>,
+    pub frames: Range<usize>,
+}
+
+impl ReplayPlayback {
+    /// Decode a replay file. Fails if it is corrupt or has no rounds.
+    pub fn load(data: &[u8]) -> Result<Self, String> {
+        Self::new(decode_replay(data).map_err(|e| e.to_string())?)
+    }
+
+    /// Watch a replay from somewhere other than a file, such as a re-run
+    /// input recording. Fails if it has no rounds.
+    pub fn new(replay: MatchReplay) -> Result<Self, String> {
+        if replay.rounds.is_empty() {
+            return Err("replay has no rounds".to_string());
+        }
+        Ok(Self {
+            replay,
+            round_index: 0,
+            next_frame: 0,
+            round_clock: 0.0,
+            started: false,
+            paused: false,
+            finished: false,
+        })
+    }
+
+    pub fn round_index(&self) -> usize {
+        self.round_index
+    }
+
+    pub fn current_round(&self) -> &RoundReplay {
+        &self.replay.rounds[self.round_index]
+    }
+
+    /// Seconds from the round's first frame to `index`, as recorded on the
+    /// server's clock.
+    fn frame_offset(&self, index: usize) -> f64 {
+        let frames = &self.current_round().frames;
+        match (frames.first(), frames.get(index)) {
+            (Some(first), Some(frame)) => self.replay.frame_span_secs(first, frame),
+            _ => 0.0,
+        }
+    }
+
+    /// Fraction of the current round played, 0.0 to 1.0.
+    pub fn round_progress(&self) -> f32 {
+        let frames = self.current_round().frames.len();
+        if frames <= 1 {
+            return if self.next_frame >= frames { 1.0 } else { 0.0 };
+        }
+        let length = self.frame_offset(frames - 1);
+        if length <= 0.0 {
+            return 1.0;
+        }
+        (self.round_clock / length).clamp(0.0, 1.0) as f32
+    }
+
+    /// Advance by `dt` seconds.
+    pub fn advance(&mut self, dt: f64) -> PlaybackStep {
+        let mut step = PlaybackStep {
+            new_round: None,
+            frames: self.next_frame..self.next_frame,
+        };
+        if !self.started {
+            self.started = true;
+            step.new_round = Some(0);
+        }
+        if self.paused || self.finished {
+            return step;
+        }
+
+        self.round_clock += dt;
+        let frame_count = self.current_round().frames.len();
+        let start = self.next_frame;
+        while self.next_frame < frame_count
+            && self.frame_offset(self.next_frame) <= self.round_clock
+        {
+            self.next_frame += 1;
+        }
+        step.frames = start..self.next_frame;
+
+        // Move on once the hold has passed, in a step with no frames of
+        // its own so the caller never mixes two rounds' frames
+        let round_end = self.frame_offset(frame_count.saturating_sub(1));
+        if step.frames.is_empty()
+            && self.next_frame >= frame_count
+            && self.round_clock >= round_end + ROUND_HOLD_SECS
+        {
+            if self.round_index + 1 < self.replay.rounds.len() {
+                self.round_index += 1;
+                self.next_frame = 0;
+                self.round_clock = 0.0;
+                step.new_round = Some(self.round_index);
+                step.frames = 0..0;
+            } else {
+                self.finished = true;
+            }
+        }
+        step
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use breakpoint_core::game_trait::GameId;
+    use breakpoint_core::replay::{ReplayFrame, ReplayMetadata, encode_replay};
+
+    use super::*;
+
+    fn round(round: u8, ticks: u32) -> RoundReplay {
+        RoundReplay {
+            round,
+            scores: Vec::new(),
+            frames: (1..=ticks)
+                .map(|tick| ReplayFrame {
+                    tick,
+                    server_time_ms: 0,
+                    state: vec![round, tick as u8],
+                    course: None,
+                })
+                .collect(),
+            truncated: false,
+            heatmap: None,
+        }
+    }
+
+    fn playback(rounds: Vec<RoundReplay>) -> ReplayPlayback {
+        let replay = MatchReplay {
+            metadata: ReplayMetadata {
+                match_id: "m1".to_string(),
+                game: GameId::Tron,
+                room_code: "ABCD-1234".to_string(),
+                players: Vec::new(),
+                custom: Default::default(),
+                tick_rate: 10.0,
+                started_at: 0,
+                ended_at: 0,
+                server_version: "0.1.0".to_string(),
+            },
+            rounds,
+            final_scores: Vec::new(),
+        };
+        ReplayPlayback::load(&encode_replay(&replay).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn frames_follow_the_clock() {
+        let mut p = playback(vec![round(1, 11)]);
+        assert_eq!(
+            p.advance(0.0),
+            PlaybackStep {
+                new_round: Some(0),
+                frames: 0..1
+            }
+        );
+        assert_eq!(p.advance(0.25).frames, 1..3);
+        p.paused = true;
+        assert!(p.advance(5.0).frames.is_empty());
+        p.paused = false;
+        assert_eq!(p.advance(1.0).frames, 3..11);
+        assert_eq!(p.round_progress(), 1.0);
+        assert!(!p.finished, "Last frame is held");
+        p.advance(ROUND_HOLD_SECS);
+        assert!(p.finished);
+    }
+
+    #[test]
+    fn rounds_change_after_the_hold() {
+        let mut p = playback(vec![round(1, 3), round(2, 3)]);
+        p.advance(0.0);
+        assert_eq!(p.advance(0.2).frames, 1..3);
+        assert_eq!(p.advance(ROUND_HOLD_SECS + 0.2).new_round, Some(1));
+        assert_eq!(p.round_index(), 1);
+        assert_eq!(p.advance(0.0).frames, 0..1);
+        assert_eq!(p.current_round().round, 2);
+    }
+
+    #[test]
+    fn rejects_bad_files() {
+        assert!(ReplayPlayback::load(b"not a replay").is_err());
+    }
+}
@@ -757,6 +757,7 @@ fn bitmask_tile_name(group: &str, idx: u32) -> &'static str {
 
 /// Compute 4-neighbor bitmask for a stone brick tile.
 /// bit 0 = solid above, bit 1 = solid below, bit 2 = solid left, bit 3 = solid right.
+#[cfg(feature = "platformer")]
 pub fn stone_brick_bitmask(
     course: &breakpoint_platformer::course_gen::Course,
     tx: i32,
@@ -794,6 +795,7 @@ pub fn bitmask_tile_for_group(group: TileGroup, mask: u32) -> &'static str {
 }
 
 /// Map room themes to visual tile groups.
+#[cfg(feature = "platformer")]
 pub fn room_theme_to_tile_group(theme: &breakpoint_platformer::course_gen::RoomTheme) -> TileGroup {
     use breakpoint_platformer::course_gen::RoomTheme;
     match theme {
@@ -0,0 +1,168 @@
+//! State diff inspector (toggled with F7 in builds with the `state-diff`
+//! feature). Diffs each game state snapshot against the one before it, or
+//! against a pinned snapshot (Shift+F7), field by field, so a desync or an
+//! `apply_state` bug shows up as named fields instead of a wrong picture.
+
+use breakpoint_core::game_trait::BreakpointGame;
+use breakpoint_core::state_diff::diff_states;
+use serde::Serialize;
+
+/// Lines shown in the panel; the rest are counted.
+const MAX_LINES: usize = 40;
+/// Numbers closer than this count as equal.
+const TOLERANCE: f64 = 1e-4;
+
+struct Snapshot {
+    tick: u32,
+    data: Vec<u8>,
+}
+
+/// Snapshots kept for diffing while the panel is open.
+#[derive(Default)]
+pub struct StateInspector {
+    pub visible: bool,
+    pinned: Option<Snapshot>,
+    previous: Option<Snapshot>,
+    report: Option<StateDiffReport>,
+    /// A new report is waiting to be pushed to the UI.
+    dirty: bool,
+}
+
+/// Panel contents sent to the UI.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateDiffReport {
+    /// Tick of the snapshot diffed against.
+    pub base_tick: u32,
+    pub tick: u32,
+    /// Whether the base is pinned rather than the previous snapshot.
+    pub pinned: bool,
+    /// `None` when the game can't be inspected or a state didn't decode.
+    pub lines: Option<Vec<String>>,
+    /// Differing fields beyond the ones in `lines`.
+    pub more: usize,
+}
+
+impl StateInspector {
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+        if !self.visible {
+            self.reset_game();
+        }
+        self.dirty = true;
+    }
+
+    /// Pin the latest snapshot as the base for later diffs, or unpin.
+    pub fn toggle_pin(&mut self) {
+        if self.pinned.is_some() {
+            self.pinned = None;
+        } else if let Some(previous) = &self.previous {
+            self.pinned = Some(Snapshot {
+                tick: previous.tick,
+                data: previous.data.clone(),
+            });
+        }
+    }
+
+    /// Diff a snapshot just applied against the base. Only records while
+    /// the panel is open, so it costs nothing otherwise.
+    pub fn record(&mut self, game: &dyn BreakpointGame, tick: u32, data: &[u8]) {
+        if !self.visible {
+            return;
+        }
+        if let Some(base) = self.pinned.as_ref().or(self.previous.as_ref()) {
+            let diff = diff_states(game, &base.data, data, TOLERANCE);
+            let total = diff.as_ref().map_or(0, |diff| diff.fields.len());
+            let lines = diff.map(|diff| {
+                diff.fields
+                    .iter()
+                    .take(MAX_LINES)
+                    .map(ToString::to_string)
+                    .collect()
+            });
+            self.report = Some(StateDiffReport {
+                base_tick: base.tick,
+                tick,
+                pinned: self.pinned.is_some(),
+                more: total.saturating_sub(MAX_LINES),
+                lines,
+            });
+            self.dirty = true;
+        }
+        self.previous = Some(Snapshot {
+            tick,
+            data: data.to_vec(),
+        });
+    }
+
+    /// Forget the snapshots of the last game.
+    pub fn reset_game(&mut self) {
+        self.pinned = None;
+        self.previous = None;
+        self.report = None;
+    }
+
+    /// The report to show if it changed since the last call: `Some(None)`
+    /// hides the panel.
+    pub fn take_update(&mut self) -> Option<Option<&StateDiffReport>> {
+        if !std::mem::take(&mut self.dirty) {
+            return None;
+        }
+        Some(self.report.as_ref().filter(|_| self.visible))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use breakpoint_core::game_trait::{GameConfig, PlayerInputs};
+    use breakpoint_core::lobby_toy::{TargetClick, TargetPractice};
+
+    use super::*;
+
+    fn hit(game: &mut TargetPractice, player_id: u64) -> Vec<u8> {
+        let (x, y) = (game.state().target_x, game.state().target_y);
+        let click = rmp_serde::to_vec(&TargetClick { x, y }).unwrap();
+        game.apply_input(player_id, &click);
+        let inputs = PlayerInputs {
+            inputs: Default::default(),
+        };
+        game.update(0.0, &inputs);
+        game.serialize_state()
+    }
+
+    #[test]
+    fn diffs_against_the_previous_or_pinned_snapshot() {
+        let mut game = TargetPractice::new();
+        game.init(&[], &GameConfig::builder().build());
+        let mut inspector = StateInspector::default();
+        inspector.record(&game, 1, &game.serialize_state());
+        assert!(inspector.previous.is_none(), "Closed panels record nothing");
+
+        inspector.toggle();
+        inspector.record(&game, 1, &game.serialize_state());
+        inspector.toggle_pin();
+        let first = hit(&mut game, 1);
+        inspector.record(&game, 2, &first);
+        let report = inspector.take_update().flatten().unwrap().clone();
+        assert_eq!((report.base_tick, report.tick, report.pinned), (1, 2, true));
+        assert!(report.lines.unwrap().iter().any(|l| l.contains("hits.1")));
+        assert_eq!(inspector.take_update(), None, "Unchanged since last push");
+
+        // Pinned diffs keep accumulating from tick 1
+        let second = hit(&mut game, 2);
+        inspector.record(&game, 3, &second);
+        let lines = inspector.take_update().flatten().unwrap().lines.clone();
+        let lines = lines.unwrap();
+        assert!(lines.iter().any(|l| l.contains("hits.1")));
+        assert!(lines.iter().any(|l| l.contains("hits.2")));
+
+        inspector.toggle_pin();
+        inspector.record(&game, 4, &second);
+        let report = inspector.take_update().flatten().unwrap();
+        assert_eq!((report.base_tick, report.pinned), (3, false));
+        assert_eq!(report.lines.as_deref(), Some(&[][..]));
+
+        inspector.toggle();
+        assert_eq!(inspector.take_update(), Some(None), "Closing hides it");
+    }
+}
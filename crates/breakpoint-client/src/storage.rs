@@ -1,3 +1,5 @@
+use crate::audio::AudioSettings;
+
 /// Calls the closure with browser localStorage, if available. No-op on non-WASM.
 #[allow(unused_variables)]
 pub fn with_local_storage(f: impl FnOnce(&web_sys::Storage)) {
@@ -10,3 +12,67 @@ pub fn with_local_storage(f: impl FnOnce(&web_sys::Storage)) {
         }
     }
 }
+
+/// Audio settings saved in localStorage, or defaults.
+pub fn load_audio_settings() -> AudioSettings {
+    let mut audio_settings = AudioSettings::default();
+    with_local_storage(|storage| {
+        if let Ok(Some(val)) = storage.get_item("audio_muted") {
+            audio_settings.muted = val == "true";
+        }
+        if let Ok(Some(val)) = storage.get_item("audio_master_volume")
+            && let Ok(v) = val.parse::<f32>()
+        {
+            audio_settings.muted = false;
+            audio_settings.master_volume = v.clamp(0.0, 1.0);
+        }
+        if let Ok(Some(val)) = storage.get_item("audio_music_volume")
+            && let Ok(v) = val.parse::<f32>()
+        {
+            audio_settings.music_volume = v.clamp(0.0, 1.0);
+        }
+    });
+    audio_settings
+}
+
+/// Whether golf aims by dragging (touch style) instead of pointing at the
+/// ground. Off unless `golf_drag_aim` is saved as `"true"`.
+pub fn load_golf_drag_aim() -> bool {
+    let mut drag_aim = false;
+    with_local_storage(|storage| {
+        if let Ok(Some(val)) = storage.get_item("golf_drag_aim") {
+            drag_aim = val == "true";
+        }
+    });
+    drag_aim
+}
+
+/// localStorage key for the player's identity secret.
+const IDENTITY_KEY: &str = "identity_key";
+
+/// The player's long-lived identity secret (32 hex chars), generated and
+/// persisted on first use. The server derives the public friend ID from it.
+pub fn load_identity_key() -> String {
+    let mut saved = None;
+    with_local_storage(|storage| {
+        if let Ok(Some(val)) = storage.get_item(IDENTITY_KEY)
+            && val.len() == 32
+            && val.bytes().all(|b| b.is_ascii_hexdigit())
+        {
+            saved = Some(val);
+        }
+    });
+    if let Some(key) = saved {
+        return key;
+    }
+
+    let mut bytes = [0u8; 16];
+    if getrandom::fill(&mut bytes).is_err() {
+        bytes.iter_mut().for_each(|b| *b = fastrand::u8(..));
+    }
+    let key: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    with_local_storage(|storage| {
+        let _ = storage.set_item(IDENTITY_KEY, &key);
+    });
+    key
+}
@@ -0,0 +1,204 @@
+//! Opt-in crash and error reporting. Panics, abnormal WebSocket closes, and
+//! decode failures are queued and sent in batches to `POST /api/v1/telemetry`.
+//! Nothing is recorded until the player opts in from the lobby.
+
+use std::cell::RefCell;
+
+use breakpoint_core::client_report::{
+    ClientReport, ClientReportBatch, ClientReportKind, MAX_BATCH_REPORTS, truncate_message,
+};
+
+/// localStorage key for the opt-in choice.
+const OPT_IN_KEY: &str = "telemetry_opt_in";
+#[cfg(target_family = "wasm")]
+const ENDPOINT: &str = "api/v1/telemetry";
+/// Reports are sent at most this often, except after a panic.
+const FLUSH_INTERVAL_MS: f64 = 30_000.0;
+/// Reports beyond this are counted as dropped until the next flush.
+const MAX_QUEUED: usize = 50;
+
+#[derive(Default)]
+struct Reporter {
+    enabled: bool,
+    session_id: String,
+    context: String,
+    queue: Vec<ClientReport>,
+    dropped: u32,
+    last_flush: f64,
+    /// Decode failures this session, counted even without opt-in for the
+    /// network diagnostics panel.
+    decode_failures: u32,
+}
+
+impl Reporter {
+    fn record(&mut self, kind: ClientReportKind, mut message: String) {
+        if !self.enabled {
+            return;
+        }
+        if self.queue.len() >= MAX_QUEUED {
+            self.dropped = self.dropped.saturating_add(1);
+            return;
+        }
+        truncate_message(&mut message);
+        self.queue.push(ClientReport {
+            kind,
+            message,
+            context: self.context.clone(),
+        });
+    }
+
+    /// Take up to one batch of queued reports.
+    fn take_batch(&mut self) -> Option<ClientReportBatch> {
+        if self.queue.is_empty() {
+            return None;
+        }
+        let n = self.queue.len().min(MAX_BATCH_REPORTS);
+        Some(ClientReportBatch {
+            session_id: self.session_id.clone(),
+            client_version: env!("CARGO_PKG_VERSION").to_string(),
+            reports: self.queue.drain(..n).collect(),
+            dropped: std::mem::take(&mut self.dropped),
+        })
+    }
+}
+
+thread_local! {
+    static REPORTER: RefCell<Reporter> = RefCell::new(Reporter::default());
+}
+
+/// Load the opt-in choice and install the panic hook. Call once at startup.
+pub fn init() {
+    let mut enabled = false;
+    crate::storage::with_local_storage(|storage| {
+        enabled = matches!(storage.get_item(OPT_IN_KEY), Ok(Some(v)) if v == "true");
+    });
+    let mut bytes = [0u8; 8];
+    if getrandom::fill(&mut bytes).is_err() {
+        bytes.iter_mut().for_each(|b| *b = fastrand::u8(..));
+    }
+    REPORTER.with_borrow_mut(|r| {
+        r.enabled = enabled;
+        r.session_id = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    });
+
+    #[cfg(target_family = "wasm")]
+    std::panic::set_hook(Box::new(|info| {
+        console_error_panic_hook::hook(info);
+        // The module is unusable after a panic, so send right away. The
+        // reporter may be mid-borrow if the panic started inside it.
+        let batch = REPORTER.with(|r| {
+            let mut r = r.try_borrow_mut().ok()?;
+            r.record(ClientReportKind::Panic, info.to_string());
+            r.take_batch()
+        });
+        if let Some(batch) = batch {
+            send(&batch);
+        }
+    }));
+}
+
+pub fn is_enabled() -> bool {
+    REPORTER.with_borrow(|r| r.enabled)
+}
+
+/// Opt in or out. Opting out discards anything not yet sent.
+pub fn set_enabled(enabled: bool) {
+    REPORTER.with_borrow_mut(|r| {
+        r.enabled = enabled;
+        if !enabled {
+            r.queue.clear();
+            r.dropped = 0;
+        }
+    });
+    crate::storage::with_local_storage(|storage| {
+        let _ = storage.set_item(OPT_IN_KEY, if enabled { "true" } else { "false" });
+    });
+}
+
+/// Describe where the client is (e.g. `InGame/tron`), attached to reports.
+pub fn set_context(context: String) {
+    REPORTER.with_borrow_mut(|r| r.context = context);
+}
+
+pub fn record(kind: ClientReportKind, message: String) {
+    REPORTER.with_borrow_mut(|r| r.record(kind, message));
+}
+
+/// Log a server message that failed to decode and report it.
+pub fn decode_failed(what: &str, len: usize, error: &impl std::fmt::Display) {
+    let message = format!("Failed to decode {what} ({len} bytes): {error}");
+    crate::diag::console_warn!("{message}");
+    REPORTER.with_borrow_mut(|r| {
+        r.decode_failures = r.decode_failures.saturating_add(1);
+        r.record(ClientReportKind::DecodeError, message);
+    });
+}
+
+/// Decode failures seen this session.
+pub fn decode_failure_count() -> u32 {
+    REPORTER.with_borrow(|r| r.decode_failures)
+}
+
+/// Send queued reports if the flush interval has passed.
+pub fn flush_if_due(timestamp: f64) {
+    let batch = REPORTER.with_borrow_mut(|r| {
+        if timestamp - r.last_flush < FLUSH_INTERVAL_MS {
+            return None;
+        }
+        let batch = r.take_batch()?;
+        r.last_flush = timestamp;
+        Some(batch)
+    });
+    if let Some(batch) = batch {
+        send(&batch);
+    }
+}
+
+/// `sendBeacon` survives page unload and doesn't need a response.
+#[allow(unused_variables)]
+fn send(batch: &ClientReportBatch) {
+    #[cfg(target_family = "wasm")]
+    if let Some(window) = web_sys::window()
+        && let Ok(json) = serde_json::to_string(batch)
+    {
+        let _ = window
+            .navigator()
+            .send_beacon_with_opt_str(ENDPOINT, Some(&json));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reporter() -> Reporter {
+        Reporter {
+            enabled: true,
+            session_id: "abcd".to_string(),
+            context: "Lobby/mini-golf".to_string(),
+            ..Reporter::default()
+        }
+    }
+
+    #[test]
+    fn nothing_recorded_without_opt_in() {
+        let mut r = Reporter::default();
+        r.record(ClientReportKind::Panic, "boom".to_string());
+        assert!(r.take_batch().is_none());
+    }
+
+    #[test]
+    fn batches_are_capped_and_count_drops() {
+        let mut r = reporter();
+        for i in 0..MAX_QUEUED + 5 {
+            r.record(ClientReportKind::DecodeError, format!("error {i}"));
+        }
+        let batch = r.take_batch().unwrap();
+        assert_eq!(batch.reports.len(), MAX_BATCH_REPORTS);
+        assert_eq!(batch.dropped, 5);
+        assert_eq!(batch.reports[0].context, "Lobby/mini-golf");
+
+        let next = r.take_batch().unwrap();
+        assert_eq!(next.dropped, 0, "Drops are reported once");
+    }
+}
@@ -8,6 +8,9 @@ const MAX_RAIN_DROPS: usize = 60;
 /// Maximum number of ambient particles (reduced from 40 for draw call budget).
 const MAX_AMBIENT_PARTICLES: usize = 20;
 
+/// Fog layer Z, in front of the platformer's effects layer.
+const Z_FOG: f32 = 1.0;
+
 /// A single rain drop particle.
 struct RainDrop {
     x: f32,
@@ -341,12 +344,8 @@ impl WeatherSystem {
                 density: self.fog_density,
                 color: Vec4::new(self.fog_color[0], self.fog_color[1], self.fog_color[2], 0.5),
             },
-            Transform::from_xyz(
-                self.camera_x,
-                self.camera_y - 3.0,
-                crate::game::platformer_render::Z_FOG,
-            )
-            .with_scale(Vec3::new(40.0, 8.0, 1.0)),
+            Transform::from_xyz(self.camera_x, self.camera_y - 3.0, Z_FOG)
+                .with_scale(Vec3::new(40.0, 8.0, 1.0)),
         );
     }
 }
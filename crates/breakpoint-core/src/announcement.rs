@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+/// How prominently an announcement is shown. Separate from alert
+/// [`Priority`](crate::events::Priority): announcements are operator
+/// messages to players, not events from external systems.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnnouncementSeverity {
+    #[default]
+    Info,
+    Warning,
+    Critical,
+}
+
+/// An operator message shown to every player, in the lobby and in-game.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Announcement {
+    pub id: String,
+    /// Shown verbatim; operators write it in whatever language they choose.
+    pub message: String,
+    #[serde(default)]
+    pub severity: AnnouncementSeverity,
+    /// Unix seconds from which the announcement is shown.
+    #[serde(default)]
+    pub starts_at: u64,
+    /// Unix seconds after which it is no longer shown. `None` means until removed.
+    #[serde(default)]
+    pub ends_at: Option<u64>,
+}
+
+impl Announcement {
+    /// Whether the announcement should be shown at `now` (Unix seconds).
+    pub fn is_active(&self, now: u64) -> bool {
+        self.starts_at <= now && self.ends_at.is_none_or(|end| now < end)
+    }
+
+    /// Whether the announcement's window has passed.
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.ends_at.is_some_and(|end| now >= end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn announcement(starts_at: u64, ends_at: Option<u64>) -> Announcement {
+        Announcement {
+            id: "a".to_string(),
+            message: "Maintenance tonight".to_string(),
+            severity: AnnouncementSeverity::Warning,
+            starts_at,
+            ends_at,
+        }
+    }
+
+    #[test]
+    fn active_within_window() {
+        let a = announcement(100, Some(200));
+        assert!(!a.is_active(99));
+        assert!(a.is_active(100));
+        assert!(a.is_active(199));
+        assert!(!a.is_active(200));
+        assert!(a.is_expired(200));
+    }
+
+    #[test]
+    fn open_ended_never_expires() {
+        let a = announcement(0, None);
+        assert!(a.is_active(u64::MAX));
+        assert!(!a.is_expired(u64::MAX));
+    }
+
+    #[test]
+    fn severity_orders_by_prominence() {
+        assert!(AnnouncementSeverity::Critical > AnnouncementSeverity::Warning);
+        assert!(AnnouncementSeverity::Warning > AnnouncementSeverity::Info);
+        let json = serde_json::to_string(&AnnouncementSeverity::Critical).unwrap();
+        assert_eq!(json, "\"critical\"");
+    }
+}
@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::game_trait::{PlayerId, PlayerScore};
+use crate::player::Player;
+
+/// How well a bot plays. Chosen per bot slot in the lobby.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BotDifficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    /// Starts at normal and retunes after every round to keep pace with
+    /// the human players.
+    Mirror,
+}
+
+impl BotDifficulty {
+    pub const ALL: [BotDifficulty; 4] = [Self::Easy, Self::Normal, Self::Hard, Self::Mirror];
+}
+
+/// The knobs a difficulty sets. Games apply the ones that make sense for
+/// them; a game without aiming ignores `aim_error`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BotSkill {
+    /// Seconds between decisions. Between decisions the bot sends no input,
+    /// so it notices danger and openings late.
+    pub reaction_delay: f32,
+    /// Largest aim offset in radians, applied uniformly in both directions.
+    pub aim_error: f32,
+    /// Chance (0-1) of taking a worse option at a decision point.
+    pub decision_noise: f32,
+}
+
+impl BotSkill {
+    pub const EASY: Self = Self {
+        reaction_delay: 0.35,
+        aim_error: 0.3,
+        decision_noise: 0.3,
+    };
+    pub const NORMAL: Self = Self {
+        reaction_delay: 0.15,
+        aim_error: 0.1,
+        decision_noise: 0.1,
+    };
+    pub const HARD: Self = Self {
+        reaction_delay: 0.0,
+        aim_error: 0.0,
+        decision_noise: 0.0,
+    };
+
+    /// Interpolate between `easy` (t = 0) and `hard` (t = 1).
+    pub fn lerp(easy: Self, hard: Self, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        Self {
+            reaction_delay: mix(easy.reaction_delay, hard.reaction_delay),
+            aim_error: mix(easy.aim_error, hard.aim_error),
+            decision_noise: mix(easy.decision_noise, hard.decision_noise),
+        }
+    }
+
+    /// Offset `angle` by up to `aim_error`. `roll` is a uniform sample in
+    /// `[0, 1)` supplied by the game, so bots stay deterministic.
+    pub fn aim(&self, angle: f32, roll: f32) -> f32 {
+        angle + (roll * 2.0 - 1.0) * self.aim_error
+    }
+
+    /// Whether to take a worse option, given a uniform sample in `[0, 1)`.
+    pub fn fumbles(&self, roll: f32) -> bool {
+        roll < self.decision_noise
+    }
+}
+
+/// Skill level of mirror bots, adjusted after each round. Bots that
+/// outscored the humans get easier; bots that were outscored get harder.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MirrorTuner {
+    /// 0 plays like easy, 1 like hard.
+    level: f32,
+}
+
+/// How far one round moves the mirror level.
+const MIRROR_STEP: f32 = 0.25;
+
+impl Default for MirrorTuner {
+    fn default() -> Self {
+        Self { level: 0.5 }
+    }
+}
+
+impl MirrorTuner {
+    pub fn level(&self) -> f32 {
+        self.level
+    }
+
+    pub fn skill(&self) -> BotSkill {
+        BotSkill::lerp(BotSkill::EASY, BotSkill::HARD, self.level)
+    }
+
+    /// Compare the average bot and human scores of a finished round.
+    /// Rounds without both humans and bots leave the level alone.
+    pub fn record_round(&mut self, results: &[PlayerScore], is_bot: impl Fn(PlayerId) -> bool) {
+        let mean = |bots: bool| {
+            let scores: Vec<f32> = results
+                .iter()
+                .filter(|s| !s.disconnected && is_bot(s.player_id) == bots)
+                .map(|s| s.score as f32)
+                .collect();
+            (!scores.is_empty()).then(|| scores.iter().sum::<f32>() / scores.len() as f32)
+        };
+        let (Some(bots), Some(humans)) = (mean(true), mean(false)) else {
+            return;
+        };
+        if humans > bots {
+            self.level = (self.level + MIRROR_STEP).min(1.0);
+        } else if bots > humans {
+            self.level = (self.level - MIRROR_STEP).max(0.0);
+        }
+    }
+}
+
+/// Per-session bot bookkeeping shared by every game: which players are
+/// bots, their skill, and when each may next decide.
+#[derive(Debug, Clone, Default)]
+pub struct BotDriver {
+    difficulties: HashMap<PlayerId, BotDifficulty>,
+    /// Ticks until each bot's next decision, once it has started deciding.
+    cooldowns: HashMap<PlayerId, u32>,
+    mirror: MirrorTuner,
+}
+
+impl BotDriver {
+    pub fn new(players: &[Player]) -> Self {
+        Self {
+            difficulties: players
+                .iter()
+                .filter(|p| p.is_bot)
+                .map(|p| (p.id, p.bot_difficulty))
+                .collect(),
+            cooldowns: HashMap::new(),
+            mirror: MirrorTuner::default(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.difficulties.is_empty()
+    }
+
+    pub fn is_bot(&self, player_id: PlayerId) -> bool {
+        self.difficulties.contains_key(&player_id)
+    }
+
+    /// Bot player ids, in ascending order.
+    pub fn bot_ids(&self) -> Vec<PlayerId> {
+        let mut ids: Vec<_> = self.difficulties.keys().copied().collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    pub fn skill(&self, player_id: PlayerId) -> BotSkill {
+        match self
+            .difficulties
+            .get(&player_id)
+            .copied()
+            .unwrap_or_default()
+        {
+            BotDifficulty::Easy => BotSkill::EASY,
+            BotDifficulty::Normal => BotSkill::NORMAL,
+            BotDifficulty::Hard => BotSkill::HARD,
+            BotDifficulty::Mirror => self.mirror.skill(),
+        }
+    }
+
+    /// Call once per tick per bot. Returns whether the bot decides this
+    /// tick; if not, the game should send it no input.
+    pub fn ready(&mut self, player_id: PlayerId, tick_rate: f32) -> bool {
+        if !self.is_bot(player_id) {
+            return false;
+        }
+        let delay_ticks = (self.skill(player_id).reaction_delay * tick_rate).round() as u32;
+        // Stagger first decisions so slow bots don't all act on the same tick
+        let cooldown = self
+            .cooldowns
+            .entry(player_id)
+            .or_insert(player_id as u32 % (delay_ticks + 1));
+        if *cooldown > 0 {
+            *cooldown -= 1;
+            return false;
+        }
+        *cooldown = delay_ticks;
+        true
+    }
+
+    /// Retune mirror bots from a finished round.
+    pub fn record_round(&mut self, results: &[PlayerScore]) {
+        let difficulties = &self.difficulties;
+        self.mirror
+            .record_round(results, |id| difficulties.contains_key(&id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::make_players;
+
+    fn score(player_id: PlayerId, score: i32) -> PlayerScore {
+        PlayerScore {
+            player_id,
+            score,
+            disconnected: false,
+        }
+    }
+
+    #[test]
+    fn mirror_tracks_the_humans() {
+        let mut tuner = MirrorTuner::default();
+        let is_bot = |id| id == 2;
+        tuner.record_round(&[score(1, 10), score(2, 2)], is_bot);
+        assert_eq!(tuner.level(), 0.75, "Humans ahead, bot gets harder");
+        tuner.record_round(&[score(1, 10), score(2, 2)], is_bot);
+        tuner.record_round(&[score(1, 10), score(2, 2)], is_bot);
+        assert_eq!(tuner.level(), 1.0);
+        assert_eq!(tuner.skill(), BotSkill::HARD);
+
+        tuner.record_round(&[score(1, 1), score(2, 5)], is_bot);
+        assert_eq!(tuner.level(), 0.75);
+        tuner.record_round(&[score(2, 5)], is_bot);
+        assert_eq!(tuner.level(), 0.75, "No humans, no change");
+    }
+
+    #[test]
+    fn reaction_delay_spaces_out_decisions() {
+        let mut players = make_players(3);
+        players[1].is_bot = true;
+        players[1].bot_difficulty = BotDifficulty::Hard;
+        players[2].is_bot = true;
+        players[2].bot_difficulty = BotDifficulty::Easy;
+        let mut driver = BotDriver::new(&players);
+        assert_eq!(driver.bot_ids(), vec![2, 3]);
+        assert!(!driver.is_bot(1));
+
+        let decisions =
+            |driver: &mut BotDriver, id| (0..60).filter(|_| driver.ready(id, 20.0)).count();
+        assert_eq!(decisions(&mut driver, 2), 60, "Hard decides every tick");
+        // Easy waits 7 ticks between decisions, after a staggered start
+        assert_eq!(decisions(&mut driver, 3), 8);
+        assert!(!driver.ready(1, 20.0), "Humans never decide");
+    }
+
+    #[test]
+    fn aim_error_is_bounded() {
+        let skill = BotSkill::NORMAL;
+        assert_eq!(skill.aim(1.0, 0.5), 1.0);
+        assert!((skill.aim(1.0, 0.0) - 0.9).abs() < 1e-6);
+        assert_eq!(BotSkill::HARD.aim(1.0, 0.99), 1.0);
+        assert!(!BotSkill::HARD.fumbles(0.0));
+    }
+}
@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+
+/// Maximum reports accepted in one batch.
+pub const MAX_BATCH_REPORTS: usize = 20;
+
+/// Messages longer than this are truncated by the client and rejected by
+/// the server.
+pub const MAX_REPORT_MESSAGE_LEN: usize = 1024;
+
+/// What went wrong on the client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientReportKind {
+    Panic,
+    /// The WebSocket closed abnormally.
+    Disconnect,
+    /// A server message failed to decode.
+    DecodeError,
+}
+
+/// One crash or error captured by an opted-in client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientReport {
+    pub kind: ClientReportKind,
+    pub message: String,
+    /// App state or game when the error occurred (e.g. `InGame/tron`).
+    #[serde(default)]
+    pub context: String,
+}
+
+/// Body of `POST /api/v1/telemetry`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientReportBatch {
+    /// Random per-page-load ID, so repeats from one client can be grouped
+    /// without identifying the player.
+    pub session_id: String,
+    pub client_version: String,
+    pub reports: Vec<ClientReport>,
+    /// Reports discarded on the client because its queue was full.
+    #[serde(default)]
+    pub dropped: u32,
+}
+
+/// Truncate `message` to [`MAX_REPORT_MESSAGE_LEN`] bytes on a char boundary.
+pub fn truncate_message(message: &mut String) {
+    if message.len() > MAX_REPORT_MESSAGE_LEN {
+        let mut end = MAX_REPORT_MESSAGE_LEN;
+        while !message.is_char_boundary(end) {
+            end -= 1;
+        }
+        message.truncate(end);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_json_roundtrip() {
+        let batch = ClientReportBatch {
+            session_id: "a1b2".to_string(),
+            client_version: "0.1.0".to_string(),
+            reports: vec![ClientReport {
+                kind: ClientReportKind::DecodeError,
+                message: "Failed to decode RoundEnd (12 bytes)".to_string(),
+                context: "InGame/tron".to_string(),
+            }],
+            dropped: 0,
+        };
+        let json = serde_json::to_string(&batch).unwrap();
+        assert!(json.contains("\"decode_error\""));
+        let decoded: ClientReportBatch = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, batch);
+    }
+
+    #[test]
+    fn truncate_respects_char_boundaries() {
+        let mut message = "é".repeat(MAX_REPORT_MESSAGE_LEN);
+        truncate_message(&mut message);
+        assert!(message.len() <= MAX_REPORT_MESSAGE_LEN);
+        assert!(message.chars().all(|c| c == 'é'));
+    }
+}
@@ -0,0 +1,90 @@
+use std::collections::BTreeMap;
+
+use crate::game_trait::PlayerId;
+
+/// Ballots for a pre-round course vote. Each player has one ballot and may
+/// change it until the vote closes.
+#[derive(Debug, Clone)]
+pub struct CourseVote {
+    candidates: u8,
+    ballots: BTreeMap<PlayerId, u8>,
+}
+
+impl CourseVote {
+    pub fn new(candidates: u8) -> Self {
+        Self {
+            candidates,
+            ballots: BTreeMap::new(),
+        }
+    }
+
+    /// Record `player_id`'s ballot, replacing any earlier one. Returns
+    /// false for a choice that is not a candidate.
+    pub fn cast(&mut self, player_id: PlayerId, choice: u8) -> bool {
+        if choice >= self.candidates {
+            return false;
+        }
+        self.ballots.insert(player_id, choice);
+        true
+    }
+
+    /// Drop a departed player's ballot.
+    pub fn withdraw(&mut self, player_id: PlayerId) {
+        self.ballots.remove(&player_id);
+    }
+
+    pub fn has_voted(&self, player_id: PlayerId) -> bool {
+        self.ballots.contains_key(&player_id)
+    }
+
+    /// Votes per candidate, in candidate order.
+    pub fn tally(&self) -> Vec<u8> {
+        let mut votes = vec![0u8; self.candidates as usize];
+        for &choice in self.ballots.values() {
+            votes[choice as usize] = votes[choice as usize].saturating_add(1);
+        }
+        votes
+    }
+
+    /// The most-voted candidate. Ties, including a vote nobody cast, go to
+    /// the earliest candidate.
+    pub fn winner(&self) -> u8 {
+        let votes = self.tally();
+        let mut best = 0;
+        for (i, &count) in votes.iter().enumerate() {
+            if count > votes[best] {
+                best = i;
+            }
+        }
+        best as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn most_votes_wins_and_ballots_can_change() {
+        let mut vote = CourseVote::new(3);
+        assert!(vote.cast(1, 2));
+        assert!(vote.cast(2, 1));
+        assert!(vote.cast(3, 2));
+        assert!(!vote.cast(4, 3), "Not a candidate");
+        assert_eq!(vote.tally(), vec![0, 1, 2]);
+        assert_eq!(vote.winner(), 2);
+
+        vote.cast(3, 1);
+        assert_eq!(vote.tally(), vec![0, 2, 1]);
+        assert_eq!(vote.winner(), 1);
+
+        vote.withdraw(2);
+        assert!(!vote.has_voted(2));
+        assert_eq!(vote.winner(), 1, "Ties go to the earliest candidate");
+    }
+
+    #[test]
+    fn empty_vote_picks_first_candidate() {
+        assert_eq!(CourseVote::new(3).winner(), 0);
+    }
+}
@@ -0,0 +1,61 @@
+//! Host debug commands for manual testing of late-round logic. Servers
+//! only act on them when built with the `debug-commands` feature and
+//! configured to allow them, and then only for a limited time per room.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::game_trait::PlayerId;
+
+/// A god-mode command a game applies to its running round.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum DebugCommand {
+    /// Move a player. `y` is height in the platformer and depth (z) in
+    /// the top-down games.
+    Teleport { player_id: PlayerId, x: f32, y: f32 },
+    /// Give a player a power-up, named as in the game's power-up enum
+    /// (e.g. `"SpeedBoots"`).
+    GrantPowerUp { player_id: PlayerId, kind: String },
+    /// End the round now, scored as it stands.
+    ForceRoundEnd,
+    /// Set the round clock to `elapsed_secs` into the round.
+    SetRoundTimer { elapsed_secs: f32 },
+}
+
+/// Why a game could not apply a [`DebugCommand`].
+pub type DebugResult = Result<(), String>;
+
+/// Parse a power-up name for [`DebugCommand::GrantPowerUp`] into a game's
+/// power-up enum.
+pub fn parse_kind<T: DeserializeOwned>(name: &str) -> Result<T, String> {
+    serde_json::from_value(serde_json::Value::String(name.to_string()))
+        .map_err(|_| format!("Unknown power-up: {name}"))
+}
+
+/// A clock value [`DebugCommand::SetRoundTimer`] can set.
+pub fn checked_elapsed(elapsed_secs: f32) -> Result<f32, String> {
+    if elapsed_secs.is_finite() && elapsed_secs >= 0.0 {
+        Ok(elapsed_secs)
+    } else {
+        Err(format!("Invalid round time: {elapsed_secs}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    enum Kind {
+        Fast,
+    }
+
+    #[test]
+    fn helpers_reject_bad_values() {
+        assert_eq!(parse_kind::<Kind>("Fast"), Ok(Kind::Fast));
+        assert!(parse_kind::<Kind>("Slow").is_err());
+        assert_eq!(checked_elapsed(12.5), Ok(12.5));
+        assert!(checked_elapsed(-1.0).is_err());
+        assert!(checked_elapsed(f32::NAN).is_err());
+    }
+}
@@ -0,0 +1,154 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::events::Event;
+
+/// Boxed future returned by [`EventSource`] methods, so the trait stays
+/// object-safe without an async-trait dependency.
+pub type SourceFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// An external system polled for events (CI runs, issue trackers, error
+/// monitors). The server owns the polling loop: it calls [`start`] once,
+/// then [`poll`] every [`poll_interval`] until the source is stopped, and
+/// publishes the status of each source.
+///
+/// [`start`]: EventSource::start
+/// [`poll`]: EventSource::poll
+/// [`poll_interval`]: EventSource::poll_interval
+pub trait EventSource: Send {
+    /// Stable identifier, used as the event `source` and in status reports
+    /// (e.g. `github-actions`).
+    fn name(&self) -> &str;
+
+    /// JSON Schema for the source's configuration block.
+    fn config_schema(&self) -> serde_json::Value;
+
+    /// Time between polls.
+    fn poll_interval(&self) -> Duration;
+
+    /// Prepare the source (validate credentials, prime caches). A failure
+    /// is reported and retried on the next interval.
+    fn start(&mut self) -> SourceFuture<'_, Result<(), String>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Fetch events that appeared since the last poll.
+    fn poll(&mut self) -> SourceFuture<'_, Result<Vec<Event>, String>>;
+
+    /// Release resources. Called once when the source is removed or the
+    /// server shuts down.
+    fn stop(&mut self) {}
+
+    /// The source's own view of its health, e.g. `Degraded` while rate
+    /// limited. Poll failures are tracked separately by the server.
+    fn health(&self) -> SourceHealth {
+        SourceHealth::Healthy
+    }
+}
+
+/// Health of an event source, worst last.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceHealth {
+    /// Started but not yet polled successfully.
+    #[default]
+    Starting,
+    Healthy,
+    /// Recent polls failed or the source reported a problem.
+    Degraded,
+    /// Repeated failures; no events are arriving.
+    Unhealthy,
+    Stopped,
+}
+
+/// Published state of one registered source.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceStatus {
+    pub name: String,
+    pub health: SourceHealth,
+    pub poll_interval_secs: u64,
+    /// Unix seconds of the last completed poll, successful or not.
+    pub last_poll_at: Option<u64>,
+    /// Unix seconds of the last successful poll.
+    pub last_success_at: Option<u64>,
+    pub last_error: Option<String>,
+    pub consecutive_failures: u32,
+    pub events_emitted: u64,
+}
+
+/// Consecutive poll failures before a source is reported unhealthy.
+pub const UNHEALTHY_AFTER_FAILURES: u32 = 3;
+
+impl SourceStatus {
+    pub fn new(name: &str, poll_interval: Duration) -> Self {
+        Self {
+            name: name.to_string(),
+            health: SourceHealth::Starting,
+            poll_interval_secs: poll_interval.as_secs(),
+            last_poll_at: None,
+            last_success_at: None,
+            last_error: None,
+            consecutive_failures: 0,
+            events_emitted: 0,
+        }
+    }
+
+    /// Record a successful poll at `now` that produced `events` events.
+    /// `reported` is the source's own health.
+    pub fn record_success(&mut self, now: u64, events: usize, reported: SourceHealth) {
+        self.last_poll_at = Some(now);
+        self.last_success_at = Some(now);
+        self.last_error = None;
+        self.consecutive_failures = 0;
+        self.events_emitted += events as u64;
+        self.health = reported.max(SourceHealth::Healthy);
+    }
+
+    /// Record a failed start or poll at `now`.
+    pub fn record_failure(&mut self, now: u64, error: String, reported: SourceHealth) {
+        self.last_poll_at = Some(now);
+        self.last_error = Some(error);
+        self.consecutive_failures += 1;
+        let from_failures = if self.consecutive_failures >= UNHEALTHY_AFTER_FAILURES {
+            SourceHealth::Unhealthy
+        } else {
+            SourceHealth::Degraded
+        };
+        self.health = reported.max(from_failures);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failures_degrade_then_mark_unhealthy() {
+        let mut status = SourceStatus::new("ci", Duration::from_secs(30));
+        assert_eq!(status.health, SourceHealth::Starting);
+
+        status.record_success(100, 4, SourceHealth::Healthy);
+        assert_eq!(status.health, SourceHealth::Healthy);
+        assert_eq!(status.events_emitted, 4);
+
+        for i in 1..UNHEALTHY_AFTER_FAILURES {
+            status.record_failure(100 + i as u64, "timeout".to_string(), SourceHealth::Healthy);
+            assert_eq!(status.health, SourceHealth::Degraded);
+        }
+        status.record_failure(200, "timeout".to_string(), SourceHealth::Healthy);
+        assert_eq!(status.health, SourceHealth::Unhealthy);
+        assert_eq!(status.last_success_at, Some(100));
+
+        status.record_success(300, 0, SourceHealth::Degraded);
+        assert_eq!(
+            status.health,
+            SourceHealth::Degraded,
+            "Source's own report wins"
+        );
+        assert_eq!(status.consecutive_failures, 0);
+        assert!(status.last_error.is_none());
+    }
+}
@@ -0,0 +1,170 @@
+//! Typed per-game lobby settings. Each game reads `GameConfig::custom`
+//! through a settings struct implementing [`FromGameConfig`], so a bad
+//! value is rejected with the offending key instead of silently falling
+//! back to a default. Servers validate a lobby's settings when the game
+//! is requested, before any round starts.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::game_trait::GameConfig;
+
+/// Raw lobby settings as carried by `GameConfig::custom`.
+pub type CustomSettings = HashMap<String, serde_json::Value>;
+
+/// A lobby setting that could not be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SettingsError {
+    pub key: String,
+    pub reason: String,
+}
+
+impl SettingsError {
+    pub fn new(key: &str, reason: impl Into<String>) -> Self {
+        Self {
+            key: key.to_string(),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid setting `{}`: {}", self.key, self.reason)
+    }
+}
+
+impl std::error::Error for SettingsError {}
+
+/// A game's settings, read from lobby `custom` values. Missing keys take
+/// their defaults and keys the game does not know are ignored, since the
+/// server adds its own (round index, vote seeds) between rounds.
+pub trait FromGameConfig: Sized {
+    fn from_custom(custom: &CustomSettings) -> Result<Self, SettingsError>;
+
+    fn from_game_config(config: &GameConfig) -> Result<Self, SettingsError> {
+        Self::from_custom(&config.custom)
+    }
+
+    /// Check `custom` without keeping the result, for rejecting a lobby's
+    /// settings before a game starts.
+    fn validate(custom: &CustomSettings) -> Result<(), SettingsError> {
+        Self::from_custom(custom).map(|_| ())
+    }
+}
+
+/// Typed access to lobby settings. Each getter returns `Ok(None)` for a
+/// missing or `null` key and an error for a value of the wrong kind.
+#[derive(Debug, Clone, Copy)]
+pub struct SettingsReader<'a> {
+    custom: &'a CustomSettings,
+}
+
+impl<'a> SettingsReader<'a> {
+    pub fn new(custom: &'a CustomSettings) -> Self {
+        Self { custom }
+    }
+
+    fn value(&self, key: &str) -> Option<&'a serde_json::Value> {
+        self.custom.get(key).filter(|v| !v.is_null())
+    }
+
+    pub fn bool(&self, key: &str) -> Result<Option<bool>, SettingsError> {
+        self.value(key)
+            .map(|v| {
+                v.as_bool()
+                    .ok_or_else(|| SettingsError::new(key, "expected true or false"))
+            })
+            .transpose()
+    }
+
+    pub fn u64(&self, key: &str) -> Result<Option<u64>, SettingsError> {
+        self.value(key)
+            .map(|v| {
+                v.as_u64()
+                    .ok_or_else(|| SettingsError::new(key, "expected a whole number"))
+            })
+            .transpose()
+    }
+
+    /// A number within `min..=max`.
+    pub fn f32_in(&self, key: &str, min: f32, max: f32) -> Result<Option<f32>, SettingsError> {
+        self.value(key)
+            .map(|v| {
+                let n = v
+                    .as_f64()
+                    .ok_or_else(|| SettingsError::new(key, "expected a number"))?
+                    as f32;
+                if (min..=max).contains(&n) {
+                    Ok(n)
+                } else {
+                    Err(SettingsError::new(
+                        key,
+                        format!("{n} is outside {min} to {max}"),
+                    ))
+                }
+            })
+            .transpose()
+    }
+
+    /// One of the named `options`.
+    pub fn choice<T: Copy>(
+        &self,
+        key: &str,
+        options: &[(&str, T)],
+    ) -> Result<Option<T>, SettingsError> {
+        self.value(key)
+            .map(|v| {
+                let name = v
+                    .as_str()
+                    .ok_or_else(|| SettingsError::new(key, "expected a string"))?;
+                options
+                    .iter()
+                    .find(|(option, _)| *option == name)
+                    .map(|&(_, value)| value)
+                    .ok_or_else(|| {
+                        let names: Vec<&str> = options.iter().map(|(option, _)| *option).collect();
+                        SettingsError::new(
+                            key,
+                            format!("`{name}` is not one of {}", names.join(", ")),
+                        )
+                    })
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reader_checks_kinds_and_ranges() {
+        let custom: CustomSettings = [
+            ("flag".to_string(), serde_json::json!(true)),
+            ("count".to_string(), serde_json::json!(3)),
+            ("secs".to_string(), serde_json::json!(1.5)),
+            ("mode".to_string(), serde_json::json!("b")),
+            ("unset".to_string(), serde_json::Value::Null),
+        ]
+        .into();
+        let reader = SettingsReader::new(&custom);
+        let modes = [("a", 1), ("b", 2)];
+
+        assert_eq!(reader.bool("flag"), Ok(Some(true)));
+        assert_eq!(reader.u64("count"), Ok(Some(3)));
+        assert_eq!(reader.f32_in("secs", 0.0, 2.0), Ok(Some(1.5)));
+        assert_eq!(reader.choice("mode", &modes), Ok(Some(2)));
+        assert_eq!(reader.bool("missing"), Ok(None));
+        assert_eq!(reader.bool("unset"), Ok(None));
+
+        assert_eq!(reader.bool("count").unwrap_err().key, "count");
+        assert!(reader.u64("secs").is_err());
+        assert!(reader.f32_in("secs", 0.0, 1.0).is_err());
+        let err = reader.choice("mode", &[("a", 1)]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid setting `mode`: `b` is not one of a"
+        );
+    }
+}
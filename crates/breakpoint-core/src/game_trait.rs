@@ -5,6 +5,13 @@ use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
+use crate::debug_command::{DebugCommand, DebugResult};
+use crate::game_settings::{CustomSettings, SettingsReader};
+use crate::heatmap::RoundHeatmap;
+use crate::pause::PauseReason;
+use crate::tutorial::TutorialObjective;
+use crate::victory::VictorySummary;
+
 /// Unique identifier for a player in the game.
 pub type PlayerId = u64;
 
@@ -72,12 +79,54 @@ pub trait BreakpointGame: Send + Sync {
         buf.extend_from_slice(&self.serialize_state());
     }
 
+    /// Whether players each get their own view of the state (e.g. fog of
+    /// war) instead of the shared broadcast. Hosts then send every player
+    /// `serialize_state_for_into` and spectators the full state, or
+    /// `spectator_view_state` when the game has one.
+    fn has_player_views(&self) -> bool {
+        false
+    }
+
+    /// Serialize the state as `viewer` may see it into a reusable buffer.
+    /// Only called when `has_player_views()` is true. Default
+    /// implementation sends the full state.
+    fn serialize_state_for_into(&self, _viewer: PlayerId, buf: &mut Vec<u8>) {
+        self.serialize_state_into(buf);
+    }
+
+    /// The state as spectators see it, when it differs from what players
+    /// get: the same state type with spectator-only data filled in, such as
+    /// [`SpectatorCues`](crate::spectator::SpectatorCues) or a casting
+    /// overlay. Hosts call it once per tick while the room has spectators
+    /// and send it to each of them in place of the shared state; players,
+    /// replays, and result reporters never see it. Default is `None`, and
+    /// spectators get the shared state.
+    fn spectator_view_state(&self) -> Option<Vec<u8>> {
+        None
+    }
+
     /// Apply authoritative state received from the host.
     fn apply_state(&mut self, state: &[u8]);
 
+    /// Decode a serialized state, as `apply_state` receives it, into a tree
+    /// with named fields for debugging tools such as the state diff
+    /// inspector. Default is `None` for games that can't be inspected.
+    fn inspect_state(&self, _state: &[u8]) -> Option<InspectedState> {
+        None
+    }
+
     /// Apply a remote player's input to the authoritative simulation.
     fn apply_input(&mut self, player_id: PlayerId, input: &[u8]);
 
+    /// Reduce an input payload to the discrete actions it carries (buttons
+    /// held, movement direction) for the server's input statistics. Clients
+    /// send input every frame, so continuous fields such as an aim angle must
+    /// be left out or every frame would count as an action. Default treats
+    /// the whole payload as discrete.
+    fn input_action_key(&self, input: &[u8]) -> Vec<u8> {
+        input.to_vec()
+    }
+
     /// Called when a new player joins mid-game.
     fn player_joined(&mut self, player: &super::player::Player);
 
@@ -95,16 +144,50 @@ pub trait BreakpointGame: Send + Sync {
         1
     }
 
+    /// Number of rounds for a session started with these lobby settings,
+    /// for games whose modes change the match length. Defaults to
+    /// `round_count_hint`.
+    fn round_count_for(&self, custom: &HashMap<String, serde_json::Value>) -> u8 {
+        let _ = custom;
+        self.round_count_hint()
+    }
+
+    /// Number of teams a session started with these lobby settings plays
+    /// in, or 0 for free-for-all. Hosts only run a captain draft for team
+    /// games.
+    fn team_count_for(&self, custom: &HashMap<String, serde_json::Value>) -> u8 {
+        let _ = custom;
+        0
+    }
+
     /// Whether the game supports the overlay pausing gameplay.
     fn supports_pause(&self) -> bool {
         true
     }
 
+    /// Pause gameplay for `reason`. Reasons stack, so the game stays paused
+    /// until every active reason has been resumed.
+    fn pause_for(&mut self, reason: PauseReason);
+
+    /// Clear `reason`. Gameplay resumes only when no other reason is active.
+    fn resume_for(&mut self, reason: PauseReason);
+
+    /// Active pause reasons; empty while the game is running.
+    fn pause_reasons(&self) -> &[PauseReason];
+
+    fn is_paused(&self) -> bool {
+        !self.pause_reasons().is_empty()
+    }
+
     /// Called when the overlay requests a pause (critical alert).
-    fn pause(&mut self);
+    fn pause(&mut self) {
+        self.pause_for(PauseReason::Alert);
+    }
 
-    /// Called when gameplay should resume after a pause.
-    fn resume(&mut self);
+    /// Called when gameplay should resume after an alert pause.
+    fn resume(&mut self) {
+        self.resume_for(PauseReason::Alert);
+    }
 
     /// Whether the current round/match is complete.
     fn is_round_complete(&self) -> bool;
@@ -112,6 +195,24 @@ pub trait BreakpointGame: Send + Sync {
     /// Final scores for the completed round.
     fn round_results(&self) -> Vec<PlayerScore>;
 
+    /// Each player's team in team modes; empty in free-for-all. Used for
+    /// the scoreboard's team totals.
+    fn teams(&self) -> HashMap<PlayerId, u8> {
+        HashMap::new()
+    }
+
+    /// Who won the completed round and what it came down to, for the
+    /// announcer banner. Defaults to the top scorers in `round_results`.
+    fn victory_summary(&self) -> VictorySummary {
+        VictorySummary::from_scores(&self.round_results())
+    }
+
+    /// Where players spent the completed round and where tags landed, for
+    /// games that track it. Sent with the round results.
+    fn round_heatmap(&self) -> Option<RoundHeatmap> {
+        None
+    }
+
     /// Return course/map data if it changed since the last call.
     /// Used for games with large static map data (e.g. platformer) that should
     /// be sent separately from per-tick state. Returns `None` when unchanged.
@@ -123,6 +224,78 @@ pub trait BreakpointGame: Send + Sync {
     /// Default is a no-op for games without separate course data.
     fn apply_course_data(&mut self, _data: &[u8]) {}
 
+    /// Return one-shot visual effects (e.g. laser shots) produced since the
+    /// last call. They are sent to clients once in a `GameEffects` message
+    /// instead of riding along in every state snapshot. Returns `None` when
+    /// there are none.
+    fn take_effects(&mut self) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Apply effects received from the server.
+    /// Default is a no-op for games without one-shot effects.
+    fn apply_effects(&mut self, _data: &[u8]) {}
+
+    /// Data only `player_id` may see (a caddie hint, a hidden role). Hosts
+    /// send it after every tick's state in a `PrivateState` message to that
+    /// player's connection alone; spectators, replays, and other players
+    /// never get it. Keep such data out of the serialized state entirely,
+    /// or every view of the state leaks it. Returns `None` for nothing to
+    /// send; games whose private data can go away should send its absence.
+    fn private_state_for(&self, _player_id: PlayerId) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Apply `player_id`'s private data received from the host.
+    /// Default is a no-op for games without private data.
+    fn apply_private_state(&mut self, _player_id: PlayerId, _data: &[u8]) {}
+
+    /// Apply a host debug command to the running round. Games support
+    /// the commands that make sense for them and refuse the rest.
+    fn apply_debug(&mut self, command: &DebugCommand) -> DebugResult {
+        Err(format!("{command:?} is not supported by this game"))
+    }
+
+    /// Settings this game accepts mid-round through
+    /// [`apply_config_change`](Self::apply_config_change), from the
+    /// `LIVE_*` keys. None by default.
+    fn live_config_keys(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Change settings in the running round without re-initializing it.
+    /// Changes last until the round ends; the next round starts from the
+    /// lobby settings. A delta with any key outside
+    /// [`live_config_keys`](Self::live_config_keys) is refused whole.
+    fn apply_config_change(&mut self, delta: &GameConfigDelta) -> Result<(), String> {
+        delta.check_keys(self.live_config_keys())
+    }
+
+    /// What this game's tutorial asks a new player to do. None by default,
+    /// for games without a tutorial.
+    fn tutorial_objectives(&self) -> &'static [TutorialObjective] {
+        &[]
+    }
+
+    /// IDs of the [`tutorial_objectives`](Self::tutorial_objectives)
+    /// `player_id` has met in the round so far, judged from the game's
+    /// state. Checked after every tick of a tutorial session.
+    fn tutorial_objectives_met(&self, _player_id: PlayerId) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    /// Rough heap held by the collections that grow while a round is
+    /// played, for the server's per-room memory accounting.
+    fn memory_usage(&self) -> GameMemory {
+        GameMemory::default()
+    }
+
+    /// Drop cosmetic or rebuildable data when the room is over its memory
+    /// budget. Returns roughly how many bytes were freed.
+    fn trim_memory(&mut self) -> usize {
+        0
+    }
+
     /// Downcast to concrete type for zero-copy state access.
     fn as_any(&self) -> &dyn Any;
 }
@@ -138,11 +311,138 @@ pub struct GameMetadata {
 }
 
 /// Configuration for a game session.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GameConfig {
     pub round_count: u8,
     pub round_duration: Duration,
     pub custom: HashMap<String, serde_json::Value>,
+    /// Off during a warmup: the round plays as usual but nothing in it
+    /// counts, so games emit no [`GameEvent::is_scoring`] events.
+    #[serde(default = "scoring_default")]
+    pub scoring: bool,
+}
+
+fn scoring_default() -> bool {
+    true
+}
+
+impl GameConfig {
+    /// Start a config of one 90-second round with no lobby settings.
+    pub fn builder() -> GameConfigBuilder {
+        GameConfigBuilder {
+            config: GameConfig {
+                round_count: 1,
+                round_duration: Duration::from_secs(90),
+                custom: HashMap::new(),
+                scoring: true,
+            },
+        }
+    }
+}
+
+/// Builds a [`GameConfig`] for a round.
+#[derive(Debug, Clone)]
+pub struct GameConfigBuilder {
+    config: GameConfig,
+}
+
+impl GameConfigBuilder {
+    pub fn round_count(mut self, round_count: u8) -> Self {
+        self.config.round_count = round_count;
+        self
+    }
+
+    pub fn round_duration(mut self, round_duration: Duration) -> Self {
+        self.config.round_duration = round_duration;
+        self
+    }
+
+    /// Replace the lobby settings.
+    pub fn custom(mut self, custom: HashMap<String, serde_json::Value>) -> Self {
+        self.config.custom = custom;
+        self
+    }
+
+    /// Set one lobby setting, replacing any earlier value.
+    pub fn setting(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.config.custom.insert(key.to_string(), value.into());
+        self
+    }
+
+    /// Whether the round's scores count; see [`GameConfig::scoring`].
+    pub fn scoring(mut self, scoring: bool) -> Self {
+        self.config.scoring = scoring;
+        self
+    }
+
+    pub fn build(self) -> GameConfig {
+        self.config
+    }
+}
+
+/// Live setting: seconds added to the running round's clock.
+pub const LIVE_EXTEND_ROUND: &str = "extend_round_secs";
+/// Live setting: multiplier on the game's hazard speed (1.0 is normal).
+pub const LIVE_HAZARD_SPEED: &str = "hazard_speed";
+/// Live setting: whether power-ups can be picked up.
+pub const LIVE_POWERUPS: &str = "powerups";
+
+/// Range [`LIVE_EXTEND_ROUND`] accepts, in seconds.
+pub const EXTEND_ROUND_RANGE: (f32, f32) = (1.0, 600.0);
+/// Range [`LIVE_HAZARD_SPEED`] accepts.
+pub const HAZARD_SPEED_RANGE: (f32, f32) = (0.25, 4.0);
+
+/// A mid-round settings change from the host, keyed like lobby settings.
+/// See [`BreakpointGame::apply_config_change`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GameConfigDelta {
+    pub changes: CustomSettings,
+}
+
+impl GameConfigDelta {
+    /// Add one change, replacing any earlier value for `key`.
+    pub fn set(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.changes.insert(key.to_string(), value.into());
+        self
+    }
+
+    pub fn reader(&self) -> SettingsReader<'_> {
+        SettingsReader::new(&self.changes)
+    }
+
+    /// Refuse an empty delta or one that changes a key outside `accepted`.
+    pub fn check_keys(&self, accepted: &[&str]) -> Result<(), String> {
+        if self.changes.is_empty() {
+            return Err("No settings to change".to_string());
+        }
+        let mut keys: Vec<&String> = self.changes.keys().collect();
+        keys.sort();
+        match keys.into_iter().find(|k| !accepted.contains(&k.as_str())) {
+            Some(key) => Err(format!("`{key}` cannot be changed mid-round")),
+            None => Ok(()),
+        }
+    }
+
+    /// Seconds to add to the round clock, if the delta extends it.
+    pub fn round_extension(&self) -> Result<Option<f32>, String> {
+        let (min, max) = EXTEND_ROUND_RANGE;
+        self.reader()
+            .f32_in(LIVE_EXTEND_ROUND, min, max)
+            .map_err(|e| e.to_string())
+    }
+
+    /// New hazard speed multiplier, if the delta sets one.
+    pub fn hazard_speed(&self) -> Result<Option<f32>, String> {
+        let (min, max) = HAZARD_SPEED_RANGE;
+        self.reader()
+            .f32_in(LIVE_HAZARD_SPEED, min, max)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Whether power-ups are turned on or off, if the delta says.
+    pub fn powerups(&self) -> Result<Option<bool>, String> {
+        self.reader().bool(LIVE_POWERUPS).map_err(|e| e.to_string())
+    }
 }
 
 /// Collected inputs from all players for a single tick.
@@ -153,22 +453,106 @@ pub struct PlayerInputs {
 /// Events emitted by a game during update (scoring, elimination, round end).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum GameEvent {
-    ScoreUpdate { player_id: PlayerId, score: i32 },
+    ScoreUpdate {
+        player_id: PlayerId,
+        score: i32,
+    },
     RoundComplete,
+    /// Golf ball sunk on the first stroke of a hole.
+    HoleInOne {
+        player_id: PlayerId,
+    },
+    /// Golf: `streak` consecutive holes finished under par (2 or more).
+    BirdieStreak {
+        player_id: PlayerId,
+        streak: u32,
+    },
+    /// Golf ball sunk by a stroke that bounced off `bounces` walls.
+    ChipIn {
+        player_id: PlayerId,
+        bounces: u8,
+    },
+    /// A round ended with a single winner.
+    RoundWon {
+        player_id: PlayerId,
+    },
+    /// A round ended with nobody left standing: `player_ids` were eliminated
+    /// together on the final tick.
+    RoundDraw {
+        player_ids: Vec<PlayerId>,
+    },
+    /// A draw went to sudden-death overtime between `player_ids`.
+    SuddenDeath {
+        player_ids: Vec<PlayerId>,
+    },
+    /// A player picked up a power-up; `kind` is its
+    /// [`PowerUpKind::name`](crate::powerup::PowerUpKind::name).
+    PowerupCollected {
+        player_id: PlayerId,
+        kind: String,
+    },
+    /// A timed power-up wore off. Power-ups used up by an action (a
+    /// deployed turret, a broken shield) end without one.
+    PowerupExpired {
+        player_id: PlayerId,
+        kind: String,
+    },
+}
+
+impl GameEvent {
+    /// Events that award or celebrate points. Games leave them out of
+    /// rounds that do not score (see [`GameConfig::scoring`]).
+    pub fn is_scoring(&self) -> bool {
+        matches!(
+            self,
+            Self::ScoreUpdate { .. }
+                | Self::HoleInOne { .. }
+                | Self::BirdieStreak { .. }
+                | Self::ChipIn { .. }
+                | Self::RoundWon { .. }
+        )
+    }
 }
 
 /// Score entry for a player at the end of a round.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PlayerScore {
     pub player_id: PlayerId,
     pub score: i32,
+    /// The player left before the round ended; the score is what they had
+    /// earned by then.
+    #[serde(default)]
+    pub disconnected: bool,
+}
+
+/// Heap a game holds in collections that grow during a round, in bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameMemory {
+    /// Walls and trail segments the simulation collides against.
+    pub walls_bytes: usize,
+    /// Cosmetic trails and effects, which `trim_memory` may drop.
+    pub trails_bytes: usize,
 }
 
-/// Generates the 5 boilerplate `BreakpointGame` methods that are identical across all games:
-/// `serialize_state`, `apply_state`, `pause`, `resume`, `is_round_complete`.
+/// A game state decoded for inspection: a JSON tree with named fields.
+pub type InspectedState = serde_json::Value;
+
+/// Decode MessagePack state bytes as `T` and re-encode them as JSON, so
+/// struct fields are named and player maps are keyed by ID. Used by
+/// `BreakpointGame::inspect_state` implementations.
+pub fn inspect_state<T: serde::de::DeserializeOwned + Serialize>(
+    state: &[u8],
+) -> Option<InspectedState> {
+    let decoded: T = rmp_serde::from_slice(state).ok()?;
+    serde_json::to_value(&decoded).ok()
+}
+
+/// Generates the boilerplate `BreakpointGame` methods that are identical across all games:
+/// `serialize_state`, `apply_state`, `inspect_state`, `pause_for`, `resume_for`, `pause_reasons`,
+/// `is_round_complete`.
 ///
-/// Requires the implementing struct to have `state: $StateType` and `paused: bool` fields,
-/// and `$StateType` to have a `round_complete: bool` field.
+/// Requires the implementing struct to have a `state: $StateType` field, and
+/// `$StateType` to have `round_complete: bool` and `pause: PauseState` fields.
 #[macro_export]
 macro_rules! breakpoint_game_boilerplate {
     (state_type: $StateType:ty) => {
@@ -188,12 +572,20 @@ macro_rules! breakpoint_game_boilerplate {
             }
         }
 
-        fn pause(&mut self) {
-            self.paused = true;
+        fn inspect_state(&self, state: &[u8]) -> Option<$crate::game_trait::InspectedState> {
+            $crate::game_trait::inspect_state::<$StateType>(state)
+        }
+
+        fn pause_for(&mut self, reason: $crate::pause::PauseReason) {
+            self.state.pause.add(reason);
+        }
+
+        fn resume_for(&mut self, reason: $crate::pause::PauseReason) {
+            self.state.pause.remove(reason);
         }
 
-        fn resume(&mut self) {
-            self.paused = false;
+        fn pause_reasons(&self) -> &[$crate::pause::PauseReason] {
+            self.state.pause.reasons()
         }
 
         fn is_round_complete(&self) -> bool {
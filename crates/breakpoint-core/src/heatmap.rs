@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+
+/// Most cells per side of the grid sent with round results.
+pub const HEATMAP_MAX_CELLS: usize = 32;
+
+/// Where players spent a round and where tags landed, downsampled to at
+/// most `HEATMAP_MAX_CELLS` per side. Cells are row-major from the arena's
+/// min corner; row `r`, column `c` covers
+/// `x in [c, c + 1) * width / cols`, `z in [r, r + 1) * depth / rows`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoundHeatmap {
+    pub width: f32,
+    pub depth: f32,
+    pub cols: u8,
+    pub rows: u8,
+    /// Time spent in each cell, scaled so the busiest cell is 255.
+    pub presence: Vec<u8>,
+    /// Tags landed in each cell.
+    pub tags: Vec<u16>,
+}
+
+/// Host-side accumulator for one round's heatmap. Samples land in cells of
+/// `cell_size` world units and are only downsampled when the round ends.
+#[derive(Debug, Clone)]
+pub struct HeatmapAccumulator {
+    width: f32,
+    depth: f32,
+    cols: usize,
+    rows: usize,
+    presence: Vec<f32>,
+    tags: Vec<u32>,
+}
+
+impl HeatmapAccumulator {
+    pub fn new(width: f32, depth: f32, cell_size: f32) -> Self {
+        let cols = ((width / cell_size).ceil() as usize).max(1);
+        let rows = ((depth / cell_size).ceil() as usize).max(1);
+        Self {
+            width,
+            depth,
+            cols,
+            rows,
+            presence: vec![0.0; cols * rows],
+            tags: vec![0; cols * rows],
+        }
+    }
+
+    /// Cell index for a world position. Positions outside the arena are
+    /// clamped to the edge cells; non-finite ones are dropped.
+    fn cell(&self, x: f32, z: f32) -> Option<usize> {
+        if !x.is_finite() || !z.is_finite() || self.width <= 0.0 || self.depth <= 0.0 {
+            return None;
+        }
+        let c = ((x / self.width * self.cols as f32) as usize).min(self.cols - 1);
+        let r = ((z / self.depth * self.rows as f32) as usize).min(self.rows - 1);
+        Some(r * self.cols + c)
+    }
+
+    /// Credit `secs` of presence at a position.
+    pub fn record_presence(&mut self, x: f32, z: f32, secs: f32) {
+        if let Some(i) = self.cell(x, z) {
+            self.presence[i] += secs;
+        }
+    }
+
+    /// Record a tag landing at a position.
+    pub fn record_tag(&mut self, x: f32, z: f32) {
+        if let Some(i) = self.cell(x, z) {
+            self.tags[i] = self.tags[i].saturating_add(1);
+        }
+    }
+
+    /// Downsample to the grid sent with round results.
+    pub fn finish(&self) -> RoundHeatmap {
+        let step = self.cols.max(self.rows).div_ceil(HEATMAP_MAX_CELLS);
+        let cols = self.cols.div_ceil(step);
+        let rows = self.rows.div_ceil(step);
+        let mut presence = vec![0.0f32; cols * rows];
+        let mut tags = vec![0u32; cols * rows];
+        for r in 0..self.rows {
+            for c in 0..self.cols {
+                let src = r * self.cols + c;
+                let dst = (r / step) * cols + c / step;
+                presence[dst] += self.presence[src];
+                tags[dst] += self.tags[src];
+            }
+        }
+        let busiest = presence.iter().copied().fold(0.0f32, f32::max);
+        RoundHeatmap {
+            width: self.width,
+            depth: self.depth,
+            cols: cols as u8,
+            rows: rows as u8,
+            presence: presence
+                .iter()
+                .map(|&p| {
+                    if busiest > 0.0 {
+                        (p / busiest * 255.0).round() as u8
+                    } else {
+                        0
+                    }
+                })
+                .collect(),
+            tags: tags
+                .iter()
+                .map(|&t| t.min(u32::from(u16::MAX)) as u16)
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finish_downsamples_and_normalizes() {
+        let mut acc = HeatmapAccumulator::new(100.0, 50.0, 1.0);
+        acc.record_presence(0.5, 0.5, 2.0);
+        acc.record_presence(1.5, 1.5, 2.0);
+        acc.record_presence(99.0, 49.0, 1.0);
+        acc.record_tag(1.0, 1.0);
+        acc.record_tag(500.0, -3.0);
+        acc.record_presence(f32::NAN, 0.0, 10.0);
+
+        let map = acc.finish();
+        assert_eq!((map.cols, map.rows), (25, 13));
+        assert_eq!(map.presence.len(), 25 * 13);
+        assert_eq!(map.presence[0], 255, "Busiest cell is full scale");
+        assert_eq!(map.presence[12 * 25 + 24], 64);
+        assert_eq!(map.tags[0], 1);
+        assert_eq!(map.tags[24], 1, "Out-of-bounds tags clamp to the edge");
+        assert_eq!(map.tags.iter().sum::<u16>(), 2);
+    }
+
+    #[test]
+    fn small_arenas_are_not_upsampled() {
+        let map = HeatmapAccumulator::new(10.0, 8.0, 1.0).finish();
+        assert_eq!((map.cols, map.rows), (10, 8));
+        assert!(map.presence.iter().all(|&p| p == 0));
+    }
+}
@@ -0,0 +1,389 @@
+//! Message codes for user-facing text.
+//!
+//! The server never sends display strings to players (alert titles excepted,
+//! since they come verbatim from external sources). Instead it sends a
+//! [`LocalizedText`] — a stable key plus named parameters — and the client
+//! looks the key up in its locale files (`web/locales/<lang>.json`), which
+//! use `{name}` placeholders for parameters.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Stable identifier for a piece of user-facing text.
+///
+/// Serialized as its dotted key (e.g. `"error.room_full"`), which is also the
+/// key in every locale file. Never rename a key; add a new one instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TextKey {
+    // Join errors (server-sent)
+    #[serde(rename = "error.invalid_player_name")]
+    InvalidPlayerName,
+    #[serde(rename = "error.invalid_room_code")]
+    InvalidRoomCode,
+    #[serde(rename = "error.room_not_found")]
+    RoomNotFound,
+    #[serde(rename = "error.room_full")]
+    RoomFull,
+    /// Params: `client`, `server`.
+    #[serde(rename = "error.protocol_mismatch")]
+    ProtocolMismatch,
+    #[serde(rename = "error.session_invalid")]
+    SessionInvalid,
+    #[serde(rename = "error.session_expired")]
+    SessionExpired,
+    #[serde(rename = "error.room_gone")]
+    RoomGone,
+    #[serde(rename = "error.player_slot_unavailable")]
+    PlayerSlotUnavailable,
+    #[serde(rename = "error.join_queue_full")]
+    JoinQueueFull,
+    #[serde(rename = "error.join_denied")]
+    JoinDenied,
+    #[serde(rename = "error.server_full")]
+    ServerFull,
+    #[serde(rename = "error.too_many_rooms")]
+    TooManyRooms,
+    #[serde(rename = "error.invalid_room_alias")]
+    InvalidRoomAlias,
+    #[serde(rename = "error.room_alias_taken")]
+    RoomAliasTaken,
+    #[serde(rename = "error.room_alias_quota")]
+    RoomAliasQuota,
+    #[serde(rename = "error.bot_token_rejected")]
+    BotTokenRejected,
+    #[serde(rename = "error.banned")]
+    Banned,
+
+    // Lobby status (client-local)
+    #[serde(rename = "status.creating_room")]
+    CreatingRoom,
+    /// Params: `code`.
+    #[serde(rename = "status.joining_room")]
+    JoiningRoom,
+    #[serde(rename = "status.room_created")]
+    RoomCreated,
+    #[serde(rename = "status.joined_waiting")]
+    JoinedWaiting,
+    #[serde(rename = "status.already_in_room_create")]
+    AlreadyInRoomCreate,
+    #[serde(rename = "status.already_in_room_join")]
+    AlreadyInRoomJoin,
+    #[serde(rename = "status.enter_room_code")]
+    EnterRoomCode,
+    /// Params: `reason`.
+    #[serde(rename = "status.connection_failed")]
+    ConnectionFailed,
+    #[serde(rename = "status.connection_lost_rejoin")]
+    ConnectionLostRejoin,
+    /// Params: `reason`.
+    #[serde(rename = "status.replay_invalid")]
+    ReplayInvalid,
+    #[serde(rename = "status.replay_leave_room")]
+    ReplayLeaveRoom,
+    /// Params: `reason`.
+    #[serde(rename = "status.room_config_invalid")]
+    RoomConfigInvalid,
+    #[serde(rename = "status.room_config_imported")]
+    RoomConfigImported,
+    #[serde(rename = "status.room_config_leader_only")]
+    RoomConfigLeaderOnly,
+    /// Params: `position`, `total`.
+    #[serde(rename = "status.join_queued")]
+    JoinQueued,
+    /// Params: `position`, `total`.
+    #[serde(rename = "status.join_awaiting_approval")]
+    JoinAwaitingApproval,
+    /// Params: `position`, `total`.
+    #[serde(rename = "status.room_queued")]
+    RoomQueued,
+    #[serde(rename = "status.connecting_relay")]
+    ConnectingRelay,
+    #[serde(rename = "status.offline_ready")]
+    OfflineReady,
+    #[serde(rename = "status.relay_connection_lost")]
+    RelayConnectionLost,
+
+    // Round announcer (game-sent; the client adds `winners`)
+    /// Params: `winners`, `stat`.
+    #[serde(rename = "victory.top_score")]
+    VictoryTopScore,
+    /// Params: `winners`, `stat`.
+    #[serde(rename = "victory.golf_strokes")]
+    VictoryGolfStrokes,
+    /// Params: `winners`, `stat`.
+    #[serde(rename = "victory.most_tags")]
+    VictoryMostTags,
+    /// Params: `winners`.
+    #[serde(rename = "victory.first_across")]
+    VictoryFirstAcross,
+    /// Params: `winners`, `stat`.
+    #[serde(rename = "victory.last_rider")]
+    VictoryLastRider,
+    /// Params: `winners`.
+    #[serde(rename = "victory.draw")]
+    VictoryDraw,
+    #[serde(rename = "victory.no_winner")]
+    VictoryNoWinner,
+
+    // Tutorial objectives (game-defined, server-sent in checklists)
+    #[serde(rename = "tutorial.golf_sink")]
+    TutorialGolfSink,
+    #[serde(rename = "tutorial.golf_sink_in_three")]
+    TutorialGolfSinkInThree,
+    #[serde(rename = "tutorial.platformer_finish")]
+    TutorialPlatformerFinish,
+    #[serde(rename = "tutorial.platformer_powerup")]
+    TutorialPlatformerPowerUp,
+    #[serde(rename = "tutorial.lasertag_tag")]
+    TutorialLaserTagTag,
+    #[serde(rename = "tutorial.lasertag_powerup")]
+    TutorialLaserTagPowerUp,
+    #[serde(rename = "tutorial.tron_survive")]
+    TutorialTronSurvive,
+    #[serde(rename = "tutorial.tron_win")]
+    TutorialTronWin,
+}
+
+impl TextKey {
+    /// Every key, for locale completeness checks.
+    pub const ALL: &[TextKey] = &[
+        Self::InvalidPlayerName,
+        Self::InvalidRoomCode,
+        Self::RoomNotFound,
+        Self::RoomFull,
+        Self::ProtocolMismatch,
+        Self::SessionInvalid,
+        Self::SessionExpired,
+        Self::RoomGone,
+        Self::PlayerSlotUnavailable,
+        Self::JoinQueueFull,
+        Self::JoinDenied,
+        Self::ServerFull,
+        Self::TooManyRooms,
+        Self::InvalidRoomAlias,
+        Self::RoomAliasTaken,
+        Self::RoomAliasQuota,
+        Self::BotTokenRejected,
+        Self::Banned,
+        Self::CreatingRoom,
+        Self::JoiningRoom,
+        Self::RoomCreated,
+        Self::JoinedWaiting,
+        Self::AlreadyInRoomCreate,
+        Self::AlreadyInRoomJoin,
+        Self::EnterRoomCode,
+        Self::ConnectionFailed,
+        Self::ConnectionLostRejoin,
+        Self::ReplayInvalid,
+        Self::ReplayLeaveRoom,
+        Self::RoomConfigInvalid,
+        Self::RoomConfigImported,
+        Self::RoomConfigLeaderOnly,
+        Self::JoinQueued,
+        Self::JoinAwaitingApproval,
+        Self::RoomQueued,
+        Self::ConnectingRelay,
+        Self::OfflineReady,
+        Self::RelayConnectionLost,
+        Self::VictoryTopScore,
+        Self::VictoryGolfStrokes,
+        Self::VictoryMostTags,
+        Self::VictoryFirstAcross,
+        Self::VictoryLastRider,
+        Self::VictoryDraw,
+        Self::VictoryNoWinner,
+        Self::TutorialGolfSink,
+        Self::TutorialGolfSinkInThree,
+        Self::TutorialPlatformerFinish,
+        Self::TutorialPlatformerPowerUp,
+        Self::TutorialLaserTagTag,
+        Self::TutorialLaserTagPowerUp,
+        Self::TutorialTronSurvive,
+        Self::TutorialTronWin,
+    ];
+
+    /// The dotted locale-file key.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::InvalidPlayerName => "error.invalid_player_name",
+            Self::InvalidRoomCode => "error.invalid_room_code",
+            Self::RoomNotFound => "error.room_not_found",
+            Self::RoomFull => "error.room_full",
+            Self::ProtocolMismatch => "error.protocol_mismatch",
+            Self::SessionInvalid => "error.session_invalid",
+            Self::SessionExpired => "error.session_expired",
+            Self::RoomGone => "error.room_gone",
+            Self::PlayerSlotUnavailable => "error.player_slot_unavailable",
+            Self::JoinQueueFull => "error.join_queue_full",
+            Self::JoinDenied => "error.join_denied",
+            Self::ServerFull => "error.server_full",
+            Self::TooManyRooms => "error.too_many_rooms",
+            Self::InvalidRoomAlias => "error.invalid_room_alias",
+            Self::RoomAliasTaken => "error.room_alias_taken",
+            Self::RoomAliasQuota => "error.room_alias_quota",
+            Self::BotTokenRejected => "error.bot_token_rejected",
+            Self::Banned => "error.banned",
+            Self::CreatingRoom => "status.creating_room",
+            Self::JoiningRoom => "status.joining_room",
+            Self::RoomCreated => "status.room_created",
+            Self::JoinedWaiting => "status.joined_waiting",
+            Self::AlreadyInRoomCreate => "status.already_in_room_create",
+            Self::AlreadyInRoomJoin => "status.already_in_room_join",
+            Self::EnterRoomCode => "status.enter_room_code",
+            Self::ConnectionFailed => "status.connection_failed",
+            Self::ConnectionLostRejoin => "status.connection_lost_rejoin",
+            Self::ReplayInvalid => "status.replay_invalid",
+            Self::ReplayLeaveRoom => "status.replay_leave_room",
+            Self::RoomConfigInvalid => "status.room_config_invalid",
+            Self::RoomConfigImported => "status.room_config_imported",
+            Self::RoomConfigLeaderOnly => "status.room_config_leader_only",
+            Self::JoinQueued => "status.join_queued",
+            Self::JoinAwaitingApproval => "status.join_awaiting_approval",
+            Self::RoomQueued => "status.room_queued",
+            Self::ConnectingRelay => "status.connecting_relay",
+            Self::OfflineReady => "status.offline_ready",
+            Self::RelayConnectionLost => "status.relay_connection_lost",
+            Self::VictoryTopScore => "victory.top_score",
+            Self::VictoryGolfStrokes => "victory.golf_strokes",
+            Self::VictoryMostTags => "victory.most_tags",
+            Self::VictoryFirstAcross => "victory.first_across",
+            Self::VictoryLastRider => "victory.last_rider",
+            Self::VictoryDraw => "victory.draw",
+            Self::VictoryNoWinner => "victory.no_winner",
+            Self::TutorialGolfSink => "tutorial.golf_sink",
+            Self::TutorialGolfSinkInThree => "tutorial.golf_sink_in_three",
+            Self::TutorialPlatformerFinish => "tutorial.platformer_finish",
+            Self::TutorialPlatformerPowerUp => "tutorial.platformer_powerup",
+            Self::TutorialLaserTagTag => "tutorial.lasertag_tag",
+            Self::TutorialLaserTagPowerUp => "tutorial.lasertag_powerup",
+            Self::TutorialTronSurvive => "tutorial.tron_survive",
+            Self::TutorialTronWin => "tutorial.tron_win",
+        }
+    }
+}
+
+/// A message code plus named parameters, rendered by the client in the
+/// player's language.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalizedText {
+    pub key: TextKey,
+    #[serde(default)]
+    pub params: BTreeMap<String, String>,
+}
+
+impl LocalizedText {
+    pub fn new(key: TextKey) -> Self {
+        Self {
+            key,
+            params: BTreeMap::new(),
+        }
+    }
+
+    /// Add a named parameter, substituted for `{name}` in the translation.
+    pub fn with(mut self, name: &str, value: impl ToString) -> Self {
+        self.params.insert(name.to_string(), value.to_string());
+        self
+    }
+}
+
+impl From<TextKey> for LocalizedText {
+    fn from(key: TextKey) -> Self {
+        Self::new(key)
+    }
+}
+
+/// Language-neutral rendering for logs: the key followed by its parameters.
+impl fmt::Display for LocalizedText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.key.as_str())?;
+        if !self.params.is_empty() {
+            let params: Vec<String> = self
+                .params
+                .iter()
+                .map(|(k, v)| format!("{k}={v}"))
+                .collect();
+            write!(f, " ({})", params.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_serializes_as_dotted_string() {
+        for &key in TextKey::ALL {
+            let json = serde_json::to_string(&key).unwrap();
+            assert_eq!(json, format!("\"{}\"", key.as_str()));
+        }
+    }
+
+    #[test]
+    fn localized_text_msgpack_roundtrip() {
+        let text = LocalizedText::new(TextKey::ProtocolMismatch)
+            .with("client", 1)
+            .with("server", 2);
+        let data = rmp_serde::to_vec(&text).unwrap();
+        let back: LocalizedText = rmp_serde::from_slice(&data).unwrap();
+        assert_eq!(back, text);
+    }
+
+    /// Locale files shipped with the web client.
+    const LOCALES: &[(&str, &str)] = &[
+        ("en", include_str!("../../../web/locales/en.json")),
+        ("es", include_str!("../../../web/locales/es.json")),
+    ];
+
+    fn placeholders(text: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = text
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn every_locale_translates_every_key() {
+        let (_, en) = LOCALES[0];
+        let en: BTreeMap<String, String> = serde_json::from_str(en).unwrap();
+        for &key in TextKey::ALL {
+            assert!(
+                en.contains_key(key.as_str()),
+                "en.json missing {}",
+                key.as_str()
+            );
+        }
+        for &(lang, data) in &LOCALES[1..] {
+            let dict: BTreeMap<String, String> = serde_json::from_str(data).unwrap();
+            for (key, text) in &en {
+                let translated = dict
+                    .get(key)
+                    .unwrap_or_else(|| panic!("{lang}.json missing {key}"));
+                assert_eq!(
+                    placeholders(translated),
+                    placeholders(text),
+                    "{lang}.json placeholders differ for {key}"
+                );
+            }
+            for key in dict.keys() {
+                assert!(en.contains_key(key), "{lang}.json has unknown key {key}");
+            }
+        }
+    }
+
+    #[test]
+    fn display_includes_params() {
+        let text = LocalizedText::new(TextKey::JoiningRoom).with("code", "ABCD-1234");
+        assert_eq!(text.to_string(), "status.joining_room (code=ABCD-1234)");
+        assert_eq!(
+            LocalizedText::from(TextKey::RoomFull).to_string(),
+            "error.room_full"
+        );
+    }
+}
@@ -0,0 +1,605 @@
+//! Input recordings (`.bpinputs` files): a hosted match kept as the inputs
+//! that drove it rather than the states it broadcast.
+//!
+//! A recording holds the players and [`GameConfig`] each round was started
+//! with, every input in the order the host applied it, and the players who
+//! joined or left along the way. Games draw their randomness from the
+//! `seed` lobby setting, which the recorder fills in when the lobby left it
+//! unset, so a [`ReplayPlayer`] feeding the same inputs to a fresh game
+//! reaches the same states. The file layout follows [`crate::replay`]:
+//! magic, a format version byte, then DEFLATE-compressed MessagePack, here
+//! compact since a long match holds many thousands of inputs.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Read, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::game_settings::CustomSettings;
+use crate::game_trait::{
+    BreakpointGame, GameConfig, GameConfigDelta, GameEvent, GameId, PlayerId, PlayerInputs,
+    PlayerScore,
+};
+use crate::player::Player;
+use crate::replay::{
+    MAX_DECODED_REPLAY_SIZE, MatchReplay, ReplayError, ReplayFrame, ReplayMetadata, RoundReplay,
+};
+
+/// First bytes of every input recording.
+pub const INPUT_REPLAY_MAGIC: &[u8; 8] = b"BPINPUTS";
+
+/// Bumped when [`InputReplay`] changes incompatibly.
+pub const INPUT_REPLAY_FORMAT_VERSION: u8 = 1;
+
+/// File extension, without the dot.
+pub const INPUT_REPLAY_FILE_EXTENSION: &str = "bpinputs";
+
+/// Lobby setting that asks the host to record the match's inputs.
+pub const RECORD_REPLAY_SETTING: &str = "record_replay";
+
+/// Lobby setting games seed their randomness from.
+pub const SEED_SETTING: &str = "seed";
+
+/// Whether the lobby settings ask for an input recording.
+pub fn record_replay_requested(custom: &CustomSettings) -> bool {
+    custom
+        .get(RECORD_REPLAY_SETTING)
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// A recorded match: enough to re-run it through its game.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputReplay {
+    pub game: GameId,
+    /// The `seed` setting every round was started with.
+    pub seed: u64,
+    /// Simulation ticks per second; each update advanced `1 / tick_rate`.
+    pub tick_rate: f32,
+    pub rounds: Vec<InputRound>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputRound {
+    /// Players the round was initialized with.
+    pub players: Vec<Player>,
+    pub config: GameConfig,
+    /// Updates the round ran before it ended.
+    pub ticks: u32,
+    pub inputs: Vec<InputRecord>,
+    /// Joins, leaves, and settings changes, in order.
+    pub changes: Vec<RoundChange>,
+}
+
+/// An input applied before update `tick` (the number of updates already
+/// run in the round).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputRecord {
+    pub tick: u32,
+    pub player_id: PlayerId,
+    #[serde(with = "crate::replay::bin")]
+    pub input: Vec<u8>,
+}
+
+/// Something other than an input that changed the round before update
+/// `tick`, after the first `inputs_before` of the round's inputs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoundChange {
+    pub tick: u32,
+    pub inputs_before: u32,
+    pub kind: RoundChangeKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RoundChangeKind {
+    Joined(Player),
+    Left(PlayerId),
+    Config(GameConfigDelta),
+}
+
+/// Records a match as the host runs it. Call [`begin_round`] after each
+/// `init`, the other methods alongside the game calls they mirror, and
+/// [`tick`] after each `update`. Debug commands are not recorded, so a
+/// match that used them won't re-run as played.
+///
+/// [`begin_round`]: InputRecorder::begin_round
+/// [`tick`]: InputRecorder::tick
+#[derive(Debug, Clone)]
+pub struct InputRecorder {
+    replay: InputReplay,
+}
+
+impl InputRecorder {
+    /// Start recording a match of `game`. Gives `custom` a random `seed`
+    /// if it has none, so the match can be re-run; start the game with the
+    /// seeded settings.
+    pub fn start(game: GameId, tick_rate: f32, custom: &mut CustomSettings) -> Self {
+        let seed = match custom.get(SEED_SETTING).and_then(|v| v.as_u64()) {
+            Some(seed) => seed,
+            None => {
+                let seed = rand::random::<u64>();
+                custom.insert(SEED_SETTING.to_string(), seed.into());
+                seed
+            },
+        };
+        Self {
+            replay: InputReplay {
+                game,
+                seed,
+                tick_rate,
+                rounds: Vec::new(),
+            },
+        }
+    }
+
+    pub fn begin_round(&mut self, players: &[Player], config: &GameConfig) {
+        self.replay.rounds.push(InputRound {
+            players: players.to_vec(),
+            config: config.clone(),
+            ticks: 0,
+            inputs: Vec::new(),
+            changes: Vec::new(),
+        });
+    }
+
+    pub fn input(&mut self, player_id: PlayerId, input: &[u8]) {
+        if let Some(round) = self.replay.rounds.last_mut() {
+            round.inputs.push(InputRecord {
+                tick: round.ticks,
+                player_id,
+                input: input.to_vec(),
+            });
+        }
+    }
+
+    pub fn joined(&mut self, player: &Player) {
+        self.change(RoundChangeKind::Joined(player.clone()));
+    }
+
+    pub fn left(&mut self, player_id: PlayerId) {
+        self.change(RoundChangeKind::Left(player_id));
+    }
+
+    /// A mid-round settings change the game accepted.
+    pub fn config_changed(&mut self, delta: &GameConfigDelta) {
+        self.change(RoundChangeKind::Config(delta.clone()));
+    }
+
+    fn change(&mut self, kind: RoundChangeKind) {
+        if let Some(round) = self.replay.rounds.last_mut() {
+            round.changes.push(RoundChange {
+                tick: round.ticks,
+                inputs_before: round.inputs.len() as u32,
+                kind,
+            });
+        }
+    }
+
+    pub fn tick(&mut self) {
+        if let Some(round) = self.replay.rounds.last_mut() {
+            round.ticks += 1;
+        }
+    }
+
+    pub fn finish(self) -> InputReplay {
+        self.replay
+    }
+}
+
+/// Re-runs an [`InputReplay`] through a fresh instance of its game.
+pub struct ReplayPlayer {
+    replay: InputReplay,
+    game: Box<dyn BreakpointGame>,
+    round_index: usize,
+    /// Updates run in the current round.
+    tick: u32,
+    next_input: usize,
+    next_change: usize,
+    /// Last input from each player since the previous update.
+    buffer: HashMap<PlayerId, Vec<u8>>,
+}
+
+impl ReplayPlayer {
+    /// Start re-running `replay` with `game`, which must be a new instance
+    /// of `replay.game`. The first round is initialized right away.
+    pub fn new(replay: InputReplay, game: Box<dyn BreakpointGame>) -> Self {
+        let mut player = Self {
+            replay,
+            game,
+            round_index: 0,
+            tick: 0,
+            next_input: 0,
+            next_change: 0,
+            buffer: HashMap::new(),
+        };
+        player.init_round();
+        player
+    }
+
+    pub fn replay(&self) -> &InputReplay {
+        &self.replay
+    }
+
+    pub fn game(&self) -> &dyn BreakpointGame {
+        &*self.game
+    }
+
+    pub fn round_index(&self) -> usize {
+        self.round_index
+    }
+
+    /// Updates run in the current round.
+    pub fn tick(&self) -> u32 {
+        self.tick
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.round_index >= self.replay.rounds.len()
+    }
+
+    /// Whether the current round has run all its recorded updates.
+    pub fn round_done(&self) -> bool {
+        self.replay
+            .rounds
+            .get(self.round_index)
+            .is_none_or(|round| self.tick >= round.ticks)
+    }
+
+    /// Apply the next update's inputs and run it. Returns `None` instead
+    /// once the round is done; call [`ReplayPlayer::next_round`] then.
+    pub fn step(&mut self) -> Option<Vec<GameEvent>> {
+        if self.round_done() {
+            return None;
+        }
+        self.apply_recorded(self.tick);
+        let inputs = PlayerInputs {
+            inputs: std::mem::take(&mut self.buffer),
+        };
+        let events = self.game.update(1.0 / self.replay.tick_rate, &inputs);
+        self.tick += 1;
+        Some(events)
+    }
+
+    /// Apply what happened after the current round's last update, then
+    /// start the next round if there is one.
+    pub fn next_round(&mut self) {
+        if self.is_finished() {
+            return;
+        }
+        self.apply_recorded(u32::MAX);
+        self.round_index += 1;
+        self.init_round();
+    }
+
+    fn init_round(&mut self) {
+        self.tick = 0;
+        self.next_input = 0;
+        self.next_change = 0;
+        self.buffer.clear();
+        if let Some(round) = self.replay.rounds.get(self.round_index) {
+            self.game.init(&round.players, &round.config);
+        }
+    }
+
+    /// Apply the current round's inputs and changes recorded before update
+    /// `until`, in the order the host applied them.
+    fn apply_recorded(&mut self, until: u32) {
+        let Some(round) = self.replay.rounds.get(self.round_index) else {
+            return;
+        };
+        loop {
+            let change = round
+                .changes
+                .get(self.next_change)
+                .filter(|c| c.tick <= until);
+            let input = round
+                .inputs
+                .get(self.next_input)
+                .filter(|i| i.tick <= until);
+            match (change, input) {
+                (Some(change), _) if change.inputs_before as usize <= self.next_input => {
+                    match &change.kind {
+                        RoundChangeKind::Joined(player) => self.game.player_joined(player),
+                        RoundChangeKind::Left(player_id) => {
+                            self.game.player_left(*player_id);
+                            self.buffer.remove(player_id);
+                        },
+                        RoundChangeKind::Config(delta) => {
+                            // It was accepted live, so it will be again
+                            let _ = self.game.apply_config_change(delta);
+                        },
+                    }
+                    self.next_change += 1;
+                },
+                (_, Some(input)) => {
+                    self.game.apply_input(input.player_id, &input.input);
+                    self.buffer.insert(input.player_id, input.input.clone());
+                    self.next_input += 1;
+                },
+                _ => break,
+            }
+        }
+    }
+
+    /// Re-run the whole match, keeping the state after every update as a
+    /// [`MatchReplay`] frame so the match can be watched like a recording.
+    pub fn into_match_replay(mut self) -> MatchReplay {
+        let players = self
+            .replay
+            .rounds
+            .first()
+            .map(|r| r.players.clone())
+            .unwrap_or_default();
+        let custom = self
+            .replay
+            .rounds
+            .first()
+            .map(|r| r.config.custom.clone())
+            .unwrap_or_default();
+        let mut rounds = Vec::with_capacity(self.replay.rounds.len());
+        let mut totals: BTreeMap<PlayerId, i32> = BTreeMap::new();
+        while !self.is_finished() {
+            let mut frames = vec![ReplayFrame {
+                tick: 0,
+                server_time_ms: 0,
+                state: self.game.serialize_state(),
+                course: self.game.course_data(),
+            }];
+            while self.step().is_some() {
+                frames.push(ReplayFrame {
+                    tick: self.tick,
+                    server_time_ms: 0,
+                    state: self.game.serialize_state(),
+                    course: self.game.course_data(),
+                });
+            }
+            let scores = self.game.round_results();
+            for s in &scores {
+                *totals.entry(s.player_id).or_insert(0) += s.score;
+            }
+            rounds.push(RoundReplay {
+                round: self.round_index as u8 + 1,
+                scores,
+                frames,
+                truncated: false,
+                heatmap: self.game.round_heatmap(),
+            });
+            self.next_round();
+        }
+        MatchReplay {
+            metadata: ReplayMetadata {
+                match_id: String::new(),
+                game: self.replay.game,
+                room_code: String::new(),
+                players,
+                custom,
+                tick_rate: self.replay.tick_rate,
+                started_at: 0,
+                ended_at: 0,
+                server_version: env!("CARGO_PKG_VERSION").to_string(),
+            },
+            rounds,
+            final_scores: totals
+                .into_iter()
+                .map(|(player_id, score)| PlayerScore {
+                    player_id,
+                    score,
+                    disconnected: false,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Encode a recording into `.bpinputs` file bytes.
+pub fn encode_input_replay(replay: &InputReplay) -> Result<Vec<u8>, ReplayError> {
+    let payload = rmp_serde::to_vec(replay).map_err(|e| ReplayError::Encode(e.to_string()))?;
+    let mut out = Vec::with_capacity(payload.len() / 4 + INPUT_REPLAY_MAGIC.len() + 1);
+    out.extend_from_slice(INPUT_REPLAY_MAGIC);
+    out.push(INPUT_REPLAY_FORMAT_VERSION);
+    let mut encoder = flate2::write::DeflateEncoder::new(out, flate2::Compression::default());
+    encoder
+        .write_all(&payload)
+        .map_err(|e| ReplayError::Encode(e.to_string()))?;
+    encoder
+        .finish()
+        .map_err(|e| ReplayError::Encode(e.to_string()))
+}
+
+/// Decode `.bpinputs` file bytes.
+pub fn decode_input_replay(data: &[u8]) -> Result<InputReplay, ReplayError> {
+    let body = data
+        .strip_prefix(INPUT_REPLAY_MAGIC.as_slice())
+        .ok_or(ReplayError::NotAReplay)?;
+    let (&version, compressed) = body.split_first().ok_or(ReplayError::NotAReplay)?;
+    if version != INPUT_REPLAY_FORMAT_VERSION {
+        return Err(ReplayError::UnsupportedVersion(version));
+    }
+    let mut payload = Vec::new();
+    flate2::read::DeflateDecoder::new(compressed)
+        .take(MAX_DECODED_REPLAY_SIZE + 1)
+        .read_to_end(&mut payload)
+        .map_err(|e| ReplayError::Decode(e.to_string()))?;
+    if payload.len() as u64 > MAX_DECODED_REPLAY_SIZE {
+        return Err(ReplayError::TooLarge);
+    }
+    rmp_serde::from_slice(&payload).map_err(|e| ReplayError::Decode(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_trait::GameMetadata;
+    use crate::pause::PauseReason;
+    use crate::test_helpers::make_players;
+
+    /// Each player's position wanders by their inputs and a seeded
+    /// random step, so a re-run only matches with the same seed.
+    #[derive(Default)]
+    struct Walk {
+        rng: u64,
+        positions: BTreeMap<PlayerId, i64>,
+        steps: u32,
+    }
+
+    impl BreakpointGame for Walk {
+        fn metadata(&self) -> GameMetadata {
+            GameMetadata {
+                name: "Walk".to_string(),
+                description: String::new(),
+                min_players: 1,
+                max_players: u8::MAX,
+                estimated_round_duration: std::time::Duration::ZERO,
+            }
+        }
+        fn init(&mut self, players: &[Player], config: &GameConfig) {
+            self.rng = config.custom[SEED_SETTING].as_u64().unwrap();
+            self.positions = players.iter().map(|p| (p.id, 0)).collect();
+            self.steps = 0;
+        }
+        fn update(&mut self, _: f32, inputs: &PlayerInputs) -> Vec<GameEvent> {
+            self.rng = self.rng.wrapping_mul(6364136223846793005).wrapping_add(1);
+            for (id, pos) in &mut self.positions {
+                *pos += inputs.inputs.get(id).map_or(0, |i| i64::from(i[0]));
+                *pos += (self.rng >> 60) as i64;
+            }
+            self.steps += 1;
+            Vec::new()
+        }
+        fn serialize_state(&self) -> Vec<u8> {
+            rmp_serde::to_vec(&(&self.positions, self.steps)).unwrap()
+        }
+        fn apply_state(&mut self, _: &[u8]) {}
+        fn apply_input(&mut self, _: PlayerId, _: &[u8]) {}
+        fn player_joined(&mut self, player: &Player) {
+            self.positions.insert(player.id, 100);
+        }
+        fn player_left(&mut self, player_id: PlayerId) {
+            self.positions.remove(&player_id);
+        }
+        fn pause_for(&mut self, _: PauseReason) {}
+        fn resume_for(&mut self, _: PauseReason) {}
+        fn pause_reasons(&self) -> &[PauseReason] {
+            &[]
+        }
+        fn is_round_complete(&self) -> bool {
+            false
+        }
+        fn round_results(&self) -> Vec<PlayerScore> {
+            self.positions
+                .iter()
+                .map(|(&player_id, &pos)| PlayerScore {
+                    player_id,
+                    score: pos as i32,
+                    disconnected: false,
+                })
+                .collect()
+        }
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    /// Play two rounds live while recording, returning each update's state.
+    fn play_live() -> (InputReplay, Vec<Vec<u8>>) {
+        let mut custom = CustomSettings::new();
+        let mut recorder = InputRecorder::start(GameId::Tron, 20.0, &mut custom);
+        let mut game = Walk::default();
+        let mut states = Vec::new();
+        let mut players = make_players(2);
+        for round in 0..2u8 {
+            let config = GameConfig::builder()
+                .custom(custom.clone())
+                .setting("hole_index", round)
+                .build();
+            game.init(&players, &config);
+            recorder.begin_round(&players, &config);
+            let mut buffer = HashMap::new();
+            for tick in 0..30u8 {
+                if tick % 3 == 0 {
+                    let input = vec![tick % 7];
+                    game.apply_input(1, &input);
+                    recorder.input(1, &input);
+                    buffer.insert(1, input);
+                }
+                if round == 0 && tick == 10 {
+                    let guest = make_players(3).pop().unwrap();
+                    game.player_joined(&guest);
+                    recorder.joined(&guest);
+                    players.push(guest);
+                }
+                if round == 1 && tick == 20 {
+                    game.player_left(2);
+                    recorder.left(2);
+                    buffer.remove(&2);
+                }
+                let inputs = PlayerInputs {
+                    inputs: std::mem::take(&mut buffer),
+                };
+                game.update(0.05, &inputs);
+                recorder.tick();
+                states.push(game.serialize_state());
+            }
+        }
+        (recorder.finish(), states)
+    }
+
+    #[test]
+    fn recorder_seeds_unseeded_matches() {
+        let mut custom = CustomSettings::new();
+        let recorder = InputRecorder::start(GameId::Tron, 20.0, &mut custom);
+        assert_eq!(custom[SEED_SETTING].as_u64(), Some(recorder.replay.seed));
+
+        let mut custom = CustomSettings::from([(SEED_SETTING.to_string(), 7.into())]);
+        assert_eq!(
+            InputRecorder::start(GameId::Tron, 20.0, &mut custom)
+                .finish()
+                .seed,
+            7
+        );
+        assert!(!record_replay_requested(&custom));
+        custom.insert(RECORD_REPLAY_SETTING.to_string(), true.into());
+        assert!(record_replay_requested(&custom));
+    }
+
+    #[test]
+    fn player_reruns_the_recorded_match() {
+        let (replay, live) = play_live();
+        let file = encode_input_replay(&replay).unwrap();
+        assert!(file.starts_with(INPUT_REPLAY_MAGIC));
+        let replay = decode_input_replay(&file).unwrap();
+        assert_eq!(replay.rounds.len(), 2);
+        assert_eq!(replay.rounds[0].changes.len(), 1);
+
+        let mut player = ReplayPlayer::new(replay.clone(), Box::new(Walk::default()));
+        let mut rerun = Vec::new();
+        while !player.is_finished() {
+            while player.step().is_some() {
+                rerun.push(player.game().serialize_state());
+            }
+            player.next_round();
+        }
+        assert_eq!(rerun, live);
+
+        let watched = ReplayPlayer::new(replay, Box::new(Walk::default())).into_match_replay();
+        assert_eq!(watched.rounds.len(), 2);
+        // The state after init, then one frame per update
+        assert_eq!(watched.rounds[1].frames.len(), 31);
+        assert_eq!(watched.rounds[1].frames[30].state, live[59]);
+        assert_eq!(watched.final_scores.len(), 3);
+    }
+
+    #[test]
+    fn rejects_state_replays_and_future_files() {
+        assert!(matches!(
+            decode_input_replay(b"BPREPLAY\x01"),
+            Err(ReplayError::NotAReplay)
+        ));
+        let mut file = encode_input_replay(&play_live().0).unwrap();
+        file[INPUT_REPLAY_MAGIC.len()] = INPUT_REPLAY_FORMAT_VERSION + 1;
+        assert!(matches!(
+            decode_input_replay(&file),
+            Err(ReplayError::UnsupportedVersion(_))
+        ));
+    }
+}
@@ -0,0 +1,88 @@
+//! What happens to a player who joins while a round is being played. Each
+//! game picks its default and the policies it can honor; the lobby can
+//! choose among them with the [`LATE_JOIN_SETTING`] setting.
+//!
+//! The policy only covers players new to the round. A player who left and
+//! comes back before the round ends always gets their place back.
+
+use crate::game_settings::{CustomSettings, SettingsError, SettingsReader};
+
+/// Lobby setting choosing the late-join policy: `spectate`, `spawn`, or
+/// `next_round`.
+pub const LATE_JOIN_SETTING: &str = "late_join";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LateJoinPolicy {
+    /// Watch the rest of the round from the sidelines, without a place in
+    /// it or its results, and play from the next round.
+    Spectate,
+    /// Enter the round straight away.
+    SpawnImmediately,
+    /// Sit out the rest of the round on its scoreboard, scoring nothing,
+    /// and play from the next round.
+    SpawnNextRound,
+}
+
+impl LateJoinPolicy {
+    const OPTIONS: [(&'static str, Self); 3] = [
+        ("spectate", Self::Spectate),
+        ("spawn", Self::SpawnImmediately),
+        ("next_round", Self::SpawnNextRound),
+    ];
+
+    /// The setting value naming this policy.
+    pub fn as_str(self) -> &'static str {
+        Self::OPTIONS
+            .iter()
+            .find(|&&(_, policy)| policy == self)
+            .map_or("", |&(name, _)| name)
+    }
+
+    /// The policy the lobby settings ask for, or `default` when unset. A
+    /// game that can't honor a policy leaves it out of `supported`, and a
+    /// lobby asking for it is rejected.
+    pub fn from_custom(
+        custom: &CustomSettings,
+        default: Self,
+        supported: &[Self],
+    ) -> Result<Self, SettingsError> {
+        let policy = SettingsReader::new(custom)
+            .choice(LATE_JOIN_SETTING, &Self::OPTIONS)?
+            .unwrap_or(default);
+        if supported.contains(&policy) {
+            Ok(policy)
+        } else {
+            Err(SettingsError::new(
+                LATE_JOIN_SETTING,
+                format!("`{}` isn't supported by this game", policy.as_str()),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policy_is_read_from_the_lobby_and_checked_against_the_game() {
+        use LateJoinPolicy::*;
+        let supported = [Spectate, SpawnNextRound];
+        let mut custom = CustomSettings::new();
+        assert_eq!(
+            LateJoinPolicy::from_custom(&custom, SpawnNextRound, &supported),
+            Ok(SpawnNextRound)
+        );
+
+        custom.insert(LATE_JOIN_SETTING.to_string(), serde_json::json!("spectate"));
+        assert_eq!(
+            LateJoinPolicy::from_custom(&custom, SpawnNextRound, &supported),
+            Ok(Spectate)
+        );
+
+        custom.insert(LATE_JOIN_SETTING.to_string(), serde_json::json!("spawn"));
+        let err = LateJoinPolicy::from_custom(&custom, SpawnNextRound, &supported).unwrap_err();
+        assert_eq!(err.key, LATE_JOIN_SETTING);
+        assert_eq!(SpawnImmediately.as_str(), "spawn");
+    }
+}
@@ -1,13 +1,41 @@
+pub mod announcement;
+pub mod bot;
+pub mod client_report;
+pub mod course_vote;
+pub mod debug_command;
+pub mod event_source;
 pub mod events;
 pub mod game_registry;
+pub mod game_settings;
 pub mod game_trait;
+pub mod heatmap;
+pub mod i18n;
+#[cfg(feature = "replay")]
+pub mod input_replay;
+pub mod late_join;
+pub mod lobby_toy;
+pub mod math;
+pub mod names;
 pub mod net;
 pub mod overlay;
+pub mod palette;
+pub mod pause;
 pub mod player;
 pub mod powerup;
 #[cfg(feature = "profiling")]
 pub mod profiling;
+#[cfg(feature = "replay")]
+pub mod replay;
 pub mod room;
+pub mod room_export;
+pub mod round_phase;
+pub mod scoreboard;
+pub mod settings_sync;
+pub mod spectator;
+#[cfg(any(test, feature = "state-diff"))]
+pub mod state_diff;
+pub mod team_draft;
+pub mod telemetry;
 
 /// No-op profiling macro when the `profiling` feature is disabled.
 #[cfg(not(feature = "profiling"))]
@@ -16,16 +44,20 @@ macro_rules! profile {
     ($name:expr) => {};
 }
 pub mod time;
+pub mod tutorial;
+pub mod victory;
+pub mod win_condition;
 
 #[cfg(any(test, feature = "test-helpers"))]
 pub mod test_helpers {
     use std::collections::HashMap;
-    use std::time::Duration;
+    use std::time::{Duration, Instant};
 
     use crate::events::{Event, EventType, Priority};
     use crate::game_trait::{
         BreakpointGame, GameConfig, GameEvent, PlayerId, PlayerInputs, PlayerScore,
     };
+    use crate::pause::PauseReason;
     use crate::player::{Player, PlayerColor};
 
     /// Create `n` test players with sequential IDs starting at 1.
@@ -38,17 +70,18 @@ pub mod test_helpers {
                 is_leader: i == 0,
                 is_spectator: false,
                 is_bot: false,
+                bot_difficulty: Default::default(),
+                hot_seat_of: None,
+                external_bot: false,
             })
             .collect()
     }
 
     /// Create a default GameConfig with the given round duration in seconds.
     pub fn default_config(round_duration_secs: u64) -> GameConfig {
-        GameConfig {
-            round_count: 1,
-            round_duration: Duration::from_secs(round_duration_secs),
-            custom: HashMap::new(),
-        }
+        GameConfig::builder()
+            .round_duration(Duration::from_secs(round_duration_secs))
+            .build()
     }
 
     /// Create a generic test event with the given id.
@@ -145,7 +178,8 @@ pub mod test_helpers {
         );
     }
 
-    /// Running update() enough times must eventually reach is_round_complete().
+    /// Running update() enough times must eventually reach is_round_complete(),
+    /// and the finished round's winners must be players still in it.
     pub fn contract_round_eventually_completes(game: &mut dyn BreakpointGame, max_ticks: usize) {
         let empty = PlayerInputs {
             inputs: HashMap::new(),
@@ -153,13 +187,29 @@ pub mod test_helpers {
         for _ in 0..max_ticks {
             game.update(1.0, &empty);
             if game.is_round_complete() {
-                return;
+                break;
             }
         }
         assert!(
             game.is_round_complete(),
             "Game must complete after {max_ticks} ticks of 1s each"
         );
+
+        let summary = game.victory_summary();
+        let results = game.round_results();
+        for id in &summary.winner_ids {
+            assert!(
+                results
+                    .iter()
+                    .any(|s| s.player_id == *id && !s.disconnected),
+                "Winner {id} must be a player still in the round"
+            );
+        }
+        assert_eq!(
+            summary.key_stat.map(|s| s.to_string()).as_ref(),
+            summary.message.params.get("stat"),
+            "The key stat must be the message's `stat`"
+        );
     }
 
     /// serialize_state → apply_state roundtrip: the game must produce
@@ -196,6 +246,36 @@ pub mod test_helpers {
         assert_ne!(during_pause, after_resume, "State must change after resume");
     }
 
+    /// Resuming one pause reason must not unpause a game still held by
+    /// another, and the active reasons must travel with the broadcast state.
+    pub fn contract_pause_reasons_stack(game: &mut dyn BreakpointGame) {
+        game.pause_for(PauseReason::HostPause);
+        game.pause_for(PauseReason::Vote);
+        game.resume_for(PauseReason::HostPause);
+        assert_eq!(game.pause_reasons(), &[PauseReason::Vote]);
+
+        let before = game.serialize_state();
+        let empty = PlayerInputs {
+            inputs: HashMap::new(),
+        };
+        game.update(1.0, &empty);
+        assert_eq!(
+            before,
+            game.serialize_state(),
+            "State must not change while any reason is active"
+        );
+
+        game.resume_for(PauseReason::Vote);
+        game.apply_state(&before);
+        assert_eq!(
+            game.pause_reasons(),
+            &[PauseReason::Vote],
+            "Pause reasons must be carried in serialized state"
+        );
+        game.resume_for(PauseReason::Vote);
+        assert!(!game.is_paused());
+    }
+
     /// player_left() must remove player data from state.
     pub fn contract_player_left_cleanup(
         game: &mut dyn BreakpointGame,
@@ -206,13 +286,23 @@ pub mod test_helpers {
         game.player_left(player_id);
         let after = game.serialize_state();
         assert_ne!(before, after, "player_left must change state");
-        // After removing a player, round_results should have one fewer entry
+        // The departed player stays in round_results, marked disconnected
         let results = game.round_results();
         assert_eq!(
             results.len(),
-            player_count - 1,
-            "round_results should have {} entries after removing player",
-            player_count - 1
+            player_count,
+            "round_results must keep an entry for the departed player"
+        );
+        assert!(
+            results
+                .iter()
+                .any(|s| s.player_id == player_id && s.disconnected),
+            "Departed player's score must be marked disconnected"
+        );
+        assert_eq!(
+            results.iter().filter(|s| s.disconnected).count(),
+            1,
+            "Only the departed player is marked disconnected"
         );
     }
 
@@ -229,4 +319,87 @@ pub mod test_helpers {
         );
         results
     }
+
+    // ================================================================
+    // Performance Budgets
+    // ================================================================
+    // Hot paths have a time budget per call, set for optimized builds.
+    // Game crates assert them from their own #[cfg(test)] modules so a
+    // regression fails `cargo test` instead of showing up as server lag.
+
+    /// How many times longer a budget allows in unoptimized builds, which
+    /// run game code far slower. Loose, but an accidental quadratic loop
+    /// still blows through it.
+    pub const DEBUG_BUDGET_FACTOR: u32 = 25;
+
+    /// Calls timed per budget check, after a tenth as many warmup calls.
+    pub const BUDGET_SAMPLES: usize = 200;
+
+    /// `budget` as it applies to this build.
+    pub fn scaled_budget(budget: Duration) -> Duration {
+        if cfg!(debug_assertions) {
+            budget * DEBUG_BUDGET_FACTOR
+        } else {
+            budget
+        }
+    }
+
+    /// Time [`BUDGET_SAMPLES`] calls of `f` and fail when the median call
+    /// takes longer than `budget`. The median keeps one call stalled by a
+    /// busy machine from failing the test. Returns the median.
+    pub fn assert_within_budget(label: &str, budget: Duration, mut f: impl FnMut()) -> Duration {
+        let times = (0..BUDGET_SAMPLES / 10 + BUDGET_SAMPLES).map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        });
+        check_median(label, budget, times)
+    }
+
+    /// Assert a tick budget: before each tick `drive` queues inputs or
+    /// puts the game back in the state being measured, untimed; then
+    /// `update` runs at the game's tick rate. The round must still be in
+    /// progress afterwards, or the check would have timed idle ticks.
+    pub fn assert_tick_budget<G: BreakpointGame>(
+        label: &str,
+        game: &mut G,
+        budget: Duration,
+        mut drive: impl FnMut(&mut G),
+    ) -> Duration {
+        let dt = 1.0 / game.tick_rate();
+        let empty = PlayerInputs {
+            inputs: HashMap::new(),
+        };
+        let times: Vec<Duration> = (0..BUDGET_SAMPLES / 10 + BUDGET_SAMPLES)
+            .map(|_| {
+                drive(game);
+                let start = Instant::now();
+                game.update(dt, &empty);
+                start.elapsed()
+            })
+            .collect();
+        assert!(
+            !game.is_round_complete(),
+            "{label}: the round ended while it was being timed"
+        );
+        check_median(label, budget, times)
+    }
+
+    /// Drop the warmup samples and compare the median of the rest.
+    fn check_median(
+        label: &str,
+        budget: Duration,
+        times: impl IntoIterator<Item = Duration>,
+    ) -> Duration {
+        let mut times: Vec<Duration> = times.into_iter().skip(BUDGET_SAMPLES / 10).collect();
+        times.sort_unstable();
+        let median = times[times.len() / 2];
+        let allowed = scaled_budget(budget);
+        assert!(
+            median <= allowed,
+            "{label} took {median:?} per call, over its {budget:?} budget \
+             ({allowed:?} in this build)"
+        );
+        median
+    }
 }
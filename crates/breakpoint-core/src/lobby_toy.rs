@@ -0,0 +1,227 @@
+//! Target practice for the lobby: one shared target that everyone in the
+//! room can click while it waits for players. The room's host runs it like
+//! any other [`BreakpointGame`] while the room is in its lobby, ticking it
+//! on each click rather than at a fixed rate. Hits are zero-stakes: they
+//! are shown in the lobby and never recorded.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::breakpoint_game_boilerplate;
+use crate::game_trait::{
+    BreakpointGame, GameConfig, GameEvent, GameMetadata, PlayerId, PlayerInputs, PlayerScore,
+};
+use crate::pause::PauseState;
+use crate::player::Player;
+
+/// Radius of a fresh target, as a fraction of the (square) play area.
+const START_RADIUS: f32 = 0.12;
+
+/// The target never shrinks below this.
+const MIN_RADIUS: f32 = 0.04;
+
+/// The target shrinks by 5% every this many hits.
+const HITS_PER_SHRINK: u32 = 5;
+
+/// Shared state of the lobby target.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetPracticeState {
+    /// Target center, from 0 to 1 across and down the play area.
+    pub target_x: f32,
+    pub target_y: f32,
+    pub radius: f32,
+    /// Hits per player since the room opened.
+    pub hits: HashMap<PlayerId, u32>,
+    /// Who hit the target last.
+    pub last_hit_by: Option<PlayerId>,
+    /// Hits by everyone; the target shrinks as it grows.
+    pub total_hits: u32,
+    /// Always false: the toy runs until the host starts a game.
+    pub round_complete: bool,
+    pub pause: PauseState,
+}
+
+impl Default for TargetPracticeState {
+    fn default() -> Self {
+        Self {
+            target_x: 0.5,
+            target_y: 0.5,
+            radius: START_RADIUS,
+            hits: HashMap::new(),
+            last_hit_by: None,
+            total_hits: 0,
+            round_complete: false,
+            pause: PauseState::default(),
+        }
+    }
+}
+
+/// A click in the play area, in the same units as the target.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TargetClick {
+    pub x: f32,
+    pub y: f32,
+}
+
+/// The lobby toy.
+#[derive(Default)]
+pub struct TargetPractice {
+    state: TargetPracticeState,
+    /// Clicks since the last update, in arrival order.
+    pending: Vec<(PlayerId, TargetClick)>,
+}
+
+impl TargetPractice {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn state(&self) -> &TargetPracticeState {
+        &self.state
+    }
+
+    fn is_hit(&self, click: TargetClick) -> bool {
+        let dx = click.x - self.state.target_x;
+        let dy = click.y - self.state.target_y;
+        dx * dx + dy * dy <= self.state.radius * self.state.radius
+    }
+
+    /// Shrink the target for the new hit count and move it somewhere else.
+    fn respawn_target(&mut self) {
+        let shrinks = (self.state.total_hits / HITS_PER_SHRINK) as i32;
+        let radius = (START_RADIUS * 0.95f32.powi(shrinks)).max(MIN_RADIUS);
+        let mut rng = rand::rng();
+        self.state.radius = radius;
+        self.state.target_x = rng.random_range(radius..=1.0 - radius);
+        self.state.target_y = rng.random_range(radius..=1.0 - radius);
+    }
+}
+
+impl BreakpointGame for TargetPractice {
+    fn metadata(&self) -> GameMetadata {
+        GameMetadata {
+            name: "Target Practice".to_string(),
+            description: "Click the target while the room fills up.".to_string(),
+            min_players: 1,
+            max_players: u8::MAX,
+            estimated_round_duration: Duration::ZERO,
+        }
+    }
+
+    fn init(&mut self, players: &[Player], _config: &GameConfig) {
+        self.state = TargetPracticeState::default();
+        self.pending.clear();
+        for player in players {
+            self.player_joined(player);
+        }
+    }
+
+    fn update(&mut self, _dt: f32, _inputs: &PlayerInputs) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+        for (player_id, click) in std::mem::take(&mut self.pending) {
+            if self.state.pause.is_paused() || !self.is_hit(click) {
+                continue;
+            }
+            let hits = self.state.hits.entry(player_id).or_insert(0);
+            *hits += 1;
+            events.push(GameEvent::ScoreUpdate {
+                player_id,
+                score: *hits as i32,
+            });
+            self.state.last_hit_by = Some(player_id);
+            self.state.total_hits += 1;
+            self.respawn_target();
+        }
+        events
+    }
+
+    breakpoint_game_boilerplate!(state_type: TargetPracticeState);
+
+    fn apply_input(&mut self, player_id: PlayerId, input: &[u8]) {
+        if let Ok(click) = rmp_serde::from_slice::<TargetClick>(input)
+            && click.x.is_finite()
+            && click.y.is_finite()
+        {
+            self.pending.push((player_id, click));
+        }
+    }
+
+    fn player_joined(&mut self, player: &Player) {
+        if !player.is_bot {
+            self.state.hits.entry(player.id).or_insert(0);
+        }
+    }
+
+    fn player_left(&mut self, player_id: PlayerId) {
+        self.state.hits.remove(&player_id);
+        self.pending.retain(|&(id, _)| id != player_id);
+    }
+
+    fn round_results(&self) -> Vec<PlayerScore> {
+        self.state
+            .hits
+            .iter()
+            .map(|(&player_id, &hits)| PlayerScore {
+                player_id,
+                score: hits as i32,
+                disconnected: false,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{default_config, make_players, run_game_ticks};
+
+    fn click(game: &mut TargetPractice, player_id: PlayerId, x: f32, y: f32) -> Vec<GameEvent> {
+        let input = rmp_serde::to_vec(&TargetClick { x, y }).unwrap();
+        game.apply_input(player_id, &input);
+        run_game_ticks(game, 1, 0.0)
+    }
+
+    #[test]
+    fn hits_score_and_move_the_target() {
+        let mut game = TargetPractice::new();
+        game.init(&make_players(2), &default_config(0));
+        assert!(
+            click(&mut game, 1, 0.0, 0.0).is_empty(),
+            "Misses score nothing"
+        );
+
+        let events = click(&mut game, 2, 0.5, 0.5);
+        assert!(matches!(
+            events[..],
+            [GameEvent::ScoreUpdate {
+                player_id: 2,
+                score: 1
+            }]
+        ));
+        let state = game.state();
+        assert_eq!(state.last_hit_by, Some(2));
+        assert_eq!(state.hits[&1], 0);
+        let r = state.radius;
+        assert!((r..=1.0 - r).contains(&state.target_x));
+        assert!((r..=1.0 - r).contains(&state.target_y));
+        assert!(!game.is_round_complete());
+
+        game.player_left(2);
+        assert_eq!(game.round_results().len(), 1);
+    }
+
+    #[test]
+    fn target_shrinks_to_a_floor() {
+        let mut game = TargetPractice::new();
+        game.init(&make_players(1), &default_config(0));
+        for _ in 0..200 {
+            let (x, y) = (game.state().target_x, game.state().target_y);
+            click(&mut game, 1, x, y);
+        }
+        assert_eq!(game.state().hits[&1], 200);
+        assert_eq!(game.state().radius, MIN_RADIUS);
+    }
+}
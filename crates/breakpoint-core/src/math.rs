@@ -0,0 +1,460 @@
+//! Geometry shared by the game simulations. The 3D games play on the XZ
+//! plane and the platformer on XY, so most helpers work in 2D; `Vec3::xz`
+//! projects a 3D point onto the ground plane.
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+use serde::{Deserialize, Serialize};
+
+/// Segments shorter than this (squared) are treated as points.
+const DEGENERATE_LEN_SQ: f32 = 1e-8;
+
+/// A 2D point or direction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Vec2 {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Vec2 {
+    pub const ZERO: Self = Self::new(0.0, 0.0);
+
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn dot(self, other: Self) -> f32 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Z component of the 3D cross product; positive when `other` is
+    /// counter-clockwise from `self`.
+    pub fn perp_dot(self, other: Self) -> f32 {
+        self.x * other.y - self.y * other.x
+    }
+
+    pub fn length_squared(self) -> f32 {
+        self.dot(self)
+    }
+
+    pub fn length(self) -> f32 {
+        self.length_squared().sqrt()
+    }
+
+    pub fn distance(self, other: Self) -> f32 {
+        (other - self).length()
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Mul<f32> for Vec2 {
+    type Output = Self;
+    fn mul(self, rhs: f32) -> Self {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+/// A 3D point or direction (Y up).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Vec3 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+impl Vec3 {
+    pub const ZERO: Self = Self::new(0.0, 0.0, 0.0);
+
+    pub const fn new(x: f32, y: f32, z: f32) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Projection onto the ground plane.
+    pub fn xz(self) -> Vec2 {
+        Vec2::new(self.x, self.z)
+    }
+
+    pub fn length(self) -> f32 {
+        (self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
+    }
+
+    /// Distance on the ground plane, ignoring height.
+    pub fn distance_xz(self, other: Self) -> f32 {
+        self.xz().distance(other.xz())
+    }
+}
+
+impl Add for Vec3 {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Vec3 {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Mul<f32> for Vec3 {
+    type Output = Self;
+    fn mul(self, rhs: f32) -> Self {
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+/// An axis-aligned box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl Aabb {
+    pub fn from_center(center: Vec2, half_width: f32, half_height: f32) -> Self {
+        Self {
+            min: Vec2::new(center.x - half_width, center.y - half_height),
+            max: Vec2::new(center.x + half_width, center.y + half_height),
+        }
+    }
+
+    /// Bounds of two opposite corners given in any order.
+    pub fn from_corners(a: Vec2, b: Vec2) -> Self {
+        Self {
+            min: Vec2::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Vec2::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    /// Whether the interiors overlap. Boxes that only share an edge do not.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.min.x < other.max.x
+            && self.max.x > other.min.x
+            && self.min.y < other.max.y
+            && self.max.y > other.min.y
+    }
+
+    /// Whether the boxes overlap or come within `epsilon` of each other.
+    pub fn touches(&self, other: &Self, epsilon: f32) -> bool {
+        self.min.x <= other.max.x + epsilon
+            && other.min.x <= self.max.x + epsilon
+            && self.min.y <= other.max.y + epsilon
+            && other.min.y <= self.max.y + epsilon
+    }
+}
+
+/// The point on segment `a`-`b` nearest to `p`.
+pub fn closest_point_on_segment(p: Vec2, a: Vec2, b: Vec2) -> Vec2 {
+    let ab = b - a;
+    let len_sq = ab.length_squared();
+    if len_sq < DEGENERATE_LEN_SQ {
+        return a;
+    }
+    let t = ((p - a).dot(ab) / len_sq).clamp(0.0, 1.0);
+    a + ab * t
+}
+
+/// Distance from `p` to segment `a`-`b`.
+pub fn point_segment_distance(p: Vec2, a: Vec2, b: Vec2) -> f32 {
+    p.distance(closest_point_on_segment(p, a, b))
+}
+
+/// Whether segments `p1`-`p2` and `q1`-`q2` properly intersect. Touching
+/// at an endpoint or overlapping collinearly does not count.
+pub fn segments_cross(p1: Vec2, p2: Vec2, q1: Vec2, q2: Vec2) -> bool {
+    let side = |o: Vec2, a: Vec2, b: Vec2| (a - o).perp_dot(b - o);
+    let d1 = side(q1, q2, p1);
+    let d2 = side(q1, q2, p2);
+    let d3 = side(p1, p2, q1);
+    let d4 = side(p1, p2, q2);
+    d1 * d2 < 0.0 && d3 * d4 < 0.0
+}
+
+/// Where the ray `origin + t * dir` (t > 0) hits segment `a`-`b`, as `t`
+/// and the segment's unit normal facing back toward the ray. Parallel rays
+/// and degenerate segments miss.
+pub fn ray_segment(origin: Vec2, dir: Vec2, a: Vec2, b: Vec2) -> Option<(f32, Vec2)> {
+    let seg = b - a;
+    let denom = dir.perp_dot(seg);
+    if denom.abs() < DEGENERATE_LEN_SQ {
+        return None;
+    }
+    let to_a = a - origin;
+    let t = to_a.perp_dot(seg) / denom;
+    let u = to_a.perp_dot(dir) / denom;
+    if t <= 0.0 || !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let len = seg.length();
+    if len < 1e-6 {
+        return None;
+    }
+    let normal = Vec2::new(-seg.y / len, seg.x / len);
+    if normal.dot(dir) > 0.0 {
+        Some((t, -normal))
+    } else {
+        Some((t, normal))
+    }
+}
+
+/// Nearest `t > 0` where the ray `origin + t * dir` meets the circle. A ray
+/// starting inside the circle hits where it leaves.
+pub fn ray_circle(origin: Vec2, dir: Vec2, center: Vec2, radius: f32) -> Option<f32> {
+    let (t1, t2) = line_circle(origin, dir, center, radius)?;
+    if t1 > 0.0 {
+        Some(t1)
+    } else if t2 > 0.0 {
+        Some(t2)
+    } else {
+        None
+    }
+}
+
+/// Whether segment `a`-`b` passes through or lies inside the circle.
+pub fn segment_intersects_circle(a: Vec2, b: Vec2, center: Vec2, radius: f32) -> bool {
+    if (b - a).length_squared() < DEGENERATE_LEN_SQ {
+        return false;
+    }
+    let Some((t1, t2)) = line_circle(a, b - a, center, radius) else {
+        return false;
+    };
+    (0.0..=1.0).contains(&t1) || (0.0..=1.0).contains(&t2) || (t1 < 0.0 && t2 > 1.0)
+}
+
+/// Nearest `t >= 0` where the ray `origin + t * dir` enters the box, or 0
+/// when it starts inside.
+pub fn ray_aabb(origin: Vec2, dir: Vec2, aabb: &Aabb) -> Option<f32> {
+    let mut t_min = 0.0f32;
+    let mut t_max = f32::INFINITY;
+    for (o, d, lo, hi) in [
+        (origin.x, dir.x, aabb.min.x, aabb.max.x),
+        (origin.y, dir.y, aabb.min.y, aabb.max.y),
+    ] {
+        if d.abs() < f32::EPSILON {
+            if o < lo || o > hi {
+                return None;
+            }
+            continue;
+        }
+        let (t1, t2) = ((lo - o) / d, (hi - o) / d);
+        t_min = t_min.max(t1.min(t2));
+        t_max = t_max.min(t1.max(t2));
+        if t_min > t_max {
+            return None;
+        }
+    }
+    Some(t_min)
+}
+
+/// Heading of a camera looking from `position` at `target`, as an angle on
+/// the ground plane (radians, 0 = +X, π/2 = +Z) like the games' aim angles.
+pub fn camera_yaw(position: Vec3, target: Vec3) -> f32 {
+    (target.z - position.z).atan2(target.x - position.x)
+}
+
+/// An aim given relative to the camera instead of the world: a drag on
+/// screen plus the camera's yaw when it was made. Clients send this so the
+/// host does the one screen-to-world conversion, whatever the camera mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScreenAim {
+    /// Aim direction in screen axes (x right, y down, as pointer events
+    /// report it). Only the direction matters.
+    pub drag: Vec2,
+    /// The camera's heading, from [`camera_yaw`].
+    pub camera_yaw: f32,
+}
+
+impl ScreenAim {
+    /// The world aim angle this drag points along: screen up is the camera's
+    /// heading and screen right is a quarter turn toward +Z from it. `None`
+    /// for a zero or non-finite drag.
+    pub fn world_angle(&self) -> Option<f32> {
+        let Self { drag, camera_yaw } = *self;
+        let len_sq = drag.length_squared();
+        if !len_sq.is_finite() || len_sq <= DEGENERATE_LEN_SQ || !camera_yaw.is_finite() {
+            return None;
+        }
+        let angle = camera_yaw + drag.x.atan2(-drag.y);
+        Some(angle.rem_euclid(std::f32::consts::TAU))
+    }
+}
+
+/// Both parameters (ascending) where the line `origin + t * dir` meets the
+/// circle, if it does.
+fn line_circle(origin: Vec2, dir: Vec2, center: Vec2, radius: f32) -> Option<(f32, f32)> {
+    let f = origin - center;
+    let a = dir.length_squared();
+    let b = 2.0 * f.dot(dir);
+    let c = f.length_squared() - radius * radius;
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let sqrt_d = discriminant.sqrt();
+    Some(((-b - sqrt_d) / (2.0 * a), (-b + sqrt_d) / (2.0 * a)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(x: f32, y: f32) -> Vec2 {
+        Vec2::new(x, y)
+    }
+
+    #[test]
+    fn point_segment_distance_projects_and_clamps() {
+        let (a, b) = (v(0.0, 0.0), v(10.0, 0.0));
+        assert!((point_segment_distance(v(5.0, 3.0), a, b) - 3.0).abs() < 1e-5);
+        // Past the end: distance to the endpoint
+        assert!((point_segment_distance(v(13.0, 4.0), a, b) - 5.0).abs() < 1e-5);
+        // Degenerate segment: distance to the point
+        assert!((point_segment_distance(v(3.0, 4.0), a, a) - 5.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn segments_cross_excludes_touching() {
+        assert!(segments_cross(
+            v(0.0, 0.0),
+            v(2.0, 2.0),
+            v(0.0, 2.0),
+            v(2.0, 0.0)
+        ));
+        assert!(!segments_cross(
+            v(0.0, 0.0),
+            v(1.0, 1.0),
+            v(1.0, 1.0),
+            v(2.0, 0.0)
+        ));
+        assert!(!segments_cross(
+            v(0.0, 0.0),
+            v(1.0, 0.0),
+            v(0.0, 1.0),
+            v(1.0, 1.0)
+        ));
+    }
+
+    #[test]
+    fn ray_segment_hits_with_normal_facing_origin() {
+        let (t, n) = ray_segment(v(0.0, 0.0), v(1.0, 0.0), v(5.0, -1.0), v(5.0, 1.0)).unwrap();
+        assert!((t - 5.0).abs() < 1e-5);
+        assert!(n.x < 0.0, "Normal faces back toward the origin");
+        // Reversed segment winding gives the same normal
+        let (_, n2) = ray_segment(v(0.0, 0.0), v(1.0, 0.0), v(5.0, 1.0), v(5.0, -1.0)).unwrap();
+        assert_eq!(n, n2);
+        // Parallel, behind, past the end, and degenerate all miss
+        assert!(ray_segment(v(0.0, 0.0), v(1.0, 0.0), v(0.0, 1.0), v(5.0, 1.0)).is_none());
+        assert!(ray_segment(v(0.0, 0.0), v(-1.0, 0.0), v(5.0, -1.0), v(5.0, 1.0)).is_none());
+        assert!(ray_segment(v(0.0, 0.0), v(1.0, 0.0), v(5.0, 1.0), v(5.0, 2.0)).is_none());
+        assert!(ray_segment(v(0.0, 0.0), v(1.0, 0.0), v(5.0, 0.0), v(5.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn ray_circle_nearest_forward_hit() {
+        let hit = ray_circle(v(0.0, 0.0), v(1.0, 0.0), v(5.0, 0.0), 1.0).unwrap();
+        assert!((hit - 4.0).abs() < 1e-5);
+        let inside = ray_circle(v(5.0, 0.0), v(1.0, 0.0), v(5.0, 0.0), 1.0).unwrap();
+        assert!((inside - 1.0).abs() < 1e-5, "Exit point from inside");
+        assert!(ray_circle(v(0.0, 0.0), v(-1.0, 0.0), v(5.0, 0.0), 1.0).is_none());
+        assert!(ray_circle(v(0.0, 2.0), v(1.0, 0.0), v(5.0, 0.0), 1.0).is_none());
+    }
+
+    #[test]
+    fn segment_circle_covers_inside_and_through() {
+        let c = v(5.0, 0.0);
+        assert!(segment_intersects_circle(v(0.0, 0.0), v(10.0, 0.0), c, 1.0));
+        assert!(segment_intersects_circle(v(4.9, 0.0), v(5.1, 0.0), c, 1.0));
+        assert!(!segment_intersects_circle(v(0.0, 0.0), v(3.0, 0.0), c, 1.0));
+        assert!(!segment_intersects_circle(v(5.0, 0.0), v(5.0, 0.0), c, 1.0));
+    }
+
+    #[test]
+    fn ray_aabb_entry_distance() {
+        let aabb = Aabb::from_center(v(5.0, 0.0), 1.0, 1.0);
+        assert_eq!(ray_aabb(v(0.0, 0.0), v(1.0, 0.0), &aabb), Some(4.0));
+        assert_eq!(ray_aabb(v(5.0, 0.0), v(1.0, 0.0), &aabb), Some(0.0));
+        assert_eq!(ray_aabb(v(0.0, 3.0), v(1.0, 0.0), &aabb), None);
+        assert_eq!(ray_aabb(v(0.0, 0.0), v(-1.0, 0.0), &aabb), None);
+    }
+
+    #[test]
+    fn aabb_overlap_is_strict_and_touch_is_inclusive() {
+        let a = Aabb::from_corners(v(1.0, 1.0), v(0.0, 0.0));
+        let b = Aabb::from_corners(v(1.0, 0.0), v(2.0, 1.0));
+        assert!(!a.overlaps(&b), "Shared edge");
+        assert!(a.touches(&b, 0.0));
+        let c = Aabb::from_corners(v(1.05, 0.0), v(2.0, 1.0));
+        assert!(!a.touches(&c, 0.01));
+        assert!(a.touches(&c, 0.1));
+    }
+
+    #[test]
+    fn vec3_serializes_like_a_plain_struct() {
+        let p = Vec3::new(1.0, 2.0, 3.0);
+        let json = serde_json::to_value(p).unwrap();
+        assert_eq!(json, serde_json::json!({"x": 1.0, "y": 2.0, "z": 3.0}));
+        assert!((p.distance_xz(Vec3::new(4.0, 9.0, 7.0)) - 5.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn screen_aim_follows_the_camera() {
+        use std::f32::consts::{FRAC_PI_2, PI};
+        let angle_eq = |a: f32, b: f32| (a - b).sin().abs() < 1e-4 && (a - b).cos() > 0.0;
+        // Camera behind the ball looking along +Z, as the golf follow camera
+        let yaw = camera_yaw(Vec3::new(0.0, 15.0, -2.0), Vec3::new(0.0, 0.0, 0.0));
+        assert!(angle_eq(yaw, FRAC_PI_2));
+        let aim = |x, y| {
+            ScreenAim {
+                drag: v(x, y),
+                camera_yaw: yaw,
+            }
+            .world_angle()
+            .unwrap()
+        };
+        assert!(
+            angle_eq(aim(0.0, -1.0), FRAC_PI_2),
+            "Up is away from camera"
+        );
+        assert!(angle_eq(aim(0.0, 1.0), -FRAC_PI_2), "Down is toward it");
+        // Looking along +Z with Y up, screen right is world -X
+        assert!(angle_eq(aim(1.0, 0.0), PI));
+        assert!(angle_eq(aim(-1.0, 0.0), 0.0));
+
+        // The same drag turns with the camera
+        let turned = ScreenAim {
+            drag: v(0.0, -5.0),
+            camera_yaw: 0.3,
+        };
+        assert!(angle_eq(turned.world_angle().unwrap(), 0.3));
+    }
+
+    #[test]
+    fn screen_aim_rejects_degenerate_drags() {
+        let aim = |drag, camera_yaw| ScreenAim { drag, camera_yaw }.world_angle();
+        assert_eq!(aim(Vec2::ZERO, 0.0), None);
+        assert_eq!(aim(v(f32::NAN, 1.0), 0.0), None);
+        assert_eq!(aim(v(1.0, 0.0), f32::INFINITY), None);
+    }
+}
@@ -0,0 +1,147 @@
+//! Generated display names for players who join without one: an adjective
+//! and an animal, like "Brave Otter". Names are picked from a seed, so the
+//! same room, player and re-roll count always give the same suggestion,
+//! and a room never hands out a name someone in it already has. Both word
+//! lists are hand-picked, and every pairing of them is family-friendly.
+
+use crate::game_trait::PlayerId;
+use crate::player::Player;
+
+pub const ADJECTIVES: [&str; 32] = [
+    "Brave", "Bouncy", "Breezy", "Bright", "Calm", "Cheery", "Clever", "Cosmic", "Cozy", "Dapper",
+    "Eager", "Fuzzy", "Gentle", "Giddy", "Golden", "Happy", "Jolly", "Lucky", "Mellow", "Mighty",
+    "Nimble", "Peppy", "Plucky", "Quick", "Quiet", "Snappy", "Sparkly", "Speedy", "Sunny", "Swift",
+    "Witty", "Zesty",
+];
+
+pub const ANIMALS: [&str; 32] = [
+    "Badger",
+    "Beaver",
+    "Bison",
+    "Chinchilla",
+    "Cheetah",
+    "Dolphin",
+    "Falcon",
+    "Ferret",
+    "Gecko",
+    "Heron",
+    "Koala",
+    "Lemur",
+    "Llama",
+    "Lynx",
+    "Manatee",
+    "Marmot",
+    "Meerkat",
+    "Moose",
+    "Narwhal",
+    "Ocelot",
+    "Otter",
+    "Owl",
+    "Panda",
+    "Pelican",
+    "Penguin",
+    "Puffin",
+    "Quokka",
+    "Raccoon",
+    "Sloth",
+    "Tapir",
+    "Walrus",
+    "Wombat",
+];
+
+/// Distinct adjective-animal pairs.
+const COMBINATIONS: usize = ADJECTIVES.len() * ANIMALS.len();
+
+/// Odd, so stepping by it visits every pair before repeating, and large
+/// enough that consecutive picks differ in both words.
+const STRIDE: usize = 389;
+
+/// Seed for `player_id`'s name in `room_code` after `roll` re-rolls.
+pub fn seed(room_code: &str, player_id: PlayerId, roll: u32) -> u64 {
+    // FNV-1a over the code, then mix in the player and roll
+    let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in room_code.bytes() {
+        h = (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3);
+    }
+    mix(h ^ player_id.rotate_left(32) ^ u64::from(roll))
+}
+
+/// SplitMix64 finalizer, so nearby seeds land far apart.
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// The name for `seed`, skipping any in `taken` (compared without case).
+/// Once every pair is taken, a number is appended.
+pub fn generate(seed: u64, taken: &[&str]) -> String {
+    let start = (seed % COMBINATIONS as u64) as usize;
+    let is_free = |name: &str| !taken.iter().any(|t| t.eq_ignore_ascii_case(name));
+    for step in 0..COMBINATIONS {
+        let i = (start + step * STRIDE) % COMBINATIONS;
+        let name = format!(
+            "{} {}",
+            ADJECTIVES[i % ADJECTIVES.len()],
+            ANIMALS[i / ADJECTIVES.len()]
+        );
+        if is_free(&name) {
+            return name;
+        }
+    }
+    let base = format!(
+        "{} {}",
+        ADJECTIVES[start % ADJECTIVES.len()],
+        ANIMALS[start / ADJECTIVES.len()]
+    );
+    (2..)
+        .map(|n| format!("{base} {n}"))
+        .find(|name| is_free(name))
+        .unwrap_or(base)
+}
+
+/// Names already in use in a room.
+pub fn taken_names(players: &[Player]) -> Vec<&str> {
+    players.iter().map(|p| p.display_name.as_str()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_deterministic_and_unique_per_room() {
+        let a = generate(seed("ABCD-1234", 3, 0), &[]);
+        assert_eq!(a, generate(seed("ABCD-1234", 3, 0), &[]));
+        assert_ne!(
+            a,
+            generate(seed("ABCD-1234", 3, 1), &[]),
+            "Re-rolls move on"
+        );
+
+        // The same seed in a filling room keeps finding free names
+        let mut taken: Vec<String> = Vec::new();
+        for _ in 0..8 {
+            let refs: Vec<&str> = taken.iter().map(String::as_str).collect();
+            let name = generate(seed("ABCD-1234", 1, 0), &refs);
+            assert!(!taken.contains(&name), "{name} handed out twice");
+            assert!(name.len() <= 32);
+            taken.push(name);
+        }
+
+        // Numbers only appear once every pair is in use
+        let mut all: Vec<String> = ADJECTIVES
+            .iter()
+            .flat_map(|a| ANIMALS.iter().map(move |b| format!("{a} {b}")))
+            .collect();
+        let refs: Vec<&str> = all.iter().map(String::as_str).collect();
+        let numbered = generate(7, &refs);
+        assert!(numbered.ends_with(" 2"), "{numbered}");
+        all.push(numbered);
+        let refs: Vec<&str> = all.iter().map(String::as_str).collect();
+        assert!(generate(7, &refs).ends_with(" 3"));
+
+        let shouted = a.to_uppercase();
+        assert_ne!(generate(seed("ABCD-1234", 3, 0), &[&shouted]), a);
+    }
+}
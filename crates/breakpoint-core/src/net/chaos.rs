@@ -0,0 +1,245 @@
+//! Artificial network degradation for testing prediction, interpolation, and
+//! reconnection under bad networks. Debug-only: disabled unless explicitly
+//! enabled in server config or relay flags. With the `chaos-pipe` feature,
+//! [`spawn_chaos_pipe`] applies it to a stream of messages on tokio.
+
+use std::time::Duration;
+
+use rand::Rng;
+use serde::Deserialize;
+
+/// Extra hold applied to reordered messages on top of the normal delay, so
+/// messages sent shortly after them overtake.
+const REORDER_HOLD: Duration = Duration::from_millis(50);
+
+/// Per-connection network fault injection settings.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ChaosConfig {
+    /// Master switch. All other settings are ignored when false.
+    pub enabled: bool,
+    /// Fixed one-way delay added to every message.
+    pub latency_ms: u32,
+    /// Uniform random extra delay in `[0, jitter_ms]`.
+    pub jitter_ms: u32,
+    /// Probability in `[0, 1]` that a message is dropped.
+    pub drop_rate: f32,
+    /// Probability in `[0, 1]` that a message is held back so later ones overtake it.
+    pub reorder_rate: f32,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            latency_ms: 0,
+            jitter_ms: 0,
+            drop_rate: 0.0,
+            reorder_rate: 0.0,
+        }
+    }
+}
+
+/// What to do with a single message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChaosVerdict {
+    Drop,
+    Deliver(Duration),
+}
+
+impl ChaosConfig {
+    /// Whether chaos is enabled and would affect any message.
+    pub fn is_active(&self) -> bool {
+        self.enabled
+            && (self.latency_ms > 0
+                || self.jitter_ms > 0
+                || self.drop_rate > 0.0
+                || self.reorder_rate > 0.0)
+    }
+
+    /// Decide the fate of one message.
+    pub fn roll(&self, rng: &mut impl Rng) -> ChaosVerdict {
+        if !self.enabled {
+            return ChaosVerdict::Deliver(Duration::ZERO);
+        }
+        if self.drop_rate > 0.0 && rng.random::<f32>() < self.drop_rate {
+            return ChaosVerdict::Drop;
+        }
+        let jitter = if self.jitter_ms > 0 {
+            rng.random_range(0..=self.jitter_ms)
+        } else {
+            0
+        };
+        // u64 millis can't overflow from two u32s
+        let mut delay = Duration::from_millis(u64::from(self.latency_ms) + u64::from(jitter));
+        if self.reorder_rate > 0.0 && rng.random::<f32>() < self.reorder_rate {
+            delay += Duration::from_millis(u64::from(self.jitter_ms)) + REORDER_HOLD;
+        }
+        ChaosVerdict::Deliver(delay)
+    }
+
+    /// Parse a compact spec such as `latency=120,jitter=40,drop=0.05,reorder=0.02`.
+    /// A parsed spec is always enabled.
+    pub fn parse_spec(spec: &str) -> Result<Self, String> {
+        let mut cfg = Self {
+            enabled: true,
+            ..Self::default()
+        };
+        for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("expected key=value, got '{part}'"))?;
+            let bad = |e: &dyn std::fmt::Display| format!("invalid value for {key}: {e}");
+            match key {
+                "latency" => cfg.latency_ms = value.parse().map_err(|e| bad(&e))?,
+                "jitter" => cfg.jitter_ms = value.parse().map_err(|e| bad(&e))?,
+                "drop" => cfg.drop_rate = value.parse().map_err(|e| bad(&e))?,
+                "reorder" => cfg.reorder_rate = value.parse().map_err(|e| bad(&e))?,
+                _ => return Err(format!("unknown chaos key '{key}'")),
+            }
+        }
+        cfg.validate()?;
+        Ok(cfg)
+    }
+
+    /// Check that rates are probabilities.
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, rate) in [
+            ("drop_rate", self.drop_rate),
+            ("reorder_rate", self.reorder_rate),
+        ] {
+            if !(0.0..=1.0).contains(&rate) {
+                return Err(format!("{name} must be within [0, 1], got {rate}"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Insert a fault-injecting stage between a stream and its consumer.
+///
+/// Each message is independently dropped or delayed according to `chaos`.
+/// Delayed messages are released by their own timers, so jitter and the
+/// reorder hold naturally produce out-of-order delivery.
+#[cfg(feature = "chaos-pipe")]
+pub fn spawn_chaos_pipe<T: Send + 'static>(
+    mut rx: impl futures::Stream<Item = T> + Unpin + Send + 'static,
+    chaos: ChaosConfig,
+    capacity: usize,
+) -> tokio::sync::mpsc::Receiver<T> {
+    use futures::StreamExt;
+    use rand::SeedableRng;
+
+    let (tx, out) = tokio::sync::mpsc::channel(capacity);
+    tokio::spawn(async move {
+        let mut rng = rand::rngs::StdRng::from_os_rng();
+        while let Some(item) = rx.next().await {
+            match chaos.roll(&mut rng) {
+                ChaosVerdict::Drop => {},
+                ChaosVerdict::Deliver(delay) if delay.is_zero() => {
+                    if tx.send(item).await.is_err() {
+                        break;
+                    }
+                },
+                ChaosVerdict::Deliver(delay) => {
+                    let tx = tx.clone();
+                    tokio::spawn(async move {
+                        tokio::time::sleep(delay).await;
+                        let _ = tx.send(item).await;
+                    });
+                },
+            }
+        }
+    });
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn disabled_delivers_immediately() {
+        let cfg = ChaosConfig {
+            latency_ms: 500,
+            drop_rate: 1.0,
+            ..ChaosConfig::default()
+        };
+        let mut rng = StdRng::seed_from_u64(1);
+        assert!(!cfg.is_active());
+        assert_eq!(cfg.roll(&mut rng), ChaosVerdict::Deliver(Duration::ZERO));
+    }
+
+    #[test]
+    fn latency_and_jitter_bound_delay() {
+        let cfg = ChaosConfig::parse_spec("latency=100,jitter=20").unwrap();
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..200 {
+            match cfg.roll(&mut rng) {
+                ChaosVerdict::Deliver(d) => {
+                    assert!(d >= Duration::from_millis(100));
+                    assert!(d <= Duration::from_millis(120));
+                },
+                ChaosVerdict::Drop => panic!("drop_rate is zero"),
+            }
+        }
+    }
+
+    #[test]
+    fn extreme_latency_does_not_overflow() {
+        let cfg = ChaosConfig::parse_spec("latency=4294967295,jitter=1").unwrap();
+        let mut rng = StdRng::seed_from_u64(5);
+        let ChaosVerdict::Deliver(d) = cfg.roll(&mut rng) else {
+            panic!("drop_rate is zero");
+        };
+        assert!(d >= Duration::from_millis(u64::from(u32::MAX)));
+    }
+
+    #[test]
+    fn drop_rate_drops_roughly_that_fraction() {
+        let cfg = ChaosConfig::parse_spec("drop=0.25").unwrap();
+        let mut rng = StdRng::seed_from_u64(42);
+        let drops = (0..4000)
+            .filter(|_| cfg.roll(&mut rng) == ChaosVerdict::Drop)
+            .count();
+        assert!((800..1200).contains(&drops), "got {drops} drops");
+    }
+
+    #[test]
+    fn reordered_messages_exceed_max_jitter() {
+        let cfg = ChaosConfig::parse_spec("latency=10,jitter=10,reorder=1.0").unwrap();
+        let mut rng = StdRng::seed_from_u64(3);
+        let ChaosVerdict::Deliver(d) = cfg.roll(&mut rng) else {
+            panic!("drop_rate is zero");
+        };
+        assert!(d > Duration::from_millis(20));
+    }
+
+    #[test]
+    fn parse_spec_rejects_bad_input() {
+        assert!(ChaosConfig::parse_spec("latency").is_err());
+        assert!(ChaosConfig::parse_spec("speed=3").is_err());
+        assert!(ChaosConfig::parse_spec("drop=abc").is_err());
+        assert!(ChaosConfig::parse_spec("drop=1.5").is_err());
+    }
+
+    #[cfg(feature = "chaos-pipe")]
+    #[tokio::test]
+    async fn drop_all_closes_without_delivery() {
+        let chaos = ChaosConfig::parse_spec("drop=1.0").unwrap();
+        let mut out = spawn_chaos_pipe(futures::stream::iter(0..5), chaos, 8);
+        assert_eq!(out.recv().await, None);
+    }
+
+    #[cfg(feature = "chaos-pipe")]
+    #[tokio::test]
+    async fn latency_delays_delivery() {
+        let chaos = ChaosConfig::parse_spec("latency=50").unwrap();
+        let start = tokio::time::Instant::now();
+        let mut out = spawn_chaos_pipe(futures::stream::iter([1u8]), chaos, 8);
+        assert_eq!(out.recv().await, Some(1));
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+}
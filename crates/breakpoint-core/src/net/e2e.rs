@@ -0,0 +1,213 @@
+//! End-to-end encryption for games played through a relay. The host
+//! generates a room secret and shares it out-of-band (read aloud, pasted
+//! in chat); host and clients derive the room key from it and the room
+//! code, and seal every game message so the relay only ever forwards
+//! ciphertext. Relay envelopes and the join that tells the relay which
+//! room to route to stay in the clear.
+//!
+//! Sealed frames are `[Sealed | nonce (24) | ciphertext]`. When a player
+//! leaves, the host rotates to a new secret, sending it sealed under the
+//! old key with `RoomRekey`; new players join with the secret the host
+//! shows now. Rotation only keeps out a departed player who no longer
+//! sees the room's traffic: the rekey is readable with the old secret, so
+//! one who still watches it (or colludes with the relay) learns the new
+//! key too.
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use hkdf::Hkdf;
+use rand::Rng;
+use sha2::Sha256;
+
+use super::messages::MessageType;
+use super::protocol::{MAX_MESSAGE_SIZE, ProtocolError};
+
+/// Bytes a sealed frame adds in front of the ciphertext.
+pub const SEALED_HEADER_LEN: usize = 1 + NONCE_LEN;
+
+const NONCE_LEN: usize = 24;
+
+/// Poly1305 tag appended to each ciphertext.
+const TAG_LEN: usize = 16;
+
+/// Characters of a room secret: no 0/O or 1/I, so secrets survive being
+/// read aloud.
+const SECRET_ALPHABET: &[u8; 32] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+/// Groups of four characters in a room secret (80 bits).
+const SECRET_GROUPS: usize = 4;
+
+/// A new room secret in `ABCD-EFGH-JKLM-NPQR` format.
+pub fn generate_secret() -> String {
+    let mut rng = rand::rng();
+    (0..SECRET_GROUPS)
+        .map(|_| {
+            (0..4)
+                .map(|_| SECRET_ALPHABET[rng.random_range(0..SECRET_ALPHABET.len())] as char)
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// A secret as typed by a player, with case, spaces and dashes ignored.
+fn normalize_secret(secret: &str) -> String {
+    secret
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+fn derive(room_code: &str, secret: &str) -> XChaCha20Poly1305 {
+    let hk = Hkdf::<Sha256>::new(
+        Some(room_code.as_bytes()),
+        normalize_secret(secret).as_bytes(),
+    );
+    let mut key = [0u8; 32];
+    // 32 bytes is always a valid HKDF-SHA256 output length
+    let _ = hk.expand(b"breakpoint relay e2e v1", &mut key);
+    XChaCha20Poly1305::new(&key.into())
+}
+
+/// Whether `data` is a sealed frame.
+pub fn is_sealed(data: &[u8]) -> bool {
+    data.first() == Some(&(MessageType::Sealed as u8))
+}
+
+/// The room key, plus the one it replaced so messages sent before a
+/// rotation still open.
+pub struct RoomCipher {
+    room_code: String,
+    /// Rotations seen, so a replayed `RoomRekey` can't roll the key back.
+    epoch: u32,
+    key: XChaCha20Poly1305,
+    previous: Option<XChaCha20Poly1305>,
+}
+
+impl RoomCipher {
+    pub fn new(room_code: &str, secret: &str) -> Self {
+        Self {
+            room_code: room_code.to_string(),
+            epoch: 0,
+            key: derive(room_code, secret),
+            previous: None,
+        }
+    }
+
+    pub fn epoch(&self) -> u32 {
+        self.epoch
+    }
+
+    /// Switch to `secret` as rotation `epoch`. Epochs only move forward;
+    /// returns whether the key changed.
+    pub fn rotate(&mut self, epoch: u32, secret: &str) -> bool {
+        if epoch <= self.epoch {
+            return false;
+        }
+        let key = derive(&self.room_code, secret);
+        self.previous = Some(std::mem::replace(&mut self.key, key));
+        self.epoch = epoch;
+        true
+    }
+
+    /// Encrypt an encoded message into a sealed frame.
+    pub fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>, ProtocolError> {
+        let total = SEALED_HEADER_LEN + plaintext.len() + TAG_LEN;
+        if total > MAX_MESSAGE_SIZE {
+            return Err(ProtocolError::PayloadTooLarge(total));
+        }
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::rng().fill(&mut nonce);
+        let mut buf = Vec::with_capacity(total);
+        buf.push(MessageType::Sealed as u8);
+        buf.extend_from_slice(&nonce);
+        let ciphertext = self
+            .key
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext,
+                    aad: &buf[..1],
+                },
+            )
+            .map_err(|_| ProtocolError::SerializeError("encryption failed".to_string()))?;
+        buf.extend_from_slice(&ciphertext);
+        Ok(buf)
+    }
+
+    /// Decrypt a sealed frame back into the encoded message it carries,
+    /// with the current key or the one before it. Fails for frames sealed
+    /// with another room's key or an older one, or changed in transit.
+    pub fn open(&self, sealed: &[u8]) -> Result<Vec<u8>, ProtocolError> {
+        if !is_sealed(sealed) || sealed.len() < SEALED_HEADER_LEN + TAG_LEN {
+            return Err(ProtocolError::DeserializeError(
+                "not a sealed frame".to_string(),
+            ));
+        }
+        let nonce = XNonce::from_slice(&sealed[1..SEALED_HEADER_LEN]);
+        let payload = || Payload {
+            msg: &sealed[SEALED_HEADER_LEN..],
+            aad: &sealed[..1],
+        };
+        self.key
+            .decrypt(nonce, payload())
+            .or_else(|e| match self.previous {
+                Some(ref previous) => previous.decrypt(nonce, payload()),
+                None => Err(e),
+            })
+            .map_err(|_| ProtocolError::DeserializeError("sealed frame failed to open".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sealed_frames_open_only_with_the_room_key() {
+        let secret = generate_secret();
+        assert_eq!(secret.len(), 19);
+        let host = RoomCipher::new("ABCD-1234", &secret);
+        let client = RoomCipher::new("ABCD-1234", &secret.to_lowercase().replace('-', " "));
+
+        let sealed = host.seal(b"\x11hello").unwrap();
+        assert!(is_sealed(&sealed));
+        assert!(
+            !sealed.windows(5).any(|w| w == b"hello"),
+            "Relay sees no plaintext"
+        );
+        assert_eq!(client.open(&sealed).unwrap(), b"\x11hello");
+
+        let other_room = RoomCipher::new("WXYZ-9876", &secret);
+        assert!(other_room.open(&sealed).is_err());
+        let wrong_secret = RoomCipher::new("ABCD-1234", &generate_secret());
+        assert!(wrong_secret.open(&sealed).is_err());
+
+        let mut tampered = sealed.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(client.open(&tampered).is_err());
+        assert!(client.open(b"\x11hello").is_err());
+    }
+
+    #[test]
+    fn rotation_keeps_one_previous_key() {
+        let mut host = RoomCipher::new("ABCD-1234", "first");
+        let mut client = RoomCipher::new("ABCD-1234", "first");
+        let mut departed = RoomCipher::new("ABCD-1234", "first");
+        let in_flight = client.seal(b"input").unwrap();
+
+        assert!(host.rotate(1, "second"));
+        assert!(!host.rotate(1, "second"), "Epochs only move forward");
+        assert_eq!(host.open(&in_flight).unwrap(), b"input");
+        assert!(client.rotate(1, "second"));
+
+        let after = host.seal(b"state").unwrap();
+        assert_eq!(client.open(&after).unwrap(), b"state");
+        assert!(departed.open(&after).is_err(), "Old members are locked out");
+        assert!(!departed.rotate(0, "second"));
+
+        assert!(host.rotate(2, "third"));
+        assert!(host.open(&in_flight).is_err(), "Only one old key is kept");
+    }
+}
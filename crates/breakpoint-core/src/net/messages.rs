@@ -1,10 +1,20 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
+use crate::announcement::Announcement;
+use crate::bot::BotDifficulty;
+use crate::debug_command::DebugCommand;
 use crate::events::Event;
-use crate::game_trait::PlayerId;
+use crate::game_trait::{GameConfigDelta, PlayerId};
+use crate::heatmap::RoundHeatmap;
+use crate::i18n::LocalizedText;
 use crate::overlay::config::OverlayConfigMsg;
 use crate::player::{Player, PlayerColor};
 use crate::room::{RoomConfig, RoomState};
+use crate::round_phase::{RoundPacing, RoundPhase};
+use crate::tutorial::TutorialChecklist;
+use crate::victory::VictorySummary;
 
 /// Network message type discriminator.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -19,9 +29,40 @@ pub enum MessageType {
     RequestGameStart = 0x30,
     AddBot = 0x31,
     RemoveBot = 0x32,
+    SetFriend = 0x33,
+    InviteFriend = 0x34,
+    ManageJoinQueue = 0x35,
+    SetBotDifficulty = 0x36,
+    CastCourseVote = 0x37,
+    AddLocalPlayer = 0x38,
+    RemoveLocalPlayer = 0x39,
+    AdvanceRound = 0x3A,
+    DebugCommand = 0x3B,
+    LobbyToyInput = 0x3C,
+    BotAck = 0x3D,
+    RerollName = 0x3E,
+    ClockSync = 0x3F,
 
     // Server -> Client
     JoinRoomResponse = 0x06,
+    JoinQueued = 0x07,
+    JoinQueueUpdate = 0x08,
+    RoomTransfer = 0x09,
+
+    // Client -> Server (party management)
+    ReshapeRoom = 0x0A,
+    DraftPick = 0x0B,
+
+    // Transport migration: the first message on a new connection, and
+    // the server's answer
+    MigrateTransport = 0x0C,
+    TransportMigrated = 0x0D,
+
+    // Client -> Server (mid-round settings)
+    ChangeGameConfig = 0x0E,
+
+    // Client -> Server (friends): opt in to showing friends where you play
+    SetPresenceSharing = 0x0F,
 
     // Server -> Client (game lifecycle)
     GameState = 0x10,
@@ -30,6 +71,13 @@ pub enum MessageType {
     GameStart = 0x13,
     RoundEnd = 0x14,
     GameEnd = 0x15,
+    RoundPhase = 0x17,
+    CourseVote = 0x18,
+    Intermission = 0x19,
+    LobbyToyState = 0x1A,
+    ClockSyncReply = 0x1B,
+    Standings = 0x1C,
+    TeamDraft = 0x1D,
 
     // Server -> Client (Alert channel)
     AlertEvent = 0x20,
@@ -39,8 +87,33 @@ pub enum MessageType {
     // Overlay config
     OverlayConfig = 0x23,
 
+    // Server -> Client (operator announcements and MOTD)
+    Announcements = 0x24,
+
     // Server -> Client (large static data, sent once or on change)
     CourseUpdate = 0x16,
+    // Server -> Client (one-shot visual effects, sent once)
+    GameEffects = 0x1E,
+    // Server -> Client (one player's hidden information, sent only to them)
+    PrivateState = 0x1F,
+
+    // Server -> Client (friends and invites)
+    SocialUpdate = 0x40,
+    FriendInvite = 0x41,
+    // Server -> Client (onboarding)
+    TutorialProgress = 0x42,
+
+    // Relay <-> hosting client (host-authoritative LAN games)
+    RelayRoomCreated = 0x50,
+    RelayFromPeer = 0x51,
+    RelayPeerLeft = 0x52,
+    RelayToPeer = 0x53,
+    RoomRekey = 0x54,
+
+    // Server -> Client (framing): several messages in one WebSocket frame
+    Batch = 0x60,
+    // Either direction (framing): an end-to-end encrypted message
+    Sealed = 0x61,
 }
 
 impl MessageType {
@@ -52,6 +125,15 @@ impl MessageType {
             0x04 => Some(Self::ClaimAlert),
             0x05 => Some(Self::ChatMessage),
             0x06 => Some(Self::JoinRoomResponse),
+            0x07 => Some(Self::JoinQueued),
+            0x08 => Some(Self::JoinQueueUpdate),
+            0x09 => Some(Self::RoomTransfer),
+            0x0A => Some(Self::ReshapeRoom),
+            0x0B => Some(Self::DraftPick),
+            0x0C => Some(Self::MigrateTransport),
+            0x0D => Some(Self::TransportMigrated),
+            0x0E => Some(Self::ChangeGameConfig),
+            0x0F => Some(Self::SetPresenceSharing),
             0x10 => Some(Self::GameState),
             0x11 => Some(Self::PlayerList),
             0x12 => Some(Self::RoomConfigMsg),
@@ -59,13 +141,46 @@ impl MessageType {
             0x14 => Some(Self::RoundEnd),
             0x15 => Some(Self::GameEnd),
             0x16 => Some(Self::CourseUpdate),
+            0x17 => Some(Self::RoundPhase),
+            0x18 => Some(Self::CourseVote),
+            0x19 => Some(Self::Intermission),
+            0x1A => Some(Self::LobbyToyState),
+            0x1B => Some(Self::ClockSyncReply),
+            0x1C => Some(Self::Standings),
+            0x1D => Some(Self::TeamDraft),
+            0x1E => Some(Self::GameEffects),
+            0x1F => Some(Self::PrivateState),
             0x20 => Some(Self::AlertEvent),
             0x21 => Some(Self::AlertClaimed),
             0x22 => Some(Self::AlertDismissed),
             0x23 => Some(Self::OverlayConfig),
+            0x24 => Some(Self::Announcements),
             0x30 => Some(Self::RequestGameStart),
             0x31 => Some(Self::AddBot),
             0x32 => Some(Self::RemoveBot),
+            0x33 => Some(Self::SetFriend),
+            0x34 => Some(Self::InviteFriend),
+            0x35 => Some(Self::ManageJoinQueue),
+            0x36 => Some(Self::SetBotDifficulty),
+            0x37 => Some(Self::CastCourseVote),
+            0x38 => Some(Self::AddLocalPlayer),
+            0x39 => Some(Self::RemoveLocalPlayer),
+            0x3A => Some(Self::AdvanceRound),
+            0x3B => Some(Self::DebugCommand),
+            0x3C => Some(Self::LobbyToyInput),
+            0x3D => Some(Self::BotAck),
+            0x3E => Some(Self::RerollName),
+            0x3F => Some(Self::ClockSync),
+            0x40 => Some(Self::SocialUpdate),
+            0x41 => Some(Self::FriendInvite),
+            0x42 => Some(Self::TutorialProgress),
+            0x50 => Some(Self::RelayRoomCreated),
+            0x51 => Some(Self::RelayFromPeer),
+            0x52 => Some(Self::RelayPeerLeft),
+            0x53 => Some(Self::RelayToPeer),
+            0x54 => Some(Self::RoomRekey),
+            0x60 => Some(Self::Batch),
+            0x61 => Some(Self::Sealed),
             _ => None,
         }
     }
@@ -75,7 +190,9 @@ impl MessageType {
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct JoinRoomMsg {
+    /// Room to join, by code or vanity alias; empty to create one.
     pub room_code: String,
+    /// Display name; blank to be given a generated one.
     pub player_name: String,
     pub player_color: PlayerColor,
     /// Protocol version for compatibility checks. Defaults to 0 for
@@ -85,6 +202,28 @@ pub struct JoinRoomMsg {
     /// Session token from a previous connection, used for reconnection.
     #[serde(default)]
     pub session_token: Option<String>,
+    /// Long-lived secret kept in the client's local storage. The server
+    /// derives the player's public friend ID from it; without one the
+    /// player has no friends list or recent players.
+    #[serde(default)]
+    pub identity_key: Option<String>,
+    /// The client unpacks `Batch` frames, so the server may coalesce
+    /// messages queued for it into one frame.
+    #[serde(default)]
+    pub accepts_batches: bool,
+    /// Vanity alias to claim when creating a room, so others can join
+    /// with it instead of the generated code.
+    #[serde(default)]
+    pub room_alias: Option<String>,
+    /// Token from the server's `[bot_api]` config, for external bot
+    /// clients. The player is marked `external_bot` and drives itself
+    /// with `BotAck` instead of `PlayerInput`.
+    #[serde(default)]
+    pub bot_token: Option<String>,
+    /// Wire-format game ID to play that game's tutorial in a new solo room.
+    /// Only honored when creating a room.
+    #[serde(default)]
+    pub tutorial: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -93,11 +232,173 @@ pub struct JoinRoomResponseMsg {
     pub player_id: Option<PlayerId>,
     pub room_code: Option<String>,
     pub room_state: Option<RoomState>,
-    pub error: Option<String>,
+    /// Why the join failed, as a message code the client renders in the
+    /// player's language.
+    pub error: Option<LocalizedText>,
     /// Session token for reconnection. Clients should store this and send
     /// it back in JoinRoomMsg to reclaim their player slot.
     #[serde(default)]
     pub session_token: Option<String>,
+    /// The lobby toy's state when joining a room that waits in its lobby,
+    /// so the target shows before anyone scores. Later hits arrive as
+    /// `LobbyToyState`.
+    #[serde(default)]
+    pub lobby_toy: Option<Vec<u8>>,
+}
+
+/// `JoinRoomResponse` as clients before protocol version 6 decode it, with
+/// a plain-string error. Frozen so a version mismatch still reaches them as
+/// a readable rejection; never extend it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LegacyJoinRoomResponseMsg {
+    pub success: bool,
+    pub player_id: Option<PlayerId>,
+    pub room_code: Option<String>,
+    pub room_state: Option<RoomState>,
+    pub error: Option<String>,
+}
+
+/// Sent instead of a `JoinRoomResponse` when the room is full or the
+/// leader approves joins: the connection waits in the room's join queue.
+/// Re-sent whenever the position changes; a `JoinRoomResponse` follows on
+/// admission or denial.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JoinQueuedMsg {
+    pub room_code: String,
+    /// 1-based place in line.
+    pub position: u16,
+    pub queue_len: u16,
+    /// The leader must admit this player before they can enter.
+    pub awaiting_approval: bool,
+}
+
+/// A connection waiting in a room's join queue, as shown to the room.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QueuedJoinEntry {
+    pub ticket: u64,
+    pub display_name: String,
+    /// Admitted by the leader; enters when a slot opens.
+    pub approved: bool,
+}
+
+/// The room's join queue, sent to its players whenever it changes.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JoinQueueUpdateMsg {
+    pub require_approval: bool,
+    pub entries: Vec<QueuedJoinEntry>,
+}
+
+/// Leader actions on the room's join queue.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum JoinQueueAction {
+    /// Hold every new join until the leader admits it.
+    RequireApproval(bool),
+    Admit(u64),
+    Deny(u64),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ManageJoinQueueMsg {
+    pub action: JoinQueueAction,
+}
+
+/// How a leader wants to reshape their room. Both only work from the
+/// lobby.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum RoomReshape {
+    /// Offer everyone a move into the room with this code or alias.
+    MergeInto(String),
+    /// Offer half the lobby a move into a new room, balanced by session
+    /// score.
+    Split,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReshapeRoomMsg {
+    pub action: RoomReshape,
+}
+
+/// Why a player is offered a move to another room.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TransferReason {
+    Merge,
+    Split,
+}
+
+/// An offer to move to another room, sent when a room is merged or split.
+/// Accepting means joining `room_code` with `transfer_token` as the
+/// session token, which carries the player's session score over; the
+/// offer lapses after `expires_in_secs`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RoomTransferMsg {
+    pub room_code: String,
+    pub transfer_token: String,
+    pub reason: TransferReason,
+    pub expires_in_secs: u32,
+}
+
+/// Sent as the first message on a new connection, instead of `JoinRoom`,
+/// to move a live session onto it. The player keeps their ID, room,
+/// identity, and hot-seat guests; the old connection is closed without
+/// the room seeing them leave.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MigrateTransportMsg {
+    /// The session token of the connection being replaced.
+    pub session_token: String,
+    pub protocol_version: u8,
+    /// The client unpacks `Batch` frames on the new connection.
+    #[serde(default)]
+    pub accepts_batches: bool,
+}
+
+/// The answer to `MigrateTransport`, on the new connection.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TransportMigratedMsg {
+    pub success: bool,
+    pub player_id: Option<PlayerId>,
+    pub room_code: Option<String>,
+    /// Replaces the old token, which no longer migrates or reconnects.
+    pub session_token: Option<String>,
+    /// Newest input tick the server took from the old connection, so the
+    /// client carries its input sequence on from there.
+    pub last_input_tick: Option<u32>,
+    /// Why the session could not move; the client falls back to joining.
+    pub error: Option<LocalizedText>,
+}
+
+/// Sent by the relay to a hosting client once its room exists.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RelayRoomCreatedMsg {
+    pub room_code: String,
+}
+
+/// A peer's message, wrapped by the relay so the host knows who sent it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RelayFromPeerMsg {
+    pub peer_id: u64,
+    pub data: Vec<u8>,
+}
+
+/// A peer's connection to the relay closed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RelayPeerLeftMsg {
+    pub peer_id: u64,
+}
+
+/// A host message for one peer. The relay unwraps it; anything else the
+/// host sends goes to every peer.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RelayToPeerMsg {
+    pub peer_id: u64,
+    pub data: Vec<u8>,
+}
+
+/// A relay host's new end-to-end room secret, sent sealed under the
+/// current key to the players still in the room.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RoomRekeyMsg {
+    pub epoch: u32,
+    pub secret: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -123,16 +424,74 @@ pub struct RequestGameStartMsg {
     pub game_name: String,
     #[serde(default)]
     pub custom: std::collections::HashMap<String, serde_json::Value>,
+    /// Replace the room's win condition before starting. `None` keeps it.
+    #[serde(default)]
+    pub win_condition: Option<crate::win_condition::WinCondition>,
+    /// Replace the room's round pacing before starting. `None` keeps it.
+    #[serde(default)]
+    pub pacing: Option<RoundPacing>,
+    /// Play the server season's featured game of the week, with its
+    /// modifiers applied over `custom`, instead of `game_name`.
+    #[serde(default)]
+    pub featured: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AddBotMsg {
+    #[serde(default)]
+    pub difficulty: BotDifficulty,
 }
 
+/// Leader changes the difficulty of a bot already in the lobby.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct AddBotMsg {}
+pub struct SetBotDifficultyMsg {
+    pub player_id: PlayerId,
+    pub difficulty: BotDifficulty,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct RemoveBotMsg {
     pub player_id: PlayerId,
 }
 
+/// Most hot-seat guests one connection can add, on top of its own player.
+pub const MAX_LOCAL_PLAYERS: usize = 3;
+
+/// Add a hot-seat guest: another player sharing the sender's keyboard.
+/// The guest joins the sender's room and its inputs arrive on the
+/// sender's connection as `PlayerInput` with the guest's `player_id`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AddLocalPlayerMsg {
+    pub player_name: String,
+    pub player_color: PlayerColor,
+}
+
+/// Remove one of the sender's hot-seat guests.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RemoveLocalPlayerMsg {
+    pub player_id: PlayerId,
+}
+
+/// Add or remove a player (by public friend ID) from the sender's friends.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SetFriendMsg {
+    pub friend_id: String,
+    pub is_friend: bool,
+}
+
+/// Invite a friend to the sender's current room.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InviteFriendMsg {
+    pub friend_id: String,
+}
+
+/// Opt in or out of showing the sender's room and game to the players on
+/// their own friends list. Off until the player turns it on.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SetPresenceSharingMsg {
+    pub share: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ClaimAlertMsg {
     pub player_id: PlayerId,
@@ -143,6 +502,14 @@ pub struct ClaimAlertMsg {
 pub struct PlayerListMsg {
     pub players: Vec<Player>,
     pub leader_id: PlayerId,
+    /// Final scores summed over the matches each player finished in this
+    /// room, including any carried over from a merge or split.
+    #[serde(default)]
+    pub session_scores: HashMap<PlayerId, i32>,
+    /// Wire-format IDs of the games a scheduled session lined up, next
+    /// first. Empty for rooms without a playlist.
+    #[serde(default)]
+    pub playlist: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -153,6 +520,10 @@ pub struct RoomConfigPayload {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct GameStateMsg {
     pub tick: u32,
+    /// When the tick was simulated, in milliseconds on the sender's clock:
+    /// Unix time from the server, the page clock from a browser host.
+    /// Clients map it onto their own clock with `ClockSync`.
+    pub server_time_ms: u64,
     pub state_data: Vec<u8>,
 }
 
@@ -167,20 +538,224 @@ pub struct GameStartMsg {
 pub struct RoundEndMsg {
     pub round: u8,
     pub scores: Vec<PlayerScoreEntry>,
-    /// Seconds until the next round starts.
+    /// Seconds until the next round starts; 0 when the room waits for the
+    /// leader. `Intermission` messages follow with the live countdown.
     #[serde(default)]
     pub between_round_secs: u16,
+    /// Positional heatmap of the round, for games that track one.
+    #[serde(default)]
+    pub heatmap: Option<RoundHeatmap>,
+    /// Who won the round, for the announcer banner.
+    #[serde(default)]
+    pub victory: Option<VictorySummary>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PlayerScoreEntry {
     pub player_id: PlayerId,
     pub score: i32,
+    /// The player has left the match; their score is kept for the leaderboard.
+    #[serde(default)]
+    pub disconnected: bool,
+}
+
+/// The round entered a new phase. Sent on every transition; clients
+/// count down `remaining_secs` locally.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RoundPhaseMsg {
+    pub round: u8,
+    pub phase: RoundPhase,
+    /// Length of the phase in seconds (0 for untimed phases).
+    pub remaining_secs: f32,
+}
+
+/// Live standings for the in-game scoreboard, sent about once a second
+/// while a round is played, so clients needn't work scores out of every
+/// game's state.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StandingsMsg {
+    pub round: u8,
+    pub round_count: u8,
+    /// Best match score first.
+    pub players: Vec<StandingEntry>,
+    /// Team totals in team modes, best first; empty otherwise.
+    pub teams: Vec<TeamStanding>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StandingEntry {
+    pub player_id: PlayerId,
+    /// Score in the round so far.
+    pub round_score: i32,
+    /// Earlier rounds plus this one.
+    pub match_score: i32,
+    pub team: Option<u8>,
+    /// Round trip the player last reported, if any.
+    pub ping_ms: Option<u16>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TeamStanding {
+    pub team: u8,
+    /// The members' match scores summed.
+    pub score: i32,
+}
+
+/// Countdown to the next round, sent when the intermission starts and
+/// whenever it changes. Clients count `remaining_secs` down locally.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IntermissionMsg {
+    /// The round that just ended.
+    pub round: u8,
+    /// Seconds until the next round, or `None` while waiting for the leader.
+    pub remaining_secs: Option<f32>,
+}
+
+/// Leader starts the next round. It begins once the results have been up
+/// for the room's minimum, or right away if they have.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AdvanceRoundMsg {
+    /// The round that just ended; stale requests for earlier rounds are
+    /// ignored. 0 ends the warmup and starts the match.
+    pub round: u8,
+}
+
+/// Leader applies a god-mode command to the running round. Servers
+/// ignore it unless built and configured for debugging.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DebugCommandMsg {
+    pub command: DebugCommand,
+}
+
+/// Leader changes live settings in the running round, such as extending
+/// its clock. Games refuse keys they don't accept mid-round.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChangeGameConfigMsg {
+    pub delta: GameConfigDelta,
+}
+
+/// A click on the lobby toy while the room waits for players.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LobbyToyInputMsg {
+    pub input_data: Vec<u8>,
+}
+
+/// An external bot's answer to the `GameState` of `tick`: the input it
+/// chose after seeing that state. The server drops acks for ticks the bot
+/// already answered or that fell too far behind the simulation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BotAckMsg {
+    pub tick: u32,
+    pub input_data: Vec<u8>,
+}
+
+/// Replace a player's name with the next generated suggestion, in the
+/// lobby. `player_id` may name the sender or one of its hot-seat guests.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RerollNameMsg {
+    pub player_id: PlayerId,
+}
+
+/// A clock probe. The reply echoes `client_time` (the client's own clock,
+/// in ms) beside the server's, so the client can measure the round trip
+/// and the offset between the two clocks.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClockSyncMsg {
+    pub client_time: f64,
+    /// The sender's latest measured round trip in ms, shown as its ping
+    /// on the scoreboard.
+    #[serde(default)]
+    pub rtt_ms: Option<u32>,
+}
+
+/// Answer to a `ClockSync`, sent only to the prober.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClockSyncReplyMsg {
+    pub client_time: f64,
+    /// The clock `GameState::server_time_ms` is stamped with.
+    pub server_time_ms: u64,
+}
+
+/// The lobby toy's state after a change, and to players as they join.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LobbyToyStateMsg {
+    pub state_data: Vec<u8>,
+}
+
+/// A course offered in a pre-round vote.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CourseCandidate {
+    pub seed: u64,
+    pub cols: u8,
+    pub rows: u8,
+    /// Thumbnail cells, row-major with row 0 at the bottom, as the game's
+    /// minimap cell codes.
+    pub cells: Vec<u8>,
+}
+
+/// Pre-round course vote. Sent when voting opens, again on every ballot,
+/// and a last time with `winner` set when it closes.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CourseVoteMsg {
+    /// The round the vote is for.
+    pub round: u8,
+    /// The courses on offer. Only the opening message carries them; later
+    /// messages leave this empty.
+    pub candidates: Vec<CourseCandidate>,
+    /// Votes per candidate.
+    pub votes: Vec<u8>,
+    /// Seconds until voting closes; clients count down locally.
+    pub remaining_secs: f32,
+    /// Index of the chosen course, once voting has closed.
+    pub winner: Option<u8>,
+}
+
+/// A player's ballot in the pre-round course vote. Casting again changes it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CastCourseVoteMsg {
+    pub round: u8,
+    pub choice: u8,
+}
+
+/// A player drafted onto a team.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DraftedPlayer {
+    pub player_id: PlayerId,
+    pub team: u8,
+}
+
+/// Captain draft before a team match. Sent when it opens, after every
+/// pick, and a last time with no `turn` when the teams are settled.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TeamDraftMsg {
+    /// Each team's captain, by team index.
+    pub captains: Vec<PlayerId>,
+    /// Drafted players, captains first, then in pick order.
+    pub picks: Vec<DraftedPlayer>,
+    /// Players still to be picked, in the order an auto-pick takes them.
+    pub pool: Vec<PlayerId>,
+    /// The team picking now; `None` once the draft is over.
+    pub turn: Option<u8>,
+    /// Seconds left on the pick clock; clients count down locally.
+    pub remaining_secs: f32,
+}
+
+/// A captain's pick in the team draft. Ignored unless it is their turn.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DraftPickMsg {
+    pub player_id: PlayerId,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct GameEndMsg {
     pub final_scores: Vec<PlayerScoreEntry>,
+    /// Set when the match was recorded; its replay can be downloaded from
+    /// the history API.
+    #[serde(default)]
+    pub match_id: Option<String>,
+    /// Who won the final round, for the announcer banner.
+    #[serde(default)]
+    pub victory: Option<VictorySummary>,
 }
 
 /// Course/map data sent separately from game state (large, rarely changes).
@@ -190,6 +765,80 @@ pub struct CourseUpdateMsg {
     pub data: Vec<u8>,
 }
 
+/// Game-specific one-shot effects (e.g. laser shots) produced on `tick`,
+/// sent once instead of with every state snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GameEffectsMsg {
+    pub tick: u32,
+    pub data: Vec<u8>,
+}
+
+/// Game-specific data only `player_id` may see (e.g. a caddie hint), sent
+/// after the `GameState` of `tick` to that player's connection alone.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PrivateStateMsg {
+    pub tick: u32,
+    pub player_id: PlayerId,
+    pub data: Vec<u8>,
+}
+
+/// The full set of currently active announcements (including the MOTD).
+/// Replaces whatever the client showed before; an empty list clears them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AnnouncementsMsg {
+    pub announcements: Vec<Announcement>,
+}
+
+/// A friend or recent co-player as shown in the lobby.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SocialEntry {
+    /// Public friend ID.
+    pub id: String,
+    /// Display name last seen for this player.
+    pub name: String,
+    pub online: bool,
+    /// Unix seconds of the last game played together (0 for friends never played with).
+    pub last_played: u64,
+    /// Room the player is in, if they share their presence and have the
+    /// recipient on their own friends list.
+    #[serde(default)]
+    pub room_code: Option<String>,
+    /// Wire-format ID of the game being played in that room; `None` while
+    /// it waits in the lobby.
+    #[serde(default)]
+    pub game: Option<String>,
+}
+
+/// The recipient's friends list and recent co-players.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SocialUpdateMsg {
+    /// The recipient's own public friend ID, for sharing.
+    pub your_id: String,
+    pub friends: Vec<SocialEntry>,
+    pub recent: Vec<SocialEntry>,
+    /// Whether the recipient shares their own presence with friends.
+    #[serde(default)]
+    pub sharing_presence: bool,
+}
+
+/// A friend invited the recipient to their room. Delivered live to online
+/// players, or from the pending-invite inbox on their next connection.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FriendInviteMsg {
+    pub from_id: String,
+    pub from_name: String,
+    pub room_code: String,
+    /// Unix seconds when the invite was sent.
+    pub sent_at: u64,
+}
+
+/// The recipient's tutorial checklists: every game's on connecting, then
+/// the tutorial's game again each time they complete an objective in it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TutorialProgressMsg {
+    pub checklists: Vec<TutorialChecklist>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AlertEventMsg {
     pub event: Event,
@@ -220,6 +869,24 @@ pub enum ClientMessage {
     RequestGameStart(RequestGameStartMsg),
     AddBot(AddBotMsg),
     RemoveBot(RemoveBotMsg),
+    SetFriend(SetFriendMsg),
+    InviteFriend(InviteFriendMsg),
+    ManageJoinQueue(ManageJoinQueueMsg),
+    SetBotDifficulty(SetBotDifficultyMsg),
+    CastCourseVote(CastCourseVoteMsg),
+    AddLocalPlayer(AddLocalPlayerMsg),
+    RemoveLocalPlayer(RemoveLocalPlayerMsg),
+    AdvanceRound(AdvanceRoundMsg),
+    DebugCommand(DebugCommandMsg),
+    LobbyToyInput(LobbyToyInputMsg),
+    BotAck(BotAckMsg),
+    RerollName(RerollNameMsg),
+    ClockSync(ClockSyncMsg),
+    ReshapeRoom(ReshapeRoomMsg),
+    DraftPick(DraftPickMsg),
+    MigrateTransport(MigrateTransportMsg),
+    ChangeGameConfig(ChangeGameConfigMsg),
+    SetPresenceSharing(SetPresenceSharingMsg),
 }
 
 impl ClientMessage {
@@ -234,42 +901,105 @@ impl ClientMessage {
             Self::RequestGameStart(_) => MessageType::RequestGameStart,
             Self::AddBot(_) => MessageType::AddBot,
             Self::RemoveBot(_) => MessageType::RemoveBot,
+            Self::SetFriend(_) => MessageType::SetFriend,
+            Self::InviteFriend(_) => MessageType::InviteFriend,
+            Self::ManageJoinQueue(_) => MessageType::ManageJoinQueue,
+            Self::SetBotDifficulty(_) => MessageType::SetBotDifficulty,
+            Self::CastCourseVote(_) => MessageType::CastCourseVote,
+            Self::AddLocalPlayer(_) => MessageType::AddLocalPlayer,
+            Self::RemoveLocalPlayer(_) => MessageType::RemoveLocalPlayer,
+            Self::AdvanceRound(_) => MessageType::AdvanceRound,
+            Self::DebugCommand(_) => MessageType::DebugCommand,
+            Self::LobbyToyInput(_) => MessageType::LobbyToyInput,
+            Self::BotAck(_) => MessageType::BotAck,
+            Self::RerollName(_) => MessageType::RerollName,
+            Self::ClockSync(_) => MessageType::ClockSync,
+            Self::ReshapeRoom(_) => MessageType::ReshapeRoom,
+            Self::DraftPick(_) => MessageType::DraftPick,
+            Self::MigrateTransport(_) => MessageType::MigrateTransport,
+            Self::ChangeGameConfig(_) => MessageType::ChangeGameConfig,
+            Self::SetPresenceSharing(_) => MessageType::SetPresenceSharing,
         }
     }
 }
 
+/// Envelopes exchanged between the relay and a hosting client.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum RelayMessage {
+    RoomCreated(RelayRoomCreatedMsg),
+    FromPeer(RelayFromPeerMsg),
+    PeerLeft(RelayPeerLeftMsg),
+    ToPeer(RelayToPeerMsg),
+}
+
 /// Messages sent from server to client.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ServerMessage {
     JoinRoomResponse(JoinRoomResponseMsg),
+    JoinQueued(JoinQueuedMsg),
+    JoinQueueUpdate(JoinQueueUpdateMsg),
+    RoomTransfer(RoomTransferMsg),
+    TransportMigrated(TransportMigratedMsg),
     PlayerList(PlayerListMsg),
     RoomConfig(RoomConfigPayload),
     GameState(GameStateMsg),
     GameStart(GameStartMsg),
     RoundEnd(RoundEndMsg),
     GameEnd(GameEndMsg),
+    RoundPhase(RoundPhaseMsg),
+    CourseVote(CourseVoteMsg),
+    Intermission(IntermissionMsg),
+    LobbyToyState(LobbyToyStateMsg),
+    ClockSyncReply(ClockSyncReplyMsg),
+    Standings(StandingsMsg),
+    TeamDraft(TeamDraftMsg),
     AlertEvent(Box<AlertEventMsg>),
     AlertClaimed(AlertClaimedMsg),
     AlertDismissed(AlertDismissedMsg),
     OverlayConfig(OverlayConfigMsg),
     CourseUpdate(CourseUpdateMsg),
+    GameEffects(GameEffectsMsg),
+    PrivateState(PrivateStateMsg),
+    SocialUpdate(SocialUpdateMsg),
+    FriendInvite(FriendInviteMsg),
+    TutorialProgress(TutorialProgressMsg),
+    Announcements(AnnouncementsMsg),
+    RoomRekey(RoomRekeyMsg),
 }
 
 impl ServerMessage {
     pub fn message_type(&self) -> MessageType {
         match self {
             Self::JoinRoomResponse(_) => MessageType::JoinRoomResponse,
+            Self::JoinQueued(_) => MessageType::JoinQueued,
+            Self::JoinQueueUpdate(_) => MessageType::JoinQueueUpdate,
+            Self::RoomTransfer(_) => MessageType::RoomTransfer,
+            Self::TransportMigrated(_) => MessageType::TransportMigrated,
             Self::PlayerList(_) => MessageType::PlayerList,
             Self::RoomConfig(_) => MessageType::RoomConfigMsg,
             Self::GameState(_) => MessageType::GameState,
             Self::GameStart(_) => MessageType::GameStart,
             Self::RoundEnd(_) => MessageType::RoundEnd,
             Self::GameEnd(_) => MessageType::GameEnd,
+            Self::RoundPhase(_) => MessageType::RoundPhase,
+            Self::CourseVote(_) => MessageType::CourseVote,
+            Self::Intermission(_) => MessageType::Intermission,
+            Self::LobbyToyState(_) => MessageType::LobbyToyState,
+            Self::ClockSyncReply(_) => MessageType::ClockSyncReply,
+            Self::Standings(_) => MessageType::Standings,
+            Self::TeamDraft(_) => MessageType::TeamDraft,
             Self::AlertEvent(_) => MessageType::AlertEvent,
             Self::AlertClaimed(_) => MessageType::AlertClaimed,
             Self::AlertDismissed(_) => MessageType::AlertDismissed,
             Self::OverlayConfig(_) => MessageType::OverlayConfig,
             Self::CourseUpdate(_) => MessageType::CourseUpdate,
+            Self::GameEffects(_) => MessageType::GameEffects,
+            Self::PrivateState(_) => MessageType::PrivateState,
+            Self::SocialUpdate(_) => MessageType::SocialUpdate,
+            Self::FriendInvite(_) => MessageType::FriendInvite,
+            Self::TutorialProgress(_) => MessageType::TutorialProgress,
+            Self::Announcements(_) => MessageType::Announcements,
+            Self::RoomRekey(_) => MessageType::RoomRekey,
         }
     }
 }
@@ -1,2 +1,5 @@
+pub mod chaos;
+#[cfg(feature = "e2e")]
+pub mod e2e;
 pub mod messages;
 pub mod protocol;
@@ -1,16 +1,30 @@
 use serde::{Deserialize, Serialize};
 
+use crate::i18n::{LocalizedText, TextKey};
 use crate::overlay::config::OverlayConfigMsg;
 
 use super::messages::{
-    AddBotMsg, AlertClaimedMsg, AlertDismissedMsg, AlertEventMsg, ChatMessageMsg, ClaimAlertMsg,
-    ClientMessage, CourseUpdateMsg, GameEndMsg, GameStartMsg, GameStateMsg, JoinRoomMsg,
-    JoinRoomResponseMsg, LeaveRoomMsg, MessageType, PlayerInputMsg, PlayerListMsg, RemoveBotMsg,
-    RequestGameStartMsg, RoomConfigPayload, RoundEndMsg, ServerMessage,
+    AddBotMsg, AddLocalPlayerMsg, AdvanceRoundMsg, AlertClaimedMsg, AlertDismissedMsg,
+    AlertEventMsg, AnnouncementsMsg, BotAckMsg, CastCourseVoteMsg, ChangeGameConfigMsg,
+    ChatMessageMsg, ClaimAlertMsg, ClientMessage, ClockSyncMsg, ClockSyncReplyMsg, CourseUpdateMsg,
+    CourseVoteMsg, DebugCommandMsg, DraftPickMsg, FriendInviteMsg, GameEffectsMsg, GameEndMsg,
+    GameStartMsg, GameStateMsg, IntermissionMsg, InviteFriendMsg, JoinQueueUpdateMsg,
+    JoinQueuedMsg, JoinRoomMsg, JoinRoomResponseMsg, LeaveRoomMsg, LegacyJoinRoomResponseMsg,
+    LobbyToyInputMsg, LobbyToyStateMsg, ManageJoinQueueMsg, MessageType, MigrateTransportMsg,
+    PlayerInputMsg, PlayerListMsg, PrivateStateMsg, RelayFromPeerMsg, RelayMessage,
+    RelayPeerLeftMsg, RelayRoomCreatedMsg, RelayToPeerMsg, RemoveBotMsg, RemoveLocalPlayerMsg,
+    RequestGameStartMsg, RerollNameMsg, ReshapeRoomMsg, RoomConfigPayload, RoomRekeyMsg,
+    RoomTransferMsg, RoundEndMsg, RoundPhaseMsg, ServerMessage, SetBotDifficultyMsg, SetFriendMsg,
+    SetPresenceSharingMsg, SocialUpdateMsg, StandingsMsg, TeamDraftMsg, TransportMigratedMsg,
+    TutorialProgressMsg,
 };
 
 /// Current protocol version.
-pub const PROTOCOL_VERSION: u8 = 2;
+pub const PROTOCOL_VERSION: u8 = 6;
+
+/// First protocol version whose `JoinRoomResponse` error is a
+/// [`LocalizedText`] rather than a plain string.
+pub const LOCALIZED_ERRORS_VERSION: u8 = 6;
 
 /// Default game tick rate in Hz.
 pub const DEFAULT_TICK_RATE_HZ: u32 = 10;
@@ -75,6 +89,24 @@ pub fn encode_client_message(msg: &ClientMessage) -> Result<Vec<u8>, ProtocolErr
         ClientMessage::RequestGameStart(m) => encode_message(MessageType::RequestGameStart, m),
         ClientMessage::AddBot(m) => encode_message(MessageType::AddBot, m),
         ClientMessage::RemoveBot(m) => encode_message(MessageType::RemoveBot, m),
+        ClientMessage::SetFriend(m) => encode_message(MessageType::SetFriend, m),
+        ClientMessage::InviteFriend(m) => encode_message(MessageType::InviteFriend, m),
+        ClientMessage::ManageJoinQueue(m) => encode_message(MessageType::ManageJoinQueue, m),
+        ClientMessage::SetBotDifficulty(m) => encode_message(MessageType::SetBotDifficulty, m),
+        ClientMessage::CastCourseVote(m) => encode_message(MessageType::CastCourseVote, m),
+        ClientMessage::AddLocalPlayer(m) => encode_message(MessageType::AddLocalPlayer, m),
+        ClientMessage::RemoveLocalPlayer(m) => encode_message(MessageType::RemoveLocalPlayer, m),
+        ClientMessage::AdvanceRound(m) => encode_message(MessageType::AdvanceRound, m),
+        ClientMessage::DebugCommand(m) => encode_message(MessageType::DebugCommand, m),
+        ClientMessage::LobbyToyInput(m) => encode_message(MessageType::LobbyToyInput, m),
+        ClientMessage::BotAck(m) => encode_message(MessageType::BotAck, m),
+        ClientMessage::RerollName(m) => encode_message(MessageType::RerollName, m),
+        ClientMessage::ClockSync(m) => encode_message(MessageType::ClockSync, m),
+        ClientMessage::ReshapeRoom(m) => encode_message(MessageType::ReshapeRoom, m),
+        ClientMessage::DraftPick(m) => encode_message(MessageType::DraftPick, m),
+        ClientMessage::MigrateTransport(m) => encode_message(MessageType::MigrateTransport, m),
+        ClientMessage::ChangeGameConfig(m) => encode_message(MessageType::ChangeGameConfig, m),
+        ClientMessage::SetPresenceSharing(m) => encode_message(MessageType::SetPresenceSharing, m),
     }
 }
 
@@ -82,9 +114,15 @@ pub fn encode_client_message(msg: &ClientMessage) -> Result<Vec<u8>, ProtocolErr
 pub fn encode_server_message(msg: &ServerMessage) -> Result<Vec<u8>, ProtocolError> {
     match msg {
         ServerMessage::JoinRoomResponse(m) => encode_message(MessageType::JoinRoomResponse, m),
+        ServerMessage::JoinQueued(m) => encode_message(MessageType::JoinQueued, m),
+        ServerMessage::JoinQueueUpdate(m) => encode_message(MessageType::JoinQueueUpdate, m),
+        ServerMessage::RoomTransfer(m) => encode_message(MessageType::RoomTransfer, m),
+        ServerMessage::TransportMigrated(m) => encode_message(MessageType::TransportMigrated, m),
         ServerMessage::PlayerList(m) => encode_message(MessageType::PlayerList, m),
         ServerMessage::RoomConfig(m) => encode_message(MessageType::RoomConfigMsg, m),
-        ServerMessage::GameState(m) => encode_game_state_fast(m.tick, &m.state_data),
+        ServerMessage::GameState(m) => {
+            encode_game_state_fast(m.tick, m.server_time_ms, &m.state_data)
+        },
         ServerMessage::GameStart(m) => encode_message(MessageType::GameStart, m),
         ServerMessage::RoundEnd(m) => encode_message(MessageType::RoundEnd, m),
         ServerMessage::GameEnd(m) => encode_message(MessageType::GameEnd, m),
@@ -93,32 +131,150 @@ pub fn encode_server_message(msg: &ServerMessage) -> Result<Vec<u8>, ProtocolErr
         ServerMessage::AlertDismissed(m) => encode_message(MessageType::AlertDismissed, m),
         ServerMessage::OverlayConfig(m) => encode_message(MessageType::OverlayConfig, m),
         ServerMessage::CourseUpdate(m) => encode_message(MessageType::CourseUpdate, m),
+        ServerMessage::GameEffects(m) => encode_message(MessageType::GameEffects, m),
+        ServerMessage::PrivateState(m) => encode_message(MessageType::PrivateState, m),
+        ServerMessage::SocialUpdate(m) => encode_message(MessageType::SocialUpdate, m),
+        ServerMessage::FriendInvite(m) => encode_message(MessageType::FriendInvite, m),
+        ServerMessage::TutorialProgress(m) => encode_message(MessageType::TutorialProgress, m),
+        ServerMessage::Announcements(m) => encode_message(MessageType::Announcements, m),
+        ServerMessage::RoundPhase(m) => encode_message(MessageType::RoundPhase, m),
+        ServerMessage::CourseVote(m) => encode_message(MessageType::CourseVote, m),
+        ServerMessage::Intermission(m) => encode_message(MessageType::Intermission, m),
+        ServerMessage::LobbyToyState(m) => encode_message(MessageType::LobbyToyState, m),
+        ServerMessage::ClockSyncReply(m) => encode_message(MessageType::ClockSyncReply, m),
+        ServerMessage::Standings(m) => encode_message(MessageType::Standings, m),
+        ServerMessage::TeamDraft(m) => encode_message(MessageType::TeamDraft, m),
+        ServerMessage::RoomRekey(m) => encode_message(MessageType::RoomRekey, m),
+    }
+}
+
+/// Encode the `JoinRoomResponse` rejecting a client on another protocol
+/// version. Clients older than [`LOCALIZED_ERRORS_VERSION`] get the frozen
+/// legacy shape with an English message, since they can't decode a
+/// [`LocalizedText`]; newer ones get the usual localized error.
+pub fn encode_version_mismatch(client_version: u8) -> Result<Vec<u8>, ProtocolError> {
+    if client_version < LOCALIZED_ERRORS_VERSION {
+        let msg = LegacyJoinRoomResponseMsg {
+            success: false,
+            player_id: None,
+            room_code: None,
+            room_state: None,
+            error: Some(format!(
+                "Game version mismatch (client {client_version}, server {PROTOCOL_VERSION}). \
+                 Please reload the page."
+            )),
+        };
+        return encode_message(MessageType::JoinRoomResponse, &msg);
     }
+    let error = LocalizedText::new(TextKey::ProtocolMismatch)
+        .with("client", client_version)
+        .with("server", PROTOCOL_VERSION);
+    encode_server_message(&ServerMessage::JoinRoomResponse(JoinRoomResponseMsg {
+        success: false,
+        player_id: None,
+        room_code: None,
+        room_state: None,
+        error: Some(error),
+        session_token: None,
+        lobby_toy: None,
+    }))
 }
 
-/// Encode a game state directly: `[type_byte | tick_le32 | raw_state_data]`.
+/// Bytes in front of the state in a fast-format `GameState`.
+const GAME_STATE_HEADER: usize = 1 + 4 + 8;
+
+/// Encode a game state directly:
+/// `[type_byte | tick_le32 | server_time_ms_le64 | raw_state_data]`.
 /// Avoids the `GameStateMsg` wrapper and its extra MessagePack pass.
-pub fn encode_game_state_fast(tick: u32, state_data: &[u8]) -> Result<Vec<u8>, ProtocolError> {
-    let total = 1 + 4 + state_data.len();
+pub fn encode_game_state_fast(
+    tick: u32,
+    server_time_ms: u64,
+    state_data: &[u8],
+) -> Result<Vec<u8>, ProtocolError> {
+    let total = GAME_STATE_HEADER + state_data.len();
     if total > MAX_MESSAGE_SIZE {
         return Err(ProtocolError::PayloadTooLarge(total));
     }
     let mut buf = Vec::with_capacity(total);
     buf.push(MessageType::GameState as u8);
     buf.extend_from_slice(&tick.to_le_bytes());
+    buf.extend_from_slice(&server_time_ms.to_le_bytes());
     buf.extend_from_slice(state_data);
     Ok(buf)
 }
 
-/// Decode the fast game state format: returns `(tick, state_data_slice)`.
-/// The caller should check that `data[0] == MessageType::GameState as u8` first.
-pub fn decode_game_state_fast(data: &[u8]) -> Result<(u32, &[u8]), ProtocolError> {
-    // Minimum: 1 type byte + 4 tick bytes
-    if data.len() < 5 {
+/// Bytes added in front of each message inside a `Batch` frame.
+pub const BATCH_LEN_PREFIX: usize = 4;
+
+/// Pack already-encoded messages into one frame:
+/// `[type_byte | (len_le32 | message)*]`. Batches do not nest.
+pub fn encode_batch<T: AsRef<[u8]>>(frames: &[T]) -> Result<Vec<u8>, ProtocolError> {
+    let total = 1 + frames
+        .iter()
+        .map(|f| BATCH_LEN_PREFIX + f.as_ref().len())
+        .sum::<usize>();
+    if total > MAX_MESSAGE_SIZE {
+        return Err(ProtocolError::PayloadTooLarge(total));
+    }
+    let mut buf = Vec::with_capacity(total);
+    buf.push(MessageType::Batch as u8);
+    for frame in frames {
+        let frame = frame.as_ref();
+        match frame.first() {
+            None => return Err(ProtocolError::EmptyMessage),
+            Some(&b) if b == MessageType::Batch as u8 => {
+                return Err(ProtocolError::SerializeError("nested batch".to_string()));
+            },
+            Some(_) => {},
+        }
+        buf.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+        buf.extend_from_slice(frame);
+    }
+    Ok(buf)
+}
+
+/// Split a `Batch` frame into the messages it carries, in order.
+/// The caller should check that `data[0] == MessageType::Batch as u8` first.
+pub fn split_batch(data: &[u8]) -> Result<Vec<&[u8]>, ProtocolError> {
+    if data.is_empty() {
+        return Err(ProtocolError::EmptyMessage);
+    }
+    let mut frames = Vec::new();
+    let mut rest = &data[1..];
+    while !rest.is_empty() {
+        let Some((len, tail)) = rest.split_first_chunk::<BATCH_LEN_PREFIX>() else {
+            return Err(ProtocolError::DeserializeError(
+                "truncated batch length".to_string(),
+            ));
+        };
+        let len = u32::from_le_bytes(*len) as usize;
+        if len == 0 || len > tail.len() {
+            return Err(ProtocolError::DeserializeError(format!(
+                "batch entry of {len} bytes with {} left",
+                tail.len()
+            )));
+        }
+        let (frame, tail) = tail.split_at(len);
+        if frame[0] == MessageType::Batch as u8 {
+            return Err(ProtocolError::DeserializeError("nested batch".to_string()));
+        }
+        frames.push(frame);
+        rest = tail;
+    }
+    Ok(frames)
+}
+
+/// Decode the fast game state format: returns
+/// `(tick, server_time_ms, state_data_slice)`. The caller should check that
+/// `data[0] == MessageType::GameState as u8` first.
+pub fn decode_game_state_fast(data: &[u8]) -> Result<(u32, u64, &[u8]), ProtocolError> {
+    if data.len() < GAME_STATE_HEADER {
         return Err(ProtocolError::EmptyMessage);
     }
     let tick = u32::from_le_bytes([data[1], data[2], data[3], data[4]]);
-    Ok((tick, &data[5..]))
+    let mut time = [0u8; 8];
+    time.copy_from_slice(&data[5..GAME_STATE_HEADER]);
+    Ok((tick, u64::from_le_bytes(time), &data[GAME_STATE_HEADER..]))
 }
 
 /// Extract the message type byte from raw wire data.
@@ -137,6 +293,36 @@ pub fn decode_payload<T: for<'de> Deserialize<'de>>(data: &[u8]) -> Result<T, Pr
     rmp_serde::from_slice(&data[1..]).map_err(|e| ProtocolError::DeserializeError(e.to_string()))
 }
 
+/// Encode a `RelayMessage` to wire format.
+pub fn encode_relay_message(msg: &RelayMessage) -> Result<Vec<u8>, ProtocolError> {
+    match msg {
+        RelayMessage::RoomCreated(m) => encode_message(MessageType::RelayRoomCreated, m),
+        RelayMessage::FromPeer(m) => encode_message(MessageType::RelayFromPeer, m),
+        RelayMessage::PeerLeft(m) => encode_message(MessageType::RelayPeerLeft, m),
+        RelayMessage::ToPeer(m) => encode_message(MessageType::RelayToPeer, m),
+    }
+}
+
+/// Decode raw wire data into a `RelayMessage`.
+pub fn decode_relay_message(data: &[u8]) -> Result<RelayMessage, ProtocolError> {
+    let msg_type = decode_message_type(data)?;
+    match msg_type {
+        MessageType::RelayRoomCreated => Ok(RelayMessage::RoomCreated(decode_payload::<
+            RelayRoomCreatedMsg,
+        >(data)?)),
+        MessageType::RelayFromPeer => Ok(RelayMessage::FromPeer(
+            decode_payload::<RelayFromPeerMsg>(data)?,
+        )),
+        MessageType::RelayPeerLeft => Ok(RelayMessage::PeerLeft(
+            decode_payload::<RelayPeerLeftMsg>(data)?,
+        )),
+        MessageType::RelayToPeer => Ok(RelayMessage::ToPeer(decode_payload::<RelayToPeerMsg>(
+            data,
+        )?)),
+        _ => Err(ProtocolError::UnknownMessageType(data[0])),
+    }
+}
+
 /// Decode raw wire data into a `ClientMessage`.
 pub fn decode_client_message(data: &[u8]) -> Result<ClientMessage, ProtocolError> {
     let msg_type = decode_message_type(data)?;
@@ -166,6 +352,58 @@ pub fn decode_client_message(data: &[u8]) -> Result<ClientMessage, ProtocolError
         MessageType::RemoveBot => Ok(ClientMessage::RemoveBot(decode_payload::<RemoveBotMsg>(
             data,
         )?)),
+        MessageType::SetFriend => Ok(ClientMessage::SetFriend(decode_payload::<SetFriendMsg>(
+            data,
+        )?)),
+        MessageType::InviteFriend => Ok(ClientMessage::InviteFriend(decode_payload::<
+            InviteFriendMsg,
+        >(data)?)),
+        MessageType::ManageJoinQueue => Ok(ClientMessage::ManageJoinQueue(decode_payload::<
+            ManageJoinQueueMsg,
+        >(data)?)),
+        MessageType::SetBotDifficulty => Ok(ClientMessage::SetBotDifficulty(decode_payload::<
+            SetBotDifficultyMsg,
+        >(data)?)),
+        MessageType::CastCourseVote => Ok(ClientMessage::CastCourseVote(decode_payload::<
+            CastCourseVoteMsg,
+        >(data)?)),
+        MessageType::AddLocalPlayer => Ok(ClientMessage::AddLocalPlayer(decode_payload::<
+            AddLocalPlayerMsg,
+        >(data)?)),
+        MessageType::RemoveLocalPlayer => Ok(ClientMessage::RemoveLocalPlayer(decode_payload::<
+            RemoveLocalPlayerMsg,
+        >(data)?)),
+        MessageType::AdvanceRound => Ok(ClientMessage::AdvanceRound(decode_payload::<
+            AdvanceRoundMsg,
+        >(data)?)),
+        MessageType::DebugCommand => Ok(ClientMessage::DebugCommand(decode_payload::<
+            DebugCommandMsg,
+        >(data)?)),
+        MessageType::LobbyToyInput => Ok(ClientMessage::LobbyToyInput(decode_payload::<
+            LobbyToyInputMsg,
+        >(data)?)),
+        MessageType::BotAck => Ok(ClientMessage::BotAck(decode_payload::<BotAckMsg>(data)?)),
+        MessageType::ClockSync => Ok(ClientMessage::ClockSync(decode_payload::<ClockSyncMsg>(
+            data,
+        )?)),
+        MessageType::RerollName => Ok(ClientMessage::RerollName(decode_payload::<RerollNameMsg>(
+            data,
+        )?)),
+        MessageType::ReshapeRoom => Ok(ClientMessage::ReshapeRoom(
+            decode_payload::<ReshapeRoomMsg>(data)?,
+        )),
+        MessageType::DraftPick => Ok(ClientMessage::DraftPick(decode_payload::<DraftPickMsg>(
+            data,
+        )?)),
+        MessageType::MigrateTransport => Ok(ClientMessage::MigrateTransport(decode_payload::<
+            MigrateTransportMsg,
+        >(data)?)),
+        MessageType::ChangeGameConfig => Ok(ClientMessage::ChangeGameConfig(decode_payload::<
+            ChangeGameConfigMsg,
+        >(data)?)),
+        MessageType::SetPresenceSharing => Ok(ClientMessage::SetPresenceSharing(decode_payload::<
+            SetPresenceSharingMsg,
+        >(data)?)),
         _ => Err(ProtocolError::UnknownMessageType(data[0])),
     }
 }
@@ -177,6 +415,18 @@ pub fn decode_server_message(data: &[u8]) -> Result<ServerMessage, ProtocolError
         MessageType::JoinRoomResponse => Ok(ServerMessage::JoinRoomResponse(decode_payload::<
             JoinRoomResponseMsg,
         >(data)?)),
+        MessageType::JoinQueued => Ok(ServerMessage::JoinQueued(decode_payload::<JoinQueuedMsg>(
+            data,
+        )?)),
+        MessageType::JoinQueueUpdate => Ok(ServerMessage::JoinQueueUpdate(decode_payload::<
+            JoinQueueUpdateMsg,
+        >(data)?)),
+        MessageType::RoomTransfer => Ok(ServerMessage::RoomTransfer(decode_payload::<
+            RoomTransferMsg,
+        >(data)?)),
+        MessageType::TransportMigrated => Ok(ServerMessage::TransportMigrated(decode_payload::<
+            TransportMigratedMsg,
+        >(data)?)),
         MessageType::PlayerList => Ok(ServerMessage::PlayerList(decode_payload::<PlayerListMsg>(
             data,
         )?)),
@@ -184,10 +434,11 @@ pub fn decode_server_message(data: &[u8]) -> Result<ServerMessage, ProtocolError
             RoomConfigPayload,
         >(data)?)),
         MessageType::GameState => {
-            // Fast format: [type_byte | tick_le32 | raw_state_data]
-            let (tick, state_data) = decode_game_state_fast(data)?;
+            // Fast format: [type_byte | tick_le32 | server_time_ms_le64 | raw_state_data]
+            let (tick, server_time_ms, state_data) = decode_game_state_fast(data)?;
             Ok(ServerMessage::GameState(GameStateMsg {
                 tick,
+                server_time_ms,
                 state_data: state_data.to_vec(),
             }))
         },
@@ -213,6 +464,48 @@ pub fn decode_server_message(data: &[u8]) -> Result<ServerMessage, ProtocolError
         MessageType::CourseUpdate => Ok(ServerMessage::CourseUpdate(decode_payload::<
             CourseUpdateMsg,
         >(data)?)),
+        MessageType::GameEffects => Ok(ServerMessage::GameEffects(
+            decode_payload::<GameEffectsMsg>(data)?,
+        )),
+        MessageType::PrivateState => Ok(ServerMessage::PrivateState(decode_payload::<
+            PrivateStateMsg,
+        >(data)?)),
+        MessageType::SocialUpdate => Ok(ServerMessage::SocialUpdate(decode_payload::<
+            SocialUpdateMsg,
+        >(data)?)),
+        MessageType::FriendInvite => Ok(ServerMessage::FriendInvite(decode_payload::<
+            FriendInviteMsg,
+        >(data)?)),
+        MessageType::TutorialProgress => Ok(ServerMessage::TutorialProgress(decode_payload::<
+            TutorialProgressMsg,
+        >(data)?)),
+        MessageType::Announcements => Ok(ServerMessage::Announcements(decode_payload::<
+            AnnouncementsMsg,
+        >(data)?)),
+        MessageType::RoundPhase => Ok(ServerMessage::RoundPhase(decode_payload::<RoundPhaseMsg>(
+            data,
+        )?)),
+        MessageType::CourseVote => Ok(ServerMessage::CourseVote(decode_payload::<CourseVoteMsg>(
+            data,
+        )?)),
+        MessageType::Intermission => Ok(ServerMessage::Intermission(decode_payload::<
+            IntermissionMsg,
+        >(data)?)),
+        MessageType::LobbyToyState => Ok(ServerMessage::LobbyToyState(decode_payload::<
+            LobbyToyStateMsg,
+        >(data)?)),
+        MessageType::ClockSyncReply => Ok(ServerMessage::ClockSyncReply(decode_payload::<
+            ClockSyncReplyMsg,
+        >(data)?)),
+        MessageType::Standings => Ok(ServerMessage::Standings(decode_payload::<StandingsMsg>(
+            data,
+        )?)),
+        MessageType::TeamDraft => Ok(ServerMessage::TeamDraft(decode_payload::<TeamDraftMsg>(
+            data,
+        )?)),
+        MessageType::RoomRekey => Ok(ServerMessage::RoomRekey(decode_payload::<RoomRekeyMsg>(
+            data,
+        )?)),
         _ => Err(ProtocolError::UnknownMessageType(data[0])),
     }
 }
@@ -220,7 +513,10 @@ pub fn decode_server_message(data: &[u8]) -> Result<ServerMessage, ProtocolError
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bot::BotDifficulty;
+    use crate::debug_command::DebugCommand;
     use crate::events::{Event, EventType, Priority};
+    use crate::game_trait::{GameConfigDelta, LIVE_EXTEND_ROUND, LIVE_POWERUPS};
     use crate::player::{Player, PlayerColor};
     use crate::room::RoomConfig;
     use std::collections::HashMap;
@@ -233,6 +529,9 @@ mod tests {
             is_leader: true,
             is_spectator: false,
             is_bot: false,
+            bot_difficulty: Default::default(),
+            hot_seat_of: None,
+            external_bot: false,
         }
     }
 
@@ -255,6 +554,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn version_mismatch_stays_readable_by_old_clients() {
+        let data = encode_version_mismatch(LOCALIZED_ERRORS_VERSION - 1).unwrap();
+        assert_eq!(data[0], MessageType::JoinRoomResponse as u8);
+        let legacy: LegacyJoinRoomResponseMsg = rmp_serde::from_slice(&data[1..]).unwrap();
+        assert!(!legacy.success);
+        assert!(legacy.error.unwrap().contains("version mismatch"));
+
+        let data = encode_version_mismatch(PROTOCOL_VERSION + 1).unwrap();
+        match decode_server_message(&data).unwrap() {
+            ServerMessage::JoinRoomResponse(m) => {
+                assert_eq!(m.error.unwrap().key, TextKey::ProtocolMismatch);
+            },
+            other => panic!("Expected JoinRoomResponse, got {other:?}"),
+        }
+    }
+
     #[test]
     fn roundtrip_join_room() {
         let msg = ClientMessage::JoinRoom(JoinRoomMsg {
@@ -263,6 +579,11 @@ mod tests {
             player_color: PlayerColor::default(),
             protocol_version: PROTOCOL_VERSION,
             session_token: None,
+            identity_key: None,
+            accepts_batches: false,
+            room_alias: None,
+            bot_token: None,
+            tutorial: None,
         });
         let encoded = encode_client_message(&msg).unwrap();
         let decoded = decode_client_message(&encoded).unwrap();
@@ -363,6 +684,7 @@ mod tests {
             room_state: Some(crate::room::RoomState::Lobby),
             error: None,
             session_token: Some("test-token".to_string()),
+            lobby_toy: Some(vec![1, 2, 3]),
         });
         let encoded = encode_server_message(&msg).unwrap();
         let decoded = decode_server_message(&encoded).unwrap();
@@ -374,6 +696,8 @@ mod tests {
         let msg = ServerMessage::PlayerList(PlayerListMsg {
             players: vec![test_player()],
             leader_id: 42,
+            session_scores: [(42, 17)].into(),
+            playlist: vec!["laser-tag".to_string()],
         });
         let encoded = encode_server_message(&msg).unwrap();
         let decoded = decode_server_message(&encoded).unwrap();
@@ -394,11 +718,17 @@ mod tests {
     fn roundtrip_game_state() {
         let msg = ServerMessage::GameState(GameStateMsg {
             tick: 500,
+            server_time_ms: 1_767_225_600_250,
             state_data: vec![1, 2, 3, 4, 5],
         });
         let encoded = encode_server_message(&msg).unwrap();
+        assert_eq!(encoded.len(), 1 + 4 + 8 + 5);
         let decoded = decode_server_message(&encoded).unwrap();
         assert_eq!(msg, decoded);
+        assert!(
+            decode_game_state_fast(&encoded[..12]).is_err(),
+            "Short header"
+        );
     }
 
     #[test]
@@ -421,8 +751,24 @@ mod tests {
             scores: vec![PlayerScoreEntry {
                 player_id: 42,
                 score: 5,
+                disconnected: false,
             }],
             between_round_secs: 30,
+            heatmap: Some(crate::heatmap::RoundHeatmap {
+                width: 40.0,
+                depth: 30.0,
+                cols: 2,
+                rows: 1,
+                presence: vec![255, 12],
+                tags: vec![3, 0],
+            }),
+            victory: Some(
+                crate::victory::VictorySummary::new(
+                    crate::i18n::TextKey::VictoryMostTags,
+                    vec![42],
+                )
+                .with_stat(5),
+            ),
         });
         let encoded = encode_server_message(&msg).unwrap();
         let decoded = decode_server_message(&encoded).unwrap();
@@ -436,7 +782,10 @@ mod tests {
             final_scores: vec![PlayerScoreEntry {
                 player_id: 1,
                 score: 10,
+                disconnected: false,
             }],
+            match_id: Some("3f2a".to_string()),
+            victory: Some(crate::victory::VictorySummary::no_winner()),
         });
         let encoded = encode_server_message(&msg).unwrap();
         let decoded = decode_server_message(&encoded).unwrap();
@@ -494,6 +843,11 @@ mod tests {
             player_color: PlayerColor::default(),
             protocol_version: PROTOCOL_VERSION,
             session_token: None,
+            identity_key: None,
+            accepts_batches: false,
+            room_alias: None,
+            bot_token: None,
+            tutorial: None,
         });
         let encoded = encode_client_message(&msg).unwrap();
         assert_eq!(encoded[0], MessageType::JoinRoom as u8);
@@ -519,6 +873,9 @@ mod tests {
         let msg = ClientMessage::RequestGameStart(RequestGameStartMsg {
             game_name: "mini-golf".to_string(),
             custom: std::collections::HashMap::new(),
+            win_condition: None,
+            pacing: None,
+            featured: false,
         });
         let encoded = encode_client_message(&msg).unwrap();
         let decoded = decode_client_message(&encoded).unwrap();
@@ -536,11 +893,406 @@ mod tests {
         assert_eq!(msg, decoded);
     }
 
+    #[test]
+    fn roundtrip_announcements() {
+        use crate::announcement::{Announcement, AnnouncementSeverity};
+        let msg = ServerMessage::Announcements(AnnouncementsMsg {
+            announcements: vec![Announcement {
+                id: "motd".to_string(),
+                message: "Welcome!".to_string(),
+                severity: AnnouncementSeverity::Info,
+                starts_at: 0,
+                ends_at: Some(1_700_000_000),
+            }],
+        });
+        let encoded = encode_server_message(&msg).unwrap();
+        assert_eq!(encoded[0], 0x24);
+        let decoded = decode_server_message(&encoded).unwrap();
+        assert_eq!(msg, decoded);
+    }
+
+    #[test]
+    fn roundtrip_round_phase() {
+        let msg = ServerMessage::RoundPhase(RoundPhaseMsg {
+            round: 2,
+            phase: crate::round_phase::RoundPhase::Countdown,
+            remaining_secs: 3.0,
+        });
+        let encoded = encode_server_message(&msg).unwrap();
+        assert_eq!(encoded[0], 0x17);
+        let decoded = decode_server_message(&encoded).unwrap();
+        assert_eq!(msg, decoded);
+    }
+
+    #[test]
+    fn roundtrip_course_vote_messages() {
+        use crate::net::messages::CourseCandidate;
+        let open = ServerMessage::CourseVote(CourseVoteMsg {
+            round: 1,
+            candidates: vec![CourseCandidate {
+                seed: u64::MAX,
+                cols: 2,
+                rows: 1,
+                cells: vec![1, 5],
+            }],
+            votes: vec![0],
+            remaining_secs: 10.0,
+            winner: None,
+        });
+        let encoded = encode_server_message(&open).unwrap();
+        assert_eq!(encoded[0], 0x18);
+        assert_eq!(decode_server_message(&encoded).unwrap(), open);
+
+        let cast = ClientMessage::CastCourseVote(CastCourseVoteMsg {
+            round: 1,
+            choice: 2,
+        });
+        let encoded = encode_client_message(&cast).unwrap();
+        assert_eq!(encoded[0], 0x37);
+        assert_eq!(decode_client_message(&encoded).unwrap(), cast);
+    }
+
+    #[test]
+    fn roundtrip_join_queue_messages() {
+        use crate::net::messages::{JoinQueueAction, QueuedJoinEntry};
+        let queued = ServerMessage::JoinQueued(JoinQueuedMsg {
+            room_code: "ABCD-1234".to_string(),
+            position: 2,
+            queue_len: 3,
+            awaiting_approval: true,
+        });
+        let update = ServerMessage::JoinQueueUpdate(JoinQueueUpdateMsg {
+            require_approval: true,
+            entries: vec![QueuedJoinEntry {
+                ticket: 7,
+                display_name: "Dana".to_string(),
+                approved: false,
+            }],
+        });
+        for (msg, byte) in [(queued, 0x07), (update, 0x08)] {
+            let encoded = encode_server_message(&msg).unwrap();
+            assert_eq!(encoded[0], byte);
+            assert_eq!(decode_server_message(&encoded).unwrap(), msg);
+        }
+
+        let manage = ClientMessage::ManageJoinQueue(ManageJoinQueueMsg {
+            action: JoinQueueAction::Admit(7),
+        });
+        let encoded = encode_client_message(&manage).unwrap();
+        assert_eq!(encoded[0], 0x35);
+        assert_eq!(decode_client_message(&encoded).unwrap(), manage);
+    }
+
+    #[test]
+    fn roundtrip_bot_difficulty_messages() {
+        let add = ClientMessage::AddBot(AddBotMsg {
+            difficulty: BotDifficulty::Mirror,
+        });
+        let set = ClientMessage::SetBotDifficulty(SetBotDifficultyMsg {
+            player_id: 4,
+            difficulty: BotDifficulty::Easy,
+        });
+        for (msg, byte) in [(add, 0x31), (set, 0x36)] {
+            let encoded = encode_client_message(&msg).unwrap();
+            assert_eq!(encoded[0], byte);
+            assert_eq!(decode_client_message(&encoded).unwrap(), msg);
+        }
+
+        // AddBot from older clients carries no difficulty
+        let legacy = [MessageType::AddBot as u8, 0x90]; // empty msgpack array
+        let ClientMessage::AddBot(msg) = decode_client_message(&legacy).unwrap() else {
+            panic!("expected AddBot");
+        };
+        assert_eq!(msg.difficulty, BotDifficulty::Normal);
+    }
+
+    #[test]
+    fn roundtrip_local_player_messages() {
+        let add = ClientMessage::AddLocalPlayer(AddLocalPlayerMsg {
+            player_name: "Couch".to_string(),
+            player_color: PlayerColor::PALETTE[2],
+        });
+        let remove = ClientMessage::RemoveLocalPlayer(RemoveLocalPlayerMsg { player_id: 6 });
+        for (msg, byte) in [(add, 0x38), (remove, 0x39)] {
+            let encoded = encode_client_message(&msg).unwrap();
+            assert_eq!(encoded[0], byte);
+            assert_eq!(decode_client_message(&encoded).unwrap(), msg);
+        }
+    }
+
+    #[test]
+    fn roundtrip_intermission_messages() {
+        for remaining_secs in [Some(12.5), None] {
+            let msg = ServerMessage::Intermission(IntermissionMsg {
+                round: 2,
+                remaining_secs,
+            });
+            let encoded = encode_server_message(&msg).unwrap();
+            assert_eq!(encoded[0], 0x19);
+            assert_eq!(decode_server_message(&encoded).unwrap(), msg);
+        }
+        let advance = ClientMessage::AdvanceRound(AdvanceRoundMsg { round: 2 });
+        let encoded = encode_client_message(&advance).unwrap();
+        assert_eq!(encoded[0], 0x3A);
+        assert_eq!(decode_client_message(&encoded).unwrap(), advance);
+    }
+
+    #[test]
+    fn roundtrip_debug_command() {
+        let msg = ClientMessage::DebugCommand(DebugCommandMsg {
+            command: DebugCommand::Teleport {
+                player_id: 2,
+                x: 4.5,
+                y: -1.0,
+            },
+        });
+        let encoded = encode_client_message(&msg).unwrap();
+        assert_eq!(encoded[0], 0x3B);
+        assert_eq!(decode_client_message(&encoded).unwrap(), msg);
+    }
+
+    #[test]
+    fn roundtrip_change_game_config() {
+        let msg = ClientMessage::ChangeGameConfig(ChangeGameConfigMsg {
+            delta: GameConfigDelta::default()
+                .set(LIVE_EXTEND_ROUND, 30.0)
+                .set(LIVE_POWERUPS, false),
+        });
+        let encoded = encode_client_message(&msg).unwrap();
+        assert_eq!(encoded[0], 0x0E);
+        assert_eq!(decode_client_message(&encoded).unwrap(), msg);
+    }
+
+    #[test]
+    fn roundtrip_lobby_toy() {
+        let input = ClientMessage::LobbyToyInput(LobbyToyInputMsg {
+            input_data: vec![0x92, 0x01, 0x02],
+        });
+        let encoded = encode_client_message(&input).unwrap();
+        assert_eq!(encoded[0], 0x3C);
+        assert_eq!(decode_client_message(&encoded).unwrap(), input);
+
+        let state = ServerMessage::LobbyToyState(LobbyToyStateMsg {
+            state_data: vec![1, 2, 3],
+        });
+        let encoded = encode_server_message(&state).unwrap();
+        assert_eq!(encoded[0], 0x1A);
+        assert_eq!(decode_server_message(&encoded).unwrap(), state);
+    }
+
+    #[test]
+    fn roundtrip_bot_ack() {
+        let msg = ClientMessage::BotAck(BotAckMsg {
+            tick: 412,
+            input_data: vec![0x93, 0x01, 0x02, 0x03],
+        });
+        let encoded = encode_client_message(&msg).unwrap();
+        assert_eq!(encoded[0], 0x3D);
+        assert_eq!(decode_client_message(&encoded).unwrap(), msg);
+    }
+
+    #[test]
+    fn roundtrip_reroll_name() {
+        let msg = ClientMessage::RerollName(RerollNameMsg { player_id: 4 });
+        let encoded = encode_client_message(&msg).unwrap();
+        assert_eq!(encoded[0], 0x3E);
+        assert_eq!(decode_client_message(&encoded).unwrap(), msg);
+    }
+
+    #[test]
+    fn roundtrip_clock_sync() {
+        let probe = ClientMessage::ClockSync(ClockSyncMsg {
+            client_time: 1234.5,
+            rtt_ms: Some(42),
+        });
+        let encoded = encode_client_message(&probe).unwrap();
+        assert_eq!(encoded[0], 0x3F);
+        assert_eq!(decode_client_message(&encoded).unwrap(), probe);
+
+        let reply = ServerMessage::ClockSyncReply(ClockSyncReplyMsg {
+            client_time: 1234.5,
+            server_time_ms: 1_767_225_600_123,
+        });
+        let encoded = encode_server_message(&reply).unwrap();
+        assert_eq!(encoded[0], 0x1B);
+        assert_eq!(decode_server_message(&encoded).unwrap(), reply);
+    }
+
+    #[test]
+    fn roundtrip_standings() {
+        use crate::net::messages::{StandingEntry, TeamStanding};
+        let msg = ServerMessage::Standings(StandingsMsg {
+            round: 2,
+            round_count: 3,
+            players: vec![
+                StandingEntry {
+                    player_id: 1,
+                    round_score: 4,
+                    match_score: 9,
+                    team: Some(0),
+                    ping_ms: Some(38),
+                },
+                StandingEntry {
+                    player_id: 2,
+                    round_score: -1,
+                    match_score: 3,
+                    team: Some(1),
+                    ping_ms: None,
+                },
+            ],
+            teams: vec![
+                TeamStanding { team: 0, score: 9 },
+                TeamStanding { team: 1, score: 3 },
+            ],
+        });
+        let encoded = encode_server_message(&msg).unwrap();
+        assert_eq!(encoded[0], 0x1C);
+        assert_eq!(decode_server_message(&encoded).unwrap(), msg);
+    }
+
+    #[test]
+    fn roundtrip_game_effects() {
+        let msg = ServerMessage::GameEffects(GameEffectsMsg {
+            tick: 42,
+            data: vec![0x91, 0x01, 0x02],
+        });
+        let encoded = encode_server_message(&msg).unwrap();
+        assert_eq!(encoded[0], 0x1E);
+        assert_eq!(decode_server_message(&encoded).unwrap(), msg);
+    }
+
+    #[test]
+    fn roundtrip_private_state() {
+        let msg = ServerMessage::PrivateState(PrivateStateMsg {
+            tick: 42,
+            player_id: 3,
+            data: vec![0x91, 0xC0],
+        });
+        let encoded = encode_server_message(&msg).unwrap();
+        assert_eq!(encoded[0], 0x1F);
+        assert_eq!(decode_server_message(&encoded).unwrap(), msg);
+    }
+
+    #[test]
+    fn roundtrip_team_draft_and_pick() {
+        use crate::net::messages::DraftedPlayer;
+        let msg = ServerMessage::TeamDraft(TeamDraftMsg {
+            captains: vec![4, 2],
+            picks: vec![
+                DraftedPlayer {
+                    player_id: 4,
+                    team: 0,
+                },
+                DraftedPlayer {
+                    player_id: 2,
+                    team: 1,
+                },
+            ],
+            pool: vec![1, 3],
+            turn: Some(0),
+            remaining_secs: 14.5,
+        });
+        let encoded = encode_server_message(&msg).unwrap();
+        assert_eq!(encoded[0], 0x1D);
+        assert_eq!(decode_server_message(&encoded).unwrap(), msg);
+
+        let pick = ClientMessage::DraftPick(DraftPickMsg { player_id: 3 });
+        let encoded = encode_client_message(&pick).unwrap();
+        assert_eq!(encoded[0], 0x0B);
+        assert_eq!(decode_client_message(&encoded).unwrap(), pick);
+    }
+
+    #[test]
+    fn roundtrip_room_reshape_and_transfer() {
+        use crate::net::messages::{RoomReshape, TransferReason};
+        for action in [
+            RoomReshape::MergeInto("WXYZ-5678".to_string()),
+            RoomReshape::Split,
+        ] {
+            let msg = ClientMessage::ReshapeRoom(ReshapeRoomMsg { action });
+            let encoded = encode_client_message(&msg).unwrap();
+            assert_eq!(encoded[0], 0x0A);
+            assert_eq!(decode_client_message(&encoded).unwrap(), msg);
+        }
+
+        let offer = ServerMessage::RoomTransfer(RoomTransferMsg {
+            room_code: "WXYZ-5678".to_string(),
+            transfer_token: "transfer-token".to_string(),
+            reason: TransferReason::Split,
+            expires_in_secs: 60,
+        });
+        let encoded = encode_server_message(&offer).unwrap();
+        assert_eq!(encoded[0], 0x09);
+        assert_eq!(decode_server_message(&encoded).unwrap(), offer);
+    }
+
+    #[test]
+    fn roundtrip_transport_migration() {
+        let migrate = ClientMessage::MigrateTransport(MigrateTransportMsg {
+            session_token: "session-token".to_string(),
+            protocol_version: PROTOCOL_VERSION,
+            accepts_batches: true,
+        });
+        let encoded = encode_client_message(&migrate).unwrap();
+        assert_eq!(encoded[0], 0x0C);
+        assert_eq!(decode_client_message(&encoded).unwrap(), migrate);
+
+        let migrated = ServerMessage::TransportMigrated(TransportMigratedMsg {
+            success: true,
+            player_id: Some(7),
+            room_code: Some("ABCD-1234".to_string()),
+            session_token: Some("new-token".to_string()),
+            last_input_tick: Some(412),
+            error: None,
+        });
+        let encoded = encode_server_message(&migrated).unwrap();
+        assert_eq!(encoded[0], 0x0D);
+        assert_eq!(decode_server_message(&encoded).unwrap(), migrated);
+    }
+
+    #[test]
+    fn roundtrip_room_rekey() {
+        let msg = ServerMessage::RoomRekey(RoomRekeyMsg {
+            epoch: 3,
+            secret: "ABCD-EFGH-JKLM-NPQR".to_string(),
+        });
+        let encoded = encode_server_message(&msg).unwrap();
+        assert_eq!(encoded[0], 0x54);
+        assert_eq!(decode_server_message(&encoded).unwrap(), msg);
+    }
+
+    #[test]
+    fn roundtrip_relay_messages() {
+        let inner = encode_client_message(&ClientMessage::LeaveRoom(LeaveRoomMsg { player_id: 3 }))
+            .unwrap();
+        let msgs = [
+            RelayMessage::RoomCreated(RelayRoomCreatedMsg {
+                room_code: "ABCD-1234".to_string(),
+            }),
+            RelayMessage::FromPeer(RelayFromPeerMsg {
+                peer_id: 2,
+                data: inner.clone(),
+            }),
+            RelayMessage::PeerLeft(RelayPeerLeftMsg { peer_id: 2 }),
+            RelayMessage::ToPeer(RelayToPeerMsg {
+                peer_id: 2,
+                data: inner,
+            }),
+        ];
+        for (msg, byte) in msgs.into_iter().zip(0x50..) {
+            let encoded = encode_relay_message(&msg).unwrap();
+            assert_eq!(encoded[0], byte);
+            assert_eq!(decode_relay_message(&encoded).unwrap(), msg);
+            assert!(decode_client_message(&encoded).is_err());
+        }
+    }
+
     #[test]
     fn decode_client_msg_with_server_type_fails() {
         // Encode a server message, then try to decode as client → should fail
         let msg = ServerMessage::GameState(GameStateMsg {
             tick: 1,
+            server_time_ms: 0,
             state_data: vec![],
         });
         let encoded = encode_server_message(&msg).unwrap();
@@ -577,6 +1329,15 @@ mod tests {
             (0x04, MessageType::ClaimAlert),
             (0x05, MessageType::ChatMessage),
             (0x06, MessageType::JoinRoomResponse),
+            (0x07, MessageType::JoinQueued),
+            (0x08, MessageType::JoinQueueUpdate),
+            (0x09, MessageType::RoomTransfer),
+            (0x0A, MessageType::ReshapeRoom),
+            (0x0B, MessageType::DraftPick),
+            (0x0C, MessageType::MigrateTransport),
+            (0x0D, MessageType::TransportMigrated),
+            (0x0E, MessageType::ChangeGameConfig),
+            (0x0F, MessageType::SetPresenceSharing),
             (0x10, MessageType::GameState),
             (0x11, MessageType::PlayerList),
             (0x12, MessageType::RoomConfigMsg),
@@ -584,13 +1345,46 @@ mod tests {
             (0x14, MessageType::RoundEnd),
             (0x15, MessageType::GameEnd),
             (0x16, MessageType::CourseUpdate),
+            (0x17, MessageType::RoundPhase),
+            (0x18, MessageType::CourseVote),
+            (0x19, MessageType::Intermission),
+            (0x1A, MessageType::LobbyToyState),
+            (0x1B, MessageType::ClockSyncReply),
+            (0x1C, MessageType::Standings),
+            (0x1D, MessageType::TeamDraft),
+            (0x1E, MessageType::GameEffects),
+            (0x1F, MessageType::PrivateState),
             (0x20, MessageType::AlertEvent),
             (0x21, MessageType::AlertClaimed),
             (0x22, MessageType::AlertDismissed),
             (0x23, MessageType::OverlayConfig),
+            (0x24, MessageType::Announcements),
             (0x30, MessageType::RequestGameStart),
             (0x31, MessageType::AddBot),
             (0x32, MessageType::RemoveBot),
+            (0x33, MessageType::SetFriend),
+            (0x34, MessageType::InviteFriend),
+            (0x35, MessageType::ManageJoinQueue),
+            (0x36, MessageType::SetBotDifficulty),
+            (0x37, MessageType::CastCourseVote),
+            (0x38, MessageType::AddLocalPlayer),
+            (0x39, MessageType::RemoveLocalPlayer),
+            (0x3A, MessageType::AdvanceRound),
+            (0x3B, MessageType::DebugCommand),
+            (0x3C, MessageType::LobbyToyInput),
+            (0x3D, MessageType::BotAck),
+            (0x3E, MessageType::RerollName),
+            (0x3F, MessageType::ClockSync),
+            (0x40, MessageType::SocialUpdate),
+            (0x41, MessageType::FriendInvite),
+            (0x42, MessageType::TutorialProgress),
+            (0x50, MessageType::RelayRoomCreated),
+            (0x51, MessageType::RelayFromPeer),
+            (0x52, MessageType::RelayPeerLeft),
+            (0x53, MessageType::RelayToPeer),
+            (0x54, MessageType::RoomRekey),
+            (0x60, MessageType::Batch),
+            (0x61, MessageType::Sealed),
         ];
         for (byte, expected) in &known {
             assert_eq!(
@@ -623,6 +1417,11 @@ mod tests {
                     player_color: PlayerColor::default(),
                     protocol_version: 0,
                     session_token: None,
+                    identity_key: None,
+                    accepts_batches: false,
+                    room_alias: None,
+                    bot_token: None,
+                    tutorial: None,
                 }),
                 0x02,
             ),
@@ -656,6 +1455,9 @@ mod tests {
                 ClientMessage::RequestGameStart(RequestGameStartMsg {
                     game_name: "g".to_string(),
                     custom: std::collections::HashMap::new(),
+                    win_condition: None,
+                    pacing: None,
+                    featured: false,
                 }),
                 0x30,
             ),
@@ -700,4 +1502,41 @@ mod tests {
             panic!("Expected PayloadTooLarge error");
         }
     }
+
+    #[test]
+    fn batch_roundtrip_keeps_messages_in_order() {
+        let alert = encode_server_message(&ServerMessage::AlertEvent(Box::new(AlertEventMsg {
+            event: test_event(),
+        })))
+        .unwrap();
+        let state = encode_game_state_fast(7, 0, &[1, 2, 3]).unwrap();
+        let batch = encode_batch(&[&state, &alert]).unwrap();
+        assert_eq!(decode_message_type(&batch).unwrap(), MessageType::Batch);
+        assert_eq!(
+            batch.len(),
+            1 + 2 * BATCH_LEN_PREFIX + state.len() + alert.len()
+        );
+
+        let frames = split_batch(&batch).unwrap();
+        assert_eq!(frames, vec![state.as_slice(), alert.as_slice()]);
+        assert!(matches!(
+            decode_server_message(frames[1]).unwrap(),
+            ServerMessage::AlertEvent(_)
+        ));
+    }
+
+    #[test]
+    fn malformed_batches_rejected() {
+        let state = encode_game_state_fast(1, 0, &[9]).unwrap();
+        let batch = encode_batch(&[&state]).unwrap();
+        assert!(split_batch(&batch[..batch.len() - 1]).is_err(), "Truncated");
+        assert!(split_batch(&batch[..3]).is_err(), "Partial length prefix");
+        assert!(encode_batch(&[&batch]).is_err(), "Nested");
+        assert!(encode_batch(&[&[] as &[u8]]).is_err(), "Empty message");
+        let big = vec![MessageType::GameState as u8; MAX_MESSAGE_SIZE / 2];
+        assert!(matches!(
+            encode_batch(&[&big, &big]),
+            Err(ProtocolError::PayloadTooLarge(_))
+        ));
+    }
 }
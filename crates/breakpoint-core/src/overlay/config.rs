@@ -2,7 +2,12 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::events::Priority;
+use crate::events::{Event, EventType, Priority};
+
+/// Most entries allowed in each event filter list.
+pub const MAX_FILTER_ENTRIES: usize = 32;
+/// Longest event filter entry, in bytes.
+pub const MAX_FILTER_ENTRY_LEN: usize = 64;
 
 /// Position of the scrolling ticker bar.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -33,9 +38,18 @@ pub enum NotificationDensity {
 
 /// Room-level overlay configuration set by the host.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct OverlayRoomConfig {
     /// Which event sources are enabled (empty = all).
     pub enabled_sources: Vec<String>,
+    /// Only events carrying one of these tags (empty = all). A `key:value`
+    /// entry also matches event metadata, so `repo:backend` matches events
+    /// whose `repo` is `backend` or ends in `/backend`.
+    pub event_tags: Vec<String>,
+    /// Only these event types (empty = all).
+    pub event_types: Vec<EventType>,
+    /// Drop events below this priority.
+    pub min_priority: Priority,
     /// Override priority for specific event types (event_type string -> priority).
     pub priority_overrides: HashMap<String, Priority>,
     /// Where to display the ticker bar.
@@ -50,6 +64,9 @@ impl Default for OverlayRoomConfig {
     fn default() -> Self {
         Self {
             enabled_sources: Vec::new(),
+            event_tags: Vec::new(),
+            event_types: Vec::new(),
+            min_priority: Priority::default(),
             priority_overrides: HashMap::new(),
             ticker_position: TickerPosition::default(),
             dashboard_auto_expand_between_rounds: true,
@@ -58,6 +75,59 @@ impl Default for OverlayRoomConfig {
     }
 }
 
+impl OverlayRoomConfig {
+    /// Whether the room's event filter lets `event` through.
+    pub fn accepts_event(&self, event: &Event) -> bool {
+        if event.priority < self.min_priority {
+            return false;
+        }
+        if !self.enabled_sources.is_empty() && !self.enabled_sources.contains(&event.source) {
+            return false;
+        }
+        if !self.event_types.is_empty() && !self.event_types.contains(&event.event_type) {
+            return false;
+        }
+        self.event_tags.is_empty() || self.event_tags.iter().any(|tag| tag_matches(tag, event))
+    }
+
+    /// Check the filter lists against the size limits.
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, len) in [
+            ("enabled_sources", self.enabled_sources.len()),
+            ("event_tags", self.event_tags.len()),
+            ("event_types", self.event_types.len()),
+        ] {
+            if len > MAX_FILTER_ENTRIES {
+                return Err(format!("{name} has more than {MAX_FILTER_ENTRIES} entries"));
+            }
+        }
+        if self
+            .enabled_sources
+            .iter()
+            .chain(&self.event_tags)
+            .any(|s| s.len() > MAX_FILTER_ENTRY_LEN)
+        {
+            return Err(format!(
+                "filter entries must be at most {MAX_FILTER_ENTRY_LEN} bytes"
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn tag_matches(tag: &str, event: &Event) -> bool {
+    if event.tags.iter().any(|t| t == tag) {
+        return true;
+    }
+    let Some((key, value)) = tag.split_once(':') else {
+        return false;
+    };
+    match event.metadata.get(key).and_then(|v| v.as_str()) {
+        Some(actual) => actual == value || actual.rsplit('/').next() == Some(value),
+        None => false,
+    }
+}
+
 /// Per-player overlay preferences.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OverlayPlayerPrefs {
@@ -89,3 +159,74 @@ impl Default for OverlayPlayerPrefs {
 pub struct OverlayConfigMsg {
     pub room_config: OverlayRoomConfig,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::make_test_event;
+
+    fn ci_failure(repo: &str) -> Event {
+        let mut event = make_test_event("ci-1");
+        event.event_type = EventType::PipelineFailed;
+        event.source = "github-actions".to_string();
+        event.tags = vec!["ci".to_string()];
+        event
+            .metadata
+            .insert("repo".to_string(), serde_json::json!(repo));
+        event
+    }
+
+    #[test]
+    fn default_filter_accepts_everything() {
+        let config = OverlayRoomConfig::default();
+        assert!(config.accepts_event(&make_test_event("a")));
+        assert!(config.accepts_event(&ci_failure("acme/frontend")));
+    }
+
+    #[test]
+    fn filter_narrows_to_one_repos_ci_failures() {
+        let config = OverlayRoomConfig {
+            event_tags: vec!["repo:backend".to_string()],
+            event_types: vec![EventType::PipelineFailed],
+            ..OverlayRoomConfig::default()
+        };
+        assert!(config.accepts_event(&ci_failure("acme/backend")));
+        assert!(config.accepts_event(&ci_failure("backend")));
+        assert!(!config.accepts_event(&ci_failure("acme/frontend")));
+
+        let mut passed = ci_failure("acme/backend");
+        passed.event_type = EventType::PipelineSucceeded;
+        assert!(!config.accepts_event(&passed));
+    }
+
+    #[test]
+    fn sources_tags_and_priority_all_apply() {
+        let config = OverlayRoomConfig {
+            enabled_sources: vec!["github-actions".to_string()],
+            event_tags: vec!["ci".to_string()],
+            min_priority: Priority::Urgent,
+            ..OverlayRoomConfig::default()
+        };
+        let mut event = ci_failure("acme/backend");
+        assert!(!config.accepts_event(&event), "Notice is below Urgent");
+        event.priority = Priority::Critical;
+        assert!(config.accepts_event(&event));
+        event.source = "sentry".to_string();
+        assert!(!config.accepts_event(&event));
+    }
+
+    #[test]
+    fn oversized_filters_are_rejected() {
+        let config = OverlayRoomConfig {
+            event_tags: vec!["ci".to_string(); MAX_FILTER_ENTRIES + 1],
+            ..OverlayRoomConfig::default()
+        };
+        assert!(config.validate().is_err());
+        let config = OverlayRoomConfig {
+            enabled_sources: vec!["x".repeat(MAX_FILTER_ENTRY_LEN + 1)],
+            ..OverlayRoomConfig::default()
+        };
+        assert!(config.validate().is_err());
+        assert!(OverlayRoomConfig::default().validate().is_ok());
+    }
+}
@@ -0,0 +1,301 @@
+//! Per-room color assignment. Players keep the color they pick unless it
+//! is hard to tell apart from one already in the room, in which case they
+//! get the palette color furthest from everyone else's. "Hard to tell
+//! apart" is judged in Oklab under normal vision and simulated protanopia,
+//! deuteranopia and tritanopia, so two colors only count as distinct if
+//! they stay distinct for colorblind players too.
+
+use std::collections::HashMap;
+
+use crate::game_trait::PlayerId;
+use crate::player::{Player, PlayerColor};
+
+/// Colors handed out automatically, based on the Okabe-Ito colorblind-safe
+/// set (with a pale cyan in place of black, which vanishes on dark arenas).
+pub const SAFE_PALETTE: [PlayerColor; 8] = [
+    PlayerColor {
+        r: 230,
+        g: 159,
+        b: 0,
+    }, // Orange
+    PlayerColor {
+        r: 86,
+        g: 180,
+        b: 233,
+    }, // Sky blue
+    PlayerColor {
+        r: 0,
+        g: 158,
+        b: 115,
+    }, // Bluish green
+    PlayerColor {
+        r: 240,
+        g: 228,
+        b: 66,
+    }, // Yellow
+    PlayerColor {
+        r: 0,
+        g: 114,
+        b: 178,
+    }, // Blue
+    PlayerColor {
+        r: 213,
+        g: 94,
+        b: 0,
+    }, // Vermillion
+    PlayerColor {
+        r: 204,
+        g: 121,
+        b: 167,
+    }, // Reddish purple
+    PlayerColor {
+        r: 153,
+        g: 255,
+        b: 255,
+    }, // Pale cyan
+];
+
+/// Base hue of each team in team modes; teammates get shades of it.
+pub const TEAM_HUES: [PlayerColor; 4] = [
+    SAFE_PALETTE[1], // Sky blue
+    SAFE_PALETTE[5], // Vermillion
+    SAFE_PALETTE[3], // Yellow
+    SAFE_PALETTE[6], // Reddish purple
+];
+
+/// Smallest [`distance`] at which two players' colors count as distinct.
+pub const MIN_DISTANCE: f32 = 0.07;
+
+/// Lightness spread (in Oklab L) between the darkest and lightest shade
+/// of a team.
+const TEAM_SHADE_SPREAD: f32 = 0.24;
+
+/// Linear-RGB matrices simulating each kind of color vision, after
+/// Machado et al. (2009) at full severity.
+const VISION: [[[f32; 3]; 3]; 4] = [
+    // Normal
+    [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+    // Protanopia
+    [
+        [0.152286, 1.052583, -0.204868],
+        [0.114503, 0.786281, 0.099216],
+        [-0.003882, -0.048116, 1.051998],
+    ],
+    // Deuteranopia
+    [
+        [0.367322, 0.860646, -0.227968],
+        [0.280085, 0.672501, 0.047413],
+        [-0.011820, 0.042940, 0.968881],
+    ],
+    // Tritanopia
+    [
+        [1.255528, -0.076749, -0.178779],
+        [-0.078411, 0.930809, 0.147602],
+        [0.004733, 0.691367, 0.303900],
+    ],
+];
+
+fn to_linear(c: u8) -> f32 {
+    let c = f32::from(c) / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn from_linear(c: f32) -> u8 {
+    let c = c.clamp(0.0, 1.0);
+    let c = if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    };
+    (c * 255.0).round() as u8
+}
+
+fn linear_to_oklab([r, g, b]: [f32; 3]) -> [f32; 3] {
+    let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+    let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+    let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+    [
+        0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+        1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+        0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+    ]
+}
+
+fn oklab_to_linear([l, a, b]: [f32; 3]) -> [f32; 3] {
+    let l_ = (l + 0.396_337_78 * a + 0.215_803_76 * b).powi(3);
+    let m_ = (l - 0.105_561_346 * a - 0.063_854_17 * b).powi(3);
+    let s_ = (l - 0.089_484_18 * a - 1.291_485_5 * b).powi(3);
+    [
+        4.076_741_7 * l_ - 3.307_711_6 * m_ + 0.230_969_94 * s_,
+        -1.268_438 * l_ + 2.609_757_4 * m_ - 0.341_319_38 * s_,
+        -0.004_196_086_3 * l_ - 0.703_418_6 * m_ + 1.707_614_7 * s_,
+    ]
+}
+
+fn simulate(c: PlayerColor, vision: &[[f32; 3]; 3]) -> [f32; 3] {
+    let rgb = [to_linear(c.r), to_linear(c.g), to_linear(c.b)];
+    let mut out = [0.0; 3];
+    for (o, row) in out.iter_mut().zip(vision) {
+        *o = (row[0] * rgb[0] + row[1] * rgb[1] + row[2] * rgb[2]).clamp(0.0, 1.0);
+    }
+    linear_to_oklab(out)
+}
+
+/// Perceptual distance between two colors for the viewer who finds them
+/// hardest to tell apart.
+pub fn distance(a: PlayerColor, b: PlayerColor) -> f32 {
+    VISION
+        .iter()
+        .map(|vision| {
+            let (pa, pb) = (simulate(a, vision), simulate(b, vision));
+            pa.iter()
+                .zip(pb)
+                .map(|(x, y)| (x - y) * (x - y))
+                .sum::<f32>()
+                .sqrt()
+        })
+        .fold(f32::INFINITY, f32::min)
+}
+
+fn nearest(color: PlayerColor, taken: &[PlayerColor]) -> f32 {
+    taken
+        .iter()
+        .map(|&t| distance(color, t))
+        .fold(f32::INFINITY, f32::min)
+}
+
+/// The [`SAFE_PALETTE`] color furthest from every color in `taken`. Ties
+/// go to the earlier palette entry, so an empty room starts with orange.
+pub fn most_distinct(taken: &[PlayerColor]) -> PlayerColor {
+    let mut best = SAFE_PALETTE[0];
+    let mut best_distance = f32::NEG_INFINITY;
+    for &candidate in &SAFE_PALETTE {
+        let d = nearest(candidate, taken);
+        if d > best_distance {
+            best = candidate;
+            best_distance = d;
+        }
+    }
+    best
+}
+
+/// Keep `requested` if it is distinct from every color in `taken`,
+/// otherwise fall back to [`most_distinct`].
+pub fn resolve(requested: PlayerColor, taken: &[PlayerColor]) -> PlayerColor {
+    if nearest(requested, taken) >= MIN_DISTANCE {
+        requested
+    } else {
+        most_distinct(taken)
+    }
+}
+
+/// Colors already worn by players in a room.
+pub fn taken_colors(players: &[Player]) -> Vec<PlayerColor> {
+    players.iter().map(|p| p.color).collect()
+}
+
+/// Shade `index` of `team_size` for a team: the team hue with lightness
+/// spread evenly around it, darkest first.
+pub fn team_shade(team: u8, index: usize, team_size: usize) -> PlayerColor {
+    let hue = TEAM_HUES[usize::from(team) % TEAM_HUES.len()];
+    let [l, a, b] = linear_to_oklab([to_linear(hue.r), to_linear(hue.g), to_linear(hue.b)]);
+    let offset = if team_size > 1 {
+        TEAM_SHADE_SPREAD * (index as f32 / (team_size - 1) as f32 - 0.5)
+    } else {
+        0.0
+    };
+    let [r, g, b] = oklab_to_linear([(l + offset).clamp(0.0, 1.0), a, b]);
+    PlayerColor {
+        r: from_linear(r),
+        g: from_linear(g),
+        b: from_linear(b),
+    }
+}
+
+/// Color for every player in a team mode, keyed by player. Teammates are
+/// shaded in player-ID order so every client agrees on who is which.
+pub fn team_colors(teams: &HashMap<PlayerId, u8>) -> HashMap<PlayerId, PlayerColor> {
+    let mut by_team: HashMap<u8, Vec<PlayerId>> = HashMap::new();
+    for (&player_id, &team) in teams {
+        by_team.entry(team).or_default().push(player_id);
+    }
+    let mut colors = HashMap::with_capacity(teams.len());
+    for (team, mut members) in by_team {
+        members.sort_unstable();
+        let size = members.len();
+        for (i, player_id) in members.into_iter().enumerate() {
+            colors.insert(player_id, team_shade(team, i, size));
+        }
+    }
+    colors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn safe_palette_is_distinct_for_every_kind_of_vision() {
+        for (i, &a) in SAFE_PALETTE.iter().enumerate() {
+            for &b in &SAFE_PALETTE[i + 1..] {
+                assert!(
+                    distance(a, b) >= MIN_DISTANCE,
+                    "{a:?} and {b:?} are too close ({})",
+                    distance(a, b)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn auto_assignment_fills_a_room_without_repeats() {
+        let mut taken = Vec::new();
+        for _ in 0..SAFE_PALETTE.len() {
+            let color = most_distinct(&taken);
+            assert!(!taken.contains(&color));
+            taken.push(color);
+        }
+        assert_eq!(taken[0], SAFE_PALETTE[0]);
+    }
+
+    #[test]
+    fn conflicting_custom_colors_are_replaced() {
+        let teal = PlayerColor::PALETTE[1];
+        let green = PlayerColor::PALETTE[4];
+        let purple = PlayerColor::PALETTE[3];
+        // Teal and green blur together for deuteranopes
+        assert!(distance(teal, green) < MIN_DISTANCE);
+
+        assert_eq!(resolve(teal, &[]), teal);
+        assert_eq!(resolve(purple, &[teal]), purple, "Distinct picks stay");
+        let replaced = resolve(green, &[teal, purple]);
+        assert_ne!(replaced, green);
+        assert!(nearest(replaced, &[teal, purple]) >= MIN_DISTANCE);
+        assert_eq!(resolve(teal, &[teal]), most_distinct(&[teal]));
+    }
+
+    #[test]
+    fn teammates_are_shades_of_the_team_hue() {
+        let teams: HashMap<PlayerId, u8> = [(1, 0), (2, 1), (3, 0), (4, 1), (5, 0)].into();
+        let colors = team_colors(&teams);
+        assert_eq!(colors.len(), 5);
+        assert_eq!(colors[&3], TEAM_HUES[0], "Middle shade is the hue itself");
+
+        let lightness =
+            |c: PlayerColor| linear_to_oklab([to_linear(c.r), to_linear(c.g), to_linear(c.b)])[0];
+        assert!(lightness(colors[&1]) < lightness(colors[&3]));
+        assert!(lightness(colors[&3]) < lightness(colors[&5]));
+
+        // Every player is closer to their own team's hue than to the other's
+        for (id, &team) in &teams {
+            let own = distance(colors[id], TEAM_HUES[usize::from(team)]);
+            let other = distance(colors[id], TEAM_HUES[usize::from(1 - team)]);
+            assert!(own < other, "Player {id} reads as the wrong team");
+        }
+        assert_eq!(team_shade(0, 0, 1), TEAM_HUES[0]);
+    }
+}
@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+
+/// Why a game is paused. Reasons stack: the game runs only once every
+/// active reason has been resumed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PauseReason {
+    /// The room leader paused the match.
+    HostPause,
+    /// The overlay raised a critical alert.
+    Alert,
+    /// Players voted to pause.
+    Vote,
+    /// The host left and authority is moving to another peer.
+    HostMigration,
+}
+
+/// Set of active pause reasons, carried in game state so clients can
+/// explain why play is held.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PauseState {
+    reasons: Vec<PauseReason>,
+}
+
+impl PauseState {
+    /// Add `reason`. Returns `false` if it was already active.
+    pub fn add(&mut self, reason: PauseReason) -> bool {
+        if self.reasons.contains(&reason) {
+            return false;
+        }
+        self.reasons.push(reason);
+        true
+    }
+
+    /// Clear `reason`. Returns `false` if it was not active.
+    pub fn remove(&mut self, reason: PauseReason) -> bool {
+        let before = self.reasons.len();
+        self.reasons.retain(|r| *r != reason);
+        self.reasons.len() != before
+    }
+
+    pub fn is_paused(&self) -> bool {
+        !self.reasons.is_empty()
+    }
+
+    /// Active reasons, oldest first.
+    pub fn reasons(&self) -> &[PauseReason] {
+        &self.reasons
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reasons_stack_independently() {
+        let mut pause = PauseState::default();
+        assert!(pause.add(PauseReason::HostPause));
+        assert!(pause.add(PauseReason::Alert));
+        assert!(!pause.add(PauseReason::Alert), "Reasons are not counted");
+
+        assert!(pause.remove(PauseReason::Alert));
+        assert!(pause.is_paused(), "Host pause still holds the game");
+        assert_eq!(pause.reasons(), &[PauseReason::HostPause]);
+
+        assert!(!pause.remove(PauseReason::Vote));
+        assert!(pause.remove(PauseReason::HostPause));
+        assert!(!pause.is_paused());
+    }
+}
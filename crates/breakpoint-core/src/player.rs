@@ -2,6 +2,7 @@ use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
+use crate::bot::BotDifficulty;
 use crate::game_trait::PlayerId;
 
 /// A player connected to a Breakpoint room.
@@ -14,6 +15,59 @@ pub struct Player {
     pub is_spectator: bool,
     #[serde(default)]
     pub is_bot: bool,
+    /// Skill of a bot player. Ignored for humans.
+    #[serde(default)]
+    pub bot_difficulty: BotDifficulty,
+    /// For a hot-seat guest, the player whose connection it shares.
+    #[serde(default)]
+    pub hot_seat_of: Option<PlayerId>,
+    /// Connected through the bot API and driven by an external program,
+    /// not by the server like `is_bot` players.
+    #[serde(default)]
+    pub external_bot: bool,
+}
+
+/// A player's identity across connections, rooms, and sessions, unlike
+/// the `PlayerId` a room hands out for one stay. The server derives it
+/// from the secret identity key a client keeps in local storage, and it
+/// doubles as the public friend ID; knowing it does not let anyone act
+/// as the player.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PlayerIdentity(String);
+
+impl PlayerIdentity {
+    /// Lowercase hex characters in an identity.
+    pub const LEN: usize = 12;
+
+    /// The identity whose text starts a digest of the player's secret.
+    pub fn from_digest(digest: &[u8]) -> Self {
+        Self(
+            digest
+                .iter()
+                .take(Self::LEN / 2)
+                .map(|b| format!("{b:02x}"))
+                .collect(),
+        )
+    }
+
+    /// An identity as shown to players, e.g. a friend ID typed into the
+    /// lobby. `None` unless it is `LEN` lowercase hex characters.
+    pub fn parse(s: &str) -> Option<Self> {
+        let valid =
+            s.len() == Self::LEN && s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'));
+        valid.then(|| Self(s.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for PlayerIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
 }
 
 /// Avatar color selection.
@@ -294,6 +348,16 @@ mod tests {
         assert_eq!(cfg.color_at(0), PlayerColor::default());
     }
 
+    #[test]
+    fn identity_parses_only_its_own_shape() {
+        let id = PlayerIdentity::from_digest(&[0xab, 0x01, 0xff, 0x00, 0x10, 0x9c, 0x77]);
+        assert_eq!(id.as_str(), "ab01ff00109c");
+        assert_eq!(PlayerIdentity::parse("ab01ff00109c"), Some(id));
+        assert!(PlayerIdentity::parse("AB01FF00109C").is_none());
+        assert!(PlayerIdentity::parse("ab01ff00109").is_none());
+        assert!(PlayerIdentity::parse("ab01ff00109g").is_none());
+    }
+
     #[test]
     fn default_palette_matches_hardcoded_palette() {
         let cfg = PlayerColorConfig::default();
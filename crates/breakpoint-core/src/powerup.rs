@@ -1,9 +1,19 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 
+use crate::game_trait::{GameEvent, PlayerId};
+
+/// Power-up events a [`PowerUpFeed`] keeps.
+pub const FEED_LEN: usize = 16;
+
 /// Trait for game-specific power-up kind enums.
 pub trait PowerUpKind: Clone + Copy + PartialEq + Serialize + DeserializeOwned {
     /// Duration in seconds for this power-up. Use `f32::INFINITY` for permanent effects.
     fn duration(&self) -> f32;
+
+    /// Stable snake_case name, used in power-up events.
+    fn name(&self) -> &'static str;
 }
 
 /// Active power-up effect on a player, generic over the kind enum.
@@ -32,3 +42,145 @@ impl<K: PowerUpKind> ActivePowerUp<K> {
         self.remaining <= 0.0
     }
 }
+
+/// The event for `player_id` picking up `kind`.
+pub fn collected<K: PowerUpKind>(player_id: PlayerId, kind: K) -> GameEvent {
+    GameEvent::PowerupCollected {
+        player_id,
+        kind: kind.name().to_string(),
+    }
+}
+
+/// Tick every player's active power-ups and drop the expired ones,
+/// returning a `PowerupExpired` event for each, in player order.
+pub fn tick_active<K: PowerUpKind>(
+    active: &mut HashMap<PlayerId, Vec<ActivePowerUp<K>>>,
+    dt: f32,
+) -> Vec<GameEvent> {
+    let mut events = Vec::new();
+    for (&player_id, pus) in active.iter_mut() {
+        for pu in pus.iter_mut() {
+            pu.tick(dt);
+        }
+        pus.retain(|p| {
+            if p.is_expired() {
+                events.push(GameEvent::PowerupExpired {
+                    player_id,
+                    kind: p.kind.name().to_string(),
+                });
+            }
+            !p.is_expired()
+        });
+    }
+    events.sort_by_key(|e| match e {
+        GameEvent::PowerupExpired { player_id, .. } => *player_id,
+        _ => 0,
+    });
+    events
+}
+
+/// Recent power-up events, kept in a game's state so clients can play
+/// effects and fill the kill feed without diffing. Entries are numbered:
+/// a client remembers the next number it expects and reads from there, so
+/// a skipped state loses nothing unless more than [`FEED_LEN`] events
+/// happened in between.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PowerUpFeed {
+    /// Number the next event gets. Starts over at 0 each round.
+    pub next_seq: u32,
+    /// The most recent events with their numbers, oldest first.
+    pub recent: Vec<(u32, GameEvent)>,
+}
+
+impl PowerUpFeed {
+    pub fn push(&mut self, event: GameEvent) {
+        if self.recent.len() == FEED_LEN {
+            self.recent.remove(0);
+        }
+        self.recent.push((self.next_seq, event));
+        self.next_seq += 1;
+    }
+
+    /// Events numbered `seq` or later.
+    pub fn since(&self, seq: u32) -> impl Iterator<Item = &GameEvent> {
+        self.recent
+            .iter()
+            .filter(move |(n, _)| *n >= seq)
+            .map(|(_, e)| e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    enum Kind {
+        Quick,
+        Forever,
+    }
+
+    impl PowerUpKind for Kind {
+        fn duration(&self) -> f32 {
+            match self {
+                Kind::Quick => 1.0,
+                Kind::Forever => f32::INFINITY,
+            }
+        }
+
+        fn name(&self) -> &'static str {
+            match self {
+                Kind::Quick => "quick",
+                Kind::Forever => "forever",
+            }
+        }
+    }
+
+    #[test]
+    fn expiring_power_ups_emit_events_in_player_order() {
+        let mut active = HashMap::from([
+            (3, vec![ActivePowerUp::new(Kind::Quick)]),
+            (
+                1,
+                vec![
+                    ActivePowerUp::new(Kind::Forever),
+                    ActivePowerUp::new(Kind::Quick),
+                ],
+            ),
+        ]);
+        assert!(tick_active(&mut active, 0.5).is_empty());
+        let expired: Vec<(PlayerId, String)> = tick_active(&mut active, 0.5)
+            .into_iter()
+            .map(|e| match e {
+                GameEvent::PowerupExpired { player_id, kind } => (player_id, kind),
+                other => panic!("unexpected {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            expired,
+            [(1, "quick".to_string()), (3, "quick".to_string())]
+        );
+        assert_eq!(active[&1].len(), 1);
+        assert!(active[&3].is_empty());
+    }
+
+    #[test]
+    fn feed_keeps_the_latest_events_numbered() {
+        let mut feed = PowerUpFeed::default();
+        for i in 0..FEED_LEN as PlayerId + 4 {
+            feed.push(collected(i, Kind::Quick));
+        }
+        assert_eq!(feed.next_seq, FEED_LEN as u32 + 4);
+        assert_eq!(feed.recent.len(), FEED_LEN);
+        assert_eq!(feed.recent[0].0, 4);
+        let late: Vec<PlayerId> = feed
+            .since(FEED_LEN as u32 + 2)
+            .map(|e| match e {
+                GameEvent::PowerupCollected { player_id, .. } => *player_id,
+                other => panic!("unexpected {other:?}"),
+            })
+            .collect();
+        assert_eq!(late, [FEED_LEN as PlayerId + 2, FEED_LEN as PlayerId + 3]);
+        assert_eq!(feed.since(feed.next_seq).count(), 0);
+    }
+}
@@ -0,0 +1,448 @@
+//! Portable match recordings (`.bpreplay` files).
+//!
+//! A file is the [`REPLAY_MAGIC`] header, a format version byte, and a
+//! DEFLATE-compressed MessagePack [`MatchReplay`] with named fields. DEFLATE
+//! rather than zstd keeps the decoder pure Rust, so the WASM client can open
+//! files without a C toolchain in the build.
+
+use std::collections::HashMap;
+use std::io::{Read, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::game_trait::{GameId, PlayerScore};
+use crate::heatmap::RoundHeatmap;
+use crate::player::Player;
+
+/// First bytes of every replay file.
+pub const REPLAY_MAGIC: &[u8; 8] = b"BPREPLAY";
+
+/// Bumped when [`MatchReplay`] changes incompatibly.
+pub const REPLAY_FORMAT_VERSION: u8 = 1;
+
+/// File extension, without the dot.
+pub const REPLAY_FILE_EXTENSION: &str = "bpreplay";
+
+/// Decoding stops past this many decompressed bytes.
+pub const MAX_DECODED_REPLAY_SIZE: u64 = 256 * 1024 * 1024;
+
+/// A finished match: metadata, per-round state frames, and results.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatchReplay {
+    pub metadata: ReplayMetadata,
+    pub rounds: Vec<RoundReplay>,
+    pub final_scores: Vec<PlayerScore>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayMetadata {
+    pub match_id: String,
+    pub game: GameId,
+    pub room_code: String,
+    /// Players at the start of the match.
+    pub players: Vec<Player>,
+    /// Game settings the match was started with.
+    #[serde(default)]
+    pub custom: HashMap<String, serde_json::Value>,
+    /// Simulation ticks per second, for converting frame ticks to time.
+    pub tick_rate: f32,
+    /// Unix seconds.
+    pub started_at: u64,
+    pub ended_at: u64,
+    /// Version of the server that recorded the match.
+    pub server_version: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoundReplay {
+    pub round: u8,
+    pub scores: Vec<PlayerScore>,
+    pub frames: Vec<ReplayFrame>,
+    /// True when the round outlasted the recorder's frame limit; frames
+    /// after the limit are missing.
+    #[serde(default)]
+    pub truncated: bool,
+    /// Positional heatmap of the round, for games that track one.
+    #[serde(default)]
+    pub heatmap: Option<RoundHeatmap>,
+}
+
+/// Serialized game state as broadcast to clients at `tick`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayFrame {
+    pub tick: u32,
+    /// The state's `GameState::server_time_ms`; 0 in older recordings.
+    #[serde(default)]
+    pub server_time_ms: u64,
+    #[serde(with = "bin")]
+    pub state: Vec<u8>,
+    /// Course data sent since the previous frame, if any.
+    #[serde(default, with = "opt_bin")]
+    pub course: Option<Vec<u8>>,
+}
+
+impl MatchReplay {
+    /// Frame time in seconds from the start of its round.
+    pub fn frame_secs(&self, frame: &ReplayFrame) -> f64 {
+        if self.metadata.tick_rate > 0.0 {
+            f64::from(frame.tick) / f64::from(self.metadata.tick_rate)
+        } else {
+            0.0
+        }
+    }
+
+    /// Seconds between two frames of a round. Uses the server's timestamps
+    /// when both frames have them, so a tick the server ran late plays back
+    /// late too, as it did live.
+    pub fn frame_span_secs(&self, from: &ReplayFrame, to: &ReplayFrame) -> f64 {
+        if from.server_time_ms > 0 && to.server_time_ms > 0 {
+            (to.server_time_ms as f64 - from.server_time_ms as f64) / 1000.0
+        } else {
+            self.frame_secs(to) - self.frame_secs(from)
+        }
+    }
+}
+
+/// A round's frames thinned to every few ticks, for scrubbing through a
+/// round without downloading the whole replay. Served as named-field
+/// MessagePack by `GET /api/v1/history/:match_id/keyframes`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayKeyframes {
+    pub match_id: String,
+    pub game: GameId,
+    pub round: u8,
+    pub tick_rate: f32,
+    /// Minimum tick spacing between keyframes; may be wider than requested.
+    pub every: u32,
+    /// Last tick recorded for the round, so the scrubber can size its track.
+    pub last_tick: u32,
+    pub frames: Vec<ReplayFrame>,
+}
+
+impl RoundReplay {
+    /// Frames with ticks in `from..=to`, at least `every` ticks apart. The
+    /// last frame in range is always kept so scrubbing reaches the end.
+    /// Course data is carried forward from skipped frames (including those
+    /// before `from`) onto the next kept one, so every keyframe can be
+    /// rendered on its own.
+    pub fn keyframes(&self, from: u32, to: u32, every: u32) -> Vec<ReplayFrame> {
+        let every = every.max(1);
+        let mut out: Vec<ReplayFrame> = Vec::new();
+        let mut course: Option<&Vec<u8>> = None;
+        let mut pending: Option<&ReplayFrame> = None;
+        for frame in self.frames.iter().take_while(|f| f.tick <= to) {
+            if let Some(c) = &frame.course {
+                course = Some(c);
+            }
+            if frame.tick < from {
+                continue;
+            }
+            let due = out
+                .last()
+                .is_none_or(|kept| frame.tick >= kept.tick.saturating_add(every));
+            if due {
+                out.push(ReplayFrame {
+                    course: course.take().cloned(),
+                    ..frame.clone()
+                });
+                pending = None;
+            } else {
+                pending = Some(frame);
+            }
+        }
+        if let Some(frame) = pending {
+            out.push(ReplayFrame {
+                course: course.cloned(),
+                ..frame.clone()
+            });
+        }
+        out
+    }
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    NotAReplay,
+    UnsupportedVersion(u8),
+    TooLarge,
+    Encode(String),
+    Decode(String),
+}
+
+impl std::fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotAReplay => write!(f, "not a Breakpoint replay file"),
+            Self::UnsupportedVersion(v) => {
+                write!(
+                    f,
+                    "replay format version {v} is not supported by this build"
+                )
+            },
+            Self::TooLarge => write!(
+                f,
+                "replay exceeds {MAX_DECODED_REPLAY_SIZE} bytes when decompressed"
+            ),
+            Self::Encode(e) => write!(f, "encode error: {e}"),
+            Self::Decode(e) => write!(f, "decode error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+/// Encode a replay into `.bpreplay` file bytes.
+pub fn encode_replay(replay: &MatchReplay) -> Result<Vec<u8>, ReplayError> {
+    let payload =
+        rmp_serde::to_vec_named(replay).map_err(|e| ReplayError::Encode(e.to_string()))?;
+    let mut out = Vec::with_capacity(payload.len() / 4 + REPLAY_MAGIC.len() + 1);
+    out.extend_from_slice(REPLAY_MAGIC);
+    out.push(REPLAY_FORMAT_VERSION);
+    let mut encoder = flate2::write::DeflateEncoder::new(out, flate2::Compression::default());
+    encoder
+        .write_all(&payload)
+        .map_err(|e| ReplayError::Encode(e.to_string()))?;
+    encoder
+        .finish()
+        .map_err(|e| ReplayError::Encode(e.to_string()))
+}
+
+/// Decode `.bpreplay` file bytes.
+pub fn decode_replay(data: &[u8]) -> Result<MatchReplay, ReplayError> {
+    let body = data
+        .strip_prefix(REPLAY_MAGIC.as_slice())
+        .ok_or(ReplayError::NotAReplay)?;
+    let (&version, compressed) = body.split_first().ok_or(ReplayError::NotAReplay)?;
+    if version != REPLAY_FORMAT_VERSION {
+        return Err(ReplayError::UnsupportedVersion(version));
+    }
+    let mut payload = Vec::new();
+    flate2::read::DeflateDecoder::new(compressed)
+        .take(MAX_DECODED_REPLAY_SIZE + 1)
+        .read_to_end(&mut payload)
+        .map_err(|e| ReplayError::Decode(e.to_string()))?;
+    if payload.len() as u64 > MAX_DECODED_REPLAY_SIZE {
+        return Err(ReplayError::TooLarge);
+    }
+    rmp_serde::from_slice(&payload).map_err(|e| ReplayError::Decode(e.to_string()))
+}
+
+/// Serialize `Vec<u8>` as a MessagePack bin rather than an array of ints.
+pub(crate) mod bin {
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_bytes(bytes)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<u8>, D::Error> {
+        d.deserialize_byte_buf(BytesVisitor)
+    }
+
+    pub(super) struct BytesVisitor;
+
+    impl<'de> serde::de::Visitor<'de> for BytesVisitor {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("bytes")
+        }
+
+        fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Vec<u8>, E> {
+            Ok(v.to_vec())
+        }
+
+        fn visit_byte_buf<E: serde::de::Error>(self, v: Vec<u8>) -> Result<Vec<u8>, E> {
+            Ok(v)
+        }
+
+        fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+            let mut out = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(b) = seq.next_element()? {
+                out.push(b);
+            }
+            Ok(out)
+        }
+    }
+}
+
+mod opt_bin {
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &Option<Vec<u8>>, s: S) -> Result<S::Ok, S::Error> {
+        match bytes {
+            Some(b) => s.serialize_some(&Bin(b)),
+            None => s.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Vec<u8>>, D::Error> {
+        d.deserialize_option(OptVisitor)
+    }
+
+    struct Bin<'a>(&'a [u8]);
+
+    impl serde::Serialize for Bin<'_> {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            s.serialize_bytes(self.0)
+        }
+    }
+
+    struct OptVisitor;
+
+    impl<'de> serde::de::Visitor<'de> for OptVisitor {
+        type Value = Option<Vec<u8>>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("optional bytes")
+        }
+
+        fn visit_none<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_unit<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D: Deserializer<'de>>(self, d: D) -> Result<Self::Value, D::Error> {
+            d.deserialize_byte_buf(super::bin::BytesVisitor).map(Some)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::make_players;
+
+    fn sample() -> MatchReplay {
+        MatchReplay {
+            metadata: ReplayMetadata {
+                match_id: "m1".to_string(),
+                game: GameId::Tron,
+                room_code: "ABCD-1234".to_string(),
+                players: make_players(2),
+                custom: HashMap::from([("movement".to_string(), serde_json::json!("grid"))]),
+                tick_rate: 20.0,
+                started_at: 1_700_000_000,
+                ended_at: 1_700_000_090,
+                server_version: "0.1.0".to_string(),
+            },
+            rounds: vec![RoundReplay {
+                round: 1,
+                scores: vec![PlayerScore {
+                    player_id: 1,
+                    score: 3,
+                    disconnected: false,
+                }],
+                frames: vec![
+                    ReplayFrame {
+                        tick: 1,
+                        server_time_ms: 0,
+                        state: vec![0x92, 0x01, 0xff],
+                        course: Some(vec![7; 40]),
+                    },
+                    ReplayFrame {
+                        tick: 2,
+                        server_time_ms: 0,
+                        state: vec![0x92, 0x02, 0xfe],
+                        course: None,
+                    },
+                ],
+                truncated: false,
+                heatmap: None,
+            }],
+            final_scores: vec![PlayerScore {
+                player_id: 1,
+                score: 3,
+                disconnected: false,
+            }],
+        }
+    }
+
+    #[test]
+    fn file_roundtrip() {
+        let replay = sample();
+        let file = encode_replay(&replay).unwrap();
+        assert!(file.starts_with(REPLAY_MAGIC));
+        assert_eq!(file[REPLAY_MAGIC.len()], REPLAY_FORMAT_VERSION);
+        assert_eq!(decode_replay(&file).unwrap(), replay);
+        assert_eq!(replay.frame_secs(&replay.rounds[0].frames[1]), 0.1);
+    }
+
+    #[test]
+    fn frame_spans_prefer_server_time() {
+        let mut replay = sample();
+        let [a, b] = &replay.rounds[0].frames[..] else {
+            unreachable!()
+        };
+        assert!(
+            (replay.frame_span_secs(a, b) - 0.05).abs() < 1e-9,
+            "From ticks"
+        );
+
+        // The second tick ran 30 ms late
+        replay.rounds[0].frames[0].server_time_ms = 10_000;
+        replay.rounds[0].frames[1].server_time_ms = 10_080;
+        let [a, b] = &replay.rounds[0].frames[..] else {
+            unreachable!()
+        };
+        assert!((replay.frame_span_secs(a, b) - 0.08).abs() < 1e-9);
+    }
+
+    #[test]
+    fn keyframes_thin_a_round_and_keep_course_data() {
+        let frame = |tick: u32, course: Option<u8>| ReplayFrame {
+            tick,
+            server_time_ms: u64::from(tick) * 50,
+            state: vec![tick as u8],
+            course: course.map(|c| vec![c]),
+        };
+        let round = RoundReplay {
+            round: 1,
+            scores: Vec::new(),
+            frames: (1..=23)
+                .map(|t| frame(t, [1, 8].contains(&t).then_some(t as u8)))
+                .collect(),
+            truncated: false,
+            heatmap: None,
+        };
+
+        let ticks = |frames: &[ReplayFrame]| frames.iter().map(|f| f.tick).collect::<Vec<_>>();
+        let all = round.keyframes(0, u32::MAX, 5);
+        assert_eq!(ticks(&all), [1, 6, 11, 16, 21, 23]);
+        assert_eq!(all[0].course, Some(vec![1]));
+        // Tick 8's course is skipped over, so it rides on the next keyframe
+        assert_eq!(all[2].course, Some(vec![8]));
+        assert!(all[1].course.is_none() && all[3].course.is_none());
+
+        let window = round.keyframes(10, 14, 5);
+        assert_eq!(ticks(&window), [10, 14]);
+        assert_eq!(
+            window[0].course,
+            Some(vec![8]),
+            "Course from before the range"
+        );
+        assert!(round.keyframes(30, 40, 5).is_empty());
+    }
+
+    #[test]
+    fn rejects_foreign_and_future_files() {
+        assert!(matches!(
+            decode_replay(b"PK\x03\x04 not a replay"),
+            Err(ReplayError::NotAReplay)
+        ));
+
+        let mut file = encode_replay(&sample()).unwrap();
+        file[REPLAY_MAGIC.len()] = REPLAY_FORMAT_VERSION + 1;
+        assert!(matches!(
+            decode_replay(&file),
+            Err(ReplayError::UnsupportedVersion(_))
+        ));
+
+        let mut file = encode_replay(&sample()).unwrap();
+        file.truncate(file.len() / 2);
+        assert!(decode_replay(&file).is_err());
+    }
+}
@@ -2,8 +2,11 @@ use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 use crate::game_trait::PlayerId;
+use crate::i18n::TextKey;
 use crate::overlay::config::OverlayRoomConfig;
 use crate::player::Player;
+use crate::round_phase::{MICRO_ROUND_PACING, MICRO_ROUND_TIMINGS, RoundPacing, RoundPhaseTimings};
+use crate::win_condition::WinCondition;
 
 /// Configuration for a Breakpoint room.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -11,10 +14,54 @@ pub struct RoomConfig {
     pub max_players: u8,
     pub round_count: u8,
     pub round_duration: Duration,
-    pub between_round_duration: Duration,
+    /// Intermission length and how the next round starts.
+    #[serde(default)]
+    pub pacing: RoundPacing,
     pub host_migration_enabled: bool,
     pub host_disconnect_grace_period: Duration,
     pub overlay_config: OverlayRoomConfig,
+    /// Pre-round 3-2-1 countdown; inputs are ignored while it runs.
+    #[serde(default = "default_countdown_duration")]
+    pub countdown_duration: Duration,
+    /// Final frame held after a round ends, before results are shown.
+    #[serde(default = "default_freeze_frame_duration")]
+    pub freeze_frame_duration: Duration,
+    /// When a match ends. The default plays the game's own round count.
+    #[serde(default)]
+    pub win_condition: WinCondition,
+}
+
+fn default_countdown_duration() -> Duration {
+    Duration::from_secs_f32(RoundPhaseTimings::default().countdown_secs)
+}
+
+fn default_freeze_frame_duration() -> Duration {
+    Duration::from_secs_f32(RoundPhaseTimings::default().freeze_frame_secs)
+}
+
+impl RoomConfig {
+    /// Phase durations for the game loop's round state machine.
+    pub fn phase_timings(&self) -> RoundPhaseTimings {
+        RoundPhaseTimings {
+            countdown_secs: self.countdown_duration.as_secs_f32(),
+            freeze_frame_secs: self.freeze_frame_duration.as_secs_f32(),
+        }
+    }
+
+    /// This config with the micro-round length, pacing, and phase timings
+    /// in place of the room's own, for one session. `None` leaves it as is.
+    pub fn with_micro_rounds(&self, micro_round_secs: Option<f32>) -> Self {
+        let Some(secs) = micro_round_secs else {
+            return self.clone();
+        };
+        Self {
+            round_duration: Duration::from_secs_f32(secs),
+            pacing: MICRO_ROUND_PACING,
+            countdown_duration: Duration::from_secs_f32(MICRO_ROUND_TIMINGS.countdown_secs),
+            freeze_frame_duration: Duration::from_secs_f32(MICRO_ROUND_TIMINGS.freeze_frame_secs),
+            ..self.clone()
+        }
+    }
 }
 
 impl Default for RoomConfig {
@@ -23,10 +70,13 @@ impl Default for RoomConfig {
             max_players: 8,
             round_count: 9,
             round_duration: Duration::from_secs(90),
-            between_round_duration: Duration::from_secs(30),
+            pacing: RoundPacing::default(),
             host_migration_enabled: false,
             host_disconnect_grace_period: Duration::from_secs(60),
             overlay_config: OverlayRoomConfig::default(),
+            countdown_duration: default_countdown_duration(),
+            freeze_frame_duration: default_freeze_frame_duration(),
+            win_condition: WinCondition::default(),
         }
     }
 }
@@ -65,34 +115,200 @@ impl Room {
     }
 }
 
+/// Shape of generated room codes: `length` characters in dash-separated
+/// groups of four. The classic format (empty `alphabet`) is letters then
+/// digits, as in `ABCD-1234`; busy servers can lengthen codes or draw them
+/// from a larger alphabet to make collisions rarer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RoomCodeFormat {
+    /// Characters in a code, not counting dashes.
+    pub length: usize,
+    /// Characters codes are drawn from; empty for the classic format.
+    pub alphabet: String,
+}
+
+impl Default for RoomCodeFormat {
+    fn default() -> Self {
+        Self {
+            length: 8,
+            alphabet: String::new(),
+        }
+    }
+}
+
+/// Shortest and longest allowed [`RoomCodeFormat::length`].
+pub const ROOM_CODE_LENGTH_RANGE: std::ops::RangeInclusive<usize> = 6..=16;
+
+impl RoomCodeFormat {
+    /// Check the format is usable, for config validation.
+    pub fn validate(&self) -> Result<(), String> {
+        if !ROOM_CODE_LENGTH_RANGE.contains(&self.length) {
+            return Err(format!(
+                "room code length must be {} to {}",
+                ROOM_CODE_LENGTH_RANGE.start(),
+                ROOM_CODE_LENGTH_RANGE.end()
+            ));
+        }
+        if self.alphabet.is_empty() {
+            return Ok(());
+        }
+        if !self
+            .alphabet
+            .bytes()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+        {
+            return Err("room code alphabet must be uppercase letters and digits".to_string());
+        }
+        let mut chars: Vec<u8> = self.alphabet.bytes().collect();
+        chars.sort_unstable();
+        chars.dedup();
+        if chars.len() != self.alphabet.len() {
+            return Err("room code alphabet has repeated characters".to_string());
+        }
+        if chars.len() < 10 {
+            return Err("room code alphabet needs at least 10 characters".to_string());
+        }
+        Ok(())
+    }
+
+    /// Whether position `i` of a classic code is a letter (else a digit).
+    fn classic_letter(&self, i: usize) -> bool {
+        i < self.length / 2
+    }
+
+    /// Generate a code in this format.
+    pub fn generate(&self) -> String {
+        use rand::Rng;
+        let mut rng = rand::rng();
+        let alphabet = self.alphabet.as_bytes();
+        let mut code = String::with_capacity(self.length + self.length / 4);
+        for i in 0..self.length {
+            if i > 0 && i % 4 == 0 {
+                code.push('-');
+            }
+            let c = if !alphabet.is_empty() {
+                alphabet[rng.random_range(0..alphabet.len())]
+            } else if self.classic_letter(i) {
+                b'A' + rng.random_range(0..26u8)
+            } else {
+                b'0' + rng.random_range(0..10u8)
+            };
+            code.push(c as char);
+        }
+        code
+    }
+
+    /// Whether `code` is a code this format could generate.
+    pub fn is_valid(&self, code: &str) -> bool {
+        let bytes = code.as_bytes();
+        if bytes.len() != self.length + (self.length - 1) / 4 {
+            return false;
+        }
+        let mut i = 0;
+        for (pos, &b) in bytes.iter().enumerate() {
+            if pos % 5 == 4 {
+                if b != b'-' {
+                    return false;
+                }
+                continue;
+            }
+            let ok = if !self.alphabet.is_empty() {
+                self.alphabet.as_bytes().contains(&b)
+            } else if self.classic_letter(i) {
+                b.is_ascii_uppercase()
+            } else {
+                b.is_ascii_digit()
+            };
+            if !ok {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+}
+
 /// Generate a room code in ABCD-1234 format.
 pub fn generate_room_code() -> String {
-    use rand::Rng;
-    let mut rng = rand::rng();
-    let letters: String = (0..4)
-        .map(|_| (b'A' + rng.random_range(0..26u8)) as char)
-        .collect();
-    let digits: String = (0..4)
-        .map(|_| (b'0' + rng.random_range(0..10u8)) as char)
-        .collect();
-    format!("{letters}-{digits}")
+    RoomCodeFormat::default().generate()
 }
 
 /// Validates that a room code matches the ABCD-1234 format.
 pub fn is_valid_room_code(code: &str) -> bool {
-    if code.len() != 9 {
-        return false;
+    RoomCodeFormat::default().is_valid(code)
+}
+
+/// Shortest and longest vanity room alias.
+pub const ROOM_ALIAS_LENGTH_RANGE: std::ops::RangeInclusive<usize> = 4..=20;
+
+/// Aliases no host may claim, compared with dashes removed: names that
+/// could pass for the server or its staff, or for a mode rather than a room.
+const RESERVED_ALIASES: &[&str] = &[
+    "ADMIN",
+    "ADMINISTRATOR",
+    "API",
+    "BREAKPOINT",
+    "HOST",
+    "LOBBY",
+    "MODERATOR",
+    "NULL",
+    "OFFLINE",
+    "OFFICIAL",
+    "RELAY",
+    "ROOT",
+    "SERVER",
+    "SOLO",
+    "STAFF",
+    "SUPPORT",
+    "SYSTEM",
+    "UNDEFINED",
+];
+
+/// A host-chosen vanity alias for a room (`FRIDAY-GOLF`), normalized to
+/// the uppercase form players type to join. Aliases are 4 to 20 letters,
+/// digits and single inner dashes, contain a letter, are not reserved, and
+/// never look like a room code in `format`, so lookups are unambiguous.
+pub fn normalize_room_alias(alias: &str, format: &RoomCodeFormat) -> Result<String, TextKey> {
+    let alias = alias.trim().to_ascii_uppercase();
+    let bytes = alias.as_bytes();
+    let well_formed = ROOM_ALIAS_LENGTH_RANGE.contains(&bytes.len())
+        && bytes
+            .iter()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || *b == b'-')
+        && bytes.iter().any(u8::is_ascii_uppercase)
+        && bytes.first() != Some(&b'-')
+        && bytes.last() != Some(&b'-')
+        && !alias.contains("--");
+    if !well_formed
+        || format.is_valid(&alias)
+        || is_valid_room_code(&alias)
+        || RESERVED_ALIASES.contains(&alias.replace('-', "").as_str())
+    {
+        return Err(TextKey::InvalidRoomAlias);
     }
-    let bytes = code.as_bytes();
-    bytes[0..4].iter().all(|b| b.is_ascii_uppercase())
-        && bytes[4] == b'-'
-        && bytes[5..9].iter().all(|b| b.is_ascii_digit())
+    Ok(alias)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn micro_rounds_override_round_timing_only() {
+        let room = RoomConfig {
+            max_players: 4,
+            ..RoomConfig::default()
+        };
+        assert_eq!(room.with_micro_rounds(None), room);
+        let micro = room.with_micro_rounds(Some(35.0));
+        assert_eq!(micro.round_duration, Duration::from_secs(35));
+        assert_eq!(micro.pacing, MICRO_ROUND_PACING);
+        assert_eq!(micro.phase_timings(), MICRO_ROUND_TIMINGS);
+        assert_eq!(micro.max_players, 4);
+        assert_eq!(micro.round_count, room.round_count);
+    }
+
     #[test]
     fn valid_room_codes() {
         assert!(is_valid_room_code("ABCD-1234"));
@@ -110,4 +326,86 @@ mod tests {
         assert!(!is_valid_room_code("ABCD-123A"));
         assert!(!is_valid_room_code("1234-ABCD"));
     }
+
+    #[test]
+    fn custom_code_formats_round_trip() {
+        let long = RoomCodeFormat {
+            length: 12,
+            alphabet: String::new(),
+        };
+        let code = long.generate();
+        assert_eq!(code.len(), 14);
+        assert!(long.is_valid(&code), "{code}");
+        assert!(long.is_valid("ABCD-EF12-3456"));
+        assert!(!long.is_valid("ABCD-1234"));
+
+        let crockford = RoomCodeFormat {
+            length: 10,
+            alphabet: "0123456789ABCDEFGHJKMNPQRSTVWXYZ".to_string(),
+        };
+        assert!(crockford.validate().is_ok());
+        let code = crockford.generate();
+        assert!(crockford.is_valid(&code), "{code}");
+        assert!(
+            !crockford.is_valid("ABCD-EFGH-IL"),
+            "I and L are not in the alphabet"
+        );
+
+        assert!(RoomCodeFormat::default().validate().is_ok());
+        let short = RoomCodeFormat {
+            length: 4,
+            ..Default::default()
+        };
+        assert!(short.validate().is_err());
+        let tiny = RoomCodeFormat {
+            length: 8,
+            alphabet: "ABC".to_string(),
+        };
+        assert!(tiny.validate().is_err());
+        let repeated = RoomCodeFormat {
+            length: 8,
+            alphabet: "AABCDEFGHIJ".to_string(),
+        };
+        assert!(repeated.validate().is_err());
+    }
+
+    #[test]
+    fn room_aliases_are_normalized_and_checked() {
+        let format = RoomCodeFormat::default();
+        assert_eq!(
+            normalize_room_alias(" friday-golf ", &format),
+            Ok("FRIDAY-GOLF".to_string())
+        );
+        assert_eq!(
+            normalize_room_alias("TEAM42", &format),
+            Ok("TEAM42".to_string())
+        );
+        for bad in [
+            "abc",
+            "1234",
+            "NO SPACES",
+            "-EDGE",
+            "EDGE-",
+            "TWO--DASH",
+            "A".repeat(21).as_str(),
+            "ABCD-1234",
+            "admin",
+            "AD-MIN",
+            "Solo",
+        ] {
+            assert_eq!(
+                normalize_room_alias(bad, &format),
+                Err(TextKey::InvalidRoomAlias),
+                "{bad}"
+            );
+        }
+        let open = RoomCodeFormat {
+            length: 8,
+            alphabet: "ABCDEFGHJKLMNPQRSTUVWXYZ".to_string(),
+        };
+        assert!(
+            normalize_room_alias("GAME-DAYS", &open).is_err(),
+            "Looks like a code"
+        );
+    }
 }
@@ -0,0 +1,237 @@
+//! Room configurations as shareable JSON. A leader exports the lobby's game,
+//! settings, round pacing, and alert filter; pasting the text into another
+//! room's lobby imports them. Each export names its format version, and
+//! imports reject versions this build does not read with a message saying
+//! which side is out of date.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::game_settings::CustomSettings;
+use crate::game_trait::GameId;
+use crate::overlay::config::OverlayRoomConfig;
+use crate::round_phase::{MAX_PACING_SECS, RoundPacing};
+
+/// Marks a JSON document as a room configuration.
+pub const ROOM_EXPORT_FORMAT: &str = "breakpoint-room";
+/// The format version this build writes and the newest it reads.
+pub const ROOM_EXPORT_VERSION: u32 = 1;
+/// Largest import accepted, in bytes.
+pub const MAX_ROOM_EXPORT_BYTES: usize = 64 * 1024;
+
+/// A room configuration as exported.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RoomExport {
+    /// Always [`ROOM_EXPORT_FORMAT`].
+    pub format: String,
+    pub version: u32,
+    /// Wire-format game ID, e.g. `"laser-tag"`.
+    pub game: String,
+    /// Lobby settings, checked against the game when imported.
+    #[serde(default)]
+    pub settings: CustomSettings,
+    #[serde(default)]
+    pub pacing: RoundPacing,
+    /// Which alerts reach the room, and how they are shown.
+    #[serde(default)]
+    pub overlay: OverlayRoomConfig,
+}
+
+/// Why an import was refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RoomImportError {
+    /// The text is not JSON, or is too large.
+    NotJson(String),
+    /// Valid JSON, but not a room configuration.
+    NotRoomConfig,
+    /// Exported by a newer build than this one.
+    NewerVersion { found: u64 },
+    /// A version no build has written.
+    UnknownVersion { found: u64 },
+    /// A field is missing, misspelled, or out of range.
+    Invalid(String),
+}
+
+impl fmt::Display for RoomImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotJson(e) => write!(f, "not valid JSON: {e}"),
+            Self::NotRoomConfig => write!(
+                f,
+                "not a room config (expected \"format\": \"{ROOM_EXPORT_FORMAT}\")"
+            ),
+            Self::NewerVersion { found } => write!(
+                f,
+                "exported by a newer Breakpoint (format version {found}; this one reads up to \
+                 {ROOM_EXPORT_VERSION}); update this server or export again from an older one"
+            ),
+            Self::UnknownVersion { found } => {
+                write!(f, "unknown format version {found}")
+            },
+            Self::Invalid(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for RoomImportError {}
+
+impl RoomExport {
+    pub fn new(
+        game: GameId,
+        settings: CustomSettings,
+        pacing: RoundPacing,
+        overlay: OverlayRoomConfig,
+    ) -> Self {
+        Self {
+            format: ROOM_EXPORT_FORMAT.to_string(),
+            version: ROOM_EXPORT_VERSION,
+            game: game.as_str().to_string(),
+            settings,
+            pacing,
+            overlay,
+        }
+    }
+
+    /// Pretty-printed, for pasting into chat or a wiki.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Parse and check an export. The format and version are checked
+    /// before the fields, so a config from a newer build is reported as
+    /// such rather than as whichever field it added.
+    pub fn from_json(text: &str) -> Result<Self, RoomImportError> {
+        if text.len() > MAX_ROOM_EXPORT_BYTES {
+            return Err(RoomImportError::NotJson(format!(
+                "larger than {MAX_ROOM_EXPORT_BYTES} bytes"
+            )));
+        }
+        let value: serde_json::Value =
+            serde_json::from_str(text).map_err(|e| RoomImportError::NotJson(e.to_string()))?;
+        if value.get("format").and_then(|f| f.as_str()) != Some(ROOM_EXPORT_FORMAT) {
+            return Err(RoomImportError::NotRoomConfig);
+        }
+        let version = value
+            .get("version")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| RoomImportError::Invalid("missing \"version\"".to_string()))?;
+        if version > u64::from(ROOM_EXPORT_VERSION) {
+            return Err(RoomImportError::NewerVersion { found: version });
+        }
+        if version == 0 {
+            return Err(RoomImportError::UnknownVersion { found: version });
+        }
+        let export: Self =
+            serde_json::from_value(value).map_err(|e| RoomImportError::Invalid(e.to_string()))?;
+        export.validate()?;
+        Ok(export)
+    }
+
+    /// The game to select. Only `None` for exports not checked by
+    /// [`from_json`](Self::from_json).
+    pub fn game_id(&self) -> Option<GameId> {
+        GameId::from_str_opt(&self.game)
+    }
+
+    fn validate(&self) -> Result<(), RoomImportError> {
+        if self.game_id().is_none() {
+            return Err(RoomImportError::Invalid(format!(
+                "unknown game \"{}\"",
+                self.game
+            )));
+        }
+        if self.pacing != self.pacing.clamped() {
+            return Err(RoomImportError::Invalid(format!(
+                "pacing durations must be at most {MAX_PACING_SECS} seconds"
+            )));
+        }
+        self.overlay
+            .validate()
+            .map_err(|e| RoomImportError::Invalid(format!("overlay: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::events::Priority;
+
+    fn export() -> RoomExport {
+        let overlay = OverlayRoomConfig {
+            event_tags: vec!["repo:backend".to_string()],
+            min_priority: Priority::Urgent,
+            ..OverlayRoomConfig::default()
+        };
+        RoomExport::new(
+            GameId::LaserTag,
+            [("team_mode".to_string(), json!("teams2"))].into(),
+            RoundPacing {
+                intermission_secs: 10,
+                auto_advance: false,
+                min_results_secs: 3,
+            },
+            overlay,
+        )
+    }
+
+    #[test]
+    fn exports_round_trip() {
+        let original = export();
+        let text = original.to_json();
+        assert!(text.contains("\"format\": \"breakpoint-room\""));
+        assert_eq!(RoomExport::from_json(&text), Ok(original));
+
+        // Sections left out take their defaults
+        let minimal = r#"{"format": "breakpoint-room", "version": 1, "game": "tron"}"#;
+        let parsed = RoomExport::from_json(minimal).unwrap();
+        assert_eq!(parsed.game_id(), Some(GameId::Tron));
+        assert_eq!(parsed.pacing, RoundPacing::default());
+    }
+
+    #[test]
+    fn versions_are_checked_before_fields() {
+        let mut newer = serde_json::to_value(export()).unwrap();
+        newer["version"] = json!(2);
+        newer["playlist"] = json!(["tron"]);
+        let err = RoomExport::from_json(&newer.to_string()).unwrap_err();
+        assert_eq!(err, RoomImportError::NewerVersion { found: 2 });
+        assert!(err.to_string().contains("reads up to 1"), "{err}");
+
+        newer["version"] = json!(0);
+        assert_eq!(
+            RoomExport::from_json(&newer.to_string()),
+            Err(RoomImportError::UnknownVersion { found: 0 })
+        );
+        assert_eq!(
+            RoomExport::from_json(r#"{"game": "tron"}"#),
+            Err(RoomImportError::NotRoomConfig)
+        );
+        assert!(matches!(
+            RoomExport::from_json("{\"format\": "),
+            Err(RoomImportError::NotJson(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_fields_are_named() {
+        let reason = |patch: serde_json::Value| {
+            let mut value = serde_json::to_value(export()).unwrap();
+            for (key, v) in patch.as_object().unwrap() {
+                value[key] = v.clone();
+            }
+            match RoomExport::from_json(&value.to_string()) {
+                Err(RoomImportError::Invalid(reason)) => reason,
+                other => panic!("expected Invalid, got {other:?}"),
+            }
+        };
+        assert!(reason(json!({"gmae": "tron"})).contains("unknown field `gmae`"));
+        assert!(reason(json!({"game": "chess"})).contains("unknown game \"chess\""));
+        assert!(reason(json!({"pacing": {"intermission_secs": 9999, "auto_advance": true, "min_results_secs": 0}})).contains("at most"));
+        let too_many: Vec<String> = (0..100).map(|i| format!("src{i}")).collect();
+        assert!(reason(json!({"overlay": {"enabled_sources": too_many}})).starts_with("overlay:"));
+    }
+}
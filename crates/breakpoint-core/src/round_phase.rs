@@ -0,0 +1,403 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game_settings::{CustomSettings, SettingsError, SettingsReader};
+
+/// Lobby setting that opens a match with a warmup: free play that scores
+/// nothing, until the host starts the first round.
+pub const WARMUP_SETTING: &str = "warmup";
+
+/// Whether the lobby settings ask for a warmup.
+pub fn warmup_requested(custom: &CustomSettings) -> bool {
+    custom
+        .get(WARMUP_SETTING)
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Lobby setting for micro-rounds: very short rounds in every game with
+/// almost no wait between them, for players who can only spare a minute.
+/// `true` plays [`MICRO_ROUND_DEFAULT_SECS`] rounds; a number picks the
+/// round length within [`MICRO_ROUND_SECS_RANGE`].
+pub const MICRO_ROUNDS_SETTING: &str = "micro_rounds";
+
+/// Shortest and longest micro-round, in seconds.
+pub const MICRO_ROUND_SECS_RANGE: (f32, f32) = (30.0, 45.0);
+
+/// Micro-round length when the lobby just turns the mode on, in seconds.
+pub const MICRO_ROUND_DEFAULT_SECS: f32 = 40.0;
+
+/// Between-round pacing in micro-rounds: results flash up and the next
+/// round starts on its own.
+pub const MICRO_ROUND_PACING: RoundPacing = RoundPacing {
+    intermission_secs: 3,
+    auto_advance: true,
+    min_results_secs: 2,
+};
+
+/// Countdown and freeze frame in micro-rounds.
+pub const MICRO_ROUND_TIMINGS: RoundPhaseTimings = RoundPhaseTimings {
+    countdown_secs: 1.0,
+    freeze_frame_secs: 0.5,
+};
+
+/// The micro-round length the lobby settings ask for, or `None` when the
+/// mode is off.
+pub fn micro_round_secs(custom: &CustomSettings) -> Result<Option<f32>, SettingsError> {
+    let reader = SettingsReader::new(custom);
+    let (min_secs, max_secs) = MICRO_ROUND_SECS_RANGE;
+    match custom.get(MICRO_ROUNDS_SETTING) {
+        Some(serde_json::Value::Bool(on)) => Ok(on.then_some(MICRO_ROUND_DEFAULT_SECS)),
+        _ => reader.f32_in(MICRO_ROUNDS_SETTING, min_secs, max_secs),
+    }
+}
+
+/// Phase of a round, driven by the server game loop and shared with every
+/// game and the client HUD.
+///
+/// A round runs `Countdown -> Playing -> FreezeFrame -> Results`. The game
+/// only simulates during `Warmup` and `Playing`; player inputs are
+/// discarded otherwise.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoundPhase {
+    /// Free play before the first round, with scoring off. Untimed; lasts
+    /// until the host starts the match.
+    Warmup,
+    /// Synchronized 3-2-1 before the simulation starts.
+    #[default]
+    Countdown,
+    Playing,
+    /// Final frame held on screen after the round ends.
+    FreezeFrame,
+    /// Round over; results are being shown.
+    Results,
+}
+
+impl RoundPhase {
+    /// Whether player inputs are applied in this phase.
+    pub fn accepts_input(self) -> bool {
+        matches!(self, Self::Warmup | Self::Playing)
+    }
+}
+
+/// Durations of the timed phases, in seconds. Zero skips the phase.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoundPhaseTimings {
+    pub countdown_secs: f32,
+    pub freeze_frame_secs: f32,
+}
+
+impl Default for RoundPhaseTimings {
+    fn default() -> Self {
+        Self {
+            countdown_secs: 3.0,
+            freeze_frame_secs: 1.5,
+        }
+    }
+}
+
+/// Round phase state machine. The owner advances it with [`tick`](Self::tick)
+/// and calls [`finish`](Self::finish) when the game reports the round complete.
+#[derive(Debug, Clone)]
+pub struct RoundPhaseMachine {
+    phase: RoundPhase,
+    remaining: f32,
+    timings: RoundPhaseTimings,
+}
+
+impl RoundPhaseMachine {
+    /// Start a round, in `Countdown` unless the countdown is disabled.
+    pub fn new(timings: RoundPhaseTimings) -> Self {
+        let mut machine = Self {
+            phase: RoundPhase::Countdown,
+            remaining: 0.0,
+            timings,
+        };
+        machine.restart();
+        machine
+    }
+
+    /// Start in `Warmup`, which lasts until [`end_warmup`](Self::end_warmup).
+    pub fn warmup(timings: RoundPhaseTimings) -> Self {
+        Self {
+            phase: RoundPhase::Warmup,
+            remaining: 0.0,
+            timings,
+        }
+    }
+
+    /// The host starts the match: count down into the first round. Returns
+    /// the phase entered, or `None` if not warming up.
+    pub fn end_warmup(&mut self) -> Option<RoundPhase> {
+        if self.phase != RoundPhase::Warmup {
+            return None;
+        }
+        self.restart();
+        Some(self.phase)
+    }
+
+    /// Reset to the start of a new round.
+    pub fn restart(&mut self) {
+        self.enter(RoundPhase::Countdown, self.timings.countdown_secs);
+    }
+
+    pub fn phase(&self) -> RoundPhase {
+        self.phase
+    }
+
+    /// Seconds left in the current timed phase (0 for untimed phases).
+    pub fn remaining(&self) -> f32 {
+        self.remaining
+    }
+
+    /// The number to show during the countdown (3, 2, 1), if counting down.
+    pub fn countdown_number(&self) -> Option<u8> {
+        (self.phase == RoundPhase::Countdown).then(|| self.remaining.ceil().max(1.0) as u8)
+    }
+
+    /// Advance timed phases by `dt` seconds. Returns the new phase on a
+    /// transition.
+    pub fn tick(&mut self, dt: f32) -> Option<RoundPhase> {
+        let next = match self.phase {
+            RoundPhase::Countdown => RoundPhase::Playing,
+            RoundPhase::FreezeFrame => RoundPhase::Results,
+            RoundPhase::Warmup | RoundPhase::Playing | RoundPhase::Results => return None,
+        };
+        self.remaining -= dt;
+        if self.remaining > 0.0 {
+            return None;
+        }
+        self.enter(next, 0.0);
+        Some(next)
+    }
+
+    /// End play and hold the final frame. Returns the phase entered, or
+    /// `None` if the round was not being played. A warmup never finishes.
+    pub fn finish(&mut self) -> Option<RoundPhase> {
+        if self.phase != RoundPhase::Playing {
+            return None;
+        }
+        self.enter(RoundPhase::FreezeFrame, self.timings.freeze_frame_secs);
+        Some(self.phase)
+    }
+
+    /// Enter `phase`, skipping straight past it when its duration is zero.
+    fn enter(&mut self, phase: RoundPhase, secs: f32) {
+        self.phase = phase;
+        self.remaining = secs.max(0.0);
+        if self.remaining == 0.0 {
+            match phase {
+                RoundPhase::Countdown => self.phase = RoundPhase::Playing,
+                RoundPhase::FreezeFrame => self.phase = RoundPhase::Results,
+                RoundPhase::Warmup | RoundPhase::Playing | RoundPhase::Results => {},
+            }
+        }
+    }
+}
+
+/// Longest intermission or results hold a room can configure, in seconds.
+pub const MAX_PACING_SECS: u16 = 300;
+
+/// How a match moves from one round to the next. Set per room and enforced
+/// by whoever runs the game loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundPacing {
+    /// Seconds between rounds when advancing automatically.
+    pub intermission_secs: u16,
+    /// Start the next round when the intermission runs out. When off, the
+    /// room waits for the leader.
+    pub auto_advance: bool,
+    /// Seconds the results stay up before the next round can start, even
+    /// if the leader advances right away.
+    pub min_results_secs: u16,
+}
+
+impl Default for RoundPacing {
+    fn default() -> Self {
+        Self {
+            intermission_secs: 30,
+            auto_advance: true,
+            min_results_secs: 5,
+        }
+    }
+}
+
+impl RoundPacing {
+    /// Clamp every duration to [`MAX_PACING_SECS`].
+    pub fn clamped(self) -> Self {
+        Self {
+            intermission_secs: self.intermission_secs.min(MAX_PACING_SECS),
+            auto_advance: self.auto_advance,
+            min_results_secs: self.min_results_secs.min(MAX_PACING_SECS),
+        }
+    }
+}
+
+/// The pause between two rounds. The owner advances it with
+/// [`tick`](Self::tick) and starts the next round once it is
+/// [`done`](Self::is_done).
+#[derive(Debug, Clone)]
+pub struct Intermission {
+    pacing: RoundPacing,
+    elapsed: f32,
+    advance_requested: bool,
+}
+
+impl Intermission {
+    pub fn new(pacing: RoundPacing) -> Self {
+        Self {
+            pacing: pacing.clamped(),
+            elapsed: 0.0,
+            advance_requested: false,
+        }
+    }
+
+    pub fn tick(&mut self, dt: f32) {
+        self.elapsed += dt;
+    }
+
+    /// The leader asks for the next round. It starts once the results have
+    /// been up for `min_results_secs`. Returns `false` if already asked.
+    pub fn request_advance(&mut self) -> bool {
+        !std::mem::replace(&mut self.advance_requested, true)
+    }
+
+    /// Seconds until the next round, or `None` while waiting for the leader.
+    pub fn remaining(&self) -> Option<f32> {
+        let min_results = f32::from(self.pacing.min_results_secs);
+        let end = if self.advance_requested {
+            min_results
+        } else if self.pacing.auto_advance {
+            min_results.max(f32::from(self.pacing.intermission_secs))
+        } else {
+            return None;
+        };
+        Some((end - self.elapsed).max(0.0))
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.remaining() == Some(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn micro_rounds_setting() {
+        let custom =
+            |v: serde_json::Value| CustomSettings::from([(MICRO_ROUNDS_SETTING.into(), v)]);
+        assert_eq!(micro_round_secs(&CustomSettings::new()), Ok(None));
+        assert_eq!(micro_round_secs(&custom(false.into())), Ok(None));
+        assert_eq!(
+            micro_round_secs(&custom(true.into())),
+            Ok(Some(MICRO_ROUND_DEFAULT_SECS))
+        );
+        assert_eq!(micro_round_secs(&custom(30.into())), Ok(Some(30.0)));
+        for bad in [serde_json::json!(90), serde_json::json!("short")] {
+            let err = micro_round_secs(&custom(bad)).unwrap_err();
+            assert_eq!(err.key, MICRO_ROUNDS_SETTING);
+        }
+    }
+
+    #[test]
+    fn countdown_then_playing() {
+        let mut machine = RoundPhaseMachine::new(RoundPhaseTimings::default());
+        assert_eq!(machine.phase(), RoundPhase::Countdown);
+        assert_eq!(machine.countdown_number(), Some(3));
+        assert!(!machine.phase().accepts_input());
+
+        assert_eq!(machine.tick(1.2), None);
+        assert_eq!(machine.countdown_number(), Some(2));
+        assert_eq!(machine.tick(1.0), None);
+        assert_eq!(machine.countdown_number(), Some(1));
+        assert_eq!(machine.tick(1.0), Some(RoundPhase::Playing));
+        assert!(machine.phase().accepts_input());
+        assert_eq!(machine.countdown_number(), None);
+        assert_eq!(machine.tick(100.0), None, "Playing is untimed");
+    }
+
+    #[test]
+    fn finish_holds_freeze_frame_before_results() {
+        let mut machine = RoundPhaseMachine::new(RoundPhaseTimings::default());
+        assert_eq!(machine.finish(), None, "Cannot finish during countdown");
+        machine.tick(3.0);
+        assert_eq!(machine.finish(), Some(RoundPhase::FreezeFrame));
+        assert!(!machine.phase().accepts_input());
+        assert_eq!(machine.tick(1.0), None);
+        assert_eq!(machine.tick(1.0), Some(RoundPhase::Results));
+
+        machine.restart();
+        assert_eq!(machine.phase(), RoundPhase::Countdown);
+    }
+
+    #[test]
+    fn zero_durations_skip_phases() {
+        let mut machine = RoundPhaseMachine::new(RoundPhaseTimings {
+            countdown_secs: 0.0,
+            freeze_frame_secs: 0.0,
+        });
+        assert_eq!(machine.phase(), RoundPhase::Playing);
+        assert_eq!(machine.finish(), Some(RoundPhase::Results));
+    }
+
+    #[test]
+    fn warmup_lasts_until_the_host_starts_the_match() {
+        let mut machine = RoundPhaseMachine::warmup(RoundPhaseTimings::default());
+        assert_eq!(machine.phase(), RoundPhase::Warmup);
+        assert!(machine.phase().accepts_input());
+        assert_eq!(machine.tick(600.0), None, "Warmup is untimed");
+        assert_eq!(
+            machine.finish(),
+            None,
+            "A warmup round never ends the match"
+        );
+
+        assert_eq!(machine.end_warmup(), Some(RoundPhase::Countdown));
+        assert_eq!(machine.end_warmup(), None);
+        assert_eq!(machine.tick(3.0), Some(RoundPhase::Playing));
+    }
+
+    #[test]
+    fn warmup_is_a_lobby_setting() {
+        let mut custom = CustomSettings::new();
+        assert!(!warmup_requested(&custom));
+        custom.insert(WARMUP_SETTING.to_string(), serde_json::json!(true));
+        assert!(warmup_requested(&custom));
+    }
+
+    #[test]
+    fn intermission_auto_advances_after_the_longer_wait() {
+        let mut pause = Intermission::new(RoundPacing {
+            intermission_secs: 3,
+            auto_advance: true,
+            min_results_secs: 5,
+        });
+        assert_eq!(pause.remaining(), Some(5.0), "Results hold wins");
+        pause.tick(4.0);
+        assert!(!pause.is_done());
+        pause.tick(1.0);
+        assert!(pause.is_done());
+    }
+
+    #[test]
+    fn intermission_waits_for_the_leader_but_not_below_the_minimum() {
+        let mut pause = Intermission::new(RoundPacing {
+            intermission_secs: 30,
+            auto_advance: false,
+            min_results_secs: 5,
+        });
+        pause.tick(60.0);
+        assert_eq!(pause.remaining(), None);
+        assert!(!pause.is_done());
+        assert!(pause.request_advance());
+        assert!(!pause.request_advance(), "Asking twice changes nothing");
+        assert!(pause.is_done());
+
+        // Advancing early still shows results for the minimum time
+        let mut early = Intermission::new(RoundPacing::default());
+        early.tick(1.0);
+        early.request_advance();
+        assert_eq!(early.remaining(), Some(4.0));
+    }
+}
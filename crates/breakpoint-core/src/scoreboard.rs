@@ -0,0 +1,181 @@
+//! Live standings for the hold-to-view scoreboard. The server game loop
+//! and a LAN host both build a [`StandingsMsg`] from the running game
+//! every [`STANDINGS_INTERVAL`], so clients show the same board for every
+//! game without decoding game state.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::game_trait::{BreakpointGame, PlayerId};
+use crate::net::messages::{StandingEntry, StandingsMsg, TeamStanding};
+
+/// How often standings are sent while a round is played.
+pub const STANDINGS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Ticks between standings at `tick_rate`, at least one.
+pub fn standings_every(tick_rate: f32) -> u32 {
+    ((tick_rate * STANDINGS_INTERVAL.as_secs_f32()).round() as u32).max(1)
+}
+
+/// The standings of `game` part way through `round`. `earlier` holds each
+/// player's total from the rounds already finished and `pings` the round
+/// trips players reported. Departed players are left off.
+pub fn standings(
+    game: &dyn BreakpointGame,
+    round: u8,
+    round_count: u8,
+    earlier: &HashMap<PlayerId, i32>,
+    pings: &HashMap<PlayerId, u16>,
+) -> StandingsMsg {
+    let teams = game.teams();
+    let mut players: Vec<StandingEntry> = game
+        .round_results()
+        .into_iter()
+        .filter(|s| !s.disconnected)
+        .map(|s| StandingEntry {
+            player_id: s.player_id,
+            round_score: s.score,
+            match_score: earlier.get(&s.player_id).copied().unwrap_or(0) + s.score,
+            team: teams.get(&s.player_id).copied(),
+            ping_ms: pings.get(&s.player_id).copied(),
+        })
+        .collect();
+    players.sort_by(|a, b| {
+        b.match_score
+            .cmp(&a.match_score)
+            .then(b.round_score.cmp(&a.round_score))
+            .then(a.player_id.cmp(&b.player_id))
+    });
+
+    let mut totals: HashMap<u8, i32> = HashMap::new();
+    for entry in &players {
+        if let Some(team) = entry.team {
+            *totals.entry(team).or_insert(0) += entry.match_score;
+        }
+    }
+    let mut teams: Vec<TeamStanding> = totals
+        .into_iter()
+        .map(|(team, score)| TeamStanding { team, score })
+        .collect();
+    teams.sort_by(|a, b| b.score.cmp(&a.score).then(a.team.cmp(&b.team)));
+
+    StandingsMsg {
+        round,
+        round_count,
+        players,
+        teams,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_trait::{GameConfig, GameEvent, GameMetadata, PlayerInputs, PlayerScore};
+    use crate::pause::PauseReason;
+    use crate::player::Player;
+
+    /// A game that reports fixed scores and teams.
+    struct Fixed {
+        scores: Vec<PlayerScore>,
+        teams: HashMap<PlayerId, u8>,
+    }
+
+    impl BreakpointGame for Fixed {
+        fn metadata(&self) -> GameMetadata {
+            GameMetadata {
+                name: "Fixed".to_string(),
+                description: String::new(),
+                min_players: 1,
+                max_players: u8::MAX,
+                estimated_round_duration: Duration::ZERO,
+            }
+        }
+        fn init(&mut self, _: &[Player], _: &GameConfig) {}
+        fn update(&mut self, _: f32, _: &PlayerInputs) -> Vec<GameEvent> {
+            Vec::new()
+        }
+        fn serialize_state(&self) -> Vec<u8> {
+            Vec::new()
+        }
+        fn apply_state(&mut self, _: &[u8]) {}
+        fn apply_input(&mut self, _: PlayerId, _: &[u8]) {}
+        fn player_joined(&mut self, _: &Player) {}
+        fn player_left(&mut self, _: PlayerId) {}
+        fn pause_for(&mut self, _: PauseReason) {}
+        fn resume_for(&mut self, _: PauseReason) {}
+        fn pause_reasons(&self) -> &[PauseReason] {
+            &[]
+        }
+        fn is_round_complete(&self) -> bool {
+            false
+        }
+        fn round_results(&self) -> Vec<PlayerScore> {
+            self.scores.clone()
+        }
+        fn teams(&self) -> HashMap<PlayerId, u8> {
+            self.teams.clone()
+        }
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    fn score(player_id: PlayerId, score: i32, disconnected: bool) -> PlayerScore {
+        PlayerScore {
+            player_id,
+            score,
+            disconnected,
+        }
+    }
+
+    #[test]
+    fn match_totals_rank_players_and_teams() {
+        let game = Fixed {
+            scores: vec![
+                score(1, 2, false),
+                score(2, 5, false),
+                score(3, 1, false),
+                score(4, 9, true),
+            ],
+            teams: HashMap::from([(1, 0), (2, 1), (3, 0)]),
+        };
+        let earlier = HashMap::from([(1, 10), (3, 4)]);
+        let pings = HashMap::from([(2, 80)]);
+        let msg = standings(&game, 2, 3, &earlier, &pings);
+
+        assert_eq!((msg.round, msg.round_count), (2, 3));
+        let rows: Vec<(PlayerId, i32, i32)> = msg
+            .players
+            .iter()
+            .map(|e| (e.player_id, e.round_score, e.match_score))
+            .collect();
+        // Player 4 left, so drops off the board
+        assert_eq!(rows, [(1, 2, 12), (2, 5, 5), (3, 1, 5)]);
+        assert_eq!(msg.players[1].ping_ms, Some(80));
+        assert_eq!(msg.players[0].ping_ms, None);
+        assert_eq!(
+            msg.teams,
+            [
+                TeamStanding { team: 0, score: 17 },
+                TeamStanding { team: 1, score: 5 },
+            ]
+        );
+    }
+
+    #[test]
+    fn free_for_all_has_no_teams() {
+        let game = Fixed {
+            scores: vec![score(1, 3, false)],
+            teams: HashMap::new(),
+        };
+        let msg = standings(&game, 1, 1, &HashMap::new(), &HashMap::new());
+        assert_eq!(msg.players[0].team, None);
+        assert!(msg.teams.is_empty());
+    }
+
+    #[test]
+    fn interval_is_at_least_a_tick() {
+        assert_eq!(standings_every(10.0), 10);
+        assert_eq!(standings_every(0.2), 1);
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::tutorial::TutorialProgress;
+
+/// Settings values accepted per player.
+pub const MAX_SETTINGS_VALUES: usize = 64;
+/// Keybinds accepted per player.
+pub const MAX_KEYBINDS: usize = 128;
+/// Longest setting name, keybind action, or bound key.
+pub const MAX_SETTINGS_KEY_LEN: usize = 64;
+/// Largest serialized settings value.
+pub const MAX_SETTINGS_VALUE_LEN: usize = 1024;
+/// Games with tutorial progress accepted per player.
+pub const MAX_TUTORIAL_GAMES: usize = 16;
+
+/// One action's key binding, timestamped so bindings changed on different
+/// devices merge instead of overwriting each other.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Keybind {
+    pub key: String,
+    /// Client clock (unix ms) when the binding was last changed.
+    pub updated_at: u64,
+}
+
+/// A player's preferences as synced through
+/// `GET/PUT /api/v1/players/{id}/settings`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SyncedSettings {
+    /// Client clock (unix ms) of the last change to `values`. Zero means
+    /// the device has never changed a setting.
+    #[serde(default)]
+    pub updated_at: u64,
+    /// Plain preferences (volume, language, ...), replaced as a whole.
+    #[serde(default)]
+    pub values: BTreeMap<String, serde_json::Value>,
+    /// Key bindings by action, merged one action at a time.
+    #[serde(default)]
+    pub keybinds: BTreeMap<String, Keybind>,
+    /// Tutorial objectives completed, recorded by the server as they are
+    /// met and merged as a union.
+    #[serde(default, skip_serializing_if = "TutorialProgress::is_empty")]
+    pub tutorial: TutorialProgress,
+}
+
+impl SyncedSettings {
+    /// Fold another device's settings into these. `values` are
+    /// last-write-wins as a whole; each keybind keeps whichever side
+    /// changed it last; tutorial progress keeps everything either side
+    /// has done. Ties keep the current side, so merging the same upload
+    /// twice changes nothing.
+    pub fn merge(&mut self, other: SyncedSettings) {
+        if other.updated_at > self.updated_at {
+            self.updated_at = other.updated_at;
+            self.values = other.values;
+        }
+        for (action, bind) in other.keybinds {
+            match self.keybinds.get(&action) {
+                Some(current) if current.updated_at >= bind.updated_at => {},
+                _ => {
+                    self.keybinds.insert(action, bind);
+                },
+            }
+        }
+        self.tutorial.merge(other.tutorial);
+    }
+
+    /// Check the size limits the server enforces on uploads.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.values.len() > MAX_SETTINGS_VALUES {
+            return Err(format!("values exceed {MAX_SETTINGS_VALUES} entries"));
+        }
+        if self.keybinds.len() > MAX_KEYBINDS {
+            return Err(format!("keybinds exceed {MAX_KEYBINDS} entries"));
+        }
+        for (name, value) in &self.values {
+            if name.is_empty() || name.len() > MAX_SETTINGS_KEY_LEN {
+                return Err(format!(
+                    "setting names must be 1-{MAX_SETTINGS_KEY_LEN} bytes"
+                ));
+            }
+            if value.to_string().len() > MAX_SETTINGS_VALUE_LEN {
+                return Err(format!(
+                    "setting {name} exceeds {MAX_SETTINGS_VALUE_LEN} bytes"
+                ));
+            }
+        }
+        for (action, bind) in &self.keybinds {
+            if action.is_empty() || action.len() > MAX_SETTINGS_KEY_LEN {
+                return Err(format!(
+                    "keybind actions must be 1-{MAX_SETTINGS_KEY_LEN} bytes"
+                ));
+            }
+            if bind.key.len() > MAX_SETTINGS_KEY_LEN {
+                return Err(format!("bound keys exceed {MAX_SETTINGS_KEY_LEN} bytes"));
+            }
+        }
+        self.tutorial
+            .validate(MAX_TUTORIAL_GAMES, MAX_SETTINGS_KEY_LEN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(updated_at: u64, volume: f64, binds: &[(&str, &str, u64)]) -> SyncedSettings {
+        SyncedSettings {
+            updated_at,
+            values: [("volume".to_string(), serde_json::json!(volume))].into(),
+            keybinds: binds
+                .iter()
+                .map(|&(action, key, at)| {
+                    (
+                        action.to_string(),
+                        Keybind {
+                            key: key.to_string(),
+                            updated_at: at,
+                        },
+                    )
+                })
+                .collect(),
+            tutorial: Default::default(),
+        }
+    }
+
+    #[test]
+    fn values_are_last_write_wins_and_keybinds_merge_per_action() {
+        let mut server = settings(100, 0.5, &[("jump", "Space", 100), ("fire", "KeyF", 50)]);
+        // An older device rebound fire after the server's copy was written
+        server.merge(settings(
+            80,
+            0.9,
+            &[("jump", "KeyW", 20), ("fire", "KeyJ", 90)],
+        ));
+        assert_eq!(server.updated_at, 100);
+        assert_eq!(server.values["volume"], 0.5, "Older values lose");
+        assert_eq!(server.keybinds["jump"].key, "Space");
+        assert_eq!(server.keybinds["fire"].key, "KeyJ", "Newer binding wins");
+
+        server.merge(settings(200, 0.1, &[("dash", "ShiftLeft", 200)]));
+        assert_eq!(server.values["volume"], 0.1);
+        assert_eq!(
+            server.keybinds.len(),
+            3,
+            "Bindings missing on one side are kept"
+        );
+
+        let before = server.clone();
+        server.merge(before.clone());
+        assert_eq!(server, before, "Merging is idempotent");
+    }
+
+    #[test]
+    fn validate_rejects_oversized_uploads() {
+        assert!(settings(1, 0.5, &[("jump", "Space", 1)]).validate().is_ok());
+        let mut big = SyncedSettings::default();
+        big.values
+            .insert("blob".to_string(), serde_json::json!("x".repeat(2000)));
+        assert!(big.validate().is_err());
+        let mut unnamed = SyncedSettings::default();
+        unnamed.values.insert(String::new(), serde_json::json!(1));
+        assert!(unnamed.validate().is_err());
+    }
+}
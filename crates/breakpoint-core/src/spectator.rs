@@ -0,0 +1,115 @@
+//! Spectator cues: where a game suggests spectators look. Games that have
+//! them build a [`SpectatorCues`] each tick and send it only to spectators,
+//! through `BreakpointGame::spectator_view_state`; players never get it.
+
+use serde::{Deserialize, Serialize};
+
+use crate::game_trait::PlayerId;
+
+/// Shortest streak worth calling out.
+pub const STREAK_HIGHLIGHT: u32 = 3;
+
+/// What a spectator's camera should follow and who to call out.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpectatorCues {
+    /// The player where the action is, for the camera to follow. `None`
+    /// keeps the game's overview.
+    pub focus: Option<PlayerId>,
+    /// The leader first, then streaks in player-ID order.
+    pub highlights: Vec<PlayerHighlight>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerHighlight {
+    pub player_id: PlayerId,
+    pub kind: HighlightKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HighlightKind {
+    /// Alone at the top of the scores.
+    Leader,
+    /// Tags or kills in a row without being caught.
+    Streak { count: u32 },
+}
+
+impl SpectatorCues {
+    /// Cues highlighting the sole leader in `scores`, if anyone leads with
+    /// a positive score, and everyone whose streak is at least
+    /// [`STREAK_HIGHLIGHT`].
+    pub fn new(
+        focus: Option<PlayerId>,
+        scores: impl IntoIterator<Item = (PlayerId, i64)>,
+        streaks: impl IntoIterator<Item = (PlayerId, u32)>,
+    ) -> Self {
+        let mut highlights = Vec::new();
+        let mut top: Option<(PlayerId, i64)> = None;
+        let mut tied = false;
+        for (player_id, score) in scores {
+            match top {
+                Some((_, best)) if score < best => {},
+                Some((_, best)) if score == best => tied = true,
+                _ => {
+                    top = Some((player_id, score));
+                    tied = false;
+                },
+            }
+        }
+        if let Some((player_id, score)) = top
+            && score > 0
+            && !tied
+        {
+            highlights.push(PlayerHighlight {
+                player_id,
+                kind: HighlightKind::Leader,
+            });
+        }
+        let mut streaks: Vec<(PlayerId, u32)> = streaks
+            .into_iter()
+            .filter(|&(_, count)| count >= STREAK_HIGHLIGHT)
+            .collect();
+        streaks.sort_unstable();
+        highlights.extend(
+            streaks
+                .into_iter()
+                .map(|(player_id, count)| PlayerHighlight {
+                    player_id,
+                    kind: HighlightKind::Streak { count },
+                }),
+        );
+        Self { focus, highlights }
+    }
+
+    /// How `player_id` is highlighted, if at all. A leader on a streak is
+    /// shown as the leader.
+    pub fn highlight_of(&self, player_id: PlayerId) -> Option<HighlightKind> {
+        self.highlights
+            .iter()
+            .find(|h| h.player_id == player_id)
+            .map(|h| h.kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_a_sole_leader_and_long_streaks_are_highlighted() {
+        let cues = SpectatorCues::new(Some(2), [(1, 3), (2, 5), (3, 0)], [(3, 4), (2, 3), (1, 2)]);
+        assert_eq!(cues.focus, Some(2));
+        assert_eq!(cues.highlight_of(2), Some(HighlightKind::Leader));
+        assert_eq!(
+            cues.highlight_of(3),
+            Some(HighlightKind::Streak { count: 4 })
+        );
+        assert_eq!(cues.highlight_of(1), None);
+        assert_eq!(cues.highlights.len(), 3);
+
+        // A tie at the top, or nobody scoring yet, has no leader
+        let tied = SpectatorCues::new(None, [(1, 5), (2, 5)], []);
+        assert!(tied.highlights.is_empty());
+        let scoreless = SpectatorCues::new(None, [(1, 0), (2, 0)], []);
+        assert!(scoreless.highlights.is_empty());
+    }
+}
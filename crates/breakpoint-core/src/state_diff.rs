@@ -0,0 +1,265 @@
+//! Field-by-field diffs of two serialized states of the same game, for
+//! tracking down host/client desyncs and `apply_state` migration bugs.
+//! States are compared as the named trees `BreakpointGame::inspect_state`
+//! builds, so a diff reads `balls.2.position.x: 4.1 -> 4.35 (+0.25)`
+//! rather than as array offsets into MessagePack.
+
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::game_trait::BreakpointGame;
+
+/// Longest value shown in a diff line before it is cut short.
+const MAX_VALUE_CHARS: usize = 48;
+
+/// How one field differs between the two states.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// Only in the second state (e.g. a player who joined).
+    Added(Value),
+    /// Only in the first state.
+    Removed(Value),
+    /// A number changed, such as a position or a timer.
+    Number { from: f64, to: f64 },
+    /// Any other change, including a change of type.
+    Value { from: Value, to: Value },
+}
+
+/// One differing field, addressed by its path from the state root.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDiff {
+    /// Dot-separated field names and map keys; array elements as `[i]`.
+    pub path: String,
+    pub change: Change,
+}
+
+/// Every field that differs between two states, in field order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StateDiff {
+    pub fields: Vec<FieldDiff>,
+}
+
+impl StateDiff {
+    /// Compare two state trees. Numbers within `tolerance` of each other
+    /// count as equal, to hide float noise.
+    pub fn compare(before: &Value, after: &Value, tolerance: f64) -> Self {
+        let mut diff = Self::default();
+        diff.walk(&mut String::new(), before, after, tolerance);
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    fn walk(&mut self, path: &mut String, before: &Value, after: &Value, tolerance: f64) {
+        match (before, after) {
+            (Value::Object(a), Value::Object(b)) => {
+                for (key, a_val) in a {
+                    let len = path.len();
+                    push_key(path, key);
+                    match b.get(key) {
+                        Some(b_val) => self.walk(path, a_val, b_val, tolerance),
+                        None => self.push(path, Change::Removed(a_val.clone())),
+                    }
+                    path.truncate(len);
+                }
+                for (key, b_val) in b.iter().filter(|(key, _)| !a.contains_key(*key)) {
+                    let len = path.len();
+                    push_key(path, key);
+                    self.push(path, Change::Added(b_val.clone()));
+                    path.truncate(len);
+                }
+            },
+            (Value::Array(a), Value::Array(b)) => {
+                for i in 0..a.len().max(b.len()) {
+                    let len = path.len();
+                    path.push_str(&format!("[{i}]"));
+                    match (a.get(i), b.get(i)) {
+                        (Some(a_val), Some(b_val)) => self.walk(path, a_val, b_val, tolerance),
+                        (Some(a_val), None) => self.push(path, Change::Removed(a_val.clone())),
+                        (None, Some(b_val)) => self.push(path, Change::Added(b_val.clone())),
+                        (None, None) => {},
+                    }
+                    path.truncate(len);
+                }
+            },
+            (Value::Number(a), Value::Number(b)) => {
+                if let (Some(from), Some(to)) = (a.as_f64(), b.as_f64())
+                    && (to - from).abs() > tolerance
+                {
+                    self.push(path, Change::Number { from, to });
+                }
+            },
+            _ if before != after => self.push(
+                path,
+                Change::Value {
+                    from: before.clone(),
+                    to: after.clone(),
+                },
+            ),
+            _ => {},
+        }
+    }
+
+    fn push(&mut self, path: &str, change: Change) {
+        self.fields.push(FieldDiff {
+            path: path.to_string(),
+            change,
+        });
+    }
+}
+
+fn push_key(path: &mut String, key: &str) {
+    if !path.is_empty() {
+        path.push('.');
+    }
+    path.push_str(key);
+}
+
+/// Diff two serialized states of `game`'s kind. `None` if the game can't
+/// be inspected or either state fails to decode.
+pub fn diff_states(
+    game: &dyn BreakpointGame,
+    before: &[u8],
+    after: &[u8],
+    tolerance: f64,
+) -> Option<StateDiff> {
+    let before = game.inspect_state(before)?;
+    let after = game.inspect_state(after)?;
+    Some(StateDiff::compare(&before, &after, tolerance))
+}
+
+/// A value as compact JSON, cut short if long.
+fn short(value: &Value) -> String {
+    let text = value.to_string();
+    if text.chars().count() <= MAX_VALUE_CHARS {
+        return text;
+    }
+    let cut: String = text.chars().take(MAX_VALUE_CHARS).collect();
+    format!("{cut}...")
+}
+
+/// A number to four decimals without trailing zeros. States hold `f32`s,
+/// which widen to long `f64` expansions (`0.1` as `0.10000000149011612`).
+fn number(v: f64) -> String {
+    let text = format!("{v:.4}");
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+impl fmt::Display for FieldDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "(root)"
+        } else {
+            &self.path
+        };
+        match &self.change {
+            Change::Added(value) => write!(f, "+ {path} = {}", short(value)),
+            Change::Removed(value) => write!(f, "- {path} (was {})", short(value)),
+            Change::Number { from, to } => {
+                let delta = to - from;
+                let sign = if delta < 0.0 { "-" } else { "+" };
+                write!(
+                    f,
+                    "{path}: {} -> {} ({sign}{})",
+                    number(*from),
+                    number(*to),
+                    number(delta.abs())
+                )
+            },
+            Change::Value { from, to } => {
+                write!(f, "{path}: {} -> {}", short(from), short(to))
+            },
+        }
+    }
+}
+
+impl fmt::Display for StateDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "states are identical");
+        }
+        for field in &self.fields {
+            writeln!(f, "{field}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::lobby_toy::{TargetClick, TargetPractice};
+    use crate::test_helpers::{default_config, make_players, run_game_ticks};
+
+    #[test]
+    fn players_joining_and_leaving_are_listed() {
+        let before = json!({"players": {"1": {"x": 1.0}, "2": {"x": 2.0}}});
+        let after = json!({"players": {"2": {"x": 2.0}, "3": {"x": 0.0}}});
+        let diff = StateDiff::compare(&before, &after, 0.0);
+        let lines: Vec<String> = diff.fields.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            lines,
+            ["- players.1 (was {\"x\":1.0})", "+ players.3 = {\"x\":0.0}"]
+        );
+    }
+
+    #[test]
+    fn numbers_show_their_delta_unless_within_tolerance() {
+        let before = json!({"ball": {"pos": [1.0, 0.5]}, "timer": 30.0, "phase": "play"});
+        let widened = f64::from(29.5f32 - 0.1f32);
+        let after = json!({"ball": {"pos": [1.25, 0.5001]}, "timer": widened, "phase": "done"});
+        let diff = StateDiff::compare(&before, &after, 0.001);
+        let lines: Vec<String> = diff.fields.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            lines,
+            [
+                "ball.pos[0]: 1 -> 1.25 (+0.25)",
+                "phase: \"play\" -> \"done\"",
+                "timer: 30 -> 29.4 (-0.6)",
+            ]
+        );
+        assert!(StateDiff::compare(&before, &before, 0.0).is_empty());
+    }
+
+    #[test]
+    fn long_values_are_cut_short() {
+        let diff = StateDiff::compare(&json!({}), &json!({"walls": vec![0; 100]}), 0.0);
+        let line = diff.fields[0].to_string();
+        assert!(line.ends_with("..."), "{line}");
+        assert!(line.len() < 70, "{line}");
+    }
+
+    #[test]
+    fn game_states_diff_by_field_name() {
+        let mut game = TargetPractice::new();
+        game.init(&make_players(2), &default_config(0));
+        let before = game.serialize_state();
+        let hit = rmp_serde::to_vec(&TargetClick { x: 0.5, y: 0.5 }).unwrap();
+        game.apply_input(2, &hit);
+        run_game_ticks(&mut game, 1, 0.0);
+        let after = game.serialize_state();
+
+        let diff = diff_states(&game, &before, &after, 0.0).unwrap();
+        let paths: Vec<&str> = diff.fields.iter().map(|f| f.path.as_str()).collect();
+        for path in ["hits.2", "last_hit_by", "total_hits"] {
+            assert!(paths.contains(&path), "{path} missing from:\n{diff}");
+        }
+        assert!(!paths.contains(&"hits.1"));
+        assert!(
+            diff_states(&game, &before, &before, 0.0)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(diff_states(&game, b"junk", &after, 0.0).is_none());
+    }
+}
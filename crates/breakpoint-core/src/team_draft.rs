@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+
+use crate::game_trait::PlayerId;
+
+/// Lobby setting holding an explicit team for each player, as a JSON
+/// object from player id to team index. Team games read it in `init`.
+pub const TEAMS_SETTING: &str = "teams";
+
+/// A captain draft: each team's captain takes turns picking from the
+/// undrafted players until none are left.
+#[derive(Debug, Clone)]
+pub struct TeamDraft {
+    captains: Vec<PlayerId>,
+    pool: Vec<PlayerId>,
+    picks: Vec<(PlayerId, u8)>,
+    turn: usize,
+}
+
+impl TeamDraft {
+    /// Captains go on their own teams, in order; `pool` is offered in order
+    /// and the first of it is what an auto-pick takes.
+    pub fn new(captains: Vec<PlayerId>, pool: Vec<PlayerId>) -> Self {
+        let picks = captains
+            .iter()
+            .enumerate()
+            .map(|(team, &id)| (id, team as u8))
+            .collect();
+        Self {
+            captains,
+            pool,
+            picks,
+            turn: 0,
+        }
+    }
+
+    pub fn captains(&self) -> &[PlayerId] {
+        &self.captains
+    }
+
+    /// Players not drafted yet.
+    pub fn pool(&self) -> &[PlayerId] {
+        &self.pool
+    }
+
+    /// Drafted players and their teams, captains first, then in pick order.
+    pub fn picks(&self) -> &[(PlayerId, u8)] {
+        &self.picks
+    }
+
+    /// The team picking now; `None` once the pool is empty.
+    pub fn turn(&self) -> Option<u8> {
+        (!self.pool.is_empty() && !self.captains.is_empty())
+            .then(|| (self.turn % self.captains.len()) as u8)
+    }
+
+    /// The captain picking now.
+    pub fn captain(&self) -> Option<PlayerId> {
+        self.turn().map(|team| self.captains[team as usize])
+    }
+
+    /// `captain` drafts `player` onto their team. Returns false when it is
+    /// not their turn or `player` is not in the pool.
+    pub fn pick(&mut self, captain: PlayerId, player: PlayerId) -> bool {
+        if self.captain() != Some(captain) || !self.pool.contains(&player) {
+            return false;
+        }
+        self.draft(player);
+        true
+    }
+
+    /// Draft the first player in the pool for the team whose captain ran
+    /// out of time. Returns who was picked.
+    pub fn auto_pick(&mut self) -> Option<PlayerId> {
+        self.turn()?;
+        let player = self.pool[0];
+        self.draft(player);
+        Some(player)
+    }
+
+    /// Offer a player who arrived during the draft.
+    pub fn add(&mut self, player: PlayerId) {
+        if !self.pool.contains(&player) && !self.picks.iter().any(|&(id, _)| id == player) {
+            self.pool.push(player);
+        }
+    }
+
+    /// Take a departed player out of the draft. A departed captain keeps
+    /// their turns, which the host fills with auto-picks.
+    pub fn withdraw(&mut self, player: PlayerId) {
+        self.pool.retain(|&id| id != player);
+        self.picks.retain(|&(id, _)| id != player);
+    }
+
+    /// Every drafted player's team.
+    pub fn teams(&self) -> HashMap<PlayerId, u8> {
+        self.picks.iter().copied().collect()
+    }
+
+    fn draft(&mut self, player: PlayerId) {
+        let team = (self.turn % self.captains.len()) as u8;
+        self.pool.retain(|&id| id != player);
+        self.picks.push((player, team));
+        self.turn += 1;
+    }
+}
+
+/// The [`TEAMS_SETTING`] value for `teams`.
+pub fn teams_setting(teams: &HashMap<PlayerId, u8>) -> serde_json::Value {
+    serde_json::Value::Object(
+        teams
+            .iter()
+            .map(|(id, team)| (id.to_string(), serde_json::json!(team)))
+            .collect(),
+    )
+}
+
+/// The explicit teams in lobby settings; entries that are not a player id
+/// and a team index are skipped.
+pub fn explicit_teams(custom: &HashMap<String, serde_json::Value>) -> HashMap<PlayerId, u8> {
+    let Some(serde_json::Value::Object(map)) = custom.get(TEAMS_SETTING) else {
+        return HashMap::new();
+    };
+    map.iter()
+        .filter_map(|(id, team)| {
+            let team = u8::try_from(team.as_u64()?).ok()?;
+            Some((id.parse().ok()?, team))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn captains_alternate_until_the_pool_is_empty() {
+        let mut draft = TeamDraft::new(vec![1, 2], vec![3, 4, 5]);
+        assert_eq!(draft.turn(), Some(0));
+        assert!(!draft.pick(2, 3), "Not captain 2's turn");
+        assert!(!draft.pick(1, 9), "Not in the pool");
+        assert!(draft.pick(1, 4));
+        assert_eq!(draft.captain(), Some(2));
+        assert!(draft.pick(2, 3));
+        assert_eq!(draft.auto_pick(), Some(5));
+        assert_eq!(draft.turn(), None);
+        assert_eq!(draft.auto_pick(), None);
+        assert_eq!(draft.picks(), [(1, 0), (2, 1), (4, 0), (3, 1), (5, 0)]);
+    }
+
+    #[test]
+    fn late_arrivals_join_the_pool_and_leavers_drop_out() {
+        let mut draft = TeamDraft::new(vec![1, 2], vec![3]);
+        draft.add(4);
+        draft.add(5);
+        draft.add(1);
+        assert_eq!(draft.pool(), [3, 4, 5]);
+        draft.withdraw(5);
+        draft.withdraw(2);
+        assert!(draft.pick(1, 3));
+        // A departed captain still has a turn, filled by an auto-pick
+        assert_eq!(draft.captain(), Some(2));
+        assert_eq!(draft.auto_pick(), Some(4));
+        assert_eq!(draft.teams(), HashMap::from([(1, 0), (3, 0), (4, 1)]));
+    }
+
+    #[test]
+    fn teams_setting_roundtrips() {
+        let teams = HashMap::from([(7, 1), (12, 0)]);
+        let mut custom = HashMap::from([(TEAMS_SETTING.to_string(), teams_setting(&teams))]);
+        assert_eq!(explicit_teams(&custom), teams);
+
+        custom.insert(
+            TEAMS_SETTING.to_string(),
+            serde_json::json!({"3": 1, "x": 0, "4": 300}),
+        );
+        assert_eq!(explicit_teams(&custom), HashMap::from([(3, 1)]));
+    }
+}
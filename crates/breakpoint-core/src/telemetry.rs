@@ -0,0 +1,172 @@
+//! Log output and optional OpenTelemetry export for the server binaries.
+//!
+//! With the `otel` feature, setting `OTEL_EXPORTER_OTLP_ENDPOINT` exports
+//! spans and metrics over OTLP/HTTP. The standard `OTEL_*` variables
+//! (sampler, headers, service name) are honored; `BREAKPOINT_OTEL_FILTER`
+//! selects which spans are exported (default [`DEFAULT_OTEL_FILTER`]).
+
+/// Target for metric events. Emit them at `trace` level with the
+/// `tracing-opentelemetry` field prefixes (`monotonic_counter.`,
+/// `histogram.`), after at least one attribute field: a dotted name
+/// directly after `target:` is ambiguous to the macro.
+/// Without an exporter they are filtered out like any other trace event.
+pub const METRICS_TARGET: &str = "breakpoint::metrics";
+
+/// Spans exported when `BREAKPOINT_OTEL_FILTER` is unset. Per-message and
+/// per-tick spans are `debug`, so they are included for our crates only.
+pub const DEFAULT_OTEL_FILTER: &str =
+    "info,breakpoint_server=debug,breakpoint_relay=debug,breakpoint_core=debug";
+
+/// Keeps exporters alive; flushes pending spans and metrics on drop.
+#[cfg(feature = "telemetry")]
+#[must_use = "telemetry is flushed when the guard is dropped"]
+pub struct TelemetryGuard {
+    #[cfg(feature = "otel")]
+    otel: Option<otel::Providers>,
+}
+
+#[cfg(feature = "telemetry")]
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "otel")]
+        if let Some(ref providers) = self.otel {
+            providers.shutdown();
+        }
+    }
+}
+
+/// Install the global subscriber: `RUST_LOG`-filtered logs (JSON when
+/// `json_logs`), plus OTLP export when built with `otel` and configured.
+#[cfg(feature = "telemetry")]
+pub fn init(service_name: &'static str, json_logs: bool) -> TelemetryGuard {
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
+    use tracing_subscriber::{EnvFilter, Layer, Registry};
+
+    let fmt_layer = if json_logs {
+        tracing_subscriber::fmt::layer().json().boxed()
+    } else {
+        tracing_subscriber::fmt::layer().boxed()
+    };
+    #[cfg_attr(not(feature = "otel"), allow(unused_mut))]
+    let mut layers: Vec<Box<dyn Layer<Registry> + Send + Sync>> =
+        vec![fmt_layer.with_filter(EnvFilter::from_default_env()).boxed()];
+
+    #[cfg(feature = "otel")]
+    let (otel, otel_error) = match otel::layers(service_name) {
+        Ok(Some((otel_layers, providers))) => {
+            layers.extend(otel_layers);
+            (Some(providers), None)
+        },
+        Ok(None) => (None, None),
+        Err(e) => (None, Some(e)),
+    };
+    #[cfg(not(feature = "otel"))]
+    let _ = service_name;
+
+    tracing_subscriber::registry().with(layers).init();
+
+    #[cfg(feature = "otel")]
+    {
+        if let Some(e) = otel_error {
+            tracing::warn!(error = %e, "OpenTelemetry export disabled");
+        } else if otel.is_some() {
+            tracing::info!(service = service_name, "OpenTelemetry export enabled");
+        }
+        TelemetryGuard { otel }
+    }
+    #[cfg(not(feature = "otel"))]
+    TelemetryGuard {}
+}
+
+#[cfg(feature = "otel")]
+mod otel {
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_otlp::{MetricExporter, SpanExporter};
+    use opentelemetry_sdk::Resource;
+    use opentelemetry_sdk::metrics::SdkMeterProvider;
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use tracing::Level;
+    use tracing_subscriber::filter::{EnvFilter, Targets};
+    use tracing_subscriber::{Layer, Registry};
+
+    use super::{DEFAULT_OTEL_FILTER, METRICS_TARGET};
+
+    type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+    pub struct Providers {
+        tracer: SdkTracerProvider,
+        meter: SdkMeterProvider,
+    }
+
+    impl Providers {
+        pub fn shutdown(&self) {
+            // The subscriber may already be gone at exit; report on stderr
+            if let Err(e) = self.tracer.shutdown() {
+                eprintln!("Failed to flush OpenTelemetry spans: {e}");
+            }
+            if let Err(e) = self.meter.shutdown() {
+                eprintln!("Failed to flush OpenTelemetry metrics: {e}");
+            }
+        }
+    }
+
+    /// Export is on when an OTLP endpoint is configured.
+    fn endpoint_configured() -> bool {
+        [
+            "OTEL_EXPORTER_OTLP_ENDPOINT",
+            "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+        ]
+        .iter()
+        .any(|var| std::env::var(var).is_ok_and(|v| !v.trim().is_empty()))
+    }
+
+    /// Span and metric layers plus their providers, or `None` when no
+    /// endpoint is configured.
+    pub fn layers(
+        service_name: &'static str,
+    ) -> Result<Option<(Vec<BoxedLayer>, Providers)>, String> {
+        if !endpoint_configured() {
+            return Ok(None);
+        }
+
+        let resource = Resource::builder()
+            .with_service_name(
+                std::env::var("OTEL_SERVICE_NAME").unwrap_or_else(|_| service_name.to_string()),
+            )
+            .build();
+
+        let span_exporter = SpanExporter::builder()
+            .with_http()
+            .build()
+            .map_err(|e| format!("span exporter: {e}"))?;
+        let tracer = SdkTracerProvider::builder()
+            .with_batch_exporter(span_exporter)
+            .with_resource(resource.clone())
+            .build();
+
+        let metric_exporter = MetricExporter::builder()
+            .with_http()
+            .build()
+            .map_err(|e| format!("metric exporter: {e}"))?;
+        let meter = SdkMeterProvider::builder()
+            .with_periodic_exporter(metric_exporter)
+            .with_resource(resource)
+            .build();
+
+        let span_filter = EnvFilter::try_from_env("BREAKPOINT_OTEL_FILTER")
+            .unwrap_or_else(|_| EnvFilter::new(DEFAULT_OTEL_FILTER));
+        let trace_layer = tracing_opentelemetry::layer()
+            .with_tracer(tracer.tracer(service_name))
+            .with_filter(span_filter)
+            .boxed();
+        let metrics_layer = tracing_opentelemetry::MetricsLayer::new(meter.clone())
+            .with_filter(Targets::new().with_target(METRICS_TARGET, Level::TRACE))
+            .boxed();
+
+        Ok(Some((
+            vec![trace_layer, metrics_layer],
+            Providers { tracer, meter },
+        )))
+    }
+}
@@ -1,7 +1,21 @@
 /// Returns a simple ISO 8601 timestamp (Unix epoch seconds with Z suffix).
 pub fn timestamp_now() -> String {
-    let dur = std::time::SystemTime::now()
+    format!("{}Z", unix_now())
+}
+
+/// Current time as Unix epoch milliseconds. Native only: the system clock
+/// panics on `wasm32-unknown-unknown`.
+pub fn unix_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// Current time as Unix epoch seconds.
+pub fn unix_now() -> u64 {
+    std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default();
-    format!("{}Z", dur.as_secs())
+        .unwrap_or_default()
+        .as_secs()
 }
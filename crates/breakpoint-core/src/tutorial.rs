@@ -0,0 +1,159 @@
+//! Onboarding tutorials: a short list of objectives per game that a new
+//! player works through in a solo room. Games list their objectives and
+//! check them against their own state with
+//! `BreakpointGame::tutorial_objectives_met`; the server records what each
+//! player has done in their synced settings and sends them a checklist.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::game_settings::CustomSettings;
+use crate::i18n::TextKey;
+
+/// Lobby setting the server sets on a tutorial session, so it checks
+/// objectives while the round is played.
+pub const TUTORIAL_SETTING: &str = "tutorial";
+
+/// Most objective IDs kept per game, so uploads can't grow the record.
+pub const MAX_TUTORIAL_OBJECTIVES: usize = 16;
+
+/// Whether the lobby settings mark this session as a tutorial.
+pub fn tutorial_requested(custom: &CustomSettings) -> bool {
+    custom
+        .get(TUTORIAL_SETTING)
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// One thing a tutorial asks the player to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TutorialObjective {
+    /// Stable ID, stored in players' progress. Never rename one.
+    pub id: &'static str,
+    pub text: TextKey,
+}
+
+/// The tutorial objectives a player has completed, by game. Only grows:
+/// merging two devices' records keeps everything either has done.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TutorialProgress {
+    #[serde(default)]
+    pub completed: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl TutorialProgress {
+    /// Mark `objective` done in `game`. Returns whether it was new.
+    pub fn complete(&mut self, game: &str, objective: &str) -> bool {
+        self.completed
+            .entry(game.to_string())
+            .or_default()
+            .insert(objective.to_string())
+    }
+
+    pub fn is_done(&self, game: &str, objective: &str) -> bool {
+        self.completed
+            .get(game)
+            .is_some_and(|done| done.contains(objective))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.completed.is_empty()
+    }
+
+    pub fn merge(&mut self, other: TutorialProgress) {
+        for (game, done) in other.completed {
+            self.completed.entry(game).or_default().extend(done);
+        }
+    }
+
+    /// `game`'s objectives, each marked with whether it's done.
+    pub fn checklist(&self, game: &str, objectives: &[TutorialObjective]) -> TutorialChecklist {
+        TutorialChecklist {
+            game: game.to_string(),
+            steps: objectives
+                .iter()
+                .map(|o| TutorialStep {
+                    id: o.id.to_string(),
+                    text: o.text,
+                    done: self.is_done(game, o.id),
+                })
+                .collect(),
+        }
+    }
+
+    /// Check the size limits the server enforces on uploads.
+    pub fn validate(&self, max_games: usize, max_key_len: usize) -> Result<(), String> {
+        if self.completed.len() > max_games {
+            return Err(format!("tutorial progress exceeds {max_games} games"));
+        }
+        for (game, done) in &self.completed {
+            if done.len() > MAX_TUTORIAL_OBJECTIVES {
+                return Err(format!(
+                    "tutorial progress for {game} exceeds {MAX_TUTORIAL_OBJECTIVES} objectives"
+                ));
+            }
+            if game.len() > max_key_len || done.iter().any(|id| id.len() > max_key_len) {
+                return Err(format!(
+                    "tutorial games and objectives must be at most {max_key_len} bytes"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A game's tutorial as shown in the lobby checklist.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TutorialChecklist {
+    /// Wire-format game ID, as in `GameStartMsg`.
+    pub game: String,
+    pub steps: Vec<TutorialStep>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TutorialStep {
+    pub id: String,
+    pub text: TextKey,
+    pub done: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_only_grows_and_fills_in_checklists() {
+        let objectives = [
+            TutorialObjective {
+                id: "tag",
+                text: TextKey::TutorialLaserTagTag,
+            },
+            TutorialObjective {
+                id: "powerup",
+                text: TextKey::TutorialLaserTagPowerUp,
+            },
+        ];
+        let mut phone = TutorialProgress::default();
+        assert!(phone.complete("laser-tag", "tag"));
+        assert!(!phone.complete("laser-tag", "tag"), "Already done");
+        let mut laptop = TutorialProgress::default();
+        laptop.complete("laser-tag", "powerup");
+        laptop.complete("tron", "survive");
+
+        laptop.merge(phone.clone());
+        assert!(laptop.is_done("laser-tag", "tag"));
+        assert!(laptop.is_done("laser-tag", "powerup"));
+        assert!(laptop.is_done("tron", "survive"));
+
+        let checklist = phone.checklist("laser-tag", &objectives);
+        assert_eq!(checklist.game, "laser-tag");
+        let done: Vec<bool> = checklist.steps.iter().map(|s| s.done).collect();
+        assert_eq!(done, [true, false]);
+
+        assert!(laptop.validate(4, 64).is_ok());
+        assert!(laptop.validate(1, 64).is_err());
+        phone.complete("laser-tag", &"x".repeat(65));
+        assert!(phone.validate(4, 64).is_err());
+    }
+}
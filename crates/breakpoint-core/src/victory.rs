@@ -0,0 +1,119 @@
+//! Round winners for the announcer banner. Each game says who won a round
+//! and what the win came down to as a [`VictorySummary`]; the message is a
+//! [`LocalizedText`] whose `{winners}` parameter the client fills in with
+//! the winners' names, so games never build display strings.
+
+use serde::{Deserialize, Serialize};
+
+use crate::game_trait::{PlayerId, PlayerScore};
+use crate::i18n::{LocalizedText, TextKey};
+
+/// Who won a round and why.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VictorySummary {
+    /// Winners in player-ID order; more than one for a shared win or a
+    /// draw, none when nobody won.
+    pub winner_ids: Vec<PlayerId>,
+    /// The number the win came down to (points, strokes, tags), also
+    /// passed to `message` as `{stat}`.
+    pub key_stat: Option<i32>,
+    /// Announcer line, without the `winners` parameter.
+    pub message: LocalizedText,
+}
+
+impl VictorySummary {
+    pub fn new(key: TextKey, mut winner_ids: Vec<PlayerId>) -> Self {
+        winner_ids.sort_unstable();
+        winner_ids.dedup();
+        Self {
+            winner_ids,
+            key_stat: None,
+            message: LocalizedText::new(key),
+        }
+    }
+
+    /// A round nobody won.
+    pub fn no_winner() -> Self {
+        Self::new(TextKey::VictoryNoWinner, Vec::new())
+    }
+
+    pub fn with_stat(mut self, stat: i32) -> Self {
+        self.key_stat = Some(stat);
+        self.message = self.message.with("stat", stat);
+        self
+    }
+
+    /// The players still in the round with the best `stat`, lowest first
+    /// when `lower_wins`, announced with `key` and that stat. No winner if
+    /// nobody has a stat.
+    pub fn best_by(
+        key: TextKey,
+        stats: impl IntoIterator<Item = (PlayerId, i32)>,
+        lower_wins: bool,
+    ) -> Self {
+        let stats: Vec<(PlayerId, i32)> = stats.into_iter().collect();
+        let best = if lower_wins {
+            stats.iter().map(|&(_, s)| s).min()
+        } else {
+            stats.iter().map(|&(_, s)| s).max()
+        };
+        let Some(best) = best else {
+            return Self::no_winner();
+        };
+        let winners = stats
+            .iter()
+            .filter(|&&(_, s)| s == best)
+            .map(|&(id, _)| id)
+            .collect();
+        Self::new(key, winners).with_stat(best)
+    }
+
+    /// The top scorers among players who finished the round, for games
+    /// without a more telling stat. Nobody wins a round nobody scored in.
+    pub fn from_scores(scores: &[PlayerScore]) -> Self {
+        Self::best_by(
+            TextKey::VictoryTopScore,
+            scores
+                .iter()
+                .filter(|s| !s.disconnected && s.score > 0)
+                .map(|s| (s.player_id, s.score)),
+            false,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(player_id: PlayerId, score: i32, disconnected: bool) -> PlayerScore {
+        PlayerScore {
+            player_id,
+            score,
+            disconnected,
+        }
+    }
+
+    #[test]
+    fn top_scorers_win_and_ties_share() {
+        let summary = VictorySummary::from_scores(&[
+            score(3, 10, false),
+            score(1, 4, false),
+            score(2, 10, false),
+            score(4, 50, true),
+        ]);
+        assert_eq!(summary.winner_ids, vec![2, 3], "Leavers can't win");
+        assert_eq!(summary.key_stat, Some(10));
+        assert_eq!(summary.message.key, TextKey::VictoryTopScore);
+        assert_eq!(summary.message.params["stat"], "10");
+
+        let strokes = VictorySummary::best_by(TextKey::VictoryGolfStrokes, [(1, 5), (2, 3)], true);
+        assert_eq!(strokes.winner_ids, vec![2]);
+        assert_eq!(strokes.key_stat, Some(3));
+
+        assert_eq!(
+            VictorySummary::from_scores(&[score(1, 0, false), score(2, 0, false)]),
+            VictorySummary::no_winner()
+        );
+    }
+}
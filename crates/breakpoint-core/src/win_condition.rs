@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::game_trait::{PlayerId, PlayerScore};
+
+/// Hard limit on rounds in one match, so a rule that is never met (or a
+/// tie that never breaks) can't keep a room busy forever.
+pub const MAX_MATCH_ROUNDS: u8 = 99;
+
+/// One rule that can end a match. Rules are checked after every round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum WinRule {
+    /// A player's total score reaches `points`.
+    FirstTo { points: i32 },
+    /// A player has won a majority of `rounds` rounds, or all of them have
+    /// been played. A round is won by the unique top scorer.
+    BestOf { rounds: u8 },
+    /// At least `secs` seconds have passed since the match started. The
+    /// round in progress is always finished.
+    TimeCapped { secs: u32 },
+}
+
+/// How a room decides that a match is over. The match ends after the first
+/// round in which any rule is met; with no rules it runs for the game's own
+/// round count. The winner is always the player with the highest total.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WinCondition {
+    #[serde(default)]
+    pub rules: Vec<WinRule>,
+    /// When the match would end with the lead tied, keep playing single
+    /// rounds until the tie breaks.
+    #[serde(default)]
+    pub sudden_death: bool,
+}
+
+/// Running totals for one match, checked against a [`WinCondition`].
+#[derive(Debug, Clone)]
+pub struct MatchTracker {
+    condition: WinCondition,
+    round_count: u8,
+    rounds_played: u8,
+    totals: HashMap<PlayerId, i32>,
+    round_wins: HashMap<PlayerId, u8>,
+    sudden_death: bool,
+}
+
+impl MatchTracker {
+    /// `round_count` is the game's planned number of rounds, used when the
+    /// condition has no rules.
+    pub fn new(condition: WinCondition, round_count: u8) -> Self {
+        Self {
+            condition,
+            round_count,
+            rounds_played: 0,
+            totals: HashMap::new(),
+            round_wins: HashMap::new(),
+            sudden_death: false,
+        }
+    }
+
+    /// Add a finished round's results.
+    pub fn record_round(&mut self, results: &[PlayerScore]) {
+        self.rounds_played = self.rounds_played.saturating_add(1);
+        for s in results {
+            *self.totals.entry(s.player_id).or_insert(0) += s.score;
+        }
+        if let Some(winner) = unique_leader(results.iter().map(|s| (s.player_id, s.score))) {
+            *self.round_wins.entry(winner).or_insert(0) += 1;
+        }
+    }
+
+    /// Total score per player across all recorded rounds.
+    pub fn totals(&self) -> &HashMap<PlayerId, i32> {
+        &self.totals
+    }
+
+    pub fn rounds_played(&self) -> u8 {
+        self.rounds_played
+    }
+
+    /// Whether the match is being extended to break a tied lead.
+    pub fn in_sudden_death(&self) -> bool {
+        self.sudden_death
+    }
+
+    /// Whether the match is over after the rounds recorded so far.
+    /// `elapsed` is the time since the match started.
+    pub fn is_over(&mut self, elapsed: Duration) -> bool {
+        if self.rounds_played >= MAX_MATCH_ROUNDS {
+            return true;
+        }
+        let leader = unique_leader(self.totals.iter().map(|(&pid, &score)| (pid, score)));
+        if self.sudden_death {
+            return leader.is_some();
+        }
+        if !self.rule_met(elapsed) {
+            return false;
+        }
+        if self.condition.sudden_death && leader.is_none() && self.totals.len() > 1 {
+            self.sudden_death = true;
+            return false;
+        }
+        true
+    }
+
+    fn rule_met(&self, elapsed: Duration) -> bool {
+        if self.condition.rules.is_empty() {
+            return self.rounds_played >= self.round_count;
+        }
+        self.condition.rules.iter().any(|rule| match *rule {
+            WinRule::FirstTo { points } => self.totals.values().any(|&s| s >= points),
+            WinRule::BestOf { rounds } => {
+                self.rounds_played >= rounds
+                    || self
+                        .round_wins
+                        .values()
+                        .any(|&w| w as u16 * 2 > rounds as u16)
+            },
+            WinRule::TimeCapped { secs } => elapsed >= Duration::from_secs(secs.into()),
+        })
+    }
+}
+
+/// The player with the strictly highest score, if there is one.
+fn unique_leader(scores: impl Iterator<Item = (PlayerId, i32)>) -> Option<PlayerId> {
+    let mut best: Option<(PlayerId, i32)> = None;
+    let mut tied = false;
+    for (pid, score) in scores {
+        match best {
+            Some((_, top)) if score < top => {},
+            Some((_, top)) if score == top => tied = true,
+            _ => {
+                best = Some((pid, score));
+                tied = false;
+            },
+        }
+    }
+    best.filter(|_| !tied).map(|(pid, _)| pid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round(scores: &[(PlayerId, i32)]) -> Vec<PlayerScore> {
+        scores
+            .iter()
+            .map(|&(player_id, score)| PlayerScore {
+                player_id,
+                score,
+                disconnected: false,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn no_rules_plays_the_game_round_count() {
+        let mut tracker = MatchTracker::new(WinCondition::default(), 2);
+        tracker.record_round(&round(&[(1, 5), (2, 3)]));
+        assert!(!tracker.is_over(Duration::ZERO));
+        tracker.record_round(&round(&[(1, 1), (2, 3)]));
+        assert!(tracker.is_over(Duration::ZERO));
+        assert_eq!(tracker.totals()[&1], 6);
+        assert_eq!(tracker.totals()[&2], 6);
+    }
+
+    #[test]
+    fn first_to_and_time_cap_compose() {
+        let condition = WinCondition {
+            rules: vec![
+                WinRule::FirstTo { points: 10 },
+                WinRule::TimeCapped { secs: 300 },
+            ],
+            sudden_death: false,
+        };
+        let mut tracker = MatchTracker::new(condition, 1);
+        tracker.record_round(&round(&[(1, 6), (2, 4)]));
+        assert!(!tracker.is_over(Duration::from_secs(60)));
+        assert!(tracker.is_over(Duration::from_secs(300)), "Time cap hit");
+
+        tracker.record_round(&round(&[(1, 4), (2, 0)]));
+        assert!(
+            tracker.is_over(Duration::from_secs(120)),
+            "Player 1 reached 10"
+        );
+    }
+
+    #[test]
+    fn best_of_ends_once_a_majority_is_clinched() {
+        let condition = WinCondition {
+            rules: vec![WinRule::BestOf { rounds: 5 }],
+            sudden_death: false,
+        };
+        let mut tracker = MatchTracker::new(condition, 1);
+        tracker.record_round(&round(&[(1, 3), (2, 1)]));
+        tracker.record_round(&round(&[(1, 2), (2, 2)])); // tied round, no winner
+        tracker.record_round(&round(&[(1, 3), (2, 1)]));
+        assert!(!tracker.is_over(Duration::ZERO));
+        tracker.record_round(&round(&[(1, 3), (2, 1)]));
+        assert!(tracker.is_over(Duration::ZERO), "3 of 5 clinches");
+        assert_eq!(tracker.rounds_played(), 4);
+    }
+
+    #[test]
+    fn sudden_death_extends_a_tied_match() {
+        let condition = WinCondition {
+            rules: vec![WinRule::BestOf { rounds: 1 }],
+            sudden_death: true,
+        };
+        let mut tracker = MatchTracker::new(condition, 1);
+        tracker.record_round(&round(&[(1, 4), (2, 4), (3, 1)]));
+        assert!(!tracker.is_over(Duration::ZERO));
+        assert!(tracker.in_sudden_death());
+
+        tracker.record_round(&round(&[(1, 2), (2, 2), (3, 4)]));
+        assert!(!tracker.is_over(Duration::ZERO), "Lead still tied at 6");
+        tracker.record_round(&round(&[(1, 1), (2, 0), (3, 0)]));
+        assert!(tracker.is_over(Duration::ZERO));
+    }
+
+    #[test]
+    fn unmet_rules_stop_at_the_round_limit() {
+        let condition = WinCondition {
+            rules: vec![WinRule::FirstTo { points: i32::MAX }],
+            sudden_death: false,
+        };
+        let mut tracker = MatchTracker::new(condition, 1);
+        for _ in 0..MAX_MATCH_ROUNDS - 1 {
+            tracker.record_round(&round(&[(1, 1)]));
+            assert!(!tracker.is_over(Duration::ZERO));
+        }
+        tracker.record_round(&round(&[(1, 1)]));
+        assert!(tracker.is_over(Duration::ZERO));
+    }
+}
@@ -0,0 +1,159 @@
+//! Conformance vectors for the Breakpoint WebSocket protocol.
+//!
+//! `vectors/messages.json` holds one encoded sample of every
+//! `ClientMessage` and `ServerMessage` variant, plus a `Batch` frame. Each
+//! vector gives the frame as hex, the payload as a generic MessagePack
+//! decoder sees it (`payload`), and the same message with its field names
+//! as `breakpoint-core` defines it (`message`). Alternative clients (the
+//! browser UI, bots, stream widgets) can decode `hex` with their own code
+//! and compare against `payload`, or encode `payload` and compare against
+//! `hex`.
+//!
+//! The vectors are generated from [`samples`] and checked against
+//! `breakpoint-core` by this crate's tests, so a wire change fails CI until
+//! the file is regenerated with `BREAKPOINT_BLESS=1 cargo test -p
+//! breakpoint-protocol-tests`. [`reference`] is a small standalone decoder
+//! to port when writing a client in another language.
+//!
+//! `vectors/schemas.json` does the same for what travels inside messages as
+//! opaque bytes: every game's state, input, private state and effects, plus
+//! `GameEvent`s and replay files (see [`schemas`]). Each snapshot pairs the
+//! encoding with the named fields, and the tests check that the committed
+//! bytes still decode, so a field rename or reorder that would break older
+//! clients or stored replays needs an explicit snapshot update.
+
+pub mod reference;
+pub mod samples;
+pub mod schemas;
+
+use serde::{Deserialize, Serialize};
+
+use breakpoint_core::net::protocol::{
+    ProtocolError, encode_batch, encode_client_message, encode_server_message,
+};
+
+/// The checked-in vectors, as published.
+pub const VECTORS_JSON: &str = include_str!("../vectors/messages.json");
+
+/// The checked-in schema snapshots.
+pub const SCHEMAS_JSON: &str = include_str!("../vectors/schemas.json");
+
+/// Who sends a vector's frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    ClientToServer,
+    ServerToClient,
+}
+
+/// One encoded sample frame and what it decodes to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Vector {
+    /// Unique name, `<variant>` or `<variant>_<case>`.
+    pub name: String,
+    pub direction: Direction,
+    /// The frame's first byte.
+    pub type_byte: u8,
+    /// The whole frame, type byte included, as lowercase hex.
+    pub hex: String,
+    /// The frame as [`reference::decode_frame`] returns it.
+    pub payload: serde_json::Value,
+    /// The decoded message with field names, as `serde_json` writes the
+    /// `breakpoint-core` type. Absent for framing-only vectors.
+    #[serde(default)]
+    pub message: Option<serde_json::Value>,
+}
+
+impl Vector {
+    pub fn bytes(&self) -> Vec<u8> {
+        hex::decode(&self.hex).expect("vector hex is valid")
+    }
+}
+
+/// The vectors in [`VECTORS_JSON`].
+pub fn vectors() -> Vec<Vector> {
+    serde_json::from_str(VECTORS_JSON).expect("vectors/messages.json is valid")
+}
+
+/// One encoded exemplar of a type that travels inside messages.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Unique name, as in [`schemas::Exemplar::name`].
+    pub name: String,
+    /// The encoded value as lowercase hex.
+    pub hex: String,
+    /// The value with its field names, as `serde_json` writes it.
+    pub value: serde_json::Value,
+}
+
+impl Snapshot {
+    pub fn bytes(&self) -> Vec<u8> {
+        hex::decode(&self.hex).expect("snapshot hex is valid")
+    }
+}
+
+/// The snapshots in [`SCHEMAS_JSON`].
+pub fn snapshots() -> Vec<Snapshot> {
+    serde_json::from_str(SCHEMAS_JSON).expect("vectors/schemas.json is valid")
+}
+
+/// Build the snapshots from [`schemas`] with the current types. Values go
+/// through JSON text once, so `f32`s compare equal to the committed ones.
+pub fn generate_snapshots() -> Vec<Snapshot> {
+    let snapshots: Vec<Snapshot> = schemas::exemplars()
+        .into_iter()
+        .map(|exemplar| Snapshot {
+            name: exemplar.name.to_string(),
+            hex: hex::encode(&exemplar.bytes),
+            value: exemplar.value,
+        })
+        .collect();
+    let json = serde_json::to_string(&snapshots).expect("snapshots serialize");
+    serde_json::from_str(&json).expect("snapshots deserialize")
+}
+
+/// Build the vectors from [`samples`] with the current encoder.
+pub fn generate() -> Result<Vec<Vector>, ProtocolError> {
+    let mut vectors = Vec::new();
+    for (name, msg) in samples::client_messages() {
+        let frame = encode_client_message(&msg)?;
+        vectors.push(vector(name, Direction::ClientToServer, frame, Some(&msg))?);
+    }
+    for (name, msg) in samples::server_messages() {
+        let frame = encode_server_message(&msg)?;
+        vectors.push(vector(name, Direction::ServerToClient, frame, Some(&msg))?);
+    }
+    let frames = samples::batch()
+        .iter()
+        .map(encode_server_message)
+        .collect::<Result<Vec<_>, _>>()?;
+    let batch = encode_batch(&frames)?;
+    vectors.push(vector(
+        "batch",
+        Direction::ServerToClient,
+        batch,
+        None::<&()>,
+    )?);
+    Ok(vectors)
+}
+
+fn vector<T: Serialize>(
+    name: &str,
+    direction: Direction,
+    frame: Vec<u8>,
+    msg: Option<&T>,
+) -> Result<Vector, ProtocolError> {
+    let payload = reference::decode_frame(&frame)
+        .map_err(|e| ProtocolError::DeserializeError(e.to_string()))?;
+    let message = msg
+        .map(|m| serde_json::to_value(m).map_err(|e| ProtocolError::SerializeError(e.to_string())))
+        .transpose()?;
+    Ok(Vector {
+        name: name.to_string(),
+        direction,
+        type_byte: frame[0],
+        hex: hex::encode(&frame),
+        payload,
+        message,
+    })
+}
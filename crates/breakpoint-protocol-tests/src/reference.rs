@@ -0,0 +1,284 @@
+//! A reference decoder for Breakpoint frames, written without
+//! `breakpoint-core` or a MessagePack library so it can be ported line by
+//! line.
+//!
+//! Every frame starts with a type byte. Three layouts follow it:
+//!
+//! - `GameState` (0x10): `tick` as little-endian u32, `server_time_ms` as
+//!   little-endian u64, then the game's own state bytes.
+//! - `Batch` (0x60): repeated `(len as little-endian u32, frame)`, where
+//!   each frame is a complete non-batch frame.
+//! - Anything else: one MessagePack value, the message's fields as an
+//!   array in declaration order. Enums are a string for unit variants and
+//!   a one-entry map `{variant: value}` otherwise; `Option::None` is nil.
+//!
+//! `Sealed` (0x61) frames need the room key and are not decoded here.
+
+use serde_json::{Map, Number, Value, json};
+
+const GAME_STATE: u8 = 0x10;
+const BATCH: u8 = 0x60;
+const SEALED: u8 = 0x61;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    Empty,
+    /// The frame ended inside a value or header.
+    Truncated,
+    /// Bytes were left over after the payload.
+    TrailingBytes(usize),
+    /// A MessagePack marker this protocol never sends (ext types, reserved).
+    UnsupportedMarker(u8),
+    /// A map key that is neither a string nor an integer.
+    UnsupportedKey,
+    SealedFrame,
+    NestedBatch,
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty frame"),
+            Self::Truncated => write!(f, "frame ends early"),
+            Self::TrailingBytes(n) => write!(f, "{n} bytes after the payload"),
+            Self::UnsupportedMarker(b) => write!(f, "unsupported MessagePack marker 0x{b:02x}"),
+            Self::UnsupportedKey => write!(f, "map key is not a string or integer"),
+            Self::SealedFrame => write!(f, "sealed frames need the room key"),
+            Self::NestedBatch => write!(f, "batch inside a batch"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Decode everything after the type byte of `frame` into JSON. Byte
+/// strings become arrays of numbers and integer map keys become strings,
+/// as JSON has neither.
+pub fn decode_frame(frame: &[u8]) -> Result<Value, DecodeError> {
+    let (&type_byte, body) = frame.split_first().ok_or(DecodeError::Empty)?;
+    match type_byte {
+        GAME_STATE => {
+            let mut reader = Reader { data: body };
+            let tick = u32::from_le_bytes(reader.array()?);
+            let server_time_ms = u64::from_le_bytes(reader.array()?);
+            Ok(json!({
+                "tick": tick,
+                "server_time_ms": server_time_ms,
+                "state_data": reader.data,
+            }))
+        },
+        BATCH => {
+            let mut reader = Reader { data: body };
+            let mut frames = Vec::new();
+            while !reader.data.is_empty() {
+                let len = u32::from_le_bytes(reader.array()?) as usize;
+                let inner = reader.take(len)?;
+                if inner.first() == Some(&BATCH) {
+                    return Err(DecodeError::NestedBatch);
+                }
+                frames.push(json!({
+                    "type_byte": inner.first().ok_or(DecodeError::Empty)?,
+                    "payload": decode_frame(inner)?,
+                }));
+            }
+            Ok(Value::Array(frames))
+        },
+        SEALED => Err(DecodeError::SealedFrame),
+        _ => {
+            let mut reader = Reader { data: body };
+            let value = reader.value()?;
+            if !reader.data.is_empty() {
+                return Err(DecodeError::TrailingBytes(reader.data.len()));
+            }
+            Ok(value)
+        },
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], DecodeError> {
+        if n > self.data.len() {
+            return Err(DecodeError::Truncated);
+        }
+        let (head, tail) = self.data.split_at(n);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        let mut out = [0; N];
+        out.copy_from_slice(self.take(N)?);
+        Ok(out)
+    }
+
+    /// A big-endian length or integer of `n` bytes.
+    fn uint(&mut self, n: usize) -> Result<u64, DecodeError> {
+        Ok(self
+            .take(n)?
+            .iter()
+            .fold(0, |acc, &b| (acc << 8) | u64::from(b)))
+    }
+
+    /// A big-endian two's complement integer of `n` bytes.
+    fn int(&mut self, n: usize) -> Result<i64, DecodeError> {
+        let bits = 64 - 8 * n as u32;
+        Ok(((self.uint(n)? << bits) as i64) >> bits)
+    }
+
+    fn value(&mut self) -> Result<Value, DecodeError> {
+        let marker = self.take(1)?[0];
+        match marker {
+            0x00..=0x7f => Ok(json!(marker)),
+            0x80..=0x8f => self.map(usize::from(marker & 0x0f)),
+            0x90..=0x9f => self.seq(usize::from(marker & 0x0f)),
+            0xa0..=0xbf => self.str(usize::from(marker & 0x1f)),
+            0xc0 => Ok(Value::Null),
+            0xc2 => Ok(Value::Bool(false)),
+            0xc3 => Ok(Value::Bool(true)),
+            0xc4..=0xc6 => {
+                let len = self.uint(1 << (marker - 0xc4))? as usize;
+                Ok(json!(self.take(len)?))
+            },
+            0xca => {
+                let f = f32::from_be_bytes(self.array()?);
+                Ok(float(f64::from(f)))
+            },
+            0xcb => Ok(float(f64::from_be_bytes(self.array()?))),
+            0xcc..=0xcf => Ok(json!(self.uint(1 << (marker - 0xcc))?)),
+            0xd0..=0xd3 => Ok(json!(self.int(1 << (marker - 0xd0))?)),
+            0xd9..=0xdb => {
+                let len = self.uint(1 << (marker - 0xd9))? as usize;
+                self.str(len)
+            },
+            0xdc | 0xdd => {
+                let len = self.uint(2 << (marker - 0xdc))? as usize;
+                self.seq(len)
+            },
+            0xde | 0xdf => {
+                let len = self.uint(2 << (marker - 0xde))? as usize;
+                self.map(len)
+            },
+            0xe0..=0xff => Ok(json!(marker as i8)),
+            _ => Err(DecodeError::UnsupportedMarker(marker)),
+        }
+    }
+
+    fn str(&mut self, len: usize) -> Result<Value, DecodeError> {
+        Ok(Value::String(
+            String::from_utf8_lossy(self.take(len)?).into_owned(),
+        ))
+    }
+
+    fn seq(&mut self, len: usize) -> Result<Value, DecodeError> {
+        // Each element takes at least a byte, which bounds hostile lengths
+        if len > self.data.len() {
+            return Err(DecodeError::Truncated);
+        }
+        (0..len)
+            .map(|_| self.value())
+            .collect::<Result<_, _>>()
+            .map(Value::Array)
+    }
+
+    fn map(&mut self, len: usize) -> Result<Value, DecodeError> {
+        if len > self.data.len() {
+            return Err(DecodeError::Truncated);
+        }
+        let mut map = Map::new();
+        for _ in 0..len {
+            let key = match self.value()? {
+                Value::String(s) => s,
+                Value::Number(n) if n.is_i64() || n.is_u64() => n.to_string(),
+                _ => return Err(DecodeError::UnsupportedKey),
+            };
+            map.insert(key, self.value()?);
+        }
+        Ok(Value::Object(map))
+    }
+}
+
+/// JSON has no NaN or infinities; they decode as null.
+fn float(f: f64) -> Value {
+    Number::from_f64(f).map_or(Value::Null, Value::Number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_every_integer_width() {
+        let frame = [
+            0x01, 0x98, // fixarray(8)
+            0x7f, // positive fixint
+            0xe0, // negative fixint -32
+            0xcc, 0xff, // uint8
+            0xcd, 0x01, 0x00, // uint16
+            0xce, 0x00, 0x01, 0x00, 0x00, // uint32
+            0xcf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // uint64
+            0xd1, 0xff, 0x00, // int16 -256
+            0xd3, 0x80, 0, 0, 0, 0, 0, 0, 0, // int64 min
+        ];
+        assert_eq!(
+            decode_frame(&frame).unwrap(),
+            json!([127, -32, 255, 256, 65536, u64::MAX, -256, i64::MIN])
+        );
+    }
+
+    #[test]
+    fn decodes_framing_layouts() {
+        let mut state = vec![GAME_STATE];
+        state.extend_from_slice(&7u32.to_le_bytes());
+        state.extend_from_slice(&9u64.to_le_bytes());
+        state.extend_from_slice(&[0xaa, 0xbb]);
+        assert_eq!(
+            decode_frame(&state).unwrap(),
+            json!({"tick": 7, "server_time_ms": 9, "state_data": [0xaa, 0xbb]})
+        );
+
+        // Batch of one LeaveRoom: [player_id = 3]
+        let batch = [BATCH, 3, 0, 0, 0, 0x03, 0x91, 0x03];
+        assert_eq!(
+            decode_frame(&batch).unwrap(),
+            json!([{"type_byte": 3, "payload": [3]}])
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_frames() {
+        assert_eq!(decode_frame(&[]), Err(DecodeError::Empty));
+        assert_eq!(
+            decode_frame(&[0x03, 0x92, 0x01]),
+            Err(DecodeError::Truncated)
+        );
+        assert_eq!(
+            decode_frame(&[0x03, 0xdd, 0xff, 0xff, 0xff, 0xff]),
+            Err(DecodeError::Truncated)
+        );
+        assert_eq!(
+            decode_frame(&[0x03, 0x91, 0x01, 0x02]),
+            Err(DecodeError::TrailingBytes(1))
+        );
+        assert_eq!(
+            decode_frame(&[0x03, 0xd4, 0x00, 0x00]),
+            Err(DecodeError::UnsupportedMarker(0xd4))
+        );
+        assert_eq!(
+            decode_frame(&[0x03, 0x81, 0xc0, 0x01]),
+            Err(DecodeError::UnsupportedKey)
+        );
+        assert_eq!(decode_frame(&[SEALED, 0x00]), Err(DecodeError::SealedFrame));
+        assert_eq!(
+            decode_frame(&[BATCH, 1, 0, 0, 0, BATCH]),
+            Err(DecodeError::NestedBatch)
+        );
+        assert_eq!(
+            decode_frame(&[GAME_STATE, 1, 0]),
+            Err(DecodeError::Truncated)
+        );
+    }
+}
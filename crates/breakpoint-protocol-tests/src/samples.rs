@@ -0,0 +1,627 @@
+//! The messages behind the vectors. Every `ClientMessage` and
+//! `ServerMessage` variant appears at least once, with optional fields set
+//! so their position on the wire is pinned down too. `HashMap`s hold at most
+//! one entry, since their MessagePack order would otherwise vary between
+//! runs.
+
+use std::collections::HashMap;
+
+use breakpoint_core::announcement::{Announcement, AnnouncementSeverity};
+use breakpoint_core::bot::BotDifficulty;
+use breakpoint_core::debug_command::DebugCommand;
+use breakpoint_core::events::{Event, EventType, Priority};
+use breakpoint_core::game_trait::{GameConfigDelta, LIVE_EXTEND_ROUND};
+use breakpoint_core::heatmap::RoundHeatmap;
+use breakpoint_core::i18n::{LocalizedText, TextKey};
+use breakpoint_core::net::messages::*;
+use breakpoint_core::net::protocol::PROTOCOL_VERSION;
+use breakpoint_core::overlay::config::{OverlayConfigMsg, OverlayRoomConfig};
+use breakpoint_core::player::{Player, PlayerColor};
+use breakpoint_core::room::{RoomConfig, RoomState};
+use breakpoint_core::round_phase::{RoundPacing, RoundPhase};
+use breakpoint_core::tutorial::{TutorialChecklist, TutorialStep};
+use breakpoint_core::victory::VictorySummary;
+use breakpoint_core::win_condition::{WinCondition, WinRule};
+
+const ROOM: &str = "ABCD-1234";
+
+fn player(id: u64, name: &str, is_leader: bool) -> Player {
+    Player {
+        id,
+        display_name: name.to_string(),
+        color: PlayerColor::PALETTE[id as usize % PlayerColor::PALETTE.len()],
+        is_leader,
+        is_spectator: false,
+        is_bot: false,
+        bot_difficulty: BotDifficulty::default(),
+        hot_seat_of: None,
+        external_bot: false,
+    }
+}
+
+fn players() -> Vec<Player> {
+    vec![player(1, "Alice", true), player(2, "Bob", false)]
+}
+
+fn scores() -> Vec<PlayerScoreEntry> {
+    vec![
+        PlayerScoreEntry {
+            player_id: 1,
+            score: 12,
+            disconnected: false,
+        },
+        PlayerScoreEntry {
+            player_id: 2,
+            score: -3,
+            disconnected: true,
+        },
+    ]
+}
+
+fn event() -> Event {
+    Event {
+        id: "evt-1".to_string(),
+        event_type: EventType::PipelineFailed,
+        source: "github".to_string(),
+        priority: Priority::Urgent,
+        title: "CI failed on main".to_string(),
+        body: Some("3 tests failed".to_string()),
+        timestamp: "2026-01-01T00:00:00Z".to_string(),
+        url: Some("https://example.com/run/1".to_string()),
+        actor: Some("octocat".to_string()),
+        tags: vec!["ci".to_string()],
+        action_required: true,
+        group_key: Some("ci:main".to_string()),
+        expires_at: None,
+        metadata: HashMap::from([("run".to_string(), serde_json::json!(1))]),
+    }
+}
+
+/// One sample per client message variant, named after it.
+pub fn client_messages() -> Vec<(&'static str, ClientMessage)> {
+    vec![
+        (
+            "join_room",
+            ClientMessage::JoinRoom(JoinRoomMsg {
+                room_code: ROOM.to_string(),
+                player_name: "Alice".to_string(),
+                player_color: PlayerColor::default(),
+                protocol_version: PROTOCOL_VERSION,
+                session_token: Some("session-token".to_string()),
+                identity_key: None,
+                accepts_batches: true,
+                room_alias: None,
+                bot_token: None,
+                tutorial: None,
+            }),
+        ),
+        (
+            "leave_room",
+            ClientMessage::LeaveRoom(LeaveRoomMsg { player_id: 1 }),
+        ),
+        (
+            "player_input",
+            ClientMessage::PlayerInput(PlayerInputMsg {
+                player_id: 1,
+                tick: 300,
+                input_data: vec![0x92, 0xc3, 0xca, 0x3f, 0x80, 0x00, 0x00],
+            }),
+        ),
+        (
+            "chat_message",
+            ClientMessage::ChatMessage(ChatMessageMsg {
+                player_id: 1,
+                content: "gg ⛳".to_string(),
+            }),
+        ),
+        (
+            "claim_alert",
+            ClientMessage::ClaimAlert(ClaimAlertMsg {
+                player_id: 1,
+                event_id: "evt-1".to_string(),
+            }),
+        ),
+        (
+            "overlay_config",
+            ClientMessage::OverlayConfig(OverlayConfigMsg {
+                room_config: OverlayRoomConfig::default(),
+            }),
+        ),
+        (
+            "request_game_start",
+            ClientMessage::RequestGameStart(RequestGameStartMsg {
+                game_name: "mini-golf".to_string(),
+                custom: HashMap::from([("hole_count".to_string(), serde_json::json!(9))]),
+                win_condition: Some(WinCondition {
+                    rules: vec![WinRule::FirstTo { points: 20 }],
+                    sudden_death: true,
+                }),
+                pacing: Some(RoundPacing::default()),
+                featured: false,
+            }),
+        ),
+        (
+            "add_bot",
+            ClientMessage::AddBot(AddBotMsg {
+                difficulty: BotDifficulty::Hard,
+            }),
+        ),
+        (
+            "remove_bot",
+            ClientMessage::RemoveBot(RemoveBotMsg { player_id: 5 }),
+        ),
+        (
+            "set_friend",
+            ClientMessage::SetFriend(SetFriendMsg {
+                friend_id: "f-7k2m".to_string(),
+                is_friend: true,
+            }),
+        ),
+        (
+            "invite_friend",
+            ClientMessage::InviteFriend(InviteFriendMsg {
+                friend_id: "f-7k2m".to_string(),
+            }),
+        ),
+        (
+            "manage_join_queue",
+            ClientMessage::ManageJoinQueue(ManageJoinQueueMsg {
+                action: JoinQueueAction::Admit(7),
+            }),
+        ),
+        (
+            "set_bot_difficulty",
+            ClientMessage::SetBotDifficulty(SetBotDifficultyMsg {
+                player_id: 5,
+                difficulty: BotDifficulty::Mirror,
+            }),
+        ),
+        (
+            "cast_course_vote",
+            ClientMessage::CastCourseVote(CastCourseVoteMsg {
+                round: 2,
+                choice: 1,
+            }),
+        ),
+        (
+            "add_local_player",
+            ClientMessage::AddLocalPlayer(AddLocalPlayerMsg {
+                player_name: "Couch".to_string(),
+                player_color: PlayerColor::PALETTE[2],
+            }),
+        ),
+        (
+            "remove_local_player",
+            ClientMessage::RemoveLocalPlayer(RemoveLocalPlayerMsg { player_id: 6 }),
+        ),
+        (
+            "advance_round",
+            ClientMessage::AdvanceRound(AdvanceRoundMsg { round: 2 }),
+        ),
+        (
+            "debug_command",
+            ClientMessage::DebugCommand(DebugCommandMsg {
+                command: DebugCommand::Teleport {
+                    player_id: 2,
+                    x: 4.5,
+                    y: -1.0,
+                },
+            }),
+        ),
+        (
+            "lobby_toy_input",
+            ClientMessage::LobbyToyInput(LobbyToyInputMsg {
+                input_data: vec![0x92, 0x01, 0x02],
+            }),
+        ),
+        (
+            "bot_ack",
+            ClientMessage::BotAck(BotAckMsg {
+                tick: 412,
+                input_data: vec![0x93, 0x01, 0x02, 0x03],
+            }),
+        ),
+        (
+            "reroll_name",
+            ClientMessage::RerollName(RerollNameMsg { player_id: 1 }),
+        ),
+        (
+            "clock_sync",
+            ClientMessage::ClockSync(ClockSyncMsg {
+                client_time: 1234.5,
+                rtt_ms: Some(42),
+            }),
+        ),
+        (
+            "reshape_room_merge",
+            ClientMessage::ReshapeRoom(ReshapeRoomMsg {
+                action: RoomReshape::MergeInto("WXYZ-5678".to_string()),
+            }),
+        ),
+        (
+            "reshape_room_split",
+            ClientMessage::ReshapeRoom(ReshapeRoomMsg {
+                action: RoomReshape::Split,
+            }),
+        ),
+        (
+            "draft_pick",
+            ClientMessage::DraftPick(DraftPickMsg { player_id: 3 }),
+        ),
+        (
+            "migrate_transport",
+            ClientMessage::MigrateTransport(MigrateTransportMsg {
+                session_token: "session-token".to_string(),
+                protocol_version: PROTOCOL_VERSION,
+                accepts_batches: true,
+            }),
+        ),
+        (
+            "change_game_config",
+            ClientMessage::ChangeGameConfig(ChangeGameConfigMsg {
+                delta: GameConfigDelta::default().set(LIVE_EXTEND_ROUND, 30.0),
+            }),
+        ),
+        (
+            "set_presence_sharing",
+            ClientMessage::SetPresenceSharing(SetPresenceSharingMsg { share: true }),
+        ),
+    ]
+}
+
+/// One sample per server message variant, named after it.
+pub fn server_messages() -> Vec<(&'static str, ServerMessage)> {
+    vec![
+        (
+            "join_room_response",
+            ServerMessage::JoinRoomResponse(JoinRoomResponseMsg {
+                success: true,
+                player_id: Some(1),
+                room_code: Some(ROOM.to_string()),
+                room_state: Some(RoomState::Lobby),
+                error: None,
+                session_token: Some("session-token".to_string()),
+                lobby_toy: Some(vec![1, 2, 3]),
+            }),
+        ),
+        (
+            "join_room_response_error",
+            ServerMessage::JoinRoomResponse(JoinRoomResponseMsg {
+                success: false,
+                player_id: None,
+                room_code: None,
+                room_state: None,
+                error: Some(
+                    LocalizedText::new(TextKey::ProtocolMismatch)
+                        .with("client", 2)
+                        .with("server", PROTOCOL_VERSION),
+                ),
+                session_token: None,
+                lobby_toy: None,
+            }),
+        ),
+        (
+            "join_queued",
+            ServerMessage::JoinQueued(JoinQueuedMsg {
+                room_code: ROOM.to_string(),
+                position: 2,
+                queue_len: 3,
+                awaiting_approval: true,
+            }),
+        ),
+        (
+            "join_queue_update",
+            ServerMessage::JoinQueueUpdate(JoinQueueUpdateMsg {
+                require_approval: true,
+                entries: vec![QueuedJoinEntry {
+                    ticket: 7,
+                    display_name: "Dana".to_string(),
+                    approved: false,
+                }],
+            }),
+        ),
+        (
+            "room_transfer",
+            ServerMessage::RoomTransfer(RoomTransferMsg {
+                room_code: "WXYZ-5678".to_string(),
+                transfer_token: "transfer-token".to_string(),
+                reason: TransferReason::Merge,
+                expires_in_secs: 60,
+            }),
+        ),
+        (
+            "transport_migrated",
+            ServerMessage::TransportMigrated(TransportMigratedMsg {
+                success: true,
+                player_id: Some(2),
+                room_code: Some(ROOM.to_string()),
+                session_token: Some("new-session-token".to_string()),
+                last_input_tick: Some(412),
+                error: None,
+            }),
+        ),
+        (
+            "transport_migrated_refused",
+            ServerMessage::TransportMigrated(TransportMigratedMsg {
+                success: false,
+                player_id: None,
+                room_code: None,
+                session_token: None,
+                last_input_tick: None,
+                error: Some(TextKey::SessionInvalid.into()),
+            }),
+        ),
+        (
+            "player_list",
+            ServerMessage::PlayerList(PlayerListMsg {
+                players: players(),
+                leader_id: 1,
+                session_scores: HashMap::from([(1, 17)]),
+                playlist: vec!["mini-golf".to_string(), "tron".to_string()],
+            }),
+        ),
+        (
+            "room_config",
+            ServerMessage::RoomConfig(RoomConfigPayload {
+                config: RoomConfig::default(),
+            }),
+        ),
+        (
+            "game_state",
+            ServerMessage::GameState(GameStateMsg {
+                tick: 500,
+                server_time_ms: 1_767_225_600_250,
+                state_data: vec![0x93, 0x01, 0x02, 0x03],
+            }),
+        ),
+        (
+            "game_start",
+            ServerMessage::GameStart(GameStartMsg {
+                game_name: "mini-golf".to_string(),
+                players: players(),
+                leader_id: 1,
+            }),
+        ),
+        (
+            "round_end",
+            ServerMessage::RoundEnd(RoundEndMsg {
+                round: 3,
+                scores: scores(),
+                between_round_secs: 30,
+                heatmap: Some(RoundHeatmap {
+                    width: 40.0,
+                    depth: 30.0,
+                    cols: 2,
+                    rows: 1,
+                    presence: vec![255, 12],
+                    tags: vec![3, 0],
+                }),
+                victory: Some(VictorySummary::new(TextKey::VictoryMostTags, vec![1]).with_stat(5)),
+            }),
+        ),
+        (
+            "game_end",
+            ServerMessage::GameEnd(GameEndMsg {
+                final_scores: scores(),
+                match_id: Some("3f2a".to_string()),
+                victory: Some(VictorySummary::no_winner()),
+            }),
+        ),
+        (
+            "round_phase",
+            ServerMessage::RoundPhase(RoundPhaseMsg {
+                round: 2,
+                phase: RoundPhase::Countdown,
+                remaining_secs: 3.0,
+            }),
+        ),
+        (
+            "course_vote",
+            ServerMessage::CourseVote(CourseVoteMsg {
+                round: 1,
+                candidates: vec![CourseCandidate {
+                    seed: u64::MAX,
+                    cols: 2,
+                    rows: 1,
+                    cells: vec![1, 5],
+                }],
+                votes: vec![0],
+                remaining_secs: 10.0,
+                winner: None,
+            }),
+        ),
+        (
+            "intermission",
+            ServerMessage::Intermission(IntermissionMsg {
+                round: 2,
+                remaining_secs: Some(12.5),
+            }),
+        ),
+        (
+            "intermission_waiting",
+            ServerMessage::Intermission(IntermissionMsg {
+                round: 2,
+                remaining_secs: None,
+            }),
+        ),
+        (
+            "lobby_toy_state",
+            ServerMessage::LobbyToyState(LobbyToyStateMsg {
+                state_data: vec![1, 2, 3],
+            }),
+        ),
+        (
+            "clock_sync_reply",
+            ServerMessage::ClockSyncReply(ClockSyncReplyMsg {
+                client_time: 1234.5,
+                server_time_ms: 1_767_225_600_123,
+            }),
+        ),
+        (
+            "standings",
+            ServerMessage::Standings(StandingsMsg {
+                round: 2,
+                round_count: 3,
+                players: vec![
+                    StandingEntry {
+                        player_id: 1,
+                        round_score: 4,
+                        match_score: 9,
+                        team: Some(0),
+                        ping_ms: Some(38),
+                    },
+                    StandingEntry {
+                        player_id: 2,
+                        round_score: -1,
+                        match_score: 3,
+                        team: Some(1),
+                        ping_ms: None,
+                    },
+                ],
+                teams: vec![
+                    TeamStanding { team: 0, score: 9 },
+                    TeamStanding { team: 1, score: 3 },
+                ],
+            }),
+        ),
+        (
+            "team_draft",
+            ServerMessage::TeamDraft(TeamDraftMsg {
+                captains: vec![4, 2],
+                picks: vec![
+                    DraftedPlayer {
+                        player_id: 4,
+                        team: 0,
+                    },
+                    DraftedPlayer {
+                        player_id: 2,
+                        team: 1,
+                    },
+                ],
+                pool: vec![1, 3],
+                turn: Some(0),
+                remaining_secs: 14.5,
+            }),
+        ),
+        (
+            "alert_event",
+            ServerMessage::AlertEvent(Box::new(AlertEventMsg { event: event() })),
+        ),
+        (
+            "alert_claimed",
+            ServerMessage::AlertClaimed(AlertClaimedMsg {
+                event_id: "evt-1".to_string(),
+                claimed_by: 1,
+            }),
+        ),
+        (
+            "alert_dismissed",
+            ServerMessage::AlertDismissed(AlertDismissedMsg {
+                event_id: "evt-1".to_string(),
+            }),
+        ),
+        (
+            "overlay_config_broadcast",
+            ServerMessage::OverlayConfig(OverlayConfigMsg {
+                room_config: OverlayRoomConfig::default(),
+            }),
+        ),
+        (
+            "course_update",
+            ServerMessage::CourseUpdate(CourseUpdateMsg {
+                version: 2,
+                data: vec![0x91, 0x07],
+            }),
+        ),
+        (
+            "game_effects",
+            ServerMessage::GameEffects(GameEffectsMsg {
+                tick: 2,
+                data: vec![0x91, 0x07],
+            }),
+        ),
+        (
+            "private_state",
+            ServerMessage::PrivateState(PrivateStateMsg {
+                tick: 2,
+                player_id: 3,
+                data: vec![0x91, 0xC0],
+            }),
+        ),
+        (
+            "social_update",
+            ServerMessage::SocialUpdate(SocialUpdateMsg {
+                your_id: "f-7k2m".to_string(),
+                friends: vec![SocialEntry {
+                    id: "f-9q4x".to_string(),
+                    name: "Bob".to_string(),
+                    online: true,
+                    last_played: 1_767_225_600,
+                    room_code: Some(ROOM.to_string()),
+                    game: Some("tron".to_string()),
+                }],
+                recent: Vec::new(),
+                sharing_presence: true,
+            }),
+        ),
+        (
+            "friend_invite",
+            ServerMessage::FriendInvite(FriendInviteMsg {
+                from_id: "f-9q4x".to_string(),
+                from_name: "Bob".to_string(),
+                room_code: ROOM.to_string(),
+                sent_at: 1_767_225_600,
+            }),
+        ),
+        (
+            "tutorial_progress",
+            ServerMessage::TutorialProgress(TutorialProgressMsg {
+                checklists: vec![TutorialChecklist {
+                    game: "laser-tag".to_string(),
+                    steps: vec![TutorialStep {
+                        id: "tag".to_string(),
+                        text: TextKey::TutorialLaserTagTag,
+                        done: true,
+                    }],
+                }],
+            }),
+        ),
+        (
+            "announcements",
+            ServerMessage::Announcements(AnnouncementsMsg {
+                announcements: vec![Announcement {
+                    id: "motd".to_string(),
+                    message: "Welcome!".to_string(),
+                    severity: AnnouncementSeverity::Info,
+                    starts_at: 0,
+                    ends_at: Some(1_767_225_600),
+                }],
+            }),
+        ),
+        (
+            "room_rekey",
+            ServerMessage::RoomRekey(RoomRekeyMsg {
+                epoch: 3,
+                secret: "ABCD-EFGH-JKLM-NPQR".to_string(),
+            }),
+        ),
+    ]
+}
+
+/// The messages packed into the `batch` vector, as a server sends them
+/// right after a join.
+pub fn batch() -> Vec<ServerMessage> {
+    vec![
+        ServerMessage::PlayerList(PlayerListMsg {
+            players: vec![player(1, "Alice", true)],
+            leader_id: 1,
+            session_scores: HashMap::new(),
+            playlist: Vec::new(),
+        }),
+        ServerMessage::RoundPhase(RoundPhaseMsg {
+            round: 1,
+            phase: RoundPhase::Playing,
+            remaining_secs: 90.0,
+        }),
+    ]
+}
@@ -0,0 +1,309 @@
+//! The exemplars behind the schema snapshots: every type whose encoding
+//! reaches another process without a `ServerMessage` wrapper around it. Game
+//! states, inputs, private states and effects travel as opaque bytes inside
+//! messages, and recorded replays keep those bytes for good, so a renamed or
+//! reordered field breaks clients and old recordings without touching the
+//! message vectors.
+//!
+//! Game states come from each game's own `serialize_state_into` after an
+//! `init` with a single player and default config, so they are exactly what
+//! the host broadcasts. As in [`samples`](crate::samples), `HashMap`s hold at
+//! most one entry.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use breakpoint_core::game_trait::{BreakpointGame, GameConfig, GameEvent, GameId, PlayerScore};
+use breakpoint_core::lobby_toy::{TargetClick, TargetPractice};
+use breakpoint_core::math::{ScreenAim, Vec2};
+use breakpoint_core::player::{Player, PlayerColor};
+use breakpoint_core::replay::{MatchReplay, ReplayFrame, ReplayMetadata, RoundReplay};
+use breakpoint_golf::caddie::CaddieHint;
+use breakpoint_golf::physics::GolfConfig;
+use breakpoint_golf::{GolfInput, GolfPrivateState, MiniGolf};
+use breakpoint_lasertag::projectile::LaserTagConfig;
+use breakpoint_lasertag::trail::LaserFired;
+use breakpoint_lasertag::{LaserTagArena, LaserTagInput};
+use breakpoint_platformer::PlatformRacer;
+use breakpoint_platformer::physics::{PlatformerConfig, PlatformerInput};
+use breakpoint_tron::config::TronConfig;
+use breakpoint_tron::{TronCycles, TronInput, TurnDirection};
+
+/// Decodes a snapshot's bytes with the current types and encodes them
+/// again. `None` when they no longer decode.
+pub type Reencode = fn(&[u8]) -> Option<Vec<u8>>;
+
+/// One exemplar, encoded.
+pub struct Exemplar {
+    /// Unique name, `<crate>.<type>` or `<crate>.<type>_<case>`.
+    pub name: &'static str,
+    /// The bytes as they go on the wire or into a replay.
+    pub bytes: Vec<u8>,
+    /// The same value with its field names.
+    pub value: serde_json::Value,
+    pub reencode: Reencode,
+}
+
+fn player() -> Player {
+    Player {
+        id: 1,
+        display_name: "Alice".to_string(),
+        color: PlayerColor::default(),
+        is_leader: true,
+        is_spectator: false,
+        is_bot: false,
+        bot_difficulty: Default::default(),
+        hot_seat_of: None,
+        external_bot: false,
+    }
+}
+
+fn golf() -> Box<dyn BreakpointGame> {
+    Box::new(MiniGolf::with_config(GolfConfig::default()))
+}
+
+fn lasertag() -> Box<dyn BreakpointGame> {
+    Box::new(LaserTagArena::with_config(LaserTagConfig::default()))
+}
+
+fn platformer() -> Box<dyn BreakpointGame> {
+    Box::new(PlatformRacer::with_config(PlatformerConfig::default()))
+}
+
+fn tron() -> Box<dyn BreakpointGame> {
+    Box::new(TronCycles::with_config(TronConfig::default()))
+}
+
+fn target_practice() -> Box<dyn BreakpointGame> {
+    Box::new(TargetPractice::new())
+}
+
+/// A game as a one-player room starts it.
+fn started(new_game: fn() -> Box<dyn BreakpointGame>) -> Box<dyn BreakpointGame> {
+    let mut game = new_game();
+    game.init(&[player()], &GameConfig::builder().build());
+    game
+}
+
+/// The state as the host broadcasts it.
+fn broadcast_state(game: &dyn BreakpointGame) -> Vec<u8> {
+    let mut buf = Vec::new();
+    game.serialize_state_into(&mut buf);
+    buf
+}
+
+/// Feeds the bytes to a freshly started game and broadcasts its state.
+macro_rules! reencode_state {
+    ($new_game:expr) => {
+        |bytes: &[u8]| {
+            let mut game = started($new_game);
+            game.apply_state(bytes);
+            Some(broadcast_state(game.as_ref()))
+        }
+    };
+}
+
+fn state(
+    name: &'static str,
+    new_game: fn() -> Box<dyn BreakpointGame>,
+    reencode: Reencode,
+) -> Exemplar {
+    let game = started(new_game);
+    let bytes = broadcast_state(game.as_ref());
+    let value = game
+        .inspect_state(&bytes)
+        .expect("every game can inspect its state");
+    Exemplar {
+        name,
+        bytes,
+        value,
+        reencode,
+    }
+}
+
+fn reencode<T: Serialize + DeserializeOwned>(bytes: &[u8]) -> Option<Vec<u8>> {
+    let value: T = rmp_serde::from_slice(bytes).ok()?;
+    rmp_serde::to_vec(&value).ok()
+}
+
+fn reencode_named<T: Serialize + DeserializeOwned>(bytes: &[u8]) -> Option<Vec<u8>> {
+    let value: T = rmp_serde::from_slice(bytes).ok()?;
+    rmp_serde::to_vec_named(&value).ok()
+}
+
+/// A value as the games encode inputs, effects and private states:
+/// compact MessagePack arrays.
+fn typed<T: Serialize + DeserializeOwned>(name: &'static str, value: &T) -> Exemplar {
+    Exemplar {
+        name,
+        bytes: rmp_serde::to_vec(value).expect("exemplar encodes"),
+        value: serde_json::to_value(value).expect("exemplar converts to JSON"),
+        reencode: reencode::<T>,
+    }
+}
+
+/// A value with named fields, as replay files store it.
+fn named<T: Serialize + DeserializeOwned>(name: &'static str, value: &T) -> Exemplar {
+    Exemplar {
+        name,
+        bytes: rmp_serde::to_vec_named(value).expect("exemplar encodes"),
+        value: serde_json::to_value(value).expect("exemplar converts to JSON"),
+        reencode: reencode_named::<T>,
+    }
+}
+
+fn game_states() -> Vec<Exemplar> {
+    vec![
+        state("golf.state", golf, reencode_state!(golf)),
+        state("lasertag.state", lasertag, reencode_state!(lasertag)),
+        state("platformer.state", platformer, reencode_state!(platformer)),
+        state("tron.state", tron, reencode_state!(tron)),
+        state(
+            "lobby_toy.state",
+            target_practice,
+            reencode_state!(target_practice),
+        ),
+    ]
+}
+
+fn inputs() -> Vec<Exemplar> {
+    vec![
+        typed(
+            "golf.input",
+            &GolfInput {
+                aim_angle: 1.5,
+                power: 0.75,
+                stroke: true,
+                skip_intro: false,
+                undo: false,
+                caddie: true,
+                screen_aim: Some(ScreenAim {
+                    drag: Vec2 { x: 0.0, y: -1.0 },
+                    camera_yaw: 0.5,
+                }),
+            },
+        ),
+        typed(
+            "lasertag.input",
+            &LaserTagInput {
+                move_x: 1.0,
+                move_z: -0.5,
+                aim_angle: 3.0,
+                fire: true,
+                use_powerup: false,
+            },
+        ),
+        typed(
+            "platformer.input",
+            &PlatformerInput {
+                move_dir: -1.0,
+                jump: true,
+                use_powerup: false,
+                attack: true,
+            },
+        ),
+        typed(
+            "tron.input",
+            &TronInput {
+                turn: TurnDirection::Left,
+                brake: true,
+            },
+        ),
+        typed("lobby_toy.input", &TargetClick { x: 0.25, y: 0.75 }),
+    ]
+}
+
+fn side_channels() -> Vec<Exemplar> {
+    vec![
+        typed(
+            "golf.private_state",
+            &GolfPrivateState {
+                caddie_hint: Some(CaddieHint {
+                    aim_angle: 0.5,
+                    power: 0.4,
+                    expected_distance: 0.0,
+                }),
+            },
+        ),
+        typed(
+            "lasertag.effects",
+            &vec![LaserFired::new(1, 12.5, &[(0.0, 0.0, 4.0, 2.5)])],
+        ),
+    ]
+}
+
+fn events() -> Vec<Exemplar> {
+    vec![
+        typed(
+            "core.game_event_score_update",
+            &GameEvent::ScoreUpdate {
+                player_id: 1,
+                score: 7,
+            },
+        ),
+        typed("core.game_event_round_complete", &GameEvent::RoundComplete),
+        typed(
+            "core.game_event_round_draw",
+            &GameEvent::RoundDraw {
+                player_ids: vec![1, 2],
+            },
+        ),
+        typed(
+            "core.game_event_powerup_collected",
+            &GameEvent::PowerupCollected {
+                player_id: 1,
+                kind: "speed".to_string(),
+            },
+        ),
+    ]
+}
+
+fn replays() -> Vec<Exemplar> {
+    let state = broadcast_state(started(golf).as_ref());
+    let score = PlayerScore {
+        player_id: 1,
+        score: 3,
+        disconnected: false,
+    };
+    vec![named(
+        "core.match_replay",
+        &MatchReplay {
+            metadata: ReplayMetadata {
+                match_id: "match-1".to_string(),
+                game: GameId::Golf,
+                room_code: "ABCD-1234".to_string(),
+                players: vec![player()],
+                custom: HashMap::from([("hole_count".to_string(), serde_json::json!(9))]),
+                tick_rate: 10.0,
+                started_at: 1_767_225_600,
+                ended_at: 1_767_225_720,
+                server_version: "0.1.0".to_string(),
+            },
+            rounds: vec![RoundReplay {
+                round: 1,
+                scores: vec![score.clone()],
+                frames: vec![ReplayFrame {
+                    tick: 10,
+                    server_time_ms: 1_767_225_601_000,
+                    state,
+                    course: Some(vec![0x90]),
+                }],
+                truncated: false,
+                heatmap: None,
+            }],
+            final_scores: vec![score],
+        },
+    )]
+}
+
+/// Every exemplar, grouped by kind.
+pub fn exemplars() -> Vec<Exemplar> {
+    let mut all = game_states();
+    all.extend(inputs());
+    all.extend(side_channels());
+    all.extend(events());
+    all.extend(replays());
+    all
+}
@@ -0,0 +1,59 @@
+//! Checks the committed schema snapshots against the current game, input
+//! and replay types.
+
+use std::collections::HashSet;
+
+use breakpoint_protocol_tests::schemas::exemplars;
+use breakpoint_protocol_tests::{generate_snapshots, snapshots};
+
+const BLESS_VAR: &str = "BREAKPOINT_BLESS";
+
+#[test]
+fn snapshots_match_the_current_types() {
+    let generated = generate_snapshots();
+    if std::env::var_os(BLESS_VAR).is_some() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/vectors/schemas.json");
+        let json = serde_json::to_string_pretty(&generated).unwrap() + "\n";
+        std::fs::write(path, json).expect("write snapshots");
+        return;
+    }
+    let published = snapshots();
+    for snapshot in &generated {
+        let old = published.iter().find(|s| s.name == snapshot.name);
+        assert_eq!(
+            old,
+            Some(snapshot),
+            "Snapshot `{}` changed; if the schema change is intended, rerun with {BLESS_VAR}=1",
+            snapshot.name
+        );
+    }
+    assert_eq!(
+        published.len(),
+        generated.len(),
+        "Published snapshots without an exemplar; rerun with {BLESS_VAR}=1"
+    );
+}
+
+#[test]
+fn committed_bytes_still_decode() {
+    let exemplars = exemplars();
+    for snapshot in snapshots() {
+        let Some(exemplar) = exemplars.iter().find(|e| e.name == snapshot.name) else {
+            continue;
+        };
+        let reencoded = (exemplar.reencode)(&snapshot.bytes());
+        assert_eq!(
+            reencoded.map(hex::encode).as_deref(),
+            Some(snapshot.hex.as_str()),
+            "`{}` no longer decodes to the same value",
+            snapshot.name
+        );
+    }
+}
+
+#[test]
+fn snapshot_names_are_unique() {
+    let snapshots = snapshots();
+    let names: HashSet<&str> = snapshots.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names.len(), snapshots.len());
+}
@@ -0,0 +1,120 @@
+//! Checks the published vectors against `breakpoint-core`, the source of
+//! truth for the wire format.
+
+use std::collections::HashSet;
+
+use breakpoint_core::net::messages::MessageType;
+use breakpoint_core::net::protocol::{
+    decode_client_message, decode_server_message, encode_client_message, encode_server_message,
+    split_batch,
+};
+use breakpoint_protocol_tests::{Direction, Vector, generate, reference, vectors};
+
+const BLESS_VAR: &str = "BREAKPOINT_BLESS";
+
+#[test]
+fn vectors_match_the_current_encoder() {
+    let generated = generate().expect("samples encode");
+    if std::env::var_os(BLESS_VAR).is_some() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/vectors/messages.json");
+        let json = serde_json::to_string_pretty(&generated).unwrap() + "\n";
+        std::fs::write(path, json).expect("write vectors");
+        return;
+    }
+    let published = vectors();
+    for vector in &generated {
+        let old = published.iter().find(|v| v.name == vector.name);
+        assert_eq!(
+            old,
+            Some(vector),
+            "Vector `{}` changed; if the wire change is intended, rerun with {BLESS_VAR}=1",
+            vector.name
+        );
+    }
+    assert_eq!(
+        published.len(),
+        generated.len(),
+        "Published vectors without a sample; rerun with {BLESS_VAR}=1"
+    );
+}
+
+fn decode_and_reencode(vector: &Vector) -> (serde_json::Value, Vec<u8>) {
+    let bytes = vector.bytes();
+    match vector.direction {
+        Direction::ClientToServer => {
+            let msg = decode_client_message(&bytes).unwrap();
+            (
+                serde_json::to_value(&msg).unwrap(),
+                encode_client_message(&msg).unwrap(),
+            )
+        },
+        Direction::ServerToClient => {
+            let msg = decode_server_message(&bytes).unwrap();
+            (
+                serde_json::to_value(&msg).unwrap(),
+                encode_server_message(&msg).unwrap(),
+            )
+        },
+    }
+}
+
+#[test]
+fn breakpoint_core_decodes_every_vector() {
+    for vector in vectors() {
+        let Some(message) = &vector.message else {
+            continue;
+        };
+        let (decoded, reencoded) = decode_and_reencode(&vector);
+        assert_eq!(&decoded, message, "{}", vector.name);
+        assert_eq!(hex::encode(reencoded), vector.hex, "{}", vector.name);
+    }
+
+    let batch = vectors().into_iter().find(|v| v.name == "batch").unwrap();
+    let bytes = batch.bytes();
+    let frames = split_batch(&bytes).unwrap();
+    assert_eq!(frames.len(), 2);
+    for frame in frames {
+        decode_server_message(frame).unwrap();
+    }
+}
+
+#[test]
+fn reference_decoder_agrees_with_every_vector() {
+    for vector in vectors() {
+        let bytes = vector.bytes();
+        assert_eq!(bytes[0], vector.type_byte, "{}", vector.name);
+        assert_eq!(
+            reference::decode_frame(&bytes).unwrap(),
+            vector.payload,
+            "{}",
+            vector.name
+        );
+    }
+}
+
+#[test]
+fn every_message_type_has_a_vector() {
+    let vectors = vectors();
+    let names: HashSet<&str> = vectors.iter().map(|v| v.name.as_str()).collect();
+    assert_eq!(names.len(), vectors.len(), "Vector names are unique");
+
+    let covered: HashSet<u8> = vectors.iter().map(|v| v.type_byte).collect();
+    for byte in 0..=u8::MAX {
+        let Some(msg_type) = MessageType::from_byte(byte) else {
+            continue;
+        };
+        // Relay envelopes never reach clients and sealed frames need a key
+        let exempt = matches!(
+            msg_type,
+            MessageType::RelayRoomCreated
+                | MessageType::RelayFromPeer
+                | MessageType::RelayPeerLeft
+                | MessageType::RelayToPeer
+                | MessageType::Sealed
+        );
+        assert!(
+            exempt || covered.contains(&byte),
+            "No vector for {msg_type:?} (0x{byte:02x}); add one to samples.rs"
+        );
+    }
+}
@@ -3,24 +3,26 @@ mod relay;
 
 use std::sync::Arc;
 
-use axum::Router;
 use axum::extract::ws::{Message, WebSocket};
 use axum::extract::{State, WebSocketUpgrade};
 use axum::response::IntoResponse;
+use axum::{Extension, Router};
 use futures::{SinkExt, StreamExt};
 use tokio::sync::{RwLock, mpsc};
-use tracing_subscriber::EnvFilter;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::Instrument;
 
+use breakpoint_core::net::chaos::{ChaosConfig, spawn_chaos_pipe};
 use breakpoint_core::net::messages::MessageType;
 use breakpoint_core::net::protocol::decode_message_type;
+use breakpoint_core::room::RoomCodeFormat;
+use breakpoint_core::telemetry::METRICS_TARGET;
 
 use relay::{RelayState, SharedRelayState};
 
 #[tokio::main]
 async fn main() {
-    tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::from_default_env())
-        .init();
+    let _telemetry = breakpoint_core::telemetry::init("breakpoint-relay", false);
 
     let port = std::env::args()
         .nth(1)
@@ -34,11 +36,52 @@ async fn main() {
         .and_then(|p| p.parse::<usize>().ok())
         .unwrap_or(100);
 
-    let state: SharedRelayState = Arc::new(RwLock::new(RelayState::new(max_rooms)));
+    // Debug-only network degradation, e.g. `--chaos=latency=120,jitter=40,drop=0.05`
+    let chaos = match std::env::args()
+        .find_map(|a| a.strip_prefix("--chaos=").map(String::from))
+        .map(|spec| ChaosConfig::parse_spec(&spec))
+    {
+        Some(Ok(cfg)) => {
+            tracing::warn!(
+                ?cfg,
+                "Chaos testing enabled — relay traffic is deliberately degraded"
+            );
+            cfg
+        },
+        Some(Err(e)) => {
+            tracing::error!("Invalid --chaos spec: {e}");
+            std::process::exit(1);
+        },
+        None => ChaosConfig::default(),
+    };
+
+    // Longer codes or a larger alphabet, e.g. `--code-length=12`
+    let mut code_format = RoomCodeFormat::default();
+    if let Some(length) =
+        std::env::args().find_map(|a| a.strip_prefix("--code-length=").map(String::from))
+    {
+        code_format.length = length
+            .parse()
+            .unwrap_or_else(|_| panic!("Invalid --code-length: {length}"));
+    }
+    if let Some(alphabet) =
+        std::env::args().find_map(|a| a.strip_prefix("--code-alphabet=").map(String::from))
+    {
+        code_format.alphabet = alphabet;
+    }
+    if let Err(e) = code_format.validate() {
+        panic!("Invalid room code format: {e}");
+    }
+
+    let state: SharedRelayState = Arc::new(RwLock::new(RelayState::with_code_format(
+        max_rooms,
+        code_format,
+    )));
 
     let app = Router::new()
         .route("/relay", axum::routing::get(relay_ws_handler))
         .route("/health", axum::routing::get(health_handler))
+        .layer(Extension(chaos))
         .with_state(state);
 
     let addr = format!("0.0.0.0:{port}");
@@ -60,11 +103,12 @@ async fn health_handler() -> &'static str {
 async fn relay_ws_handler(
     ws: WebSocketUpgrade,
     State(state): State<SharedRelayState>,
+    Extension(chaos): Extension<ChaosConfig>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_relay_socket(socket, state))
+    ws.on_upgrade(move |socket| handle_relay_socket(socket, state, chaos))
 }
 
-async fn handle_relay_socket(socket: WebSocket, state: SharedRelayState) {
+async fn handle_relay_socket(socket: WebSocket, state: SharedRelayState, chaos: ChaosConfig) {
     let (ws_sender, mut ws_receiver) = socket.split();
 
     // Wait for first message to determine role (create or join)
@@ -96,9 +140,10 @@ async fn handle_relay_socket(socket: WebSocket, state: SharedRelayState) {
 
     if join.room_code.is_empty() {
         // Create a new room — this connection is the host
-        let code = breakpoint_core::room::generate_room_code();
         let mut relay = state.write().await;
-        if let Err(e) = relay.create_room(code.clone(), tx) {
+        let code = relay.generate_code();
+        let alias = join.room_alias.as_deref().filter(|a| !a.trim().is_empty());
+        if let Err(e) = relay.create_room_with_alias(code.clone(), tx, alias) {
             tracing::warn!(error = %e, "Failed to create relay room");
             return;
         }
@@ -106,9 +151,9 @@ async fn handle_relay_socket(socket: WebSocket, state: SharedRelayState) {
 
         tracing::info!(room_code = %code, "Relay room created");
 
-        // Forward original JoinRoom to "self" (host processes it locally)
-        // The host doesn't need to receive it back — just start the writer
-        spawn_relay_writer(ws_sender, rx);
+        // The host runs the room itself; it only needs the room code, which
+        // create_room queued for it
+        spawn_relay_writer(ws_sender, rx, &chaos);
 
         // Host read loop
         host_read_loop(&mut ws_receiver, &state, &code).await;
@@ -118,9 +163,9 @@ async fn handle_relay_socket(socket: WebSocket, state: SharedRelayState) {
         relay.destroy_room(&code);
         tracing::info!(room_code = %code, "Relay room destroyed (host disconnected)");
     } else {
-        // Join existing room as client
-        let code = join.room_code.clone();
+        // Join existing room as client, by code or vanity alias
         let mut relay = state.write().await;
+        let code = relay.resolve_room_code(&join.room_code);
         let client_id = match relay.join_room(&code, tx) {
             Ok(id) => id,
             Err(e) => {
@@ -133,12 +178,12 @@ async fn handle_relay_socket(socket: WebSocket, state: SharedRelayState) {
         // Forward the original JoinRoom message to the host
         {
             let relay = state.read().await;
-            relay.relay_to_host(&code, &first_msg);
+            relay.forward_from_client(&code, client_id, &first_msg);
         }
 
         tracing::info!(room_code = %code, client_id, "Client joined relay room");
 
-        spawn_relay_writer(ws_sender, rx);
+        spawn_relay_writer(ws_sender, rx, &chaos);
 
         // Client read loop
         client_read_loop(&mut ws_receiver, &state, &code, client_id).await;
@@ -150,10 +195,18 @@ async fn handle_relay_socket(socket: WebSocket, state: SharedRelayState) {
     }
 }
 
+/// Every message to a peer passes through its writer, so applying chaos here
+/// degrades both host-to-client and client-to-host traffic.
 fn spawn_relay_writer(
     mut ws_sender: futures::stream::SplitSink<WebSocket, Message>,
-    mut rx: mpsc::Receiver<Vec<u8>>,
+    rx: mpsc::Receiver<Vec<u8>>,
+    chaos: &ChaosConfig,
 ) {
+    let mut rx = if chaos.is_active() {
+        spawn_chaos_pipe(ReceiverStream::new(rx), chaos.clone(), 256)
+    } else {
+        rx
+    };
     tokio::spawn(async move {
         while let Some(data) = rx.recv().await {
             if ws_sender.send(Message::Binary(data.into())).await.is_err() {
@@ -195,7 +248,8 @@ impl RateLimiter {
     }
 }
 
-/// Host read loop: messages from host go to all clients.
+/// Host read loop: messages from host go to all clients, or to one client
+/// when wrapped in `RelayToPeer`.
 async fn host_read_loop(
     ws_receiver: &mut futures::stream::SplitStream<WebSocket>,
     state: &SharedRelayState,
@@ -229,12 +283,28 @@ async fn host_read_loop(
         }
 
         // Protocol-agnostic: forward all host messages to clients
-        let relay = state.read().await;
-        relay.relay_to_clients(room_code, &data);
+        let span = tracing::debug_span!(
+            "relay_forward",
+            room = room_code,
+            direction = "to_clients",
+            bytes = data.len()
+        );
+        async {
+            let relay = state.read().await;
+            relay.forward_from_host(room_code, &data);
+        }
+        .instrument(span)
+        .await;
+        tracing::trace!(
+            target: METRICS_TARGET,
+            direction = "to_clients",
+            monotonic_counter.relay_messages = 1_u64
+        );
     }
 }
 
-/// Client read loop: messages from clients go to the host.
+/// Client read loop: messages from clients go to the host, tagged with the
+/// client's ID.
 async fn client_read_loop(
     ws_receiver: &mut futures::stream::SplitStream<WebSocket>,
     state: &SharedRelayState,
@@ -270,7 +340,23 @@ async fn client_read_loop(
         }
 
         // Forward all client messages to the host
-        let relay = state.read().await;
-        relay.relay_to_host(room_code, &data);
+        let span = tracing::debug_span!(
+            "relay_forward",
+            room = room_code,
+            client_id,
+            direction = "to_host",
+            bytes = data.len()
+        );
+        async {
+            let relay = state.read().await;
+            relay.forward_from_client(room_code, client_id, &data);
+        }
+        .instrument(span)
+        .await;
+        tracing::trace!(
+            target: METRICS_TARGET,
+            direction = "to_host",
+            monotonic_counter.relay_messages = 1_u64
+        );
     }
 }
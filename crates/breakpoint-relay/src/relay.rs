@@ -3,14 +3,21 @@ use std::sync::Arc;
 
 use tokio::sync::{RwLock, mpsc};
 
-use breakpoint_core::net::messages::MessageType;
+use breakpoint_core::net::messages::{
+    MessageType, RelayFromPeerMsg, RelayMessage, RelayPeerLeftMsg, RelayRoomCreatedMsg,
+};
+use breakpoint_core::net::protocol::{decode_relay_message, encode_relay_message};
+use breakpoint_core::room::{RoomCodeFormat, normalize_room_alias};
 
 /// A connected client in a relay room.
 struct RelayClient {
     tx: mpsc::Sender<Vec<u8>>,
 }
 
-/// A relay room: first joiner is host, subsequent are clients.
+/// A relay room: first joiner is host, subsequent are clients. Client
+/// messages reach the host wrapped in `RelayFromPeer` so it can tell
+/// senders apart; host messages go to every client unless wrapped in
+/// `RelayToPeer`.
 struct RelayRoom {
     host_tx: mpsc::Sender<Vec<u8>>,
     clients: HashMap<u64, RelayClient>,
@@ -33,8 +40,8 @@ impl RelayRoom {
         id
     }
 
-    fn remove_client(&mut self, id: u64) {
-        self.clients.remove(&id);
+    fn remove_client(&mut self, id: u64) -> bool {
+        self.clients.remove(&id).is_some()
     }
 
     /// Forward message from a client to the host.
@@ -42,16 +49,18 @@ impl RelayRoom {
         let _ = self.host_tx.try_send(data.to_vec());
     }
 
+    fn forward_to_client(&self, id: u64, data: &[u8]) {
+        if let Some(client) = self.clients.get(&id) {
+            let _ = client.tx.try_send(data.to_vec());
+        }
+    }
+
     /// Forward message from the host to all clients.
     fn forward_to_all_clients(&self, data: &[u8]) {
         for client in self.clients.values() {
             let _ = client.tx.try_send(data.to_vec());
         }
     }
-
-    fn is_empty(&self) -> bool {
-        self.clients.is_empty()
-    }
 }
 
 /// Manages all relay rooms.
@@ -60,22 +69,62 @@ pub struct RelayState {
     rooms: HashMap<String, RelayRoom>,
     max_rooms: usize,
     max_clients_per_room: usize,
+    code_format: RoomCodeFormat,
+    /// Maps vanity alias → room code, for rooms whose host chose one.
+    aliases: HashMap<String, String>,
 }
 
 impl RelayState {
     pub fn new(max_rooms: usize) -> Self {
+        Self::with_code_format(max_rooms, RoomCodeFormat::default())
+    }
+
+    pub fn with_code_format(max_rooms: usize, code_format: RoomCodeFormat) -> Self {
         Self {
             rooms: HashMap::new(),
             max_rooms,
             max_clients_per_room: 16,
+            code_format,
+            aliases: HashMap::new(),
+        }
+    }
+
+    /// A room code in the relay's format that no open room uses.
+    pub fn generate_code(&self) -> String {
+        loop {
+            let code = self.code_format.generate();
+            if !self.rooms.contains_key(&code) {
+                return code;
+            }
         }
     }
 
-    /// Create a new room, returning the room code. The creator is the host.
+    /// The room code a client typed to join: the code of the room with
+    /// that vanity alias, or what they typed if no room has it.
+    pub fn resolve_room_code(&self, code_or_alias: &str) -> String {
+        let key = code_or_alias.trim().to_ascii_uppercase();
+        self.aliases
+            .get(&key)
+            .cloned()
+            .unwrap_or_else(|| code_or_alias.to_string())
+    }
+
+    /// Create a new room. The creator is the host and is told the room
+    /// code with a `RelayRoomCreated` message.
     pub fn create_room(
         &mut self,
         code: String,
         host_tx: mpsc::Sender<Vec<u8>>,
+    ) -> Result<(), String> {
+        self.create_room_with_alias(code, host_tx, None)
+    }
+
+    /// Create a new room that clients can also join by `alias`.
+    pub fn create_room_with_alias(
+        &mut self,
+        code: String,
+        host_tx: mpsc::Sender<Vec<u8>>,
+        alias: Option<&str>,
     ) -> Result<(), String> {
         if self.rooms.len() >= self.max_rooms {
             return Err("Maximum room limit reached".to_string());
@@ -83,6 +132,20 @@ impl RelayState {
         if self.rooms.contains_key(&code) {
             return Err("Room already exists".to_string());
         }
+        if let Some(alias) = alias {
+            let alias = normalize_room_alias(alias, &self.code_format)
+                .map_err(|_| format!("Invalid room alias: {alias}"))?;
+            if self.aliases.contains_key(&alias) {
+                return Err("Room alias already in use".to_string());
+            }
+            self.aliases.insert(alias, code.clone());
+        }
+        let created = RelayMessage::RoomCreated(RelayRoomCreatedMsg {
+            room_code: code.clone(),
+        });
+        if let Ok(data) = encode_relay_message(&created) {
+            let _ = host_tx.try_send(data);
+        }
         self.rooms.insert(code, RelayRoom::new(host_tx));
         Ok(())
     }
@@ -99,21 +162,26 @@ impl RelayState {
         Ok(room.add_client(tx))
     }
 
-    /// Remove a client from a room. Returns true if the room was destroyed.
+    /// Remove a client from a room and tell the host. The room stays open
+    /// until the host leaves. Returns true if the client was in the room.
     pub fn leave_room(&mut self, code: &str, client_id: u64) -> bool {
-        if let Some(room) = self.rooms.get_mut(code) {
-            room.remove_client(client_id);
-            if room.is_empty() {
-                self.rooms.remove(code);
-                return true;
-            }
+        let Some(room) = self.rooms.get_mut(code) else {
+            return false;
+        };
+        if !room.remove_client(client_id) {
+            return false;
+        }
+        let left = RelayMessage::PeerLeft(RelayPeerLeftMsg { peer_id: client_id });
+        if let Ok(data) = encode_relay_message(&left) {
+            room.forward_to_host(&data);
         }
-        false
+        true
     }
 
     /// Remove a room entirely (when host disconnects).
     pub fn destroy_room(&mut self, code: &str) {
         self.rooms.remove(code);
+        self.aliases.retain(|_, c| c != code);
     }
 
     /// Forward a message from a client to the host.
@@ -130,6 +198,35 @@ impl RelayState {
         }
     }
 
+    /// Wrap a client's message with its ID and forward it to the host.
+    pub fn forward_from_client(&self, code: &str, client_id: u64, data: &[u8]) {
+        let wrapped = RelayMessage::FromPeer(RelayFromPeerMsg {
+            peer_id: client_id,
+            data: data.to_vec(),
+        });
+        match encode_relay_message(&wrapped) {
+            Ok(wrapped) => self.relay_to_host(code, &wrapped),
+            Err(e) => {
+                tracing::warn!(room = code, client_id, error = %e, "Failed to wrap client message")
+            },
+        }
+    }
+
+    /// Route a host message: `RelayToPeer` envelopes are unwrapped and sent
+    /// to one client, anything else goes to all of them.
+    pub fn forward_from_host(&self, code: &str, data: &[u8]) {
+        let Some(room) = self.rooms.get(code) else {
+            return;
+        };
+        if peek_message_type(data) == Some(MessageType::RelayToPeer) {
+            if let Ok(RelayMessage::ToPeer(msg)) = decode_relay_message(data) {
+                room.forward_to_client(msg.peer_id, &msg.data);
+            }
+            return;
+        }
+        room.forward_to_all_clients(data);
+    }
+
     pub fn room_exists(&self, code: &str) -> bool {
         self.rooms.contains_key(code)
     }
@@ -211,8 +308,9 @@ mod tests {
         let cid = state.join_room("ABCD-1234", client_tx).unwrap();
 
         // Remove the only client — room still exists (host is still there)
-        let destroyed = state.leave_room("ABCD-1234", cid);
-        assert!(destroyed); // empty of clients, removed
+        assert!(state.leave_room("ABCD-1234", cid));
+        assert!(state.room_exists("ABCD-1234"));
+        assert!(!state.leave_room("ABCD-1234", cid), "Already gone");
     }
 
     #[test]
@@ -224,11 +322,94 @@ mod tests {
         let (client_tx, _client_rx) = mpsc::channel(256);
         let _cid = state.join_room("ABCD-1234", client_tx).unwrap();
 
+        let created = decode_relay_message(&host_rx.try_recv().unwrap()).unwrap();
+        assert_eq!(
+            created,
+            RelayMessage::RoomCreated(RelayRoomCreatedMsg {
+                room_code: "ABCD-1234".to_string()
+            })
+        );
+
         state.relay_to_host("ABCD-1234", &[0x01, 0x02, 0x03]);
         let received = host_rx.try_recv().unwrap();
         assert_eq!(received, vec![0x01, 0x02, 0x03]);
     }
 
+    #[test]
+    fn host_sees_client_ids_and_can_unicast() {
+        use breakpoint_core::net::messages::RelayToPeerMsg;
+
+        let mut state = RelayState::new(10);
+        let (host_tx, mut host_rx) = mpsc::channel(256);
+        state.create_room("ABCD-1234".to_string(), host_tx).unwrap();
+        let _ = host_rx.try_recv(); // RelayRoomCreated
+        let (tx1, mut rx1) = mpsc::channel(256);
+        let id1 = state.join_room("ABCD-1234", tx1).unwrap();
+        let (tx2, mut rx2) = mpsc::channel(256);
+        let id2 = state.join_room("ABCD-1234", tx2).unwrap();
+
+        state.forward_from_client("ABCD-1234", id2, &[0x01, 0x07]);
+        assert_eq!(
+            decode_relay_message(&host_rx.try_recv().unwrap()).unwrap(),
+            RelayMessage::FromPeer(RelayFromPeerMsg {
+                peer_id: id2,
+                data: vec![0x01, 0x07],
+            })
+        );
+
+        let to_first = encode_relay_message(&RelayMessage::ToPeer(RelayToPeerMsg {
+            peer_id: id1,
+            data: vec![0x06, 0x01],
+        }))
+        .unwrap();
+        state.forward_from_host("ABCD-1234", &to_first);
+        assert_eq!(rx1.try_recv().unwrap(), vec![0x06, 0x01]);
+        assert!(rx2.try_recv().is_err(), "Unicast reaches one client");
+
+        state.forward_from_host("ABCD-1234", &[0x10, 0x00]);
+        assert_eq!(rx1.try_recv().unwrap(), vec![0x10, 0x00]);
+        assert_eq!(rx2.try_recv().unwrap(), vec![0x10, 0x00]);
+
+        state.leave_room("ABCD-1234", id1);
+        assert_eq!(
+            decode_relay_message(&host_rx.try_recv().unwrap()).unwrap(),
+            RelayMessage::PeerLeft(RelayPeerLeftMsg { peer_id: id1 })
+        );
+    }
+
+    #[test]
+    fn sealed_frames_pass_through_unchanged() {
+        use breakpoint_core::net::messages::RelayToPeerMsg;
+
+        let mut state = RelayState::new(10);
+        let (host_tx, mut host_rx) = mpsc::channel(256);
+        state.create_room("ABCD-1234".to_string(), host_tx).unwrap();
+        let _ = host_rx.try_recv(); // RelayRoomCreated
+        let (tx, mut rx) = mpsc::channel(256);
+        let id = state.join_room("ABCD-1234", tx).unwrap();
+
+        // The relay routes end-to-end encrypted traffic without reading it
+        let sealed = vec![MessageType::Sealed as u8, 0x9e, 0x42, 0x17];
+        state.forward_from_host("ABCD-1234", &sealed);
+        assert_eq!(rx.try_recv().unwrap(), sealed);
+        let to_peer = encode_relay_message(&RelayMessage::ToPeer(RelayToPeerMsg {
+            peer_id: id,
+            data: sealed.clone(),
+        }))
+        .unwrap();
+        state.forward_from_host("ABCD-1234", &to_peer);
+        assert_eq!(rx.try_recv().unwrap(), sealed);
+
+        state.forward_from_client("ABCD-1234", id, &sealed);
+        assert_eq!(
+            decode_relay_message(&host_rx.try_recv().unwrap()).unwrap(),
+            RelayMessage::FromPeer(RelayFromPeerMsg {
+                peer_id: id,
+                data: sealed,
+            })
+        );
+    }
+
     #[test]
     fn forward_to_clients() {
         let mut state = RelayState::new(10);
@@ -307,6 +488,47 @@ mod tests {
         assert!(result.is_err(), "Duplicate room code should be rejected");
     }
 
+    #[test]
+    fn aliases_route_to_their_room_until_it_closes() {
+        let mut state = RelayState::with_code_format(
+            10,
+            RoomCodeFormat {
+                length: 10,
+                alphabet: String::new(),
+            },
+        );
+        let code = state.generate_code();
+        assert_eq!(code.len(), 12);
+        let (tx1, _rx1) = mpsc::channel(256);
+        state
+            .create_room_with_alias(code.clone(), tx1, Some("game-night"))
+            .unwrap();
+        assert_eq!(state.resolve_room_code("Game-Night"), code);
+
+        let (tx2, _rx2) = mpsc::channel(256);
+        let other = state.generate_code();
+        assert!(
+            state
+                .create_room_with_alias(other.clone(), tx2.clone(), Some("GAME-NIGHT"))
+                .is_err(),
+            "Aliases are unique"
+        );
+        assert!(
+            state
+                .create_room_with_alias(other.clone(), tx2, Some("relay"))
+                .is_err(),
+            "Reserved words are blocked"
+        );
+        assert!(!state.room_exists(&other));
+
+        let (tx3, _rx3) = mpsc::channel(256);
+        let joined = state.resolve_room_code("game-night");
+        assert_eq!(state.join_room(&joined, tx3).unwrap(), 1);
+
+        state.destroy_room(&code);
+        assert_eq!(state.resolve_room_code("GAME-NIGHT"), "GAME-NIGHT");
+    }
+
     #[test]
     fn room_count_tracking() {
         let mut state = RelayState::new(10);
@@ -335,8 +557,8 @@ mod tests {
     #[test]
     fn leave_nonexistent_room_returns_false() {
         let mut state = RelayState::new(10);
-        let destroyed = state.leave_room("NOPE-0000", 1);
-        assert!(!destroyed, "Leaving non-existent room should return false");
+        let left = state.leave_room("NOPE-0000", 1);
+        assert!(!left, "Leaving non-existent room should return false");
     }
 
     #[test]
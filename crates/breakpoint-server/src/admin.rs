@@ -0,0 +1,432 @@
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::Json;
+use serde::{Deserialize, Serialize};
+
+use breakpoint_core::announcement::Announcement;
+use breakpoint_core::net::messages::RoomReshape;
+
+use crate::announcements::{self, AnnouncementRequest, MotdRequest};
+use crate::client_reports::ClientReportSummary;
+use crate::content::{self, ContentItem, ContentKind};
+use crate::error::AppError;
+use crate::moderation::{AuditEntry, Ban, BanRequest, MODERATOR_HEADER};
+use crate::room_manager::{RoomInputStats, RoomMemoryStats};
+use crate::room_webhooks::{RoomWebhook, RoomWebhookRequest};
+use crate::state::AppState;
+
+/// Response for the input statistics admin endpoint.
+#[derive(Debug, Serialize)]
+pub struct InputStatsResponse {
+    pub rooms: Vec<RoomInputStats>,
+}
+
+/// GET /api/v1/admin/input-stats — per-player input rates and anti-bot
+/// heuristic violations for every room that has run a game.
+pub async fn get_input_stats(State(state): State<AppState>) -> Json<InputStatsResponse> {
+    let rooms = state.rooms.read().await;
+    Json(InputStatsResponse {
+        rooms: rooms.input_stats(),
+    })
+}
+
+/// Response for the memory accounting admin endpoint.
+#[derive(Debug, Serialize)]
+pub struct MemoryResponse {
+    pub rooms: Vec<RoomMemoryStats>,
+}
+
+/// GET /api/v1/admin/memory — what each room running a game holds, by
+/// state, walls, trails, outbound backlog and replay, largest first.
+pub async fn get_memory(State(state): State<AppState>) -> Json<MemoryResponse> {
+    let rooms = state.rooms.read().await;
+    Json(MemoryResponse {
+        rooms: rooms.memory_stats(),
+    })
+}
+
+/// GET /api/v1/admin/telemetry — opt-in client crash and error reports,
+/// grouped by kind and message, most frequent first.
+pub async fn get_client_reports(State(state): State<AppState>) -> Json<ClientReportSummary> {
+    Json(state.client_reports.read().await.summary())
+}
+
+/// Response for the announcement list endpoint.
+#[derive(Debug, Serialize)]
+pub struct AnnouncementsResponse {
+    pub announcements: Vec<Announcement>,
+}
+
+/// Response for the MOTD endpoint. `None` when the MOTD was cleared.
+#[derive(Debug, Serialize)]
+pub struct MotdResponse {
+    pub motd: Option<Announcement>,
+}
+
+/// POST /api/v1/admin/announcements — schedule a global announcement.
+pub async fn post_announcement(
+    State(state): State<AppState>,
+    Json(body): Json<AnnouncementRequest>,
+) -> Result<(StatusCode, Json<Announcement>), AppError> {
+    let now = breakpoint_core::time::unix_now();
+    let announcement = state
+        .announcements
+        .write()
+        .await
+        .create(body, now)
+        .map_err(AppError::BadRequest)?;
+    tracing::info!(
+        announcement_id = %announcement.id,
+        severity = ?announcement.severity,
+        "Announcement created"
+    );
+    announcements::publish(&state).await;
+    Ok((StatusCode::CREATED, Json(announcement)))
+}
+
+/// GET /api/v1/admin/announcements — MOTD and all announcements, including
+/// ones that have not started yet.
+pub async fn list_announcements(State(state): State<AppState>) -> Json<AnnouncementsResponse> {
+    Json(AnnouncementsResponse {
+        announcements: state.announcements.read().await.all(),
+    })
+}
+
+/// DELETE /api/v1/admin/announcements/{id} — withdraw an announcement
+/// (`motd` clears the message of the day).
+pub async fn delete_announcement(
+    State(state): State<AppState>,
+    Path(announcement_id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    if !state.announcements.write().await.remove(&announcement_id) {
+        return Err(AppError::NotFound(format!(
+            "Announcement {announcement_id} not found"
+        )));
+    }
+    announcements::publish(&state).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// PUT /api/v1/admin/motd — replace the message of the day (empty clears it).
+pub async fn put_motd(
+    State(state): State<AppState>,
+    Json(body): Json<MotdRequest>,
+) -> Result<Json<MotdResponse>, AppError> {
+    let motd = state
+        .announcements
+        .write()
+        .await
+        .set_motd(&body.message, body.severity)
+        .map_err(AppError::BadRequest)?;
+    announcements::publish(&state).await;
+    Ok(Json(MotdResponse { motd }))
+}
+
+/// Response for the ban list endpoint.
+#[derive(Debug, Serialize)]
+pub struct BansResponse {
+    pub bans: Vec<Ban>,
+}
+
+/// Response for the moderation audit endpoint.
+#[derive(Debug, Serialize)]
+pub struct AuditResponse {
+    pub entries: Vec<AuditEntry>,
+}
+
+/// The moderator named in `X-Moderator`, for the audit trail.
+fn moderator(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(MODERATOR_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.trim().chars().take(64).collect::<String>())
+        .filter(|v| !v.is_empty())
+}
+
+/// POST /api/v1/admin/bans — ban an identity or IP, for a while or for
+/// good. Takes effect on the target's next join or reconnect.
+pub async fn post_ban(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<BanRequest>,
+) -> Result<(StatusCode, Json<Ban>), AppError> {
+    let now = breakpoint_core::time::unix_now();
+    let ban = state
+        .bans
+        .write()
+        .await
+        .add(body, moderator(&headers), now)
+        .map_err(AppError::BadRequest)?;
+    Ok((StatusCode::CREATED, Json(ban)))
+}
+
+/// GET /api/v1/admin/bans — bans still in force.
+pub async fn list_bans(State(state): State<AppState>) -> Json<BansResponse> {
+    let now = breakpoint_core::time::unix_now();
+    Json(BansResponse {
+        bans: state.bans.read().await.active(now),
+    })
+}
+
+/// DELETE /api/v1/admin/bans/{id} — lift a ban.
+pub async fn delete_ban(
+    State(state): State<AppState>,
+    Path(ban_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<StatusCode, AppError> {
+    let now = breakpoint_core::time::unix_now();
+    if !state
+        .bans
+        .write()
+        .await
+        .remove(&ban_id, moderator(&headers), now)
+    {
+        return Err(AppError::NotFound(format!("Ban {ban_id} not found")));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /api/v1/admin/moderation/audit — recent bans, unbans, and refused
+/// connections, newest first.
+pub async fn get_moderation_audit(State(state): State<AppState>) -> Json<AuditResponse> {
+    Json(AuditResponse {
+        entries: state.bans.read().await.audit(),
+    })
+}
+
+/// Response for the user-generated content endpoint.
+#[derive(Debug, Serialize)]
+pub struct ContentResponse {
+    pub items: Vec<ContentItem>,
+    pub total_bytes: usize,
+    pub max_total_bytes: usize,
+}
+
+/// GET /api/v1/admin/content — every piece of user-generated content the
+/// server holds, with its owner and size.
+pub async fn list_content(State(state): State<AppState>) -> Json<ContentResponse> {
+    let items = content::inventory(&state).await;
+    Json(ContentResponse {
+        total_bytes: items.iter().map(|item| item.bytes).sum(),
+        max_total_bytes: state.config.content.max_total_bytes,
+        items,
+    })
+}
+
+/// DELETE /api/v1/admin/content/{kind}/{id} — delete one piece of
+/// user-generated content. Deleting an alias leaves its room open.
+pub async fn delete_content(
+    State(state): State<AppState>,
+    Path((kind, content_id)): Path<(String, String)>,
+) -> Result<StatusCode, AppError> {
+    let kind = ContentKind::parse(&kind)
+        .ok_or_else(|| AppError::BadRequest(format!("Unknown content kind {kind}")))?;
+    if !content::remove(&state, kind, &content_id).await {
+        return Err(AppError::NotFound(format!(
+            "No {kind:?} content {content_id}"
+        )));
+    }
+    tracing::info!(?kind, content_id, "User content deleted");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Response for the room webhook list endpoint.
+#[derive(Debug, Serialize)]
+pub struct RoomWebhooksResponse {
+    pub hooks: Vec<RoomWebhook>,
+}
+
+/// POST /api/v1/admin/room-webhooks — register an outbound webhook for
+/// room lifecycle events.
+pub async fn post_room_webhook(
+    State(state): State<AppState>,
+    Json(body): Json<RoomWebhookRequest>,
+) -> Result<(StatusCode, Json<RoomWebhook>), AppError> {
+    let hook = state
+        .room_webhooks
+        .write()
+        .await
+        .create(body)
+        .map_err(AppError::BadRequest)?;
+    tracing::info!(hook_id = %hook.id, url = %hook.url, "Room webhook registered");
+    Ok((StatusCode::CREATED, Json(hook)))
+}
+
+/// GET /api/v1/admin/room-webhooks — registered room webhooks, without
+/// their secrets.
+pub async fn list_room_webhooks(State(state): State<AppState>) -> Json<RoomWebhooksResponse> {
+    Json(RoomWebhooksResponse {
+        hooks: state.room_webhooks.read().await.all(),
+    })
+}
+
+/// DELETE /api/v1/admin/room-webhooks/{id} — unregister a room webhook.
+pub async fn delete_room_webhook(
+    State(state): State<AppState>,
+    Path(hook_id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    if !state.room_webhooks.write().await.remove(&hook_id) {
+        return Err(AppError::NotFound(format!(
+            "Room webhook {hook_id} not found"
+        )));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Body for the room merge endpoint.
+#[derive(Debug, Deserialize)]
+pub struct MergeRoomRequest {
+    /// Code (or alias) of the room to merge into.
+    pub into: String,
+}
+
+/// Response for the room merge and split endpoints.
+#[derive(Debug, Serialize)]
+pub struct ReshapeRoomResponse {
+    /// Room the offered players would move to.
+    pub target_room: String,
+    /// Players who were offered the move.
+    pub offered: usize,
+}
+
+/// POST /api/v1/admin/rooms/{code}/merge — offer a lobby's players a move
+/// into another room, even one that approves its joins.
+pub async fn merge_room(
+    State(state): State<AppState>,
+    Path(room_code): Path<String>,
+    Json(body): Json<MergeRoomRequest>,
+) -> Result<Json<ReshapeRoomResponse>, AppError> {
+    reshape_room(&state, &room_code, RoomReshape::MergeInto(body.into)).await
+}
+
+/// POST /api/v1/admin/rooms/{code}/split — offer half of a lobby a move
+/// into a new room, balancing their session scores.
+pub async fn split_room(
+    State(state): State<AppState>,
+    Path(room_code): Path<String>,
+) -> Result<Json<ReshapeRoomResponse>, AppError> {
+    reshape_room(&state, &room_code, RoomReshape::Split).await
+}
+
+async fn reshape_room(
+    state: &AppState,
+    room_code: &str,
+    action: RoomReshape,
+) -> Result<Json<ReshapeRoomResponse>, AppError> {
+    let mut rooms = state.rooms.write().await;
+    let room_code = rooms.resolve_room_code(room_code);
+    if rooms.get_room_state(&room_code).is_none() {
+        return Err(AppError::NotFound(format!("Room {room_code} not found")));
+    }
+    let (target_room, offered) = rooms
+        .reshape_room(&room_code, None, action)
+        .map_err(AppError::BadRequest)?;
+    Ok(Json(ReshapeRoomResponse {
+        target_room,
+        offered,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ServerConfig;
+    use breakpoint_core::announcement::AnnouncementSeverity;
+    use breakpoint_core::player::PlayerColor;
+
+    #[tokio::test]
+    async fn input_stats_lists_rooms_with_sessions() {
+        let state = AppState::new(ServerConfig::default());
+        let (tx, _rx) = crate::outbound::channel(16);
+        let code = {
+            let mut rooms = state.rooms.write().await;
+            let (code, host_id, _) =
+                rooms.create_room("Alice".to_string(), PlayerColor::default(), tx.clone());
+            rooms.create_room("Bob".to_string(), PlayerColor::default(), tx);
+            rooms
+                .start_game(
+                    &code,
+                    breakpoint_core::net::messages::RequestGameStartMsg {
+                        game_name: "mini-golf".to_string(),
+                        custom: Default::default(),
+                        win_condition: None,
+                        pacing: None,
+                        featured: false,
+                    },
+                    host_id,
+                    &state.game_registry,
+                    std::sync::Arc::clone(&state.rooms),
+                )
+                .unwrap();
+            code
+        };
+
+        let json = get_input_stats(State(state.clone())).await;
+        assert_eq!(json.rooms.len(), 1, "Only rooms with a game report stats");
+        assert_eq!(json.rooms[0].room_code, code);
+
+        let json = get_memory(State(state)).await;
+        assert_eq!(json.rooms.len(), 1);
+        assert_eq!(json.rooms[0].room_code, code);
+    }
+
+    fn announcement_request(message: &str) -> AnnouncementRequest {
+        AnnouncementRequest {
+            message: message.to_string(),
+            severity: AnnouncementSeverity::Critical,
+            starts_at: None,
+            ends_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn announcement_lifecycle() {
+        let state = AppState::new(ServerConfig::default());
+        let body = announcement_request("Server restart in 10 minutes");
+        let (status, Json(created)) = post_announcement(State(state.clone()), Json(body))
+            .await
+            .unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+
+        let motd = MotdRequest {
+            message: "Welcome!".to_string(),
+            severity: AnnouncementSeverity::Info,
+        };
+        let Json(resp) = put_motd(State(state.clone()), Json(motd)).await.unwrap();
+        assert_eq!(resp.motd.unwrap().id, announcements::MOTD_ID);
+
+        let Json(all) = list_announcements(State(state.clone())).await;
+        assert_eq!(all.announcements.len(), 2);
+
+        let status = delete_announcement(State(state.clone()), Path(created.id.clone()))
+            .await
+            .unwrap();
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let result = delete_announcement(State(state), Path(created.id)).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn split_needs_an_existing_full_enough_lobby() {
+        let state = AppState::new(ServerConfig::default());
+        let result = split_room(State(state.clone()), Path("ZZZZ".to_string())).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let (tx, _rx) = crate::outbound::channel(16);
+        let (code, ..) =
+            state
+                .rooms
+                .write()
+                .await
+                .create_room("Alice".to_string(), PlayerColor::default(), tx);
+        let result = split_room(State(state), Path(code)).await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn empty_announcement_rejected() {
+        let state = AppState::new(ServerConfig::default());
+        let result = post_announcement(State(state), Json(announcement_request("  "))).await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+}
@@ -0,0 +1,282 @@
+use serde::Deserialize;
+
+use breakpoint_core::announcement::{Announcement, AnnouncementSeverity};
+use breakpoint_core::net::messages::{AnnouncementsMsg, ServerMessage};
+use breakpoint_core::net::protocol::encode_server_message;
+
+use crate::config::AnnouncementsConfig;
+use crate::room_manager::Lane;
+use crate::state::AppState;
+
+/// ID reserved for the message of the day.
+pub const MOTD_ID: &str = "motd";
+/// Longest announcement text accepted, in characters.
+const MAX_MESSAGE_CHARS: usize = 500;
+
+/// Request body for `POST /api/v1/admin/announcements`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AnnouncementRequest {
+    pub message: String,
+    #[serde(default)]
+    pub severity: AnnouncementSeverity,
+    /// Unix seconds to start showing. Defaults to now.
+    #[serde(default)]
+    pub starts_at: Option<u64>,
+    /// Unix seconds to stop showing. Defaults to never (until deleted).
+    #[serde(default)]
+    pub ends_at: Option<u64>,
+}
+
+/// Request body for `PUT /api/v1/admin/motd`. An empty message clears it.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MotdRequest {
+    pub message: String,
+    #[serde(default)]
+    pub severity: AnnouncementSeverity,
+}
+
+/// Operator announcements plus the message of the day.
+///
+/// Remembers the last set published to players so the broadcaster only
+/// sends when the active set changes (created, deleted, started, ended).
+#[derive(Debug)]
+pub struct AnnouncementStore {
+    motd: Option<Announcement>,
+    scheduled: Vec<Announcement>,
+    max_announcements: usize,
+    next_id: u64,
+    published: Vec<Announcement>,
+}
+
+fn validate_message(message: &str) -> Result<String, String> {
+    let message = message.trim();
+    if message.is_empty() {
+        return Err("message must not be empty".to_string());
+    }
+    if message.chars().count() > MAX_MESSAGE_CHARS {
+        return Err(format!(
+            "message must be at most {MAX_MESSAGE_CHARS} characters"
+        ));
+    }
+    if message.chars().any(char::is_control) {
+        return Err("message must not contain control characters".to_string());
+    }
+    Ok(message.to_string())
+}
+
+impl AnnouncementStore {
+    pub fn new(config: &AnnouncementsConfig) -> Self {
+        let mut store = Self {
+            motd: None,
+            scheduled: Vec::new(),
+            max_announcements: config.max_announcements,
+            next_id: 1,
+            published: Vec::new(),
+        };
+        if let Some(ref motd) = config.motd
+            && let Err(e) = store.set_motd(motd, config.motd_severity)
+        {
+            tracing::warn!(error = %e, "Ignoring invalid configured MOTD");
+        }
+        store
+    }
+
+    /// Replace the message of the day. An empty message clears it.
+    pub fn set_motd(
+        &mut self,
+        message: &str,
+        severity: AnnouncementSeverity,
+    ) -> Result<Option<Announcement>, String> {
+        if message.trim().is_empty() {
+            self.motd = None;
+            return Ok(None);
+        }
+        let motd = Announcement {
+            id: MOTD_ID.to_string(),
+            message: validate_message(message)?,
+            severity,
+            starts_at: 0,
+            ends_at: None,
+        };
+        self.motd = Some(motd.clone());
+        Ok(Some(motd))
+    }
+
+    /// Schedule an announcement.
+    pub fn create(&mut self, req: AnnouncementRequest, now: u64) -> Result<Announcement, String> {
+        let message = validate_message(&req.message)?;
+        let starts_at = req.starts_at.unwrap_or(now);
+        if let Some(end) = req.ends_at {
+            if end <= starts_at {
+                return Err("ends_at must be after starts_at".to_string());
+            }
+            if end <= now {
+                return Err("ends_at must be in the future".to_string());
+            }
+        }
+        if self.scheduled.len() >= self.max_announcements {
+            return Err(format!(
+                "too many announcements (max {})",
+                self.max_announcements
+            ));
+        }
+        let announcement = Announcement {
+            id: format!("ann-{}", self.next_id),
+            message,
+            severity: req.severity,
+            starts_at,
+            ends_at: req.ends_at,
+        };
+        self.next_id += 1;
+        self.scheduled.push(announcement.clone());
+        Ok(announcement)
+    }
+
+    /// Remove an announcement, or clear the MOTD when given [`MOTD_ID`].
+    pub fn remove(&mut self, id: &str) -> bool {
+        if id == MOTD_ID {
+            return self.motd.take().is_some();
+        }
+        let before = self.scheduled.len();
+        self.scheduled.retain(|a| a.id != id);
+        self.scheduled.len() != before
+    }
+
+    /// Every announcement including future ones, MOTD first.
+    pub fn all(&self) -> Vec<Announcement> {
+        let mut out: Vec<Announcement> = self.motd.iter().cloned().collect();
+        let mut scheduled = self.scheduled.clone();
+        scheduled.sort_by_key(|a| a.starts_at);
+        out.extend(scheduled);
+        out
+    }
+
+    /// Announcements players should see now, most severe first.
+    pub fn active(&self, now: u64) -> Vec<Announcement> {
+        let mut out: Vec<Announcement> = self
+            .all()
+            .into_iter()
+            .filter(|a| a.is_active(now))
+            .collect();
+        // Stable sort keeps the MOTD ahead of equally severe announcements
+        out.sort_by_key(|a| std::cmp::Reverse(a.severity));
+        out
+    }
+
+    /// Drop expired announcements and return the active set if it differs
+    /// from what was last published.
+    pub fn take_changes(&mut self, now: u64) -> Option<Vec<Announcement>> {
+        self.scheduled.retain(|a| !a.is_expired(now));
+        let active = self.active(now);
+        if active == self.published {
+            return None;
+        }
+        self.published.clone_from(&active);
+        Some(active)
+    }
+}
+
+/// Encode the active announcement set for the wire.
+pub fn encode_announcements(announcements: Vec<Announcement>) -> Option<Vec<u8>> {
+    let msg = ServerMessage::Announcements(AnnouncementsMsg { announcements });
+    match encode_server_message(&msg) {
+        Ok(data) => Some(data),
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to encode announcements");
+            None
+        },
+    }
+}
+
+/// Broadcast the active set to every room if it changed since last time.
+pub async fn publish(state: &AppState) {
+    let now = breakpoint_core::time::unix_now();
+    let Some(active) = state.announcements.write().await.take_changes(now) else {
+        return;
+    };
+    tracing::info!(count = active.len(), "Publishing announcements");
+    if let Some(data) = encode_announcements(active) {
+        let rooms = state.rooms.read().await;
+        rooms.broadcast_to_all_rooms(Lane::Control, &data);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> AnnouncementStore {
+        AnnouncementStore::new(&AnnouncementsConfig::default())
+    }
+
+    fn request(message: &str, starts_at: Option<u64>, ends_at: Option<u64>) -> AnnouncementRequest {
+        AnnouncementRequest {
+            message: message.to_string(),
+            severity: AnnouncementSeverity::Warning,
+            starts_at,
+            ends_at,
+        }
+    }
+
+    #[test]
+    fn create_validates_input() {
+        let mut s = store();
+        assert!(s.create(request("  ", None, None), 100).is_err());
+        assert!(s.create(request("bad\u{7}", None, None), 100).is_err());
+        assert!(s.create(request("x", Some(200), Some(150)), 100).is_err());
+        assert!(s.create(request("x", None, Some(50)), 100).is_err());
+        let a = s
+            .create(request(" Restart at 5 ", None, None), 100)
+            .unwrap();
+        assert_eq!(a.message, "Restart at 5");
+        assert_eq!(a.starts_at, 100);
+    }
+
+    #[test]
+    fn configured_motd_is_active() {
+        let config = AnnouncementsConfig {
+            motd: Some("Welcome!".to_string()),
+            ..AnnouncementsConfig::default()
+        };
+        let s = AnnouncementStore::new(&config);
+        let active = s.active(0);
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].id, MOTD_ID);
+    }
+
+    #[test]
+    fn active_sorted_by_severity() {
+        let mut s = store();
+        s.set_motd("Welcome", AnnouncementSeverity::Info).unwrap();
+        let mut req = request("Down soon", None, None);
+        req.severity = AnnouncementSeverity::Critical;
+        s.create(req, 100).unwrap();
+        let active = s.active(100);
+        assert_eq!(active[0].severity, AnnouncementSeverity::Critical);
+        assert_eq!(active[1].id, MOTD_ID);
+    }
+
+    #[test]
+    fn take_changes_follows_schedule() {
+        let mut s = store();
+        s.create(request("Tonight", Some(200), Some(300)), 100)
+            .unwrap();
+        assert_eq!(s.take_changes(100), None, "nothing active yet");
+        assert_eq!(s.take_changes(200).unwrap().len(), 1, "window opened");
+        assert_eq!(s.take_changes(250), None, "unchanged");
+        assert_eq!(s.take_changes(300), Some(vec![]), "window closed");
+        assert!(s.all().is_empty(), "expired announcement pruned");
+    }
+
+    #[test]
+    fn remove_and_clear_motd() {
+        let mut s = store();
+        s.set_motd("Welcome", AnnouncementSeverity::Info).unwrap();
+        let a = s.create(request("Hi", None, None), 100).unwrap();
+        assert!(s.remove(&a.id));
+        assert!(!s.remove(&a.id));
+        assert!(s.remove(MOTD_ID));
+        assert!(s.active(100).is_empty());
+        assert_eq!(s.set_motd("", AnnouncementSeverity::Info), Ok(None));
+    }
+}
@@ -1,12 +1,14 @@
 use axum::extract::State;
-use axum::http::StatusCode;
-use axum::response::Json;
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Json};
 use serde::{Deserialize, Serialize};
 
 use breakpoint_core::events::Event;
 
 use crate::error::AppError;
+use crate::schedule::{ScheduleRequest, ScheduleSummary, ScheduledSession};
 use crate::state::AppState;
+use crate::status::ServerStatus;
 
 /// Request body for posting a single event.
 #[derive(Debug, Deserialize)]
@@ -152,9 +154,11 @@ pub async fn claim_event(
     }
 }
 
-/// Status response.
+/// Status response: server health plus the event store's contents.
 #[derive(Debug, Serialize)]
 pub struct StatusResponse {
+    #[serde(flatten)]
+    pub server: ServerStatus,
     pub stats: crate::event_store::EventStoreStats,
     pub recent_events: Vec<EventSummary>,
     pub pending_actions: Vec<EventSummary>,
@@ -183,8 +187,9 @@ impl From<&crate::event_store::StoredEvent> for EventSummary {
     }
 }
 
-/// GET /api/v1/status — returns pending actions, recent events, stats.
+/// GET /api/v1/status — server health, pending actions, recent events, stats.
 pub async fn get_status(State(state): State<AppState>) -> Json<StatusResponse> {
+    let server = ServerStatus::collect(&state).await;
     let store = state.event_store.read().await;
     let stats = store.stats();
 
@@ -201,12 +206,97 @@ pub async fn get_status(State(state): State<AppState>) -> Json<StatusResponse> {
         .collect();
 
     Json(StatusResponse {
+        server,
         stats,
         recent_events,
         pending_actions,
     })
 }
 
+/// Response listing scheduled game nights.
+#[derive(Debug, Serialize)]
+pub struct SchedulesResponse<T> {
+    pub schedules: Vec<T>,
+}
+
+/// POST /api/v1/schedules — schedule a game night.
+pub async fn post_schedule(
+    State(state): State<AppState>,
+    Json(body): Json<ScheduleRequest>,
+) -> Result<(StatusCode, Json<ScheduledSession>), AppError> {
+    let now = breakpoint_core::time::unix_now();
+    let mut schedules = state.schedules.write().await;
+    let session = schedules.create(body, now).map_err(AppError::BadRequest)?;
+    tracing::info!(
+        schedule_id = %session.id,
+        start_at = session.start_at,
+        "Game night scheduled"
+    );
+    Ok((StatusCode::CREATED, Json(session)))
+}
+
+/// GET /api/v1/schedules — all pending schedules, including invitees.
+pub async fn list_schedules(
+    State(state): State<AppState>,
+) -> Json<SchedulesResponse<ScheduledSession>> {
+    let now = breakpoint_core::time::unix_now();
+    let schedules = state.schedules.read().await;
+    Json(SchedulesResponse {
+        schedules: schedules.upcoming(now).into_iter().cloned().collect(),
+    })
+}
+
+/// DELETE /api/v1/schedules/:schedule_id — cancel a schedule.
+pub async fn delete_schedule(
+    State(state): State<AppState>,
+    axum::extract::Path(schedule_id): axum::extract::Path<String>,
+) -> Result<StatusCode, AppError> {
+    if state.schedules.write().await.remove(&schedule_id) {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(AppError::NotFound(format!(
+            "Schedule {schedule_id} not found"
+        )))
+    }
+}
+
+/// GET /api/v1/public/schedules — upcoming sessions for the lobby (no auth).
+pub async fn list_public_schedules(
+    State(state): State<AppState>,
+) -> Json<SchedulesResponse<ScheduleSummary>> {
+    let now = breakpoint_core::time::unix_now();
+    let schedules = state.schedules.read().await;
+    Json(SchedulesResponse {
+        schedules: schedules
+            .upcoming(now)
+            .into_iter()
+            .map(ScheduleSummary::from)
+            .collect(),
+    })
+}
+
+/// GET /api/v1/public/schedules/:schedule_id/ics — calendar file download.
+pub async fn get_schedule_ics(
+    State(state): State<AppState>,
+    axum::extract::Path(schedule_id): axum::extract::Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let schedules = state.schedules.read().await;
+    let session = schedules
+        .get(&schedule_id)
+        .ok_or_else(|| AppError::NotFound(format!("Schedule {schedule_id} not found")))?;
+    let ics = session.to_ics(breakpoint_core::time::unix_now());
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"game-night.ics\"",
+            ),
+        ],
+        ics,
+    ))
+}
+
 /// GET /api/v1/profile — returns profiling stats (only available with `profiling` feature).
 #[cfg(feature = "profiling")]
 pub async fn get_profile() -> Json<breakpoint_core::profiling::ProfileReport> {
@@ -349,6 +439,8 @@ mod tests {
         }
 
         let json = get_status(State(state)).await;
+        assert_eq!(json.server.status, "ok");
+        assert_eq!(json.server.backlog.pending_actions, 1);
         assert_eq!(json.stats.total_stored, 2);
         assert_eq!(json.stats.total_pending_actions, 1);
         assert_eq!(json.recent_events.len(), 2);
@@ -406,4 +498,53 @@ mod tests {
         let result = validate_event_fields(&event);
         assert!(result.is_ok(), "Valid event should pass validation");
     }
+
+    fn schedule_request(start_at: u64) -> ScheduleRequest {
+        ScheduleRequest {
+            title: "Friday golf".to_string(),
+            start_at,
+            games: vec!["mini-golf".to_string()],
+            host: Some("alice".to_string()),
+            invitees: vec!["bob".to_string()],
+            duration_minutes: None,
+            reminder_minutes: None,
+            notify_url: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn schedule_create_list_and_cancel() {
+        let state = AppState::new(ServerConfig::default());
+        let start = breakpoint_core::time::unix_now() + 3600;
+        let (status, Json(session)) =
+            post_schedule(State(state.clone()), Json(schedule_request(start)))
+                .await
+                .unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+
+        let Json(public) = list_public_schedules(State(state.clone())).await;
+        assert_eq!(public.schedules.len(), 1);
+        assert_eq!(public.schedules[0].id, session.id);
+
+        let ics = get_schedule_ics(
+            State(state.clone()),
+            axum::extract::Path(session.id.clone()),
+        )
+        .await;
+        assert!(ics.is_ok());
+
+        let status = delete_schedule(State(state.clone()), axum::extract::Path(session.id))
+            .await
+            .unwrap();
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let Json(all) = list_schedules(State(state)).await;
+        assert!(all.schedules.is_empty());
+    }
+
+    #[tokio::test]
+    async fn schedule_in_the_past_rejected() {
+        let state = AppState::new(ServerConfig::default());
+        let result = post_schedule(State(state), Json(schedule_request(1_000))).await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
 }
@@ -0,0 +1,81 @@
+//! The tick/ack flow for external bots. A bot connection sees every
+//! `GameState` like a browser does and answers with a `BotAck` naming the
+//! tick it looked at. The game loop accepts at most one ack per tick, in
+//! order, and drops acks that fell more than [`ACK_WINDOW_TICKS`] behind
+//! the simulation, so a slow bot plays on stale information instead of
+//! steering with it.
+
+use std::collections::HashMap;
+
+use breakpoint_core::game_trait::PlayerId;
+
+/// Ticks an ack may trail the simulation by (half a second at 20 Hz).
+pub const ACK_WINDOW_TICKS: u32 = 10;
+
+/// Why an ack was dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AckRejection {
+    /// The tick was already answered, or an earlier one than the last.
+    Repeated,
+    /// The tick hasn't been simulated yet.
+    Future,
+    /// The tick is more than [`ACK_WINDOW_TICKS`] old.
+    Stale,
+}
+
+/// Each bot's last accepted tick in the current round.
+#[derive(Debug, Default)]
+pub struct BotAcks {
+    last: HashMap<PlayerId, u32>,
+}
+
+impl BotAcks {
+    /// Check `player_id`'s ack for `acked` while the simulation is at
+    /// `current`, recording it when accepted.
+    pub fn accept(
+        &mut self,
+        player_id: PlayerId,
+        acked: u32,
+        current: u32,
+    ) -> Result<(), AckRejection> {
+        if acked > current {
+            return Err(AckRejection::Future);
+        }
+        if current - acked > ACK_WINDOW_TICKS {
+            return Err(AckRejection::Stale);
+        }
+        if self.last.get(&player_id).is_some_and(|&last| acked <= last) {
+            return Err(AckRejection::Repeated);
+        }
+        self.last.insert(player_id, acked);
+        Ok(())
+    }
+
+    /// Ticks restart with every round.
+    pub fn new_round(&mut self) {
+        self.last.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn acks_are_ordered_and_recent() {
+        let mut acks = BotAcks::default();
+        assert_eq!(acks.accept(1, 5, 6), Ok(()));
+        assert_eq!(acks.accept(1, 5, 7), Err(AckRejection::Repeated));
+        assert_eq!(acks.accept(1, 4, 7), Err(AckRejection::Repeated));
+        assert_eq!(acks.accept(2, 5, 7), Ok(()), "Bots are tracked apart");
+        assert_eq!(acks.accept(1, 9, 8), Err(AckRejection::Future));
+        assert_eq!(
+            acks.accept(1, 6, 6 + ACK_WINDOW_TICKS + 1),
+            Err(AckRejection::Stale)
+        );
+        assert_eq!(acks.accept(1, 6, 6 + ACK_WINDOW_TICKS), Ok(()));
+
+        acks.new_round();
+        assert_eq!(acks.accept(1, 0, 0), Ok(()));
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::{HashMap, HashSet};
+
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Json;
+use serde::Serialize;
+
+use breakpoint_core::client_report::{
+    ClientReportBatch, ClientReportKind, MAX_BATCH_REPORTS, MAX_REPORT_MESSAGE_LEN,
+};
+
+use crate::config::ClientTelemetryConfig;
+use crate::error::AppError;
+use crate::state::AppState;
+
+const MAX_SESSION_ID_LEN: usize = 64;
+const MAX_VERSION_LEN: usize = 32;
+const MAX_CONTEXT_LEN: usize = 64;
+/// Distinct sessions remembered per group; the count saturates here.
+const MAX_TRACKED_SESSIONS: usize = 100;
+
+/// Reports with the same kind and message, merged for triage.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportGroup {
+    pub kind: ClientReportKind,
+    pub message: String,
+    pub count: u64,
+    /// Distinct client sessions that reported it (capped at 100).
+    pub sessions: usize,
+    pub first_seen: u64,
+    pub last_seen: u64,
+    pub last_context: String,
+    pub last_client_version: String,
+    #[serde(skip)]
+    session_ids: HashSet<String>,
+}
+
+/// Admin view of received client reports, most frequent first.
+#[derive(Debug, Clone, Serialize)]
+pub struct ClientReportSummary {
+    pub total_reports: u64,
+    /// Reports clients discarded before sending because their queue was full.
+    pub client_dropped: u64,
+    pub groups: Vec<ReportGroup>,
+}
+
+/// Opt-in crash and error reports from browser clients, grouped by kind
+/// and message. Bounded: the least recently seen group is evicted when full.
+#[derive(Debug)]
+pub struct ClientReportStore {
+    groups: HashMap<(ClientReportKind, String), ReportGroup>,
+    max_groups: usize,
+    total_reports: u64,
+    client_dropped: u64,
+}
+
+fn validate_batch(batch: &ClientReportBatch) -> Result<(), String> {
+    if batch.session_id.is_empty() || batch.session_id.len() > MAX_SESSION_ID_LEN {
+        return Err(format!("session_id must be 1-{MAX_SESSION_ID_LEN} bytes"));
+    }
+    if batch.client_version.len() > MAX_VERSION_LEN {
+        return Err(format!("client_version exceeds {MAX_VERSION_LEN} bytes"));
+    }
+    if batch.reports.len() > MAX_BATCH_REPORTS {
+        return Err(format!("reports exceed {MAX_BATCH_REPORTS} entries"));
+    }
+    for report in &batch.reports {
+        if report.message.len() > MAX_REPORT_MESSAGE_LEN {
+            return Err(format!("message exceeds {MAX_REPORT_MESSAGE_LEN} bytes"));
+        }
+        if report.context.len() > MAX_CONTEXT_LEN {
+            return Err(format!("context exceeds {MAX_CONTEXT_LEN} bytes"));
+        }
+    }
+    Ok(())
+}
+
+impl ClientReportStore {
+    pub fn new(config: &ClientTelemetryConfig) -> Self {
+        Self {
+            groups: HashMap::new(),
+            max_groups: config.max_groups,
+            total_reports: 0,
+            client_dropped: 0,
+        }
+    }
+
+    /// Validate and record a batch. Returns the number of reports accepted.
+    pub fn ingest(&mut self, batch: ClientReportBatch, now: u64) -> Result<usize, String> {
+        validate_batch(&batch)?;
+        let accepted = batch.reports.len();
+        self.total_reports += accepted as u64;
+        self.client_dropped += u64::from(batch.dropped);
+
+        for report in batch.reports {
+            let key = (report.kind, report.message);
+            if !self.groups.contains_key(&key) && self.groups.len() >= self.max_groups {
+                self.evict_oldest();
+            }
+            let group = self
+                .groups
+                .entry(key.clone())
+                .or_insert_with(|| ReportGroup {
+                    kind: key.0,
+                    message: key.1,
+                    count: 0,
+                    sessions: 0,
+                    first_seen: now,
+                    last_seen: now,
+                    last_context: String::new(),
+                    last_client_version: String::new(),
+                    session_ids: HashSet::new(),
+                });
+            group.count += 1;
+            group.last_seen = now;
+            group.last_context = report.context;
+            group.last_client_version.clone_from(&batch.client_version);
+            if group.session_ids.len() < MAX_TRACKED_SESSIONS {
+                group.session_ids.insert(batch.session_id.clone());
+                group.sessions = group.session_ids.len();
+            }
+        }
+        Ok(accepted)
+    }
+
+    fn evict_oldest(&mut self) {
+        if let Some(key) = self
+            .groups
+            .iter()
+            .min_by_key(|(_, g)| g.last_seen)
+            .map(|(k, _)| k.clone())
+        {
+            self.groups.remove(&key);
+        }
+    }
+
+    pub fn summary(&self) -> ClientReportSummary {
+        let mut groups: Vec<ReportGroup> = self.groups.values().cloned().collect();
+        groups.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| b.last_seen.cmp(&a.last_seen))
+        });
+        ClientReportSummary {
+            total_reports: self.total_reports,
+            client_dropped: self.client_dropped,
+            groups,
+        }
+    }
+}
+
+/// Response for an accepted telemetry batch.
+#[derive(Debug, Serialize)]
+pub struct TelemetryResponse {
+    pub accepted: usize,
+}
+
+/// POST /api/v1/telemetry — opt-in crash and error reports from clients.
+///
+/// Unauthenticated but rate limited. The body is parsed regardless of
+/// content type because `navigator.sendBeacon` posts strings as text/plain.
+pub async fn post_telemetry(
+    State(state): State<AppState>,
+    body: Bytes,
+) -> Result<(StatusCode, Json<TelemetryResponse>), AppError> {
+    if !state.config.client_telemetry.enabled {
+        return Err(AppError::NotFound(
+            "Client telemetry is disabled".to_string(),
+        ));
+    }
+    let batch: ClientReportBatch = serde_json::from_slice(&body)
+        .map_err(|e| AppError::BadRequest(format!("Invalid JSON: {e}")))?;
+    let now = breakpoint_core::time::unix_now();
+    let accepted = state
+        .client_reports
+        .write()
+        .await
+        .ingest(batch, now)
+        .map_err(AppError::BadRequest)?;
+    Ok((StatusCode::ACCEPTED, Json(TelemetryResponse { accepted })))
+}
+
+#[cfg(test)]
+mod tests {
+    use breakpoint_core::client_report::ClientReport;
+
+    use super::*;
+
+    fn batch(session_id: &str, messages: &[&str]) -> ClientReportBatch {
+        ClientReportBatch {
+            session_id: session_id.to_string(),
+            client_version: "0.1.0".to_string(),
+            reports: messages
+                .iter()
+                .map(|m| ClientReport {
+                    kind: ClientReportKind::Panic,
+                    message: (*m).to_string(),
+                    context: "InGame/golf".to_string(),
+                })
+                .collect(),
+            dropped: 0,
+        }
+    }
+
+    #[test]
+    fn groups_repeats_across_sessions() {
+        let mut store = ClientReportStore::new(&ClientTelemetryConfig::default());
+        store.ingest(batch("s1", &["boom", "boom"]), 100).unwrap();
+        store.ingest(batch("s2", &["boom", "other"]), 200).unwrap();
+
+        let summary = store.summary();
+        assert_eq!(summary.total_reports, 4);
+        assert_eq!(summary.groups.len(), 2);
+        let top = &summary.groups[0];
+        assert_eq!(top.message, "boom");
+        assert_eq!(top.count, 3);
+        assert_eq!(top.sessions, 2);
+        assert_eq!((top.first_seen, top.last_seen), (100, 200));
+    }
+
+    #[test]
+    fn evicts_least_recently_seen_group() {
+        let mut store = ClientReportStore::new(&ClientTelemetryConfig {
+            max_groups: 2,
+            ..ClientTelemetryConfig::default()
+        });
+        store.ingest(batch("s1", &["a"]), 1).unwrap();
+        store.ingest(batch("s1", &["b"]), 2).unwrap();
+        store.ingest(batch("s1", &["c"]), 3).unwrap();
+
+        let messages: Vec<String> = store
+            .summary()
+            .groups
+            .into_iter()
+            .map(|g| g.message)
+            .collect();
+        assert_eq!(messages.len(), 2);
+        assert!(!messages.contains(&"a".to_string()));
+    }
+
+    #[test]
+    fn rejects_oversized_batches() {
+        let mut store = ClientReportStore::new(&ClientTelemetryConfig::default());
+        let too_many = vec!["x"; MAX_BATCH_REPORTS + 1];
+        assert!(store.ingest(batch("s1", &too_many), 1).is_err());
+        assert!(store.ingest(batch("", &["x"]), 1).is_err());
+        let long = "x".repeat(MAX_REPORT_MESSAGE_LEN + 1);
+        assert!(store.ingest(batch("s1", &[&long]), 1).is_err());
+        assert_eq!(store.summary().total_reports, 0);
+    }
+}
@@ -1,6 +1,20 @@
+use std::time::Duration;
+
 use serde::Deserialize;
 
+use breakpoint_core::announcement::AnnouncementSeverity;
+use breakpoint_core::game_settings::CustomSettings;
+use breakpoint_core::game_trait::GameId;
+use breakpoint_core::net::chaos::ChaosConfig;
 use breakpoint_core::overlay::config::OverlayRoomConfig;
+use breakpoint_core::room::RoomCodeFormat;
+use breakpoint_core::round_phase::RoundPhaseTimings;
+
+use crate::join_queue::JoinQueueLimits;
+use crate::lifecycle_hooks::LifecycleHook;
+use crate::memory_budget::MemoryBudget;
+use crate::room_manager::RoomCapacity;
+use crate::room_webhooks::RoomWebhookRequest;
 
 /// Top-level server configuration, loaded from `breakpoint.toml`.
 #[derive(Debug, Clone, Deserialize)]
@@ -13,6 +27,33 @@ pub struct ServerConfig {
     pub github: Option<GitHubConfig>,
     pub limits: LimitsConfig,
     pub rooms: RoomsConfig,
+    pub schedules: SchedulesConfig,
+    pub announcements: AnnouncementsConfig,
+    pub referee: RefereeConfig,
+    pub client_telemetry: ClientTelemetryConfig,
+    pub replays: ReplaysConfig,
+    /// Per-room memory accounting and the caps that trigger trimming.
+    pub memory: MemoryConfig,
+    pub settings_sync: SettingsSyncConfig,
+    /// Quotas on user-generated content (synced settings, vanity aliases).
+    pub content: ContentLimitsConfig,
+    /// Bans and the moderation audit trail.
+    pub moderation: ModerationConfig,
+    pub room_webhooks: RoomWebhooksConfig,
+    /// Commands or URLs run as rooms open and close, for session managers.
+    pub lifecycle_hooks: LifecycleHooksConfig,
+    /// Game starts and results published as alert events.
+    pub game_events: GameEventsConfig,
+    /// Debug-only network fault injection applied to every WebSocket connection.
+    pub chaos: ChaosConfig,
+    /// Host debug commands; only honored by builds with `debug-commands`.
+    pub debug: DebugConfig,
+    /// External bot clients.
+    pub bot_api: BotApiConfig,
+    /// Weekly featured games and modifiers for rooms in featured mode.
+    pub season: SeasonConfig,
+    /// The rotating stats board for office TVs.
+    pub stats_board: StatsBoardConfig,
 }
 
 impl Default for ServerConfig {
@@ -25,6 +66,23 @@ impl Default for ServerConfig {
             github: None,
             limits: LimitsConfig::default(),
             rooms: RoomsConfig::default(),
+            schedules: SchedulesConfig::default(),
+            announcements: AnnouncementsConfig::default(),
+            referee: RefereeConfig::default(),
+            client_telemetry: ClientTelemetryConfig::default(),
+            replays: ReplaysConfig::default(),
+            memory: MemoryConfig::default(),
+            settings_sync: SettingsSyncConfig::default(),
+            content: ContentLimitsConfig::default(),
+            moderation: ModerationConfig::default(),
+            room_webhooks: RoomWebhooksConfig::default(),
+            lifecycle_hooks: LifecycleHooksConfig::default(),
+            game_events: GameEventsConfig::default(),
+            chaos: ChaosConfig::default(),
+            debug: DebugConfig::default(),
+            bot_api: BotApiConfig::default(),
+            season: SeasonConfig::default(),
+            stats_board: StatsBoardConfig::default(),
         }
     }
 }
@@ -71,13 +129,458 @@ impl Default for LimitsConfig {
 pub struct RoomsConfig {
     pub idle_timeout_secs: u64,
     pub idle_check_interval_secs: u64,
+    /// Pre-round 3-2-1 countdown in seconds (0 disables it).
+    pub countdown_secs: f32,
+    /// Seconds the final frame is held before round results (0 disables it).
+    pub freeze_frame_secs: f32,
+    /// Players who may wait in line for a full room (0 rejects joins to
+    /// full rooms outright).
+    pub join_queue_max: usize,
+    /// Queued joins per IP address in one room's line.
+    pub join_queue_per_ip: usize,
+    /// Most rooms open at once. Further room creators wait in line.
+    pub max_rooms: usize,
+    /// Most open rooms created from one IP address.
+    pub max_rooms_per_ip: usize,
+    /// Players who may wait for a room while the server is at `max_rooms`
+    /// (0 turns them away).
+    pub room_queue_max: usize,
+    /// Characters in generated room codes, not counting dashes (6 to 16).
+    /// Longer codes make collisions and guessing rarer on busy servers.
+    pub code_length: usize,
+    /// Characters room codes are drawn from (uppercase letters and
+    /// digits, at least 10). Empty keeps the classic `ABCD-1234` style.
+    pub code_alphabet: String,
 }
 
 impl Default for RoomsConfig {
     fn default() -> Self {
+        let timings = RoundPhaseTimings::default();
         Self {
             idle_timeout_secs: 3600,
             idle_check_interval_secs: 60,
+            countdown_secs: timings.countdown_secs,
+            freeze_frame_secs: timings.freeze_frame_secs,
+            join_queue_max: 16,
+            join_queue_per_ip: 2,
+            max_rooms: 100,
+            max_rooms_per_ip: 5,
+            room_queue_max: 32,
+            code_length: RoomCodeFormat::default().length,
+            code_alphabet: String::new(),
+        }
+    }
+}
+
+impl RoomsConfig {
+    pub fn phase_timings(&self) -> RoundPhaseTimings {
+        RoundPhaseTimings {
+            countdown_secs: self.countdown_secs,
+            freeze_frame_secs: self.freeze_frame_secs,
+        }
+    }
+
+    pub fn join_queue_limits(&self) -> JoinQueueLimits {
+        JoinQueueLimits {
+            max_len: self.join_queue_max,
+            per_ip: self.join_queue_per_ip,
+        }
+    }
+
+    pub fn capacity(&self) -> RoomCapacity {
+        RoomCapacity {
+            max_rooms: self.max_rooms,
+            max_rooms_per_ip: self.max_rooms_per_ip,
+            queue_max: self.room_queue_max,
+        }
+    }
+
+    pub fn code_format(&self) -> RoomCodeFormat {
+        RoomCodeFormat {
+            length: self.code_length,
+            alphabet: self.code_alphabet.clone(),
+        }
+    }
+}
+
+/// Scheduled game night configuration.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SchedulesConfig {
+    /// How often the scheduler checks for due reminders and openings.
+    pub check_interval_secs: u64,
+    /// Default reminder lead time when a schedule doesn't specify one.
+    pub reminder_minutes: u32,
+    /// Maximum number of pending scheduled sessions.
+    pub max_schedules: usize,
+}
+
+impl Default for SchedulesConfig {
+    fn default() -> Self {
+        Self {
+            check_interval_secs: 15,
+            reminder_minutes: 15,
+            max_schedules: 100,
+        }
+    }
+}
+
+/// Message of the day and global announcement configuration.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct AnnouncementsConfig {
+    /// Message of the day shown to every player until changed via the admin API.
+    pub motd: Option<String>,
+    pub motd_severity: AnnouncementSeverity,
+    /// How often scheduled announcements are checked for start/end.
+    pub check_interval_secs: u64,
+    /// Maximum number of scheduled announcements (excluding the MOTD).
+    pub max_announcements: usize,
+}
+
+impl Default for AnnouncementsConfig {
+    fn default() -> Self {
+        Self {
+            motd: None,
+            motd_severity: AnnouncementSeverity::Info,
+            check_interval_secs: 5,
+            max_announcements: 50,
+        }
+    }
+}
+
+/// Limits for the headless referee (`POST /api/v1/referee/simulate`).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RefereeConfig {
+    /// Maximum input log entries per request.
+    pub max_inputs: usize,
+    /// Rounds that run past this many ticks are reported as incomplete.
+    pub max_ticks_per_round: u32,
+    pub max_rounds: u8,
+}
+
+impl Default for RefereeConfig {
+    fn default() -> Self {
+        Self {
+            max_inputs: 20_000,
+            // 30 minutes at 20 Hz
+            max_ticks_per_round: 36_000,
+            max_rounds: 18,
+        }
+    }
+}
+
+/// Opt-in crash and error reports from browser clients.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ClientTelemetryConfig {
+    /// When false, `POST /api/v1/telemetry` returns 404.
+    pub enabled: bool,
+    /// Distinct report groups (kind + message) kept for the admin view.
+    pub max_groups: usize,
+}
+
+impl Default for ClientTelemetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_groups: 200,
+        }
+    }
+}
+
+/// Match recording and the replay history API.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ReplaysConfig {
+    /// When false, matches are not recorded and the history API returns 404.
+    pub enabled: bool,
+    /// Finished matches kept in memory; the oldest is dropped first.
+    pub max_matches: usize,
+    /// State frames kept per round; later frames are not recorded.
+    pub max_frames_per_round: usize,
+}
+
+impl Default for ReplaysConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_matches: 20,
+            // 10 minutes at 20 Hz
+            max_frames_per_round: 12_000,
+        }
+    }
+}
+
+/// Per-room memory accounting, sampled by each game session and shown by
+/// `GET /api/v1/admin/memory`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MemoryConfig {
+    /// Seconds between samples of each running game session.
+    pub sample_interval_secs: u64,
+    /// State, wall and trail bytes above which a game drops cosmetic data.
+    pub max_game_bytes: usize,
+    /// Recording bytes above which a match replay is thinned.
+    pub max_replay_bytes: usize,
+}
+
+impl Default for MemoryConfig {
+    fn default() -> Self {
+        let budget = MemoryBudget::default();
+        Self {
+            sample_interval_secs: budget.sample_interval.as_secs(),
+            max_game_bytes: budget.max_game_bytes,
+            max_replay_bytes: budget.max_replay_bytes,
+        }
+    }
+}
+
+impl MemoryConfig {
+    pub fn budget(&self) -> MemoryBudget {
+        MemoryBudget {
+            sample_interval: Duration::from_secs(self.sample_interval_secs),
+            max_game_bytes: self.max_game_bytes,
+            max_replay_bytes: self.max_replay_bytes,
+        }
+    }
+}
+
+/// Cross-device player settings sync.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SettingsSyncConfig {
+    /// When false, the player settings endpoints return 404.
+    pub enabled: bool,
+    /// Players whose settings are kept in memory; the least recently
+    /// accessed is dropped first.
+    pub max_players: usize,
+}
+
+impl Default for SettingsSyncConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_players: 10_000,
+        }
+    }
+}
+
+/// Quotas on user-generated content, enforced when it is uploaded.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ContentLimitsConfig {
+    /// Most items one identity may hold across every kind of content.
+    pub max_items_per_identity: usize,
+    /// Largest single item, in serialized bytes.
+    pub max_item_bytes: usize,
+    /// Most bytes of user content held by the whole instance.
+    pub max_total_bytes: usize,
+}
+
+impl Default for ContentLimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_items_per_identity: 16,
+            max_item_bytes: 64 * 1024,
+            max_total_bytes: 64 * 1024 * 1024,
+        }
+    }
+}
+
+/// Identity and IP bans, and the audit trail of moderation actions.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ModerationConfig {
+    /// JSON file bans are kept in across restarts. In memory only when
+    /// unset.
+    pub ban_file: Option<String>,
+    /// File each moderation action is appended to as a JSON line.
+    pub audit_file: Option<String>,
+    /// Mixed into client IPs before hashing, so stored hashes can't be
+    /// matched against a list of addresses. Required with `ban_file`;
+    /// without one, bans held in memory use a random salt.
+    pub ip_hash_salt: String,
+    /// Most bans held at once; expired bans don't count.
+    pub max_bans: usize,
+    /// Moderation actions kept in memory for the audit endpoint.
+    pub audit_entries: usize,
+}
+
+impl Default for ModerationConfig {
+    fn default() -> Self {
+        Self {
+            ban_file: None,
+            audit_file: None,
+            ip_hash_salt: String::new(),
+            max_bans: 10_000,
+            audit_entries: 500,
+        }
+    }
+}
+
+impl ModerationConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_bans == 0 || self.audit_entries == 0 {
+            return Err("moderation.max_bans and audit_entries must be > 0".to_string());
+        }
+        // An unsalted hash of an IPv4 address is reversed by trying them all
+        if self.ban_file.is_some() && self.ip_hash_salt.is_empty() {
+            return Err(
+                "moderation.ban_file needs moderation.ip_hash_salt (or BREAKPOINT_IP_HASH_SALT)"
+                    .to_string(),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Outbound webhooks fired on room lifecycle events.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RoomWebhooksConfig {
+    /// Most hooks registered at once, including ones added at runtime.
+    pub max_hooks: usize,
+    /// Hooks registered at startup (`[[room_webhooks.hooks]]`).
+    pub hooks: Vec<RoomWebhookRequest>,
+}
+
+impl Default for RoomWebhooksConfig {
+    fn default() -> Self {
+        Self {
+            max_hooks: 32,
+            hooks: Vec::new(),
+        }
+    }
+}
+
+/// Hooks run when rooms are created and closed.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct LifecycleHooksConfig {
+    /// `[[lifecycle_hooks.hooks]]` entries.
+    pub hooks: Vec<LifecycleHook>,
+    /// Tries per hook and event, for failures worth retrying.
+    pub attempts: u32,
+    /// Limit on each try: the POST, or the command running to exit.
+    pub timeout_secs: u64,
+    /// Events whose hooks may run at once; events past this are skipped.
+    pub max_in_flight: usize,
+}
+
+impl Default for LifecycleHooksConfig {
+    fn default() -> Self {
+        Self {
+            hooks: Vec::new(),
+            attempts: 3,
+            timeout_secs: 10,
+            max_in_flight: 64,
+        }
+    }
+}
+
+/// Game milestones published to the event store, so they reach overlays,
+/// SSE, and chat bridges like any other alert.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct GameEventsConfig {
+    pub enabled: bool,
+    /// Announce games as they start, not just their winners.
+    pub announce_starts: bool,
+    /// Fewest players a game needs to be announced. Featured games are
+    /// announced regardless.
+    pub min_players: usize,
+}
+
+impl Default for GameEventsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            announce_starts: true,
+            min_players: 2,
+        }
+    }
+}
+
+/// Host debug commands (teleport, grant power-up, end round, set timer).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct DebugConfig {
+    /// Accept debug commands from room leaders. Has no effect unless the
+    /// server was built with the `debug-commands` feature.
+    pub enabled: bool,
+    /// Seconds after a room's first debug command during which it accepts
+    /// more; later ones are refused for the life of the room.
+    pub window_secs: u64,
+}
+
+impl Default for DebugConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window_secs: 600,
+        }
+    }
+}
+
+/// External bot clients that join rooms over the WebSocket like players.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct BotApiConfig {
+    /// Tokens bot clients may join with, one per bot author or
+    /// tournament. Empty disables the bot API.
+    pub tokens: Vec<String>,
+}
+
+impl BotApiConfig {
+    pub fn accepts(&self, token: &str) -> bool {
+        !token.is_empty() && self.tokens.iter().any(|t| t == token)
+    }
+}
+
+/// A season of weekly featured games. Week `n` of the season features
+/// `weeks[n]`; the season ends after the last one.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SeasonConfig {
+    /// Shown in the lobby banner.
+    pub name: String,
+    /// Unix time the first week starts.
+    pub starts_at: u64,
+    /// Empty disables seasons.
+    pub weeks: Vec<SeasonWeek>,
+}
+
+/// One week's featured game and the lobby settings it is played with.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SeasonWeek {
+    /// Game ID, e.g. `platform-racer`.
+    pub game: String,
+    /// Shown in the lobby banner, e.g. "Low-gravity week".
+    pub title: String,
+    /// Lobby settings applied over the room's own, e.g. `gravity_scale`.
+    pub modifiers: CustomSettings,
+}
+
+/// The stats board at `/stats-board.html`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct StatsBoardConfig {
+    pub enabled: bool,
+    /// How long each page stays up.
+    pub page_secs: u64,
+    /// Rows per page on the leaderboard, highlights and schedule.
+    pub max_rows: usize,
+}
+
+impl Default for StatsBoardConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            page_secs: 15,
+            max_rows: 8,
         }
     }
 }
@@ -229,6 +732,160 @@ impl ServerConfig {
             tracing::error!("rooms.idle_check_interval_secs must be > 0");
             std::process::exit(1);
         }
+        if !(0.0..=30.0).contains(&self.rooms.countdown_secs) {
+            tracing::error!("rooms.countdown_secs must be between 0 and 30");
+            std::process::exit(1);
+        }
+        if !(0.0..=30.0).contains(&self.rooms.freeze_frame_secs) {
+            tracing::error!("rooms.freeze_frame_secs must be between 0 and 30");
+            std::process::exit(1);
+        }
+        if self.rooms.max_rooms == 0 {
+            tracing::error!("rooms.max_rooms must be > 0");
+            std::process::exit(1);
+        }
+        if self.rooms.max_rooms_per_ip == 0 {
+            tracing::error!("rooms.max_rooms_per_ip must be > 0");
+            std::process::exit(1);
+        }
+        if self.rooms.join_queue_max > 0 && self.rooms.join_queue_per_ip == 0 {
+            tracing::error!("rooms.join_queue_per_ip must be > 0 when the join queue is enabled");
+            std::process::exit(1);
+        }
+        if let Err(e) = self.rooms.code_format().validate() {
+            tracing::error!("rooms.code_length/code_alphabet: {e}");
+            std::process::exit(1);
+        }
+
+        // Validate schedules
+        if self.schedules.check_interval_secs == 0 {
+            tracing::error!("schedules.check_interval_secs must be > 0");
+            std::process::exit(1);
+        }
+
+        // Validate announcements
+        if self.announcements.check_interval_secs == 0 {
+            tracing::error!("announcements.check_interval_secs must be > 0");
+            std::process::exit(1);
+        }
+
+        // Validate referee
+        if self.referee.max_ticks_per_round == 0 || self.referee.max_rounds == 0 {
+            tracing::error!("referee.max_ticks_per_round and referee.max_rounds must be > 0");
+            std::process::exit(1);
+        }
+
+        if self.client_telemetry.max_groups == 0 {
+            tracing::error!("client_telemetry.max_groups must be > 0");
+            std::process::exit(1);
+        }
+
+        if self.replays.enabled
+            && (self.replays.max_matches == 0 || self.replays.max_frames_per_round == 0)
+        {
+            tracing::error!("replays.max_matches and replays.max_frames_per_round must be > 0");
+            std::process::exit(1);
+        }
+
+        if self.memory.sample_interval_secs == 0 {
+            tracing::error!("memory.sample_interval_secs must be > 0");
+            std::process::exit(1);
+        }
+
+        if self.settings_sync.enabled && self.settings_sync.max_players == 0 {
+            tracing::error!("settings_sync.max_players must be > 0");
+            std::process::exit(1);
+        }
+        if self.content.max_items_per_identity == 0
+            || self.content.max_item_bytes == 0
+            || self.content.max_item_bytes > self.content.max_total_bytes
+        {
+            tracing::error!("content limits must be > 0, with max_item_bytes <= max_total_bytes");
+            std::process::exit(1);
+        }
+        if let Err(e) = self.moderation.validate() {
+            tracing::error!("{e}");
+            std::process::exit(1);
+        }
+
+        if self.room_webhooks.hooks.len() > self.room_webhooks.max_hooks {
+            tracing::error!("room_webhooks.hooks exceeds room_webhooks.max_hooks");
+            std::process::exit(1);
+        }
+        if !self.room_webhooks.hooks.is_empty() {
+            tracing::warn!(
+                "room webhook secrets are set in config file — keep breakpoint.toml private"
+            );
+        }
+
+        let hooks = &self.lifecycle_hooks;
+        if hooks.attempts == 0 || hooks.timeout_secs == 0 || hooks.max_in_flight == 0 {
+            tracing::error!(
+                "lifecycle_hooks.attempts, timeout_secs, and max_in_flight must be > 0"
+            );
+            std::process::exit(1);
+        }
+        for (i, hook) in hooks.hooks.iter().enumerate() {
+            if let Err(e) = hook.validate() {
+                tracing::error!(hook = i + 1, error = %e, "lifecycle_hooks.hooks: invalid hook");
+                std::process::exit(1);
+            }
+        }
+        if hooks.hooks.iter().any(|h| !h.command.is_empty()) {
+            tracing::info!("Lifecycle hook commands will run as the server's user");
+        }
+
+        if !self.bot_api.tokens.is_empty() {
+            tracing::info!(
+                tokens = self.bot_api.tokens.len(),
+                "Bot API enabled — external bots may join rooms"
+            );
+        }
+
+        for (i, week) in self.season.weeks.iter().enumerate() {
+            if GameId::from_str_opt(&week.game).is_none() {
+                tracing::error!(week = i + 1, game = %week.game, "season.weeks: unknown game");
+                std::process::exit(1);
+            }
+        }
+
+        if self.stats_board.page_secs == 0 {
+            tracing::error!("stats_board.page_secs must be > 0");
+            std::process::exit(1);
+        }
+        if self.stats_board.max_rows == 0 {
+            tracing::error!("stats_board.max_rows must be > 0");
+            std::process::exit(1);
+        }
+
+        // Validate chaos testing
+        if let Err(e) = self.chaos.validate() {
+            tracing::error!("chaos: {e}");
+            std::process::exit(1);
+        }
+        if self.debug.enabled {
+            if cfg!(feature = "debug-commands") {
+                tracing::warn!(
+                    window_secs = self.debug.window_secs,
+                    "Debug commands enabled — room leaders can teleport players and end rounds"
+                );
+            } else {
+                tracing::warn!(
+                    "debug.enabled is set but this build lacks the debug-commands feature — \
+                     debug commands will be refused"
+                );
+            }
+        }
+
+        if self.chaos.is_active() {
+            tracing::warn!(
+                latency_ms = self.chaos.latency_ms,
+                jitter_ms = self.chaos.jitter_ms,
+                drop_rate = self.chaos.drop_rate,
+                reorder_rate = self.chaos.reorder_rate,
+                "Chaos testing enabled — WebSocket traffic is deliberately degraded"
+            );
+        }
     }
 
     /// Load config from `breakpoint.toml` if it exists, then apply env var overrides.
@@ -271,6 +928,19 @@ impl ServerConfig {
         {
             config.auth.github_webhook_secret = Some(secret);
         }
+        if let Ok(tokens) = std::env::var("BREAKPOINT_BOT_TOKENS") {
+            config.bot_api.tokens = tokens
+                .split(',')
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .map(String::from)
+                .collect();
+        }
+        if let Ok(salt) = std::env::var("BREAKPOINT_IP_HASH_SALT")
+            && !salt.is_empty()
+        {
+            config.moderation.ip_hash_salt = salt;
+        }
 
         // Limits overrides
         if let Ok(val) = std::env::var("BREAKPOINT_MAX_WS_CONNECTIONS")
@@ -278,6 +948,11 @@ impl ServerConfig {
         {
             config.limits.max_ws_connections = n;
         }
+        if let Ok(val) = std::env::var("BREAKPOINT_MAX_ROOMS")
+            && let Ok(n) = val.parse::<usize>()
+        {
+            config.rooms.max_rooms = n;
+        }
         if let Ok(val) = std::env::var("BREAKPOINT_MAX_SSE_SUBSCRIBERS")
             && let Ok(n) = val.parse::<usize>()
         {
@@ -331,6 +1006,52 @@ bearer_token = "secret123"
         assert_eq!(cfg.auth.bearer_token.as_deref(), Some("secret123"));
     }
 
+    #[test]
+    fn bot_api_accepts_only_listed_tokens() {
+        let cfg: ServerConfig = toml::from_str(
+            r#"
+[bot_api]
+tokens = ["league-2026"]
+"#,
+        )
+        .unwrap();
+        assert!(cfg.bot_api.accepts("league-2026"));
+        assert!(!cfg.bot_api.accepts("league-2025"));
+        assert!(!cfg.bot_api.accepts(""));
+        assert!(!ServerConfig::default().bot_api.accepts("league-2026"));
+    }
+
+    #[test]
+    fn season_weeks_parse_with_modifiers() {
+        let cfg: ServerConfig = toml::from_str(
+            r#"
+[season]
+name = "Spring Cup"
+starts_at = 1767571200
+
+[[season.weeks]]
+game = "platform-racer"
+title = "Low-gravity week"
+modifiers = { gravity_scale = 0.5 }
+
+[[season.weeks]]
+game = "laser-tag"
+title = "Wide-beam week"
+modifiers = { beam_width = 1.5, team_mode = "teams_2" }
+"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.season.weeks.len(), 2);
+        assert_eq!(
+            cfg.season.weeks[0].modifiers["gravity_scale"],
+            serde_json::json!(0.5)
+        );
+        assert_eq!(
+            cfg.season.weeks[1].modifiers["team_mode"],
+            serde_json::json!("teams_2")
+        );
+    }
+
     #[test]
     fn validate_accepts_valid_config() {
         // Default config should pass validation without panicking
@@ -348,6 +1069,18 @@ bearer_token = "secret123"
         assert!(cfg.listen_addr.parse::<std::net::SocketAddr>().is_err());
     }
 
+    #[test]
+    fn persisted_bans_need_a_salt() {
+        let mut moderation = ModerationConfig {
+            ban_file: Some("bans.json".to_string()),
+            ..ModerationConfig::default()
+        };
+        assert!(moderation.validate().is_err());
+        moderation.ip_hash_salt = "pepper".to_string();
+        assert!(moderation.validate().is_ok());
+        assert!(ModerationConfig::default().validate().is_ok());
+    }
+
     #[test]
     fn validate_rejects_zero_poll_interval() {
         let cfg = ServerConfig {
@@ -404,6 +1137,7 @@ agent_patterns = ["*[bot]"]
         let cfg = RoomsConfig::default();
         assert_eq!(cfg.idle_timeout_secs, 3600);
         assert_eq!(cfg.idle_check_interval_secs, 60);
+        assert_eq!(cfg.phase_timings(), RoundPhaseTimings::default());
     }
 
     #[test]
@@ -421,6 +1155,9 @@ player_message_buffer = 512
 [rooms]
 idle_timeout_secs = 7200
 idle_check_interval_secs = 120
+max_rooms = 40
+max_rooms_per_ip = 2
+code_length = 12
 "#;
         let cfg: ServerConfig = toml::from_str(toml_str).unwrap();
         assert_eq!(cfg.limits.max_ws_connections, 500);
@@ -432,6 +1169,29 @@ idle_check_interval_secs = 120
         assert_eq!(cfg.limits.player_message_buffer, 512);
         assert_eq!(cfg.rooms.idle_timeout_secs, 7200);
         assert_eq!(cfg.rooms.idle_check_interval_secs, 120);
+        let capacity = cfg.rooms.capacity();
+        assert_eq!(capacity.max_rooms, 40);
+        assert_eq!(capacity.max_rooms_per_ip, 2);
+        assert_eq!(capacity.queue_max, 32, "Unset keys keep their defaults");
+        let format = cfg.rooms.code_format();
+        assert_eq!(format.length, 12);
+        assert!(format.alphabet.is_empty());
+        assert!(format.validate().is_ok());
+    }
+
+    #[test]
+    fn parse_chaos_toml() {
+        let toml_str = r#"
+[chaos]
+enabled = true
+latency_ms = 150
+drop_rate = 0.1
+"#;
+        let cfg: ServerConfig = toml::from_str(toml_str).unwrap();
+        assert!(cfg.chaos.is_active());
+        assert_eq!(cfg.chaos.latency_ms, 150);
+        assert_eq!(cfg.chaos.jitter_ms, 0);
+        assert!(!ServerConfig::default().chaos.is_active());
     }
 
     #[test]
@@ -0,0 +1,312 @@
+//! Quotas on user-generated content, and the inventory admins review.
+//!
+//! Everything players can make the server hold (synced settings, vanity
+//! room aliases) is listed as a `ContentItem`, so one set of limits covers
+//! every kind. Uploads are checked against `[content]` before they are
+//! stored; what is already held can be listed and deleted by an admin.
+
+use serde::{Deserialize, Serialize};
+
+use breakpoint_core::player::PlayerIdentity;
+
+use crate::config::ContentLimitsConfig;
+use crate::error::AppError;
+use crate::player_settings::PlayerSettingsStore;
+use crate::room_manager::RoomManager;
+use crate::state::AppState;
+
+/// The kinds of user-generated content the server holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentKind {
+    PlayerSettings,
+    RoomAlias,
+}
+
+impl ContentKind {
+    /// Parse the kind as written in admin URLs (`player_settings`).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "player_settings" => Some(Self::PlayerSettings),
+            "room_alias" => Some(Self::RoomAlias),
+            _ => None,
+        }
+    }
+}
+
+/// One piece of user-generated content.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ContentItem {
+    pub kind: ContentKind,
+    /// Unique within its kind: the friend ID for settings, the alias itself
+    /// for aliases.
+    pub id: String,
+    /// Who it counts against. Aliases of rooms whose host joined without
+    /// an identity have no owner.
+    pub owner: Option<PlayerIdentity>,
+    /// Serialized size.
+    pub bytes: usize,
+    /// The room an alias points at.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub room_code: Option<String>,
+}
+
+impl ContentItem {
+    fn same_item(&self, other: &ContentItem) -> bool {
+        self.kind == other.kind && self.id == other.id
+    }
+}
+
+/// Serialized size of `value`, as counted against the limits.
+pub fn json_size<T: Serialize>(value: &T) -> usize {
+    serde_json::to_vec(value).map_or(0, |v| v.len())
+}
+
+/// Why an upload was refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LimitError {
+    TooLarge { bytes: usize, max: usize },
+    TooManyItems { max: usize },
+    StorageFull { max: usize },
+}
+
+impl std::fmt::Display for LimitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooLarge { bytes, max } => {
+                write!(f, "Upload is {bytes} bytes; the limit is {max} bytes")
+            },
+            Self::TooManyItems { max } => {
+                write!(f, "Each player may keep at most {max} uploads")
+            },
+            Self::StorageFull { max } => write!(
+                f,
+                "This server's storage for player uploads is full ({max} bytes)"
+            ),
+        }
+    }
+}
+
+impl From<LimitError> for AppError {
+    fn from(err: LimitError) -> Self {
+        match err {
+            LimitError::TooLarge { .. } => AppError::PayloadTooLarge(err.to_string()),
+            LimitError::TooManyItems { .. } | LimitError::StorageFull { .. } => {
+                AppError::QuotaExceeded(err.to_string())
+            },
+        }
+    }
+}
+
+/// Check that storing `candidate` keeps `inventory` within `limits`. A
+/// candidate that replaces an item already held only counts its new size.
+pub fn check(
+    limits: &ContentLimitsConfig,
+    inventory: &[ContentItem],
+    candidate: &ContentItem,
+) -> Result<(), LimitError> {
+    if candidate.bytes > limits.max_item_bytes {
+        return Err(LimitError::TooLarge {
+            bytes: candidate.bytes,
+            max: limits.max_item_bytes,
+        });
+    }
+    let others = inventory.iter().filter(|item| !item.same_item(candidate));
+    if let Some(owner) = &candidate.owner {
+        let owned = others
+            .clone()
+            .filter(|item| item.owner.as_ref() == Some(owner))
+            .count();
+        if owned >= limits.max_items_per_identity {
+            return Err(LimitError::TooManyItems {
+                max: limits.max_items_per_identity,
+            });
+        }
+    }
+    let total: usize = others.map(|item| item.bytes).sum();
+    if total + candidate.bytes > limits.max_total_bytes {
+        return Err(LimitError::StorageFull {
+            max: limits.max_total_bytes,
+        });
+    }
+    Ok(())
+}
+
+/// A vanity alias as counted against the limits.
+fn alias_item(
+    alias: String,
+    room_code: Option<String>,
+    owner: Option<PlayerIdentity>,
+) -> ContentItem {
+    ContentItem {
+        kind: ContentKind::RoomAlias,
+        bytes: alias.len(),
+        id: alias,
+        owner,
+        room_code,
+    }
+}
+
+/// Everything user-generated the server holds, by kind then ID.
+pub async fn inventory(state: &AppState) -> Vec<ContentItem> {
+    let items = state.player_settings.read().await.items();
+    with_aliases(items, &*state.rooms.read().await)
+}
+
+/// `items` plus the vanity aliases of `rooms`, by kind then ID.
+pub fn with_aliases(mut items: Vec<ContentItem>, rooms: &RoomManager) -> Vec<ContentItem> {
+    items.extend(
+        rooms
+            .vanity_aliases()
+            .into_iter()
+            .map(|(alias, room_code, owner)| alias_item(alias, Some(room_code), owner)),
+    );
+    items.sort_by(|a, b| (a.kind, &a.id).cmp(&(b.kind, &b.id)));
+    items
+}
+
+/// Check that a host with `owner`'s identity can claim the normalized
+/// vanity `alias` without breaking `limits`.
+pub fn check_alias(
+    limits: &ContentLimitsConfig,
+    inventory: &[ContentItem],
+    alias: &str,
+    owner: Option<&PlayerIdentity>,
+) -> Result<(), LimitError> {
+    check(
+        limits,
+        inventory,
+        &alias_item(alias.to_string(), None, owner.cloned()),
+    )
+}
+
+/// Refuse `candidate` with a clear error if storing it in `settings` would
+/// break the configured limits. Callers hold the settings write guard until
+/// the candidate is stored, so two uploads can't both pass against the same
+/// inventory.
+pub async fn check_upload(
+    state: &AppState,
+    settings: &PlayerSettingsStore,
+    candidate: &ContentItem,
+) -> Result<(), AppError> {
+    let inventory = with_aliases(settings.items(), &*state.rooms.read().await);
+    check(&state.config.content, &inventory, candidate)?;
+    Ok(())
+}
+
+/// Delete one item. Returns false if nothing of that kind has that ID.
+pub async fn remove(state: &AppState, kind: ContentKind, id: &str) -> bool {
+    match kind {
+        ContentKind::PlayerSettings => match PlayerIdentity::parse(id) {
+            Some(identity) => state.player_settings.write().await.remove(&identity),
+            None => false,
+        },
+        ContentKind::RoomAlias => state.rooms.write().await.release_alias(id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use breakpoint_core::player::PlayerColor;
+
+    use super::*;
+    use crate::outbound;
+    use crate::room_manager::RoomRequest;
+
+    fn limits() -> ContentLimitsConfig {
+        ContentLimitsConfig {
+            max_items_per_identity: 2,
+            max_item_bytes: 100,
+            max_total_bytes: 250,
+        }
+    }
+
+    fn item(id: &str, owner: Option<u8>, bytes: usize) -> ContentItem {
+        ContentItem {
+            kind: ContentKind::RoomAlias,
+            id: id.to_string(),
+            owner: owner.map(|n| PlayerIdentity::from_digest(&[n; 6])),
+            bytes,
+            room_code: None,
+        }
+    }
+
+    #[test]
+    fn oversized_items_are_refused() {
+        let err = check(&limits(), &[], &item("A", Some(1), 101)).unwrap_err();
+        assert_eq!(
+            err,
+            LimitError::TooLarge {
+                bytes: 101,
+                max: 100
+            }
+        );
+        assert!(matches!(AppError::from(err), AppError::PayloadTooLarge(_)));
+        assert!(check(&limits(), &[], &item("A", Some(1), 100)).is_ok());
+    }
+
+    #[test]
+    fn each_identity_has_an_item_quota() {
+        let held = [item("A", Some(1), 10), item("B", Some(1), 10)];
+        assert_eq!(
+            check(&limits(), &held, &item("C", Some(1), 10)),
+            Err(LimitError::TooManyItems { max: 2 })
+        );
+        // Replacing an item already held doesn't add one
+        assert!(check(&limits(), &held, &item("B", Some(1), 10)).is_ok());
+        assert!(check(&limits(), &held, &item("C", Some(2), 10)).is_ok());
+        assert!(check(&limits(), &held, &item("C", None, 10)).is_ok());
+    }
+
+    #[test]
+    fn instance_storage_is_capped() {
+        let held = [item("A", Some(1), 100), item("B", Some(2), 100)];
+        assert_eq!(
+            check(&limits(), &held, &item("C", Some(3), 51)),
+            Err(LimitError::StorageFull { max: 250 })
+        );
+        assert!(check(&limits(), &held, &item("C", Some(3), 50)).is_ok());
+        // A replacement is measured without the copy it replaces
+        assert!(check(&limits(), &held, &item("B", Some(2), 100)).is_ok());
+    }
+
+    #[test]
+    fn aliases_count_against_the_host_quota() {
+        let host = PlayerIdentity::from_digest(&[1; 6]);
+        let mut rooms = RoomManager::new();
+        let (tx, _rx) = outbound::channel(8);
+        let Ok(RoomRequest::Created {
+            room_code,
+            player_id,
+            ..
+        }) = rooms.request_room(
+            "P".to_string(),
+            PlayerColor::default(),
+            tx,
+            IpAddr::from([10, 0, 0, 1]),
+            Some("friday-golf"),
+        )
+        else {
+            panic!("Room should open");
+        };
+        rooms.bind_identity(&room_code, player_id, host.clone());
+        let settings = ContentItem {
+            kind: ContentKind::PlayerSettings,
+            id: host.to_string(),
+            owner: Some(host.clone()),
+            bytes: 10,
+            room_code: None,
+        };
+
+        let held = with_aliases(vec![settings], &rooms);
+        assert_eq!(held.len(), 2);
+        assert_eq!(
+            check_alias(&limits(), &held, "SATURDAY-TRON", Some(&host)),
+            Err(LimitError::TooManyItems { max: 2 })
+        );
+        let other = PlayerIdentity::from_digest(&[2; 6]);
+        assert!(check_alias(&limits(), &held, "SATURDAY-TRON", Some(&other)).is_ok());
+    }
+}
@@ -0,0 +1,253 @@
+//! Decathlon scoring: a room's finished matches across games, with each
+//! game's scores normalized so a golf round and a laser tag match count the
+//! same. Downloadable from the history API and delivered to room webhooks
+//! subscribed to `decathlon_results` when the room closes, for offices that
+//! feed recurring tournaments into their own spreadsheets or bots.
+
+use std::collections::HashMap;
+
+use axum::extract::{Path, Query, State};
+use axum::http::header;
+use axum::response::{IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+
+use breakpoint_core::game_trait::PlayerId;
+
+use crate::error::AppError;
+use crate::history::MatchSummary;
+use crate::state::AppState;
+
+/// Normalized score of the winner of a game; the last place gets 0.
+pub const NORMALIZED_MAX: f64 = 100.0;
+
+/// A room's multi-game results.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DecathlonResults {
+    pub room_code: String,
+    /// Finished matches, oldest first.
+    pub games: Vec<GameResult>,
+    /// Players by total normalized score, best first.
+    pub standings: Vec<DecathlonStanding>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GameResult {
+    pub match_id: String,
+    pub game: String,
+    pub started_at: u64,
+    pub ended_at: u64,
+    /// Best first.
+    pub results: Vec<PlayerResult>,
+}
+
+/// One player's finish in one game.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlayerResult {
+    pub player_id: PlayerId,
+    pub name: String,
+    /// The game's own final score.
+    pub score: i32,
+    /// 1-based; tied players share the better rank.
+    pub rank: u32,
+    /// The score scaled so the game's best is [`NORMALIZED_MAX`] and its
+    /// worst 0. Everyone gets the maximum when all scores are equal.
+    pub normalized: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DecathlonStanding {
+    pub player_id: PlayerId,
+    pub name: String,
+    pub total: f64,
+    pub games_played: u32,
+    /// 1-based; tied players share the better rank.
+    pub rank: u32,
+}
+
+impl DecathlonResults {
+    /// Results of `room_code`'s matches among `matches`, skipping any that
+    /// ended before `since` (unix seconds).
+    pub fn from_history(room_code: &str, matches: &[MatchSummary], since: u64) -> Self {
+        let mut games: Vec<GameResult> = matches
+            .iter()
+            .filter(|m| m.room_code == room_code && m.ended_at >= since)
+            .map(game_result)
+            .collect();
+        games.sort_by_key(|g| g.ended_at);
+
+        let mut totals: HashMap<PlayerId, DecathlonStanding> = HashMap::new();
+        for result in games.iter().flat_map(|g| &g.results) {
+            let standing = totals
+                .entry(result.player_id)
+                .or_insert_with(|| DecathlonStanding {
+                    player_id: result.player_id,
+                    name: String::new(),
+                    total: 0.0,
+                    games_played: 0,
+                    rank: 0,
+                });
+            // Games are in order, so the latest name wins
+            standing.name.clone_from(&result.name);
+            standing.total = round_tenth(standing.total + result.normalized);
+            standing.games_played += 1;
+        }
+        let mut standings: Vec<DecathlonStanding> = totals.into_values().collect();
+        standings.sort_by(|a, b| {
+            b.total
+                .total_cmp(&a.total)
+                .then_with(|| a.player_id.cmp(&b.player_id))
+        });
+        let totals: Vec<f64> = standings.iter().map(|s| s.total).collect();
+        for standing in &mut standings {
+            standing.rank = 1 + totals.iter().filter(|&&t| t > standing.total).count() as u32;
+        }
+
+        Self {
+            room_code: room_code.to_string(),
+            games,
+            standings,
+        }
+    }
+}
+
+fn game_result(summary: &MatchSummary) -> GameResult {
+    let names: HashMap<PlayerId, &str> = summary
+        .players
+        .iter()
+        .map(|p| (p.id, p.display_name.as_str()))
+        .collect();
+    let scores: Vec<i32> = summary.final_scores.iter().map(|s| s.score).collect();
+    let best = scores.iter().copied().max().unwrap_or(0);
+    let worst = scores.iter().copied().min().unwrap_or(0);
+    let mut results: Vec<PlayerResult> = summary
+        .final_scores
+        .iter()
+        .map(|s| PlayerResult {
+            player_id: s.player_id,
+            name: names
+                .get(&s.player_id)
+                .map_or_else(|| format!("Player {}", s.player_id), |n| n.to_string()),
+            score: s.score,
+            rank: 1 + scores.iter().filter(|&&other| other > s.score).count() as u32,
+            normalized: if best == worst {
+                NORMALIZED_MAX
+            } else {
+                round_tenth(NORMALIZED_MAX * f64::from(s.score - worst) / f64::from(best - worst))
+            },
+        })
+        .collect();
+    results.sort_by_key(|r| (r.rank, r.player_id));
+    GameResult {
+        match_id: summary.match_id.clone(),
+        game: summary.game.clone(),
+        started_at: summary.started_at,
+        ended_at: summary.ended_at,
+        results,
+    }
+}
+
+/// Spreadsheets and chat bots don't need more precision than this.
+fn round_tenth(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DecathlonQuery {
+    /// Only matches that ended at or after this unix time.
+    #[serde(default)]
+    pub since: u64,
+}
+
+/// GET /api/v1/history/rooms/:room_code/decathlon — the room's results as
+/// a JSON download.
+pub async fn get_decathlon(
+    State(state): State<AppState>,
+    Path(room_code): Path<String>,
+    Query(query): Query<DecathlonQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let history = state.history.read().await;
+    if !history.enabled() {
+        return Err(AppError::NotFound("Replays are disabled".to_string()));
+    }
+    let results = DecathlonResults::from_history(&room_code, &history.list(), query.since);
+    if results.games.is_empty() {
+        return Err(AppError::NotFound(format!(
+            "No finished matches for room {room_code}"
+        )));
+    }
+    let disposition = format!("attachment; filename=\"breakpoint-{room_code}-decathlon.json\"");
+    Ok(([(header::CONTENT_DISPOSITION, disposition)], Json(results)))
+}
+
+#[cfg(test)]
+mod tests {
+    use breakpoint_core::game_trait::PlayerScore;
+    use breakpoint_core::test_helpers::make_players;
+
+    use super::*;
+
+    fn summary(match_id: &str, room_code: &str, ended_at: u64, scores: &[i32]) -> MatchSummary {
+        MatchSummary {
+            match_id: match_id.to_string(),
+            game: "tron".to_string(),
+            room_code: room_code.to_string(),
+            players: make_players(scores.len()),
+            rounds: 1,
+            final_scores: scores
+                .iter()
+                .enumerate()
+                .map(|(i, &score)| PlayerScore {
+                    player_id: i as PlayerId + 1,
+                    score,
+                    disconnected: false,
+                })
+                .collect(),
+            started_at: ended_at - 60,
+            ended_at,
+            replay_bytes: 0,
+        }
+    }
+
+    #[test]
+    fn games_are_normalized_and_ranked() {
+        let matches = [
+            summary("m2", "ROOM", 300, &[10, 10, 10]),
+            summary("m1", "ROOM", 200, &[-4, 8, 2]),
+            summary("other", "ELSE", 250, &[99]),
+        ];
+        let results = DecathlonResults::from_history("ROOM", &matches, 0);
+        assert_eq!(results.games.len(), 2);
+
+        let first = &results.games[0];
+        assert_eq!(first.match_id, "m1");
+        let ranked: Vec<(PlayerId, u32, f64)> = first
+            .results
+            .iter()
+            .map(|r| (r.player_id, r.rank, r.normalized))
+            .collect();
+        assert_eq!(ranked, [(2, 1, 100.0), (3, 2, 50.0), (1, 3, 0.0)]);
+        assert_eq!(first.results[0].name, "Player2");
+
+        // A draw gives everyone the maximum
+        assert!(results.games[1].results.iter().all(|r| r.rank == 1));
+        let standings: Vec<(PlayerId, f64, u32)> = results
+            .standings
+            .iter()
+            .map(|s| (s.player_id, s.total, s.rank))
+            .collect();
+        assert_eq!(standings, [(2, 200.0, 1), (3, 150.0, 2), (1, 100.0, 3)]);
+        assert!(results.standings.iter().all(|s| s.games_played == 2));
+    }
+
+    #[test]
+    fn since_drops_earlier_matches() {
+        let matches = [
+            summary("m1", "ROOM", 200, &[1, 2]),
+            summary("m2", "ROOM", 300, &[5, 1]),
+        ];
+        let results = DecathlonResults::from_history("ROOM", &matches, 250);
+        assert_eq!(results.games.len(), 1);
+        assert_eq!(results.standings[0].player_id, 1);
+        assert_eq!(results.standings[1].rank, 2);
+    }
+}
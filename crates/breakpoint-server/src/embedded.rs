@@ -0,0 +1,61 @@
+//! The web client compiled into the server binary (`embed-assets` feature),
+//! served when `web_root` does not exist so a single binary is a complete
+//! deployment. Build the WASM bundle into `web/pkg` before the server so it
+//! is embedded too.
+
+use axum::http::{StatusCode, Uri, header};
+use axum::response::{IntoResponse, Response};
+use rust_embed::RustEmbed;
+
+/// `web/` as it was when the server was built. Debug builds read the files
+/// from disk instead, so edits show up without rebuilding.
+#[derive(RustEmbed)]
+#[folder = "../../web"]
+struct WebAssets;
+
+/// Serve `uri` from the embedded assets; directories serve their `index.html`.
+pub async fn serve(uri: Uri) -> Response {
+    let path = asset_path(uri.path());
+    match WebAssets::get(&path) {
+        Some(file) => (
+            [(header::CONTENT_TYPE, file.metadata.mimetype().to_string())],
+            file.data,
+        )
+            .into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+fn asset_path(uri_path: &str) -> String {
+    let path = uri_path.trim_start_matches('/');
+    if path.is_empty() || path.ends_with('/') {
+        format!("{path}index.html")
+    } else {
+        path.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn serves_index_and_assets_with_their_type() {
+        let index = serve(Uri::from_static("/")).await;
+        assert_eq!(index.status(), StatusCode::OK);
+        assert_eq!(index.headers()[header::CONTENT_TYPE], "text/html");
+
+        let css = serve(Uri::from_static("/style.css")).await;
+        assert_eq!(css.headers()[header::CONTENT_TYPE], "text/css");
+
+        let missing = serve(Uri::from_static("/no-such-file.js")).await;
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn directories_map_to_their_index() {
+        assert_eq!(asset_path("/"), "index.html");
+        assert_eq!(asset_path("/locales/"), "locales/index.html");
+        assert_eq!(asset_path("/pkg/client.js"), "pkg/client.js");
+    }
+}
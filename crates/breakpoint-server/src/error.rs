@@ -7,15 +7,22 @@ pub enum AppError {
     BadRequest(String),
     NotFound(String),
     Unauthorized(String),
+    /// An upload over the per-item size limit.
+    PayloadTooLarge(String),
+    /// An upload refused by a per-identity or instance-wide quota.
+    QuotaExceeded(String),
     Internal(String),
 }
 
 impl std::fmt::Display for AppError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::BadRequest(m) | Self::NotFound(m) | Self::Unauthorized(m) | Self::Internal(m) => {
-                write!(f, "{m}")
-            },
+            Self::BadRequest(m)
+            | Self::NotFound(m)
+            | Self::Unauthorized(m)
+            | Self::PayloadTooLarge(m)
+            | Self::QuotaExceeded(m)
+            | Self::Internal(m) => write!(f, "{m}"),
         }
     }
 }
@@ -26,6 +33,8 @@ impl IntoResponse for AppError {
             Self::BadRequest(m) => (StatusCode::BAD_REQUEST, m.clone()),
             Self::NotFound(m) => (StatusCode::NOT_FOUND, m.clone()),
             Self::Unauthorized(m) => (StatusCode::UNAUTHORIZED, m.clone()),
+            Self::PayloadTooLarge(m) => (StatusCode::PAYLOAD_TOO_LARGE, m.clone()),
+            Self::QuotaExceeded(m) => (StatusCode::FORBIDDEN, m.clone()),
             Self::Internal(m) => (StatusCode::INTERNAL_SERVER_ERROR, m.clone()),
         };
         (status, Json(serde_json::json!({ "error": message }))).into_response()
@@ -0,0 +1,242 @@
+//! Registry of polled event sources (see [`EventSource`]).
+//!
+//! Each registered source runs in its own task: the registry starts it,
+//! polls it on its interval, feeds the events into the [`EventStore`], and
+//! keeps a [`SourceStatus`] for `GET /api/v1/status`. Sources can be added
+//! and removed while the server runs.
+//!
+//! [`EventStore`]: crate::event_store::EventStore
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use tokio_util::sync::CancellationToken;
+
+use breakpoint_core::event_source::{EventSource, SourceHealth, SourceStatus};
+
+use crate::state::SharedEventStore;
+
+struct RegisteredSource {
+    status: Arc<Mutex<SourceStatus>>,
+    cancel: CancellationToken,
+}
+
+/// Running event sources, keyed by name. Cheap to clone.
+#[derive(Clone)]
+pub struct EventSourceRegistry {
+    sources: Arc<Mutex<BTreeMap<String, RegisteredSource>>>,
+    event_store: SharedEventStore,
+    shutdown: CancellationToken,
+}
+
+impl EventSourceRegistry {
+    /// Sources stop when `shutdown` is cancelled.
+    pub fn new(event_store: SharedEventStore, shutdown: CancellationToken) -> Self {
+        Self {
+            sources: Arc::new(Mutex::new(BTreeMap::new())),
+            event_store,
+            shutdown,
+        }
+    }
+
+    /// Start polling `source`. Fails if a source with the same name is
+    /// already registered.
+    pub fn register(&self, source: Box<dyn EventSource>) -> Result<(), String> {
+        let name = source.name().to_string();
+        let mut sources = self
+            .sources
+            .lock()
+            .map_err(|_| "Event source registry poisoned".to_string())?;
+        if sources.contains_key(&name) {
+            return Err(format!("Event source {name} is already registered"));
+        }
+        let status = Arc::new(Mutex::new(SourceStatus::new(&name, source.poll_interval())));
+        let cancel = self.shutdown.child_token();
+        tokio::spawn(run_source(
+            source,
+            Arc::clone(&status),
+            cancel.clone(),
+            Arc::clone(&self.event_store),
+        ));
+        sources.insert(name.clone(), RegisteredSource { status, cancel });
+        tracing::info!(source = %name, "Event source registered");
+        Ok(())
+    }
+
+    /// Stop and remove a source. Returns `false` if it was not registered.
+    pub fn unregister(&self, name: &str) -> bool {
+        let Ok(mut sources) = self.sources.lock() else {
+            return false;
+        };
+        match sources.remove(name) {
+            Some(entry) => {
+                entry.cancel.cancel();
+                tracing::info!(source = name, "Event source removed");
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Status of every registered source, by name.
+    pub fn statuses(&self) -> Vec<SourceStatus> {
+        let Ok(sources) = self.sources.lock() else {
+            return Vec::new();
+        };
+        sources
+            .values()
+            .filter_map(|entry| entry.status.lock().ok().map(|s| s.clone()))
+            .collect()
+    }
+}
+
+/// Start, then poll until cancelled. A failed start is retried each
+/// interval, so a source whose backend is down at boot recovers on its own.
+async fn run_source(
+    mut source: Box<dyn EventSource>,
+    status: Arc<Mutex<SourceStatus>>,
+    cancel: CancellationToken,
+    event_store: SharedEventStore,
+) {
+    let interval = source.poll_interval();
+    let mut started = false;
+    loop {
+        let result = tokio::select! {
+            _ = cancel.cancelled() => break,
+            result = async {
+                if !started {
+                    source.start().await?;
+                    started = true;
+                }
+                source.poll().await
+            } => result,
+        };
+        let now = breakpoint_core::time::unix_now();
+        let reported = source.health();
+        match result {
+            Ok(events) => {
+                let count = events.len();
+                if count > 0 {
+                    let mut store = event_store.write().await;
+                    for event in events {
+                        store.insert(event);
+                    }
+                }
+                if let Ok(mut status) = status.lock() {
+                    status.record_success(now, count, reported);
+                }
+            },
+            Err(e) => {
+                tracing::warn!(source = source.name(), error = %e, "Event source poll failed");
+                if let Ok(mut status) = status.lock() {
+                    status.record_failure(now, e, reported);
+                }
+            },
+        }
+        tokio::select! {
+            _ = cancel.cancelled() => break,
+            _ = tokio::time::sleep(interval) => {},
+        }
+    }
+    source.stop();
+    if let Ok(mut status) = status.lock() {
+        status.health = SourceHealth::Stopped;
+    }
+    tracing::info!(source = source.name(), "Event source stopped");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use breakpoint_core::event_source::SourceFuture;
+    use breakpoint_core::events::Event;
+    use breakpoint_core::test_helpers::make_test_event;
+    use tokio::sync::RwLock;
+
+    use super::*;
+    use crate::event_store::EventStore;
+
+    /// Emits one event per poll, failing every other poll when `flaky`.
+    struct TestSource {
+        polls: u32,
+        flaky: bool,
+    }
+
+    impl EventSource for TestSource {
+        fn name(&self) -> &str {
+            "test-source"
+        }
+
+        fn config_schema(&self) -> serde_json::Value {
+            serde_json::json!({ "type": "object" })
+        }
+
+        fn poll_interval(&self) -> Duration {
+            Duration::from_millis(10)
+        }
+
+        fn poll(&mut self) -> SourceFuture<'_, Result<Vec<Event>, String>> {
+            self.polls += 1;
+            let polls = self.polls;
+            let flaky = self.flaky;
+            Box::pin(async move {
+                if flaky && polls.is_multiple_of(2) {
+                    return Err("backend unavailable".to_string());
+                }
+                Ok(vec![make_test_event(&format!("test-{polls}"))])
+            })
+        }
+    }
+
+    fn registry() -> (EventSourceRegistry, SharedEventStore, CancellationToken) {
+        let store: SharedEventStore = Arc::new(RwLock::new(EventStore::new()));
+        let shutdown = CancellationToken::new();
+        (
+            EventSourceRegistry::new(Arc::clone(&store), shutdown.clone()),
+            store,
+            shutdown,
+        )
+    }
+
+    #[tokio::test]
+    async fn registered_source_feeds_event_store() {
+        let (registry, store, shutdown) = registry();
+        registry
+            .register(Box::new(TestSource {
+                polls: 0,
+                flaky: false,
+            }))
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert!(store.read().await.get("test-1").is_some());
+        let statuses = registry.statuses();
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0].health, SourceHealth::Healthy);
+        assert!(statuses[0].events_emitted >= 1);
+        assert!(statuses[0].last_success_at.is_some());
+
+        shutdown.cancel();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(registry.statuses()[0].health, SourceHealth::Stopped);
+    }
+
+    #[tokio::test]
+    async fn duplicate_names_are_rejected_until_unregistered() {
+        let (registry, _store, _shutdown) = registry();
+        let source = || {
+            Box::new(TestSource {
+                polls: 0,
+                flaky: true,
+            })
+        };
+        registry.register(source()).unwrap();
+        assert!(registry.register(source()).is_err());
+
+        assert!(registry.unregister("test-source"));
+        assert!(registry.statuses().is_empty());
+        assert!(!registry.unregister("test-source"));
+        registry.register(source()).unwrap();
+    }
+}
@@ -115,6 +115,11 @@ impl EventStore {
             .collect()
     }
 
+    /// Events sent but not yet received by every subscriber.
+    pub fn queued_broadcasts(&self) -> usize {
+        self.broadcast_tx.len()
+    }
+
     /// Subscribe to the broadcast channel for new events.
     pub fn subscribe(&self) -> broadcast::Receiver<Event> {
         self.broadcast_tx.subscribe()
@@ -0,0 +1,278 @@
+//! Game milestones as alert events. Rooms already report their activity
+//! for room webhooks; with `[game_events]` enabled, game starts and winners
+//! also become `Event`s in the event store, so they reach other rooms'
+//! overlays, SSE subscribers, and chat bridges the same way CI alerts do.
+
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use breakpoint_core::events::{Event, EventType, Priority};
+
+use crate::config::GameEventsConfig;
+use crate::room_webhooks::{RoomActivity, ScoreLine};
+
+/// `source` of every game milestone event, for room event filters.
+pub const SOURCE: &str = "breakpoint";
+
+/// Turns room activity into milestone events. Owned by the room activity
+/// dispatcher, which sees every room's activity in order.
+pub struct GameEventProducer {
+    config: GameEventsConfig,
+    /// Games running in each room, remembered from their start so the
+    /// result can name the game.
+    running: HashMap<String, RunningGame>,
+}
+
+struct RunningGame {
+    game: String,
+    featured: bool,
+    players: usize,
+}
+
+impl RunningGame {
+    /// Small casual games stay quiet; featured games are always news.
+    fn is_newsworthy(&self, config: &GameEventsConfig) -> bool {
+        self.featured || self.players >= config.min_players
+    }
+}
+
+impl GameEventProducer {
+    pub fn new(config: GameEventsConfig) -> Self {
+        Self {
+            config,
+            running: HashMap::new(),
+        }
+    }
+
+    /// The milestone event for `activity`, if it is one worth announcing.
+    pub fn observe(&mut self, activity: &RoomActivity, now: u64) -> Option<Event> {
+        if !self.config.enabled {
+            return None;
+        }
+        match activity {
+            RoomActivity::GameStarted {
+                room_code,
+                game,
+                players,
+                featured,
+            } => {
+                let running = RunningGame {
+                    game: game.clone(),
+                    featured: *featured,
+                    players: players.len(),
+                };
+                let announce = self.config.announce_starts && running.is_newsworthy(&self.config);
+                let title = if running.featured {
+                    format!("This week's featured {game} is starting in room {room_code}")
+                } else {
+                    format!("{game} is starting in room {room_code}")
+                };
+                let event = announce.then(|| {
+                    let mut event = milestone(&running, room_code, "started", title, now);
+                    event.body = Some(players.join(", "));
+                    event.url = Some(format!("/?room={room_code}"));
+                    event
+                });
+                self.running.insert(room_code.clone(), running);
+                event
+            },
+            RoomActivity::GameEnded {
+                room_code,
+                final_scores,
+                match_id,
+            } => {
+                let running = self.running.remove(room_code)?;
+                if !running.is_newsworthy(&self.config) {
+                    return None;
+                }
+                let winners = winners(final_scores);
+                if winners.is_empty() {
+                    return None;
+                }
+                let game = &running.game;
+                let what = if running.featured {
+                    format!("this week's featured {game}")
+                } else {
+                    format!("{game} in room {room_code}")
+                };
+                let title = match winners.as_slice() {
+                    [winner] => format!("{winner} won {what}"),
+                    _ => format!("{} tied for the win in {what}", winners.join(" and ")),
+                };
+                let mut event = milestone(&running, room_code, "ended", title, now);
+                event.priority = Priority::Notice;
+                event.body = Some(standings(final_scores));
+                event.actor = winners.first().cloned();
+                if let Some(id) = match_id {
+                    event.url = Some(format!("/api/v1/public/history/{id}/replay"));
+                    event
+                        .metadata
+                        .insert("match_id".to_string(), serde_json::json!(id));
+                }
+                Some(event)
+            },
+            RoomActivity::RoomClosed { room_code } => {
+                self.running.remove(room_code);
+                None
+            },
+            RoomActivity::RoomCreated { .. }
+            | RoomActivity::RoundFinished { .. }
+            | RoomActivity::DecathlonResults(_) => None,
+        }
+    }
+}
+
+fn milestone(running: &RunningGame, room_code: &str, kind: &str, title: String, now: u64) -> Event {
+    let mut tags = vec![
+        format!("game:{}", running.game),
+        format!("room:{room_code}"),
+    ];
+    if running.featured {
+        tags.push("featured".to_string());
+    }
+    let mut metadata = HashMap::new();
+    metadata.insert("room_code".to_string(), serde_json::json!(room_code));
+    metadata.insert("game".to_string(), serde_json::json!(running.game));
+    Event {
+        id: format!("game-{kind}-{}", Uuid::new_v4()),
+        event_type: EventType::Custom,
+        source: SOURCE.to_string(),
+        priority: Priority::Ambient,
+        title,
+        body: None,
+        timestamp: format!("{now}Z"),
+        url: None,
+        actor: None,
+        tags,
+        action_required: false,
+        group_key: Some(format!("game:{room_code}")),
+        expires_at: None,
+        metadata,
+    }
+}
+
+fn name(line: &ScoreLine) -> String {
+    line.name
+        .clone()
+        .unwrap_or_else(|| format!("Player {}", line.player_id))
+}
+
+/// Everyone sharing the top score.
+fn winners(scores: &[ScoreLine]) -> Vec<String> {
+    let Some(best) = scores.iter().map(|s| s.score).max() else {
+        return Vec::new();
+    };
+    scores
+        .iter()
+        .filter(|s| s.score == best)
+        .map(name)
+        .collect()
+}
+
+/// "Alice 12, Bob 9", best first.
+fn standings(scores: &[ScoreLine]) -> String {
+    let mut sorted: Vec<&ScoreLine> = scores.iter().collect();
+    sorted.sort_by_key(|s| std::cmp::Reverse(s.score));
+    sorted
+        .iter()
+        .map(|s| format!("{} {}", name(s), s.score))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled() -> GameEventsConfig {
+        GameEventsConfig {
+            enabled: true,
+            ..GameEventsConfig::default()
+        }
+    }
+
+    fn started(room_code: &str, players: &[&str], featured: bool) -> RoomActivity {
+        RoomActivity::GameStarted {
+            room_code: room_code.to_string(),
+            game: "mini-golf".to_string(),
+            players: players.iter().map(|p| p.to_string()).collect(),
+            featured,
+        }
+    }
+
+    fn ended(room_code: &str, scores: &[(&str, i32)]) -> RoomActivity {
+        RoomActivity::GameEnded {
+            room_code: room_code.to_string(),
+            final_scores: scores
+                .iter()
+                .enumerate()
+                .map(|(i, &(name, score))| ScoreLine {
+                    player_id: i as u64 + 1,
+                    name: Some(name.to_string()),
+                    score,
+                })
+                .collect(),
+            match_id: Some("m-1".to_string()),
+        }
+    }
+
+    #[test]
+    fn starts_and_winners_become_events() {
+        let mut producer = GameEventProducer::new(enabled());
+        let start = producer
+            .observe(&started("ABCD", &["Alice", "Bob"], false), 100)
+            .expect("Two-player games are announced");
+        assert_eq!(start.source, SOURCE);
+        assert_eq!(start.title, "mini-golf is starting in room ABCD");
+        assert_eq!(start.url.as_deref(), Some("/?room=ABCD"));
+
+        let end = producer
+            .observe(&ended("ABCD", &[("Bob", 4), ("Alice", 9)]), 200)
+            .unwrap();
+        assert_eq!(end.title, "Alice won mini-golf in room ABCD");
+        assert_eq!(end.body.as_deref(), Some("Alice 9, Bob 4"));
+        assert_eq!(end.priority, Priority::Notice);
+        assert_eq!(end.metadata["match_id"], "m-1");
+        assert!(
+            producer
+                .observe(&ended("ABCD", &[("Alice", 1)]), 300)
+                .is_none(),
+            "Each game ends once"
+        );
+    }
+
+    #[test]
+    fn quiet_unless_enabled_and_newsworthy() {
+        let mut off = GameEventProducer::new(GameEventsConfig::default());
+        assert!(
+            off.observe(&started("ABCD", &["A", "B"], false), 0)
+                .is_none()
+        );
+
+        let mut producer = GameEventProducer::new(enabled());
+        assert!(
+            producer
+                .observe(&started("SOLO", &["Alice"], false), 0)
+                .is_none()
+        );
+        assert!(
+            producer
+                .observe(&ended("SOLO", &[("Alice", 3)]), 1)
+                .is_none()
+        );
+
+        // A featured game is news even played alone, and ties share the win
+        let start = producer
+            .observe(&started("WEEK", &["Alice"], true), 0)
+            .unwrap();
+        assert!(start.tags.contains(&"featured".to_string()));
+        let end = producer
+            .observe(&ended("WEEK", &[("Alice", 5), ("Bob", 5)]), 1)
+            .unwrap();
+        assert_eq!(
+            end.title,
+            "Alice and Bob tied for the win in this week's featured mini-golf"
+        );
+    }
+}
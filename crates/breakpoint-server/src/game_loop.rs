@@ -1,18 +1,41 @@
-use std::collections::HashMap;
-use std::time::Duration;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use bytes::Bytes;
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
 
+use breakpoint_core::bot::BotDriver;
+use breakpoint_core::course_vote::CourseVote;
+use breakpoint_core::debug_command::DebugCommand;
+use breakpoint_core::game_settings::{CustomSettings, FromGameConfig, SettingsError};
 use breakpoint_core::game_trait::{
-    BreakpointGame, GameConfig, GameEvent, GameId, PlayerId, PlayerInputs,
+    BreakpointGame, GameConfig, GameConfigDelta, GameEvent, GameId, PlayerId, PlayerInputs,
+    PlayerScore,
 };
 use breakpoint_core::net::messages::{
-    CourseUpdateMsg, GameEndMsg, GameStartMsg, PlayerScoreEntry, RoundEndMsg, ServerMessage,
+    CourseCandidate, CourseUpdateMsg, CourseVoteMsg, DraftedPlayer, GameEffectsMsg, GameEndMsg,
+    GameStartMsg, IntermissionMsg, PlayerScoreEntry, PrivateStateMsg, RoundEndMsg, RoundPhaseMsg,
+    ServerMessage, StandingsMsg, TeamDraftMsg,
 };
 use breakpoint_core::net::protocol::{encode_game_state_fast, encode_server_message};
 use breakpoint_core::player::Player;
+use breakpoint_core::replay::ReplayMetadata;
+use breakpoint_core::round_phase::{
+    self, Intermission, RoundPacing, RoundPhase, RoundPhaseMachine, RoundPhaseTimings,
+};
+use breakpoint_core::scoreboard;
+use breakpoint_core::team_draft::{self, TeamDraft};
+use breakpoint_core::telemetry::METRICS_TARGET;
+use breakpoint_core::tutorial::{TutorialChecklist, TutorialObjective, TutorialProgress};
+use breakpoint_core::win_condition::{MatchTracker, WinCondition};
+
+use crate::bot_api::BotAcks;
+use crate::history::{ReplayRecorder, ReplaySink, store_replay};
+use crate::input_stats::SharedInputStats;
+use crate::memory_budget::{RoomMemory, SharedRoomMemory};
+use crate::tutorial::{TutorialSink, TutorialTracker};
 
 /// Commands sent from the WebSocket handler to the game tick loop.
 #[derive(Debug)]
@@ -22,6 +45,13 @@ pub enum GameCommand {
         tick: u32,
         input_data: Vec<u8>,
     },
+    /// An external bot's input for the state of `tick`, checked with
+    /// [`BotAcks`] before it counts.
+    BotAck {
+        player_id: PlayerId,
+        tick: u32,
+        input_data: Vec<u8>,
+    },
     PlayerJoined {
         player_id: PlayerId,
         player: Player,
@@ -29,6 +59,39 @@ pub enum GameCommand {
     PlayerLeft {
         player_id: PlayerId,
     },
+    /// A ballot in the pre-round course vote.
+    CourseVote {
+        player_id: PlayerId,
+        round: u8,
+        choice: u8,
+    },
+    /// A captain's pick in the pre-match team draft.
+    DraftPick {
+        player_id: PlayerId,
+        pick: PlayerId,
+    },
+    /// The leader asks to start the round after `round`.
+    AdvanceRound {
+        round: u8,
+    },
+    /// A leader's debug command, already checked against the room's
+    /// debug window.
+    Debug {
+        command: DebugCommand,
+    },
+    /// A leader's mid-round change to the game's live settings.
+    ConfigChange {
+        delta: GameConfigDelta,
+    },
+    /// The player's latest round trip, for the scoreboard.
+    Ping {
+        player_id: PlayerId,
+        rtt_ms: u16,
+    },
+    /// No connected player is left in the match (`true`), or one is back
+    /// (`false`). A dormant session stops ticking and only repeats its
+    /// last state every [`DORMANT_KEEPALIVE`].
+    Dormant(bool),
     Stop,
 }
 
@@ -38,6 +101,17 @@ pub enum GameBroadcast {
     /// Serialized ServerMessage bytes ready to send over WebSocket.
     /// Uses `Bytes` for zero-copy cloning across player channels.
     EncodedMessage(Bytes),
+    /// A `GameState` some connections see their own way: each active
+    /// player with fog of war, and spectators when the game has a spectator
+    /// view. Everyone else gets `shared`.
+    PlayerViews {
+        shared: Bytes,
+        views: HashMap<PlayerId, Bytes>,
+    },
+    /// `PrivateState` and `TutorialProgress` messages, each for the
+    /// connection of the player ID paired with it and no one else. They are never observed, recorded,
+    /// or re-sent to a dormant room.
+    Private(Vec<(PlayerId, Bytes)>),
     /// Signal that the game has ended and the loop has exited.
     GameEnded,
 }
@@ -45,9 +119,14 @@ pub enum GameBroadcast {
 /// Factory function type for creating game instances on the server.
 type ServerGameFactory = fn() -> Box<dyn BreakpointGame>;
 
+/// Checks a game's lobby settings without creating the game.
+type SettingsValidator = fn(&CustomSettings) -> Result<(), SettingsError>;
+
 /// Registry mapping game IDs to factory functions (server-side).
 pub struct ServerGameRegistry {
     factories: HashMap<GameId, ServerGameFactory>,
+    validators: HashMap<GameId, SettingsValidator>,
+    tutorials: HashMap<GameId, &'static [TutorialObjective]>,
 }
 
 impl Default for ServerGameRegistry {
@@ -60,6 +139,8 @@ impl ServerGameRegistry {
     pub fn new() -> Self {
         let mut registry = Self {
             factories: HashMap::new(),
+            validators: HashMap::new(),
+            tutorials: HashMap::new(),
         };
         registry.register_defaults();
         registry
@@ -67,33 +148,134 @@ impl ServerGameRegistry {
 
     fn register_defaults(&mut self) {
         #[cfg(feature = "golf")]
-        self.factories
-            .insert(GameId::Golf, || Box::new(breakpoint_golf::MiniGolf::new()));
+        {
+            self.factories
+                .insert(GameId::Golf, || Box::new(breakpoint_golf::MiniGolf::new()));
+            self.validators.insert(
+                GameId::Golf,
+                breakpoint_golf::settings::GolfSettings::validate,
+            );
+            self.tutorials
+                .insert(GameId::Golf, breakpoint_golf::TUTORIAL_OBJECTIVES);
+        }
         #[cfg(feature = "platformer")]
-        self.factories.insert(GameId::Platformer, || {
-            Box::new(breakpoint_platformer::PlatformRacer::new())
-        });
+        {
+            self.factories.insert(GameId::Platformer, || {
+                Box::new(breakpoint_platformer::PlatformRacer::new())
+            });
+            self.validators.insert(
+                GameId::Platformer,
+                breakpoint_platformer::settings::PlatformerSettings::validate,
+            );
+            self.tutorials.insert(
+                GameId::Platformer,
+                breakpoint_platformer::TUTORIAL_OBJECTIVES,
+            );
+        }
         #[cfg(feature = "lasertag")]
-        self.factories.insert(GameId::LaserTag, || {
-            Box::new(breakpoint_lasertag::LaserTagArena::new())
-        });
+        {
+            self.factories.insert(GameId::LaserTag, || {
+                Box::new(breakpoint_lasertag::LaserTagArena::new())
+            });
+            self.validators.insert(
+                GameId::LaserTag,
+                breakpoint_lasertag::settings::LaserTagSettings::validate,
+            );
+            self.tutorials
+                .insert(GameId::LaserTag, breakpoint_lasertag::TUTORIAL_OBJECTIVES);
+        }
         #[cfg(feature = "tron")]
-        self.factories.insert(
-            GameId::Tron,
-            || Box::new(breakpoint_tron::TronCycles::new()),
-        );
+        {
+            self.factories.insert(
+                GameId::Tron,
+                || Box::new(breakpoint_tron::TronCycles::new()),
+            );
+            self.validators.insert(
+                GameId::Tron,
+                breakpoint_tron::settings::TronSettings::validate,
+            );
+            self.tutorials
+                .insert(GameId::Tron, breakpoint_tron::TUTORIAL_OBJECTIVES);
+        }
     }
 
     pub fn create(&self, game_id: GameId) -> Option<Box<dyn BreakpointGame>> {
         self.factories.get(&game_id).map(|f| f())
     }
 
+    /// Check lobby settings for `game_id` before a session is started with
+    /// them. Games without a validator accept anything.
+    pub fn validate_settings(
+        &self,
+        game_id: GameId,
+        custom: &CustomSettings,
+    ) -> Result<(), SettingsError> {
+        self.validators
+            .get(&game_id)
+            .map_or(Ok(()), |validate| validate(custom))
+    }
+
+    /// The tutorial objectives of `game_id`; empty for games without one.
+    pub fn tutorial_objectives(&self, game_id: GameId) -> &'static [TutorialObjective] {
+        self.tutorials.get(&game_id).copied().unwrap_or_default()
+    }
+
+    /// A checklist for every registered game with a tutorial, filled in
+    /// from `progress`.
+    pub fn tutorial_checklists(&self, progress: &TutorialProgress) -> Vec<TutorialChecklist> {
+        let mut games: Vec<_> = self.tutorials.iter().collect();
+        games.sort_by_key(|(id, _)| id.as_str());
+        games
+            .into_iter()
+            .map(|(id, objectives)| progress.checklist(id.as_str(), objectives))
+            .collect()
+    }
+
     /// Return the number of registered game types.
     pub fn available_games(&self) -> usize {
         self.factories.len()
     }
 }
 
+/// Generate inputs for Tron bots from the current state, applying them to
+/// the game and adding them to this tick's input buffer.
+#[cfg(feature = "tron")]
+pub(crate) fn apply_tron_bot_inputs(
+    game: &mut dyn BreakpointGame,
+    bots: &mut BotDriver,
+    input_buffer: &mut HashMap<PlayerId, Vec<u8>>,
+) {
+    let bot_state = game.serialize_state();
+    let Ok(state) = rmp_serde::from_slice::<breakpoint_tron::TronState>(&bot_state) else {
+        return;
+    };
+    let tron_config = breakpoint_tron::config::TronConfig::default();
+    let tick_rate = game.tick_rate();
+    for bot_id in bots.bot_ids() {
+        if !bots.ready(bot_id, tick_rate) {
+            continue;
+        }
+        let skill = bots.skill(bot_id);
+        let bot_input =
+            breakpoint_tron::bot::generate_bot_input(&state, bot_id, &tron_config, &skill);
+        if let Ok(input_bytes) = rmp_serde::to_vec(&bot_input) {
+            game.apply_input(bot_id, &input_bytes);
+            input_buffer.insert(bot_id, input_bytes);
+        }
+    }
+}
+
+/// Courses offered in a pre-round course vote.
+const COURSE_VOTE_CANDIDATES: usize = 3;
+/// How long a pre-round course vote stays open if not everyone votes.
+const COURSE_VOTE_DURATION: Duration = Duration::from_secs(10);
+/// How long a captain has for each pick in a team draft before one is
+/// made for them.
+const DRAFT_PICK_DURATION: Duration = Duration::from_secs(15);
+/// How often a dormant session repeats its last state, so displays
+/// watching an empty room still get a frame.
+pub const DORMANT_KEEPALIVE: Duration = Duration::from_secs(5);
+
 /// Configuration for a game session spawned by the server.
 pub struct GameSessionConfig {
     pub game_id: GameId,
@@ -101,8 +283,21 @@ pub struct GameSessionConfig {
     pub leader_id: PlayerId,
     pub round_count: u8,
     pub round_duration: Duration,
-    pub between_round_duration: Duration,
+    /// How long the results stay up and who starts the next round.
+    pub pacing: RoundPacing,
+    /// Pre-round countdown and post-round freeze-frame durations.
+    pub phase_timings: RoundPhaseTimings,
+    /// When the match ends, checked after every round.
+    pub win_condition: WinCondition,
     pub custom: HashMap<String, serde_json::Value>,
+    /// Per-player input statistics, shared with the admin API.
+    pub input_stats: SharedInputStats,
+    /// Memory accounting and caps, shared with the admin API.
+    pub memory: SharedRoomMemory,
+    /// Record the match for the history API when set.
+    pub replay: Option<ReplaySink>,
+    /// Track tutorial objectives when this is a tutorial session.
+    pub tutorial: Option<TutorialSink>,
 }
 
 /// Spawn a game tick loop as a tokio task.
@@ -137,20 +332,85 @@ async fn run_game_tick_loop(
     let round_count = if config.round_count > 0 {
         config.round_count
     } else {
-        game.round_count_hint()
+        game.round_count_for(&config.custom)
     };
 
-    let game_config = GameConfig {
-        round_count,
-        round_duration: config.round_duration,
-        custom: config.custom.clone(),
-    };
-    game.init(&config.players, &game_config);
+    let mut players = config.players.clone();
+    // Sessions that vote on their course share one base seed so every
+    // round's candidates are reproducible
+    let course_vote_seed = config
+        .custom
+        .get("course_vote")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+        .then(|| {
+            config
+                .custom
+                .get("seed")
+                .and_then(|v| v.as_u64())
+                .unwrap_or_else(rand::random)
+        });
+
+    // Set while no connected player is left to play; see `GameCommand::Dormant`
+    let mut dormant = false;
+    // Captains draft the teams once, before the first round; every round
+    // then starts from the drafted teams
+    let team_count = game.team_count_for(&config.custom);
+    let drafting = team_count >= 2
+        && config.custom.get("team_select").and_then(|v| v.as_str()) == Some("draft");
+    let mut drafted_teams = None;
+    if drafting {
+        let draft = TeamDraftRound { team_count };
+        match draft
+            .run(&mut players, &mut cmd_rx, &broadcast_tx, &mut dormant)
+            .await
+        {
+            Some(teams) => drafted_teams = Some(team_draft::teams_setting(&teams)),
+            None => {
+                let _ = broadcast_tx.send(GameBroadcast::GameEnded);
+                return;
+            },
+        }
+    }
+    let mut custom = config.custom.clone();
+    if let Some(ref teams) = drafted_teams {
+        custom.insert(team_draft::TEAMS_SETTING.to_string(), teams.clone());
+    }
+    if let Some(base_seed) = course_vote_seed {
+        let vote = CourseVoteRound {
+            game_id: config.game_id,
+            base_seed,
+            round: 1,
+        };
+        if !vote
+            .run(
+                &mut custom,
+                &mut players,
+                &mut cmd_rx,
+                &broadcast_tx,
+                &mut dormant,
+            )
+            .await
+        {
+            let _ = broadcast_tx.send(GameBroadcast::GameEnded);
+            return;
+        }
+    }
+    // A warmup plays the first round's setup with scoring off until the
+    // leader starts the match
+    let warmup = round_phase::warmup_requested(&config.custom);
+    let game_config = GameConfig::builder()
+        .round_count(round_count)
+        .round_duration(config.round_duration)
+        .custom(custom)
+        .scoring(!warmup)
+        .build();
+    game.init(&players, &game_config);
 
     // Send initial GameStart to all clients
     let start_msg = ServerMessage::GameStart(GameStartMsg {
         game_name: config.game_id.to_string(),
-        players: config.players.clone(),
+        players: players.clone(),
         leader_id: config.leader_id,
     });
     match encode_server_message(&start_msg) {
@@ -159,137 +419,301 @@ async fn run_game_tick_loop(
         },
         Err(e) => tracing::error!(error = %e, "Failed to encode GameStart"),
     }
+    let mut tutorial = match config.tutorial {
+        Some(ref sink) => {
+            let tracker =
+                TutorialTracker::load(sink, config.game_id, game.tutorial_objectives(), &players)
+                    .await;
+            let _ = broadcast_tx.send(GameBroadcast::Private(tracker.checklists()));
+            Some(tracker)
+        },
+        None => None,
+    };
 
     let tick_rate = game.tick_rate();
+    let mut recorder = config.replay.as_ref().map(|sink| {
+        let mut recorder = ReplayRecorder::new(
+            ReplayMetadata {
+                match_id: sink.match_id.clone(),
+                game: config.game_id,
+                room_code: sink.room_code.clone(),
+                players: players.clone(),
+                custom: game_config.custom.clone(),
+                tick_rate,
+                started_at: breakpoint_core::time::unix_now(),
+                ended_at: 0,
+                server_version: env!("CARGO_PKG_VERSION").to_string(),
+            },
+            sink.max_frames_per_round,
+        );
+        recorder.begin_round(1);
+        recorder
+    });
     let tick_interval = Duration::from_secs_f32(1.0 / tick_rate);
     let mut interval = tokio::time::interval(tick_interval);
     interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let mut keepalive = tokio::time::interval(DORMANT_KEEPALIVE);
+    // The latest state broadcast, repeated by the keepalive while dormant
+    let mut last_state: Option<GameBroadcast> = None;
 
     let mut tick: u32 = 0;
+    let mut bot_acks = BotAcks::default();
     let mut current_round: u8 = 1;
-    let mut cumulative_scores: HashMap<PlayerId, i32> = HashMap::new();
+    let mut tracker = MatchTracker::new(config.win_condition.clone(), round_count);
+    let mut match_start = Instant::now();
     let mut input_buffer: HashMap<PlayerId, Vec<u8>> = HashMap::new();
-    let mut players = config.players.clone();
     let mut state_buf: Vec<u8> = Vec::with_capacity(512);
+    let mut view_buf: Vec<u8> = Vec::with_capacity(512);
     let is_tron = config.game_id == GameId::Tron;
-    let bot_player_ids: Vec<PlayerId> = players.iter().filter(|p| p.is_bot).map(|p| p.id).collect();
+    let mut bots = BotDriver::new(&players);
+    let mut phase = if warmup {
+        RoundPhaseMachine::warmup(config.phase_timings)
+    } else {
+        RoundPhaseMachine::new(config.phase_timings)
+    };
+    // Phase changes are announced after the tick's state broadcast
+    let mut phase_changed = true;
+    let standings_every = scoreboard::standings_every(tick_rate);
+    let memory_every = config
+        .memory
+        .lock()
+        .map_or(1, |memory| memory.budget().sample_every(tick_rate));
+    let mut pings: HashMap<PlayerId, u16> = HashMap::new();
 
     #[cfg(feature = "profiling")]
     let mut profile_stats = breakpoint_core::profiling::ProfileStats::new(120);
 
     loop {
         tokio::select! {
-            _ = interval.tick() => {
+            _ = interval.tick(), if !dormant => {
                 #[cfg(feature = "profiling")]
                 breakpoint_core::profiling::ProfileFrame::reset();
                 #[cfg(feature = "profiling")]
                 breakpoint_core::profile!("tick");
 
-                // Generate bot inputs for Tron games
-                #[cfg(feature = "tron")]
-                if is_tron && !bot_player_ids.is_empty() {
-                    #[cfg(feature = "profiling")]
-                    breakpoint_core::profile!("bot_input");
-                    let bot_state = game.serialize_state();
-                    if let Ok(state) =
-                        rmp_serde::from_slice::<breakpoint_tron::TronState>(&bot_state)
+                // Span covers the synchronous simulation and broadcast; the
+                // between-round pause below awaits and stays outside it
+                let tick_start = Instant::now();
+                let tick_span = tracing::debug_span!(
+                    "game_tick",
+                    game = %config.game_id,
+                    round = current_round,
+                    tick = tick + 1,
+                );
+                tick_span.in_scope(|| {
+                    let dt = 1.0 / tick_rate;
+                    if phase.tick(dt).is_some() {
+                        phase_changed = true;
+                    }
+                    let warming_up = phase.phase() == RoundPhase::Warmup;
+                    let playing = phase.phase() == RoundPhase::Playing;
+
+                    // Generate bot inputs for Tron games
+                    #[cfg(feature = "tron")]
+                    if (playing || warming_up) && is_tron && !bots.is_empty() {
+                        #[cfg(feature = "profiling")]
+                        breakpoint_core::profile!("bot_input");
+                        apply_tron_bot_inputs(game, &mut bots, &mut input_buffer);
+                    }
+
+                    // Collect buffered inputs
+                    let inputs = PlayerInputs {
+                        inputs: std::mem::take(&mut input_buffer),
+                    };
+
+                    // The simulation only runs in the warmup and between the
+                    // countdown and the freeze frame; the state is still
+                    // broadcast so clients can render the starting (or final)
+                    // positions.
+                    tick += 1;
+                    let events = if playing || warming_up {
+                        #[cfg(feature = "profiling")]
+                        breakpoint_core::profile!("game_update");
+                        game.update(dt, &inputs)
+                    } else {
+                        Vec::new()
+                    };
+                    let server_time_ms = breakpoint_core::time::unix_millis();
+
+                    // Broadcast game state (reuse buffer to avoid per-tick allocations)
                     {
-                        let tron_config = breakpoint_tron::config::TronConfig::default();
-                        for &bot_id in &bot_player_ids {
-                            let bot_input = breakpoint_tron::bot::generate_bot_input(
-                                &state,
-                                bot_id,
-                                &tron_config,
-                            );
-                            if let Ok(input_bytes) = rmp_serde::to_vec(&bot_input) {
-                                game.apply_input(bot_id, &input_bytes);
-                                input_buffer.insert(bot_id, input_bytes);
+                        #[cfg(feature = "profiling")]
+                        breakpoint_core::profile!("serialize_state");
+                        game.serialize_state_into(&mut state_buf);
+                    }
+                    {
+                        #[cfg(feature = "profiling")]
+                        breakpoint_core::profile!("encode_broadcast");
+                        match encode_game_state_fast(tick, server_time_ms, &state_buf) {
+                            Ok(data) => {
+                                let shared = Bytes::from(data);
+                                let mut views = if game.has_player_views() {
+                                    encode_player_views(
+                                        game,
+                                        &players,
+                                        tick,
+                                        server_time_ms,
+                                        &mut view_buf,
+                                    )
+                                } else {
+                                    HashMap::new()
+                                };
+                                views.extend(encode_spectator_views(
+                                    game,
+                                    &players,
+                                    tick,
+                                    server_time_ms,
+                                ));
+                                let broadcast = if views.is_empty() {
+                                    GameBroadcast::EncodedMessage(shared)
+                                } else {
+                                    GameBroadcast::PlayerViews { shared, views }
+                                };
+                                last_state = Some(broadcast.clone());
+                                let _ = broadcast_tx.send(broadcast);
+                            },
+                            Err(e) => tracing::error!(
+                                tick, error = %e, "Failed to encode GameState"
+                            ),
+                        }
+                        let private = encode_private_states(game, &players, tick);
+                        if !private.is_empty() {
+                            let _ = broadcast_tx.send(GameBroadcast::Private(private));
+                        }
+                        if let Some(ref mut tutorial) = tutorial
+                            && playing
+                        {
+                            let progress = tutorial.check(game);
+                            if !progress.is_empty() {
+                                let _ = broadcast_tx.send(GameBroadcast::Private(progress));
                             }
                         }
                     }
-                }
 
-                // Collect buffered inputs
-                let inputs = PlayerInputs {
-                    inputs: std::mem::take(&mut input_buffer),
-                };
+                    // Broadcast course data if changed (first tick or wall break)
+                    let course_data = game.course_data();
+                    // The match recording starts after the warmup
+                    if let Some(ref mut recorder) = recorder
+                        && !warming_up
+                    {
+                        recorder.record_frame(
+                            tick,
+                            server_time_ms,
+                            &state_buf,
+                            course_data.as_deref(),
+                        );
+                    }
+                    if let Some(course_bytes) = course_data {
+                        let course_msg = ServerMessage::CourseUpdate(CourseUpdateMsg {
+                            version: tick,
+                            data: course_bytes,
+                        });
+                        match encode_server_message(&course_msg) {
+                            Ok(data) => {
+                                let _ = broadcast_tx.send(
+                                    GameBroadcast::EncodedMessage(Bytes::from(data)),
+                                );
+                            },
+                            Err(e) => tracing::error!(
+                                tick, error = %e, "Failed to encode CourseUpdate"
+                            ),
+                        }
+                    }
 
-                tick += 1;
-                let events = {
-                    #[cfg(feature = "profiling")]
-                    breakpoint_core::profile!("game_update");
-                    game.update(1.0 / tick_rate, &inputs)
-                };
+                    // One-shot effects (laser shots) go out once, not per tick
+                    if let Some(effects) = game.take_effects() {
+                        let effects_msg = ServerMessage::GameEffects(GameEffectsMsg {
+                            tick,
+                            data: effects,
+                        });
+                        match encode_server_message(&effects_msg) {
+                            Ok(data) => {
+                                let _ = broadcast_tx.send(
+                                    GameBroadcast::EncodedMessage(Bytes::from(data)),
+                                );
+                            },
+                            Err(e) => tracing::error!(
+                                tick, error = %e, "Failed to encode GameEffects"
+                            ),
+                        }
+                    }
 
-                // Broadcast game state (reuse buffer to avoid per-tick allocations)
-                {
-                    #[cfg(feature = "profiling")]
-                    breakpoint_core::profile!("serialize_state");
-                    game.serialize_state_into(&mut state_buf);
-                }
-                {
+                    // Record profiling stats
                     #[cfg(feature = "profiling")]
-                    breakpoint_core::profile!("encode_broadcast");
-                    match encode_game_state_fast(tick, &state_buf) {
-                        Ok(data) => {
-                            let _ = broadcast_tx.send(GameBroadcast::EncodedMessage(
-                                Bytes::from(data),
-                            ));
-                        },
-                        Err(e) => tracing::error!(
-                            tick, error = %e, "Failed to encode GameState"
-                        ),
+                    {
+                        let scopes =
+                            breakpoint_core::profiling::ProfileFrame::snapshot();
+                        profile_stats.record_frame(&scopes);
+                        if tick.is_multiple_of(100) {
+                            let report = profile_stats.compute();
+                            for s in &report {
+                                tracing::info!(
+                                    scope = s.name,
+                                    min_us = format!("{:.0}", s.min_us),
+                                    max_us = format!("{:.0}", s.max_us),
+                                    mean_us = format!("{:.0}", s.mean_us),
+                                    p95_us = format!("{:.0}", s.p95_us),
+                                    "profile"
+                                );
+                            }
+                        }
                     }
-                }
 
-                // Broadcast course data if changed (first tick or wall break)
-                if let Some(course_bytes) = game.course_data() {
-                    let course_msg = ServerMessage::CourseUpdate(CourseUpdateMsg {
-                        version: tick,
-                        data: course_bytes,
-                    });
-                    match encode_server_message(&course_msg) {
-                        Ok(data) => {
-                            let _ = broadcast_tx.send(
-                                GameBroadcast::EncodedMessage(Bytes::from(data)),
-                            );
-                        },
-                        Err(e) => tracing::error!(
-                            tick, error = %e, "Failed to encode CourseUpdate"
-                        ),
+                    if playing && tick.is_multiple_of(standings_every) {
+                        let standings = scoreboard::standings(
+                            game,
+                            current_round,
+                            round_count,
+                            tracker.totals(),
+                            &pings,
+                        );
+                        send_standings(&broadcast_tx, standings);
                     }
-                }
 
-                // Record profiling stats
-                #[cfg(feature = "profiling")]
-                {
-                    let scopes =
-                        breakpoint_core::profiling::ProfileFrame::snapshot();
-                    profile_stats.record_frame(&scopes);
-                    if tick.is_multiple_of(100) {
-                        let report = profile_stats.compute();
-                        for s in &report {
-                            tracing::info!(
-                                scope = s.name,
-                                min_us = format!("{:.0}", s.min_us),
-                                max_us = format!("{:.0}", s.max_us),
-                                mean_us = format!("{:.0}", s.mean_us),
-                                p95_us = format!("{:.0}", s.p95_us),
-                                "profile"
-                            );
-                        }
+                    if tick.is_multiple_of(memory_every) {
+                        sample_memory(
+                            game,
+                            &state_buf,
+                            recorder.as_mut(),
+                            &config.memory,
+                            config.game_id,
+                        );
+                    }
+
+                    // Check for round completion; hold the final frame first
+                    let round_complete = playing
+                        && (events.iter().any(|e| matches!(e, GameEvent::RoundComplete))
+                            || game.is_round_complete());
+                    if round_complete && phase.finish().is_some() {
+                        phase_changed = true;
+                    }
+                    // A warmup round that ends starts over
+                    if warming_up
+                        && (events.iter().any(|e| matches!(e, GameEvent::RoundComplete))
+                            || game.is_round_complete())
+                    {
+                        game.init(&players, &game_config);
                     }
-                }
 
-                // Check for round completion
-                let round_complete = events.iter().any(|e| {
-                    matches!(e, GameEvent::RoundComplete)
-                }) || game.is_round_complete();
+                    if phase_changed {
+                        phase_changed = false;
+                        send_round_phase(&broadcast_tx, current_round, &phase);
+                    }
+                });
+                tracing::trace!(
+                    target: METRICS_TARGET,
+                    game = %config.game_id,
+                    histogram.game_tick_ms = tick_start.elapsed().as_secs_f64() * 1000.0,
+                );
 
-                if round_complete {
+                if phase.phase() == RoundPhase::Results {
                     let results = game.round_results();
-                    for s in &results {
-                        *cumulative_scores.entry(s.player_id).or_insert(0) += s.score;
+                    let heatmap = game.round_heatmap();
+                    let victory = game.victory_summary();
+                    tracker.record_round(&results);
+                    bots.record_round(&results);
+                    if let Some(ref mut recorder) = recorder {
+                        recorder.end_round(results.clone(), heatmap.clone());
                     }
 
                     let scores: Vec<PlayerScoreEntry> = results
@@ -297,19 +721,32 @@ async fn run_game_tick_loop(
                         .map(|s| PlayerScoreEntry {
                             player_id: s.player_id,
                             score: s.score,
+                            disconnected: s.disconnected,
                         })
                         .collect();
 
-                    if current_round >= round_count {
-                        // Final round — send GameEnd
-                        let final_scores: Vec<PlayerScoreEntry> = cumulative_scores
+                    if tracker.is_over(match_start.elapsed()) {
+                        // Final round — send GameEnd. Anyone with a score
+                        // who is not still playing has left the match.
+                        let still_playing: HashSet<PlayerId> = results
+                            .iter()
+                            .filter(|s| !s.disconnected)
+                            .map(|s| s.player_id)
+                            .collect();
+                        let final_scores: Vec<PlayerScoreEntry> = tracker
+                            .totals()
                             .iter()
                             .map(|(&pid, &score)| PlayerScoreEntry {
                                 player_id: pid,
                                 score,
+                                disconnected: !still_playing.contains(&pid),
                             })
                             .collect();
-                        let end_msg = ServerMessage::GameEnd(GameEndMsg { final_scores });
+                        let end_msg = ServerMessage::GameEnd(GameEndMsg {
+                            final_scores,
+                            match_id: config.replay.as_ref().map(|s| s.match_id.clone()),
+                            victory: Some(victory),
+                        });
                         match encode_server_message(&end_msg) {
                             Ok(data) => {
                                 let _ = broadcast_tx.send(
@@ -320,14 +757,33 @@ async fn run_game_tick_loop(
                                 error = %e, "Failed to encode GameEnd"
                             ),
                         }
+                        if let (Some(recorder), Some(sink)) = (recorder.take(), &config.replay) {
+                            let final_scores = tracker
+                                .totals()
+                                .iter()
+                                .map(|(&player_id, &score)| PlayerScore {
+                                    player_id,
+                                    score,
+                                    disconnected: !still_playing.contains(&player_id),
+                                })
+                                .collect();
+                            let replay = recorder
+                                .finish(final_scores, breakpoint_core::time::unix_now());
+                            tokio::spawn(store_replay(Arc::clone(&sink.history), replay));
+                        }
                         break;
                     }
 
                     // More rounds — send RoundEnd, wait, re-init
+                    let mut intermission = Intermission::new(config.pacing);
                     let round_end_msg = ServerMessage::RoundEnd(RoundEndMsg {
                         round: current_round,
                         scores,
-                        between_round_secs: config.between_round_duration.as_secs() as u16,
+                        between_round_secs: intermission
+                            .remaining()
+                            .map_or(0, |secs| secs.ceil() as u16),
+                        heatmap,
+                        victory: Some(victory),
                     });
                     match encode_server_message(&round_end_msg) {
                         Ok(data) => {
@@ -343,9 +799,19 @@ async fn run_game_tick_loop(
                     }
 
                     // Pause between rounds (drain commands but don't tick)
-                    let pause_duration = config.between_round_duration;
-                    let pause_end = tokio::time::Instant::now() + pause_duration;
-                    while tokio::time::Instant::now() < pause_end {
+                    send_intermission(&broadcast_tx, current_round, &intermission);
+                    let mut last = tokio::time::Instant::now();
+                    loop {
+                        let now = tokio::time::Instant::now();
+                        intermission.tick(now.duration_since(last).as_secs_f32());
+                        last = now;
+                        if intermission.is_done() {
+                            break;
+                        }
+                        // Waiting for the leader has no deadline
+                        let wake = intermission
+                            .remaining()
+                            .map(|secs| now + Duration::from_secs_f32(secs));
                         tokio::select! {
                             cmd = cmd_rx.recv() => {
                                 match cmd {
@@ -356,23 +822,45 @@ async fn run_game_tick_loop(
                                     Some(GameCommand::PlayerLeft { player_id }) => {
                                         game.player_left(player_id);
                                         players.retain(|p| p.id != player_id);
+                                        if let Ok(mut tracker) = config.input_stats.lock() {
+                                            tracker.remove(player_id);
+                                        }
                                     },
                                     Some(GameCommand::PlayerJoined { player_id: _, player }) => {
                                         game.player_joined(&player);
                                         players.push(player);
                                     },
+                                    Some(GameCommand::Dormant(idle)) => dormant = idle,
+                                    Some(GameCommand::Ping { player_id, rtt_ms }) => {
+                                        pings.insert(player_id, rtt_ms);
+                                    },
+                                    // Stale requests for an earlier round are ignored
+                                    Some(GameCommand::AdvanceRound { round })
+                                        if round == current_round
+                                            && intermission.request_advance() =>
+                                    {
+                                        send_intermission(
+                                            &broadcast_tx,
+                                            current_round,
+                                            &intermission,
+                                        );
+                                    },
                                     _ => {},
                                 }
                             }
-                            _ = tokio::time::sleep_until(pause_end) => {
-                                break;
-                            }
+                            _ = async {
+                                match wake {
+                                    Some(at) => tokio::time::sleep_until(at).await,
+                                    None => std::future::pending().await,
+                                }
+                            } => {}
                         }
                     }
 
                     // Advance round and re-init
                     current_round += 1;
                     tick = 0;
+                    bot_acks.new_round();
                     input_buffer.clear();
 
                     // Promote spectators for new round
@@ -381,16 +869,43 @@ async fn run_game_tick_loop(
                     }
 
                     let mut custom = config.custom.clone();
-                    custom.insert(
-                        "hole_index".to_string(),
-                        serde_json::json!(current_round - 1),
-                    );
-                    let next_config = GameConfig {
-                        round_count,
-                        round_duration: config.round_duration,
-                        custom,
-                    };
+                    if let Some(ref teams) = drafted_teams {
+                        custom.insert(team_draft::TEAMS_SETTING.to_string(), teams.clone());
+                    }
+                    if let Some(base_seed) = course_vote_seed {
+                        let vote = CourseVoteRound {
+                            game_id: config.game_id,
+                            base_seed,
+                            round: current_round,
+                        };
+                        if !vote
+                            .run(
+                                &mut custom,
+                                &mut players,
+                                &mut cmd_rx,
+                                &broadcast_tx,
+                                &mut dormant,
+                            )
+                            .await
+                        {
+                            break;
+                        }
+                    }
+                    let next_config = GameConfig::builder()
+                        .round_count(round_count)
+                        .round_duration(config.round_duration)
+                        .custom(custom)
+                        .setting("hole_index", current_round - 1)
+                        .build();
                     game.init(&players, &next_config);
+                    if let Some(ref mut recorder) = recorder {
+                        recorder.begin_round(current_round);
+                    }
+                    if let Ok(mut memory) = config.memory.lock() {
+                        memory.begin_round();
+                    }
+                    phase.restart();
+                    phase_changed = true;
 
                     // Send GameStart for next round
                     let next_start = ServerMessage::GameStart(GameStartMsg {
@@ -416,14 +931,36 @@ async fn run_game_tick_loop(
                     interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
                 }
             }
+            _ = keepalive.tick(), if dormant => {
+                if let Some(ref state) = last_state {
+                    let _ = broadcast_tx.send(state.clone());
+                }
+            }
             cmd = cmd_rx.recv() => {
                 match cmd {
                     Some(GameCommand::PlayerInput { player_id, tick: _, input_data }) => {
+                        record_input_stats(&config.input_stats, player_id, &game.input_action_key(&input_data));
+                        // Inputs outside the playing phase are dropped
+                        if !phase.phase().accepts_input() {
+                            continue;
+                        }
                         // Buffer input for next tick; also apply immediately for
                         // responsiveness (game.apply_input handles dedup)
                         game.apply_input(player_id, &input_data);
                         input_buffer.insert(player_id, input_data);
                     },
+                    Some(GameCommand::BotAck { player_id, tick: acked, input_data }) => {
+                        if !phase.phase().accepts_input() {
+                            continue;
+                        }
+                        if let Err(reason) = bot_acks.accept(player_id, acked, tick) {
+                            tracing::debug!(player_id, acked, tick, ?reason, "Dropped bot ack");
+                            continue;
+                        }
+                        record_input_stats(&config.input_stats, player_id, &game.input_action_key(&input_data));
+                        game.apply_input(player_id, &input_data);
+                        input_buffer.insert(player_id, input_data);
+                    },
                     Some(GameCommand::PlayerJoined { player_id: _, player }) => {
                         game.player_joined(&player);
                         players.push(player);
@@ -431,10 +968,83 @@ async fn run_game_tick_loop(
                     Some(GameCommand::PlayerLeft { player_id }) => {
                         game.player_left(player_id);
                         players.retain(|p| p.id != player_id);
+                        pings.remove(&player_id);
+                        if let Ok(mut tracker) = config.input_stats.lock() {
+                            tracker.remove(player_id);
+                        }
                         if players.is_empty() {
                             break;
                         }
                     },
+                    Some(GameCommand::Ping { player_id, rtt_ms }) => {
+                        pings.insert(player_id, rtt_ms);
+                    },
+                    // Ballots and picks only count while a vote or draft is open
+                    Some(GameCommand::CourseVote { .. } | GameCommand::DraftPick { .. }) => {},
+                    // Round 0 is the warmup: the leader starts the match
+                    Some(GameCommand::AdvanceRound { round: 0 })
+                        if phase.end_warmup().is_some() =>
+                    {
+                        for p in &mut players {
+                            p.is_spectator = false;
+                        }
+                        let match_config = GameConfig {
+                            scoring: true,
+                            ..game_config.clone()
+                        };
+                        game.init(&players, &match_config);
+                        tick = 0;
+                        bot_acks.new_round();
+                        input_buffer.clear();
+                        match_start = Instant::now();
+                        if let Ok(mut memory) = config.memory.lock() {
+                            memory.begin_round();
+                        }
+                        phase_changed = true;
+                    },
+                    // Otherwise only meaningful between rounds
+                    Some(GameCommand::AdvanceRound { .. }) => {},
+                    Some(GameCommand::Dormant(idle)) => {
+                        if idle != dormant {
+                            tracing::debug!(game = %config.game_id, dormant = idle, "Session dormancy changed");
+                        }
+                        dormant = idle;
+                        // Pick up from here on waking rather than with a
+                        // burst of missed ticks, and skip the immediate
+                        // keepalive
+                        interval.reset();
+                        keepalive.reset();
+                    },
+                    Some(GameCommand::Debug { command }) => {
+                        match game.apply_debug(&command) {
+                            Ok(()) => tracing::info!(
+                                game = %config.game_id,
+                                ?command,
+                                "Applied debug command"
+                            ),
+                            Err(e) => tracing::warn!(
+                                game = %config.game_id,
+                                ?command,
+                                error = %e,
+                                "Debug command refused"
+                            ),
+                        }
+                    },
+                    Some(GameCommand::ConfigChange { delta }) => {
+                        match game.apply_config_change(&delta) {
+                            Ok(()) => tracing::info!(
+                                game = %config.game_id,
+                                changes = ?delta.changes,
+                                "Applied live settings change"
+                            ),
+                            Err(e) => tracing::warn!(
+                                game = %config.game_id,
+                                changes = ?delta.changes,
+                                error = %e,
+                                "Live settings change refused"
+                            ),
+                        }
+                    },
                     Some(GameCommand::Stop) | None => {
                         break;
                     },
@@ -446,64 +1056,512 @@ async fn run_game_tick_loop(
     let _ = broadcast_tx.send(GameBroadcast::GameEnded);
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use breakpoint_core::player::PlayerColor;
+/// A pre-round course vote: which game, which round, and the session's
+/// base seed the candidates are derived from.
+struct CourseVoteRound {
+    game_id: GameId,
+    base_seed: u64,
+    round: u8,
+}
 
-    fn make_test_players(n: usize) -> Vec<Player> {
-        (0..n)
-            .map(|i| Player {
-                id: (i + 1) as PlayerId,
-                display_name: format!("Player{}", i + 1),
-                color: PlayerColor::PALETTE[i % PlayerColor::PALETTE.len()],
-                is_leader: i == 0,
-                is_spectator: false,
-                is_bot: false,
-            })
-            .collect()
+impl CourseVoteRound {
+    /// Courses on offer. Only the platformer builds its course from a seed,
+    /// so other games have nothing to vote on.
+    fn candidates(&self) -> Vec<CourseCandidate> {
+        match self.game_id {
+            #[cfg(feature = "platformer")]
+            GameId::Platformer => breakpoint_platformer::course_gen::candidate_seeds(
+                self.base_seed,
+                self.round,
+                COURSE_VOTE_CANDIDATES,
+            )
+            .into_iter()
+            .map(breakpoint_platformer::minimap::preview)
+            .collect(),
+            _ => Vec::new(),
+        }
     }
 
-    #[tokio::test]
-    async fn registry_creates_golf() {
-        let registry = ServerGameRegistry::new();
-        let game = registry.create(GameId::Golf);
-        assert!(game.is_some(), "Golf should be registered");
-    }
+    /// Offer the candidates and collect ballots until every human player
+    /// has voted or time runs out, then put the winning seed into `custom`.
+    /// Returns false if the session stopped or emptied while voting.
+    async fn run(
+        &self,
+        custom: &mut HashMap<String, serde_json::Value>,
+        players: &mut Vec<Player>,
+        cmd_rx: &mut mpsc::UnboundedReceiver<GameCommand>,
+        broadcast_tx: &mpsc::UnboundedSender<GameBroadcast>,
+        dormant: &mut bool,
+    ) -> bool {
+        let candidates = self.candidates();
+        if candidates.is_empty() {
+            return true;
+        }
+        let seeds: Vec<u64> = candidates.iter().map(|c| c.seed).collect();
+        let mut vote = CourseVote::new(seeds.len() as u8);
+        let deadline = tokio::time::Instant::now() + COURSE_VOTE_DURATION;
+        let remaining = || {
+            deadline
+                .saturating_duration_since(tokio::time::Instant::now())
+                .as_secs_f32()
+        };
 
-    #[tokio::test]
-    async fn registry_creates_platformer() {
-        let registry = ServerGameRegistry::new();
-        let game = registry.create(GameId::Platformer);
-        assert!(game.is_some(), "Platformer should be registered");
+        self.announce(broadcast_tx, candidates, &vote, remaining(), None);
+        let everyone_voted = |vote: &CourseVote, players: &[Player]| {
+            players
+                .iter()
+                .filter(|p| !p.is_bot && !p.is_spectator)
+                .all(|p| vote.has_voted(p.id))
+        };
+        while !everyone_voted(&vote, players) {
+            tokio::select! {
+                cmd = cmd_rx.recv() => {
+                    match cmd {
+                        Some(GameCommand::Stop) | None => return false,
+                        Some(GameCommand::PlayerLeft { player_id }) => {
+                            vote.withdraw(player_id);
+                            players.retain(|p| p.id != player_id);
+                            if players.is_empty() {
+                                return false;
+                            }
+                        },
+                        Some(GameCommand::PlayerJoined { player_id: _, player }) => {
+                            players.push(player);
+                        },
+                        Some(GameCommand::CourseVote { player_id, round, choice })
+                            if round == self.round && vote.cast(player_id, choice) =>
+                        {
+                            self.announce(broadcast_tx, Vec::new(), &vote, remaining(), None);
+                        },
+                        Some(GameCommand::Dormant(idle)) => *dormant = idle,
+                        _ => {},
+                    }
+                }
+                _ = tokio::time::sleep_until(deadline) => break,
+            }
+        }
+
+        let winner = vote.winner();
+        self.announce(broadcast_tx, Vec::new(), &vote, 0.0, Some(winner));
+        custom.insert(
+            "seed".to_string(),
+            serde_json::json!(seeds[winner as usize]),
+        );
+        true
     }
 
-    #[tokio::test]
-    async fn registry_creates_lasertag() {
-        let registry = ServerGameRegistry::new();
-        let game = registry.create(GameId::LaserTag);
-        assert!(game.is_some(), "LaserTag should be registered");
+    fn announce(
+        &self,
+        broadcast_tx: &mpsc::UnboundedSender<GameBroadcast>,
+        candidates: Vec<CourseCandidate>,
+        vote: &CourseVote,
+        remaining_secs: f32,
+        winner: Option<u8>,
+    ) {
+        let msg = ServerMessage::CourseVote(CourseVoteMsg {
+            round: self.round,
+            candidates,
+            votes: vote.tally(),
+            remaining_secs,
+            winner,
+        });
+        match encode_server_message(&msg) {
+            Ok(data) => {
+                let _ = broadcast_tx.send(GameBroadcast::EncodedMessage(Bytes::from(data)));
+            },
+            Err(e) => {
+                tracing::error!(round = self.round, error = %e, "Failed to encode CourseVote")
+            },
+        }
     }
+}
 
-    #[tokio::test]
-    async fn game_session_starts_and_broadcasts_state() {
-        let registry = ServerGameRegistry::new();
-        let players = make_test_players(2);
+/// A captain draft before a team match.
+struct TeamDraftRound {
+    team_count: u8,
+}
 
-        let config = GameSessionConfig {
-            game_id: GameId::Golf,
-            players: players.clone(),
-            leader_id: 1,
-            round_count: 1,
-            round_duration: Duration::from_secs(90),
-            between_round_duration: Duration::from_secs(1),
-            custom: HashMap::new(),
-        };
+impl TeamDraftRound {
+    /// Choose captains, preferring players on their own connection, and
+    /// let them take turns picking the rest. A captain who is a bot, has
+    /// left, or lets the pick clock run out gets the next player in the
+    /// pool. Returns the teams, or `None` if the session stopped or
+    /// emptied while drafting.
+    async fn run(
+        &self,
+        players: &mut Vec<Player>,
+        cmd_rx: &mut mpsc::UnboundedReceiver<GameCommand>,
+        broadcast_tx: &mpsc::UnboundedSender<GameBroadcast>,
+        dormant: &mut bool,
+    ) -> Option<HashMap<PlayerId, u8>> {
+        use rand::seq::SliceRandom;
+
+        let can_pick = |p: &Player| !p.is_bot && p.hot_seat_of.is_none();
+        let mut order: Vec<&Player> = players.iter().filter(|p| !p.is_spectator).collect();
+        order.shuffle(&mut rand::rng());
+        order.sort_by_key(|p| !can_pick(p));
+        let mut ids: Vec<PlayerId> = order.iter().map(|p| p.id).collect();
+        let pool = ids.split_off(ids.len().min(self.team_count as usize));
+        let mut draft = TeamDraft::new(ids, pool);
+
+        let mut deadline = tokio::time::Instant::now() + DRAFT_PICK_DURATION;
+        self.announce(broadcast_tx, &draft, deadline);
+        while let Some(captain) = draft.captain() {
+            if !players.iter().any(|p| p.id == captain && can_pick(p)) {
+                draft.auto_pick();
+                deadline = tokio::time::Instant::now() + DRAFT_PICK_DURATION;
+                self.announce(broadcast_tx, &draft, deadline);
+                continue;
+            }
+            tokio::select! {
+                cmd = cmd_rx.recv() => {
+                    match cmd {
+                        Some(GameCommand::Stop) | None => return None,
+                        Some(GameCommand::PlayerLeft { player_id }) => {
+                            draft.withdraw(player_id);
+                            players.retain(|p| p.id != player_id);
+                            if players.is_empty() {
+                                return None;
+                            }
+                            self.announce(broadcast_tx, &draft, deadline);
+                        },
+                        Some(GameCommand::PlayerJoined { player_id: _, player }) => {
+                            if !player.is_spectator {
+                                draft.add(player.id);
+                                self.announce(broadcast_tx, &draft, deadline);
+                            }
+                            players.push(player);
+                        },
+                        Some(GameCommand::DraftPick { player_id, pick })
+                            if draft.pick(player_id, pick) =>
+                        {
+                            deadline = tokio::time::Instant::now() + DRAFT_PICK_DURATION;
+                            self.announce(broadcast_tx, &draft, deadline);
+                        },
+                        Some(GameCommand::Dormant(idle)) => *dormant = idle,
+                        _ => {},
+                    }
+                }
+                _ = tokio::time::sleep_until(deadline) => {
+                    draft.auto_pick();
+                    deadline = tokio::time::Instant::now() + DRAFT_PICK_DURATION;
+                    self.announce(broadcast_tx, &draft, deadline);
+                }
+            }
+        }
+        Some(draft.teams())
+    }
 
-        let (cmd_tx, mut broadcast_rx, handle) =
-            spawn_game_session(&registry, config).expect("should spawn");
+    fn announce(
+        &self,
+        broadcast_tx: &mpsc::UnboundedSender<GameBroadcast>,
+        draft: &TeamDraft,
+        deadline: tokio::time::Instant,
+    ) {
+        let turn = draft.turn();
+        let msg = ServerMessage::TeamDraft(TeamDraftMsg {
+            captains: draft.captains().to_vec(),
+            picks: draft
+                .picks()
+                .iter()
+                .map(|&(player_id, team)| DraftedPlayer { player_id, team })
+                .collect(),
+            pool: draft.pool().to_vec(),
+            turn,
+            remaining_secs: if turn.is_some() {
+                deadline
+                    .saturating_duration_since(tokio::time::Instant::now())
+                    .as_secs_f32()
+            } else {
+                0.0
+            },
+        });
+        match encode_server_message(&msg) {
+            Ok(data) => {
+                let _ = broadcast_tx.send(GameBroadcast::EncodedMessage(Bytes::from(data)));
+            },
+            Err(e) => tracing::error!(error = %e, "Failed to encode TeamDraft"),
+        }
+    }
+}
 
-        // First message should be GameStart
+/// Announce the current round phase to all clients.
+fn send_round_phase(
+    broadcast_tx: &mpsc::UnboundedSender<GameBroadcast>,
+    round: u8,
+    phase: &RoundPhaseMachine,
+) {
+    let msg = ServerMessage::RoundPhase(RoundPhaseMsg {
+        round,
+        phase: phase.phase(),
+        remaining_secs: phase.remaining(),
+    });
+    match encode_server_message(&msg) {
+        Ok(data) => {
+            let _ = broadcast_tx.send(GameBroadcast::EncodedMessage(Bytes::from(data)));
+        },
+        Err(e) => tracing::error!(round, error = %e, "Failed to encode RoundPhase"),
+    }
+}
+
+/// Account for what the session holds and trim whatever is over budget.
+fn sample_memory(
+    game: &mut dyn BreakpointGame,
+    state: &[u8],
+    recorder: Option<&mut ReplayRecorder>,
+    memory: &SharedRoomMemory,
+    game_id: GameId,
+) {
+    let usage = game.memory_usage();
+    let sample = RoomMemory {
+        state_bytes: state.len(),
+        walls_bytes: usage.walls_bytes,
+        trails_bytes: usage.trails_bytes,
+        backlog_bytes: 0,
+        replay_bytes: recorder.as_ref().map_or(0, |r| r.bytes()),
+    };
+    tracing::trace!(
+        target: METRICS_TARGET,
+        game = %game_id,
+        histogram.room_memory_bytes = sample.total() as u64,
+    );
+    let Ok(mut memory) = memory.lock() else {
+        return;
+    };
+    let over = memory.record(sample);
+    if over.game {
+        memory.record_trim(game.trim_memory());
+    }
+    if over.replay
+        && let Some(recorder) = recorder
+    {
+        memory.record_trim(recorder.thin());
+    }
+}
+
+fn send_standings(broadcast_tx: &mpsc::UnboundedSender<GameBroadcast>, standings: StandingsMsg) {
+    let round = standings.round;
+    match encode_server_message(&ServerMessage::Standings(standings)) {
+        Ok(data) => {
+            let _ = broadcast_tx.send(GameBroadcast::EncodedMessage(Bytes::from(data)));
+        },
+        Err(e) => tracing::error!(round, error = %e, "Failed to encode Standings"),
+    }
+}
+
+fn send_intermission(
+    broadcast_tx: &mpsc::UnboundedSender<GameBroadcast>,
+    round: u8,
+    intermission: &Intermission,
+) {
+    let msg = ServerMessage::Intermission(IntermissionMsg {
+        round,
+        remaining_secs: intermission.remaining(),
+    });
+    match encode_server_message(&msg) {
+        Ok(data) => {
+            let _ = broadcast_tx.send(GameBroadcast::EncodedMessage(Bytes::from(data)));
+        },
+        Err(e) => tracing::error!(round, error = %e, "Failed to encode Intermission"),
+    }
+}
+
+/// Encode each active player's own view of the game state.
+fn encode_player_views(
+    game: &dyn BreakpointGame,
+    players: &[Player],
+    tick: u32,
+    server_time_ms: u64,
+    buf: &mut Vec<u8>,
+) -> HashMap<PlayerId, Bytes> {
+    players
+        .iter()
+        .filter(|p| !p.is_spectator)
+        .filter_map(|p| {
+            game.serialize_state_for_into(p.id, buf);
+            match encode_game_state_fast(tick, server_time_ms, buf) {
+                Ok(data) => Some((p.id, Bytes::from(data))),
+                Err(e) => {
+                    tracing::error!(tick, player_id = p.id, error = %e, "Failed to encode player view");
+                    None
+                },
+            }
+        })
+        .collect()
+}
+
+/// Encode the spectator view once, for every spectator, if the game has one.
+fn encode_spectator_views(
+    game: &dyn BreakpointGame,
+    players: &[Player],
+    tick: u32,
+    server_time_ms: u64,
+) -> Vec<(PlayerId, Bytes)> {
+    let spectators: Vec<PlayerId> = players
+        .iter()
+        .filter(|p| p.is_spectator && !p.is_bot)
+        .map(|p| p.id)
+        .collect();
+    if spectators.is_empty() {
+        return Vec::new();
+    }
+    let Some(state) = game.spectator_view_state() else {
+        return Vec::new();
+    };
+    match encode_game_state_fast(tick, server_time_ms, &state) {
+        Ok(data) => {
+            let data = Bytes::from(data);
+            spectators
+                .into_iter()
+                .map(|id| (id, data.clone()))
+                .collect()
+        },
+        Err(e) => {
+            tracing::error!(tick, error = %e, "Failed to encode spectator view");
+            Vec::new()
+        },
+    }
+}
+
+/// Encode each active player's private data, paired with the player whose
+/// connection carries it: their own, or their host's for a hot-seat guest.
+fn encode_private_states(
+    game: &dyn BreakpointGame,
+    players: &[Player],
+    tick: u32,
+) -> Vec<(PlayerId, Bytes)> {
+    players
+        .iter()
+        .filter(|p| !p.is_spectator && !p.is_bot)
+        .filter_map(|p| {
+            let data = game.private_state_for(p.id)?;
+            let msg = ServerMessage::PrivateState(PrivateStateMsg {
+                tick,
+                player_id: p.id,
+                data,
+            });
+            match encode_server_message(&msg) {
+                Ok(data) => Some((p.hot_seat_of.unwrap_or(p.id), Bytes::from(data))),
+                Err(e) => {
+                    tracing::error!(tick, player_id = p.id, error = %e, "Failed to encode PrivateState");
+                    None
+                },
+            }
+        })
+        .collect()
+}
+
+/// Record a player input in the session's statistics and log anti-bot
+/// heuristic violations.
+fn record_input_stats(stats: &SharedInputStats, player_id: PlayerId, input_data: &[u8]) {
+    let Ok(mut tracker) = stats.lock() else {
+        tracing::warn!(player_id, "Input stats mutex poisoned");
+        return;
+    };
+    if let Some(violation) = tracker.record(player_id, input_data, std::time::Instant::now()) {
+        tracing::warn!(player_id, ?violation, "Suspicious input pattern");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory_budget::{MemoryBudget, RoomMemoryTracker};
+    use breakpoint_core::player::PlayerColor;
+
+    fn make_test_players(n: usize) -> Vec<Player> {
+        (0..n)
+            .map(|i| Player {
+                id: (i + 1) as PlayerId,
+                display_name: format!("Player{}", i + 1),
+                color: PlayerColor::PALETTE[i % PlayerColor::PALETTE.len()],
+                is_leader: i == 0,
+                is_spectator: false,
+                is_bot: false,
+                bot_difficulty: Default::default(),
+                hot_seat_of: None,
+                external_bot: false,
+            })
+            .collect()
+    }
+
+    /// Skip the countdown and freeze frame so tests see play immediately.
+    fn no_phases() -> RoundPhaseTimings {
+        RoundPhaseTimings {
+            countdown_secs: 0.0,
+            freeze_frame_secs: 0.0,
+        }
+    }
+
+    /// One-second intermissions that advance on their own.
+    fn quick_pacing() -> RoundPacing {
+        RoundPacing {
+            intermission_secs: 1,
+            auto_advance: true,
+            min_results_secs: 0,
+        }
+    }
+
+    /// Next decoded server message from the session.
+    async fn next_server_msg(rx: &mut mpsc::UnboundedReceiver<GameBroadcast>) -> ServerMessage {
+        match rx.recv().await {
+            Some(
+                GameBroadcast::EncodedMessage(data)
+                | GameBroadcast::PlayerViews { shared: data, .. },
+            ) => {
+                breakpoint_core::net::protocol::decode_server_message(&data).expect("should decode")
+            },
+            Some(GameBroadcast::Private(_)) => panic!("Expected a message for everyone"),
+            Some(GameBroadcast::GameEnded) | None => panic!("Game ended unexpectedly"),
+        }
+    }
+
+    #[tokio::test]
+    async fn registry_creates_golf() {
+        let registry = ServerGameRegistry::new();
+        let game = registry.create(GameId::Golf);
+        assert!(game.is_some(), "Golf should be registered");
+    }
+
+    #[tokio::test]
+    async fn registry_creates_platformer() {
+        let registry = ServerGameRegistry::new();
+        let game = registry.create(GameId::Platformer);
+        assert!(game.is_some(), "Platformer should be registered");
+    }
+
+    #[tokio::test]
+    async fn registry_creates_lasertag() {
+        let registry = ServerGameRegistry::new();
+        let game = registry.create(GameId::LaserTag);
+        assert!(game.is_some(), "LaserTag should be registered");
+    }
+
+    #[tokio::test]
+    async fn game_session_starts_and_broadcasts_state() {
+        let registry = ServerGameRegistry::new();
+        let players = make_test_players(2);
+
+        let config = GameSessionConfig {
+            game_id: GameId::Golf,
+            players: players.clone(),
+            leader_id: 1,
+            round_count: 1,
+            round_duration: Duration::from_secs(90),
+            pacing: quick_pacing(),
+            phase_timings: no_phases(),
+            win_condition: WinCondition::default(),
+            custom: HashMap::new(),
+            input_stats: Default::default(),
+            memory: Default::default(),
+            replay: None,
+            tutorial: None,
+        };
+
+        let (cmd_tx, mut broadcast_rx, handle) =
+            spawn_game_session(&registry, config).expect("should spawn");
+
+        // First message should be GameStart
         let msg = broadcast_rx.recv().await.expect("should receive broadcast");
         match msg {
             GameBroadcast::EncodedMessage(data) => {
@@ -536,6 +1594,239 @@ mod tests {
         let _ = handle.await;
     }
 
+    #[tokio::test]
+    async fn fog_of_war_sends_players_their_own_views() {
+        let registry = ServerGameRegistry::new();
+        let mut players = make_test_players(3);
+        players[2].is_spectator = true;
+
+        let config = GameSessionConfig {
+            game_id: GameId::LaserTag,
+            players,
+            leader_id: 1,
+            round_count: 1,
+            round_duration: Duration::from_secs(90),
+            pacing: quick_pacing(),
+            phase_timings: no_phases(),
+            win_condition: WinCondition::default(),
+            custom: HashMap::from([("fog_of_war".to_string(), serde_json::json!(true))]),
+            input_stats: Default::default(),
+            memory: Default::default(),
+            replay: None,
+            tutorial: None,
+        };
+
+        let (cmd_tx, mut broadcast_rx, handle) =
+            spawn_game_session(&registry, config).expect("should spawn");
+        let _start = broadcast_rx.recv().await.expect("should receive GameStart");
+
+        match broadcast_rx.recv().await.expect("should receive tick") {
+            GameBroadcast::PlayerViews { shared, views } => {
+                let mut viewers: Vec<PlayerId> = views.keys().copied().collect();
+                viewers.sort_unstable();
+                assert_eq!(viewers, vec![1, 2, 3], "Spectators get the spectator view");
+                for data in views.values().chain([&shared]) {
+                    let decoded = breakpoint_core::net::protocol::decode_server_message(data)
+                        .expect("should decode");
+                    assert!(matches!(decoded, ServerMessage::GameState(_)));
+                }
+            },
+            other => panic!("Expected PlayerViews, got: {other:?}"),
+        }
+
+        let _ = cmd_tx.send(GameCommand::Stop);
+        let _ = handle.await;
+    }
+
+    #[tokio::test]
+    async fn spectators_get_the_spectator_view() {
+        let registry = ServerGameRegistry::new();
+        let mut players = make_test_players(3);
+        players[2].is_spectator = true;
+
+        let config = GameSessionConfig {
+            game_id: GameId::Tron,
+            players,
+            leader_id: 1,
+            round_count: 1,
+            round_duration: Duration::from_secs(90),
+            pacing: quick_pacing(),
+            phase_timings: no_phases(),
+            win_condition: WinCondition::default(),
+            custom: HashMap::new(),
+            input_stats: Default::default(),
+            memory: Default::default(),
+            replay: None,
+            tutorial: None,
+        };
+
+        let (cmd_tx, mut broadcast_rx, handle) =
+            spawn_game_session(&registry, config).expect("should spawn");
+        let _start = broadcast_rx.recv().await.expect("should receive GameStart");
+
+        let state_of =
+            |data: &Bytes| match breakpoint_core::net::protocol::decode_server_message(data) {
+                Ok(ServerMessage::GameState(msg)) => {
+                    rmp_serde::from_slice::<breakpoint_tron::TronState>(&msg.state_data).unwrap()
+                },
+                other => panic!("Expected GameState, got: {other:?}"),
+            };
+        match broadcast_rx.recv().await.expect("should receive tick") {
+            GameBroadcast::PlayerViews { shared, views } => {
+                let viewers: Vec<PlayerId> = views.keys().copied().collect();
+                assert_eq!(viewers, vec![3], "Riders get the shared state");
+                assert!(state_of(&shared).spectator.is_none());
+                assert!(state_of(&views[&3]).spectator.is_some());
+            },
+            other => panic!("Expected PlayerViews, got: {other:?}"),
+        }
+
+        let _ = cmd_tx.send(GameCommand::Stop);
+        let _ = handle.await;
+    }
+
+    #[tokio::test]
+    async fn private_state_goes_to_each_player_alone() {
+        let registry = ServerGameRegistry::new();
+        let mut players = make_test_players(4);
+        players[2].is_spectator = true;
+        players[3].hot_seat_of = Some(1);
+
+        let config = GameSessionConfig {
+            game_id: GameId::Golf,
+            players,
+            leader_id: 1,
+            round_count: 1,
+            round_duration: Duration::from_secs(90),
+            pacing: quick_pacing(),
+            phase_timings: no_phases(),
+            win_condition: WinCondition::default(),
+            custom: HashMap::from([("caddie_hints".to_string(), serde_json::json!(1))]),
+            input_stats: Default::default(),
+            memory: Default::default(),
+            replay: None,
+            tutorial: None,
+        };
+
+        let (cmd_tx, mut broadcast_rx, handle) =
+            spawn_game_session(&registry, config).expect("should spawn");
+        let _start = broadcast_rx.recv().await.expect("should receive GameStart");
+        assert!(matches!(
+            next_server_msg(&mut broadcast_rx).await,
+            ServerMessage::GameState(_)
+        ));
+
+        match broadcast_rx
+            .recv()
+            .await
+            .expect("should receive private state")
+        {
+            GameBroadcast::Private(messages) => {
+                let routes: Vec<(PlayerId, PlayerId)> = messages
+                    .iter()
+                    .map(|(connection, data)| {
+                        match breakpoint_core::net::protocol::decode_server_message(data) {
+                            Ok(ServerMessage::PrivateState(msg)) => (*connection, msg.player_id),
+                            other => panic!("Expected PrivateState, got: {other:?}"),
+                        }
+                    })
+                    .collect();
+                assert_eq!(
+                    routes,
+                    vec![(1, 1), (2, 2), (1, 4)],
+                    "Spectators get none; guests' go to their host"
+                );
+            },
+            other => panic!("Expected Private, got: {other:?}"),
+        }
+
+        let _ = cmd_tx.send(GameCommand::Stop);
+        let _ = handle.await;
+    }
+
+    #[tokio::test]
+    async fn tutorial_objectives_are_sent_and_recorded_as_they_are_met() {
+        let registry = ServerGameRegistry::new();
+        let mut players = make_test_players(2);
+        players[1].is_bot = true;
+        let store: crate::state::SharedPlayerSettings = Arc::new(tokio::sync::RwLock::new(
+            crate::player_settings::PlayerSettingsStore::new(
+                &crate::config::SettingsSyncConfig::default(),
+            ),
+        ));
+        let identity = breakpoint_core::player::PlayerIdentity::from_digest(&[7; 6]);
+        let config = GameSessionConfig {
+            game_id: GameId::Tron,
+            players,
+            leader_id: 1,
+            round_count: 1,
+            round_duration: Duration::from_secs(90),
+            pacing: quick_pacing(),
+            phase_timings: no_phases(),
+            win_condition: WinCondition::default(),
+            custom: HashMap::from([("tutorial".to_string(), serde_json::json!(true))]),
+            input_stats: Default::default(),
+            memory: Default::default(),
+            replay: None,
+            tutorial: Some(TutorialSink {
+                player_settings: Some(Arc::clone(&store)),
+                identities: HashMap::from([(1, identity.clone())]),
+            }),
+        };
+
+        let (cmd_tx, mut broadcast_rx, handle) =
+            spawn_game_session(&registry, config).expect("should spawn");
+        let _start = broadcast_rx.recv().await.expect("should receive GameStart");
+        let checklist = |messages: &[(PlayerId, Bytes)]| -> Option<TutorialChecklist> {
+            let (pid, data) = messages.first()?;
+            assert_eq!(*pid, 1, "Only the human player is tracked");
+            match breakpoint_core::net::protocol::decode_server_message(data) {
+                Ok(ServerMessage::TutorialProgress(mut msg)) => msg.checklists.pop(),
+                _ => None,
+            }
+        };
+        let opening = match broadcast_rx.recv().await {
+            Some(GameBroadcast::Private(messages)) => checklist(&messages),
+            other => panic!("Expected the opening checklist, got: {other:?}"),
+        }
+        .expect("opening checklist");
+        assert_eq!(opening.game, "tron");
+        assert!(opening.steps.iter().all(|s| !s.done));
+
+        let _ = cmd_tx.send(GameCommand::Debug {
+            command: DebugCommand::SetRoundTimer { elapsed_secs: 20.0 },
+        });
+        let mut update = None;
+        for _ in 0..50 {
+            if let Some(GameBroadcast::Private(messages)) = broadcast_rx.recv().await
+                && let Some(c) = checklist(&messages)
+            {
+                update = Some(c);
+                break;
+            }
+        }
+        let update = update.expect("checklist update");
+        assert!(update.steps.iter().any(|s| s.id == "survive" && s.done));
+
+        let mut recorded = false;
+        for _ in 0..50 {
+            if store
+                .read()
+                .await
+                .tutorial(&identity)
+                .is_done("tron", "survive")
+            {
+                recorded = true;
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(recorded, "Completed objectives are stored for the player");
+
+        let _ = cmd_tx.send(GameCommand::Stop);
+        let _ = handle.await;
+    }
+
     #[tokio::test]
     async fn player_input_reaches_game() {
         let registry = ServerGameRegistry::new();
@@ -547,8 +1838,14 @@ mod tests {
             leader_id: 1,
             round_count: 1,
             round_duration: Duration::from_secs(90),
-            between_round_duration: Duration::from_secs(1),
+            pacing: quick_pacing(),
+            phase_timings: no_phases(),
+            win_condition: WinCondition::default(),
             custom: HashMap::new(),
+            input_stats: Default::default(),
+            memory: Default::default(),
+            replay: None,
+            tutorial: None,
         };
 
         let (cmd_tx, mut broadcast_rx, handle) =
@@ -562,6 +1859,10 @@ mod tests {
             aim_angle: 0.0,
             power: 0.5,
             stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
         };
         let input_data = rmp_serde::to_vec(&golf_input).unwrap();
         let _ = cmd_tx.send(GameCommand::PlayerInput {
@@ -593,6 +1894,432 @@ mod tests {
         // validates the pipeline doesn't panic.
     }
 
+    #[tokio::test]
+    async fn laser_shots_are_broadcast_once_as_effects() {
+        let registry = ServerGameRegistry::new();
+        let config = GameSessionConfig {
+            game_id: GameId::LaserTag,
+            players: make_test_players(2),
+            leader_id: 1,
+            round_count: 1,
+            round_duration: Duration::from_secs(90),
+            pacing: quick_pacing(),
+            phase_timings: no_phases(),
+            win_condition: WinCondition::default(),
+            custom: HashMap::new(),
+            input_stats: Default::default(),
+            memory: Default::default(),
+            replay: None,
+            tutorial: None,
+        };
+        let (cmd_tx, mut broadcast_rx, handle) =
+            spawn_game_session(&registry, config).expect("should spawn");
+
+        let input = breakpoint_lasertag::LaserTagInput {
+            fire: true,
+            ..Default::default()
+        };
+        let _ = cmd_tx.send(GameCommand::PlayerInput {
+            player_id: 1,
+            tick: 1,
+            input_data: rmp_serde::to_vec(&input).unwrap(),
+        });
+
+        let mut effects = 0;
+        for _ in 0..40 {
+            if let ServerMessage::GameEffects(msg) = next_server_msg(&mut broadcast_rx).await {
+                let shots: Vec<breakpoint_lasertag::trail::LaserFired> =
+                    rmp_serde::from_slice(&msg.data).unwrap();
+                assert_eq!(shots.len(), 1);
+                assert_eq!(shots[0].shooter, 1);
+                effects += 1;
+            }
+        }
+        assert_eq!(effects, 1, "One shot, sent once");
+
+        let _ = cmd_tx.send(GameCommand::Stop);
+        let _ = handle.await;
+    }
+
+    #[tokio::test]
+    async fn countdown_drops_inputs_until_play_starts() {
+        let registry = ServerGameRegistry::new();
+        let config = GameSessionConfig {
+            game_id: GameId::Golf,
+            players: make_test_players(1),
+            leader_id: 1,
+            round_count: 1,
+            round_duration: Duration::from_secs(90),
+            pacing: quick_pacing(),
+            phase_timings: RoundPhaseTimings {
+                countdown_secs: 0.3,
+                freeze_frame_secs: 0.0,
+            },
+            win_condition: WinCondition::default(),
+            custom: HashMap::new(),
+            input_stats: Default::default(),
+            memory: Default::default(),
+            replay: None,
+            tutorial: None,
+        };
+        let (cmd_tx, mut broadcast_rx, handle) =
+            spawn_game_session(&registry, config).expect("should spawn");
+        let stroke = rmp_serde::to_vec(&breakpoint_golf::GolfInput {
+            aim_angle: 0.0,
+            power: 0.5,
+            stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
+        })
+        .unwrap();
+
+        // Countdown is announced and inputs sent during it are dropped
+        let mut saw_countdown = false;
+        loop {
+            match next_server_msg(&mut broadcast_rx).await {
+                ServerMessage::RoundPhase(msg) if msg.phase == RoundPhase::Countdown => {
+                    assert!(msg.remaining_secs > 0.0 && msg.remaining_secs <= 0.3);
+                    saw_countdown = true;
+                    let _ = cmd_tx.send(GameCommand::PlayerInput {
+                        player_id: 1,
+                        tick: 1,
+                        input_data: stroke.clone(),
+                    });
+                },
+                ServerMessage::RoundPhase(msg) if msg.phase == RoundPhase::Playing => break,
+                ServerMessage::GameState(gs) => {
+                    let state: breakpoint_golf::GolfState =
+                        rmp_serde::from_slice(&gs.state_data).unwrap();
+                    assert_eq!(state.strokes.get(&1).copied().unwrap_or(0), 0);
+                },
+                _ => {},
+            }
+        }
+        assert!(saw_countdown, "Countdown should be announced first");
+
+        // Once playing, the same input is applied
+        let _ = cmd_tx.send(GameCommand::PlayerInput {
+            player_id: 1,
+            tick: 2,
+            input_data: stroke,
+        });
+        let mut applied = false;
+        for _ in 0..10 {
+            if let ServerMessage::GameState(gs) = next_server_msg(&mut broadcast_rx).await {
+                let state: breakpoint_golf::GolfState =
+                    rmp_serde::from_slice(&gs.state_data).unwrap();
+                if state.strokes.get(&1).copied().unwrap_or(0) > 0 {
+                    applied = true;
+                    break;
+                }
+            }
+        }
+        assert!(applied, "Input should apply after the countdown");
+
+        let _ = cmd_tx.send(GameCommand::Stop);
+        let _ = handle.await;
+    }
+
+    #[tokio::test]
+    async fn freeze_frame_precedes_game_end() {
+        let registry = ServerGameRegistry::new();
+        let config = GameSessionConfig {
+            game_id: GameId::Platformer,
+            players: make_test_players(1),
+            leader_id: 1,
+            round_count: 1,
+            round_duration: Duration::from_millis(100),
+            pacing: quick_pacing(),
+            phase_timings: RoundPhaseTimings {
+                countdown_secs: 0.0,
+                freeze_frame_secs: 0.2,
+            },
+            win_condition: WinCondition::default(),
+            custom: HashMap::new(),
+            input_stats: Default::default(),
+            memory: Default::default(),
+            replay: None,
+            tutorial: None,
+        };
+        let (_cmd_tx, mut broadcast_rx, handle) =
+            spawn_game_session(&registry, config).expect("should spawn");
+
+        let mut phases = Vec::new();
+        loop {
+            match next_server_msg(&mut broadcast_rx).await {
+                ServerMessage::RoundPhase(msg) => phases.push(msg.phase),
+                ServerMessage::GameEnd(_) => break,
+                _ => {},
+            }
+        }
+        assert_eq!(
+            phases,
+            vec![
+                RoundPhase::Playing,
+                RoundPhase::FreezeFrame,
+                RoundPhase::Results
+            ]
+        );
+        let _ = handle.await;
+    }
+
+    #[tokio::test]
+    async fn warmup_replays_until_the_leader_starts_the_match() {
+        let registry = ServerGameRegistry::new();
+        let config = GameSessionConfig {
+            game_id: GameId::Platformer,
+            players: make_test_players(1),
+            leader_id: 1,
+            round_count: 1,
+            round_duration: Duration::from_millis(100),
+            pacing: quick_pacing(),
+            phase_timings: no_phases(),
+            win_condition: WinCondition::default(),
+            custom: HashMap::from([(
+                round_phase::WARMUP_SETTING.to_string(),
+                serde_json::json!(true),
+            )]),
+            input_stats: Default::default(),
+            memory: Default::default(),
+            replay: None,
+            tutorial: None,
+        };
+        let (cmd_tx, mut broadcast_rx, handle) =
+            spawn_game_session(&registry, config).expect("should spawn");
+
+        // Several warmup rounds run out their clock without ending the match
+        let warmup = tokio::time::Instant::now();
+        let mut phases = Vec::new();
+        while warmup.elapsed() < Duration::from_millis(400) {
+            match next_server_msg(&mut broadcast_rx).await {
+                ServerMessage::RoundPhase(msg) => phases.push(msg.phase),
+                ServerMessage::RoundEnd(_) | ServerMessage::GameEnd(_) => {
+                    panic!("Warmup rounds should not be scored")
+                },
+                _ => {},
+            }
+        }
+        assert_eq!(phases, vec![RoundPhase::Warmup]);
+
+        let _ = cmd_tx.send(GameCommand::AdvanceRound { round: 0 });
+        loop {
+            match next_server_msg(&mut broadcast_rx).await {
+                ServerMessage::RoundPhase(msg) => phases.push(msg.phase),
+                ServerMessage::GameEnd(_) => break,
+                _ => {},
+            }
+        }
+        assert_eq!(
+            phases,
+            vec![RoundPhase::Warmup, RoundPhase::Playing, RoundPhase::Results]
+        );
+        let _ = handle.await;
+    }
+
+    #[tokio::test]
+    async fn win_condition_ends_match_before_round_count() {
+        let registry = ServerGameRegistry::new();
+        let config = GameSessionConfig {
+            game_id: GameId::Platformer,
+            players: make_test_players(1),
+            leader_id: 1,
+            round_count: 5,
+            round_duration: Duration::from_millis(100),
+            pacing: quick_pacing(),
+            phase_timings: no_phases(),
+            win_condition: WinCondition {
+                rules: vec![breakpoint_core::win_condition::WinRule::TimeCapped { secs: 0 }],
+                sudden_death: false,
+            },
+            custom: HashMap::new(),
+            input_stats: Default::default(),
+            memory: Default::default(),
+            replay: None,
+            tutorial: None,
+        };
+        let (_cmd_tx, mut broadcast_rx, handle) =
+            spawn_game_session(&registry, config).expect("should spawn");
+
+        loop {
+            match next_server_msg(&mut broadcast_rx).await {
+                ServerMessage::RoundEnd(_) => panic!("Time cap should end the match"),
+                ServerMessage::GameEnd(end) => {
+                    assert_eq!(end.final_scores.len(), 1);
+                    break;
+                },
+                _ => {},
+            }
+        }
+        let _ = handle.await;
+    }
+
+    #[tokio::test]
+    async fn intermission_waits_for_the_leader() {
+        let registry = ServerGameRegistry::new();
+        let config = GameSessionConfig {
+            game_id: GameId::Platformer,
+            players: make_test_players(1),
+            leader_id: 1,
+            round_count: 2,
+            round_duration: Duration::from_millis(100),
+            pacing: RoundPacing {
+                intermission_secs: 0,
+                auto_advance: false,
+                min_results_secs: 0,
+            },
+            phase_timings: no_phases(),
+            win_condition: WinCondition::default(),
+            custom: HashMap::new(),
+            input_stats: Default::default(),
+            memory: Default::default(),
+            replay: None,
+            tutorial: None,
+        };
+        let (cmd_tx, mut broadcast_rx, handle) =
+            spawn_game_session(&registry, config).expect("should spawn");
+
+        loop {
+            if let ServerMessage::RoundEnd(end) = next_server_msg(&mut broadcast_rx).await {
+                assert_eq!(end.between_round_secs, 0, "No countdown while waiting");
+                break;
+            }
+        }
+        match next_server_msg(&mut broadcast_rx).await {
+            ServerMessage::Intermission(msg) => {
+                assert_eq!(msg.round, 1);
+                assert_eq!(msg.remaining_secs, None);
+            },
+            other => panic!("Expected Intermission, got {other:?}"),
+        }
+
+        // Nothing happens until the leader advances; a stale round is ignored
+        let _ = cmd_tx.send(GameCommand::AdvanceRound { round: 0 });
+        let waited = tokio::time::timeout(
+            Duration::from_millis(200),
+            next_server_msg(&mut broadcast_rx),
+        )
+        .await;
+        assert!(waited.is_err(), "Round should not start on its own");
+
+        let _ = cmd_tx.send(GameCommand::AdvanceRound { round: 1 });
+        match next_server_msg(&mut broadcast_rx).await {
+            ServerMessage::Intermission(msg) => assert_eq!(msg.remaining_secs, Some(0.0)),
+            other => panic!("Expected Intermission, got {other:?}"),
+        }
+        loop {
+            if let ServerMessage::GameEnd(_) = next_server_msg(&mut broadcast_rx).await {
+                break;
+            }
+        }
+        let _ = handle.await;
+    }
+
+    #[tokio::test]
+    async fn finished_match_is_recorded() {
+        let registry = ServerGameRegistry::new();
+        let history: crate::state::SharedMatchHistory = Arc::new(tokio::sync::RwLock::new(
+            crate::history::MatchHistory::new(&crate::config::ReplaysConfig::default()),
+        ));
+        let config = GameSessionConfig {
+            game_id: GameId::Platformer,
+            players: make_test_players(1),
+            leader_id: 1,
+            round_count: 1,
+            round_duration: Duration::from_millis(100),
+            pacing: quick_pacing(),
+            phase_timings: no_phases(),
+            win_condition: WinCondition::default(),
+            custom: HashMap::new(),
+            input_stats: Default::default(),
+            memory: Default::default(),
+            replay: Some(ReplaySink {
+                history: Arc::clone(&history),
+                match_id: "match-1".to_string(),
+                room_code: "ABCD-1234".to_string(),
+                max_frames_per_round: 1000,
+            }),
+            tutorial: None,
+        };
+        let (_cmd_tx, mut broadcast_rx, handle) =
+            spawn_game_session(&registry, config).expect("should spawn");
+
+        let match_id = loop {
+            if let ServerMessage::GameEnd(end) = next_server_msg(&mut broadcast_rx).await {
+                break end.match_id;
+            }
+        };
+        assert_eq!(match_id.as_deref(), Some("match-1"));
+        let _ = handle.await;
+
+        // Encoding runs on a blocking thread after the loop exits
+        let mut file = None;
+        for _ in 0..50 {
+            if let Some((_, f)) = history.read().await.replay_file("match-1") {
+                file = Some(f);
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let replay = breakpoint_core::replay::decode_replay(&file.expect("replay stored"))
+            .expect("valid replay");
+        assert_eq!(replay.metadata.game, GameId::Platformer);
+        assert_eq!(replay.rounds.len(), 1);
+        assert!(!replay.rounds[0].frames.is_empty());
+        assert!(replay.rounds[0].frames[0].course.is_some());
+        assert_eq!(replay.final_scores.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn memory_is_sampled_and_trimmed_over_budget() {
+        let registry = ServerGameRegistry::new();
+        let history: crate::state::SharedMatchHistory = Arc::new(tokio::sync::RwLock::new(
+            crate::history::MatchHistory::new(&crate::config::ReplaysConfig::default()),
+        ));
+        let memory = Arc::new(std::sync::Mutex::new(RoomMemoryTracker::new(
+            MemoryBudget {
+                sample_interval: Duration::ZERO,
+                max_game_bytes: usize::MAX,
+                max_replay_bytes: 0,
+            },
+        )));
+        let config = GameSessionConfig {
+            game_id: GameId::Platformer,
+            players: make_test_players(1),
+            leader_id: 1,
+            round_count: 1,
+            round_duration: Duration::from_millis(100),
+            pacing: quick_pacing(),
+            phase_timings: no_phases(),
+            win_condition: WinCondition::default(),
+            custom: HashMap::new(),
+            input_stats: Default::default(),
+            memory: Arc::clone(&memory),
+            replay: Some(ReplaySink {
+                history,
+                match_id: "match-1".to_string(),
+                room_code: "ABCD-1234".to_string(),
+                max_frames_per_round: 1000,
+            }),
+            tutorial: None,
+        };
+        let (_cmd_tx, mut broadcast_rx, handle) =
+            spawn_game_session(&registry, config).expect("should spawn");
+        loop {
+            if let ServerMessage::GameEnd(_) = next_server_msg(&mut broadcast_rx).await {
+                break;
+            }
+        }
+        let _ = handle.await;
+
+        let snapshot = memory.lock().unwrap().snapshot();
+        assert!(snapshot.samples > 0, "Sampled every tick");
+        assert!(snapshot.current.state_bytes > 0);
+        assert!(snapshot.peak_bytes >= snapshot.current.total());
+        assert!(snapshot.trims > 0, "The replay is over its zero-byte cap");
+    }
+
     #[tokio::test]
     async fn player_leave_during_game() {
         let registry = ServerGameRegistry::new();
@@ -604,8 +2331,14 @@ mod tests {
             leader_id: 1,
             round_count: 1,
             round_duration: Duration::from_secs(90),
-            between_round_duration: Duration::from_secs(1),
+            pacing: quick_pacing(),
+            phase_timings: no_phases(),
+            win_condition: WinCondition::default(),
             custom: HashMap::new(),
+            input_stats: Default::default(),
+            memory: Default::default(),
+            replay: None,
+            tutorial: None,
         };
 
         let (cmd_tx, mut broadcast_rx, handle) =
@@ -636,8 +2369,14 @@ mod tests {
             leader_id: 1,
             round_count: 1,
             round_duration: Duration::from_secs(90),
-            between_round_duration: Duration::from_secs(1),
+            pacing: quick_pacing(),
+            phase_timings: no_phases(),
+            win_condition: WinCondition::default(),
             custom: HashMap::new(),
+            input_stats: Default::default(),
+            memory: Default::default(),
+            replay: None,
+            tutorial: None,
         };
 
         let (cmd_tx, mut broadcast_rx, handle) =
@@ -682,6 +2421,45 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn dormant_session_stops_ticking_until_woken() {
+        let registry = ServerGameRegistry::new();
+        let config = GameSessionConfig {
+            game_id: GameId::Golf,
+            players: make_test_players(2),
+            leader_id: 1,
+            round_count: 1,
+            round_duration: Duration::from_secs(90),
+            pacing: quick_pacing(),
+            phase_timings: no_phases(),
+            win_condition: WinCondition::default(),
+            custom: HashMap::new(),
+            input_stats: Default::default(),
+            memory: Default::default(),
+            replay: None,
+            tutorial: None,
+        };
+        let (cmd_tx, mut broadcast_rx, _handle) =
+            spawn_game_session(&registry, config).expect("should spawn");
+        let ServerMessage::GameStart(_) = next_server_msg(&mut broadcast_rx).await else {
+            panic!("Expected GameStart first");
+        };
+
+        cmd_tx.send(GameCommand::Dormant(true)).unwrap();
+        // Let anything sent before the command arrive, then expect silence
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        while broadcast_rx.try_recv().is_ok() {}
+        let quiet = tokio::time::timeout(Duration::from_millis(300), broadcast_rx.recv()).await;
+        assert!(quiet.is_err(), "A dormant session doesn't tick");
+
+        cmd_tx.send(GameCommand::Dormant(false)).unwrap();
+        let woke = tokio::time::timeout(Duration::from_millis(200), broadcast_rx.recv()).await;
+        assert!(
+            matches!(woke, Ok(Some(GameBroadcast::EncodedMessage(_)))),
+            "Waking resumes the tick loop"
+        );
+    }
+
     #[tokio::test]
     async fn stop_command_ends_game_cleanly() {
         let registry = ServerGameRegistry::new();
@@ -693,8 +2471,14 @@ mod tests {
             leader_id: 1,
             round_count: 1,
             round_duration: Duration::from_secs(90),
-            between_round_duration: Duration::from_secs(1),
+            pacing: quick_pacing(),
+            phase_timings: no_phases(),
+            win_condition: WinCondition::default(),
             custom: HashMap::new(),
+            input_stats: Default::default(),
+            memory: Default::default(),
+            replay: None,
+            tutorial: None,
         };
 
         let (cmd_tx, mut broadcast_rx, handle) =
@@ -722,6 +2506,230 @@ mod tests {
         let _ = handle.await;
     }
 
+    #[tokio::test]
+    async fn course_vote_picks_the_seed_before_start() {
+        let registry = ServerGameRegistry::new();
+        let mut custom = HashMap::new();
+        custom.insert("course_vote".to_string(), serde_json::json!(true));
+        custom.insert("seed".to_string(), serde_json::json!(7));
+        let config = GameSessionConfig {
+            game_id: GameId::Platformer,
+            players: make_test_players(2),
+            leader_id: 1,
+            round_count: 1,
+            round_duration: Duration::from_secs(90),
+            pacing: quick_pacing(),
+            phase_timings: no_phases(),
+            win_condition: WinCondition::default(),
+            custom,
+            input_stats: Default::default(),
+            memory: Default::default(),
+            replay: None,
+            tutorial: None,
+        };
+
+        let (cmd_tx, mut broadcast_rx, handle) =
+            spawn_game_session(&registry, config).expect("should spawn");
+
+        let ServerMessage::CourseVote(opening) = next_server_msg(&mut broadcast_rx).await else {
+            panic!("Vote should open before the game starts");
+        };
+        assert_eq!(opening.round, 1);
+        assert_eq!(opening.candidates.len(), COURSE_VOTE_CANDIDATES);
+        assert_eq!(opening.winner, None);
+        let chosen = opening.candidates[2].seed;
+
+        for player_id in [1, 2] {
+            let _ = cmd_tx.send(GameCommand::CourseVote {
+                player_id,
+                round: 1,
+                choice: 2,
+            });
+        }
+        let _ = next_server_msg(&mut broadcast_rx).await;
+        let ServerMessage::CourseVote(update) = next_server_msg(&mut broadcast_rx).await else {
+            panic!("Expected a vote update");
+        };
+        assert_eq!(update.votes, vec![0, 0, 2]);
+        let ServerMessage::CourseVote(closing) = next_server_msg(&mut broadcast_rx).await else {
+            panic!("Vote should close once everyone has voted");
+        };
+        assert_eq!(closing.winner, Some(2));
+        assert!(matches!(
+            next_server_msg(&mut broadcast_rx).await,
+            ServerMessage::GameStart(_)
+        ));
+
+        // The first tick ships the course generated from the winning seed
+        let _ = next_server_msg(&mut broadcast_rx).await;
+        let ServerMessage::CourseUpdate(course) = next_server_msg(&mut broadcast_rx).await else {
+            panic!("Expected the course after the first state");
+        };
+        let expected = breakpoint_platformer::course_gen::generate_course(chosen);
+        assert_eq!(rmp_serde::to_vec(&expected).unwrap(), course.data);
+
+        let _ = cmd_tx.send(GameCommand::Stop);
+        let _ = handle.await;
+    }
+
+    #[tokio::test]
+    async fn captains_draft_the_teams_before_start() {
+        let registry = ServerGameRegistry::new();
+        let mut players = make_test_players(4);
+        players[2].is_bot = true;
+        players[3].is_bot = true;
+        let custom = HashMap::from([
+            ("team_mode".to_string(), serde_json::json!("teams_2")),
+            ("team_select".to_string(), serde_json::json!("draft")),
+        ]);
+        let config = GameSessionConfig {
+            game_id: GameId::LaserTag,
+            players,
+            leader_id: 1,
+            round_count: 1,
+            round_duration: Duration::from_secs(90),
+            pacing: quick_pacing(),
+            phase_timings: no_phases(),
+            win_condition: WinCondition::default(),
+            custom,
+            input_stats: Default::default(),
+            memory: Default::default(),
+            replay: None,
+            tutorial: None,
+        };
+
+        let (cmd_tx, mut broadcast_rx, handle) =
+            spawn_game_session(&registry, config).expect("should spawn");
+
+        let ServerMessage::TeamDraft(opening) = next_server_msg(&mut broadcast_rx).await else {
+            panic!("The draft should open before the game starts");
+        };
+        // The humans captain; the bots are up for grabs
+        let mut captains = opening.captains.clone();
+        captains.sort();
+        assert_eq!(captains, [1, 2]);
+        assert_eq!(opening.turn, Some(0));
+        let mut pool = opening.pool.clone();
+        pool.sort();
+        assert_eq!(pool, [3, 4]);
+
+        let (first, second) = (opening.captains[0], opening.captains[1]);
+        let pick = opening.pool[1];
+        // Out of turn, so ignored
+        let _ = cmd_tx.send(GameCommand::DraftPick {
+            player_id: second,
+            pick,
+        });
+        let _ = cmd_tx.send(GameCommand::DraftPick {
+            player_id: first,
+            pick,
+        });
+        let ServerMessage::TeamDraft(update) = next_server_msg(&mut broadcast_rx).await else {
+            panic!("Expected a draft update");
+        };
+        assert_eq!(update.turn, Some(1));
+        assert_eq!(update.pool, [opening.pool[0]]);
+        let _ = cmd_tx.send(GameCommand::DraftPick {
+            player_id: second,
+            pick: opening.pool[0],
+        });
+        let ServerMessage::TeamDraft(closing) = next_server_msg(&mut broadcast_rx).await else {
+            panic!("Expected the draft to close");
+        };
+        assert_eq!(closing.turn, None);
+        assert!(matches!(
+            next_server_msg(&mut broadcast_rx).await,
+            ServerMessage::GameStart(_)
+        ));
+
+        let ServerMessage::GameState(gs) = next_server_msg(&mut broadcast_rx).await else {
+            panic!("Expected the first state");
+        };
+        let state: breakpoint_lasertag::LaserTagState =
+            rmp_serde::from_slice(&gs.state_data).unwrap();
+        assert_eq!(
+            state.teams,
+            HashMap::from([(first, 0), (pick, 0), (second, 1), (opening.pool[0], 1)])
+        );
+
+        let _ = cmd_tx.send(GameCommand::Stop);
+        let _ = handle.await;
+    }
+
+    #[tokio::test]
+    async fn replay_records_the_drafted_teams() {
+        let registry = ServerGameRegistry::new();
+        let history: crate::state::SharedMatchHistory = Arc::new(tokio::sync::RwLock::new(
+            crate::history::MatchHistory::new(&crate::config::ReplaysConfig::default()),
+        ));
+        let mut players = make_test_players(4);
+        players[2].is_bot = true;
+        players[3].is_bot = true;
+        let config = GameSessionConfig {
+            game_id: GameId::LaserTag,
+            players,
+            leader_id: 1,
+            round_count: 1,
+            round_duration: Duration::from_millis(100),
+            pacing: quick_pacing(),
+            phase_timings: no_phases(),
+            win_condition: WinCondition::default(),
+            custom: HashMap::from([
+                ("team_mode".to_string(), serde_json::json!("teams_2")),
+                ("team_select".to_string(), serde_json::json!("draft")),
+                ("round_duration".to_string(), serde_json::json!(1.0)),
+            ]),
+            input_stats: Default::default(),
+            memory: Default::default(),
+            replay: Some(ReplaySink {
+                history: Arc::clone(&history),
+                match_id: "match-draft".to_string(),
+                room_code: "ABCD-1234".to_string(),
+                max_frames_per_round: 1000,
+            }),
+            tutorial: None,
+        };
+        let (cmd_tx, mut broadcast_rx, handle) =
+            spawn_game_session(&registry, config).expect("should spawn");
+
+        let ServerMessage::TeamDraft(opening) = next_server_msg(&mut broadcast_rx).await else {
+            panic!("The draft should open before the game starts");
+        };
+        let (first, second) = (opening.captains[0], opening.captains[1]);
+        let _ = cmd_tx.send(GameCommand::DraftPick {
+            player_id: first,
+            pick: opening.pool[0],
+        });
+        let _ = cmd_tx.send(GameCommand::DraftPick {
+            player_id: second,
+            pick: opening.pool[1],
+        });
+        while !matches!(
+            next_server_msg(&mut broadcast_rx).await,
+            ServerMessage::GameEnd(_)
+        ) {}
+        let _ = handle.await;
+
+        let mut file = None;
+        for _ in 0..50 {
+            if let Some((_, f)) = history.read().await.replay_file("match-draft") {
+                file = Some(f);
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let replay = breakpoint_core::replay::decode_replay(&file.expect("replay stored"))
+            .expect("valid replay");
+        assert_eq!(replay.metadata.players.len(), 4);
+        assert!(
+            replay
+                .metadata
+                .custom
+                .contains_key(team_draft::TEAMS_SETTING),
+            "The replay should carry the drafted teams the match was played with"
+        );
+    }
+
     #[tokio::test]
     async fn player_join_during_game() {
         let registry = ServerGameRegistry::new();
@@ -733,8 +2741,14 @@ mod tests {
             leader_id: 1,
             round_count: 1,
             round_duration: Duration::from_secs(90),
-            between_round_duration: Duration::from_secs(1),
+            pacing: quick_pacing(),
+            phase_timings: no_phases(),
+            win_condition: WinCondition::default(),
             custom: HashMap::new(),
+            input_stats: Default::default(),
+            memory: Default::default(),
+            replay: None,
+            tutorial: None,
         };
 
         let (cmd_tx, mut broadcast_rx, handle) =
@@ -751,6 +2765,9 @@ mod tests {
             is_leader: false,
             is_spectator: false,
             is_bot: false,
+            bot_difficulty: Default::default(),
+            hot_seat_of: None,
+            external_bot: false,
         };
         let _ = cmd_tx.send(GameCommand::PlayerJoined {
             player_id: 2,
@@ -779,8 +2796,14 @@ mod tests {
             leader_id: 1,
             round_count: 1,
             round_duration: Duration::from_secs(90),
-            between_round_duration: Duration::from_secs(1),
+            pacing: quick_pacing(),
+            phase_timings: no_phases(),
+            win_condition: WinCondition::default(),
             custom: HashMap::new(),
+            input_stats: Default::default(),
+            memory: Default::default(),
+            replay: None,
+            tutorial: None,
         };
 
         let (cmd_tx, mut broadcast_rx, handle) =
@@ -832,8 +2855,14 @@ mod tests {
             leader_id: 1,
             round_count: 1,
             round_duration: Duration::from_secs(90),
-            between_round_duration: Duration::from_secs(1),
+            pacing: quick_pacing(),
+            phase_timings: no_phases(),
+            win_condition: WinCondition::default(),
             custom: HashMap::new(),
+            input_stats: Default::default(),
+            memory: Default::default(),
+            replay: None,
+            tutorial: None,
         };
 
         let (cmd_tx, mut broadcast_rx, handle) =
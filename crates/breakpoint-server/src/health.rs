@@ -2,6 +2,7 @@ use std::sync::atomic::Ordering;
 
 use axum::Json;
 use axum::extract::State;
+use axum::http::StatusCode;
 use serde::Serialize;
 
 use crate::state::AppState;
@@ -15,13 +16,13 @@ pub struct HealthResponse {
     pub rooms: RoomInfo,
 }
 
-#[derive(Serialize)]
+#[derive(Debug, Clone, Copy, Serialize)]
 pub struct ConnectionInfo {
     pub websocket: usize,
     pub sse: usize,
 }
 
-#[derive(Serialize)]
+#[derive(Debug, Clone, Copy, Serialize)]
 pub struct RoomInfo {
     pub active: usize,
     pub players: usize,
@@ -49,16 +50,77 @@ pub async fn health_check(State(state): State<AppState>) -> Json<HealthResponse>
     })
 }
 
-/// Readiness check — verifies essential subsystems are initialized.
-pub async fn readiness_check(State(state): State<AppState>) -> &'static str {
-    // Verify game registry has at least one game registered
-    let has_games = state.game_registry.available_games() > 0;
-    if !has_games {
-        return "not ready: no games registered";
+/// Readiness check response: whether to route new players here, and how
+/// full the instance is.
+#[derive(Serialize)]
+pub struct ReadinessResponse {
+    /// `ready`, `saturated`, or `not ready`.
+    pub status: &'static str,
+    pub utilization: Utilization,
+}
+
+#[derive(Serialize)]
+pub struct Utilization {
+    pub websocket: Usage,
+    pub rooms: Usage,
+    /// Players waiting for a room to free up.
+    pub room_queue: usize,
+}
+
+#[derive(Serialize)]
+pub struct Usage {
+    pub current: usize,
+    pub max: usize,
+}
+
+impl Usage {
+    fn is_full(&self) -> bool {
+        self.current >= self.max
     }
+}
 
-    // If we got here, config was loaded and state is initialized
-    "ready"
+/// Readiness check — verifies essential subsystems are initialized and the
+/// instance has room for more players. Answers 503 when not ready or
+/// saturated, so load balancers stop routing new connections here.
+pub async fn readiness_check(
+    State(state): State<AppState>,
+) -> (StatusCode, Json<ReadinessResponse>) {
+    let (rooms, room_queue) = {
+        let rooms = state.rooms.read().await;
+        (rooms.stats().0, rooms.room_queue_len())
+    };
+    let utilization = Utilization {
+        websocket: Usage {
+            current: state.ws_connection_count.load(Ordering::Relaxed),
+            max: state.config.limits.max_ws_connections,
+        },
+        rooms: Usage {
+            current: rooms,
+            max: state.config.rooms.max_rooms,
+        },
+        room_queue,
+    };
+
+    // Verify game registry has at least one game registered
+    let status = if state.game_registry.available_games() == 0 {
+        "not ready"
+    } else if utilization.websocket.is_full() || utilization.rooms.is_full() {
+        "saturated"
+    } else {
+        "ready"
+    };
+    let code = if status == "ready" {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (
+        code,
+        Json(ReadinessResponse {
+            status,
+            utilization,
+        }),
+    )
 }
 
 #[cfg(test)]
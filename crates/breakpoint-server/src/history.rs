@@ -0,0 +1,487 @@
+//! Match history: finished matches recorded by the game loop, exported as
+//! portable `.bpreplay` files.
+
+use std::collections::VecDeque;
+
+use axum::body::Bytes;
+use axum::extract::{Path, Query, State};
+use axum::http::header;
+use axum::response::{IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+
+use breakpoint_core::game_trait::PlayerScore;
+use breakpoint_core::heatmap::RoundHeatmap;
+use breakpoint_core::player::Player;
+use breakpoint_core::replay::{
+    MatchReplay, REPLAY_FILE_EXTENSION, ReplayFrame, ReplayKeyframes, ReplayMetadata, RoundReplay,
+    decode_replay, encode_replay,
+};
+
+use crate::config::ReplaysConfig;
+use crate::error::AppError;
+use crate::state::{AppState, SharedMatchHistory};
+
+/// Where a game session delivers its recording when the match ends.
+pub struct ReplaySink {
+    pub history: SharedMatchHistory,
+    pub match_id: String,
+    pub room_code: String,
+    pub max_frames_per_round: usize,
+}
+
+/// Collects state frames and results while a match is played.
+pub struct ReplayRecorder {
+    replay: MatchReplay,
+    max_frames_per_round: usize,
+    /// Only every `stride`th tick is recorded once the replay was thinned.
+    stride: u32,
+    bytes: usize,
+}
+
+impl ReplayRecorder {
+    pub fn new(metadata: ReplayMetadata, max_frames_per_round: usize) -> Self {
+        Self {
+            replay: MatchReplay {
+                metadata,
+                rounds: Vec::new(),
+                final_scores: Vec::new(),
+            },
+            max_frames_per_round,
+            stride: 1,
+            bytes: 0,
+        }
+    }
+
+    pub fn begin_round(&mut self, round: u8) {
+        self.replay.rounds.push(RoundReplay {
+            round,
+            scores: Vec::new(),
+            frames: Vec::new(),
+            truncated: false,
+            heatmap: None,
+        });
+    }
+
+    /// Record the state broadcast at `tick` and `server_time_ms`, with any
+    /// course data sent alongside it.
+    pub fn record_frame(
+        &mut self,
+        tick: u32,
+        server_time_ms: u64,
+        state: &[u8],
+        course: Option<&[u8]>,
+    ) {
+        let Some(round) = self.replay.rounds.last_mut() else {
+            return;
+        };
+        if round.frames.len() >= self.max_frames_per_round {
+            round.truncated = true;
+            return;
+        }
+        if course.is_none() && !tick.is_multiple_of(self.stride) {
+            return;
+        }
+        let frame = ReplayFrame {
+            tick,
+            server_time_ms,
+            state: state.to_vec(),
+            course: course.map(<[u8]>::to_vec),
+        };
+        self.bytes += frame_bytes(&frame);
+        round.frames.push(frame);
+    }
+
+    /// Bytes of state and course data recorded so far.
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// Drop every other frame and record half as often from now on, for a
+    /// match over its memory budget. Frames carrying course data are kept.
+    /// Returns the bytes freed.
+    pub fn thin(&mut self) -> usize {
+        let before = self.bytes;
+        for round in &mut self.replay.rounds {
+            let mut keep = false;
+            round.frames.retain(|frame| {
+                keep = !keep;
+                keep || frame.course.is_some()
+            });
+        }
+        self.bytes = self
+            .replay
+            .rounds
+            .iter()
+            .flat_map(|round| &round.frames)
+            .map(frame_bytes)
+            .sum();
+        self.stride = self.stride.saturating_mul(2);
+        before - self.bytes
+    }
+
+    pub fn end_round(&mut self, scores: Vec<PlayerScore>, heatmap: Option<RoundHeatmap>) {
+        if let Some(round) = self.replay.rounds.last_mut() {
+            round.scores = scores;
+            round.heatmap = heatmap;
+        }
+    }
+
+    pub fn finish(mut self, mut final_scores: Vec<PlayerScore>, ended_at: u64) -> MatchReplay {
+        final_scores.sort_by_key(|s| s.player_id);
+        self.replay.final_scores = final_scores;
+        self.replay.metadata.ended_at = ended_at;
+        self.replay
+    }
+}
+
+fn frame_bytes(frame: &ReplayFrame) -> usize {
+    frame.state.len() + frame.course.as_ref().map_or(0, Vec::len)
+}
+
+/// A recorded match as listed by `GET /api/v1/history`.
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchSummary {
+    pub match_id: String,
+    pub game: String,
+    pub room_code: String,
+    pub players: Vec<Player>,
+    pub rounds: usize,
+    pub final_scores: Vec<PlayerScore>,
+    pub started_at: u64,
+    pub ended_at: u64,
+    /// Size of the `.bpreplay` download.
+    pub replay_bytes: usize,
+}
+
+struct StoredMatch {
+    summary: MatchSummary,
+    file: Bytes,
+}
+
+/// Recently finished matches with their encoded replays. Bounded: the
+/// oldest match is dropped when full.
+pub struct MatchHistory {
+    enabled: bool,
+    matches: VecDeque<StoredMatch>,
+    max_matches: usize,
+}
+
+impl MatchHistory {
+    pub fn new(config: &ReplaysConfig) -> Self {
+        Self {
+            enabled: config.enabled,
+            matches: VecDeque::new(),
+            max_matches: config.max_matches,
+        }
+    }
+
+    pub fn insert(&mut self, replay: &MatchReplay, file: Bytes) {
+        let meta = &replay.metadata;
+        let summary = MatchSummary {
+            match_id: meta.match_id.clone(),
+            game: meta.game.to_string(),
+            room_code: meta.room_code.clone(),
+            players: meta.players.clone(),
+            rounds: replay.rounds.len(),
+            final_scores: replay.final_scores.clone(),
+            started_at: meta.started_at,
+            ended_at: meta.ended_at,
+            replay_bytes: file.len(),
+        };
+        while self.matches.len() >= self.max_matches {
+            self.matches.pop_front();
+        }
+        self.matches.push_back(StoredMatch { summary, file });
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Summaries, most recent first.
+    pub fn list(&self) -> Vec<MatchSummary> {
+        self.matches
+            .iter()
+            .rev()
+            .map(|m| m.summary.clone())
+            .collect()
+    }
+
+    /// The replay file and the game it belongs to.
+    pub fn replay_file(&self, match_id: &str) -> Option<(&MatchSummary, Bytes)> {
+        self.matches
+            .iter()
+            .find(|m| m.summary.match_id == match_id)
+            .map(|m| (&m.summary, m.file.clone()))
+    }
+}
+
+/// Compress `replay` off the async workers and add it to the history.
+pub async fn store_replay(history: SharedMatchHistory, replay: MatchReplay) {
+    let encoded = tokio::task::spawn_blocking(move || {
+        let file = encode_replay(&replay);
+        (replay, file)
+    })
+    .await;
+    match encoded {
+        Ok((replay, Ok(file))) => {
+            tracing::info!(
+                match_id = %replay.metadata.match_id,
+                game = %replay.metadata.game,
+                bytes = file.len(),
+                "Match replay recorded"
+            );
+            history.write().await.insert(&replay, Bytes::from(file));
+        },
+        Ok((replay, Err(e))) => tracing::error!(
+            match_id = %replay.metadata.match_id,
+            error = %e,
+            "Failed to encode match replay"
+        ),
+        Err(e) => tracing::error!(error = %e, "Replay encoding task failed"),
+    }
+}
+
+/// Response for the history list endpoint.
+#[derive(Debug, Serialize)]
+pub struct HistoryResponse {
+    pub matches: Vec<MatchSummary>,
+}
+
+/// GET /api/v1/history — recently finished matches, most recent first.
+pub async fn list_history(
+    State(state): State<AppState>,
+) -> Result<Json<HistoryResponse>, AppError> {
+    let history = state.history.read().await;
+    if !history.enabled {
+        return Err(AppError::NotFound("Replays are disabled".to_string()));
+    }
+    Ok(Json(HistoryResponse {
+        matches: history.list(),
+    }))
+}
+
+/// GET /api/v1/history/:match_id/replay — download a match as a `.bpreplay`
+/// file. Also served without auth under `/api/v1/public`, since match IDs
+/// are random and only sent to the match's players.
+pub async fn get_replay(
+    State(state): State<AppState>,
+    Path(match_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let history = state.history.read().await;
+    let (summary, file) = history
+        .replay_file(&match_id)
+        .ok_or_else(|| AppError::NotFound(format!("Match {match_id} not found")))?;
+    let short_id: String = summary.match_id.chars().take(8).collect();
+    let disposition = format!(
+        "attachment; filename=\"breakpoint-{}-{short_id}.{REPLAY_FILE_EXTENSION}\"",
+        summary.game
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/octet-stream".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        file,
+    ))
+}
+
+/// Keyframes served per request; `every` is widened to stay under it.
+const MAX_KEYFRAMES: u32 = 1200;
+
+#[derive(Debug, Deserialize)]
+pub struct KeyframesQuery {
+    /// Round number; the last round when omitted.
+    pub round: Option<u8>,
+    #[serde(default)]
+    pub from: u32,
+    #[serde(default = "default_to")]
+    pub to: u32,
+    #[serde(default = "default_every")]
+    pub every: u32,
+}
+
+fn default_to() -> u32 {
+    u32::MAX
+}
+
+fn default_every() -> u32 {
+    5
+}
+
+/// Decimated frames of one round of `replay`, or `None` when the round was
+/// not played.
+pub fn replay_keyframes(replay: &MatchReplay, query: &KeyframesQuery) -> Option<ReplayKeyframes> {
+    let round = match query.round {
+        Some(n) => replay.rounds.iter().find(|r| r.round == n)?,
+        None => replay.rounds.last()?,
+    };
+    let first_tick = round.frames.first().map_or(0, |f| f.tick);
+    let last_tick = round.frames.last().map_or(0, |f| f.tick);
+    let span = query
+        .to
+        .min(last_tick)
+        .saturating_sub(query.from.max(first_tick));
+    let every = query.every.max(1).max(span.div_ceil(MAX_KEYFRAMES - 1));
+    Some(ReplayKeyframes {
+        match_id: replay.metadata.match_id.clone(),
+        game: replay.metadata.game,
+        round: round.round,
+        tick_rate: replay.metadata.tick_rate,
+        every,
+        last_tick,
+        frames: round.keyframes(query.from, query.to, every),
+    })
+}
+
+/// GET /api/v1/history/:match_id/keyframes — one round's state frames every
+/// few ticks (`?round=&from=&to=&every=`), as MessagePack, for the
+/// between-rounds scrubber. Public like the replay download.
+pub async fn get_keyframes(
+    State(state): State<AppState>,
+    Path(match_id): Path<String>,
+    Query(query): Query<KeyframesQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let file = state
+        .history
+        .read()
+        .await
+        .replay_file(&match_id)
+        .map(|(_, file)| file)
+        .ok_or_else(|| AppError::NotFound(format!("Match {match_id} not found")))?;
+    let round = query.round;
+    let body = tokio::task::spawn_blocking(move || {
+        let replay = decode_replay(&file).map_err(|e| AppError::Internal(e.to_string()))?;
+        let keyframes = replay_keyframes(&replay, &query).ok_or_else(|| {
+            AppError::NotFound(format!("Round {} not found", round.unwrap_or_default()))
+        })?;
+        rmp_serde::to_vec_named(&keyframes).map_err(|e| AppError::Internal(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::Internal(e.to_string()))??;
+    Ok(([(header::CONTENT_TYPE, "application/msgpack")], body))
+}
+
+#[cfg(test)]
+mod tests {
+    use breakpoint_core::game_trait::GameId;
+    use breakpoint_core::test_helpers::make_players;
+
+    use super::*;
+
+    fn metadata(match_id: &str) -> ReplayMetadata {
+        ReplayMetadata {
+            match_id: match_id.to_string(),
+            game: GameId::Golf,
+            room_code: "ABCD-1234".to_string(),
+            players: make_players(2),
+            custom: Default::default(),
+            tick_rate: 10.0,
+            started_at: 100,
+            ended_at: 0,
+            server_version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+
+    fn score(player_id: u64, score: i32) -> PlayerScore {
+        PlayerScore {
+            player_id,
+            score,
+            disconnected: false,
+        }
+    }
+
+    #[test]
+    fn recorder_caps_frames_per_round() {
+        let mut recorder = ReplayRecorder::new(metadata("m1"), 3);
+        recorder.begin_round(1);
+        for tick in 1..=5 {
+            let course = (tick == 1).then_some(&[9u8, 9][..]);
+            recorder.record_frame(tick, u64::from(tick) * 50, &[tick as u8], course);
+        }
+        recorder.end_round(vec![score(1, 2)], None);
+        recorder.begin_round(2);
+        recorder.record_frame(1, 50, &[1], None);
+        let replay = recorder.finish(vec![score(2, 1), score(1, 4)], 200);
+
+        assert_eq!(replay.rounds.len(), 2);
+        let first = &replay.rounds[0];
+        assert_eq!(first.frames.len(), 3);
+        assert!(first.truncated);
+        assert_eq!(first.frames[0].course.as_deref(), Some(&[9u8, 9][..]));
+        assert_eq!(first.scores, vec![score(1, 2)]);
+        assert!(!replay.rounds[1].truncated);
+        assert_eq!(replay.final_scores, vec![score(1, 4), score(2, 1)]);
+        assert_eq!(replay.metadata.ended_at, 200);
+    }
+
+    #[test]
+    fn thinning_halves_the_recording() {
+        let mut recorder = ReplayRecorder::new(metadata("m1"), 100);
+        recorder.begin_round(1);
+        for tick in 1..=8 {
+            let course = (tick == 2).then_some(&[9u8, 9][..]);
+            recorder.record_frame(tick, u64::from(tick) * 50, &[0; 10], course);
+        }
+        assert_eq!(recorder.bytes(), 82);
+
+        // Tick 2 carries the course, so it stays
+        assert_eq!(recorder.thin(), 30);
+        assert_eq!(recorder.bytes(), 52);
+        for tick in 9..=12 {
+            recorder.record_frame(tick, u64::from(tick) * 50, &[0; 10], None);
+        }
+        let replay = recorder.finish(Vec::new(), 700);
+        let ticks: Vec<u32> = replay.rounds[0].frames.iter().map(|f| f.tick).collect();
+        assert_eq!(ticks, [1, 2, 3, 5, 7, 10, 12]);
+    }
+
+    #[test]
+    fn history_keeps_most_recent_matches() {
+        let mut history = MatchHistory::new(&ReplaysConfig {
+            max_matches: 2,
+            ..ReplaysConfig::default()
+        });
+        for id in ["a", "b", "c"] {
+            let replay = ReplayRecorder::new(metadata(id), 10).finish(Vec::new(), 1);
+            let file = encode_replay(&replay).unwrap();
+            history.insert(&replay, Bytes::from(file));
+        }
+
+        let ids: Vec<String> = history.list().into_iter().map(|m| m.match_id).collect();
+        assert_eq!(ids, vec!["c", "b"]);
+        assert!(history.replay_file("a").is_none());
+        let (summary, file) = history.replay_file("b").unwrap();
+        assert_eq!(summary.replay_bytes, file.len());
+        assert_eq!(decode_replay(&file).unwrap().metadata.match_id, "b");
+    }
+
+    #[test]
+    fn keyframes_widen_to_the_cap() {
+        let mut recorder = ReplayRecorder::new(metadata("m1"), 100_000);
+        for round in 1..=2 {
+            recorder.begin_round(round);
+            for tick in 1..=(u32::from(round) * 6000) {
+                recorder.record_frame(tick, u64::from(tick) * 100, &[0], None);
+            }
+        }
+        let replay = recorder.finish(Vec::new(), 1);
+        let query = |round, every| KeyframesQuery {
+            round,
+            from: 0,
+            to: u32::MAX,
+            every,
+        };
+
+        let first = replay_keyframes(&replay, &query(Some(1), 5)).unwrap();
+        assert_eq!((first.every, first.last_tick), (6, 6000));
+        assert!(first.frames.len() as u32 <= MAX_KEYFRAMES);
+
+        let last = replay_keyframes(&replay, &query(None, 0)).unwrap();
+        assert_eq!(last.round, 2);
+        assert_eq!(last.every, 11);
+        assert!(last.frames.len() as u32 <= MAX_KEYFRAMES);
+
+        assert!(replay_keyframes(&replay, &query(Some(3), 5)).is_none());
+    }
+}
@@ -0,0 +1,303 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use breakpoint_core::game_trait::PlayerId;
+
+/// Sliding window used to compute actions per minute.
+const APM_WINDOW: Duration = Duration::from_secs(60);
+/// Sustained action rate above which a player is flagged as spamming.
+/// Humans rarely sustain more than ~8 distinct input changes per second.
+pub const MAX_HUMAN_APM: f64 = 600.0;
+/// Minimum action intervals sampled before the regularity check applies.
+const MIN_INTERVAL_SAMPLES: u64 = 40;
+/// Coefficient of variation (stddev / mean) of action intervals below which
+/// input timing is considered machine-regular.
+pub const MIN_HUMAN_INTERVAL_CV: f64 = 0.05;
+/// Minimum time between two violations for the same player, so a single
+/// sustained burst counts once per period rather than once per input.
+const VIOLATION_COOLDOWN: Duration = Duration::from_secs(10);
+
+/// Shared handle to a game session's input statistics. The game loop writes,
+/// the admin API reads.
+pub type SharedInputStats = Arc<Mutex<InputStatsTracker>>;
+
+/// Why a player's input pattern was flagged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputViolation {
+    /// Action rate above [`MAX_HUMAN_APM`].
+    ExcessiveApm,
+    /// Inter-action timing too regular to be human (macro or bot).
+    RegularTiming,
+}
+
+/// Running input statistics for one player.
+///
+/// Clients send their input state every frame, so an "action" is an input
+/// message whose action key (see `BreakpointGame::input_action_key`) differs
+/// from the previous one. Raw message counts are tracked separately.
+#[derive(Debug, Default)]
+struct PlayerInputStats {
+    messages: u64,
+    actions: u64,
+    last_action_key: Vec<u8>,
+    last_action_at: Option<Instant>,
+    /// Timestamps of actions within the APM window.
+    recent_actions: VecDeque<Instant>,
+    /// Welford running mean/variance of inter-action intervals (seconds).
+    interval_count: u64,
+    interval_mean: f64,
+    interval_m2: f64,
+    violations: u32,
+    last_violation: Option<(InputViolation, Instant)>,
+}
+
+impl PlayerInputStats {
+    fn interval_stddev(&self) -> f64 {
+        if self.interval_count < 2 {
+            return 0.0;
+        }
+        (self.interval_m2 / (self.interval_count - 1) as f64).sqrt()
+    }
+
+    fn apm(&self) -> f64 {
+        let Some(first) = self.recent_actions.front() else {
+            return 0.0;
+        };
+        let Some(last) = self.recent_actions.back() else {
+            return 0.0;
+        };
+        // Use a minimum span of 10s so a short burst doesn't extrapolate wildly.
+        let span = last
+            .duration_since(*first)
+            .max(Duration::from_secs(10))
+            .as_secs_f64();
+        self.recent_actions.len() as f64 * 60.0 / span
+    }
+
+    fn check(&self) -> Option<InputViolation> {
+        if self.apm() > MAX_HUMAN_APM {
+            return Some(InputViolation::ExcessiveApm);
+        }
+        if self.interval_count >= MIN_INTERVAL_SAMPLES
+            && self.interval_mean > 0.0
+            && self.interval_stddev() / self.interval_mean < MIN_HUMAN_INTERVAL_CV
+        {
+            return Some(InputViolation::RegularTiming);
+        }
+        None
+    }
+}
+
+/// Per-player input statistics snapshot exposed by the admin API.
+#[derive(Debug, Clone, Serialize)]
+pub struct InputStatsSnapshot {
+    pub player_id: PlayerId,
+    pub messages: u64,
+    pub actions: u64,
+    pub apm: f64,
+    pub mean_interval_ms: f64,
+    pub interval_stddev_ms: f64,
+    pub violations: u32,
+    pub last_violation: Option<InputViolation>,
+}
+
+/// Tracks input frequency and timing patterns for every player in a session.
+#[derive(Debug, Default)]
+pub struct InputStatsTracker {
+    players: HashMap<PlayerId, PlayerInputStats>,
+}
+
+impl InputStatsTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an input message, reduced to its discrete action key. Returns a
+    /// violation when this input pushed the player over a heuristic threshold
+    /// (at most once per cooldown period).
+    pub fn record(
+        &mut self,
+        player_id: PlayerId,
+        action_key: &[u8],
+        now: Instant,
+    ) -> Option<InputViolation> {
+        let stats = self.players.entry(player_id).or_default();
+        stats.messages += 1;
+
+        if stats.messages > 1 && stats.last_action_key == action_key {
+            return None;
+        }
+        stats.last_action_key.clear();
+        stats.last_action_key.extend_from_slice(action_key);
+        stats.actions += 1;
+
+        if let Some(prev) = stats.last_action_at {
+            let dt = now.duration_since(prev).as_secs_f64();
+            stats.interval_count += 1;
+            let delta = dt - stats.interval_mean;
+            stats.interval_mean += delta / stats.interval_count as f64;
+            stats.interval_m2 += delta * (dt - stats.interval_mean);
+        }
+        stats.last_action_at = Some(now);
+
+        stats.recent_actions.push_back(now);
+        while let Some(&front) = stats.recent_actions.front() {
+            if now.duration_since(front) > APM_WINDOW {
+                stats.recent_actions.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        let violation = stats.check()?;
+        if stats
+            .last_violation
+            .is_some_and(|(_, at)| now.duration_since(at) < VIOLATION_COOLDOWN)
+        {
+            return None;
+        }
+        stats.violations += 1;
+        stats.last_violation = Some((violation, now));
+        Some(violation)
+    }
+
+    /// Forget a player's statistics (e.g. when they leave the session).
+    pub fn remove(&mut self, player_id: PlayerId) {
+        self.players.remove(&player_id);
+    }
+
+    /// Snapshot all players' statistics, sorted by player ID.
+    pub fn snapshot(&self) -> Vec<InputStatsSnapshot> {
+        let mut out: Vec<InputStatsSnapshot> = self
+            .players
+            .iter()
+            .map(|(&player_id, s)| InputStatsSnapshot {
+                player_id,
+                messages: s.messages,
+                actions: s.actions,
+                apm: s.apm(),
+                mean_interval_ms: s.interval_mean * 1000.0,
+                interval_stddev_ms: s.interval_stddev() * 1000.0,
+                violations: s.violations,
+                last_violation: s.last_violation.map(|(v, _)| v),
+            })
+            .collect();
+        out.sort_by_key(|s| s.player_id);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_payload_is_not_an_action() {
+        let mut tracker = InputStatsTracker::new();
+        let start = Instant::now();
+        for i in 0..10 {
+            tracker.record(1, &[0], start + Duration::from_millis(i * 16));
+        }
+        let snap = &tracker.snapshot()[0];
+        assert_eq!(snap.messages, 10);
+        assert_eq!(snap.actions, 1);
+    }
+
+    #[test]
+    fn human_timing_not_flagged() {
+        let mut tracker = InputStatsTracker::new();
+        let start = Instant::now();
+        let mut t = start;
+        // Irregular intervals between 150ms and 600ms
+        for i in 0..100u64 {
+            t += Duration::from_millis(150 + (i * 137) % 450);
+            assert!(tracker.record(1, &[(i % 2) as u8], t).is_none());
+        }
+        assert_eq!(tracker.snapshot()[0].violations, 0);
+    }
+
+    #[test]
+    fn metronomic_timing_flagged_as_regular() {
+        let mut tracker = InputStatsTracker::new();
+        let start = Instant::now();
+        let mut flagged = None;
+        for i in 0..60u64 {
+            let t = start + Duration::from_millis(i * 250);
+            if let Some(v) = tracker.record(1, &[(i % 2) as u8], t) {
+                flagged = Some(v);
+            }
+        }
+        assert_eq!(flagged, Some(InputViolation::RegularTiming));
+        assert_eq!(tracker.snapshot()[0].violations, 1);
+    }
+
+    #[test]
+    fn spam_flagged_as_excessive_apm() {
+        let mut tracker = InputStatsTracker::new();
+        let start = Instant::now();
+        let mut flagged = None;
+        // ~25 distinct inputs per second with jittered timing
+        for i in 0..400u64 {
+            let t = start + Duration::from_millis(i * 40 + (i * 7) % 13);
+            if let Some(v) = tracker.record(1, &i.to_le_bytes(), t) {
+                flagged.get_or_insert(v);
+            }
+        }
+        assert_eq!(flagged, Some(InputViolation::ExcessiveApm));
+    }
+
+    #[test]
+    fn violations_rate_limited_by_cooldown() {
+        let mut tracker = InputStatsTracker::new();
+        let start = Instant::now();
+        // 5 seconds of metronomic input: well inside one cooldown period
+        for i in 0..100u64 {
+            tracker.record(1, &[(i % 2) as u8], start + Duration::from_millis(i * 50));
+        }
+        assert_eq!(tracker.snapshot()[0].violations, 1);
+    }
+
+    #[cfg(feature = "lasertag")]
+    #[test]
+    fn lasertag_aim_drift_not_flagged() {
+        use breakpoint_core::game_trait::BreakpointGame;
+        use breakpoint_lasertag::{LaserTagArena, LaserTagInput};
+
+        let game = LaserTagArena::new();
+        let mut tracker = InputStatsTracker::new();
+        let start = Instant::now();
+        // Two minutes at 60 Hz: the mouse never stops, movement and the
+        // trigger change a few times a second
+        for frame in 0..7200u64 {
+            let input = LaserTagInput {
+                move_x: if (frame / 37) % 2 == 0 { 1.0 } else { 0.0 },
+                move_z: if (frame / 53) % 3 == 0 { -1.0 } else { 0.0 },
+                aim_angle: frame as f32 * 0.013,
+                fire: frame % 45 < 4,
+                use_powerup: false,
+            };
+            let data = rmp_serde::to_vec(&input).unwrap();
+            let t = start + Duration::from_micros(frame * 16_667);
+            let violation = tracker.record(1, &game.input_action_key(&data), t);
+            assert!(violation.is_none(), "frame {frame}: {violation:?}");
+        }
+        let snap = &tracker.snapshot()[0];
+        assert_eq!(snap.messages, 7200);
+        assert!(snap.apm < MAX_HUMAN_APM, "apm {}", snap.apm);
+    }
+
+    #[test]
+    fn remove_forgets_player() {
+        let mut tracker = InputStatsTracker::new();
+        tracker.record(1, &[0], Instant::now());
+        tracker.record(2, &[0], Instant::now());
+        tracker.remove(1);
+        let snap = tracker.snapshot();
+        assert_eq!(snap.len(), 1);
+        assert_eq!(snap[0].player_id, 2);
+    }
+}
@@ -0,0 +1,280 @@
+//! Join queues: when a room is full, or its leader approves joins, new
+//! connections wait in line instead of being turned away, and enter in
+//! order as slots open. The same line holds players waiting for a new room
+//! while the server is at its room cap.
+
+use std::collections::VecDeque;
+use std::net::IpAddr;
+
+use bytes::Bytes;
+use tokio::sync::oneshot;
+
+use breakpoint_core::game_trait::PlayerId;
+use breakpoint_core::i18n::{LocalizedText, TextKey};
+use breakpoint_core::net::messages::{
+    JoinQueueUpdateMsg, JoinQueuedMsg, QueuedJoinEntry, ServerMessage,
+};
+use breakpoint_core::net::protocol::encode_server_message;
+use breakpoint_core::player::PlayerColor;
+use breakpoint_core::room::RoomState;
+
+use crate::room_manager::{Lane, PlayerSender};
+
+/// Caps that keep a popular room's line from being flooded.
+#[derive(Debug, Clone, Copy)]
+pub struct JoinQueueLimits {
+    /// Longest line per room; 0 disables queueing.
+    pub max_len: usize,
+    /// Most entries one IP address may hold in a room's line.
+    pub per_ip: usize,
+}
+
+impl Default for JoinQueueLimits {
+    fn default() -> Self {
+        Self {
+            max_len: 16,
+            per_ip: 2,
+        }
+    }
+}
+
+/// A queued connection's new player slot.
+#[derive(Debug)]
+pub struct Admission {
+    pub room_code: String,
+    pub player_id: PlayerId,
+    pub session_token: String,
+    pub room_state: RoomState,
+}
+
+/// Delivered to a waiting connection once: its slot, or why it was turned
+/// away. A dropped sender means the room closed.
+pub type AdmissionResult = Result<Admission, LocalizedText>;
+
+/// A connection waiting for a slot.
+pub struct QueuedJoin {
+    pub ticket: u64,
+    pub player_name: String,
+    pub player_color: PlayerColor,
+    /// Becomes the player's outbound channel on admission; carries
+    /// position updates until then.
+    pub sender: PlayerSender,
+    ip: IpAddr,
+    approved: bool,
+    admit_tx: oneshot::Sender<AdmissionResult>,
+}
+
+impl QueuedJoin {
+    pub fn ip(&self) -> IpAddr {
+        self.ip
+    }
+
+    /// Hand the connection its result. Returns false if it already left.
+    pub fn resolve(self, result: AdmissionResult) -> bool {
+        self.admit_tx.send(result).is_ok()
+    }
+}
+
+/// One room's line of waiting connections, oldest first.
+#[derive(Default)]
+pub struct JoinQueue {
+    entries: VecDeque<QueuedJoin>,
+    /// Hold every new join until the leader admits it.
+    pub require_approval: bool,
+    next_ticket: u64,
+}
+
+impl JoinQueue {
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Add a connection to the end of the line. Returns its ticket and the
+    /// receiver its admission arrives on.
+    pub fn enqueue(
+        &mut self,
+        player_name: String,
+        player_color: PlayerColor,
+        sender: PlayerSender,
+        ip: IpAddr,
+        limits: JoinQueueLimits,
+    ) -> Result<(u64, oneshot::Receiver<AdmissionResult>), LocalizedText> {
+        if limits.max_len == 0 {
+            return Err(TextKey::RoomFull.into());
+        }
+        // Entries whose connection is gone don't hold places
+        self.entries.retain(|e| !e.admit_tx.is_closed());
+        let same_ip = self.entries.iter().filter(|e| e.ip == ip).count();
+        if self.entries.len() >= limits.max_len || same_ip >= limits.per_ip {
+            return Err(TextKey::JoinQueueFull.into());
+        }
+        self.next_ticket += 1;
+        let ticket = self.next_ticket;
+        let (admit_tx, admit_rx) = oneshot::channel();
+        self.entries.push_back(QueuedJoin {
+            ticket,
+            player_name,
+            player_color,
+            sender,
+            ip,
+            approved: false,
+            admit_tx,
+        });
+        Ok((ticket, admit_rx))
+    }
+
+    pub fn contains(&self, ticket: u64) -> bool {
+        self.entries.iter().any(|e| e.ticket == ticket)
+    }
+
+    pub fn remove(&mut self, ticket: u64) -> Option<QueuedJoin> {
+        let index = self.entries.iter().position(|e| e.ticket == ticket)?;
+        self.entries.remove(index)
+    }
+
+    /// Let a waiting connection in once a slot opens. Returns false for an
+    /// unknown ticket.
+    pub fn approve(&mut self, ticket: u64) -> bool {
+        match self.entries.iter_mut().find(|e| e.ticket == ticket) {
+            Some(entry) => {
+                entry.approved = true;
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Take the first connection allowed in, skipping any still waiting
+    /// for approval. Connections that already left are discarded.
+    pub fn pop_admissible(&mut self) -> Option<QueuedJoin> {
+        self.entries.retain(|e| !e.admit_tx.is_closed());
+        let index = self
+            .entries
+            .iter()
+            .position(|e| e.approved || !self.require_approval)?;
+        self.entries.remove(index)
+    }
+
+    /// Turn everyone away, e.g. when the room closes.
+    pub fn clear(&mut self, reason: TextKey) {
+        for entry in self.entries.drain(..) {
+            entry.resolve(Err(reason.into()));
+        }
+    }
+
+    /// The line as shown to the room's players.
+    pub fn update_msg(&self) -> JoinQueueUpdateMsg {
+        JoinQueueUpdateMsg {
+            require_approval: self.require_approval,
+            entries: self
+                .entries
+                .iter()
+                .map(|e| QueuedJoinEntry {
+                    ticket: e.ticket,
+                    display_name: e.player_name.clone(),
+                    approved: e.approved,
+                })
+                .collect(),
+        }
+    }
+
+    /// Tell every waiting connection its place in line. The server-wide
+    /// line for new rooms has an empty `room_code`.
+    pub fn notify_positions(&self, room_code: &str) {
+        let queue_len = self.entries.len().min(u16::MAX as usize) as u16;
+        for (i, entry) in self.entries.iter().enumerate() {
+            let msg = ServerMessage::JoinQueued(JoinQueuedMsg {
+                room_code: room_code.to_string(),
+                position: (i + 1).min(u16::MAX as usize) as u16,
+                queue_len,
+                awaiting_approval: self.require_approval && !entry.approved,
+            });
+            if let Ok(data) = encode_server_message(&msg)
+                && let Err(e) = entry.sender.try_send(Lane::Control, Bytes::from(data))
+            {
+                tracing::debug!(
+                    ticket = entry.ticket, room = room_code, error = %e,
+                    "Failed to send queue position"
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    fn ip(last: u8) -> IpAddr {
+        IpAddr::V4(Ipv4Addr::new(10, 0, 0, last))
+    }
+
+    fn enqueue(
+        queue: &mut JoinQueue,
+        name: &str,
+        from: IpAddr,
+        limits: JoinQueueLimits,
+    ) -> Result<(u64, oneshot::Receiver<AdmissionResult>), LocalizedText> {
+        let (tx, _rx) = crate::outbound::channel(8);
+        queue.enqueue(name.to_string(), PlayerColor::default(), tx, from, limits)
+    }
+
+    #[test]
+    fn caps_line_length_and_entries_per_ip() {
+        let limits = JoinQueueLimits {
+            max_len: 3,
+            per_ip: 2,
+        };
+        let mut queue = JoinQueue::default();
+        let _a = enqueue(&mut queue, "a", ip(1), limits).unwrap();
+        let _b = enqueue(&mut queue, "b", ip(1), limits).unwrap();
+        let err = enqueue(&mut queue, "c", ip(1), limits).unwrap_err();
+        assert_eq!(err.key, TextKey::JoinQueueFull);
+        let _d = enqueue(&mut queue, "d", ip(2), limits).unwrap();
+        assert!(enqueue(&mut queue, "e", ip(3), limits).is_err());
+
+        // A connection that left frees its place
+        drop(_d);
+        assert!(enqueue(&mut queue, "f", ip(3), limits).is_ok());
+
+        let disabled = JoinQueueLimits {
+            max_len: 0,
+            per_ip: 2,
+        };
+        let err = enqueue(&mut JoinQueue::default(), "g", ip(4), disabled).unwrap_err();
+        assert_eq!(err.key, TextKey::RoomFull);
+    }
+
+    #[test]
+    fn approval_lets_later_entries_pass_unapproved_ones() {
+        let limits = JoinQueueLimits::default();
+        let mut queue = JoinQueue {
+            require_approval: true,
+            ..JoinQueue::default()
+        };
+        let (first, _rx1) = enqueue(&mut queue, "first", ip(1), limits).unwrap();
+        let (second, _rx2) = enqueue(&mut queue, "second", ip(2), limits).unwrap();
+        assert!(queue.pop_admissible().is_none());
+
+        assert!(queue.approve(second));
+        assert_eq!(queue.pop_admissible().map(|e| e.ticket), Some(second));
+
+        queue.require_approval = false;
+        assert_eq!(queue.pop_admissible().map(|e| e.ticket), Some(first));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn clearing_turns_everyone_away() {
+        let mut queue = JoinQueue::default();
+        let (_, mut rx) = enqueue(&mut queue, "a", ip(1), JoinQueueLimits::default()).unwrap();
+        queue.clear(TextKey::RoomGone);
+        assert_eq!(rx.try_recv().unwrap().unwrap_err().key, TextKey::RoomGone);
+    }
+}
@@ -1,14 +1,41 @@
+pub mod admin;
+pub mod announcements;
 pub mod api;
 pub mod auth;
+pub mod bot_api;
+pub mod client_reports;
 pub mod config;
+pub mod content;
+pub mod decathlon;
+#[cfg(feature = "embed-assets")]
+pub mod embedded;
 pub mod error;
+pub mod event_sources;
 pub mod event_store;
+pub mod game_events;
 pub mod game_loop;
 pub mod health;
+pub mod history;
+pub mod input_stats;
+pub mod join_queue;
+pub mod lifecycle_hooks;
+pub mod memory_budget;
+pub mod moderation;
+pub mod outbound;
+pub mod player_settings;
 pub mod rate_limit;
+pub mod referee;
 pub mod room_manager;
+pub mod room_webhooks;
+pub mod schedule;
+pub mod season;
+pub mod social;
 pub mod sse;
 pub mod state;
+pub mod stats_board;
+pub mod status;
+pub mod transfers;
+pub mod tutorial;
 pub mod webhooks;
 pub mod ws;
 
@@ -16,7 +43,7 @@ use std::net::SocketAddr;
 use std::time::Duration;
 
 use axum::Router;
-use axum::extract::ConnectInfo;
+use axum::extract::{ConnectInfo, DefaultBodyLimit};
 use axum::http::HeaderValue;
 use axum::middleware;
 use tower::ServiceBuilder;
@@ -25,9 +52,11 @@ use tower_http::cors::{Any, CorsLayer};
 use tower_http::services::ServeDir;
 use tower_http::set_header::SetResponseHeaderLayer;
 use tower_http::timeout::TimeoutLayer;
+use tracing::Instrument;
 
 use breakpoint_core::net::messages::{AlertEventMsg, ServerMessage};
 use breakpoint_core::net::protocol::encode_server_message;
+use breakpoint_core::telemetry::METRICS_TARGET;
 
 use config::ServerConfig;
 use state::AppState;
@@ -45,7 +74,83 @@ pub fn build_app(config: ServerConfig) -> (Router<()>, AppState) {
             axum::routing::post(api::claim_event),
         )
         .route("/events/stream", axum::routing::get(sse::event_stream))
-        .route("/status", axum::routing::get(api::get_status));
+        .route("/status", axum::routing::get(api::get_status))
+        .route(
+            "/schedules",
+            axum::routing::post(api::post_schedule).get(api::list_schedules),
+        )
+        .route(
+            "/schedules/{schedule_id}",
+            axum::routing::delete(api::delete_schedule),
+        )
+        .route(
+            "/admin/input-stats",
+            axum::routing::get(admin::get_input_stats),
+        )
+        .route("/admin/memory", axum::routing::get(admin::get_memory))
+        .route(
+            "/admin/announcements",
+            axum::routing::post(admin::post_announcement).get(admin::list_announcements),
+        )
+        .route(
+            "/admin/announcements/{announcement_id}",
+            axum::routing::delete(admin::delete_announcement),
+        )
+        .route("/admin/motd", axum::routing::put(admin::put_motd))
+        .route(
+            "/admin/telemetry",
+            axum::routing::get(admin::get_client_reports),
+        )
+        .route(
+            "/admin/bans",
+            axum::routing::post(admin::post_ban).get(admin::list_bans),
+        )
+        .route(
+            "/admin/bans/{ban_id}",
+            axum::routing::delete(admin::delete_ban),
+        )
+        .route(
+            "/admin/moderation/audit",
+            axum::routing::get(admin::get_moderation_audit),
+        )
+        .route("/admin/content", axum::routing::get(admin::list_content))
+        .route(
+            "/admin/content/{kind}/{content_id}",
+            axum::routing::delete(admin::delete_content),
+        )
+        .route(
+            "/admin/room-webhooks",
+            axum::routing::post(admin::post_room_webhook).get(admin::list_room_webhooks),
+        )
+        .route(
+            "/admin/room-webhooks/{hook_id}",
+            axum::routing::delete(admin::delete_room_webhook),
+        )
+        .route(
+            "/admin/rooms/{room_code}/merge",
+            axum::routing::post(admin::merge_room),
+        )
+        .route(
+            "/admin/rooms/{room_code}/split",
+            axum::routing::post(admin::split_room),
+        )
+        .route(
+            "/referee/simulate",
+            axum::routing::post(referee::post_simulate),
+        )
+        .route("/history", axum::routing::get(history::list_history))
+        .route(
+            "/history/rooms/{room_code}/decathlon",
+            axum::routing::get(decathlon::get_decathlon),
+        )
+        .route(
+            "/history/{match_id}/replay",
+            axum::routing::get(history::get_replay),
+        )
+        .route(
+            "/history/{match_id}/keyframes",
+            axum::routing::get(history::get_keyframes),
+        );
     #[cfg(feature = "profiling")]
     let api_routes = api_routes.route("/profile", axum::routing::get(api::get_profile));
     let api_routes = api_routes
@@ -77,6 +182,53 @@ pub fn build_app(config: ServerConfig) -> (Router<()>, AppState) {
             Duration::from_secs(30),
         )));
 
+    // Client crash/error reports (no bearer auth — browsers post these; rate limited)
+    let telemetry_routes = Router::new()
+        .route("/", axum::routing::post(client_reports::post_telemetry))
+        .layer(DefaultBodyLimit::max(64 * 1024))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            api_rate_limit_layer,
+        ));
+
+    // Player settings sync (no bearer auth — checked against the player's
+    // identity key; rate limited)
+    let player_routes = Router::new()
+        .route(
+            "/{id}/settings",
+            axum::routing::get(player_settings::get_settings).put(player_settings::put_settings),
+        )
+        .layer(DefaultBodyLimit::max(64 * 1024))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            api_rate_limit_layer,
+        ));
+
+    // Public read-only routes for the lobby (no bearer auth, still rate limited)
+    let public_routes = Router::new()
+        .route("/schedules", axum::routing::get(api::list_public_schedules))
+        .route(
+            "/schedules/{schedule_id}/ics",
+            axum::routing::get(api::get_schedule_ics),
+        )
+        .route(
+            "/history/{match_id}/replay",
+            axum::routing::get(history::get_replay),
+        )
+        .route(
+            "/history/{match_id}/keyframes",
+            axum::routing::get(history::get_keyframes),
+        )
+        .route("/season", axum::routing::get(season::get_season))
+        .route(
+            "/stats-board/stream",
+            axum::routing::get(stats_board::stats_board_stream),
+        )
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            api_rate_limit_layer,
+        ));
+
     let cors = CorsLayer::new()
         .allow_origin(Any)
         .allow_methods(Any)
@@ -85,15 +237,17 @@ pub fn build_app(config: ServerConfig) -> (Router<()>, AppState) {
     // Static file serving with Cache-Control headers for immutable assets.
     // WASM bundles, JS, and CSS are fingerprinted by wasm-pack, so long
     // cache lifetimes are safe. HTML is short-cached to pick up new deploys.
-    let static_service = ServeDir::new(&web_root);
-
     let app = Router::new()
         .route("/ws", axum::routing::get(ws::ws_handler))
         .route("/health", axum::routing::get(health::health_check))
         .route("/health/ready", axum::routing::get(health::readiness_check))
+        .route("/status", axum::routing::get(status::status_page))
         .nest("/api/v1", api_routes)
         .nest("/api/v1/webhooks", webhook_routes)
-        .fallback_service(static_service)
+        .nest("/api/v1/public", public_routes)
+        .nest("/api/v1/telemetry", telemetry_routes)
+        .nest("/api/v1/players", player_routes);
+    let app = serve_static(app, &web_root)
         .layer(axum::middleware::from_fn(cache_control_middleware))
         .layer(CompressionLayer::new())
         .layer(cors)
@@ -129,6 +283,20 @@ pub fn build_app(config: ServerConfig) -> (Router<()>, AppState) {
     (app, state)
 }
 
+/// Serve the web client from `web_root`. Builds with `embed-assets` use
+/// the copy compiled into the binary when `web_root` does not exist.
+fn serve_static(app: Router<AppState>, web_root: &str) -> Router<AppState> {
+    #[cfg(feature = "embed-assets")]
+    if !std::path::Path::new(web_root).is_dir() {
+        tracing::info!(
+            web_root,
+            "web_root not found — serving the embedded web client"
+        );
+        return app.fallback(embedded::serve);
+    }
+    app.fallback_service(ServeDir::new(web_root))
+}
+
 /// Background task that subscribes to the EventStore broadcast channel and
 /// re-broadcasts each new event to all connected rooms via WSS.
 pub fn spawn_event_broadcaster(state: AppState) {
@@ -151,24 +319,43 @@ pub fn spawn_event_broadcaster(state: AppState) {
                 result = rx.recv() => {
                     match result {
                         Ok(event) => {
+                            let received = std::time::Instant::now();
+                            let event_type = event.event_type.clone();
+                            let filter_event = event.clone();
+                            let span = tracing::info_span!(
+                                "event_broadcast",
+                                event_id = %event.id,
+                                ?event_type,
+                            );
                             let msg = ServerMessage::AlertEvent(
                                 Box::new(AlertEventMsg { event }),
                             );
                             match encode_server_message(&msg) {
                                 Ok(data) => {
-                                    let rooms = state.rooms.read().await;
-                                    rooms.broadcast_to_all_rooms(&data);
+                                    async {
+                                        let rooms = state.rooms.read().await;
+                                        rooms.broadcast_event(&filter_event, &data);
+                                    }
+                                    .instrument(span)
+                                    .await;
+                                    state.broadcast_stats.record(received.elapsed());
+                                    tracing::trace!(
+                                        target: METRICS_TARGET,
+                                        ?event_type,
+                                        monotonic_counter.events_broadcast = 1_u64
+                                    );
                                 },
                                 Err(e) => {
-                                    tracing::error!(
+                                    span.in_scope(|| tracing::error!(
                                         error = %e,
                                         "Failed to encode AlertEvent for broadcast"
-                                    );
+                                    ));
                                 },
                             }
                         },
                         Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
                             total_lagged += n;
+                            state.broadcast_stats.record_lag(n);
                             tracing::warn!(
                                 skipped = n, total_lagged,
                                 "Event broadcaster lagged"
@@ -187,6 +374,231 @@ pub fn spawn_event_broadcaster(state: AppState) {
     });
 }
 
+/// Background task that publishes announcements to every room as they
+/// start and end. Admin changes are published immediately by the handlers.
+pub fn spawn_announcement_broadcaster(state: AppState) {
+    let check_interval = state.config.announcements.check_interval_secs;
+    let shutdown = state.shutdown.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(check_interval));
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => {
+                    tracing::info!("Announcement broadcaster shutting down");
+                    break;
+                }
+                _ = interval.tick() => {
+                    announcements::publish(&state).await;
+                }
+            }
+        }
+    });
+}
+
+/// Background task that refreshes friends' lists as games start and end in
+/// rooms, so players who share their presence show what they're playing.
+pub fn spawn_presence_notifier(state: AppState) {
+    let shutdown = state.shutdown.clone();
+    tokio::spawn(async move {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        state.rooms.write().await.report_game_changes(tx);
+        loop {
+            let room_code = tokio::select! {
+                _ = shutdown.cancelled() => {
+                    tracing::info!("Presence notifier shutting down");
+                    break;
+                }
+                room_code = rx.recv() => match room_code {
+                    Some(room_code) => room_code,
+                    None => break,
+                },
+            };
+            ws::notify_room_watchers(&state, &room_code).await;
+        }
+    });
+}
+
+/// Background task that delivers room lifecycle events to the registered
+/// room webhooks, runs `[lifecycle_hooks]` as rooms open and close, and
+/// publishes game milestones to the event store when `[game_events]` is
+/// enabled.
+pub fn spawn_room_webhook_dispatcher(state: AppState) {
+    let shutdown = state.shutdown.clone();
+    let mut milestones = game_events::GameEventProducer::new(state.config.game_events.clone());
+    tokio::spawn(async move {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(room_webhooks::QUEUE_CAPACITY);
+        state
+            .rooms
+            .write()
+            .await
+            .report_activity(room_webhooks::RoomActivitySink::new(tx));
+        let http = reqwest::Client::new();
+        let mut lifecycle =
+            lifecycle_hooks::LifecycleHooks::new(&state.config.lifecycle_hooks, http.clone());
+        loop {
+            let activity = tokio::select! {
+                _ = shutdown.cancelled() => {
+                    tracing::info!("Room webhook dispatcher shutting down");
+                    break;
+                }
+                activity = rx.recv() => match activity {
+                    Some(activity) => activity,
+                    None => break,
+                },
+            };
+            let now = breakpoint_core::time::unix_now();
+            if let Some(event) = milestones.observe(&activity, now) {
+                state.event_store.write().await.insert(event);
+            }
+            if let Some(ref mut lifecycle) = lifecycle {
+                let metadata = match &activity {
+                    room_webhooks::RoomActivity::RoomCreated { room_code } => {
+                        state.rooms.read().await.room_metadata(room_code, now)
+                    },
+                    _ => None,
+                };
+                lifecycle.observe(&activity, metadata, now);
+            }
+            deliver_room_activity(&state, &http, &activity, now).await;
+            if let room_webhooks::RoomActivity::RoomClosed { room_code } = &activity
+                && !state
+                    .room_webhooks
+                    .read()
+                    .await
+                    .matching("decathlon_results")
+                    .is_empty()
+            {
+                let matches = state.history.read().await.list();
+                let results = decathlon::DecathlonResults::from_history(room_code, &matches, 0);
+                if !results.games.is_empty() {
+                    let activity = room_webhooks::RoomActivity::DecathlonResults(results);
+                    deliver_room_activity(&state, &http, &activity, now).await;
+                }
+            }
+        }
+    });
+}
+
+/// POST `activity` to every room webhook subscribed to its kind.
+async fn deliver_room_activity(
+    state: &AppState,
+    http: &reqwest::Client,
+    activity: &room_webhooks::RoomActivity,
+    now: u64,
+) {
+    let kind = activity.kind();
+    let hooks = state.room_webhooks.read().await.matching(kind);
+    if hooks.is_empty() {
+        return;
+    }
+    let body = room_webhooks::delivery_body(activity, now);
+    for hook in hooks {
+        tokio::spawn(room_webhooks::deliver(
+            http.clone(),
+            hook,
+            kind,
+            body.clone(),
+        ));
+    }
+}
+
+/// Background task that sends game night reminders and opens a room for
+/// each scheduled session when its start time arrives.
+pub fn spawn_schedule_runner(state: AppState) {
+    let check_interval = state.config.schedules.check_interval_secs;
+    let shutdown = state.shutdown.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(check_interval));
+        let http = reqwest::Client::new();
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => {
+                    tracing::info!("Schedule runner shutting down");
+                    break;
+                }
+                _ = interval.tick() => {
+                    run_schedules(&state, &http, breakpoint_core::time::unix_now()).await;
+                }
+            }
+        }
+    });
+}
+
+/// One scheduler pass: emit due reminders, open due rooms, prune ended sessions.
+async fn run_schedules(state: &AppState, http: &reqwest::Client, now: u64) {
+    let (reminders, openings) = {
+        let mut schedules = state.schedules.write().await;
+        schedules.prune(now);
+        (
+            schedules.take_due_reminders(now),
+            schedules.due_openings(now),
+        )
+    };
+
+    let mut events = Vec::new();
+    for session in reminders {
+        let event = session.reminder_event(now);
+        notify_schedule_webhook(http, &session, "reminder", &event);
+        events.push(event);
+    }
+    for id in openings {
+        let Some(scheduled) = state.schedules.read().await.get(&id).cloned() else {
+            continue;
+        };
+        let code = state.rooms.write().await.create_scheduled_room(&scheduled);
+        let Some(session) = state
+            .schedules
+            .write()
+            .await
+            .set_room_code(&id, code.clone())
+        else {
+            continue;
+        };
+        tracing::info!(schedule_id = %id, room = %code, "Opened room for scheduled session");
+        let event = session.opened_event(now);
+        notify_schedule_webhook(http, &session, "open", &event);
+        events.push(event);
+    }
+
+    if !events.is_empty() {
+        let mut store = state.event_store.write().await;
+        for event in events {
+            store.insert(event);
+        }
+    }
+}
+
+/// Fire-and-forget POST of a schedule event to the session's notify URL.
+fn notify_schedule_webhook(
+    http: &reqwest::Client,
+    session: &schedule::ScheduledSession,
+    kind: &'static str,
+    event: &breakpoint_core::events::Event,
+) {
+    let Some(url) = session.notify_url.clone() else {
+        return;
+    };
+    let body = serde_json::json!({
+        "kind": kind,
+        "schedule": schedule::ScheduleSummary::from(session),
+        "invitees": session.invitees,
+        "event": event,
+    });
+    let request = http.post(url).timeout(Duration::from_secs(10)).json(&body);
+    let schedule_id = session.id.clone();
+    tokio::spawn(async move {
+        match request.send().await {
+            Ok(resp) if !resp.status().is_success() => {
+                tracing::warn!(%schedule_id, status = %resp.status(), "Schedule webhook rejected");
+            },
+            Ok(_) => {},
+            Err(e) => {
+                tracing::warn!(%schedule_id, error = %e, "Schedule webhook failed");
+            },
+        }
+    });
+}
+
 /// Background task that periodically removes idle rooms.
 pub fn spawn_idle_room_cleanup(state: AppState) {
     let check_interval = state.config.rooms.idle_check_interval_secs;
@@ -224,8 +636,12 @@ async fn cache_control_middleware(
     let path = request.uri().path().to_string();
     let mut response = next.run(request).await;
 
-    // Skip API routes, WebSocket, and health check
-    if path.starts_with("/api/") || path.starts_with("/ws") || path == "/health" {
+    // Skip API routes, WebSocket, and health and status checks
+    if path.starts_with("/api/")
+        || path.starts_with("/ws")
+        || path == "/health"
+        || path == "/status"
+    {
         return response;
     }
 
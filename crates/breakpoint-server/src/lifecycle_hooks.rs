@@ -0,0 +1,475 @@
+//! Room lifecycle hooks for external session managers.
+//!
+//! Operators list hooks in `[lifecycle_hooks]` that run when a room is
+//! created and when it closes, so outside systems can follow rooms: a bot
+//! opening a voice channel per room, a booking dashboard, a cleanup job.
+//! A hook is either a URL that receives the event as a JSON POST (signed
+//! like room webhooks when it has a secret) or a command that gets the
+//! same JSON on stdin. Hooks are only configured from the file; the admin
+//! API cannot add commands.
+//!
+//! Hooks run on their own tasks, with a timeout, retries and a cap on how
+//! many run at once, so a slow or failing session manager never holds up
+//! a room. A room's close hooks wait for its create hooks to finish, so a
+//! manager never hears about a room closing before it opened.
+
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
+
+use crate::config::LifecycleHooksConfig;
+use crate::room_webhooks::{self, RoomActivity};
+
+/// Events a lifecycle hook can run on.
+pub const LIFECYCLE_EVENTS: &[&str] = &["room_created", "room_closed"];
+
+/// What a session manager is told about a room.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RoomMetadata {
+    pub room_code: String,
+    /// Vanity alias the room can also be joined by.
+    pub alias: Option<String>,
+    /// Display name of the room's leader; none for a scheduled room that
+    /// nobody has joined yet.
+    pub host: Option<String>,
+    pub max_players: u8,
+    /// Opened by the scheduler for a game night, not by a player.
+    pub scheduled: bool,
+    /// Unix seconds when the room was created.
+    pub created_at: u64,
+    /// Unix seconds when the room closed; only on `room_closed`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub closed_at: Option<u64>,
+}
+
+impl RoomMetadata {
+    /// What is known about a room that closed before its details were
+    /// read.
+    fn bare(room_code: &str, now: u64) -> Self {
+        Self {
+            room_code: room_code.to_string(),
+            alias: None,
+            host: None,
+            max_players: 0,
+            scheduled: false,
+            created_at: now,
+            closed_at: None,
+        }
+    }
+}
+
+/// JSON body sent to hooks: the event kind, when it was dispatched, and
+/// the room.
+#[derive(Debug, Serialize)]
+pub struct LifecycleEvent<'a> {
+    pub event: &'static str,
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub room: &'a RoomMetadata,
+}
+
+/// One `[[lifecycle_hooks.hooks]]` entry. Exactly one of `url` and
+/// `command` is set.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct LifecycleHook {
+    /// Endpoint that receives the event as a JSON POST.
+    pub url: Option<String>,
+    /// Signs POSTs as `X-Breakpoint-Signature-256`, like room webhooks.
+    pub secret: Option<String>,
+    /// Program and arguments to run, with the event JSON on stdin and
+    /// `BREAKPOINT_EVENT` and `BREAKPOINT_ROOM_CODE` in the environment.
+    pub command: Vec<String>,
+    /// Events to run on. Empty means both.
+    pub events: Vec<String>,
+}
+
+impl LifecycleHook {
+    pub fn validate(&self) -> Result<(), String> {
+        match (&self.url, self.command.is_empty()) {
+            (Some(_), false) | (None, true) => {
+                return Err("set exactly one of url and command".to_string());
+            },
+            (Some(url), true) if !(url.starts_with("http://") || url.starts_with("https://")) => {
+                return Err("url must be http:// or https://".to_string());
+            },
+            _ => {},
+        }
+        if self.secret.as_deref().is_some_and(str::is_empty) {
+            return Err("secret must not be empty".to_string());
+        }
+        if let Some(unknown) = self
+            .events
+            .iter()
+            .find(|e| !LIFECYCLE_EVENTS.contains(&e.as_str()))
+        {
+            return Err(format!("unknown event: {unknown}"));
+        }
+        Ok(())
+    }
+
+    pub fn wants(&self, kind: &str) -> bool {
+        self.events.is_empty() || self.events.iter().any(|e| e == kind)
+    }
+
+    fn describe(&self) -> &str {
+        self.url
+            .as_deref()
+            .or(self.command.first().map(String::as_str))
+            .unwrap_or_default()
+    }
+}
+
+/// Why one attempt at running a hook failed.
+#[derive(Debug)]
+enum HookFailure {
+    /// Worth another attempt.
+    Transient(String),
+    /// The hook refused the event; retrying won't help.
+    Rejected(String),
+}
+
+/// Runs the configured hooks as rooms open and close. Owned by the room
+/// activity dispatcher.
+#[derive(Debug)]
+pub struct LifecycleHooks {
+    hooks: Arc<Vec<LifecycleHook>>,
+    attempts: u32,
+    timeout: Duration,
+    in_flight: Arc<Semaphore>,
+    http: reqwest::Client,
+    /// Open rooms, with their create hooks while those still run.
+    open: HashMap<String, (RoomMetadata, Option<JoinHandle<()>>)>,
+}
+
+impl LifecycleHooks {
+    /// Hooks from the config file, or `None` when there are none.
+    /// Invalid entries are rejected at startup by config validation.
+    pub fn new(config: &LifecycleHooksConfig, http: reqwest::Client) -> Option<Self> {
+        if config.hooks.is_empty() {
+            return None;
+        }
+        Some(Self {
+            hooks: Arc::new(config.hooks.clone()),
+            attempts: config.attempts,
+            timeout: Duration::from_secs(config.timeout_secs),
+            in_flight: Arc::new(Semaphore::new(config.max_in_flight)),
+            http,
+            open: HashMap::new(),
+        })
+    }
+
+    /// Run the hooks for a room opening or closing. `metadata` is the
+    /// room's current details for `RoomCreated`; other activity is
+    /// ignored.
+    pub fn observe(&mut self, activity: &RoomActivity, metadata: Option<RoomMetadata>, now: u64) {
+        match activity {
+            RoomActivity::RoomCreated { room_code } => {
+                let room = metadata.unwrap_or_else(|| RoomMetadata::bare(room_code, now));
+                let task = self.spawn("room_created", room.clone(), now, None);
+                self.open.insert(room_code.clone(), (room, task));
+            },
+            RoomActivity::RoomClosed { room_code } => {
+                let (mut room, created) = self
+                    .open
+                    .remove(room_code)
+                    .unwrap_or_else(|| (RoomMetadata::bare(room_code, now), None));
+                room.closed_at = Some(now);
+                self.spawn("room_closed", room, now, created);
+            },
+            _ => {},
+        }
+    }
+
+    /// Rooms the hooks were told about that have not closed yet.
+    pub fn open_rooms(&self) -> usize {
+        self.open.len()
+    }
+
+    /// Start the hooks for one event on a task of their own, after
+    /// `after` finishes. Returns `None` when no hook wants the event.
+    fn spawn(
+        &self,
+        kind: &'static str,
+        room: RoomMetadata,
+        now: u64,
+        after: Option<JoinHandle<()>>,
+    ) -> Option<JoinHandle<()>> {
+        let wanted: Vec<usize> = (0..self.hooks.len())
+            .filter(|&i| self.hooks[i].wants(kind))
+            .collect();
+        if wanted.is_empty() {
+            return None;
+        }
+        let Ok(permit) = Arc::clone(&self.in_flight).try_acquire_owned() else {
+            tracing::warn!(room = %room.room_code, event = kind, "Too many lifecycle hooks running; skipped");
+            return None;
+        };
+        let body = serde_json::to_vec(&LifecycleEvent {
+            event: kind,
+            timestamp: now,
+            room: &room,
+        })
+        .unwrap_or_default();
+        let hooks = Arc::clone(&self.hooks);
+        let http = self.http.clone();
+        let (attempts, timeout) = (self.attempts, self.timeout);
+        Some(tokio::spawn(async move {
+            let _permit = permit;
+            if let Some(created) = after {
+                let _ = created.await;
+            }
+            let runs = wanted.into_iter().map(|i| {
+                run_with_retries(
+                    &http,
+                    &hooks[i],
+                    kind,
+                    &room.room_code,
+                    &body,
+                    attempts,
+                    timeout,
+                )
+            });
+            futures::future::join_all(runs).await;
+        }))
+    }
+}
+
+/// Run one hook, retrying transient failures with backoff.
+async fn run_with_retries(
+    http: &reqwest::Client,
+    hook: &LifecycleHook,
+    kind: &'static str,
+    room_code: &str,
+    body: &[u8],
+    attempts: u32,
+    timeout: Duration,
+) {
+    for attempt in 1..=attempts {
+        let result = match &hook.url {
+            Some(url) => post(http, url, hook.secret.as_deref(), kind, body, timeout).await,
+            None => run_command(&hook.command, kind, room_code, body, timeout).await,
+        };
+        match result {
+            Ok(()) => return,
+            Err(HookFailure::Rejected(reason)) => {
+                tracing::warn!(hook = hook.describe(), event = kind, room = room_code, %reason, "Lifecycle hook rejected the event");
+                return;
+            },
+            Err(HookFailure::Transient(reason)) => {
+                tracing::warn!(hook = hook.describe(), event = kind, room = room_code, attempt, %reason, "Lifecycle hook failed");
+            },
+        }
+        if attempt < attempts {
+            tokio::time::sleep(Duration::from_secs(1 << attempt)).await;
+        }
+    }
+}
+
+async fn post(
+    http: &reqwest::Client,
+    url: &str,
+    secret: Option<&str>,
+    kind: &str,
+    body: &[u8],
+    timeout: Duration,
+) -> Result<(), HookFailure> {
+    let mut request = http
+        .post(url)
+        .timeout(timeout)
+        .header("content-type", "application/json")
+        .header(room_webhooks::EVENT_HEADER, kind)
+        .body(body.to_vec());
+    if let Some(secret) = secret {
+        request = request.header(
+            room_webhooks::SIGNATURE_HEADER,
+            room_webhooks::sign(secret, body),
+        );
+    }
+    match request.send().await {
+        Ok(resp) if resp.status().is_success() => Ok(()),
+        Ok(resp) if resp.status().is_client_error() => {
+            Err(HookFailure::Rejected(resp.status().to_string()))
+        },
+        Ok(resp) => Err(HookFailure::Transient(resp.status().to_string())),
+        Err(e) => Err(HookFailure::Transient(e.to_string())),
+    }
+}
+
+async fn run_command(
+    command: &[String],
+    kind: &str,
+    room_code: &str,
+    body: &[u8],
+    timeout: Duration,
+) -> Result<(), HookFailure> {
+    let Some((program, args)) = command.split_first() else {
+        return Err(HookFailure::Rejected("empty command".to_string()));
+    };
+    let mut child = tokio::process::Command::new(program)
+        .args(args)
+        .env("BREAKPOINT_EVENT", kind)
+        .env("BREAKPOINT_ROOM_CODE", room_code)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| HookFailure::Transient(e.to_string()))?;
+    let run = async {
+        if let Some(mut stdin) = child.stdin.take() {
+            // A command that ignores its input may close stdin early
+            let _ = stdin.write_all(body).await;
+        }
+        child.wait().await
+    };
+    match tokio::time::timeout(timeout, run).await {
+        Ok(Ok(status)) if status.success() => Ok(()),
+        Ok(Ok(status)) => Err(HookFailure::Transient(status.to_string())),
+        Ok(Err(e)) => Err(HookFailure::Transient(e.to_string())),
+        Err(_) => Err(HookFailure::Transient(format!(
+            "timed out after {}s",
+            timeout.as_secs()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command_hook(script: &str) -> LifecycleHook {
+        LifecycleHook {
+            command: vec!["sh".to_string(), "-c".to_string(), script.to_string()],
+            ..LifecycleHook::default()
+        }
+    }
+
+    fn hooks(hooks: Vec<LifecycleHook>) -> LifecycleHooks {
+        let config = LifecycleHooksConfig {
+            hooks,
+            attempts: 2,
+            timeout_secs: 5,
+            ..LifecycleHooksConfig::default()
+        };
+        LifecycleHooks::new(&config, reqwest::Client::new()).unwrap()
+    }
+
+    fn created(code: &str) -> RoomActivity {
+        RoomActivity::RoomCreated {
+            room_code: code.to_string(),
+        }
+    }
+
+    fn closed(code: &str) -> RoomActivity {
+        RoomActivity::RoomClosed {
+            room_code: code.to_string(),
+        }
+    }
+
+    fn metadata(code: &str) -> RoomMetadata {
+        RoomMetadata {
+            alias: Some("FRIDAY-GOLF".to_string()),
+            host: Some("Alice".to_string()),
+            max_players: 8,
+            ..RoomMetadata::bare(code, 1_700_000_000)
+        }
+    }
+
+    async fn wait_for_lines(path: &std::path::Path, lines: usize) -> Vec<serde_json::Value> {
+        for _ in 0..100 {
+            if let Ok(text) = std::fs::read_to_string(path)
+                && text.lines().count() >= lines
+            {
+                return text
+                    .lines()
+                    .map(|l| serde_json::from_str(l).unwrap())
+                    .collect();
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        panic!("hook output never reached {lines} lines");
+    }
+
+    #[test]
+    fn hooks_need_exactly_one_target() {
+        assert!(command_hook("true").validate().is_ok());
+        let url = LifecycleHook {
+            url: Some("https://voice.example.com/rooms".to_string()),
+            ..LifecycleHook::default()
+        };
+        assert!(url.validate().is_ok());
+        assert!(LifecycleHook::default().validate().is_err());
+        let both = LifecycleHook {
+            command: vec!["true".to_string()],
+            ..url.clone()
+        };
+        assert!(both.validate().is_err());
+        let ftp = LifecycleHook {
+            url: Some("ftp://example.com".to_string()),
+            ..LifecycleHook::default()
+        };
+        assert!(ftp.validate().is_err());
+        let unknown = LifecycleHook {
+            events: vec!["game_started".to_string()],
+            ..command_hook("true")
+        };
+        assert!(unknown.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn commands_get_room_metadata_on_open_and_close() {
+        let dir = std::env::temp_dir().join(format!("breakpoint-hooks-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let out = dir.join("events.jsonl");
+        let _ = std::fs::remove_file(&out);
+        let script = format!(
+            "cat >> {path} && echo >> {path} && test \"$BREAKPOINT_ROOM_CODE\" = ABCD-1234",
+            path = out.display()
+        );
+        let mut hooks = hooks(vec![command_hook(&script)]);
+
+        hooks.observe(&created("ABCD-1234"), Some(metadata("ABCD-1234")), 100);
+        assert_eq!(hooks.open_rooms(), 1);
+        hooks.observe(&closed("ABCD-1234"), None, 160);
+        assert_eq!(hooks.open_rooms(), 0);
+
+        let events = wait_for_lines(&out, 2).await;
+        assert_eq!(events[0]["event"], "room_created");
+        assert_eq!(events[0]["alias"], "FRIDAY-GOLF");
+        assert_eq!(events[0]["host"], "Alice");
+        assert!(events[0].get("closed_at").is_none());
+        // Close hooks run after the create hooks, with the same details
+        assert_eq!(events[1]["event"], "room_closed");
+        assert_eq!(events[1]["host"], "Alice");
+        assert_eq!(events[1]["closed_at"], 160);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn failing_commands_are_retried_then_dropped() {
+        let dir = std::env::temp_dir().join(format!("breakpoint-retry-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let out = dir.join("attempts");
+        let _ = std::fs::remove_file(&out);
+        let script = format!("echo '{{}}' >> {} && exit 1", out.display());
+        let mut failing = command_hook(&script);
+        failing.events = vec!["room_created".to_string()];
+        let mut hooks = hooks(vec![failing]);
+
+        hooks.observe(&created("WXYZ-9876"), None, 100);
+        let (_, task) = hooks.open.remove("WXYZ-9876").unwrap();
+        tokio::time::timeout(Duration::from_secs(10), task.unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(wait_for_lines(&out, 2).await.len(), 2, "Tried twice");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
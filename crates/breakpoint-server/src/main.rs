@@ -1,8 +1,8 @@
-use tracing_subscriber::EnvFilter;
-
 use breakpoint_server::config::ServerConfig;
 use breakpoint_server::{
-    build_app, spawn_event_broadcaster, spawn_idle_room_cleanup, spawn_rate_limit_cleanup,
+    build_app, spawn_announcement_broadcaster, spawn_event_broadcaster, spawn_idle_room_cleanup,
+    spawn_presence_notifier, spawn_rate_limit_cleanup, spawn_room_webhook_dispatcher,
+    spawn_schedule_runner,
 };
 
 #[tokio::main]
@@ -11,16 +11,7 @@ async fn main() {
         .map(|v| v.eq_ignore_ascii_case("json"))
         .unwrap_or(false);
 
-    if json_logs {
-        tracing_subscriber::fmt()
-            .with_env_filter(EnvFilter::from_default_env())
-            .json()
-            .init();
-    } else {
-        tracing_subscriber::fmt()
-            .with_env_filter(EnvFilter::from_default_env())
-            .init();
-    }
+    let _telemetry = breakpoint_core::telemetry::init("breakpoint-server", json_logs);
 
     let config = ServerConfig::load();
     config.validate();
@@ -37,13 +28,25 @@ async fn main() {
     // Spawn rate limiter cleanup (removes stale per-IP buckets every 5 minutes)
     spawn_rate_limit_cleanup(state.clone());
 
+    // Spawn game night scheduler (reminders, room opening, webhook notifications)
+    spawn_schedule_runner(state.clone());
+
+    // Spawn room webhook dispatcher (room created, game started, results, closed)
+    spawn_room_webhook_dispatcher(state.clone());
+
+    // Spawn presence notifier (friends see games start and end in shared rooms)
+    spawn_presence_notifier(state.clone());
+
+    // Spawn announcement broadcaster (scheduled announcements starting/ending)
+    spawn_announcement_broadcaster(state.clone());
+
     // Conditionally spawn GitHub Actions poller
     #[cfg(feature = "github-poller")]
     if let Some(ref gh) = state.config.github
         && gh.enabled
     {
         if gh.token.is_some() {
-            spawn_github_poller(&state, gh);
+            register_github_poller(&state, gh);
         } else {
             tracing::warn!(
                 "GitHub poller is enabled but no token is configured; \
@@ -106,9 +109,9 @@ async fn shutdown_signal(token: tokio_util::sync::CancellationToken) {
     token.cancel();
 }
 
-/// Spawn the GitHub Actions polling monitor as a background task.
+/// Register the GitHub Actions polling monitor as an event source.
 #[cfg(feature = "github-poller")]
-fn spawn_github_poller(
+fn register_github_poller(
     state: &breakpoint_server::state::AppState,
     gh: &breakpoint_server::config::GitHubConfig,
 ) {
@@ -119,21 +122,7 @@ fn spawn_github_poller(
         agent_patterns: gh.agent_patterns.clone(),
     };
     let poller = breakpoint_github::GitHubPoller::new(poller_config);
-    let event_store = state.event_store.clone();
-    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
-
-    // Poller task
-    tokio::spawn(async move {
-        poller.run(tx).await;
-    });
-
-    // Relay events from poller into EventStore
-    tokio::spawn(async move {
-        while let Some(event) = rx.recv().await {
-            let mut store = event_store.write().await;
-            store.insert(event);
-        }
-    });
-
-    tracing::info!("GitHub Actions poller started");
+    if let Err(e) = state.event_sources.register(Box::new(poller)) {
+        tracing::error!(error = %e, "Failed to start GitHub Actions poller");
+    }
 }
@@ -0,0 +1,243 @@
+//! Per-room memory accounting. The game loop samples what its session holds
+//! every [`MemoryBudget::sample_interval`], trims whatever is over budget,
+//! and flags rooms whose footprint keeps climbing from round to round.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::Serialize;
+
+/// Rounds of steadily growing baselines before a room is flagged.
+const LEAK_ROUNDS: usize = 4;
+/// How much the last baseline must exceed the first of those rounds.
+const LEAK_GROWTH: f64 = 1.5;
+
+/// Shared handle to a game session's memory accounting. The game loop
+/// writes, the admin API reads.
+pub type SharedRoomMemory = Arc<Mutex<RoomMemoryTracker>>;
+
+/// Sampling and the caps that trigger trimming, from the `[memory]`
+/// config section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryBudget {
+    pub sample_interval: Duration,
+    /// State, wall and trail bytes above which the game drops cosmetic data.
+    pub max_game_bytes: usize,
+    /// Recording bytes above which the match replay is thinned.
+    pub max_replay_bytes: usize,
+}
+
+impl Default for MemoryBudget {
+    fn default() -> Self {
+        Self {
+            sample_interval: Duration::from_secs(5),
+            max_game_bytes: 16 << 20,
+            max_replay_bytes: 64 << 20,
+        }
+    }
+}
+
+impl MemoryBudget {
+    /// Ticks between samples at `tick_rate`, at least one.
+    pub fn sample_every(&self, tick_rate: f32) -> u32 {
+        ((tick_rate * self.sample_interval.as_secs_f32()).round() as u32).max(1)
+    }
+}
+
+/// Bytes one room holds, by where they are held.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct RoomMemory {
+    /// The latest serialized game state.
+    pub state_bytes: usize,
+    pub walls_bytes: usize,
+    pub trails_bytes: usize,
+    /// Messages waiting in players' outbound queues. The game loop cannot
+    /// see these; the room manager fills them in when read.
+    pub backlog_bytes: usize,
+    /// The match recording so far.
+    pub replay_bytes: usize,
+}
+
+impl RoomMemory {
+    /// What the game itself holds, checked against `max_game_bytes`.
+    pub fn game_bytes(&self) -> usize {
+        self.state_bytes + self.walls_bytes + self.trails_bytes
+    }
+
+    pub fn total(&self) -> usize {
+        self.game_bytes() + self.backlog_bytes + self.replay_bytes
+    }
+}
+
+/// What a sample found over budget.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OverBudget {
+    pub game: bool,
+    pub replay: bool,
+}
+
+/// Memory accounting for one room, as shown by the admin API.
+#[derive(Debug, Clone, Serialize)]
+pub struct RoomMemorySnapshot {
+    pub current: RoomMemory,
+    pub peak_bytes: usize,
+    pub samples: u64,
+    /// Times part of the room was trimmed to get back under budget.
+    pub trims: u32,
+    /// Each recent round starts holding more than the last.
+    pub suspected_leak: bool,
+}
+
+/// Running memory accounting for one game session.
+#[derive(Debug, Default)]
+pub struct RoomMemoryTracker {
+    budget: MemoryBudget,
+    current: RoomMemory,
+    peak_bytes: usize,
+    samples: u64,
+    trims: u32,
+    /// Game bytes at the first sample of each recent round, oldest first.
+    /// Rounds rebuild their state, so these should stay level.
+    round_baselines: VecDeque<usize>,
+    baseline_taken: bool,
+    suspected_leak: bool,
+}
+
+impl RoomMemoryTracker {
+    pub fn new(budget: MemoryBudget) -> Self {
+        Self {
+            budget,
+            ..Self::default()
+        }
+    }
+
+    pub fn budget(&self) -> MemoryBudget {
+        self.budget
+    }
+
+    /// A new round started; its first sample is the round's baseline.
+    pub fn begin_round(&mut self) {
+        self.baseline_taken = false;
+    }
+
+    /// Record a sample and report which parts are over budget.
+    pub fn record(&mut self, sample: RoomMemory) -> OverBudget {
+        self.current = sample;
+        self.peak_bytes = self.peak_bytes.max(sample.total());
+        self.samples += 1;
+        if !self.baseline_taken {
+            self.baseline_taken = true;
+            self.record_baseline(sample.game_bytes());
+        }
+        OverBudget {
+            game: sample.game_bytes() > self.budget.max_game_bytes,
+            replay: sample.replay_bytes > self.budget.max_replay_bytes,
+        }
+    }
+
+    /// Part of the room was trimmed, freeing `freed` bytes.
+    pub fn record_trim(&mut self, freed: usize) {
+        self.trims += 1;
+        tracing::debug!(freed, trims = self.trims, "Trimmed room over memory budget");
+    }
+
+    pub fn snapshot(&self) -> RoomMemorySnapshot {
+        RoomMemorySnapshot {
+            current: self.current,
+            peak_bytes: self.peak_bytes,
+            samples: self.samples,
+            trims: self.trims,
+            suspected_leak: self.suspected_leak,
+        }
+    }
+
+    fn record_baseline(&mut self, bytes: usize) {
+        if self.round_baselines.len() == LEAK_ROUNDS {
+            self.round_baselines.pop_front();
+        }
+        self.round_baselines.push_back(bytes);
+
+        let baselines = self.round_baselines.make_contiguous();
+        let growing = baselines.len() == LEAK_ROUNDS
+            && baselines.windows(2).all(|w| w[1] > w[0])
+            && baselines[LEAK_ROUNDS - 1] as f64 >= baselines[0] as f64 * LEAK_GROWTH;
+        if growing && !self.suspected_leak {
+            tracing::warn!(
+                baselines = ?baselines,
+                "Room memory grows every round — possible leak"
+            );
+        }
+        self.suspected_leak = growing;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(state_bytes: usize, replay_bytes: usize) -> RoomMemory {
+        RoomMemory {
+            state_bytes,
+            replay_bytes,
+            ..RoomMemory::default()
+        }
+    }
+
+    #[test]
+    fn samples_over_a_cap_ask_for_trimming() {
+        let mut tracker = RoomMemoryTracker::new(MemoryBudget {
+            max_game_bytes: 100,
+            max_replay_bytes: 1000,
+            ..MemoryBudget::default()
+        });
+        assert_eq!(tracker.record(sample(50, 500)), OverBudget::default());
+        assert_eq!(
+            tracker.record(sample(150, 2000)),
+            OverBudget {
+                game: true,
+                replay: true,
+            }
+        );
+        tracker.record_trim(50);
+        tracker.record(sample(40, 100));
+
+        let snapshot = tracker.snapshot();
+        assert_eq!(snapshot.current.total(), 140);
+        assert_eq!(snapshot.peak_bytes, 2150);
+        assert_eq!((snapshot.samples, snapshot.trims), (3, 1));
+    }
+
+    #[test]
+    fn baselines_growing_every_round_flag_a_leak() {
+        let mut tracker = RoomMemoryTracker::default();
+        for bytes in [100, 100, 110, 90] {
+            tracker.begin_round();
+            tracker.record(sample(bytes, 0));
+            // Growth within a round is expected
+            tracker.record(sample(bytes * 10, 0));
+        }
+        assert!(!tracker.snapshot().suspected_leak);
+
+        for bytes in [120, 150, 200] {
+            tracker.begin_round();
+            tracker.record(sample(bytes, 0));
+        }
+        assert!(tracker.snapshot().suspected_leak);
+
+        tracker.begin_round();
+        tracker.record(sample(100, 0));
+        assert!(!tracker.snapshot().suspected_leak);
+    }
+
+    #[test]
+    fn sampling_is_at_least_every_tick() {
+        let budget = MemoryBudget::default();
+        assert_eq!(budget.sample_every(20.0), 100);
+        let budget = MemoryBudget {
+            sample_interval: Duration::ZERO,
+            ..budget
+        };
+        assert_eq!(budget.sample_every(20.0), 1);
+    }
+}
@@ -0,0 +1,449 @@
+//! Bans for public instances, and an audit trail of moderation actions.
+//!
+//! A ban names a player identity (friend ID) or a hashed client IP, with
+//! a reason and an optional expiry. Joins, reconnects, and transport
+//! migrations are refused while one applies. Bans are written to
+//! `[moderation] ban_file` when set, so they survive restarts; raw IPs are
+//! never stored, only a salted hash. Persisted bans need a configured salt;
+//! bans kept in memory fall back to a random one. Every ban and unban is logged and
+//! kept in a bounded audit trail, also appended to `audit_file` when set.
+
+use std::collections::VecDeque;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use breakpoint_core::player::PlayerIdentity;
+
+use crate::config::ModerationConfig;
+
+/// Header naming the moderator behind an admin request, for the audit
+/// trail.
+pub const MODERATOR_HEADER: &str = "x-moderator";
+
+/// Who a ban applies to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BanTarget {
+    Identity(PlayerIdentity),
+    /// Hex SHA-256 of the salt and the client IP.
+    IpHash(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Ban {
+    pub id: String,
+    pub target: BanTarget,
+    pub reason: String,
+    pub created_at: u64,
+    /// Unix seconds after which the ban lapses; `None` is permanent.
+    pub expires_at: Option<u64>,
+    pub moderator: Option<String>,
+}
+
+impl Ban {
+    pub fn is_active(&self, now: u64) -> bool {
+        self.expires_at.is_none_or(|t| now < t)
+    }
+}
+
+/// Body of `POST /api/v1/admin/bans`. Exactly one of `identity`, `ip`,
+/// and `ip_hash` names the target.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BanRequest {
+    /// Friend ID.
+    pub identity: Option<String>,
+    /// Raw client IP, hashed before it is stored.
+    pub ip: Option<IpAddr>,
+    pub ip_hash: Option<String>,
+    pub reason: String,
+    /// How long the ban lasts; permanent when absent.
+    pub duration_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Ban,
+    Unban,
+    /// A join, reconnect, or migration refused by a ban.
+    Refused,
+}
+
+/// One moderation action, as logged.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuditEntry {
+    pub at: u64,
+    pub action: AuditAction,
+    pub ban_id: String,
+    pub target: BanTarget,
+    pub reason: String,
+    pub moderator: Option<String>,
+}
+
+/// On-disk form of the ban file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BanFile {
+    next_id: u64,
+    bans: Vec<Ban>,
+}
+
+/// Active and timed bans, bounded, with the recent audit trail.
+#[derive(Debug)]
+pub struct BanStore {
+    bans: Vec<Ban>,
+    next_id: u64,
+    max_bans: usize,
+    salt: String,
+    ban_file: Option<PathBuf>,
+    audit_file: Option<PathBuf>,
+    audit: VecDeque<AuditEntry>,
+    max_audit: usize,
+}
+
+impl BanStore {
+    /// Create a store, loading `ban_file` if it exists. An unreadable
+    /// file is logged and the store starts empty.
+    pub fn new(config: &ModerationConfig) -> Self {
+        let mut store = Self {
+            bans: Vec::new(),
+            next_id: 1,
+            max_bans: config.max_bans,
+            salt: if config.ip_hash_salt.is_empty() {
+                hex::encode(rand::random::<[u8; 32]>())
+            } else {
+                config.ip_hash_salt.clone()
+            },
+            ban_file: config.ban_file.as_ref().map(PathBuf::from),
+            audit_file: config.audit_file.as_ref().map(PathBuf::from),
+            audit: VecDeque::new(),
+            max_audit: config.audit_entries,
+        };
+        if let Some(path) = &store.ban_file
+            && path.exists()
+        {
+            match load(path) {
+                Ok(file) => {
+                    tracing::info!(path = %path.display(), bans = file.bans.len(), "Loaded ban list");
+                    store.next_id = file.next_id.max(1);
+                    store.bans = file.bans;
+                },
+                Err(e) => {
+                    tracing::error!(path = %path.display(), error = %e, "Failed to load ban list");
+                },
+            }
+        }
+        store
+    }
+
+    /// Salted hash of a client IP, as stored in IP bans.
+    pub fn hash_ip(&self, ip: IpAddr) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.salt.as_bytes());
+        hasher.update(ip.to_string().as_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    /// The ban keeping this identity or IP out, if any.
+    pub fn check(&self, identity: Option<&PlayerIdentity>, ip: IpAddr, now: u64) -> Option<&Ban> {
+        let ip_hash = self.hash_ip(ip);
+        self.bans.iter().find(|ban| {
+            ban.is_active(now)
+                && match &ban.target {
+                    BanTarget::Identity(id) => identity == Some(id),
+                    BanTarget::IpHash(hash) => *hash == ip_hash,
+                }
+        })
+    }
+
+    /// Record that `ban` refused a connection.
+    pub fn note_refused(&mut self, ban: &Ban, now: u64) {
+        let ban = ban.clone();
+        self.record(AuditEntry {
+            at: now,
+            action: AuditAction::Refused,
+            ban_id: ban.id,
+            target: ban.target,
+            reason: ban.reason,
+            moderator: None,
+        });
+    }
+
+    pub fn add(
+        &mut self,
+        request: BanRequest,
+        moderator: Option<String>,
+        now: u64,
+    ) -> Result<Ban, String> {
+        let target = match (request.identity, request.ip, request.ip_hash) {
+            (Some(id), None, None) => BanTarget::Identity(
+                PlayerIdentity::parse(id.trim())
+                    .ok_or_else(|| format!("`{id}` is not a friend ID"))?,
+            ),
+            (None, Some(ip), None) => BanTarget::IpHash(self.hash_ip(ip)),
+            (None, None, Some(hash)) => {
+                let hash = hash.trim().to_ascii_lowercase();
+                if hash.len() != 64 || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+                    return Err("ip_hash must be 64 hex digits".to_string());
+                }
+                BanTarget::IpHash(hash)
+            },
+            _ => return Err("give exactly one of identity, ip, or ip_hash".to_string()),
+        };
+        let reason = request.reason.trim();
+        if reason.is_empty() {
+            return Err("reason must not be empty".to_string());
+        }
+        if request.duration_secs == Some(0) {
+            return Err("duration_secs must be > 0".to_string());
+        }
+        self.bans.retain(|b| b.is_active(now));
+        if self.bans.len() >= self.max_bans {
+            return Err(format!("at most {} bans", self.max_bans));
+        }
+        let ban = Ban {
+            id: format!("ban-{}", self.next_id),
+            target,
+            reason: reason.to_string(),
+            created_at: now,
+            expires_at: request.duration_secs.map(|d| now.saturating_add(d)),
+            moderator,
+        };
+        self.next_id += 1;
+        self.bans.push(ban.clone());
+        self.save();
+        self.record(AuditEntry {
+            at: now,
+            action: AuditAction::Ban,
+            ban_id: ban.id.clone(),
+            target: ban.target.clone(),
+            reason: ban.reason.clone(),
+            moderator: ban.moderator.clone(),
+        });
+        Ok(ban)
+    }
+
+    /// Lift a ban. Returns false if no ban has that ID.
+    pub fn remove(&mut self, id: &str, moderator: Option<String>, now: u64) -> bool {
+        let Some(index) = self.bans.iter().position(|b| b.id == id) else {
+            return false;
+        };
+        let ban = self.bans.remove(index);
+        self.save();
+        self.record(AuditEntry {
+            at: now,
+            action: AuditAction::Unban,
+            ban_id: ban.id,
+            target: ban.target,
+            reason: ban.reason,
+            moderator,
+        });
+        true
+    }
+
+    /// Bans still in force, oldest first.
+    pub fn active(&self, now: u64) -> Vec<Ban> {
+        self.bans
+            .iter()
+            .filter(|b| b.is_active(now))
+            .cloned()
+            .collect()
+    }
+
+    /// Recent moderation actions, newest first.
+    pub fn audit(&self) -> Vec<AuditEntry> {
+        self.audit.iter().rev().cloned().collect()
+    }
+
+    fn record(&mut self, entry: AuditEntry) {
+        tracing::info!(
+            action = ?entry.action,
+            ban_id = %entry.ban_id,
+            target = ?entry.target,
+            reason = %entry.reason,
+            moderator = entry.moderator.as_deref().unwrap_or("-"),
+            "Moderation action"
+        );
+        if let Some(path) = &self.audit_file
+            && let Err(e) = append_line(path, &entry)
+        {
+            tracing::warn!(path = %path.display(), error = %e, "Failed to write audit log");
+        }
+        if self.audit.len() >= self.max_audit {
+            self.audit.pop_front();
+        }
+        self.audit.push_back(entry);
+    }
+
+    /// Write the ban list through a temporary file, so a crash mid-write
+    /// never leaves a truncated list behind.
+    fn save(&self) {
+        let Some(path) = &self.ban_file else {
+            return;
+        };
+        let file = BanFile {
+            next_id: self.next_id,
+            bans: self.bans.clone(),
+        };
+        let result = serde_json::to_vec_pretty(&file)
+            .map_err(std::io::Error::other)
+            .and_then(|data| {
+                let tmp = path.with_extension("tmp");
+                std::fs::write(&tmp, data)?;
+                std::fs::rename(&tmp, path)
+            });
+        if let Err(e) = result {
+            tracing::error!(path = %path.display(), error = %e, "Failed to save ban list");
+        }
+    }
+}
+
+fn load(path: &Path) -> std::io::Result<BanFile> {
+    let data = std::fs::read(path)?;
+    serde_json::from_slice(&data).map_err(std::io::Error::other)
+}
+
+fn append_line(path: &Path, entry: &AuditEntry) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut line = serde_json::to_vec(entry).map_err(std::io::Error::other)?;
+    line.push(b'\n');
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(&line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(last: u8) -> IpAddr {
+        IpAddr::from([203, 0, 113, last])
+    }
+
+    fn friend(n: u8) -> PlayerIdentity {
+        PlayerIdentity::from_digest(&[n; 6])
+    }
+
+    fn ban_identity(n: u8, duration_secs: Option<u64>) -> BanRequest {
+        BanRequest {
+            identity: Some(friend(n).to_string()),
+            reason: "griefing".to_string(),
+            duration_secs,
+            ..BanRequest::default()
+        }
+    }
+
+    #[test]
+    fn bans_match_identity_or_ip_until_they_expire() {
+        let mut store = BanStore::new(&ModerationConfig::default());
+        let timed = store.add(ban_identity(1, Some(60)), None, 1000).unwrap();
+        store
+            .add(
+                BanRequest {
+                    ip: Some(ip(7)),
+                    reason: "spam".to_string(),
+                    ..BanRequest::default()
+                },
+                Some("ops".to_string()),
+                1000,
+            )
+            .unwrap();
+
+        assert_eq!(
+            store.check(Some(&friend(1)), ip(1), 1059).unwrap().id,
+            timed.id
+        );
+        assert!(
+            store.check(Some(&friend(1)), ip(1), 1060).is_none(),
+            "Expired"
+        );
+        assert!(store.check(Some(&friend(2)), ip(1), 1000).is_none());
+        assert!(
+            store.check(None, ip(7), 5000).is_some(),
+            "IP bans are permanent"
+        );
+        assert_eq!(store.active(1060).len(), 1);
+    }
+
+    #[test]
+    fn requests_need_one_target_and_a_reason() {
+        let mut store = BanStore::new(&ModerationConfig::default());
+        let both = BanRequest {
+            ip: Some(ip(1)),
+            ..ban_identity(1, None)
+        };
+        assert!(store.add(both, None, 0).is_err());
+        let no_reason = BanRequest {
+            reason: " ".to_string(),
+            ..ban_identity(1, None)
+        };
+        assert!(store.add(no_reason, None, 0).is_err());
+        let bad_hash = BanRequest {
+            ip_hash: Some("abc".to_string()),
+            reason: "spam".to_string(),
+            ..BanRequest::default()
+        };
+        assert!(store.add(bad_hash, None, 0).is_err());
+        assert!(store.active(0).is_empty());
+        assert!(store.audit().is_empty());
+    }
+
+    #[test]
+    fn unconfigured_salt_is_random() {
+        let ip = IpAddr::from([192, 168, 1, 20]);
+        let a = BanStore::new(&ModerationConfig::default());
+        let b = BanStore::new(&ModerationConfig::default());
+        assert_ne!(a.hash_ip(ip), b.hash_ip(ip));
+        let unsalted = hex::encode(Sha256::digest(ip.to_string().as_bytes()));
+        assert_ne!(a.hash_ip(ip), unsalted);
+
+        let config = ModerationConfig {
+            ip_hash_salt: "pepper".to_string(),
+            ..ModerationConfig::default()
+        };
+        assert_eq!(
+            BanStore::new(&config).hash_ip(ip),
+            BanStore::new(&config).hash_ip(ip)
+        );
+    }
+
+    #[test]
+    fn bans_persist_and_actions_are_audited() {
+        let dir = std::env::temp_dir().join(format!("breakpoint-bans-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config = ModerationConfig {
+            ban_file: Some(dir.join("bans.json").display().to_string()),
+            audit_file: Some(dir.join("audit.jsonl").display().to_string()),
+            ..ModerationConfig::default()
+        };
+
+        let mut store = BanStore::new(&config);
+        let kept = store.add(ban_identity(1, None), None, 10).unwrap();
+        let lifted = store.add(ban_identity(2, None), None, 10).unwrap();
+        assert!(store.remove(&lifted.id, Some("ops".to_string()), 20));
+        assert!(!store.remove(&lifted.id, None, 20));
+
+        let reloaded = BanStore::new(&config);
+        assert_eq!(reloaded.active(30), vec![kept]);
+        let actions: Vec<AuditAction> = store.audit().iter().map(|e| e.action).collect();
+        assert_eq!(
+            actions,
+            [AuditAction::Unban, AuditAction::Ban, AuditAction::Ban]
+        );
+        let log = std::fs::read_to_string(dir.join("audit.jsonl")).unwrap();
+        assert_eq!(log.lines().count(), 3);
+
+        // IDs keep counting across restarts
+        let mut reloaded = reloaded;
+        assert_eq!(
+            reloaded.add(ban_identity(3, None), None, 40).unwrap().id,
+            "ban-3"
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
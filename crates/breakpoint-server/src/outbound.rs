@@ -0,0 +1,335 @@
+//! Per-connection outbound queues with priority lanes.
+//!
+//! Every message is tagged with a [`Lane`]. Delivery stays in queue order;
+//! the lanes only decide what is shed when a slow client's queue is full.
+//! A message may evict the oldest message of a less important lane, so
+//! chat and state snapshots are dropped before round results, and round
+//! results before control messages and alerts.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use bytes::Bytes;
+use futures::Stream;
+
+use breakpoint_core::net::messages::MessageType;
+
+/// Outbound priority, most important first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Lane {
+    /// Join and room management, configuration, and alerts.
+    Control,
+    /// Round and game results, round phase changes, and course data,
+    /// which is sent once and never superseded by a later snapshot.
+    Results,
+    /// Game state snapshots and private state. A newer snapshot supersedes
+    /// a shed one.
+    State,
+    /// Chat, social updates, and the lobby toy.
+    Cosmetic,
+}
+
+impl Lane {
+    pub const COUNT: usize = 4;
+
+    /// Lane for an encoded server message, by its type byte. Unknown or
+    /// empty messages go in the control lane so they are never shed first.
+    pub fn of(data: &[u8]) -> Self {
+        match data.first().copied().and_then(MessageType::from_byte) {
+            Some(
+                MessageType::RoundEnd
+                | MessageType::GameEnd
+                | MessageType::RoundPhase
+                | MessageType::CourseUpdate,
+            ) => Self::Results,
+            Some(MessageType::GameState | MessageType::PrivateState) => Self::State,
+            Some(
+                MessageType::ChatMessage
+                | MessageType::SocialUpdate
+                | MessageType::LobbyToyState
+                | MessageType::GameEffects,
+            ) => Self::Cosmetic,
+            _ => Self::Control,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Why [`PlayerSender::try_send`] refused a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrySendError {
+    /// The queue is full of messages at least as important.
+    Full,
+    /// The connection's receiver is gone.
+    Closed,
+}
+
+impl fmt::Display for TrySendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Full => write!(f, "outbound queue full"),
+            Self::Closed => write!(f, "outbound queue closed"),
+        }
+    }
+}
+
+impl std::error::Error for TrySendError {}
+
+struct Queue {
+    /// Messages per lane, tagged with their position in send order.
+    lanes: [VecDeque<(u64, Bytes)>; Lane::COUNT],
+    len: usize,
+    capacity: usize,
+    next_seq: u64,
+    senders: usize,
+    receiver_alive: bool,
+    waker: Option<Waker>,
+    shed: u64,
+}
+
+impl Queue {
+    /// Pop the message that was sent first, whichever lane it is in.
+    fn pop(&mut self) -> Option<Bytes> {
+        let lane = self
+            .lanes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, q)| q.front().map(|(seq, _)| (*seq, i)))
+            .min()?
+            .1;
+        self.len -= 1;
+        self.lanes[lane].pop_front().map(|(_, data)| data)
+    }
+
+    /// Drop the oldest message of the least important lane below `lane`.
+    fn shed_below(&mut self, lane: Lane) -> bool {
+        let Some(victim) = self.lanes[lane.index() + 1..]
+            .iter_mut()
+            .rev()
+            .find(|q| !q.is_empty())
+        else {
+            return false;
+        };
+        victim.pop_front();
+        self.len -= 1;
+        self.shed += 1;
+        true
+    }
+
+    fn wake(&mut self) {
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+fn lock(queue: &Mutex<Queue>) -> std::sync::MutexGuard<'_, Queue> {
+    queue.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Create a queue holding up to `capacity` messages across all lanes.
+pub fn channel(capacity: usize) -> (PlayerSender, OutboundReceiver) {
+    let queue = Arc::new(Mutex::new(Queue {
+        lanes: Default::default(),
+        len: 0,
+        capacity: capacity.max(1),
+        next_seq: 0,
+        senders: 1,
+        receiver_alive: true,
+        waker: None,
+        shed: 0,
+    }));
+    (
+        PlayerSender {
+            queue: Arc::clone(&queue),
+        },
+        OutboundReceiver { queue },
+    )
+}
+
+/// Sending half of a connection's outbound queue. Cheap to clone; the
+/// receiver sees the end of the stream once every sender is dropped.
+pub struct PlayerSender {
+    queue: Arc<Mutex<Queue>>,
+}
+
+impl PlayerSender {
+    /// Queue `data` on `lane` without waiting. When the queue is full, the
+    /// oldest message of a less important lane is shed to make room.
+    pub fn try_send(&self, lane: Lane, data: Bytes) -> Result<(), TrySendError> {
+        let mut queue = lock(&self.queue);
+        if !queue.receiver_alive {
+            return Err(TrySendError::Closed);
+        }
+        if queue.len >= queue.capacity && !queue.shed_below(lane) {
+            return Err(TrySendError::Full);
+        }
+        let seq = queue.next_seq;
+        queue.next_seq += 1;
+        queue.lanes[lane.index()].push_back((seq, data));
+        queue.len += 1;
+        queue.wake();
+        Ok(())
+    }
+
+    /// Bytes waiting to be sent.
+    pub fn queued_bytes(&self) -> usize {
+        lock(&self.queue)
+            .lanes
+            .iter()
+            .flatten()
+            .map(|(_, data)| data.len())
+            .sum()
+    }
+
+    /// Messages shed from this queue so far.
+    pub fn shed_count(&self) -> u64 {
+        lock(&self.queue).shed
+    }
+}
+
+impl Clone for PlayerSender {
+    fn clone(&self) -> Self {
+        lock(&self.queue).senders += 1;
+        Self {
+            queue: Arc::clone(&self.queue),
+        }
+    }
+}
+
+impl Drop for PlayerSender {
+    fn drop(&mut self) {
+        let mut queue = lock(&self.queue);
+        queue.senders -= 1;
+        if queue.senders == 0 {
+            queue.wake();
+        }
+    }
+}
+
+impl fmt::Debug for PlayerSender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PlayerSender").finish_non_exhaustive()
+    }
+}
+
+/// Receiving half of a connection's outbound queue, yielding messages in
+/// the order they were sent.
+pub struct OutboundReceiver {
+    queue: Arc<Mutex<Queue>>,
+}
+
+impl OutboundReceiver {
+    /// Wait for the next message. `None` once the queue is empty and every
+    /// sender has been dropped.
+    pub async fn recv(&mut self) -> Option<Bytes> {
+        std::future::poll_fn(|cx| self.poll_recv(cx)).await
+    }
+
+    /// Take the next message if one is queued.
+    pub fn try_recv(&mut self) -> Option<Bytes> {
+        lock(&self.queue).pop()
+    }
+
+    fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<Bytes>> {
+        let mut queue = lock(&self.queue);
+        if let Some(data) = queue.pop() {
+            return Poll::Ready(Some(data));
+        }
+        if queue.senders == 0 {
+            return Poll::Ready(None);
+        }
+        queue.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl Stream for OutboundReceiver {
+    type Item = Bytes;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Bytes>> {
+        self.get_mut().poll_recv(cx)
+    }
+}
+
+impl Drop for OutboundReceiver {
+    fn drop(&mut self) {
+        let mut queue = lock(&self.queue);
+        queue.receiver_alive = false;
+        queue.lanes.iter_mut().for_each(VecDeque::clear);
+        queue.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(tag: u8) -> Bytes {
+        Bytes::from(vec![tag])
+    }
+
+    #[test]
+    fn lanes_follow_message_type() {
+        assert_eq!(Lane::of(&[MessageType::GameState as u8]), Lane::State);
+        assert_eq!(Lane::of(&[MessageType::RoundEnd as u8]), Lane::Results);
+        assert_eq!(Lane::of(&[MessageType::AlertEvent as u8]), Lane::Control);
+        assert_eq!(Lane::of(&[MessageType::ChatMessage as u8]), Lane::Cosmetic);
+        assert_eq!(Lane::of(&[MessageType::GameEffects as u8]), Lane::Cosmetic);
+        assert_eq!(Lane::of(&[MessageType::PrivateState as u8]), Lane::State);
+        assert_eq!(
+            Lane::of(&[MessageType::CourseUpdate as u8]),
+            Lane::Results,
+            "Course data is sent once, so it must not be shed like a snapshot"
+        );
+        assert_eq!(Lane::of(&[]), Lane::Control);
+    }
+
+    #[test]
+    fn full_queue_sheds_least_important_lane_first() {
+        let (tx, mut rx) = channel(3);
+        tx.try_send(Lane::State, msg(1)).unwrap();
+        tx.try_send(Lane::Cosmetic, msg(2)).unwrap();
+        tx.try_send(Lane::State, msg(3)).unwrap();
+
+        tx.try_send(Lane::Control, msg(4)).unwrap();
+        tx.try_send(Lane::Results, msg(5)).unwrap();
+        assert_eq!(
+            tx.try_send(Lane::State, msg(6)),
+            Err(TrySendError::Full),
+            "Nothing less important left to shed"
+        );
+        assert_eq!(tx.shed_count(), 2);
+
+        // Survivors come out in send order
+        let received: Vec<_> = std::iter::from_fn(|| rx.try_recv()).collect();
+        assert_eq!(received, vec![msg(3), msg(4), msg(5)]);
+    }
+
+    #[tokio::test]
+    async fn receiver_ends_when_senders_drop() {
+        let (tx, mut rx) = channel(4);
+        let tx2 = tx.clone();
+        tx.try_send(Lane::Control, msg(1)).unwrap();
+        drop(tx);
+        drop(tx2);
+        assert_eq!(rx.recv().await, Some(msg(1)));
+        assert_eq!(rx.recv().await, None);
+    }
+
+    #[test]
+    fn send_fails_after_receiver_drops() {
+        let (tx, rx) = channel(4);
+        drop(rx);
+        assert_eq!(
+            tx.try_send(Lane::Control, msg(1)),
+            Err(TrySendError::Closed)
+        );
+    }
+}
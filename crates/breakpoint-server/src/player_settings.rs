@@ -0,0 +1,290 @@
+//! Cross-device settings sync, keyed by `PlayerIdentity` (the public
+//! friend ID).
+//!
+//! Reads and writes must carry the identity secret the friend ID is
+//! derived from in the `X-Identity-Key` header, so a friend ID alone does
+//! not expose or overwrite anyone's settings. Held in memory and bounded;
+//! uploads also count against the `[content]` limits.
+
+use std::collections::HashMap;
+
+use axum::extract::{Path, State};
+use axum::http::HeaderMap;
+use axum::response::Json;
+
+use breakpoint_core::player::PlayerIdentity;
+use breakpoint_core::settings_sync::SyncedSettings;
+use breakpoint_core::tutorial::TutorialProgress;
+
+use crate::config::SettingsSyncConfig;
+use crate::content::{self, ContentItem, ContentKind};
+use crate::error::AppError;
+use crate::social::player_identity;
+use crate::state::AppState;
+
+/// Header carrying the player's identity secret.
+pub const IDENTITY_HEADER: &str = "x-identity-key";
+
+#[derive(Debug)]
+struct StoredSettings {
+    settings: SyncedSettings,
+    last_access: u64,
+    /// Serialized size, for the content limits.
+    bytes: usize,
+}
+
+/// Synced settings for every player who has uploaded some. Bounded: the
+/// least recently accessed player is evicted when full.
+#[derive(Debug)]
+pub struct PlayerSettingsStore {
+    players: HashMap<PlayerIdentity, StoredSettings>,
+    max_players: usize,
+}
+
+impl PlayerSettingsStore {
+    pub fn new(config: &SettingsSyncConfig) -> Self {
+        Self {
+            players: HashMap::new(),
+            max_players: config.max_players,
+        }
+    }
+
+    pub fn get(&mut self, id: &PlayerIdentity, now: u64) -> Option<SyncedSettings> {
+        let stored = self.players.get_mut(id)?;
+        stored.last_access = now;
+        Some(stored.settings.clone())
+    }
+
+    /// What merging `upload` would store, without storing it.
+    pub fn preview(
+        &self,
+        id: &PlayerIdentity,
+        upload: &SyncedSettings,
+    ) -> Result<SyncedSettings, String> {
+        upload.validate()?;
+        let Some(stored) = self.players.get(id) else {
+            return Ok(upload.clone());
+        };
+        let mut merged = stored.settings.clone();
+        merged.merge(upload.clone());
+        Ok(merged)
+    }
+
+    /// Merge an upload into the stored settings and return the result,
+    /// which the client adopts. The first upload is stored as-is, even from
+    /// a device that never stamped a change.
+    pub fn merge(
+        &mut self,
+        id: &PlayerIdentity,
+        upload: SyncedSettings,
+        now: u64,
+    ) -> Result<SyncedSettings, String> {
+        upload.validate()?;
+        let Some(stored) = self.players.get_mut(id) else {
+            if self.players.len() >= self.max_players {
+                self.evict_oldest();
+            }
+            self.players.insert(
+                id.clone(),
+                StoredSettings {
+                    bytes: content::json_size(&upload),
+                    settings: upload.clone(),
+                    last_access: now,
+                },
+            );
+            return Ok(upload);
+        };
+        stored.settings.merge(upload);
+        stored.last_access = now;
+        stored.bytes = content::json_size(&stored.settings);
+        Ok(stored.settings.clone())
+    }
+
+    /// The player's tutorial progress; empty if they have none stored.
+    pub fn tutorial(&self, id: &PlayerIdentity) -> TutorialProgress {
+        self.players
+            .get(id)
+            .map(|stored| stored.settings.tutorial.clone())
+            .unwrap_or_default()
+    }
+
+    /// Record a tutorial objective the player completed, storing settings
+    /// for them if they have none yet. Returns whether it was new.
+    pub fn record_tutorial(
+        &mut self,
+        id: &PlayerIdentity,
+        game: &str,
+        objective: &str,
+        now: u64,
+    ) -> bool {
+        if !self.players.contains_key(id) && self.players.len() >= self.max_players {
+            self.evict_oldest();
+        }
+        let stored = self
+            .players
+            .entry(id.clone())
+            .or_insert_with(|| StoredSettings {
+                settings: SyncedSettings::default(),
+                last_access: now,
+                bytes: 0,
+            });
+        stored.last_access = now;
+        let new = stored.settings.tutorial.complete(game, objective);
+        stored.bytes = content::json_size(&stored.settings);
+        new
+    }
+
+    pub fn remove(&mut self, id: &PlayerIdentity) -> bool {
+        self.players.remove(id).is_some()
+    }
+
+    /// Every player's stored settings, as content items.
+    pub fn items(&self) -> Vec<ContentItem> {
+        self.players
+            .iter()
+            .map(|(id, stored)| ContentItem {
+                kind: ContentKind::PlayerSettings,
+                id: id.to_string(),
+                owner: Some(id.clone()),
+                bytes: stored.bytes,
+                room_code: None,
+            })
+            .collect()
+    }
+
+    fn evict_oldest(&mut self) {
+        if let Some(id) = self
+            .players
+            .iter()
+            .min_by_key(|(_, s)| s.last_access)
+            .map(|(id, _)| id.clone())
+        {
+            self.players.remove(&id);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.players.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.players.is_empty()
+    }
+}
+
+/// Check that the request's identity secret belongs to friend ID `id`,
+/// and return that identity.
+fn authorize(state: &AppState, headers: &HeaderMap, id: &str) -> Result<PlayerIdentity, AppError> {
+    if !state.config.settings_sync.enabled {
+        return Err(AppError::NotFound("Settings sync is disabled".to_string()));
+    }
+    let key = headers
+        .get(IDENTITY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    player_identity(key)
+        .filter(|identity| identity.as_str() == id)
+        .ok_or_else(|| AppError::Unauthorized("Identity key does not match player".to_string()))
+}
+
+/// GET /api/v1/players/{id}/settings — a player's synced settings.
+pub async fn get_settings(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<SyncedSettings>, AppError> {
+    let identity = authorize(&state, &headers, &id)?;
+    let now = breakpoint_core::time::unix_now();
+    state
+        .player_settings
+        .write()
+        .await
+        .get(&identity, now)
+        .map(Json)
+        .ok_or_else(|| AppError::NotFound("No synced settings".to_string()))
+}
+
+/// PUT /api/v1/players/{id}/settings — merge this device's settings into
+/// the synced copy and return the merged result. Refused if the merged
+/// copy would break the content limits.
+pub async fn put_settings(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    Json(upload): Json<SyncedSettings>,
+) -> Result<Json<SyncedSettings>, AppError> {
+    let identity = authorize(&state, &headers, &id)?;
+    // Checked and stored under one guard so concurrent uploads can't
+    // overshoot the quota together
+    let mut settings = state.player_settings.write().await;
+    let merged = settings
+        .preview(&identity, &upload)
+        .map_err(AppError::BadRequest)?;
+    let candidate = ContentItem {
+        kind: ContentKind::PlayerSettings,
+        id: identity.to_string(),
+        owner: Some(identity.clone()),
+        bytes: content::json_size(&merged),
+        room_code: None,
+    };
+    content::check_upload(&state, &settings, &candidate).await?;
+    let now = breakpoint_core::time::unix_now();
+    settings
+        .merge(&identity, upload, now)
+        .map(Json)
+        .map_err(AppError::BadRequest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(n: u8) -> PlayerIdentity {
+        PlayerIdentity::from_digest(&[n; 6])
+    }
+
+    fn upload(updated_at: u64) -> SyncedSettings {
+        SyncedSettings {
+            updated_at,
+            values: [("audio_muted".to_string(), serde_json::json!("true"))].into(),
+            keybinds: Default::default(),
+            tutorial: Default::default(),
+        }
+    }
+
+    #[test]
+    fn merge_returns_the_newest_settings() {
+        let mut store = PlayerSettingsStore::new(&SettingsSyncConfig::default());
+        assert!(store.get(&id(1), 1).is_none());
+        assert_eq!(store.merge(&id(1), upload(0), 1).unwrap(), upload(0));
+        store.merge(&id(1), upload(200), 1).unwrap();
+        let merged = store.merge(&id(1), SyncedSettings::default(), 2).unwrap();
+        assert_eq!(merged, upload(200), "A fresh device adopts the synced copy");
+        assert_eq!(store.get(&id(1), 3), Some(upload(200)));
+    }
+
+    #[test]
+    fn evicts_least_recently_accessed_player() {
+        let mut store = PlayerSettingsStore::new(&SettingsSyncConfig {
+            max_players: 2,
+            ..SettingsSyncConfig::default()
+        });
+        store.merge(&id(1), upload(1), 1).unwrap();
+        store.merge(&id(2), upload(1), 2).unwrap();
+        store.get(&id(1), 3);
+        store.merge(&id(3), upload(1), 4).unwrap();
+        assert_eq!(store.len(), 2);
+        assert!(store.get(&id(2), 5).is_none());
+        assert!(store.get(&id(1), 5).is_some());
+    }
+
+    #[test]
+    fn tutorial_progress_is_recorded_and_kept_through_uploads() {
+        let mut store = PlayerSettingsStore::new(&SettingsSyncConfig::default());
+        assert!(store.record_tutorial(&id(1), "tron", "survive", 1));
+        assert!(!store.record_tutorial(&id(1), "tron", "survive", 2));
+        store.merge(&id(1), upload(10), 3).unwrap();
+        assert!(store.tutorial(&id(1)).is_done("tron", "survive"));
+        assert!(store.tutorial(&id(2)).completed.is_empty());
+    }
+}
@@ -0,0 +1,463 @@
+//! Headless referee: re-simulate a match from its input log.
+//!
+//! `POST /api/v1/referee/simulate` runs the same game code as a live room,
+//! without clients, and reports the scores and key events. Used to verify
+//! daily-challenge submissions and replays, and to adjudicate external
+//! tournaments.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::State;
+use axum::response::Json;
+use serde::{Deserialize, Serialize};
+
+use breakpoint_core::bot::{BotDifficulty, BotDriver};
+use breakpoint_core::game_trait::{
+    BreakpointGame, GameConfig, GameEvent, GameId, PlayerId, PlayerInputs, PlayerScore,
+};
+use breakpoint_core::input_replay::SEED_SETTING;
+use breakpoint_core::player::{Player, PlayerColor};
+
+use crate::config::RefereeConfig;
+use crate::error::AppError;
+use crate::game_loop::ServerGameRegistry;
+use crate::state::AppState;
+
+/// A match to re-simulate.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SimulateRequest {
+    /// Wire-format game ID (`mini-golf`, `platform-racer`, `laser-tag`, `tron`).
+    pub game: String,
+    pub players: Vec<RefereePlayer>,
+    /// Rounds to play; 0 uses the game's default.
+    #[serde(default)]
+    pub round_count: u8,
+    /// Round time limit in seconds, as in the room config.
+    #[serde(default = "default_round_duration_secs")]
+    pub round_duration_secs: u64,
+    /// Game settings, as sent in `RequestGameStart`. Without a `seed`, the
+    /// simulation picks one and reports it in the response.
+    #[serde(default)]
+    pub custom: HashMap<String, serde_json::Value>,
+    pub inputs: Vec<InputRecord>,
+}
+
+fn default_round_duration_secs() -> u64 {
+    90
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RefereePlayer {
+    pub id: PlayerId,
+    #[serde(default)]
+    pub name: String,
+    /// Bots generate their inputs during the simulation, as in a live room.
+    #[serde(default)]
+    pub is_bot: bool,
+    #[serde(default)]
+    pub bot_difficulty: BotDifficulty,
+}
+
+/// One player input. `tick` counts simulation steps from 1 at the start of
+/// each round; the input is applied before that step runs.
+#[derive(Debug, Clone, Deserialize)]
+pub struct InputRecord {
+    #[serde(default = "first_round")]
+    pub round: u8,
+    pub tick: u32,
+    pub player_id: PlayerId,
+    /// The game's input struct as JSON, e.g. `{"turn": "Left", "brake": false}`.
+    pub input: serde_json::Value,
+}
+
+fn first_round() -> u8 {
+    1
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulateResponse {
+    pub game: String,
+    /// The `seed` setting the match ran with. Pass it back in `custom` to
+    /// repeat a match whose request had none.
+    pub seed: u64,
+    pub rounds: Vec<RoundReport>,
+    /// Totals across all rounds, sorted by player ID.
+    pub final_scores: Vec<PlayerScore>,
+    /// Inputs that were never applied: unknown players, rounds that were not
+    /// played, or ticks after the round ended.
+    pub ignored_inputs: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RoundReport {
+    pub round: u8,
+    /// Simulation steps run.
+    pub ticks: u32,
+    /// False when the round hit the tick limit without finishing.
+    pub completed: bool,
+    pub scores: Vec<PlayerScore>,
+    pub events: Vec<TickEvent>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TickEvent {
+    pub tick: u32,
+    pub event: GameEvent,
+}
+
+/// Encoded inputs keyed by `(round, tick)`, in log order within a tick.
+type InputSchedule = BTreeMap<(u8, u32), Vec<(PlayerId, Vec<u8>)>>;
+
+/// Re-simulate a match. Requests with the same `seed` setting always
+/// produce the same response; an unseeded one is given a random seed.
+pub fn simulate(
+    registry: &ServerGameRegistry,
+    req: &SimulateRequest,
+    limits: &RefereeConfig,
+) -> Result<SimulateResponse, String> {
+    let game_id =
+        GameId::from_str_opt(&req.game).ok_or_else(|| format!("Unknown game: {}", req.game))?;
+    let mut game = registry
+        .create(game_id)
+        .ok_or_else(|| format!("Game not available on this server: {}", req.game))?;
+    registry
+        .validate_settings(game_id, &req.custom)
+        .map_err(|e| e.to_string())?;
+    // Random choices (shuffled teams, random courses) follow the seed
+    let mut custom = req.custom.clone();
+    let seed = match custom.get(SEED_SETTING).and_then(|v| v.as_u64()) {
+        Some(seed) => seed,
+        None => {
+            let seed = rand::random::<u64>();
+            custom.insert(SEED_SETTING.to_string(), seed.into());
+            seed
+        },
+    };
+
+    let max_players = game.metadata().max_players as usize;
+    if req.players.is_empty() || req.players.len() > max_players {
+        return Err(format!("{} needs 1-{max_players} players", req.game));
+    }
+    let mut seen = HashSet::new();
+    if let Some(dup) = req.players.iter().find(|p| !seen.insert(p.id)) {
+        return Err(format!("Duplicate player id {}", dup.id));
+    }
+    if req.inputs.len() > limits.max_inputs {
+        return Err(format!("inputs exceed {} entries", limits.max_inputs));
+    }
+
+    let players: Vec<Player> = req
+        .players
+        .iter()
+        .enumerate()
+        .map(|(i, p)| Player {
+            id: p.id,
+            display_name: if p.name.is_empty() {
+                format!("Player {}", p.id)
+            } else {
+                p.name.clone()
+            },
+            color: PlayerColor::default(),
+            is_leader: i == 0,
+            is_spectator: false,
+            is_bot: p.is_bot,
+            bot_difficulty: p.bot_difficulty,
+            hot_seat_of: None,
+            external_bot: false,
+        })
+        .collect();
+    let mut bots = BotDriver::new(&players);
+
+    let round_count = if req.round_count > 0 {
+        req.round_count
+    } else {
+        game.round_count_for(&custom)
+    };
+    if round_count > limits.max_rounds {
+        return Err(format!("round_count exceeds {}", limits.max_rounds));
+    }
+
+    // Encode every input up front so a bad entry fails the whole request
+    let mut schedule = InputSchedule::new();
+    let mut ignored_inputs = 0;
+    for (i, record) in req.inputs.iter().enumerate() {
+        let bytes = rmp_serde::to_vec(&record.input).map_err(|e| format!("inputs[{i}]: {e}"))?;
+        if seen.contains(&record.player_id) && record.tick > 0 {
+            schedule
+                .entry((record.round, record.tick))
+                .or_default()
+                .push((record.player_id, bytes));
+        } else {
+            ignored_inputs += 1;
+        }
+    }
+
+    let dt = 1.0 / game.tick_rate();
+    let mut rounds = Vec::with_capacity(round_count as usize);
+    let mut totals: BTreeMap<PlayerId, i32> = BTreeMap::new();
+    for round in 1..=round_count {
+        game.init(
+            &players,
+            &GameConfig::builder()
+                .round_count(round_count)
+                .round_duration(Duration::from_secs(req.round_duration_secs))
+                .custom(custom.clone())
+                .setting("hole_index", round - 1)
+                .build(),
+        );
+
+        let report = simulate_round(
+            &mut *game,
+            game_id,
+            round,
+            dt,
+            &mut schedule,
+            &mut bots,
+            limits.max_ticks_per_round,
+        );
+        bots.record_round(&report.scores);
+        for s in &report.scores {
+            *totals.entry(s.player_id).or_insert(0) += s.score;
+        }
+        rounds.push(report);
+    }
+    ignored_inputs += schedule.values().map(Vec::len).sum::<usize>();
+
+    Ok(SimulateResponse {
+        game: game_id.to_string(),
+        seed,
+        rounds,
+        final_scores: totals
+            .into_iter()
+            .map(|(player_id, score)| PlayerScore {
+                player_id,
+                score,
+                disconnected: false,
+            })
+            .collect(),
+        ignored_inputs,
+    })
+}
+
+/// Step one round until it completes or hits `max_ticks`, consuming its
+/// inputs from `schedule`.
+fn simulate_round(
+    game: &mut dyn BreakpointGame,
+    game_id: GameId,
+    round: u8,
+    dt: f32,
+    schedule: &mut InputSchedule,
+    bots: &mut BotDriver,
+    max_ticks: u32,
+) -> RoundReport {
+    let mut events = Vec::new();
+    let mut tick = 0;
+    let mut completed = false;
+
+    while tick < max_ticks {
+        tick += 1;
+        let mut input_buffer = HashMap::new();
+        for (player_id, bytes) in schedule.remove(&(round, tick)).unwrap_or_default() {
+            game.apply_input(player_id, &bytes);
+            input_buffer.insert(player_id, bytes);
+        }
+        #[cfg(feature = "tron")]
+        if game_id == GameId::Tron && !bots.is_empty() {
+            crate::game_loop::apply_tron_bot_inputs(game, bots, &mut input_buffer);
+        }
+        #[cfg(not(feature = "tron"))]
+        let _ = (game_id, &bots);
+
+        let tick_events = game.update(
+            dt,
+            &PlayerInputs {
+                inputs: input_buffer,
+            },
+        );
+        let done = tick_events
+            .iter()
+            .any(|e| matches!(e, GameEvent::RoundComplete))
+            || game.is_round_complete();
+        events.extend(
+            tick_events
+                .into_iter()
+                .map(|event| TickEvent { tick, event }),
+        );
+        if done {
+            completed = true;
+            break;
+        }
+    }
+
+    let mut scores = game.round_results();
+    scores.sort_by_key(|s| s.player_id);
+    RoundReport {
+        round,
+        ticks: tick,
+        completed,
+        scores,
+        events,
+    }
+}
+
+/// POST /api/v1/referee/simulate — re-simulate a match from its input log.
+pub async fn post_simulate(
+    State(state): State<AppState>,
+    Json(body): Json<SimulateRequest>,
+) -> Result<Json<SimulateResponse>, AppError> {
+    let registry = Arc::clone(&state.game_registry);
+    let limits = state.config.referee.clone();
+    // Simulation is CPU-bound; keep it off the async workers
+    let result = tokio::task::spawn_blocking(move || simulate(&registry, &body, &limits))
+        .await
+        .map_err(|e| AppError::Internal(format!("Simulation failed: {e}")))?;
+    let response = result.map_err(AppError::BadRequest)?;
+    tracing::info!(
+        game = %response.game,
+        rounds = response.rounds.len(),
+        ignored_inputs = response.ignored_inputs,
+        "Referee simulation complete"
+    );
+    Ok(Json(response))
+}
+
+#[cfg(all(test, feature = "tron"))]
+mod tests {
+    use super::*;
+
+    fn tron_request(inputs: Vec<InputRecord>) -> SimulateRequest {
+        SimulateRequest {
+            game: "tron".to_string(),
+            players: vec![
+                RefereePlayer {
+                    id: 1,
+                    name: "Alice".to_string(),
+                    is_bot: false,
+                    bot_difficulty: Default::default(),
+                },
+                RefereePlayer {
+                    id: 2,
+                    name: String::new(),
+                    is_bot: false,
+                    bot_difficulty: Default::default(),
+                },
+            ],
+            round_count: 1,
+            round_duration_secs: 90,
+            custom: HashMap::from([(SEED_SETTING.to_string(), 7.into())]),
+            inputs,
+        }
+    }
+
+    fn turn(tick: u32, player_id: PlayerId, dir: &str) -> InputRecord {
+        InputRecord {
+            round: 1,
+            tick,
+            player_id,
+            input: serde_json::json!({ "turn": dir, "brake": false }),
+        }
+    }
+
+    #[test]
+    fn same_log_same_result() {
+        let registry = ServerGameRegistry::new();
+        let limits = RefereeConfig::default();
+        let req = tron_request(vec![turn(10, 1, "Left"), turn(25, 2, "Right")]);
+
+        let a = simulate(&registry, &req, &limits).unwrap();
+        let b = simulate(&registry, &req, &limits).unwrap();
+        assert_eq!(
+            serde_json::to_value(&a).unwrap(),
+            serde_json::to_value(&b).unwrap()
+        );
+        assert_eq!(a.rounds.len(), 1);
+        assert!(a.rounds[0].completed, "Cycles eventually crash");
+        assert_eq!(a.ignored_inputs, 0);
+    }
+
+    #[test]
+    fn inputs_change_the_outcome() {
+        let registry = ServerGameRegistry::new();
+        let limits = RefereeConfig::default();
+        let idle = simulate(&registry, &tron_request(Vec::new()), &limits).unwrap();
+        let turned = simulate(&registry, &tron_request(vec![turn(5, 1, "Left")]), &limits).unwrap();
+        assert_ne!(
+            serde_json::to_value(&idle.rounds).unwrap(),
+            serde_json::to_value(&turned.rounds).unwrap()
+        );
+    }
+
+    #[test]
+    fn unknown_players_and_late_rounds_are_ignored() {
+        let registry = ServerGameRegistry::new();
+        let limits = RefereeConfig::default();
+        let mut late = turn(5, 1, "Left");
+        late.round = 3;
+        let req = tron_request(vec![turn(5, 99, "Left"), late]);
+        let result = simulate(&registry, &req, &limits).unwrap();
+        assert_eq!(result.ignored_inputs, 2);
+    }
+
+    #[test]
+    fn rejects_bad_requests() {
+        let registry = ServerGameRegistry::new();
+        let limits = RefereeConfig::default();
+
+        let mut req = tron_request(Vec::new());
+        req.game = "chess".to_string();
+        assert!(simulate(&registry, &req, &limits).is_err());
+
+        let mut req = tron_request(Vec::new());
+        req.players[1].id = 1;
+        assert!(simulate(&registry, &req, &limits).is_err());
+
+        let req = tron_request(vec![turn(1, 1, "Left"); limits.max_inputs + 1]);
+        assert!(simulate(&registry, &req, &limits).is_err());
+    }
+}
+
+#[cfg(all(test, feature = "lasertag"))]
+mod lasertag_tests {
+    use super::*;
+
+    #[test]
+    fn shuffled_teams_repeat_with_the_reported_seed() {
+        let registry = ServerGameRegistry::new();
+        let limits = RefereeConfig::default();
+        let mut req = SimulateRequest {
+            game: "laser-tag".to_string(),
+            players: (1..=4)
+                .map(|id| RefereePlayer {
+                    id,
+                    name: String::new(),
+                    is_bot: false,
+                    bot_difficulty: Default::default(),
+                })
+                .collect(),
+            round_count: 1,
+            round_duration_secs: 5,
+            custom: HashMap::from([
+                ("team_mode".to_string(), serde_json::json!("teams_2")),
+                ("team_select".to_string(), serde_json::json!("shuffle")),
+            ]),
+            inputs: Vec::new(),
+        };
+
+        let unseeded = simulate(&registry, &req, &limits).unwrap();
+        req.custom
+            .insert(SEED_SETTING.to_string(), unseeded.seed.into());
+        let a = simulate(&registry, &req, &limits).unwrap();
+        let b = simulate(&registry, &req, &limits).unwrap();
+        assert_eq!(a.seed, unseeded.seed);
+        assert_eq!(
+            serde_json::to_value(&a).unwrap(),
+            serde_json::to_value(&b).unwrap()
+        );
+        assert_eq!(
+            serde_json::to_value(&a).unwrap(),
+            serde_json::to_value(&unseeded).unwrap()
+        );
+    }
+}
@@ -1,30 +1,81 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use bytes::Bytes;
 use uuid::Uuid;
 
-use breakpoint_core::game_trait::{GameId, PlayerId};
-use breakpoint_core::net::messages::{JoinRoomResponseMsg, PlayerListMsg, ServerMessage};
-use breakpoint_core::net::protocol::encode_server_message;
-use breakpoint_core::player::{Player, PlayerColor};
-use breakpoint_core::room::{Room, RoomState};
-use tokio::sync::mpsc;
+use breakpoint_core::bot::BotDifficulty;
+use breakpoint_core::debug_command::DebugCommand;
+use breakpoint_core::events::Event;
+use breakpoint_core::game_trait::{
+    BreakpointGame, GameConfigDelta, GameId, PlayerId, PlayerInputs,
+};
+use breakpoint_core::i18n::{LocalizedText, TextKey};
+use breakpoint_core::lobby_toy::TargetPractice;
+use breakpoint_core::names;
+use breakpoint_core::net::messages::{
+    JoinQueueAction, JoinRoomResponseMsg, LobbyToyStateMsg, MAX_LOCAL_PLAYERS, MessageType,
+    PlayerListMsg, PlayerScoreEntry, RequestGameStartMsg, RoomReshape, RoomTransferMsg,
+    ServerMessage, TransferReason,
+};
+use breakpoint_core::net::protocol::{decode_server_message, encode_server_message};
+use breakpoint_core::overlay::config::OverlayRoomConfig;
+use breakpoint_core::palette;
+use breakpoint_core::player::{Player, PlayerColor, PlayerIdentity};
+use breakpoint_core::room::{Room, RoomCodeFormat, RoomState};
+use breakpoint_core::round_phase::{self, RoundPhaseTimings};
+use breakpoint_core::tutorial;
+use tokio::sync::{mpsc, oneshot};
 use tokio::task::JoinHandle;
 
+use crate::config::DebugConfig;
 use crate::game_loop::{
     GameBroadcast, GameCommand, GameSessionConfig, ServerGameRegistry, spawn_game_session,
 };
-
-/// Per-player sender for outbound WebSocket binary messages.
-/// Bounded to 256 messages to prevent memory exhaustion from slow clients.
-/// Uses `Bytes` for zero-copy cloning when broadcasting to multiple players.
-pub type PlayerSender = mpsc::Sender<Bytes>;
+use crate::history::ReplaySink;
+use crate::input_stats::{InputStatsSnapshot, SharedInputStats};
+use crate::join_queue::{Admission, AdmissionResult, JoinQueue, JoinQueueLimits};
+use crate::lifecycle_hooks::RoomMetadata;
+use crate::memory_budget::{MemoryBudget, RoomMemorySnapshot, RoomMemoryTracker, SharedRoomMemory};
+use crate::room_webhooks::{ResultReporter, RoomActivity, RoomActivitySink};
+use crate::schedule::ScheduledSession;
+use crate::season::{Season, SeasonScorer};
+use crate::state::{SharedMatchHistory, SharedPlayerSettings};
+use crate::transfers::{MIN_SPLIT_PLAYERS, PendingTransfer, TRANSFER_TTL, balanced_split};
+use crate::tutorial::TutorialSink;
+
+pub use crate::outbound::{Lane, PlayerSender};
 
 /// Tracks a connected player's outbound channel.
 struct ConnectedPlayer {
     sender: PlayerSender,
+    /// Newest `PlayerInput` tick taken from this player's connection,
+    /// handed on when the session migrates to another transport.
+    last_input_tick: AtomicU32,
+}
+
+impl ConnectedPlayer {
+    fn new(sender: PlayerSender) -> Self {
+        Self {
+            sender,
+            last_input_tick: AtomicU32::new(0),
+        }
+    }
+}
+
+/// A live session moved onto a new connection by
+/// [`RoomManager::migrate_transport`].
+#[derive(Debug)]
+pub struct Migration {
+    pub room_code: String,
+    pub player_id: PlayerId,
+    /// Replaces the token the old connection held.
+    pub session_token: String,
+    pub last_input_tick: u32,
+    pub identity: Option<PlayerIdentity>,
 }
 
 /// Session record for reconnection. When a player disconnects mid-game,
@@ -44,6 +95,57 @@ pub struct RoomManager {
     next_player_id: PlayerId,
     /// Maps session_token → disconnected session info.
     sessions: HashMap<String, DisconnectedSession>,
+    /// Countdown and freeze-frame durations applied to new rooms.
+    phase_timings: RoundPhaseTimings,
+    /// Where finished matches are recorded, with the per-round frame limit.
+    match_history: Option<(SharedMatchHistory, usize)>,
+    /// Where tutorial sessions record the objectives players complete.
+    player_settings: Option<SharedPlayerSettings>,
+    /// Memory sampling and caps for new game sessions.
+    memory_budget: MemoryBudget,
+    join_queue_limits: JoinQueueLimits,
+    /// Overlay settings (including the event filter) applied to new rooms.
+    overlay_defaults: OverlayRoomConfig,
+    /// Where room lifecycle events go for outbound webhooks.
+    activity: Option<RoomActivitySink>,
+    /// Told the code of each room whose game starts or ends, so friends
+    /// following its players see what they're playing.
+    game_changes: Option<mpsc::UnboundedSender<String>>,
+    /// Featured games for rooms that opt in, and where their points go.
+    season: Option<Season>,
+    capacity: RoomCapacity,
+    /// Connections waiting for a new room while the server is at its cap.
+    room_queue: JoinQueue,
+    /// Shape of generated room codes.
+    code_format: RoomCodeFormat,
+    /// Maps vanity alias → room code. Entries for closed rooms are ignored
+    /// and pruned with idle rooms.
+    aliases: HashMap<String, String>,
+    /// Aliases claimed by connections waiting in the room queue, by ticket.
+    pending_aliases: HashMap<u64, String>,
+    /// Open merge/split offers, by transfer token.
+    transfers: HashMap<String, PendingTransfer>,
+}
+
+/// Server-wide caps on open rooms.
+#[derive(Debug, Clone, Copy)]
+pub struct RoomCapacity {
+    /// Most rooms open at once.
+    pub max_rooms: usize,
+    /// Most open rooms created from one IP address.
+    pub max_rooms_per_ip: usize,
+    /// Longest line of connections waiting for a room; 0 turns them away.
+    pub queue_max: usize,
+}
+
+impl Default for RoomCapacity {
+    fn default() -> Self {
+        Self {
+            max_rooms: 100,
+            max_rooms_per_ip: 5,
+            queue_max: 32,
+        }
+    }
 }
 
 struct RoomEntry {
@@ -61,6 +163,191 @@ struct RoomEntry {
     /// Shared sender map for active game broadcasts. Updated on reconnection
     /// so the broadcast forwarder can reach reconnected clients.
     broadcast_senders: Arc<Mutex<HashMap<PlayerId, PlayerSender>>>,
+    /// Input statistics for the current (or most recent) game session.
+    input_stats: Option<SharedInputStats>,
+    /// Memory accounting for the current (or most recent) game session.
+    memory: Option<SharedRoomMemory>,
+    /// Connections waiting for a slot.
+    join_queue: JoinQueue,
+    /// Address of the connection that created the room, for per-IP caps.
+    creator_ip: Option<IpAddr>,
+    /// When the room's first debug command arrived; its debug window
+    /// runs from here.
+    debug_opened: Option<Instant>,
+    /// Target practice for players waiting in the lobby.
+    lobby_toy: TargetPractice,
+    /// When the lobby toy last ticked.
+    lobby_toy_at: Instant,
+    /// How often each player has re-rolled their generated name.
+    name_rolls: HashMap<PlayerId, u32>,
+    /// Final scores summed over the games each player finished here,
+    /// carried along when they transfer to another room.
+    session_scores: HashMap<PlayerId, i32>,
+    /// Stable identities of players who joined with an identity key. Kept
+    /// while a disconnected player's slot is preserved, so a lost session
+    /// token can be recovered.
+    identities: HashMap<PlayerId, PlayerIdentity>,
+    /// Round trips players last reported, passed on to each game session
+    /// for the scoreboard.
+    pings: HashMap<PlayerId, u16>,
+    /// The running game was told no connected player is left in it.
+    dormant: bool,
+    /// The game being played, while a session runs.
+    game: Option<GameId>,
+    /// Games a scheduled session lined up, next first. Each one started
+    /// moves to the back.
+    playlist: VecDeque<GameId>,
+}
+
+impl RoomEntry {
+    fn new(room: Room) -> Self {
+        Self {
+            room,
+            connections: HashMap::new(),
+            last_activity: Instant::now(),
+            player_sessions: HashMap::new(),
+            game_command_tx: None,
+            game_task: None,
+            broadcast_task: None,
+            broadcast_senders: Arc::new(Mutex::new(HashMap::new())),
+            input_stats: None,
+            memory: None,
+            join_queue: JoinQueue::default(),
+            creator_ip: None,
+            debug_opened: None,
+            lobby_toy: TargetPractice::new(),
+            lobby_toy_at: Instant::now(),
+            name_rolls: HashMap::new(),
+            session_scores: HashMap::new(),
+            identities: HashMap::new(),
+            pings: HashMap::new(),
+            dormant: false,
+            game: None,
+            playlist: VecDeque::new(),
+        }
+    }
+
+    /// Whether anyone is left to play: a connected human (or external
+    /// bot) in the match, or a hot-seat guest whose keyboard is connected.
+    /// Spectators and server bots don't count.
+    fn has_active_players(&self) -> bool {
+        self.room.players.iter().any(|p| {
+            !p.is_spectator
+                && !p.is_bot
+                && self
+                    .connections
+                    .contains_key(&p.hot_seat_of.unwrap_or(p.id))
+        })
+    }
+
+    fn is_full(&self) -> bool {
+        self.room.players.len() >= self.room.config.max_players as usize
+    }
+
+    /// `player_name`, or a generated name no one in the room has when it
+    /// is blank.
+    fn name_or_generated(&self, player_id: PlayerId, player_name: String) -> String {
+        if !player_name.is_empty() {
+            return player_name;
+        }
+        let roll = self.name_rolls.get(&player_id).copied().unwrap_or(0);
+        names::generate(
+            names::seed(&self.room.code, player_id, roll),
+            &names::taken_names(&self.room.players),
+        )
+    }
+
+    /// Add a human player with its connection. Returns the session token.
+    /// Late-joiners (room not in Lobby) enter as spectators, and the first
+    /// player into an empty room leads it.
+    fn add_player(
+        &mut self,
+        player_id: PlayerId,
+        player_name: String,
+        player_color: PlayerColor,
+        sender: PlayerSender,
+    ) -> String {
+        let session_token = RoomManager::generate_session_token();
+        let is_spectator = self.room.state != RoomState::Lobby;
+        let is_leader = self.room.players.is_empty();
+        if is_leader {
+            self.room.leader_id = player_id;
+        }
+        self.last_activity = Instant::now();
+        let color = palette::resolve(player_color, &palette::taken_colors(&self.room.players));
+        let display_name = self.name_or_generated(player_id, player_name);
+        self.room.players.push(Player {
+            id: player_id,
+            display_name,
+            color,
+            is_leader,
+            is_spectator,
+            is_bot: false,
+            bot_difficulty: Default::default(),
+            hot_seat_of: None,
+            external_bot: false,
+        });
+        self.connections
+            .insert(player_id, ConnectedPlayer::new(sender));
+        self.player_sessions
+            .insert(player_id, session_token.clone());
+        session_token
+    }
+
+    fn remove_player(&mut self, player_id: PlayerId) {
+        self.name_rolls.remove(&player_id);
+        self.session_scores.remove(&player_id);
+        self.identities.remove(&player_id);
+        self.pings.remove(&player_id);
+        self.room.players.retain(|p| p.id != player_id);
+        self.connections.remove(&player_id);
+        self.player_sessions.remove(&player_id);
+    }
+}
+
+/// Outcome of [`RoomManager::request_join`].
+pub enum JoinRequest {
+    Joined {
+        player_id: PlayerId,
+        session_token: String,
+    },
+    /// Waiting in the room's join queue; the admission (or refusal) arrives
+    /// on `admission`.
+    Queued {
+        ticket: u64,
+        admission: oneshot::Receiver<AdmissionResult>,
+    },
+}
+
+/// Outcome of [`RoomManager::request_room`].
+pub enum RoomRequest {
+    Created {
+        room_code: String,
+        player_id: PlayerId,
+        session_token: String,
+    },
+    /// Waiting for the server to drop below its room cap; the admission
+    /// (with the new room's code) arrives on `admission`.
+    Queued {
+        ticket: u64,
+        admission: oneshot::Receiver<AdmissionResult>,
+    },
+}
+
+/// Memory accounting for one room, for the admin API.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RoomMemoryStats {
+    pub room_code: String,
+    pub total_bytes: usize,
+    #[serde(flatten)]
+    pub memory: RoomMemorySnapshot,
+}
+
+/// Input statistics for all players in one room, for the admin API.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RoomInputStats {
+    pub room_code: String,
+    pub players: Vec<InputStatsSnapshot>,
 }
 
 impl Default for RoomManager {
@@ -71,10 +358,144 @@ impl Default for RoomManager {
 
 impl RoomManager {
     pub fn new() -> Self {
+        Self::with_phase_timings(RoundPhaseTimings::default())
+    }
+
+    /// Create a manager whose new rooms use the given countdown and
+    /// freeze-frame durations.
+    pub fn with_phase_timings(phase_timings: RoundPhaseTimings) -> Self {
         Self {
             rooms: HashMap::new(),
             next_player_id: 1,
             sessions: HashMap::new(),
+            phase_timings,
+            match_history: None,
+            player_settings: None,
+            memory_budget: MemoryBudget::default(),
+            join_queue_limits: JoinQueueLimits::default(),
+            overlay_defaults: OverlayRoomConfig::default(),
+            activity: None,
+            game_changes: None,
+            season: None,
+            capacity: RoomCapacity::default(),
+            room_queue: JoinQueue::default(),
+            code_format: RoomCodeFormat::default(),
+            aliases: HashMap::new(),
+            pending_aliases: HashMap::new(),
+            transfers: HashMap::new(),
+        }
+    }
+
+    pub fn set_join_queue_limits(&mut self, limits: JoinQueueLimits) {
+        self.join_queue_limits = limits;
+    }
+
+    pub fn set_room_capacity(&mut self, capacity: RoomCapacity) {
+        self.capacity = capacity;
+    }
+
+    pub fn set_code_format(&mut self, format: RoomCodeFormat) {
+        self.code_format = format;
+    }
+
+    pub fn code_format(&self) -> &RoomCodeFormat {
+        &self.code_format
+    }
+
+    /// The room code a player typed to join: the code of the open room
+    /// with that vanity alias, or what they typed if no room has it.
+    pub fn resolve_room_code(&self, code_or_alias: &str) -> String {
+        let key = code_or_alias.trim().to_ascii_uppercase();
+        match self.aliases.get(&key) {
+            Some(code) if self.rooms.contains_key(code) => code.clone(),
+            _ => code_or_alias.to_string(),
+        }
+    }
+
+    /// Vanity aliases of open rooms, sorted, with each room's code and its
+    /// host's identity when the host joined with one.
+    pub fn vanity_aliases(&self) -> Vec<(String, String, Option<PlayerIdentity>)> {
+        let mut aliases: Vec<_> = self
+            .aliases
+            .iter()
+            .filter_map(|(alias, code)| {
+                let entry = self.rooms.get(code)?;
+                let host = entry.identities.get(&entry.room.leader_id).cloned();
+                Some((alias.clone(), code.clone(), host))
+            })
+            .collect();
+        aliases.sort();
+        aliases
+    }
+
+    /// Drop an open room's vanity alias. The room keeps its code.
+    pub fn release_alias(&mut self, alias: &str) -> bool {
+        let key = alias.trim().to_ascii_uppercase();
+        match self.aliases.get(&key) {
+            Some(code) if self.rooms.contains_key(code) => {
+                self.aliases.remove(&key);
+                true
+            },
+            _ => false,
+        }
+    }
+
+    /// Normalize a requested alias and check no open or queued room
+    /// already has it.
+    fn claim_alias(&mut self, alias: &str) -> Result<String, LocalizedText> {
+        let alias = breakpoint_core::room::normalize_room_alias(alias, &self.code_format)?;
+        let queue = &self.room_queue;
+        self.pending_aliases
+            .retain(|&ticket, _| queue.contains(ticket));
+        let open = self
+            .aliases
+            .get(&alias)
+            .is_some_and(|code| self.rooms.contains_key(code));
+        if open || self.pending_aliases.values().any(|a| *a == alias) {
+            return Err(TextKey::RoomAliasTaken.into());
+        }
+        Ok(alias)
+    }
+
+    pub fn set_overlay_defaults(&mut self, defaults: OverlayRoomConfig) {
+        self.overlay_defaults = defaults;
+    }
+
+    /// Record every game session started from now on into `history`.
+    pub fn record_matches(&mut self, history: SharedMatchHistory, max_frames_per_round: usize) {
+        self.match_history = Some((history, max_frames_per_round));
+    }
+
+    /// Record tutorial objectives players complete in their synced
+    /// settings in `player_settings`.
+    pub fn record_tutorials(&mut self, player_settings: SharedPlayerSettings) {
+        self.player_settings = Some(player_settings);
+    }
+
+    pub fn set_memory_budget(&mut self, budget: MemoryBudget) {
+        self.memory_budget = budget;
+    }
+
+    /// Report room lifecycle events (created, game started, results,
+    /// closed) to `sink` from now on.
+    pub fn report_activity(&mut self, sink: RoomActivitySink) {
+        self.activity = Some(sink);
+    }
+
+    /// Send the code of each room whose game starts or ends to `sink` from
+    /// now on.
+    pub fn report_game_changes(&mut self, sink: mpsc::UnboundedSender<String>) {
+        self.game_changes = Some(sink);
+    }
+
+    /// Let rooms start in featured mode with `season`'s weekly games.
+    pub fn set_season(&mut self, season: Season) {
+        self.season = Some(season);
+    }
+
+    fn emit(&self, activity: RoomActivity) {
+        if let Some(ref sink) = self.activity {
+            sink.emit(activity);
         }
     }
 
@@ -95,38 +516,204 @@ impl RoomManager {
         player_color: PlayerColor,
         sender: PlayerSender,
     ) -> (String, PlayerId, String) {
-        let code = generate_unique_room_code(&self.rooms);
+        let code = generate_unique_room_code(&self.rooms, &self.code_format);
         let player_id = self.alloc_player_id();
         let session_token = Self::generate_session_token();
+        let display_name = if player_name.is_empty() {
+            names::generate(names::seed(&code, player_id, 0), &[])
+        } else {
+            player_name
+        };
         let player = Player {
             id: player_id,
-            display_name: player_name,
+            display_name,
             color: player_color,
             is_leader: true,
             is_spectator: false,
             is_bot: false,
+            bot_difficulty: Default::default(),
+            hot_seat_of: None,
+            external_bot: false,
         };
-        let room = Room::new(code.clone(), player);
-        let mut connections = HashMap::new();
-        connections.insert(player_id, ConnectedPlayer { sender });
-        let mut player_sessions = HashMap::new();
-        player_sessions.insert(player_id, session_token.clone());
-        self.rooms.insert(
-            code.clone(),
-            RoomEntry {
-                room,
-                connections,
-                last_activity: Instant::now(),
-                player_sessions,
-                game_command_tx: None,
-                game_task: None,
-                broadcast_task: None,
-                broadcast_senders: Arc::new(Mutex::new(HashMap::new())),
-            },
-        );
+        let mut room = Room::new(code.clone(), player);
+        room.config.countdown_duration = Duration::from_secs_f32(self.phase_timings.countdown_secs);
+        room.config.freeze_frame_duration =
+            Duration::from_secs_f32(self.phase_timings.freeze_frame_secs);
+        room.config.overlay_config = self.overlay_defaults.clone();
+        let mut entry = RoomEntry::new(room);
+        entry
+            .connections
+            .insert(player_id, ConnectedPlayer::new(sender));
+        entry
+            .player_sessions
+            .insert(player_id, session_token.clone());
+        // A code can come around again; its old room's alias doesn't carry over
+        self.aliases.retain(|_, c| *c != code);
+        self.rooms.insert(code.clone(), entry);
+        self.emit(RoomActivity::RoomCreated {
+            room_code: code.clone(),
+        });
         (code, player_id, session_token)
     }
 
+    /// Create a room for a connection from `ip`, or wait in the server's
+    /// line for one if the room cap is reached. The room can also be
+    /// joined by `alias`, if given. Fails if `ip` already has its share of
+    /// open rooms, the alias is invalid or taken, or the line is full.
+    pub fn request_room(
+        &mut self,
+        player_name: String,
+        player_color: PlayerColor,
+        sender: PlayerSender,
+        ip: IpAddr,
+        alias: Option<&str>,
+    ) -> Result<RoomRequest, LocalizedText> {
+        // Settle anyone who can already get a room so newcomers never cut in line
+        self.admit_room_queue();
+        if self.rooms_created_by(ip) >= self.capacity.max_rooms_per_ip {
+            return Err(TextKey::TooManyRooms.into());
+        }
+        let alias = match alias.filter(|a| !a.trim().is_empty()) {
+            Some(alias) => Some(self.claim_alias(alias)?),
+            None => None,
+        };
+        if self.rooms.len() < self.capacity.max_rooms && self.room_queue.is_empty() {
+            let (room_code, player_id, session_token) =
+                self.create_room(player_name, player_color, sender);
+            if let Some(entry) = self.rooms.get_mut(&room_code) {
+                entry.creator_ip = Some(ip);
+            }
+            if let Some(alias) = alias {
+                self.aliases.insert(alias, room_code.clone());
+            }
+            return Ok(RoomRequest::Created {
+                room_code,
+                player_id,
+                session_token,
+            });
+        }
+
+        let limits = JoinQueueLimits {
+            max_len: self.capacity.queue_max,
+            per_ip: self.capacity.max_rooms_per_ip,
+        };
+        let (ticket, admission) = self
+            .room_queue
+            .enqueue(player_name, player_color, sender, ip, limits)
+            .map_err(|_| LocalizedText::from(TextKey::ServerFull))?;
+        if let Some(alias) = alias {
+            self.pending_aliases.insert(ticket, alias);
+        }
+        self.room_queue.notify_positions("");
+        Ok(RoomRequest::Queued { ticket, admission })
+    }
+
+    fn rooms_created_by(&self, ip: IpAddr) -> usize {
+        self.rooms
+            .values()
+            .filter(|e| e.creator_ip == Some(ip))
+            .count()
+    }
+
+    /// Open rooms for connections waiting in the server's line, oldest
+    /// first, while below the room cap.
+    fn admit_room_queue(&mut self) {
+        let mut changed = false;
+        while self.rooms.len() < self.capacity.max_rooms {
+            let Some(queued) = self.room_queue.pop_admissible() else {
+                break;
+            };
+            changed = true;
+            let (room_code, player_id, session_token) = self.create_room(
+                queued.player_name.clone(),
+                queued.player_color,
+                queued.sender.clone(),
+            );
+            if let Some(entry) = self.rooms.get_mut(&room_code) {
+                entry.creator_ip = Some(queued.ip());
+            }
+            if let Some(alias) = self.pending_aliases.remove(&queued.ticket) {
+                self.aliases.insert(alias, room_code.clone());
+            }
+            let admission = Admission {
+                room_code: room_code.clone(),
+                player_id,
+                session_token,
+                room_state: RoomState::Lobby,
+            };
+            if queued.resolve(Ok(admission)) {
+                tracing::info!(player_id, room = %room_code, "Opened room from server queue");
+            } else {
+                // Left the line as the room opened
+                self.rooms.remove(&room_code);
+                self.aliases.retain(|_, c| *c != room_code);
+                self.emit(RoomActivity::RoomClosed { room_code });
+            }
+        }
+        if changed {
+            self.room_queue.notify_positions("");
+        }
+    }
+
+    /// Drop a connection that stopped waiting for a room.
+    pub fn cancel_queued_room(&mut self, ticket: u64) {
+        self.pending_aliases.remove(&ticket);
+        if self.room_queue.remove(ticket).is_some() {
+            self.room_queue.notify_positions("");
+        }
+    }
+
+    /// Connections waiting for a room, for readiness reporting.
+    pub fn room_queue_len(&self) -> usize {
+        self.room_queue.len()
+    }
+
+    /// Create an empty room for a scheduled session. The first player to
+    /// join becomes its leader. Returns the room code.
+    pub fn create_reserved_room(&mut self) -> String {
+        let code = generate_unique_room_code(&self.rooms, &self.code_format);
+        let room = Room {
+            code: code.clone(),
+            config: Default::default(),
+            state: RoomState::Lobby,
+            players: Vec::new(),
+            leader_id: 0,
+            current_round: 0,
+        };
+        self.rooms.insert(code.clone(), RoomEntry::new(room));
+        self.emit(RoomActivity::RoomCreated {
+            room_code: code.clone(),
+        });
+        code
+    }
+
+    /// Create the room for a scheduled session, with its playlist lined
+    /// up. Returns the room code.
+    pub fn create_scheduled_room(&mut self, session: &ScheduledSession) -> String {
+        let code = self.create_reserved_room();
+        if let Some(entry) = self.rooms.get_mut(&code) {
+            entry.room.config.countdown_duration =
+                Duration::from_secs_f32(self.phase_timings.countdown_secs);
+            entry.room.config.freeze_frame_duration =
+                Duration::from_secs_f32(self.phase_timings.freeze_frame_secs);
+            entry.room.config.overlay_config = self.overlay_defaults.clone();
+            entry.playlist = session
+                .games
+                .iter()
+                .filter_map(|g| GameId::from_str_opt(g))
+                .collect();
+        }
+        code
+    }
+
+    /// The games lined up in a room, next first.
+    pub fn playlist(&self, room_code: &str) -> Vec<GameId> {
+        self.rooms
+            .get(room_code)
+            .map(|entry| entry.playlist.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
     /// Join an existing room. Returns Ok((player_id, session_token)) or Err(reason).
     /// Players joining mid-game enter as spectators.
     pub fn join_room(
@@ -135,46 +722,182 @@ impl RoomManager {
         player_name: String,
         player_color: PlayerColor,
         sender: PlayerSender,
-    ) -> Result<(PlayerId, String), String> {
+    ) -> Result<(PlayerId, String), LocalizedText> {
         // Validate room exists and is joinable
         {
-            let entry = self
-                .rooms
-                .get(room_code)
-                .ok_or_else(|| "Room not found".to_string())?;
+            let entry = self.rooms.get(room_code).ok_or(TextKey::RoomNotFound)?;
 
-            if entry.room.players.len() >= entry.room.config.max_players as usize {
-                return Err("Room is full".to_string());
+            if entry.is_full() {
+                return Err(TextKey::RoomFull.into());
             }
         }
 
         let player_id = self.alloc_player_id();
-        let session_token = Self::generate_session_token();
         let Some(entry) = self.rooms.get_mut(room_code) else {
-            return Err("Room not found".to_string());
+            return Err(TextKey::RoomNotFound.into());
         };
+        let session_token = entry.add_player(player_id, player_name, player_color, sender);
+
+        Ok((player_id, session_token))
+    }
+
+    /// Join a room, or wait in its join queue if it is full, the leader
+    /// approves joins, or others are already waiting.
+    pub fn request_join(
+        &mut self,
+        room_code: &str,
+        player_name: String,
+        player_color: PlayerColor,
+        sender: PlayerSender,
+        ip: IpAddr,
+    ) -> Result<JoinRequest, LocalizedText> {
+        // Settle anyone who can already enter so newcomers never cut in line
+        self.admit_queued(room_code);
+        let entry = self.rooms.get_mut(room_code).ok_or(TextKey::RoomNotFound)?;
+        if !entry.is_full() && !entry.join_queue.require_approval && entry.join_queue.is_empty() {
+            let (player_id, session_token) =
+                self.join_room(room_code, player_name, player_color, sender)?;
+            return Ok(JoinRequest::Joined {
+                player_id,
+                session_token,
+            });
+        }
 
-        // Late-joiners (room not in Lobby) enter as spectators
-        let is_spectator = entry.room.state != RoomState::Lobby;
+        let (ticket, admission) = entry.join_queue.enqueue(
+            player_name,
+            player_color,
+            sender,
+            ip,
+            self.join_queue_limits,
+        )?;
         entry.last_activity = Instant::now();
-        let player = Player {
-            id: player_id,
-            display_name: player_name,
-            color: player_color,
-            is_leader: false,
-            is_spectator,
-            is_bot: false,
-        };
+        self.broadcast_join_queue(room_code);
+        Ok(JoinRequest::Queued { ticket, admission })
+    }
 
-        entry.room.players.push(player);
-        entry
-            .connections
-            .insert(player_id, ConnectedPlayer { sender });
-        entry
-            .player_sessions
-            .insert(player_id, session_token.clone());
+    /// Move waiting connections into open slots, oldest first. Returns
+    /// true if anyone entered.
+    pub fn admit_queued(&mut self, room_code: &str) -> bool {
+        let mut admitted = false;
+        loop {
+            let Some(entry) = self.rooms.get_mut(room_code) else {
+                return admitted;
+            };
+            if entry.is_full() {
+                break;
+            }
+            let Some(queued) = entry.join_queue.pop_admissible() else {
+                break;
+            };
+            let player_id = self.alloc_player_id();
+            let Some(entry) = self.rooms.get_mut(room_code) else {
+                return admitted;
+            };
+            let session_token = entry.add_player(
+                player_id,
+                queued.player_name.clone(),
+                queued.player_color,
+                queued.sender.clone(),
+            );
+            let admission = Admission {
+                room_code: room_code.to_string(),
+                player_id,
+                session_token,
+                room_state: entry.room.state,
+            };
+            if queued.resolve(Ok(admission)) {
+                tracing::info!(
+                    player_id,
+                    room = room_code,
+                    "Admitted player from join queue"
+                );
+                admitted = true;
+            } else {
+                // Left the line as the slot opened
+                entry.remove_player(player_id);
+            }
+        }
+        if admitted {
+            self.broadcast_join_queue(room_code);
+        }
+        admitted
+    }
 
-        Ok((player_id, session_token))
+    /// Drop a waiting connection that went away.
+    pub fn cancel_queued_join(&mut self, room_code: &str, ticket: u64) {
+        if let Some(entry) = self.rooms.get_mut(room_code)
+            && entry.join_queue.remove(ticket).is_some()
+        {
+            self.broadcast_join_queue(room_code);
+        }
+    }
+
+    /// Apply a leader's join queue action.
+    /// Replace a room's overlay settings, including which alerts reach it.
+    /// Only the leader may change them.
+    pub fn set_overlay_config(
+        &mut self,
+        room_code: &str,
+        requester_id: PlayerId,
+        config: OverlayRoomConfig,
+    ) -> Result<(), String> {
+        config.validate()?;
+        let entry = self
+            .rooms
+            .get_mut(room_code)
+            .ok_or_else(|| "Room not found".to_string())?;
+        if entry.room.leader_id != requester_id {
+            return Err("Only the room leader can change overlay settings".to_string());
+        }
+        entry.room.config.overlay_config = config;
+        Ok(())
+    }
+
+    pub fn overlay_config(&self, room_code: &str) -> Option<&OverlayRoomConfig> {
+        self.rooms
+            .get(room_code)
+            .map(|e| &e.room.config.overlay_config)
+    }
+
+    pub fn manage_join_queue(
+        &mut self,
+        room_code: &str,
+        requester_id: PlayerId,
+        action: JoinQueueAction,
+    ) -> Result<(), String> {
+        let queueing_enabled = self.join_queue_limits.max_len > 0;
+        let entry = self
+            .rooms
+            .get_mut(room_code)
+            .ok_or_else(|| "Room not found".to_string())?;
+        if entry.room.leader_id != requester_id {
+            return Err("Only the room leader can manage the join queue".to_string());
+        }
+        match action {
+            JoinQueueAction::RequireApproval(required) => {
+                if required && !queueing_enabled {
+                    return Err("Join queues are disabled on this server".to_string());
+                }
+                entry.join_queue.require_approval = required;
+            },
+            JoinQueueAction::Admit(ticket) => {
+                if !entry.join_queue.approve(ticket) {
+                    return Err(format!("No queued join with ticket {ticket}"));
+                }
+            },
+            JoinQueueAction::Deny(ticket) => {
+                let queued = entry
+                    .join_queue
+                    .remove(ticket)
+                    .ok_or_else(|| format!("No queued join with ticket {ticket}"))?;
+                queued.resolve(Err(TextKey::JoinDenied.into()));
+            },
+        }
+        entry.last_activity = Instant::now();
+        if !self.admit_queued(room_code) {
+            self.broadcast_join_queue(room_code);
+        }
+        Ok(())
     }
 
     /// Attempt to reconnect using a session token. Returns
@@ -183,36 +906,33 @@ impl RoomManager {
         &mut self,
         session_token: &str,
         sender: PlayerSender,
-    ) -> Result<(String, PlayerId, String), String> {
+    ) -> Result<(String, PlayerId, String), LocalizedText> {
         let session = self
             .sessions
             .remove(session_token)
-            .ok_or_else(|| "Invalid or expired session".to_string())?;
+            .ok_or(TextKey::SessionInvalid)?;
 
         // Check TTL
         if session.disconnected_at.elapsed() > SESSION_TTL {
-            return Err("Session expired".to_string());
+            return Err(TextKey::SessionExpired.into());
         }
 
         let entry = self
             .rooms
             .get_mut(&session.room_code)
-            .ok_or_else(|| "Room no longer exists".to_string())?;
+            .ok_or(TextKey::RoomGone)?;
 
         // Verify the player still exists in the room's player list
         let player_exists = entry.room.players.iter().any(|p| p.id == session.player_id);
         if !player_exists {
-            return Err("Player slot no longer available".to_string());
+            return Err(TextKey::PlayerSlotUnavailable.into());
         }
 
         // Restore connection
         let new_token = Self::generate_session_token();
-        entry.connections.insert(
-            session.player_id,
-            ConnectedPlayer {
-                sender: sender.clone(),
-            },
-        );
+        entry
+            .connections
+            .insert(session.player_id, ConnectedPlayer::new(sender.clone()));
         entry
             .player_sessions
             .insert(session.player_id, new_token.clone());
@@ -229,9 +949,105 @@ impl RoomManager {
             );
         }
 
+        self.update_dormancy(&session.room_code);
         Ok((session.room_code, session.player_id, new_token))
     }
 
+    /// The identity behind a live session token, if it joined with one.
+    pub fn session_identity(&self, session_token: &str) -> Option<PlayerIdentity> {
+        self.rooms.values().find_map(|entry| {
+            let (&pid, _) = entry
+                .player_sessions
+                .iter()
+                .find(|(_, token)| token.as_str() == session_token)?;
+            entry.identities.get(&pid).cloned()
+        })
+    }
+
+    /// Move a connected player's session onto a new connection, keeping
+    /// their player ID, guests, identity, and place in any running game.
+    /// The old connection's outbound queue is dropped, which closes it, and
+    /// its session token stops working, so its disconnect is not a leave
+    /// (see [`RoomManager::session_migrated`]).
+    pub fn migrate_transport(
+        &mut self,
+        session_token: &str,
+        sender: PlayerSender,
+    ) -> Result<Migration, LocalizedText> {
+        let (room_code, player_id) = self
+            .rooms
+            .iter()
+            .find_map(|(code, entry)| {
+                entry
+                    .player_sessions
+                    .iter()
+                    .find(|(_, token)| token.as_str() == session_token)
+                    .map(|(&pid, _)| (code.clone(), pid))
+            })
+            .ok_or(TextKey::SessionInvalid)?;
+        let entry = self
+            .rooms
+            .get_mut(&room_code)
+            .ok_or(TextKey::SessionInvalid)?;
+        let Some(conn) = entry.connections.get_mut(&player_id) else {
+            return Err(TextKey::SessionInvalid.into());
+        };
+
+        conn.sender = sender.clone();
+        let last_input_tick = conn.last_input_tick.load(Ordering::Relaxed);
+        let new_token = Self::generate_session_token();
+        entry.player_sessions.insert(player_id, new_token.clone());
+        entry.last_activity = Instant::now();
+        if let Ok(mut senders) = entry.broadcast_senders.lock()
+            && senders.contains_key(&player_id)
+        {
+            senders.insert(player_id, sender);
+        }
+
+        Ok(Migration {
+            identity: entry.identities.get(&player_id).cloned(),
+            room_code,
+            player_id,
+            session_token: new_token,
+            last_input_tick,
+        })
+    }
+
+    /// Whether `player_id`'s session in `room_code` has moved from the
+    /// connection holding `session_token` to another transport.
+    pub fn session_migrated(
+        &self,
+        room_code: &str,
+        player_id: PlayerId,
+        session_token: &str,
+    ) -> bool {
+        self.rooms
+            .get(room_code)
+            .and_then(|entry| entry.player_sessions.get(&player_id))
+            .is_some_and(|token| token != session_token)
+    }
+
+    /// Reconnect the player whose preserved slot in `room_code` belongs to
+    /// `identity`, for clients that lost their session token (a new tab, a
+    /// cleared storage) but still hold their identity key.
+    pub fn reconnect_identity(
+        &mut self,
+        room_code: &str,
+        identity: &PlayerIdentity,
+        sender: PlayerSender,
+    ) -> Result<(String, PlayerId, String), LocalizedText> {
+        let entry = self.rooms.get(room_code).ok_or(TextKey::SessionInvalid)?;
+        let token = self
+            .sessions
+            .iter()
+            .find(|(_, s)| {
+                s.room_code == room_code && entry.identities.get(&s.player_id) == Some(identity)
+            })
+            .map(|(token, _)| token.clone())
+            .ok_or(TextKey::SessionInvalid)?;
+        self.reconnect(&token, sender)
+    }
+
     /// Remove a player from their room. If the room is mid-game, the player's
     /// slot is preserved for reconnection via session token.
     /// Returns the room code if the room was destroyed (empty after leave).
@@ -262,6 +1078,7 @@ impl RoomManager {
                 room = room_code,
                 "Player disconnected mid-game, session preserved for reconnection"
             );
+            self.update_dormancy(room_code);
             return None;
         }
 
@@ -273,9 +1090,42 @@ impl RoomManager {
         }
 
         entry.player_sessions.remove(&player_id);
-        entry.room.players.retain(|p| p.id != player_id);
+        // Hot-seat guests leave with the connection they share
+        let guests: Vec<PlayerId> = entry
+            .room
+            .players
+            .iter()
+            .filter(|p| p.hot_seat_of == Some(player_id))
+            .map(|p| p.id)
+            .collect();
+        if let Some(ref cmd_tx) = entry.game_command_tx {
+            for &guest_id in &guests {
+                let _ = cmd_tx.send(GameCommand::PlayerLeft {
+                    player_id: guest_id,
+                });
+            }
+        }
+        entry
+            .room
+            .players
+            .retain(|p| p.id != player_id && !guests.contains(&p.id));
+        entry.lobby_toy.player_left(player_id);
+        entry.session_scores.remove(&player_id);
+        entry.identities.remove(&player_id);
+        entry.pings.remove(&player_id);
+
+        if entry.room.players.is_empty() && !is_in_game && !entry.join_queue.is_empty() {
+            // Nobody is left to approve joins, so let the line in rather
+            // than closing the room on it
+            entry.join_queue.require_approval = false;
+            if self.admit_queued(room_code) {
+                return None;
+            }
+        }
+        let entry = self.rooms.get_mut(room_code)?;
 
         if entry.room.players.is_empty() {
+            entry.join_queue.clear(TextKey::RoomGone);
             // Stop the game session if running
             if let Some(ref cmd_tx) = entry.game_command_tx
                 && let Err(e) = cmd_tx.send(GameCommand::Stop)
@@ -283,12 +1133,18 @@ impl RoomManager {
                 tracing::debug!(room = room_code, error = %e, "Game session already stopped");
             }
             self.rooms.remove(room_code);
+            self.aliases.retain(|_, c| c != room_code);
+            self.emit(RoomActivity::RoomClosed {
+                room_code: room_code.to_string(),
+            });
+            self.admit_room_queue();
             return Some(room_code.to_string());
         }
 
-        // If the host left, migrate to the next player
+        // If the host left, migrate to the next player with a connection
+        // of their own
         if entry.room.leader_id == player_id
-            && let Some(new_host) = entry.room.players.first()
+            && let Some(new_host) = entry.room.players.iter().find(|p| p.hot_seat_of.is_none())
         {
             entry.room.leader_id = new_host.id;
             for p in &mut entry.room.players {
@@ -296,14 +1152,38 @@ impl RoomManager {
             }
         }
 
+        self.admit_queued(room_code);
+        self.update_dormancy(room_code);
         None
     }
 
+    /// Put a room's running game to sleep when no connected player is left
+    /// in it, and wake it as soon as one is back. Display rooms left on a
+    /// TV then cost a keepalive every few seconds instead of a full tick
+    /// loop.
+    fn update_dormancy(&mut self, room_code: &str) {
+        let Some(entry) = self.rooms.get_mut(room_code) else {
+            return;
+        };
+        let Some(ref cmd_tx) = entry.game_command_tx else {
+            return;
+        };
+        let dormant = !entry.has_active_players();
+        if dormant == entry.dormant {
+            return;
+        }
+        entry.dormant = dormant;
+        if cmd_tx.send(GameCommand::Dormant(dormant)).is_ok() {
+            tracing::info!(room = room_code, dormant, "Game session dormancy changed");
+        }
+    }
+
     /// Clean up expired disconnected sessions. Returns the number removed.
     pub fn cleanup_expired_sessions(&mut self) -> usize {
         let before = self.sessions.len();
         self.sessions
             .retain(|_, s| s.disconnected_at.elapsed() <= SESSION_TTL);
+        self.transfers.retain(|_, t| !t.is_expired());
 
         // Also remove player slots from rooms for expired sessions
         // Note: We don't remove player entries from rooms here because the
@@ -312,75 +1192,510 @@ impl RoomManager {
         before - self.sessions.len()
     }
 
+    /// Merge a lobby into another room, or split it in two, by offering a
+    /// `RoomTransfer` to each connected player who would move. `requester`
+    /// is the leader asking, or `None` for an admin, who may also merge into
+    /// rooms that approve their joins. Returns the target room and how many
+    /// players were offered a move.
+    pub fn reshape_room(
+        &mut self,
+        room_code: &str,
+        requester: Option<PlayerId>,
+        action: RoomReshape,
+    ) -> Result<(String, usize), String> {
+        let entry = self
+            .rooms
+            .get(room_code)
+            .ok_or_else(|| "Room not found".to_string())?;
+        if requester.is_some_and(|id| id != entry.room.leader_id) {
+            return Err("Only the room leader can merge or split the room".to_string());
+        }
+        if entry.room.state != RoomState::Lobby {
+            return Err("Rooms can only be merged or split in the lobby".to_string());
+        }
+        // Bots and hot-seat guests have no connection of their own to move
+        let humans: Vec<(PlayerId, i32)> = entry
+            .room
+            .players
+            .iter()
+            .filter(|p| !p.is_bot && p.hot_seat_of.is_none())
+            .filter(|p| entry.connections.contains_key(&p.id))
+            .map(|p| (p.id, entry.session_scores.get(&p.id).copied().unwrap_or(0)))
+            .collect();
+
+        let (target, moving, reason) = match action {
+            RoomReshape::MergeInto(code) => {
+                let target = self.resolve_room_code(&code);
+                if target == room_code {
+                    return Err("A room cannot merge into itself".to_string());
+                }
+                let other = self
+                    .rooms
+                    .get(&target)
+                    .ok_or_else(|| "Target room not found".to_string())?;
+                if other.room.state != RoomState::Lobby {
+                    return Err("The target room is not in its lobby".to_string());
+                }
+                if requester.is_some() && other.join_queue.require_approval {
+                    return Err("The target room approves its joins".to_string());
+                }
+                let open = (other.room.config.max_players as usize)
+                    .saturating_sub(other.room.players.len());
+                if open < humans.len() {
+                    return Err(format!(
+                        "The target room has {open} open slots for {} players",
+                        humans.len()
+                    ));
+                }
+                let moving = humans.iter().map(|&(id, _)| id).collect();
+                (target, moving, TransferReason::Merge)
+            },
+            RoomReshape::Split => {
+                if humans.len() < MIN_SPLIT_PLAYERS {
+                    return Err(format!(
+                        "Splitting needs at least {MIN_SPLIT_PLAYERS} connected players"
+                    ));
+                }
+                let moving = balanced_split(entry.room.leader_id, &humans);
+                let config = entry.room.config.clone();
+                let target = self.create_reserved_room();
+                if let Some(new_room) = self.rooms.get_mut(&target) {
+                    new_room.room.config = config;
+                }
+                (target, moving, TransferReason::Split)
+            },
+        };
+
+        let Some(entry) = self.rooms.get(room_code) else {
+            return Err("Room not found".to_string());
+        };
+        let mut offers = Vec::with_capacity(moving.len());
+        for &player_id in &moving {
+            let Some(player) = entry.room.players.iter().find(|p| p.id == player_id) else {
+                continue;
+            };
+            let transfer_token = Self::generate_session_token();
+            let msg = ServerMessage::RoomTransfer(RoomTransferMsg {
+                room_code: target.clone(),
+                transfer_token: transfer_token.clone(),
+                reason,
+                expires_in_secs: TRANSFER_TTL.as_secs() as u32,
+            });
+            if let Ok(data) = encode_server_message(&msg) {
+                self.send_to_player(room_code, player_id, Lane::Control, Bytes::from(data));
+            }
+            offers.push((
+                transfer_token,
+                PendingTransfer {
+                    room_code: target.clone(),
+                    display_name: player.display_name.clone(),
+                    color: player.color,
+                    session_score: entry.session_scores.get(&player_id).copied().unwrap_or(0),
+                    offered_at: Instant::now(),
+                },
+            ));
+        }
+        let offered = offers.len();
+        self.transfers.extend(offers);
+        tracing::info!(
+            room = room_code,
+            target = %target,
+            ?reason,
+            offered,
+            "Offered room transfers"
+        );
+        Ok((target, offered))
+    }
+
+    /// Claim a transfer offer by its token. Each token works once.
+    pub fn take_transfer(&mut self, transfer_token: &str) -> Option<PendingTransfer> {
+        self.transfers.remove(transfer_token)
+    }
+
+    /// Join the room a claimed transfer points at, keeping the player's
+    /// name, color, and session score. Returns
+    /// Ok((room_code, player_id, session_token)).
+    pub fn join_by_transfer(
+        &mut self,
+        transfer: PendingTransfer,
+        sender: PlayerSender,
+    ) -> Result<(String, PlayerId, String), LocalizedText> {
+        if transfer.is_expired() {
+            return Err(TextKey::SessionExpired.into());
+        }
+        let (player_id, session_token) = self.join_room(
+            &transfer.room_code,
+            transfer.display_name,
+            transfer.color,
+            sender,
+        )?;
+        if let Some(entry) = self.rooms.get_mut(&transfer.room_code)
+            && transfer.session_score != 0
+        {
+            entry
+                .session_scores
+                .insert(player_id, transfer.session_score);
+        }
+        Ok((transfer.room_code, player_id, session_token))
+    }
+
+    /// Add a finished game's final scores to the room's session scores.
+    pub fn record_session_scores(&mut self, room_code: &str, final_scores: &[PlayerScoreEntry]) {
+        let Some(entry) = self.rooms.get_mut(room_code) else {
+            return;
+        };
+        for score in final_scores.iter().filter(|s| !s.disconnected) {
+            *entry.session_scores.entry(score.player_id).or_default() += score.score;
+        }
+    }
+
     /// Add a bot player to the room. Only the room leader can add bots, and
     /// the room must be in the Lobby state. Returns the bot's PlayerId.
-    pub fn add_bot(&mut self, room_code: &str, requester_id: PlayerId) -> Result<PlayerId, String> {
+    pub fn add_bot(
+        &mut self,
+        room_code: &str,
+        requester_id: PlayerId,
+        difficulty: BotDifficulty,
+    ) -> Result<PlayerId, String> {
         // Validate first with an immutable borrow
         {
             let entry = self
                 .rooms
                 .get(room_code)
                 .ok_or_else(|| "Room not found".to_string())?;
-            if entry.room.leader_id != requester_id {
-                return Err("Only the room leader can add bots".to_string());
+            if entry.room.leader_id != requester_id {
+                return Err("Only the room leader can add bots".to_string());
+            }
+            if entry.room.state != RoomState::Lobby {
+                return Err("Can only add bots in lobby".to_string());
+            }
+            if entry.room.players.len() >= entry.room.config.max_players as usize {
+                return Err("Room is full".to_string());
+            }
+        }
+
+        let bot_id = self.alloc_player_id();
+        // Safe: we just validated the room exists above
+        let Some(entry) = self.rooms.get_mut(room_code) else {
+            return Err("Room not found".to_string());
+        };
+        let bot_number = entry.room.players.iter().filter(|p| p.is_bot).count() + 1;
+        let color = palette::most_distinct(&palette::taken_colors(&entry.room.players));
+
+        let bot = Player {
+            id: bot_id,
+            display_name: format!("Bot {bot_number}"),
+            color,
+            is_leader: false,
+            is_spectator: false,
+            is_bot: true,
+            bot_difficulty: difficulty,
+            hot_seat_of: None,
+            external_bot: false,
+        };
+        entry.room.players.push(bot);
+        entry.last_activity = Instant::now();
+
+        Ok(bot_id)
+    }
+
+    /// Remove a bot player from the room. Only the room leader can remove bots.
+    pub fn remove_bot(
+        &mut self,
+        room_code: &str,
+        bot_id: PlayerId,
+        requester_id: PlayerId,
+    ) -> Result<(), String> {
+        let entry = self
+            .rooms
+            .get_mut(room_code)
+            .ok_or_else(|| "Room not found".to_string())?;
+
+        if entry.room.leader_id != requester_id {
+            return Err("Only the room leader can remove bots".to_string());
+        }
+
+        let is_bot = entry
+            .room
+            .players
+            .iter()
+            .any(|p| p.id == bot_id && p.is_bot);
+        if !is_bot {
+            return Err("Player is not a bot".to_string());
+        }
+
+        entry.room.players.retain(|p| p.id != bot_id);
+        entry.last_activity = Instant::now();
+        self.admit_queued(room_code);
+        Ok(())
+    }
+
+    /// Add a hot-seat guest sharing `owner_id`'s connection. Only in the
+    /// lobby, and at most [`MAX_LOCAL_PLAYERS`] per connection. Returns the
+    /// guest's PlayerId.
+    pub fn add_local_player(
+        &mut self,
+        room_code: &str,
+        owner_id: PlayerId,
+        player_name: String,
+        player_color: PlayerColor,
+    ) -> Result<PlayerId, String> {
+        {
+            let entry = self
+                .rooms
+                .get(room_code)
+                .ok_or_else(|| "Room not found".to_string())?;
+            if !entry.connections.contains_key(&owner_id) {
+                return Err("Only connected players can add local players".to_string());
             }
             if entry.room.state != RoomState::Lobby {
-                return Err("Can only add bots in lobby".to_string());
+                return Err("Can only add local players in lobby".to_string());
             }
-            if entry.room.players.len() >= entry.room.config.max_players as usize {
+            if entry.is_full() {
                 return Err("Room is full".to_string());
             }
+            let guests = entry
+                .room
+                .players
+                .iter()
+                .filter(|p| p.hot_seat_of == Some(owner_id))
+                .count();
+            if guests >= MAX_LOCAL_PLAYERS {
+                return Err(format!("At most {MAX_LOCAL_PLAYERS} local players"));
+            }
         }
 
-        let bot_id = self.alloc_player_id();
-        // Safe: we just validated the room exists above
+        let guest_id = self.alloc_player_id();
         let Some(entry) = self.rooms.get_mut(room_code) else {
             return Err("Room not found".to_string());
         };
-        let bot_number = entry.room.players.iter().filter(|p| p.is_bot).count() + 1;
-        let color_index = entry.room.players.len();
-        let color = PlayerColor::PALETTE[color_index % PlayerColor::PALETTE.len()];
-
-        let bot = Player {
-            id: bot_id,
-            display_name: format!("Bot {bot_number}"),
+        let color = palette::resolve(player_color, &palette::taken_colors(&entry.room.players));
+        let display_name = entry.name_or_generated(guest_id, player_name);
+        entry.room.players.push(Player {
+            id: guest_id,
+            display_name,
             color,
             is_leader: false,
             is_spectator: false,
-            is_bot: true,
-        };
-        entry.room.players.push(bot);
+            is_bot: false,
+            bot_difficulty: Default::default(),
+            hot_seat_of: Some(owner_id),
+            external_bot: false,
+        });
         entry.last_activity = Instant::now();
 
-        Ok(bot_id)
+        Ok(guest_id)
     }
 
-    /// Remove a bot player from the room. Only the room leader can remove bots.
-    pub fn remove_bot(
+    /// Give `player_id` the next generated name, in the lobby. `requester`
+    /// may re-roll itself or one of its hot-seat guests. Returns the new name.
+    pub fn reroll_name(
         &mut self,
         room_code: &str,
-        bot_id: PlayerId,
-        requester_id: PlayerId,
-    ) -> Result<(), String> {
+        requester: PlayerId,
+        player_id: PlayerId,
+    ) -> Result<String, String> {
         let entry = self
             .rooms
             .get_mut(room_code)
             .ok_or_else(|| "Room not found".to_string())?;
-
-        if entry.room.leader_id != requester_id {
-            return Err("Only the room leader can remove bots".to_string());
+        if entry.room.state != RoomState::Lobby {
+            return Err("Names can only be re-rolled in the lobby".to_string());
+        }
+        let allowed =
+            entry.room.players.iter().any(|p| {
+                p.id == player_id && (p.id == requester || p.hot_seat_of == Some(requester))
+            });
+        if !allowed {
+            return Err("Players can only re-roll their own names".to_string());
+        }
+        *entry.name_rolls.entry(player_id).or_insert(0) += 1;
+        let name = entry.name_or_generated(player_id, String::new());
+        if let Some(player) = entry.room.players.iter_mut().find(|p| p.id == player_id) {
+            player.display_name.clone_from(&name);
         }
+        entry.last_activity = Instant::now();
+        Ok(name)
+    }
 
-        let is_bot = entry
+    /// Remove one of `owner_id`'s hot-seat guests. Mid-game the session is
+    /// told the guest left, as if it had disconnected.
+    pub fn remove_local_player(
+        &mut self,
+        room_code: &str,
+        guest_id: PlayerId,
+        owner_id: PlayerId,
+    ) -> Result<(), String> {
+        let entry = self
+            .rooms
+            .get_mut(room_code)
+            .ok_or_else(|| "Room not found".to_string())?;
+
+        let is_guest = entry
             .room
             .players
             .iter()
-            .any(|p| p.id == bot_id && p.is_bot);
-        if !is_bot {
-            return Err("Player is not a bot".to_string());
+            .any(|p| p.id == guest_id && p.hot_seat_of == Some(owner_id));
+        if !is_guest {
+            return Err("Player is not one of your local players".to_string());
         }
 
-        entry.room.players.retain(|p| p.id != bot_id);
+        entry.room.players.retain(|p| p.id != guest_id);
+        entry.last_activity = Instant::now();
+        if let Some(ref cmd_tx) = entry.game_command_tx
+            && let Err(e) = cmd_tx.send(GameCommand::PlayerLeft {
+                player_id: guest_id,
+            })
+        {
+            tracing::debug!(player_id = guest_id, room = room_code, error = %e, "Game session gone");
+        }
+        self.admit_queued(room_code);
+        Ok(())
+    }
+
+    /// Hot-seat guests sharing `owner_id`'s connection.
+    pub fn local_players_of(&self, room_code: &str, owner_id: PlayerId) -> Vec<PlayerId> {
+        self.rooms
+            .get(room_code)
+            .map(|entry| {
+                entry
+                    .room
+                    .players
+                    .iter()
+                    .filter(|p| p.hot_seat_of == Some(owner_id))
+                    .map(|p| p.id)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Which player an input sent on `connection_id`'s connection drives:
+    /// one of its hot-seat guests if it names one, otherwise the
+    /// connection's own player.
+    pub fn input_target(
+        &self,
+        room_code: &str,
+        connection_id: PlayerId,
+        claimed_id: PlayerId,
+    ) -> PlayerId {
+        if claimed_id == connection_id {
+            return connection_id;
+        }
+        let is_guest = self.rooms.get(room_code).is_some_and(|entry| {
+            entry
+                .room
+                .players
+                .iter()
+                .any(|p| p.id == claimed_id && p.hot_seat_of == Some(connection_id))
+        });
+        if is_guest { claimed_id } else { connection_id }
+    }
+
+    /// Record the stable identity behind a joined player.
+    pub fn bind_identity(
+        &mut self,
+        room_code: &str,
+        player_id: PlayerId,
+        identity: PlayerIdentity,
+    ) {
+        if let Some(entry) = self.rooms.get_mut(room_code)
+            && entry.room.players.iter().any(|p| p.id == player_id)
+        {
+            entry.identities.insert(player_id, identity);
+        }
+    }
+
+    pub fn identity_of(&self, room_code: &str, player_id: PlayerId) -> Option<&PlayerIdentity> {
+        self.rooms.get(room_code)?.identities.get(&player_id)
+    }
+
+    /// Identities of everyone connected in a room, sorted.
+    pub fn identities_in_room(&self, room_code: &str) -> Vec<PlayerIdentity> {
+        let Some(entry) = self.rooms.get(room_code) else {
+            return Vec::new();
+        };
+        let mut ids: Vec<PlayerIdentity> = entry
+            .identities
+            .iter()
+            .filter(|(pid, _)| entry.connections.contains_key(pid))
+            .map(|(_, id)| id.clone())
+            .collect();
+        ids.sort();
+        ids.dedup();
+        ids
+    }
+
+    /// Mark a player who joined with a bot API token as an external bot.
+    pub fn mark_external_bot(&mut self, room_code: &str, player_id: PlayerId) {
+        if let Some(player) = self
+            .rooms
+            .get_mut(room_code)
+            .and_then(|entry| entry.room.players.iter_mut().find(|p| p.id == player_id))
+        {
+            player.external_bot = true;
+        }
+    }
+
+    pub fn is_external_bot(&self, room_code: &str, player_id: PlayerId) -> bool {
+        self.rooms.get(room_code).is_some_and(|entry| {
+            entry
+                .room
+                .players
+                .iter()
+                .any(|p| p.id == player_id && p.external_bot)
+        })
+    }
+
+    /// Route an external bot's ack to the active game session, which
+    /// decides whether it still counts.
+    pub fn route_bot_ack(
+        &self,
+        room_code: &str,
+        player_id: PlayerId,
+        tick: u32,
+        input_data: Vec<u8>,
+    ) {
+        if !self.is_external_bot(room_code, player_id) {
+            return;
+        }
+        if let Some(entry) = self.rooms.get(room_code)
+            && let Some(ref cmd_tx) = entry.game_command_tx
+            && let Err(e) = cmd_tx.send(GameCommand::BotAck {
+                player_id,
+                tick,
+                input_data,
+            })
+        {
+            tracing::debug!(player_id, room = room_code, error = %e, "Game session gone");
+        }
+    }
+
+    /// Change a bot's difficulty. Only the room leader can, and only in the
+    /// lobby; a running match keeps the difficulties it started with.
+    pub fn set_bot_difficulty(
+        &mut self,
+        room_code: &str,
+        bot_id: PlayerId,
+        difficulty: BotDifficulty,
+        requester_id: PlayerId,
+    ) -> Result<(), String> {
+        let entry = self
+            .rooms
+            .get_mut(room_code)
+            .ok_or_else(|| "Room not found".to_string())?;
+        if entry.room.leader_id != requester_id {
+            return Err("Only the room leader can change bots".to_string());
+        }
+        if entry.room.state != RoomState::Lobby {
+            return Err("Can only change bots in lobby".to_string());
+        }
+        let bot = entry
+            .room
+            .players
+            .iter_mut()
+            .find(|p| p.id == bot_id && p.is_bot)
+            .ok_or_else(|| "Player is not a bot".to_string())?;
+        bot.bot_difficulty = difficulty;
         entry.last_activity = Instant::now();
         Ok(())
     }
@@ -434,12 +1749,18 @@ impl RoomManager {
     pub fn start_game(
         &mut self,
         room_code: &str,
-        game_name: &str,
+        request: RequestGameStartMsg,
         requester_id: PlayerId,
         registry: &std::sync::Arc<ServerGameRegistry>,
         rooms: crate::state::SharedRoomManager,
-        custom: HashMap<String, serde_json::Value>,
     ) -> Result<(), String> {
+        let RequestGameStartMsg {
+            mut game_name,
+            mut custom,
+            win_condition,
+            pacing,
+            featured,
+        } = request;
         let entry = self
             .rooms
             .get_mut(room_code)
@@ -455,18 +1776,74 @@ impl RoomManager {
             return Err("Game already in progress".to_string());
         }
 
+        // A start without a game plays the next one on the playlist
+        if game_name.is_empty()
+            && let Some(next) = entry.playlist.front()
+        {
+            game_name = next.as_str().to_string();
+        }
+
+        // Featured mode plays this week's game with its modifiers
+        let season_scorer = if featured {
+            let season = self
+                .season
+                .as_ref()
+                .ok_or_else(|| "No season is running".to_string())?;
+            let week = season
+                .featured_at(breakpoint_core::time::unix_now())
+                .ok_or_else(|| "No featured game this week".to_string())?;
+            game_name.clone_from(&week.week.game);
+            custom.extend(week.week.modifiers.clone());
+            Some(season.scorer(&entry.room.players, &entry.identities))
+        } else {
+            None
+        };
+
         let game_id =
-            GameId::from_str_opt(game_name).ok_or_else(|| format!("Unknown game: {game_name}"))?;
+            GameId::from_str_opt(&game_name).ok_or_else(|| format!("Unknown game: {game_name}"))?;
+        registry
+            .validate_settings(game_id, &custom)
+            .map_err(|e| e.to_string())?;
+        if let Some(win_condition) = win_condition {
+            entry.room.config.win_condition = win_condition;
+        }
+        if let Some(pacing) = pacing {
+            entry.room.config.pacing = pacing.clamped();
+        }
+        // Micro-rounds shorten this session only; the room keeps its config
+        let micro_round_secs = round_phase::micro_round_secs(&custom).map_err(|e| e.to_string())?;
+        let session_config = entry.room.config.with_micro_rounds(micro_round_secs);
+        // Tutorials play a single round and are left out of the match history
+        let tutorial = tutorial::tutorial_requested(&custom);
 
         let config = GameSessionConfig {
             game_id,
             players: entry.room.players.clone(),
             leader_id: entry.room.leader_id,
-            round_count: 0, // Let the game decide via round_count_hint()
-            round_duration: entry.room.config.round_duration,
-            between_round_duration: entry.room.config.between_round_duration,
+            // Otherwise let the game decide via round_count_hint()
+            round_count: if tutorial { 1 } else { 0 },
+            round_duration: session_config.round_duration,
+            pacing: session_config.pacing,
+            phase_timings: session_config.phase_timings(),
+            win_condition: entry.room.config.win_condition.clone(),
             custom,
+            input_stats: SharedInputStats::default(),
+            memory: Arc::new(Mutex::new(RoomMemoryTracker::new(self.memory_budget))),
+            replay: self.match_history.as_ref().filter(|_| !tutorial).map(
+                |(history, max_frames)| ReplaySink {
+                    history: Arc::clone(history),
+                    match_id: Uuid::new_v4().to_string(),
+                    room_code: room_code.to_string(),
+                    max_frames_per_round: *max_frames,
+                },
+            ),
+            tutorial: tutorial.then(|| TutorialSink {
+                player_settings: self.player_settings.clone(),
+                identities: entry.identities.clone(),
+            }),
         };
+        let input_stats = Arc::clone(&config.input_stats);
+        let memory = Arc::clone(&config.memory);
 
         let (cmd_tx, broadcast_rx, game_handle) = spawn_game_session(registry, config)
             .ok_or_else(|| format!("Failed to create game: {game_name}"))?;
@@ -484,23 +1861,122 @@ impl RoomManager {
         let shared_senders = Arc::clone(&entry.broadcast_senders);
         let room_code_owned = room_code.to_string();
         let rooms_clone = rooms;
+        let featured = season_scorer.is_some();
+        let reporter = self.activity.clone().map(|sink| {
+            sink.emit(RoomActivity::GameStarted {
+                room_code: room_code.to_string(),
+                game: game_id.as_str().to_string(),
+                players: entry
+                    .room
+                    .players
+                    .iter()
+                    .filter(|p| !p.is_spectator)
+                    .map(|p| p.display_name.clone())
+                    .collect(),
+                featured,
+            });
+            let names = entry
+                .room
+                .players
+                .iter()
+                .map(|p| (p.id, p.display_name.clone()))
+                .collect();
+            ResultReporter::new(sink, room_code, names)
+        });
         let broadcast_handle = tokio::spawn(async move {
-            forward_broadcasts(broadcast_rx, shared_senders, &room_code_owned).await;
+            let final_scores = forward_broadcasts(
+                broadcast_rx,
+                shared_senders,
+                &room_code_owned,
+                reporter,
+                season_scorer,
+            )
+            .await;
             // Game ended — clean up room state and notify clients
             let mut mgr = rooms_clone.write().await;
+            mgr.record_session_scores(&room_code_owned, &final_scores);
             mgr.end_game_session(&room_code_owned);
             mgr.broadcast_player_list(&room_code_owned);
         });
 
+        for (&player_id, &rtt_ms) in &entry.pings {
+            let _ = cmd_tx.send(GameCommand::Ping { player_id, rtt_ms });
+        }
         entry.game_command_tx = Some(cmd_tx);
+        entry.dormant = false;
         entry.game_task = Some(game_handle);
         entry.broadcast_task = Some(broadcast_handle);
+        entry.input_stats = Some(input_stats);
+        entry.memory = Some(memory);
         entry.room.state = RoomState::InGame;
+        entry.game = Some(game_id);
         entry.last_activity = Instant::now();
+        if entry.playlist.front() == Some(&game_id) {
+            entry.playlist.rotate_left(1);
+        }
+        self.report_game_change(room_code);
 
         Ok(())
     }
 
+    /// Start `game_id`'s tutorial for `player_id` in the room they just
+    /// created: fill it with easy bots up to the game's minimum, cap it at
+    /// that size so anyone else waits in its join queue, and start a
+    /// one-round tutorial session.
+    pub fn start_tutorial(
+        &mut self,
+        room_code: &str,
+        player_id: PlayerId,
+        game_id: GameId,
+        registry: &std::sync::Arc<ServerGameRegistry>,
+        rooms: crate::state::SharedRoomManager,
+    ) -> Result<(), String> {
+        if registry.tutorial_objectives(game_id).is_empty() {
+            return Err(format!("No tutorial for {game_id}"));
+        }
+        let min_players = registry
+            .create(game_id)
+            .map_or(1, |game| usize::from(game.metadata().min_players));
+        let present = self
+            .rooms
+            .get(room_code)
+            .ok_or_else(|| "Room not found".to_string())?
+            .room
+            .players
+            .len();
+        for _ in present..min_players {
+            self.add_bot(room_code, player_id, BotDifficulty::Easy)?;
+        }
+        if let Some(entry) = self.rooms.get_mut(room_code) {
+            entry.room.config.max_players =
+                u8::try_from(entry.room.players.len()).unwrap_or(u8::MAX);
+        }
+        let request = RequestGameStartMsg {
+            game_name: game_id.as_str().to_string(),
+            custom: [(
+                tutorial::TUTORIAL_SETTING.to_string(),
+                serde_json::json!(true),
+            )]
+            .into(),
+            win_condition: None,
+            pacing: None,
+            featured: false,
+        };
+        self.start_game(room_code, request, player_id, registry, rooms)
+    }
+
+    /// Note the tick of an input that arrived on `connection_id`'s
+    /// connection, for handing its sequence on to a migrated transport.
+    pub fn note_input_tick(&self, room_code: &str, connection_id: PlayerId, tick: u32) {
+        if let Some(conn) = self
+            .rooms
+            .get(room_code)
+            .and_then(|entry| entry.connections.get(&connection_id))
+        {
+            conn.last_input_tick.fetch_max(tick, Ordering::Relaxed);
+        }
+    }
+
     /// Route a player's input to the active game session.
     pub fn route_player_input(
         &self,
@@ -521,6 +1997,189 @@ impl RoomManager {
         }
     }
 
+    /// Note a connection's reported round trip for its player and any
+    /// hot-seat guests sharing it, and pass it to the running game for the
+    /// scoreboard.
+    pub fn record_ping(&mut self, room_code: &str, player_id: PlayerId, rtt_ms: u32) {
+        let Some(entry) = self.rooms.get_mut(room_code) else {
+            return;
+        };
+        let rtt_ms = u16::try_from(rtt_ms).unwrap_or(u16::MAX);
+        let sharing: Vec<PlayerId> = entry
+            .room
+            .players
+            .iter()
+            .filter(|p| p.id == player_id || p.hot_seat_of == Some(player_id))
+            .map(|p| p.id)
+            .collect();
+        for id in sharing {
+            entry.pings.insert(id, rtt_ms);
+            if let Some(ref cmd_tx) = entry.game_command_tx
+                && let Err(e) = cmd_tx.send(GameCommand::Ping {
+                    player_id: id,
+                    rtt_ms,
+                })
+            {
+                tracing::debug!(player_id, room = room_code, error = %e, "Game session gone");
+            }
+        }
+    }
+
+    /// Route a player's course ballot to the active game session.
+    pub fn route_course_vote(&self, room_code: &str, player_id: PlayerId, round: u8, choice: u8) {
+        if let Some(entry) = self.rooms.get(room_code)
+            && let Some(ref cmd_tx) = entry.game_command_tx
+            && let Err(e) = cmd_tx.send(GameCommand::CourseVote {
+                player_id,
+                round,
+                choice,
+            })
+        {
+            tracing::debug!(player_id, room = room_code, error = %e, "Game session gone");
+        }
+    }
+
+    /// Route a captain's team draft pick to the active game session.
+    pub fn route_draft_pick(&self, room_code: &str, player_id: PlayerId, pick: PlayerId) {
+        if let Some(entry) = self.rooms.get(room_code)
+            && let Some(ref cmd_tx) = entry.game_command_tx
+            && let Err(e) = cmd_tx.send(GameCommand::DraftPick { player_id, pick })
+        {
+            tracing::debug!(player_id, room = room_code, error = %e, "Game session gone");
+        }
+    }
+
+    /// Ask the room's game session to start the round after `round`. Only
+    /// the leader can skip the rest of an intermission.
+    pub fn advance_round(
+        &self,
+        room_code: &str,
+        requester_id: PlayerId,
+        round: u8,
+    ) -> Result<(), String> {
+        let entry = self
+            .rooms
+            .get(room_code)
+            .ok_or_else(|| "Room not found".to_string())?;
+        if entry.room.leader_id != requester_id {
+            return Err("Only the room leader can start the next round".to_string());
+        }
+        let cmd_tx = entry
+            .game_command_tx
+            .as_ref()
+            .ok_or_else(|| "No game in progress".to_string())?;
+        cmd_tx
+            .send(GameCommand::AdvanceRound { round })
+            .map_err(|_| "Game session gone".to_string())
+    }
+
+    /// Pass a leader's debug command to the room's game session. Refused
+    /// unless the server was built with `debug-commands` and `config`
+    /// enables them, and once the room's debug window (opened by its first
+    /// command) has run out.
+    pub fn debug_command(
+        &mut self,
+        room_code: &str,
+        requester_id: PlayerId,
+        command: DebugCommand,
+        config: &DebugConfig,
+    ) -> Result<(), String> {
+        if !cfg!(feature = "debug-commands") {
+            return Err("Debug commands are not built into this server".to_string());
+        }
+        if !config.enabled {
+            return Err("Debug commands are disabled".to_string());
+        }
+        let entry = self
+            .rooms
+            .get_mut(room_code)
+            .ok_or_else(|| "Room not found".to_string())?;
+        if entry.room.leader_id != requester_id {
+            return Err("Only the room leader can send debug commands".to_string());
+        }
+        let cmd_tx = entry
+            .game_command_tx
+            .as_ref()
+            .ok_or_else(|| "No game in progress".to_string())?;
+        let opened = *entry.debug_opened.get_or_insert_with(Instant::now);
+        if opened.elapsed() > Duration::from_secs(config.window_secs) {
+            return Err("This room's debug window has closed".to_string());
+        }
+        cmd_tx
+            .send(GameCommand::Debug { command })
+            .map_err(|_| "Game session gone".to_string())
+    }
+
+    /// Pass a leader's mid-round settings change to the room's game
+    /// session, which applies whatever its game accepts live.
+    pub fn change_game_config(
+        &self,
+        room_code: &str,
+        requester_id: PlayerId,
+        delta: GameConfigDelta,
+    ) -> Result<(), String> {
+        let entry = self
+            .rooms
+            .get(room_code)
+            .ok_or_else(|| "Room not found".to_string())?;
+        if entry.room.leader_id != requester_id {
+            return Err("Only the room leader can change settings mid-round".to_string());
+        }
+        let cmd_tx = entry
+            .game_command_tx
+            .as_ref()
+            .ok_or_else(|| "No game in progress".to_string())?;
+        cmd_tx
+            .send(GameCommand::ConfigChange { delta })
+            .map_err(|_| "Game session gone".to_string())
+    }
+
+    /// Snapshot input statistics for every room that has run a game session.
+    pub fn input_stats(&self) -> Vec<RoomInputStats> {
+        let mut out: Vec<RoomInputStats> = self
+            .rooms
+            .iter()
+            .filter_map(|(code, entry)| {
+                let tracker = entry.input_stats.as_ref()?.lock().ok()?;
+                Some(RoomInputStats {
+                    room_code: code.clone(),
+                    players: tracker.snapshot(),
+                })
+            })
+            .collect();
+        out.sort_by(|a, b| a.room_code.cmp(&b.room_code));
+        out
+    }
+
+    /// Memory accounting for every room that has run a game session,
+    /// largest first. Outbound backlogs are measured now; the rest is the
+    /// session's latest sample.
+    pub fn memory_stats(&self) -> Vec<RoomMemoryStats> {
+        let mut out: Vec<RoomMemoryStats> = self
+            .rooms
+            .iter()
+            .filter_map(|(code, entry)| {
+                let mut memory = entry.memory.as_ref()?.lock().ok()?.snapshot();
+                memory.current.backlog_bytes = entry
+                    .connections
+                    .values()
+                    .map(|conn| conn.sender.queued_bytes())
+                    .sum();
+                Some(RoomMemoryStats {
+                    room_code: code.clone(),
+                    total_bytes: memory.current.total(),
+                    memory,
+                })
+            })
+            .collect();
+        out.sort_by(|a, b| {
+            b.total_bytes
+                .cmp(&a.total_bytes)
+                .then_with(|| a.room_code.cmp(&b.room_code))
+        });
+        out
+    }
+
     /// Check if a room has an active game session.
     pub fn has_active_game(&self, room_code: &str) -> bool {
         self.rooms
@@ -541,14 +2200,61 @@ impl RoomManager {
             entry.game_task = None;
             entry.broadcast_task = None;
             entry.room.state = RoomState::Lobby;
+            entry.game = None;
+            self.report_game_change(room_code);
+        }
+    }
+
+    /// The game being played in a room, if one is running.
+    pub fn current_game(&self, room_code: &str) -> Option<GameId> {
+        self.rooms.get(room_code)?.game
+    }
+
+    fn report_game_change(&self, room_code: &str) {
+        if let Some(ref sink) = self.game_changes {
+            let _ = sink.send(room_code.to_string());
+        }
+    }
+
+    /// Apply a click to the room's lobby toy while it waits in the lobby,
+    /// sharing the toy with the room when the click scored.
+    pub fn lobby_toy_input(&mut self, room_code: &str, player_id: PlayerId, input_data: &[u8]) {
+        let Some(entry) = self.rooms.get_mut(room_code) else {
+            return;
+        };
+        if entry.room.state != RoomState::Lobby {
+            return;
+        }
+        let dt = entry.lobby_toy_at.elapsed().as_secs_f32();
+        entry.lobby_toy_at = Instant::now();
+        entry.lobby_toy.apply_input(player_id, input_data);
+        let events = entry.lobby_toy.update(
+            dt,
+            &PlayerInputs {
+                inputs: HashMap::new(),
+            },
+        );
+        if !events.is_empty()
+            && let Some(data) = encode_lobby_toy(&entry.lobby_toy)
+        {
+            self.broadcast_to_room(room_code, Lane::Cosmetic, &data);
+        }
+    }
+
+    /// The lobby toy's state for a player joining a room in its lobby.
+    pub fn lobby_toy_state(&self, room_code: &str) -> Option<Vec<u8>> {
+        let entry = self.rooms.get(room_code)?;
+        if entry.room.state != RoomState::Lobby {
+            return None;
         }
+        Some(entry.lobby_toy.serialize_state())
     }
 
-    /// Send a raw binary message to a specific player.
-    pub fn send_to_player(&self, room_code: &str, player_id: PlayerId, data: Bytes) {
+    /// Send a raw binary message to a specific player on `lane`.
+    pub fn send_to_player(&self, room_code: &str, player_id: PlayerId, lane: Lane, data: Bytes) {
         if let Some(entry) = self.rooms.get(room_code)
             && let Some(conn) = entry.connections.get(&player_id)
-            && let Err(e) = conn.sender.try_send(data)
+            && let Err(e) = conn.sender.try_send(lane, data)
         {
             tracing::debug!(
                 player_id, room = room_code, error = %e,
@@ -559,11 +2265,11 @@ impl RoomManager {
 
     /// Broadcast raw binary data to all players in a room.
     /// Uses `Bytes` internally for zero-copy cloning across player channels.
-    pub fn broadcast_to_room(&self, room_code: &str, data: &[u8]) {
+    pub fn broadcast_to_room(&self, room_code: &str, lane: Lane, data: &[u8]) {
         if let Some(entry) = self.rooms.get(room_code) {
             let bytes = Bytes::copy_from_slice(data);
             for (&pid, conn) in &entry.connections {
-                if let Err(e) = conn.sender.try_send(bytes.clone()) {
+                if let Err(e) = conn.sender.try_send(lane, bytes.clone()) {
                     tracing::debug!(
                         player_id = pid, room = room_code, error = %e,
                         "Skipping broadcast to slow client"
@@ -574,12 +2280,18 @@ impl RoomManager {
     }
 
     /// Broadcast raw binary data to all players except one.
-    pub fn broadcast_to_room_except(&self, room_code: &str, exclude: PlayerId, data: &[u8]) {
+    pub fn broadcast_to_room_except(
+        &self,
+        room_code: &str,
+        exclude: PlayerId,
+        lane: Lane,
+        data: &[u8],
+    ) {
         if let Some(entry) = self.rooms.get(room_code) {
             let bytes = Bytes::copy_from_slice(data);
             for (&id, conn) in &entry.connections {
                 if id != exclude
-                    && let Err(e) = conn.sender.try_send(bytes.clone())
+                    && let Err(e) = conn.sender.try_send(lane, bytes.clone())
                 {
                     tracing::debug!(
                         player_id = id, room = room_code, error = %e,
@@ -596,11 +2308,17 @@ impl RoomManager {
             let msg = ServerMessage::PlayerList(PlayerListMsg {
                 players: entry.room.players.clone(),
                 leader_id: entry.room.leader_id,
+                session_scores: entry.session_scores.clone(),
+                playlist: entry
+                    .playlist
+                    .iter()
+                    .map(|g| g.as_str().to_string())
+                    .collect(),
             });
             if let Ok(data) = encode_server_message(&msg) {
                 let bytes = Bytes::from(data);
                 for (&pid, conn) in &entry.connections {
-                    if let Err(e) = conn.sender.try_send(bytes.clone()) {
+                    if let Err(e) = conn.sender.try_send(Lane::Control, bytes.clone()) {
                         tracing::debug!(
                             player_id = pid, room = room_code, error = %e,
                             "Skipping player list broadcast to slow client"
@@ -611,12 +2329,26 @@ impl RoomManager {
         }
     }
 
+    /// Send the join queue to the room's players, and each waiting
+    /// connection its place in line.
+    pub fn broadcast_join_queue(&self, room_code: &str) {
+        let Some(entry) = self.rooms.get(room_code) else {
+            return;
+        };
+        entry.join_queue.notify_positions(room_code);
+        let msg = ServerMessage::JoinQueueUpdate(entry.join_queue.update_msg());
+        if let Ok(data) = encode_server_message(&msg) {
+            self.broadcast_to_room(room_code, Lane::Control, &data);
+        }
+    }
+
     /// Build a JoinRoomResponse success message.
     pub fn make_join_response(
         player_id: PlayerId,
         room_code: &str,
         room_state: RoomState,
         session_token: &str,
+        lobby_toy: Option<Vec<u8>>,
     ) -> Result<Vec<u8>, breakpoint_core::net::protocol::ProtocolError> {
         let msg = ServerMessage::JoinRoomResponse(JoinRoomResponseMsg {
             success: true,
@@ -625,32 +2357,34 @@ impl RoomManager {
             room_state: Some(room_state),
             error: None,
             session_token: Some(session_token.to_string()),
+            lobby_toy,
         });
         encode_server_message(&msg)
     }
 
     /// Build a JoinRoomResponse error message.
     pub fn make_join_error(
-        error: &str,
+        error: LocalizedText,
     ) -> Result<Vec<u8>, breakpoint_core::net::protocol::ProtocolError> {
         let msg = ServerMessage::JoinRoomResponse(JoinRoomResponseMsg {
             success: false,
             player_id: None,
             room_code: None,
             room_state: None,
-            error: Some(error.to_string()),
+            error: Some(error),
             session_token: None,
+            lobby_toy: None,
         });
         encode_server_message(&msg)
     }
 
     /// Broadcast raw binary data to all players in all rooms.
     /// Uses `Bytes` for zero-copy cloning across all player channels.
-    pub fn broadcast_to_all_rooms(&self, data: &[u8]) {
+    pub fn broadcast_to_all_rooms(&self, lane: Lane, data: &[u8]) {
         let bytes = Bytes::copy_from_slice(data);
         for (room_code, entry) in &self.rooms {
             for (&pid, conn) in &entry.connections {
-                if let Err(e) = conn.sender.try_send(bytes.clone()) {
+                if let Err(e) = conn.sender.try_send(lane, bytes.clone()) {
                     tracing::debug!(
                         player_id = pid, room = %room_code, error = %e,
                         "Skipping global broadcast to slow client"
@@ -660,6 +2394,25 @@ impl RoomManager {
         }
     }
 
+    /// Send an encoded alert event to every room whose filter accepts it.
+    /// Alerts travel on the control lane.
+    pub fn broadcast_event(&self, event: &Event, data: &[u8]) {
+        let bytes = Bytes::copy_from_slice(data);
+        for (room_code, entry) in &self.rooms {
+            if !entry.room.config.overlay_config.accepts_event(event) {
+                continue;
+            }
+            for (&pid, conn) in &entry.connections {
+                if let Err(e) = conn.sender.try_send(Lane::Control, bytes.clone()) {
+                    tracing::debug!(
+                        player_id = pid, room = %room_code, error = %e,
+                        "Skipping event broadcast to slow client"
+                    );
+                }
+            }
+        }
+    }
+
     /// Look up a player's display name by room code and player id.
     pub fn get_player_name(&self, room_code: &str, player_id: PlayerId) -> Option<String> {
         self.rooms
@@ -682,10 +2435,22 @@ impl RoomManager {
     /// Returns the number of rooms removed.
     pub fn cleanup_idle_rooms(&mut self, max_idle: Duration) -> usize {
         let now = Instant::now();
-        let before = self.rooms.len();
-        self.rooms
-            .retain(|_, entry| now.duration_since(entry.last_activity) < max_idle);
-        before - self.rooms.len()
+        let idle: Vec<String> = self
+            .rooms
+            .iter()
+            .filter(|(_, entry)| now.duration_since(entry.last_activity) >= max_idle)
+            .map(|(code, _)| code.clone())
+            .collect();
+        for code in &idle {
+            self.rooms.remove(code);
+            self.emit(RoomActivity::RoomClosed {
+                room_code: code.clone(),
+            });
+        }
+        let rooms = &self.rooms;
+        self.aliases.retain(|_, code| rooms.contains_key(code));
+        self.admit_room_queue();
+        idle.len()
     }
 
     /// Return (active_room_count, total_player_count) for health reporting.
@@ -695,6 +2460,31 @@ impl RoomManager {
         (rooms, players)
     }
 
+    /// What lifecycle hooks are told about a room, as of `now` (unix
+    /// seconds), or `None` if it has closed.
+    pub fn room_metadata(&self, room_code: &str, now: u64) -> Option<RoomMetadata> {
+        let entry = self.rooms.get(room_code)?;
+        let room = &entry.room;
+        Some(RoomMetadata {
+            room_code: room_code.to_string(),
+            alias: self
+                .aliases
+                .iter()
+                .find(|(_, code)| *code == room_code)
+                .map(|(alias, _)| alias.clone()),
+            host: room
+                .players
+                .iter()
+                .find(|p| p.id == room.leader_id)
+                .map(|p| p.display_name.clone()),
+            max_players: room.config.max_players,
+            // Only the scheduler opens rooms with nobody in them
+            scheduled: room.players.is_empty(),
+            created_at: now,
+            closed_at: None,
+        })
+    }
+
     /// Check if a room exists.
     #[cfg(test)]
     pub fn room_exists(&self, room_code: &str) -> bool {
@@ -702,44 +2492,102 @@ impl RoomManager {
     }
 }
 
-/// Forward game broadcasts to all connected players in a room.
+/// Forward game broadcasts to all connected players in a room, each on the
+/// lane for its message type.
 /// Uses a shared sender map so reconnected clients are included dynamically.
+/// Returns the game's final scores, for the room's session scores.
 async fn forward_broadcasts(
     mut broadcast_rx: mpsc::UnboundedReceiver<crate::game_loop::GameBroadcast>,
     senders: Arc<Mutex<HashMap<PlayerId, PlayerSender>>>,
     room_code: &str,
-) {
+    reporter: Option<ResultReporter>,
+    season_scorer: Option<SeasonScorer>,
+) -> Vec<PlayerScoreEntry> {
+    let mut final_scores = Vec::new();
     while let Some(broadcast) = broadcast_rx.recv().await {
-        match broadcast {
-            GameBroadcast::EncodedMessage(data) => {
-                let Ok(guard) = senders.lock() else {
-                    tracing::error!(room = room_code, "Broadcast senders mutex poisoned");
-                    break;
-                };
-                let snapshot = guard.clone();
-                drop(guard);
-                for (&player_id, sender) in &snapshot {
-                    if sender.try_send(data.clone()).is_err() {
-                        tracing::debug!(
-                            player_id,
-                            room = room_code,
-                            "Skipping broadcast to slow client (channel full or closed)"
-                        );
-                    }
-                }
+        let (data, views) = match broadcast {
+            GameBroadcast::EncodedMessage(data) => (data, HashMap::new()),
+            GameBroadcast::PlayerViews { shared, views } => (shared, views),
+            GameBroadcast::Private(messages) => {
+                send_private(&senders, room_code, messages);
+                continue;
             },
             GameBroadcast::GameEnded => {
                 tracing::info!(room = room_code, "Game session ended");
                 break;
             },
+        };
+        if let Some(ref reporter) = reporter {
+            reporter.observe(&data);
+        }
+        if let Some(ref scorer) = season_scorer {
+            scorer.observe(&data);
+        }
+        if data.first() == Some(&(MessageType::GameEnd as u8))
+            && let Ok(ServerMessage::GameEnd(end)) = decode_server_message(&data)
+        {
+            final_scores = end.final_scores;
+        }
+        let Ok(guard) = senders.lock() else {
+            tracing::error!(room = room_code, "Broadcast senders mutex poisoned");
+            break;
+        };
+        let snapshot = guard.clone();
+        drop(guard);
+        let lane = Lane::of(&data);
+        for (&player_id, sender) in &snapshot {
+            let data = views.get(&player_id).unwrap_or(&data);
+            if sender.try_send(lane, data.clone()).is_err() {
+                tracing::debug!(
+                    player_id,
+                    room = room_code,
+                    "Skipping broadcast to slow client (channel full or closed)"
+                );
+            }
+        }
+    }
+    final_scores
+}
+
+/// Deliver private messages, each to its one connection only. A player who
+/// isn't connected just misses theirs.
+fn send_private(
+    senders: &Mutex<HashMap<PlayerId, PlayerSender>>,
+    room_code: &str,
+    messages: Vec<(PlayerId, Bytes)>,
+) {
+    let Ok(guard) = senders.lock() else {
+        tracing::error!(room = room_code, "Broadcast senders mutex poisoned");
+        return;
+    };
+    for (player_id, data) in messages {
+        let Some(sender) = guard.get(&player_id) else {
+            continue;
+        };
+        if sender.try_send(Lane::of(&data), data).is_err() {
+            tracing::debug!(
+                player_id,
+                room = room_code,
+                "Skipping private state to slow client (channel full or closed)"
+            );
         }
     }
 }
 
+fn encode_lobby_toy(toy: &TargetPractice) -> Option<Vec<u8>> {
+    let msg = ServerMessage::LobbyToyState(LobbyToyStateMsg {
+        state_data: toy.serialize_state(),
+    });
+    encode_server_message(&msg).ok()
+}
+
 /// Generate a unique room code, retrying on collision with existing rooms.
-fn generate_unique_room_code(existing: &HashMap<String, RoomEntry>) -> String {
+fn generate_unique_room_code(
+    existing: &HashMap<String, RoomEntry>,
+    format: &RoomCodeFormat,
+) -> String {
     loop {
-        let code = breakpoint_core::room::generate_room_code();
+        let code = format.generate();
         if !existing.contains_key(&code) {
             return code;
         }
@@ -751,8 +2599,8 @@ mod tests {
     use super::*;
     use breakpoint_core::player::PlayerColor;
 
-    fn make_sender() -> (PlayerSender, mpsc::Receiver<Bytes>) {
-        mpsc::channel(256)
+    fn make_sender() -> (PlayerSender, crate::outbound::OutboundReceiver) {
+        crate::outbound::channel(256)
     }
 
     #[test]
@@ -780,6 +2628,61 @@ mod tests {
         assert_eq!(players.len(), 2);
     }
 
+    #[test]
+    fn clashing_colors_are_reassigned_on_join() {
+        let mut mgr = RoomManager::new();
+        let (tx1, _rx1) = make_sender();
+        let (code, ..) = mgr.create_room("Alice".into(), PlayerColor::PALETTE[1], tx1);
+
+        let (tx2, _rx2) = make_sender();
+        mgr.join_room(&code, "Bob".into(), PlayerColor::PALETTE[1], tx2)
+            .unwrap();
+        let (tx3, _rx3) = make_sender();
+        mgr.join_room(&code, "Carol".into(), PlayerColor::PALETTE[3], tx3)
+            .unwrap();
+        for _ in 0..3 {
+            mgr.add_bot(&code, 1, BotDifficulty::default()).unwrap();
+        }
+
+        let players = mgr.get_players(&code).unwrap();
+        assert_eq!(players[0].color, PlayerColor::PALETTE[1]);
+        assert_ne!(
+            players[1].color,
+            PlayerColor::PALETTE[1],
+            "Bob's pick clashed"
+        );
+        assert_eq!(
+            players[2].color,
+            PlayerColor::PALETTE[3],
+            "Carol's pick stands"
+        );
+        for (i, a) in players.iter().enumerate() {
+            for b in &players[i + 1..] {
+                assert!(palette::distance(a.color, b.color) >= palette::MIN_DISTANCE);
+            }
+        }
+    }
+
+    #[test]
+    fn first_joiner_leads_reserved_room() {
+        let mut mgr = RoomManager::new();
+        let code = mgr.create_reserved_room();
+        assert!(mgr.get_players(&code).unwrap().is_empty());
+
+        let (tx1, _rx1) = make_sender();
+        let (alice, _) = mgr
+            .join_room(&code, "Alice".into(), PlayerColor::default(), tx1)
+            .unwrap();
+        let (tx2, _rx2) = make_sender();
+        mgr.join_room(&code, "Bob".into(), PlayerColor::PALETTE[1], tx2)
+            .unwrap();
+
+        let players = mgr.get_players(&code).unwrap();
+        assert!(players[0].is_leader);
+        assert_eq!(players[0].id, alice);
+        assert!(!players[1].is_leader);
+    }
+
     #[test]
     fn join_nonexistent_room_fails() {
         let mut mgr = RoomManager::new();
@@ -805,7 +2708,161 @@ mod tests {
         let (tx_extra, _rx_extra) = make_sender();
         let result = mgr.join_room(&code, "Extra".into(), PlayerColor::default(), tx_extra);
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("full"));
+        assert_eq!(result.unwrap_err().key, TextKey::RoomFull);
+    }
+
+    #[test]
+    fn leaving_admits_queued_joiner() {
+        let mut mgr = RoomManager::new();
+        let (tx, _rx) = make_sender();
+        let (code, _, _) = mgr.create_room("Host".to_string(), PlayerColor::default(), tx);
+        let mut others = Vec::new();
+        for i in 0..7 {
+            let (tx, _rx) = make_sender();
+            let (id, _) = mgr
+                .join_room(&code, format!("P{i}"), PlayerColor::default(), tx)
+                .unwrap();
+            others.push(id);
+        }
+
+        let (tx, mut queued_rx) = make_sender();
+        let ip = IpAddr::from([10, 0, 0, 1]);
+        let Ok(JoinRequest::Queued { mut admission, .. }) =
+            mgr.request_join(&code, "Late".to_string(), PlayerColor::default(), tx, ip)
+        else {
+            panic!("Full room should queue the joiner");
+        };
+        assert!(
+            queued_rx.try_recv().is_some(),
+            "Queued player is told its position"
+        );
+        assert!(admission.try_recv().is_err());
+
+        mgr.leave_room(&code, others[0]);
+        let admitted = admission.try_recv().unwrap().unwrap();
+        let players = mgr.get_players(&code).unwrap();
+        assert_eq!(players.len(), 8);
+        assert!(players.iter().any(|p| p.id == admitted.player_id));
+
+        // With approval on, the last player leaving lets the line in
+        // instead of closing the room
+        let (tx, _rx) = make_sender();
+        let (solo, solo_host, _) = mgr.create_room("Solo".to_string(), PlayerColor::default(), tx);
+        mgr.manage_join_queue(&solo, solo_host, JoinQueueAction::RequireApproval(true))
+            .unwrap();
+        let (tx, _rx) = make_sender();
+        let Ok(JoinRequest::Queued { mut admission, .. }) =
+            mgr.request_join(&solo, "Later".to_string(), PlayerColor::default(), tx, ip)
+        else {
+            panic!("Approval should queue the joiner");
+        };
+        assert!(mgr.leave_room(&solo, solo_host).is_none());
+        let admitted = admission.try_recv().unwrap().unwrap();
+        assert_eq!(mgr.get_leader_id(&solo), Some(admitted.player_id));
+    }
+
+    #[test]
+    fn room_creation_is_capped_per_ip_and_server_wide() {
+        let mut mgr = RoomManager::new();
+        mgr.set_room_capacity(RoomCapacity {
+            max_rooms: 2,
+            max_rooms_per_ip: 1,
+            queue_max: 0,
+        });
+        let (a, b, c) = (
+            IpAddr::from([10, 0, 0, 1]),
+            IpAddr::from([10, 0, 0, 2]),
+            IpAddr::from([10, 0, 0, 3]),
+        );
+        let request = |mgr: &mut RoomManager, ip| {
+            let (tx, _rx) = make_sender();
+            mgr.request_room("P".to_string(), PlayerColor::default(), tx, ip, None)
+        };
+
+        let Ok(RoomRequest::Created {
+            room_code,
+            player_id,
+            ..
+        }) = request(&mut mgr, a)
+        else {
+            panic!("First room should open");
+        };
+        let err = request(&mut mgr, a).err().unwrap();
+        assert_eq!(err.key, TextKey::TooManyRooms);
+        assert!(matches!(
+            request(&mut mgr, b),
+            Ok(RoomRequest::Created { .. })
+        ));
+        let err = request(&mut mgr, c).err().unwrap();
+        assert_eq!(err.key, TextKey::ServerFull, "At the cap with no line");
+
+        // Closing a room frees the slot and the creator's share
+        mgr.leave_room(&room_code, player_id);
+        assert!(matches!(
+            request(&mut mgr, a),
+            Ok(RoomRequest::Created { .. })
+        ));
+    }
+
+    #[test]
+    fn vanity_aliases_resolve_to_their_room() {
+        let mut mgr = RoomManager::new();
+        mgr.set_code_format(RoomCodeFormat {
+            length: 12,
+            alphabet: String::new(),
+        });
+        mgr.set_room_capacity(RoomCapacity {
+            max_rooms: 1,
+            max_rooms_per_ip: 5,
+            queue_max: 4,
+        });
+        let ip = IpAddr::from([10, 0, 0, 1]);
+        let request = |mgr: &mut RoomManager, alias| {
+            let (tx, _rx) = make_sender();
+            mgr.request_room("P".to_string(), PlayerColor::default(), tx, ip, alias)
+        };
+
+        let Ok(RoomRequest::Created {
+            room_code,
+            player_id,
+            ..
+        }) = request(&mut mgr, Some("friday-golf"))
+        else {
+            panic!("Room should open");
+        };
+        assert!(mgr.code_format().is_valid(&room_code), "{room_code}");
+        assert_eq!(mgr.resolve_room_code("Friday-Golf"), room_code);
+        assert_eq!(mgr.resolve_room_code("NOPE-NOPE"), "NOPE-NOPE");
+
+        let err = request(&mut mgr, Some("FRIDAY-GOLF")).err().unwrap();
+        assert_eq!(err.key, TextKey::RoomAliasTaken);
+        let err = request(&mut mgr, Some("admin")).err().unwrap();
+        assert_eq!(err.key, TextKey::InvalidRoomAlias);
+
+        // A queued creator holds its alias until its room opens
+        let Ok(RoomRequest::Queued { mut admission, .. }) = request(&mut mgr, Some("LATE-SHOW"))
+        else {
+            panic!("At the cap, creators wait");
+        };
+        let err = request(&mut mgr, Some("late-show")).err().unwrap();
+        assert_eq!(err.key, TextKey::RoomAliasTaken);
+
+        // Closing the first room frees its alias and admits the queued one
+        mgr.leave_room(&room_code, player_id);
+        assert_eq!(mgr.resolve_room_code("FRIDAY-GOLF"), "FRIDAY-GOLF");
+        mgr.cleanup_idle_rooms(Duration::from_secs(3600));
+        let admitted = admission.try_recv().unwrap().unwrap();
+        assert_eq!(mgr.resolve_room_code("LATE-SHOW"), admitted.room_code);
+
+        // An admin can take an alias back without closing its room
+        assert_eq!(
+            mgr.vanity_aliases(),
+            vec![("LATE-SHOW".to_string(), admitted.room_code.clone(), None)]
+        );
+        assert!(mgr.release_alias("late-show"));
+        assert!(!mgr.release_alias("late-show"));
+        assert_eq!(mgr.resolve_room_code("LATE-SHOW"), "LATE-SHOW");
+        assert!(mgr.vanity_aliases().is_empty());
     }
 
     #[test]
@@ -836,6 +2893,57 @@ mod tests {
         assert!(!mgr.room_exists(&code));
     }
 
+    #[test]
+    fn room_lifecycle_is_reported() {
+        let mut mgr = RoomManager::new();
+        let (activity_tx, mut activity_rx) = mpsc::channel(8);
+        mgr.report_activity(RoomActivitySink::new(activity_tx));
+        let (tx, _rx) = make_sender();
+        let (code, leader_id, _) = mgr.create_room("Alice".into(), PlayerColor::default(), tx);
+        let idle = mgr.create_reserved_room();
+        mgr.leave_room(&code, leader_id);
+        assert_eq!(mgr.cleanup_idle_rooms(Duration::ZERO), 1);
+
+        let kinds: Vec<_> = std::iter::from_fn(|| activity_rx.try_recv().ok())
+            .map(|a| (a.kind(), a))
+            .collect();
+        assert_eq!(kinds.len(), 4);
+        assert_eq!(
+            kinds[1].1,
+            RoomActivity::RoomCreated {
+                room_code: idle.clone()
+            }
+        );
+        assert_eq!(kinds[2].0, "room_closed");
+        assert_eq!(kinds[3].1, RoomActivity::RoomClosed { room_code: idle });
+    }
+
+    #[test]
+    fn room_metadata_names_the_host_and_alias() {
+        let mut mgr = RoomManager::new();
+        let (tx, _rx) = make_sender();
+        let Ok(RoomRequest::Created { room_code, .. }) = mgr.request_room(
+            "Alice".to_string(),
+            PlayerColor::default(),
+            tx,
+            IpAddr::from([10, 0, 0, 1]),
+            Some("friday-golf"),
+        ) else {
+            panic!("Room should open");
+        };
+        let meta = mgr.room_metadata(&room_code, 1_700_000_000).unwrap();
+        assert_eq!(meta.alias.as_deref(), Some("FRIDAY-GOLF"));
+        assert_eq!(meta.host.as_deref(), Some("Alice"));
+        assert!(!meta.scheduled);
+        assert_eq!(meta.created_at, 1_700_000_000);
+
+        let reserved = mgr.create_reserved_room();
+        let meta = mgr.room_metadata(&reserved, 0).unwrap();
+        assert!(meta.scheduled);
+        assert_eq!(meta.host, None);
+        assert!(mgr.room_metadata("GONE-GONE", 0).is_none());
+    }
+
     #[test]
     fn host_migration_on_leave() {
         let mut mgr = RoomManager::new();
@@ -938,6 +3046,107 @@ mod tests {
         assert_ne!(new_token, token); // new token issued
     }
 
+    #[test]
+    fn lost_session_token_reclaims_slot_by_identity() {
+        let mut mgr = RoomManager::new();
+        let (tx1, _rx1) = make_sender();
+        let (code, alice, _) = mgr.create_room("Alice".into(), PlayerColor::default(), tx1);
+        let (tx2, _rx2) = make_sender();
+        let (bob, _) = mgr
+            .join_room(&code, "Bob".into(), PlayerColor::PALETTE[1], tx2)
+            .unwrap();
+        let ada = PlayerIdentity::from_digest(&[1; 6]);
+        let bo = PlayerIdentity::from_digest(&[2; 6]);
+        mgr.bind_identity(&code, alice, ada.clone());
+        mgr.bind_identity(&code, bob, bo.clone());
+        assert_eq!(mgr.identities_in_room(&code), [ada.clone(), bo.clone()]);
+        mgr.set_room_state(&code, RoomState::InGame);
+        mgr.leave_room(&code, alice);
+        assert_eq!(mgr.identities_in_room(&code), vec![bo.clone()]);
+
+        // Someone else's identity can't take the preserved slot
+        let (tx, _rx) = make_sender();
+        assert!(mgr.reconnect_identity(&code, &bo, tx).is_err());
+        let (tx, _rx) = make_sender();
+        let (recon_code, recon_pid, _) = mgr.reconnect_identity(&code, &ada, tx).unwrap();
+        assert_eq!((recon_code.as_str(), recon_pid), (code.as_str(), alice));
+        assert_eq!(mgr.identity_of(&code, alice), Some(&ada));
+
+        // Leaving for good forgets the identity
+        mgr.set_room_state(&code, RoomState::Lobby);
+        mgr.leave_room(&code, alice);
+        assert_eq!(mgr.identity_of(&code, alice), None);
+        let (tx, _rx) = make_sender();
+        assert!(mgr.reconnect_identity(&code, &ada, tx).is_err());
+    }
+
+    #[test]
+    fn event_filter_is_set_by_leader_and_applied_per_room() {
+        use breakpoint_core::events::EventType;
+        use breakpoint_core::test_helpers::make_test_event;
+
+        let mut mgr = RoomManager::new();
+        let (tx1, mut rx1) = make_sender();
+        let (filtered, alice, _) = mgr.create_room("Alice".into(), PlayerColor::default(), tx1);
+        let (tx2, _rx2) = make_sender();
+        let (bob, _) = mgr
+            .join_room(&filtered, "Bob".into(), PlayerColor::PALETTE[1], tx2)
+            .unwrap();
+        let (tx3, mut rx3) = make_sender();
+        mgr.create_room("Carol".into(), PlayerColor::default(), tx3);
+
+        let config = OverlayRoomConfig {
+            event_types: vec![EventType::PipelineFailed],
+            ..OverlayRoomConfig::default()
+        };
+        assert!(
+            mgr.set_overlay_config(&filtered, bob, config.clone())
+                .is_err()
+        );
+        mgr.set_overlay_config(&filtered, alice, config).unwrap();
+        while rx1.try_recv().is_some() {}
+
+        mgr.broadcast_event(&make_test_event("pr"), b"pr");
+        assert!(rx1.try_recv().is_none(), "Filtered room skips PR events");
+        assert!(rx3.try_recv().is_some(), "Unfiltered room gets everything");
+
+        let mut failure = make_test_event("ci");
+        failure.event_type = EventType::PipelineFailed;
+        mgr.broadcast_event(&failure, b"ci");
+        assert!(rx1.try_recv().is_some());
+    }
+
+    #[test]
+    fn lobby_toy_hits_are_shared_only_in_the_lobby() {
+        use breakpoint_core::lobby_toy::{TargetClick, TargetPracticeState};
+        use breakpoint_core::net::protocol::decode_server_message;
+
+        let mut mgr = RoomManager::new();
+        let (tx1, mut rx1) = make_sender();
+        let (code, alice, _) = mgr.create_room("Alice".into(), PlayerColor::default(), tx1);
+        while rx1.try_recv().is_some() {}
+        let click = |x, y| rmp_serde::to_vec(&TargetClick { x, y }).unwrap();
+
+        mgr.lobby_toy_input(&code, alice, &click(0.0, 0.0));
+        assert!(rx1.try_recv().is_none(), "Misses aren't broadcast");
+        mgr.lobby_toy_input(&code, alice, &click(0.5, 0.5));
+        let data = rx1.try_recv().expect("Hits are shared with the room");
+        let Ok(ServerMessage::LobbyToyState(msg)) = decode_server_message(&data) else {
+            panic!("Expected the lobby toy");
+        };
+        let state: TargetPracticeState = rmp_serde::from_slice(&msg.state_data).unwrap();
+        assert_eq!(state.hits[&alice], 1);
+
+        mgr.rooms.get_mut(&code).unwrap().room.state = RoomState::InGame;
+        assert!(mgr.lobby_toy_state(&code).is_none());
+        let (x, y) = (state.target_x, state.target_y);
+        mgr.lobby_toy_input(&code, alice, &click(x, y));
+        assert!(
+            rx1.try_recv().is_none(),
+            "The toy stops once the game starts"
+        );
+    }
+
     #[test]
     fn session_invalid_token_rejected() {
         let mut mgr = RoomManager::new();
@@ -945,4 +3154,330 @@ mod tests {
         let result = mgr.reconnect("nonexistent-token", tx);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn leader_sets_bot_difficulty_in_lobby() {
+        let mut mgr = RoomManager::new();
+        let (tx, _rx) = make_sender();
+        let (code, alice, _) = mgr.create_room("Alice".into(), PlayerColor::default(), tx);
+        let bot = mgr.add_bot(&code, alice, BotDifficulty::Easy).unwrap();
+        let difficulty = |mgr: &RoomManager| {
+            mgr.get_players(&code)
+                .unwrap()
+                .iter()
+                .find(|p| p.id == bot)
+                .map(|p| p.bot_difficulty)
+        };
+        assert_eq!(difficulty(&mgr), Some(BotDifficulty::Easy));
+
+        mgr.set_bot_difficulty(&code, bot, BotDifficulty::Mirror, alice)
+            .unwrap();
+        assert_eq!(difficulty(&mgr), Some(BotDifficulty::Mirror));
+        assert!(
+            mgr.set_bot_difficulty(&code, alice, BotDifficulty::Hard, alice)
+                .is_err(),
+            "Humans have no difficulty"
+        );
+        assert!(
+            mgr.set_bot_difficulty(&code, bot, BotDifficulty::Hard, bot)
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_settings_fail_before_the_game_starts() {
+        let state = crate::state::AppState::new(crate::config::ServerConfig::default());
+        let (tx, _rx) = make_sender();
+        let mut rooms = state.rooms.write().await;
+        let (code, host_id, _) = rooms.create_room("Alice".into(), PlayerColor::default(), tx);
+        let request = |mode: &str| RequestGameStartMsg {
+            game_name: "laser-tag".to_string(),
+            custom: [("team_mode".to_string(), serde_json::json!(mode))].into(),
+            win_condition: None,
+            pacing: None,
+            featured: false,
+        };
+
+        let err = rooms
+            .start_game(
+                &code,
+                request("2teams"),
+                host_id,
+                &state.game_registry,
+                std::sync::Arc::clone(&state.rooms),
+            )
+            .unwrap_err();
+        assert!(err.contains("team_mode"), "Error names the setting: {err}");
+        assert_eq!(rooms.get_room_state(&code), Some(RoomState::Lobby));
+
+        rooms
+            .start_game(
+                &code,
+                request("teams_2"),
+                host_id,
+                &state.game_registry,
+                std::sync::Arc::clone(&state.rooms),
+            )
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn scheduled_rooms_play_their_playlist() {
+        let state = crate::state::AppState::new(crate::config::ServerConfig::default());
+        let session = crate::schedule::ScheduleStore::new(10, 15)
+            .create(
+                crate::schedule::ScheduleRequest {
+                    title: "Game night".to_string(),
+                    start_at: 2000,
+                    games: vec!["tron".to_string(), "mini-golf".to_string()],
+                    host: None,
+                    invitees: Vec::new(),
+                    duration_minutes: None,
+                    reminder_minutes: None,
+                    notify_url: None,
+                },
+                1000,
+            )
+            .unwrap();
+        let mut rooms = state.rooms.write().await;
+        let code = rooms.create_scheduled_room(&session);
+        assert_eq!(rooms.playlist(&code), [GameId::Tron, GameId::Golf]);
+
+        let (tx, _rx) = make_sender();
+        let (host_id, _) = rooms
+            .join_room(&code, "Alice".into(), PlayerColor::default(), tx)
+            .unwrap();
+        let (tx, _rx2) = make_sender();
+        rooms
+            .join_room(&code, "Bob".into(), PlayerColor::default(), tx)
+            .unwrap();
+        rooms
+            .start_game(
+                &code,
+                RequestGameStartMsg {
+                    game_name: String::new(),
+                    custom: Default::default(),
+                    win_condition: None,
+                    pacing: None,
+                    featured: false,
+                },
+                host_id,
+                &state.game_registry,
+                std::sync::Arc::clone(&state.rooms),
+            )
+            .unwrap();
+        assert_eq!(rooms.current_game(&code), Some(GameId::Tron));
+        assert_eq!(
+            rooms.playlist(&code),
+            [GameId::Golf, GameId::Tron],
+            "The next game moves up"
+        );
+    }
+
+    #[tokio::test]
+    async fn debug_commands_need_the_feature_config_and_an_open_window() {
+        let state = crate::state::AppState::new(crate::config::ServerConfig::default());
+        let (tx1, _rx1) = make_sender();
+        let (tx2, _rx2) = make_sender();
+        let mut rooms = state.rooms.write().await;
+        let (code, host_id, _) = rooms.create_room("Alice".into(), PlayerColor::default(), tx1);
+        let (guest_id, _) = rooms
+            .join_room(&code, "Bob".into(), PlayerColor::default(), tx2)
+            .unwrap();
+        let mut config = DebugConfig {
+            enabled: true,
+            window_secs: 60,
+        };
+        let end_round = || DebugCommand::SetRoundTimer { elapsed_secs: 80.0 };
+        assert!(
+            rooms
+                .debug_command(&code, host_id, end_round(), &config)
+                .is_err(),
+            "Nothing to debug before a game starts"
+        );
+
+        rooms
+            .start_game(
+                &code,
+                RequestGameStartMsg {
+                    game_name: "mini-golf".to_string(),
+                    custom: Default::default(),
+                    win_condition: None,
+                    pacing: None,
+                    featured: false,
+                },
+                host_id,
+                &state.game_registry,
+                std::sync::Arc::clone(&state.rooms),
+            )
+            .unwrap();
+        let result = rooms.debug_command(&code, host_id, end_round(), &config);
+        if !cfg!(feature = "debug-commands") {
+            assert!(result.is_err(), "Refused without the feature");
+            return;
+        }
+        result.unwrap();
+        assert!(
+            rooms
+                .debug_command(&code, guest_id, end_round(), &config)
+                .is_err()
+        );
+
+        config.enabled = false;
+        assert!(
+            rooms
+                .debug_command(&code, host_id, end_round(), &config)
+                .is_err()
+        );
+        config.enabled = true;
+        config.window_secs = 0;
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(
+            rooms
+                .debug_command(&code, host_id, end_round(), &config)
+                .is_err(),
+            "The window runs from the room's first command"
+        );
+    }
+
+    #[test]
+    fn local_players_share_their_owners_connection() {
+        let mut mgr = RoomManager::new();
+        let (tx1, _rx1) = make_sender();
+        let (code, alice, _) = mgr.create_room("Alice".into(), PlayerColor::default(), tx1);
+        let (tx2, _rx2) = make_sender();
+        let (bob, _) = mgr
+            .join_room(&code, "Bob".into(), PlayerColor::default(), tx2)
+            .unwrap();
+
+        let guests: Vec<PlayerId> = (0..MAX_LOCAL_PLAYERS)
+            .map(|i| {
+                mgr.add_local_player(&code, bob, format!("Couch {i}"), PlayerColor::default())
+                    .unwrap()
+            })
+            .collect();
+        assert!(
+            mgr.add_local_player(&code, bob, "Extra".into(), PlayerColor::default())
+                .is_err()
+        );
+        assert!(
+            mgr.add_local_player(&code, guests[0], "Nested".into(), PlayerColor::default())
+                .is_err(),
+            "Guests have no connection of their own"
+        );
+        assert_eq!(mgr.local_players_of(&code, bob), guests);
+
+        assert_eq!(mgr.input_target(&code, bob, guests[1]), guests[1]);
+        assert_eq!(
+            mgr.input_target(&code, alice, guests[1]),
+            alice,
+            "Inputs for another connection's guest drive the sender"
+        );
+
+        assert!(mgr.remove_local_player(&code, guests[2], alice).is_err());
+        mgr.remove_local_player(&code, guests[2], bob).unwrap();
+        assert_eq!(mgr.get_players(&code).unwrap().len(), 4);
+
+        mgr.leave_room(&code, alice);
+        assert_eq!(
+            mgr.get_leader_id(&code),
+            Some(bob),
+            "Leadership passes to the next connection"
+        );
+        mgr.leave_room(&code, bob);
+        assert!(!mgr.room_exists(&code), "Guests leave with their owner");
+    }
+
+    #[test]
+    fn merge_and_split_offer_transfers_that_keep_session_scores() {
+        let mut mgr = RoomManager::new();
+        let (tx, mut alice_rx) = make_sender();
+        let (small, alice, _) = mgr.create_room("Alice".into(), PlayerColor::default(), tx);
+        let (tx, _bob_rx) = make_sender();
+        let (bob, _) = mgr
+            .join_room(&small, "Bob".into(), PlayerColor::PALETTE[1], tx)
+            .unwrap();
+        let (tx, _carol_rx) = make_sender();
+        let (big, ..) = mgr.create_room("Carol".into(), PlayerColor::default(), tx);
+        mgr.record_session_scores(
+            &small,
+            &[PlayerScoreEntry {
+                player_id: alice,
+                score: 7,
+                disconnected: false,
+            }],
+        );
+        while alice_rx.try_recv().is_some() {}
+
+        let merge = RoomReshape::MergeInto(big.clone());
+        assert!(mgr.reshape_room(&small, Some(bob), merge.clone()).is_err());
+        let too_small = mgr.reshape_room(&small, Some(alice), RoomReshape::Split);
+        assert!(too_small.is_err(), "Two players can't split");
+        assert_eq!(
+            mgr.reshape_room(&small, Some(alice), merge),
+            Ok((big.clone(), 2))
+        );
+
+        let data = alice_rx.try_recv().expect("Alice is offered the move");
+        let Ok(ServerMessage::RoomTransfer(offer)) = decode_server_message(&data) else {
+            panic!("Expected a transfer offer");
+        };
+        assert_eq!(offer.room_code, big);
+        assert_eq!(offer.reason, TransferReason::Merge);
+
+        mgr.leave_room(&small, alice);
+        let transfer = mgr.take_transfer(&offer.transfer_token).unwrap();
+        assert!(
+            mgr.take_transfer(&offer.transfer_token).is_none(),
+            "Tokens work once"
+        );
+        let (tx, _rx) = make_sender();
+        let (code, moved, _) = mgr.join_by_transfer(transfer, tx).unwrap();
+        assert_eq!(code, big);
+        assert_eq!(mgr.get_player_name(&big, moved).as_deref(), Some("Alice"));
+        assert_eq!(mgr.rooms[&big].session_scores[&moved], 7);
+
+        // Carol, Alice, and two more are enough to split
+        for name in ["Dave", "Erin"] {
+            let (tx, _rx) = make_sender();
+            mgr.join_room(&big, name.into(), PlayerColor::default(), tx)
+                .unwrap();
+        }
+        let carol = mgr.get_leader_id(&big).unwrap();
+        let (half, offered) = mgr.reshape_room(&big, None, RoomReshape::Split).unwrap();
+        assert_eq!(offered, 2, "Half of the four move");
+        assert_ne!(half, big);
+        assert!(mgr.get_players(&half).unwrap().is_empty());
+        assert_eq!(mgr.get_leader_id(&big), Some(carol));
+    }
+
+    #[test]
+    fn game_sleeps_while_no_player_is_connected() {
+        let mut mgr = RoomManager::new();
+        let (tx, _rx1) = make_sender();
+        let (code, alice, _) = mgr.create_room("Alice".into(), PlayerColor::default(), tx);
+        let (tx, _rx2) = make_sender();
+        let (bob, _) = mgr
+            .join_room(&code, "Bob".into(), PlayerColor::PALETTE[1], tx)
+            .unwrap();
+        let (cmd_tx, mut cmd_rx) = mpsc::unbounded_channel();
+        let entry = mgr.rooms.get_mut(&code).unwrap();
+        entry.game_command_tx = Some(cmd_tx);
+        entry.room.state = RoomState::InGame;
+        let bob_token = entry.player_sessions[&bob].clone();
+
+        mgr.leave_room(&code, alice);
+        assert!(cmd_rx.try_recv().is_err(), "Bob is still playing");
+        // A spectator watching on a display doesn't keep the game awake
+        let (tx, _rx3) = make_sender();
+        mgr.join_room(&code, "Lobby TV".into(), PlayerColor::default(), tx)
+            .unwrap();
+        mgr.leave_room(&code, bob);
+        assert!(matches!(cmd_rx.try_recv(), Ok(GameCommand::Dormant(true))));
+
+        let (tx, _rx4) = make_sender();
+        mgr.reconnect(&bob_token, tx).unwrap();
+        assert!(matches!(cmd_rx.try_recv(), Ok(GameCommand::Dormant(false))));
+        assert!(cmd_rx.try_recv().is_err());
+    }
 }
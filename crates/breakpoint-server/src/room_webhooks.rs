@@ -0,0 +1,435 @@
+//! Outbound webhooks for room lifecycle events.
+//!
+//! Operators register URLs (in `[room_webhooks]` or through the admin API)
+//! that receive a JSON POST when a room is created, a game starts, a round
+//! or game finishes, or a room closes, followed by the room's decathlon
+//! results when it finished any matches. Each body is signed with the hook's
+//! secret as `X-Breakpoint-Signature-256: sha256=<hex HMAC-SHA256>`.
+//! Activity is queued on a bounded channel so room operations never wait
+//! on the network; when the queue is full, events are dropped.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tokio::sync::mpsc;
+
+use breakpoint_core::game_trait::PlayerId;
+use breakpoint_core::net::messages::{MessageType, PlayerScoreEntry, ServerMessage};
+use breakpoint_core::net::protocol::decode_server_message;
+
+use crate::config::RoomWebhooksConfig;
+use crate::decathlon::DecathlonResults;
+
+/// Header naming the event kind of a delivery.
+pub const EVENT_HEADER: &str = "x-breakpoint-event";
+/// Header carrying `sha256=<hex HMAC-SHA256(secret, body)>`.
+pub const SIGNATURE_HEADER: &str = "x-breakpoint-signature-256";
+/// Event kinds a hook can subscribe to.
+pub const EVENT_KINDS: &[&str] = &[
+    "room_created",
+    "game_started",
+    "round_finished",
+    "game_ended",
+    "room_closed",
+    "decathlon_results",
+];
+/// Activity waiting for delivery before new events are dropped.
+pub const QUEUE_CAPACITY: usize = 256;
+const DELIVERY_ATTEMPTS: u32 = 3;
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A player's score as reported in round and game results.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScoreLine {
+    pub player_id: PlayerId,
+    /// Display name when the player was in the room at game start.
+    pub name: Option<String>,
+    pub score: i32,
+}
+
+/// Something that happened to a room. Serialized flat, with the kind
+/// in `event`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum RoomActivity {
+    RoomCreated {
+        room_code: String,
+    },
+    GameStarted {
+        room_code: String,
+        game: String,
+        players: Vec<String>,
+        /// Playing the season's featured game.
+        featured: bool,
+    },
+    RoundFinished {
+        room_code: String,
+        round: u8,
+        scores: Vec<ScoreLine>,
+    },
+    GameEnded {
+        room_code: String,
+        final_scores: Vec<ScoreLine>,
+        match_id: Option<String>,
+    },
+    RoomClosed {
+        room_code: String,
+    },
+    /// The closed room's finished matches across games, sent by the
+    /// dispatcher after `RoomClosed`.
+    DecathlonResults(DecathlonResults),
+}
+
+impl RoomActivity {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::RoomCreated { .. } => "room_created",
+            Self::GameStarted { .. } => "game_started",
+            Self::RoundFinished { .. } => "round_finished",
+            Self::GameEnded { .. } => "game_ended",
+            Self::RoomClosed { .. } => "room_closed",
+            Self::DecathlonResults(_) => "decathlon_results",
+        }
+    }
+}
+
+/// Where the room manager queues activity for the webhook dispatcher.
+#[derive(Debug, Clone)]
+pub struct RoomActivitySink(mpsc::Sender<RoomActivity>);
+
+impl RoomActivitySink {
+    pub fn new(tx: mpsc::Sender<RoomActivity>) -> Self {
+        Self(tx)
+    }
+
+    pub fn emit(&self, activity: RoomActivity) {
+        if let Err(e) = self.0.try_send(activity) {
+            tracing::debug!(error = %e, "Room activity dropped");
+        }
+    }
+}
+
+/// Turns a game session's round and game results into activity. Owned by
+/// the task forwarding the session's broadcasts.
+#[derive(Debug)]
+pub struct ResultReporter {
+    sink: RoomActivitySink,
+    room_code: String,
+    names: HashMap<PlayerId, String>,
+}
+
+impl ResultReporter {
+    pub fn new(sink: RoomActivitySink, room_code: &str, names: HashMap<PlayerId, String>) -> Self {
+        Self {
+            sink,
+            room_code: room_code.to_string(),
+            names,
+        }
+    }
+
+    /// Inspect an encoded broadcast, reporting round ends and game ends.
+    pub fn observe(&self, data: &[u8]) {
+        let is_result = data
+            .first()
+            .is_some_and(|&b| b == MessageType::RoundEnd as u8 || b == MessageType::GameEnd as u8);
+        if !is_result {
+            return;
+        }
+        let room_code = self.room_code.clone();
+        match decode_server_message(data) {
+            Ok(ServerMessage::RoundEnd(msg)) => self.sink.emit(RoomActivity::RoundFinished {
+                room_code,
+                round: msg.round,
+                scores: self.score_lines(&msg.scores),
+            }),
+            Ok(ServerMessage::GameEnd(msg)) => self.sink.emit(RoomActivity::GameEnded {
+                room_code,
+                final_scores: self.score_lines(&msg.final_scores),
+                match_id: msg.match_id,
+            }),
+            _ => {},
+        }
+    }
+
+    fn score_lines(&self, scores: &[PlayerScoreEntry]) -> Vec<ScoreLine> {
+        scores
+            .iter()
+            .map(|s| ScoreLine {
+                player_id: s.player_id,
+                name: self.names.get(&s.player_id).cloned(),
+                score: s.score,
+            })
+            .collect()
+    }
+}
+
+/// Request body for `POST /api/v1/admin/room-webhooks`, and the shape of
+/// each `[[room_webhooks.hooks]]` entry.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RoomWebhookRequest {
+    pub url: String,
+    pub secret: String,
+    /// Event kinds to deliver. Empty means all of them.
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+/// A registered webhook. The secret is never echoed back.
+#[derive(Debug, Clone, Serialize)]
+pub struct RoomWebhook {
+    pub id: String,
+    pub url: String,
+    #[serde(skip_serializing)]
+    pub secret: String,
+    pub events: Vec<String>,
+}
+
+impl RoomWebhook {
+    pub fn wants(&self, kind: &str) -> bool {
+        self.events.is_empty() || self.events.iter().any(|e| e == kind)
+    }
+}
+
+/// Registered room webhooks.
+#[derive(Debug)]
+pub struct RoomWebhookStore {
+    hooks: Vec<RoomWebhook>,
+    max_hooks: usize,
+    next_id: u64,
+}
+
+impl RoomWebhookStore {
+    /// Create a store seeded with the hooks from the config file. Invalid
+    /// entries are logged and skipped.
+    pub fn new(config: &RoomWebhooksConfig) -> Self {
+        let mut store = Self {
+            hooks: Vec::new(),
+            max_hooks: config.max_hooks,
+            next_id: 1,
+        };
+        for hook in &config.hooks {
+            if let Err(e) = store.create(hook.clone()) {
+                tracing::warn!(url = %hook.url, error = %e, "Skipping configured room webhook");
+            }
+        }
+        store
+    }
+
+    pub fn create(&mut self, request: RoomWebhookRequest) -> Result<RoomWebhook, String> {
+        if self.hooks.len() >= self.max_hooks {
+            return Err(format!("at most {} room webhooks", self.max_hooks));
+        }
+        let url = request.url.trim();
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return Err("url must be http:// or https://".to_string());
+        }
+        if request.secret.is_empty() {
+            return Err("secret must not be empty".to_string());
+        }
+        if let Some(unknown) = request
+            .events
+            .iter()
+            .find(|e| !EVENT_KINDS.contains(&e.as_str()))
+        {
+            return Err(format!("unknown event: {unknown}"));
+        }
+        let hook = RoomWebhook {
+            id: format!("hook-{}", self.next_id),
+            url: url.to_string(),
+            secret: request.secret,
+            events: request.events,
+        };
+        self.next_id += 1;
+        self.hooks.push(hook.clone());
+        Ok(hook)
+    }
+
+    pub fn remove(&mut self, id: &str) -> bool {
+        let before = self.hooks.len();
+        self.hooks.retain(|h| h.id != id);
+        self.hooks.len() != before
+    }
+
+    pub fn all(&self) -> Vec<RoomWebhook> {
+        self.hooks.clone()
+    }
+
+    /// Hooks subscribed to `kind`.
+    pub fn matching(&self, kind: &str) -> Vec<RoomWebhook> {
+        self.hooks
+            .iter()
+            .filter(|h| h.wants(kind))
+            .cloned()
+            .collect()
+    }
+}
+
+/// `sha256=<hex>` signature of `body` under `secret`.
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        <Hmac<Sha256>>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+#[derive(Serialize)]
+struct Delivery<'a> {
+    /// Unix seconds when the event was dispatched.
+    timestamp: u64,
+    #[serde(flatten)]
+    activity: &'a RoomActivity,
+}
+
+/// JSON body delivered for `activity`.
+pub fn delivery_body(activity: &RoomActivity, timestamp: u64) -> Vec<u8> {
+    serde_json::to_vec(&Delivery {
+        timestamp,
+        activity,
+    })
+    .unwrap_or_default()
+}
+
+/// POST a signed body to one hook, retrying network failures and server
+/// errors with backoff. Rejections (4xx) are not retried.
+pub async fn deliver(http: reqwest::Client, hook: RoomWebhook, kind: &'static str, body: Vec<u8>) {
+    let signature = sign(&hook.secret, &body);
+    for attempt in 1..=DELIVERY_ATTEMPTS {
+        let result = http
+            .post(&hook.url)
+            .timeout(DELIVERY_TIMEOUT)
+            .header("content-type", "application/json")
+            .header(EVENT_HEADER, kind)
+            .header(SIGNATURE_HEADER, &signature)
+            .body(body.clone())
+            .send()
+            .await;
+        match result {
+            Ok(resp) if resp.status().is_success() => return,
+            Ok(resp) if resp.status().is_client_error() => {
+                tracing::warn!(hook_id = %hook.id, status = %resp.status(), "Room webhook rejected");
+                return;
+            },
+            Ok(resp) => {
+                tracing::warn!(hook_id = %hook.id, attempt, status = %resp.status(), "Room webhook failed");
+            },
+            Err(e) => {
+                tracing::warn!(hook_id = %hook.id, attempt, error = %e, "Room webhook failed");
+            },
+        }
+        if attempt < DELIVERY_ATTEMPTS {
+            tokio::time::sleep(Duration::from_secs(1 << attempt)).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use breakpoint_core::net::messages::RoundEndMsg;
+    use breakpoint_core::net::protocol::encode_server_message;
+
+    fn request(events: &[&str]) -> RoomWebhookRequest {
+        RoomWebhookRequest {
+            url: "https://hooks.example.com/breakpoint".to_string(),
+            secret: "s3cret".to_string(),
+            events: events.iter().map(|e| e.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn hooks_filter_by_event_and_validate() {
+        let mut store = RoomWebhookStore::new(&RoomWebhooksConfig::default());
+        let all = store.create(request(&[])).unwrap();
+        let rounds = store.create(request(&["round_finished"])).unwrap();
+        assert_eq!(store.matching("room_created").len(), 1);
+        assert_eq!(store.matching("round_finished").len(), 2);
+
+        assert!(store.create(request(&["lunch_break"])).is_err());
+        let mut bad_url = request(&[]);
+        bad_url.url = "ftp://example.com".to_string();
+        assert!(store.create(bad_url).is_err());
+
+        assert!(store.remove(&rounds.id));
+        assert!(!store.remove(&rounds.id));
+        assert_eq!(store.all().len(), 1);
+        assert!(
+            !serde_json::to_string(&all).unwrap().contains("s3cret"),
+            "Secrets are not serialized"
+        );
+    }
+
+    #[test]
+    fn body_is_flat_and_signed() {
+        let activity = RoomActivity::RoomClosed {
+            room_code: "ABCD-1234".to_string(),
+        };
+        let body = delivery_body(&activity, 1_700_000_000);
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["event"], "room_closed");
+        assert_eq!(json["room_code"], "ABCD-1234");
+        assert_eq!(json["timestamp"], 1_700_000_000);
+
+        let signature = sign("s3cret", &body);
+        assert!(crate::auth::verify_github_signature(
+            &signature, "s3cret", &body
+        ));
+        assert_ne!(signature, sign("other", &body));
+    }
+
+    #[test]
+    fn decathlon_results_are_delivered_flat() {
+        let activity = RoomActivity::DecathlonResults(DecathlonResults {
+            room_code: "ABCD-1234".to_string(),
+            games: Vec::new(),
+            standings: Vec::new(),
+        });
+        assert_eq!(activity.kind(), "decathlon_results");
+        let json: serde_json::Value =
+            serde_json::from_slice(&delivery_body(&activity, 1_700_000_000)).unwrap();
+        assert_eq!(json["event"], "decathlon_results");
+        assert_eq!(json["room_code"], "ABCD-1234");
+        assert!(json["standings"].is_array());
+    }
+
+    #[test]
+    fn reporter_names_round_scores() {
+        let (tx, mut rx) = mpsc::channel(4);
+        let reporter = ResultReporter::new(
+            RoomActivitySink::new(tx),
+            "ABCD-1234",
+            [(1, "Alice".to_string())].into(),
+        );
+        let round_end = encode_server_message(&ServerMessage::RoundEnd(RoundEndMsg {
+            round: 2,
+            scores: vec![
+                PlayerScoreEntry {
+                    player_id: 1,
+                    score: 5,
+                    disconnected: false,
+                },
+                PlayerScoreEntry {
+                    player_id: 9,
+                    score: 3,
+                    disconnected: false,
+                },
+            ],
+            between_round_secs: 3,
+            heatmap: None,
+            victory: None,
+        }))
+        .unwrap();
+        reporter.observe(&round_end);
+        reporter.observe(&[MessageType::GameState as u8, 0, 0]);
+
+        let RoomActivity::RoundFinished { round, scores, .. } = rx.try_recv().unwrap() else {
+            panic!("Expected a round result");
+        };
+        assert_eq!(round, 2);
+        assert_eq!(scores[0].name.as_deref(), Some("Alice"));
+        assert_eq!(scores[1].name, None, "Late joiners have no name");
+        assert!(rx.try_recv().is_err(), "Other broadcasts are ignored");
+    }
+}
@@ -0,0 +1,438 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use breakpoint_core::events::{Event, EventType, Priority};
+use breakpoint_core::game_trait::GameId;
+
+/// Maximum length of a schedule title.
+const MAX_TITLE_LEN: usize = 128;
+/// Maximum number of games in a playlist.
+const MAX_PLAYLIST_LEN: usize = 10;
+/// Maximum number of invitees per schedule.
+const MAX_INVITEES: usize = 50;
+/// Maximum length of an invitee or host name.
+const MAX_NAME_LEN: usize = 64;
+/// Maximum length of a notification URL.
+const MAX_URL_LEN: usize = 2048;
+/// Session length used for listings and calendar entries when none is given.
+const DEFAULT_DURATION_MINUTES: u32 = 60;
+/// Longest session that can be scheduled.
+const MAX_DURATION_MINUTES: u32 = 24 * 60;
+
+/// Request body for `POST /api/v1/schedules`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScheduleRequest {
+    pub title: String,
+    /// Start time as Unix epoch seconds.
+    pub start_at: u64,
+    /// Game playlist, as wire-format game IDs (e.g. `"mini-golf"`).
+    #[serde(default)]
+    pub games: Vec<String>,
+    #[serde(default)]
+    pub host: Option<String>,
+    #[serde(default)]
+    pub invitees: Vec<String>,
+    #[serde(default)]
+    pub duration_minutes: Option<u32>,
+    /// How long before the start to send the reminder. Defaults to the
+    /// server's `schedules.reminder_minutes`.
+    #[serde(default)]
+    pub reminder_minutes: Option<u32>,
+    /// Optional URL that receives a JSON POST for each reminder/opening.
+    #[serde(default)]
+    pub notify_url: Option<String>,
+}
+
+/// A scheduled game night.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduledSession {
+    pub id: String,
+    pub title: String,
+    pub host: Option<String>,
+    pub games: Vec<String>,
+    pub invitees: Vec<String>,
+    pub start_at: u64,
+    pub duration_minutes: u32,
+    pub reminder_minutes: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notify_url: Option<String>,
+    pub reminder_sent: bool,
+    /// Room created for this session once its start time is reached.
+    pub room_code: Option<String>,
+}
+
+impl ScheduledSession {
+    fn end_at(&self) -> u64 {
+        self.start_at + u64::from(self.duration_minutes) * 60
+    }
+
+    fn reminder_at(&self) -> u64 {
+        self.start_at
+            .saturating_sub(u64::from(self.reminder_minutes) * 60)
+    }
+
+    /// Alert event announcing that the session starts soon.
+    pub fn reminder_event(&self, now: u64) -> Event {
+        let minutes = self.start_at.saturating_sub(now).div_ceil(60);
+        self.event(
+            "reminder",
+            Priority::Notice,
+            format!("Game night \"{}\" starts in {minutes} min", self.title),
+            now,
+        )
+    }
+
+    /// Alert event announcing that the session's room is open.
+    pub fn opened_event(&self, now: u64) -> Event {
+        let code = self.room_code.as_deref().unwrap_or_default();
+        let mut event = self.event(
+            "open",
+            Priority::Urgent,
+            format!(
+                "Game night \"{}\" is starting — join room {code}",
+                self.title
+            ),
+            now,
+        );
+        event.url = Some(format!("/?room={code}"));
+        event
+    }
+
+    fn event(&self, kind: &str, priority: Priority, title: String, now: u64) -> Event {
+        let mut metadata = HashMap::new();
+        metadata.insert("schedule_id".to_string(), serde_json::json!(self.id));
+        metadata.insert("start_at".to_string(), serde_json::json!(self.start_at));
+        metadata.insert("games".to_string(), serde_json::json!(self.games));
+        if let Some(ref code) = self.room_code {
+            metadata.insert("room_code".to_string(), serde_json::json!(code));
+        }
+        Event {
+            id: format!("schedule-{}-{kind}", self.id),
+            event_type: EventType::Custom,
+            source: "schedule".to_string(),
+            priority,
+            title,
+            body: (!self.games.is_empty()).then(|| format!("Playlist: {}", self.games.join(", "))),
+            timestamp: format!("{now}Z"),
+            url: None,
+            actor: self.host.clone(),
+            tags: vec!["schedule".to_string()],
+            action_required: false,
+            group_key: Some(format!("schedule:{}", self.id)),
+            expires_at: Some(format!("{}Z", self.end_at())),
+            metadata,
+        }
+    }
+
+    /// Render the session as an iCalendar (RFC 5545) document.
+    pub fn to_ics(&self, now: u64) -> String {
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_string(),
+            "VERSION:2.0".to_string(),
+            "PRODID:-//Breakpoint//Game Night//EN".to_string(),
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}@breakpoint", self.id),
+            format!("DTSTAMP:{}", ics_utc(now)),
+            format!("DTSTART:{}", ics_utc(self.start_at)),
+            format!("DTEND:{}", ics_utc(self.end_at())),
+            format!("SUMMARY:{}", ics_escape(&self.title)),
+        ];
+        if !self.games.is_empty() {
+            lines.push(format!(
+                "DESCRIPTION:{}",
+                ics_escape(&format!("Playlist: {}", self.games.join(", ")))
+            ));
+        }
+        if let Some(ref code) = self.room_code {
+            lines.push(format!("LOCATION:Breakpoint room {code}"));
+        }
+        lines.push("END:VEVENT".to_string());
+        lines.push("END:VCALENDAR".to_string());
+        let mut out = lines.join("\r\n");
+        out.push_str("\r\n");
+        out
+    }
+}
+
+/// Public view of a schedule for the lobby (no invitees or notify URL).
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleSummary {
+    pub id: String,
+    pub title: String,
+    pub host: Option<String>,
+    pub games: Vec<String>,
+    pub start_at: u64,
+    pub duration_minutes: u32,
+    pub room_code: Option<String>,
+}
+
+impl From<&ScheduledSession> for ScheduleSummary {
+    fn from(s: &ScheduledSession) -> Self {
+        Self {
+            id: s.id.clone(),
+            title: s.title.clone(),
+            host: s.host.clone(),
+            games: s.games.clone(),
+            start_at: s.start_at,
+            duration_minutes: s.duration_minutes,
+            room_code: s.room_code.clone(),
+        }
+    }
+}
+
+/// In-memory store of scheduled game nights, ordered by start time.
+pub struct ScheduleStore {
+    sessions: Vec<ScheduledSession>,
+    max_schedules: usize,
+    default_reminder_minutes: u32,
+}
+
+impl ScheduleStore {
+    pub fn new(max_schedules: usize, default_reminder_minutes: u32) -> Self {
+        Self {
+            sessions: Vec::new(),
+            max_schedules,
+            default_reminder_minutes,
+        }
+    }
+
+    /// Validate and store a new schedule.
+    pub fn create(&mut self, req: ScheduleRequest, now: u64) -> Result<ScheduledSession, String> {
+        let title = req.title.trim().to_string();
+        if title.is_empty() || title.len() > MAX_TITLE_LEN {
+            return Err(format!("title must be 1-{MAX_TITLE_LEN} chars"));
+        }
+        if req.start_at <= now {
+            return Err("start_at must be in the future".to_string());
+        }
+        if req.games.len() > MAX_PLAYLIST_LEN {
+            return Err(format!("games exceed {MAX_PLAYLIST_LEN} entries"));
+        }
+        if let Some(bad) = req.games.iter().find(|g| GameId::from_str_opt(g).is_none()) {
+            return Err(format!("unknown game: {bad}"));
+        }
+        if req.invitees.len() > MAX_INVITEES {
+            return Err(format!("invitees exceed {MAX_INVITEES} entries"));
+        }
+        if req
+            .invitees
+            .iter()
+            .chain(req.host.iter())
+            .any(|n| n.is_empty() || n.len() > MAX_NAME_LEN)
+        {
+            return Err(format!("names must be 1-{MAX_NAME_LEN} chars"));
+        }
+        let duration_minutes = req.duration_minutes.unwrap_or(DEFAULT_DURATION_MINUTES);
+        if duration_minutes == 0 || duration_minutes > MAX_DURATION_MINUTES {
+            return Err(format!("duration_minutes must be 1-{MAX_DURATION_MINUTES}"));
+        }
+        if let Some(ref url) = req.notify_url
+            && (url.len() > MAX_URL_LEN
+                || !(url.starts_with("https://") || url.starts_with("http://")))
+        {
+            return Err("notify_url must be an http(s) URL".to_string());
+        }
+        if self.sessions.len() >= self.max_schedules {
+            return Err(format!(
+                "Too many scheduled sessions (max {})",
+                self.max_schedules
+            ));
+        }
+
+        let session = ScheduledSession {
+            id: Uuid::new_v4().to_string(),
+            title,
+            host: req.host,
+            games: req.games,
+            invitees: req.invitees,
+            start_at: req.start_at,
+            duration_minutes,
+            reminder_minutes: req
+                .reminder_minutes
+                .unwrap_or(self.default_reminder_minutes),
+            notify_url: req.notify_url,
+            reminder_sent: false,
+            room_code: None,
+        };
+        let pos = self
+            .sessions
+            .partition_point(|s| s.start_at <= session.start_at);
+        self.sessions.insert(pos, session.clone());
+        Ok(session)
+    }
+
+    pub fn get(&self, id: &str) -> Option<&ScheduledSession> {
+        self.sessions.iter().find(|s| s.id == id)
+    }
+
+    /// Cancel a schedule. Returns true if it existed.
+    pub fn remove(&mut self, id: &str) -> bool {
+        let before = self.sessions.len();
+        self.sessions.retain(|s| s.id != id);
+        self.sessions.len() != before
+    }
+
+    /// Sessions that have not yet ended, soonest first.
+    pub fn upcoming(&self, now: u64) -> Vec<&ScheduledSession> {
+        self.sessions.iter().filter(|s| s.end_at() > now).collect()
+    }
+
+    /// Mark and return sessions whose reminder is due. Sessions that are
+    /// already due to open skip the reminder.
+    pub fn take_due_reminders(&mut self, now: u64) -> Vec<ScheduledSession> {
+        let mut due = Vec::new();
+        for s in &mut self.sessions {
+            if !s.reminder_sent && s.reminder_minutes > 0 && s.reminder_at() <= now {
+                s.reminder_sent = true;
+                if s.start_at > now {
+                    due.push(s.clone());
+                }
+            }
+        }
+        due
+    }
+
+    /// IDs of sessions whose start time has arrived but have no room yet.
+    pub fn due_openings(&self, now: u64) -> Vec<String> {
+        self.sessions
+            .iter()
+            .filter(|s| s.room_code.is_none() && s.start_at <= now && s.end_at() > now)
+            .map(|s| s.id.clone())
+            .collect()
+    }
+
+    /// Record the room created for a session, returning the updated session.
+    pub fn set_room_code(&mut self, id: &str, room_code: String) -> Option<ScheduledSession> {
+        let s = self.sessions.iter_mut().find(|s| s.id == id)?;
+        s.room_code = Some(room_code);
+        Some(s.clone())
+    }
+
+    /// Drop sessions that have ended. Returns the number removed.
+    pub fn prune(&mut self, now: u64) -> usize {
+        let before = self.sessions.len();
+        self.sessions.retain(|s| s.end_at() > now);
+        before - self.sessions.len()
+    }
+}
+
+/// Format Unix seconds as an iCalendar UTC timestamp (`YYYYMMDDTHHMMSSZ`).
+fn ics_utc(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+    // Civil-from-days (Howard Hinnant), valid for the proleptic Gregorian calendar.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}{month:02}{day:02}T{:02}{:02}{:02}Z",
+        rem / 3600,
+        (rem / 60) % 60,
+        rem % 60
+    )
+}
+
+/// Escape text for an iCalendar property value.
+fn ics_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+        .replace('\r', "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(title: &str, start_at: u64) -> ScheduleRequest {
+        ScheduleRequest {
+            title: title.to_string(),
+            start_at,
+            games: vec!["mini-golf".to_string(), "tron".to_string()],
+            host: Some("alice".to_string()),
+            invitees: vec!["bob".to_string()],
+            duration_minutes: None,
+            reminder_minutes: Some(10),
+            notify_url: None,
+        }
+    }
+
+    #[test]
+    fn create_rejects_invalid_requests() {
+        let mut store = ScheduleStore::new(10, 15);
+        assert!(store.create(request("", 2000), 1000).is_err());
+        assert!(store.create(request("Past", 500), 1000).is_err());
+        let mut bad_game = request("Bad game", 2000);
+        bad_game.games.push("chess".to_string());
+        assert!(store.create(bad_game, 1000).is_err());
+        let mut bad_url = request("Bad url", 2000);
+        bad_url.notify_url = Some("file:///etc/passwd".to_string());
+        assert!(store.create(bad_url, 1000).is_err());
+    }
+
+    #[test]
+    fn sessions_sorted_by_start_and_capped() {
+        let mut store = ScheduleStore::new(2, 15);
+        store.create(request("Later", 5000), 1000).unwrap();
+        store.create(request("Sooner", 3000), 1000).unwrap();
+        assert!(store.create(request("Too many", 4000), 1000).is_err());
+        let titles: Vec<&str> = store
+            .upcoming(1000)
+            .iter()
+            .map(|s| s.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["Sooner", "Later"]);
+    }
+
+    #[test]
+    fn reminder_fires_once_at_lead_time() {
+        let mut store = ScheduleStore::new(10, 15);
+        store.create(request("Night", 10_000), 1000).unwrap();
+        // Reminder lead is 10 minutes → due at 9400
+        assert!(store.take_due_reminders(9_399).is_empty());
+        let due = store.take_due_reminders(9_400);
+        assert_eq!(due.len(), 1);
+        assert!(due[0].reminder_event(9_400).title.contains("10 min"));
+        assert!(store.take_due_reminders(9_500).is_empty());
+    }
+
+    #[test]
+    fn opening_lifecycle() {
+        let mut store = ScheduleStore::new(10, 15);
+        let id = store.create(request("Night", 10_000), 1000).unwrap().id;
+        assert!(store.due_openings(9_999).is_empty());
+        assert_eq!(store.due_openings(10_000), vec![id.clone()]);
+
+        let opened = store.set_room_code(&id, "ABCD-1234".to_string()).unwrap();
+        let event = opened.opened_event(10_000);
+        assert_eq!(event.url.as_deref(), Some("/?room=ABCD-1234"));
+        assert!(store.due_openings(10_001).is_empty());
+
+        // Default 60 minute duration: gone after the session ends
+        assert_eq!(store.prune(10_000 + 3600), 1);
+        assert!(store.get(&id).is_none());
+    }
+
+    #[test]
+    fn ics_contains_utc_times() {
+        let mut store = ScheduleStore::new(10, 15);
+        // 2025-01-02T03:04:05Z
+        let session = store
+            .create(request("Friday, games; fun", 1_735_787_045), 1000)
+            .unwrap();
+        let ics = session.to_ics(1_735_787_045);
+        assert!(ics.contains("DTSTART:20250102T030405Z\r\n"));
+        assert!(ics.contains("DTEND:20250102T040405Z\r\n"));
+        assert!(ics.contains("SUMMARY:Friday\\, games\\; fun\r\n"));
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+    }
+}
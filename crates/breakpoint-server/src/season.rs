@@ -0,0 +1,315 @@
+//! Seasons: a weekly rotation of featured games, each played with modifier
+//! settings such as low gravity or wide laser beams. Rooms opt in with
+//! `featured` on `RequestGameStart`; the room manager then starts the
+//! week's game with its modifiers, and each finished featured match awards
+//! season points, tallied apart from regular play and kept per player
+//! identity where the player has one. `GET
+//! /api/v1/public/season` reports the week and standings for the lobby.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use axum::Json;
+use axum::extract::State;
+use serde::Serialize;
+
+use breakpoint_core::game_settings::CustomSettings;
+use breakpoint_core::game_trait::PlayerId;
+use breakpoint_core::net::messages::{MessageType, ServerMessage};
+use breakpoint_core::net::protocol::decode_server_message;
+use breakpoint_core::player::{Player, PlayerIdentity};
+
+use crate::config::{SeasonConfig, SeasonWeek};
+use crate::error::AppError;
+use crate::state::AppState;
+
+/// Length of a season week.
+pub const WEEK_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Season points for first, second and third place in a featured match.
+pub const PLACEMENT_POINTS: [u32; 3] = [5, 3, 1];
+
+/// Season points for every player who finishes a featured match.
+pub const FINISH_POINTS: u32 = 1;
+
+/// Players listed in the standings.
+pub const STANDINGS_SHOWN: usize = 10;
+
+/// The week being featured and when it ends.
+#[derive(Debug, Clone, Copy)]
+pub struct FeaturedWeek<'a> {
+    /// 1-based.
+    pub number: u32,
+    pub week: &'a SeasonWeek,
+    pub ends_at: u64,
+}
+
+/// A player finishing a featured match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entrant {
+    /// Where their points go. Players without an identity are tallied by
+    /// name, so a namesake shares their points.
+    pub identity: Option<PlayerIdentity>,
+    pub name: String,
+}
+
+impl Entrant {
+    fn key(&self) -> StandingKey {
+        match self.identity {
+            Some(ref identity) => StandingKey::Identity(identity.clone()),
+            None => StandingKey::Name(self.name.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum StandingKey {
+    Identity(PlayerIdentity),
+    Name(String),
+}
+
+/// Season points per player, listed under the name they last played as.
+#[derive(Debug, Default)]
+pub struct SeasonStandings {
+    points: HashMap<StandingKey, Standing>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Standing {
+    pub name: String,
+    pub points: u32,
+}
+
+impl SeasonStandings {
+    /// Award points for a finished featured match from each player's final
+    /// score. Tied players share the better placement.
+    pub fn award(&mut self, results: &[(Entrant, i32)]) {
+        for (entrant, score) in results {
+            let place = results.iter().filter(|(_, s)| s > score).count();
+            let points = FINISH_POINTS + PLACEMENT_POINTS.get(place).copied().unwrap_or(0);
+            let standing = self.points.entry(entrant.key()).or_insert(Standing {
+                name: String::new(),
+                points: 0,
+            });
+            standing.name.clone_from(&entrant.name);
+            standing.points += points;
+        }
+    }
+
+    /// The `n` highest totals, ties by name.
+    pub fn top(&self, n: usize) -> Vec<Standing> {
+        let mut standings: Vec<Standing> = self.points.values().cloned().collect();
+        standings.sort_by(|a, b| b.points.cmp(&a.points).then_with(|| a.name.cmp(&b.name)));
+        standings.truncate(n);
+        standings
+    }
+}
+
+/// The configured season and its standings, shared by the room manager
+/// and the API.
+#[derive(Debug, Clone)]
+pub struct Season {
+    config: Arc<SeasonConfig>,
+    standings: Arc<Mutex<SeasonStandings>>,
+}
+
+impl Season {
+    pub fn new(config: SeasonConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+            standings: Arc::new(Mutex::new(SeasonStandings::default())),
+        }
+    }
+
+    pub fn is_configured(&self) -> bool {
+        !self.config.weeks.is_empty()
+    }
+
+    /// The week featured at unix time `now`, if the season is running.
+    pub fn featured_at(&self, now: u64) -> Option<FeaturedWeek<'_>> {
+        let elapsed = now.checked_sub(self.config.starts_at)?;
+        let index = elapsed / WEEK_SECS;
+        let week = self.config.weeks.get(usize::try_from(index).ok()?)?;
+        Some(FeaturedWeek {
+            number: index as u32 + 1,
+            week,
+            ends_at: self.config.starts_at + (index + 1) * WEEK_SECS,
+        })
+    }
+
+    /// Scores a featured match's `GameEnd` for the players who started it,
+    /// crediting each to their identity if `identities` has one. Bots and
+    /// spectators earn nothing.
+    pub fn scorer(
+        &self,
+        players: &[Player],
+        identities: &HashMap<PlayerId, PlayerIdentity>,
+    ) -> SeasonScorer {
+        SeasonScorer {
+            standings: Arc::clone(&self.standings),
+            entrants: players
+                .iter()
+                .filter(|p| !p.is_bot && !p.is_spectator)
+                .map(|p| {
+                    let entrant = Entrant {
+                        identity: identities.get(&p.id).cloned(),
+                        name: p.display_name.clone(),
+                    };
+                    (p.id, entrant)
+                })
+                .collect(),
+        }
+    }
+
+    pub fn standings(&self) -> Vec<Standing> {
+        self.standings
+            .lock()
+            .map(|s| s.top(STANDINGS_SHOWN))
+            .unwrap_or_default()
+    }
+}
+
+/// Watches a featured match's broadcasts and awards season points when it
+/// ends.
+pub struct SeasonScorer {
+    standings: Arc<Mutex<SeasonStandings>>,
+    entrants: HashMap<PlayerId, Entrant>,
+}
+
+impl SeasonScorer {
+    /// Inspect an encoded broadcast, awarding points on `GameEnd`.
+    pub fn observe(&self, data: &[u8]) {
+        if data.first() != Some(&(MessageType::GameEnd as u8)) {
+            return;
+        }
+        let Ok(ServerMessage::GameEnd(end)) = decode_server_message(data) else {
+            return;
+        };
+        let results: Vec<(Entrant, i32)> = end
+            .final_scores
+            .iter()
+            .filter(|s| !s.disconnected)
+            .filter_map(|s| Some((self.entrants.get(&s.player_id)?.clone(), s.score)))
+            .collect();
+        if let Ok(mut standings) = self.standings.lock() {
+            standings.award(&results);
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SeasonInfo {
+    pub name: String,
+    /// Absent before the season starts and after it ends.
+    pub featured: Option<FeaturedInfo>,
+    pub standings: Vec<Standing>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeaturedInfo {
+    pub week: u32,
+    pub weeks: usize,
+    pub game: String,
+    pub title: String,
+    pub modifiers: CustomSettings,
+    pub ends_at: u64,
+}
+
+/// GET /api/v1/public/season — the featured week and season standings.
+pub async fn get_season(State(state): State<AppState>) -> Result<Json<SeasonInfo>, AppError> {
+    let season = &state.season;
+    if !season.is_configured() {
+        return Err(AppError::NotFound("No season is configured".to_string()));
+    }
+    let featured = season
+        .featured_at(breakpoint_core::time::unix_now())
+        .map(|f| FeaturedInfo {
+            week: f.number,
+            weeks: season.config.weeks.len(),
+            game: f.week.game.clone(),
+            title: f.week.title.clone(),
+            modifiers: f.week.modifiers.clone(),
+            ends_at: f.ends_at,
+        });
+    Ok(Json(SeasonInfo {
+        name: season.config.name.clone(),
+        featured,
+        standings: season.standings(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn two_week_season() -> Season {
+        Season::new(SeasonConfig {
+            name: "Spring Cup".to_string(),
+            starts_at: 1_000,
+            weeks: vec![
+                SeasonWeek {
+                    game: "platform-racer".to_string(),
+                    ..SeasonWeek::default()
+                },
+                SeasonWeek {
+                    game: "laser-tag".to_string(),
+                    ..SeasonWeek::default()
+                },
+            ],
+        })
+    }
+
+    #[test]
+    fn weeks_rotate_until_the_season_ends() {
+        let season = two_week_season();
+        assert!(season.featured_at(999).is_none(), "Not started");
+        let first = season.featured_at(1_000).unwrap();
+        assert_eq!(
+            (first.number, first.week.game.as_str()),
+            (1, "platform-racer")
+        );
+        assert_eq!(first.ends_at, 1_000 + WEEK_SECS);
+        let second = season.featured_at(1_000 + WEEK_SECS).unwrap();
+        assert_eq!((second.number, second.week.game.as_str()), (2, "laser-tag"));
+        assert!(season.featured_at(1_000 + 2 * WEEK_SECS).is_none(), "Over");
+    }
+
+    fn named(name: &str) -> Entrant {
+        Entrant {
+            identity: None,
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn placements_earn_points_and_ties_share() {
+        let mut standings = SeasonStandings::default();
+        standings.award(&[
+            (named("Ada"), 30),
+            (named("Bo"), 20),
+            (named("Cy"), 20),
+            (named("Di"), 5),
+        ]);
+        standings.award(&[(named("Di"), 9), (named("Ada"), 1)]);
+        let top = standings.top(3);
+        let totals: Vec<(&str, u32)> = top.iter().map(|s| (s.name.as_str(), s.points)).collect();
+        // Ada 6 + 4; Bo and Cy 4 each; Di 1 + 6
+        assert_eq!(totals, [("Ada", 10), ("Di", 7), ("Bo", 4)]);
+    }
+
+    #[test]
+    fn points_follow_the_identity_across_names() {
+        let identity = PlayerIdentity::from_digest(&[7; 6]);
+        let as_name = |name: &str| Entrant {
+            identity: Some(identity.clone()),
+            name: name.to_string(),
+        };
+        let mut standings = SeasonStandings::default();
+        standings.award(&[(as_name("Ada"), 10), (named("Bo"), 5)]);
+        standings.award(&[(as_name("Ada2"), 10), (named("Ada"), 5)]);
+        let top = standings.top(3);
+        let totals: Vec<(&str, u32)> = top.iter().map(|s| (s.name.as_str(), s.points)).collect();
+        // The renamed player keeps their 12 points; a namesake is someone else
+        assert_eq!(totals, [("Ada2", 12), ("Ada", 4), ("Bo", 4)]);
+    }
+}
@@ -0,0 +1,482 @@
+//! Friends, recent co-players, presence, and room invites.
+//!
+//! Friends always see whether each other are online. Which room someone is
+//! in is only shown once they opt in, and then only to the players on
+//! their own friends list, so adding someone as a friend does not reveal
+//! where they play.
+//!
+//! Players are identified by a secret `identity_key` their client keeps in
+//! local storage and sends on join. The server only ever exposes the
+//! `PlayerIdentity` derived from it (shown to players as their friend ID),
+//! so knowing someone's friend ID does not let you impersonate them.
+//! Everything is held in memory and bounded.
+
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+use sha2::{Digest, Sha256};
+
+use breakpoint_core::game_trait::PlayerId;
+use breakpoint_core::net::messages::{FriendInviteMsg, SocialEntry, SocialUpdateMsg};
+use breakpoint_core::player::PlayerIdentity;
+
+/// Co-players remembered per identity.
+const MAX_RECENT: usize = 20;
+/// Friends allowed per identity.
+const MAX_FRIENDS: usize = 100;
+/// Undelivered invites kept per identity.
+const MAX_INBOX: usize = 10;
+/// Pending invites older than this are discarded.
+const INVITE_TTL_SECS: u64 = 60 * 60;
+/// Identities tracked before the least recently seen offline one is evicted.
+const MAX_PROFILES: usize = 10_000;
+/// Derive the player identity for an identity secret. Returns `None` for
+/// secrets that are too short or contain anything but ASCII alphanumerics
+/// and dashes.
+pub fn player_identity(identity_key: &str) -> Option<PlayerIdentity> {
+    let valid = (16..=128).contains(&identity_key.len())
+        && identity_key
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-');
+    if !valid {
+        return None;
+    }
+    Some(PlayerIdentity::from_digest(&Sha256::digest(
+        identity_key.as_bytes(),
+    )))
+}
+
+/// Where an online player is connected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Presence {
+    pub room_code: String,
+    pub player_id: PlayerId,
+}
+
+/// What happened to an invite.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InviteOutcome {
+    /// The friend is online; send the invite to this connection.
+    Deliver(Presence, FriendInviteMsg),
+    /// The friend is offline; the invite waits in their inbox.
+    Queued,
+}
+
+#[derive(Debug, Clone)]
+struct RecentPlayer {
+    id: PlayerIdentity,
+    last_played: u64,
+}
+
+#[derive(Debug, Default)]
+struct Profile {
+    name: String,
+    last_seen: u64,
+    recent: VecDeque<RecentPlayer>,
+    friends: BTreeSet<PlayerIdentity>,
+    inbox: Vec<FriendInviteMsg>,
+    /// Show this identity's room to its friends.
+    share_presence: bool,
+}
+
+/// In-memory social graph and presence for all known identities.
+#[derive(Debug, Default)]
+pub struct SocialStore {
+    profiles: HashMap<PlayerIdentity, Profile>,
+    presence: HashMap<PlayerIdentity, Presence>,
+}
+
+impl SocialStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark an identity online in a room. Returns invites that were waiting
+    /// in its inbox.
+    pub fn connect(
+        &mut self,
+        id: &PlayerIdentity,
+        name: &str,
+        room_code: &str,
+        player_id: PlayerId,
+        now: u64,
+    ) -> Vec<FriendInviteMsg> {
+        if !self.profiles.contains_key(id) {
+            self.evict_if_full();
+        }
+        let profile = self.profiles.entry(id.clone()).or_default();
+        profile.name = name.to_string();
+        profile.last_seen = now;
+        self.presence.insert(
+            id.clone(),
+            Presence {
+                room_code: room_code.to_string(),
+                player_id,
+            },
+        );
+        let mut inbox = std::mem::take(&mut profile.inbox);
+        inbox.retain(|i| now.saturating_sub(i.sent_at) < INVITE_TTL_SECS);
+        inbox
+    }
+
+    /// Mark an identity offline, unless it has since reconnected elsewhere.
+    pub fn disconnect(&mut self, id: &PlayerIdentity, player_id: PlayerId, now: u64) {
+        if self
+            .presence
+            .get(id)
+            .is_some_and(|p| p.player_id == player_id)
+        {
+            self.presence.remove(id);
+        }
+        if let Some(profile) = self.profiles.get_mut(id) {
+            profile.last_seen = now;
+        }
+    }
+
+    pub fn presence(&self, id: &PlayerIdentity) -> Option<&Presence> {
+        self.presence.get(id)
+    }
+
+    /// Record that these identities played a game together.
+    pub fn record_game(&mut self, ids: &[PlayerIdentity], now: u64) {
+        for id in ids {
+            let Some(profile) = self.profiles.get_mut(id) else {
+                continue;
+            };
+            for other in ids.iter().filter(|o| *o != id) {
+                profile.recent.retain(|r| &r.id != other);
+                profile.recent.push_front(RecentPlayer {
+                    id: other.clone(),
+                    last_played: now,
+                });
+            }
+            profile.recent.truncate(MAX_RECENT);
+        }
+    }
+
+    /// Add or remove a friend. Only known identities can be added.
+    pub fn set_friend(
+        &mut self,
+        id: &PlayerIdentity,
+        friend_id: &PlayerIdentity,
+        is_friend: bool,
+    ) -> Result<(), String> {
+        if id == friend_id {
+            return Err("cannot friend yourself".to_string());
+        }
+        if is_friend && !self.profiles.contains_key(friend_id) {
+            return Err(format!("unknown friend ID {friend_id}"));
+        }
+        let profile = self
+            .profiles
+            .get_mut(id)
+            .ok_or_else(|| format!("unknown identity {id}"))?;
+        if !is_friend {
+            profile.friends.remove(friend_id);
+            return Ok(());
+        }
+        if profile.friends.len() >= MAX_FRIENDS && !profile.friends.contains(friend_id) {
+            return Err(format!("friends list is full ({MAX_FRIENDS})"));
+        }
+        profile.friends.insert(friend_id.clone());
+        Ok(())
+    }
+
+    /// Opt in or out of showing this identity's room to its friends.
+    pub fn set_presence_sharing(&mut self, id: &PlayerIdentity, share: bool) -> Result<(), String> {
+        let profile = self
+            .profiles
+            .get_mut(id)
+            .ok_or_else(|| format!("unknown identity {id}"))?;
+        profile.share_presence = share;
+        Ok(())
+    }
+
+    /// The room `of` is in, if it shares its presence with `viewer`.
+    fn visible_room(&self, of: &PlayerIdentity, viewer: &PlayerIdentity) -> Option<String> {
+        let profile = self.profiles.get(of)?;
+        if !profile.share_presence || !profile.friends.contains(viewer) {
+            return None;
+        }
+        self.presence.get(of).map(|p| p.room_code.clone())
+    }
+
+    /// Invite a friend to the sender's current room.
+    pub fn invite(
+        &mut self,
+        from: &PlayerIdentity,
+        to: &PlayerIdentity,
+        now: u64,
+    ) -> Result<InviteOutcome, String> {
+        let sender = self
+            .profiles
+            .get(from)
+            .ok_or_else(|| format!("unknown identity {from}"))?;
+        if !sender.friends.contains(to) {
+            return Err(format!("{to} is not a friend"));
+        }
+        let room_code = self
+            .presence
+            .get(from)
+            .map(|p| p.room_code.clone())
+            .ok_or_else(|| "sender is not in a room".to_string())?;
+        let invite = FriendInviteMsg {
+            from_id: from.to_string(),
+            from_name: sender.name.clone(),
+            room_code,
+            sent_at: now,
+        };
+
+        if let Some(presence) = self.presence.get(to) {
+            return Ok(InviteOutcome::Deliver(presence.clone(), invite));
+        }
+        let recipient = self
+            .profiles
+            .get_mut(to)
+            .ok_or_else(|| format!("unknown friend ID {to}"))?;
+        recipient.inbox.retain(|i| i.from_id != from.as_str());
+        recipient.inbox.push(invite);
+        if recipient.inbox.len() > MAX_INBOX {
+            recipient.inbox.remove(0);
+        }
+        Ok(InviteOutcome::Queued)
+    }
+
+    /// Build the friends/recent list for an identity. Entries carry a room
+    /// code where that player shares their presence with it; the games
+    /// played in those rooms are left for the caller to fill in.
+    pub fn update_for(&self, id: &PlayerIdentity) -> Option<SocialUpdateMsg> {
+        let profile = self.profiles.get(id)?;
+        let entry = |other: &PlayerIdentity, last_played: u64| SocialEntry {
+            id: other.to_string(),
+            name: self
+                .profiles
+                .get(other)
+                .map(|p| p.name.clone())
+                .unwrap_or_default(),
+            online: self.presence.contains_key(other),
+            last_played,
+            room_code: self.visible_room(other, id),
+            game: None,
+        };
+        let last_played = |other: &PlayerIdentity| {
+            profile
+                .recent
+                .iter()
+                .find(|r| r.id == *other)
+                .map_or(0, |r| r.last_played)
+        };
+        Some(SocialUpdateMsg {
+            your_id: id.to_string(),
+            friends: profile
+                .friends
+                .iter()
+                .map(|f| entry(f, last_played(f)))
+                .collect(),
+            recent: profile
+                .recent
+                .iter()
+                .map(|r| entry(&r.id, r.last_played))
+                .collect(),
+            sharing_presence: profile.share_presence,
+        })
+    }
+
+    /// Online identities that list `id` as a friend, so they can be told when
+    /// its presence changes.
+    pub fn online_watchers(&self, id: &PlayerIdentity) -> Vec<(PlayerIdentity, Presence)> {
+        self.presence
+            .iter()
+            .filter(|(watcher, _)| {
+                self.profiles
+                    .get(*watcher)
+                    .is_some_and(|p| p.friends.contains(id))
+            })
+            .map(|(watcher, presence)| (watcher.clone(), presence.clone()))
+            .collect()
+    }
+
+    /// Drop the least recently seen offline identity when at capacity.
+    fn evict_if_full(&mut self) {
+        if self.profiles.len() < MAX_PROFILES {
+            return;
+        }
+        let oldest = self
+            .profiles
+            .iter()
+            .filter(|(id, _)| !self.presence.contains_key(*id))
+            .min_by_key(|(_, p)| p.last_seen)
+            .map(|(id, _)| id.clone());
+        if let Some(id) = oldest {
+            self.profiles.remove(&id);
+        }
+    }
+
+    /// Number of known identities.
+    pub fn len(&self) -> usize {
+        self.profiles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.profiles.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(name: &str) -> PlayerIdentity {
+        player_identity(&format!("{name}-secret-identity-key")).unwrap()
+    }
+
+    fn store_with(names: &[&str]) -> SocialStore {
+        let mut store = SocialStore::new();
+        for (i, name) in names.iter().enumerate() {
+            store.connect(
+                &id(name),
+                &format!("P{i}"),
+                "ROOM-0001",
+                i as PlayerId + 1,
+                100,
+            );
+        }
+        store
+    }
+
+    #[test]
+    fn player_identity_is_stable_and_validated() {
+        let a = player_identity("0123456789abcdef0123456789abcdef").unwrap();
+        assert_eq!(a.as_str().len(), PlayerIdentity::LEN);
+        assert_eq!(PlayerIdentity::parse(a.as_str()), Some(a.clone()));
+        assert_eq!(player_identity("0123456789abcdef0123456789abcdef"), Some(a));
+        assert!(player_identity("short").is_none());
+        assert!(player_identity("0123456789abcdef<script>").is_none());
+    }
+
+    #[test]
+    fn record_game_tracks_recent_co_players() {
+        let mut store = store_with(&["a", "b", "c"]);
+        store.record_game(&[id("a"), id("b")], 200);
+        store.record_game(&[id("a"), id("c")], 300);
+
+        let update = store.update_for(&id("a")).unwrap();
+        let recent: Vec<&str> = update.recent.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(recent, vec![id("c").as_str(), id("b").as_str()]);
+        assert_eq!(update.recent[0].last_played, 300);
+        assert_eq!(update.recent[0].name, "P2");
+        assert!(update.recent[0].online);
+    }
+
+    #[test]
+    fn set_friend_requires_known_identity() {
+        let mut store = store_with(&["a", "b"]);
+        assert!(store.set_friend(&id("a"), &id("zzz"), true).is_err());
+        assert!(store.set_friend(&id("a"), &id("a"), true).is_err());
+        store.set_friend(&id("a"), &id("b"), true).unwrap();
+        assert_eq!(
+            store.update_for(&id("a")).unwrap().friends[0].id,
+            id("b").as_str()
+        );
+        store.set_friend(&id("a"), &id("b"), false).unwrap();
+        assert!(store.update_for(&id("a")).unwrap().friends.is_empty());
+    }
+
+    #[test]
+    fn invite_delivers_to_online_friend() {
+        let mut store = store_with(&["a", "b"]);
+        assert!(
+            store.invite(&id("a"), &id("b"), 150).is_err(),
+            "not a friend yet"
+        );
+        store.set_friend(&id("a"), &id("b"), true).unwrap();
+        let InviteOutcome::Deliver(presence, invite) =
+            store.invite(&id("a"), &id("b"), 150).unwrap()
+        else {
+            panic!("b is online");
+        };
+        assert_eq!(presence.player_id, 2);
+        assert_eq!(invite.room_code, "ROOM-0001");
+        assert_eq!(invite.from_name, "P0");
+    }
+
+    #[test]
+    fn invite_to_offline_friend_waits_in_inbox() {
+        let mut store = store_with(&["a", "b"]);
+        store.set_friend(&id("a"), &id("b"), true).unwrap();
+        store.disconnect(&id("b"), 2, 120);
+        assert_eq!(
+            store.invite(&id("a"), &id("b"), 150).unwrap(),
+            InviteOutcome::Queued
+        );
+        // Re-inviting replaces rather than duplicates
+        assert_eq!(
+            store.invite(&id("a"), &id("b"), 160).unwrap(),
+            InviteOutcome::Queued
+        );
+
+        let inbox = store.connect(&id("b"), "P1", "ROOM-0002", 9, 200);
+        assert_eq!(inbox.len(), 1);
+        assert_eq!(inbox[0].sent_at, 160);
+        assert!(
+            store
+                .connect(&id("b"), "P1", "ROOM-0002", 9, 210)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn expired_invites_are_dropped() {
+        let mut store = store_with(&["a", "b"]);
+        store.set_friend(&id("a"), &id("b"), true).unwrap();
+        store.disconnect(&id("b"), 2, 120);
+        store.invite(&id("a"), &id("b"), 150).unwrap();
+        assert!(
+            store
+                .connect(&id("b"), "P1", "ROOM-0002", 9, 150 + INVITE_TTL_SECS)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn stale_disconnect_keeps_newer_presence() {
+        let mut store = store_with(&["a"]);
+        store.connect(&id("a"), "P0", "ROOM-0002", 7, 150);
+        store.disconnect(&id("a"), 1, 160);
+        assert_eq!(store.presence(&id("a")).unwrap().player_id, 7);
+    }
+
+    #[test]
+    fn watchers_are_online_friends() {
+        let mut store = store_with(&["a", "b", "c"]);
+        store.set_friend(&id("a"), &id("c"), true).unwrap();
+        store.set_friend(&id("b"), &id("c"), true).unwrap();
+        store.disconnect(&id("b"), 2, 120);
+        let watchers = store.online_watchers(&id("c"));
+        assert_eq!(watchers.len(), 1);
+        assert_eq!(watchers[0].0, id("a"));
+    }
+
+    #[test]
+    fn rooms_are_shown_only_to_friends_of_a_sharing_player() {
+        let mut store = store_with(&["a", "b", "c"]);
+        store.connect(&id("b"), "P1", "ROOM-0002", 2, 110);
+        store.set_friend(&id("a"), &id("b"), true).unwrap();
+        store.set_friend(&id("c"), &id("b"), true).unwrap();
+        store.set_friend(&id("b"), &id("a"), true).unwrap();
+        let room_seen_by = |store: &SocialStore, viewer: &str| {
+            store.update_for(&id(viewer)).unwrap().friends[0]
+                .room_code
+                .clone()
+        };
+        assert_eq!(room_seen_by(&store, "a"), None, "b has not opted in");
+
+        store.set_presence_sharing(&id("b"), true).unwrap();
+        assert!(store.update_for(&id("b")).unwrap().sharing_presence);
+        assert_eq!(room_seen_by(&store, "a").as_deref(), Some("ROOM-0002"));
+        assert_eq!(room_seen_by(&store, "c"), None, "not on b's friends list");
+
+        store.disconnect(&id("b"), 2, 120);
+        assert_eq!(room_seen_by(&store, "a"), None, "b is offline");
+        assert!(store.set_presence_sharing(&id("zzz"), true).is_err());
+    }
+}
@@ -2,24 +2,59 @@ use std::collections::HashMap;
 use std::net::IpAddr;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
 
 use tokio::sync::RwLock;
 use tokio_util::sync::CancellationToken;
 
+use crate::announcements::AnnouncementStore;
 use crate::auth::AuthConfig;
+use crate::client_reports::ClientReportStore;
 use crate::config::ServerConfig;
+use crate::event_sources::EventSourceRegistry;
 use crate::event_store::EventStore;
 use crate::game_loop::ServerGameRegistry;
+use crate::history::MatchHistory;
+use crate::moderation::BanStore;
+use crate::player_settings::PlayerSettingsStore;
 use crate::rate_limit::IpRateLimiter;
 use crate::room_manager::RoomManager;
+use crate::room_webhooks::RoomWebhookStore;
+use crate::schedule::ScheduleStore;
+use crate::season::Season;
+use crate::social::SocialStore;
+use crate::status::BroadcastStats;
 
 pub type SharedRoomManager = Arc<RwLock<RoomManager>>;
 pub type SharedEventStore = Arc<RwLock<EventStore>>;
+pub type SharedScheduleStore = Arc<RwLock<ScheduleStore>>;
+pub type SharedSocialStore = Arc<RwLock<SocialStore>>;
+pub type SharedAnnouncementStore = Arc<RwLock<AnnouncementStore>>;
+pub type SharedClientReportStore = Arc<RwLock<ClientReportStore>>;
+pub type SharedMatchHistory = Arc<RwLock<MatchHistory>>;
+pub type SharedPlayerSettings = Arc<RwLock<PlayerSettingsStore>>;
+pub type SharedRoomWebhooks = Arc<RwLock<RoomWebhookStore>>;
+pub type SharedBanStore = Arc<RwLock<BanStore>>;
 
 #[derive(Clone)]
 pub struct AppState {
     pub rooms: SharedRoomManager,
     pub event_store: SharedEventStore,
+    /// Polled external event sources (CI, issue trackers, monitors).
+    pub event_sources: EventSourceRegistry,
+    pub schedules: SharedScheduleStore,
+    pub social: SharedSocialStore,
+    pub announcements: SharedAnnouncementStore,
+    pub client_reports: SharedClientReportStore,
+    pub history: SharedMatchHistory,
+    /// Cross-device settings, keyed by public friend ID.
+    pub player_settings: SharedPlayerSettings,
+    /// Outbound webhooks for room lifecycle events.
+    pub room_webhooks: SharedRoomWebhooks,
+    /// Identity and IP bans, checked on join and reconnect.
+    pub bans: SharedBanStore,
+    /// Weekly featured games and season standings.
+    pub season: Season,
     pub auth: AuthConfig,
     pub game_registry: Arc<ServerGameRegistry>,
     pub config: Arc<ServerConfig>,
@@ -27,6 +62,9 @@ pub struct AppState {
     pub sse_subscriber_count: Arc<AtomicUsize>,
     pub api_rate_limiter: Arc<IpRateLimiter>,
     pub ws_per_ip: Arc<std::sync::Mutex<HashMap<IpAddr, usize>>>,
+    /// Alert fan-out timing, for the status page.
+    pub broadcast_stats: Arc<BroadcastStats>,
+    pub started_at: Instant,
     pub shutdown: CancellationToken,
 }
 
@@ -41,13 +79,50 @@ impl AppState {
             config.limits.max_stored_events,
             config.limits.broadcast_capacity,
         );
+        let schedules = ScheduleStore::new(
+            config.schedules.max_schedules,
+            config.schedules.reminder_minutes,
+        );
         let api_rate_limiter = Arc::new(IpRateLimiter::new(
             config.limits.api_rate_limit_burst as f64,
             config.limits.api_rate_limit_per_sec,
         ));
+        let history: SharedMatchHistory = Arc::new(RwLock::new(MatchHistory::new(&config.replays)));
+        let player_settings: SharedPlayerSettings =
+            Arc::new(RwLock::new(PlayerSettingsStore::new(&config.settings_sync)));
+        let mut rooms = RoomManager::with_phase_timings(config.rooms.phase_timings());
+        rooms.set_join_queue_limits(config.rooms.join_queue_limits());
+        rooms.set_room_capacity(config.rooms.capacity());
+        rooms.set_code_format(config.rooms.code_format());
+        rooms.set_overlay_defaults(config.overlay.room_config.clone());
+        rooms.set_memory_budget(config.memory.budget());
+        if config.replays.enabled {
+            rooms.record_matches(Arc::clone(&history), config.replays.max_frames_per_round);
+        }
+        if config.settings_sync.enabled {
+            rooms.record_tutorials(Arc::clone(&player_settings));
+        }
+        let season = Season::new(config.season.clone());
+        if season.is_configured() {
+            rooms.set_season(season.clone());
+        }
+        let event_store: SharedEventStore = Arc::new(RwLock::new(event_store));
+        let shutdown = CancellationToken::new();
         Self {
-            rooms: Arc::new(RwLock::new(RoomManager::new())),
-            event_store: Arc::new(RwLock::new(event_store)),
+            rooms: Arc::new(RwLock::new(rooms)),
+            event_sources: EventSourceRegistry::new(Arc::clone(&event_store), shutdown.clone()),
+            event_store,
+            schedules: Arc::new(RwLock::new(schedules)),
+            social: Arc::new(RwLock::new(SocialStore::new())),
+            announcements: Arc::new(RwLock::new(AnnouncementStore::new(&config.announcements))),
+            client_reports: Arc::new(RwLock::new(ClientReportStore::new(
+                &config.client_telemetry,
+            ))),
+            history,
+            player_settings,
+            room_webhooks: Arc::new(RwLock::new(RoomWebhookStore::new(&config.room_webhooks))),
+            bans: Arc::new(RwLock::new(BanStore::new(&config.moderation))),
+            season,
             auth,
             game_registry: Arc::new(ServerGameRegistry::new()),
             config: Arc::new(config),
@@ -55,7 +130,9 @@ impl AppState {
             sse_subscriber_count: Arc::new(AtomicUsize::new(0)),
             api_rate_limiter,
             ws_per_ip: Arc::new(std::sync::Mutex::new(HashMap::new())),
-            shutdown: CancellationToken::new(),
+            broadcast_stats: Arc::new(BroadcastStats::default()),
+            started_at: Instant::now(),
+            shutdown,
         }
     }
 }
@@ -105,6 +182,10 @@ impl IpConnectionGuard {
         drop(map);
         Some(Self { ip, ws_per_ip })
     }
+
+    pub fn ip(&self) -> IpAddr {
+        self.ip
+    }
 }
 
 impl Drop for IpConnectionGuard {
@@ -0,0 +1,348 @@
+//! The stats board: a rotating display for an office TV at
+//! `/stats-board.html`. `GET /api/v1/public/stats-board/stream` pushes one
+//! page every `[stats_board] page_secs` as a server-sent `frame` event,
+//! cycling through the session leaderboard, recent match highlights, alert
+//! status, and upcoming scheduled games, so the page needs no interaction.
+//! Like `/status` it is public, so alerts are reduced to counts.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::sse::{Event as SseEvent, KeepAlive, Sse};
+use futures::stream::{self, Stream};
+use serde::Serialize;
+
+use breakpoint_core::event_source::{SourceHealth, SourceStatus};
+use breakpoint_core::events::Priority;
+
+use crate::event_store::StoredEvent;
+use crate::history::MatchSummary;
+use crate::schedule::ScheduleSummary;
+use crate::state::{AppState, ConnectionGuard};
+
+/// One player's totals over the matches in the history.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LeaderboardRow {
+    pub name: String,
+    /// Final scores summed over every match played.
+    pub points: i64,
+    /// Matches finished with the top score, ties included.
+    pub wins: u32,
+    pub matches: u32,
+}
+
+/// A recently finished match and who won it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Highlight {
+    pub game: String,
+    /// Everyone tied for the top score.
+    pub winners: Vec<String>,
+    pub winning_score: i32,
+    pub players: usize,
+    pub ended_at: u64,
+}
+
+/// Alert counts, without titles or sources' errors.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AlertStatus {
+    /// `ok`, or `degraded` while an event source is failing.
+    pub status: &'static str,
+    /// Action-required events nobody has claimed.
+    pub pending_actions: usize,
+    /// Unclaimed actions at urgent or critical priority.
+    pub urgent_actions: usize,
+    /// Event sources reporting degraded or unhealthy.
+    pub failing_sources: usize,
+}
+
+/// What one frame shows.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "page", rename_all = "snake_case")]
+pub enum BoardPage {
+    Leaderboard { rows: Vec<LeaderboardRow> },
+    Highlights { matches: Vec<Highlight> },
+    Alerts(AlertStatus),
+    Schedule { upcoming: Vec<ScheduleSummary> },
+}
+
+/// A page as pushed to the display.
+#[derive(Debug, Clone, Serialize)]
+pub struct DisplayFrame {
+    #[serde(flatten)]
+    pub page: BoardPage,
+    /// Position of this page in the rotation, and how many pages it has.
+    pub index: usize,
+    pub pages: usize,
+    /// Seconds until the next frame.
+    pub hold_secs: u64,
+}
+
+/// Players' totals over `matches`, highest points first, at most `max_rows`.
+pub fn leaderboard(matches: &[MatchSummary], max_rows: usize) -> Vec<LeaderboardRow> {
+    let mut rows: HashMap<&str, LeaderboardRow> = HashMap::new();
+    for m in matches {
+        let top = m.final_scores.iter().map(|s| s.score).max();
+        for score in &m.final_scores {
+            let Some(player) = m.players.iter().find(|p| p.id == score.player_id) else {
+                continue;
+            };
+            let row = rows
+                .entry(&player.display_name)
+                .or_insert_with(|| LeaderboardRow {
+                    name: player.display_name.clone(),
+                    points: 0,
+                    wins: 0,
+                    matches: 0,
+                });
+            row.points += i64::from(score.score);
+            row.matches += 1;
+            if Some(score.score) == top {
+                row.wins += 1;
+            }
+        }
+    }
+    let mut rows: Vec<LeaderboardRow> = rows.into_values().collect();
+    rows.sort_by(|a, b| {
+        b.points
+            .cmp(&a.points)
+            .then(b.wins.cmp(&a.wins))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    rows.truncate(max_rows);
+    rows
+}
+
+/// The winners of `matches` (most recent first), at most `max_rows`.
+pub fn highlights(matches: &[MatchSummary], max_rows: usize) -> Vec<Highlight> {
+    matches
+        .iter()
+        .filter_map(|m| {
+            let winning_score = m.final_scores.iter().map(|s| s.score).max()?;
+            let winners = m
+                .final_scores
+                .iter()
+                .filter(|s| s.score == winning_score)
+                .filter_map(|s| m.players.iter().find(|p| p.id == s.player_id))
+                .map(|p| p.display_name.clone())
+                .collect();
+            Some(Highlight {
+                game: m.game.clone(),
+                winners,
+                winning_score,
+                players: m.final_scores.len(),
+                ended_at: m.ended_at,
+            })
+        })
+        .take(max_rows)
+        .collect()
+}
+
+pub fn alert_status(pending: &[&StoredEvent], sources: &[SourceStatus]) -> AlertStatus {
+    let failing_sources = sources
+        .iter()
+        .filter(|s| matches!(s.health, SourceHealth::Degraded | SourceHealth::Unhealthy))
+        .count();
+    AlertStatus {
+        status: if failing_sources > 0 {
+            "degraded"
+        } else {
+            "ok"
+        },
+        pending_actions: pending.len(),
+        urgent_actions: pending
+            .iter()
+            .filter(|e| e.event.priority >= Priority::Urgent)
+            .count(),
+        failing_sources,
+    }
+}
+
+/// The pages worth showing right now. Pages with nothing on them are left
+/// out; the alert status always has something to say.
+pub fn pages(
+    matches: &[MatchSummary],
+    alerts: AlertStatus,
+    upcoming: Vec<ScheduleSummary>,
+    max_rows: usize,
+) -> Vec<BoardPage> {
+    let mut pages = Vec::new();
+    let rows = leaderboard(matches, max_rows);
+    if !rows.is_empty() {
+        pages.push(BoardPage::Leaderboard { rows });
+    }
+    let matches = highlights(matches, max_rows);
+    if !matches.is_empty() {
+        pages.push(BoardPage::Highlights { matches });
+    }
+    pages.push(BoardPage::Alerts(alerts));
+    if !upcoming.is_empty() {
+        let upcoming = upcoming.into_iter().take(max_rows).collect();
+        pages.push(BoardPage::Schedule { upcoming });
+    }
+    pages
+}
+
+async fn collect_pages(state: &AppState) -> Vec<BoardPage> {
+    let max_rows = state.config.stats_board.max_rows;
+    let matches = state.history.read().await.list();
+    let alerts = {
+        let store = state.event_store.read().await;
+        alert_status(&store.pending_actions(), &state.event_sources.statuses())
+    };
+    let upcoming = {
+        let now = breakpoint_core::time::unix_now();
+        let schedules = state.schedules.read().await;
+        schedules
+            .upcoming(now)
+            .into_iter()
+            .map(ScheduleSummary::from)
+            .collect()
+    };
+    pages(&matches, alerts, upcoming, max_rows)
+}
+
+/// GET /api/v1/public/stats-board/stream — the board's frames as SSE.
+pub async fn stats_board_stream(
+    State(state): State<AppState>,
+) -> Result<Sse<impl Stream<Item = Result<SseEvent, Infallible>>>, StatusCode> {
+    let config = &state.config.stats_board;
+    if !config.enabled {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let max_sse = state.config.limits.max_sse_subscribers;
+    let current = state.sse_subscriber_count.load(Ordering::Relaxed);
+    if current >= max_sse {
+        tracing::warn!(current, max = max_sse, "SSE subscriber limit reached");
+        return Err(StatusCode::SERVICE_UNAVAILABLE);
+    }
+    let guard = ConnectionGuard::new(Arc::clone(&state.sse_subscriber_count));
+    let hold = Duration::from_secs(config.page_secs);
+
+    // The first frame goes out at once, then one per `hold`
+    let frames = stream::unfold(
+        (state, 0usize, guard),
+        move |(state, cursor, guard)| async move {
+            if cursor > 0 {
+                tokio::time::sleep(hold).await;
+            }
+            let mut pages = collect_pages(&state).await;
+            let index = cursor % pages.len();
+            let frame = DisplayFrame {
+                page: pages.swap_remove(index),
+                index,
+                pages: pages.len() + 1,
+                hold_secs: hold.as_secs(),
+            };
+            let json = serde_json::to_string(&frame).unwrap_or_default();
+            let event = SseEvent::default().event("frame").data(json);
+            Some((Ok(event), (state, cursor.wrapping_add(1), guard)))
+        },
+    );
+    Ok(Sse::new(frames).keep_alive(KeepAlive::default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use breakpoint_core::events::Event;
+    use breakpoint_core::game_trait::PlayerScore;
+    use breakpoint_core::test_helpers::{make_players, make_test_event};
+
+    use super::*;
+
+    fn finished(game: &str, scores: &[(u64, i32)], ended_at: u64) -> MatchSummary {
+        MatchSummary {
+            match_id: format!("m-{ended_at}"),
+            game: game.to_string(),
+            room_code: "ABCD-1234".to_string(),
+            players: make_players(3),
+            rounds: 1,
+            final_scores: scores
+                .iter()
+                .map(|&(player_id, score)| PlayerScore {
+                    player_id,
+                    score,
+                    disconnected: false,
+                })
+                .collect(),
+            started_at: ended_at - 60,
+            ended_at,
+            replay_bytes: 0,
+        }
+    }
+
+    fn stored(event: Event) -> StoredEvent {
+        StoredEvent {
+            event,
+            claimed_by: None,
+            claimed_at: None,
+        }
+    }
+
+    #[test]
+    fn leaderboard_sums_the_session_and_counts_shared_wins() {
+        // Most recent first, as the history lists them
+        let matches = [
+            finished("tron", &[(1, 2), (2, 2), (3, 0)], 300),
+            finished("mini-golf", &[(1, 5), (2, 9)], 200),
+        ];
+        let rows = leaderboard(&matches, 8);
+        let summary: Vec<(&str, i64, u32, u32)> = rows
+            .iter()
+            .map(|r| (r.name.as_str(), r.points, r.wins, r.matches))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("Player2", 11, 2, 2),
+                ("Player1", 7, 1, 2),
+                ("Player3", 0, 0, 1)
+            ]
+        );
+        assert_eq!(leaderboard(&matches, 1).len(), 1);
+
+        let recent = highlights(&matches, 8);
+        assert_eq!(recent[0].game, "tron");
+        assert_eq!(recent[0].winners, ["Player1", "Player2"]);
+        assert_eq!((recent[1].winning_score, recent[1].players), (9, 2));
+    }
+
+    #[test]
+    fn alerts_are_counts_only_and_empty_pages_are_skipped() {
+        let mut urgent = make_test_event("a");
+        urgent.priority = Priority::Urgent;
+        let (urgent, notice) = (stored(urgent), stored(make_test_event("b")));
+        let mut failing = SourceStatus::new("ci", Duration::from_secs(30));
+        failing.health = SourceHealth::Unhealthy;
+        let alerts = alert_status(&[&urgent, &notice], &[failing]);
+        assert_eq!(
+            alerts,
+            AlertStatus {
+                status: "degraded",
+                pending_actions: 2,
+                urgent_actions: 1,
+                failing_sources: 1,
+            }
+        );
+
+        // A fresh server still has the alert status to show
+        let fresh = pages(&[], alert_status(&[], &[]), Vec::new(), 8);
+        assert!(matches!(fresh[..], [BoardPage::Alerts(_)]));
+
+        let all = pages(&[finished("tron", &[(1, 1)], 100)], alerts, Vec::new(), 8);
+        let json = serde_json::to_value(DisplayFrame {
+            page: all[0].clone(),
+            index: 0,
+            pages: all.len(),
+            hold_secs: 15,
+        })
+        .unwrap();
+        assert_eq!(json["page"], "leaderboard");
+        assert_eq!(json["rows"][0]["name"], "Player1");
+        assert_eq!(all.len(), 3, "No schedule page without upcoming games");
+    }
+}
@@ -0,0 +1,289 @@
+//! Server status: the document behind `GET /api/v1/status` and the
+//! server-rendered page at `/status`, which leaves out events and source
+//! errors so it can be pinned on a dashboard without a token.
+
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use axum::extract::State;
+use axum::http::header;
+use axum::response::{Html, IntoResponse};
+use serde::Serialize;
+
+use breakpoint_core::event_source::{SourceHealth, SourceStatus};
+
+use crate::health::{ConnectionInfo, RoomInfo};
+use crate::state::AppState;
+
+/// Seconds between reloads of the status page.
+const PAGE_REFRESH_SECS: u32 = 30;
+
+/// How long alert events take to reach the rooms, recorded by the event
+/// broadcaster.
+#[derive(Debug, Default)]
+pub struct BroadcastStats {
+    last: Mutex<Option<BroadcastSample>>,
+    lagged: AtomicU64,
+}
+
+/// The most recent fan-out of an alert event to every room.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct BroadcastSample {
+    /// Unix seconds the broadcast finished.
+    pub at: u64,
+    /// From the broadcaster receiving the event to every room having it.
+    pub latency_ms: f64,
+}
+
+impl BroadcastStats {
+    pub fn record(&self, latency: Duration) {
+        let sample = BroadcastSample {
+            at: breakpoint_core::time::unix_now(),
+            latency_ms: latency.as_secs_f64() * 1000.0,
+        };
+        *self.last.lock().unwrap_or_else(|e| e.into_inner()) = Some(sample);
+    }
+
+    /// The broadcaster fell behind and skipped `skipped` events.
+    pub fn record_lag(&self, skipped: u64) {
+        self.lagged.fetch_add(skipped, Ordering::Relaxed);
+    }
+
+    pub fn last(&self) -> Option<BroadcastSample> {
+        *self.last.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn lagged(&self) -> u64 {
+        self.lagged.load(Ordering::Relaxed)
+    }
+}
+
+/// Events waiting on someone or something.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct EventBacklog {
+    /// Action-required events nobody has claimed.
+    pub pending_actions: usize,
+    /// Events stored but not yet fanned out to the rooms.
+    pub queued_broadcasts: usize,
+    /// Events the broadcaster skipped since startup because it fell behind.
+    pub lagged_broadcasts: u64,
+}
+
+/// Health of the whole server.
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerStatus {
+    /// `ok`, or `degraded` while an event source is failing.
+    pub status: &'static str,
+    pub version: &'static str,
+    pub uptime_secs: u64,
+    pub rooms: RoomInfo,
+    pub connections: ConnectionInfo,
+    /// Health and last poll of each registered event source.
+    pub sources: Vec<SourceStatus>,
+    pub backlog: EventBacklog,
+    pub last_broadcast: Option<BroadcastSample>,
+}
+
+impl ServerStatus {
+    pub async fn collect(state: &AppState) -> Self {
+        let (active, players) = state.rooms.read().await.stats();
+        let (pending_actions, queued_broadcasts) = {
+            let store = state.event_store.read().await;
+            (
+                store.stats().total_pending_actions,
+                store.queued_broadcasts(),
+            )
+        };
+        let sources = state.event_sources.statuses();
+        let failing = sources
+            .iter()
+            .any(|s| matches!(s.health, SourceHealth::Degraded | SourceHealth::Unhealthy));
+        Self {
+            status: if failing { "degraded" } else { "ok" },
+            version: env!("CARGO_PKG_VERSION"),
+            uptime_secs: state.started_at.elapsed().as_secs(),
+            rooms: RoomInfo { active, players },
+            connections: ConnectionInfo {
+                websocket: state.ws_connection_count.load(Ordering::Relaxed),
+                sse: state.sse_subscriber_count.load(Ordering::Relaxed),
+            },
+            sources,
+            backlog: EventBacklog {
+                pending_actions,
+                queued_broadcasts,
+                lagged_broadcasts: state.broadcast_stats.lagged(),
+            },
+            last_broadcast: state.broadcast_stats.last(),
+        }
+    }
+
+    /// The status as a self-refreshing HTML page. Inline styles are blocked
+    /// by the content security policy, so it is plain markup.
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        let _ = write!(
+            html,
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n\
+             <meta charset=\"utf-8\">\n\
+             <meta http-equiv=\"refresh\" content=\"{PAGE_REFRESH_SECS}\">\n\
+             <title>Breakpoint status: {status}</title>\n</head>\n<body>\n\
+             <h1>Breakpoint is {status}</h1>\n<table>\n",
+            status = self.status,
+        );
+        let last_broadcast = self.last_broadcast.map_or("none yet".to_string(), |b| {
+            format!("{:.1} ms", b.latency_ms)
+        });
+        for (label, value) in [
+            ("Version", self.version.to_string()),
+            ("Uptime", format_uptime(self.uptime_secs)),
+            ("Active rooms", self.rooms.active.to_string()),
+            ("Players", self.rooms.players.to_string()),
+            (
+                "WebSocket connections",
+                self.connections.websocket.to_string(),
+            ),
+            ("SSE subscribers", self.connections.sse.to_string()),
+            (
+                "Unclaimed actions",
+                self.backlog.pending_actions.to_string(),
+            ),
+            (
+                "Queued broadcasts",
+                self.backlog.queued_broadcasts.to_string(),
+            ),
+            (
+                "Skipped broadcasts",
+                self.backlog.lagged_broadcasts.to_string(),
+            ),
+            ("Last broadcast latency", last_broadcast),
+        ] {
+            let _ = writeln!(html, "<tr><th>{label}</th><td>{value}</td></tr>");
+        }
+        html.push_str("</table>\n<h2>Event sources</h2>\n");
+        if self.sources.is_empty() {
+            html.push_str("<p>None registered.</p>\n");
+        } else {
+            html.push_str(
+                "<table>\n<tr><th>Source</th><th>Health</th>\
+                 <th>Failures in a row</th></tr>\n",
+            );
+            for source in &self.sources {
+                let _ = writeln!(
+                    html,
+                    "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    escape(&source.name),
+                    health_label(source.health),
+                    source.consecutive_failures,
+                );
+            }
+            html.push_str("</table>\n");
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+/// GET /status — server health for humans.
+pub async fn status_page(State(state): State<AppState>) -> impl IntoResponse {
+    let html = ServerStatus::collect(&state).await.to_html();
+    ([(header::CACHE_CONTROL, "no-store")], Html(html))
+}
+
+fn health_label(health: SourceHealth) -> &'static str {
+    match health {
+        SourceHealth::Starting => "starting",
+        SourceHealth::Healthy => "healthy",
+        SourceHealth::Degraded => "degraded",
+        SourceHealth::Unhealthy => "unhealthy",
+        SourceHealth::Stopped => "stopped",
+    }
+}
+
+/// `3d 4h 5m`, leaving out leading zero units.
+fn format_uptime(secs: u64) -> String {
+    let (days, hours, minutes) = (secs / 86_400, secs / 3600 % 24, secs / 60 % 60);
+    if days > 0 {
+        format!("{days}d {hours}h {minutes}m")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else {
+        format!("{minutes}m")
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(sources: Vec<SourceStatus>) -> ServerStatus {
+        ServerStatus {
+            status: "ok",
+            version: "1.2.3",
+            uptime_secs: 90_061,
+            rooms: RoomInfo {
+                active: 2,
+                players: 7,
+            },
+            connections: ConnectionInfo {
+                websocket: 7,
+                sse: 1,
+            },
+            sources,
+            backlog: EventBacklog {
+                pending_actions: 3,
+                queued_broadcasts: 0,
+                lagged_broadcasts: 0,
+            },
+            last_broadcast: Some(BroadcastSample {
+                at: 1_760_600_000,
+                latency_ms: 1.5,
+            }),
+        }
+    }
+
+    #[test]
+    fn page_shows_totals_and_escapes_source_names() {
+        let mut source = SourceStatus::new("ci<poller>", Duration::from_secs(30));
+        source.health = SourceHealth::Degraded;
+        source.last_error = Some("HTTP 502 from \"api\"".to_string());
+        let html = status(vec![source]).to_html();
+        assert!(html.contains("<tr><th>Uptime</th><td>1d 1h 1m</td></tr>"));
+        assert!(html.contains("<tr><th>Players</th><td>7</td></tr>"));
+        assert!(html.contains("<td>1.5 ms</td>"));
+        assert!(html.contains("ci&lt;poller&gt;"));
+        assert!(!html.contains("<poller>"));
+        // Errors can name internal hosts; the page is public
+        assert!(!html.contains("HTTP 502"));
+    }
+
+    #[test]
+    fn broadcast_stats_keep_the_latest_sample_and_total_lag() {
+        let stats = BroadcastStats::default();
+        assert_eq!(stats.last(), None);
+        stats.record(Duration::from_millis(4));
+        stats.record(Duration::from_micros(1500));
+        stats.record_lag(2);
+        stats.record_lag(3);
+        assert_eq!(stats.last().unwrap().latency_ms, 1.5);
+        assert_eq!(stats.lagged(), 5);
+        assert_eq!(format_uptime(59), "0m");
+        assert_eq!(format_uptime(3_660), "1h 1m");
+    }
+}
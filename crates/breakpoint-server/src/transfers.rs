@@ -0,0 +1,110 @@
+//! Room merges and splits. Rather than moving connections between rooms,
+//! the server offers each affected player a `RoomTransfer`: a target room
+//! and a one-time token. Clients that accept leave and join the target
+//! with the token as their session token, which keeps their name, color,
+//! and session score; anyone who declines simply stays put.
+
+use std::time::{Duration, Instant};
+
+use breakpoint_core::game_trait::PlayerId;
+use breakpoint_core::player::PlayerColor;
+
+/// How long a transfer offer stays open.
+pub const TRANSFER_TTL: Duration = Duration::from_secs(60);
+
+/// Fewest connected players a lobby needs before it can be split.
+pub const MIN_SPLIT_PLAYERS: usize = 4;
+
+/// An offered move, redeemed by joining with its token.
+#[derive(Debug, Clone)]
+pub struct PendingTransfer {
+    pub room_code: String,
+    pub display_name: String,
+    pub color: PlayerColor,
+    pub session_score: i32,
+    pub offered_at: Instant,
+}
+
+impl PendingTransfer {
+    pub fn is_expired(&self) -> bool {
+        self.offered_at.elapsed() > TRANSFER_TTL
+    }
+}
+
+/// Split `players` (with their session scores) into two even halves with
+/// totals as close as we cheaply can: a greedy pass, then single swaps
+/// while they narrow the gap. `leader` stays; returns who moves.
+pub fn balanced_split(leader: PlayerId, players: &[(PlayerId, i32)]) -> Vec<PlayerId> {
+    let mut rest: Vec<(PlayerId, i32)> = players
+        .iter()
+        .copied()
+        .filter(|&(id, _)| id != leader)
+        .collect();
+    // Strongest first, so each pick evens out the gap left by the last
+    rest.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    let total = rest.len() + 1;
+    let stay_cap = total.div_ceil(2);
+    let move_cap = total / 2;
+    let leader_score = players.iter().find(|p| p.0 == leader).map_or(0, |p| p.1);
+    let mut staying: Vec<(PlayerId, i32)> = Vec::with_capacity(stay_cap);
+    let mut moving: Vec<(PlayerId, i32)> = Vec::with_capacity(move_cap);
+    let sum = |group: &[(PlayerId, i32)]| group.iter().map(|p| p.1).sum::<i32>();
+    for player in rest {
+        let stay_total = leader_score + sum(&staying);
+        let move_behind = (sum(&moving), moving.len()) <= (stay_total, staying.len() + 1);
+        if moving.len() < move_cap && (staying.len() + 1 == stay_cap || move_behind) {
+            moving.push(player);
+        } else {
+            staying.push(player);
+        }
+    }
+
+    let gap = |moving: &[(PlayerId, i32)], staying: &[(PlayerId, i32)]| {
+        (sum(moving) - sum(staying) - leader_score).abs()
+    };
+    loop {
+        let current = gap(&moving, &staying);
+        let best = (0..moving.len())
+            .flat_map(|m| (0..staying.len()).map(move |s| (m, s)))
+            .map(|(m, s)| {
+                let shift = moving[m].1 - staying[s].1;
+                let gap = (sum(&moving) - shift - (sum(&staying) + shift) - leader_score).abs();
+                (gap, m, s)
+            })
+            .min();
+        match best {
+            Some((gap, m, s)) if gap < current => std::mem::swap(&mut moving[m], &mut staying[s]),
+            _ => break,
+        }
+    }
+    moving.into_iter().map(|(id, _)| id).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_are_even_and_balanced() {
+        let players = [(1, 2), (2, 10), (3, 9), (4, 1), (5, 0), (6, 8)];
+        let moving = balanced_split(1, &players);
+        assert_eq!(moving.len(), 3);
+        assert!(!moving.contains(&1), "The leader stays");
+        let moved: i32 = players
+            .iter()
+            .filter(|p| moving.contains(&p.0))
+            .map(|p| p.1)
+            .sum();
+        let stayed: i32 = players.iter().map(|p| p.1).sum::<i32>() - moved;
+        // 13 against 17 is the closest these scores allow
+        assert_eq!((moved - stayed).abs(), 4, "{moved} vs {stayed}");
+
+        // Odd counts leave the extra player with the leader
+        let moving = balanced_split(1, &players[..5]);
+        assert_eq!(moving.len(), 2);
+        // Without scores it still halves the room
+        let fresh: Vec<(PlayerId, i32)> = (1..=4).map(|id| (id, 0)).collect();
+        assert_eq!(balanced_split(3, &fresh).len(), 2);
+    }
+}
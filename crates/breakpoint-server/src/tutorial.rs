@@ -0,0 +1,128 @@
+//! Tutorial sessions: the server checks each human player's objectives
+//! against the game every tick, tells them when they complete one, and
+//! records it in their synced settings so the lobby checklist stays ticked
+//! on every device.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use bytes::Bytes;
+
+use breakpoint_core::game_trait::{BreakpointGame, GameId, PlayerId};
+use breakpoint_core::net::messages::{ServerMessage, TutorialProgressMsg};
+use breakpoint_core::net::protocol::encode_server_message;
+use breakpoint_core::player::{Player, PlayerIdentity};
+use breakpoint_core::tutorial::{TutorialObjective, TutorialProgress};
+
+use crate::state::SharedPlayerSettings;
+
+/// Where a tutorial session records the objectives its players complete.
+pub struct TutorialSink {
+    /// `None` when settings sync is off; progress then lasts the session.
+    pub player_settings: Option<SharedPlayerSettings>,
+    /// Identities of the players who joined with one. Players without one
+    /// still get their checklist, but nothing is stored for them.
+    pub identities: HashMap<PlayerId, PlayerIdentity>,
+}
+
+/// Tracks a tutorial session's players through the game's objectives.
+pub struct TutorialTracker {
+    game: GameId,
+    objectives: &'static [TutorialObjective],
+    progress: HashMap<PlayerId, TutorialProgress>,
+    player_settings: Option<SharedPlayerSettings>,
+    identities: HashMap<PlayerId, PlayerIdentity>,
+}
+
+impl TutorialTracker {
+    /// Start tracking the human players, picking up the progress stored
+    /// for them.
+    pub async fn load(
+        sink: &TutorialSink,
+        game: GameId,
+        objectives: &'static [TutorialObjective],
+        players: &[Player],
+    ) -> Self {
+        let mut progress = HashMap::new();
+        for p in players
+            .iter()
+            .filter(|p| !p.is_bot && !p.is_spectator && p.hot_seat_of.is_none())
+        {
+            let stored = match (&sink.player_settings, sink.identities.get(&p.id)) {
+                (Some(store), Some(identity)) => store.read().await.tutorial(identity),
+                _ => TutorialProgress::default(),
+            };
+            progress.insert(p.id, stored);
+        }
+        Self {
+            game,
+            objectives,
+            progress,
+            player_settings: sink.player_settings.clone(),
+            identities: sink.identities.clone(),
+        }
+    }
+
+    /// Every tracked player's checklist, to open the session with.
+    pub fn checklists(&self) -> Vec<(PlayerId, Bytes)> {
+        self.progress
+            .iter()
+            .filter_map(|(&pid, progress)| Some((pid, self.encode(progress)?)))
+            .collect()
+    }
+
+    /// Check the objectives against the game. Returns an updated checklist
+    /// for each player who completed one, and stores the new objectives.
+    pub fn check(&mut self, game: &dyn BreakpointGame) -> Vec<(PlayerId, Bytes)> {
+        let game_name = self.game.as_str();
+        let mut updates = Vec::new();
+        for (&pid, progress) in &mut self.progress {
+            let completed: Vec<&'static str> = game
+                .tutorial_objectives_met(pid)
+                .into_iter()
+                .filter(|id| self.objectives.iter().any(|o| o.id == *id))
+                .filter(|id| progress.complete(game_name, id))
+                .collect();
+            if completed.is_empty() {
+                continue;
+            }
+            tracing::debug!(
+                player_id = pid,
+                game = game_name,
+                ?completed,
+                "Tutorial objectives met"
+            );
+            if let (Some(store), Some(identity)) =
+                (&self.player_settings, self.identities.get(&pid))
+            {
+                let store = Arc::clone(store);
+                let identity = identity.clone();
+                tokio::spawn(async move {
+                    let now = breakpoint_core::time::unix_now();
+                    let mut store = store.write().await;
+                    for objective in completed {
+                        store.record_tutorial(&identity, game_name, objective, now);
+                    }
+                });
+            }
+            updates.push(pid);
+        }
+        updates
+            .into_iter()
+            .filter_map(|pid| Some((pid, self.encode(&self.progress[&pid])?)))
+            .collect()
+    }
+
+    fn encode(&self, progress: &TutorialProgress) -> Option<Bytes> {
+        let msg = ServerMessage::TutorialProgress(TutorialProgressMsg {
+            checklists: vec![progress.checklist(self.game.as_str(), self.objectives)],
+        });
+        match encode_server_message(&msg) {
+            Ok(data) => Some(Bytes::from(data)),
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to encode TutorialProgress");
+                None
+            },
+        }
+    }
+}
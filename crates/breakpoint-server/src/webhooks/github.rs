@@ -7,6 +7,7 @@ use serde_json::Value;
 use uuid::Uuid;
 
 use breakpoint_core::events::{Event, EventType, Priority};
+use breakpoint_core::telemetry::METRICS_TARGET;
 
 use crate::auth::verify_github_signature;
 use crate::state::AppState;
@@ -19,6 +20,11 @@ pub struct WebhookResponse {
 }
 
 /// POST /api/v1/webhooks/github — handle GitHub webhook payloads.
+#[tracing::instrument(
+    name = "github_webhook",
+    skip_all,
+    fields(bytes = body.len(), github_event = tracing::field::Empty, accepted = tracing::field::Empty)
+)]
 pub async fn github_webhook(
     State(state): State<AppState>,
     headers: HeaderMap,
@@ -50,11 +56,18 @@ pub async fn github_webhook(
         .get("x-github-event")
         .and_then(|v| v.to_str().ok())
         .unwrap_or("unknown");
+    tracing::Span::current().record("github_event", gh_event);
 
     let payload: Value = serde_json::from_slice(&body)
         .map_err(|e| (StatusCode::BAD_REQUEST, format!("Invalid JSON: {e}")))?;
 
     let events = transform_github_event(gh_event, &payload);
+    tracing::Span::current().record("accepted", events.len());
+    tracing::trace!(
+        target: METRICS_TARGET,
+        source = "github",
+        monotonic_counter.webhook_events = events.len() as u64
+    );
 
     let mut event_ids = Vec::with_capacity(events.len());
     let mut store = state.event_store.write().await;
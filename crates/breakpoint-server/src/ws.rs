@@ -1,4 +1,4 @@
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
 
@@ -9,16 +9,31 @@ use axum::extract::{State, WebSocketUpgrade};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use bytes::Bytes;
-use futures::{SinkExt, StreamExt};
-use tokio::sync::mpsc;
+use futures::{FutureExt, SinkExt, StreamExt};
+use tokio::sync::{mpsc, oneshot};
+use tracing::Instrument;
 
-use breakpoint_core::game_trait::PlayerId;
-use breakpoint_core::net::messages::{AlertClaimedMsg, JoinRoomMsg, MessageType, ServerMessage};
+use breakpoint_core::game_trait::{GameId, PlayerId};
+use breakpoint_core::i18n::{LocalizedText, TextKey};
+use breakpoint_core::net::chaos::spawn_chaos_pipe;
+use breakpoint_core::net::messages::{
+    AlertClaimedMsg, ClientMessage, ClockSyncReplyMsg, JoinRoomMsg, MessageType,
+    MigrateTransportMsg, ServerMessage, TransportMigratedMsg, TutorialProgressMsg,
+};
 use breakpoint_core::net::protocol::{
-    PROTOCOL_VERSION, decode_client_message, decode_message_type, encode_server_message,
+    BATCH_LEN_PREFIX, MAX_MESSAGE_SIZE, PROTOCOL_VERSION, decode_client_message,
+    decode_message_type, encode_batch, encode_server_message, encode_version_mismatch,
 };
+use breakpoint_core::overlay::config::{OverlayConfigMsg, OverlayRoomConfig};
+use breakpoint_core::player::PlayerIdentity;
 use breakpoint_core::room::RoomState;
+use breakpoint_core::telemetry::METRICS_TARGET;
 
+use crate::content;
+use crate::join_queue::{Admission, AdmissionResult};
+use crate::outbound::{self, OutboundReceiver};
+use crate::room_manager::{JoinRequest, Lane, Migration, RoomManager, RoomRequest};
+use crate::social::{InviteOutcome, SocialStore};
 use crate::state::{AppState, ConnectionGuard, IpConnectionGuard};
 
 pub async fn ws_handler(
@@ -29,7 +44,11 @@ pub async fn ws_handler(
     let current = state.ws_connection_count.load(Ordering::Relaxed);
     if current >= max_ws {
         tracing::warn!(current, max = max_ws, "WS connection limit reached");
-        return Err(StatusCode::SERVICE_UNAVAILABLE);
+        // Upgrade only to tell the client why, then hang up
+        let ws = WebSocketUpgrade::from_request(request, &state)
+            .await
+            .map_err(|_| StatusCode::SERVICE_UNAVAILABLE)?;
+        return Ok(ws.on_upgrade(reject_socket).into_response());
     }
 
     // Per-IP connection limit
@@ -55,7 +74,15 @@ pub async fn ws_handler(
         .into_response())
 }
 
-async fn handle_socket(socket: WebSocket, state: AppState, _ip_guard: IpConnectionGuard) {
+/// Answer a connection the server has no room for with a `ServerFull` join
+/// error instead of a bare failed handshake.
+async fn reject_socket(socket: WebSocket) {
+    let (mut ws_sender, _) = socket.split();
+    send_join_error(&mut ws_sender, TextKey::ServerFull.into()).await;
+    let _ = ws_sender.close().await;
+}
+
+async fn handle_socket(socket: WebSocket, state: AppState, ip_guard: IpConnectionGuard) {
     let _guard = ConnectionGuard::new(Arc::clone(&state.ws_connection_count));
     let (mut ws_sender, mut ws_receiver) = socket.split();
 
@@ -79,7 +106,12 @@ async fn handle_socket(socket: WebSocket, state: AppState, _ip_guard: IpConnecti
     };
 
     let join = match client_msg {
-        breakpoint_core::net::messages::ClientMessage::JoinRoom(j) => j,
+        ClientMessage::JoinRoom(j) => j,
+        // An existing session moving onto this connection
+        ClientMessage::MigrateTransport(m) => {
+            migrate_socket(ws_sender, ws_receiver, m, &state, ip_guard.ip()).await;
+            return;
+        },
         other => {
             tracing::warn!(msg = ?std::mem::discriminant(&other), "WS first message was not JoinRoom");
             return;
@@ -88,83 +120,385 @@ async fn handle_socket(socket: WebSocket, state: AppState, _ip_guard: IpConnecti
 
     // Validate protocol version
     if join.protocol_version != 0 && join.protocol_version != PROTOCOL_VERSION {
-        if let Ok(response) = crate::room_manager::RoomManager::make_join_error(&format!(
-            "Protocol version mismatch: client={}, server={}",
-            join.protocol_version, PROTOCOL_VERSION
-        )) && let Err(e) = ws_sender.send(Message::Binary(response.into())).await
+        if let Ok(response) = encode_version_mismatch(join.protocol_version)
+            && let Err(e) = ws_sender.send(Message::Binary(response.into())).await
         {
             tracing::warn!(error = %e, "Failed to send protocol mismatch error");
         }
         return;
     }
 
+    // Bot clients must present a token the server accepts
+    if let Some(ref token) = join.bot_token
+        && !state.config.bot_api.accepts(token)
+    {
+        tracing::warn!(ip = %ip_guard.ip(), "Rejected bot client with an unknown token");
+        send_join_error(&mut ws_sender, TextKey::BotTokenRejected.into()).await;
+        return;
+    }
+
+    // The stable identity behind this connection, for reconnection,
+    // settings, friends, and season standings
+    let identity = join
+        .identity_key
+        .as_deref()
+        .and_then(crate::social::player_identity);
+
+    // Banned players are turned away before any join or reconnect
+    if refused_by_ban(&state, identity.as_ref(), ip_guard.ip()).await {
+        send_join_error(&mut ws_sender, TextKey::Banned.into()).await;
+        return;
+    }
+
     // Attempt join (reconnect or normal)
-    let result = match attempt_join(&join, &state).await {
+    let result = match attempt_join(&join, identity.as_ref(), &state, ip_guard.ip()).await {
         Some(r) => r,
         None => {
-            send_join_error(&mut ws_sender, "Invalid player name").await;
+            send_join_error(&mut ws_sender, TextKey::InvalidPlayerName.into()).await;
             return;
         },
     };
 
-    let (room_code, player_id, rx) = match result {
+    let (room_code, player_id, session_token, room_state, rx) = match result {
         JoinResult::Success {
             room_code,
             player_id,
             session_token,
             room_state,
             rx,
+        } => (room_code, player_id, session_token, room_state, rx),
+        JoinResult::Queued {
+            room_code,
+            ticket,
+            mut admission,
+            mut rx,
         } => {
-            let Ok(response) = crate::room_manager::RoomManager::make_join_response(
-                player_id,
-                &room_code,
-                room_state,
-                &session_token,
-            ) else {
-                tracing::warn!("Failed to encode JoinRoomResponse");
-                return;
-            };
-
-            if ws_sender
-                .send(Message::Binary(response.into()))
-                .await
-                .is_err()
-            {
-                return;
+            tracing::info!(room = %room_code, ticket, "Player waiting in join queue");
+            match wait_in_queue(&mut ws_sender, &mut ws_receiver, &mut rx, &mut admission).await {
+                QueueExit::Admitted(a) => {
+                    (a.room_code, a.player_id, a.session_token, a.room_state, rx)
+                },
+                QueueExit::Refused(err) => {
+                    send_join_error(&mut ws_sender, err).await;
+                    return;
+                },
+                QueueExit::Disconnected => {
+                    let mut rooms = state.rooms.write().await;
+                    if room_code.is_empty() {
+                        rooms.cancel_queued_room(ticket);
+                    } else {
+                        rooms.cancel_queued_join(&room_code, ticket);
+                    }
+                    // Admitted just as the connection closed
+                    if let Ok(Ok(a)) = admission.try_recv()
+                        && rooms.leave_room(&a.room_code, a.player_id).is_none()
+                    {
+                        rooms.broadcast_player_list(&a.room_code);
+                    }
+                    return;
+                },
             }
-
-            (room_code, player_id, rx)
         },
         JoinResult::Error(err) => {
-            send_join_error(&mut ws_sender, &err).await;
+            send_join_error(&mut ws_sender, err).await;
             return;
         },
     };
 
+    let lobby_toy = state.rooms.read().await.lobby_toy_state(&room_code);
+    let Ok(response) = crate::room_manager::RoomManager::make_join_response(
+        player_id,
+        &room_code,
+        room_state,
+        &session_token,
+        lobby_toy,
+    ) else {
+        tracing::warn!("Failed to encode JoinRoomResponse");
+        return;
+    };
+    if ws_sender
+        .send(Message::Binary(response.into()))
+        .await
+        .is_err()
+    {
+        return;
+    }
+
+    if join.bot_token.is_some() {
+        state
+            .rooms
+            .write()
+            .await
+            .mark_external_bot(&room_code, player_id);
+        tracing::info!(player_id, room = %room_code, "External bot joined");
+    }
+
+    if let Some(ref id) = identity {
+        state
+            .rooms
+            .write()
+            .await
+            .bind_identity(&room_code, player_id, id.clone());
+    }
+
     // Broadcast player list
     {
         let rooms = state.rooms.read().await;
         rooms.broadcast_player_list(&room_code);
     }
 
-    spawn_writer(ws_sender, rx);
+    // The room's overlay settings when customized, so the lobby shows its
+    // event filter. Clients assume the defaults otherwise.
+    {
+        let rooms = state.rooms.read().await;
+        if let Some(config) = rooms
+            .overlay_config(&room_code)
+            .filter(|c| **c != OverlayRoomConfig::default())
+        {
+            let msg = ServerMessage::OverlayConfig(OverlayConfigMsg {
+                room_config: config.clone(),
+            });
+            if let Ok(data) = encode_server_message(&msg) {
+                rooms.send_to_player(&room_code, player_id, Lane::Control, data.into());
+            }
+        }
+    }
+
+    // Current MOTD and announcements; later changes arrive via broadcast
+    let active = state
+        .announcements
+        .read()
+        .await
+        .active(breakpoint_core::time::unix_now());
+    if !active.is_empty()
+        && let Some(data) = crate::announcements::encode_announcements(active)
+    {
+        let rooms = state.rooms.read().await;
+        rooms.send_to_player(&room_code, player_id, Lane::Control, data.into());
+    }
 
-    // Read loop: relay incoming messages
-    read_loop(&mut ws_receiver, &state, &room_code, player_id).await;
+    // Friends list, pending invites, and presence for players with an identity
+    if let Some(ref id) = identity {
+        social_connect(&state, id, &join.player_name, &room_code, player_id).await;
+        if join.bot_token.is_none() {
+            send_tutorial_checklists(&state, id, &room_code, player_id).await;
+        }
+    }
+
+    // A tutorial starts in the room the join just created for it
+    if let Some(ref game) = join.tutorial
+        && join.room_code.is_empty()
+    {
+        start_tutorial(&state, &room_code, player_id, game).await;
+    }
+
+    serve_connection(
+        ws_sender,
+        ws_receiver,
+        rx,
+        join.accepts_batches,
+        &state,
+        &room_code,
+        player_id,
+        identity.as_ref(),
+    )
+    .await;
+    close_connection(
+        &state,
+        &room_code,
+        player_id,
+        identity.as_ref(),
+        &session_token,
+    )
+    .await;
+}
+
+/// Take over a live session named by `MigrateTransport` on this
+/// connection, then serve it like a joined one. The player's room, ID,
+/// identity, and guests carry over, so no join messages are sent.
+async fn migrate_socket(
+    mut ws_sender: futures::stream::SplitSink<WebSocket, Message>,
+    ws_receiver: futures::stream::SplitStream<WebSocket>,
+    migrate: MigrateTransportMsg,
+    state: &AppState,
+    ip: IpAddr,
+) {
+    let reply = |result: Result<&Migration, LocalizedText>| {
+        let msg = match result {
+            Ok(m) => TransportMigratedMsg {
+                success: true,
+                player_id: Some(m.player_id),
+                room_code: Some(m.room_code.clone()),
+                session_token: Some(m.session_token.clone()),
+                last_input_tick: Some(m.last_input_tick),
+                error: None,
+            },
+            Err(error) => TransportMigratedMsg {
+                success: false,
+                player_id: None,
+                room_code: None,
+                session_token: None,
+                last_input_tick: None,
+                error: Some(error),
+            },
+        };
+        encode_server_message(&ServerMessage::TransportMigrated(msg))
+    };
+
+    if migrate.protocol_version != PROTOCOL_VERSION {
+        let error = LocalizedText::new(TextKey::ProtocolMismatch)
+            .with("client", migrate.protocol_version)
+            .with("server", PROTOCOL_VERSION);
+        if let Ok(data) = reply(Err(error)) {
+            let _ = ws_sender.send(Message::Binary(data.into())).await;
+        }
+        return;
+    }
+
+    let identity = state
+        .rooms
+        .read()
+        .await
+        .session_identity(&migrate.session_token);
+    if refused_by_ban(state, identity.as_ref(), ip).await {
+        if let Ok(data) = reply(Err(TextKey::Banned.into())) {
+            let _ = ws_sender.send(Message::Binary(data.into())).await;
+        }
+        return;
+    }
+
+    let (tx, rx) = outbound::channel(state.config.limits.player_message_buffer);
+    let migration = state
+        .rooms
+        .write()
+        .await
+        .migrate_transport(&migrate.session_token, tx);
+    let Ok(data) = reply(migration.as_ref().map_err(Clone::clone)) else {
+        tracing::warn!("Failed to encode TransportMigrated");
+        return;
+    };
+    if ws_sender.send(Message::Binary(data.into())).await.is_err() {
+        // The old connection is already closed, so this one counts as
+        // the player's connection until it is cleaned up below
+        if let Ok(m) = migration {
+            close_connection(
+                state,
+                &m.room_code,
+                m.player_id,
+                m.identity.as_ref(),
+                &m.session_token,
+            )
+            .await;
+        }
+        return;
+    }
+    let Ok(m) = migration else {
+        return;
+    };
+    tracing::info!(player_id = m.player_id, room = %m.room_code, "Player migrated transport");
+
+    serve_connection(
+        ws_sender,
+        ws_receiver,
+        rx,
+        migrate.accepts_batches,
+        state,
+        &m.room_code,
+        m.player_id,
+        m.identity.as_ref(),
+    )
+    .await;
+    close_connection(
+        state,
+        &m.room_code,
+        m.player_id,
+        m.identity.as_ref(),
+        &m.session_token,
+    )
+    .await;
+}
+
+/// Pump a joined connection until it closes: its outbound queue to the
+/// socket, and its messages to the room.
+#[allow(clippy::too_many_arguments)]
+async fn serve_connection(
+    ws_sender: futures::stream::SplitSink<WebSocket, Message>,
+    mut ws_receiver: futures::stream::SplitStream<WebSocket>,
+    rx: OutboundReceiver,
+    accepts_batches: bool,
+    state: &AppState,
+    room_code: &str,
+    player_id: PlayerId,
+    identity: Option<&PlayerIdentity>,
+) {
+    // Debug chaos mode degrades both directions of this connection
+    let chaos = state.config.chaos.clone();
+    let buffer = state.config.limits.player_message_buffer;
+    if chaos.is_active() {
+        let rx = spawn_chaos_pipe(rx, chaos.clone(), buffer);
+        spawn_writer(
+            ws_sender,
+            tokio_stream::wrappers::ReceiverStream::new(rx),
+            accepts_batches,
+        );
+
+        let (in_tx, in_rx) = mpsc::channel(buffer);
+        tokio::spawn(async move {
+            while let Some(msg) = ws_receiver.next().await {
+                if in_tx.send(msg).await.is_err() {
+                    break;
+                }
+            }
+        });
+        let in_rx = spawn_chaos_pipe(
+            tokio_stream::wrappers::ReceiverStream::new(in_rx),
+            chaos,
+            buffer,
+        );
+        let mut inbound = tokio_stream::wrappers::ReceiverStream::new(in_rx);
+        read_loop(&mut inbound, state, room_code, player_id, identity).await;
+    } else {
+        spawn_writer(ws_sender, rx, accepts_batches);
+        read_loop(&mut ws_receiver, state, room_code, player_id, identity).await;
+    }
+}
+
+/// Clean up after a connection closes: the player leaves the room (or
+/// keeps their slot mid-game) and goes offline to friends. Skipped when the
+/// session has since migrated to another connection.
+async fn close_connection(
+    state: &AppState,
+    room_code: &str,
+    player_id: PlayerId,
+    identity: Option<&PlayerIdentity>,
+    session_token: &str,
+) {
+    if state
+        .rooms
+        .read()
+        .await
+        .session_migrated(room_code, player_id, session_token)
+    {
+        tracing::info!(player_id, room_code, "Old transport closed after migration");
+        return;
+    }
+
+    if let Some(id) = identity {
+        let mut social = state.social.write().await;
+        social.disconnect(id, player_id, breakpoint_core::time::unix_now());
+        let rooms = state.rooms.read().await;
+        notify_watchers(&rooms, &social, id);
+    }
 
     // Player disconnected — clean up
     let mut rooms = state.rooms.write().await;
-    let destroyed = rooms.leave_room(&room_code, player_id);
+    let destroyed = rooms.leave_room(room_code, player_id);
     if destroyed.is_none() {
-        rooms.broadcast_player_list(&room_code);
+        rooms.broadcast_player_list(room_code);
     }
     drop(rooms);
 
-    tracing::info!(
-        player_id,
-        room_code = %room_code,
-        "Player disconnected"
-    );
+    tracing::info!(player_id, room_code, "Player disconnected");
 }
 
 enum JoinResult {
@@ -173,16 +507,89 @@ enum JoinResult {
         player_id: PlayerId,
         session_token: String,
         room_state: RoomState,
-        rx: mpsc::Receiver<Bytes>,
+        rx: OutboundReceiver,
+    },
+    /// Waiting in the room's join queue, or in the server's line for a new
+    /// room when `room_code` is empty.
+    Queued {
+        room_code: String,
+        ticket: u64,
+        admission: oneshot::Receiver<AdmissionResult>,
+        rx: OutboundReceiver,
     },
-    Error(String),
+    Error(LocalizedText),
 }
 
-async fn attempt_join(join: &JoinRoomMsg, state: &AppState) -> Option<JoinResult> {
-    // Try session-based reconnection first
+/// How a wait in a join queue ended.
+enum QueueExit {
+    Admitted(Admission),
+    Refused(LocalizedText),
+    Disconnected,
+}
+
+/// Hold a queued connection open, forwarding its position updates, until
+/// it is admitted, refused, or hangs up. Anything the client sends while
+/// waiting is ignored.
+async fn wait_in_queue(
+    ws_sender: &mut futures::stream::SplitSink<WebSocket, Message>,
+    ws_receiver: &mut futures::stream::SplitStream<WebSocket>,
+    rx: &mut OutboundReceiver,
+    admission: &mut oneshot::Receiver<AdmissionResult>,
+) -> QueueExit {
+    loop {
+        tokio::select! {
+            // Admission first, so room messages queued behind it reach the
+            // client after its JoinRoomResponse
+            biased;
+            result = &mut *admission => {
+                return match result {
+                    Ok(Ok(a)) => QueueExit::Admitted(a),
+                    Ok(Err(reason)) => QueueExit::Refused(reason),
+                    Err(_) => QueueExit::Refused(TextKey::RoomGone.into()),
+                };
+            },
+            Some(data) = rx.recv() => {
+                if ws_sender.send(Message::Binary(data.to_vec().into())).await.is_err() {
+                    return QueueExit::Disconnected;
+                }
+            },
+            msg = ws_receiver.next() => match msg {
+                Some(Ok(Message::Close(_)) | Err(_)) | None => return QueueExit::Disconnected,
+                Some(Ok(_)) => {},
+            },
+        }
+    }
+}
+
+async fn attempt_join(
+    join: &JoinRoomMsg,
+    identity: Option<&PlayerIdentity>,
+    state: &AppState,
+    ip: IpAddr,
+) -> Option<JoinResult> {
+    // Try session-based reconnection first; a merge or split transfer
+    // token rides in the same field
     if let Some(ref token) = join.session_token {
-        let (tx, rx) = mpsc::channel::<Bytes>(state.config.limits.player_message_buffer);
+        let (tx, rx) = outbound::channel(state.config.limits.player_message_buffer);
         let mut rooms = state.rooms.write().await;
+        if let Some(transfer) = rooms.take_transfer(token) {
+            let result = match rooms.join_by_transfer(transfer, tx) {
+                Ok((code, pid, new_token)) => {
+                    let room_state = rooms.get_room_state(&code).unwrap_or(RoomState::Lobby);
+                    tracing::info!(player_id = pid, room = %code, "Player joined via transfer");
+                    JoinResult::Success {
+                        room_code: code,
+                        player_id: pid,
+                        session_token: new_token,
+                        room_state,
+                        rx,
+                    }
+                },
+                Err(e) => JoinResult::Error(e),
+            };
+            drop(rooms);
+            return Some(result);
+        }
         match rooms.reconnect(token, tx) {
             Ok((code, pid, new_token)) => {
                 let room_state = rooms.get_room_state(&code).unwrap_or(RoomState::Lobby);
@@ -203,51 +610,128 @@ async fn attempt_join(join: &JoinRoomMsg, state: &AppState) -> Option<JoinResult
         }
     }
 
+    // Without a usable token, a player whose slot is preserved mid-game can
+    // still reclaim it by identity
+    if let Some(identity) = identity
+        && !join.room_code.is_empty()
+    {
+        let (tx, rx) = outbound::channel(state.config.limits.player_message_buffer);
+        let mut rooms = state.rooms.write().await;
+        let room_code = rooms.resolve_room_code(&join.room_code);
+        if let Ok((code, pid, new_token)) = rooms.reconnect_identity(&room_code, identity, tx) {
+            let room_state = rooms.get_room_state(&code).unwrap_or(RoomState::Lobby);
+            drop(rooms);
+            tracing::info!(player_id = pid, room = %code, "Player reconnected via identity");
+            return Some(JoinResult::Success {
+                room_code: code,
+                player_id: pid,
+                session_token: new_token,
+                room_state,
+                rx,
+            });
+        }
+    }
+
     // Normal join path
-    let (tx, rx) = mpsc::channel::<Bytes>(state.config.limits.player_message_buffer);
+    let (tx, rx) = outbound::channel(state.config.limits.player_message_buffer);
 
-    // Validate player name
+    // Validate player name; a blank one is replaced with a generated name
     let name = join.player_name.trim().to_string();
-    if name.is_empty() || name.len() > 32 || name.chars().any(|c| c.is_control()) {
+    if name.len() > 32 || name.chars().any(|c| c.is_control()) {
         return None; // signals name validation failure
     }
 
+    // Vanity aliases count against the host's content quota. The settings
+    // guard is held until the room exists, so a settings upload can't slip
+    // in between the check and the claim
+    let alias = join.room_alias.as_deref().filter(|a| !a.trim().is_empty());
+    let held_settings = match alias {
+        Some(_) if join.room_code.is_empty() => Some(state.player_settings.read().await),
+        _ => None,
+    };
+
     let mut rooms = state.rooms.write().await;
 
     if join.room_code.is_empty() {
-        // Create new room
-        let (code, pid, token) = rooms.create_room(name, join.player_color, tx);
+        if let Some(alias) = alias
+            && let Ok(alias) =
+                breakpoint_core::room::normalize_room_alias(alias, rooms.code_format())
+        {
+            let held = held_settings
+                .as_ref()
+                .map(|s| s.items())
+                .unwrap_or_default();
+            let inventory = content::with_aliases(held, &rooms);
+            if let Err(err) =
+                content::check_alias(&state.config.content, &inventory, &alias, identity)
+            {
+                drop(rooms);
+                tracing::info!(%ip, reason = %err, "Room alias refused");
+                return Some(JoinResult::Error(TextKey::RoomAliasQuota.into()));
+            }
+        }
+        // Create new room, or wait for one while the server is at capacity
+        let result =
+            match rooms.request_room(name, join.player_color, tx, ip, join.room_alias.as_deref()) {
+                Ok(RoomRequest::Created {
+                    room_code,
+                    player_id,
+                    session_token,
+                }) => JoinResult::Success {
+                    room_code,
+                    player_id,
+                    session_token,
+                    room_state: RoomState::Lobby,
+                    rx,
+                },
+                Ok(RoomRequest::Queued { ticket, admission }) => JoinResult::Queued {
+                    room_code: String::new(),
+                    ticket,
+                    admission,
+                    rx,
+                },
+                Err(err) => {
+                    tracing::info!(%ip, reason = %err, "Room creation refused");
+                    JoinResult::Error(err)
+                },
+            };
         drop(rooms);
-        Some(JoinResult::Success {
-            room_code: code,
-            player_id: pid,
-            session_token: token,
-            room_state: RoomState::Lobby,
-            rx,
-        })
+        drop(held_settings);
+        Some(result)
     } else {
-        // Validate room code format before lookup
-        if !breakpoint_core::room::is_valid_room_code(&join.room_code) {
+        // Players may type a room's vanity alias instead of its code;
+        // validate the code format before lookup
+        let room_code = rooms.resolve_room_code(&join.room_code);
+        if !rooms.code_format().is_valid(&room_code) {
             drop(rooms);
-            return Some(JoinResult::Error("Invalid room code".to_string()));
+            return Some(JoinResult::Error(TextKey::InvalidRoomCode.into()));
         }
 
-        // Join existing room
-        match rooms.join_room(&join.room_code, name, join.player_color, tx) {
-            Ok((pid, token)) => {
-                let room_state = rooms
-                    .get_room_state(&join.room_code)
-                    .unwrap_or(RoomState::Lobby);
-                let code = join.room_code.clone();
+        // Join existing room, or wait in line for it
+        match rooms.request_join(&room_code, name, join.player_color, tx, ip) {
+            Ok(JoinRequest::Joined {
+                player_id,
+                session_token,
+            }) => {
+                let room_state = rooms.get_room_state(&room_code).unwrap_or(RoomState::Lobby);
                 drop(rooms);
                 Some(JoinResult::Success {
-                    room_code: code,
-                    player_id: pid,
-                    session_token: token,
+                    room_code,
+                    player_id,
+                    session_token,
                     room_state,
                     rx,
                 })
             },
+            Ok(JoinRequest::Queued { ticket, admission }) => {
+                drop(rooms);
+                Some(JoinResult::Queued {
+                    room_code,
+                    ticket,
+                    admission,
+                    rx,
+                })
+            },
             Err(err) => {
                 drop(rooms);
                 Some(JoinResult::Error(err))
@@ -256,9 +740,20 @@ async fn attempt_join(join: &JoinRoomMsg, state: &AppState) -> Option<JoinResult
     }
 }
 
+/// Whether a ban keeps this identity or IP out. Refusals are audited.
+async fn refused_by_ban(state: &AppState, identity: Option<&PlayerIdentity>, ip: IpAddr) -> bool {
+    let now = breakpoint_core::time::unix_now();
+    let Some(ban) = state.bans.read().await.check(identity, ip, now).cloned() else {
+        return false;
+    };
+    tracing::info!(%ip, ban_id = %ban.id, "Banned player refused");
+    state.bans.write().await.note_refused(&ban, now);
+    true
+}
+
 async fn send_join_error(
     ws_sender: &mut futures::stream::SplitSink<WebSocket, Message>,
-    error: &str,
+    error: LocalizedText,
 ) {
     if let Ok(response) = crate::room_manager::RoomManager::make_join_error(error)
         && let Err(e) = ws_sender.send(Message::Binary(response.into())).await
@@ -267,23 +762,266 @@ async fn send_join_error(
     }
 }
 
+/// Send an identity its current friends and recent players, if it is online.
+fn send_social_update(rooms: &RoomManager, social: &SocialStore, id: &PlayerIdentity) {
+    let (Some(presence), Some(mut update)) = (social.presence(id), social.update_for(id)) else {
+        return;
+    };
+    for entry in update.friends.iter_mut().chain(&mut update.recent) {
+        entry.game = entry
+            .room_code
+            .as_deref()
+            .and_then(|code| rooms.current_game(code))
+            .map(|game| game.as_str().to_string());
+    }
+    if let Ok(encoded) = encode_server_message(&ServerMessage::SocialUpdate(update)) {
+        rooms.send_to_player(
+            &presence.room_code,
+            presence.player_id,
+            Lane::Cosmetic,
+            encoded.into(),
+        );
+    }
+}
+
+/// Refresh the lists of online players who have `id` as a friend, after its
+/// presence changed.
+fn notify_watchers(rooms: &RoomManager, social: &SocialStore, id: &PlayerIdentity) {
+    for (watcher, _) in social.online_watchers(id) {
+        send_social_update(rooms, social, &watcher);
+    }
+}
+
+/// Refresh the lists of everyone following a player in `room_code`, after
+/// its game started or ended.
+pub async fn notify_room_watchers(state: &AppState, room_code: &str) {
+    let social = state.social.read().await;
+    let rooms = state.rooms.read().await;
+    for id in rooms.identities_in_room(room_code) {
+        notify_watchers(&rooms, &social, &id);
+    }
+}
+
+/// Register presence after a join, then deliver the friends list and any
+/// invites that arrived while the player was offline.
+async fn social_connect(
+    state: &AppState,
+    id: &PlayerIdentity,
+    fallback_name: &str,
+    room_code: &str,
+    player_id: PlayerId,
+) {
+    let mut social = state.social.write().await;
+    let rooms = state.rooms.read().await;
+    let name = rooms
+        .get_player_name(room_code, player_id)
+        .unwrap_or_else(|| fallback_name.trim().to_string());
+    let inbox = social.connect(
+        id,
+        &name,
+        room_code,
+        player_id,
+        breakpoint_core::time::unix_now(),
+    );
+    send_social_update(&rooms, &social, id);
+    for invite in inbox {
+        if let Ok(encoded) = encode_server_message(&ServerMessage::FriendInvite(invite)) {
+            rooms.send_to_player(room_code, player_id, Lane::Control, encoded.into());
+        }
+    }
+    notify_watchers(&rooms, &social, id);
+}
+
+/// Send the player every game's tutorial checklist for the lobby.
+async fn send_tutorial_checklists(
+    state: &AppState,
+    id: &PlayerIdentity,
+    room_code: &str,
+    player_id: PlayerId,
+) {
+    let progress = state.player_settings.read().await.tutorial(id);
+    let msg = ServerMessage::TutorialProgress(TutorialProgressMsg {
+        checklists: state.game_registry.tutorial_checklists(&progress),
+    });
+    if let Ok(encoded) = encode_server_message(&msg) {
+        let rooms = state.rooms.read().await;
+        rooms.send_to_player(room_code, player_id, Lane::Control, encoded.into());
+    }
+}
+
+/// Start the tutorial a joining player asked for. Unknown games and
+/// failures leave them in their new room's lobby.
+async fn start_tutorial(state: &AppState, room_code: &str, player_id: PlayerId, game: &str) {
+    let Some(game_id) = GameId::from_str_opt(game) else {
+        tracing::debug!(player_id, game, "Tutorial requested for an unknown game");
+        return;
+    };
+    let mut rooms = state.rooms.write().await;
+    match rooms.start_tutorial(
+        room_code,
+        player_id,
+        game_id,
+        &state.game_registry,
+        Arc::clone(&state.rooms),
+    ) {
+        Ok(()) => tracing::info!(player_id, room_code, %game_id, "Tutorial started"),
+        Err(e) => {
+            tracing::warn!(player_id, room_code, %game_id, error = %e, "Tutorial failed to start")
+        },
+    }
+}
+
+/// Handle `SetFriend`, `InviteFriend`, and `SetPresenceSharing`. Players
+/// without an identity have no friends list, so their requests are ignored.
+async fn handle_social_message(
+    state: &AppState,
+    identity: Option<&PlayerIdentity>,
+    msg: ClientMessage,
+) {
+    let Some(id) = identity else {
+        return;
+    };
+    let now = breakpoint_core::time::unix_now();
+    let mut social = state.social.write().await;
+    match msg {
+        ClientMessage::SetFriend(req) => {
+            let Some(friend) = PlayerIdentity::parse(&req.friend_id) else {
+                tracing::debug!(identity = %id, friend = %req.friend_id, "SetFriend with a malformed id");
+                return;
+            };
+            if let Err(e) = social.set_friend(id, &friend, req.is_friend) {
+                tracing::debug!(identity = %id, error = %e, "SetFriend rejected");
+                return;
+            }
+            let rooms = state.rooms.read().await;
+            send_social_update(&rooms, &social, id);
+            // Whether the friend can see the sender's room may have changed
+            send_social_update(&rooms, &social, &friend);
+        },
+        ClientMessage::SetPresenceSharing(req) => {
+            if let Err(e) = social.set_presence_sharing(id, req.share) {
+                tracing::debug!(identity = %id, error = %e, "SetPresenceSharing rejected");
+                return;
+            }
+            let rooms = state.rooms.read().await;
+            send_social_update(&rooms, &social, id);
+            notify_watchers(&rooms, &social, id);
+        },
+        ClientMessage::InviteFriend(req) => {
+            let Some(friend) = PlayerIdentity::parse(&req.friend_id) else {
+                tracing::debug!(identity = %id, friend = %req.friend_id, "InviteFriend with a malformed id");
+                return;
+            };
+            match social.invite(id, &friend, now) {
+                Ok(InviteOutcome::Deliver(presence, invite)) => {
+                    if let Ok(encoded) = encode_server_message(&ServerMessage::FriendInvite(invite))
+                    {
+                        let rooms = state.rooms.read().await;
+                        rooms.send_to_player(
+                            &presence.room_code,
+                            presence.player_id,
+                            Lane::Control,
+                            encoded.into(),
+                        );
+                    }
+                },
+                Ok(InviteOutcome::Queued) => {
+                    tracing::debug!(identity = %id, %friend, "Invite queued");
+                },
+                Err(e) => {
+                    tracing::debug!(identity = %id, error = %e, "InviteFriend rejected");
+                },
+            }
+        },
+        _ => {},
+    }
+}
+
+/// Record everyone with an identity in the room as having played together
+/// and refresh their recent-players lists.
+async fn record_co_players(state: &AppState, room_code: &str) {
+    let mut social = state.social.write().await;
+    let rooms = state.rooms.read().await;
+    let ids = rooms.identities_in_room(room_code);
+    if ids.len() < 2 {
+        return;
+    }
+    social.record_game(&ids, breakpoint_core::time::unix_now());
+    for id in &ids {
+        send_social_update(&rooms, &social, id);
+    }
+}
+
+/// Forward queued messages to the socket. For clients that accept batches,
+/// whatever else is already queued (the rest of a tick's state, alerts,
+/// ticker updates) goes out in the same frame.
 fn spawn_writer(
     mut ws_sender: futures::stream::SplitSink<WebSocket, Message>,
-    mut rx: mpsc::Receiver<Bytes>,
+    mut rx: impl futures::Stream<Item = Bytes> + Unpin + Send + 'static,
+    batch: bool,
 ) {
     tokio::spawn(async move {
-        while let Some(data) = rx.recv().await {
-            if ws_sender
-                .send(Message::Binary(data.to_vec().into()))
-                .await
-                .is_err()
-            {
-                break;
+        let mut queued = Vec::new();
+        while let Some(data) = rx.next().await {
+            queued.push(data);
+            if batch {
+                // Let broadcasts from the same tick finish fanning out
+                tokio::task::yield_now().await;
+                while let Some(Some(data)) = rx.next().now_or_never() {
+                    queued.push(data);
+                }
+            }
+            for frame in coalesce_frames(std::mem::take(&mut queued)) {
+                if ws_sender
+                    .send(Message::Binary(frame.to_vec().into()))
+                    .await
+                    .is_err()
+                {
+                    return;
+                }
             }
         }
+        // Every sender is gone: the player left, or the session moved to
+        // another transport
+        let _ = ws_sender.close().await;
     });
 }
 
+/// Pack messages into as few `Batch` frames as fit the message size limit,
+/// keeping their order. A message that ends up alone is sent as is.
+fn coalesce_frames(frames: Vec<Bytes>) -> Vec<Bytes> {
+    if frames.len() < 2 {
+        return frames;
+    }
+    let mut out = Vec::new();
+    let mut group: Vec<Bytes> = Vec::new();
+    let mut group_len = 1;
+    for frame in frames {
+        let len = BATCH_LEN_PREFIX + frame.len();
+        if !group.is_empty() && group_len + len > MAX_MESSAGE_SIZE {
+            flush_group(&mut out, std::mem::take(&mut group));
+            group_len = 1;
+        }
+        group_len += len;
+        group.push(frame);
+    }
+    flush_group(&mut out, group);
+    out
+}
+
+fn flush_group(out: &mut Vec<Bytes>, group: Vec<Bytes>) {
+    if group.len() > 1 {
+        match encode_batch(&group) {
+            Ok(data) => {
+                out.push(Bytes::from(data));
+                return;
+            },
+            Err(e) => tracing::warn!(error = %e, "Failed to encode batch, sending messages singly"),
+        }
+    }
+    out.extend(group);
+}
+
 /// Per-connection rate limiter (token bucket).
 struct RateLimiter {
     tokens: f64,
@@ -302,6 +1040,12 @@ impl RateLimiter {
         }
     }
 
+    /// Change the burst size and refill rate, keeping current tokens.
+    fn set_rate(&mut self, rate: f64) {
+        self.max_tokens = rate;
+        self.refill_rate = rate;
+    }
+
     /// Returns true if the message is allowed; false if rate-limited.
     fn allow(&mut self) -> bool {
         let now = tokio::time::Instant::now();
@@ -319,13 +1063,24 @@ impl RateLimiter {
 }
 
 async fn read_loop(
-    ws_receiver: &mut futures::stream::SplitStream<WebSocket>,
+    ws_receiver: &mut (impl futures::Stream<Item = Result<Message, axum::Error>> + Unpin),
     state: &AppState,
     room_code: &str,
     player_id: PlayerId,
+    identity: Option<&PlayerIdentity>,
 ) {
     let rate = state.config.limits.ws_rate_limit_per_sec;
     let mut rate_limiter = RateLimiter::new(rate, rate);
+    // A migrated session brings its hot-seat guests along
+    let guests = state
+        .rooms
+        .read()
+        .await
+        .local_players_of(room_code, player_id)
+        .len();
+    if guests > 0 {
+        rate_limiter.set_rate(rate * (guests + 1) as f64);
+    }
     let mut rate_limit_drops: u32 = 0;
 
     while let Some(Ok(msg)) = ws_receiver.next().await {
@@ -382,166 +1137,428 @@ async fn read_loop(
             continue;
         }
 
-        // RequestGameStart: client asks the server to start a game
-        if msg_type == MessageType::RequestGameStart {
-            if let Ok(breakpoint_core::net::messages::ClientMessage::RequestGameStart(req)) =
-                decode_client_message(&data)
-            {
-                let mut rooms = state.rooms.write().await;
-                match rooms.start_game(
-                    room_code,
-                    &req.game_name,
-                    player_id,
-                    &state.game_registry,
-                    Arc::clone(&state.rooms),
-                    req.custom,
-                ) {
-                    Ok(()) => {
-                        tracing::info!(
-                            player_id,
-                            room_code,
-                            game = %req.game_name,
-                            "Game started"
-                        );
-                    },
-                    Err(e) => {
-                        tracing::warn!(
-                            player_id,
-                            room_code,
-                            game = %req.game_name,
-                            error = %e,
-                            "Failed to start game"
-                        );
-                    },
-                }
+        let span = tracing::debug_span!(
+            "ws_message",
+            room_code,
+            player_id,
+            ?msg_type,
+            bytes = data.len()
+        );
+        handle_message(state, room_code, player_id, identity, msg_type, &data)
+            .instrument(span)
+            .await;
+        // Hot-seat guests send inputs on this connection too, so its budget
+        // grows with them
+        if matches!(
+            msg_type,
+            MessageType::AddLocalPlayer | MessageType::RemoveLocalPlayer
+        ) {
+            let guests = state
+                .rooms
+                .read()
+                .await
+                .local_players_of(room_code, player_id)
+                .len();
+            rate_limiter.set_rate(rate * (guests + 1) as f64);
+        }
+        tracing::trace!(
+            target: METRICS_TARGET,
+            msg_type = ?msg_type,
+            monotonic_counter.ws_messages = 1_u64
+        );
+    }
+}
+
+/// Handle one validated client message.
+async fn handle_message(
+    state: &AppState,
+    room_code: &str,
+    player_id: PlayerId,
+    identity: Option<&PlayerIdentity>,
+    msg_type: MessageType,
+    data: &[u8],
+) {
+    // RequestGameStart: client asks the server to start a game
+    if msg_type == MessageType::RequestGameStart {
+        if let Ok(breakpoint_core::net::messages::ClientMessage::RequestGameStart(req)) =
+            decode_client_message(data)
+        {
+            let game_name = req.game_name.clone();
+            let mut rooms = state.rooms.write().await;
+            match rooms.start_game(
+                room_code,
+                req,
+                player_id,
+                &state.game_registry,
+                Arc::clone(&state.rooms),
+            ) {
+                Ok(()) => {
+                    drop(rooms);
+                    tracing::info!(
+                        player_id,
+                        room_code,
+                        game = %game_name,
+                        "Game started"
+                    );
+                    record_co_players(state, room_code).await;
+                },
+                Err(e) => {
+                    tracing::warn!(
+                        player_id,
+                        room_code,
+                        game = %game_name,
+                        error = %e,
+                        "Failed to start game"
+                    );
+                },
             }
-            continue;
         }
+        return;
+    }
 
-        // AddBot: leader adds a bot player to the lobby
-        if msg_type == MessageType::AddBot {
+    // DebugCommand: leader applies a god-mode command to the running round
+    if msg_type == MessageType::DebugCommand {
+        let command = match decode_client_message(data) {
+            Ok(ClientMessage::DebugCommand(req)) => req.command,
+            _ => return,
+        };
+        let mut rooms = state.rooms.write().await;
+        if let Err(e) = rooms.debug_command(room_code, player_id, command, &state.config.debug) {
+            tracing::warn!(player_id, room_code, error = %e, "Debug command rejected");
+        }
+        return;
+    }
+
+    // ChangeGameConfig: leader changes live settings in the running round
+    if msg_type == MessageType::ChangeGameConfig {
+        let delta = match decode_client_message(data) {
+            Ok(ClientMessage::ChangeGameConfig(req)) => req.delta,
+            _ => return,
+        };
+        let rooms = state.rooms.read().await;
+        if let Err(e) = rooms.change_game_config(room_code, player_id, delta) {
+            tracing::warn!(player_id, room_code, error = %e, "Settings change rejected");
+        }
+        return;
+    }
+
+    // LobbyToyInput: a click on the lobby toy while the room waits
+    if msg_type == MessageType::LobbyToyInput {
+        if let Ok(ClientMessage::LobbyToyInput(req)) = decode_client_message(data) {
             let mut rooms = state.rooms.write().await;
-            match rooms.add_bot(room_code, player_id) {
-                Ok(bot_id) => {
-                    tracing::info!(player_id, room_code, bot_id, "Bot added");
+            rooms.lobby_toy_input(room_code, player_id, &req.input_data);
+        }
+        return;
+    }
+
+    // ClockSync: answer the prober with our clock beside theirs
+    if msg_type == MessageType::ClockSync {
+        if let Ok(ClientMessage::ClockSync(req)) = decode_client_message(data) {
+            if let Some(rtt_ms) = req.rtt_ms {
+                state
+                    .rooms
+                    .write()
+                    .await
+                    .record_ping(room_code, player_id, rtt_ms);
+            }
+            let reply = ServerMessage::ClockSyncReply(ClockSyncReplyMsg {
+                client_time: req.client_time,
+                server_time_ms: breakpoint_core::time::unix_millis(),
+            });
+            if let Ok(encoded) = encode_server_message(&reply) {
+                let rooms = state.rooms.read().await;
+                rooms.send_to_player(room_code, player_id, Lane::Control, Bytes::from(encoded));
+            }
+        }
+        return;
+    }
+
+    // RerollName: swap a name for the next generated suggestion
+    if msg_type == MessageType::RerollName {
+        if let Ok(ClientMessage::RerollName(req)) = decode_client_message(data) {
+            let mut rooms = state.rooms.write().await;
+            match rooms.reroll_name(room_code, player_id, req.player_id) {
+                Ok(name) => {
+                    tracing::debug!(player_id = req.player_id, room_code, %name, "Name re-rolled");
                     rooms.broadcast_player_list(room_code);
                 },
                 Err(e) => {
-                    tracing::warn!(player_id, room_code, error = %e, "Failed to add bot");
+                    tracing::debug!(player_id, room_code, error = %e, "Name re-roll refused");
                 },
             }
-            continue;
         }
+        return;
+    }
 
-        // RemoveBot: leader removes a bot player from the lobby
-        if msg_type == MessageType::RemoveBot {
-            if let Ok(breakpoint_core::net::messages::ClientMessage::RemoveBot(req)) =
-                decode_client_message(&data)
-            {
-                let mut rooms = state.rooms.write().await;
-                match rooms.remove_bot(room_code, req.player_id, player_id) {
-                    Ok(()) => {
-                        tracing::info!(player_id, room_code, bot_id = req.player_id, "Bot removed");
-                        rooms.broadcast_player_list(room_code);
-                    },
-                    Err(e) => {
-                        tracing::warn!(player_id, room_code, error = %e, "Failed to remove bot");
-                    },
-                }
+    // AddBot: leader adds a bot player to the lobby
+    if msg_type == MessageType::AddBot {
+        let difficulty = match decode_client_message(data) {
+            Ok(ClientMessage::AddBot(req)) => req.difficulty,
+            _ => return,
+        };
+        let mut rooms = state.rooms.write().await;
+        match rooms.add_bot(room_code, player_id, difficulty) {
+            Ok(bot_id) => {
+                tracing::info!(player_id, room_code, bot_id, "Bot added");
+                rooms.broadcast_player_list(room_code);
+            },
+            Err(e) => {
+                tracing::warn!(player_id, room_code, error = %e, "Failed to add bot");
+            },
+        }
+        return;
+    }
+
+    // RemoveBot: leader removes a bot player from the lobby
+    if msg_type == MessageType::RemoveBot {
+        if let Ok(breakpoint_core::net::messages::ClientMessage::RemoveBot(req)) =
+            decode_client_message(data)
+        {
+            let mut rooms = state.rooms.write().await;
+            match rooms.remove_bot(room_code, req.player_id, player_id) {
+                Ok(()) => {
+                    tracing::info!(player_id, room_code, bot_id = req.player_id, "Bot removed");
+                    rooms.broadcast_player_list(room_code);
+                },
+                Err(e) => {
+                    tracing::warn!(player_id, room_code, error = %e, "Failed to remove bot");
+                },
             }
-            continue;
         }
+        return;
+    }
 
-        // ClaimAlert needs special lock handling (read→drop→write→read)
-        if msg_type == MessageType::ClaimAlert {
-            if let Ok(breakpoint_core::net::messages::ClientMessage::ClaimAlert(claim)) =
-                decode_client_message(&data)
-            {
-                // Reject spoofed claims
-                if claim.player_id != player_id {
-                    continue;
-                }
+    // AddLocalPlayer: a hot-seat guest joins on this connection
+    if msg_type == MessageType::AddLocalPlayer {
+        let Ok(ClientMessage::AddLocalPlayer(req)) = decode_client_message(data) else {
+            return;
+        };
+        let name = req.player_name.trim().to_string();
+        if name.len() > 32 || name.chars().any(|c| c.is_control()) {
+            return;
+        }
+        let mut rooms = state.rooms.write().await;
+        match rooms.add_local_player(room_code, player_id, name, req.player_color) {
+            Ok(guest_id) => {
+                tracing::info!(player_id, room_code, guest_id, "Local player added");
+                rooms.broadcast_player_list(room_code);
+            },
+            Err(e) => {
+                tracing::warn!(player_id, room_code, error = %e, "Failed to add local player");
+            },
+        }
+        return;
+    }
 
-                let player_name = {
-                    let rooms = state.rooms.read().await;
-                    rooms
-                        .get_player_name(room_code, claim.player_id)
-                        .unwrap_or_else(|| format!("Player {}", claim.player_id))
-                };
+    // RemoveLocalPlayer: drop one of this connection's hot-seat guests
+    if msg_type == MessageType::RemoveLocalPlayer {
+        if let Ok(ClientMessage::RemoveLocalPlayer(req)) = decode_client_message(data) {
+            let mut rooms = state.rooms.write().await;
+            match rooms.remove_local_player(room_code, req.player_id, player_id) {
+                Ok(()) => {
+                    tracing::info!(
+                        player_id,
+                        room_code,
+                        guest_id = req.player_id,
+                        "Local player removed"
+                    );
+                    rooms.broadcast_player_list(room_code);
+                },
+                Err(e) => {
+                    tracing::warn!(player_id, room_code, error = %e, "Failed to remove local player");
+                },
+            }
+        }
+        return;
+    }
 
-                // Record the claim in the event store
-                let now = breakpoint_core::time::timestamp_now();
-                {
-                    let mut store = state.event_store.write().await;
-                    store.claim(&claim.event_id, player_name.clone(), now);
-                }
+    // SetBotDifficulty: leader changes a bot's skill in the lobby
+    if msg_type == MessageType::SetBotDifficulty {
+        if let Ok(ClientMessage::SetBotDifficulty(req)) = decode_client_message(data) {
+            let mut rooms = state.rooms.write().await;
+            match rooms.set_bot_difficulty(room_code, req.player_id, req.difficulty, player_id) {
+                Ok(()) => rooms.broadcast_player_list(room_code),
+                Err(e) => {
+                    tracing::warn!(player_id, room_code, error = %e, "Bot difficulty rejected");
+                },
+            }
+        }
+        return;
+    }
 
-                // Build and broadcast AlertClaimed to the room
-                let msg = ServerMessage::AlertClaimed(AlertClaimedMsg {
-                    event_id: claim.event_id,
-                    claimed_by: claim.player_id,
-                });
-                if let Ok(encoded) = encode_server_message(&msg) {
-                    let rooms = state.rooms.read().await;
-                    rooms.broadcast_to_room(room_code, &encoded);
-                }
+    // ManageJoinQueue: leader approves, denies, or toggles approval
+    if msg_type == MessageType::ManageJoinQueue {
+        if let Ok(ClientMessage::ManageJoinQueue(req)) = decode_client_message(data) {
+            let mut rooms = state.rooms.write().await;
+            match rooms.manage_join_queue(room_code, player_id, req.action) {
+                Ok(()) => rooms.broadcast_player_list(room_code),
+                Err(e) => {
+                    tracing::warn!(player_id, room_code, error = %e, "Join queue action rejected");
+                },
             }
-            continue;
         }
+        return;
+    }
 
-        // All other messages use a read lock
-        let rooms = state.rooms.read().await;
+    // ReshapeRoom: leader merges the lobby into another room or splits it
+    if msg_type == MessageType::ReshapeRoom {
+        if let Ok(ClientMessage::ReshapeRoom(req)) = decode_client_message(data) {
+            let mut rooms = state.rooms.write().await;
+            if let Err(e) = rooms.reshape_room(room_code, Some(player_id), req.action) {
+                tracing::warn!(player_id, room_code, error = %e, "Room reshape rejected");
+            }
+        }
+        return;
+    }
 
-        match msg_type {
-            // Player inputs routed to the server game session
-            MessageType::PlayerInput => {
-                if let Ok(breakpoint_core::net::messages::ClientMessage::PlayerInput(pi)) =
-                    decode_client_message(&data)
-                {
-                    rooms.route_player_input(room_code, player_id, pi.tick, pi.input_data);
-                }
-            },
+    // OverlayConfig: leader changes overlay settings and the room's event filter
+    if msg_type == MessageType::OverlayConfig {
+        if let Ok(ClientMessage::OverlayConfig(msg)) = decode_client_message(data) {
+            let mut rooms = state.rooms.write().await;
+            match rooms.set_overlay_config(room_code, player_id, msg.room_config) {
+                Ok(()) => rooms.broadcast_to_room(room_code, Lane::Control, data),
+                Err(e) => {
+                    tracing::warn!(player_id, room_code, error = %e, "Overlay config rejected");
+                },
+            }
+        }
+        return;
+    }
 
-            // Chat messages broadcast to all (cap at 1024 bytes, valid UTF-8, no control chars)
-            MessageType::ChatMessage => {
-                if data.len() <= 1024 {
-                    // Decode and validate content length at the application level
-                    if let Ok(breakpoint_core::net::messages::ClientMessage::ChatMessage(cm)) =
-                        decode_client_message(&data)
-                    {
-                        if cm.content.len() > 1024 {
-                            tracing::debug!(
-                                player_id,
-                                room_code,
-                                "Chat message content exceeds 1024 chars"
-                            );
-                            continue;
-                        }
-                        if cm.content.chars().any(|c| c.is_control() && c != '\n') {
-                            continue;
-                        }
-                        rooms.broadcast_to_room(room_code, &data);
-                    }
-                }
-            },
+    // Friends list changes, invites, and presence sharing
+    if matches!(
+        msg_type,
+        MessageType::SetFriend | MessageType::InviteFriend | MessageType::SetPresenceSharing
+    ) {
+        if let Ok(msg) = decode_client_message(data) {
+            handle_social_message(state, identity, msg).await;
+        }
+        return;
+    }
 
-            // Alert events, claimed, dismissed — broadcast to all
-            MessageType::AlertEvent | MessageType::AlertClaimed | MessageType::AlertDismissed => {
-                rooms.broadcast_to_room(room_code, &data);
-            },
+    // ClaimAlert needs special lock handling (read→drop→write→read)
+    if msg_type == MessageType::ClaimAlert {
+        if let Ok(breakpoint_core::net::messages::ClientMessage::ClaimAlert(claim)) =
+            decode_client_message(data)
+        {
+            // Reject spoofed claims
+            if claim.player_id != player_id {
+                return;
+            }
 
-            // Player list updates broadcast to all
-            MessageType::PlayerList | MessageType::RoomConfigMsg => {
-                rooms.broadcast_to_room(room_code, &data);
-            },
+            let player_name = {
+                let rooms = state.rooms.read().await;
+                rooms
+                    .get_player_name(room_code, claim.player_id)
+                    .unwrap_or_else(|| format!("Player {}", claim.player_id))
+            };
 
-            // Overlay config broadcast to all
-            MessageType::OverlayConfig => {
-                rooms.broadcast_to_room(room_code, &data);
-            },
+            // Record the claim in the event store
+            let now = breakpoint_core::time::timestamp_now();
+            {
+                let mut store = state.event_store.write().await;
+                store.claim(&claim.event_id, player_name.clone(), now);
+            }
 
-            _ => {},
+            // Build and broadcast AlertClaimed to the room
+            let msg = ServerMessage::AlertClaimed(AlertClaimedMsg {
+                event_id: claim.event_id,
+                claimed_by: claim.player_id,
+            });
+            if let Ok(encoded) = encode_server_message(&msg) {
+                let rooms = state.rooms.read().await;
+                rooms.broadcast_to_room(room_code, Lane::Control, &encoded);
+            }
         }
+        return;
+    }
+
+    // All other messages use a read lock
+    let rooms = state.rooms.read().await;
+
+    match msg_type {
+        // Player inputs routed to the server game session
+        MessageType::PlayerInput => {
+            if let Ok(breakpoint_core::net::messages::ClientMessage::PlayerInput(pi)) =
+                decode_client_message(data)
+                // External bots play through the ack flow only
+                && !rooms.is_external_bot(room_code, player_id)
+            {
+                rooms.note_input_tick(room_code, player_id, pi.tick);
+                let target = rooms.input_target(room_code, player_id, pi.player_id);
+                rooms.route_player_input(room_code, target, pi.tick, pi.input_data);
+            }
+        },
+
+        // External bot inputs, checked against the session's tick
+        MessageType::BotAck => {
+            if let Ok(breakpoint_core::net::messages::ClientMessage::BotAck(ack)) =
+                decode_client_message(data)
+            {
+                rooms.route_bot_ack(room_code, player_id, ack.tick, ack.input_data);
+            }
+        },
+
+        // Course ballots routed to the server game session's pre-round vote
+        MessageType::CastCourseVote => {
+            if let Ok(breakpoint_core::net::messages::ClientMessage::CastCourseVote(vote)) =
+                decode_client_message(data)
+            {
+                rooms.route_course_vote(room_code, player_id, vote.round, vote.choice);
+            }
+        },
+
+        // Captain picks routed to the server game session's team draft
+        MessageType::DraftPick => {
+            if let Ok(breakpoint_core::net::messages::ClientMessage::DraftPick(pick)) =
+                decode_client_message(data)
+            {
+                rooms.route_draft_pick(room_code, player_id, pick.player_id);
+            }
+        },
+
+        // Leader skips the rest of the intermission or ends the warmup
+        MessageType::AdvanceRound => {
+            if let Ok(breakpoint_core::net::messages::ClientMessage::AdvanceRound(req)) =
+                decode_client_message(data)
+                && let Err(e) = rooms.advance_round(room_code, player_id, req.round)
+            {
+                tracing::debug!(player_id, room_code, error = %e, "Advance round rejected");
+            }
+        },
+
+        // Chat messages broadcast to all (cap at 1024 bytes, valid UTF-8, no control chars)
+        MessageType::ChatMessage if data.len() <= 1024 => {
+            // Decode and validate content length at the application level
+            if let Ok(breakpoint_core::net::messages::ClientMessage::ChatMessage(cm)) =
+                decode_client_message(data)
+            {
+                if cm.content.len() > 1024 {
+                    tracing::debug!(
+                        player_id,
+                        room_code,
+                        "Chat message content exceeds 1024 chars"
+                    );
+                    return;
+                }
+                if cm.content.chars().any(|c| c.is_control() && c != '\n') {
+                    return;
+                }
+                rooms.broadcast_to_room(room_code, Lane::Cosmetic, data);
+            }
+        },
+
+        // Alert events, claimed, dismissed — broadcast to all
+        MessageType::AlertEvent | MessageType::AlertClaimed | MessageType::AlertDismissed => {
+            rooms.broadcast_to_room(room_code, Lane::Control, data);
+        },
+
+        // Player list updates broadcast to all
+        MessageType::PlayerList | MessageType::RoomConfigMsg => {
+            rooms.broadcast_to_room(room_code, Lane::Control, data);
+        },
+
+        _ => {},
     }
 }
 
@@ -583,4 +1600,25 @@ mod tests {
             "Should succeed after time passes and tokens refill"
         );
     }
+
+    #[test]
+    fn coalesce_frames_packs_up_to_the_size_limit() {
+        let small = |b: u8| Bytes::from(vec![MessageType::GameState as u8, b]);
+        assert_eq!(
+            coalesce_frames(vec![small(1)]),
+            vec![small(1)],
+            "Lone frames pass through"
+        );
+
+        let packed = coalesce_frames(vec![small(1), small(2), small(3)]);
+        assert_eq!(packed.len(), 1);
+        let frames = breakpoint_core::net::protocol::split_batch(&packed[0]).unwrap();
+        assert_eq!(frames, vec![&small(1)[..], &small(2)[..], &small(3)[..]]);
+
+        let big = Bytes::from(vec![MessageType::GameState as u8; MAX_MESSAGE_SIZE / 2]);
+        let packed = coalesce_frames(vec![big.clone(), big.clone(), small(4)]);
+        assert_eq!(packed.len(), 2, "Second big frame starts a new batch");
+        assert_eq!(packed[0], big);
+        assert_eq!(packed[1][0], MessageType::Batch as u8);
+    }
 }
@@ -103,6 +103,29 @@ async fn get_status_shows_posted_event() {
     let recent = body["recent_events"].as_array().unwrap();
     assert_eq!(recent.len(), 1);
     assert_eq!(recent[0]["id"], "status-evt-1");
+    assert_eq!(body["status"], "ok");
+    assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(body["rooms"]["active"], 0);
+    assert!(body["backlog"]["queued_broadcasts"].is_u64());
+}
+
+#[tokio::test]
+async fn status_page_is_public_html() {
+    let server = TestServer::with_auth("test-token", "webhook-secret").await;
+    let resp = reqwest::get(format!("{}/status", server.base_url()))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers()["cache-control"], "no-store");
+    assert!(
+        resp.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/html")
+    );
+    let html = resp.text().await.unwrap();
+    assert!(html.contains("<h1>Breakpoint is ok</h1>"));
+    assert!(html.contains("Active rooms"));
 }
 
 #[tokio::test]
@@ -279,3 +302,413 @@ async fn no_auth_mode_allows_requests() {
 
     assert_eq!(resp.status(), 201);
 }
+
+#[tokio::test]
+async fn admin_announcement_broadcast_to_rooms() {
+    use breakpoint_core::net::messages::ServerMessage;
+
+    let server = TestServer::new().await;
+    let mut ws = common::ws_connect(&server.ws_url()).await;
+    common::ws_create_room(&mut ws, "Alice").await;
+    // Drain the player list that follows the join
+    common::ws_read_server_msg(&mut ws).await;
+
+    let resp = reqwest::Client::new()
+        .post(format!("{}/api/v1/admin/announcements", server.base_url()))
+        .json(&serde_json::json!({
+            "message": "Maintenance in 15 minutes",
+            "severity": "warning",
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 201);
+
+    match common::ws_read_server_msg(&mut ws).await {
+        ServerMessage::Announcements(msg) => {
+            assert_eq!(msg.announcements.len(), 1);
+            assert_eq!(msg.announcements[0].message, "Maintenance in 15 minutes");
+        },
+        other => panic!("Expected Announcements, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn configured_motd_sent_on_join() {
+    use breakpoint_core::net::messages::ServerMessage;
+    use breakpoint_server::config::{AnnouncementsConfig, ServerConfig};
+
+    let server = TestServer::from_config(ServerConfig {
+        announcements: AnnouncementsConfig {
+            motd: Some("Welcome to Breakpoint".to_string()),
+            ..AnnouncementsConfig::default()
+        },
+        ..ServerConfig::default()
+    })
+    .await;
+    let mut ws = common::ws_connect(&server.ws_url()).await;
+    common::ws_create_room(&mut ws, "Alice").await;
+    common::ws_read_server_msg(&mut ws).await;
+
+    match common::ws_read_server_msg(&mut ws).await {
+        ServerMessage::Announcements(msg) => {
+            assert_eq!(msg.announcements[0].id, "motd");
+        },
+        other => panic!("Expected Announcements, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn referee_simulates_input_log() {
+    let server = TestServer::with_auth("test-token", "webhook-secret").await;
+    let client = reqwest::Client::new();
+    let url = format!("{}/api/v1/referee/simulate", server.base_url());
+    let body = serde_json::json!({
+        "game": "tron",
+        "players": [{ "id": 1, "name": "Alice" }, { "id": 2, "name": "Bob" }],
+        "round_count": 1,
+        "inputs": [
+            { "tick": 10, "player_id": 1, "input": { "turn": "Left", "brake": false } },
+        ],
+    });
+
+    let resp = client.post(&url).json(&body).send().await.unwrap();
+    assert_eq!(resp.status(), 401, "Referee requires bearer auth");
+
+    let resp = client
+        .post(&url)
+        .bearer_auth("test-token")
+        .json(&body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let result: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(result["game"], "tron");
+    assert_eq!(result["rounds"][0]["completed"], true);
+    assert_eq!(result["final_scores"].as_array().unwrap().len(), 2);
+    assert_eq!(result["ignored_inputs"], 0);
+
+    let resp = client
+        .post(&url)
+        .bearer_auth("test-token")
+        .json(&serde_json::json!({ "game": "chess", "players": [{ "id": 1 }], "inputs": [] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+}
+
+#[tokio::test]
+async fn client_telemetry_reaches_admin_view() {
+    let server = TestServer::with_auth("test-token", "webhook-secret").await;
+    let client = reqwest::Client::new();
+    let batch = serde_json::json!({
+        "session_id": "3f9a",
+        "client_version": "0.1.0",
+        "reports": [
+            { "kind": "decode_error", "message": "Failed to decode RoundEnd (9 bytes)", "context": "InGame/golf" },
+            { "kind": "disconnect", "message": "code=1006 reason=''" },
+        ],
+    });
+
+    // No bearer token needed; sendBeacon posts text/plain
+    let resp = client
+        .post(format!("{}/api/v1/telemetry", server.base_url()))
+        .header("content-type", "text/plain;charset=UTF-8")
+        .body(batch.to_string())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 202);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body["accepted"], 2);
+
+    let resp = client
+        .get(format!("{}/api/v1/admin/telemetry", server.base_url()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 401);
+
+    let resp = client
+        .get(format!("{}/api/v1/admin/telemetry", server.base_url()))
+        .bearer_auth("test-token")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let summary: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(summary["total_reports"], 2);
+    assert_eq!(summary["groups"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn match_history_routes() {
+    let server = TestServer::with_auth("test-token", "webhook-secret").await;
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .get(format!("{}/api/v1/history", server.base_url()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 401);
+
+    let resp = client
+        .get(format!("{}/api/v1/history", server.base_url()))
+        .bearer_auth("test-token")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body["matches"], serde_json::json!([]));
+
+    // Downloads need no token, but the match must exist
+    let resp = client
+        .get(format!(
+            "{}/api/v1/public/history/no-such-match/replay",
+            server.base_url()
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 404);
+
+    let resp = client
+        .get(format!(
+            "{}/api/v1/public/history/no-such-match/keyframes?round=1&every=5",
+            server.base_url()
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 404);
+}
+
+#[tokio::test]
+async fn player_settings_sync_requires_identity_key() {
+    let server = TestServer::with_auth("test-token", "webhook-secret").await;
+    let client = reqwest::Client::new();
+    let key = "0123456789abcdef0123456789abcdef";
+    let id = breakpoint_server::social::player_identity(key).unwrap();
+    let url = format!("{}/api/v1/players/{id}/settings", server.base_url());
+
+    // The bearer token is not enough; only the owner's identity key is
+    let resp = client
+        .get(&url)
+        .bearer_auth("test-token")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 401);
+    let resp = client
+        .get(&url)
+        .header("x-identity-key", key)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 404, "Nothing synced yet");
+
+    let settings = serde_json::json!({
+        "updated_at": 200,
+        "values": { "ui_language": "es" },
+        "keybinds": { "jump": { "key": "Space", "updated_at": 200 } },
+    });
+    let resp = client
+        .put(&url)
+        .header("x-identity-key", key)
+        .json(&settings)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+
+    // A second device with older settings adopts the synced copy
+    let resp = client
+        .put(&url)
+        .header("x-identity-key", key)
+        .json(&serde_json::json!({ "updated_at": 100, "values": { "ui_language": "en" } }))
+        .send()
+        .await
+        .unwrap();
+    let merged: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(merged, settings);
+
+    let other = "fedcba9876543210fedcba9876543210";
+    let resp = client
+        .put(&url)
+        .header("x-identity-key", other)
+        .json(&settings)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 401);
+}
+
+#[tokio::test]
+async fn content_limits_refuse_uploads_and_admins_can_delete() {
+    use breakpoint_server::config::{ContentLimitsConfig, ServerConfig};
+
+    let server = TestServer::from_config(ServerConfig {
+        content: ContentLimitsConfig {
+            max_item_bytes: 200,
+            ..ContentLimitsConfig::default()
+        },
+        ..ServerConfig::default()
+    })
+    .await;
+    let client = reqwest::Client::new();
+    let key = "0123456789abcdef0123456789abcdef";
+    let id = breakpoint_server::social::player_identity(key).unwrap();
+    let url = format!("{}/api/v1/players/{id}/settings", server.base_url());
+
+    let resp = client
+        .put(&url)
+        .header("x-identity-key", key)
+        .json(&serde_json::json!({ "updated_at": 1, "values": { "name": "x".repeat(300) } }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 413);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert!(
+        body["error"]
+            .as_str()
+            .unwrap()
+            .contains("limit is 200 bytes")
+    );
+
+    let resp = client
+        .put(&url)
+        .header("x-identity-key", key)
+        .json(&serde_json::json!({ "updated_at": 1, "values": { "ui_language": "es" } }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+
+    let content_url = format!("{}/api/v1/admin/content", server.base_url());
+    let listing: serde_json::Value = client
+        .get(&content_url)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let items = listing["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["kind"], "player_settings");
+    assert_eq!(items[0]["owner"], id.to_string());
+    assert_eq!(listing["total_bytes"], items[0]["bytes"]);
+
+    let item_url = format!("{content_url}/player_settings/{id}");
+    let resp = client.delete(&item_url).send().await.unwrap();
+    assert_eq!(resp.status(), 204);
+    let resp = client.delete(&item_url).send().await.unwrap();
+    assert_eq!(resp.status(), 404);
+    let resp = client
+        .get(&url)
+        .header("x-identity-key", key)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 404, "Deleted settings are gone");
+    let resp = client
+        .delete(format!("{content_url}/skins/abc"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+}
+
+#[tokio::test]
+async fn banned_identities_are_refused_until_unbanned() {
+    use breakpoint_core::i18n::TextKey;
+    use breakpoint_core::net::messages::{ClientMessage, JoinRoomMsg, ServerMessage};
+    use breakpoint_core::player::PlayerColor;
+
+    let server = TestServer::new().await;
+    let client = reqwest::Client::new();
+    let key = "0123456789abcdef0123456789abcdef";
+    let id = breakpoint_server::social::player_identity(key).unwrap();
+    let bans_url = format!("{}/api/v1/admin/bans", server.base_url());
+
+    let resp = client
+        .post(&bans_url)
+        .header("x-moderator", "alice")
+        .json(&serde_json::json!({ "identity": id.to_string(), "reason": "griefing" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 201);
+    let ban: serde_json::Value = resp.json().await.unwrap();
+    let ban_id = ban["id"].as_str().unwrap().to_string();
+    assert_eq!(ban["moderator"], "alice");
+
+    let join = ClientMessage::JoinRoom(JoinRoomMsg {
+        room_code: String::new(),
+        player_name: "Mallory".to_string(),
+        player_color: PlayerColor::default(),
+        protocol_version: breakpoint_core::net::protocol::PROTOCOL_VERSION,
+        session_token: None,
+        identity_key: Some(key.to_string()),
+        accepts_batches: false,
+        room_alias: None,
+        bot_token: None,
+        tutorial: None,
+    });
+    let mut ws = common::ws_connect(&server.ws_url()).await;
+    common::ws_send_client_msg(&mut ws, &join).await;
+    match common::ws_read_server_msg(&mut ws).await {
+        ServerMessage::JoinRoomResponse(resp) => {
+            assert!(!resp.success);
+            assert_eq!(resp.error.unwrap().key, TextKey::Banned);
+        },
+        other => panic!("Expected JoinRoomResponse, got: {other:?}"),
+    }
+
+    let listing: serde_json::Value = client
+        .get(&bans_url)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(listing["bans"].as_array().unwrap().len(), 1);
+
+    let resp = client
+        .delete(format!("{bans_url}/{ban_id}"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 204);
+    let mut ws = common::ws_connect(&server.ws_url()).await;
+    common::ws_send_client_msg(&mut ws, &join).await;
+    match common::ws_read_server_msg(&mut ws).await {
+        ServerMessage::JoinRoomResponse(resp) => assert!(resp.success, "{resp:?}"),
+        other => panic!("Expected JoinRoomResponse, got: {other:?}"),
+    }
+
+    let audit: serde_json::Value = client
+        .get(format!(
+            "{}/api/v1/admin/moderation/audit",
+            server.base_url()
+        ))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let actions: Vec<&str> = audit["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["action"].as_str().unwrap())
+        .collect();
+    assert_eq!(actions, ["unban", "refused", "ban"]);
+}
@@ -16,8 +16,21 @@ use breakpoint_core::net::protocol::{
 };
 use breakpoint_core::player::PlayerColor;
 
-use breakpoint_server::config::{AuthFileConfig, ServerConfig};
-use breakpoint_server::{build_app, spawn_event_broadcaster};
+use breakpoint_server::config::{AuthFileConfig, RoomsConfig, ServerConfig};
+use breakpoint_server::{build_app, spawn_event_broadcaster, spawn_presence_notifier};
+
+/// Default server config with the round countdown and freeze frame
+/// disabled, so games accept input as soon as they start.
+pub fn test_config() -> ServerConfig {
+    ServerConfig {
+        rooms: RoomsConfig {
+            countdown_secs: 0.0,
+            freeze_frame_secs: 0.0,
+            ..RoomsConfig::default()
+        },
+        ..ServerConfig::default()
+    }
+}
 
 pub struct TestServer {
     pub addr: SocketAddr,
@@ -27,7 +40,7 @@ pub struct TestServer {
 impl TestServer {
     /// Start a test server with no auth.
     pub async fn new() -> Self {
-        Self::from_config(ServerConfig::default()).await
+        Self::from_config(test_config()).await
     }
 
     /// Start a test server with no auth and no webhook signature requirement.
@@ -37,7 +50,7 @@ impl TestServer {
                 require_webhook_signature: false,
                 ..AuthFileConfig::default()
             },
-            ..ServerConfig::default()
+            ..test_config()
         };
         Self::from_config(config).await
     }
@@ -50,7 +63,7 @@ impl TestServer {
                 github_webhook_secret: Some(webhook_secret.to_string()),
                 require_webhook_signature: false,
             },
-            ..ServerConfig::default()
+            ..test_config()
         };
         Self::from_config(config).await
     }
@@ -60,6 +73,7 @@ impl TestServer {
         let addr = listener.local_addr().unwrap();
 
         let (app, state) = build_app(config);
+        spawn_presence_notifier(state.clone());
         spawn_event_broadcaster(state);
 
         let handle = tokio::spawn(async move {
@@ -102,6 +116,11 @@ pub async fn ws_create_room(
         player_color: PlayerColor::default(),
         protocol_version: breakpoint_core::net::protocol::PROTOCOL_VERSION,
         session_token: None,
+        identity_key: None,
+        accepts_batches: false,
+        room_alias: None,
+        bot_token: None,
+        tutorial: None,
     });
     let encoded = encode_client_message(&msg).unwrap();
     stream.send(Message::Binary(encoded.into())).await.unwrap();
@@ -132,6 +151,11 @@ pub async fn ws_join_room(
         player_color: PlayerColor::PALETTE[1],
         protocol_version: breakpoint_core::net::protocol::PROTOCOL_VERSION,
         session_token: None,
+        identity_key: None,
+        accepts_batches: false,
+        room_alias: None,
+        bot_token: None,
+        tutorial: None,
     });
     let encoded = encode_client_message(&msg).unwrap();
     stream.send(Message::Binary(encoded.into())).await.unwrap();
@@ -155,6 +179,11 @@ pub async fn ws_join_room_expect_error(
         player_color: PlayerColor::default(),
         protocol_version: breakpoint_core::net::protocol::PROTOCOL_VERSION,
         session_token: None,
+        identity_key: None,
+        accepts_batches: false,
+        room_alias: None,
+        bot_token: None,
+        tutorial: None,
     });
     let encoded = encode_client_message(&msg).unwrap();
     stream.send(Message::Binary(encoded.into())).await.unwrap();
@@ -178,6 +207,11 @@ pub async fn ws_join_room_with_name(
         player_color: PlayerColor::default(),
         protocol_version: breakpoint_core::net::protocol::PROTOCOL_VERSION,
         session_token: None,
+        identity_key: None,
+        accepts_batches: false,
+        room_alias: None,
+        bot_token: None,
+        tutorial: None,
     });
     let encoded = encode_client_message(&msg).unwrap();
     stream.send(Message::Binary(encoded.into())).await.unwrap();
@@ -267,6 +301,9 @@ pub async fn ws_request_game_start(stream: &mut WsStream, game_name: &str) {
     let msg = ClientMessage::RequestGameStart(RequestGameStartMsg {
         game_name: game_name.to_string(),
         custom: std::collections::HashMap::new(),
+        win_condition: None,
+        pacing: None,
+        featured: false,
     });
     ws_send_client_msg(stream, &msg).await;
 }
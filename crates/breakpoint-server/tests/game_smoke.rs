@@ -74,6 +74,10 @@ async fn golf_input_processed_by_server() {
         aim_angle: 0.5,
         power: 0.6,
         stroke: true,
+        skip_intro: false,
+        undo: false,
+        caddie: false,
+        screen_aim: None,
     };
     let input_data = rmp_serde::to_vec(&golf_input).unwrap();
     let msg = ClientMessage::PlayerInput(PlayerInputMsg {
@@ -212,6 +216,10 @@ async fn full_golf_round_via_game_engine() {
         aim_angle: aim,
         power: 0.6,
         stroke: true,
+        skip_intro: false,
+        undo: false,
+        caddie: false,
+        screen_aim: None,
     };
     let data = rmp_serde::to_vec(&input).unwrap();
     game.apply_input(1, &data);
@@ -232,6 +240,10 @@ async fn full_golf_round_via_game_engine() {
                 aim_angle: aim,
                 power: 0.4,
                 stroke: true,
+                skip_intro: false,
+                undo: false,
+                caddie: false,
+                screen_aim: None,
             };
             let data = rmp_serde::to_vec(&input).unwrap();
             game.apply_input(1, &data);
@@ -277,6 +289,10 @@ async fn golf_stroke_at_all_cardinal_directions() {
             aim_angle: *angle,
             power: 0.5,
             stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
         };
         let data = rmp_serde::to_vec(&input).unwrap();
         game.apply_input(1, &data);
@@ -321,6 +337,10 @@ async fn golf_zero_power_stroke_no_movement() {
         aim_angle: 0.0,
         power: 0.0,
         stroke: true,
+        skip_intro: false,
+        undo: false,
+        caddie: false,
+        screen_aim: None,
     };
     let data = rmp_serde::to_vec(&input).unwrap();
     game.apply_input(1, &data);
@@ -343,6 +363,10 @@ async fn golf_stroke_while_moving_rejected() {
         aim_angle: 0.0,
         power: 0.5,
         stroke: true,
+        skip_intro: false,
+        undo: false,
+        caddie: false,
+        screen_aim: None,
     };
     let data = rmp_serde::to_vec(&input).unwrap();
     game.apply_input(1, &data);
@@ -420,6 +444,10 @@ async fn multi_round_state_resets_golf() {
         aim_angle: 0.0,
         power: 0.5,
         stroke: true,
+        skip_intro: false,
+        undo: false,
+        caddie: false,
+        screen_aim: None,
     };
     let data = rmp_serde::to_vec(&input).unwrap();
     game.apply_input(1, &data);
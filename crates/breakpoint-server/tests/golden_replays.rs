@@ -0,0 +1,131 @@
+//! Golden replay regression tests: each file in `tests/golden/` holds a
+//! recorded input log, as a referee `SimulateRequest`, and the outcome it
+//! produced. The log is re-simulated and the test fails when a physics or
+//! config change alters the outcome. After an intentional change,
+//! regenerate the outcomes with:
+//!
+//! ```sh
+//! BREAKPOINT_BLESS=1 cargo test -p breakpoint-server --test golden_replays
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use breakpoint_core::game_trait::{PlayerId, PlayerScore};
+use breakpoint_server::config::RefereeConfig;
+use breakpoint_server::game_loop::ServerGameRegistry;
+use breakpoint_server::referee::{self, SimulateRequest};
+use serde::{Deserialize, Serialize};
+
+const BLESS_VAR: &str = "BREAKPOINT_BLESS";
+
+/// One golden file. The request is kept as written so blessing only
+/// rewrites `expected`.
+#[derive(Debug, Serialize, Deserialize)]
+struct GoldenReplay {
+    request: serde_json::Value,
+    #[serde(default)]
+    expected: Option<Outcome>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Outcome {
+    rounds: Vec<RoundOutcome>,
+    final_scores: Vec<(PlayerId, i32)>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct RoundOutcome {
+    ticks: u32,
+    completed: bool,
+    scores: Vec<(PlayerId, i32)>,
+}
+
+fn scores(scores: &[PlayerScore]) -> Vec<(PlayerId, i32)> {
+    scores.iter().map(|s| (s.player_id, s.score)).collect()
+}
+
+fn simulate(path: &Path, request: &serde_json::Value) -> Outcome {
+    let request: SimulateRequest = serde_json::from_value(request.clone())
+        .unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+    let response = referee::simulate(
+        &ServerGameRegistry::new(),
+        &request,
+        &RefereeConfig::default(),
+    )
+    .unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+    assert_eq!(
+        response.ignored_inputs,
+        0,
+        "{}: inputs after a round ended or for unknown players",
+        path.display()
+    );
+    Outcome {
+        rounds: response
+            .rounds
+            .iter()
+            .map(|r| RoundOutcome {
+                ticks: r.ticks,
+                completed: r.completed,
+                scores: scores(&r.scores),
+            })
+            .collect(),
+        final_scores: scores(&response.final_scores),
+    }
+}
+
+fn golden_replays() -> Vec<(PathBuf, GoldenReplay)> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden");
+    let mut files: Vec<PathBuf> = std::fs::read_dir(&dir)
+        .expect("tests/golden should exist")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+    assert!(!files.is_empty(), "No golden replays found");
+    files
+        .into_iter()
+        .map(|path| {
+            let replay = serde_json::from_str(&std::fs::read_to_string(&path).unwrap())
+                .unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+            (path, replay)
+        })
+        .collect()
+}
+
+#[test]
+fn golden_replays_reproduce_their_outcomes() {
+    let bless = std::env::var_os(BLESS_VAR).is_some();
+    let mut changed = Vec::new();
+    for (path, mut replay) in golden_replays() {
+        let outcome = simulate(&path, &replay.request);
+        if bless {
+            replay.expected = Some(outcome);
+            let json = serde_json::to_string_pretty(&replay).unwrap();
+            std::fs::write(&path, json + "\n").unwrap();
+        } else if replay.expected.as_ref() != Some(&outcome) {
+            changed.push(format!(
+                "{}:\n  expected {:?}\n  got      {outcome:?}",
+                path.display(),
+                replay.expected
+            ));
+        }
+    }
+    assert!(
+        changed.is_empty(),
+        "Replays no longer reproduce their recorded outcomes. If the change \
+         is intentional, rerun with {BLESS_VAR}=1 to update them.\n{}",
+        changed.join("\n")
+    );
+}
+
+#[test]
+fn golden_replays_are_deterministic() {
+    for (path, replay) in golden_replays() {
+        assert_eq!(
+            simulate(&path, &replay.request),
+            simulate(&path, &replay.request),
+            "{} plays out differently each run",
+            path.display()
+        );
+    }
+}
@@ -0,0 +1,230 @@
+#[allow(dead_code)]
+mod common;
+
+use std::time::Duration;
+
+use breakpoint_core::net::messages::{
+    ClientMessage, FriendInviteMsg, InviteFriendMsg, JoinRoomMsg, ServerMessage, SetFriendMsg,
+    SetPresenceSharingMsg, SocialUpdateMsg,
+};
+use breakpoint_core::net::protocol::decode_server_message;
+use breakpoint_core::player::PlayerColor;
+use common::{
+    TestServer, WsStream, ws_connect, ws_read_raw, ws_request_game_start, ws_send_client_msg,
+};
+
+const ALICE_KEY: &str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+const BOB_KEY: &str = "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+
+/// Join (or create, with an empty code) a room with an identity key.
+/// Returns the room code.
+async fn join_with_identity(
+    stream: &mut WsStream,
+    room_code: &str,
+    name: &str,
+    key: &str,
+) -> String {
+    let msg = ClientMessage::JoinRoom(JoinRoomMsg {
+        room_code: room_code.to_string(),
+        player_name: name.to_string(),
+        player_color: PlayerColor::default(),
+        protocol_version: breakpoint_core::net::protocol::PROTOCOL_VERSION,
+        session_token: None,
+        identity_key: Some(key.to_string()),
+        accepts_batches: false,
+        room_alias: None,
+        bot_token: None,
+        tutorial: None,
+    });
+    ws_send_client_msg(stream, &msg).await;
+    match read_until(stream, |m| matches!(m, ServerMessage::JoinRoomResponse(_))).await {
+        ServerMessage::JoinRoomResponse(resp) => {
+            assert!(resp.success, "join failed: {resp:?}");
+            resp.room_code.unwrap()
+        },
+        _ => unreachable!(),
+    }
+}
+
+/// Read messages until one matches, skipping the rest.
+async fn read_until(stream: &mut WsStream, pred: impl Fn(&ServerMessage) -> bool) -> ServerMessage {
+    loop {
+        let data = ws_read_raw(stream).await;
+        // Fast-format game state frames are not MessagePack; skip them too
+        if let Ok(msg) = decode_server_message(&data)
+            && pred(&msg)
+        {
+            return msg;
+        }
+    }
+}
+
+async fn next_social_update(stream: &mut WsStream) -> SocialUpdateMsg {
+    match read_until(stream, |m| matches!(m, ServerMessage::SocialUpdate(_))).await {
+        ServerMessage::SocialUpdate(update) => update,
+        _ => unreachable!(),
+    }
+}
+
+async fn next_invite(stream: &mut WsStream) -> FriendInviteMsg {
+    match read_until(stream, |m| matches!(m, ServerMessage::FriendInvite(_))).await {
+        ServerMessage::FriendInvite(invite) => invite,
+        _ => unreachable!(),
+    }
+}
+
+#[tokio::test]
+async fn co_players_friend_and_invite_live() {
+    let server = TestServer::new().await;
+    let mut alice = ws_connect(&server.ws_url()).await;
+    let room = join_with_identity(&mut alice, "", "Alice", ALICE_KEY).await;
+    let alice_id = next_social_update(&mut alice).await.your_id;
+
+    let mut bob = ws_connect(&server.ws_url()).await;
+    join_with_identity(&mut bob, &room, "Bob", BOB_KEY).await;
+    let bob_id = next_social_update(&mut bob).await.your_id;
+    assert_ne!(alice_id, bob_id);
+
+    // Starting a game records both as recent co-players
+    ws_request_game_start(&mut alice, "mini-golf").await;
+    let update = next_social_update(&mut alice).await;
+    assert_eq!(update.recent.len(), 1);
+    assert_eq!(update.recent[0].id, bob_id);
+    assert_eq!(update.recent[0].name, "Bob");
+    assert!(update.recent[0].online);
+    let update = next_social_update(&mut bob).await;
+    assert_eq!(update.recent[0].id, alice_id);
+
+    // Alice adds Bob as a friend and invites him
+    ws_send_client_msg(
+        &mut alice,
+        &ClientMessage::SetFriend(SetFriendMsg {
+            friend_id: bob_id.clone(),
+            is_friend: true,
+        }),
+    )
+    .await;
+    let update = next_social_update(&mut alice).await;
+    assert_eq!(update.friends.len(), 1);
+    assert_eq!(update.friends[0].id, bob_id);
+
+    ws_send_client_msg(
+        &mut alice,
+        &ClientMessage::InviteFriend(InviteFriendMsg {
+            friend_id: bob_id.clone(),
+        }),
+    )
+    .await;
+    let invite = next_invite(&mut bob).await;
+    assert_eq!(invite.from_id, alice_id);
+    assert_eq!(invite.from_name, "Alice");
+    assert_eq!(invite.room_code, room);
+}
+
+#[tokio::test]
+async fn offline_friend_receives_invite_on_next_connect() {
+    let server = TestServer::new().await;
+    let mut alice = ws_connect(&server.ws_url()).await;
+    let room = join_with_identity(&mut alice, "", "Alice", ALICE_KEY).await;
+    next_social_update(&mut alice).await;
+
+    let mut bob = ws_connect(&server.ws_url()).await;
+    join_with_identity(&mut bob, &room, "Bob", BOB_KEY).await;
+    let bob_id = next_social_update(&mut bob).await.your_id;
+
+    ws_send_client_msg(
+        &mut alice,
+        &ClientMessage::SetFriend(SetFriendMsg {
+            friend_id: bob_id.clone(),
+            is_friend: true,
+        }),
+    )
+    .await;
+    let update = next_social_update(&mut alice).await;
+    assert!(update.friends[0].online);
+
+    // Bob goes offline; Alice's list reflects it
+    drop(bob);
+    let update = next_social_update(&mut alice).await;
+    assert!(!update.friends[0].online);
+
+    ws_send_client_msg(
+        &mut alice,
+        &ClientMessage::InviteFriend(InviteFriendMsg { friend_id: bob_id }),
+    )
+    .await;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    // Bob comes back in his own room and finds the invite waiting
+    let mut bob = ws_connect(&server.ws_url()).await;
+    join_with_identity(&mut bob, "", "Bob", BOB_KEY).await;
+    let invite = next_invite(&mut bob).await;
+    assert_eq!(invite.room_code, room);
+    assert_eq!(invite.from_name, "Alice");
+}
+
+#[tokio::test]
+async fn shared_presence_lets_friends_follow_into_a_room() {
+    let server = TestServer::new().await;
+    let mut alice = ws_connect(&server.ws_url()).await;
+    join_with_identity(&mut alice, "", "Alice", ALICE_KEY).await;
+    let alice_id = next_social_update(&mut alice).await.your_id;
+
+    let mut bob = ws_connect(&server.ws_url()).await;
+    let bob_room = join_with_identity(&mut bob, "", "Bob", BOB_KEY).await;
+    let bob_id = next_social_update(&mut bob).await.your_id;
+
+    let set_friend = |friend_id: &str| {
+        ClientMessage::SetFriend(SetFriendMsg {
+            friend_id: friend_id.to_string(),
+            is_friend: true,
+        })
+    };
+    ws_send_client_msg(&mut alice, &set_friend(&bob_id)).await;
+    let update = next_social_update(&mut alice).await;
+    assert!(update.friends[0].online);
+    assert_eq!(update.friends[0].room_code, None);
+    // Bob's list is refreshed too, in case Alice shares her room with him
+    assert!(!next_social_update(&mut bob).await.sharing_presence);
+
+    // Bob opts in, but Alice isn't on his friends list yet
+    ws_send_client_msg(
+        &mut bob,
+        &ClientMessage::SetPresenceSharing(SetPresenceSharingMsg { share: true }),
+    )
+    .await;
+    assert!(next_social_update(&mut bob).await.sharing_presence);
+    assert_eq!(
+        next_social_update(&mut alice).await.friends[0].room_code,
+        None
+    );
+
+    ws_send_client_msg(&mut bob, &set_friend(&alice_id)).await;
+    next_social_update(&mut bob).await;
+    let update = next_social_update(&mut alice).await;
+    assert_eq!(
+        update.friends[0].room_code.as_deref(),
+        Some(bob_room.as_str())
+    );
+    assert_eq!(update.friends[0].game, None, "Bob is in his lobby");
+
+    // Alice's list follows Bob into a game
+    ws_request_game_start(&mut bob, "mini-golf").await;
+    let update = next_social_update(&mut alice).await;
+    assert_eq!(update.friends[0].game.as_deref(), Some("mini-golf"));
+
+    // And she can follow him into it
+    let mut follower = ws_connect(&server.ws_url()).await;
+    join_with_identity(&mut follower, &bob_room, "Alice", ALICE_KEY).await;
+}
+
+#[tokio::test]
+async fn players_without_identity_get_no_social_messages() {
+    let server = TestServer::new().await;
+    let mut alice = ws_connect(&server.ws_url()).await;
+    let (_, _room) = common::ws_create_room(&mut alice, "Alice").await;
+    // Only the player list follows the join response
+    let msg = common::ws_read_server_msg(&mut alice).await;
+    assert!(matches!(msg, ServerMessage::PlayerList(_)));
+    assert!(common::ws_try_read_raw(&mut alice, 100).await.is_none());
+}
@@ -5,13 +5,15 @@
 #[allow(dead_code)]
 mod common;
 
-use breakpoint_core::net::messages::{ClientMessage, JoinRoomMsg, ServerMessage};
+use breakpoint_core::net::messages::{
+    ClientMessage, JoinRoomMsg, MigrateTransportMsg, PlayerInputMsg, ServerMessage,
+};
 use breakpoint_core::net::protocol::{decode_server_message, encode_client_message};
 use breakpoint_core::player::PlayerColor;
 use common::{
     TestServer, ws_connect, ws_join_room, ws_read_raw, ws_read_server_msg, ws_request_game_start,
 };
-use futures::SinkExt;
+use futures::{SinkExt, StreamExt};
 use tokio_tungstenite::tungstenite::Message;
 
 /// Helper: create a 2-player room, returning (leader, client, leader_id, client_id, room_code).
@@ -188,6 +190,11 @@ async fn session_reconnect_during_game() {
         player_color: PlayerColor::PALETTE[1],
         protocol_version: breakpoint_core::net::protocol::PROTOCOL_VERSION,
         session_token: None,
+        identity_key: None,
+        accepts_batches: false,
+        room_alias: None,
+        bot_token: None,
+        tutorial: None,
     });
     let encoded = encode_client_message(&join_msg).unwrap();
     client.send(Message::Binary(encoded.into())).await.unwrap();
@@ -230,6 +237,11 @@ async fn session_reconnect_during_game() {
         player_color: PlayerColor::PALETTE[1],
         protocol_version: breakpoint_core::net::protocol::PROTOCOL_VERSION,
         session_token: Some(token),
+        identity_key: None,
+        accepts_batches: false,
+        room_alias: None,
+        bot_token: None,
+        tutorial: None,
     });
     let encoded = encode_client_message(&reconnect_msg).unwrap();
     client2.send(Message::Binary(encoded.into())).await.unwrap();
@@ -290,6 +302,11 @@ async fn invalid_session_token_rejected() {
         player_color: PlayerColor::PALETTE[1],
         protocol_version: breakpoint_core::net::protocol::PROTOCOL_VERSION,
         session_token: Some("bogus-token-12345".to_string()),
+        identity_key: None,
+        accepts_batches: false,
+        room_alias: None,
+        bot_token: None,
+        tutorial: None,
     });
     let encoded = encode_client_message(&reconnect_msg).unwrap();
     client2.send(Message::Binary(encoded.into())).await.unwrap();
@@ -309,3 +326,83 @@ async fn invalid_session_token_rejected() {
         other => panic!("Expected JoinRoomResponse, got {other:?}"),
     }
 }
+
+/// A live session moves onto a new connection without rejoining: same
+/// player, input sequence carried over, old connection closed, and the old
+/// token retired.
+#[tokio::test]
+async fn transport_migration_keeps_the_session() {
+    let server = TestServer::new().await;
+    let mut leader = ws_connect(&server.ws_url()).await;
+    let (_, room_code) = common::ws_create_room(&mut leader, "Leader").await;
+    let _ = ws_read_server_msg(&mut leader).await; // PlayerList
+
+    let mut client = ws_connect(&server.ws_url()).await;
+    let join = ws_join_room(&mut client, &room_code, "Client").await;
+    let client_id = join.player_id.unwrap();
+    let token = join.session_token.unwrap();
+    let _ = ws_read_server_msg(&mut client).await; // PlayerList
+    let _ = ws_read_server_msg(&mut leader).await; // PlayerList
+
+    ws_request_game_start(&mut leader, "mini-golf").await;
+    let _ = ws_read_server_msg(&mut client).await; // GameStart
+    let input = ClientMessage::PlayerInput(PlayerInputMsg {
+        player_id: client_id,
+        tick: 7,
+        input_data: Vec::new(),
+    });
+    common::ws_send_client_msg(&mut client, &input).await;
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+    let migrate = |session_token: String| {
+        ClientMessage::MigrateTransport(MigrateTransportMsg {
+            session_token,
+            protocol_version: breakpoint_core::net::protocol::PROTOCOL_VERSION,
+            accepts_batches: false,
+        })
+    };
+    let mut upgraded = ws_connect(&server.ws_url()).await;
+    common::ws_send_client_msg(&mut upgraded, &migrate(token.clone())).await;
+    let migrated = match ws_read_server_msg(&mut upgraded).await {
+        ServerMessage::TransportMigrated(m) => m,
+        other => panic!("Expected TransportMigrated, got {other:?}"),
+    };
+    assert!(migrated.success, "Migration should succeed: {migrated:?}");
+    assert_eq!(migrated.player_id, Some(client_id));
+    assert_eq!(migrated.room_code.as_deref(), Some(room_code.as_str()));
+    assert_eq!(migrated.last_input_tick, Some(7));
+    assert_ne!(migrated.session_token.as_deref(), Some(token.as_str()));
+
+    // The server hangs up the old connection
+    let closed = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        loop {
+            match client.next().await {
+                Some(Ok(Message::Close(_)) | Err(_)) | None => return,
+                Some(Ok(_)) => {},
+            }
+        }
+    })
+    .await;
+    assert!(closed.is_ok(), "Old connection should be closed");
+
+    // Game state keeps flowing on the new one after the old one is gone,
+    // so the player never left
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+    while common::ws_try_read_raw(&mut upgraded, 50).await.is_some() {}
+    let mut got_state = false;
+    for _ in 0..20 {
+        if let ServerMessage::GameState(_) = ws_read_server_msg(&mut upgraded).await {
+            got_state = true;
+            break;
+        }
+    }
+    assert!(got_state, "Migrated connection should receive GameState");
+
+    // The old token is spent
+    let mut stale = ws_connect(&server.ws_url()).await;
+    common::ws_send_client_msg(&mut stale, &migrate(token)).await;
+    match ws_read_server_msg(&mut stale).await {
+        ServerMessage::TransportMigrated(m) => assert!(!m.success && m.error.is_some()),
+        other => panic!("Expected TransportMigrated, got {other:?}"),
+    }
+}
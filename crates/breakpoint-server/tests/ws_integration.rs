@@ -1,9 +1,11 @@
 #[allow(dead_code)]
 mod common;
 
+use breakpoint_core::i18n::TextKey;
 use breakpoint_core::net::messages::{
-    ChatMessageMsg, ClientMessage, GameEndMsg, GameStateMsg, JoinRoomMsg, PlayerInputMsg,
-    RoundEndMsg, ServerMessage,
+    AddBotMsg, ChatMessageMsg, ClientMessage, GameEndMsg, GameStateMsg, JoinQueueAction,
+    JoinRoomMsg, ManageJoinQueueMsg, PlayerInputMsg, RemoveBotMsg, RerollNameMsg, RoundEndMsg,
+    ServerMessage,
 };
 use breakpoint_core::net::protocol::{decode_client_message, encode_client_message};
 use breakpoint_core::player::PlayerColor;
@@ -222,18 +224,51 @@ async fn leader_migration_on_disconnect() {
 }
 
 #[tokio::test]
-async fn join_with_empty_name_rejected() {
+async fn join_with_empty_name_gets_generated_name() {
     let server = TestServer::new().await;
-    let mut stream = ws_connect(&server.ws_url()).await;
-
-    let resp = ws_join_room_with_name(&mut stream, "").await;
-    assert!(!resp.success);
+    let mut host = ws_connect(&server.ws_url()).await;
+    let (_, code) = common::ws_create_room(&mut host, "").await;
+    let ServerMessage::PlayerList(pl) = ws_read_server_msg(&mut host).await else {
+        panic!("Expected PlayerList");
+    };
+    let host_name = pl.players[0].display_name.clone();
     assert!(
-        resp.error
-            .as_deref()
-            .unwrap()
-            .contains("Invalid player name")
+        host_name.contains(' '),
+        "Expected adjective and animal: {host_name}"
     );
+
+    let mut guest = ws_connect(&server.ws_url()).await;
+    let join = ws_join_room(&mut guest, &code, "").await;
+    assert!(join.success);
+    let guest_id = join.player_id.unwrap();
+    let ServerMessage::PlayerList(pl) = ws_read_server_msg(&mut host).await else {
+        panic!("Expected PlayerList");
+    };
+    assert_ne!(
+        pl.players[1].display_name, host_name,
+        "Names are unique per room"
+    );
+    let _ = ws_read_server_msg(&mut guest).await; // PlayerList
+
+    // Players can only re-roll their own names
+    ws_send_client_msg(
+        &mut guest,
+        &ClientMessage::RerollName(RerollNameMsg { player_id: 1 }),
+    )
+    .await;
+    ws_send_client_msg(
+        &mut guest,
+        &ClientMessage::RerollName(RerollNameMsg {
+            player_id: guest_id,
+        }),
+    )
+    .await;
+    let ServerMessage::PlayerList(rerolled) = ws_read_server_msg(&mut host).await else {
+        panic!("Expected PlayerList");
+    };
+    assert_eq!(rerolled.players[0].display_name, host_name);
+    assert_ne!(rerolled.players[1].display_name, pl.players[1].display_name);
+    assert_ne!(rerolled.players[1].display_name, host_name);
 }
 
 #[tokio::test]
@@ -244,12 +279,7 @@ async fn join_with_long_name_rejected() {
     let long_name = "A".repeat(33);
     let resp = ws_join_room_with_name(&mut stream, &long_name).await;
     assert!(!resp.success);
-    assert!(
-        resp.error
-            .as_deref()
-            .unwrap()
-            .contains("Invalid player name")
-    );
+    assert_eq!(resp.error.map(|e| e.key), Some(TextKey::InvalidPlayerName));
 }
 
 // ============================================================================
@@ -320,6 +350,10 @@ async fn server_game_lifecycle() {
         aim_angle: 0.5,
         power: 0.6,
         stroke: true,
+        skip_intro: false,
+        undo: false,
+        caddie: false,
+        screen_aim: None,
     };
     let input_data = rmp_serde::to_vec(&golf_input).unwrap();
     let input = ClientMessage::PlayerInput(PlayerInputMsg {
@@ -375,6 +409,7 @@ async fn server_only_messages_rejected_from_clients() {
     // GameState from client should be silently rejected
     let gs = ServerMessage::GameState(GameStateMsg {
         tick: 999,
+        server_time_ms: 0,
         state_data: vec![0xFF],
     });
     ws_send_server_msg(&mut leader, &gs).await;
@@ -389,6 +424,8 @@ async fn server_only_messages_rejected_from_clients() {
         round: 1,
         scores: vec![],
         between_round_secs: 0,
+        heatmap: None,
+        victory: None,
     });
     ws_send_server_msg(&mut client, &re).await;
     let maybe = ws_try_read_raw(&mut leader, 500).await;
@@ -400,6 +437,8 @@ async fn server_only_messages_rejected_from_clients() {
     // GameEnd from client should be rejected
     let ge = ServerMessage::GameEnd(GameEndMsg {
         final_scores: vec![],
+        match_id: None,
+        victory: None,
     });
     ws_send_server_msg(&mut client, &ge).await;
     let maybe = ws_try_read_raw(&mut leader, 500).await;
@@ -442,6 +481,10 @@ async fn player_input_with_real_golf_data() {
         aim_angle: 1.57,
         power: 0.8,
         stroke: true,
+        skip_intro: false,
+        undo: false,
+        caddie: false,
+        screen_aim: None,
     };
     let input_data = rmp_serde::to_vec(&golf_input).unwrap();
     let input_msg = ClientMessage::PlayerInput(PlayerInputMsg {
@@ -472,20 +515,6 @@ async fn player_input_with_real_golf_data() {
 // Error path tests
 // ============================================================================
 
-#[tokio::test]
-async fn join_empty_player_name_rejected() {
-    let server = TestServer::new().await;
-    let mut stream = ws_connect(&server.ws_url()).await;
-
-    let resp = ws_join_room_with_name(&mut stream, "").await;
-    assert!(!resp.success);
-    let err = resp.error.as_deref().expect("Should have error message");
-    assert!(
-        err.contains("Invalid player name"),
-        "Expected 'Invalid player name' error, got: {err}"
-    );
-}
-
 #[tokio::test]
 async fn join_control_chars_in_name_rejected() {
     let server = TestServer::new().await;
@@ -494,9 +523,9 @@ async fn join_control_chars_in_name_rejected() {
     let mut stream1 = ws_connect(&server.ws_url()).await;
     let resp = ws_join_room_with_name(&mut stream1, "Alice\nBob").await;
     assert!(!resp.success);
-    let err = resp.error.as_deref().expect("Should have error message");
+    let err = resp.error.as_ref().expect("Should have error message");
     assert!(
-        err.contains("Invalid player name"),
+        err.key == TextKey::InvalidPlayerName,
         "Newline in name should be rejected, got: {err}"
     );
 
@@ -504,9 +533,9 @@ async fn join_control_chars_in_name_rejected() {
     let mut stream2 = ws_connect(&server.ws_url()).await;
     let resp = ws_join_room_with_name(&mut stream2, "Alice\0Bob").await;
     assert!(!resp.success);
-    let err = resp.error.as_deref().expect("Should have error message");
+    let err = resp.error.as_ref().expect("Should have error message");
     assert!(
-        err.contains("Invalid player name"),
+        err.key == TextKey::InvalidPlayerName,
         "Null byte in name should be rejected, got: {err}"
     );
 
@@ -514,9 +543,9 @@ async fn join_control_chars_in_name_rejected() {
     let mut stream3 = ws_connect(&server.ws_url()).await;
     let resp = ws_join_room_with_name(&mut stream3, "Alice\tBob").await;
     assert!(!resp.success);
-    let err = resp.error.as_deref().expect("Should have error message");
+    let err = resp.error.as_ref().expect("Should have error message");
     assert!(
-        err.contains("Invalid player name"),
+        err.key == TextKey::InvalidPlayerName,
         "Tab in name should be rejected, got: {err}"
     );
 }
@@ -530,9 +559,9 @@ async fn join_too_long_name_rejected() {
     let long_name = "A".repeat(33);
     let resp = ws_join_room_with_name(&mut stream, &long_name).await;
     assert!(!resp.success);
-    let err = resp.error.as_deref().expect("Should have error message");
+    let err = resp.error.as_ref().expect("Should have error message");
     assert!(
-        err.contains("Invalid player name"),
+        err.key == TextKey::InvalidPlayerName,
         "Name > 32 chars should be rejected, got: {err}"
     );
 
@@ -581,6 +610,10 @@ async fn oversized_message_dropped() {
         aim_angle: 0.5,
         power: 0.6,
         stroke: true,
+        skip_intro: false,
+        undo: false,
+        caddie: false,
+        screen_aim: None,
     };
     let input_data = rmp_serde::to_vec(&golf_input).unwrap();
     let normal_input = ClientMessage::PlayerInput(PlayerInputMsg {
@@ -614,6 +647,11 @@ async fn protocol_version_mismatch_rejected() {
         player_color: PlayerColor::default(),
         protocol_version: 99,
         session_token: None,
+        identity_key: None,
+        accepts_batches: false,
+        room_alias: None,
+        bot_token: None,
+        tutorial: None,
     });
     let encoded = encode_client_message(&msg).unwrap();
     stream.send(Message::Binary(encoded.into())).await.unwrap();
@@ -622,9 +660,9 @@ async fn protocol_version_mismatch_rejected() {
     match resp {
         ServerMessage::JoinRoomResponse(join) => {
             assert!(!join.success, "Mismatched protocol version should fail");
-            let err = join.error.as_deref().expect("Should have error message");
+            let err = join.error.as_ref().expect("Should have error message");
             assert!(
-                err.contains("version mismatch") || err.contains("Protocol version mismatch"),
+                err.key == TextKey::ProtocolMismatch,
                 "Error should mention version mismatch, got: {err}"
             );
         },
@@ -632,6 +670,203 @@ async fn protocol_version_mismatch_rejected() {
     }
 }
 
+#[tokio::test]
+async fn bot_tokens_gate_external_bot_joins() {
+    use breakpoint_server::config::{BotApiConfig, ServerConfig};
+
+    let server = TestServer::from_config(ServerConfig {
+        bot_api: BotApiConfig {
+            tokens: vec!["tourney-7".to_string()],
+        },
+        ..ServerConfig::default()
+    })
+    .await;
+    let mut host = ws_connect(&server.ws_url()).await;
+    let (_, code) = common::ws_create_room(&mut host, "Alice").await;
+    let _ = ws_read_server_msg(&mut host).await; // PlayerList
+
+    let bot_join = |token: &str| {
+        ClientMessage::JoinRoom(JoinRoomMsg {
+            room_code: code.clone(),
+            player_name: "PuttBot".to_string(),
+            player_color: PlayerColor::default(),
+            protocol_version: breakpoint_core::net::protocol::PROTOCOL_VERSION,
+            session_token: None,
+            identity_key: None,
+            accepts_batches: false,
+            room_alias: None,
+            bot_token: Some(token.to_string()),
+            tutorial: None,
+        })
+    };
+
+    let mut impostor = ws_connect(&server.ws_url()).await;
+    ws_send_client_msg(&mut impostor, &bot_join("guess")).await;
+    let ServerMessage::JoinRoomResponse(refused) = ws_read_server_msg(&mut impostor).await else {
+        panic!("Expected JoinRoomResponse");
+    };
+    assert!(!refused.success);
+    assert_eq!(refused.error.unwrap().key, TextKey::BotTokenRejected);
+
+    let mut bot = ws_connect(&server.ws_url()).await;
+    ws_send_client_msg(&mut bot, &bot_join("tourney-7")).await;
+    let ServerMessage::JoinRoomResponse(joined) = ws_read_server_msg(&mut bot).await else {
+        panic!("Expected JoinRoomResponse");
+    };
+    assert!(joined.success, "{joined:?}");
+    let ServerMessage::PlayerList(pl) = ws_read_server_msg(&mut host).await else {
+        panic!("Expected PlayerList");
+    };
+    let flags: Vec<_> = pl
+        .players
+        .iter()
+        .map(|p| (p.display_name.as_str(), p.external_bot))
+        .collect();
+    assert_eq!(flags, [("Alice", false), ("PuttBot", true)]);
+}
+
+#[tokio::test]
+async fn players_join_by_vanity_alias() {
+    let server = TestServer::new().await;
+    let mut host = ws_connect(&server.ws_url()).await;
+    let msg = ClientMessage::JoinRoom(JoinRoomMsg {
+        room_code: String::new(),
+        player_name: "Alice".to_string(),
+        player_color: PlayerColor::default(),
+        protocol_version: breakpoint_core::net::protocol::PROTOCOL_VERSION,
+        session_token: None,
+        identity_key: None,
+        accepts_batches: false,
+        room_alias: Some("Friday-Golf".to_string()),
+        bot_token: None,
+        tutorial: None,
+    });
+    ws_send_client_msg(&mut host, &msg).await;
+    let ServerMessage::JoinRoomResponse(created) = ws_read_server_msg(&mut host).await else {
+        panic!("Expected JoinRoomResponse");
+    };
+    let code = created.room_code.expect("Room should open");
+
+    let mut guest = ws_connect(&server.ws_url()).await;
+    let join = ws_join_room(&mut guest, "friday-golf", "Bob").await;
+    assert!(join.success, "{join:?}");
+    assert_eq!(join.room_code.as_deref(), Some(code.as_str()));
+
+    // A second room can't claim the same alias
+    let mut rival = ws_connect(&server.ws_url()).await;
+    ws_send_client_msg(&mut rival, &msg).await;
+    let ServerMessage::JoinRoomResponse(refused) = ws_read_server_msg(&mut rival).await else {
+        panic!("Expected JoinRoomResponse");
+    };
+    assert!(!refused.success);
+    assert_eq!(refused.error.unwrap().key, TextKey::RoomAliasTaken);
+}
+
+#[tokio::test]
+async fn tutorial_join_starts_a_solo_game_with_a_checklist() {
+    let server = TestServer::new().await;
+    let mut player = ws_connect(&server.ws_url()).await;
+    let msg = ClientMessage::JoinRoom(JoinRoomMsg {
+        room_code: String::new(),
+        player_name: "Newbie".to_string(),
+        player_color: PlayerColor::default(),
+        protocol_version: breakpoint_core::net::protocol::PROTOCOL_VERSION,
+        session_token: None,
+        identity_key: Some("tutorial-test-identity-key".to_string()),
+        accepts_batches: false,
+        room_alias: None,
+        bot_token: None,
+        tutorial: Some("laser-tag".to_string()),
+    });
+    ws_send_client_msg(&mut player, &msg).await;
+    let ServerMessage::JoinRoomResponse(created) = ws_read_server_msg(&mut player).await else {
+        panic!("Expected JoinRoomResponse");
+    };
+    let code = created.room_code.expect("Room should open");
+
+    // Every game's checklist for the lobby, then the tutorial itself
+    let ServerMessage::TutorialProgress(lobby) = read_until(&mut player, |m| {
+        matches!(m, ServerMessage::TutorialProgress(_))
+    })
+    .await
+    else {
+        unreachable!()
+    };
+    assert!(lobby.checklists.len() >= 2, "{lobby:?}");
+    let ServerMessage::GameStart(start) =
+        read_until(&mut player, |m| matches!(m, ServerMessage::GameStart(_))).await
+    else {
+        unreachable!()
+    };
+    assert_eq!(start.game_name, "laser-tag");
+    assert!(
+        start.players.iter().any(|p| p.is_bot),
+        "Bots fill the arena"
+    );
+    let ServerMessage::TutorialProgress(session) = read_until(&mut player, |m| {
+        matches!(m, ServerMessage::TutorialProgress(_))
+    })
+    .await
+    else {
+        unreachable!()
+    };
+    assert_eq!(session.checklists.len(), 1);
+    assert_eq!(session.checklists[0].game, "laser-tag");
+
+    // The room is full, so anyone else waits in its queue
+    let mut other = ws_connect(&server.ws_url()).await;
+    ws_send_client_msg(&mut other, &join_msg(&code, "Bob")).await;
+    let reply = ws_read_server_msg(&mut other).await;
+    assert!(matches!(reply, ServerMessage::JoinQueued(_)), "{reply:?}");
+}
+
+#[tokio::test]
+async fn queued_messages_batched_for_clients_that_accept_batches() {
+    use breakpoint_core::net::messages::MessageType;
+    use breakpoint_core::net::protocol::{decode_server_message, split_batch};
+    use breakpoint_server::config::{AnnouncementsConfig, ServerConfig};
+
+    let server = TestServer::from_config(ServerConfig {
+        announcements: AnnouncementsConfig {
+            motd: Some("Welcome".to_string()),
+            ..AnnouncementsConfig::default()
+        },
+        ..ServerConfig::default()
+    })
+    .await;
+    let mut stream = ws_connect(&server.ws_url()).await;
+    let msg = ClientMessage::JoinRoom(JoinRoomMsg {
+        room_code: String::new(),
+        player_name: "Alice".to_string(),
+        player_color: PlayerColor::default(),
+        protocol_version: breakpoint_core::net::protocol::PROTOCOL_VERSION,
+        session_token: None,
+        identity_key: None,
+        accepts_batches: true,
+        room_alias: None,
+        bot_token: None,
+        tutorial: None,
+    });
+    ws_send_client_msg(&mut stream, &msg).await;
+    let resp = ws_read_server_msg(&mut stream).await;
+    assert!(matches!(resp, ServerMessage::JoinRoomResponse(ref j) if j.success));
+
+    // The player list and MOTD are queued before the writer starts, so
+    // they arrive as one frame
+    let data = ws_read_raw(&mut stream).await;
+    assert_eq!(data[0], MessageType::Batch as u8);
+    let frames = split_batch(&data).unwrap();
+    assert_eq!(frames.len(), 2);
+    assert!(matches!(
+        decode_server_message(frames[0]).unwrap(),
+        ServerMessage::PlayerList(_)
+    ));
+    assert!(matches!(
+        decode_server_message(frames[1]).unwrap(),
+        ServerMessage::Announcements(_)
+    ));
+}
+
 // ============================================================================
 // Additional error path integration tests (Phase 3)
 // ============================================================================
@@ -724,9 +959,9 @@ async fn invalid_room_code_format_rejected() {
     // Try joining with invalid room code format
     let resp = ws_join_room_expect_error(&mut stream, "not-a-valid-code!!!", "Alice").await;
     assert!(!resp.success);
-    let err = resp.error.as_deref().unwrap();
+    let err = resp.error.as_ref().unwrap();
     assert!(
-        err.contains("Invalid room code") || err.contains("Room not found"),
+        matches!(err.key, TextKey::InvalidRoomCode | TextKey::RoomNotFound),
         "Invalid room code format should be rejected, got: {err}"
     );
 }
@@ -755,6 +990,10 @@ async fn spoofed_player_input_has_no_effect() {
         aim_angle: 0.0,
         power: 1.0,
         stroke: true,
+        skip_intro: false,
+        undo: false,
+        caddie: false,
+        screen_aim: None,
     };
     let input_data = rmp_serde::to_vec(&golf_input).unwrap();
     let spoofed = ClientMessage::PlayerInput(PlayerInputMsg {
@@ -824,15 +1063,288 @@ async fn non_join_first_message_disconnects() {
 }
 
 #[tokio::test]
-async fn whitespace_only_name_rejected() {
+async fn whitespace_only_name_gets_generated_name() {
     let server = TestServer::new().await;
     let mut stream = ws_connect(&server.ws_url()).await;
 
     let resp = ws_join_room_with_name(&mut stream, "   ").await;
-    assert!(!resp.success);
-    let err = resp.error.as_deref().unwrap();
-    assert!(
-        err.contains("Invalid player name"),
-        "Whitespace-only name should be rejected, got: {err}"
-    );
+    assert!(resp.success, "{resp:?}");
+    let ServerMessage::PlayerList(pl) = ws_read_server_msg(&mut stream).await else {
+        panic!("Expected PlayerList");
+    };
+    assert!(!pl.players[0].display_name.trim().is_empty());
+}
+
+/// Read server messages until one matches, skipping the rest.
+async fn read_until(
+    stream: &mut common::WsStream,
+    pred: impl Fn(&ServerMessage) -> bool,
+) -> ServerMessage {
+    loop {
+        let msg = ws_read_server_msg(stream).await;
+        if pred(&msg) {
+            return msg;
+        }
+    }
+}
+
+fn join_msg(room_code: &str, name: &str) -> ClientMessage {
+    ClientMessage::JoinRoom(JoinRoomMsg {
+        room_code: room_code.to_string(),
+        player_name: name.to_string(),
+        player_color: PlayerColor::default(),
+        protocol_version: breakpoint_core::net::protocol::PROTOCOL_VERSION,
+        session_token: None,
+        identity_key: None,
+        accepts_batches: false,
+        room_alias: None,
+        bot_token: None,
+        tutorial: None,
+    })
+}
+
+#[tokio::test]
+async fn full_room_queues_joiner_until_slot_opens() {
+    let server = TestServer::new().await;
+    let mut leader = ws_connect(&server.ws_url()).await;
+    let (_, room_code) = common::ws_create_room(&mut leader, "Alice").await;
+    let _ = ws_read_server_msg(&mut leader).await; // PlayerList
+
+    // Fill the room (max 8) with bots
+    let mut bot_id = 0;
+    for _ in 0..7 {
+        ws_send_client_msg(&mut leader, &ClientMessage::AddBot(AddBotMsg::default())).await;
+        if let ServerMessage::PlayerList(pl) = ws_read_server_msg(&mut leader).await {
+            bot_id = pl.players.last().unwrap().id;
+        }
+    }
+
+    let mut joiner = ws_connect(&server.ws_url()).await;
+    ws_send_client_msg(&mut joiner, &join_msg(&room_code, "Dana")).await;
+    match ws_read_server_msg(&mut joiner).await {
+        ServerMessage::JoinQueued(q) => {
+            assert_eq!((q.position, q.queue_len), (1, 1));
+            assert!(!q.awaiting_approval);
+        },
+        other => panic!("Expected JoinQueued, got: {other:?}"),
+    }
+    match read_until(&mut leader, |m| {
+        matches!(m, ServerMessage::JoinQueueUpdate(_))
+    })
+    .await
+    {
+        ServerMessage::JoinQueueUpdate(u) => {
+            assert_eq!(u.entries.len(), 1);
+            assert_eq!(u.entries[0].display_name, "Dana");
+        },
+        _ => unreachable!(),
+    }
+
+    // Removing a bot opens a slot and admits the joiner
+    let remove = ClientMessage::RemoveBot(RemoveBotMsg { player_id: bot_id });
+    ws_send_client_msg(&mut leader, &remove).await;
+    match read_until(&mut joiner, |m| {
+        matches!(m, ServerMessage::JoinRoomResponse(_))
+    })
+    .await
+    {
+        ServerMessage::JoinRoomResponse(resp) => {
+            assert!(resp.success);
+            assert_eq!(resp.room_code.as_deref(), Some(room_code.as_str()));
+        },
+        _ => unreachable!(),
+    }
+    match read_until(&mut leader, |m| {
+        matches!(m, ServerMessage::PlayerList(pl) if pl.players.iter().any(|p| p.display_name == "Dana"))
+    })
+    .await
+    {
+        ServerMessage::PlayerList(pl) => assert_eq!(pl.players.len(), 8),
+        _ => unreachable!(),
+    }
+}
+
+#[tokio::test]
+async fn room_cap_queues_creators_until_a_room_closes() {
+    let mut config = common::test_config();
+    config.rooms.max_rooms = 1;
+    let server = TestServer::from_config(config).await;
+    let ready_url = format!("{}/health/ready", server.base_url());
+
+    let resp = reqwest::get(&ready_url).await.unwrap();
+    assert_eq!(resp.status(), 200);
+
+    let mut alice = ws_connect(&server.ws_url()).await;
+    let _ = common::ws_create_room(&mut alice, "Alice").await;
+
+    // At the cap: readiness reports saturation and new creators wait
+    let resp = reqwest::get(&ready_url).await.unwrap();
+    assert_eq!(resp.status(), 503);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body["status"], "saturated");
+    assert_eq!(body["utilization"]["rooms"]["current"], 1);
+    assert_eq!(body["utilization"]["rooms"]["max"], 1);
+
+    let mut bob = ws_connect(&server.ws_url()).await;
+    ws_send_client_msg(&mut bob, &join_msg("", "Bob")).await;
+    match ws_read_server_msg(&mut bob).await {
+        ServerMessage::JoinQueued(q) => {
+            assert!(q.room_code.is_empty(), "Waiting for a room, not in one");
+            assert_eq!((q.position, q.queue_len), (1, 1));
+        },
+        other => panic!("Expected JoinQueued, got: {other:?}"),
+    }
+
+    // Alice's room closing opens one for Bob
+    alice.close(None).await.unwrap();
+    match read_until(&mut bob, |m| {
+        matches!(m, ServerMessage::JoinRoomResponse(_))
+    })
+    .await
+    {
+        ServerMessage::JoinRoomResponse(resp) => {
+            assert!(resp.success, "{resp:?}");
+            assert!(resp.room_code.is_some());
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[tokio::test]
+async fn leader_can_deny_queued_join() {
+    let server = TestServer::new().await;
+    let mut leader = ws_connect(&server.ws_url()).await;
+    let (_, room_code) = common::ws_create_room(&mut leader, "Alice").await;
+    let _ = ws_read_server_msg(&mut leader).await; // PlayerList
+
+    let approval = ManageJoinQueueMsg {
+        action: JoinQueueAction::RequireApproval(true),
+    };
+    ws_send_client_msg(&mut leader, &ClientMessage::ManageJoinQueue(approval)).await;
+    let _ = read_until(
+        &mut leader,
+        |m| matches!(m, ServerMessage::JoinQueueUpdate(u) if u.require_approval),
+    )
+    .await;
+
+    // The room has space, but joins now wait for the leader
+    let mut joiner = ws_connect(&server.ws_url()).await;
+    ws_send_client_msg(&mut joiner, &join_msg(&room_code, "Dana")).await;
+    match ws_read_server_msg(&mut joiner).await {
+        ServerMessage::JoinQueued(q) => assert!(q.awaiting_approval),
+        other => panic!("Expected JoinQueued, got: {other:?}"),
+    }
+    let ticket = match read_until(
+        &mut leader,
+        |m| matches!(m, ServerMessage::JoinQueueUpdate(u) if !u.entries.is_empty()),
+    )
+    .await
+    {
+        ServerMessage::JoinQueueUpdate(u) => u.entries[0].ticket,
+        _ => unreachable!(),
+    };
+
+    let deny = ManageJoinQueueMsg {
+        action: JoinQueueAction::Deny(ticket),
+    };
+    ws_send_client_msg(&mut leader, &ClientMessage::ManageJoinQueue(deny)).await;
+    match ws_read_server_msg(&mut joiner).await {
+        ServerMessage::JoinRoomResponse(resp) => {
+            assert!(!resp.success);
+            assert_eq!(resp.error.unwrap().key, TextKey::JoinDenied);
+        },
+        other => panic!("Expected JoinRoomResponse, got: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn featured_start_plays_the_weeks_game() {
+    use breakpoint_core::net::messages::RequestGameStartMsg;
+    use breakpoint_server::config::{SeasonConfig, SeasonWeek, ServerConfig};
+
+    let server = TestServer::from_config(ServerConfig {
+        season: SeasonConfig {
+            name: "Spring Cup".to_string(),
+            starts_at: breakpoint_core::time::unix_now() - 60,
+            weeks: vec![SeasonWeek {
+                game: "laser-tag".to_string(),
+                title: "Wide beams".to_string(),
+                modifiers: [("beam_width".to_string(), serde_json::json!(1.5))].into(),
+            }],
+        },
+        ..common::test_config()
+    })
+    .await;
+
+    let season: serde_json::Value =
+        reqwest::get(format!("{}/api/v1/public/season", server.base_url()))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+    assert_eq!(season["featured"]["game"], "laser-tag");
+    assert_eq!(season["featured"]["week"], 1);
+
+    let mut leader = ws_connect(&server.ws_url()).await;
+    let _ = common::ws_create_room(&mut leader, "Alice").await;
+    let _ = ws_read_server_msg(&mut leader).await; // PlayerList
+    let start = ClientMessage::RequestGameStart(RequestGameStartMsg {
+        game_name: "mini-golf".to_string(),
+        custom: std::collections::HashMap::new(),
+        win_condition: None,
+        pacing: None,
+        featured: true,
+    });
+    ws_send_client_msg(&mut leader, &start).await;
+
+    match ws_read_server_msg(&mut leader).await {
+        ServerMessage::GameStart(gs) => assert_eq!(gs.game_name, "laser-tag"),
+        other => panic!("Expected GameStart, got: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn clock_sync_and_snapshots_share_the_server_clock() {
+    use breakpoint_core::net::messages::ClockSyncMsg;
+
+    let server = TestServer::new().await;
+    let mut leader = ws_connect(&server.ws_url()).await;
+    let _ = common::ws_create_room(&mut leader, "Alice").await;
+    let _ = ws_read_server_msg(&mut leader).await; // PlayerList
+
+    let before = breakpoint_core::time::unix_millis();
+    let probe = ClientMessage::ClockSync(ClockSyncMsg {
+        client_time: 1234.5,
+        rtt_ms: Some(40),
+    });
+    ws_send_client_msg(&mut leader, &probe).await;
+    let probed_at = match ws_read_server_msg(&mut leader).await {
+        ServerMessage::ClockSyncReply(reply) => {
+            assert_eq!(reply.client_time, 1234.5, "Echoed untouched");
+            assert!(reply.server_time_ms >= before);
+            reply.server_time_ms
+        },
+        other => panic!("Expected ClockSyncReply, got: {other:?}"),
+    };
+
+    ws_request_game_start(&mut leader, "mini-golf").await;
+    let _ = ws_read_server_msg(&mut leader).await; // GameStart
+    loop {
+        if let ServerMessage::GameState(gs) = ws_read_server_msg(&mut leader).await {
+            assert!(gs.server_time_ms >= probed_at);
+            assert!(gs.server_time_ms <= breakpoint_core::time::unix_millis());
+            break;
+        }
+    }
+
+    // The reported round trip shows up as the player's ping
+    loop {
+        if let ServerMessage::Standings(standings) = ws_read_server_msg(&mut leader).await {
+            assert_eq!(standings.round, 1);
+            assert_eq!(standings.players.len(), 1);
+            assert_eq!(standings.players[0].ping_ms, Some(40));
+            break;
+        }
+    }
 }
@@ -0,0 +1,111 @@
+//! The caddie: an on-demand stroke suggestion for players still learning
+//! the courses. The host simulates a grid of candidate strokes fanned
+//! around the sightline aim and suggests the one that sinks the ball, or
+//! failing that leaves it closest to the cup. Lobbies set how many hints
+//! each player gets per hole; a hint goes only to the player who asked.
+
+use serde::{Deserialize, Serialize};
+
+use crate::course::Course;
+use crate::physics::{BallState, MAX_POWER};
+use crate::sightline::shot_hint;
+
+/// Aim offsets tried either side of the sightline aim, in radians.
+const AIM_OFFSETS: [f32; 7] = [-0.24, -0.16, -0.08, 0.0, 0.08, 0.16, 0.24];
+
+/// Stroke powers tried (fractions of full power, like `GolfInput::power`),
+/// spaced evenly in roll distance from a short putt to a full drive.
+const POWERS: [f32; 8] = [0.06, 0.09, 0.13, 0.18, 0.25, 0.35, 0.5, 0.7];
+
+/// Ticks a simulated stroke may run before it is scored where it lies.
+/// A full-power stroke comes to rest in about 80.
+const MAX_SIM_TICKS: u32 = 150;
+
+/// A suggested stroke, computed on the host.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CaddieHint {
+    /// Suggested aim in radians (same convention as `GolfInput::aim_angle`).
+    pub aim_angle: f32,
+    /// Suggested power, 0.0 to 1.0 like `GolfInput::power`.
+    pub power: f32,
+    /// How far from the cup the stroke should leave the ball; zero when
+    /// it sinks.
+    pub expected_distance: f32,
+}
+
+/// Find the best of the candidate strokes for `ball` on `course`. Among
+/// strokes that sink, the gentlest wins.
+pub fn suggest(course: &Course, ball: &BallState) -> CaddieHint {
+    let aim = shot_hint(course, ball.position).aim_angle;
+    let mut best: Option<CaddieHint> = None;
+    for offset in AIM_OFFSETS {
+        for power in POWERS {
+            let candidate = CaddieHint {
+                aim_angle: aim + offset,
+                power,
+                expected_distance: simulate(course, ball, aim + offset, power),
+            };
+            let better = best.is_none_or(|b| {
+                candidate.expected_distance < b.expected_distance
+                    || (candidate.expected_distance == b.expected_distance && power < b.power)
+            });
+            if better {
+                best = Some(candidate);
+            }
+        }
+    }
+    best.expect("candidate grid is not empty")
+}
+
+/// Where a stroke from `ball` leaves it, as distance to the cup.
+fn simulate(course: &Course, ball: &BallState, aim_angle: f32, power: f32) -> f32 {
+    let mut ball = ball.clone();
+    ball.stroke(aim_angle, power * MAX_POWER);
+    for _ in 0..MAX_SIM_TICKS {
+        ball.tick(course);
+        if ball.is_stopped() {
+            break;
+        }
+    }
+    if ball.is_sunk {
+        0.0
+    } else {
+        ball.distance_to_hole(course)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::course::{Vec3, default_course};
+
+    #[test]
+    fn open_shot_is_holed_gently() {
+        let course = default_course();
+        // Above the L-shaped obstacle, straight below the cup
+        let ball = BallState::new(Vec3::new(10.0, 0.0, 24.0));
+        let hint = suggest(&course, &ball);
+        assert_eq!(hint.expected_distance, 0.0, "{hint:?}");
+
+        let mut played = ball.clone();
+        played.stroke(hint.aim_angle, hint.power * MAX_POWER);
+        for _ in 0..MAX_SIM_TICKS {
+            played.tick(&course);
+        }
+        assert!(played.is_sunk, "The suggestion replays the same way");
+    }
+
+    #[test]
+    fn blind_tee_shot_beats_putting_at_the_wall() {
+        let course = default_course();
+        let ball = BallState::new(course.spawn_point);
+        let hint = suggest(&course, &ball);
+        let to_hole = course.hole_position - course.spawn_point;
+        let straight = simulate(&course, &ball, to_hole.z.atan2(to_hole.x), hint.power);
+        assert!(
+            hint.expected_distance < straight,
+            "{hint:?} vs straight at the cup {straight}"
+        );
+        assert!(hint.expected_distance < ball.distance_to_hole(&course) / 2.0);
+    }
+}
@@ -1,20 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-/// A 3D point used for course geometry.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
-pub struct Vec3 {
-    pub x: f32,
-    pub y: f32,
-    pub z: f32,
-}
-
-impl Vec3 {
-    pub const fn new(x: f32, y: f32, z: f32) -> Self {
-        Self { x, y, z }
-    }
+use crate::physics::BallInteraction;
+use crate::svg_walls;
 
-    pub const ZERO: Self = Self::new(0.0, 0.0, 0.0);
-}
+pub use breakpoint_core::math::Vec3;
 
 /// A wall segment on the course (two endpoints on the XZ plane + height).
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -32,6 +21,23 @@ pub struct Bumper {
     pub bounce_speed: f32,
 }
 
+/// A circular zone that bends the path of a rolling ball: an attractor
+/// when `strength` is positive, a repulsor when negative. The pull is
+/// `strength` (speed per tick) at the center and fades linearly to nothing
+/// at `radius`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GravityWell {
+    pub position: Vec3,
+    pub radius: f32,
+    pub strength: f32,
+}
+
+impl GravityWell {
+    pub fn attracts(&self) -> bool {
+        self.strength > 0.0
+    }
+}
+
 /// A mini-golf course definition.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Course {
@@ -43,6 +49,16 @@ pub struct Course {
     pub hole_position: Vec3,
     pub walls: Vec<Wall>,
     pub bumpers: Vec<Bumper>,
+    /// Played in the dark: players only see what the balls' lanterns light.
+    #[serde(default)]
+    pub night: bool,
+    /// How balls treat each other on this course, unless the lobby says
+    /// otherwise.
+    #[serde(default)]
+    pub ball_interaction: BallInteraction,
+    /// Attractors and repulsors bending rolling balls.
+    #[serde(default)]
+    pub gravity_wells: Vec<GravityWell>,
 }
 
 /// Create the default mini-golf course.
@@ -124,6 +140,9 @@ pub fn default_course() -> Course {
         hole_position: Vec3::new(w / 2.0, 0.0, 27.0),
         walls,
         bumpers,
+        night: false,
+        ball_interaction: BallInteraction::default(),
+        gravity_wells: Vec::new(),
     }
 }
 
@@ -166,6 +185,9 @@ fn gentle_straight() -> Course {
         hole_position: Vec3::new(w / 2.0, 0.0, 21.0),
         walls: boundary_walls(w, d, 1.0),
         bumpers: vec![],
+        night: false,
+        ball_interaction: BallInteraction::default(),
+        gravity_wells: Vec::new(),
     }
 }
 
@@ -194,6 +216,9 @@ fn the_bend() -> Course {
             radius: 1.0,
             bounce_speed: 1.6,
         }],
+        night: false,
+        ball_interaction: BallInteraction::default(),
+        gravity_wells: Vec::new(),
     }
 }
 
@@ -244,6 +269,9 @@ fn bumper_alley() -> Course {
                 bounce_speed: 1.4,
             },
         ],
+        night: false,
+        ball_interaction: BallInteraction::default(),
+        gravity_wells: Vec::new(),
     }
 }
 
@@ -277,6 +305,9 @@ fn dogleg() -> Course {
             radius: 1.2,
             bounce_speed: 1.6,
         }],
+        night: false,
+        ball_interaction: BallInteraction::default(),
+        gravity_wells: Vec::new(),
     }
 }
 
@@ -318,6 +349,9 @@ fn the_funnel() -> Course {
                 bounce_speed: 1.6,
             },
         ],
+        night: false,
+        ball_interaction: BallInteraction::default(),
+        gravity_wells: Vec::new(),
     }
 }
 
@@ -378,6 +412,9 @@ fn pinball() -> Course {
                 bounce_speed: 1.6,
             },
         ],
+        night: false,
+        ball_interaction: BallInteraction::default(),
+        gravity_wells: Vec::new(),
     }
 }
 
@@ -423,6 +460,9 @@ fn zigzag() -> Course {
                 bounce_speed: 1.6,
             },
         ],
+        night: false,
+        ball_interaction: BallInteraction::default(),
+        gravity_wells: Vec::new(),
     }
 }
 
@@ -485,12 +525,17 @@ fn fortress() -> Course {
                 bounce_speed: 1.6,
             },
         ],
+        night: true,
+        ball_interaction: BallInteraction::default(),
+        gravity_wells: Vec::new(),
     }
 }
 
 /// Load courses from JSON files in a directory.
 ///
 /// Files are sorted by name (use `01_`, `02_` prefixes for ordering).
+/// An SVG file with the same stem (`03_the_bend.svg`) adds its shapes to
+/// the course's walls; see [`crate::svg_walls`].
 /// Falls back to the hardcoded `all_courses()` if the directory is missing,
 /// empty, or contains unparseable files.
 pub fn load_courses_from_dir(dir: &str) -> Vec<Course> {
@@ -516,7 +561,13 @@ pub fn load_courses_from_dir(dir: &str) -> Vec<Course> {
     for file in &files {
         match std::fs::read_to_string(file) {
             Ok(content) => match serde_json::from_str::<Course>(&content) {
-                Ok(course) => courses.push(course),
+                Ok(mut course) => {
+                    if let Err(e) = add_svg_walls(&mut course, &file.with_extension("svg")) {
+                        tracing::warn!("{e}, falling back to defaults");
+                        return all_courses();
+                    }
+                    courses.push(course);
+                },
                 Err(e) => {
                     tracing::warn!(
                         "Failed to parse {}: {e}, falling back to defaults",
@@ -538,6 +589,23 @@ pub fn load_courses_from_dir(dir: &str) -> Vec<Course> {
     courses
 }
 
+/// Append the walls drawn in `svg_path` to `course`, if that file exists.
+fn add_svg_walls(course: &mut Course, svg_path: &std::path::Path) -> Result<(), String> {
+    let svg = match std::fs::read_to_string(svg_path) {
+        Ok(svg) => svg,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(format!("Failed to read {}: {e}", svg_path.display())),
+    };
+    let walls = svg_walls::walls_from_svg(&svg)
+        .and_then(|walls| {
+            svg_walls::check_bounds(&walls, course.width, course.depth)?;
+            Ok(walls)
+        })
+        .map_err(|e| format!("Invalid walls in {}: {e}", svg_path.display()))?;
+    course.walls.extend(walls);
+    Ok(())
+}
+
 /// Returns all 9 courses in play order (index 0 = hole 1, etc.).
 pub fn all_courses() -> Vec<Course> {
     vec![
@@ -673,4 +741,41 @@ mod tests {
 
         let _ = std::fs::remove_dir_all(&dir);
     }
+
+    #[test]
+    fn load_adds_walls_from_svg() {
+        let dir = std::env::temp_dir().join("breakpoint_test_svg_courses");
+        let _ = std::fs::create_dir_all(&dir);
+        let course = gentle_straight();
+        let json = serde_json::to_string(&course).unwrap();
+        std::fs::write(dir.join("01.json"), json).unwrap();
+        std::fs::write(
+            dir.join("01.svg"),
+            r#"<svg><path d="M2 12 H10" data-height="0.5"/></svg>"#,
+        )
+        .unwrap();
+
+        let courses = load_courses_from_dir(dir.to_str().unwrap());
+        assert_eq!(courses.len(), 1);
+        assert_eq!(courses[0].walls.len(), course.walls.len() + 1);
+        assert_eq!(
+            courses[0].walls.last(),
+            Some(&Wall {
+                a: Vec3::new(2.0, 0.0, 12.0),
+                b: Vec3::new(10.0, 0.0, 12.0),
+                height: 0.5,
+            })
+        );
+
+        // Walls off the course reject the whole directory
+        std::fs::write(
+            dir.join("01.svg"),
+            r#"<line x1="0" y1="0" x2="40" y2="0"/>"#,
+        )
+        .unwrap();
+        let courses = load_courses_from_dir(dir.to_str().unwrap());
+        assert_eq!(courses.len(), 9, "Should fall back to hardcoded courses");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+
+use crate::course::{Course, GravityWell, Vec3};
+
+/// Camera height of the in-game follow camera; the flyover ends on the same
+/// pose so the hand-off to gameplay is seamless.
+const FOLLOW_HEIGHT: f32 = 15.0;
+/// Z offset of the follow camera behind the ball.
+const FOLLOW_OFFSET_Z: f32 = -2.0;
+
+/// One camera keyframe of the intro flyover.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct FlyoverWaypoint {
+    pub position: Vec3,
+    pub look_at: Vec3,
+}
+
+/// One-shot course metadata sent to clients at round start: par, hole
+/// length, a camera spline for the intro flyover, and the gravity wells to
+/// draw.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CoursePreview {
+    pub course_index: u8,
+    pub name: String,
+    pub par: u8,
+    /// Straight-line distance from the tee to the cup.
+    pub hole_length: f32,
+    /// Flyover playback time in seconds. Zero when the intro is disabled.
+    pub duration_secs: f32,
+    pub waypoints: Vec<FlyoverWaypoint>,
+    /// The course's wells, which clients may not have (custom courses).
+    #[serde(default)]
+    pub gravity_wells: Vec<GravityWell>,
+}
+
+fn lerp(a: Vec3, b: Vec3, t: f32) -> Vec3 {
+    Vec3::new(
+        a.x + (b.x - a.x) * t,
+        a.y + (b.y - a.y) * t,
+        a.z + (b.z - a.z) * t,
+    )
+}
+
+/// Uniform Catmull-Rom interpolation between `p1` and `p2`.
+fn catmull_rom(p0: Vec3, p1: Vec3, p2: Vec3, p3: Vec3, t: f32) -> Vec3 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    let axis = |a: f32, b: f32, c: f32, d: f32| {
+        0.5 * (2.0 * b
+            + (c - a) * t
+            + (2.0 * a - 5.0 * b + 4.0 * c - d) * t2
+            + (3.0 * b - a - 3.0 * c + d) * t3)
+    };
+    Vec3::new(
+        axis(p0.x, p1.x, p2.x, p3.x),
+        axis(p0.y, p1.y, p2.y, p3.y),
+        axis(p0.z, p1.z, p2.z, p3.z),
+    )
+}
+
+impl CoursePreview {
+    /// Build the preview for a course. The flyover starts above the cup,
+    /// sweeps around the course, and settles on the tee from the follow
+    /// camera's viewpoint.
+    pub fn from_course(course: &Course, course_index: u8, duration_secs: f32) -> Self {
+        let hole = course.hole_position;
+        let spawn = course.spawn_point;
+        let center = Vec3::new(course.width / 2.0, 0.0, course.depth / 2.0);
+        let extent = course.width.max(course.depth);
+
+        // Swing out to whichever side of the tee-to-cup line has more room
+        let side_x = if hole.x + spawn.x < course.width {
+            course.width
+        } else {
+            0.0
+        };
+
+        let waypoints = vec![
+            FlyoverWaypoint {
+                position: Vec3::new(hole.x, extent * 0.35, hole.z + extent * 0.2),
+                look_at: hole,
+            },
+            FlyoverWaypoint {
+                position: Vec3::new(side_x, extent * 0.6, center.z),
+                look_at: center,
+            },
+            FlyoverWaypoint {
+                position: Vec3::new(center.x, extent * 1.1, center.z - extent * 0.15),
+                look_at: center,
+            },
+            FlyoverWaypoint {
+                position: Vec3::new(spawn.x, FOLLOW_HEIGHT, spawn.z + FOLLOW_OFFSET_Z),
+                look_at: spawn,
+            },
+        ];
+
+        Self {
+            course_index,
+            name: course.name.clone(),
+            par: course.par,
+            hole_length: spawn.distance_xz(hole),
+            duration_secs,
+            waypoints,
+            gravity_wells: course.gravity_wells.clone(),
+        }
+    }
+
+    /// Camera pose at `progress` (0.0 = start, 1.0 = end) along the spline.
+    pub fn sample(&self, progress: f32) -> Option<FlyoverWaypoint> {
+        let n = self.waypoints.len();
+        match n {
+            0 => return None,
+            1 => return Some(self.waypoints[0]),
+            _ => {},
+        }
+        let scaled = progress.clamp(0.0, 1.0) * (n - 1) as f32;
+        let seg = (scaled as usize).min(n - 2);
+        let t = scaled - seg as f32;
+        let at = |i: isize| self.waypoints[i.clamp(0, n as isize - 1) as usize];
+        let (p0, p1, p2, p3) = (
+            at(seg as isize - 1),
+            at(seg as isize),
+            at(seg as isize + 1),
+            at(seg as isize + 2),
+        );
+        Some(FlyoverWaypoint {
+            position: catmull_rom(p0.position, p1.position, p2.position, p3.position, t),
+            // Look targets move less dramatically; linear keeps them stable
+            look_at: lerp(p1.look_at, p2.look_at, t),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::course::all_courses;
+
+    #[test]
+    fn preview_carries_course_metadata() {
+        let courses = all_courses();
+        let preview = CoursePreview::from_course(&courses[1], 1, 6.0);
+        assert_eq!(preview.course_index, 1);
+        assert_eq!(preview.name, courses[1].name);
+        assert_eq!(preview.par, courses[1].par);
+        assert!(preview.hole_length > 0.0);
+    }
+
+    #[test]
+    fn flyover_ends_on_follow_camera_pose() {
+        for course in all_courses() {
+            let preview = CoursePreview::from_course(&course, 0, 6.0);
+            let end = preview.sample(1.0).unwrap();
+            assert_eq!(end.look_at, course.spawn_point, "{}", course.name);
+            assert!((end.position.y - FOLLOW_HEIGHT).abs() < 1e-4);
+            let start = preview.sample(0.0).unwrap();
+            assert_eq!(start.look_at, course.hole_position, "{}", course.name);
+        }
+    }
+
+    #[test]
+    fn sample_is_continuous() {
+        let preview = CoursePreview::from_course(&all_courses()[0], 0, 6.0);
+        let mut prev = preview.sample(0.0).unwrap().position;
+        for i in 1..=100 {
+            let p = preview.sample(i as f32 / 100.0).unwrap().position;
+            let step = (p - prev).length();
+            assert!(step < 3.0, "Jump of {step} at sample {i}");
+            prev = p;
+        }
+    }
+}
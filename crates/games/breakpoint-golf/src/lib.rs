@@ -1,21 +1,62 @@
+pub mod caddie;
 pub mod course;
+pub mod flyover;
 pub mod physics;
 pub mod scoring;
+pub mod settings;
+pub mod sightline;
+pub mod svg_walls;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
 use breakpoint_core::breakpoint_game_boilerplate;
+use breakpoint_core::debug_command::{self, DebugCommand, DebugResult};
+use breakpoint_core::game_settings::FromGameConfig;
 use breakpoint_core::game_trait::{
-    BreakpointGame, GameConfig, GameEvent, GameMetadata, PlayerId, PlayerInputs, PlayerScore,
+    BreakpointGame, GameConfig, GameConfigDelta, GameEvent, GameMetadata, LIVE_EXTEND_ROUND,
+    PlayerId, PlayerInputs, PlayerScore,
 };
+use breakpoint_core::i18n::TextKey;
+use breakpoint_core::late_join::LateJoinPolicy;
+use breakpoint_core::math::ScreenAim;
+use breakpoint_core::pause::PauseState;
 use breakpoint_core::player::Player;
+use breakpoint_core::tutorial::TutorialObjective;
+use breakpoint_core::victory::VictorySummary;
 
+use caddie::CaddieHint;
 use course::{Course, all_courses, load_courses_from_dir};
-use physics::{BallState, GolfConfig};
-use scoring::calculate_score_with_config;
+use flyover::CoursePreview;
+use physics::{BallInteraction, BallState, GolfConfig};
+use scoring::{PinResult, calculate_score_with_config, closest_to_pin_scores};
+use settings::GolfSettings;
+use sightline::{ShotHint, shot_hint};
+
+/// `mode` setting for the quick one-stroke mode: everyone gets a single
+/// stroke on a random course and scores by how close they finish.
+pub const MODE_CLOSEST_TO_PIN: &str = "closest_to_pin";
+
+/// Strokes the second tutorial objective allows.
+const TUTORIAL_STROKES: u32 = 3;
+
+/// What the golf tutorial asks a new player to do.
+pub const TUTORIAL_OBJECTIVES: &[TutorialObjective] = &[
+    TutorialObjective {
+        id: "sink",
+        text: TextKey::TutorialGolfSink,
+    },
+    TutorialObjective {
+        id: "sink_in_three",
+        text: TextKey::TutorialGolfSinkInThree,
+    },
+];
+
+fn is_closest_to_pin(custom: &HashMap<String, serde_json::Value>) -> bool {
+    GolfSettings::from_custom(custom).is_ok_and(|s| s.closest_to_pin)
+}
 
 /// Serializable game state broadcast from host to clients.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,6 +68,58 @@ pub struct GolfState {
     pub round_complete: bool,
     /// Which course (0-indexed) is currently being played.
     pub course_index: u8,
+    /// Seconds of course flyover left. Strokes and the round timer wait
+    /// until it reaches zero.
+    #[serde(default)]
+    pub intro_remaining: f32,
+    /// Players who voted to skip the flyover.
+    #[serde(default)]
+    pub intro_skip_votes: Vec<PlayerId>,
+    /// Hole-in-one, birdie streak, and chip-in events earned on this hole,
+    /// in order, for round stats and client celebrations.
+    #[serde(default)]
+    pub highlights: Vec<GameEvent>,
+    /// Consecutive holes each player has finished under par. Carried over
+    /// between the holes of a match.
+    #[serde(default)]
+    pub birdie_streaks: HashMap<PlayerId, u32>,
+    /// Active pause reasons, so clients can show why play is held.
+    #[serde(default)]
+    pub pause: PauseState,
+    /// Line of sight to the cup for each ball at rest, so clients can point
+    /// the way on blind shots. Empty when the lobby turns hints off.
+    #[serde(default)]
+    pub shot_hints: HashMap<PlayerId, ShotHint>,
+    /// Closest-to-the-pin mode: one stroke each, scored by final distance.
+    #[serde(default)]
+    pub closest_to_pin: bool,
+    /// Seconds each player has left to undo their last stroke. A window
+    /// closes early once the ball touches anything.
+    #[serde(default)]
+    pub undo_windows: HashMap<PlayerId, f32>,
+    /// Caddie hints each player has left this hole. Empty when the lobby
+    /// gives none.
+    #[serde(default)]
+    pub caddie_left: HashMap<PlayerId, u32>,
+    /// This hole is played in the dark (see [`Course::night`]).
+    #[serde(default)]
+    pub night: bool,
+    /// How far each ball still in play lights the course on a night hole.
+    /// Empty on day holes.
+    #[serde(default)]
+    pub lanterns: HashMap<PlayerId, f32>,
+    /// How the balls on this hole treat each other; clients draw other
+    /// players' balls translucent when ghosted.
+    #[serde(default)]
+    pub ball_interaction: BallInteraction,
+}
+
+/// What the host tells one player alone, through
+/// `BreakpointGame::private_state_for`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GolfPrivateState {
+    /// The caddie's suggestion for this player's ball at rest.
+    pub caddie_hint: Option<CaddieHint>,
 }
 
 /// Input from a single player for a stroke.
@@ -38,6 +131,27 @@ pub struct GolfInput {
     pub power: f32,
     /// Whether the player is actually taking a stroke this tick.
     pub stroke: bool,
+    /// Vote to skip the course flyover.
+    #[serde(default)]
+    pub skip_intro: bool,
+    /// Take back the last stroke while its undo window is open.
+    #[serde(default)]
+    pub undo: bool,
+    /// Ask the caddie for a stroke suggestion.
+    #[serde(default)]
+    pub caddie: bool,
+    /// Aim relative to the sender's camera. When set, the host derives the
+    /// stroke direction from it and ignores `aim_angle`.
+    #[serde(default)]
+    pub screen_aim: Option<ScreenAim>,
+}
+
+/// A player's hole in progress when they left. Kept so results still credit
+/// them and a rejoin picks up where they stopped.
+#[derive(Debug, Clone)]
+struct DepartedGolfer {
+    ball: BallState,
+    strokes: u32,
 }
 
 /// The MiniGolf game, implementing `BreakpointGame`.
@@ -46,11 +160,36 @@ pub struct MiniGolf {
     course_index: usize,
     state: GolfState,
     player_ids: Vec<PlayerId>,
-    paused: bool,
     /// O(1) lookup companion for `state.sunk_order`.
     sunk_set: HashSet<PlayerId>,
+    /// Players who left during the current hole.
+    departed: BTreeMap<PlayerId, DepartedGolfer>,
     /// Data-driven game configuration (physics, scoring, timing).
     game_config: GolfConfig,
+    /// Preview for the current course (built on the server, received on clients).
+    preview: Option<CoursePreview>,
+    /// Whether `preview` still needs to be sent to clients.
+    preview_dirty: bool,
+    /// Whether to compute `state.shot_hints` (off for competitive lobbies).
+    shot_hints_enabled: bool,
+    /// Length of the stroke undo window; zero turns undo off.
+    undo_window_secs: f32,
+    /// Each ball as it lay before the stroke whose undo window is open.
+    pre_stroke: HashMap<PlayerId, BallState>,
+    /// Caddie hints each player gets per hole; zero turns the caddie off.
+    caddie_budget: u32,
+    /// Caddie suggestions for balls at rest. Kept out of `GolfState` and
+    /// sent privately, so only the asker sees one. On clients, just the
+    /// hints sent to this connection.
+    caddie_hints: HashMap<PlayerId, CaddieHint>,
+    /// Whether sinking scores and earns highlights (off during a warmup).
+    scoring: bool,
+    /// Seconds the host has added to this hole's clock.
+    round_extension: f32,
+    /// Hole time limit in micro-rounds, in place of a longer configured one.
+    micro_round_secs: Option<f32>,
+    /// What happens to players who join mid-hole.
+    late_join: LateJoinPolicy,
 }
 
 impl MiniGolf {
@@ -78,12 +217,35 @@ impl MiniGolf {
                 round_timer: 0.0,
                 round_complete: false,
                 course_index: 0,
+                intro_remaining: 0.0,
+                intro_skip_votes: Vec::new(),
+                highlights: Vec::new(),
+                birdie_streaks: HashMap::new(),
+                pause: PauseState::default(),
+                shot_hints: HashMap::new(),
+                closest_to_pin: false,
+                undo_windows: HashMap::new(),
+                caddie_left: HashMap::new(),
+                night: false,
+                lanterns: HashMap::new(),
+                ball_interaction: BallInteraction::default(),
             },
             courses,
             player_ids: Vec::new(),
-            paused: false,
             sunk_set: HashSet::new(),
+            departed: BTreeMap::new(),
             game_config,
+            preview: None,
+            preview_dirty: false,
+            shot_hints_enabled: true,
+            undo_window_secs: 0.0,
+            pre_stroke: HashMap::new(),
+            caddie_budget: 0,
+            caddie_hints: HashMap::new(),
+            scoring: true,
+            round_extension: 0.0,
+            micro_round_secs: None,
+            late_join: LateJoinPolicy::SpawnImmediately,
         }
     }
 
@@ -97,6 +259,11 @@ impl MiniGolf {
         &self.state
     }
 
+    /// The caddie's suggestion for `player_id`'s ball, if one is showing.
+    pub fn caddie_hint(&self, player_id: PlayerId) -> Option<&CaddieHint> {
+        self.caddie_hints.get(&player_id)
+    }
+
     /// Current course index (0-based).
     pub fn course_index(&self) -> usize {
         self.course_index
@@ -107,14 +274,162 @@ impl MiniGolf {
         self.courses.len()
     }
 
+    /// Par, hole length, and flyover path for the current course, once
+    /// built (server) or received (client).
+    pub fn preview(&self) -> Option<&CoursePreview> {
+        self.preview.as_ref()
+    }
+
+    /// Record a skip vote; a majority of players ends the flyover.
+    fn vote_skip_intro(&mut self, player_id: PlayerId) {
+        if self.state.intro_remaining <= 0.0
+            || !self.player_ids.contains(&player_id)
+            || self.state.intro_skip_votes.contains(&player_id)
+        {
+            return;
+        }
+        self.state.intro_skip_votes.push(player_id);
+        self.check_intro_skip();
+    }
+
+    fn check_intro_skip(&mut self) {
+        if self.state.intro_remaining > 0.0
+            && self.state.intro_skip_votes.len() * 2 > self.player_ids.len()
+        {
+            self.state.intro_remaining = 0.0;
+            self.state.intro_skip_votes.clear();
+        }
+    }
+
+    /// Keep a hint for every ball at rest and drop hints for balls in
+    /// motion or in the cup. A resting ball's hint is computed once.
+    fn refresh_shot_hints(&mut self) {
+        if !self.shot_hints_enabled {
+            return;
+        }
+        let course = &self.courses[self.course_index];
+        for (&pid, ball) in &self.state.balls {
+            if ball.is_sunk || !ball.is_stopped() {
+                self.state.shot_hints.remove(&pid);
+            } else {
+                self.state
+                    .shot_hints
+                    .entry(pid)
+                    .or_insert_with(|| shot_hint(course, ball.position));
+            }
+        }
+    }
+
+    /// Give every ball still in play a lantern on night holes and put out
+    /// the lanterns of balls in the cup.
+    fn refresh_lanterns(&mut self) {
+        if !self.state.night {
+            return;
+        }
+        let radius = self.game_config.lantern_radius;
+        for (&pid, ball) in &self.state.balls {
+            if ball.is_sunk {
+                self.state.lanterns.remove(&pid);
+            } else {
+                self.state.lanterns.insert(pid, radius);
+            }
+        }
+    }
+
+    /// Answer a caddie request for a ball at rest, spending one of the
+    /// player's hints. A hint already showing is not charged again.
+    fn ask_caddie(&mut self, player_id: PlayerId) {
+        if self.state.intro_remaining > 0.0 || self.caddie_hints.contains_key(&player_id) {
+            return;
+        }
+        let Some(ball) = self.state.balls.get(&player_id) else {
+            return;
+        };
+        if ball.is_sunk || !ball.is_stopped() {
+            return;
+        }
+        let Some(left) = self
+            .state
+            .caddie_left
+            .get_mut(&player_id)
+            .filter(|n| **n > 0)
+        else {
+            return;
+        };
+        *left -= 1;
+        let hint = caddie::suggest(&self.courses[self.course_index], ball);
+        self.caddie_hints.insert(player_id, hint);
+    }
+
+    /// Drop caddie hints once their ball has been played.
+    fn drop_played_caddie_hints(&mut self) {
+        let balls = &self.state.balls;
+        self.caddie_hints
+            .retain(|pid, _| balls.get(pid).is_some_and(|b| b.is_stopped() && !b.is_sunk));
+    }
+
+    /// Rewind a player's ball to where it lay before their last stroke and
+    /// take the stroke back, if its undo window is still open.
+    fn undo_stroke(&mut self, player_id: PlayerId) {
+        if self.state.undo_windows.remove(&player_id).is_none() {
+            return;
+        }
+        let Some(ball) = self.pre_stroke.remove(&player_id) else {
+            return;
+        };
+        self.state.balls.insert(player_id, ball);
+        if let Some(strokes) = self.state.strokes.get_mut(&player_id) {
+            *strokes = strokes.saturating_sub(1);
+        }
+        self.refresh_shot_hints();
+    }
+
+    /// Count down open undo windows, closing any whose ball has touched
+    /// something since the stroke.
+    fn tick_undo_windows(&mut self, dt: f32) {
+        let balls = &self.state.balls;
+        self.state.undo_windows.retain(|pid, remaining| {
+            *remaining -= dt;
+            *remaining > 0.0 && balls.get(pid).is_some_and(|b| !b.has_contact())
+        });
+        let open = &self.state.undo_windows;
+        self.pre_stroke.retain(|pid, _| open.contains_key(pid));
+    }
+
     /// Accessor for the game configuration.
     pub fn config(&self) -> &GolfConfig {
         &self.game_config
     }
 
-    /// Round time limit in seconds (from config).
+    /// Round time limit in seconds (from config, capped in micro-rounds),
+    /// plus any extension the host has given this hole.
     fn round_duration(&self) -> f32 {
-        self.game_config.round_duration_secs
+        let base = if self.state.closest_to_pin {
+            self.game_config.closest_to_pin_secs
+        } else {
+            self.game_config.round_duration_secs
+        };
+        let base = self.micro_round_secs.map_or(base, |secs| base.min(secs));
+        base + self.round_extension
+    }
+
+    /// Closest-to-the-pin ends once every player has taken their stroke
+    /// and every ball has come to rest.
+    fn all_strokes_settled(&self) -> bool {
+        self.player_ids.iter().all(|id| {
+            self.state.strokes.get(id).copied().unwrap_or(0) > 0
+                && self.state.balls.get(id).is_some_and(|b| b.is_stopped())
+        })
+    }
+
+    fn pin_result(&self, ball: &BallState, strokes: u32) -> PinResult {
+        if ball.is_sunk {
+            PinResult::Sunk
+        } else if strokes == 0 {
+            PinResult::NoStroke
+        } else {
+            PinResult::Distance(ball.distance_to_hole(&self.courses[self.course_index]))
+        }
     }
 }
 
@@ -137,23 +452,79 @@ impl BreakpointGame for MiniGolf {
     }
 
     fn init(&mut self, players: &[Player], config: &GameConfig) {
-        // Select course from config (default to 0)
-        let hole_index = config
-            .custom
-            .get("hole_index")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0) as usize;
+        // Hosts validate settings before starting; anything that slips
+        // through falls back to the defaults
+        let settings = GolfSettings::from_game_config(config).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Ignoring invalid golf settings");
+            GolfSettings::default()
+        });
+        // Select course from config (default to 0); closest-to-the-pin
+        // plays a random one, fixed by the `seed` setting when given
+        let closest_to_pin = settings.closest_to_pin;
+        let hole_index = if closest_to_pin {
+            let seed = settings.seed.unwrap_or_else(rand::random);
+            (seed % self.courses.len().max(1) as u64) as usize
+        } else {
+            settings.hole_index
+        };
         self.course_index = hole_index.min(self.courses.len().saturating_sub(1));
+        self.state.closest_to_pin = closest_to_pin;
+        self.scoring = config.scoring;
 
         self.state.balls.clear();
         self.state.strokes.clear();
         self.state.sunk_order.clear();
         self.sunk_set.clear();
+        self.departed.clear();
         self.state.round_timer = 0.0;
+        self.round_extension = 0.0;
+        self.micro_round_secs = settings.micro_round_secs;
+        self.late_join = settings.late_join;
         self.state.round_complete = false;
         self.state.course_index = self.course_index as u8;
+        self.state.highlights.clear();
+        self.state.shot_hints.clear();
+        self.state.undo_windows.clear();
+        self.pre_stroke.clear();
+        self.caddie_hints.clear();
+        self.state.caddie_left.clear();
+        self.state.lanterns.clear();
+        // Streaks run across the holes of one match
+        if hole_index == 0 || closest_to_pin {
+            self.state.birdie_streaks.clear();
+        }
         self.player_ids.clear();
 
+        // Optional flyover intro, enabled per lobby
+        let intro_secs = if settings.course_intro {
+            self.game_config.intro_duration_secs.max(0.0)
+        } else {
+            0.0
+        };
+        self.state.intro_remaining = intro_secs;
+        self.state.intro_skip_votes.clear();
+
+        // Night holes hide the course beyond each ball's lantern
+        self.state.night = self.courses[self.course_index].night;
+        // Balls share the hole as the lobby says, or as the course does
+        self.state.ball_interaction = settings
+            .ball_interaction
+            .unwrap_or(self.courses[self.course_index].ball_interaction);
+        // Blind-shot hints, on unless the lobby turns them off (for night
+        // holes or altogether)
+        self.shot_hints_enabled =
+            settings.shot_hints && (settings.night_hints || !self.state.night);
+        // Misclick undo for casual lobbies, off unless the lobby sets it
+        self.undo_window_secs = settings.undo_window_secs;
+        // Stroke suggestions for onboarding, off unless the lobby sets a budget
+        self.caddie_budget = settings.caddie_hints;
+        self.preview = Some(CoursePreview::from_course(
+            &self.courses[self.course_index],
+            self.state.course_index,
+            intro_secs,
+        ));
+        self.preview_dirty = true;
+
         let spawn = self.courses[self.course_index].spawn_point;
         for player in players {
             if player.is_spectator {
@@ -162,12 +533,25 @@ impl BreakpointGame for MiniGolf {
             self.player_ids.push(player.id);
             self.state.balls.insert(player.id, BallState::new(spawn));
             self.state.strokes.insert(player.id, 0);
+            if self.caddie_budget > 0 {
+                self.state.caddie_left.insert(player.id, self.caddie_budget);
+            }
         }
+        self.refresh_lanterns();
+        self.refresh_shot_hints();
     }
 
     fn update(&mut self, dt: f32, _inputs: &PlayerInputs) -> Vec<GameEvent> {
         breakpoint_core::profile!("golf_update");
-        if self.paused || self.state.round_complete {
+        if self.state.pause.is_paused() || self.state.round_complete {
+            return Vec::new();
+        }
+
+        if self.state.intro_remaining > 0.0 {
+            self.state.intro_remaining = (self.state.intro_remaining - dt).max(0.0);
+            if self.state.intro_remaining == 0.0 {
+                self.state.intro_skip_votes.clear();
+            }
             return Vec::new();
         }
 
@@ -179,6 +563,16 @@ impl BreakpointGame for MiniGolf {
         for ball in self.state.balls.values_mut() {
             ball.tick(course);
         }
+        match self.state.ball_interaction {
+            // Balls never touch each other; a bumper mode would resolve
+            // ball-on-ball contacts here
+            BallInteraction::PassThrough | BallInteraction::Ghost => {},
+        }
+        self.tick_undo_windows(dt);
+        self.refresh_lanterns();
+        self.refresh_shot_hints();
+        self.drop_played_caddie_hints();
+        let course = &self.courses[self.course_index];
 
         // Check for newly sunk balls
         let mut events = Vec::new();
@@ -192,20 +586,59 @@ impl BreakpointGame for MiniGolf {
                 self.sunk_set.insert(pid);
                 let was_first = self.state.sunk_order.len() == 1;
                 let strokes = self.state.strokes.get(&pid).copied().unwrap_or(0);
-                let score =
-                    calculate_score_with_config(strokes, course.par, was_first, true, scoring);
-                events.push(GameEvent::ScoreUpdate {
-                    player_id: pid,
-                    score,
-                });
+                // Closest-to-the-pin scores only once everyone has played
+                if !self.state.closest_to_pin && self.scoring {
+                    let score =
+                        calculate_score_with_config(strokes, course.par, was_first, true, scoring);
+                    events.push(GameEvent::ScoreUpdate {
+                        player_id: pid,
+                        score,
+                    });
+                }
+
+                let mut highlights = Vec::new();
+                if strokes == 1 {
+                    highlights.push(GameEvent::HoleInOne { player_id: pid });
+                }
+                if ball.bounces > 0 {
+                    highlights.push(GameEvent::ChipIn {
+                        player_id: pid,
+                        bounces: ball.bounces,
+                    });
+                }
+                let streak = self.state.birdie_streaks.entry(pid).or_insert(0);
+                if self.state.closest_to_pin {
+                    // One stroke on a random hole is not a birdie run
+                } else if strokes < u32::from(course.par) {
+                    *streak += 1;
+                    if *streak >= 2 {
+                        highlights.push(GameEvent::BirdieStreak {
+                            player_id: pid,
+                            streak: *streak,
+                        });
+                    }
+                } else {
+                    *streak = 0;
+                }
+                if self.scoring {
+                    self.state.highlights.extend(highlights.iter().cloned());
+                    events.extend(highlights);
+                }
             }
         }
 
         // Check round completion: all sunk or timer expired
         let all_sunk = self.player_ids.iter().all(|id| self.sunk_set.contains(id));
         let timer_expired = self.state.round_timer >= self.round_duration();
+        let strokes_settled = self.state.closest_to_pin && self.all_strokes_settled();
 
-        if all_sunk || timer_expired {
+        if all_sunk || timer_expired || strokes_settled {
+            // Not finishing the hole ends a streak
+            for pid in &self.player_ids {
+                if !self.sunk_set.contains(pid) {
+                    self.state.birdie_streaks.remove(pid);
+                }
+            }
             self.state.round_complete = true;
             events.push(GameEvent::RoundComplete);
         }
@@ -215,6 +648,27 @@ impl BreakpointGame for MiniGolf {
 
     breakpoint_game_boilerplate!(state_type: GolfState);
 
+    fn private_state_for(&self, player_id: PlayerId) -> Option<Vec<u8>> {
+        // Sent every tick while the caddie is on, so a used hint disappears
+        if self.caddie_budget == 0 {
+            return None;
+        }
+        let private = GolfPrivateState {
+            caddie_hint: self.caddie_hints.get(&player_id).copied(),
+        };
+        rmp_serde::to_vec(&private).ok()
+    }
+
+    fn apply_private_state(&mut self, player_id: PlayerId, data: &[u8]) {
+        let Ok(private) = rmp_serde::from_slice::<GolfPrivateState>(data) else {
+            return;
+        };
+        match private.caddie_hint {
+            Some(hint) => self.caddie_hints.insert(player_id, hint),
+            None => self.caddie_hints.remove(&player_id),
+        };
+    }
+
     fn apply_input(&mut self, player_id: PlayerId, input: &[u8]) {
         let golf_input: GolfInput = match rmp_serde::from_slice(input) {
             Ok(i) => i,
@@ -224,13 +678,41 @@ impl BreakpointGame for MiniGolf {
             },
         };
 
+        if golf_input.skip_intro {
+            self.vote_skip_intro(player_id);
+        }
+        if golf_input.undo {
+            self.undo_stroke(player_id);
+            return;
+        }
+        if golf_input.caddie {
+            self.ask_caddie(player_id);
+            return;
+        }
+
+        let out_of_strokes = self.state.closest_to_pin
+            && self.state.strokes.get(&player_id).copied().unwrap_or(0) > 0;
+        let aim_angle = match golf_input.screen_aim {
+            Some(aim) => aim.world_angle(),
+            None => Some(golf_input.aim_angle),
+        };
         if golf_input.stroke
+            && let Some(aim_angle) = aim_angle
+            && self.state.intro_remaining <= 0.0
+            && !out_of_strokes
             && let Some(ball) = self.state.balls.get_mut(&player_id)
             && ball.is_stopped()
             && !ball.is_sunk
         {
-            ball.stroke(golf_input.aim_angle, golf_input.power * physics::MAX_POWER);
+            let before = ball.clone();
+            ball.stroke(aim_angle, golf_input.power * physics::MAX_POWER);
             *self.state.strokes.entry(player_id).or_insert(0) += 1;
+            if self.undo_window_secs > 0.0 {
+                self.pre_stroke.insert(player_id, before);
+                self.state
+                    .undo_windows
+                    .insert(player_id, self.undo_window_secs);
+            }
         }
     }
 
@@ -239,39 +721,208 @@ impl BreakpointGame for MiniGolf {
             return;
         }
         if !self.player_ids.contains(&player.id) {
+            let departed = self.departed.remove(&player.id);
+            if departed.is_none() && self.late_join == LateJoinPolicy::Spectate {
+                return;
+            }
             self.player_ids.push(player.id);
-            let spawn = self.courses[self.course_index].spawn_point;
-            self.state.balls.insert(player.id, BallState::new(spawn));
-            self.state.strokes.insert(player.id, 0);
+            let (ball, strokes) = match departed {
+                Some(d) => (d.ball, d.strokes),
+                None => (
+                    BallState::new(self.courses[self.course_index].spawn_point),
+                    0,
+                ),
+            };
+            self.state.balls.insert(player.id, ball);
+            self.state.strokes.insert(player.id, strokes);
+            if self.caddie_budget > 0 {
+                self.state
+                    .caddie_left
+                    .entry(player.id)
+                    .or_insert(self.caddie_budget);
+            }
+            self.refresh_lanterns();
+            self.refresh_shot_hints();
         }
     }
 
     fn player_left(&mut self, player_id: PlayerId) {
+        if self.player_ids.contains(&player_id)
+            && let Some(ball) = self.state.balls.get(&player_id)
+        {
+            let strokes = self.state.strokes.get(&player_id).copied().unwrap_or(0);
+            self.departed.insert(
+                player_id,
+                DepartedGolfer {
+                    ball: ball.clone(),
+                    strokes,
+                },
+            );
+        }
         self.player_ids.retain(|&id| id != player_id);
         self.state.balls.remove(&player_id);
         self.state.strokes.remove(&player_id);
+        self.state.birdie_streaks.remove(&player_id);
+        self.state.shot_hints.remove(&player_id);
+        self.state.lanterns.remove(&player_id);
+        self.state.undo_windows.remove(&player_id);
+        self.pre_stroke.remove(&player_id);
+        self.caddie_hints.remove(&player_id);
+        self.state.intro_skip_votes.retain(|&id| id != player_id);
+        self.check_intro_skip();
     }
 
     fn round_count_hint(&self) -> u8 {
         self.courses.len() as u8
     }
 
+    fn round_count_for(&self, custom: &HashMap<String, serde_json::Value>) -> u8 {
+        if is_closest_to_pin(custom) {
+            1
+        } else {
+            self.round_count_hint()
+        }
+    }
+
     fn round_results(&self) -> Vec<PlayerScore> {
+        if self.state.closest_to_pin {
+            let mut players = Vec::new();
+            let mut results = Vec::new();
+            for &pid in &self.player_ids {
+                if let Some(ball) = self.state.balls.get(&pid) {
+                    let strokes = self.state.strokes.get(&pid).copied().unwrap_or(0);
+                    players.push((pid, false));
+                    results.push(self.pin_result(ball, strokes));
+                }
+            }
+            for (&pid, d) in &self.departed {
+                players.push((pid, true));
+                results.push(self.pin_result(&d.ball, d.strokes));
+            }
+            let scores = closest_to_pin_scores(&results, &self.game_config.scoring);
+            return players
+                .into_iter()
+                .zip(scores)
+                .map(|((player_id, disconnected), score)| PlayerScore {
+                    player_id,
+                    score,
+                    disconnected,
+                })
+                .collect();
+        }
+
         let par = self.courses[self.course_index].par;
         let scoring = &self.game_config.scoring;
-        self.player_ids
+        let score = |pid: PlayerId, strokes: u32, disconnected: bool| {
+            let finished = self.sunk_set.contains(&pid);
+            let was_first = self.state.sunk_order.first() == Some(&pid);
+            PlayerScore {
+                player_id: pid,
+                score: calculate_score_with_config(strokes, par, was_first, finished, scoring),
+                disconnected,
+            }
+        };
+        let active = self.player_ids.iter().map(|&pid| {
+            let strokes = self.state.strokes.get(&pid).copied().unwrap_or(0);
+            score(pid, strokes, false)
+        });
+        let departed = self
+            .departed
             .iter()
-            .map(|&pid| {
-                let strokes = self.state.strokes.get(&pid).copied().unwrap_or(0);
-                let finished = self.sunk_set.contains(&pid);
-                let was_first = self.state.sunk_order.first() == Some(&pid);
-                let score = calculate_score_with_config(strokes, par, was_first, finished, scoring);
-                PlayerScore {
-                    player_id: pid,
-                    score,
+            .map(|(&pid, d)| score(pid, d.strokes, true));
+        active.chain(departed).collect()
+    }
+
+    fn victory_summary(&self) -> VictorySummary {
+        if self.state.closest_to_pin {
+            return VictorySummary::from_scores(&self.round_results());
+        }
+        // Stroke play: fewest strokes among players who holed out
+        VictorySummary::best_by(
+            TextKey::VictoryGolfStrokes,
+            self.player_ids
+                .iter()
+                .filter(|pid| self.sunk_set.contains(pid))
+                .map(|&pid| {
+                    let strokes = self.state.strokes.get(&pid).copied().unwrap_or(0);
+                    (pid, strokes as i32)
+                }),
+            true,
+        )
+    }
+
+    fn tutorial_objectives(&self) -> &'static [TutorialObjective] {
+        TUTORIAL_OBJECTIVES
+    }
+
+    fn tutorial_objectives_met(&self, player_id: PlayerId) -> Vec<&'static str> {
+        if !self.state.balls.get(&player_id).is_some_and(|b| b.is_sunk) {
+            return Vec::new();
+        }
+        let strokes = self.state.strokes.get(&player_id).copied().unwrap_or(0);
+        if strokes <= TUTORIAL_STROKES {
+            vec!["sink", "sink_in_three"]
+        } else {
+            vec!["sink"]
+        }
+    }
+
+    fn course_data(&mut self) -> Option<Vec<u8>> {
+        if !self.preview_dirty {
+            return None;
+        }
+        self.preview_dirty = false;
+        self.preview
+            .as_ref()
+            .map(|p| rmp_serde::to_vec(p).expect("course preview serialization must succeed"))
+    }
+
+    fn apply_course_data(&mut self, data: &[u8]) {
+        if let Ok(preview) = rmp_serde::from_slice::<CoursePreview>(data) {
+            self.preview = Some(preview);
+        }
+    }
+
+    fn apply_debug(&mut self, command: &DebugCommand) -> DebugResult {
+        match *command {
+            DebugCommand::Teleport { player_id, x, y } => {
+                let ball = self
+                    .state
+                    .balls
+                    .get_mut(&player_id)
+                    .ok_or_else(|| format!("No ball for player {player_id}"))?;
+                if ball.is_sunk {
+                    return Err(format!("Player {player_id} has already sunk"));
                 }
-            })
-            .collect()
+                ball.position.x = x;
+                ball.position.z = y;
+                ball.velocity = course::Vec3::new(0.0, 0.0, 0.0);
+                // A teleport is not a stroke to take back
+                self.pre_stroke.remove(&player_id);
+                self.state.undo_windows.remove(&player_id);
+                self.caddie_hints.remove(&player_id);
+            },
+            DebugCommand::ForceRoundEnd => self.state.round_complete = true,
+            DebugCommand::SetRoundTimer { elapsed_secs } => {
+                self.state.round_timer = debug_command::checked_elapsed(elapsed_secs)?;
+            },
+            DebugCommand::GrantPowerUp { .. } => {
+                return Err("Golf has no power-ups".to_string());
+            },
+        }
+        Ok(())
+    }
+
+    fn live_config_keys(&self) -> &'static [&'static str] {
+        &[LIVE_EXTEND_ROUND]
+    }
+
+    fn apply_config_change(&mut self, delta: &GameConfigDelta) -> Result<(), String> {
+        delta.check_keys(self.live_config_keys())?;
+        if let Some(secs) = delta.round_extension()? {
+            self.round_extension += secs;
+        }
+        Ok(())
     }
 }
 
@@ -314,6 +965,10 @@ mod tests {
             aim_angle: 0.0,
             power: 0.5,
             stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
         };
         let data = rmp_serde::to_vec(&input).unwrap();
         game.apply_input(1, &data);
@@ -336,6 +991,10 @@ mod tests {
             aim_angle: 0.0,
             power: 0.5,
             stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
         };
         let data = rmp_serde::to_vec(&input).unwrap();
         game.apply_input(1, &data);
@@ -390,6 +1049,53 @@ mod tests {
         assert!(events.iter().any(|e| matches!(e, GameEvent::RoundComplete)));
     }
 
+    #[test]
+    fn micro_rounds_cap_the_hole_clock() {
+        use breakpoint_core::round_phase::MICRO_ROUNDS_SETTING;
+
+        let mut game = MiniGolf::new();
+        let config = GameConfig::builder()
+            .setting(MICRO_ROUNDS_SETTING, 30)
+            .build();
+        game.init(&make_players(1), &config);
+        assert_eq!(game.round_duration(), 30.0);
+
+        game.state.round_timer = 29.95;
+        game.update(
+            0.1,
+            &PlayerInputs {
+                inputs: HashMap::new(),
+            },
+        );
+        assert!(game.is_round_complete());
+    }
+
+    #[test]
+    fn host_can_extend_the_hole_clock() {
+        use breakpoint_core::game_trait::{GameConfigDelta, LIVE_POWERUPS};
+
+        let mut game = MiniGolf::new();
+        game.init(&make_players(1), &default_config(90));
+        let base = game.round_duration();
+        let no_powerups = GameConfigDelta::default().set(LIVE_POWERUPS, false);
+        assert!(game.apply_config_change(&no_powerups).is_err());
+
+        let extend = GameConfigDelta::default().set(LIVE_EXTEND_ROUND, 60.0);
+        game.apply_config_change(&extend).unwrap();
+        game.state.round_timer = base;
+        game.update(
+            0.1,
+            &PlayerInputs {
+                inputs: HashMap::new(),
+            },
+        );
+        assert!(
+            !game.is_round_complete(),
+            "The extension keeps the hole open"
+        );
+        assert_eq!(game.round_duration(), base + 60.0);
+    }
+
     #[test]
     fn serialize_deserialize_state_roundtrip() {
         let mut game = MiniGolf::new();
@@ -433,6 +1139,171 @@ mod tests {
         let p2 = results.iter().find(|r| r.player_id == 2).unwrap();
         // DNF: -1
         assert_eq!(p2.score, -1);
+
+        let victory = game.victory_summary();
+        assert_eq!(victory.winner_ids, vec![1]);
+        assert_eq!(victory.key_stat, Some(2));
+        assert_eq!(victory.message.key, TextKey::VictoryGolfStrokes);
+    }
+
+    #[test]
+    fn closest_to_pin_is_one_stroke_scored_by_distance() {
+        let mut game = MiniGolf::default();
+        let mut config = default_config(90);
+        config
+            .custom
+            .insert("mode".to_string(), serde_json::json!(MODE_CLOSEST_TO_PIN));
+        config
+            .custom
+            .insert("seed".to_string(), serde_json::json!(4));
+        assert_eq!(game.round_count_for(&config.custom), 1);
+        game.init(&make_players(3), &config);
+        assert_eq!(game.course_index(), 4 % game.total_holes());
+        assert!(game.state().closest_to_pin);
+
+        let tap = rmp_serde::to_vec(&GolfInput {
+            aim_angle: 0.0,
+            power: 0.0,
+            stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
+        })
+        .unwrap();
+        game.apply_input(1, &tap);
+        game.apply_input(1, &tap);
+        assert_eq!(game.state().strokes[&1], 1, "Only one stroke each");
+        game.apply_input(2, &tap);
+        game.update(
+            0.1,
+            &PlayerInputs {
+                inputs: HashMap::new(),
+            },
+        );
+        assert!(!game.state().round_complete, "Player 3 has not played");
+
+        // Player 2's ball finished next to the hole
+        let hole = game.course().hole_position;
+        game.state.balls.get_mut(&2).unwrap().position =
+            course::Vec3::new(hole.x + 1.0, hole.y, hole.z);
+        game.apply_input(3, &tap);
+        let events = game.update(
+            0.1,
+            &PlayerInputs {
+                inputs: HashMap::new(),
+            },
+        );
+        assert!(events.iter().any(|e| matches!(e, GameEvent::RoundComplete)));
+
+        let score = |pid| {
+            game.round_results()
+                .iter()
+                .find(|r| r.player_id == pid)
+                .unwrap()
+                .score
+        };
+        assert_eq!(score(2), 3, "Nearest of three");
+        assert_eq!(score(1), 1, "Balls left at the tee tie");
+        assert_eq!(score(3), 1);
+    }
+
+    fn intro_config() -> GameConfig {
+        let mut config = default_config(90);
+        config
+            .custom
+            .insert("course_intro".to_string(), serde_json::json!(true));
+        config
+    }
+
+    fn skip_vote() -> Vec<u8> {
+        rmp_serde::to_vec(&GolfInput {
+            aim_angle: 0.0,
+            power: 0.0,
+            stroke: false,
+            skip_intro: true,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn intro_holds_strokes_and_timer() {
+        let mut game = MiniGolf::default();
+        game.init(&make_players(1), &intro_config());
+        assert!(game.state.intro_remaining > 0.0);
+
+        let stroke = rmp_serde::to_vec(&GolfInput {
+            aim_angle: 0.0,
+            power: 0.5,
+            stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
+        })
+        .unwrap();
+        game.apply_input(1, &stroke);
+        assert_eq!(game.state.strokes[&1], 0, "Stroke ignored during intro");
+
+        let inputs = PlayerInputs {
+            inputs: HashMap::new(),
+        };
+        game.update(1.0, &inputs);
+        assert_eq!(game.state.round_timer, 0.0, "Timer waits for the intro");
+
+        let intro = game.config().intro_duration_secs;
+        game.update(intro, &inputs);
+        assert_eq!(game.state.intro_remaining, 0.0);
+        game.apply_input(1, &stroke);
+        assert_eq!(game.state.strokes[&1], 1);
+    }
+
+    #[test]
+    fn intro_skipped_by_majority_vote() {
+        let mut game = MiniGolf::default();
+        game.init(&make_players(3), &intro_config());
+
+        game.apply_input(1, &skip_vote());
+        game.apply_input(1, &skip_vote());
+        assert_eq!(
+            game.state.intro_skip_votes,
+            vec![1],
+            "Duplicate vote ignored"
+        );
+        assert!(game.state.intro_remaining > 0.0, "1 of 3 is not a majority");
+
+        game.apply_input(2, &skip_vote());
+        assert_eq!(game.state.intro_remaining, 0.0);
+        assert!(game.state.intro_skip_votes.is_empty());
+    }
+
+    #[test]
+    fn intro_disabled_by_default() {
+        let mut game = MiniGolf::default();
+        game.init(&make_players(1), &default_config(90));
+        assert_eq!(game.state.intro_remaining, 0.0);
+        assert_eq!(game.preview().unwrap().duration_secs, 0.0);
+    }
+
+    #[test]
+    fn course_preview_sent_once_per_round() {
+        let mut server = MiniGolf::default();
+        server.init(&make_players(1), &intro_config());
+        let data = server.course_data().expect("Preview sent on round start");
+        assert!(server.course_data().is_none(), "Preview is one-shot");
+
+        let mut client = MiniGolf::default();
+        client.apply_course_data(&data);
+        let preview = client.preview().unwrap();
+        assert_eq!(preview, server.preview().unwrap());
+        assert!(preview.duration_secs > 0.0);
+        assert!(!preview.waypoints.is_empty());
+
+        server.init(&make_players(1), &intro_config());
+        assert!(server.course_data().is_some(), "Re-sent for the next hole");
     }
 
     #[test]
@@ -466,6 +1337,30 @@ mod tests {
         assert_eq!(game.player_ids.len(), 1);
     }
 
+    #[test]
+    fn departed_player_scored_and_restored_on_rejoin() {
+        let mut game = MiniGolf::new();
+        let players = make_players(2);
+        game.init(&players, &default_config(90));
+
+        // Player 2 sinks in 2 strokes, then disconnects
+        game.state.strokes.insert(2, 2);
+        game.state.sunk_order.push(2);
+        game.sunk_set.insert(2);
+        game.player_left(2);
+
+        let results = game.round_results();
+        let p2 = results.iter().find(|r| r.player_id == 2).unwrap();
+        assert!(p2.disconnected);
+        assert_eq!(p2.score, 5, "Keeps the under-par and first-sink credit");
+
+        game.player_joined(&players[1]);
+        assert_eq!(game.state.strokes[&2], 2, "Rejoin restores strokes");
+        let results = game.round_results();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| !r.disconnected));
+    }
+
     // ================================================================
     // Full game session / simulation tests
     // ================================================================
@@ -501,6 +1396,10 @@ mod tests {
             aim_angle: aim,
             power: 0.6,
             stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
         };
         let data = rmp_serde::to_vec(&input).unwrap();
         game.apply_input(1, &data);
@@ -527,6 +1426,10 @@ mod tests {
                     aim_angle: aim,
                     power: 0.4,
                     stroke: true,
+                    skip_intro: false,
+                    undo: false,
+                    caddie: false,
+                    screen_aim: None,
                 };
                 let data = rmp_serde::to_vec(&input).unwrap();
                 game.apply_input(1, &data);
@@ -552,6 +1455,10 @@ mod tests {
             aim_angle: 0.5,
             power: 0.6,
             stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
         };
         let data = rmp_serde::to_vec(&input).unwrap();
         game.apply_input(1, &data);
@@ -601,6 +1508,10 @@ mod tests {
             aim_angle: 0.0,
             power: 0.5,
             stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
         };
         let data = rmp_serde::to_vec(&input).unwrap();
         game.apply_input(1, &data);
@@ -628,6 +1539,10 @@ mod tests {
             aim_angle: std::f32::consts::FRAC_PI_2,
             power: 0.5,
             stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
         };
         let data = rmp_serde::to_vec(&input).unwrap();
         game.apply_input(1, &data);
@@ -645,6 +1560,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn screen_aim_overrides_aim_angle() {
+        let mut game = MiniGolf::new();
+        let players = make_players(2);
+        game.init(&players, &gentle_straight_config());
+
+        // A camera looking along +Z turns an upward drag into +Z
+        let mut input = GolfInput {
+            aim_angle: std::f32::consts::PI,
+            power: 0.5,
+            stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: Some(ScreenAim {
+                drag: breakpoint_core::math::Vec2::new(0.0, -40.0),
+                camera_yaw: std::f32::consts::FRAC_PI_2,
+            }),
+        };
+        game.apply_input(1, &rmp_serde::to_vec(&input).unwrap());
+        let ball = &game.state.balls[&1];
+        assert!(ball.velocity.x.abs() < 0.1, "vx {}", ball.velocity.x);
+        assert!(ball.velocity.z > 0.0, "vz {}", ball.velocity.z);
+
+        // A zero drag has no direction, so it doesn't swing
+        input.screen_aim = Some(ScreenAim::default());
+        game.apply_input(2, &rmp_serde::to_vec(&input).unwrap());
+        assert!(game.state.balls[&2].is_stopped());
+        assert_eq!(game.state.strokes.get(&2).copied().unwrap_or(0), 0);
+    }
+
     #[test]
     fn apply_input_aim_at_hole_moves_toward_hole() {
         let mut game = MiniGolf::new();
@@ -659,6 +1605,10 @@ mod tests {
             aim_angle: aim,
             power: 0.4,
             stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
         };
         let data = rmp_serde::to_vec(&input).unwrap();
         game.apply_input(1, &data);
@@ -675,8 +1625,8 @@ mod tests {
         }
 
         let final_pos = game.state.balls[&1].position;
-        let initial_dist = ((hole.x - spawn.x).powi(2) + (hole.z - spawn.z).powi(2)).sqrt();
-        let final_dist = ((hole.x - final_pos.x).powi(2) + (hole.z - final_pos.z).powi(2)).sqrt();
+        let initial_dist = spawn.distance_xz(hole);
+        let final_dist = final_pos.distance_xz(hole);
         assert!(
             final_dist < initial_dist,
             "Ball should be closer to hole: initial_dist={initial_dist}, final_dist={final_dist}"
@@ -709,6 +1659,10 @@ mod tests {
                 aim_angle: angle,
                 power: 0.5,
                 stroke: true,
+                skip_intro: false,
+                undo: false,
+                caddie: false,
+                screen_aim: None,
             };
             let data = rmp_serde::to_vec(&input).unwrap();
             game.apply_input(1, &data);
@@ -772,6 +1726,10 @@ mod tests {
             aim_angle: 0.0,
             power: 0.5,
             stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
         };
         let data = rmp_serde::to_vec(&input).unwrap();
         game.apply_input(1, &data);
@@ -807,6 +1765,10 @@ mod tests {
             aim_angle: 1.0,
             power: 0.4,
             stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
         };
         let data2 = rmp_serde::to_vec(&input2).unwrap();
         game.apply_input(2, &data2);
@@ -889,6 +1851,63 @@ mod tests {
         assert!(events.iter().any(|e| matches!(e, GameEvent::RoundComplete)));
     }
 
+    #[test]
+    fn hole_in_one_chip_in_and_birdie_streak() {
+        let mut game = MiniGolf::default();
+        let players = make_players(2);
+        game.init(&players, &default_config(90));
+
+        // Player 1 aces a bank shot, already one hole into a birdie streak
+        let ball = game.state.balls.get_mut(&1).unwrap();
+        ball.position = game.courses[0].hole_position;
+        ball.bounces = 2;
+        game.state.strokes.insert(1, 1);
+        game.state.birdie_streaks.insert(1, 1);
+        game.state.birdie_streaks.insert(2, 3);
+
+        let inputs = PlayerInputs {
+            inputs: HashMap::new(),
+        };
+        let events = game.update(0.1, &inputs);
+        assert!(game.state.balls[&1].is_sunk);
+        for e in [&events, &game.state.highlights] {
+            assert!(
+                e.iter()
+                    .any(|e| matches!(e, GameEvent::HoleInOne { player_id: 1 }))
+            );
+            assert!(e.iter().any(|e| matches!(
+                e,
+                GameEvent::ChipIn {
+                    player_id: 1,
+                    bounces: 2
+                }
+            )));
+            assert!(e.iter().any(|e| matches!(
+                e,
+                GameEvent::BirdieStreak {
+                    player_id: 1,
+                    streak: 2
+                }
+            )));
+        }
+
+        // Player 2 runs out of time, ending their streak
+        game.state.round_timer = game.round_duration();
+        game.update(0.1, &inputs);
+        assert!(game.is_round_complete());
+        assert_eq!(game.state.birdie_streaks.get(&2), None);
+
+        // Streaks carry to the next hole, highlights do not
+        let mut next = default_config(90);
+        next.custom
+            .insert("hole_index".to_string(), serde_json::json!(1));
+        game.init(&players, &next);
+        assert!(game.state.highlights.is_empty());
+        assert_eq!(game.state.birdie_streaks[&1], 2);
+        game.init(&players, &default_config(90));
+        assert!(game.state.birdie_streaks.is_empty());
+    }
+
     #[test]
     fn dnf_timeout_scoring() {
         let mut game = MiniGolf::new();
@@ -939,6 +1958,10 @@ mod tests {
             aim_angle: 0.0,
             power: 1.5,
             stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
         };
         let data = rmp_serde::to_vec(&input).unwrap();
         game.apply_input(1, &data);
@@ -951,8 +1974,7 @@ mod tests {
         assert_eq!(game.state.strokes[&1], 1, "Stroke should be counted");
 
         // Velocity magnitude should be clamped to MAX_POWER
-        let vel = &game.state.balls[&1].velocity;
-        let speed = (vel.x * vel.x + vel.z * vel.z).sqrt();
+        let speed = game.state.balls[&1].speed();
         assert!(
             speed <= physics::MAX_POWER + 0.01,
             "Speed should be clamped to MAX_POWER ({:.2}), got {speed:.2}",
@@ -980,6 +2002,10 @@ mod tests {
             aim_angle: 0.5,
             power: 0.5,
             stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
         };
         let data = rmp_serde::to_vec(&input).unwrap();
         breakpoint_core::test_helpers::contract_apply_input_changes_state(&mut game, &data, 1);
@@ -1018,6 +2044,14 @@ mod tests {
         breakpoint_core::test_helpers::contract_pause_stops_updates(&mut game);
     }
 
+    #[test]
+    fn contract_pause_reasons_stack() {
+        let mut game = MiniGolf::new();
+        let players = make_players(1);
+        game.init(&players, &default_config(90));
+        breakpoint_core::test_helpers::contract_pause_reasons_stack(&mut game);
+    }
+
     #[test]
     fn contract_player_left_cleanup() {
         let mut game = MiniGolf::new();
@@ -1044,6 +2078,10 @@ mod tests {
             aim_angle: 1.23,
             power: 0.75,
             stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
         };
         let encoded = rmp_serde::to_vec(&input).unwrap();
         let decoded: GolfInput = rmp_serde::from_slice(&encoded).unwrap();
@@ -1061,6 +2099,10 @@ mod tests {
             aim_angle: 0.5,
             power: 0.8,
             stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
         };
         let input_data = rmp_serde::to_vec(&input).unwrap();
         let msg = ClientMessage::PlayerInput(PlayerInputMsg {
@@ -1095,6 +2137,10 @@ mod tests {
             aim_angle: 0.0,
             power: 0.5,
             stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
         };
         let data = rmp_serde::to_vec(&input).unwrap();
         game.apply_input(1, &data);
@@ -1122,6 +2168,10 @@ mod tests {
             aim_angle: f32::NAN,
             power: 0.5,
             stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
         };
         let data = rmp_serde::to_vec(&input).unwrap();
         game.apply_input(1, &data);
@@ -1146,12 +2196,15 @@ mod tests {
             aim_angle: 0.0,
             power: f32::INFINITY,
             stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
         };
         let data = rmp_serde::to_vec(&input).unwrap();
         game.apply_input(1, &data);
 
-        let vel = &game.state.balls[&1].velocity;
-        let speed = (vel.x * vel.x + vel.z * vel.z).sqrt();
+        let speed = game.state.balls[&1].speed();
         assert!(
             speed <= physics::MAX_POWER + 0.01,
             "Inf power should be clamped to MAX_POWER, got {speed}"
@@ -1172,7 +2225,7 @@ mod tests {
             let dx = hole.x - spawn.x;
             let dz = hole.z - spawn.z;
             let aim_angle = dz.atan2(dx);
-            let initial_dist = ((hole.x - spawn.x).powi(2) + (hole.z - spawn.z).powi(2)).sqrt();
+            let initial_dist = spawn.distance_xz(hole);
 
             let mut ball = physics::BallState::new(spawn);
             ball.stroke(aim_angle, physics::MAX_POWER * 0.8);
@@ -1184,8 +2237,7 @@ mod tests {
                 }
             }
 
-            let final_dist =
-                ((hole.x - ball.position.x).powi(2) + (hole.z - ball.position.z).powi(2)).sqrt();
+            let final_dist = ball.position.distance_xz(hole);
             assert!(
                 final_dist < initial_dist || ball.is_sunk,
                 "Course {idx} ({}): ball should be closer to hole after aimed stroke. \
@@ -1550,4 +2602,294 @@ mod tests {
             "Ball should move after aim_angle=0 stroke, got dx={dx} (initial={initial_x}, after={after_x})"
         );
     }
+
+    #[test]
+    fn shot_hints_follow_resting_balls_and_can_be_disabled() {
+        let mut game = MiniGolf::default();
+        let players = make_players(2);
+        game.init(&players, &default_config(90));
+        // The starter course's obstacle hides the cup from the tee
+        assert!(!game.state.shot_hints[&1].hole_visible);
+
+        let input = GolfInput {
+            aim_angle: 0.0,
+            power: 0.5,
+            stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
+        };
+        game.apply_input(1, &rmp_serde::to_vec(&input).unwrap());
+        let inputs = PlayerInputs {
+            inputs: HashMap::new(),
+        };
+        game.update(0.1, &inputs);
+        assert!(
+            !game.state.shot_hints.contains_key(&1),
+            "Moving balls have no hint"
+        );
+        assert!(game.state.shot_hints.contains_key(&2));
+
+        let mut config = default_config(90);
+        config
+            .custom
+            .insert("shot_hints".to_string(), serde_json::json!(false));
+        game.init(&players, &config);
+        game.update(0.1, &inputs);
+        assert!(game.state.shot_hints.is_empty());
+    }
+
+    #[test]
+    fn night_holes_light_lanterns_and_can_drop_hints() {
+        let mut game = MiniGolf::with_config(GolfConfig::default());
+        let players = make_players(2);
+        let mut config = default_config(90);
+        config
+            .custom
+            .insert("hole_index".to_string(), serde_json::json!(8));
+        game.init(&players, &config);
+        assert!(game.course().night);
+        assert!(game.state.night);
+        assert_eq!(game.state.lanterns.len(), 2);
+        assert_eq!(game.state.lanterns[&1], game.config().lantern_radius);
+        assert!(!game.state.shot_hints.is_empty());
+
+        game.state.balls.get_mut(&1).unwrap().is_sunk = true;
+        let inputs = PlayerInputs {
+            inputs: HashMap::new(),
+        };
+        game.update(0.1, &inputs);
+        assert!(
+            !game.state.lanterns.contains_key(&1),
+            "Balls in the cup go dark"
+        );
+
+        config
+            .custom
+            .insert("night_hints".to_string(), serde_json::json!(false));
+        game.init(&players, &config);
+        assert!(game.state.shot_hints.is_empty());
+
+        // Day holes carry no lanterns and keep their hints
+        config
+            .custom
+            .insert("hole_index".to_string(), serde_json::json!(0));
+        game.init(&players, &config);
+        assert!(!game.state.night);
+        assert!(game.state.lanterns.is_empty());
+        assert!(!game.state.shot_hints.is_empty());
+    }
+
+    #[test]
+    fn ghosted_balls_roll_through_each_other() {
+        let mut course = course::default_course();
+        course.ball_interaction = BallInteraction::Ghost;
+        let mut game = MiniGolf::with_config_and_courses(GolfConfig::default(), vec![course]);
+        let players = make_players(2);
+        game.init(&players, &default_config(90));
+        assert_eq!(game.state.ball_interaction, BallInteraction::Ghost);
+
+        // Two balls rolling head-on along the same line
+        let start = course::Vec3::new(10.0, 0.0, 8.0);
+        for (pid, dx, vx) in [(1, -1.0, 2.0), (2, 1.0, -2.0)] {
+            let ball = game.state.balls.get_mut(&pid).unwrap();
+            ball.position = course::Vec3::new(start.x + dx, 0.0, start.z);
+            ball.velocity = course::Vec3::new(vx, 0.0, 0.0);
+        }
+        let inputs = PlayerInputs {
+            inputs: HashMap::new(),
+        };
+        game.update(0.1, &inputs);
+        assert!(game.state.balls[&1].position.x > start.x);
+        assert!(game.state.balls[&2].position.x < start.x);
+
+        // The lobby setting wins over the course
+        let mut config = default_config(90);
+        config.custom.insert(
+            "ball_interaction".to_string(),
+            serde_json::json!("pass_through"),
+        );
+        game.init(&players, &config);
+        assert_eq!(game.state.ball_interaction, BallInteraction::PassThrough);
+        assert!(!game.state.ball_interaction.ghosted());
+    }
+
+    #[test]
+    fn stroke_undo_rewinds_until_the_ball_touches_something() {
+        let players = make_players(1);
+        let stroke = rmp_serde::to_vec(&GolfInput {
+            aim_angle: 0.0,
+            power: 0.5,
+            stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
+        })
+        .unwrap();
+        let undo = rmp_serde::to_vec(&GolfInput {
+            aim_angle: 0.0,
+            power: 0.0,
+            stroke: false,
+            skip_intro: false,
+            undo: true,
+            caddie: false,
+            screen_aim: None,
+        })
+        .unwrap();
+        let inputs = PlayerInputs {
+            inputs: HashMap::new(),
+        };
+
+        // Off unless the lobby sets a window
+        let mut game = MiniGolf::default();
+        game.init(&players, &default_config(90));
+        game.apply_input(1, &stroke);
+        assert!(game.state.undo_windows.is_empty());
+        game.apply_input(1, &undo);
+        assert_eq!(game.state.strokes[&1], 1);
+
+        let mut config = default_config(90);
+        config
+            .custom
+            .insert("undo_window".to_string(), serde_json::json!(1.5));
+        game.init(&players, &config);
+        let tee = game.state.balls[&1].clone();
+        game.apply_input(1, &stroke);
+        assert_eq!(game.state.undo_windows[&1], 1.5);
+        game.apply_input(1, &undo);
+        assert_eq!(game.state.strokes[&1], 0);
+        assert_eq!(game.state.balls[&1], tee);
+        assert!(game.state.undo_windows.is_empty());
+
+        // A wall bounce closes the window
+        game.apply_input(1, &stroke);
+        game.state.balls.get_mut(&1).unwrap().bounces = 1;
+        game.update(0.1, &inputs);
+        assert!(game.state.undo_windows.is_empty());
+        game.apply_input(1, &undo);
+        assert_eq!(game.state.strokes[&1], 1);
+
+        // So does running out of time
+        game.init(&players, &config);
+        game.apply_input(1, &stroke);
+        game.update(2.0, &inputs);
+        assert!(game.state.undo_windows.is_empty());
+        game.apply_input(1, &undo);
+        assert_eq!(game.state.strokes[&1], 1);
+    }
+
+    #[test]
+    fn caddie_hints_are_budgeted_and_private() {
+        let players = make_players(2);
+        let ask = rmp_serde::to_vec(&GolfInput {
+            aim_angle: 0.0,
+            power: 0.0,
+            stroke: false,
+            skip_intro: false,
+            undo: false,
+            caddie: true,
+            screen_aim: None,
+        })
+        .unwrap();
+        let inputs = PlayerInputs {
+            inputs: HashMap::new(),
+        };
+
+        // Off unless the lobby sets a budget
+        let mut game = MiniGolf::default();
+        game.init(&players, &default_config(90));
+        game.apply_input(1, &ask);
+        assert!(game.caddie_hints.is_empty());
+        assert_eq!(game.private_state_for(1), None);
+
+        let mut config = default_config(90);
+        config
+            .custom
+            .insert("caddie_hints".to_string(), serde_json::json!(1));
+        game.init(&players, &config);
+        game.apply_input(1, &ask);
+        let hint = game.caddie_hints[&1];
+        assert_eq!(game.state.caddie_left[&1], 0);
+        game.apply_input(1, &ask);
+        assert_eq!(game.caddie_hints[&1], hint, "Asking again is free");
+
+        // Only the asker is told the hint, and the shared state never has it
+        let private_of = |game: &MiniGolf, player_id| {
+            let data = game.private_state_for(player_id).unwrap();
+            rmp_serde::from_slice::<GolfPrivateState>(&data).unwrap()
+        };
+        assert_eq!(private_of(&game, 1).caddie_hint, Some(hint));
+        assert_eq!(private_of(&game, 2).caddie_hint, None);
+        let mut client = MiniGolf::default();
+        client.apply_state(&game.serialize_state());
+        assert_eq!(client.caddie_hint(1), None);
+        client.apply_private_state(1, &game.private_state_for(1).unwrap());
+        assert_eq!(client.caddie_hint(1), Some(&hint));
+        client.apply_state(&game.serialize_state());
+        assert_eq!(client.caddie_hint(1), Some(&hint), "Snapshots keep it");
+
+        // Playing the stroke uses up the hint, and the budget is spent
+        let stroke = GolfInput {
+            aim_angle: hint.aim_angle,
+            power: hint.power,
+            stroke: true,
+            skip_intro: false,
+            undo: false,
+            caddie: false,
+            screen_aim: None,
+        };
+        game.apply_input(1, &rmp_serde::to_vec(&stroke).unwrap());
+        game.update(0.1, &inputs);
+        assert!(game.caddie_hints.is_empty());
+        client.apply_private_state(1, &game.private_state_for(1).unwrap());
+        assert_eq!(client.caddie_hint(1), None, "The client drops it too");
+        for _ in 0..200 {
+            game.update(0.1, &inputs);
+        }
+        game.apply_input(1, &ask);
+        assert!(game.caddie_hints.is_empty());
+
+        // The next hole refills it
+        game.init(&players, &config);
+        assert_eq!(game.state.caddie_left[&1], 1);
+    }
+
+    #[test]
+    fn tick_fits_its_budget_at_max_players() {
+        use breakpoint_core::test_helpers::assert_tick_budget;
+
+        let mut game = MiniGolf::new();
+        let players = make_players(game.metadata().max_players as usize);
+        game.init(&players, &default_config(90));
+        game.state.intro_remaining = 0.0;
+        for (i, player) in players.iter().enumerate() {
+            let input = GolfInput {
+                aim_angle: i as f32 * std::f32::consts::TAU / 8.0,
+                power: 0.8,
+                stroke: true,
+                skip_intro: false,
+                undo: false,
+                caddie: false,
+                screen_aim: None,
+            };
+            game.apply_input(player.id, &rmp_serde::to_vec(&input).unwrap());
+        }
+        game.update(
+            0.05,
+            &PlayerInputs {
+                inputs: HashMap::new(),
+            },
+        );
+        let rolling = game.state.balls.clone();
+
+        // Every ball is put back mid-roll before each tick
+        assert_tick_budget(
+            "golf update, 8 players",
+            &mut game,
+            Duration::from_micros(250),
+            |game| game.state.balls.clone_from(&rolling),
+        );
+    }
 }
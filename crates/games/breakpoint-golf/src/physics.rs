@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 
-use crate::course::{Bumper, Course, Vec3, Wall};
+use breakpoint_core::math::{Vec2, closest_point_on_segment};
+
+use crate::course::{Bumper, Course, GravityWell, Vec3, Wall};
 
 /// Ball radius in world units.
 pub const BALL_RADIUS: f32 = 0.3;
@@ -82,6 +84,12 @@ pub struct GolfConfig {
     pub scoring: GolfScoringConfig,
     pub round_duration_secs: f32,
     pub tick_rate_hz: f32,
+    /// Length of the course flyover intro, when the lobby enables it.
+    pub intro_duration_secs: f32,
+    /// Time limit for the one-stroke closest-to-the-pin mode.
+    pub closest_to_pin_secs: f32,
+    /// How far each ball's lantern lights the course on night holes.
+    pub lantern_radius: f32,
 }
 
 impl Default for GolfConfig {
@@ -91,6 +99,9 @@ impl Default for GolfConfig {
             scoring: GolfScoringConfig::default(),
             round_duration_secs: 90.0,
             tick_rate_hz: 10.0,
+            intro_duration_secs: 6.0,
+            closest_to_pin_secs: 60.0,
+            lantern_radius: 5.0,
         }
     }
 }
@@ -114,12 +125,39 @@ impl GolfConfig {
     }
 }
 
+/// How balls in play at the same time treat each other. A course sets its
+/// own with `ball_interaction`, and the lobby setting of the same name
+/// overrides it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BallInteraction {
+    /// Balls roll through each other and are drawn normally.
+    #[default]
+    PassThrough,
+    /// Balls roll through each other, and clients draw other players'
+    /// balls translucent so shared lines never look like a collision.
+    Ghost,
+}
+
+impl BallInteraction {
+    /// Whether clients draw other players' balls translucent.
+    pub fn ghosted(self) -> bool {
+        matches!(self, Self::Ghost)
+    }
+}
+
 /// State of a single ball on the course.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
 pub struct BallState {
     pub position: Vec3,
     pub velocity: Vec3,
     pub is_sunk: bool,
+    /// Wall bounces since the last stroke.
+    #[serde(default)]
+    pub bounces: u8,
+    /// Bumper hits since the last stroke.
+    #[serde(default)]
+    pub bumper_hits: u8,
 }
 
 impl BallState {
@@ -128,12 +166,24 @@ impl BallState {
             position: spawn,
             velocity: Vec3::ZERO,
             is_sunk: false,
+            bounces: 0,
+            bumper_hits: 0,
         }
     }
 
+    /// Speed on the XZ plane.
+    pub fn speed(&self) -> f32 {
+        self.velocity.xz().length()
+    }
+
     /// Whether the ball is effectively stationary.
     pub fn is_stopped(&self) -> bool {
-        self.is_sunk || velocity_magnitude(&self.velocity) < MIN_VELOCITY
+        self.is_sunk || self.speed() < MIN_VELOCITY
+    }
+
+    /// Distance on the XZ plane from the ball to the hole.
+    pub fn distance_to_hole(&self, course: &Course) -> f32 {
+        self.position.distance_xz(course.hole_position)
     }
 
     /// Apply a stroke impulse at the given angle (radians) and power (0..MAX_POWER).
@@ -147,6 +197,14 @@ impl BallState {
         let p = power.clamp(0.0, MAX_POWER);
         self.velocity.x = angle.cos() * p;
         self.velocity.z = angle.sin() * p;
+        self.bounces = 0;
+        self.bumper_hits = 0;
+    }
+
+    /// Whether the ball has touched a wall, bumper, or the cup since the
+    /// last stroke.
+    pub fn has_contact(&self) -> bool {
+        self.is_sunk || self.bounces > 0 || self.bumper_hits > 0
     }
 
     /// Advance the ball by one tick on the given course.
@@ -155,12 +213,20 @@ impl BallState {
             return;
         }
 
+        // Wells only bend a rolling ball, so a ball at rest stays put
+        let rolling = !self.is_stopped();
         let dt = 1.0 / SUBSTEPS as f32;
         for _ in 0..SUBSTEPS {
             if self.is_sunk {
                 break;
             }
 
+            if rolling {
+                for well in &course.gravity_wells {
+                    self.pull_toward(well, dt);
+                }
+            }
+
             // Move
             self.position.x += self.velocity.x * dt;
             self.position.z += self.velocity.z * dt;
@@ -179,10 +245,8 @@ impl BallState {
             self.clamp_to_bounds(course.width, course.depth);
 
             // Hole detection (ball near-stationary at hole)
-            let dx = self.position.x - course.hole_position.x;
-            let dz = self.position.z - course.hole_position.z;
-            let dist = (dx * dx + dz * dz).sqrt();
-            if dist < HOLE_RADIUS && velocity_magnitude(&self.velocity) < HOLE_SINK_SPEED {
+            let dist = self.distance_to_hole(course);
+            if dist < HOLE_RADIUS && self.speed() < HOLE_SINK_SPEED {
                 self.is_sunk = true;
                 self.velocity = Vec3::ZERO;
                 self.position = course.hole_position;
@@ -194,34 +258,35 @@ impl BallState {
         self.velocity.z *= FRICTION;
 
         // Stop if below threshold
-        if velocity_magnitude(&self.velocity) < MIN_VELOCITY {
+        if self.speed() < MIN_VELOCITY {
             self.velocity = Vec3::ZERO;
         }
     }
 
+    fn pull_toward(&mut self, well: &GravityWell, dt: f32) {
+        let Vec2 { x: dx, y: dz } = well.position.xz() - self.position.xz();
+        let dist = self.position.distance_xz(well.position);
+        if dist >= well.radius || dist < 1e-6 {
+            return;
+        }
+        // Linear falloff from full strength at the center, along the unit
+        // vector toward it (away from it for negative strength)
+        let pull = well.strength * (1.0 - dist / well.radius) * dt / dist;
+        self.velocity.x += dx * pull;
+        self.velocity.z += dz * pull;
+    }
+
     fn collide_wall(&mut self, wall: &Wall) {
         // 2D line-segment collision on XZ plane
-        let ax = wall.a.x;
-        let az = wall.a.z;
-        let bx = wall.b.x;
-        let bz = wall.b.z;
-
-        let dx = bx - ax;
-        let dz = bz - az;
-        let len_sq = dx * dx + dz * dz;
-        if len_sq < 1e-6 {
+        let (a, b) = (wall.a.xz(), wall.b.xz());
+        // Degenerate (zero-length) walls don't collide
+        if (b - a).length_squared() < 1e-6 {
             return;
         }
-
-        // Project ball center onto wall segment
-        let t = ((self.position.x - ax) * dx + (self.position.z - az) * dz) / len_sq;
-        let t = t.clamp(0.0, 1.0);
-        let closest_x = ax + t * dx;
-        let closest_z = az + t * dz;
-
-        let nx = self.position.x - closest_x;
-        let nz = self.position.z - closest_z;
-        let dist = (nx * nx + nz * nz).sqrt();
+        let center = self.position.xz();
+        let closest = closest_point_on_segment(center, a, b);
+        let Vec2 { x: nx, y: nz } = center - closest;
+        let dist = center.distance(closest);
 
         if dist < BALL_RADIUS && dist > 1e-6 {
             // Normalize
@@ -242,14 +307,14 @@ impl BallState {
                 // Slight energy loss on wall bounce
                 self.velocity.x *= WALL_BOUNCE_RESTITUTION;
                 self.velocity.z *= WALL_BOUNCE_RESTITUTION;
+                self.bounces = self.bounces.saturating_add(1);
             }
         }
     }
 
     fn collide_bumper(&mut self, bumper: &Bumper) {
-        let dx = self.position.x - bumper.position.x;
-        let dz = self.position.z - bumper.position.z;
-        let dist = (dx * dx + dz * dz).sqrt();
+        let Vec2 { x: dx, y: dz } = self.position.xz() - bumper.position.xz();
+        let dist = self.position.distance_xz(bumper.position);
         let min_dist = BALL_RADIUS + bumper.radius;
 
         if dist < min_dist && dist > 1e-6 {
@@ -265,6 +330,7 @@ impl BallState {
             // Bounce away at fixed speed
             self.velocity.x = nx * bumper.bounce_speed;
             self.velocity.z = nz * bumper.bounce_speed;
+            self.bumper_hits = self.bumper_hits.saturating_add(1);
         }
     }
 
@@ -288,10 +354,6 @@ impl BallState {
     }
 }
 
-fn velocity_magnitude(v: &Vec3) -> f32 {
-    (v.x * v.x + v.z * v.z).sqrt()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -331,6 +393,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn zero_length_wall_does_not_collide() {
+        let point = Vec3::new(5.0, 0.0, 5.0);
+        let wall = Wall {
+            a: point,
+            b: point,
+            height: 1.0,
+        };
+        let mut ball = BallState::new(Vec3::new(5.0 + BALL_RADIUS * 0.5, 0.0, 5.0));
+        ball.velocity = Vec3::new(-2.0, 0.0, 0.0);
+
+        ball.collide_wall(&wall);
+
+        assert_eq!(ball.position.x, 5.0 + BALL_RADIUS * 0.5);
+        assert_eq!(ball.velocity.x, -2.0);
+        assert_eq!(ball.bounces, 0);
+    }
+
     #[test]
     fn ball_sinks_in_hole() {
         let course = default_course();
@@ -403,12 +483,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn gravity_wells_bend_rolling_balls() {
+        let roll = |strength: f32| {
+            let mut course = default_course();
+            course.bumpers.clear();
+            course.gravity_wells.push(GravityWell {
+                position: Vec3::new(10.0, 0.0, 7.0),
+                radius: 4.0,
+                strength,
+            });
+            let mut ball = BallState::new(Vec3::new(3.0, 0.0, 5.0));
+            ball.velocity = Vec3::new(3.0, 0.0, 0.0);
+            for _ in 0..5 {
+                ball.tick(&course);
+            }
+            ball.position.z
+        };
+        assert_eq!(roll(0.0), 5.0);
+        assert!(roll(0.5) > 5.1, "Attractor pulls the ball toward it");
+        assert!(roll(-0.5) < 4.9, "Repulsor pushes the ball away");
+        assert_eq!(roll(0.5), roll(0.5), "Deterministic");
+
+        // A ball at rest inside a well stays put
+        let mut course = default_course();
+        course.gravity_wells.push(GravityWell {
+            position: Vec3::new(10.0, 0.0, 7.0),
+            radius: 4.0,
+            strength: 2.0,
+        });
+        let mut ball = BallState::new(Vec3::new(9.0, 0.0, 7.0));
+        ball.tick(&course);
+        assert_eq!(ball.position, Vec3::new(9.0, 0.0, 7.0));
+    }
+
     #[test]
     fn stroke_power_clamped() {
         let course = default_course();
         let mut ball = BallState::new(course.spawn_point);
         ball.stroke(0.0, MAX_POWER * 2.0);
-        let speed = velocity_magnitude(&ball.velocity);
+        let speed = ball.speed();
         assert!(
             (speed - MAX_POWER).abs() < 0.01,
             "Power should be clamped to MAX_POWER, got {speed}"
@@ -575,7 +689,7 @@ mod tests {
         let mut ball = BallState::new(Vec3::new(5.0, 0.0, 5.0));
         let half = MAX_POWER * 0.5;
         ball.stroke(0.0, half);
-        let speed = velocity_magnitude(&ball.velocity);
+        let speed = ball.speed();
         assert!(
             (speed - half).abs() < 0.01,
             "Half power should give half speed, got {speed}"
@@ -586,7 +700,7 @@ mod tests {
     fn stroke_full_power_max_speed() {
         let mut ball = BallState::new(Vec3::new(5.0, 0.0, 5.0));
         ball.stroke(0.0, MAX_POWER);
-        let speed = velocity_magnitude(&ball.velocity);
+        let speed = ball.speed();
         assert!(
             (speed - MAX_POWER).abs() < 0.01,
             "Full power should give MAX_POWER speed, got {speed}"
@@ -599,7 +713,7 @@ mod tests {
         let min_power = 0.15 * MAX_POWER;
         let mut ball = BallState::new(Vec3::new(5.0, 0.0, 5.0));
         ball.stroke(0.0, min_power);
-        let speed = velocity_magnitude(&ball.velocity);
+        let speed = ball.speed();
         assert!(
             speed > MIN_VELOCITY,
             "Minimum client power ({min_power}) should produce movement above MIN_VELOCITY, \
@@ -622,7 +736,7 @@ mod tests {
         for angle in angles {
             let mut ball = BallState::new(Vec3::new(5.0, 0.0, 5.0));
             ball.stroke(angle, power);
-            let speed = velocity_magnitude(&ball.velocity);
+            let speed = ball.speed();
             assert!(
                 (speed - power).abs() < 0.01,
                 "angle={angle:.3}: |velocity| should equal power ({power}), got {speed}"
@@ -657,7 +771,7 @@ mod tests {
     fn stroke_with_inf_power_clamps_to_max() {
         let mut ball = BallState::new(Vec3::new(5.0, 0.0, 5.0));
         ball.stroke(0.0, f32::INFINITY);
-        let speed = velocity_magnitude(&ball.velocity);
+        let speed = ball.speed();
         assert!(
             (speed - MAX_POWER).abs() < 0.01,
             "Inf power should clamp to MAX_POWER ({MAX_POWER}), got {speed}"
@@ -669,7 +783,7 @@ mod tests {
     fn stroke_with_neg_inf_power_clamps_to_zero() {
         let mut ball = BallState::new(Vec3::new(5.0, 0.0, 5.0));
         ball.stroke(0.0, f32::NEG_INFINITY);
-        let speed = velocity_magnitude(&ball.velocity);
+        let speed = ball.speed();
         assert!(
             speed < 0.01,
             "Negative Inf power should clamp to 0, got {speed}"
@@ -696,7 +810,7 @@ mod tests {
             ) {
                 let mut ball = BallState::new(Vec3::new(5.0, 0.0, 5.0));
                 ball.stroke(angle, power);
-                let speed = velocity_magnitude(&ball.velocity);
+                let speed = ball.speed();
                 let clamped = power.clamp(0.0, MAX_POWER);
                 prop_assert!(
                     (speed - clamped).abs() < 0.01,
@@ -830,7 +944,7 @@ mod tests {
                 // Place ball near the L-wall corner area
                 let mut ball = BallState::new(Vec3::new(14.0, 0.0, 15.0));
                 ball.stroke(angle, MAX_POWER);
-                let initial_dist = velocity_magnitude(&ball.velocity);
+                let initial_dist = ball.speed();
 
                 for _ in 0..300 {
                     ball.tick(&course);
@@ -48,10 +48,72 @@ pub fn calculate_score_with_config(
     score
 }
 
+/// Where a player's ball ended up in closest-to-the-pin.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PinResult {
+    Sunk,
+    /// At rest this far from the hole.
+    Distance(f32),
+    /// Never took the stroke.
+    NoStroke,
+}
+
+impl PinResult {
+    /// Sort key: sunk balls first, then nearest to the hole.
+    fn rank_key(self) -> Option<f32> {
+        match self {
+            Self::Sunk => Some(-1.0),
+            Self::Distance(d) => Some(d),
+            Self::NoStroke => None,
+        }
+    }
+}
+
+/// Closest-to-the-pin scores, one per result in the same order.
+///
+/// Players earn one point for every player they finished strictly closer
+/// than, plus one, so the nearest of `n` scores `n` and equal distances
+/// share a score. Sinking counts as nearest of all and adds
+/// `first_sink_bonus`. Not taking the stroke scores `dnf_penalty`.
+pub fn closest_to_pin_scores(results: &[PinResult], config: &GolfScoringConfig) -> Vec<i32> {
+    let stroked: Vec<f32> = results.iter().filter_map(|r| r.rank_key()).collect();
+    results
+        .iter()
+        .map(|&result| {
+            let Some(key) = result.rank_key() else {
+                return config.dnf_penalty;
+            };
+            let beaten = stroked.iter().filter(|&&other| other > key).count() as i32;
+            let score = beaten + 1;
+            if result == PinResult::Sunk {
+                score + config.first_sink_bonus
+            } else {
+                score
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn closest_to_pin_ranks_by_distance_and_sinking_trumps_all() {
+        let scores = closest_to_pin_scores(
+            &[
+                PinResult::Distance(4.0),
+                PinResult::Sunk,
+                PinResult::Distance(1.5),
+                PinResult::NoStroke,
+                PinResult::Distance(1.5),
+            ],
+            &GolfScoringConfig::default(),
+        );
+        // Sunk beats all three other strokes: 3 + 1 + 3 bonus
+        assert_eq!(scores, vec![1, 7, 2, -1, 2]);
+    }
+
     #[test]
     fn first_sink_under_par() {
         // Par 3, 1 stroke, first to sink: (3-1)*2 + 3 = 7
@@ -0,0 +1,190 @@
+use breakpoint_core::game_settings::{
+    CustomSettings, FromGameConfig, SettingsError, SettingsReader,
+};
+use breakpoint_core::late_join::LateJoinPolicy;
+use breakpoint_core::round_phase::{self, MICRO_ROUNDS_SETTING};
+
+use crate::physics::BallInteraction;
+
+/// Longest stroke undo window a lobby can set, in seconds.
+pub const MAX_UNDO_WINDOW_SECS: f32 = 5.0;
+
+/// Most caddie hints a lobby can give each player per hole.
+pub const MAX_CADDIE_HINTS: u32 = 10;
+
+/// Golf lobby settings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GolfSettings {
+    /// `mode`: `course` (the default) or `closest_to_pin`.
+    pub closest_to_pin: bool,
+    /// `seed`: picks the closest-to-the-pin course; random when unset.
+    pub seed: Option<u64>,
+    /// `hole_index`: the hole this round plays, set by the host per round.
+    pub hole_index: usize,
+    /// `course_intro`: fly over the course before play.
+    pub course_intro: bool,
+    /// `shot_hints`: publish blind-shot aim hints.
+    pub shot_hints: bool,
+    /// `night_hints`: keep blind-shot aim hints on night holes.
+    pub night_hints: bool,
+    /// `undo_window`: seconds a stroke can be taken back; zero is off.
+    pub undo_window_secs: f32,
+    /// `caddie_hints`: stroke suggestions each player may ask for per
+    /// hole; zero is off.
+    pub caddie_hints: u32,
+    /// `ball_interaction`: `pass_through` or `ghost`; the course's own
+    /// choice when unset.
+    pub ball_interaction: Option<BallInteraction>,
+    /// `micro_rounds`: caps each hole at this many seconds. The flyover
+    /// intro would eat most of that, so the two can't be combined.
+    pub micro_round_secs: Option<f32>,
+    /// `late_join`: `spawn` (the default) tees late joiners off on the
+    /// current hole; `spectate` has them watch until the next one.
+    pub late_join: LateJoinPolicy,
+}
+
+impl Default for GolfSettings {
+    fn default() -> Self {
+        Self {
+            closest_to_pin: false,
+            seed: None,
+            hole_index: 0,
+            course_intro: false,
+            shot_hints: true,
+            night_hints: true,
+            undo_window_secs: 0.0,
+            caddie_hints: 0,
+            ball_interaction: None,
+            micro_round_secs: None,
+            late_join: LateJoinPolicy::SpawnImmediately,
+        }
+    }
+}
+
+impl FromGameConfig for GolfSettings {
+    fn from_custom(custom: &CustomSettings) -> Result<Self, SettingsError> {
+        let reader = SettingsReader::new(custom);
+        let defaults = Self::default();
+        let micro_round_secs = round_phase::micro_round_secs(custom)?;
+        let course_intro = reader
+            .bool("course_intro")?
+            .unwrap_or(defaults.course_intro);
+        if micro_round_secs.is_some() && course_intro {
+            return Err(SettingsError::new(
+                MICRO_ROUNDS_SETTING,
+                "micro-rounds can't be combined with the course intro",
+            ));
+        }
+        Ok(Self {
+            closest_to_pin: reader
+                .choice(
+                    "mode",
+                    &[("course", false), (crate::MODE_CLOSEST_TO_PIN, true)],
+                )?
+                .unwrap_or(defaults.closest_to_pin),
+            seed: reader.u64("seed")?,
+            hole_index: reader
+                .u64("hole_index")?
+                .map_or(defaults.hole_index, |i| i as usize),
+            course_intro,
+            shot_hints: reader.bool("shot_hints")?.unwrap_or(defaults.shot_hints),
+            night_hints: reader.bool("night_hints")?.unwrap_or(defaults.night_hints),
+            undo_window_secs: reader
+                .f32_in("undo_window", 0.0, MAX_UNDO_WINDOW_SECS)?
+                .unwrap_or(defaults.undo_window_secs),
+            caddie_hints: match reader.u64("caddie_hints")? {
+                None => defaults.caddie_hints,
+                Some(n) if n <= u64::from(MAX_CADDIE_HINTS) => n as u32,
+                Some(n) => {
+                    return Err(SettingsError::new(
+                        "caddie_hints",
+                        format!("{n} is more than {MAX_CADDIE_HINTS}"),
+                    ));
+                },
+            },
+            ball_interaction: reader.choice(
+                "ball_interaction",
+                &[
+                    ("pass_through", BallInteraction::PassThrough),
+                    ("ghost", BallInteraction::Ghost),
+                ],
+            )?,
+            micro_round_secs,
+            late_join: LateJoinPolicy::from_custom(
+                custom,
+                defaults.late_join,
+                &[LateJoinPolicy::Spectate, LateJoinPolicy::SpawnImmediately],
+            )?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use breakpoint_core::game_trait::GameConfig;
+
+    #[test]
+    fn settings_read_lobby_values_and_reject_bad_ones() {
+        let config = GameConfig::builder()
+            .setting("mode", crate::MODE_CLOSEST_TO_PIN)
+            .setting("hole_index", 2)
+            .setting("undo_window", 1.5)
+            .setting("shot_hints", false)
+            .setting("night_hints", false)
+            .setting("caddie_hints", 3)
+            .setting("ball_interaction", "ghost")
+            .build();
+        let settings = GolfSettings::from_game_config(&config).unwrap();
+        assert!(settings.closest_to_pin);
+        assert_eq!(settings.hole_index, 2);
+        assert_eq!(settings.undo_window_secs, 1.5);
+        assert!(!settings.shot_hints);
+        assert!(!settings.night_hints);
+        assert_eq!(settings.caddie_hints, 3);
+        assert_eq!(settings.ball_interaction, Some(BallInteraction::Ghost));
+        assert!(!settings.course_intro, "Unset keys keep their defaults");
+
+        let too_long = GameConfig::builder()
+            .setting("undo_window", MAX_UNDO_WINDOW_SECS + 1.0)
+            .build();
+        let err = GolfSettings::validate(&too_long.custom).unwrap_err();
+        assert_eq!(err.key, "undo_window");
+        let too_many = GameConfig::builder()
+            .setting("caddie_hints", MAX_CADDIE_HINTS + 1)
+            .build();
+        assert_eq!(
+            GolfSettings::validate(&too_many.custom).unwrap_err().key,
+            "caddie_hints"
+        );
+        let bumpers = GameConfig::builder()
+            .setting("ball_interaction", "bumper")
+            .build();
+        assert_eq!(
+            GolfSettings::validate(&bumpers.custom).unwrap_err().key,
+            "ball_interaction"
+        );
+        let micro = GameConfig::builder()
+            .setting(MICRO_ROUNDS_SETTING, 30)
+            .build();
+        assert_eq!(
+            GolfSettings::from_game_config(&micro)
+                .unwrap()
+                .micro_round_secs,
+            Some(30.0)
+        );
+        let micro_intro = GameConfig::builder()
+            .setting(MICRO_ROUNDS_SETTING, true)
+            .setting("course_intro", true)
+            .build();
+        assert_eq!(
+            GolfSettings::validate(&micro_intro.custom).unwrap_err().key,
+            MICRO_ROUNDS_SETTING
+        );
+        let bad_mode = GameConfig::builder().setting("mode", "skins").build();
+        assert_eq!(
+            GolfSettings::validate(&bad_mode.custom).unwrap_err().key,
+            "mode"
+        );
+    }
+}
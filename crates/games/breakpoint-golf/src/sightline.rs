@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+
+use breakpoint_core::math::segments_cross;
+
+use crate::course::{Course, Vec3};
+use crate::physics::BALL_RADIUS;
+
+/// Eye height for line-of-sight checks. Walls lower than this are seen
+/// over, so only walls at least this tall can make a shot blind.
+pub const SIGHT_HEIGHT: f32 = BALL_RADIUS * 2.0;
+
+/// How far past a wall end a suggested aim point sits, so the ball clears
+/// the corner instead of clipping it.
+const CORNER_CLEARANCE: f32 = 1.0;
+
+/// What a player at rest can see of the cup, computed on the host.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ShotHint {
+    /// Whether a straight line from the ball to the cup clears every wall.
+    pub hole_visible: bool,
+    /// Suggested aim in radians (same convention as `GolfInput::aim_angle`).
+    /// Points at the cup when it is visible, otherwise past the wall end
+    /// that opens the shortest route to it.
+    pub aim_angle: f32,
+    /// Height of the tallest wall between the ball and the cup, or zero
+    /// when the cup is visible.
+    pub occluder_height: f32,
+}
+
+/// Work out the hint for a ball at `from` on `course`.
+pub fn shot_hint(course: &Course, from: Vec3) -> ShotHint {
+    let hole = course.hole_position;
+    let Some(occluder_height) = tallest_occluder(course, from, hole) else {
+        return ShotHint {
+            hole_visible: true,
+            aim_angle: angle_to(from, hole),
+            occluder_height: 0.0,
+        };
+    };
+
+    // Shortest one-corner route: ball -> wall end -> cup, both legs clear.
+    // Failing that, the visible wall end nearest the cup.
+    let mut best_route: Option<(f32, Vec3)> = None;
+    let mut best_fallback: Option<(f32, Vec3)> = None;
+    for point in corner_points(course) {
+        if tallest_occluder(course, from, point).is_some() {
+            continue;
+        }
+        let to_hole = point.distance_xz(hole);
+        if tallest_occluder(course, point, hole).is_none() {
+            let length = from.distance_xz(point) + to_hole;
+            if best_route.is_none_or(|(best, _)| length < best) {
+                best_route = Some((length, point));
+            }
+        } else if best_fallback.is_none_or(|(best, _)| to_hole < best) {
+            best_fallback = Some((to_hole, point));
+        }
+    }
+    let target = best_route.or(best_fallback).map_or(hole, |(_, p)| p);
+
+    ShotHint {
+        hole_visible: false,
+        aim_angle: angle_to(from, target),
+        occluder_height,
+    }
+}
+
+/// Height of the tallest sight-blocking wall crossed by the segment `a`-`b`
+/// on the XZ plane, if any.
+fn tallest_occluder(course: &Course, a: Vec3, b: Vec3) -> Option<f32> {
+    course
+        .walls
+        .iter()
+        .filter(|w| w.height >= SIGHT_HEIGHT && segments_cross(a.xz(), b.xz(), w.a.xz(), w.b.xz()))
+        .map(|w| w.height)
+        .reduce(f32::max)
+}
+
+/// Candidate aim points just beyond each end of every blocking wall,
+/// inside the course bounds.
+fn corner_points(course: &Course) -> impl Iterator<Item = Vec3> + '_ {
+    const OFFSETS: [(f32, f32); 4] = [(1.0, 1.0), (1.0, -1.0), (-1.0, 1.0), (-1.0, -1.0)];
+    course
+        .walls
+        .iter()
+        .filter(|w| w.height >= SIGHT_HEIGHT)
+        .flat_map(|w| [w.a, w.b])
+        .flat_map(|p| {
+            OFFSETS.iter().map(move |&(dx, dz)| {
+                Vec3::new(
+                    p.x + dx * CORNER_CLEARANCE,
+                    0.0,
+                    p.z + dz * CORNER_CLEARANCE,
+                )
+            })
+        })
+        .filter(|p| {
+            p.x > BALL_RADIUS
+                && p.x < course.width - BALL_RADIUS
+                && p.z > BALL_RADIUS
+                && p.z < course.depth - BALL_RADIUS
+        })
+}
+
+fn angle_to(from: Vec3, to: Vec3) -> f32 {
+    (to.z - from.z).atan2(to.x - from.x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::course::default_course;
+
+    #[test]
+    fn open_shot_aims_at_the_cup() {
+        let course = default_course();
+        // Above the L-shaped obstacle, straight below the cup
+        let hint = shot_hint(&course, Vec3::new(10.0, 0.0, 24.0));
+        assert!(hint.hole_visible);
+        assert!((hint.aim_angle - std::f32::consts::FRAC_PI_2).abs() < 1e-4);
+        assert_eq!(hint.occluder_height, 0.0);
+    }
+
+    #[test]
+    fn blind_shot_aims_around_the_wall() {
+        let course = default_course();
+        let hint = shot_hint(&course, course.spawn_point);
+        assert!(!hint.hole_visible, "L obstacle blocks the tee");
+        assert_eq!(hint.occluder_height, 1.0);
+        // The open side of the L is to the left (-X), and forward (+Z)
+        assert!(hint.aim_angle.cos() < 0.0, "aim {}", hint.aim_angle);
+        assert!(hint.aim_angle.sin() > 0.0, "aim {}", hint.aim_angle);
+    }
+
+    #[test]
+    fn low_walls_do_not_block_sight() {
+        let mut course = default_course();
+        for wall in &mut course.walls {
+            wall.height = SIGHT_HEIGHT / 2.0;
+        }
+        assert!(shot_hint(&course, course.spawn_point).hole_visible);
+    }
+}
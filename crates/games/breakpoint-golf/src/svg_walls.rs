@@ -0,0 +1,443 @@
+//! Wall layouts drawn as SVG. Course authors can draw a course's walls in
+//! any vector editor and save them next to the course JSON; at load time
+//! every straight-edged shape becomes a run of [`Wall`]s. SVG x maps to
+//! course x and SVG y to course z, one user unit to one course unit.
+//!
+//! Only straight edges are understood: `<path>` with `M`/`L`/`H`/`V`/`Z`
+//! (absolute or relative), `<line>`, `<polyline>`, `<polygon>` and
+//! `<rect>`. Curves and `transform` attributes are rejected rather than
+//! guessed at. A shape's `data-height` attribute sets its wall height.
+
+use crate::course::{Vec3, Wall};
+
+/// Height of walls whose shape has no `data-height`.
+pub const DEFAULT_WALL_HEIGHT: f32 = 1.0;
+
+/// Points closer than this to the straight line between their neighbours
+/// are dropped, so hand-drawn strokes don't turn into dozens of walls.
+pub const SIMPLIFY_TOLERANCE: f32 = 0.05;
+
+/// Most walls one SVG file may produce.
+pub const MAX_SVG_WALLS: usize = 256;
+
+/// Walls outside the course by more than this are rejected.
+const BOUNDS_SLACK: f32 = 0.01;
+
+/// Convert the straight-edged shapes in `svg` into walls.
+pub fn walls_from_svg(svg: &str) -> Result<Vec<Wall>, String> {
+    let mut walls = Vec::new();
+    for tag in tags(svg)? {
+        if tag.attr("transform").is_some() {
+            return Err(format!(
+                "<{}> has a transform, which is not supported",
+                tag.name
+            ));
+        }
+        let height = match tag.attr("data-height") {
+            Some(h) => parse_number(h)
+                .filter(|h| h.is_finite() && *h > 0.0)
+                .ok_or_else(|| format!("<{}> has an invalid data-height {h:?}", tag.name))?,
+            None => DEFAULT_WALL_HEIGHT,
+        };
+        for line in shape_lines(&tag)? {
+            let points = simplify(&line, SIMPLIFY_TOLERANCE);
+            for pair in points.windows(2) {
+                let (a, b) = (pair[0], pair[1]);
+                if distance(a, b) <= f32::EPSILON {
+                    continue;
+                }
+                walls.push(Wall {
+                    a: Vec3::new(a.0, 0.0, a.1),
+                    b: Vec3::new(b.0, 0.0, b.1),
+                    height,
+                });
+            }
+        }
+        if walls.len() > MAX_SVG_WALLS {
+            return Err(format!("more than {MAX_SVG_WALLS} walls"));
+        }
+    }
+    if walls.is_empty() {
+        return Err("no straight-edged shapes found".to_string());
+    }
+    Ok(walls)
+}
+
+/// Check that every wall lies on a `width` x `depth` course.
+pub fn check_bounds(walls: &[Wall], width: f32, depth: f32) -> Result<(), String> {
+    let inside = |p: &Vec3| {
+        (-BOUNDS_SLACK..=width + BOUNDS_SLACK).contains(&p.x)
+            && (-BOUNDS_SLACK..=depth + BOUNDS_SLACK).contains(&p.z)
+    };
+    match walls.iter().find(|w| !inside(&w.a) || !inside(&w.b)) {
+        Some(w) => Err(format!(
+            "wall ({}, {}) to ({}, {}) is outside the {width} x {depth} course",
+            w.a.x, w.a.z, w.b.x, w.b.z
+        )),
+        None => Ok(()),
+    }
+}
+
+type Point = (f32, f32);
+
+/// One start tag and its attributes.
+struct Tag<'a> {
+    name: &'a str,
+    attrs: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Tag<'a> {
+    fn attr(&self, name: &str) -> Option<&'a str> {
+        self.attrs.iter().find(|(n, _)| *n == name).map(|(_, v)| *v)
+    }
+
+    fn number(&self, name: &str) -> Result<f32, String> {
+        let raw = self
+            .attr(name)
+            .ok_or_else(|| format!("<{}> is missing {name}", self.name))?;
+        parse_number(raw)
+            .filter(|n| n.is_finite())
+            .ok_or_else(|| format!("<{}> has an invalid {name} {raw:?}", self.name))
+    }
+}
+
+/// The start tags of `svg`, skipping comments, declarations and end tags.
+fn tags(svg: &str) -> Result<Vec<Tag<'_>>, String> {
+    let mut tags = Vec::new();
+    let mut rest = svg;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        if let Some(body) = rest.strip_prefix("!--") {
+            let end = body.find("-->").ok_or("unterminated comment")?;
+            rest = &body[end + 3..];
+            continue;
+        }
+        let end = rest.find('>').ok_or("unterminated tag")?;
+        let inner = &rest[..end];
+        rest = &rest[end + 1..];
+        if inner.starts_with(['/', '?', '!']) {
+            continue;
+        }
+        let inner = inner.strip_suffix('/').unwrap_or(inner).trim();
+        let name_end = inner
+            .find(|c: char| c.is_ascii_whitespace())
+            .unwrap_or(inner.len());
+        let (name, mut attrs_src) = inner.split_at(name_end);
+        let mut attrs = Vec::new();
+        loop {
+            attrs_src = attrs_src.trim_start();
+            if attrs_src.is_empty() {
+                break;
+            }
+            let eq = attrs_src
+                .find('=')
+                .ok_or_else(|| format!("<{name}> has a malformed attribute"))?;
+            let attr_name = attrs_src[..eq].trim();
+            let value_src = attrs_src[eq + 1..].trim_start();
+            let quote = value_src
+                .chars()
+                .next()
+                .filter(|c| *c == '"' || *c == '\'')
+                .ok_or_else(|| format!("<{name}> attribute {attr_name} is not quoted"))?;
+            let value_end = value_src[1..]
+                .find(quote)
+                .ok_or_else(|| format!("<{name}> attribute {attr_name} is not closed"))?;
+            attrs.push((attr_name, &value_src[1..1 + value_end]));
+            attrs_src = &value_src[value_end + 2..];
+        }
+        tags.push(Tag { name, attrs });
+    }
+    Ok(tags)
+}
+
+/// The polylines a shape draws; tags that draw nothing give none.
+fn shape_lines(tag: &Tag<'_>) -> Result<Vec<Vec<Point>>, String> {
+    match tag.name {
+        "path" => path_lines(tag.attr("d").unwrap_or("")),
+        "line" => Ok(vec![vec![
+            (tag.number("x1")?, tag.number("y1")?),
+            (tag.number("x2")?, tag.number("y2")?),
+        ]]),
+        "polyline" | "polygon" => {
+            let numbers = numbers(tag.attr("points").unwrap_or(""))?;
+            if numbers.len() % 2 != 0 {
+                return Err(format!("<{}> has an odd number of coordinates", tag.name));
+            }
+            let mut points: Vec<Point> = numbers.chunks(2).map(|c| (c[0], c[1])).collect();
+            if tag.name == "polygon" && !points.is_empty() {
+                points.push(points[0]);
+            }
+            Ok(vec![points])
+        },
+        "rect" => {
+            let origin = |name| tag.attr(name).map_or(Ok(0.0), |_| tag.number(name));
+            let (x, y) = (origin("x")?, origin("y")?);
+            let (w, h) = (tag.number("width")?, tag.number("height")?);
+            Ok(vec![vec![
+                (x, y),
+                (x + w, y),
+                (x + w, y + h),
+                (x, y + h),
+                (x, y),
+            ]])
+        },
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// The polylines of path data made only of straight commands.
+fn path_lines(d: &str) -> Result<Vec<Vec<Point>>, String> {
+    let mut lines: Vec<Vec<Point>> = Vec::new();
+    let mut current: Vec<Point> = Vec::new();
+    let mut pen: Point = (0.0, 0.0);
+    let mut command = None;
+    let mut rest = d;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_ascii_whitespace() || c == ',');
+        let Some(c) = rest.chars().next() else { break };
+        if c.is_ascii_alphabetic() {
+            rest = &rest[1..];
+            match c {
+                'M' | 'm' | 'L' | 'l' | 'H' | 'h' | 'V' | 'v' => command = Some(c),
+                'Z' | 'z' => {
+                    if let Some(&first) = current.first() {
+                        current.push(first);
+                        pen = first;
+                    }
+                    if current.len() > 1 {
+                        lines.push(std::mem::take(&mut current));
+                    }
+                    // A subpath drawn after a closepath starts where it closed
+                    current = vec![pen];
+                    command = None;
+                    continue;
+                },
+                _ => return Err(format!("path command {c} is not a straight edge")),
+            }
+            // A bare letter with no coordinates is not valid path data,
+            // so fall through to read its first operand.
+        }
+        let cmd = command.ok_or("path data must start with a command")?;
+        let relative = cmd.is_ascii_lowercase();
+        let base = if relative { pen } else { (0.0, 0.0) };
+        let (next, after) = match cmd.to_ascii_uppercase() {
+            'M' | 'L' => {
+                let (x, after) = take_number(rest)?;
+                let (y, after) = take_number(after)?;
+                ((base.0 + x, base.1 + y), after)
+            },
+            'H' => {
+                let (x, after) = take_number(rest)?;
+                ((base.0 + x, pen.1), after)
+            },
+            _ => {
+                let (y, after) = take_number(rest)?;
+                ((pen.0, base.1 + y), after)
+            },
+        };
+        rest = after;
+        if cmd.eq_ignore_ascii_case(&'M') {
+            if current.len() > 1 {
+                lines.push(std::mem::take(&mut current));
+            }
+            current.clear();
+            // Coordinates after a moveto are implicit linetos
+            command = Some(if relative { 'l' } else { 'L' });
+        }
+        current.push(next);
+        pen = next;
+    }
+    if current.len() > 1 {
+        lines.push(current);
+    }
+    Ok(lines)
+}
+
+/// Every number in a whitespace or comma separated list.
+fn numbers(src: &str) -> Result<Vec<f32>, String> {
+    let mut out = Vec::new();
+    let mut rest = src;
+    while !rest
+        .trim_start_matches(|c: char| c.is_ascii_whitespace() || c == ',')
+        .is_empty()
+    {
+        let (n, after) = take_number(rest)?;
+        out.push(n);
+        rest = after;
+    }
+    Ok(out)
+}
+
+/// Read one number off the front of `src`. Path data may run numbers
+/// together, as in `10-5` or `1.5.5`, so this stops where the next one
+/// starts instead of at a separator.
+fn take_number(src: &str) -> Result<(f32, &str), String> {
+    let src = src.trim_start_matches(|c: char| c.is_ascii_whitespace() || c == ',');
+    let bytes = src.as_bytes();
+    let mut end = 0;
+    if matches!(bytes.first(), Some(b'+' | b'-')) {
+        end += 1;
+    }
+    let mut seen_dot = false;
+    while let Some(&b) = bytes.get(end) {
+        if b.is_ascii_digit() || (b == b'.' && !seen_dot) {
+            seen_dot |= b == b'.';
+            end += 1;
+        } else {
+            break;
+        }
+    }
+    if matches!(bytes.get(end), Some(b'e' | b'E')) {
+        let mut exp = end + 1;
+        if matches!(bytes.get(exp), Some(b'+' | b'-')) {
+            exp += 1;
+        }
+        if bytes.get(exp).is_some_and(u8::is_ascii_digit) {
+            end = exp;
+            while bytes.get(end).is_some_and(u8::is_ascii_digit) {
+                end += 1;
+            }
+        }
+    }
+    let n = parse_number(&src[..end])
+        .filter(|n| n.is_finite())
+        .ok_or_else(|| format!("expected a number at {:?}", truncate(src)))?;
+    Ok((n, &src[end..]))
+}
+
+fn parse_number(src: &str) -> Option<f32> {
+    src.trim().parse().ok()
+}
+
+fn truncate(src: &str) -> &str {
+    src.char_indices().nth(12).map_or(src, |(i, _)| &src[..i])
+}
+
+fn distance(a: Point, b: Point) -> f32 {
+    ((b.0 - a.0).powi(2) + (b.1 - a.1).powi(2)).sqrt()
+}
+
+/// Distance from `p` to the segment `a`-`b`.
+fn segment_distance(p: Point, a: Point, b: Point) -> f32 {
+    let (dx, dz) = (b.0 - a.0, b.1 - a.1);
+    let len_sq = dx * dx + dz * dz;
+    if len_sq <= f32::EPSILON {
+        return distance(p, a);
+    }
+    let t = (((p.0 - a.0) * dx + (p.1 - a.1) * dz) / len_sq).clamp(0.0, 1.0);
+    distance(p, (a.0 + t * dx, a.1 + t * dz))
+}
+
+/// Ramer-Douglas-Peucker: keep the endpoints and every point that strays
+/// more than `tolerance` from the simplified line.
+fn simplify(points: &[Point], tolerance: f32) -> Vec<Point> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+    let (first, last) = (points[0], points[points.len() - 1]);
+    let (index, furthest) = points[1..points.len() - 1]
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (i + 1, segment_distance(*p, first, last)))
+        .fold(
+            (0, 0.0_f32),
+            |best, cur| if cur.1 > best.1 { cur } else { best },
+        );
+    if furthest <= tolerance {
+        return vec![first, last];
+    }
+    let mut left = simplify(&points[..=index], tolerance);
+    left.pop();
+    left.extend(simplify(&points[index..], tolerance));
+    left
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ends(walls: &[Wall]) -> Vec<(f32, f32, f32, f32)> {
+        walls.iter().map(|w| (w.a.x, w.a.z, w.b.x, w.b.z)).collect()
+    }
+
+    #[test]
+    fn straight_shapes_become_walls() {
+        let svg = r#"<?xml version="1.0"?>
+            <svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 20 30">
+              <!-- the L obstacle -->
+              <path d="M5,15 H14 v7" data-height="2"/>
+              <line x1="1" y1="1" x2="3" y2="1"></line>
+              <polygon points="8 4, 10 4 9 6"/>
+            </svg>"#;
+        let walls = walls_from_svg(svg).unwrap();
+        assert_eq!(
+            ends(&walls),
+            [
+                (5.0, 15.0, 14.0, 15.0),
+                (14.0, 15.0, 14.0, 22.0),
+                (1.0, 1.0, 3.0, 1.0),
+                (8.0, 4.0, 10.0, 4.0),
+                (10.0, 4.0, 9.0, 6.0),
+                (9.0, 6.0, 8.0, 4.0),
+            ]
+        );
+        assert_eq!(walls[0].height, 2.0);
+        assert_eq!(walls[2].height, DEFAULT_WALL_HEIGHT);
+    }
+
+    #[test]
+    fn relative_and_packed_path_data() {
+        // Implicit linetos after the moveto, and numbers run together
+        let walls = walls_from_svg(r#"<path d="m1 1 2 0-1-.5.5.5z"/>"#).unwrap();
+        assert_eq!(
+            ends(&walls),
+            [
+                (1.0, 1.0, 3.0, 1.0),
+                (3.0, 1.0, 2.0, 0.5),
+                (2.0, 0.5, 2.5, 1.0),
+                (2.5, 1.0, 1.0, 1.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn nearly_straight_strokes_are_simplified() {
+        let svg = r#"<polyline points="0,0 1,0.01 2,-0.01 3,0 3,4"/>"#;
+        assert_eq!(
+            ends(&walls_from_svg(svg).unwrap()),
+            [(0.0, 0.0, 3.0, 0.0), (3.0, 0.0, 3.0, 4.0)]
+        );
+    }
+
+    #[test]
+    fn unsupported_or_broken_input_is_rejected() {
+        for svg in [
+            r#"<path d="M0 0 C1 1 2 2 3 3"/>"#,
+            r#"<g transform="scale(2)"><line x1="0" y1="0" x2="1" y2="0"/></g>"#,
+            r#"<line x1="0" y1="0" x2="1"/>"#,
+            r#"<polyline points="0 0 1"/>"#,
+            r#"<path d="M0 0 L1 nan"/>"#,
+            r#"<line x1="0" y1="0" x2="1" y2="0" data-height="-1"/>"#,
+            r#"<svg><circle cx="1" cy="1" r="1"/></svg>"#,
+        ] {
+            assert!(walls_from_svg(svg).is_err(), "accepted {svg}");
+        }
+    }
+
+    #[test]
+    fn wall_count_is_capped() {
+        let points: Vec<String> = (0..=MAX_SVG_WALLS + 1)
+            .map(|i| format!("{i},{}", (i % 2) * 2))
+            .collect();
+        let svg = format!(r#"<polyline points="{}"/>"#, points.join(" "));
+        assert!(walls_from_svg(&svg).is_err());
+    }
+
+    #[test]
+    fn bounds_are_checked() {
+        let walls = walls_from_svg(r#"<rect x="2" y="2" width="4" height="3"/>"#).unwrap();
+        assert_eq!(walls.len(), 4);
+        assert!(check_bounds(&walls, 6.0, 5.0).is_ok());
+        assert!(check_bounds(&walls, 5.0, 5.0).is_err());
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use breakpoint_core::math::{Vec2, closest_point_on_segment};
+
 /// Wall type in the arena.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum WallType {
@@ -17,6 +19,132 @@ pub struct ArenaWall {
     pub wall_type: WallType,
 }
 
+/// A wall that moves on a fixed schedule. Where it stands follows from the
+/// round timer alone, so hosts and clients place it the same way and
+/// nothing about it is sent beyond the arena itself.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MovingWall {
+    pub wall_type: WallType,
+    pub motion: WallMotion,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WallMotion {
+    /// A revolving door: a segment of `length` centered on the pivot,
+    /// starting at `start_angle` and turning `speed` radians per second.
+    Rotating {
+        pivot_x: f32,
+        pivot_z: f32,
+        length: f32,
+        start_angle: f32,
+        speed: f32,
+    },
+    /// A barrier that slides from `(ax, az)`-`(bx, bz)` by `(dx, dz)` and
+    /// back, taking `travel_secs` each way and holding `hold_secs` at each
+    /// end. `offset_secs` shifts it along its cycle, so barriers sharing a
+    /// schedule can move out of step.
+    Sliding {
+        ax: f32,
+        az: f32,
+        bx: f32,
+        bz: f32,
+        dx: f32,
+        dz: f32,
+        travel_secs: f32,
+        hold_secs: f32,
+        #[serde(default)]
+        offset_secs: f32,
+    },
+}
+
+impl MovingWall {
+    /// The wall's segment `round_timer` seconds into the round.
+    pub fn at(&self, round_timer: f32) -> ArenaWall {
+        let (ax, az, bx, bz) = match self.motion {
+            WallMotion::Rotating {
+                pivot_x,
+                pivot_z,
+                length,
+                start_angle,
+                speed,
+            } => {
+                let angle = start_angle + speed * round_timer;
+                let (hx, hz) = (angle.cos() * length / 2.0, angle.sin() * length / 2.0);
+                (pivot_x - hx, pivot_z - hz, pivot_x + hx, pivot_z + hz)
+            },
+            WallMotion::Sliding {
+                ax,
+                az,
+                bx,
+                bz,
+                dx,
+                dz,
+                travel_secs,
+                hold_secs,
+                offset_secs,
+            } => {
+                let f = slide_fraction(round_timer + offset_secs, travel_secs, hold_secs);
+                (ax + dx * f, az + dz * f, bx + dx * f, bz + dz * f)
+            },
+        };
+        ArenaWall {
+            ax,
+            az,
+            bx,
+            bz,
+            wall_type: self.wall_type,
+        }
+    }
+}
+
+/// How far along its slide a barrier is at time `t`: holding at 0, out to
+/// 1, holding, and back.
+fn slide_fraction(t: f32, travel_secs: f32, hold_secs: f32) -> f32 {
+    let travel = travel_secs.max(0.0);
+    let hold = hold_secs.max(0.0);
+    let period = 2.0 * (travel + hold);
+    if period <= 0.0 {
+        return 0.0;
+    }
+    let phase = t.rem_euclid(period);
+    if phase < hold {
+        0.0
+    } else if phase < hold + travel {
+        (phase - hold) / travel
+    } else if phase < 2.0 * hold + travel {
+        1.0
+    } else {
+        1.0 - (phase - 2.0 * hold - travel) / travel
+    }
+}
+
+/// Move a circle of `radius` at `pos` out of any of `walls` it overlaps,
+/// along the shortest way out of each.
+pub fn push_out_of_walls(pos: Vec2, radius: f32, walls: &[ArenaWall]) -> Vec2 {
+    let mut pos = pos;
+    for wall in walls {
+        let a = Vec2::new(wall.ax, wall.az);
+        let b = Vec2::new(wall.bx, wall.bz);
+        let closest = closest_point_on_segment(pos, a, b);
+        let offset = pos - closest;
+        let dist = offset.length();
+        if dist >= radius {
+            continue;
+        }
+        let normal = if dist > 1e-4 {
+            offset * (1.0 / dist)
+        } else {
+            // Dead on the wall: step off to one side
+            let along = b - a;
+            let len = along.length().max(1e-4);
+            Vec2::new(-along.y, along.x) * (1.0 / len)
+        };
+        pos = closest + normal * radius;
+    }
+    pos
+}
+
 /// A spawn point in the arena.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpawnPoint {
@@ -34,6 +162,10 @@ pub struct Arena {
     pub walls: Vec<ArenaWall>,
     pub spawn_points: Vec<SpawnPoint>,
     pub smoke_zones: Vec<(f32, f32, f32)>, // (x, z, radius)
+    /// Revolving doors and sliding barriers. They block movement and
+    /// lasers like walls, wherever they stand at the moment.
+    #[serde(default)]
+    pub moving_walls: Vec<MovingWall>,
 }
 
 /// Arena size preset.
@@ -214,6 +346,42 @@ pub fn generate_arena(size: ArenaSize) -> Arena {
     // Smoke zones
     let smoke_zones = vec![(cx - 8.0, cz - 8.0, 3.0), (cx + 8.0, cz + 8.0, 3.0)];
 
+    // The large arena's long lanes get revolving doors and sliding barriers
+    let moving_walls = if size == ArenaSize::Large {
+        let door = |pivot_z: f32, speed: f32| MovingWall {
+            wall_type: WallType::Solid,
+            motion: WallMotion::Rotating {
+                pivot_x: cx,
+                pivot_z,
+                length: 6.0,
+                start_angle: 0.0,
+                speed,
+            },
+        };
+        let barrier = |x: f32, dx: f32, offset_secs: f32| MovingWall {
+            wall_type: WallType::Reflective,
+            motion: WallMotion::Sliding {
+                ax: x,
+                az: cz - 4.0,
+                bx: x,
+                bz: cz + 4.0,
+                dx,
+                dz: 0.0,
+                travel_secs: 3.0,
+                hold_secs: 2.0,
+                offset_secs,
+            },
+        };
+        vec![
+            door(12.0, 0.6),
+            door(depth - 12.0, -0.6),
+            barrier(12.0, 6.0, 0.0),
+            barrier(width - 12.0, -6.0, 5.0),
+        ]
+    } else {
+        Vec::new()
+    };
+
     Arena {
         name: match size {
             ArenaSize::Small => "Small Arena".to_string(),
@@ -225,6 +393,7 @@ pub fn generate_arena(size: ArenaSize) -> Arena {
         walls,
         spawn_points,
         smoke_zones,
+        moving_walls,
     }
 }
 
@@ -247,6 +416,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn moving_walls_follow_the_round_timer() {
+        let door = MovingWall {
+            wall_type: WallType::Solid,
+            motion: WallMotion::Rotating {
+                pivot_x: 10.0,
+                pivot_z: 10.0,
+                length: 4.0,
+                start_angle: 0.0,
+                speed: std::f32::consts::FRAC_PI_2,
+            },
+        };
+        let w = door.at(0.0);
+        assert_eq!((w.ax, w.az, w.bx, w.bz), (8.0, 10.0, 12.0, 10.0));
+        // A quarter turn after a second
+        let w = door.at(1.0);
+        assert!((w.ax - 10.0).abs() < 1e-4 && (w.az - 8.0).abs() < 1e-4);
+
+        let barrier = MovingWall {
+            wall_type: WallType::Reflective,
+            motion: WallMotion::Sliding {
+                ax: 0.0,
+                az: 0.0,
+                bx: 0.0,
+                bz: 4.0,
+                dx: 6.0,
+                dz: 0.0,
+                travel_secs: 2.0,
+                hold_secs: 1.0,
+                offset_secs: 0.0,
+            },
+        };
+        // Hold, slide out, hold, slide back: a 6-second cycle
+        let xs: Vec<f32> = [0.5, 2.0, 3.5, 5.0, 6.5]
+            .iter()
+            .map(|&t| barrier.at(t).ax)
+            .collect();
+        assert_eq!(xs, [0.0, 3.0, 6.0, 3.0, 0.0]);
+    }
+
+    #[test]
+    fn walls_push_players_out() {
+        let wall = ArenaWall {
+            ax: 0.0,
+            az: 0.0,
+            bx: 10.0,
+            bz: 0.0,
+            wall_type: WallType::Solid,
+        };
+        let pushed = push_out_of_walls(Vec2::new(5.0, 0.2), 0.5, std::slice::from_ref(&wall));
+        assert!((pushed.x - 5.0).abs() < 1e-4 && (pushed.y - 0.5).abs() < 1e-4);
+        let clear = Vec2::new(5.0, 2.0);
+        assert_eq!(push_out_of_walls(clear, 0.5, &[wall]), clear);
+    }
+
     #[test]
     fn load_from_missing_file_returns_none() {
         assert!(load_arena_from_file("/nonexistent/path/arena.json").is_none());
@@ -262,6 +486,7 @@ mod tests {
             assert_eq!(arena.walls.len(), loaded.walls.len());
             assert_eq!(arena.spawn_points.len(), loaded.spawn_points.len());
             assert_eq!(arena.smoke_zones.len(), loaded.smoke_zones.len());
+            assert_eq!(arena.moving_walls, loaded.moving_walls);
             assert!((arena.width - loaded.width).abs() < f32::EPSILON);
             assert!((arena.depth - loaded.depth).abs() < f32::EPSILON);
         }
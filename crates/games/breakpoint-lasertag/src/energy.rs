@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+
+/// Optional shot energy system. Each shot drains energy that recharges over
+/// time; emptying the cell overheats the laser and locks firing out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnergyConfig {
+    /// Off by default: only the fire cooldown limits shooting.
+    pub enabled: bool,
+    pub max_energy: f32,
+    /// Energy drained per shot.
+    pub shot_cost: f32,
+    /// Energy regained per second while recharging.
+    pub recharge_per_sec: f32,
+    /// Seconds after a shot before recharging starts.
+    pub recharge_delay_secs: f32,
+    /// Seconds firing is locked out after overheating.
+    pub overheat_lockout_secs: f32,
+}
+
+impl Default for EnergyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_energy: 100.0,
+            shot_cost: 20.0,
+            recharge_per_sec: 25.0,
+            recharge_delay_secs: 0.5,
+            overheat_lockout_secs: 2.0,
+        }
+    }
+}
+
+/// A player's laser energy, synced to clients for the energy bar.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Energy {
+    pub current: f32,
+    /// Seconds left in the overheat lockout.
+    pub overheat_remaining: f32,
+    /// Seconds until recharging resumes after the last shot.
+    pub recharge_delay: f32,
+}
+
+impl Energy {
+    pub fn full(config: &EnergyConfig) -> Self {
+        Self {
+            current: config.max_energy,
+            overheat_remaining: 0.0,
+            recharge_delay: 0.0,
+        }
+    }
+
+    pub fn is_overheated(&self) -> bool {
+        self.overheat_remaining > 0.0
+    }
+
+    /// Whether a shot is affordable. Always true when the system is off.
+    pub fn can_fire(&self, config: &EnergyConfig) -> bool {
+        !config.enabled || (!self.is_overheated() && self.current >= config.shot_cost)
+    }
+
+    /// Pay for a shot. Leaving too little for another shot overheats.
+    pub fn consume(&mut self, config: &EnergyConfig) {
+        if !config.enabled {
+            return;
+        }
+        self.current = (self.current - config.shot_cost).max(0.0);
+        self.recharge_delay = config.recharge_delay_secs;
+        if self.current < config.shot_cost {
+            self.overheat_remaining = config.overheat_lockout_secs;
+        }
+    }
+
+    /// Advance lockout and recharge timers by `dt` seconds.
+    pub fn tick(&mut self, dt: f32, config: &EnergyConfig) {
+        if !config.enabled {
+            return;
+        }
+        if self.overheat_remaining > 0.0 {
+            self.overheat_remaining = (self.overheat_remaining - dt).max(0.0);
+            return;
+        }
+        if self.recharge_delay > 0.0 {
+            self.recharge_delay = (self.recharge_delay - dt).max(0.0);
+            return;
+        }
+        self.current = (self.current + config.recharge_per_sec * dt).min(config.max_energy);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled() -> EnergyConfig {
+        EnergyConfig {
+            enabled: true,
+            ..EnergyConfig::default()
+        }
+    }
+
+    #[test]
+    fn disabled_never_blocks() {
+        let config = EnergyConfig::default();
+        let mut energy = Energy::default();
+        assert!(energy.can_fire(&config));
+        energy.consume(&config);
+        assert_eq!(energy, Energy::default());
+    }
+
+    #[test]
+    fn spamming_overheats() {
+        let config = enabled();
+        let mut energy = Energy::full(&config);
+        let shots = (config.max_energy / config.shot_cost) as usize;
+        for _ in 0..shots {
+            assert!(energy.can_fire(&config));
+            energy.consume(&config);
+        }
+        assert!(energy.is_overheated());
+        assert!(!energy.can_fire(&config));
+
+        // No recharge during the lockout
+        energy.tick(config.overheat_lockout_secs / 2.0, &config);
+        assert_eq!(energy.current, 0.0);
+    }
+
+    #[test]
+    fn recharges_after_delay() {
+        let config = enabled();
+        let mut energy = Energy::full(&config);
+        energy.consume(&config);
+        let after_shot = energy.current;
+
+        energy.tick(config.recharge_delay_secs / 2.0, &config);
+        assert_eq!(energy.current, after_shot, "Recharge waits for the delay");
+        energy.tick(config.recharge_delay_secs, &config);
+        energy.tick(10.0, &config);
+        assert_eq!(energy.current, config.max_energy, "Recharge caps at max");
+    }
+}
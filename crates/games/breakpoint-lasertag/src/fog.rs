@@ -0,0 +1,214 @@
+//! Fog of war: with the `fog_of_war` setting, each player is only sent the
+//! enemies they can see. Walls, moving ones included, and smoke block
+//! sight, teammates are always shown, and enemies within [`REVEAL_RADIUS`]
+//! are sensed through cover.
+//! Players the game doesn't know (spectators) get the whole arena.
+//!
+//! Views are encoded straight from the shared state with [`FoggedState`],
+//! so eight players' views cost one sightline check per pair and no
+//! copies of the state. The spectator view is encoded the same way, with
+//! everyone visible and the spectator cues added.
+
+use std::collections::HashMap;
+
+use serde::ser::{Serialize, SerializeMap, SerializeStruct, Serializer};
+use smallvec::SmallVec;
+
+use breakpoint_core::game_trait::{GameEvent, PlayerId};
+use breakpoint_core::math::{Vec2, segment_intersects_circle, segments_cross};
+use breakpoint_core::powerup::PowerUpFeed;
+use breakpoint_core::spectator::SpectatorCues;
+
+use crate::arena::ArenaWall;
+use crate::{LaserTagState, TeamMode};
+
+/// Enemies this close are visible through walls and smoke.
+pub const REVEAL_RADIUS: f32 = 2.0;
+
+/// Whether nothing blocks sight between `from` and `to`.
+pub fn line_of_sight(
+    from: Vec2,
+    to: Vec2,
+    walls: &[ArenaWall],
+    smoke_zones: &[(f32, f32, f32)],
+) -> bool {
+    smoke_zones
+        .iter()
+        .all(|&(x, z, r)| !segment_intersects_circle(from, to, Vec2::new(x, z), r))
+        && walls
+            .iter()
+            .all(|w| !segments_cross(from, to, Vec2::new(w.ax, w.az), Vec2::new(w.bx, w.bz)))
+}
+
+/// The players `viewer` can see, including themselves, or `None` when the
+/// viewer has no player in the arena and sees everything.
+pub fn visible_players(state: &LaserTagState, viewer: PlayerId) -> Option<SmallVec<[PlayerId; 8]>> {
+    let me = state.players.get(&viewer)?;
+    let eye = Vec2::new(me.x, me.z);
+    let moving: SmallVec<[ArenaWall; 4]> = state
+        .moving_walls
+        .iter()
+        .map(|w| w.at(state.round_timer))
+        .collect();
+    let my_team = match state.team_mode {
+        TeamMode::FreeForAll => None,
+        TeamMode::Teams { .. } => state.teams.get(&viewer),
+    };
+    let visible = state
+        .players
+        .iter()
+        .filter(|&(&pid, p)| {
+            if pid == viewer || (my_team.is_some() && state.teams.get(&pid) == my_team) {
+                return true;
+            }
+            let target = Vec2::new(p.x, p.z);
+            eye.distance(target) <= REVEAL_RADIUS
+                || (line_of_sight(eye, target, &state.arena_walls, &state.smoke_zones)
+                    && line_of_sight(eye, target, &moving, &[]))
+        })
+        .map(|(&pid, _)| pid)
+        .collect();
+    Some(visible)
+}
+
+/// A player's view of the state: serializes exactly like
+/// [`LaserTagState`], minus the hidden players and their power-ups.
+pub struct FoggedState<'a> {
+    state: &'a LaserTagState,
+    visible: &'a [PlayerId],
+    cues: Option<&'a SpectatorCues>,
+}
+
+impl<'a> FoggedState<'a> {
+    pub fn new(state: &'a LaserTagState, visible: &'a [PlayerId]) -> Self {
+        Self {
+            state,
+            visible,
+            cues: None,
+        }
+    }
+
+    /// Fill in `LaserTagState::spectator`, for the spectator view.
+    pub fn with_cues(mut self, cues: &'a SpectatorCues) -> Self {
+        self.cues = Some(cues);
+        self
+    }
+}
+
+/// The visible entries of a per-player map.
+struct VisibleEntries<'a, V> {
+    map: &'a HashMap<PlayerId, V>,
+    visible: &'a [PlayerId],
+}
+
+impl<V: Serialize> Serialize for VisibleEntries<'_, V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let entries = || self.map.iter().filter(|(id, _)| self.visible.contains(id));
+        let mut map = serializer.serialize_map(Some(entries().count()))?;
+        for (id, value) in entries() {
+            map.serialize_entry(id, value)?;
+        }
+        map.end()
+    }
+}
+
+/// The power-up feed without hidden players' pickups and expiries.
+/// Numbering is kept, so a client reading from a number skips the gaps.
+struct VisibleFeed<'a> {
+    feed: &'a PowerUpFeed,
+    visible: &'a [PlayerId],
+}
+
+impl Serialize for VisibleFeed<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let recent: SmallVec<[&(u32, GameEvent); 16]> = self
+            .feed
+            .recent
+            .iter()
+            .filter(|(_, event)| match event {
+                GameEvent::PowerupCollected { player_id, .. }
+                | GameEvent::PowerupExpired { player_id, .. } => self.visible.contains(player_id),
+                _ => true,
+            })
+            .collect();
+        let mut out = serializer.serialize_struct("PowerUpFeed", 2)?;
+        out.serialize_field("next_seq", &self.feed.next_seq)?;
+        out.serialize_field("recent", recent.as_slice())?;
+        out.end()
+    }
+}
+
+impl Serialize for FoggedState<'_> {
+    // Fields in `LaserTagState` declaration order
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let s = self.state;
+        let mut out = serializer.serialize_struct("LaserTagState", 20)?;
+        out.serialize_field(
+            "players",
+            &VisibleEntries {
+                map: &s.players,
+                visible: self.visible,
+            },
+        )?;
+        out.serialize_field("powerups", &s.powerups)?;
+        out.serialize_field(
+            "active_powerups",
+            &VisibleEntries {
+                map: &s.active_powerups,
+                visible: self.visible,
+            },
+        )?;
+        out.serialize_field("round_timer", &s.round_timer)?;
+        out.serialize_field("round_complete", &s.round_complete)?;
+        out.serialize_field("team_mode", &s.team_mode)?;
+        out.serialize_field("teams", &s.teams)?;
+        out.serialize_field("tags_scored", &s.tags_scored)?;
+        out.serialize_field("arena_width", &s.arena_width)?;
+        out.serialize_field("arena_depth", &s.arena_depth)?;
+        out.serialize_field("arena_walls", &s.arena_walls)?;
+        out.serialize_field("smoke_zones", &s.smoke_zones)?;
+        out.serialize_field("energy_max", &s.energy_max)?;
+        out.serialize_field("turrets", &s.turrets)?;
+        out.serialize_field("pause", &s.pause)?;
+        out.serialize_field("assists", &s.assists)?;
+        out.serialize_field(
+            "powerup_feed",
+            &VisibleFeed {
+                feed: &s.powerup_feed,
+                visible: self.visible,
+            },
+        )?;
+        out.serialize_field(
+            "recent_stuns",
+            &VisibleEntries {
+                map: &s.recent_stuns,
+                visible: self.visible,
+            },
+        )?;
+        out.serialize_field("spectator", &self.cues)?;
+        out.serialize_field("moving_walls", &s.moving_walls)?;
+        out.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arena::WallType;
+
+    #[test]
+    fn walls_and_smoke_block_sight() {
+        let walls = [ArenaWall {
+            ax: 5.0,
+            az: 0.0,
+            bx: 5.0,
+            bz: 10.0,
+            wall_type: WallType::Reflective,
+        }];
+        let from = Vec2::new(0.0, 5.0);
+        let smoke = [(0.0, 12.0, 1.0)];
+        assert!(!line_of_sight(from, Vec2::new(10.0, 5.0), &walls, &[]));
+        assert!(line_of_sight(from, Vec2::new(0.0, 20.0), &walls, &[]));
+        assert!(!line_of_sight(from, Vec2::new(0.0, 20.0), &walls, &smoke));
+    }
+}
@@ -1,26 +1,74 @@
 pub mod arena;
+pub mod energy;
+pub mod fog;
 pub mod powerups;
 pub mod projectile;
 pub mod scoring;
+pub mod settings;
+pub mod spectator;
+pub mod stun;
+pub mod trail;
+pub mod turret;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::time::Duration;
 
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
 use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 
 use breakpoint_core::breakpoint_game_boilerplate;
+use breakpoint_core::debug_command::{self, DebugCommand, DebugResult};
+use breakpoint_core::game_settings::FromGameConfig;
 use breakpoint_core::game_trait::{
-    BreakpointGame, GameConfig, GameEvent, GameMetadata, PlayerId, PlayerInputs, PlayerScore,
+    BreakpointGame, GameConfig, GameConfigDelta, GameEvent, GameMemory, GameMetadata,
+    LIVE_EXTEND_ROUND, LIVE_POWERUPS, PlayerId, PlayerInputs, PlayerScore,
 };
+use breakpoint_core::heatmap::{HeatmapAccumulator, RoundHeatmap};
+use breakpoint_core::i18n::TextKey;
+use breakpoint_core::late_join::LateJoinPolicy;
+use breakpoint_core::math::{Vec2, segment_intersects_circle};
+use breakpoint_core::pause::PauseState;
 use breakpoint_core::player::Player;
-
-use arena::{Arena, ArenaSize, load_arena};
+use breakpoint_core::powerup::{self, PowerUpFeed};
+use breakpoint_core::spectator::SpectatorCues;
+use breakpoint_core::team_draft;
+use breakpoint_core::tutorial::TutorialObjective;
+use breakpoint_core::victory::VictorySummary;
+
+use arena::{Arena, ArenaSize, ArenaWall, MovingWall, SpawnPoint, load_arena, push_out_of_walls};
+use energy::Energy;
 use powerups::{ActiveLaserPowerUp, LaserPowerUpKind, SpawnedLaserPowerUp};
 use projectile::{
     FIRE_COOLDOWN, LaserTagConfig, PLAYER_RADIUS, RAPIDFIRE_COOLDOWN_MULT, STUN_DURATION,
-    raycast_laser,
+    raycast_beam,
 };
+use settings::{LaserTagSettings, TeamSelect};
+use spectator::Spotlight;
+use stun::RecentStun;
+use trail::{LaserFired, LaserTrail};
+use turret::LaserTurret;
+
+/// Side of the heatmap cells positions are accumulated in, in world units.
+const HEATMAP_CELL_SIZE: f32 = 1.0;
+
+/// Stick deflection below which a movement axis counts as released when
+/// classifying inputs into actions.
+const MOVE_DEADZONE: f32 = 0.3;
+
+/// What the laser tag tutorial asks a new player to do.
+pub const TUTORIAL_OBJECTIVES: &[TutorialObjective] = &[
+    TutorialObjective {
+        id: "tag",
+        text: TextKey::TutorialLaserTagTag,
+    },
+    TutorialObjective {
+        id: "powerup",
+        text: TextKey::TutorialLaserTagPowerUp,
+    },
+];
 
 /// Serializable game state for network broadcast.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,11 +81,35 @@ pub struct LaserTagState {
     pub team_mode: TeamMode,
     pub teams: HashMap<PlayerId, u8>,
     pub tags_scored: HashMap<PlayerId, u32>,
-    pub laser_trails: Vec<LaserTrail>,
     pub arena_width: f32,
     pub arena_depth: f32,
     pub arena_walls: Vec<arena::ArenaWall>,
     pub smoke_zones: Vec<(f32, f32, f32)>,
+    /// Energy cell capacity; 0 when the energy system is disabled.
+    #[serde(default)]
+    pub energy_max: f32,
+    /// Deployed turrets.
+    #[serde(default)]
+    pub turrets: Vec<LaserTurret>,
+    /// Active pause reasons, so clients can show why play is held.
+    #[serde(default)]
+    pub pause: PauseState,
+    /// Assists this round: shields broken just before someone else's tag.
+    #[serde(default)]
+    pub assists: HashMap<PlayerId, u32>,
+    /// Recent power-up pickups and expiries, for client effects.
+    #[serde(default)]
+    pub powerup_feed: PowerUpFeed,
+    /// Each player's last stun, which sets how long the next one lasts.
+    #[serde(default)]
+    pub recent_stuns: HashMap<PlayerId, RecentStun>,
+    /// Who to watch. Only in the spectator view.
+    #[serde(default)]
+    pub spectator: Option<SpectatorCues>,
+    /// Doors and barriers on a schedule; place them with
+    /// [`MovingWall::at`] and `round_timer`.
+    #[serde(default)]
+    pub moving_walls: Vec<MovingWall>,
 }
 
 /// Post-stun invulnerability duration in seconds.
@@ -55,10 +127,13 @@ pub struct LaserPlayerState {
     /// Brief invulnerability after recovering from a stun.
     #[serde(default)]
     pub invulnerability_remaining: f32,
+    /// Laser energy; only meaningful when the energy system is enabled.
+    #[serde(default)]
+    pub energy: Energy,
 }
 
 impl LaserPlayerState {
-    fn new(x: f32, z: f32, angle: f32) -> Self {
+    fn new(x: f32, z: f32, angle: f32, energy: Energy) -> Self {
         Self {
             x,
             z,
@@ -67,6 +142,7 @@ impl LaserPlayerState {
             fire_cooldown: 0.0,
             move_speed: 8.0,
             invulnerability_remaining: 0.0,
+            energy,
         }
     }
 
@@ -79,6 +155,35 @@ impl LaserPlayerState {
     }
 }
 
+/// Each player's team: the one `explicit` gives them when it exists,
+/// otherwise the smallest team so far (lowest index on ties), taking
+/// players in `order`. With no explicit teams this alternates round-robin.
+fn assign_teams(
+    order: &[PlayerId],
+    team_count: u8,
+    explicit: &HashMap<PlayerId, u8>,
+) -> HashMap<PlayerId, u8> {
+    let mut sizes = vec![0usize; team_count as usize];
+    let mut teams = HashMap::new();
+    for &id in order {
+        if let Some(&team) = explicit.get(&id).filter(|&&t| t < team_count) {
+            teams.insert(id, team);
+            sizes[team as usize] += 1;
+        }
+    }
+    for &id in order {
+        if teams.contains_key(&id) {
+            continue;
+        }
+        let team = (0..team_count)
+            .min_by_key(|&t| sizes[t as usize])
+            .unwrap_or(0);
+        teams.insert(id, team);
+        sizes[team as usize] += 1;
+    }
+    teams
+}
+
 /// Team mode configuration.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TeamMode {
@@ -86,13 +191,6 @@ pub enum TeamMode {
     Teams { team_count: u8 },
 }
 
-/// Visual laser trail for client rendering.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LaserTrail {
-    pub segments: Vec<(f32, f32, f32, f32)>,
-    pub age: f32,
-}
-
 /// Input from a laser tag player.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LaserTagInput {
@@ -119,12 +217,52 @@ impl Default for LaserTagInput {
 pub struct LaserTagArena {
     arena: Arena,
     state: LaserTagState,
+    /// The arena's walls plus its moving walls where they stand this tick,
+    /// for laser hits.
+    walls: Vec<ArenaWall>,
     player_ids: Vec<PlayerId>,
     pending_inputs: HashMap<PlayerId, LaserTagInput>,
-    paused: bool,
     round_duration: f32,
     /// Data-driven game configuration (physics, timing).
     game_config: LaserTagConfig,
+    /// What players who have since left earned this round.
+    departed: BTreeMap<PlayerId, TagTally>,
+    /// Most recent shield break on each player, for assist credit.
+    shield_breaks: HashMap<PlayerId, ShieldBreak>,
+    /// Where players spent this round and where tags landed. Host-only;
+    /// sent with the round results.
+    heatmap: HeatmapAccumulator,
+    /// Whether players only receive the enemies they can see.
+    fog_of_war: bool,
+    /// Distance from a laser's center line at which it tags a player.
+    hit_radius: f32,
+    /// Whether tags and assists count (off during a warmup).
+    scoring: bool,
+    /// False while the host has turned power-up pickups off mid-round.
+    powerups_enabled: bool,
+    /// Shots fired since the last `take_effects`, sent to clients once
+    /// rather than in every snapshot.
+    pending_shots: Vec<LaserFired>,
+    /// Client side: recent shots whose trails may still be on screen.
+    recent_shots: Vec<LaserFired>,
+    /// Tag streaks and the latest tagger, for spectators' cues.
+    spotlight: Spotlight,
+    /// What happens to players who join mid-round.
+    late_join: LateJoinPolicy,
+}
+
+/// Tags and assists earned this round.
+#[derive(Debug, Clone, Copy, Default)]
+struct TagTally {
+    tags: u32,
+    assists: u32,
+}
+
+/// Who broke a player's shield, and when (round time).
+#[derive(Debug, Clone, Copy)]
+struct ShieldBreak {
+    by: PlayerId,
+    at: f32,
 }
 
 impl LaserTagArena {
@@ -136,6 +274,8 @@ impl LaserTagArena {
     pub fn with_config(config: LaserTagConfig) -> Self {
         let round_duration = config.round_duration_secs;
         let initial_arena = load_arena(ArenaSize::Default);
+        let heatmap =
+            HeatmapAccumulator::new(initial_arena.width, initial_arena.depth, HEATMAP_CELL_SIZE);
         Self {
             state: LaserTagState {
                 players: HashMap::new(),
@@ -146,18 +286,36 @@ impl LaserTagArena {
                 team_mode: TeamMode::FreeForAll,
                 teams: HashMap::new(),
                 tags_scored: HashMap::new(),
-                laser_trails: Vec::new(),
                 arena_width: initial_arena.width,
                 arena_depth: initial_arena.depth,
                 arena_walls: initial_arena.walls.clone(),
                 smoke_zones: initial_arena.smoke_zones.clone(),
+                energy_max: 0.0,
+                turrets: Vec::new(),
+                pause: PauseState::default(),
+                assists: HashMap::new(),
+                powerup_feed: PowerUpFeed::default(),
+                recent_stuns: HashMap::new(),
+                spectator: None,
+                moving_walls: initial_arena.moving_walls.clone(),
             },
+            walls: initial_arena.walls.clone(),
             arena: initial_arena,
             player_ids: Vec::new(),
             pending_inputs: HashMap::new(),
-            paused: false,
             round_duration,
             game_config: config,
+            departed: BTreeMap::new(),
+            shield_breaks: HashMap::new(),
+            heatmap,
+            fog_of_war: false,
+            hit_radius: PLAYER_RADIUS,
+            scoring: true,
+            powerups_enabled: true,
+            pending_shots: Vec::new(),
+            recent_shots: Vec::new(),
+            spotlight: Spotlight::default(),
+            late_join: LateJoinPolicy::SpawnImmediately,
         }
     }
 
@@ -169,11 +327,57 @@ impl LaserTagArena {
         &self.arena
     }
 
+    /// Laser trails on screen at the current round time.
+    pub fn laser_trails(&self) -> impl Iterator<Item = LaserTrail> + '_ {
+        let now = self.state.round_timer;
+        self.recent_shots
+            .iter()
+            .filter(move |shot| shot.is_visible(now))
+            .map(move |shot| shot.trail(now))
+    }
+
     /// Accessor for the game configuration.
     pub fn config(&self) -> &LaserTagConfig {
         &self.game_config
     }
 
+    /// Energy cell capacity reported to clients (0 when disabled).
+    fn energy_max(&self) -> f32 {
+        let energy = &self.game_config.energy;
+        if energy.enabled {
+            energy.max_energy
+        } else {
+            0.0
+        }
+    }
+
+    /// Starting energy for a newly spawned player.
+    fn initial_energy(&self) -> Energy {
+        if self.game_config.energy.enabled {
+            Energy::full(&self.game_config.energy)
+        } else {
+            Energy::default()
+        }
+    }
+
+    /// The spawn point farthest from everyone already in the arena, so a
+    /// mid-round joiner isn't tagged the moment they appear.
+    fn late_spawn_point(&self) -> SpawnPoint {
+        let nearest = |sp: &SpawnPoint| {
+            self.state
+                .players
+                .values()
+                .map(|p| (p.x - sp.x).hypot(p.z - sp.z))
+                .fold(f32::INFINITY, f32::min)
+        };
+        self.arena
+            .spawn_points
+            .iter()
+            .max_by(|a, b| nearest(a).total_cmp(&nearest(b)))
+            .cloned()
+            .expect("arenas have spawn points")
+    }
+
     fn get_team_ids(&self, player_id: PlayerId) -> Vec<u64> {
         if self.state.team_mode == TeamMode::FreeForAll {
             return Vec::new();
@@ -190,43 +394,159 @@ impl LaserTagArena {
     }
 }
 
+impl LaserTagArena {
+    /// Put the moving walls where the round timer has them.
+    fn place_moving_walls(&mut self) {
+        self.walls.clear();
+        self.walls.extend_from_slice(&self.arena.walls);
+        let t = self.state.round_timer;
+        self.walls
+            .extend(self.arena.moving_walls.iter().map(|w| w.at(t)));
+    }
+
+    /// Fire a laser for `shooter` from a player or one of their turrets,
+    /// stunning (or unshielding) whoever it hits and crediting the tag.
+    fn fire_laser(
+        &mut self,
+        shooter: PlayerId,
+        ox: f32,
+        oz: f32,
+        angle: f32,
+        events: &mut Vec<GameEvent>,
+    ) {
+        // Build player list for hit detection (stack-allocated for up to 8 players)
+        // Exclude stunned and invulnerable players
+        let player_positions: SmallVec<[(u64, f32, f32); 8]> = self
+            .state
+            .players
+            .iter()
+            .filter(|(_, p)| !p.is_stunned() && !p.is_invulnerable())
+            .map(|(&id, p)| (id, p.x, p.z))
+            .collect();
+
+        let team_ids = self.get_team_ids(shooter);
+
+        let hit = raycast_beam(
+            ox,
+            oz,
+            angle,
+            &self.walls,
+            &player_positions,
+            shooter,
+            &team_ids,
+            100.0,
+            self.hit_radius,
+        );
+
+        // Check smoke zone LOS blocking before moving segments
+        let blocked_by_smoke = hit.hit_player.is_some()
+            && self.state.smoke_zones.iter().any(|&(sx, sz, sr)| {
+                hit.segments.iter().any(|&(x1, z1, x2, z2)| {
+                    segment_intersects_circle(
+                        Vec2::new(x1, z1),
+                        Vec2::new(x2, z2),
+                        Vec2::new(sx, sz),
+                        sr,
+                    )
+                })
+            });
+
+        // Clients draw the trail from a one-off effect
+        self.pending_shots.push(LaserFired::new(
+            shooter,
+            self.state.round_timer,
+            &hit.segments,
+        ));
+
+        // Apply hit (if not blocked by smoke zone)
+        let Some(target_id) = hit.hit_player else {
+            return;
+        };
+        if blocked_by_smoke {
+            return;
+        }
+        let has_shield = self
+            .state
+            .active_powerups
+            .get(&target_id)
+            .is_some_and(|pus| pus.iter().any(|p| p.kind == LaserPowerUpKind::Shield));
+
+        if has_shield {
+            // Consume shield
+            if let Some(pus) = self.state.active_powerups.get_mut(&target_id) {
+                pus.retain(|p| p.kind != LaserPowerUpKind::Shield);
+            }
+            self.shield_breaks.insert(
+                target_id,
+                ShieldBreak {
+                    by: shooter,
+                    at: self.state.round_timer,
+                },
+            );
+        } else {
+            // Stun the target, for less if they were tagged moments ago
+            if let Some(target) = self.state.players.get_mut(&target_id) {
+                let (secs, stun) = RecentStun::next(
+                    self.state.recent_stuns.get(&target_id).copied(),
+                    self.state.round_timer,
+                    STUN_DURATION,
+                    &self.game_config.stun,
+                );
+                target.stun_remaining = secs;
+                self.state.recent_stuns.insert(target_id, stun);
+                self.heatmap.record_tag(target.x, target.z);
+                self.spotlight
+                    .record_tag(shooter, target_id, self.state.round_timer);
+            }
+            // Whoever broke the shield for this tag gets an assist
+            let shield_break = self.shield_breaks.remove(&target_id);
+            if !self.scoring {
+                return;
+            }
+            if let Some(brk) = shield_break
+                && brk.by != shooter
+                && self.state.round_timer - brk.at <= self.game_config.assist.window_secs
+            {
+                *self.state.assists.entry(brk.by).or_insert(0) += 1;
+            }
+            *self.state.tags_scored.entry(shooter).or_insert(0) += 1;
+            events.push(GameEvent::ScoreUpdate {
+                player_id: shooter,
+                score: self.state.tags_scored[&shooter] as i32,
+            });
+        }
+    }
+
+    /// Place a held turret at the player's position, aimed where they aim.
+    fn deploy_turret(&mut self, pid: PlayerId) {
+        let Some(pus) = self.state.active_powerups.get_mut(&pid) else {
+            return;
+        };
+        let Some(idx) = pus.iter().position(|p| p.kind == LaserPowerUpKind::Turret) else {
+            return;
+        };
+        pus.remove(idx);
+        let Some(player) = self.state.players.get(&pid) else {
+            return;
+        };
+        let config = &self.game_config.turret;
+        let turret = LaserTurret::new(pid, player.x, player.z, player.aim_angle, config);
+        let owned = self.state.turrets.iter().filter(|t| t.owner == pid).count();
+        if owned >= config.max_per_player
+            && let Some(oldest) = self.state.turrets.iter().position(|t| t.owner == pid)
+        {
+            self.state.turrets.remove(oldest);
+        }
+        self.state.turrets.push(turret);
+    }
+}
+
 impl Default for LaserTagArena {
     fn default() -> Self {
         Self::with_config(LaserTagConfig::default())
     }
 }
 
-/// Check if a line segment intersects a circle (for smoke zone LOS blocking).
-fn segment_intersects_circle(
-    x1: f32,
-    z1: f32,
-    x2: f32,
-    z2: f32,
-    cx: f32,
-    cz: f32,
-    radius: f32,
-) -> bool {
-    let dx = x2 - x1;
-    let dz = z2 - z1;
-    let fx = x1 - cx;
-    let fz = z1 - cz;
-    let a = dx * dx + dz * dz;
-    if a < 1e-10 {
-        return false;
-    }
-    let b = 2.0 * (fx * dx + fz * dz);
-    let c = fx * fx + fz * fz - radius * radius;
-    let discriminant = b * b - 4.0 * a * c;
-    if discriminant < 0.0 {
-        return false;
-    }
-    let sqrt_d = discriminant.sqrt();
-    let t1 = (-b - sqrt_d) / (2.0 * a);
-    let t2 = (-b + sqrt_d) / (2.0 * a);
-    // Intersection within segment [0, 1]
-    (0.0..=1.0).contains(&t1) || (0.0..=1.0).contains(&t2) || (t1 < 0.0 && t2 > 1.0)
-}
-
 impl BreakpointGame for LaserTagArena {
     fn metadata(&self) -> GameMetadata {
         GameMetadata {
@@ -242,38 +562,29 @@ impl BreakpointGame for LaserTagArena {
         20.0
     }
 
-    fn init(&mut self, players: &[Player], config: &GameConfig) {
-        // Parse team mode from config
-        let team_mode = config
-            .custom
-            .get("team_mode")
-            .and_then(|v| v.as_str())
-            .map(|s| match s {
-                "teams_2" => TeamMode::Teams { team_count: 2 },
-                "teams_3" => TeamMode::Teams { team_count: 3 },
-                "teams_4" => TeamMode::Teams { team_count: 4 },
-                _ => TeamMode::FreeForAll,
-            })
-            .unwrap_or(TeamMode::FreeForAll);
+    fn team_count_for(&self, custom: &HashMap<String, serde_json::Value>) -> u8 {
+        match LaserTagSettings::from_custom(custom).map(|s| s.team_mode) {
+            Ok(TeamMode::Teams { team_count }) => team_count,
+            _ => 0,
+        }
+    }
 
-        // Parse arena size from config
-        let arena_size = config
-            .custom
-            .get("arena_size")
-            .and_then(|v| v.as_str())
-            .map(|s| match s {
-                "small" => ArenaSize::Small,
-                "large" => ArenaSize::Large,
-                _ => ArenaSize::Default,
-            })
-            .unwrap_or(ArenaSize::Default);
+    fn init(&mut self, players: &[Player], config: &GameConfig) {
+        // Hosts validate settings before starting; anything that slips
+        // through falls back to the defaults
+        let settings = LaserTagSettings::from_game_config(config).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Ignoring invalid laser tag settings");
+            LaserTagSettings::default()
+        });
+        let team_mode = settings.team_mode;
 
-        self.arena = load_arena(arena_size);
-        self.round_duration = config
-            .custom
-            .get("round_duration")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(180.0) as f32;
+        self.arena = load_arena(settings.arena_size);
+        self.round_duration = settings.round_duration_secs;
+        self.powerups_enabled = true;
+        self.fog_of_war = settings.fog_of_war;
+        self.hit_radius = PLAYER_RADIUS + settings.beam_width / 2.0;
+        self.scoring = config.scoring;
+        self.late_join = settings.late_join;
 
         self.state = LaserTagState {
             players: HashMap::new(),
@@ -284,33 +595,63 @@ impl BreakpointGame for LaserTagArena {
             team_mode,
             teams: HashMap::new(),
             tags_scored: HashMap::new(),
-            laser_trails: Vec::new(),
             arena_width: self.arena.width,
             arena_depth: self.arena.depth,
             arena_walls: self.arena.walls.clone(),
             smoke_zones: self.arena.smoke_zones.clone(),
+            energy_max: self.energy_max(),
+            turrets: Vec::new(),
+            pause: PauseState::default(),
+            assists: HashMap::new(),
+            powerup_feed: PowerUpFeed::default(),
+            recent_stuns: HashMap::new(),
+            spectator: None,
+            moving_walls: self.arena.moving_walls.clone(),
         };
+        self.place_moving_walls();
         self.player_ids.clear();
         self.pending_inputs.clear();
-        self.paused = false;
+        self.pending_shots.clear();
+        self.departed.clear();
+        self.shield_breaks.clear();
+        self.spotlight.clear();
+        self.heatmap =
+            HeatmapAccumulator::new(self.arena.width, self.arena.depth, HEATMAP_CELL_SIZE);
 
         // Initialize player states at spawn points
         let active_players: Vec<&Player> = players.iter().filter(|p| !p.is_spectator).collect();
 
+        let energy = self.initial_energy();
         for (i, player) in active_players.iter().enumerate() {
             self.player_ids.push(player.id);
             let spawn = &self.arena.spawn_points[i % self.arena.spawn_points.len()];
             self.state.players.insert(
                 player.id,
-                LaserPlayerState::new(spawn.x, spawn.z, spawn.angle),
+                LaserPlayerState::new(spawn.x, spawn.z, spawn.angle, energy.clone()),
             );
             self.state.active_powerups.insert(player.id, Vec::new());
             self.state.tags_scored.insert(player.id, 0);
+        }
 
-            // Assign teams (round-robin)
-            if let TeamMode::Teams { team_count } = team_mode {
-                self.state.teams.insert(player.id, (i as u8) % team_count);
-            }
+        if let TeamMode::Teams { team_count } = team_mode {
+            let mut order = self.player_ids.clone();
+            let explicit = match settings.team_select {
+                TeamSelect::Shuffle => {
+                    match settings.seed {
+                        // Mixed with the round so each round still deals anew
+                        Some(seed) => {
+                            let round = config.custom.get("hole_index").and_then(|v| v.as_u64());
+                            order.shuffle(&mut StdRng::seed_from_u64(seed ^ round.unwrap_or(0)));
+                        },
+                        None => order.shuffle(&mut rand::rng()),
+                    }
+                    HashMap::new()
+                },
+                TeamSelect::RoundRobin | TeamSelect::Draft => {
+                    team_draft::explicit_teams(&config.custom)
+                },
+            };
+            self.state.teams = assign_teams(&order, team_count, &explicit);
         }
 
         // Spawn power-ups in arena (scale spread with arena size)
@@ -322,6 +663,7 @@ impl BreakpointGame for LaserTagArena {
             (cx + spread, cz, LaserPowerUpKind::SpeedBoost),
             (cx, cz - spread, LaserPowerUpKind::Shield),
             (cx, cz + spread, LaserPowerUpKind::WideBeam),
+            (cx + spread, cz + spread, LaserPowerUpKind::Turret),
         ];
         for (x, z, kind) in power_up_spots {
             self.state.powerups.push(SpawnedLaserPowerUp {
@@ -336,18 +678,15 @@ impl BreakpointGame for LaserTagArena {
 
     fn update(&mut self, dt: f32, _inputs: &PlayerInputs) -> Vec<GameEvent> {
         breakpoint_core::profile!("lasertag_update");
-        if self.paused || self.state.round_complete {
+        if self.state.pause.is_paused() || self.state.round_complete {
             return Vec::new();
         }
 
         self.state.round_timer += dt;
         let mut events = Vec::new();
-
-        // Age and remove old laser trails
-        for trail in &mut self.state.laser_trails {
-            trail.age += dt;
+        if !self.arena.moving_walls.is_empty() {
+            self.place_moving_walls();
         }
-        self.state.laser_trails.retain(|t| t.age < 0.3);
 
         // Process player movement and firing (iterate by index to avoid clone)
         for i in 0..self.player_ids.len() {
@@ -361,6 +700,7 @@ impl BreakpointGame for LaserTagArena {
                 let was_stunned = player.stun_remaining > 0.0;
                 player.stun_remaining = (player.stun_remaining - dt).max(0.0);
                 player.invulnerability_remaining = (player.invulnerability_remaining - dt).max(0.0);
+                player.energy.tick(dt, &self.game_config.energy);
 
                 // Grant brief invulnerability when stun expires
                 if was_stunned && !player.is_stunned() {
@@ -394,82 +734,28 @@ impl BreakpointGame for LaserTagArena {
             }
 
             // Firing
-            let can_fire = self
-                .state
-                .players
-                .get(&pid)
-                .is_some_and(|p| !p.is_stunned() && p.fire_cooldown <= 0.0);
+            let can_fire = self.state.players.get(&pid).is_some_and(|p| {
+                !p.is_stunned()
+                    && p.fire_cooldown <= 0.0
+                    && p.energy.can_fire(&self.game_config.energy)
+            });
+
+            if input.use_powerup
+                && self
+                    .state
+                    .players
+                    .get(&pid)
+                    .is_some_and(|p| !p.is_stunned())
+            {
+                self.deploy_turret(pid);
+            }
 
             if input.fire && can_fire {
                 let (ox, oz, angle) = {
                     let p = &self.state.players[&pid];
                     (p.x, p.z, p.aim_angle)
                 };
-
-                // Build player list for hit detection (stack-allocated for up to 8 players)
-                // Exclude stunned and invulnerable players
-                let player_positions: SmallVec<[(u64, f32, f32); 8]> = self
-                    .state
-                    .players
-                    .iter()
-                    .filter(|(_, p)| !p.is_stunned() && !p.is_invulnerable())
-                    .map(|(&id, p)| (id, p.x, p.z))
-                    .collect();
-
-                let team_ids = self.get_team_ids(pid);
-
-                let hit = raycast_laser(
-                    ox,
-                    oz,
-                    angle,
-                    &self.arena.walls,
-                    &player_positions,
-                    pid,
-                    &team_ids,
-                    100.0,
-                );
-
-                // Check smoke zone LOS blocking before moving segments
-                let blocked_by_smoke = hit.hit_player.is_some()
-                    && self.state.smoke_zones.iter().any(|&(sx, sz, sr)| {
-                        hit.segments.iter().any(|&(x1, z1, x2, z2)| {
-                            segment_intersects_circle(x1, z1, x2, z2, sx, sz, sr)
-                        })
-                    });
-
-                // Record laser trail for rendering
-                self.state.laser_trails.push(LaserTrail {
-                    segments: hit.segments,
-                    age: 0.0,
-                });
-
-                // Apply hit (if not blocked by smoke zone)
-                if let Some(target_id) = hit.hit_player
-                    && !blocked_by_smoke
-                {
-                    let has_shield = self
-                        .state
-                        .active_powerups
-                        .get(&target_id)
-                        .is_some_and(|pus| pus.iter().any(|p| p.kind == LaserPowerUpKind::Shield));
-
-                    if has_shield {
-                        // Consume shield
-                        if let Some(pus) = self.state.active_powerups.get_mut(&target_id) {
-                            pus.retain(|p| p.kind != LaserPowerUpKind::Shield);
-                        }
-                    } else {
-                        // Stun the target
-                        if let Some(target) = self.state.players.get_mut(&target_id) {
-                            target.stun_remaining = STUN_DURATION;
-                        }
-                        *self.state.tags_scored.entry(pid).or_insert(0) += 1;
-                        events.push(GameEvent::ScoreUpdate {
-                            player_id: pid,
-                            score: self.state.tags_scored[&pid] as i32,
-                        });
-                    }
-                }
+                self.fire_laser(pid, ox, oz, angle, &mut events);
 
                 // Apply cooldown
                 let cooldown =
@@ -483,11 +769,39 @@ impl BreakpointGame for LaserTagArena {
 
                 if let Some(player) = self.state.players.get_mut(&pid) {
                     player.fire_cooldown = cooldown;
+                    player.energy.consume(&self.game_config.energy);
                 }
             }
         }
 
+        // Moving walls block players, shoving aside anyone they sweep into
+        let moving = &self.walls[self.arena.walls.len()..];
+        if !moving.is_empty() {
+            for player in self.state.players.values_mut() {
+                let pos = push_out_of_walls(Vec2::new(player.x, player.z), PLAYER_RADIUS, moving);
+                player.x = pos.x.clamp(PLAYER_RADIUS, self.arena.width - PLAYER_RADIUS);
+                player.z = pos.y.clamp(PLAYER_RADIUS, self.arena.depth - PLAYER_RADIUS);
+            }
+        }
+
+        // Deployed turrets sweep and fire on their own
+        let turret_config = &self.game_config.turret;
+        let turret_shots: SmallVec<[(PlayerId, f32, f32, f32); 4]> = self
+            .state
+            .turrets
+            .iter_mut()
+            .filter_map(|t| {
+                t.tick(dt, turret_config)
+                    .map(|angle| (t.owner, t.x, t.z, angle))
+            })
+            .collect();
+        self.state.turrets.retain(|t| !t.is_expired());
+        for (owner, x, z, angle) in turret_shots {
+            self.fire_laser(owner, x, z, angle, &mut events);
+        }
+
         // Power-up collection
+        let mut powerup_events = Vec::new();
         for pu in &mut self.state.powerups {
             if pu.collected {
                 pu.respawn_timer -= dt;
@@ -496,6 +810,9 @@ impl BreakpointGame for LaserTagArena {
                 }
                 continue;
             }
+            if !self.powerups_enabled {
+                continue;
+            }
             for &pid in &self.player_ids {
                 if let Some(player) = self.state.players.get(&pid) {
                     let dx = player.x - pu.x;
@@ -508,18 +825,22 @@ impl BreakpointGame for LaserTagArena {
                             .entry(pid)
                             .or_default()
                             .push(ActiveLaserPowerUp::new(pu.kind));
+                        powerup_events.push(powerup::collected(pid, pu.kind));
                         break;
                     }
                 }
             }
         }
 
+        for player in self.state.players.values() {
+            self.heatmap.record_presence(player.x, player.z, dt);
+        }
+
         // Tick active power-ups
-        for pus in self.state.active_powerups.values_mut() {
-            for pu in pus.iter_mut() {
-                pu.tick(dt);
-            }
-            pus.retain(|p| !p.is_expired());
+        powerup_events.extend(powerup::tick_active(&mut self.state.active_powerups, dt));
+        for event in powerup_events {
+            self.state.powerup_feed.push(event.clone());
+            events.push(event);
         }
 
         // Check round completion (timer)
@@ -533,6 +854,27 @@ impl BreakpointGame for LaserTagArena {
 
     breakpoint_game_boilerplate!(state_type: LaserTagState);
 
+    fn has_player_views(&self) -> bool {
+        self.fog_of_war
+    }
+
+    fn serialize_state_for_into(&self, viewer: PlayerId, buf: &mut Vec<u8>) {
+        let Some(visible) = fog::visible_players(&self.state, viewer) else {
+            self.serialize_state_into(buf);
+            return;
+        };
+        buf.clear();
+        rmp_serde::encode::write(buf, &fog::FoggedState::new(&self.state, &visible))
+            .expect("game state serialization must succeed");
+    }
+
+    fn spectator_view_state(&self) -> Option<Vec<u8>> {
+        let everyone: SmallVec<[PlayerId; 8]> = self.state.players.keys().copied().collect();
+        let cues = self.spotlight.cues(&self.state);
+        let view = fog::FoggedState::new(&self.state, &everyone).with_cues(&cues);
+        Some(rmp_serde::to_vec(&view).expect("game state serialization must succeed"))
+    }
+
     fn apply_input(&mut self, player_id: PlayerId, input: &[u8]) {
         match rmp_serde::from_slice::<LaserTagInput>(input) {
             Err(e) => {
@@ -570,47 +912,250 @@ impl BreakpointGame for LaserTagArena {
         }
     }
 
+    fn input_action_key(&self, input: &[u8]) -> Vec<u8> {
+        let Ok(li) = rmp_serde::from_slice::<LaserTagInput>(input) else {
+            return input.to_vec();
+        };
+        // The aim angle follows the mouse every frame; only the movement
+        // direction and the buttons are deliberate actions.
+        let direction = |v: f32| {
+            if v > MOVE_DEADZONE {
+                1
+            } else if v < -MOVE_DEADZONE {
+                2
+            } else {
+                0
+            }
+        };
+        vec![
+            direction(li.move_x),
+            direction(li.move_z),
+            u8::from(li.fire),
+            u8::from(li.use_powerup),
+        ]
+    }
+
     fn player_joined(&mut self, player: &Player) {
         if player.is_spectator || self.player_ids.contains(&player.id) {
             return;
         }
-        let idx = self.player_ids.len();
+        // Players coming back to this round pick up where they left off
+        let departed = self.departed.remove(&player.id);
+        let policy = match departed {
+            Some(_) => LateJoinPolicy::SpawnImmediately,
+            None => self.late_join,
+        };
+        if policy == LateJoinPolicy::Spectate {
+            return;
+        }
         self.player_ids.push(player.id);
-        let spawn = &self.arena.spawn_points[idx % self.arena.spawn_points.len()];
-        self.state.players.insert(
-            player.id,
-            LaserPlayerState::new(spawn.x, spawn.z, spawn.angle),
-        );
+        let departed = departed.unwrap_or_default();
+        self.state.tags_scored.insert(player.id, departed.tags);
+        if departed.assists > 0 {
+            self.state.assists.insert(player.id, departed.assists);
+        }
+        if policy == LateJoinPolicy::SpawnNextRound {
+            return;
+        }
+        let energy = self.initial_energy();
+        let spawn = self.late_spawn_point();
+        let mut joiner = LaserPlayerState::new(spawn.x, spawn.z, spawn.angle, energy);
+        joiner.invulnerability_remaining = INVULNERABILITY_DURATION;
+        self.state.players.insert(player.id, joiner);
         self.state.active_powerups.insert(player.id, Vec::new());
-        self.state.tags_scored.insert(player.id, 0);
+        if let TeamMode::Teams { team_count } = self.state.team_mode {
+            let team = (0..team_count)
+                .min_by_key(|&t| self.state.teams.values().filter(|&&pt| pt == t).count())
+                .unwrap_or(0);
+            self.state.teams.insert(player.id, team);
+        }
     }
 
     fn player_left(&mut self, player_id: PlayerId) {
+        let assists = self.state.assists.remove(&player_id).unwrap_or(0);
+        if self.player_ids.contains(&player_id)
+            && let Some(tags) = self.state.tags_scored.remove(&player_id)
+        {
+            self.departed.insert(player_id, TagTally { tags, assists });
+        }
+        self.shield_breaks
+            .retain(|&target, brk| target != player_id && brk.by != player_id);
         self.player_ids.retain(|&id| id != player_id);
         self.state.players.remove(&player_id);
         self.state.active_powerups.remove(&player_id);
-        self.state.tags_scored.remove(&player_id);
         self.state.teams.remove(&player_id);
+        self.state.recent_stuns.remove(&player_id);
+        self.spotlight.player_left(player_id);
+        self.state.turrets.retain(|t| t.owner != player_id);
     }
 
     fn round_results(&self) -> Vec<PlayerScore> {
-        self.player_ids
-            .iter()
-            .map(|&pid| {
-                let tags = self.state.tags_scored.get(&pid).copied().unwrap_or(0);
-                PlayerScore {
-                    player_id: pid,
-                    score: scoring::ffa_score(tags),
-                }
+        let active = self.player_ids.iter().map(|&pid| {
+            let earned = TagTally {
+                tags: self.state.tags_scored.get(&pid).copied().unwrap_or(0),
+                assists: self.state.assists.get(&pid).copied().unwrap_or(0),
+            };
+            (pid, earned, false)
+        });
+        let departed = self.departed.iter().map(|(&pid, &d)| (pid, d, true));
+        active
+            .chain(departed)
+            .map(|(player_id, earned, disconnected)| PlayerScore {
+                player_id,
+                score: scoring::ffa_score(earned.tags)
+                    + scoring::assist_score(earned.assists, &self.game_config.assist),
+                disconnected,
             })
             .collect()
     }
+
+    fn teams(&self) -> HashMap<PlayerId, u8> {
+        self.state.teams.clone()
+    }
+
+    fn victory_summary(&self) -> VictorySummary {
+        VictorySummary::best_by(
+            TextKey::VictoryMostTags,
+            self.player_ids.iter().filter_map(|&pid| {
+                let tags = self.state.tags_scored.get(&pid).copied().unwrap_or(0);
+                (tags > 0).then_some((pid, tags as i32))
+            }),
+            false,
+        )
+    }
+
+    fn round_heatmap(&self) -> Option<RoundHeatmap> {
+        Some(self.heatmap.finish())
+    }
+
+    fn tutorial_objectives(&self) -> &'static [TutorialObjective] {
+        TUTORIAL_OBJECTIVES
+    }
+
+    fn tutorial_objectives_met(&self, player_id: PlayerId) -> Vec<&'static str> {
+        let mut met = Vec::new();
+        if self
+            .state
+            .tags_scored
+            .get(&player_id)
+            .is_some_and(|&t| t > 0)
+        {
+            met.push("tag");
+        }
+        if self
+            .state
+            .active_powerups
+            .get(&player_id)
+            .is_some_and(|pus| !pus.is_empty())
+        {
+            met.push("powerup");
+        }
+        met
+    }
+
+    fn memory_usage(&self) -> GameMemory {
+        let shots = |shots: &Vec<LaserFired>| {
+            shots.capacity() * std::mem::size_of::<LaserFired>()
+                + shots
+                    .iter()
+                    .map(|s| s.segments.capacity() * std::mem::size_of::<[i16; 4]>())
+                    .sum::<usize>()
+        };
+        GameMemory {
+            walls_bytes: self.state.arena_walls.capacity()
+                * std::mem::size_of::<arena::ArenaWall>(),
+            trails_bytes: shots(&self.pending_shots) + shots(&self.recent_shots),
+        }
+    }
+
+    fn trim_memory(&mut self) -> usize {
+        // Trails are only drawn, and fade out within a few ticks anyway
+        let freed = self.memory_usage().trails_bytes;
+        self.pending_shots = Vec::new();
+        self.recent_shots = Vec::new();
+        freed
+    }
+
+    fn take_effects(&mut self) -> Option<Vec<u8>> {
+        if self.pending_shots.is_empty() {
+            return None;
+        }
+        let shots = std::mem::take(&mut self.pending_shots);
+        match rmp_serde::to_vec(&shots) {
+            Ok(data) => Some(data),
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to encode laser shots");
+                None
+            },
+        }
+    }
+
+    fn apply_effects(&mut self, data: &[u8]) {
+        match rmp_serde::from_slice::<Vec<LaserFired>>(data) {
+            Ok(shots) => {
+                let now = self.state.round_timer;
+                self.recent_shots.retain(|shot| !shot.is_stale(now));
+                self.recent_shots.extend(shots);
+            },
+            Err(e) => tracing::debug!(error = %e, "Dropped malformed laser shots"),
+        }
+    }
+
+    fn apply_debug(&mut self, command: &DebugCommand) -> DebugResult {
+        match *command {
+            DebugCommand::Teleport { player_id, x, y } => {
+                let player = self
+                    .state
+                    .players
+                    .get_mut(&player_id)
+                    .ok_or_else(|| format!("No player {player_id}"))?;
+                player.x = x.clamp(PLAYER_RADIUS, self.arena.width - PLAYER_RADIUS);
+                player.z = y.clamp(PLAYER_RADIUS, self.arena.depth - PLAYER_RADIUS);
+            },
+            DebugCommand::GrantPowerUp {
+                player_id,
+                ref kind,
+            } => {
+                if !self.state.players.contains_key(&player_id) {
+                    return Err(format!("No player {player_id}"));
+                }
+                let kind = debug_command::parse_kind(kind)?;
+                self.state
+                    .active_powerups
+                    .entry(player_id)
+                    .or_default()
+                    .push(ActiveLaserPowerUp::new(kind));
+            },
+            DebugCommand::ForceRoundEnd => self.state.round_complete = true,
+            DebugCommand::SetRoundTimer { elapsed_secs } => {
+                self.state.round_timer = debug_command::checked_elapsed(elapsed_secs)?;
+            },
+        }
+        Ok(())
+    }
+
+    fn live_config_keys(&self) -> &'static [&'static str] {
+        &[LIVE_EXTEND_ROUND, LIVE_POWERUPS]
+    }
+
+    fn apply_config_change(&mut self, delta: &GameConfigDelta) -> Result<(), String> {
+        delta.check_keys(self.live_config_keys())?;
+        let extension = delta.round_extension()?;
+        let powerups = delta.powerups()?;
+        if let Some(secs) = extension {
+            self.round_duration += secs;
+        }
+        if let Some(enabled) = powerups {
+            self.powerups_enabled = enabled;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use breakpoint_core::test_helpers::{default_config, make_players};
+    use breakpoint_core::test_helpers::{default_config, make_players, run_game_ticks};
 
     #[test]
     fn init_creates_player_states() {
@@ -653,6 +1198,122 @@ mod tests {
         assert!(game.pending_inputs.contains_key(&1));
     }
 
+    /// Fire once as player 1 (aimed at the nearest wall) and tick past the
+    /// cooldown. Returns whether a laser shot was produced.
+    fn fire_and_cool_down(game: &mut LaserTagArena) -> bool {
+        let input = LaserTagInput {
+            fire: true,
+            ..LaserTagInput::default()
+        };
+        game.apply_input(1, &rmp_serde::to_vec(&input).unwrap());
+        let empty = PlayerInputs {
+            inputs: HashMap::new(),
+        };
+        game.update(0.05, &empty);
+        game.state.players.get_mut(&1).unwrap().fire_cooldown = 0.0;
+        game.take_effects().is_some()
+    }
+
+    #[test]
+    fn trimming_memory_drops_laser_trails() {
+        let mut game = LaserTagArena::default();
+        game.init(&make_players(2), &default_config(180));
+        let input = LaserTagInput {
+            fire: true,
+            ..LaserTagInput::default()
+        };
+        game.apply_input(1, &rmp_serde::to_vec(&input).unwrap());
+        let empty = PlayerInputs {
+            inputs: HashMap::new(),
+        };
+        game.update(0.05, &empty);
+        let usage = game.memory_usage();
+        assert!(usage.walls_bytes > 0);
+        assert!(usage.trails_bytes > 0);
+
+        assert_eq!(game.trim_memory(), usage.trails_bytes);
+        assert!(game.take_effects().is_none());
+        assert_eq!(game.memory_usage().trails_bytes, 0);
+    }
+
+    #[test]
+    fn shots_are_sent_once_and_drawn_by_clients() {
+        let players = make_players(2);
+        let mut host = LaserTagArena::default();
+        host.init(&players, &default_config(180));
+        let mut client = LaserTagArena::default();
+        client.init(&players, &default_config(180));
+        let input = LaserTagInput {
+            fire: true,
+            ..LaserTagInput::default()
+        };
+        host.apply_input(1, &rmp_serde::to_vec(&input).unwrap());
+        let empty = PlayerInputs {
+            inputs: HashMap::new(),
+        };
+        host.update(0.05, &empty);
+
+        let effects = host.take_effects().expect("the shot is an effect");
+        assert!(host.take_effects().is_none(), "Each shot is sent once");
+        client.apply_state(&host.serialize_state());
+        client.apply_effects(&effects);
+        let trails: Vec<LaserTrail> = client.laser_trails().collect();
+        assert_eq!(trails.len(), 1);
+        assert!(!trails[0].segments.is_empty());
+        assert_eq!(trails[0].age, 0.0);
+
+        // Snapshots age the trail until it fades
+        for _ in 0..6 {
+            host.update(0.05, &empty);
+        }
+        client.apply_state(&host.serialize_state());
+        assert_eq!(client.laser_trails().count(), 0);
+    }
+
+    #[test]
+    fn energy_disabled_by_default() {
+        let mut game = LaserTagArena::default();
+        game.init(&make_players(2), &default_config(180));
+        assert_eq!(game.state.energy_max, 0.0);
+        for _ in 0..20 {
+            assert!(
+                fire_and_cool_down(&mut game),
+                "Only the cooldown limits firing"
+            );
+        }
+    }
+
+    #[test]
+    fn energy_overheat_locks_out_firing() {
+        let mut config = LaserTagConfig::default();
+        config.energy.enabled = true;
+        let energy = config.energy.clone();
+        let mut game = LaserTagArena::with_config(config);
+        game.init(&make_players(2), &default_config(180));
+        assert_eq!(game.state.energy_max, energy.max_energy);
+        assert_eq!(game.state.players[&1].energy.current, energy.max_energy);
+
+        let shots = (energy.max_energy / energy.shot_cost) as usize;
+        for _ in 0..shots {
+            assert!(fire_and_cool_down(&mut game));
+        }
+        assert!(game.state.players[&1].energy.is_overheated());
+        assert!(
+            !fire_and_cool_down(&mut game),
+            "Overheated laser can't fire"
+        );
+
+        // Lockout ends and energy recharges
+        let empty = PlayerInputs {
+            inputs: HashMap::new(),
+        };
+        for _ in 0..100 {
+            game.update(0.05, &empty);
+        }
+        assert!(!game.state.players[&1].energy.is_overheated());
+        assert!(fire_and_cool_down(&mut game));
+    }
+
     #[test]
     fn tick_rate_is_20() {
         let game = LaserTagArena::new();
@@ -690,6 +1351,82 @@ mod tests {
         }
     }
 
+    #[test]
+    fn micro_rounds_set_the_round_length() {
+        use breakpoint_core::game_settings::FromGameConfig;
+        use breakpoint_core::round_phase::MICRO_ROUNDS_SETTING;
+
+        let mut game = LaserTagArena::new();
+        let mut config = default_config(180);
+        config
+            .custom
+            .insert(MICRO_ROUNDS_SETTING.to_string(), serde_json::json!(35));
+        game.init(&make_players(2), &config);
+        assert_eq!(game.round_duration, 35.0);
+
+        for (key, value) in [
+            ("round_duration", serde_json::json!(120)),
+            ("arena_size", serde_json::json!("large")),
+        ] {
+            let mut custom = config.custom.clone();
+            custom.insert(key.to_string(), value);
+            let err = LaserTagSettings::validate(&custom).unwrap_err();
+            assert_eq!(err.key, MICRO_ROUNDS_SETTING, "{key}");
+        }
+    }
+
+    #[test]
+    fn moving_walls_block_lasers_and_shove_players() {
+        let mut game = LaserTagArena::new();
+        let mut config = default_config(180);
+        config
+            .custom
+            .insert("arena_size".to_string(), serde_json::json!("large"));
+        game.init(&make_players(2), &config);
+        assert_eq!(game.state.moving_walls.len(), 4);
+        let no_inputs = PlayerInputs {
+            inputs: HashMap::new(),
+        };
+
+        let fire_from = |game: &mut LaserTagArena, round_timer: f32| {
+            game.state.round_timer = round_timer;
+            for (pid, z) in [(1, 8.0), (2, 16.0)] {
+                let p = game.state.players.get_mut(&pid).unwrap();
+                (p.x, p.z) = (33.0, z);
+                p.fire_cooldown = 0.0;
+                p.stun_remaining = 0.0;
+                p.invulnerability_remaining = 0.0;
+            }
+            let input = LaserTagInput {
+                move_x: 0.0,
+                move_z: 0.0,
+                aim_angle: std::f32::consts::FRAC_PI_2,
+                fire: true,
+                use_powerup: false,
+            };
+            game.apply_input(1, &rmp_serde::to_vec(&input).unwrap());
+            game.update(0.05, &no_inputs);
+            game.state.players[&2].is_stunned()
+        };
+        // The south door starts across the shot, then turns edge-on to it
+        assert!(!fire_from(&mut game, 0.0), "Blocked by the door");
+        let edge_on = std::f32::consts::FRAC_PI_2 / 0.6 - 0.05;
+        assert!(fire_from(&mut game, edge_on), "Through the open door");
+
+        // The west barrier slides out after two seconds, pushing through
+        // whoever stands in its way
+        let p = game.state.players.get_mut(&1).unwrap();
+        (p.x, p.z) = (15.2, 35.0);
+        game.state.round_timer = 3.5 - 0.05;
+        game.update(0.05, &no_inputs);
+        let p = &game.state.players[&1];
+        assert!(
+            (p.x - 15.0).abs() >= PLAYER_RADIUS - 1e-3,
+            "Shoved to x = {}",
+            p.x
+        );
+    }
+
     #[test]
     fn ffa_and_team_modes() {
         let mut game = LaserTagArena::new();
@@ -699,6 +1436,7 @@ mod tests {
         game.init(&players, &default_config(180));
         assert_eq!(game.state.team_mode, TeamMode::FreeForAll);
         assert!(game.state.teams.is_empty());
+        assert!(game.teams().is_empty());
 
         // Team mode
         let mut config = default_config(180);
@@ -709,6 +1447,7 @@ mod tests {
         game.init(&players, &config);
         assert_eq!(game.state.team_mode, TeamMode::Teams { team_count: 2 });
         assert_eq!(game.state.teams.len(), 4);
+        assert_eq!(game.teams(), game.state.teams);
     }
 
     // ================================================================
@@ -858,6 +1597,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn warmup_tags_stun_without_scoring() {
+        let mut game = LaserTagArena::new();
+        let players = make_players(2);
+        let config = GameConfig::builder()
+            .round_duration(Duration::from_secs(180))
+            .scoring(false)
+            .build();
+        game.init(&players, &config);
+
+        game.state.players.get_mut(&1).unwrap().x = 5.0;
+        game.state.players.get_mut(&1).unwrap().z = 10.0;
+        game.state.players.get_mut(&1).unwrap().fire_cooldown = 0.0;
+        game.state.players.get_mut(&1).unwrap().stun_remaining = 0.0;
+        game.state.players.get_mut(&2).unwrap().x = 10.0;
+        game.state.players.get_mut(&2).unwrap().z = 10.0;
+        game.state.players.get_mut(&2).unwrap().stun_remaining = 0.0;
+
+        let input = LaserTagInput {
+            fire: true,
+            ..LaserTagInput::default()
+        };
+        game.apply_input(1, &rmp_serde::to_vec(&input).unwrap());
+        let events = game.update(
+            0.05,
+            &PlayerInputs {
+                inputs: HashMap::new(),
+            },
+        );
+
+        assert!(game.state.players[&2].is_stunned(), "Tags still land");
+        assert_eq!(game.state.tags_scored.get(&1).copied().unwrap_or(0), 0);
+        assert!(!events.iter().any(GameEvent::is_scoring));
+    }
+
     #[test]
     fn powerup_duration_expiry() {
         let mut game = LaserTagArena::new();
@@ -889,12 +1663,45 @@ mod tests {
         );
 
         // Advance past the 5.0s duration (total > 5.0s)
-        game.update(4.0, &inputs);
+        let events = game.update(4.0, &inputs);
         assert_eq!(
             game.state.active_powerups[&1].len(),
             0,
             "Power-up should have expired after 6.0s total (duration is 5.0s)"
         );
+        assert!(events.iter().any(|e| matches!(
+            e,
+            GameEvent::PowerupExpired { player_id: 1, kind } if kind == "rapid_fire"
+        )));
+        assert_eq!(game.state.powerup_feed.next_seq, 1);
+    }
+
+    #[test]
+    fn powerup_pickup_emits_an_event_and_feeds_clients() {
+        let mut game = LaserTagArena::new();
+        game.init(&make_players(2), &default_config(180));
+        let (x, z, kind) = {
+            let pu = &game.state.powerups[0];
+            (pu.x, pu.z, pu.kind)
+        };
+        let p = game.state.players.get_mut(&2).unwrap();
+        p.x = x;
+        p.z = z;
+
+        let inputs = PlayerInputs {
+            inputs: HashMap::new(),
+        };
+        let events = game.update(0.05, &inputs);
+        let name = powerup::PowerUpKind::name(&kind);
+        assert!(events.iter().any(|e| matches!(
+            e,
+            GameEvent::PowerupCollected { player_id: 2, kind } if kind == name
+        )));
+        let fed: Vec<&GameEvent> = game.state.powerup_feed.since(0).collect();
+        assert!(matches!(
+            fed[..],
+            [GameEvent::PowerupCollected { player_id: 2, .. }]
+        ));
     }
 
     #[test]
@@ -1088,6 +1895,14 @@ mod tests {
         breakpoint_core::test_helpers::contract_pause_stops_updates(&mut game);
     }
 
+    #[test]
+    fn contract_pause_reasons_stack() {
+        let mut game = LaserTagArena::new();
+        let players = make_players(2);
+        game.init(&players, &default_config(180));
+        breakpoint_core::test_helpers::contract_pause_reasons_stack(&mut game);
+    }
+
     #[test]
     fn contract_player_left_cleanup() {
         let mut game = LaserTagArena::new();
@@ -1259,6 +2074,140 @@ mod tests {
         assert_eq!(game.state.tags_scored[&1], 1, "Shooter should get 1 tag");
     }
 
+    #[test]
+    fn round_heatmap_records_presence_and_tags() {
+        let mut game = LaserTagArena::new();
+        game.init(&make_players(2), &default_config(180));
+        for (pid, x) in [(1, 5.0), (2, 10.0)] {
+            let p = game.state.players.get_mut(&pid).unwrap();
+            p.x = x;
+            p.z = 10.0;
+            p.aim_angle = 0.0;
+            p.stun_remaining = 0.0;
+        }
+        game.pending_inputs.insert(
+            1,
+            LaserTagInput {
+                fire: true,
+                ..LaserTagInput::default()
+            },
+        );
+        run_game_ticks(&mut game, 1, 0.05);
+
+        let map = game.round_heatmap().unwrap();
+        assert_eq!(map.width, game.arena.width);
+        assert_eq!(map.tags.iter().sum::<u16>(), 1);
+        let cell_of = |x: f32, z: f32| {
+            let c = (x / map.width * map.cols as f32) as usize;
+            let r = (z / map.depth * map.rows as f32) as usize;
+            r * map.cols as usize + c
+        };
+        assert_eq!(
+            map.tags[cell_of(10.0, 10.0)],
+            1,
+            "Tag lands under the target"
+        );
+        assert_eq!(map.presence[cell_of(5.0, 10.0)], 255);
+
+        game.init(&make_players(2), &default_config(180));
+        let fresh = game.round_heatmap().unwrap();
+        assert!(
+            fresh.tags.iter().all(|&t| t == 0),
+            "Each round starts empty"
+        );
+    }
+
+    #[test]
+    fn tutorial_objectives_follow_tags_and_power_ups() {
+        let mut game = LaserTagArena::new();
+        game.init(&make_players(2), &default_config(180));
+        assert!(game.tutorial_objectives_met(1).is_empty());
+        for (pid, x) in [(1, 5.0), (2, 10.0)] {
+            let p = game.state.players.get_mut(&pid).unwrap();
+            p.x = x;
+            p.z = 10.0;
+            p.aim_angle = 0.0;
+            p.stun_remaining = 0.0;
+        }
+        game.pending_inputs.insert(
+            1,
+            LaserTagInput {
+                fire: true,
+                ..LaserTagInput::default()
+            },
+        );
+        run_game_ticks(&mut game, 1, 0.05);
+        assert_eq!(game.tutorial_objectives_met(1), ["tag"]);
+        assert!(game.tutorial_objectives_met(2).is_empty());
+
+        game.apply_debug(&DebugCommand::GrantPowerUp {
+            player_id: 2,
+            kind: "Shield".to_string(),
+        })
+        .unwrap();
+        assert_eq!(game.tutorial_objectives_met(2), ["powerup"]);
+        let ids: Vec<&str> = game.tutorial_objectives().iter().map(|o| o.id).collect();
+        assert_eq!(ids, ["tag", "powerup"]);
+    }
+
+    #[test]
+    fn fog_of_war_hides_enemies_out_of_sight() {
+        let mut game = LaserTagArena::new();
+        let mut config = teams_config();
+        config
+            .custom
+            .insert("fog_of_war".to_string(), serde_json::Value::Bool(true));
+        game.init(&make_players(3), &config);
+        assert!(game.has_player_views());
+        game.state.smoke_zones.clear();
+        game.state.arena_walls = vec![arena::ArenaWall {
+            ax: 20.0,
+            az: 0.0,
+            bx: 20.0,
+            bz: 50.0,
+            wall_type: arena::WallType::Solid,
+        }];
+        // Players 1 and 3 are teammates; the wall hides both from player 1
+        for (pid, x, z) in [(1, 10.0, 10.0), (2, 30.0, 30.0), (3, 30.0, 10.0)] {
+            let p = game.state.players.get_mut(&pid).unwrap();
+            p.x = x;
+            p.z = z;
+        }
+        let view_of = |game: &LaserTagArena, viewer| {
+            let mut buf = Vec::new();
+            game.serialize_state_for_into(viewer, &mut buf);
+            buf
+        };
+
+        for pid in [2, 3] {
+            game.state
+                .powerup_feed
+                .push(powerup::collected(pid, LaserPowerUpKind::Shield));
+        }
+
+        let view: LaserTagState = rmp_serde::from_slice(&view_of(&game, 1)).unwrap();
+        let mut seen: Vec<PlayerId> = view.players.keys().copied().collect();
+        seen.sort_unstable();
+        assert_eq!(seen, vec![1, 3], "Enemy behind the wall is hidden");
+        assert!(!view.active_powerups.contains_key(&2));
+        let feed: Vec<u32> = view.powerup_feed.recent.iter().map(|&(n, _)| n).collect();
+        assert_eq!(feed, [1], "So are their pickups");
+        assert_eq!(view.powerup_feed.next_seq, 2);
+        assert_eq!(view.tags_scored.len(), 3, "Scores stay public");
+
+        game.state.players.get_mut(&2).unwrap().x = 15.0;
+        assert_eq!(
+            view_of(&game, 1),
+            game.serialize_state(),
+            "A view with everyone in sight is the full state"
+        );
+        assert_eq!(
+            view_of(&game, 99),
+            game.serialize_state(),
+            "Spectators see all"
+        );
+    }
+
     #[test]
     fn lasertag_full_match_round_completes() {
         let mut game = LaserTagArena::new();
@@ -1487,6 +2436,49 @@ mod tests {
         assert!(shields.is_empty(), "Shield should be consumed");
     }
 
+    #[test]
+    fn turret_deploys_and_credits_owner() {
+        let mut game = LaserTagArena::new();
+        game.init(&make_players(2), &default_config(180));
+        game.state
+            .active_powerups
+            .entry(1)
+            .or_default()
+            .push(powerups::ActiveLaserPowerUp::new(
+                powerups::LaserPowerUpKind::Turret,
+            ));
+        game.state.players.get_mut(&1).unwrap().x = 5.0;
+        game.state.players.get_mut(&1).unwrap().z = 10.0;
+        game.state.players.get_mut(&2).unwrap().x = 10.0;
+        game.state.players.get_mut(&2).unwrap().z = 10.0;
+
+        let input = LaserTagInput {
+            use_powerup: true,
+            ..LaserTagInput::default()
+        };
+        game.apply_input(1, &rmp_serde::to_vec(&input).unwrap());
+        let inputs = PlayerInputs {
+            inputs: HashMap::new(),
+        };
+        game.update(0.05, &inputs);
+
+        assert_eq!(game.state.turrets.len(), 1);
+        assert!(
+            game.state.active_powerups[&1].is_empty(),
+            "Turret is used up"
+        );
+        assert!(game.state.players[&2].is_stunned(), "Turret shot hits");
+        assert!(!game.state.players[&1].is_stunned(), "Never hits its owner");
+        assert_eq!(game.state.tags_scored[&1], 1, "Tag credited to the owner");
+
+        let mut client = LaserTagArena::new();
+        client.apply_state(&game.serialize_state());
+        assert_eq!(client.state.turrets, game.state.turrets);
+
+        game.player_left(1);
+        assert!(game.state.turrets.is_empty());
+    }
+
     #[test]
     fn shield_consumed_second_hit_stuns() {
         let mut game = LaserTagArena::new();
@@ -1543,6 +2535,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn shield_break_before_another_tag_is_an_assist() {
+        let mut game = LaserTagArena::new();
+        game.init(&make_players(3), &default_config(180));
+        let shield_up = |game: &mut LaserTagArena| {
+            let target = game.state.players.get_mut(&2).unwrap();
+            (target.x, target.z) = (10.0, 10.0);
+            target.stun_remaining = 0.0;
+            target.invulnerability_remaining = 0.0;
+            game.state.active_powerups.entry(2).or_default().push(
+                powerups::ActiveLaserPowerUp::new(powerups::LaserPowerUpKind::Shield),
+            );
+        };
+        // Shooters stand off to the side; fire_laser takes the origin
+        for (pid, z) in [(1, 30.0), (3, 35.0)] {
+            let p = game.state.players.get_mut(&pid).unwrap();
+            (p.x, p.z) = (5.0, z);
+        }
+        let mut events = Vec::new();
+
+        // Player 1 breaks the shield, player 3 lands the tag
+        shield_up(&mut game);
+        game.fire_laser(1, 5.0, 10.0, 0.0, &mut events);
+        game.state.round_timer += 1.0;
+        game.fire_laser(3, 5.0, 10.0, 0.0, &mut events);
+        assert!(game.state.players[&2].is_stunned());
+        assert_eq!(game.state.assists.get(&1), Some(&1));
+        assert_eq!(game.state.tags_scored[&3], 1);
+
+        // Too slow: the window has closed
+        shield_up(&mut game);
+        game.fire_laser(1, 5.0, 10.0, 0.0, &mut events);
+        game.state.round_timer += game.config().assist.window_secs + 0.5;
+        game.fire_laser(3, 5.0, 10.0, 0.0, &mut events);
+        assert_eq!(game.state.tags_scored[&3], 2);
+        assert_eq!(
+            game.state.assists.get(&1),
+            Some(&1),
+            "No assist after the window"
+        );
+
+        // Assists count toward the score and survive leaving
+        game.player_left(1);
+        let results = game.round_results();
+        let breaker = results.iter().find(|s| s.player_id == 1).unwrap();
+        assert!(breaker.disconnected);
+        assert_eq!(breaker.score, 1);
+    }
+
     #[test]
     fn lasertag_fire_input_not_lost_across_overwrites() {
         // Verifies Bug 2 fix: fire:true must be preserved even if a
@@ -1771,6 +2812,126 @@ mod tests {
         );
     }
 
+    #[test]
+    fn mid_round_joiners_follow_the_late_join_policy() {
+        use breakpoint_core::late_join::LATE_JOIN_SETTING;
+
+        let players = make_players(6);
+        let mut game = LaserTagArena::new();
+        game.init(&players[..5], &teams_config());
+        game.player_joined(&players[5]);
+
+        // Spawned on the smaller team, protected, and away from everyone
+        assert_eq!(game.state.teams.get(&6), Some(&1));
+        let joiner = &game.state.players[&6];
+        assert_eq!(joiner.invulnerability_remaining, INVULNERABILITY_DURATION);
+        let nearest = game
+            .state
+            .players
+            .iter()
+            .filter(|&(&pid, _)| pid != 6)
+            .map(|(_, p)| (p.x - joiner.x).hypot(p.z - joiner.z))
+            .fold(f32::INFINITY, f32::min);
+        assert!(
+            nearest > PLAYER_RADIUS * 4.0,
+            "Spawned {nearest} from a player"
+        );
+
+        // Benched joiners are on the scoreboard but not in the arena
+        let mut config = default_config(180);
+        config.custom.insert(
+            LATE_JOIN_SETTING.to_string(),
+            serde_json::json!("next_round"),
+        );
+        game.init(&players[..2], &config);
+        game.player_joined(&players[2]);
+        assert!(!game.state.players.contains_key(&3));
+        assert!(game.round_results().iter().any(|r| r.player_id == 3));
+
+        // Spectating joiners are in neither
+        config
+            .custom
+            .insert(LATE_JOIN_SETTING.to_string(), serde_json::json!("spectate"));
+        game.init(&players[..2], &config);
+        game.player_joined(&players[2]);
+        assert!(!game.state.players.contains_key(&3));
+        assert!(game.round_results().iter().all(|r| r.player_id != 3));
+    }
+
+    #[test]
+    fn repeat_tags_on_one_victim_stun_for_less() {
+        let mut game = LaserTagArena::default();
+        game.init(&make_players(2), &default_config(180));
+        let mut events = Vec::new();
+        let mut tag_player_2 = |game: &mut LaserTagArena| {
+            let target = game.state.players.get_mut(&2).unwrap();
+            (target.x, target.z) = (10.0, 10.0);
+            target.stun_remaining = 0.0;
+            target.invulnerability_remaining = 0.0;
+            game.fire_laser(1, 5.0, 10.0, 0.0, &mut events);
+            game.state.players[&2].stun_remaining
+        };
+
+        let first = tag_player_2(&mut game);
+        assert_eq!(first, STUN_DURATION);
+        game.state.round_timer += 3.0;
+        let second = tag_player_2(&mut game);
+        assert!(second < first, "Chained stun {second} should be shorter");
+
+        // Leaving clears the chain
+        game.player_left(2);
+        assert!(!game.state.recent_stuns.contains_key(&2));
+    }
+
+    #[test]
+    fn spectators_are_pointed_at_the_latest_tagger_and_streaks() {
+        use breakpoint_core::spectator::HighlightKind;
+
+        let mut game = LaserTagArena::default();
+        let mut config = default_config(180);
+        config
+            .custom
+            .insert("fog_of_war".to_string(), serde_json::Value::Bool(true));
+        game.init(&make_players(3), &config);
+        let mut events = Vec::new();
+        for _ in 0..3 {
+            let target = game.state.players.get_mut(&2).unwrap();
+            (target.x, target.z) = (10.0, 10.0);
+            target.stun_remaining = 0.0;
+            target.invulnerability_remaining = 0.0;
+            game.fire_laser(1, 5.0, 10.0, 0.0, &mut events);
+            game.state.round_timer += 10.0;
+        }
+
+        let view = game.spectator_view_state().expect("spectator view");
+        let view: LaserTagState = rmp_serde::from_slice(&view).unwrap();
+        assert_eq!(view.players.len(), 3, "Spectators see through the fog");
+        let cues = view.spectator.expect("cues");
+        assert_eq!(cues.focus, Some(1), "The player on a streak");
+        assert_eq!(cues.highlight_of(1), Some(HighlightKind::Leader));
+        assert!(
+            cues.highlights
+                .iter()
+                .any(|h| h.player_id == 1 && h.kind == HighlightKind::Streak { count: 3 })
+        );
+
+        // Players never get the cues
+        let mut buf = Vec::new();
+        game.serialize_state_for_into(2, &mut buf);
+        let own: LaserTagState = rmp_serde::from_slice(&buf).unwrap();
+        assert!(own.spectator.is_none());
+        assert!(game.state.spectator.is_none());
+
+        // Being tagged ends a streak, and the new tagger takes the focus
+        game.state.players.get_mut(&2).unwrap().z = 40.0;
+        let target = game.state.players.get_mut(&1).unwrap();
+        (target.x, target.z) = (10.0, 10.0);
+        game.fire_laser(3, 5.0, 10.0, 0.0, &mut events);
+        let cues = game.spotlight.cues(&game.state);
+        assert_eq!(cues.focus, Some(3));
+        assert_eq!(cues.highlights.len(), 1, "Only the lead is left");
+    }
+
     // REGRESSION: Stunned player should not be able to move
     #[test]
     fn stunned_player_cannot_move() {
@@ -2005,6 +3166,50 @@ mod tests {
         config
     }
 
+    #[test]
+    fn explicit_teams_and_shuffled_teams() {
+        let players = make_players(5);
+        let mut config = teams_config();
+        config.custom.insert(
+            team_draft::TEAMS_SETTING.to_string(),
+            team_draft::teams_setting(&HashMap::from([(1, 1), (2, 1), (3, 0), (9, 0)])),
+        );
+        assert_eq!(LaserTagArena::new().team_count_for(&config.custom), 2);
+        assert_eq!(
+            LaserTagArena::new().team_count_for(&default_config(180).custom),
+            0
+        );
+
+        let mut game = LaserTagArena::new();
+        game.init(&players, &config);
+        // Drafted players keep their teams; the undrafted fill the smaller
+        assert_eq!(
+            game.state.teams,
+            HashMap::from([(1, 1), (2, 1), (3, 0), (4, 0), (5, 0)])
+        );
+
+        config.custom.insert(
+            "team_select".to_string(),
+            serde_json::Value::String("shuffle".to_string()),
+        );
+        game.init(&players, &config);
+        // Shuffling ignores the explicit teams but keeps them balanced
+        assert_eq!(game.state.teams.len(), 5);
+        let team0 = game.state.teams.values().filter(|&&t| t == 0).count();
+        assert!(
+            team0 == 2 || team0 == 3,
+            "Unbalanced shuffle: {team0} on team 0"
+        );
+
+        // A seeded shuffle deals the same teams again
+        config.custom.insert("seed".to_string(), 11.into());
+        game.init(&players, &config);
+        let seeded = game.state.teams.clone();
+        let mut rerun = LaserTagArena::new();
+        rerun.init(&players, &config);
+        assert_eq!(rerun.state.teams, seeded);
+    }
+
     #[test]
     fn three_team_mode_assignment() {
         let mut game = LaserTagArena::new();
@@ -2402,22 +3607,6 @@ mod tests {
         );
     }
 
-    #[test]
-    fn segment_intersects_circle_basic() {
-        // Line through circle center
-        assert!(super::segment_intersects_circle(
-            0.0, 0.0, 10.0, 0.0, 5.0, 0.0, 1.0
-        ));
-        // Line misses circle
-        assert!(!super::segment_intersects_circle(
-            0.0, 0.0, 10.0, 0.0, 5.0, 5.0, 1.0
-        ));
-        // Line ends before circle
-        assert!(!super::segment_intersects_circle(
-            0.0, 0.0, 2.0, 0.0, 5.0, 0.0, 1.0
-        ));
-    }
-
     #[test]
     fn nan_inputs_sanitized() {
         let mut game = LaserTagArena::new();
@@ -2454,4 +3643,40 @@ mod tests {
             "NaN move inputs should be sanitized to 0 — no movement expected"
         );
     }
+
+    #[test]
+    fn tick_fits_its_budget_at_max_players() {
+        use breakpoint_core::test_helpers::assert_tick_budget;
+
+        let mut game = LaserTagArena::new();
+        let players = make_players(game.metadata().max_players as usize);
+        game.init(&players, &default_config(180));
+        let energy = game.state.players[&1].energy.clone();
+
+        // Worst case: everyone moves and fires every tick, so each tick
+        // casts a laser per player
+        let mut tick = 0;
+        assert_tick_budget(
+            "laser tag update, 8 players",
+            &mut game,
+            Duration::from_millis(1),
+            |game| {
+                tick += 1;
+                for (i, player) in players.iter().enumerate() {
+                    let state = game.state.players.get_mut(&player.id).unwrap();
+                    state.fire_cooldown = 0.0;
+                    state.stun_remaining = 0.0;
+                    state.energy = energy.clone();
+                    let input = LaserTagInput {
+                        move_x: (tick as f32 * 0.1 + i as f32).cos(),
+                        move_z: (tick as f32 * 0.1 + i as f32).sin(),
+                        aim_angle: tick as f32 * 0.37 + i as f32,
+                        fire: true,
+                        use_powerup: false,
+                    };
+                    game.apply_input(player.id, &rmp_serde::to_vec(&input).unwrap());
+                }
+            },
+        );
+    }
 }
@@ -10,6 +10,8 @@ pub enum LaserPowerUpKind {
     SpeedBoost,
     /// Stub: spawns and can be collected, but has no gameplay effect yet.
     WideBeam,
+    /// Held until used, then deployed as a [`crate::turret::LaserTurret`].
+    Turret,
 }
 
 impl powerup::PowerUpKind for LaserPowerUpKind {
@@ -19,6 +21,17 @@ impl powerup::PowerUpKind for LaserPowerUpKind {
             LaserPowerUpKind::Shield => f32::INFINITY,
             LaserPowerUpKind::SpeedBoost => 4.0,
             LaserPowerUpKind::WideBeam => 3.0,
+            LaserPowerUpKind::Turret => f32::INFINITY,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            LaserPowerUpKind::RapidFire => "rapid_fire",
+            LaserPowerUpKind::Shield => "shield",
+            LaserPowerUpKind::SpeedBoost => "speed_boost",
+            LaserPowerUpKind::WideBeam => "wide_beam",
+            LaserPowerUpKind::Turret => "turret",
         }
     }
 }
@@ -1,4 +1,10 @@
+use breakpoint_core::math::{Vec2, ray_circle, ray_segment};
+
 use crate::arena::{ArenaWall, WallType};
+use crate::energy::EnergyConfig;
+use crate::scoring::AssistConfig;
+use crate::stun::StunConfig;
+use crate::turret::TurretConfig;
 use serde::{Deserialize, Serialize};
 
 /// Laser travel speed in units/second.
@@ -50,6 +56,10 @@ pub struct LaserTagConfig {
     pub physics: LaserTagPhysicsConfig,
     pub round_duration_secs: f32,
     pub tick_rate_hz: f32,
+    pub energy: EnergyConfig,
+    pub turret: TurretConfig,
+    pub assist: AssistConfig,
+    pub stun: StunConfig,
 }
 
 impl Default for LaserTagConfig {
@@ -58,6 +68,10 @@ impl Default for LaserTagConfig {
             physics: LaserTagPhysicsConfig::default(),
             round_duration_secs: 180.0,
             tick_rate_hz: 20.0,
+            energy: EnergyConfig::default(),
+            turret: TurretConfig::default(),
+            assist: AssistConfig::default(),
+            stun: StunConfig::default(),
         }
     }
 }
@@ -107,6 +121,34 @@ pub fn raycast_laser(
     shooter_id: u64,
     team_ids: &[u64],
     max_distance: f32,
+) -> LaserHitResult {
+    raycast_beam(
+        origin_x,
+        origin_z,
+        aim_angle,
+        walls,
+        players,
+        shooter_id,
+        team_ids,
+        max_distance,
+        PLAYER_RADIUS,
+    )
+}
+
+/// [`raycast_laser`] for a beam that tags anyone within `hit_radius` of
+/// its center line, for rounds with the `beam_width` setting. Walls still
+/// stop and reflect the center line.
+#[allow(clippy::too_many_arguments)]
+pub fn raycast_beam(
+    origin_x: f32,
+    origin_z: f32,
+    aim_angle: f32,
+    walls: &[ArenaWall],
+    players: &[(u64, f32, f32)],
+    shooter_id: u64,
+    team_ids: &[u64],
+    max_distance: f32,
+    hit_radius: f32,
 ) -> LaserHitResult {
     let mut segments = Vec::new();
     let mut cx = origin_x;
@@ -125,22 +167,33 @@ pub fn raycast_laser(
         let mut nearest_wall_normal = (0.0f32, 0.0f32);
 
         for (i, wall) in walls.iter().enumerate() {
-            if let Some((t, nx, nz)) =
-                ray_segment_intersection(cx, cz, dx, dz, wall.ax, wall.az, wall.bx, wall.bz)
-                && t > 0.01
+            if let Some((t, normal)) = ray_segment(
+                Vec2::new(cx, cz),
+                Vec2::new(dx, dz),
+                Vec2::new(wall.ax, wall.az),
+                Vec2::new(wall.bx, wall.bz),
+            ) && t > 0.01
                 && t < nearest_wall_t
             {
                 nearest_wall_t = t;
                 nearest_wall_idx = Some(i);
-                nearest_wall_normal = (nx, nz);
+                nearest_wall_normal = (normal.x, normal.y);
             }
         }
 
         // Check player hits along this ray segment
         let segment_len = nearest_wall_t;
-        if let Some((hit_t, pid)) =
-            check_player_hits(cx, cz, dx, dz, segment_len, players, shooter_id, team_ids)
-        {
+        if let Some((hit_t, pid)) = check_player_hits(
+            cx,
+            cz,
+            dx,
+            dz,
+            segment_len,
+            players,
+            shooter_id,
+            team_ids,
+            hit_radius,
+        ) {
             let end_x = cx + dx * hit_t;
             let end_z = cz + dz * hit_t;
             segments.push((cx, cz, end_x, end_z));
@@ -185,48 +238,6 @@ pub fn raycast_laser(
     }
 }
 
-/// Ray-segment intersection. Returns (t, normal_x, normal_z) if hit.
-#[allow(clippy::too_many_arguments)]
-pub(crate) fn ray_segment_intersection(
-    ox: f32,
-    oz: f32,
-    dx: f32,
-    dz: f32,
-    ax: f32,
-    az: f32,
-    bx: f32,
-    bz: f32,
-) -> Option<(f32, f32, f32)> {
-    let sx = bx - ax;
-    let sz = bz - az;
-
-    let denom = dx * sz - dz * sx;
-    if denom.abs() < 1e-8 {
-        return None; // parallel
-    }
-
-    let t = ((ax - ox) * sz - (az - oz) * sx) / denom;
-    let u = ((ax - ox) * dz - (az - oz) * dx) / denom;
-
-    if t > 0.0 && (0.0..=1.0).contains(&u) {
-        // Normal: perpendicular to segment
-        let len = (sx * sx + sz * sz).sqrt();
-        if len < 1e-6 {
-            return None;
-        }
-        let nx = -sz / len;
-        let nz = sx / len;
-        // Ensure normal faces the ray origin
-        if nx * dx + nz * dz > 0.0 {
-            Some((t, -nx, -nz))
-        } else {
-            Some((t, nx, nz))
-        }
-    } else {
-        None
-    }
-}
-
 /// Check for player hits along a ray segment. Returns (t, player_id) for nearest hit.
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn check_player_hits(
@@ -238,6 +249,7 @@ pub(crate) fn check_player_hits(
     players: &[(u64, f32, f32)],
     shooter_id: u64,
     team_ids: &[u64],
+    hit_radius: f32,
 ) -> Option<(f32, u64)> {
     let mut nearest: Option<(f32, u64)> = None;
 
@@ -247,8 +259,12 @@ pub(crate) fn check_player_hits(
         }
 
         // Line-circle intersection
-        if let Some(t) = ray_circle_intersection(ox, oz, dx, dz, px, pz, PLAYER_RADIUS)
-            && t > 0.01
+        if let Some(t) = ray_circle(
+            Vec2::new(ox, oz),
+            Vec2::new(dx, dz),
+            Vec2::new(px, pz),
+            hit_radius,
+        ) && t > 0.01
             && t < max_t
             && (nearest.is_none() || t < nearest.unwrap().0)
         {
@@ -259,40 +275,6 @@ pub(crate) fn check_player_hits(
     nearest
 }
 
-/// Ray-circle intersection (2D). Returns nearest t if hit.
-pub(crate) fn ray_circle_intersection(
-    ox: f32,
-    oz: f32,
-    dx: f32,
-    dz: f32,
-    cx: f32,
-    cz: f32,
-    radius: f32,
-) -> Option<f32> {
-    let fx = ox - cx;
-    let fz = oz - cz;
-    let a = dx * dx + dz * dz;
-    let b = 2.0 * (fx * dx + fz * dz);
-    let c = fx * fx + fz * fz - radius * radius;
-    let discriminant = b * b - 4.0 * a * c;
-
-    if discriminant < 0.0 {
-        return None;
-    }
-
-    let sqrt_d = discriminant.sqrt();
-    let t1 = (-b - sqrt_d) / (2.0 * a);
-    let t2 = (-b + sqrt_d) / (2.0 * a);
-
-    if t1 > 0.0 {
-        Some(t1)
-    } else if t2 > 0.0 {
-        Some(t2)
-    } else {
-        None
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -345,6 +327,15 @@ mod tests {
         assert_eq!(result.hit_player, Some(2));
     }
 
+    #[test]
+    fn wide_beam_tags_near_misses() {
+        let players = vec![(2, 5.0, 1.0)];
+        let narrow = raycast_laser(0.0, 0.0, 0.0, &[], &players, 1, &[], 200.0);
+        assert!(narrow.hit_player.is_none());
+        let wide = raycast_beam(0.0, 0.0, 0.0, &[], &players, 1, &[], 200.0, 1.2);
+        assert_eq!(wide.hit_player, Some(2));
+    }
+
     #[test]
     fn laser_does_not_hit_shooter() {
         let walls = vec![];
@@ -392,11 +383,11 @@ mod tests {
     #[test]
     fn ray_segment_near_parallel_returns_none() {
         // Ray nearly parallel to segment — denom close to 0
-        let result = ray_segment_intersection(
-            0.0, 0.0, // origin
-            1.0, 0.0, // direction: +X
-            0.0, 5.0, // segment start
-            10.0, 5.0, // segment end (horizontal, parallel to ray)
+        let result = ray_segment(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(0.0, 5.0),
+            Vec2::new(10.0, 5.0),
         );
         assert!(result.is_none(), "Parallel ray-segment should return None");
     }
@@ -404,25 +395,25 @@ mod tests {
     #[test]
     fn ray_segment_hits_at_endpoint_start() {
         // Ray aimed at the start of the segment (u ≈ 0)
-        let result = ray_segment_intersection(
-            0.0, 0.0, // origin
-            0.0, 1.0, // direction: +Z
-            -5.0, 5.0, // segment start
-            5.0, 5.0, // segment end
+        let result = ray_segment(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(0.0, 1.0),
+            Vec2::new(-5.0, 5.0),
+            Vec2::new(5.0, 5.0),
         );
         assert!(result.is_some(), "Ray should hit segment at u=0.5");
-        let (t, _, _) = result.unwrap();
+        let (t, _) = result.unwrap();
         assert!((t - 5.0).abs() < 0.1, "t should be ~5.0, got {t}");
     }
 
     #[test]
     fn ray_segment_misses_past_endpoint() {
         // Ray aimed past the segment endpoint (u > 1)
-        let result = ray_segment_intersection(
-            0.0, 0.0, // origin
-            0.0, 1.0, // direction: +Z
-            5.0, 5.0, // segment from (5,5) to (10,5)
-            10.0, 5.0,
+        let result = ray_segment(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(0.0, 1.0),
+            Vec2::new(5.0, 5.0),
+            Vec2::new(10.0, 5.0),
         );
         assert!(
             result.is_none(),
@@ -433,11 +424,11 @@ mod tests {
     #[test]
     fn ray_segment_degenerate_zero_length() {
         // Zero-length segment
-        let result = ray_segment_intersection(
-            0.0, 0.0, // origin
-            1.0, 0.0, // direction
-            5.0, 5.0, // degenerate segment (same start/end)
-            5.0, 5.0,
+        let result = ray_segment(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(5.0, 5.0),
+            Vec2::new(5.0, 5.0),
         );
         assert!(result.is_none(), "Zero-length segment should return None");
     }
@@ -445,14 +436,15 @@ mod tests {
     #[test]
     fn ray_segment_normal_faces_ray_origin() {
         // Ray going +X, hits a vertical segment
-        let result = ray_segment_intersection(
-            0.0, 0.0, // origin
-            1.0, 0.0, // direction: +X
-            5.0, -5.0, // vertical segment at x=5
-            5.0, 5.0,
+        let result = ray_segment(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(5.0, -5.0),
+            Vec2::new(5.0, 5.0),
         );
         assert!(result.is_some());
-        let (_, nx, nz) = result.unwrap();
+        let (_, normal) = result.unwrap();
+        let (nx, nz) = (normal.x, normal.y);
         // Normal should face back toward origin (negative X direction)
         let dot = nx * 1.0 + nz * 0.0;
         assert!(
@@ -464,14 +456,15 @@ mod tests {
     #[test]
     fn ray_segment_perpendicular_hit() {
         // Simple perpendicular hit: ray +X hitting vertical segment at x=10
-        let result = ray_segment_intersection(
-            0.0, 0.0, // origin
-            1.0, 0.0, // direction: +X
-            10.0, -5.0, // vertical segment at x=10
-            10.0, 5.0,
+        let result = ray_segment(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(10.0, -5.0),
+            Vec2::new(10.0, 5.0),
         );
         assert!(result.is_some());
-        let (t, nx, _nz) = result.unwrap();
+        let (t, normal) = result.unwrap();
+        let nx = normal.x;
         assert!(
             (t - 10.0).abs() < 0.1,
             "t should be ~10.0 for perpendicular hit, got {t}"
@@ -489,11 +482,11 @@ mod tests {
     #[test]
     fn ray_circle_direct_center_hit() {
         // Ray aimed directly at circle center
-        let result = ray_circle_intersection(
-            0.0, 0.0, // origin
-            1.0, 0.0, // direction: +X
-            10.0, 0.0, // circle center
-            1.0, // radius
+        let result = ray_circle(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            1.0,
         );
         assert!(result.is_some(), "Direct center hit should return Some");
         let t = result.unwrap();
@@ -507,11 +500,11 @@ mod tests {
     #[test]
     fn ray_circle_tangent_near_miss() {
         // Ray that just barely misses the circle (passes tangentially outside)
-        let result = ray_circle_intersection(
-            0.0, 0.0, // origin
-            1.0, 0.0, // direction: +X
-            10.0, 1.1, // circle center offset just beyond radius
-            1.0, // radius
+        let result = ray_circle(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(10.0, 1.1),
+            1.0,
         );
         assert!(result.is_none(), "Tangent near-miss should return None");
     }
@@ -519,10 +512,10 @@ mod tests {
     #[test]
     fn ray_circle_clear_miss() {
         // Ray parallel to circle edge, far away
-        let result = ray_circle_intersection(
-            0.0, 0.0, // origin
-            1.0, 0.0, // direction: +X
-            10.0, 10.0, // circle far away in Z
+        let result = ray_circle(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(10.0, 10.0),
             1.0,
         );
         assert!(result.is_none(), "Clear miss should return None");
@@ -531,11 +524,11 @@ mod tests {
     #[test]
     fn ray_circle_starts_inside() {
         // Origin inside circle — should return exit point (t2)
-        let result = ray_circle_intersection(
-            10.0, 0.0, // origin inside circle centered at (10,0)
-            1.0, 0.0, // direction: +X
-            10.0, 0.0, // circle center
-            5.0, // radius
+        let result = ray_circle(
+            Vec2::new(10.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            5.0,
         );
         assert!(
             result.is_some(),
@@ -548,10 +541,10 @@ mod tests {
     #[test]
     fn ray_circle_moving_away() {
         // Ray moving away from circle (behind origin)
-        let result = ray_circle_intersection(
-            0.0, 0.0, // origin
-            -1.0, 0.0, // direction: -X (away from circle)
-            10.0, 0.0, // circle center at +X
+        let result = ray_circle(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(-1.0, 0.0),
+            Vec2::new(10.0, 0.0),
             1.0,
         );
         assert!(
@@ -563,10 +556,10 @@ mod tests {
     #[test]
     fn ray_circle_glancing_hit() {
         // Ray that barely intersects the circle
-        let result = ray_circle_intersection(
-            0.0, 0.0, // origin
-            1.0, 0.0, // direction: +X
-            10.0, 0.95, // circle center offset just within radius
+        let result = ray_circle(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(10.0, 0.95),
             1.0,
         );
         assert!(result.is_some(), "Glancing hit should return Some");
@@ -734,12 +727,7 @@ mod tests {
                 radius in 0.5f32..3.0
             ) {
                 // Ray from origin aimed at circle center should always hit
-                let result = ray_circle_intersection(
-                    0.0, 0.0,
-                    1.0, 0.0,
-                    distance, 0.0,
-                    radius,
-                );
+                let result = ray_circle(Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(distance, 0.0), radius);
                 prop_assert!(
                     result.is_some(),
                     "Ray aimed at center should always hit: distance={distance}, radius={radius}"
@@ -799,4 +787,44 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn raycast_fits_its_budget_in_the_large_arena() {
+        use std::time::Duration;
+
+        use breakpoint_core::test_helpers::assert_within_budget;
+
+        use crate::arena::{ArenaSize, generate_arena};
+
+        let arena = generate_arena(ArenaSize::Large);
+        let players: Vec<(u64, f32, f32)> = (1..=8u64)
+            .map(|id| {
+                let angle = id as f32 * std::f32::consts::TAU / 8.0;
+                (
+                    id,
+                    arena.width / 2.0 + angle.cos() * arena.width / 3.0,
+                    arena.depth / 2.0 + angle.sin() * arena.depth / 3.0,
+                )
+            })
+            .collect();
+        let mut shot = 0;
+        assert_within_budget(
+            "raycast_laser, large arena, 8 players",
+            Duration::from_micros(5),
+            || {
+                shot += 1;
+                let (shooter, x, z) = players[shot % players.len()];
+                std::hint::black_box(raycast_laser(
+                    x,
+                    z,
+                    shot as f32 * 0.37,
+                    &arena.walls,
+                    &players,
+                    shooter,
+                    &[],
+                    100.0,
+                ));
+            },
+        );
+    }
 }
@@ -1,8 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+/// Assist credit: breaking a shield sets up a teammate's (or anyone's) tag.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AssistConfig {
+    /// Seconds after a shield break during which another player's tag on
+    /// the same target credits the shield-breaker with an assist.
+    pub window_secs: f32,
+    /// Points per assist; a tag is worth 1.
+    pub points: i32,
+}
+
+impl Default for AssistConfig {
+    fn default() -> Self {
+        Self {
+            window_secs: 3.0,
+            points: 1,
+        }
+    }
+}
+
 /// Free-for-all scoring: score = number of tags scored.
 pub fn ffa_score(tags_scored: u32) -> i32 {
     tags_scored as i32
 }
 
+/// Points earned from assists.
+pub fn assist_score(assists: u32, config: &AssistConfig) -> i32 {
+    assists as i32 * config.points
+}
+
 /// Team scoring: team_score = sum of all members' tag counts.
 pub fn team_score(member_tags: &[u32]) -> i32 {
     member_tags.iter().sum::<u32>() as i32
@@ -19,6 +46,17 @@ mod tests {
         assert_eq!(ffa_score(10), 10);
     }
 
+    #[test]
+    fn assist_scoring_uses_configured_points() {
+        assert_eq!(assist_score(3, &AssistConfig::default()), 3);
+        let config = AssistConfig {
+            points: 2,
+            ..AssistConfig::default()
+        };
+        assert_eq!(assist_score(3, &config), 6);
+        assert_eq!(assist_score(0, &config), 0);
+    }
+
     #[test]
     fn team_scoring() {
         assert_eq!(team_score(&[3, 2, 5]), 10);
@@ -0,0 +1,142 @@
+use breakpoint_core::game_settings::{
+    CustomSettings, FromGameConfig, SettingsError, SettingsReader,
+};
+use breakpoint_core::late_join::LateJoinPolicy;
+use breakpoint_core::round_phase::{self, MICRO_ROUNDS_SETTING};
+
+use crate::TeamMode;
+use crate::arena::ArenaSize;
+
+/// Shortest and longest round a lobby can set, in seconds.
+pub const ROUND_DURATION_RANGE: (f32, f32) = (1.0, 3600.0);
+
+/// Widest `beam_width` a lobby can set, in arena units.
+pub const MAX_BEAM_WIDTH: f32 = 2.0;
+
+/// How players are split into teams at the start of each round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TeamSelect {
+    /// Alternate players across the teams in join order.
+    RoundRobin,
+    /// Deal players onto teams in a fresh random order every round.
+    Shuffle,
+    /// Captains pick the teams before the match; the host hands the result
+    /// to `init` as the explicit `teams` setting.
+    Draft,
+}
+
+/// Laser tag lobby settings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LaserTagSettings {
+    /// `team_mode`: `ffa` or `teams_2` to `teams_4`.
+    pub team_mode: TeamMode,
+    /// `team_select`: `round_robin`, `shuffle`, or `draft`.
+    pub team_select: TeamSelect,
+    /// `arena_size`: `small`, `default`, or `large`.
+    pub arena_size: ArenaSize,
+    /// `round_duration`, in seconds. Micro-rounds set it instead, so the
+    /// two can't both be given; nor can a large arena, which takes longer
+    /// than a micro-round to cross.
+    pub round_duration_secs: f32,
+    /// `fog_of_war`: send each player only the enemies they can see.
+    pub fog_of_war: bool,
+    /// `beam_width`: extra laser width, in arena units. A beam tags anyone
+    /// it passes within half this distance of, on top of their own radius.
+    pub beam_width: f32,
+    /// `late_join`: `spawn` (the default) drops late joiners straight into
+    /// the arena; `next_round` and `spectate` hold them back.
+    pub late_join: LateJoinPolicy,
+    /// `seed`: deals `shuffle` teams the same way each time it's given, so
+    /// a recorded match can be re-run; random when unset.
+    pub seed: Option<u64>,
+}
+
+impl Default for LaserTagSettings {
+    fn default() -> Self {
+        Self {
+            team_mode: TeamMode::FreeForAll,
+            team_select: TeamSelect::RoundRobin,
+            arena_size: ArenaSize::Default,
+            round_duration_secs: 180.0,
+            fog_of_war: false,
+            beam_width: 0.0,
+            late_join: LateJoinPolicy::SpawnImmediately,
+            seed: None,
+        }
+    }
+}
+
+impl FromGameConfig for LaserTagSettings {
+    fn from_custom(custom: &CustomSettings) -> Result<Self, SettingsError> {
+        let reader = SettingsReader::new(custom);
+        let defaults = Self::default();
+        let (min_secs, max_secs) = ROUND_DURATION_RANGE;
+        let round_duration = reader.f32_in("round_duration", min_secs, max_secs)?;
+        let arena_size = reader
+            .choice(
+                "arena_size",
+                &[
+                    ("small", ArenaSize::Small),
+                    ("default", ArenaSize::Default),
+                    ("large", ArenaSize::Large),
+                ],
+            )?
+            .unwrap_or(defaults.arena_size);
+        let micro_round_secs = round_phase::micro_round_secs(custom)?;
+        if micro_round_secs.is_some() {
+            if round_duration.is_some() {
+                return Err(SettingsError::new(
+                    MICRO_ROUNDS_SETTING,
+                    "micro-rounds set their own round_duration",
+                ));
+            }
+            if arena_size == ArenaSize::Large {
+                return Err(SettingsError::new(
+                    MICRO_ROUNDS_SETTING,
+                    "micro-rounds can't be played in a large arena",
+                ));
+            }
+        }
+        Ok(Self {
+            team_mode: reader
+                .choice(
+                    "team_mode",
+                    &[
+                        ("ffa", TeamMode::FreeForAll),
+                        ("teams_2", TeamMode::Teams { team_count: 2 }),
+                        ("teams_3", TeamMode::Teams { team_count: 3 }),
+                        ("teams_4", TeamMode::Teams { team_count: 4 }),
+                    ],
+                )?
+                .unwrap_or(defaults.team_mode),
+            team_select: reader
+                .choice(
+                    "team_select",
+                    &[
+                        ("round_robin", TeamSelect::RoundRobin),
+                        ("shuffle", TeamSelect::Shuffle),
+                        ("draft", TeamSelect::Draft),
+                    ],
+                )?
+                .unwrap_or(defaults.team_select),
+            arena_size,
+            round_duration_secs: micro_round_secs
+                .or(round_duration)
+                .unwrap_or(defaults.round_duration_secs),
+            fog_of_war: reader.bool("fog_of_war")?.unwrap_or(defaults.fog_of_war),
+            beam_width: reader
+                .f32_in("beam_width", 0.0, MAX_BEAM_WIDTH)?
+                .unwrap_or(defaults.beam_width),
+            late_join: LateJoinPolicy::from_custom(
+                custom,
+                defaults.late_join,
+                &[
+                    LateJoinPolicy::Spectate,
+                    LateJoinPolicy::SpawnImmediately,
+                    LateJoinPolicy::SpawnNextRound,
+                ],
+            )?,
+            seed: reader.u64("seed")?,
+        })
+    }
+}
@@ -0,0 +1,67 @@
+//! Spectator cues for laser tag. Spectators see the whole arena, so the
+//! cues point out who to watch: the latest tagger for a few seconds, then
+//! whoever is on the longest streak.
+
+use std::collections::HashMap;
+
+use breakpoint_core::game_trait::PlayerId;
+use breakpoint_core::spectator::SpectatorCues;
+
+use crate::LaserTagState;
+
+/// Seconds the focus stays on a player after they tag someone.
+pub const FOCUS_HOLD_SECS: f32 = 3.0;
+
+/// Host-side record of the round the cues are built from.
+#[derive(Debug, Clone, Default)]
+pub struct Spotlight {
+    /// Tags by each player since they were last stunned.
+    streaks: HashMap<PlayerId, u32>,
+    /// The latest tag's shooter and round time.
+    last_tag: Option<(PlayerId, f32)>,
+}
+
+impl Spotlight {
+    /// `shooter` stunned `victim` at round time `at`, ending the victim's
+    /// streak.
+    pub fn record_tag(&mut self, shooter: PlayerId, victim: PlayerId, at: f32) {
+        self.streaks.remove(&victim);
+        *self.streaks.entry(shooter).or_insert(0) += 1;
+        self.last_tag = Some((shooter, at));
+    }
+
+    pub fn player_left(&mut self, player_id: PlayerId) {
+        self.streaks.remove(&player_id);
+        if self.last_tag.is_some_and(|(pid, _)| pid == player_id) {
+            self.last_tag = None;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.streaks.clear();
+        self.last_tag = None;
+    }
+
+    pub fn cues(&self, state: &LaserTagState) -> SpectatorCues {
+        let scores = state
+            .tags_scored
+            .iter()
+            .map(|(&pid, &tags)| (pid, i64::from(tags)));
+        let streaks = self.streaks.iter().map(|(&pid, &count)| (pid, count));
+        SpectatorCues::new(self.focus(state), scores, streaks)
+    }
+
+    fn focus(&self, state: &LaserTagState) -> Option<PlayerId> {
+        if let Some((shooter, at)) = self.last_tag
+            && state.round_timer - at <= FOCUS_HOLD_SECS
+            && state.players.contains_key(&shooter)
+        {
+            return Some(shooter);
+        }
+        self.streaks
+            .iter()
+            .filter(|(pid, _)| state.players.contains_key(pid))
+            .max_by_key(|&(&pid, &count)| (count, std::cmp::Reverse(pid)))
+            .map(|(&pid, _)| pid)
+    }
+}
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+/// How long a tag stuns, given how recently the victim was last tagged.
+/// Tags chained on the same victim stun for less each time, so a player
+/// caught at a spawn point gets out; a player nobody has tagged for a while
+/// is stunned a little longer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StunConfig {
+    /// Off: every tag stuns for the base duration.
+    pub enabled: bool,
+    /// A tag within this many seconds of the victim's previous one extends
+    /// their chain.
+    pub chain_window_secs: f32,
+    /// Each tag in a chain stuns for this fraction of the one before.
+    pub chain_factor: f32,
+    /// Chained stuns never drop below this.
+    pub min_stun_secs: f32,
+    /// Players untagged for this long (or since the round began) take the
+    /// longer stun.
+    pub untagged_secs: f32,
+    /// Multiplier on the base duration for a long-untagged player.
+    pub untagged_factor: f32,
+}
+
+impl Default for StunConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            chain_window_secs: 6.0,
+            chain_factor: 0.6,
+            min_stun_secs: 0.5,
+            untagged_secs: 30.0,
+            untagged_factor: 1.2,
+        }
+    }
+}
+
+/// A victim's most recent stun, for chaining.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct RecentStun {
+    /// Round time of the last tag.
+    pub at: f32,
+    /// Earlier tags in the chain the last one belonged to; 0 for a fresh
+    /// stun.
+    pub chain: u32,
+}
+
+impl RecentStun {
+    /// The stun for a tag at round time `now`, given the victim's previous
+    /// one, and the record to keep for the next.
+    pub fn next(
+        previous: Option<RecentStun>,
+        now: f32,
+        base_secs: f32,
+        config: &StunConfig,
+    ) -> (f32, RecentStun) {
+        if !config.enabled {
+            return (base_secs, RecentStun { at: now, chain: 0 });
+        }
+        let since = previous.map_or(now, |p| now - p.at);
+        let chain = match previous {
+            Some(p) if since <= config.chain_window_secs => p.chain + 1,
+            _ => 0,
+        };
+        let secs = if chain > 0 {
+            (base_secs * config.chain_factor.powi(chain as i32))
+                .max(config.min_stun_secs.min(base_secs))
+        } else if since >= config.untagged_secs {
+            base_secs * config.untagged_factor
+        } else {
+            base_secs
+        };
+        (secs, RecentStun { at: now, chain })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: f32 = 1.5;
+
+    #[test]
+    fn chained_tags_stun_for_less_down_to_the_floor() {
+        let config = StunConfig::default();
+        let (first, rec) = RecentStun::next(None, 5.0, BASE, &config);
+        assert_eq!(first, BASE);
+        let (second, rec) = RecentStun::next(Some(rec), 8.0, BASE, &config);
+        assert!((second - BASE * 0.6).abs() < 1e-5, "got {second}");
+        let (third, rec) = RecentStun::next(Some(rec), 11.0, BASE, &config);
+        assert!(third < second);
+        let (fourth, rec) = RecentStun::next(Some(rec), 14.0, BASE, &config);
+        assert_eq!(fourth, config.min_stun_secs);
+        assert_eq!(rec.chain, 3);
+
+        // Outside the window the chain starts over
+        let (fresh, rec) = RecentStun::next(Some(rec), 25.0, BASE, &config);
+        assert_eq!((fresh, rec.chain), (BASE, 0));
+    }
+
+    #[test]
+    fn long_untagged_players_are_stunned_longer() {
+        let config = StunConfig::default();
+        // Never tagged, well into the round
+        let (secs, _) = RecentStun::next(None, 40.0, BASE, &config);
+        assert!((secs - BASE * 1.2).abs() < 1e-5);
+
+        let last = RecentStun { at: 10.0, chain: 2 };
+        let (secs, rec) = RecentStun::next(Some(last), 45.0, BASE, &config);
+        assert!((secs - BASE * 1.2).abs() < 1e-5);
+        assert_eq!(rec.chain, 0);
+    }
+
+    #[test]
+    fn disabled_always_stuns_for_the_base_duration() {
+        let config = StunConfig {
+            enabled: false,
+            ..StunConfig::default()
+        };
+        let last = RecentStun { at: 9.0, chain: 4 };
+        assert_eq!(RecentStun::next(Some(last), 10.0, BASE, &config).0, BASE);
+        assert_eq!(RecentStun::next(None, 100.0, BASE, &config).0, BASE);
+    }
+}
@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+
+use breakpoint_core::game_trait::PlayerId;
+
+/// Seconds a laser trail stays on screen.
+pub const TRAIL_LIFETIME: f32 = 0.3;
+
+/// World units per quantization step of a trail coordinate (1 cm). Arenas
+/// are at most 70 units across, well inside `i16` range at this scale.
+pub const TRAIL_QUANTUM: f32 = 0.01;
+
+/// A laser shot, sent to clients once (in a `GameEffects` message) when
+/// fired. Clients draw its trail for [`TRAIL_LIFETIME`] seconds from
+/// `fired_at`, aging it against the round timer in each state snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LaserFired {
+    pub shooter: PlayerId,
+    /// Round timer when the shot was fired.
+    pub fired_at: f32,
+    /// Beam segments as `[x1, z1, x2, z2]` in [`TRAIL_QUANTUM`] steps.
+    pub segments: Vec<[i16; 4]>,
+}
+
+impl LaserFired {
+    pub fn new(shooter: PlayerId, fired_at: f32, segments: &[(f32, f32, f32, f32)]) -> Self {
+        Self {
+            shooter,
+            fired_at,
+            segments: segments
+                .iter()
+                .map(|&(x1, z1, x2, z2)| [quantize(x1), quantize(z1), quantize(x2), quantize(z2)])
+                .collect(),
+        }
+    }
+
+    /// Seconds since the shot at round time `now`.
+    pub fn age(&self, now: f32) -> f32 {
+        now - self.fired_at
+    }
+
+    /// Whether the trail shows at round time `now`. A shot can arrive a
+    /// little ahead of the snapshot that contains it; it shows once that
+    /// snapshot is applied.
+    pub fn is_visible(&self, now: f32) -> bool {
+        (0.0..TRAIL_LIFETIME).contains(&self.age(now))
+    }
+
+    /// Whether a client can forget the shot: it has faded, or it is far
+    /// enough ahead of `now` that it belongs to an earlier round.
+    pub fn is_stale(&self, now: f32) -> bool {
+        !(-TRAIL_LIFETIME..TRAIL_LIFETIME).contains(&self.age(now))
+    }
+
+    /// The renderable trail at round time `now`.
+    pub fn trail(&self, now: f32) -> LaserTrail {
+        LaserTrail {
+            segments: self
+                .segments
+                .iter()
+                .map(|&[x1, z1, x2, z2]| {
+                    (
+                        dequantize(x1),
+                        dequantize(z1),
+                        dequantize(x2),
+                        dequantize(z2),
+                    )
+                })
+                .collect(),
+            age: self.age(now),
+        }
+    }
+}
+
+/// Visual laser trail for client rendering.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaserTrail {
+    pub segments: Vec<(f32, f32, f32, f32)>,
+    pub age: f32,
+}
+
+fn quantize(v: f32) -> i16 {
+    (v / TRAIL_QUANTUM)
+        .round()
+        .clamp(f32::from(i16::MIN), f32::from(i16::MAX)) as i16
+}
+
+fn dequantize(q: i16) -> f32 {
+    f32::from(q) * TRAIL_QUANTUM
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segments_survive_quantization_within_half_a_step() {
+        let segments = [(1.234_56, 49.999, 0.0, 70.0), (25.5, 12.125, 3.333, 0.004)];
+        let shot = LaserFired::new(1, 2.0, &segments);
+        let trail = shot.trail(2.1);
+        assert!((trail.age - 0.1).abs() < 1e-5);
+        for (&(a, b, c, d), &(qa, qb, qc, qd)) in segments.iter().zip(&trail.segments) {
+            for (v, q) in [(a, qa), (b, qb), (c, qc), (d, qd)] {
+                assert!((v - q).abs() <= TRAIL_QUANTUM / 2.0 + 1e-4, "{v} vs {q}");
+            }
+        }
+    }
+
+    #[test]
+    fn quantized_shots_are_smaller_than_float_segments() {
+        let segments = vec![(12.345, 6.789, 49.999, 23.456); 3];
+        let floats = rmp_serde::to_vec(&segments).unwrap();
+        let shot = rmp_serde::to_vec(&LaserFired::new(1, 2.0, &segments)).unwrap();
+        assert!(
+            shot.len() < floats.len(),
+            "{} vs {}",
+            shot.len(),
+            floats.len()
+        );
+    }
+
+    #[test]
+    fn trails_show_for_their_lifetime() {
+        let shot = LaserFired::new(1, 10.0, &[(0.0, 0.0, 1.0, 1.0)]);
+        assert!(!shot.is_visible(9.95), "Not before its snapshot");
+        assert!(shot.is_visible(10.0));
+        assert!(shot.is_visible(10.25));
+        assert!(!shot.is_visible(10.0 + TRAIL_LIFETIME));
+        assert!(!shot.is_stale(9.95));
+        assert!(shot.is_stale(10.0 + TRAIL_LIFETIME));
+        assert!(shot.is_stale(0.5), "Left over from an earlier round");
+    }
+}
@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+
+use breakpoint_core::game_trait::PlayerId;
+
+/// Deployable laser turret: a stationary emitter that sweeps a fixed
+/// rotation and fires on an interval, crediting its tags to the owner.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TurretConfig {
+    /// Seconds a deployed turret stays active.
+    pub duration_secs: f32,
+    /// Seconds between shots.
+    pub fire_interval_secs: f32,
+    /// Sweep speed in radians per second (positive is counter-clockwise).
+    pub rotation_speed: f32,
+    /// Deployed turrets per player; deploying another removes the oldest.
+    pub max_per_player: usize,
+}
+
+impl Default for TurretConfig {
+    fn default() -> Self {
+        Self {
+            duration_secs: 8.0,
+            fire_interval_secs: 0.6,
+            rotation_speed: 1.5,
+            max_per_player: 1,
+        }
+    }
+}
+
+/// A deployed turret, synced to clients for rendering.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LaserTurret {
+    pub owner: PlayerId,
+    pub x: f32,
+    pub z: f32,
+    /// Current aim in radians.
+    pub angle: f32,
+    /// Seconds until the turret shuts down.
+    pub remaining: f32,
+    /// Seconds until the next shot.
+    pub fire_cooldown: f32,
+}
+
+impl LaserTurret {
+    /// Deploy at the owner's position, starting the sweep at their aim.
+    pub fn new(owner: PlayerId, x: f32, z: f32, angle: f32, config: &TurretConfig) -> Self {
+        Self {
+            owner,
+            x,
+            z,
+            angle,
+            remaining: config.duration_secs,
+            fire_cooldown: 0.0,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.remaining <= 0.0
+    }
+
+    /// Advance by `dt` seconds. Returns the aim angle when the turret fires.
+    pub fn tick(&mut self, dt: f32, config: &TurretConfig) -> Option<f32> {
+        if self.is_expired() {
+            return None;
+        }
+        self.remaining -= dt;
+        self.angle = (self.angle + config.rotation_speed * dt).rem_euclid(std::f32::consts::TAU);
+        self.fire_cooldown -= dt;
+        if self.fire_cooldown > 0.0 {
+            return None;
+        }
+        self.fire_cooldown += config.fire_interval_secs;
+        Some(self.angle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fires_on_interval_while_sweeping() {
+        let config = TurretConfig::default();
+        let mut turret = LaserTurret::new(1, 5.0, 5.0, 0.0, &config);
+        let dt = 0.05;
+        let mut shots = Vec::new();
+        while !turret.is_expired() {
+            if let Some(angle) = turret.tick(dt, &config) {
+                shots.push(angle);
+            }
+        }
+        let expected = (config.duration_secs / config.fire_interval_secs).ceil() as usize;
+        assert!(shots.len().abs_diff(expected) <= 1, "{} shots", shots.len());
+        assert!(shots[1] > shots[0], "Each shot is further along the sweep");
+        assert_eq!(turret.tick(dt, &config), None, "Expired turrets stay quiet");
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use breakpoint_core::math::{Aabb, Vec2};
+
 use crate::enemies::{Enemy, EnemyProjectile, kill_enemy};
 use crate::physics::PlatformerPlayerState;
 
@@ -52,8 +54,8 @@ fn is_attack_active(player: &PlatformerPlayerState) -> bool {
     (ATTACK_ACTIVE_START..=ATTACK_ACTIVE_END).contains(&elapsed)
 }
 
-/// Get the whip hitbox AABB (left, bottom, right, top) for a player.
-fn whip_hitbox(player: &PlatformerPlayerState, has_whip_extend: bool) -> (f32, f32, f32, f32) {
+/// Get the whip hitbox for a player, in front of whichever way they face.
+fn whip_hitbox(player: &PlatformerPlayerState, has_whip_extend: bool) -> Aabb {
     let mult = if has_whip_extend {
         WHIP_EXTEND_MULT
     } else {
@@ -61,36 +63,29 @@ fn whip_hitbox(player: &PlatformerPlayerState, has_whip_extend: bool) -> (f32, f
     };
     let width = WHIP_WIDTH * mult;
     let offset = WHIP_OFFSET * mult;
-    let half_h = WHIP_HEIGHT / 2.0;
-
-    if player.facing_right {
-        let left = player.x + offset - width / 2.0;
-        let right = player.x + offset + width / 2.0;
-        (left, player.y - half_h, right, player.y + half_h)
+    let center_x = if player.facing_right {
+        player.x + offset
     } else {
-        let left = player.x - offset - width / 2.0;
-        let right = player.x - offset + width / 2.0;
-        (left, player.y - half_h, right, player.y + half_h)
-    }
+        player.x - offset
+    };
+    Aabb::from_center(
+        Vec2::new(center_x, player.y),
+        width / 2.0,
+        WHIP_HEIGHT / 2.0,
+    )
 }
 
 /// Enemy AABB: approximate as 0.8 x 0.8 centered on enemy position.
 const ENEMY_HALF_SIZE: f32 = 0.4;
 
-fn enemy_aabb(enemy: &Enemy) -> (f32, f32, f32, f32) {
-    (
-        enemy.x - ENEMY_HALF_SIZE,
-        enemy.y - ENEMY_HALF_SIZE,
-        enemy.x + ENEMY_HALF_SIZE,
-        enemy.y + ENEMY_HALF_SIZE,
+fn enemy_aabb(enemy: &Enemy) -> Aabb {
+    Aabb::from_center(
+        Vec2::new(enemy.x, enemy.y),
+        ENEMY_HALF_SIZE,
+        ENEMY_HALF_SIZE,
     )
 }
 
-/// Check if two AABBs overlap.
-fn aabb_overlap(a: (f32, f32, f32, f32), b: (f32, f32, f32, f32)) -> bool {
-    a.0 < b.2 && a.2 > b.0 && a.1 < b.3 && a.3 > b.1
-}
-
 /// Check a player's whip attack against all enemies. Returns combat events for kills.
 pub fn check_player_attack(
     player: &PlatformerPlayerState,
@@ -110,8 +105,7 @@ pub fn check_player_attack(
             continue;
         }
 
-        let e_aabb = enemy_aabb(enemy);
-        if aabb_overlap(whip, e_aabb) {
+        if whip.overlaps(&enemy_aabb(enemy)) {
             enemy.hp = enemy.hp.saturating_sub(1);
             if enemy.hp == 0 {
                 kill_enemy(enemy);
@@ -127,25 +121,15 @@ pub fn check_player_attack(
 const PLAYER_HALF_W: f32 = 0.4;
 const PLAYER_HALF_H: f32 = 0.6;
 
-fn player_aabb(player: &PlatformerPlayerState) -> (f32, f32, f32, f32) {
-    (
-        player.x - PLAYER_HALF_W,
-        player.y - PLAYER_HALF_H,
-        player.x + PLAYER_HALF_W,
-        player.y + PLAYER_HALF_H,
-    )
+fn player_aabb(player: &PlatformerPlayerState) -> Aabb {
+    Aabb::from_center(Vec2::new(player.x, player.y), PLAYER_HALF_W, PLAYER_HALF_H)
 }
 
 /// Projectile AABB: small 0.3 x 0.3 hitbox.
 const PROJ_HALF_SIZE: f32 = 0.15;
 
-fn projectile_aabb(proj: &EnemyProjectile) -> (f32, f32, f32, f32) {
-    (
-        proj.x - PROJ_HALF_SIZE,
-        proj.y - PROJ_HALF_SIZE,
-        proj.x + PROJ_HALF_SIZE,
-        proj.y + PROJ_HALF_SIZE,
-    )
+fn projectile_aabb(proj: &EnemyProjectile) -> Aabb {
+    Aabb::from_center(Vec2::new(proj.x, proj.y), PROJ_HALF_SIZE, PROJ_HALF_SIZE)
 }
 
 /// Check if enemies or projectiles hit a player. Applies damage if not invincible.
@@ -171,8 +155,7 @@ pub fn check_enemy_damage(
         if !enemy.alive {
             continue;
         }
-        let e_aabb = enemy_aabb(enemy);
-        if aabb_overlap(p_aabb, e_aabb) {
+        if p_aabb.overlaps(&enemy_aabb(enemy)) {
             events.extend(apply_damage(player, player_id));
             return events; // Only one hit per tick
         }
@@ -180,8 +163,7 @@ pub fn check_enemy_damage(
 
     // Check projectile collisions
     for proj in projectiles {
-        let proj_box = projectile_aabb(proj);
-        if aabb_overlap(p_aabb, proj_box) {
+        if p_aabb.overlaps(&projectile_aabb(proj)) {
             events.extend(apply_damage(player, player_id));
             return events; // Only one hit per tick
         }
@@ -271,29 +253,29 @@ mod tests {
     fn whip_hitbox_facing_right() {
         let mut player = make_test_player();
         player.facing_right = true;
-        let (left, bottom, right, top) = whip_hitbox(&player, false);
+        let whip = whip_hitbox(&player, false);
         // Hitbox should be to the right of the player
-        assert!(left > player.x, "Whip should be to the right");
-        assert!(right > left, "Right should be > left");
-        assert!(top > bottom, "Top should be > bottom");
+        assert!(whip.min.x > player.x, "Whip should be to the right");
+        assert!(whip.max.x > whip.min.x, "Right should be > left");
+        assert!(whip.max.y > whip.min.y, "Top should be > bottom");
     }
 
     #[test]
     fn whip_hitbox_facing_left() {
         let mut player = make_test_player();
         player.facing_right = false;
-        let (_left, _bottom, right, _top) = whip_hitbox(&player, false);
+        let whip = whip_hitbox(&player, false);
         // Hitbox should be to the left of the player
-        assert!(right < player.x, "Whip should be to the left");
+        assert!(whip.max.x < player.x, "Whip should be to the left");
     }
 
     #[test]
     fn whip_extend_increases_range() {
         let player = make_test_player();
-        let (l1, _, r1, _) = whip_hitbox(&player, false);
-        let (l2, _, r2, _) = whip_hitbox(&player, true);
-        let normal_width = r1 - l1;
-        let extended_width = r2 - l2;
+        let normal = whip_hitbox(&player, false);
+        let extended = whip_hitbox(&player, true);
+        let normal_width = normal.max.x - normal.min.x;
+        let extended_width = extended.max.x - extended.min.x;
         assert!(
             extended_width > normal_width,
             "Extended whip should be wider: {} vs {}",
@@ -440,16 +422,6 @@ mod tests {
         assert_eq!(player.hp, 3, "HP should not change");
     }
 
-    #[test]
-    fn aabb_overlap_works_correctly() {
-        // Overlapping
-        assert!(aabb_overlap((0.0, 0.0, 2.0, 2.0), (1.0, 1.0, 3.0, 3.0)));
-        // Not overlapping
-        assert!(!aabb_overlap((0.0, 0.0, 1.0, 1.0), (2.0, 2.0, 3.0, 3.0)));
-        // Touching edges (not overlapping)
-        assert!(!aabb_overlap((0.0, 0.0, 1.0, 1.0), (1.0, 0.0, 2.0, 1.0)));
-    }
-
     // Suppress dead code warning for AnimState import used in test setup
     #[allow(unused)]
     fn _use_anim_state() -> AnimState {
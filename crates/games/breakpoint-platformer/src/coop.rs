@@ -0,0 +1,308 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use breakpoint_core::game_trait::PlayerId;
+
+use crate::combat::INVINCIBILITY_DURATION;
+use crate::physics::{AnimState, PlatformerPlayerState};
+
+/// Which finishers win a co-op round.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoopFinishRule {
+    /// The first player across the line wins it for the team.
+    Any,
+    /// Every player still in the round must reach the finish.
+    #[default]
+    All,
+}
+
+/// Co-op mode tuning: a shared life pool and teammate revives.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CoopConfig {
+    /// Lives shared by the whole team.
+    pub shared_lives: u8,
+    /// Seconds a teammate must stay nearby to revive a downed player.
+    pub revive_secs: f32,
+    /// How close a teammate must stand to revive, in world units.
+    pub revive_radius: f32,
+    /// Seconds a downed player waits for a revive before spending a
+    /// shared life to respawn at their checkpoint.
+    pub bleedout_secs: f32,
+    pub finish_rule: CoopFinishRule,
+}
+
+impl Default for CoopConfig {
+    fn default() -> Self {
+        Self {
+            shared_lives: 5,
+            revive_secs: 2.0,
+            revive_radius: 1.5,
+            bleedout_secs: 8.0,
+            finish_rule: CoopFinishRule::All,
+        }
+    }
+}
+
+/// Timers for a player waiting to be revived.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct DownedState {
+    /// Seconds of revive progress; resets when no teammate is nearby.
+    pub revive_progress: f32,
+    /// Seconds left before a shared life is spent.
+    pub bleedout: f32,
+}
+
+/// Team state for co-op mode, synced to clients for the HUD.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CoopState {
+    /// Shared lives left.
+    pub lives: u8,
+    pub finish_rule: CoopFinishRule,
+    pub downed: HashMap<PlayerId, DownedState>,
+    /// Revives performed, per reviver.
+    pub revives: HashMap<PlayerId, u32>,
+    /// Set once the team meets the finish rule.
+    pub won: bool,
+}
+
+/// Something that happened to a downed player this tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoopEvent {
+    Downed(PlayerId),
+    Revived {
+        player_id: PlayerId,
+        by: PlayerId,
+    },
+    /// Bled out and spent a shared life to respawn.
+    Respawned(PlayerId),
+    /// Bled out with no shared lives left.
+    Eliminated(PlayerId),
+}
+
+fn can_revive(player: &PlatformerPlayerState) -> bool {
+    !player.downed && !player.eliminated && !player.finished && player.death_respawn_timer <= 0.0
+}
+
+impl CoopState {
+    pub fn new(config: &CoopConfig, finish_rule: CoopFinishRule) -> Self {
+        Self {
+            lives: config.shared_lives,
+            finish_rule,
+            ..Self::default()
+        }
+    }
+
+    /// Down newly dead players, advance revives and bleedouts.
+    pub fn tick(
+        &mut self,
+        dt: f32,
+        player_ids: &[PlayerId],
+        players: &mut HashMap<PlayerId, PlatformerPlayerState>,
+        config: &CoopConfig,
+    ) -> Vec<CoopEvent> {
+        let mut events = Vec::new();
+
+        // Deaths hold the player down instead of respawning them
+        for &pid in player_ids {
+            if let Some(player) = players.get_mut(&pid)
+                && player.death_respawn_timer > 0.0
+                && !player.downed
+                && !player.eliminated
+            {
+                player.downed = true;
+                self.downed.insert(
+                    pid,
+                    DownedState {
+                        revive_progress: 0.0,
+                        bleedout: config.bleedout_secs,
+                    },
+                );
+                events.push(CoopEvent::Downed(pid));
+            }
+        }
+
+        let radius_sq = config.revive_radius * config.revive_radius;
+        let downed_ids: Vec<PlayerId> = player_ids
+            .iter()
+            .copied()
+            .filter(|id| self.downed.contains_key(id))
+            .collect();
+        for pid in downed_ids {
+            let Some((x, y)) = players.get(&pid).map(|p| (p.x, p.y)) else {
+                self.downed.remove(&pid);
+                continue;
+            };
+            let reviver = player_ids.iter().copied().find(|&id| {
+                id != pid
+                    && players.get(&id).is_some_and(|p| {
+                        can_revive(p) && (p.x - x).powi(2) + (p.y - y).powi(2) <= radius_sq
+                    })
+            });
+            let Some(down) = self.downed.get_mut(&pid) else {
+                continue;
+            };
+
+            let Some(player) = players.get_mut(&pid) else {
+                continue;
+            };
+            if let Some(by) = reviver {
+                down.revive_progress += dt;
+                if down.revive_progress >= config.revive_secs {
+                    self.downed.remove(&pid);
+                    revive_in_place(player);
+                    *self.revives.entry(by).or_insert(0) += 1;
+                    events.push(CoopEvent::Revived { player_id: pid, by });
+                }
+                continue;
+            }
+
+            down.revive_progress = 0.0;
+            down.bleedout -= dt;
+            if down.bleedout > 0.0 {
+                continue;
+            }
+            self.downed.remove(&pid);
+            player.downed = false;
+            if self.lives > 0 {
+                self.lives -= 1;
+                player.respawn_at_checkpoint();
+                events.push(CoopEvent::Respawned(pid));
+            } else {
+                player.eliminated = true;
+                player.death_respawn_timer = 0.0;
+                events.push(CoopEvent::Eliminated(pid));
+            }
+        }
+
+        events
+    }
+
+    /// Whether the team has met its finish rule.
+    pub fn finish_met(
+        &self,
+        player_ids: &[PlayerId],
+        players: &HashMap<PlayerId, PlatformerPlayerState>,
+    ) -> bool {
+        let mut finished = player_ids
+            .iter()
+            .filter_map(|id| players.get(id))
+            .filter(|p| p.finished);
+        match self.finish_rule {
+            CoopFinishRule::Any => finished.next().is_some(),
+            CoopFinishRule::All => {
+                finished.next().is_some()
+                    && player_ids
+                        .iter()
+                        .filter_map(|id| players.get(id))
+                        .all(|p| p.finished || p.eliminated)
+            },
+        }
+    }
+}
+
+/// Bring a downed player back where they fell, briefly invincible.
+fn revive_in_place(player: &mut PlatformerPlayerState) {
+    player.downed = false;
+    player.death_respawn_timer = 0.0;
+    player.hp = player.max_hp;
+    player.vx = 0.0;
+    player.vy = 0.0;
+    player.invincibility_timer = INVINCIBILITY_DURATION;
+    player.anim_state = AnimState::Idle;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combat::DEATH_RESPAWN_TIMER;
+
+    fn team(n: u64) -> (Vec<PlayerId>, HashMap<PlayerId, PlatformerPlayerState>) {
+        let ids: Vec<PlayerId> = (1..=n).collect();
+        let players = ids
+            .iter()
+            .map(|&id| (id, PlatformerPlayerState::new(id as f32 * 10.0, 5.0)))
+            .collect();
+        (ids, players)
+    }
+
+    fn kill(player: &mut PlatformerPlayerState) {
+        player.hp = 0;
+        player.deaths += 1;
+        player.death_respawn_timer = DEATH_RESPAWN_TIMER;
+    }
+
+    #[test]
+    fn teammate_nearby_revives() {
+        let config = CoopConfig::default();
+        let (ids, mut players) = team(2);
+        let mut coop = CoopState::new(&config, CoopFinishRule::All);
+
+        kill(players.get_mut(&1).unwrap());
+        let events = coop.tick(0.1, &ids, &mut players, &config);
+        assert_eq!(events, vec![CoopEvent::Downed(1)]);
+        assert!(players[&1].downed);
+
+        // Teammate walks over and stays for the revive time
+        let (x, y) = (players[&1].x, players[&1].y);
+        let reviver = players.get_mut(&2).unwrap();
+        (reviver.x, reviver.y) = (x + 1.0, y);
+        let mut revived = false;
+        for _ in 0..25 {
+            revived |= coop
+                .tick(0.1, &ids, &mut players, &config)
+                .contains(&CoopEvent::Revived {
+                    player_id: 1,
+                    by: 2,
+                });
+        }
+        assert!(revived);
+        let player = &players[&1];
+        assert!(!player.downed && player.death_respawn_timer <= 0.0);
+        assert_eq!(player.hp, player.max_hp);
+        assert_eq!(coop.lives, config.shared_lives, "Revives are free");
+        assert_eq!(coop.revives[&2], 1);
+    }
+
+    #[test]
+    fn bleedout_spends_shared_lives_then_eliminates() {
+        let config = CoopConfig {
+            shared_lives: 1,
+            ..CoopConfig::default()
+        };
+        let (ids, mut players) = team(2);
+        let mut coop = CoopState::new(&config, CoopFinishRule::All);
+
+        kill(players.get_mut(&1).unwrap());
+        coop.tick(0.1, &ids, &mut players, &config);
+        let events = coop.tick(config.bleedout_secs, &ids, &mut players, &config);
+        assert_eq!(events, vec![CoopEvent::Respawned(1)]);
+        assert_eq!(coop.lives, 0);
+        assert!(!players[&1].downed && !players[&1].eliminated);
+
+        kill(players.get_mut(&1).unwrap());
+        coop.tick(0.1, &ids, &mut players, &config);
+        let events = coop.tick(config.bleedout_secs, &ids, &mut players, &config);
+        assert_eq!(events, vec![CoopEvent::Eliminated(1)]);
+        assert!(players[&1].eliminated);
+    }
+
+    #[test]
+    fn finish_rules() {
+        let (ids, mut players) = team(3);
+        let any = CoopState::new(&CoopConfig::default(), CoopFinishRule::Any);
+        let all = CoopState::new(&CoopConfig::default(), CoopFinishRule::All);
+        assert!(!any.finish_met(&ids, &players));
+
+        players.get_mut(&1).unwrap().finished = true;
+        assert!(any.finish_met(&ids, &players));
+        assert!(!all.finish_met(&ids, &players));
+
+        // Eliminated teammates don't block an "all" finish
+        players.get_mut(&2).unwrap().finished = true;
+        players.get_mut(&3).unwrap().eliminated = true;
+        assert!(all.finish_met(&ids, &players));
+    }
+}
@@ -414,6 +414,13 @@ impl Course {
 // Labyrinth generation
 // ================================================================
 
+/// Seeds for the courses offered in a pre-round vote. The same base seed
+/// and round always yield the same candidates.
+pub fn candidate_seeds(base: u64, round: u8, count: usize) -> Vec<u64> {
+    let mut rng = StdRng::seed_from_u64(base ^ u64::from(round).rotate_left(32));
+    (0..count).map(|_| rng.random()).collect()
+}
+
 /// Generate a deterministic castle labyrinth course from a seed.
 pub fn generate_course(seed: u64) -> Course {
     let width = COURSE_WIDTH;
@@ -1593,6 +1600,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn candidate_seeds_are_deterministic_per_round() {
+        let seeds = candidate_seeds(7, 1, 3);
+        assert_eq!(seeds, candidate_seeds(7, 1, 3));
+        assert_eq!(seeds.len(), 3);
+        assert_ne!(seeds[0], seeds[1]);
+        assert_ne!(
+            seeds,
+            candidate_seeds(7, 2, 3),
+            "Each round gets new candidates"
+        );
+    }
+
     #[test]
     fn different_seeds_different_courses() {
         let c1 = generate_course(42);
@@ -0,0 +1,152 @@
+use serde::{Deserialize, Serialize};
+
+/// Speed curve for the survival-mode rising hazard.
+///
+/// Speed grows linearly with elapsed time and is scaled up as players are
+/// eliminated, so long rounds and thinned-out fields both close faster.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HazardConfig {
+    /// Rise speed at round start, in units per second.
+    pub base_speed: f32,
+    /// Speed added per minute of elapsed round time.
+    pub speed_per_minute: f32,
+    /// Extra multiplier when all but one player are eliminated; scales
+    /// linearly with the eliminated fraction.
+    pub elimination_boost: f32,
+    /// Hard cap on rise speed.
+    pub max_speed: f32,
+    /// Seconds the hazard holds still after each elimination.
+    pub elimination_pause_secs: f32,
+    /// How far below the spawn point the hazard starts.
+    pub start_below_spawn: f32,
+}
+
+impl Default for HazardConfig {
+    fn default() -> Self {
+        Self {
+            base_speed: 0.5,
+            speed_per_minute: 0.3,
+            elimination_boost: 1.0,
+            max_speed: 2.5,
+            elimination_pause_secs: 1.5,
+            start_below_spawn: 8.0,
+        }
+    }
+}
+
+impl HazardConfig {
+    /// Rise speed for the given elapsed time and remaining field.
+    pub fn speed(&self, elapsed_secs: f32, alive: usize, total: usize) -> f32 {
+        let eliminated_frac = if total > 1 {
+            (total - alive.min(total)) as f32 / (total - 1) as f32
+        } else {
+            0.0
+        };
+        let time_speed = self.base_speed + self.speed_per_minute * elapsed_secs / 60.0;
+        let boosted = time_speed * (1.0 + self.elimination_boost * eliminated_frac.min(1.0));
+        boosted.clamp(0.0, self.max_speed)
+    }
+
+    /// This curve with every speed multiplied by `factor`.
+    pub fn scaled(&self, factor: f32) -> Self {
+        Self {
+            base_speed: self.base_speed * factor,
+            speed_per_minute: self.speed_per_minute * factor,
+            max_speed: self.max_speed * factor,
+            ..self.clone()
+        }
+    }
+}
+
+/// Rising hazard line, synced to clients for rendering and HUD.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct HazardState {
+    /// World-space height of the hazard surface.
+    pub y: f32,
+    /// Current rise speed in units per second (0 while paused).
+    pub speed: f32,
+    /// Seconds left in the post-elimination pause.
+    pub pause_remaining: f32,
+}
+
+impl HazardState {
+    pub fn new(y: f32) -> Self {
+        Self {
+            y,
+            speed: 0.0,
+            pause_remaining: 0.0,
+        }
+    }
+
+    /// Advance the hazard by `dt` seconds.
+    pub fn tick(
+        &mut self,
+        dt: f32,
+        elapsed_secs: f32,
+        alive: usize,
+        total: usize,
+        config: &HazardConfig,
+    ) {
+        if self.pause_remaining > 0.0 {
+            self.pause_remaining = (self.pause_remaining - dt).max(0.0);
+            self.speed = 0.0;
+            return;
+        }
+        self.speed = config.speed(elapsed_secs, alive, total);
+        self.y += self.speed * dt;
+    }
+
+    /// Hold the hazard after an elimination.
+    pub fn pause(&mut self, config: &HazardConfig) {
+        self.pause_remaining = config.elimination_pause_secs;
+        self.speed = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn speed_rises_with_time() {
+        let config = HazardConfig::default();
+        let start = config.speed(0.0, 4, 4);
+        assert!((start - config.base_speed).abs() < 1e-6);
+        assert!(config.speed(120.0, 4, 4) > start);
+    }
+
+    #[test]
+    fn speed_rises_as_players_are_eliminated() {
+        let config = HazardConfig::default();
+        let full = config.speed(30.0, 4, 4);
+        let half = config.speed(30.0, 2, 4);
+        let last = config.speed(30.0, 1, 4);
+        assert!(full < half && half < last);
+        assert!((last - full * (1.0 + config.elimination_boost)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn speed_capped() {
+        let config = HazardConfig::default();
+        assert_eq!(config.speed(3600.0, 1, 6), config.max_speed);
+    }
+
+    #[test]
+    fn pause_holds_hazard() {
+        let config = HazardConfig::default();
+        let mut hazard = HazardState::new(0.0);
+        hazard.tick(1.0, 0.0, 2, 2, &config);
+        let y = hazard.y;
+        assert!(y > 0.0);
+
+        hazard.pause(&config);
+        hazard.tick(config.elimination_pause_secs / 2.0, 1.0, 1, 2, &config);
+        assert_eq!(hazard.y, y);
+        assert_eq!(hazard.speed, 0.0);
+
+        hazard.tick(config.elimination_pause_secs, 2.0, 1, 2, &config);
+        hazard.tick(1.0, 3.0, 1, 2, &config);
+        assert!(hazard.y > y, "Hazard resumes after the pause");
+    }
+}
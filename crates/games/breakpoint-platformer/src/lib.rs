@@ -1,31 +1,63 @@
 pub mod combat;
+pub mod coop;
 pub mod course_gen;
 pub mod enemies;
+pub mod hazard;
+pub mod minimap;
 pub mod physics;
 pub mod powerups;
 pub mod rubber_band;
 pub mod scoring;
+pub mod settings;
+pub mod trap;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::time::Duration;
 
 use rand::SeedableRng;
 use rand::rngs::StdRng;
 use serde::{Deserialize, Serialize};
 
+use breakpoint_core::debug_command::{self, DebugCommand, DebugResult};
+use breakpoint_core::game_settings::FromGameConfig;
 use breakpoint_core::game_trait::{
-    BreakpointGame, GameConfig, GameEvent, GameMetadata, PlayerId, PlayerInputs, PlayerScore,
+    BreakpointGame, GameConfig, GameConfigDelta, GameEvent, GameMetadata, LIVE_EXTEND_ROUND,
+    LIVE_HAZARD_SPEED, LIVE_POWERUPS, PlayerId, PlayerInputs, PlayerScore,
 };
+use breakpoint_core::i18n::TextKey;
+use breakpoint_core::late_join::LateJoinPolicy;
+use breakpoint_core::pause::{PauseReason, PauseState};
 use breakpoint_core::player::Player;
+use breakpoint_core::powerup::{self, PowerUpFeed};
+use breakpoint_core::tutorial::TutorialObjective;
+use breakpoint_core::victory::VictorySummary;
 
 use combat::{CombatEvent, check_enemy_damage, check_player_attack};
+use coop::CoopState;
 use course_gen::{Course, Tile, generate_course};
 use enemies::{Enemy, EnemyProjectile};
+use hazard::HazardState;
+use minimap::{Minimap, MinimapMarker};
 use physics::{
-    PlatformerConfig, PlatformerInput, PlatformerPlayerState, SUBSTEPS, tick_player, try_break_wall,
+    PlatformerConfig, PlatformerInput, PlatformerPlayerState, SUBSTEPS, tick_player_with_gravity,
+    try_break_wall,
 };
 use powerups::{ActivePowerUp, PowerUpKind, SpawnedPowerUp, select_powerup_for_position};
 use rubber_band::{RubberBandFactor, compute_rubber_band};
+use settings::{PlatformerMode, PlatformerSettings};
+use trap::Trap;
+
+/// What the platformer tutorial asks a new player to do.
+pub const TUTORIAL_OBJECTIVES: &[TutorialObjective] = &[
+    TutorialObjective {
+        id: "powerup",
+        text: TextKey::TutorialPlatformerPowerUp,
+    },
+    TutorialObjective {
+        id: "finish",
+        text: TextKey::TutorialPlatformerFinish,
+    },
+];
 
 /// Serializable game state for network broadcast.
 ///
@@ -48,6 +80,27 @@ pub struct PlatformerState {
     /// Clients compare this to detect course changes.
     #[serde(default)]
     pub course_version: u32,
+    /// Downsampled course grid plus per-tick player markers for the minimap.
+    #[serde(default)]
+    pub minimap: Minimap,
+    /// Rising hazard in survival mode; `None` in race mode.
+    #[serde(default)]
+    pub hazard: Option<HazardState>,
+    /// Shared lives and revives in co-op mode; `None` otherwise.
+    #[serde(default)]
+    pub coop: Option<CoopState>,
+    /// Active pause reasons, so clients can show why play is held.
+    #[serde(default)]
+    pub pause: PauseState,
+    /// Dropped banana traps.
+    #[serde(default)]
+    pub traps: Vec<Trap>,
+    /// Racers each player has tripped with a trap this round.
+    #[serde(default)]
+    pub trips: HashMap<PlayerId, u32>,
+    /// Recent power-up pickups and expiries, for client effects.
+    #[serde(default)]
+    pub powerup_feed: PowerUpFeed,
 }
 
 /// Compact wire-format state that excludes the course grid.
@@ -64,6 +117,20 @@ struct PlatformerNetState {
     projectiles: Vec<EnemyProjectile>,
     rubber_band: HashMap<PlayerId, RubberBandFactor>,
     course_version: u32,
+    /// Only the dynamic minimap bits; the grid is rebuilt from `CourseUpdate`.
+    minimap_markers: Vec<MinimapMarker>,
+    minimap_hazard_row: Option<u8>,
+    hazard: Option<HazardState>,
+    #[serde(default)]
+    coop: Option<CoopState>,
+    #[serde(default)]
+    pause: PauseState,
+    #[serde(default)]
+    traps: Vec<Trap>,
+    #[serde(default)]
+    trips: HashMap<PlayerId, u32>,
+    #[serde(default)]
+    powerup_feed: PowerUpFeed,
 }
 
 /// The Platform Racer game (Castlevania Rush).
@@ -72,7 +139,6 @@ pub struct PlatformRacer {
     state: PlatformerState,
     player_ids: Vec<PlayerId>,
     pending_inputs: HashMap<PlayerId, PlatformerInput>,
-    paused: bool,
     round_duration: f32,
     /// O(1) lookup companion for `state.finish_order`.
     finished_set: HashSet<PlayerId>,
@@ -86,6 +152,28 @@ pub struct PlatformRacer {
     tick_counter: u32,
     /// RNG for power-up selection (seeded for determinism).
     rng: StdRng,
+    /// Survival players in the order the hazard caught them.
+    elimination_order: Vec<PlayerId>,
+    /// Racers who left this round, scored as they stood when they left.
+    departed: BTreeMap<PlayerId, DepartedRacer>,
+    /// Gravity this round, after the lobby's `gravity_scale`.
+    gravity: f32,
+    /// Whether finishes, trips and deaths score (off during a warmup).
+    scoring: bool,
+    /// Multiplier on the survival hazard's speed, changed mid-round.
+    hazard_speed: f32,
+    /// False while the host has turned power-up pickups off mid-round.
+    powerups_enabled: bool,
+    /// What happens to players who join mid-round.
+    late_join: LateJoinPolicy,
+}
+
+/// A racer's standing when they left mid-round.
+#[derive(Debug, Clone, Copy)]
+struct DepartedRacer {
+    deaths: u8,
+    /// Survival place; leaving counts as being caught by the hazard.
+    place: usize,
 }
 
 impl PlatformRacer {
@@ -97,6 +185,7 @@ impl PlatformRacer {
     pub fn with_config(game_config: PlatformerConfig) -> Self {
         let round_duration = game_config.round_duration_secs;
         let initial_course = generate_course(42);
+        let minimap = Minimap::from_course(&initial_course);
         Self {
             state: PlatformerState {
                 players: HashMap::new(),
@@ -110,11 +199,17 @@ impl PlatformRacer {
                 projectiles: Vec::new(),
                 rubber_band: HashMap::new(),
                 course_version: 0,
+                minimap,
+                hazard: None,
+                coop: None,
+                pause: PauseState::default(),
+                traps: Vec::new(),
+                trips: HashMap::new(),
+                powerup_feed: PowerUpFeed::default(),
             },
             course: initial_course,
             player_ids: Vec::new(),
             pending_inputs: HashMap::new(),
-            paused: false,
             round_duration,
             finished_set: HashSet::new(),
             game_config,
@@ -122,6 +217,13 @@ impl PlatformRacer {
             rng: StdRng::seed_from_u64(42),
             course_dirty: true,
             course_version: 0,
+            elimination_order: Vec::new(),
+            departed: BTreeMap::new(),
+            gravity: physics::GRAVITY,
+            scoring: true,
+            hazard_speed: 1.0,
+            powerups_enabled: true,
+            late_join: LateJoinPolicy::SpawnImmediately,
         }
     }
 
@@ -138,6 +240,17 @@ impl PlatformRacer {
         &self.game_config
     }
 
+    /// Survival place of a caught player. Survivors share first place;
+    /// later eliminations rank higher. `None` while still standing.
+    fn survival_place(&self, pid: PlayerId) -> Option<usize> {
+        let standing = self.player_ids.len() - self.elimination_order.len();
+        self.elimination_order
+            .iter()
+            .rev()
+            .position(|&id| id == pid)
+            .map(|i| standing + i)
+    }
+
     // ---- Sub-update functions ----
 
     /// Process player movement and physics.
@@ -147,6 +260,16 @@ impl PlatformRacer {
             let pid = self.player_ids[i];
             let input = self.pending_inputs.remove(&pid).unwrap_or_default();
 
+            if input.use_powerup
+                && self
+                    .state
+                    .players
+                    .get(&pid)
+                    .is_some_and(|p| p.stumble_timer <= 0.0 && p.death_respawn_timer <= 0.0)
+            {
+                self.drop_trap(pid);
+            }
+
             if let Some(player) = self.state.players.get_mut(&pid) {
                 // Apply speed boost from SpeedBoots power-up
                 let speed_mult = if self
@@ -164,7 +287,13 @@ impl PlatformRacer {
                 boosted_input.move_dir *= speed_mult;
 
                 for _ in 0..SUBSTEPS {
-                    tick_player(player, &boosted_input, &self.course, sub_dt);
+                    tick_player_with_gravity(
+                        player,
+                        &boosted_input,
+                        &self.course,
+                        sub_dt,
+                        self.gravity,
+                    );
                 }
             }
         }
@@ -206,6 +335,7 @@ impl PlatformRacer {
                 // Only sync course when a wall was actually destroyed
                 if broke {
                     self.state.course = self.course.clone();
+                    self.rebuild_minimap_grid();
                     self.course_version += 1;
                     self.state.course_version = self.course_version;
                     self.course_dirty = true;
@@ -254,8 +384,11 @@ impl PlatformRacer {
         events
     }
 
-    /// Process power-up collection and expiration.
-    fn process_powerups(&mut self) {
+    /// Process power-up collection, returning a pickup event for each.
+    fn process_powerups(&mut self) -> Vec<GameEvent> {
+        if !self.powerups_enabled {
+            return Vec::new();
+        }
         // Collect which powerups were picked up by which players
         let mut collected: Vec<(PlayerId, PowerUpKind)> = Vec::new();
 
@@ -280,9 +413,13 @@ impl PlatformRacer {
         }
 
         // Apply collected power-ups (now that the borrow on self.state.powerups is released)
-        for (pid, kind) in collected {
-            self.apply_powerup(pid, kind);
-        }
+        collected
+            .into_iter()
+            .map(|(pid, kind)| {
+                self.apply_powerup(pid, kind);
+                powerup::collected(pid, kind)
+            })
+            .collect()
     }
 
     /// Apply a collected power-up to a player.
@@ -355,7 +492,10 @@ impl PlatformRacer {
                     .or_default()
                     .push(active_pu);
             },
-            PowerUpKind::SpeedBoots | PowerUpKind::Invincibility | PowerUpKind::WhipExtend => {
+            PowerUpKind::SpeedBoots
+            | PowerUpKind::Invincibility
+            | PowerUpKind::WhipExtend
+            | PowerUpKind::Banana => {
                 let active_pu = ActivePowerUp::new(kind);
                 self.state
                     .active_powerups
@@ -366,14 +506,75 @@ impl PlatformRacer {
         }
     }
 
-    /// Tick active power-ups (decrement timers, remove expired).
-    fn tick_active_powerups(&mut self, dt: f32) {
-        for pus in self.state.active_powerups.values_mut() {
-            for pu in pus.iter_mut() {
-                pu.tick(dt);
+    /// Drop a held banana behind the player as a trap.
+    fn drop_trap(&mut self, pid: PlayerId) {
+        let Some(pus) = self.state.active_powerups.get_mut(&pid) else {
+            return;
+        };
+        let Some(idx) = pus.iter().position(|p| p.kind == PowerUpKind::Banana) else {
+            return;
+        };
+        pus.remove(idx);
+        let Some(player) = self.state.players.get(&pid) else {
+            return;
+        };
+        let config = &self.game_config.trap;
+        let trap = Trap::behind(pid, player, config);
+        let owned = self.state.traps.iter().filter(|t| t.owner == pid).count();
+        if owned >= config.max_per_player
+            && let Some(oldest) = self.state.traps.iter().position(|t| t.owner == pid)
+        {
+            self.state.traps.remove(oldest);
+        }
+        self.state.traps.push(trap);
+    }
+
+    /// Age traps and trip whoever touches one first. Each trap springs
+    /// once; the trip is credited to its owner.
+    fn process_traps(&mut self, dt: f32) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+        let config = &self.game_config.trap;
+        let mut i = 0;
+        while i < self.state.traps.len() {
+            let trap = &mut self.state.traps[i];
+            trap.tick(dt);
+            if trap.is_expired() {
+                self.state.traps.remove(i);
+                continue;
+            }
+            let victim = self.player_ids.iter().copied().find(|pid| {
+                let invincible =
+                    self.state.active_powerups.get(pid).is_some_and(|pus| {
+                        pus.iter().any(|p| p.kind == PowerUpKind::Invincibility)
+                    });
+                self.state
+                    .players
+                    .get(pid)
+                    .is_some_and(|p| trap.trips(*pid, p, invincible, config))
+            });
+            let Some(victim) = victim else {
+                i += 1;
+                continue;
+            };
+            let trap = self.state.traps.remove(i);
+            if let Some(player) = self.state.players.get_mut(&victim) {
+                player.stumble_timer = config.stumble_secs;
+                player.vx = 0.0;
+            }
+            *self.state.trips.entry(trap.owner).or_insert(0) += 1;
+            if self.state.coop.is_none() {
+                events.push(GameEvent::ScoreUpdate {
+                    player_id: trap.owner,
+                    score: 1,
+                });
             }
-            pus.retain(|p| !p.is_expired());
         }
+        events
+    }
+
+    /// Tick active power-ups (decrement timers, remove expired).
+    fn tick_active_powerups(&mut self, dt: f32) -> Vec<GameEvent> {
+        powerup::tick_active(&mut self.state.active_powerups, dt)
     }
 
     /// Recalculate rubber-banding factors (every 30 ticks).
@@ -384,6 +585,60 @@ impl PlatformRacer {
         }
     }
 
+    /// Rebuild the static minimap grid from the course, keeping dynamic bits.
+    fn rebuild_minimap_grid(&mut self) {
+        let fresh = Minimap::from_course(&self.course);
+        self.state.minimap.cols = fresh.cols;
+        self.state.minimap.rows = fresh.rows;
+        self.state.minimap.cells = fresh.cells;
+    }
+
+    /// Raise the survival hazard and eliminate anyone it catches. Each
+    /// elimination briefly pauses the hazard.
+    fn process_hazard(&mut self, dt: f32) {
+        let config = &self.game_config.hazard.scaled(self.hazard_speed);
+        let total = self.player_ids.len();
+        let alive = self
+            .player_ids
+            .iter()
+            .filter(|id| self.state.players.get(id).is_some_and(|p| !p.eliminated))
+            .count();
+        let Some(hazard) = self.state.hazard.as_mut() else {
+            return;
+        };
+        hazard.tick(dt, self.state.round_timer, alive, total, config);
+
+        let mut caught = false;
+        for &pid in &self.player_ids {
+            if let Some(player) = self.state.players.get_mut(&pid)
+                && !player.eliminated
+                && !player.finished
+                && player.y < hazard.y
+            {
+                player.eliminated = true;
+                self.elimination_order.push(pid);
+                caught = true;
+            }
+        }
+        if caught {
+            hazard.pause(config);
+        }
+        self.state.minimap.set_hazard_y(Some(hazard.y));
+    }
+
+    /// Hold co-op deaths for teammate revives and spend shared lives on
+    /// bleedouts.
+    fn process_coop(&mut self, dt: f32) {
+        if let Some(coop) = self.state.coop.as_mut() {
+            coop.tick(
+                dt,
+                &self.player_ids,
+                &mut self.state.players,
+                &self.game_config.coop,
+            );
+        }
+    }
+
     /// Check for race finish and round completion.
     fn check_finish(&mut self) -> Vec<GameEvent> {
         let mut events = Vec::new();
@@ -400,6 +655,9 @@ impl PlatformRacer {
                 ));
                 self.state.finish_order.push(pid);
                 self.finished_set.insert(pid);
+                if self.state.coop.is_some() {
+                    continue;
+                }
                 events.push(GameEvent::ScoreUpdate {
                     player_id: pid,
                     score: scoring::race_score(
@@ -410,11 +668,29 @@ impl PlatformRacer {
             }
         }
 
-        // Round completion: all finished or timer expired
+        // Round completion: all finished or timer expired. In survival the
+        // round also ends once at most one player is left standing; in
+        // co-op it ends when the team meets its finish rule or is wiped out.
         let timer_expired = self.state.round_timer >= self.round_duration;
         let all_finished = self.state.finish_order.len() == self.player_ids.len();
+        let survival_over = self.state.hazard.is_some() && {
+            let standing = self.player_ids.len() - self.elimination_order.len();
+            standing == 0 || (standing == 1 && self.player_ids.len() > 1)
+        };
+        let coop_over = match self.state.coop.as_mut() {
+            Some(coop) => {
+                coop.won = coop.finish_met(&self.player_ids, &self.state.players);
+                let wiped = self
+                    .player_ids
+                    .iter()
+                    .filter_map(|id| self.state.players.get(id))
+                    .all(|p| p.eliminated);
+                coop.won || wiped
+            },
+            None => false,
+        };
 
-        if all_finished || timer_expired {
+        if all_finished || timer_expired || survival_over || coop_over {
             self.state.round_complete = true;
             events.push(GameEvent::RoundComplete);
         }
@@ -445,12 +721,16 @@ impl BreakpointGame for PlatformRacer {
     }
 
     fn init(&mut self, players: &[Player], config: &GameConfig) {
-        // Parse seed from config, or use default
-        let seed = config
-            .custom
-            .get("seed")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(42);
+        // Hosts validate settings before starting; anything that slips
+        // through falls back to the defaults
+        let settings = PlatformerSettings::from_game_config(config).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Ignoring invalid platformer settings");
+            PlatformerSettings::default()
+        });
+        let seed = settings.seed;
+        self.gravity = physics::GRAVITY * settings.gravity_scale;
+        self.scoring = config.scoring;
+        self.late_join = settings.late_join;
 
         self.course = generate_course(seed);
         self.rng = StdRng::seed_from_u64(seed.wrapping_add(12345));
@@ -478,14 +758,40 @@ impl BreakpointGame for PlatformRacer {
             projectiles: Vec::new(),
             rubber_band: HashMap::new(),
             course_version: 0,
+            minimap: Minimap::from_course(&self.course),
+            hazard: None,
+            coop: None,
+            pause: PauseState::default(),
+            traps: Vec::new(),
+            trips: HashMap::new(),
+            powerup_feed: PowerUpFeed::default(),
         };
         self.player_ids.clear();
         self.pending_inputs.clear();
-        self.paused = false;
         self.finished_set.clear();
         self.round_duration = config.round_duration.as_secs_f32();
+        self.hazard_speed = 1.0;
+        self.powerups_enabled = true;
         self.tick_counter = 0;
 
+        // Survival mode: a rising hazard eliminates players from below
+        self.elimination_order.clear();
+        self.departed.clear();
+        if settings.mode == PlatformerMode::Survival {
+            let start_y =
+                (self.course.spawn_y - self.game_config.hazard.start_below_spawn).max(0.0);
+            self.state.hazard = Some(HazardState::new(start_y));
+            self.state.minimap.set_hazard_y(Some(start_y));
+        }
+
+        // Co-op mode: shared lives, teammate revives, team finish
+        if settings.mode == PlatformerMode::Coop {
+            let finish_rule = settings
+                .coop_finish
+                .unwrap_or(self.game_config.coop.finish_rule);
+            self.state.coop = Some(CoopState::new(&self.game_config.coop, finish_rule));
+        }
+
         // Initialize player states
         for (i, player) in players.iter().enumerate() {
             if player.is_spectator {
@@ -499,6 +805,7 @@ impl BreakpointGame for PlatformRacer {
             );
             self.state.active_powerups.insert(player.id, Vec::new());
         }
+        self.state.minimap.update_markers(&self.state.players);
 
         // Spawn power-ups at PowerUpSpawn tiles
         for y in 0..self.course.height {
@@ -518,7 +825,7 @@ impl BreakpointGame for PlatformRacer {
     }
 
     fn update(&mut self, dt: f32, _inputs: &PlayerInputs) -> Vec<GameEvent> {
-        if self.paused || self.state.round_complete {
+        if self.state.pause.is_paused() || self.state.round_complete {
             return Vec::new();
         }
 
@@ -569,23 +876,52 @@ impl BreakpointGame for PlatformRacer {
         // 5. Power-up collection + tick active power-ups
         {
             breakpoint_core::profile!("plat_powerups");
-            self.process_powerups();
-            self.tick_active_powerups(dt);
+            let mut powerup_events = self.process_powerups();
+            powerup_events.extend(self.tick_active_powerups(dt));
+            for event in powerup_events {
+                self.state.powerup_feed.push(event.clone());
+                events.push(event);
+            }
         }
 
-        // 6. Rubber banding
+        // 6. Banana traps
+        {
+            breakpoint_core::profile!("plat_traps");
+            let trap_events = self.process_traps(dt);
+            events.extend(trap_events);
+        }
+
+        // 7. Rubber banding
         {
             breakpoint_core::profile!("plat_rubber_band");
             self.update_rubber_banding();
         }
 
-        // 7. Check finish / round completion
+        // 8. Survival hazard
+        {
+            breakpoint_core::profile!("plat_hazard");
+            self.process_hazard(dt);
+        }
+
+        // 9. Co-op revives and shared lives
+        {
+            breakpoint_core::profile!("plat_coop");
+            self.process_coop(dt);
+        }
+
+        // 10. Check finish / round completion
         {
             breakpoint_core::profile!("plat_finish");
             let finish_events = self.check_finish();
             events.extend(finish_events);
         }
 
+        // 11. Minimap markers
+        self.state.minimap.update_markers(&self.state.players);
+
+        if !self.scoring {
+            events.retain(|e| !e.is_scoring());
+        }
         events
     }
 
@@ -608,6 +944,14 @@ impl BreakpointGame for PlatformRacer {
             projectiles: self.state.projectiles.clone(),
             rubber_band: self.state.rubber_band.clone(),
             course_version: self.state.course_version,
+            minimap_markers: self.state.minimap.markers.clone(),
+            minimap_hazard_row: self.state.minimap.hazard_row,
+            hazard: self.state.hazard.clone(),
+            coop: self.state.coop.clone(),
+            pause: self.state.pause.clone(),
+            traps: self.state.traps.clone(),
+            trips: self.state.trips.clone(),
+            powerup_feed: self.state.powerup_feed.clone(),
         };
         rmp_serde::encode::write(buf, &net).expect("game state serialization must succeed");
     }
@@ -625,6 +969,14 @@ impl BreakpointGame for PlatformRacer {
             self.state.projectiles = net.projectiles;
             self.state.rubber_band = net.rubber_band;
             self.state.course_version = net.course_version;
+            self.state.minimap.markers = net.minimap_markers;
+            self.state.minimap.hazard_row = net.minimap_hazard_row;
+            self.state.hazard = net.hazard;
+            self.state.coop = net.coop;
+            self.state.pause = net.pause;
+            self.state.traps = net.traps;
+            self.state.trips = net.trips;
+            self.state.powerup_feed = net.powerup_feed;
             // course is preserved from previous state / CourseUpdate
             return;
         }
@@ -634,18 +986,47 @@ impl BreakpointGame for PlatformRacer {
         }
     }
 
-    fn pause(&mut self) {
-        self.paused = true;
+    fn inspect_state(&self, state: &[u8]) -> Option<breakpoint_core::game_trait::InspectedState> {
+        breakpoint_core::game_trait::inspect_state::<PlatformerNetState>(state)
+            .or_else(|| breakpoint_core::game_trait::inspect_state::<PlatformerState>(state))
+    }
+
+    fn pause_for(&mut self, reason: PauseReason) {
+        self.state.pause.add(reason);
     }
 
-    fn resume(&mut self) {
-        self.paused = false;
+    fn resume_for(&mut self, reason: PauseReason) {
+        self.state.pause.remove(reason);
+    }
+
+    fn pause_reasons(&self) -> &[PauseReason] {
+        self.state.pause.reasons()
     }
 
     fn is_round_complete(&self) -> bool {
         self.state.round_complete
     }
 
+    fn tutorial_objectives(&self) -> &'static [TutorialObjective] {
+        TUTORIAL_OBJECTIVES
+    }
+
+    fn tutorial_objectives_met(&self, player_id: PlayerId) -> Vec<&'static str> {
+        let mut met = Vec::new();
+        if self
+            .state
+            .active_powerups
+            .get(&player_id)
+            .is_some_and(|pus| !pus.is_empty())
+        {
+            met.push("powerup");
+        }
+        if self.finished_set.contains(&player_id) {
+            met.push("finish");
+        }
+        met
+    }
+
     fn course_data(&mut self) -> Option<Vec<u8>> {
         if self.course_dirty {
             self.course_dirty = false;
@@ -656,10 +1037,62 @@ impl BreakpointGame for PlatformRacer {
         }
     }
 
+    fn apply_debug(&mut self, command: &DebugCommand) -> DebugResult {
+        match *command {
+            DebugCommand::Teleport { player_id, x, y } => {
+                let player = self
+                    .state
+                    .players
+                    .get_mut(&player_id)
+                    .ok_or_else(|| format!("No racer {player_id}"))?;
+                player.x = x;
+                player.y = y;
+                player.vx = 0.0;
+                player.vy = 0.0;
+            },
+            DebugCommand::GrantPowerUp {
+                player_id,
+                ref kind,
+            } => {
+                if !self.state.players.contains_key(&player_id) {
+                    return Err(format!("No racer {player_id}"));
+                }
+                self.apply_powerup(player_id, debug_command::parse_kind(kind)?);
+            },
+            DebugCommand::ForceRoundEnd => self.state.round_complete = true,
+            DebugCommand::SetRoundTimer { elapsed_secs } => {
+                self.state.round_timer = debug_command::checked_elapsed(elapsed_secs)?;
+            },
+        }
+        Ok(())
+    }
+
+    fn live_config_keys(&self) -> &'static [&'static str] {
+        &[LIVE_EXTEND_ROUND, LIVE_HAZARD_SPEED, LIVE_POWERUPS]
+    }
+
+    fn apply_config_change(&mut self, delta: &GameConfigDelta) -> Result<(), String> {
+        delta.check_keys(self.live_config_keys())?;
+        let extension = delta.round_extension()?;
+        let hazard_speed = delta.hazard_speed()?;
+        let powerups = delta.powerups()?;
+        if let Some(secs) = extension {
+            self.round_duration += secs;
+        }
+        if let Some(factor) = hazard_speed {
+            self.hazard_speed = factor;
+        }
+        if let Some(enabled) = powerups {
+            self.powerups_enabled = enabled;
+        }
+        Ok(())
+    }
+
     fn apply_course_data(&mut self, data: &[u8]) {
         if let Ok(course) = rmp_serde::from_slice::<Course>(data) {
             self.course = course.clone();
             self.state.course = course;
+            self.rebuild_minimap_grid();
         }
     }
 
@@ -696,32 +1129,87 @@ impl BreakpointGame for PlatformRacer {
         if player.is_spectator || self.player_ids.contains(&player.id) {
             return;
         }
+        let departed = self.departed.remove(&player.id);
+        if departed.is_none() && self.late_join == LateJoinPolicy::Spectate {
+            return;
+        }
         self.player_ids.push(player.id);
-        self.state.players.insert(
-            player.id,
-            PlatformerPlayerState::new(self.course.spawn_x, self.course.spawn_y),
-        );
+        let mut racer = PlatformerPlayerState::new(self.course.spawn_x, self.course.spawn_y);
+        if let Some(d) = departed {
+            racer.deaths = d.deaths;
+        }
+        self.state.players.insert(player.id, racer);
         self.state.active_powerups.insert(player.id, Vec::new());
     }
 
     fn player_left(&mut self, player_id: PlayerId) {
+        if self.player_ids.contains(&player_id) {
+            // Still standing: ranked just behind everyone else left standing
+            let place = self
+                .survival_place(player_id)
+                .unwrap_or_else(|| self.player_ids.len() - self.elimination_order.len() - 1);
+            let deaths = self.state.players.get(&player_id).map_or(0, |p| p.deaths);
+            self.departed
+                .insert(player_id, DepartedRacer { deaths, place });
+        }
         self.player_ids.retain(|&id| id != player_id);
+        self.elimination_order.retain(|&id| id != player_id);
+        if let Some(coop) = self.state.coop.as_mut() {
+            coop.downed.remove(&player_id);
+        }
         self.state.players.remove(&player_id);
         self.state.active_powerups.remove(&player_id);
+        self.state
+            .minimap
+            .markers
+            .retain(|m| m.player_id != player_id);
     }
 
     fn round_results(&self) -> Vec<PlayerScore> {
-        self.player_ids
-            .iter()
-            .map(|&pid| {
+        let score = |pid: PlayerId, deaths: u8, place: usize| {
+            if let Some(ref coop) = self.state.coop {
+                return scoring::coop_score(
+                    coop.won,
+                    coop.lives,
+                    coop.revives.get(&pid).copied().unwrap_or(0),
+                );
+            }
+            let trap_bonus = scoring::trap_bonus(self.state.trips.get(&pid).copied().unwrap_or(0));
+            if self.state.hazard.is_some() {
+                scoring::survival_score(place) + trap_bonus
+            } else {
                 let pos = self.state.finish_order.iter().position(|&id| id == pid);
-                let deaths = self.state.players.get(&pid).map(|p| p.deaths).unwrap_or(0);
-                PlayerScore {
-                    player_id: pid,
-                    score: scoring::race_score(pos, deaths),
-                }
-            })
-            .collect()
+                scoring::race_score(pos, deaths) + trap_bonus
+            }
+        };
+        let active = self.player_ids.iter().map(|&pid| {
+            let deaths = self.state.players.get(&pid).map(|p| p.deaths).unwrap_or(0);
+            let place = self.survival_place(pid).unwrap_or(0);
+            PlayerScore {
+                player_id: pid,
+                score: score(pid, deaths, place),
+                disconnected: false,
+            }
+        });
+        let departed = self.departed.iter().map(|(&pid, d)| PlayerScore {
+            player_id: pid,
+            score: score(pid, d.deaths, d.place),
+            disconnected: true,
+        });
+        active.chain(departed).collect()
+    }
+
+    fn victory_summary(&self) -> VictorySummary {
+        let racing = self.state.coop.is_none() && self.state.hazard.is_none();
+        let first = self
+            .state
+            .finish_order
+            .iter()
+            .find(|pid| self.player_ids.contains(pid));
+        match first {
+            Some(&pid) if racing => VictorySummary::new(TextKey::VictoryFirstAcross, vec![pid]),
+            _ => VictorySummary::from_scores(&self.round_results()),
+        }
     }
 }
 
@@ -776,6 +1264,196 @@ mod tests {
         assert!(game.pending_inputs.contains_key(&1));
     }
 
+    #[test]
+    fn minimap_markers_sent_without_grid() {
+        let mut game = PlatformRacer::new();
+        let players = make_players(2);
+        game.init(&players, &default_config(180));
+        game.update(0.05, &empty_inputs());
+        assert_eq!(game.state.minimap.markers.len(), 2);
+
+        let mut buf = Vec::new();
+        game.serialize_state_into(&mut buf);
+
+        // Client with a different course: grid comes from CourseUpdate, markers from state
+        let mut client = PlatformRacer::new();
+        client.state.minimap.cells.clear();
+        client.apply_state(&buf);
+        assert_eq!(client.state.minimap.markers, game.state.minimap.markers);
+        assert!(client.state.minimap.cells.is_empty());
+
+        let course = game.course_data().unwrap();
+        client.apply_course_data(&course);
+        assert_eq!(client.state.minimap.cells, game.state.minimap.cells);
+        assert_eq!(client.state.minimap.markers, game.state.minimap.markers);
+    }
+
+    fn survival_config() -> GameConfig {
+        let mut config = default_config(180);
+        config
+            .custom
+            .insert("mode".to_string(), serde_json::json!("survival"));
+        config
+    }
+
+    #[test]
+    fn survival_mode_creates_hazard() {
+        let mut race = PlatformRacer::new();
+        race.init(&make_players(2), &default_config(180));
+        assert!(race.state.hazard.is_none());
+
+        let mut game = PlatformRacer::new();
+        game.init(&make_players(2), &survival_config());
+        let start_y = game.state.hazard.as_ref().unwrap().y;
+        game.update(0.05, &empty_inputs());
+        let hazard = game.state.hazard.as_ref().unwrap();
+        assert!(hazard.y > start_y, "Hazard should rise");
+        assert!(hazard.speed > 0.0, "Current speed is exposed in state");
+    }
+
+    #[test]
+    fn hazard_eliminates_and_pauses() {
+        let mut game = PlatformRacer::new();
+        game.init(&make_players(3), &survival_config());
+        let victim = game.player_ids[0];
+        // Lift the hazard past one player's feet
+        game.state.hazard.as_mut().unwrap().y = game.state.players[&victim].y + 1.0;
+        for pid in &game.player_ids[1..] {
+            game.state.players.get_mut(pid).unwrap().y += 10.0;
+        }
+
+        game.update(0.05, &empty_inputs());
+        assert!(game.state.players[&victim].eliminated);
+        assert!(!game.state.round_complete, "Two players still standing");
+        let hazard = game.state.hazard.as_ref().unwrap();
+        assert_eq!(hazard.speed, 0.0, "Hazard pauses after an elimination");
+        assert!(hazard.pause_remaining > 0.0);
+    }
+
+    #[test]
+    fn survival_ends_with_last_player_standing() {
+        let mut game = PlatformRacer::new();
+        game.init(&make_players(3), &survival_config());
+        let (first_out, second_out, winner) =
+            (game.player_ids[0], game.player_ids[1], game.player_ids[2]);
+        let base_y = game.state.players[&first_out].y;
+        game.state.players.get_mut(&second_out).unwrap().y = base_y + 10.0;
+        game.state.players.get_mut(&winner).unwrap().y = base_y + 20.0;
+
+        game.state.hazard.as_mut().unwrap().y = base_y + 1.0;
+        game.update(0.05, &empty_inputs());
+        assert!(game.state.players[&first_out].eliminated);
+        game.state.hazard.as_mut().unwrap().y = base_y + 11.0;
+        let events = game.update(0.05, &empty_inputs());
+        assert!(game.state.round_complete);
+        assert!(events.iter().any(|e| matches!(e, GameEvent::RoundComplete)));
+
+        let score = |pid| {
+            game.round_results()
+                .iter()
+                .find(|s| s.player_id == pid)
+                .unwrap()
+                .score
+        };
+        assert!(score(winner) > score(second_out));
+        assert!(score(second_out) > score(first_out));
+    }
+
+    #[test]
+    fn hazard_synced_in_net_state() {
+        let mut game = PlatformRacer::new();
+        game.init(&make_players(2), &survival_config());
+        game.update(0.05, &empty_inputs());
+
+        let mut buf = Vec::new();
+        game.serialize_state_into(&mut buf);
+        let mut client = PlatformRacer::new();
+        client.apply_state(&buf);
+        assert_eq!(client.state.hazard, game.state.hazard);
+    }
+
+    fn coop_config(finish: &str) -> GameConfig {
+        let mut config = default_config(180);
+        config
+            .custom
+            .insert("mode".to_string(), serde_json::json!("coop"));
+        config
+            .custom
+            .insert("coop_finish".to_string(), serde_json::json!(finish));
+        config
+    }
+
+    #[test]
+    fn coop_death_waits_for_revive() {
+        let mut game = PlatformRacer::new();
+        game.init(&make_players(2), &coop_config("all"));
+        let coop = game.state.coop.as_ref().unwrap();
+        assert_eq!(coop.finish_rule, coop::CoopFinishRule::All);
+        assert_eq!(coop.lives, game.game_config.coop.shared_lives);
+
+        let victim = game.player_ids[0];
+        let player = game.state.players.get_mut(&victim).unwrap();
+        player.death_respawn_timer = combat::DEATH_RESPAWN_TIMER;
+        player.deaths += 1;
+        game.update(0.05, &empty_inputs());
+        assert!(game.state.players[&victim].downed);
+
+        // Without a teammate nearby the respawn timer doesn't bring them back
+        let other = game.player_ids[1];
+        game.state.players.get_mut(&other).unwrap().x += 50.0;
+        for _ in 0..60 {
+            game.update(0.05, &empty_inputs());
+        }
+        assert!(
+            game.state.players[&victim].downed,
+            "Still waiting on a revive"
+        );
+        assert!(
+            game.state
+                .coop
+                .as_ref()
+                .unwrap()
+                .downed
+                .contains_key(&victim)
+        );
+    }
+
+    #[test]
+    fn coop_any_finish_wins_for_the_team() {
+        let mut game = PlatformRacer::new();
+        game.init(&make_players(3), &coop_config("any"));
+        let finisher = game.player_ids[0];
+        game.state.players.get_mut(&finisher).unwrap().finished = true;
+
+        let events = game.update(0.05, &empty_inputs());
+        assert!(events.iter().any(|e| matches!(e, GameEvent::RoundComplete)));
+        let coop = game.state.coop.as_ref().unwrap();
+        assert!(coop.won);
+
+        let results = game.round_results();
+        let expected = scoring::coop_score(true, coop.lives, 0);
+        assert!(results.iter().all(|r| r.score == expected), "Shared result");
+    }
+
+    #[test]
+    fn coop_wipe_loses_round() {
+        let mut game = PlatformRacer::new();
+        game.init(&make_players(2), &coop_config("all"));
+        for p in game.state.players.values_mut() {
+            p.eliminated = true;
+        }
+        game.update(0.05, &empty_inputs());
+        assert!(game.state.round_complete);
+        assert!(!game.state.coop.as_ref().unwrap().won);
+        assert!(game.round_results().iter().all(|r| r.score == 0));
+
+        let mut buf = Vec::new();
+        game.serialize_state_into(&mut buf);
+        let mut client = PlatformRacer::new();
+        client.apply_state(&buf);
+        assert_eq!(client.state.coop, game.state.coop, "Co-op state is synced");
+    }
+
     #[test]
     fn tick_rate_is_20() {
         let game = PlatformRacer::new();
@@ -881,6 +1559,179 @@ mod tests {
         );
     }
 
+    #[test]
+    fn banana_trap_trips_the_next_racer_for_its_owner() {
+        let mut game = PlatformRacer::new();
+        let players = make_players(2);
+        game.init(&players, &default_config(180));
+        game.state
+            .active_powerups
+            .entry(1)
+            .or_default()
+            .push(ActivePowerUp::new(PowerUpKind::Banana));
+
+        let input = |move_dir: f32, use_powerup: bool| {
+            rmp_serde::to_vec(&PlatformerInput {
+                move_dir,
+                jump: false,
+                use_powerup,
+                attack: false,
+            })
+            .unwrap()
+        };
+        game.apply_input(1, &input(0.0, true));
+        game.update(1.0 / 20.0, &empty_inputs());
+        assert_eq!(game.state.traps.len(), 1, "Banana is dropped on use");
+        assert!(game.state.active_powerups[&1].is_empty());
+        let trap = game.state.traps[0].clone();
+        assert!(
+            trap.x < game.state.players[&1].x,
+            "Dropped behind the owner"
+        );
+
+        // Racer 2 runs into it
+        {
+            let racer = game.state.players.get_mut(&2).unwrap();
+            racer.x = trap.x;
+            racer.y = trap.y;
+        }
+        let events = game.update(1.0 / 20.0, &empty_inputs());
+        assert!(game.state.traps.is_empty(), "Traps spring once");
+        assert!(game.state.players[&2].stumble_timer > 0.0);
+        assert_eq!(game.state.trips.get(&1), Some(&1));
+        assert!(events.iter().any(|e| matches!(
+            e,
+            GameEvent::ScoreUpdate {
+                player_id: 1,
+                score: 1
+            }
+        )));
+
+        // No control while stumbling
+        let x = game.state.players[&2].x;
+        game.apply_input(2, &input(1.0, false));
+        game.update(1.0 / 20.0, &empty_inputs());
+        assert_eq!(game.state.players[&2].x, x);
+
+        let results = game.round_results();
+        let score = |pid| results.iter().find(|s| s.player_id == pid).unwrap().score;
+        assert_eq!(score(1), 1, "Trip bonus on top of a DNF");
+        assert_eq!(score(2), 0);
+    }
+
+    #[test]
+    fn micro_rounds_rule_out_course_votes() {
+        use breakpoint_core::round_phase::MICRO_ROUNDS_SETTING;
+
+        let micro = GameConfig::builder()
+            .setting(MICRO_ROUNDS_SETTING, true)
+            .build();
+        assert!(PlatformerSettings::validate(&micro.custom).is_ok());
+        let voted = GameConfig::builder()
+            .setting(MICRO_ROUNDS_SETTING, true)
+            .setting("course_vote", true)
+            .build();
+        let err = PlatformerSettings::validate(&voted.custom).unwrap_err();
+        assert_eq!(err.key, MICRO_ROUNDS_SETTING);
+    }
+
+    #[test]
+    fn live_settings_change_the_running_round() {
+        use breakpoint_core::game_trait::{
+            GameConfigDelta, LIVE_EXTEND_ROUND, LIVE_HAZARD_SPEED, LIVE_POWERUPS,
+        };
+
+        let mut game = PlatformRacer::new();
+        game.init(&make_players(2), &survival_config());
+        let normal = game.game_config.hazard.speed(0.0, 2, 2);
+
+        // A bad value or a key the game doesn't take live changes nothing
+        let bad = GameConfigDelta::default()
+            .set(LIVE_EXTEND_ROUND, 30.0)
+            .set(LIVE_HAZARD_SPEED, 10.0);
+        assert!(game.apply_config_change(&bad).is_err());
+        let locked = GameConfigDelta::default().set("gravity_scale", 2.0);
+        assert!(game.apply_config_change(&locked).is_err());
+        assert_eq!(game.round_duration, 180.0);
+
+        let delta = GameConfigDelta::default()
+            .set(LIVE_EXTEND_ROUND, 30.0)
+            .set(LIVE_HAZARD_SPEED, 2.0)
+            .set(LIVE_POWERUPS, false);
+        game.apply_config_change(&delta).unwrap();
+        assert_eq!(game.round_duration, 210.0);
+
+        let racer = &game.state.players[&1];
+        let (x, y) = (racer.x, racer.y);
+        game.state.powerups.push(SpawnedPowerUp {
+            x,
+            y,
+            kind: PowerUpKind::DoubleJump,
+            collected: false,
+        });
+        game.update(0.05, &empty_inputs());
+        let hazard = game.state.hazard.as_ref().unwrap();
+        assert!(
+            (hazard.speed - normal * 2.0).abs() < 0.01,
+            "{}",
+            hazard.speed
+        );
+        assert!(!game.state.powerups[0].collected, "Pickups are off");
+
+        // The next round starts from the lobby settings
+        game.init(&make_players(2), &survival_config());
+        assert_eq!(game.round_duration, 180.0);
+        assert_eq!(game.hazard_speed, 1.0);
+        assert!(game.powerups_enabled);
+    }
+
+    #[test]
+    fn debug_commands_drive_the_round() {
+        let mut game = PlatformRacer::new();
+        game.init(&make_players(2), &default_config(180));
+
+        game.apply_debug(&DebugCommand::Teleport {
+            player_id: 2,
+            x: 40.0,
+            y: 6.0,
+        })
+        .unwrap();
+        assert_eq!(
+            (game.state.players[&2].x, game.state.players[&2].y),
+            (40.0, 6.0)
+        );
+
+        game.apply_debug(&DebugCommand::GrantPowerUp {
+            player_id: 1,
+            kind: "DoubleJump".to_string(),
+        })
+        .unwrap();
+        assert!(game.state.players[&1].has_double_jump);
+        assert!(
+            game.apply_debug(&DebugCommand::GrantPowerUp {
+                player_id: 1,
+                kind: "RapidFire".to_string(),
+            })
+            .is_err()
+        );
+        assert!(
+            game.apply_debug(&DebugCommand::Teleport {
+                player_id: 9,
+                x: 0.0,
+                y: 0.0,
+            })
+            .is_err()
+        );
+
+        game.apply_debug(&DebugCommand::SetRoundTimer {
+            elapsed_secs: 179.0,
+        })
+        .unwrap();
+        assert_eq!(game.state.round_timer, 179.0);
+        game.apply_debug(&DebugCommand::ForceRoundEnd).unwrap();
+        assert!(game.is_round_complete());
+    }
+
     #[test]
     fn speed_boots_multiplies_movement() {
         let mut game = PlatformRacer::new();
@@ -1079,9 +1930,19 @@ mod tests {
         assert_eq!(game.state.active_powerups[&pid].len(), 2);
 
         // Tick enough for SpeedBoots to expire (5s), at 20Hz = 100 ticks + extra
+        let mut expired = Vec::new();
         for _ in 0..120 {
-            game.update(1.0 / 20.0, &empty_inputs());
+            for event in game.update(1.0 / 20.0, &empty_inputs()) {
+                if let GameEvent::PowerupExpired { player_id, kind } = event {
+                    expired.push((player_id, kind));
+                }
+            }
         }
+        assert_eq!(expired, [(pid, "speed_boots".to_string())]);
+        assert!(game.state.powerup_feed.since(0).any(|e| matches!(
+            e,
+            GameEvent::PowerupExpired { kind, .. } if kind == "speed_boots"
+        )));
 
         let pus = &game.state.active_powerups[&pid];
         assert_eq!(
@@ -1611,6 +2472,14 @@ mod tests {
         breakpoint_core::test_helpers::contract_pause_stops_updates(&mut game);
     }
 
+    #[test]
+    fn contract_pause_reasons_stack() {
+        let mut game = PlatformRacer::new();
+        let players = make_players(1);
+        game.init(&players, &default_config(180));
+        breakpoint_core::test_helpers::contract_pause_reasons_stack(&mut game);
+    }
+
     #[test]
     fn contract_player_left_cleanup() {
         let mut game = PlatformRacer::new();
@@ -1720,4 +2589,38 @@ mod tests {
             state_bytes.len()
         );
     }
+
+    #[test]
+    fn tick_fits_its_budget_at_max_players() {
+        use breakpoint_core::test_helpers::assert_tick_budget;
+
+        let mut game = PlatformRacer::new();
+        let players = make_players(game.metadata().max_players as usize);
+        game.init(&players, &default_config(180));
+        let start = game.state.players.clone();
+
+        // Everyone runs, jumps and attacks; racers go back to the start
+        // every 40 ticks so nobody finishes mid-measurement
+        let mut tick = 0;
+        assert_tick_budget(
+            "platformer update, 6 players",
+            &mut game,
+            Duration::from_millis(1),
+            |game| {
+                tick += 1;
+                if tick % 40 == 0 {
+                    game.state.players.clone_from(&start);
+                }
+                for player in &players {
+                    let input = PlatformerInput {
+                        move_dir: 1.0,
+                        jump: tick % 10 == 0,
+                        use_powerup: true,
+                        attack: tick % 5 == 0,
+                    };
+                    game.apply_input(player.id, &rmp_serde::to_vec(&input).unwrap());
+                }
+            },
+        );
+    }
 }
@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+
+use breakpoint_core::game_trait::PlayerId;
+use breakpoint_core::net::messages::CourseCandidate;
+use serde::{Deserialize, Serialize};
+
+use crate::course_gen::{Course, Tile, generate_course};
+use crate::physics::{PlatformerPlayerState, TILE_SIZE};
+
+/// Course tiles folded into each minimap cell along each axis.
+pub const MINIMAP_SCALE: u32 = 4;
+/// Course tiles folded into each cell of a course-vote thumbnail.
+pub const THUMBNAIL_SCALE: u32 = 8;
+
+/// Downsampled classification of a block of course tiles.
+///
+/// Serialized as a `u8` like [`Tile`]. When a block mixes tile kinds the
+/// most gameplay-relevant one wins (see [`MinimapCell::priority`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum MinimapCell {
+    #[default]
+    Open = 0,
+    Solid = 1,
+    Water = 2,
+    Hazard = 3,
+    Checkpoint = 4,
+    Finish = 5,
+}
+
+impl MinimapCell {
+    fn from_tile(tile: Tile) -> Self {
+        match tile {
+            Tile::StoneBrick | Tile::BreakableWall => Self::Solid,
+            Tile::Water => Self::Water,
+            Tile::Spikes => Self::Hazard,
+            Tile::Checkpoint => Self::Checkpoint,
+            Tile::Finish => Self::Finish,
+            _ => Self::Open,
+        }
+    }
+
+    /// Higher priority wins when several tile kinds share a cell.
+    fn priority(self) -> u8 {
+        match self {
+            Self::Open => 0,
+            Self::Water => 1,
+            Self::Solid => 2,
+            Self::Hazard => 3,
+            Self::Checkpoint => 4,
+            Self::Finish => 5,
+        }
+    }
+}
+
+impl TryFrom<u8> for MinimapCell {
+    type Error = String;
+
+    fn try_from(v: u8) -> Result<Self, Self::Error> {
+        match v {
+            0 => Ok(Self::Open),
+            1 => Ok(Self::Solid),
+            2 => Ok(Self::Water),
+            3 => Ok(Self::Hazard),
+            4 => Ok(Self::Checkpoint),
+            5 => Ok(Self::Finish),
+            _ => Err(format!("invalid minimap cell value: {v}")),
+        }
+    }
+}
+
+impl Serialize for MinimapCell {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+
+impl<'de> Deserialize<'de> for MinimapCell {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let v = u8::deserialize(deserializer)?;
+        MinimapCell::try_from(v).map_err(serde::de::Error::custom)
+    }
+}
+
+/// A player's position on the minimap, in cell coordinates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MinimapMarker {
+    pub player_id: PlayerId,
+    pub col: u8,
+    pub row: u8,
+    /// Room graph distance from the start, used to order the race.
+    pub progress: u16,
+    pub finished: bool,
+    pub eliminated: bool,
+}
+
+/// Compact race-position minimap.
+///
+/// The `cells` grid is built once from the course (and rebuilt when a
+/// breakable wall is destroyed). Only `markers` and `hazard_row` change per
+/// tick, and only those are carried in the per-tick wire state.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Minimap {
+    pub cols: u8,
+    pub rows: u8,
+    /// Cell data stored row-major (row * cols + col), row 0 at the bottom.
+    pub cells: Vec<MinimapCell>,
+    pub markers: Vec<MinimapMarker>,
+    /// Minimap row of the hazard line, for modes with a rising hazard.
+    /// `None` in race mode.
+    pub hazard_row: Option<u8>,
+}
+
+impl Minimap {
+    /// Downsample a course into a minimap grid with no markers.
+    pub fn from_course(course: &Course) -> Self {
+        let (cols, rows, cells) = downsample(course, MINIMAP_SCALE);
+        Self {
+            cols,
+            rows,
+            cells,
+            markers: Vec::new(),
+            hazard_row: None,
+        }
+    }
+
+    /// Cell at the given minimap coordinate, `Open` when out of bounds.
+    pub fn cell(&self, col: u8, row: u8) -> MinimapCell {
+        if col >= self.cols || row >= self.rows {
+            return MinimapCell::Open;
+        }
+        self.cells[row as usize * self.cols as usize + col as usize]
+    }
+
+    /// Convert a world position to a clamped minimap cell coordinate.
+    pub fn world_to_cell(&self, x: f32, y: f32) -> (u8, u8) {
+        let scale = TILE_SIZE * MINIMAP_SCALE as f32;
+        let col = (x / scale)
+            .floor()
+            .clamp(0.0, self.cols.saturating_sub(1) as f32);
+        let row = (y / scale)
+            .floor()
+            .clamp(0.0, self.rows.saturating_sub(1) as f32);
+        (col as u8, row as u8)
+    }
+
+    /// Refresh player markers from the current player states.
+    /// Markers are sorted by player ID for a stable wire order.
+    pub fn update_markers(&mut self, players: &HashMap<PlayerId, PlatformerPlayerState>) {
+        self.markers.clear();
+        for (&player_id, p) in players {
+            let (col, row) = self.world_to_cell(p.x, p.y);
+            self.markers.push(MinimapMarker {
+                player_id,
+                col,
+                row,
+                progress: p.current_room_distance,
+                finished: p.finished,
+                eliminated: p.eliminated,
+            });
+        }
+        self.markers.sort_by_key(|m| m.player_id);
+    }
+
+    /// Set the hazard line from a world-space height, or clear it.
+    pub fn set_hazard_y(&mut self, hazard_y: Option<f32>) {
+        self.hazard_row = hazard_y.map(|y| self.world_to_cell(0.0, y).1);
+    }
+}
+
+/// Coarse thumbnail of a course for pre-round votes, as `(cols, rows,
+/// cells)` laid out like [`Minimap::cells`].
+pub fn thumbnail(course: &Course) -> (u8, u8, Vec<MinimapCell>) {
+    downsample(course, THUMBNAIL_SCALE)
+}
+
+/// Vote candidate for the course generated from `seed`.
+pub fn preview(seed: u64) -> CourseCandidate {
+    let (cols, rows, cells) = thumbnail(&generate_course(seed));
+    CourseCandidate {
+        seed,
+        cols,
+        rows,
+        cells: cells.into_iter().map(|c| c as u8).collect(),
+    }
+}
+
+/// Fold `scale` x `scale` blocks of tiles into cells, keeping the most
+/// gameplay-relevant kind in each.
+/// Courses wider or taller than `u8::MAX` cells are cut off at the edge.
+fn downsample(course: &Course, scale: u32) -> (u8, u8, Vec<MinimapCell>) {
+    let cols = course.width.div_ceil(scale).min(u8::MAX as u32);
+    let rows = course.height.div_ceil(scale).min(u8::MAX as u32);
+    let mut cells = vec![MinimapCell::Open; (cols * rows) as usize];
+
+    for ty in 0..course.height.min(rows * scale) {
+        for tx in 0..course.width.min(cols * scale) {
+            let kind = MinimapCell::from_tile(course.get_tile(tx as i32, ty as i32));
+            let idx = ((ty / scale) * cols + tx / scale) as usize;
+            if kind.priority() > cells[idx].priority() {
+                cells[idx] = kind;
+            }
+        }
+    }
+
+    (cols as u8, rows as u8, cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::course_gen::{COURSE_HEIGHT, COURSE_WIDTH};
+
+    #[test]
+    fn grid_dimensions_downsample_course() {
+        let course = generate_course(42);
+        let map = Minimap::from_course(&course);
+        assert_eq!(map.cols as u32, course.width.div_ceil(MINIMAP_SCALE));
+        assert_eq!(map.rows as u32, course.height.div_ceil(MINIMAP_SCALE));
+        assert_eq!(map.cells.len(), map.cols as usize * map.rows as usize);
+    }
+
+    #[test]
+    fn oversized_course_is_cut_off_at_the_edge() {
+        let mut course = generate_course(42);
+        course.width = (u8::MAX as u32 + 10) * MINIMAP_SCALE;
+        course.height = 8;
+        course.tiles = vec![Tile::Empty; (course.width * course.height) as usize];
+        let map = Minimap::from_course(&course);
+        assert_eq!(map.cols, u8::MAX);
+        assert_eq!(map.rows, 2);
+        assert_eq!(map.cells.len(), map.cols as usize * map.rows as usize);
+        let far_x = course.width as f32 * TILE_SIZE;
+        assert_eq!(map.world_to_cell(far_x, 0.0), (u8::MAX - 1, 0));
+    }
+
+    #[test]
+    fn preview_is_a_coarse_thumbnail() {
+        let candidate = preview(42);
+        assert_eq!(candidate.seed, 42);
+        assert_eq!(
+            candidate.cols as u32,
+            COURSE_WIDTH.div_ceil(THUMBNAIL_SCALE)
+        );
+        assert_eq!(
+            candidate.rows as u32,
+            COURSE_HEIGHT.div_ceil(THUMBNAIL_SCALE)
+        );
+        assert_eq!(
+            candidate.cells.len(),
+            candidate.cols as usize * candidate.rows as usize
+        );
+        assert!(candidate.cells.contains(&(MinimapCell::Finish as u8)));
+        assert_eq!(candidate, preview(42));
+    }
+
+    #[test]
+    fn finish_tile_survives_downsampling() {
+        let course = generate_course(42);
+        let map = Minimap::from_course(&course);
+        assert!(
+            map.cells.contains(&MinimapCell::Finish),
+            "Finish line must be visible on the minimap"
+        );
+    }
+
+    #[test]
+    fn markers_track_player_positions() {
+        let course = generate_course(42);
+        let mut map = Minimap::from_course(&course);
+        let mut players = HashMap::new();
+        players.insert(2, PlatformerPlayerState::new(10.0, 10.0));
+        players.insert(1, PlatformerPlayerState::new(0.5, 0.5));
+        map.update_markers(&players);
+
+        assert_eq!(map.markers.len(), 2);
+        assert_eq!(map.markers[0].player_id, 1);
+        assert_eq!((map.markers[0].col, map.markers[0].row), (0, 0));
+        assert_eq!((map.markers[1].col, map.markers[1].row), (2, 2));
+    }
+
+    #[test]
+    fn world_to_cell_clamps_out_of_bounds() {
+        let course = generate_course(42);
+        let map = Minimap::from_course(&course);
+        assert_eq!(map.world_to_cell(-50.0, -50.0), (0, 0));
+        assert_eq!(
+            map.world_to_cell(1.0e6, 1.0e6),
+            (map.cols - 1, map.rows - 1)
+        );
+    }
+
+    #[test]
+    fn cell_serializes_as_u8() {
+        let data = rmp_serde::to_vec(&MinimapCell::Finish).unwrap();
+        let back: MinimapCell = rmp_serde::from_slice(&data).unwrap();
+        assert_eq!(back, MinimapCell::Finish);
+        assert_eq!(data, vec![5]);
+    }
+}
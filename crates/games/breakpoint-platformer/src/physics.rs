@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
 
 use crate::combat::{ATTACK_COOLDOWN, ATTACK_DURATION, INVINCIBILITY_DURATION};
+use crate::coop::CoopConfig;
 use crate::course_gen::{Course, Tile};
+use crate::hazard::HazardConfig;
 use crate::powerups::PowerUpKind;
+use crate::trap::TrapConfig;
 
 /// Gravity acceleration (units/s^2, downward).
 pub const GRAVITY: f32 = -30.0;
@@ -62,6 +65,12 @@ pub struct PlatformerConfig {
     pub round_duration_secs: f32,
     pub tick_rate_hz: f32,
     pub speed_boost_multiplier: f32,
+    /// Survival-mode rising hazard speed curve.
+    pub hazard: HazardConfig,
+    /// Co-op mode shared lives and revives.
+    pub coop: CoopConfig,
+    /// Banana traps.
+    pub trap: TrapConfig,
 }
 
 impl Default for PlatformerConfig {
@@ -71,6 +80,9 @@ impl Default for PlatformerConfig {
             round_duration_secs: 180.0,
             tick_rate_hz: 20.0,
             speed_boost_multiplier: 1.5,
+            hazard: HazardConfig::default(),
+            coop: CoopConfig::default(),
+            trap: TrapConfig::default(),
         }
     }
 }
@@ -139,6 +151,12 @@ pub struct PlatformerPlayerState {
     pub powerup_timer: f32,
     /// Current room's graph distance from start (for rubber-banding/race position).
     pub current_room_distance: u16,
+    /// Co-op: dead and waiting for a teammate revive instead of respawning.
+    #[serde(default)]
+    pub downed: bool,
+    /// Seconds left without control after tripping on a trap.
+    #[serde(default)]
+    pub stumble_timer: f32,
 }
 
 impl PlatformerPlayerState {
@@ -170,6 +188,8 @@ impl PlatformerPlayerState {
             active_powerup: None,
             powerup_timer: 0.0,
             current_room_distance: 0,
+            downed: false,
+            stumble_timer: 0.0,
         }
     }
 
@@ -185,6 +205,7 @@ impl PlatformerPlayerState {
         self.attack_timer = 0.0;
         self.attack_cooldown = 0.0;
         self.death_respawn_timer = 0.0;
+        self.stumble_timer = 0.0;
         self.anim_state = AnimState::Idle;
     }
 }
@@ -215,11 +236,29 @@ pub fn tick_player(
     input: &PlatformerInput,
     course: &Course,
     dt: f32,
+) {
+    tick_player_with_gravity(player, input, course, dt, GRAVITY);
+}
+
+/// [`tick_player`] under `gravity` instead of [`GRAVITY`], for rounds with
+/// the `gravity_scale` setting.
+pub fn tick_player_with_gravity(
+    player: &mut PlatformerPlayerState,
+    input: &PlatformerInput,
+    course: &Course,
+    dt: f32,
+    gravity: f32,
 ) {
     if player.finished || player.eliminated {
         return;
     }
 
+    // Downed co-op players hold their death pose until revived
+    if player.downed {
+        player.anim_state = AnimState::Dead;
+        return;
+    }
+
     // Death respawn timer: skip all movement while dead
     if player.death_respawn_timer > 0.0 {
         player.death_respawn_timer -= dt;
@@ -238,6 +277,17 @@ pub fn tick_player(
         }
     }
 
+    // A tripped racer ignores input until the stumble wears off
+    let stumbling = player.stumble_timer > 0.0;
+    let neutral_input;
+    let input = if stumbling {
+        player.stumble_timer = (player.stumble_timer - dt).max(0.0);
+        neutral_input = PlatformerInput::default();
+        &neutral_input
+    } else {
+        input
+    };
+
     // Tick animation time
     player.anim_time += dt;
 
@@ -313,7 +363,7 @@ pub fn tick_player(
         }
 
         // Apply gravity with buoyancy (buoyancy counters ~30% of gravity)
-        player.vy += (GRAVITY + WATER_BUOYANCY) * dt;
+        player.vy += (gravity + WATER_BUOYANCY) * dt;
     } else {
         // Normal movement
         player.vx = move_dir * MOVE_SPEED;
@@ -326,7 +376,7 @@ pub fn tick_player(
         }
 
         // Apply gravity
-        player.vy += GRAVITY * dt;
+        player.vy += gravity * dt;
     }
 
     // Move
@@ -351,8 +401,8 @@ fn update_anim_state(player: &mut PlatformerPlayerState) {
         return;
     }
 
-    // Hurt overrides while in early invincibility frames
-    if player.invincibility_timer > INVINCIBILITY_DURATION - 0.3 {
+    // Hurt overrides while in early invincibility frames or stumbling
+    if player.invincibility_timer > INVINCIBILITY_DURATION - 0.3 || player.stumble_timer > 0.0 {
         player.anim_state = AnimState::Hurt;
         return;
     }
@@ -493,20 +543,18 @@ pub(crate) fn check_tile_effects(player: &mut PlatformerPlayerState, course: &Co
     player.current_room_distance = course.room_distance_at(player.x, player.y);
 
     match course.get_tile(tx, ty) {
-        Tile::Spikes => {
-            // Spikes deal 1 HP damage with invincibility, instead of instant respawn
-            if player.invincibility_timer <= 0.0 {
-                player.hp = player.hp.saturating_sub(1);
-                if player.hp == 0 {
-                    player.deaths += 1;
-                    player.death_respawn_timer = crate::combat::DEATH_RESPAWN_TIMER;
-                    player.vx = 0.0;
-                    player.vy = 0.0;
-                } else {
-                    player.invincibility_timer = INVINCIBILITY_DURATION;
-                    // Bounce player up slightly to avoid repeat damage
-                    player.vy = JUMP_VELOCITY * 0.5;
-                }
+        // Spikes deal 1 HP damage with invincibility, instead of instant respawn
+        Tile::Spikes if player.invincibility_timer <= 0.0 => {
+            player.hp = player.hp.saturating_sub(1);
+            if player.hp == 0 {
+                player.deaths += 1;
+                player.death_respawn_timer = crate::combat::DEATH_RESPAWN_TIMER;
+                player.vx = 0.0;
+                player.vy = 0.0;
+            } else {
+                player.invincibility_timer = INVINCIBILITY_DURATION;
+                // Bounce player up slightly to avoid repeat damage
+                player.vy = JUMP_VELOCITY * 0.5;
             }
         },
         Tile::Checkpoint => {
@@ -560,6 +608,20 @@ mod tests {
         assert!(player.y < y_before, "Gravity should pull player down");
     }
 
+    #[test]
+    fn low_gravity_falls_slower() {
+        let course = generate_course(42);
+        let mut normal = PlatformerPlayerState::new(2.0, 10.0);
+        let mut floaty = normal.clone();
+        let input = PlatformerInput::default();
+
+        tick_player(&mut normal, &input, &course, 0.1);
+        tick_player_with_gravity(&mut floaty, &input, &course, 0.1, GRAVITY * 0.5);
+
+        assert!(floaty.y > normal.y, "Half gravity should fall less far");
+        assert!(floaty.y < 10.0);
+    }
+
     #[test]
     fn grounding_stops_fall() {
         let course = generate_course(42);
@@ -20,6 +20,9 @@ pub enum PowerUpKind {
     Invincibility,
     /// Extended whip attack range for 10 seconds.
     WhipExtend,
+    /// Held until used, then dropped behind the player as a
+    /// [`crate::trap::Trap`].
+    Banana,
 }
 
 impl powerup::PowerUpKind for PowerUpKind {
@@ -32,6 +35,20 @@ impl powerup::PowerUpKind for PowerUpKind {
             PowerUpKind::ArmorUp => f32::INFINITY,
             PowerUpKind::Invincibility => 3.0,
             PowerUpKind::WhipExtend => 10.0,
+            PowerUpKind::Banana => f32::INFINITY,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            PowerUpKind::HolyWater => "holy_water",
+            PowerUpKind::Crucifix => "crucifix",
+            PowerUpKind::SpeedBoots => "speed_boots",
+            PowerUpKind::DoubleJump => "double_jump",
+            PowerUpKind::ArmorUp => "armor_up",
+            PowerUpKind::Invincibility => "invincibility",
+            PowerUpKind::WhipExtend => "whip_extend",
+            PowerUpKind::Banana => "banana",
         }
     }
 }
@@ -59,6 +76,7 @@ pub fn select_powerup_for_position(quality: f32, rng: &mut impl Rng) -> PowerUpK
             PowerUpKind::HolyWater,
             PowerUpKind::DoubleJump,
             PowerUpKind::WhipExtend,
+            PowerUpKind::Banana,
         ];
         options[rng.random_range(0..options.len())]
     } else if quality <= 0.7 {
@@ -68,6 +86,7 @@ pub fn select_powerup_for_position(quality: f32, rng: &mut impl Rng) -> PowerUpK
             PowerUpKind::DoubleJump,
             PowerUpKind::HolyWater,
             PowerUpKind::WhipExtend,
+            PowerUpKind::Banana,
         ];
         options[rng.random_range(0..options.len())]
     } else {
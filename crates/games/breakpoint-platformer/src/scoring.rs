@@ -19,6 +19,36 @@ pub fn race_score(finish_position: Option<usize>, deaths: u8) -> i32 {
     (base - penalty).max(0)
 }
 
+/// Calculate a player's score in Survival mode from their place (0 = still
+/// standing). Uses the race placement table without the death penalty,
+/// since deaths already cost ground against the hazard.
+pub fn survival_score(place: usize) -> i32 {
+    race_score(Some(place), 0)
+}
+
+/// Calculate a player's score in Co-op mode. The team shares the result:
+/// a win is worth 6 plus 1 per unused shared life (up to 4), and each
+/// revive the player performed adds 1 (up to 3), win or lose.
+pub fn coop_score(team_won: bool, lives_left: u8, revives: u32) -> i32 {
+    let win = if team_won {
+        6 + i32::from(lives_left.min(4))
+    } else {
+        0
+    };
+    win + revives.min(3) as i32
+}
+
+/// Most points a player can earn per round from tripping others with
+/// traps.
+pub const MAX_TRAP_BONUS: i32 = 3;
+
+/// Bonus for `trips` racers tripped with the player's traps in Race and
+/// Survival: 1 point each, up to [`MAX_TRAP_BONUS`]. Co-op has none, since
+/// the only racers to trip are teammates.
+pub fn trap_bonus(trips: u32) -> i32 {
+    trips.min(MAX_TRAP_BONUS as u32) as i32
+}
+
 /// Calculate the effective finish time including death time penalties.
 ///
 /// Each death adds `DEATH_TIME_PENALTY` seconds to the actual finish time.
@@ -57,6 +87,21 @@ mod tests {
         assert_eq!(race_score(None, 10), 0);
     }
 
+    #[test]
+    fn coop_score_rewards_wins_lives_and_revives() {
+        assert_eq!(coop_score(true, 0, 0), 6);
+        assert_eq!(coop_score(true, 10, 0), 10, "Life bonus capped");
+        assert_eq!(coop_score(false, 5, 1), 1, "Revives count on a loss");
+        assert_eq!(coop_score(true, 2, 9), 11, "Revive bonus capped");
+    }
+
+    #[test]
+    fn trap_bonus_is_capped() {
+        assert_eq!(trap_bonus(0), 0);
+        assert_eq!(trap_bonus(2), 2);
+        assert_eq!(trap_bonus(10), MAX_TRAP_BONUS);
+    }
+
     #[test]
     fn finish_time_penalty_calculation() {
         assert!((finish_time_with_penalty(60.0, 0) - 60.0).abs() < 0.001);
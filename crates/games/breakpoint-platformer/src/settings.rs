@@ -0,0 +1,99 @@
+use breakpoint_core::game_settings::{
+    CustomSettings, FromGameConfig, SettingsError, SettingsReader,
+};
+use breakpoint_core::late_join::LateJoinPolicy;
+use breakpoint_core::round_phase::{self, MICRO_ROUNDS_SETTING};
+
+use crate::coop::CoopFinishRule;
+
+/// How a platformer round is played.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PlatformerMode {
+    /// First across the finish line wins.
+    #[default]
+    Race,
+    /// A rising hazard eliminates players from below.
+    Survival,
+    /// Shared lives, teammate revives, and a team finish.
+    Coop,
+}
+
+/// Smallest and largest `gravity_scale` a lobby can set. Below the
+/// minimum, water buoyancy would lift players out of pools.
+pub const GRAVITY_SCALE_RANGE: (f32, f32) = (0.4, 2.0);
+
+/// Platformer lobby settings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlatformerSettings {
+    /// `seed`: course generation seed.
+    pub seed: u64,
+    /// `mode`: `race`, `survival`, or `coop`.
+    pub mode: PlatformerMode,
+    /// `coop_finish`: `any` or `all`. Unset keeps `config/platformer.toml`.
+    pub coop_finish: Option<CoopFinishRule>,
+    /// `course_vote`: players vote on each round's course. The vote would
+    /// hold up every restart, so micro-rounds can't have one.
+    pub course_vote: bool,
+    /// `gravity_scale`: multiplies gravity, for low- or high-gravity rounds.
+    pub gravity_scale: f32,
+    /// `late_join`: `spawn` (the default) starts late joiners at the
+    /// course start; `spectate` has them watch until the next round.
+    pub late_join: LateJoinPolicy,
+}
+
+impl Default for PlatformerSettings {
+    fn default() -> Self {
+        Self {
+            seed: 42,
+            mode: PlatformerMode::default(),
+            coop_finish: None,
+            course_vote: false,
+            gravity_scale: 1.0,
+            late_join: LateJoinPolicy::SpawnImmediately,
+        }
+    }
+}
+
+impl FromGameConfig for PlatformerSettings {
+    fn from_custom(custom: &CustomSettings) -> Result<Self, SettingsError> {
+        let reader = SettingsReader::new(custom);
+        let defaults = Self::default();
+        let course_vote = reader.bool("course_vote")?.unwrap_or(defaults.course_vote);
+        if course_vote && round_phase::micro_round_secs(custom)?.is_some() {
+            return Err(SettingsError::new(
+                MICRO_ROUNDS_SETTING,
+                "micro-rounds can't be combined with course votes",
+            ));
+        }
+        Ok(Self {
+            seed: reader.u64("seed")?.unwrap_or(defaults.seed),
+            mode: reader
+                .choice(
+                    "mode",
+                    &[
+                        ("race", PlatformerMode::Race),
+                        ("survival", PlatformerMode::Survival),
+                        ("coop", PlatformerMode::Coop),
+                    ],
+                )?
+                .unwrap_or(defaults.mode),
+            coop_finish: reader.choice(
+                "coop_finish",
+                &[("any", CoopFinishRule::Any), ("all", CoopFinishRule::All)],
+            )?,
+            course_vote,
+            gravity_scale: reader
+                .f32_in(
+                    "gravity_scale",
+                    GRAVITY_SCALE_RANGE.0,
+                    GRAVITY_SCALE_RANGE.1,
+                )?
+                .unwrap_or(defaults.gravity_scale),
+            late_join: LateJoinPolicy::from_custom(
+                custom,
+                defaults.late_join,
+                &[LateJoinPolicy::Spectate, LateJoinPolicy::SpawnImmediately],
+            )?,
+        })
+    }
+}
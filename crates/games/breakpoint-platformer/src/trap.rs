@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+
+use breakpoint_core::game_trait::PlayerId;
+
+use crate::physics::PlatformerPlayerState;
+
+/// Banana traps: dropped behind a racer holding the Banana power-up, a
+/// trap trips the next other racer to touch it, who stumbles without
+/// control for a moment. Trips are credited to the trap's owner.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrapConfig {
+    /// Seconds a dropped trap stays on the course.
+    pub duration_secs: f32,
+    /// Seconds a tripped racer has no control.
+    pub stumble_secs: f32,
+    /// Distance behind the owner the trap lands.
+    pub drop_offset: f32,
+    /// Distance from a racer's center that springs the trap.
+    pub trigger_radius: f32,
+    /// Dropped traps per player; dropping another removes the oldest.
+    pub max_per_player: usize,
+}
+
+impl Default for TrapConfig {
+    fn default() -> Self {
+        Self {
+            duration_secs: 20.0,
+            stumble_secs: 1.0,
+            drop_offset: 1.0,
+            trigger_radius: 0.7,
+            max_per_player: 2,
+        }
+    }
+}
+
+/// A dropped trap, synced to clients for rendering.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Trap {
+    pub owner: PlayerId,
+    pub x: f32,
+    pub y: f32,
+    /// Seconds until the trap disappears.
+    pub remaining: f32,
+}
+
+impl Trap {
+    /// Drop a trap behind `player`, opposite the way they face.
+    pub fn behind(owner: PlayerId, player: &PlatformerPlayerState, config: &TrapConfig) -> Self {
+        let behind = if player.facing_right { -1.0 } else { 1.0 };
+        Self {
+            owner,
+            x: player.x + behind * config.drop_offset,
+            y: player.y,
+            remaining: config.duration_secs,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.remaining <= 0.0
+    }
+
+    pub fn tick(&mut self, dt: f32) {
+        self.remaining -= dt;
+    }
+
+    /// Whether `player` (`pid`) springs this trap. Owners never trip on
+    /// their own traps, and racers who are out of play, already
+    /// stumbling, or invincible pass over them.
+    pub fn trips(
+        &self,
+        pid: PlayerId,
+        player: &PlatformerPlayerState,
+        invincible: bool,
+        config: &TrapConfig,
+    ) -> bool {
+        if pid == self.owner
+            || invincible
+            || player.stumble_timer > 0.0
+            || player.death_respawn_timer > 0.0
+            || player.finished
+            || player.eliminated
+            || player.downed
+        {
+            return false;
+        }
+        let dx = player.x - self.x;
+        let dy = player.y - self.y;
+        dx * dx + dy * dy < config.trigger_radius * config.trigger_radius
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trap_lands_behind_and_skips_its_owner() {
+        let config = TrapConfig::default();
+        let mut owner = PlatformerPlayerState::new(10.0, 2.0);
+        owner.facing_right = false;
+        let trap = Trap::behind(1, &owner, &config);
+        assert!(trap.x > owner.x, "Facing left drops the trap to the right");
+
+        let mut racer = PlatformerPlayerState::new(trap.x, trap.y);
+        assert!(!trap.trips(1, &racer, false, &config), "Owner is immune");
+        assert!(trap.trips(2, &racer, false, &config));
+        assert!(!trap.trips(2, &racer, true, &config));
+        racer.stumble_timer = 0.5;
+        assert!(!trap.trips(2, &racer, false, &config));
+        racer.stumble_timer = 0.0;
+        racer.x += 2.0;
+        assert!(!trap.trips(2, &racer, false, &config));
+    }
+}
@@ -33,19 +33,7 @@ pub fn create_arena(width: f32, depth: f32, player_count: usize) -> Arena {
         let z = cz + radius * angle.sin();
 
         // Face inward (toward center)
-        let dx = cx - x;
-        let dz = cz - z;
-        let direction = if dx.abs() > dz.abs() {
-            if dx > 0.0 {
-                super::Direction::East
-            } else {
-                super::Direction::West
-            }
-        } else if dz > 0.0 {
-            super::Direction::South
-        } else {
-            super::Direction::North
-        };
+        let direction = facing(cx - x, cz - z);
 
         spawn_points.push(SpawnPoint { x, z, direction });
     }
@@ -57,6 +45,33 @@ pub fn create_arena(width: f32, depth: f32, player_count: usize) -> Arena {
     }
 }
 
+/// Square sudden-death arena. Spawns sit on the same circle as a normal
+/// arena but face along it, so riders don't start on a head-on collision.
+pub fn create_overtime_arena(size: f32, player_count: usize) -> Arena {
+    let mut arena = create_arena(size, size, player_count);
+    let center = size / 2.0;
+    for spawn in &mut arena.spawn_points {
+        // Inward vector rotated a quarter turn
+        spawn.direction = facing(spawn.z - center, center - spawn.x);
+    }
+    arena
+}
+
+/// Cardinal direction closest to the vector `(dx, dz)`.
+fn facing(dx: f32, dz: f32) -> super::Direction {
+    if dx.abs() > dz.abs() {
+        if dx > 0.0 {
+            super::Direction::East
+        } else {
+            super::Direction::West
+        }
+    } else if dz > 0.0 {
+        super::Direction::South
+    } else {
+        super::Direction::North
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,6 +103,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn overtime_spawns_avoid_head_on_start() {
+        let arena = create_overtime_arena(100.0, 2);
+        let [a, b] = &arena.spawn_points[..] else {
+            panic!("Expected 2 spawn points");
+        };
+        assert_eq!(a.direction, crate::Direction::North);
+        assert_eq!(b.direction, crate::Direction::South);
+        assert!((a.z - b.z).abs() < 1e-3, "Riders start level");
+        assert!((a.x - b.x).abs() > 50.0, "Riders start on separate lanes");
+    }
+
     #[test]
     fn create_arena_single_player() {
         let arena = create_arena(500.0, 500.0, 1);
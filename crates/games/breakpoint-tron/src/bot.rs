@@ -1,6 +1,6 @@
+use breakpoint_core::bot::BotSkill;
 use breakpoint_core::game_trait::PlayerId;
 
-use crate::collision::point_to_segment_distance;
 use crate::config::TronConfig;
 use crate::{CycleState, Direction, TronInput, TronState, TurnDirection, WallSegment};
 
@@ -10,8 +10,18 @@ const TICK_RATE: f32 = 20.0;
 /// How many ticks of travel ahead to consider "danger zone".
 const DANGER_LOOK_AHEAD_TICKS: f32 = 5.0;
 
+/// Mixed into the bot id so fumble rolls don't line up with wander rolls.
+const FUMBLE_SALT: PlayerId = 0x5eed;
+
 /// Generate a bot input for the given player based on the current game state.
-pub fn generate_bot_input(state: &TronState, bot_id: PlayerId, config: &TronConfig) -> TronInput {
+/// With `skill.decision_noise`, the bot sometimes turns the worse way or
+/// forgets to brake when cornered.
+pub fn generate_bot_input(
+    state: &TronState,
+    bot_id: PlayerId,
+    config: &TronConfig,
+    skill: &BotSkill,
+) -> TronInput {
     let Some(cycle) = state.players.get(&bot_id) else {
         return TronInput::default();
     };
@@ -66,14 +76,15 @@ pub fn generate_bot_input(state: &TronState, bot_id: PlayerId, config: &TronConf
                 state,
             );
 
-        if left_score >= right_score {
+        let fumble = skill.fumbles(pseudo_random(bot_id ^ FUMBLE_SALT, state.round_timer));
+        if (left_score >= right_score) != fumble {
             turn = TurnDirection::Left;
         } else {
             turn = TurnDirection::Right;
         }
 
         // If all directions are bad, brake
-        if left < danger_dist && right < danger_dist {
+        if left < danger_dist && right < danger_dist && !fumble {
             brake = true;
         }
     }
@@ -154,8 +165,7 @@ fn open_distance(
                 continue;
             }
 
-            let wall_dist =
-                point_to_segment_distance(probe_x, probe_z, wall.x1, wall.z1, wall.x2, wall.z2);
+            let wall_dist = wall.distance_to(probe_x, probe_z);
             if wall_dist < config.collision_distance {
                 return dist;
             }
@@ -226,6 +236,12 @@ mod tests {
             arena_depth: 500.0,
             time_since_last_death: 0.0,
             winner_id: None,
+            wall_density: None,
+            pause: Default::default(),
+            draw: Vec::new(),
+            overtime: false,
+            danger_map: None,
+            spectator: None,
         }
     }
 
@@ -233,7 +249,7 @@ mod tests {
     fn bot_returns_default_for_missing_player() {
         let state = make_test_state();
         let config = TronConfig::default();
-        let input = generate_bot_input(&state, 999, &config);
+        let input = generate_bot_input(&state, 999, &config, &BotSkill::HARD);
         assert_eq!(input.turn, TurnDirection::None);
         assert!(!input.brake);
     }
@@ -259,7 +275,7 @@ mod tests {
             },
         );
         let config = TronConfig::default();
-        let input = generate_bot_input(&state, 1, &config);
+        let input = generate_bot_input(&state, 1, &config, &BotSkill::HARD);
         assert_eq!(input.turn, TurnDirection::None);
     }
 
@@ -285,12 +301,20 @@ mod tests {
         );
         state.alive_count = 1;
         let config = TronConfig::default();
-        let input = generate_bot_input(&state, 1, &config);
+        let input = generate_bot_input(&state, 1, &config, &BotSkill::HARD);
         assert_ne!(
             input.turn,
             TurnDirection::None,
             "Bot should turn to avoid wall"
         );
+
+        let clumsy = BotSkill {
+            decision_noise: 1.0,
+            ..BotSkill::HARD
+        };
+        let fumbled = generate_bot_input(&state, 1, &config, &clumsy);
+        assert_ne!(fumbled.turn, TurnDirection::None, "Still reacts");
+        assert_ne!(fumbled.turn, input.turn, "But picks the worse side");
     }
 
     #[test]
@@ -310,11 +334,11 @@ mod tests {
             let state_bytes = game.serialize_state();
             let state: TronState = rmp_serde::from_slice(&state_bytes).unwrap();
 
-            let bot_input = generate_bot_input(&state, 1, &config);
+            let bot_input = generate_bot_input(&state, 1, &config, &BotSkill::HARD);
             let input_bytes = rmp_serde::to_vec(&bot_input).unwrap();
             game.apply_input(1, &input_bytes);
 
-            let bot_input2 = generate_bot_input(&state, 2, &config);
+            let bot_input2 = generate_bot_input(&state, 2, &config, &BotSkill::HARD);
             let input_bytes2 = rmp_serde::to_vec(&bot_input2).unwrap();
             game.apply_input(2, &input_bytes2);
 
@@ -1,4 +1,5 @@
 use breakpoint_core::game_trait::PlayerId;
+use breakpoint_core::math::Vec2;
 
 use super::{CycleState, Direction, WallSegment};
 use crate::config::TronConfig;
@@ -41,15 +42,13 @@ pub fn check_wall_collision(
         // Skip own segments whose endpoint is at the cycle's position (turn corners).
         // At low speeds the cycle may still be within collision distance of the
         // just-closed segment after a turn.
-        if wall.owner_id == cycle_owner_id {
-            let ex = cycle.x - wall.x2;
-            let ez = cycle.z - wall.z2;
-            if (ex * ex + ez * ez).sqrt() < col_dist * 3.0 {
-                continue;
-            }
+        if wall.owner_id == cycle_owner_id
+            && Vec2::new(cycle.x, cycle.z).distance(wall.end()) < col_dist * 3.0
+        {
+            continue;
         }
 
-        let dist = point_to_segment_distance(cycle.x, cycle.z, wall.x1, wall.z1, wall.x2, wall.z2);
+        let dist = wall.distance_to(cycle.x, cycle.z);
 
         if dist < col_dist {
             let is_suicide = wall.owner_id == cycle_owner_id;
@@ -73,31 +72,6 @@ pub fn check_wall_collision(
     }
 }
 
-/// Distance from point (px, pz) to line segment (x1, z1)-(x2, z2).
-pub fn point_to_segment_distance(px: f32, pz: f32, x1: f32, z1: f32, x2: f32, z2: f32) -> f32 {
-    let dx = x2 - x1;
-    let dz = z2 - z1;
-    let len_sq = dx * dx + dz * dz;
-
-    if len_sq < 1e-8 {
-        // Degenerate segment (point)
-        let ddx = px - x1;
-        let ddz = pz - z1;
-        return (ddx * ddx + ddz * ddz).sqrt();
-    }
-
-    // Project point onto segment, clamped to [0, 1]
-    let t = ((px - x1) * dx + (pz - z1) * dz) / len_sq;
-    let t = t.clamp(0.0, 1.0);
-
-    let nearest_x = x1 + t * dx;
-    let nearest_z = z1 + t * dz;
-
-    let ddx = px - nearest_x;
-    let ddz = pz - nearest_z;
-    (ddx * ddx + ddz * ddz).sqrt()
-}
-
 /// Find the minimum distance from a cycle to any parallel wall segment within
 /// the grind threshold. Returns the minimum distance, or None if no wall is near.
 /// Skips the querying cycle's own active segment to avoid self-grinding.
@@ -146,7 +120,7 @@ pub fn nearest_wall_distance(
             continue;
         }
 
-        let dist = point_to_segment_distance(cycle.x, cycle.z, wall.x1, wall.z1, wall.x2, wall.z2);
+        let dist = wall.distance_to(cycle.x, cycle.z);
         if dist < threshold && dist < min_dist {
             min_dist = dist;
         }
@@ -163,26 +137,6 @@ pub fn nearest_wall_distance(
 mod tests {
     use super::*;
 
-    #[test]
-    fn point_to_segment_horizontal() {
-        // Horizontal segment from (0,0) to (10,0), point at (5, 3)
-        let d = point_to_segment_distance(5.0, 3.0, 0.0, 0.0, 10.0, 0.0);
-        assert!((d - 3.0).abs() < 0.01);
-    }
-
-    #[test]
-    fn point_to_segment_endpoint() {
-        // Point nearest to endpoint
-        let d = point_to_segment_distance(12.0, 0.0, 0.0, 0.0, 10.0, 0.0);
-        assert!((d - 2.0).abs() < 0.01);
-    }
-
-    #[test]
-    fn point_to_segment_degenerate() {
-        let d = point_to_segment_distance(3.0, 4.0, 0.0, 0.0, 0.0, 0.0);
-        assert!((d - 5.0).abs() < 0.01);
-    }
-
     #[test]
     fn arena_boundary_detection() {
         let cycle = CycleState {
@@ -201,4 +155,36 @@ mod tests {
         };
         assert!(check_arena_boundary(&cycle, 500.0, 500.0));
     }
+
+    #[test]
+    fn wall_check_fits_its_budget_with_10k_walls() {
+        use std::time::Duration;
+
+        use breakpoint_core::test_helpers::assert_within_budget;
+
+        let walls = crate::tests::wall_grid(10_000);
+        let config = TronConfig::default();
+        let cycle = CycleState {
+            x: 250.0,
+            z: 20.0,
+            direction: Direction::North,
+            speed: 50.0,
+            rubber: 0.5,
+            brake_fuel: 3.0,
+            alive: true,
+            trail_start_index: 0,
+            turn_cooldown: 0.0,
+            kills: 0,
+            died: false,
+            is_suicide: false,
+        };
+        assert_within_budget(
+            "check_wall_collision, 10k walls",
+            Duration::from_micros(100),
+            || {
+                let result = check_wall_collision(&cycle, 1, std::hint::black_box(&walls), &config);
+                assert!(result.alive);
+            },
+        );
+    }
 }
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::grid::GridConfig;
+
 /// Data-driven configuration for the Tron game.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -46,6 +48,31 @@ pub struct TronConfig {
     pub speed_decay_rate: f32,
     /// Collision distance for cycle-to-wall checks.
     pub collision_distance: f32,
+    /// Classic grid movement variant.
+    pub grid: GridConfig,
+    /// Sudden-death overtime for drawn rounds.
+    pub overtime: OvertimeConfig,
+}
+
+/// Sudden-death overtime. When the last cycles crash on the same tick, they
+/// respawn in a small arena and play on instead of drawing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OvertimeConfig {
+    /// Off by default; rooms can also opt in with the `overtime = true`
+    /// game setting.
+    pub enabled: bool,
+    /// Side length of the square overtime arena.
+    pub arena_size: f32,
+}
+
+impl Default for OvertimeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            arena_size: 100.0,
+        }
+    }
 }
 
 impl Default for TronConfig {
@@ -72,6 +99,8 @@ impl Default for TronConfig {
             win_zone_expand_rate: 5.0,
             speed_decay_rate: 10.0,
             collision_distance: 0.5,
+            grid: GridConfig::default(),
+            overtime: OvertimeConfig::default(),
         }
     }
 }
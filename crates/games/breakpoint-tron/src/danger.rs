@@ -0,0 +1,192 @@
+//! Spectator danger map: the arena cells each living cycle could reach
+//! within a short lookahead, so casters can see where riders are about to
+//! meet. Only the spectator view carries it (see `spectator.rs`); it would
+//! hand players their opponents' options.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use breakpoint_core::game_trait::PlayerId;
+
+use crate::{CycleState, Direction};
+
+/// Cells along each axis of the danger map; coarser than the density grid
+/// since it is rebuilt every tick.
+pub const DANGER_GRID_SIZE: u16 = 24;
+/// Ticks ahead the map looks (one second at 20 Hz).
+pub const DANGER_LOOKAHEAD_TICKS: u32 = 20;
+/// Riders tracked per map; a cell's bitmask has one bit each.
+pub const MAX_DANGER_RIDERS: usize = 8;
+
+/// Which living riders could reach each cell of the arena within
+/// [`DANGER_LOOKAHEAD_TICKS`].
+///
+/// Reach is the half-diamond a cycle can cover by riding ahead and turning
+/// once, ignoring walls: anything within `speed * lookahead` of travel that
+/// is not behind it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DangerMap {
+    pub cols: u16,
+    pub rows: u16,
+    pub cell_width: f32,
+    pub cell_depth: f32,
+    /// Rider for each bit of a cell's mask, lowest bit first.
+    pub riders: Vec<PlayerId>,
+    /// Bitmask of the riders that can reach each cell. Stored row-major
+    /// (row * cols + col), row along Z.
+    pub cells: Vec<u8>,
+}
+
+impl DangerMap {
+    /// Build the map for the living cycles in `players`, looking
+    /// `lookahead_secs` ahead. Riders are assigned bits in id order; past
+    /// [`MAX_DANGER_RIDERS`] they are left out.
+    pub fn compute(
+        players: &HashMap<PlayerId, CycleState>,
+        arena_width: f32,
+        arena_depth: f32,
+        lookahead_secs: f32,
+    ) -> Self {
+        let cols = DANGER_GRID_SIZE;
+        let rows = DANGER_GRID_SIZE;
+        let mut map = Self {
+            cols,
+            rows,
+            cell_width: arena_width / cols as f32,
+            cell_depth: arena_depth / rows as f32,
+            riders: Vec::new(),
+            cells: vec![0; cols as usize * rows as usize],
+        };
+        let mut living: Vec<(PlayerId, &CycleState)> = players
+            .iter()
+            .filter(|(_, c)| c.alive)
+            .map(|(&pid, c)| (pid, c))
+            .collect();
+        living.sort_unstable_by_key(|&(pid, _)| pid);
+        living.truncate(MAX_DANGER_RIDERS);
+
+        for (bit, (pid, cycle)) in living.into_iter().enumerate() {
+            map.riders.push(pid);
+            map.mark_reach(cycle, cycle.speed * lookahead_secs, 1 << bit);
+        }
+        map
+    }
+
+    /// Set `mask` on every cell whose center `cycle` can reach within
+    /// `reach` units of travel.
+    fn mark_reach(&mut self, cycle: &CycleState, reach: f32, mask: u8) {
+        if self.cell_width <= 0.0 || self.cell_depth <= 0.0 {
+            return;
+        }
+        let (fx, fz) = match cycle.direction {
+            Direction::North => (0.0, -1.0),
+            Direction::South => (0.0, 1.0),
+            Direction::East => (1.0, 0.0),
+            Direction::West => (-1.0, 0.0),
+        };
+        // Half a cell of slack so the rider's own cell and the cells it
+        // clips count even though their centers are off the line
+        let slack = 0.5 * self.cell_width.max(self.cell_depth);
+        let reach = reach + slack;
+        let cell_range = |pos: f32, size: f32, count: u16| {
+            let lo = ((pos - reach) / size).floor().max(0.0) as u16;
+            let hi = (((pos + reach) / size).floor().max(0.0) as u16).min(count - 1);
+            lo..=hi
+        };
+        for row in cell_range(cycle.z, self.cell_depth, self.rows) {
+            let dz = (row as f32 + 0.5) * self.cell_depth - cycle.z;
+            for col in cell_range(cycle.x, self.cell_width, self.cols) {
+                let dx = (col as f32 + 0.5) * self.cell_width - cycle.x;
+                let forward = dx * fx + dz * fz;
+                let lateral = (dx * fz - dz * fx).abs();
+                if forward >= -slack && forward.max(0.0) + lateral <= reach {
+                    self.cells[row as usize * self.cols as usize + col as usize] |= mask;
+                }
+            }
+        }
+    }
+
+    /// Riders that can reach the cell containing the given position.
+    pub fn riders_at(&self, x: f32, z: f32) -> Vec<PlayerId> {
+        let mask = self.mask_at(x, z);
+        self.riders
+            .iter()
+            .enumerate()
+            .filter(|&(bit, _)| mask & (1 << bit) != 0)
+            .map(|(_, &pid)| pid)
+            .collect()
+    }
+
+    fn mask_at(&self, x: f32, z: f32) -> u8 {
+        if self.cell_width <= 0.0 || self.cell_depth <= 0.0 || x < 0.0 || z < 0.0 {
+            return 0;
+        }
+        let col = (x / self.cell_width) as u16;
+        let row = (z / self.cell_depth) as u16;
+        if col >= self.cols || row >= self.rows {
+            return 0;
+        }
+        self.cells[row as usize * self.cols as usize + col as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cycle(x: f32, z: f32, direction: Direction) -> CycleState {
+        CycleState {
+            x,
+            z,
+            direction,
+            speed: 50.0,
+            rubber: 0.0,
+            brake_fuel: 0.0,
+            alive: true,
+            trail_start_index: 0,
+            turn_cooldown: 0.0,
+            kills: 0,
+            died: false,
+            is_suicide: false,
+        }
+    }
+
+    #[test]
+    fn reach_is_ahead_and_to_the_sides_but_not_behind() {
+        let players = HashMap::from([(1, cycle(240.0, 240.0, Direction::East))]);
+        // 500 / 24 ≈ 20.8-unit cells, 50 units of reach
+        let map = DangerMap::compute(&players, 500.0, 500.0, 1.0);
+        assert_eq!(map.riders, [1]);
+        assert_eq!(map.riders_at(240.0, 240.0), [1]);
+        assert_eq!(map.riders_at(285.0, 240.0), [1]);
+        assert_eq!(map.riders_at(240.0, 215.0), [1]);
+        assert!(map.riders_at(320.0, 240.0).is_empty());
+        assert!(map.riders_at(190.0, 240.0).is_empty());
+        assert!(map.riders_at(290.0, 190.0).is_empty());
+    }
+
+    #[test]
+    fn overlapping_reach_marks_both_riders() {
+        let mut dead = cycle(300.0, 250.0, Direction::West);
+        dead.alive = false;
+        let players = HashMap::from([
+            (4, cycle(200.0, 250.0, Direction::East)),
+            (2, cycle(300.0, 250.0, Direction::West)),
+            (9, dead),
+        ]);
+        let map = DangerMap::compute(&players, 500.0, 500.0, 2.0);
+        assert_eq!(map.riders, [2, 4]);
+        assert_eq!(map.riders_at(250.0, 250.0), [2, 4]);
+        assert_eq!(map.riders_at(200.0, 250.0), [4]);
+        assert!(map.cells.iter().all(|&mask| mask & !0b11 == 0));
+    }
+
+    #[test]
+    fn riders_near_the_edge_stay_in_bounds() {
+        let players = HashMap::from([(1, cycle(2.0, 498.0, Direction::South))]);
+        let map = DangerMap::compute(&players, 500.0, 500.0, 5.0);
+        assert_eq!(map.riders_at(2.0, 498.0), [1]);
+        assert_eq!(map.cells.len(), 24 * 24);
+    }
+}
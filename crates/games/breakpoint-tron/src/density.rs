@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+
+/// Cells along each axis of the wall density grid.
+pub const DENSITY_GRID_SIZE: u16 = 32;
+/// Publish the density grid to clients once every this many ticks (~2 Hz at 20 Hz).
+pub const DENSITY_PUBLISH_INTERVAL: u32 = 10;
+/// Maximum distance between wall samples when stamping a movement.
+const SAMPLE_STEP: f32 = 1.0;
+
+/// Low-resolution occupancy grid of wall length per arena cell.
+///
+/// Updated incrementally as cycles lay down trail, so the cost per tick is
+/// proportional to distance travelled rather than total wall count. Clients
+/// use it for the minimap; bots can query it for open-space decisions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WallDensityGrid {
+    pub cols: u16,
+    pub rows: u16,
+    pub cell_width: f32,
+    pub cell_depth: f32,
+    /// Wall length inside each cell in arena units, saturating at 255.
+    /// Stored row-major (row * cols + col), row along Z.
+    pub cells: Vec<u8>,
+    /// Sub-unit wall length not yet committed to `cells`, kept server-side only.
+    #[serde(skip)]
+    remainders: Vec<f32>,
+}
+
+impl Default for WallDensityGrid {
+    fn default() -> Self {
+        Self::new(0.0, 0.0)
+    }
+}
+
+impl WallDensityGrid {
+    /// Create an empty grid covering an arena of the given size.
+    pub fn new(arena_width: f32, arena_depth: f32) -> Self {
+        let cols = DENSITY_GRID_SIZE;
+        let rows = DENSITY_GRID_SIZE;
+        let len = cols as usize * rows as usize;
+        Self {
+            cols,
+            rows,
+            cell_width: arena_width / cols as f32,
+            cell_depth: arena_depth / rows as f32,
+            cells: vec![0; len],
+            remainders: vec![0.0; len],
+        }
+    }
+
+    /// Cell index for an arena position, or `None` outside the arena.
+    fn index_of(&self, x: f32, z: f32) -> Option<usize> {
+        if self.cell_width <= 0.0 || self.cell_depth <= 0.0 || x < 0.0 || z < 0.0 {
+            return None;
+        }
+        let col = (x / self.cell_width) as u16;
+        let row = (z / self.cell_depth) as u16;
+        if col >= self.cols || row >= self.rows {
+            return None;
+        }
+        Some(row as usize * self.cols as usize + col as usize)
+    }
+
+    /// Wall length recorded in the cell containing the given position.
+    pub fn density_at(&self, x: f32, z: f32) -> u8 {
+        self.index_of(x, z).map_or(0, |i| self.cells[i])
+    }
+
+    /// Record a straight run of wall from `(x1, z1)` to `(x2, z2)`.
+    pub fn stamp_segment(&mut self, x1: f32, z1: f32, x2: f32, z2: f32) {
+        let dx = x2 - x1;
+        let dz = z2 - z1;
+        let length = (dx * dx + dz * dz).sqrt();
+        if !length.is_finite() || length <= 0.0 {
+            return;
+        }
+        if self.remainders.len() != self.cells.len() {
+            self.remainders = vec![0.0; self.cells.len()];
+        }
+
+        let steps = (length / SAMPLE_STEP).ceil().max(1.0) as u32;
+        let step_len = length / steps as f32;
+        for i in 0..steps {
+            let t = (i as f32 + 0.5) / steps as f32;
+            let Some(idx) = self.index_of(x1 + dx * t, z1 + dz * t) else {
+                continue;
+            };
+            let total = self.remainders[idx] + step_len;
+            let whole = total.floor();
+            self.remainders[idx] = total - whole;
+            self.cells[idx] = self.cells[idx].saturating_add(whole.min(255.0) as u8);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_grid_is_empty() {
+        let grid = WallDensityGrid::new(500.0, 500.0);
+        assert_eq!(grid.cells.len(), 32 * 32);
+        assert!(grid.cells.iter().all(|&c| c == 0));
+    }
+
+    #[test]
+    fn stamp_accumulates_length_in_cell() {
+        let mut grid = WallDensityGrid::new(320.0, 320.0);
+        // Cells are 10x10; a 6-unit run stays within cell (0, 0)
+        grid.stamp_segment(1.0, 5.0, 7.0, 5.0);
+        assert_eq!(grid.density_at(5.0, 5.0), 6);
+        grid.stamp_segment(7.0, 5.0, 9.0, 5.0);
+        assert_eq!(grid.density_at(5.0, 5.0), 8);
+    }
+
+    #[test]
+    fn stamp_spans_multiple_cells() {
+        let mut grid = WallDensityGrid::new(320.0, 320.0);
+        grid.stamp_segment(0.0, 5.0, 30.0, 5.0);
+        assert_eq!(grid.density_at(5.0, 5.0), 10);
+        assert_eq!(grid.density_at(15.0, 5.0), 10);
+        assert_eq!(grid.density_at(25.0, 5.0), 10);
+        assert_eq!(grid.density_at(35.0, 5.0), 0);
+    }
+
+    #[test]
+    fn fractional_moves_carry_over() {
+        let mut grid = WallDensityGrid::new(320.0, 320.0);
+        for i in 0..4 {
+            let x = 1.0 + i as f32 * 0.5;
+            grid.stamp_segment(x, 5.0, x + 0.5, 5.0);
+        }
+        assert_eq!(grid.density_at(2.0, 5.0), 2);
+    }
+
+    #[test]
+    fn out_of_bounds_is_ignored() {
+        let mut grid = WallDensityGrid::new(320.0, 320.0);
+        grid.stamp_segment(-20.0, -20.0, -10.0, -10.0);
+        assert!(grid.cells.iter().all(|&c| c == 0));
+        assert_eq!(grid.density_at(1000.0, 1000.0), 0);
+    }
+}
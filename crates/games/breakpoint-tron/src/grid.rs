@@ -0,0 +1,247 @@
+use breakpoint_core::game_trait::PlayerId;
+use breakpoint_core::math::{Aabb, Vec2};
+use serde::{Deserialize, Serialize};
+
+use super::{CycleState, Direction, TurnDirection, WallSegment};
+use crate::collision::CollisionResult;
+use crate::config::TronConfig;
+use crate::physics::apply_turn;
+
+/// Tolerance for treating a coordinate as lying on a grid line.
+const GRID_EPSILON: f32 = 1e-3;
+
+/// "Classic grid" movement variant. Cycles ride integer grid lines and
+/// queued turns take effect at the next cell boundary, so every wall lies on
+/// a grid line and collisions reduce to exact segment overlap tests.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GridConfig {
+    /// Off by default; rooms can also opt in with the `movement = "grid"`
+    /// game setting.
+    pub enabled: bool,
+    /// Grid spacing in world units.
+    pub cell_size: f32,
+}
+
+impl Default for GridConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cell_size: 5.0,
+        }
+    }
+}
+
+/// Snap a coordinate to the nearest grid line.
+pub fn snap(value: f32, cell_size: f32) -> f32 {
+    (value / cell_size).round() * cell_size
+}
+
+/// Distance from `pos` to the next grid line ahead along the travel axis.
+/// Zero when already on a line.
+fn distance_to_next_line(pos: f32, forward: bool, cell_size: f32) -> f32 {
+    let cells = pos / cell_size;
+    let nearest = cells.round();
+    if (cells - nearest).abs() * cell_size < GRID_EPSILON {
+        return 0.0;
+    }
+    if forward {
+        cells.ceil() * cell_size - pos
+    } else {
+        pos - cells.floor() * cell_size
+    }
+}
+
+fn step(cycle: &mut CycleState, distance: f32) {
+    match cycle.direction {
+        Direction::North => cycle.z -= distance,
+        Direction::South => cycle.z += distance,
+        Direction::East => cycle.x += distance,
+        Direction::West => cycle.x -= distance,
+    }
+}
+
+/// Move a cycle `distance` units along the grid, applying `queued` at the
+/// first cell boundary reached this tick. Returns the corner point if the
+/// cycle turned; the turn stays queued if no boundary was reached.
+pub fn advance(
+    cycle: &mut CycleState,
+    queued: &mut TurnDirection,
+    distance: f32,
+    config: &TronConfig,
+) -> Option<(f32, f32)> {
+    let cell_size = config.grid.cell_size;
+    let mut remaining = distance;
+    let mut corner = None;
+
+    if *queued != TurnDirection::None {
+        let (pos, forward) = match cycle.direction {
+            Direction::North => (cycle.z, false),
+            Direction::South => (cycle.z, true),
+            Direction::East => (cycle.x, true),
+            Direction::West => (cycle.x, false),
+        };
+        let to_line = distance_to_next_line(pos, forward, cell_size);
+        if to_line <= remaining {
+            step(cycle, to_line);
+            remaining -= to_line;
+            cycle.x = snap(cycle.x, cell_size);
+            cycle.z = snap(cycle.z, cell_size);
+
+            let old_direction = cycle.direction;
+            apply_turn(cycle, *queued, config);
+            if cycle.direction != old_direction {
+                corner = Some((cycle.x, cycle.z));
+                *queued = TurnDirection::None;
+            }
+        }
+    }
+
+    step(cycle, remaining);
+    corner
+}
+
+/// Exact collision for grid movement: checks the path swept this tick
+/// (`path`, one or two legs when the cycle turned) against every wall. The
+/// start of the path is excluded since the cycle was already there.
+pub fn check_swept_collision(
+    cycle_owner_id: PlayerId,
+    path: &[(f32, f32)],
+    walls: &[WallSegment],
+) -> CollisionResult {
+    // The previous leg of our own trail meets the active one at its start
+    let active_start = walls
+        .iter()
+        .find(|w| w.owner_id == cycle_owner_id && w.is_active)
+        .map(|w| (w.x1, w.z1));
+
+    for leg in path.windows(2) {
+        let (sx, sz) = leg[0];
+        let (ex, ez) = leg[1];
+        let len = ((ex - sx).powi(2) + (ez - sz).powi(2)).sqrt();
+        if len <= GRID_EPSILON {
+            continue;
+        }
+        // Nudge the start forward so touching the previous position is not a hit
+        let t = (GRID_EPSILON * 2.0) / len;
+        // Axis-aligned segments are their own bounding boxes
+        let swept = Aabb::from_corners(
+            Vec2::new(sx + (ex - sx) * t, sz + (ez - sz) * t),
+            Vec2::new(ex, ez),
+        );
+
+        for wall in walls {
+            if wall.owner_id == cycle_owner_id
+                && (wall.is_active
+                    || active_start.is_some_and(|(ax, az)| {
+                        (wall.x2 - ax).abs() < GRID_EPSILON && (wall.z2 - az).abs() < GRID_EPSILON
+                    }))
+            {
+                continue;
+            }
+            if swept.touches(&Aabb::from_corners(wall.start(), wall.end()), GRID_EPSILON) {
+                let is_suicide = wall.owner_id == cycle_owner_id;
+                return CollisionResult {
+                    alive: false,
+                    killer_id: (!is_suicide).then_some(wall.owner_id),
+                    is_suicide,
+                };
+            }
+        }
+    }
+
+    CollisionResult {
+        alive: true,
+        killer_id: None,
+        is_suicide: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid_config() -> TronConfig {
+        TronConfig {
+            grid: GridConfig {
+                enabled: true,
+                cell_size: 5.0,
+            },
+            ..TronConfig::default()
+        }
+    }
+
+    fn cycle_at(x: f32, z: f32, direction: Direction) -> CycleState {
+        CycleState {
+            x,
+            z,
+            direction,
+            speed: 50.0,
+            rubber: 0.5,
+            brake_fuel: 3.0,
+            alive: true,
+            trail_start_index: 0,
+            turn_cooldown: 0.0,
+            kills: 0,
+            died: false,
+            is_suicide: false,
+        }
+    }
+
+    fn wall(x1: f32, z1: f32, x2: f32, z2: f32, owner_id: PlayerId) -> WallSegment {
+        WallSegment {
+            x1,
+            z1,
+            x2,
+            z2,
+            owner_id,
+            is_active: false,
+        }
+    }
+
+    #[test]
+    fn turn_waits_for_cell_boundary() {
+        let config = grid_config();
+        let mut cycle = cycle_at(101.0, 50.0, Direction::East);
+        let mut queued = TurnDirection::Left;
+
+        // 2 units short of the boundary at x = 105
+        assert_eq!(advance(&mut cycle, &mut queued, 2.0, &config), None);
+        assert_eq!(cycle.direction, Direction::East);
+        assert_eq!(queued, TurnDirection::Left, "Turn stays queued");
+
+        let corner = advance(&mut cycle, &mut queued, 2.5, &config);
+        assert_eq!(corner, Some((105.0, 50.0)));
+        assert_eq!(cycle.direction, Direction::North);
+        assert_eq!(cycle.x, 105.0, "Cycle rides the grid line after turning");
+        assert!((cycle.z - 49.5).abs() < 1e-4);
+        assert_eq!(queued, TurnDirection::None);
+    }
+
+    #[test]
+    fn swept_collision_catches_crossing_between_ticks() {
+        // A perpendicular wall between the old and new positions
+        let walls = vec![wall(110.0, 40.0, 110.0, 60.0, 2)];
+        let result = check_swept_collision(1, &[(105.0, 50.0), (112.5, 50.0)], &walls);
+        assert!(!result.alive);
+        assert_eq!(result.killer_id, Some(2));
+
+        // An adjacent parallel lane is never a hit
+        let walls = vec![wall(100.0, 55.0, 120.0, 55.0, 2)];
+        let result = check_swept_collision(1, &[(105.0, 50.0), (112.5, 50.0)], &walls);
+        assert!(result.alive);
+    }
+
+    #[test]
+    fn swept_collision_skips_own_corner() {
+        let walls = vec![
+            wall(90.0, 50.0, 105.0, 50.0, 1),
+            WallSegment {
+                is_active: true,
+                ..wall(105.0, 50.0, 105.0, 47.5, 1)
+            },
+        ];
+        let path = [(103.0, 50.0), (105.0, 50.0), (105.0, 47.5)];
+        assert!(check_swept_collision(1, &path, &walls).alive);
+    }
+}
@@ -2,24 +2,56 @@ pub mod arena;
 pub mod bot;
 pub mod collision;
 pub mod config;
+pub mod danger;
+pub mod density;
+pub mod grid;
 pub mod physics;
 pub mod scoring;
+pub mod settings;
+pub mod spectator;
 pub mod win_zone;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
-use breakpoint_core::breakpoint_game_boilerplate;
+use breakpoint_core::debug_command::{self, DebugCommand, DebugResult};
+use breakpoint_core::game_settings::FromGameConfig;
 use breakpoint_core::game_trait::{
-    BreakpointGame, GameConfig, GameEvent, GameMetadata, PlayerId, PlayerInputs, PlayerScore,
+    BreakpointGame, GameConfig, GameEvent, GameMemory, GameMetadata, PlayerId, PlayerInputs,
+    PlayerScore,
 };
+use breakpoint_core::i18n::TextKey;
+use breakpoint_core::late_join::LateJoinPolicy;
+use breakpoint_core::math::{Vec2, point_segment_distance};
+use breakpoint_core::pause::{PauseReason, PauseState};
 use breakpoint_core::player::Player;
+use breakpoint_core::spectator::SpectatorCues;
+use breakpoint_core::tutorial::TutorialObjective;
+use breakpoint_core::victory::VictorySummary;
 
 use config::TronConfig;
+use danger::{DANGER_LOOKAHEAD_TICKS, DangerMap};
+use density::{DENSITY_PUBLISH_INTERVAL, WallDensityGrid};
+use settings::TronSettings;
 use win_zone::WinZone;
 
+/// Seconds a rider must survive for the first tutorial objective.
+const TUTORIAL_SURVIVE_SECS: f32 = 20.0;
+
+/// What the tron tutorial asks a new player to do.
+pub const TUTORIAL_OBJECTIVES: &[TutorialObjective] = &[
+    TutorialObjective {
+        id: "survive",
+        text: TextKey::TutorialTronSurvive,
+    },
+    TutorialObjective {
+        id: "win",
+        text: TextKey::TutorialTronWin,
+    },
+];
+
 /// Cardinal direction on the 2D grid.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Direction {
@@ -49,6 +81,21 @@ pub struct WallSegment {
     pub is_active: bool,
 }
 
+impl WallSegment {
+    pub fn start(&self) -> Vec2 {
+        Vec2::new(self.x1, self.z1)
+    }
+
+    pub fn end(&self) -> Vec2 {
+        Vec2::new(self.x2, self.z2)
+    }
+
+    /// Distance from the point (`x`, `z`) to this wall.
+    pub fn distance_to(&self, x: f32, z: f32) -> f32 {
+        point_segment_distance(Vec2::new(x, z), self.start(), self.end())
+    }
+}
+
 /// State of a single cycle.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CycleState {
@@ -99,6 +146,27 @@ pub struct TronState {
     pub arena_depth: f32,
     pub time_since_last_death: f32,
     pub winner_id: Option<PlayerId>,
+    /// Wall density snapshot for the minimap. Only present on publish ticks
+    /// (every [`DENSITY_PUBLISH_INTERVAL`]); clients keep the last one seen.
+    #[serde(default)]
+    pub wall_density: Option<WallDensityGrid>,
+    /// Active pause reasons, so clients can show why play is held.
+    #[serde(default)]
+    pub pause: PauseState,
+    /// Riders who crashed together on the final tick, leaving nobody
+    /// standing. Empty unless the round ended in a draw.
+    #[serde(default)]
+    pub draw: Vec<PlayerId>,
+    /// Sudden-death overtime is being played in the shrunken arena.
+    #[serde(default)]
+    pub overtime: bool,
+    /// Where each living rider could be within a second. Only in the
+    /// spectator view, with the `danger_map` room setting.
+    #[serde(default)]
+    pub danger_map: Option<DangerMap>,
+    /// Camera focus and highlights. Only in the spectator view.
+    #[serde(default)]
+    pub spectator: Option<SpectatorCues>,
 }
 
 /// The Tron Light Cycles game.
@@ -106,8 +174,37 @@ pub struct TronCycles {
     state: TronState,
     player_ids: Vec<PlayerId>,
     pending_inputs: HashMap<PlayerId, TronInput>,
-    paused: bool,
     game_config: TronConfig,
+    /// Authoritative density grid on the server, last received copy on clients.
+    wall_density: WallDensityGrid,
+    /// Tick counter for reduced-rate density publishing.
+    tick_counter: u32,
+    /// Whether this round uses the classic grid movement variant.
+    grid_movement: bool,
+    /// Grid mode turns waiting for the next cell boundary.
+    queued_turns: HashMap<PlayerId, TurnDirection>,
+    /// Whether a draw goes to sudden-death overtime this round.
+    overtime_enabled: bool,
+    /// Riders who left this round. Leaving forfeits like a crash, but kills
+    /// still count.
+    departed: BTreeMap<PlayerId, DepartedRider>,
+    /// Whether a round win is announced (off during a warmup).
+    scoring: bool,
+    /// Whether spectators get a danger map every tick.
+    danger_map_enabled: bool,
+    /// This tick's danger map, kept out of the shared state.
+    danger_map: Option<DangerMap>,
+    /// Round time limit in micro-rounds; otherwise rounds run until won.
+    micro_round_secs: Option<f32>,
+    /// What happens to riders who join mid-round.
+    late_join: LateJoinPolicy,
+}
+
+/// What a departed rider had earned this round.
+#[derive(Debug, Clone, Copy)]
+struct DepartedRider {
+    kills: u32,
+    is_suicide: bool,
 }
 
 impl TronCycles {
@@ -130,10 +227,26 @@ impl TronCycles {
                 arena_depth: config.arena_depth,
                 time_since_last_death: 0.0,
                 winner_id: None,
+                wall_density: None,
+                pause: PauseState::default(),
+                draw: Vec::new(),
+                overtime: false,
+                danger_map: None,
+                spectator: None,
             },
             player_ids: Vec::new(),
             pending_inputs: HashMap::new(),
-            paused: false,
+            wall_density: WallDensityGrid::new(config.arena_width, config.arena_depth),
+            tick_counter: 0,
+            grid_movement: config.grid.enabled,
+            queued_turns: HashMap::new(),
+            overtime_enabled: config.overtime.enabled,
+            departed: BTreeMap::new(),
+            scoring: true,
+            danger_map_enabled: false,
+            danger_map: None,
+            micro_round_secs: None,
+            late_join: LateJoinPolicy::SpawnNextRound,
             game_config: config,
         }
     }
@@ -146,6 +259,16 @@ impl TronCycles {
         &self.game_config
     }
 
+    /// Whether cycles move on the classic grid this round.
+    pub fn grid_movement(&self) -> bool {
+        self.grid_movement
+    }
+
+    /// Low-resolution wall density grid for minimaps and bot pathing.
+    pub fn wall_density(&self) -> &WallDensityGrid {
+        &self.wall_density
+    }
+
     /// Kill a cycle and record who killed it.
     fn kill_cycle(&mut self, player_id: PlayerId, killer_id: Option<PlayerId>, is_suicide: bool) {
         if let Some(cycle) = self.state.players.get_mut(&player_id) {
@@ -204,6 +327,101 @@ impl TronCycles {
     }
 }
 
+impl TronCycles {
+    /// Place a live cycle at `spawn` and start its wall. Kills already
+    /// earned this round carry over.
+    fn spawn_cycle(&mut self, player_id: PlayerId, spawn: &arena::SpawnPoint) {
+        let (x, z) = if self.grid_movement {
+            let cell_size = self.game_config.grid.cell_size;
+            (
+                grid::snap(spawn.x, cell_size),
+                grid::snap(spawn.z, cell_size),
+            )
+        } else {
+            (spawn.x, spawn.z)
+        };
+
+        let cycle = CycleState {
+            x,
+            z,
+            direction: spawn.direction,
+            speed: self.game_config.base_speed,
+            rubber: self.game_config.rubber_max,
+            brake_fuel: self.game_config.brake_fuel_max,
+            alive: true,
+            trail_start_index: self.state.wall_segments.len(),
+            turn_cooldown: 0.0,
+            kills: self.state.players.get(&player_id).map_or(0, |c| c.kills),
+            died: false,
+            is_suicide: false,
+        };
+
+        // Start the initial wall segment for this cycle
+        self.state.wall_segments.push(WallSegment {
+            x1: x,
+            z1: z,
+            x2: x,
+            z2: z,
+            owner_id: player_id,
+            is_active: true,
+        });
+
+        self.state.players.insert(player_id, cycle);
+    }
+
+    /// Restart a drawn round as sudden death: `riders` respawn alone in
+    /// the small overtime arena with every wall cleared.
+    fn start_overtime(&mut self, riders: &[PlayerId]) {
+        let arena =
+            arena::create_overtime_arena(self.game_config.overtime.arena_size, riders.len());
+        self.state.overtime = true;
+        self.state.arena_width = arena.width;
+        self.state.arena_depth = arena.depth;
+        self.state.wall_segments.clear();
+        self.state.win_zone = WinZone::default();
+        self.state.time_since_last_death = 0.0;
+        self.state.alive_count = riders.len() as u32;
+        self.wall_density = WallDensityGrid::new(arena.width, arena.depth);
+        self.pending_inputs.clear();
+        self.queued_turns.clear();
+        for (&pid, spawn) in riders.iter().zip(&arena.spawn_points) {
+            self.spawn_cycle(pid, spawn);
+        }
+    }
+
+    /// Grid mode tick for one cycle: turns are queued until the next cell
+    /// boundary. Returns the corner point if the cycle turned.
+    fn update_grid_cycle(
+        &mut self,
+        pid: PlayerId,
+        input: &TronInput,
+        dt: f32,
+    ) -> Option<(f32, f32)> {
+        let cycle = self.state.players.get_mut(&pid)?;
+        if !cycle.alive {
+            return None;
+        }
+        cycle.turn_cooldown = (cycle.turn_cooldown - dt).max(0.0);
+
+        let queued = self.queued_turns.entry(pid).or_insert(TurnDirection::None);
+        if input.turn != TurnDirection::None {
+            *queued = input.turn;
+        }
+
+        physics::update_speed(
+            cycle,
+            pid,
+            input,
+            &self.state.wall_segments,
+            self.state.arena_width,
+            self.state.arena_depth,
+            dt,
+            &self.game_config,
+        );
+        grid::advance(cycle, queued, cycle.speed * dt, &self.game_config)
+    }
+}
+
 impl Default for TronCycles {
     fn default() -> Self {
         Self::with_config(TronConfig::default())
@@ -230,7 +448,7 @@ impl BreakpointGame for TronCycles {
         self.game_config.round_count
     }
 
-    fn init(&mut self, players: &[Player], _config: &GameConfig) {
+    fn init(&mut self, players: &[Player], config: &GameConfig) {
         let active_players: Vec<&Player> = players.iter().filter(|p| !p.is_spectator).collect();
 
         let arena = arena::create_arena(
@@ -252,48 +470,49 @@ impl BreakpointGame for TronCycles {
             arena_depth: arena.depth,
             time_since_last_death: 0.0,
             winner_id: None,
+            wall_density: None,
+            pause: PauseState::default(),
+            draw: Vec::new(),
+            overtime: false,
+            danger_map: None,
+            spectator: None,
         };
         self.player_ids.clear();
         self.pending_inputs.clear();
-        self.paused = false;
+        self.wall_density = WallDensityGrid::new(arena.width, arena.depth);
+        self.tick_counter = 0;
+        self.queued_turns.clear();
+        self.departed.clear();
+
+        // Room settings override the configured movement variant and
+        // overtime; hosts validate them before starting, so anything that
+        // slips through falls back to the config
+        let settings = TronSettings::from_game_config(config).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Ignoring invalid tron settings");
+            TronSettings::default()
+        });
+        self.grid_movement = settings
+            .grid_movement
+            .unwrap_or(self.game_config.grid.enabled);
+        self.overtime_enabled = settings
+            .overtime
+            .unwrap_or(self.game_config.overtime.enabled);
+        self.scoring = config.scoring;
+        self.danger_map_enabled = settings.danger_map.unwrap_or(false);
+        self.danger_map = None;
+        self.micro_round_secs = settings.micro_round_secs;
+        self.late_join = settings.late_join;
 
         for (i, player) in active_players.iter().enumerate() {
             self.player_ids.push(player.id);
-            let spawn = &arena.spawn_points[i % arena.spawn_points.len()];
-
-            let cycle = CycleState {
-                x: spawn.x,
-                z: spawn.z,
-                direction: spawn.direction,
-                speed: self.game_config.base_speed,
-                rubber: self.game_config.rubber_max,
-                brake_fuel: self.game_config.brake_fuel_max,
-                alive: true,
-                trail_start_index: self.state.wall_segments.len(),
-                turn_cooldown: 0.0,
-                kills: 0,
-                died: false,
-                is_suicide: false,
-            };
-
-            // Start the initial wall segment for this cycle
-            self.state.wall_segments.push(WallSegment {
-                x1: spawn.x,
-                z1: spawn.z,
-                x2: spawn.x,
-                z2: spawn.z,
-                owner_id: player.id,
-                is_active: true,
-            });
-
-            self.state.players.insert(player.id, cycle);
+            self.spawn_cycle(player.id, &arena.spawn_points[i % arena.spawn_points.len()]);
             self.state.scores.insert(player.id, 0);
         }
     }
 
     fn update(&mut self, dt: f32, _inputs: &PlayerInputs) -> Vec<GameEvent> {
         breakpoint_core::profile!("tron_update");
-        if self.paused || self.state.round_complete {
+        if self.state.pause.is_paused() || self.state.round_complete {
             return Vec::new();
         }
 
@@ -303,30 +522,45 @@ impl BreakpointGame for TronCycles {
 
         // Process each cycle
         let player_ids: Vec<PlayerId> = self.player_ids.clone();
+        // Grid mode: the path each cycle swept this tick, for exact collision
+        let mut swept_paths: HashMap<PlayerId, Vec<(f32, f32)>> = HashMap::new();
         for &pid in &player_ids {
             let input = self.pending_inputs.remove(&pid).unwrap_or_default();
 
             // Save pre-movement position as the potential turn point
-            let turn_point = self
+            let Some((start_x, start_z, start_dir)) = self
                 .state
                 .players
                 .get(&pid)
-                .map(|c| (c.x, c.z, c.direction));
+                .map(|c| (c.x, c.z, c.direction))
+            else {
+                continue;
+            };
 
-            // Update cycle physics (applies turn + movement)
-            physics::update_cycle(
-                match self.state.players.get_mut(&pid) {
-                    Some(c) => c,
-                    None => continue,
-                },
-                pid,
-                &input,
-                &self.state.wall_segments,
-                self.state.arena_width,
-                self.state.arena_depth,
-                dt,
-                &self.game_config,
-            );
+            let corner = if self.grid_movement {
+                self.update_grid_cycle(pid, &input, dt)
+            } else {
+                // Update cycle physics (applies turn + movement)
+                physics::update_cycle(
+                    match self.state.players.get_mut(&pid) {
+                        Some(c) => c,
+                        None => continue,
+                    },
+                    pid,
+                    &input,
+                    &self.state.wall_segments,
+                    self.state.arena_width,
+                    self.state.arena_depth,
+                    dt,
+                    &self.game_config,
+                );
+                // Free movement turns at the PRE-movement position
+                self.state
+                    .players
+                    .get(&pid)
+                    .is_some_and(|c| c.direction != start_dir)
+                    .then_some((start_x, start_z))
+            };
 
             let cycle = match self.state.players.get(&pid) {
                 Some(c) => c,
@@ -337,13 +571,16 @@ impl BreakpointGame for TronCycles {
                 continue;
             }
 
-            // If direction changed, split segment at the PRE-movement turn point
-            let direction_changed = turn_point
-                .map(|(_, _, old_dir)| old_dir != cycle.direction)
-                .unwrap_or(false);
+            // Record the trail laid this tick (through the corner, if any)
+            let mut path = vec![(start_x, start_z)];
+            path.extend(corner);
+            path.push((cycle.x, cycle.z));
+            for leg in path.windows(2) {
+                self.wall_density
+                    .stamp_segment(leg[0].0, leg[0].1, leg[1].0, leg[1].1);
+            }
 
-            if direction_changed {
-                let (tx, tz, _) = turn_point.unwrap();
+            if let Some((tx, tz)) = corner {
                 self.start_new_segment_at(pid, tx, tz, cycle.x, cycle.z);
             } else {
                 // Update the active segment endpoint
@@ -356,6 +593,9 @@ impl BreakpointGame for TronCycles {
                     }
                 }
             }
+            if self.grid_movement {
+                swept_paths.insert(pid, path);
+            }
         }
 
         // Collision detection (separate pass to avoid borrow issues)
@@ -378,28 +618,48 @@ impl BreakpointGame for TronCycles {
             }
 
             // Check wall collisions
-            let result = collision::check_wall_collision(
-                cycle,
-                pid,
-                &self.state.wall_segments,
-                &self.game_config,
-            );
+            let result = match swept_paths.get(&pid) {
+                Some(path) => grid::check_swept_collision(pid, path, &self.state.wall_segments),
+                None => collision::check_wall_collision(
+                    cycle,
+                    pid,
+                    &self.state.wall_segments,
+                    &self.game_config,
+                ),
+            };
             if !result.alive {
                 kills.push((pid, result.killer_id, result.is_suicide));
             }
         }
 
         // Apply kills
+        let crashed: Vec<PlayerId> = kills.iter().map(|&(pid, _, _)| pid).collect();
         for (pid, killer_id, is_suicide) in kills {
             self.kill_cycle(pid, killer_id, is_suicide);
         }
 
+        // Publish the density grid at a reduced rate
+        self.tick_counter += 1;
+        self.state.wall_density = self
+            .tick_counter
+            .is_multiple_of(DENSITY_PUBLISH_INTERVAL)
+            .then(|| self.wall_density.clone());
+        if self.danger_map_enabled {
+            self.danger_map = Some(DangerMap::compute(
+                &self.state.players,
+                self.state.arena_width,
+                self.state.arena_depth,
+                DANGER_LOOKAHEAD_TICKS as f32 / self.tick_rate(),
+            ));
+        }
+
         // Win zone logic
         if !self.state.win_zone.active
             && win_zone::should_spawn_win_zone(
                 self.state.round_timer,
                 self.state.time_since_last_death,
                 &self.game_config,
+                self.micro_round_secs,
             )
         {
             self.state
@@ -420,6 +680,9 @@ impl BreakpointGame for TronCycles {
                     // This player wins the round
                     self.state.winner_id = Some(pid);
                     self.state.round_complete = true;
+                    if self.scoring {
+                        events.push(GameEvent::RoundWon { player_id: pid });
+                    }
                     events.push(GameEvent::RoundComplete);
                     return events;
                 }
@@ -428,23 +691,152 @@ impl BreakpointGame for TronCycles {
 
         // Check round completion: last player alive wins
         if self.state.alive_count <= 1 && self.player_ids.len() >= 2 {
-            self.state.round_complete = true;
-            // Find the winner
-            for &pid in &player_ids {
-                if let Some(cycle) = self.state.players.get(&pid)
-                    && cycle.alive
-                {
-                    self.state.winner_id = Some(pid);
-                    break;
+            let winner = player_ids
+                .iter()
+                .copied()
+                .find(|pid| self.state.players.get(pid).is_some_and(|c| c.alive));
+            if let Some(pid) = winner {
+                self.state.winner_id = Some(pid);
+                if self.scoring {
+                    events.push(GameEvent::RoundWon { player_id: pid });
                 }
+            } else if !crashed.is_empty() {
+                // The last riders crashed on the same tick
+                if self.overtime_enabled && !self.state.overtime && crashed.len() >= 2 {
+                    self.start_overtime(&crashed);
+                    events.push(GameEvent::SuddenDeath {
+                        player_ids: crashed,
+                    });
+                    return events;
+                }
+                self.state.draw = crashed.clone();
+                events.push(GameEvent::RoundDraw {
+                    player_ids: crashed,
+                });
             }
+            self.state.round_complete = true;
+            events.push(GameEvent::RoundComplete);
+        } else if self
+            .micro_round_secs
+            .is_some_and(|secs| self.state.round_timer >= secs)
+        {
+            // Out of time: everyone still riding draws
+            let survivors: Vec<PlayerId> = player_ids
+                .iter()
+                .copied()
+                .filter(|pid| self.state.players.get(pid).is_some_and(|c| c.alive))
+                .collect();
+            self.state.draw = survivors.clone();
+            events.push(GameEvent::RoundDraw {
+                player_ids: survivors,
+            });
+            self.state.round_complete = true;
             events.push(GameEvent::RoundComplete);
         }
 
         events
     }
 
-    breakpoint_game_boilerplate!(state_type: TronState);
+    fn serialize_state(&self) -> Vec<u8> {
+        rmp_serde::to_vec(&self.state).expect("game state serialization must succeed")
+    }
+
+    fn serialize_state_into(&self, buf: &mut Vec<u8>) {
+        buf.clear();
+        rmp_serde::encode::write(buf, &self.state).expect("game state serialization must succeed");
+    }
+
+    fn spectator_view_state(&self) -> Option<Vec<u8>> {
+        let cues = spectator::cues(&self.state);
+        let view = spectator::SpectatorView {
+            state: &self.state,
+            danger_map: self.danger_map.as_ref(),
+            cues: &cues,
+        };
+        Some(rmp_serde::to_vec(&view).expect("game state serialization must succeed"))
+    }
+
+    fn apply_state(&mut self, state: &[u8]) {
+        if let Ok(s) = rmp_serde::from_slice::<TronState>(state) {
+            // Density is only sent on publish ticks; keep the last grid otherwise.
+            if let Some(ref grid) = s.wall_density {
+                self.wall_density = grid.clone();
+            }
+            self.state = s;
+        }
+    }
+
+    fn inspect_state(&self, state: &[u8]) -> Option<breakpoint_core::game_trait::InspectedState> {
+        breakpoint_core::game_trait::inspect_state::<TronState>(state)
+    }
+
+    fn pause_for(&mut self, reason: PauseReason) {
+        self.state.pause.add(reason);
+    }
+
+    fn resume_for(&mut self, reason: PauseReason) {
+        self.state.pause.remove(reason);
+    }
+
+    fn pause_reasons(&self) -> &[PauseReason] {
+        self.state.pause.reasons()
+    }
+
+    fn is_round_complete(&self) -> bool {
+        self.state.round_complete
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn tutorial_objectives(&self) -> &'static [TutorialObjective] {
+        TUTORIAL_OBJECTIVES
+    }
+
+    fn tutorial_objectives_met(&self, player_id: PlayerId) -> Vec<&'static str> {
+        let mut met = Vec::new();
+        let alive = self.state.players.get(&player_id).is_some_and(|c| c.alive);
+        if alive && self.state.round_timer >= TUTORIAL_SURVIVE_SECS {
+            met.push("survive");
+        }
+        if self.state.winner_id == Some(player_id) {
+            met.push("win");
+        }
+        met
+    }
+
+    fn memory_usage(&self) -> GameMemory {
+        GameMemory {
+            walls_bytes: self.state.wall_segments.capacity() * std::mem::size_of::<WallSegment>(),
+            trails_bytes: 0,
+        }
+    }
+
+    fn trim_memory(&mut self) -> usize {
+        // Every segment is collided against, so only the spare capacity
+        // left over from earlier rounds can go
+        let before = self.memory_usage().walls_bytes;
+        self.state.wall_segments.shrink_to_fit();
+        before - self.memory_usage().walls_bytes
+    }
+
+    fn apply_debug(&mut self, command: &DebugCommand) -> DebugResult {
+        match *command {
+            DebugCommand::ForceRoundEnd => self.state.round_complete = true,
+            DebugCommand::SetRoundTimer { elapsed_secs } => {
+                self.state.round_timer = debug_command::checked_elapsed(elapsed_secs)?;
+            },
+            // A jump would leave a gap in the cycle's wall
+            DebugCommand::Teleport { .. } => {
+                return Err("Tron cycles cannot be teleported".to_string());
+            },
+            DebugCommand::GrantPowerUp { .. } => {
+                return Err("Tron has no power-ups".to_string());
+            },
+        }
+        Ok(())
+    }
 
     fn apply_input(&mut self, player_id: PlayerId, input: &[u8]) {
         match rmp_serde::from_slice::<TronInput>(input) {
@@ -473,7 +865,13 @@ impl BreakpointGame for TronCycles {
         if player.is_spectator || self.player_ids.contains(&player.id) {
             return;
         }
-        // Late joiners start as dead spectators for this round
+        // Late joiners sit out the round as crashed riders, unless the
+        // lobby would rather they just watch. Riders coming back to this
+        // round always get their crashed rider back.
+        let departed = self.departed.remove(&player.id);
+        if departed.is_none() && self.late_join == LateJoinPolicy::Spectate {
+            return;
+        }
         self.player_ids.push(player.id);
         let cycle = CycleState {
             x: self.state.arena_width / 2.0,
@@ -485,24 +883,34 @@ impl BreakpointGame for TronCycles {
             alive: false,
             trail_start_index: self.state.wall_segments.len(),
             turn_cooldown: 0.0,
-            kills: 0,
+            kills: departed.map_or(0, |d| d.kills),
             died: true,
-            is_suicide: false,
+            is_suicide: departed.is_some_and(|d| d.is_suicide),
         };
         self.state.players.insert(player.id, cycle);
         self.state.scores.insert(player.id, 0);
     }
 
     fn player_left(&mut self, player_id: PlayerId) {
-        self.player_ids.retain(|&id| id != player_id);
-        if let Some(cycle) = self.state.players.get(&player_id)
-            && cycle.alive
-        {
-            self.state.alive_count = self.state.alive_count.saturating_sub(1);
+        if let Some(cycle) = self.state.players.get(&player_id) {
+            if cycle.alive {
+                self.state.alive_count = self.state.alive_count.saturating_sub(1);
+            }
+            if self.player_ids.contains(&player_id) {
+                self.departed.insert(
+                    player_id,
+                    DepartedRider {
+                        kills: cycle.kills,
+                        is_suicide: cycle.died && cycle.is_suicide,
+                    },
+                );
+            }
         }
+        self.player_ids.retain(|&id| id != player_id);
         self.state.players.remove(&player_id);
         self.state.scores.remove(&player_id);
         self.pending_inputs.remove(&player_id);
+        self.queued_turns.remove(&player_id);
 
         // Finalize any active wall segments for this player
         for wall in &mut self.state.wall_segments {
@@ -521,19 +929,50 @@ impl BreakpointGame for TronCycles {
                 let died = cycle.is_some_and(|c| c.died);
                 let is_suicide = cycle.is_some_and(|c| c.is_suicide);
                 let kills = cycle.map_or(0, |c| c.kills);
+                let score = if self.state.draw.contains(&pid) {
+                    scoring::calculate_draw_score(kills)
+                } else {
+                    scoring::calculate_score(survived, kills, died, is_suicide)
+                };
 
                 PlayerScore {
                     player_id: pid,
-                    score: scoring::calculate_score(survived, kills, died, is_suicide),
+                    score,
+                    disconnected: false,
                 }
             })
+            .chain(self.departed.iter().map(|(&pid, d)| PlayerScore {
+                player_id: pid,
+                score: scoring::calculate_score(false, d.kills, true, d.is_suicide),
+                disconnected: true,
+            }))
             .collect()
     }
+
+    fn victory_summary(&self) -> VictorySummary {
+        if !self.state.draw.is_empty() {
+            return VictorySummary::new(TextKey::VictoryDraw, self.state.draw.clone());
+        }
+        let survivors: Vec<(PlayerId, u32)> = self
+            .player_ids
+            .iter()
+            .filter_map(|pid| self.state.players.get(pid).map(|c| (*pid, c)))
+            .filter(|(_, c)| c.alive)
+            .map(|(pid, c)| (pid, c.kills))
+            .collect();
+        match survivors[..] {
+            [(pid, kills)] => {
+                VictorySummary::new(TextKey::VictoryLastRider, vec![pid]).with_stat(kills as i32)
+            },
+            _ => VictorySummary::from_scores(&self.round_results()),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use breakpoint_core::spectator::{HighlightKind, PlayerHighlight};
     use breakpoint_core::test_helpers::{default_config, make_players};
 
     #[test]
@@ -574,6 +1013,52 @@ mod tests {
         assert!(game.pending_inputs.contains_key(&1));
     }
 
+    #[test]
+    fn wall_density_published_at_reduced_rate() {
+        let mut game = TronCycles::new();
+        let players = make_players(2);
+        game.init(&players, &default_config(120));
+
+        let inputs = PlayerInputs {
+            inputs: HashMap::new(),
+        };
+        let mut published = 0;
+        for _ in 0..DENSITY_PUBLISH_INTERVAL * 2 {
+            game.update(0.05, &inputs);
+            if game.state.wall_density.is_some() {
+                published += 1;
+            }
+        }
+        assert_eq!(published, 2, "Density should publish once per interval");
+        assert!(
+            game.wall_density().cells.iter().any(|&c| c > 0),
+            "Moving cycles should register wall density"
+        );
+    }
+
+    #[test]
+    fn client_keeps_density_between_publishes() {
+        let mut server = TronCycles::new();
+        let players = make_players(2);
+        server.init(&players, &default_config(120));
+        let inputs = PlayerInputs {
+            inputs: HashMap::new(),
+        };
+        for _ in 0..DENSITY_PUBLISH_INTERVAL {
+            server.update(0.05, &inputs);
+        }
+
+        let mut client = TronCycles::new();
+        client.apply_state(&server.serialize_state());
+        let published = client.wall_density().cells.clone();
+        assert_eq!(published, server.wall_density().cells);
+
+        server.update(0.05, &inputs);
+        assert!(server.state.wall_density.is_none());
+        client.apply_state(&server.serialize_state());
+        assert_eq!(client.wall_density().cells, published);
+    }
+
     #[test]
     fn tick_rate_is_20() {
         let game = TronCycles::new();
@@ -804,6 +1289,14 @@ mod tests {
         breakpoint_core::test_helpers::contract_pause_stops_updates(&mut game);
     }
 
+    #[test]
+    fn contract_pause_reasons_stack() {
+        let mut game = TronCycles::new();
+        let players = make_players(2);
+        game.init(&players, &default_config(120));
+        breakpoint_core::test_helpers::contract_pause_reasons_stack(&mut game);
+    }
+
     #[test]
     fn contract_player_left_cleanup() {
         let mut game = TronCycles::new();
@@ -990,7 +1483,12 @@ mod tests {
             let state: TronState = rmp_serde::from_slice(&state_bytes).unwrap();
 
             for pid in 1..=4 {
-                let bot_input = bot::generate_bot_input(&state, pid, &config);
+                let bot_input = bot::generate_bot_input(
+                    &state,
+                    pid,
+                    &config,
+                    &breakpoint_core::bot::BotSkill::HARD,
+                );
                 let input_bytes = rmp_serde::to_vec(&bot_input).unwrap();
                 game.apply_input(pid, &input_bytes);
             }
@@ -1010,35 +1508,7 @@ mod tests {
         let players = make_players(2);
         game.init(&players, &default_config(120));
 
-        // Place both players near opposing arena boundaries heading outward
-        // so they both die on the next tick from boundary collision.
-        let cycle1 = game.state.players.get_mut(&1).unwrap();
-        cycle1.x = 0.05;
-        cycle1.z = 250.0;
-        cycle1.direction = Direction::West;
-        // Close any active wall to avoid self-collision noise
-        for wall in &mut game.state.wall_segments {
-            if wall.owner_id == 1 && wall.is_active {
-                wall.x2 = 0.05;
-                wall.z2 = 250.0;
-            }
-        }
-
-        let cycle2 = game.state.players.get_mut(&2).unwrap();
-        cycle2.x = 499.95;
-        cycle2.z = 250.0;
-        cycle2.direction = Direction::East;
-        for wall in &mut game.state.wall_segments {
-            if wall.owner_id == 2 && wall.is_active {
-                wall.x2 = 499.95;
-                wall.z2 = 250.0;
-            }
-        }
-
-        let inputs = PlayerInputs {
-            inputs: HashMap::new(),
-        };
-        game.update(0.05, &inputs);
+        let events = crash_both_next_tick(&mut game);
 
         // Both players should be dead
         assert!(
@@ -1057,5 +1527,360 @@ mod tests {
             game.state.round_complete,
             "Round should be complete when all players are dead"
         );
+        assert_eq!(game.state.winner_id, None);
+        assert_eq!(game.state.draw, vec![1, 2]);
+        assert!(
+            events
+                .iter()
+                .any(|e| matches!(e, GameEvent::RoundDraw { player_ids } if player_ids == &[1, 2]))
+        );
+        assert!(
+            !events
+                .iter()
+                .any(|e| matches!(e, GameEvent::RoundWon { .. }))
+        );
+
+        // Both riders get the draw score rather than the death penalty
+        for score in game.round_results() {
+            assert_eq!(score.score, scoring::DRAW_POINTS);
+        }
+    }
+
+    #[test]
+    fn micro_round_ends_in_a_draw_for_the_survivors() {
+        use breakpoint_core::round_phase::MICRO_ROUNDS_SETTING;
+
+        let mut game = TronCycles::new();
+        let mut config = default_config(120);
+        config
+            .custom
+            .insert(MICRO_ROUNDS_SETTING.to_string(), serde_json::json!(30));
+        game.init(&make_players(3), &config);
+        assert!(!game.overtime_enabled, "Micro-rounds never go to overtime");
+        game.kill_cycle(3, Some(1), false);
+        game.state.round_timer = 29.99;
+
+        let inputs = PlayerInputs {
+            inputs: HashMap::new(),
+        };
+        let events = game.update(0.05, &inputs);
+        assert!(game.state.round_complete);
+        assert_eq!(game.state.winner_id, None);
+        assert_eq!(game.state.draw, vec![1, 2]);
+        assert!(
+            events
+                .iter()
+                .any(|e| matches!(e, GameEvent::RoundDraw { player_ids } if player_ids == &[1, 2]))
+        );
+
+        config
+            .custom
+            .insert("overtime".to_string(), serde_json::json!(true));
+        let err = TronSettings::validate(&config.custom).unwrap_err();
+        assert_eq!(err.key, MICRO_ROUNDS_SETTING);
+    }
+
+    #[test]
+    fn late_joiners_are_benched_or_left_to_spectate() {
+        use breakpoint_core::late_join::LATE_JOIN_SETTING;
+
+        let players = make_players(3);
+        let mut game = TronCycles::new();
+        game.init(&players[..2], &default_config(120));
+        game.player_joined(&players[2]);
+        assert!(!game.state.players[&3].alive, "Benched as a crashed rider");
+        assert_eq!(game.state.alive_count, 2);
+
+        let mut config = default_config(120);
+        config
+            .custom
+            .insert(LATE_JOIN_SETTING.to_string(), serde_json::json!("spectate"));
+        game.init(&players[..2], &config);
+        game.player_joined(&players[2]);
+        assert!(!game.state.players.contains_key(&3));
+        assert!(game.round_results().iter().all(|r| r.player_id != 3));
+
+        // Riders can't be dropped into a running round
+        config
+            .custom
+            .insert(LATE_JOIN_SETTING.to_string(), serde_json::json!("spawn"));
+        let err = TronSettings::validate(&config.custom).unwrap_err();
+        assert_eq!(err.key, LATE_JOIN_SETTING);
+    }
+
+    /// Park riders 1 and 2 against opposite arena walls, heading outward,
+    /// and run the tick on which they both crash.
+    fn crash_both_next_tick(game: &mut TronCycles) -> Vec<GameEvent> {
+        let (width, mid_z) = (game.state.arena_width, game.state.arena_depth / 2.0);
+        for (pid, x, direction) in [
+            (1, 0.05, Direction::West),
+            (2, width - 0.05, Direction::East),
+        ] {
+            let cycle = game.state.players.get_mut(&pid).unwrap();
+            cycle.x = x;
+            cycle.z = mid_z;
+            cycle.direction = direction;
+            // Close any active wall to avoid self-collision noise
+            for wall in &mut game.state.wall_segments {
+                if wall.owner_id == pid && wall.is_active {
+                    wall.x2 = x;
+                    wall.z2 = mid_z;
+                }
+            }
+        }
+
+        let inputs = PlayerInputs {
+            inputs: HashMap::new(),
+        };
+        game.update(0.05, &inputs)
+    }
+
+    #[test]
+    fn drawn_round_goes_to_sudden_death_once() {
+        let mut game = TronCycles::new();
+        let mut config = default_config(120);
+        config
+            .custom
+            .insert("overtime".to_string(), serde_json::json!(true));
+        game.init(&make_players(3), &config);
+        game.kill_cycle(3, Some(1), false);
+
+        let events = crash_both_next_tick(&mut game);
+        assert!(
+            events.iter().any(
+                |e| matches!(e, GameEvent::SuddenDeath { player_ids } if player_ids == &[1, 2])
+            )
+        );
+        assert!(!game.state.round_complete, "Overtime continues the round");
+        assert!(game.state.overtime);
+        assert_eq!(game.state.alive_count, 2);
+        assert_eq!(game.state.arena_width, game.config().overtime.arena_size);
+        assert!(game.state.players[&1].alive && game.state.players[&2].alive);
+        assert!(!game.state.players[&3].alive, "Eliminated riders stay out");
+        assert_eq!(game.state.players[&1].kills, 1, "Kills carry over");
+        assert_eq!(game.state.wall_segments.len(), 2, "Only the fresh walls");
+
+        // A second draw in overtime ends the round
+        let events = crash_both_next_tick(&mut game);
+        assert!(game.state.round_complete);
+        assert_eq!(game.state.draw, vec![1, 2]);
+        assert!(
+            events
+                .iter()
+                .any(|e| matches!(e, GameEvent::RoundDraw { .. }))
+        );
+    }
+
+    #[test]
+    fn last_rider_standing_wins_the_round() {
+        let mut game = TronCycles::new();
+        game.init(&make_players(2), &default_config(120));
+        game.kill_cycle(2, None, true);
+        let events = game.update(
+            0.05,
+            &PlayerInputs {
+                inputs: HashMap::new(),
+            },
+        );
+        assert_eq!(game.state.winner_id, Some(1));
+        assert!(game.state.draw.is_empty());
+        assert!(
+            events
+                .iter()
+                .any(|e| matches!(e, GameEvent::RoundWon { player_id: 1 }))
+        );
+        let victory = game.victory_summary();
+        assert_eq!(victory.winner_ids, vec![1]);
+        assert_eq!(victory.message.key, TextKey::VictoryLastRider);
+    }
+
+    fn grid_game(players: usize) -> TronCycles {
+        let mut game = TronCycles::default();
+        let mut config = default_config(120);
+        config
+            .custom
+            .insert("movement".to_string(), serde_json::json!("grid"));
+        game.init(&make_players(players), &config);
+        game
+    }
+
+    fn on_grid(value: f32, cell_size: f32) -> bool {
+        (value - grid::snap(value, cell_size)).abs() < 1e-3
+    }
+
+    #[test]
+    fn grid_movement_turns_on_cell_boundaries() {
+        let mut game = grid_game(2);
+        assert!(game.grid_movement());
+        assert!(!TronCycles::default().grid_movement(), "Free by default");
+        let cell_size = game.config().grid.cell_size;
+
+        let inputs = PlayerInputs {
+            inputs: HashMap::new(),
+        };
+        // Zigzag so the cycle never crosses its own trail
+        let turn = |turn| rmp_serde::to_vec(&TronInput { turn, brake: false }).unwrap();
+        for i in 0..30 {
+            match i % 14 {
+                3 => game.apply_input(1, &turn(TurnDirection::Left)),
+                10 => game.apply_input(1, &turn(TurnDirection::Right)),
+                _ => {},
+            }
+            game.update(0.05, &inputs);
+        }
+        assert!(game.state.players[&1].alive);
+
+        let segments: Vec<&WallSegment> = game
+            .state
+            .wall_segments
+            .iter()
+            .filter(|w| w.owner_id == 1)
+            .collect();
+        assert!(segments.len() > 2, "Queued turns should be applied");
+        for wall in segments.iter().filter(|w| !w.is_active) {
+            for v in [wall.x1, wall.z1, wall.x2, wall.z2] {
+                assert!(on_grid(v, cell_size), "Corner {v} off the grid");
+            }
+        }
+        let cycle = &game.state.players[&1];
+        let lane = match cycle.direction {
+            Direction::North | Direction::South => cycle.x,
+            Direction::East | Direction::West => cycle.z,
+        };
+        assert!(on_grid(lane, cell_size), "Cycle left its grid line");
+    }
+
+    #[test]
+    fn grid_movement_collision_is_exact() {
+        let mut game = grid_game(2);
+        // Cycle 1 heads east toward a wall it would otherwise skip over in
+        // a single tick (the wall is between the old and new positions)
+        let cycle = game.state.players.get_mut(&1).unwrap();
+        cycle.x = 100.0;
+        cycle.z = 250.0;
+        cycle.direction = Direction::East;
+        for wall in &mut game.state.wall_segments {
+            if wall.owner_id == 1 && wall.is_active {
+                (wall.x1, wall.z1, wall.x2, wall.z2) = (100.0, 250.0, 100.0, 250.0);
+            }
+        }
+        game.state.wall_segments.push(WallSegment {
+            x1: 101.0,
+            z1: 240.0,
+            x2: 101.0,
+            z2: 260.0,
+            owner_id: 2,
+            is_active: false,
+        });
+
+        let inputs = PlayerInputs {
+            inputs: HashMap::new(),
+        };
+        game.update(0.05, &inputs);
+
+        assert!(!game.state.players[&1].alive);
+        assert_eq!(game.state.players[&2].kills, 1);
+    }
+
+    /// `count` short closed segments in a grid around the arena center,
+    /// clear of the spawn circle, owned by players 1 to 8 in turn.
+    pub(crate) fn wall_grid(count: usize) -> Vec<WallSegment> {
+        (0..count)
+            .map(|i| {
+                let x = 150.0 + (i % 100) as f32 * 2.0;
+                let z = 150.0 + (i / 100 % 100) as f32 * 2.0;
+                WallSegment {
+                    x1: x,
+                    z1: z,
+                    x2: x + 1.0,
+                    z2: z,
+                    owner_id: i as PlayerId % 8 + 1,
+                    is_active: false,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tick_fits_its_budget_with_10k_walls() {
+        use breakpoint_core::test_helpers::assert_tick_budget;
+
+        let mut game = TronCycles::new();
+        let players = make_players(game.metadata().max_players as usize);
+        game.init(&players, &default_config(120));
+        game.state.wall_segments.extend(wall_grid(10_000));
+        let spawned = game.state.players.clone();
+
+        // Cycles go back to their spawn before every tick so the whole
+        // field stays alive and checks every wall
+        assert_tick_budget(
+            "tron update, 8 players, 10k walls",
+            &mut game,
+            Duration::from_millis(2),
+            |game| game.state.players.clone_from(&spawned),
+        );
+        assert_eq!(game.state.alive_count, 8);
+    }
+
+    #[test]
+    fn danger_map_and_cues_reach_spectators_only() {
+        let inputs = PlayerInputs {
+            inputs: HashMap::new(),
+        };
+        let mut game = TronCycles::new();
+        game.init(&make_players(3), &default_config(120));
+        game.update(0.05, &inputs);
+        let view = game.spectator_view_state().expect("spectators get cues");
+        let view: TronState = rmp_serde::from_slice(&view).unwrap();
+        assert!(view.danger_map.is_none());
+        assert!(view.spectator.expect("cues").focus.is_some());
+
+        let mut config = default_config(120);
+        config
+            .custom
+            .insert("danger_map".to_string(), serde_json::json!(true));
+        game.init(&make_players(3), &config);
+        game.update(0.05, &inputs);
+        assert!(!game.has_player_views());
+
+        // Players get the shared state, which has neither
+        let shared: TronState = rmp_serde::from_slice(&game.serialize_state()).unwrap();
+        assert!(shared.danger_map.is_none());
+        assert!(shared.spectator.is_none());
+
+        let view = game.spectator_view_state().unwrap();
+        let view: TronState = rmp_serde::from_slice(&view).unwrap();
+        assert_eq!(
+            view.danger_map.expect("computed every tick").riders,
+            [1, 2, 3]
+        );
+        assert_eq!(view.players.len(), 3);
+        assert_eq!(view.round_timer, game.state.round_timer);
+
+        // The camera goes to the closest pair, on whoever has more kills
+        for (pid, (x, z, kills)) in [
+            (1, (10.0, 10.0, 0)),
+            (2, (12.0, 10.0, 1)),
+            (3, (90.0, 90.0, 3)),
+        ] {
+            let cycle = game.state.players.get_mut(&pid).unwrap();
+            (cycle.x, cycle.z, cycle.kills) = (x, z, kills);
+        }
+        game.state.scores.insert(3, 2);
+        let cues = spectator::cues(&game.state);
+        assert_eq!(cues.focus, Some(2));
+        assert_eq!(
+            cues.highlights,
+            [
+                PlayerHighlight {
+                    player_id: 3,
+                    kind: HighlightKind::Leader,
+                },
+                PlayerHighlight {
+                    player_id: 3,
+                    kind: HighlightKind::Streak { count: 3 },
+                },
+            ]
+        );
     }
 }
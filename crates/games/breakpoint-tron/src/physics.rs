@@ -101,34 +101,16 @@ pub fn update_cycle(
         TurnDirection::None => {},
     }
 
-    // Braking
-    if input.brake {
-        apply_brake(cycle, dt, config);
-    } else {
-        regen_brake(cycle, dt, config);
-    }
-
-    // Wall acceleration (grinding)
-    let accel = wall_acceleration(
+    update_speed(
         cycle,
         cycle_owner_id,
+        input,
         walls,
         arena_width,
         arena_depth,
+        dt,
         config,
     );
-    cycle.speed += accel * dt;
-
-    // Speed decay toward base speed (skip recovery when braking)
-    if cycle.speed > config.base_speed {
-        cycle.speed = (cycle.speed - config.speed_decay_rate * dt).max(config.base_speed);
-    } else if cycle.speed < config.base_speed && !input.brake {
-        // Fast recovery if below base speed (but not while braking)
-        cycle.speed = (cycle.speed + config.speed_decay_rate * 2.0 * dt).min(config.base_speed);
-    }
-
-    // Clamp speed
-    cycle.speed = cycle.speed.clamp(config.base_speed * 0.3, config.max_speed);
 
     // Move
     let distance = cycle.speed * dt;
@@ -152,6 +134,48 @@ pub fn update_cycle(
     }
 }
 
+/// Apply braking, wall grinding, and speed decay for one tick.
+#[allow(clippy::too_many_arguments)]
+pub fn update_speed(
+    cycle: &mut CycleState,
+    cycle_owner_id: PlayerId,
+    input: &TronInput,
+    walls: &[WallSegment],
+    arena_width: f32,
+    arena_depth: f32,
+    dt: f32,
+    config: &TronConfig,
+) {
+    // Braking
+    if input.brake {
+        apply_brake(cycle, dt, config);
+    } else {
+        regen_brake(cycle, dt, config);
+    }
+
+    // Wall acceleration (grinding)
+    let accel = wall_acceleration(
+        cycle,
+        cycle_owner_id,
+        walls,
+        arena_width,
+        arena_depth,
+        config,
+    );
+    cycle.speed += accel * dt;
+
+    // Speed decay toward base speed (skip recovery when braking)
+    if cycle.speed > config.base_speed {
+        cycle.speed = (cycle.speed - config.speed_decay_rate * dt).max(config.base_speed);
+    } else if cycle.speed < config.base_speed && !input.brake {
+        // Fast recovery if below base speed (but not while braking)
+        cycle.speed = (cycle.speed + config.speed_decay_rate * 2.0 * dt).min(config.base_speed);
+    }
+
+    // Clamp speed
+    cycle.speed = cycle.speed.clamp(config.base_speed * 0.3, config.max_speed);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
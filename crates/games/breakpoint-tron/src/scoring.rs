@@ -6,6 +6,8 @@ pub const KILL_POINTS: i32 = 3;
 pub const DEATH_POINTS: i32 = -2;
 /// Points deducted for suicide (hitting your own wall).
 pub const SUICIDE_POINTS: i32 = -4;
+/// Points for each rider in a drawn round, in place of the death penalty.
+pub const DRAW_POINTS: i32 = 5;
 
 /// Calculate a player's score for a round.
 pub fn calculate_score(survived: bool, kills: u32, died: bool, suicide: bool) -> i32 {
@@ -24,6 +26,13 @@ pub fn calculate_score(survived: bool, kills: u32, died: bool, suicide: bool) ->
     score
 }
 
+/// Score for a rider who crashed on the same tick as the last opponents,
+/// leaving nobody standing. The riders split the survival bonus and the
+/// crash costs nothing.
+pub fn calculate_draw_score(kills: u32) -> i32 {
+    DRAW_POINTS + kills as i32 * KILL_POINTS
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -43,6 +52,13 @@ mod tests {
         assert_eq!(calculate_score(false, 0, true, true), -4);
     }
 
+    #[test]
+    fn draw_beats_dying_but_not_surviving() {
+        assert_eq!(calculate_draw_score(1), 5 + 3);
+        assert!(calculate_draw_score(0) > calculate_score(false, 0, true, false));
+        assert!(calculate_draw_score(0) < calculate_score(true, 0, false, false));
+    }
+
     #[test]
     fn no_events() {
         assert_eq!(calculate_score(false, 0, false, false), 0);
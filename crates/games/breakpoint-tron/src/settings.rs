@@ -0,0 +1,61 @@
+use breakpoint_core::game_settings::{
+    CustomSettings, FromGameConfig, SettingsError, SettingsReader,
+};
+use breakpoint_core::late_join::LateJoinPolicy;
+use breakpoint_core::round_phase::{self, MICRO_ROUNDS_SETTING};
+
+/// Tron lobby settings. Unset values keep what `config/tron.toml` says.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TronSettings {
+    /// `movement`: `grid` for classic grid turns, `free` for smooth ones.
+    pub grid_movement: Option<bool>,
+    /// `overtime`: shrink the arena when a round runs long.
+    pub overtime: Option<bool>,
+    /// `danger_map`: send spectators each rider's reachable cells every tick.
+    pub danger_map: Option<bool>,
+    /// `micro_rounds`: end the round after this many seconds, with every
+    /// surviving rider drawing. Overtime would outlast it, so it's off.
+    pub micro_round_secs: Option<f32>,
+    /// `late_join`: `next_round` (the default) benches late joiners as
+    /// crashed riders until the round ends; `spectate` leaves them off the
+    /// grid. A rider can't start mid-round without cutting across trails,
+    /// so `spawn` isn't offered.
+    pub late_join: LateJoinPolicy,
+}
+
+impl Default for TronSettings {
+    fn default() -> Self {
+        Self {
+            grid_movement: None,
+            overtime: None,
+            danger_map: None,
+            micro_round_secs: None,
+            late_join: LateJoinPolicy::SpawnNextRound,
+        }
+    }
+}
+
+impl FromGameConfig for TronSettings {
+    fn from_custom(custom: &CustomSettings) -> Result<Self, SettingsError> {
+        let reader = SettingsReader::new(custom);
+        let overtime = reader.bool("overtime")?;
+        let micro_round_secs = round_phase::micro_round_secs(custom)?;
+        if micro_round_secs.is_some() && overtime == Some(true) {
+            return Err(SettingsError::new(
+                MICRO_ROUNDS_SETTING,
+                "micro-rounds can't go to overtime",
+            ));
+        }
+        Ok(Self {
+            grid_movement: reader.choice("movement", &[("grid", true), ("free", false)])?,
+            overtime: micro_round_secs.map_or(overtime, |_| Some(false)),
+            danger_map: reader.bool("danger_map")?,
+            micro_round_secs,
+            late_join: LateJoinPolicy::from_custom(
+                custom,
+                LateJoinPolicy::SpawnNextRound,
+                &[LateJoinPolicy::Spectate, LateJoinPolicy::SpawnNextRound],
+            )?,
+        })
+    }
+}
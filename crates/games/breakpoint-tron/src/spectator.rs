@@ -0,0 +1,84 @@
+//! What spectators get on top of the shared state: cues for the camera and
+//! the danger map. The camera follows the closest pair of living riders,
+//! where a crash is most likely next.
+
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use breakpoint_core::game_trait::PlayerId;
+use breakpoint_core::spectator::SpectatorCues;
+
+use crate::TronState;
+use crate::danger::DangerMap;
+
+/// Camera focus and highlights for `state`: the round's sole score leader
+/// and living riders on a kill streak.
+pub fn cues(state: &TronState) -> SpectatorCues {
+    let streaks = state
+        .players
+        .iter()
+        .filter(|(_, c)| c.alive)
+        .map(|(&pid, c)| (pid, c.kills));
+    let scores = state.scores.iter().map(|(&pid, &s)| (pid, i64::from(s)));
+    SpectatorCues::new(focus(state), scores, streaks)
+}
+
+/// The rider of the closest living pair with more kills (lower ID on a
+/// tie), or the last rider standing.
+fn focus(state: &TronState) -> Option<PlayerId> {
+    let mut living: Vec<(PlayerId, f32, f32, u32)> = state
+        .players
+        .iter()
+        .filter(|(_, c)| c.alive)
+        .map(|(&pid, c)| (pid, c.x, c.z, c.kills))
+        .collect();
+    living.sort_unstable_by_key(|&(pid, ..)| pid);
+    if let [(only, ..)] = living[..] {
+        return Some(only);
+    }
+    let mut closest: Option<(f32, PlayerId)> = None;
+    for (i, a) in living.iter().enumerate() {
+        for b in &living[i + 1..] {
+            let dist = (a.1 - b.1).hypot(a.2 - b.2);
+            if closest.is_none_or(|(best, _)| dist < best) {
+                let pick = if b.3 > a.3 { b.0 } else { a.0 };
+                closest = Some((dist, pick));
+            }
+        }
+    }
+    closest.map(|(_, pid)| pid)
+}
+
+/// The spectator view: serializes exactly like [`TronState`] with the
+/// danger map and cues filled in, so the full state is never copied.
+pub struct SpectatorView<'a> {
+    pub state: &'a TronState,
+    pub danger_map: Option<&'a DangerMap>,
+    pub cues: &'a SpectatorCues,
+}
+
+impl Serialize for SpectatorView<'_> {
+    // Fields in `TronState` declaration order
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let s = self.state;
+        let mut out = serializer.serialize_struct("TronState", 18)?;
+        out.serialize_field("players", &s.players)?;
+        out.serialize_field("wall_segments", &s.wall_segments)?;
+        out.serialize_field("round_timer", &s.round_timer)?;
+        out.serialize_field("round_complete", &s.round_complete)?;
+        out.serialize_field("round_number", &s.round_number)?;
+        out.serialize_field("scores", &s.scores)?;
+        out.serialize_field("win_zone", &s.win_zone)?;
+        out.serialize_field("alive_count", &s.alive_count)?;
+        out.serialize_field("arena_width", &s.arena_width)?;
+        out.serialize_field("arena_depth", &s.arena_depth)?;
+        out.serialize_field("time_since_last_death", &s.time_since_last_death)?;
+        out.serialize_field("winner_id", &s.winner_id)?;
+        out.serialize_field("wall_density", &s.wall_density)?;
+        out.serialize_field("pause", &s.pause)?;
+        out.serialize_field("draw", &s.draw)?;
+        out.serialize_field("overtime", &s.overtime)?;
+        out.serialize_field("danger_map", &self.danger_map)?;
+        out.serialize_field("spectator", &Some(self.cues))?;
+        out.end()
+    }
+}
@@ -62,12 +62,23 @@ impl WinZone {
 }
 
 /// Check whether the win zone should appear based on round timer and last death time.
+/// A micro-round of `micro_round_secs` opens it by half time, and a death
+/// holds it off for a quarter of the round at most, so it can still be
+/// reached before the clock runs out.
 pub fn should_spawn_win_zone(
     round_timer: f32,
     time_since_last_death: f32,
     config: &TronConfig,
+    micro_round_secs: Option<f32>,
 ) -> bool {
-    round_timer >= config.win_zone_delay && time_since_last_death >= config.win_zone_death_delay
+    let (delay, death_delay) = match micro_round_secs {
+        Some(secs) => (
+            config.win_zone_delay.min(secs / 2.0),
+            config.win_zone_death_delay.min(secs / 4.0),
+        ),
+        None => (config.win_zone_delay, config.win_zone_death_delay),
+    };
+    round_timer >= delay && time_since_last_death >= death_delay
 }
 
 #[cfg(test)]
@@ -104,12 +115,17 @@ mod tests {
         let config = TronConfig::default();
 
         // Too early
-        assert!(!should_spawn_win_zone(30.0, 40.0, &config));
+        assert!(!should_spawn_win_zone(30.0, 40.0, &config, None));
 
         // Round time OK but recent death
-        assert!(!should_spawn_win_zone(65.0, 10.0, &config));
+        assert!(!should_spawn_win_zone(65.0, 10.0, &config, None));
 
         // Both conditions met
-        assert!(should_spawn_win_zone(65.0, 35.0, &config));
+        assert!(should_spawn_win_zone(65.0, 35.0, &config, None));
+
+        // Micro-rounds open it by half time
+        assert!(!should_spawn_win_zone(19.0, 19.0, &config, Some(40.0)));
+        assert!(!should_spawn_win_zone(20.0, 9.0, &config, Some(40.0)));
+        assert!(should_spawn_win_zone(20.0, 10.0, &config, Some(40.0)));
     }
 }
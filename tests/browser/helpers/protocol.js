@@ -24,6 +24,7 @@ export const MSG = {
   ROUND_END:         0x14,
   GAME_END:          0x15,
   COURSE_UPDATE:     0x16,
+  ROUND_PHASE:       0x17,
   // Alert
   ALERT_EVENT:       0x20,
   ALERT_CLAIMED:     0x21,
@@ -0,0 +1,110 @@
+// Breakpoint i18n — loads web/locales/<lang>.json and renders text by key.
+//
+// Static markup opts in with data-i18n (textContent), data-i18n-placeholder,
+// data-i18n-title, and data-i18n-aria-label attributes. Dynamic text calls
+// window.bpI18n.t(key, params); server-sent message codes arrive as
+// { key, params } objects and render through window.bpI18n.localize(text).
+
+(function () {
+    "use strict";
+
+    const LANGUAGES = {
+        en: "English",
+        es: "Español",
+    };
+    const FALLBACK = "en";
+    const STORAGE_KEY = "ui_language";
+
+    const catalogs = {};
+    const listeners = [];
+    let current = FALLBACK;
+
+    function detectLanguage() {
+        try {
+            const saved = localStorage.getItem(STORAGE_KEY);
+            if (saved && LANGUAGES[saved]) return saved;
+        } catch (_) { /* storage unavailable */ }
+        const langs = navigator.languages || [navigator.language || FALLBACK];
+        for (const l of langs) {
+            const base = String(l).toLowerCase().split("-")[0];
+            if (LANGUAGES[base]) return base;
+        }
+        return FALLBACK;
+    }
+
+    function loadCatalog(lang) {
+        if (catalogs[lang]) return Promise.resolve(catalogs[lang]);
+        return fetch(`locales/${lang}.json`)
+            .then((r) => r.ok ? r.json() : {})
+            .catch(() => ({}))
+            .then((dict) => { catalogs[lang] = dict; return dict; });
+    }
+
+    // Look up a key in the current language, falling back to English, then
+    // to the key itself. {name} placeholders are replaced from params.
+    function t(key, params) {
+        const dict = catalogs[current] || {};
+        const fallback = catalogs[FALLBACK] || {};
+        let text = dict[key] != null ? dict[key] : (fallback[key] != null ? fallback[key] : key);
+        if (params) {
+            text = text.replace(/\{(\w+)\}/g, (m, name) =>
+                params[name] != null ? String(params[name]) : m);
+        }
+        return text;
+    }
+
+    // Render a server-sent { key, params } message code (or null).
+    function localize(text) {
+        if (!text) return "";
+        if (typeof text === "string") return text;
+        return t(text.key, text.params);
+    }
+
+    function apply(root) {
+        const scope = root || document;
+        scope.querySelectorAll("[data-i18n]").forEach((el) => {
+            el.textContent = t(el.dataset.i18n);
+        });
+        scope.querySelectorAll("[data-i18n-placeholder]").forEach((el) => {
+            el.setAttribute("placeholder", t(el.dataset.i18nPlaceholder));
+        });
+        scope.querySelectorAll("[data-i18n-title]").forEach((el) => {
+            el.setAttribute("title", t(el.dataset.i18nTitle));
+        });
+        scope.querySelectorAll("[data-i18n-aria-label]").forEach((el) => {
+            el.setAttribute("aria-label", t(el.dataset.i18nAriaLabel));
+        });
+        document.documentElement.lang = current;
+    }
+
+    function setLanguage(lang) {
+        if (!LANGUAGES[lang]) lang = FALLBACK;
+        return Promise.all([loadCatalog(FALLBACK), loadCatalog(lang)]).then(() => {
+            current = lang;
+            try { localStorage.setItem(STORAGE_KEY, lang); } catch (_) { /* ignore */ }
+            apply();
+            listeners.forEach((cb) => cb(lang));
+        });
+    }
+
+    window.bpI18n = {
+        LANGUAGES,
+        t,
+        localize,
+        apply,
+        setLanguage,
+        language: () => current,
+        onChange: (cb) => listeners.push(cb),
+    };
+
+    // Markup ships with English text, so only switch when another language
+    // is wanted; always load English as the fallback catalog.
+    const initial = detectLanguage();
+    loadCatalog(FALLBACK).then(() => {
+        if (initial !== FALLBACK) {
+            setLanguage(initial);
+        } else {
+            listeners.forEach((cb) => cb(FALLBACK));
+        }
+    });
+})();
@@ -0,0 +1,64 @@
+// Breakpoint Network Diagnostics Panel
+// Shows incoming message rates by type, snapshot sizes, decode failures,
+// round trip and snapshot age, tick lag, and prediction error from the
+// WASM client. Toggle with F4.
+// Hidden by default.
+(function () {
+    'use strict';
+
+    let panel = null;
+
+    function createPanel() {
+        panel = document.createElement('div');
+        panel.id = 'bp-netdiag';
+        document.body.appendChild(panel);
+    }
+
+    function kb(bytes) {
+        return bytes >= 1024 ? `${(bytes / 1024).toFixed(1)}KB` : `${Math.round(bytes)}B`;
+    }
+
+    function ms(value) {
+        return value != null ? `${Math.round(value)}ms` : '--';
+    }
+
+    function render(d) {
+        const lag = d.appliedTick != null && d.renderTick != null
+            ? d.renderTick - d.appliedTick
+            : null;
+        const rows = d.rates.map(([type, rate, bytes]) =>
+            `<div class="bp-nd-row"><span>${type}</span><span>${rate.toFixed(1)}/s ${kb(bytes)}/s</span></div>`
+        ).join('');
+        panel.innerHTML = `
+            <div class="bp-nd-title">Network</div>
+            ${rows || '<div class="bp-nd-row"><span>no traffic</span></div>'}
+            <div class="bp-nd-row bp-nd-sep"><span>sent</span><span>${d.sentRate.toFixed(1)}/s ${kb(d.sentBytesPerSec)}/s</span></div>
+            <div class="bp-nd-row"><span>snapshot</span><span>${kb(d.lastSnapshotBytes)} avg ${kb(d.avgSnapshotBytes)} max ${kb(d.maxSnapshotBytes)}</span></div>
+            <div class="bp-nd-row"><span>decode failures</span><span class="${d.decodeFailures > 0 ? 'bp-nd-bad' : ''}">${d.decodeFailures}</span></div>
+            <div class="bp-nd-row"><span>rtt / snapshot age</span><span>${ms(d.rttMs)} / ${ms(d.snapshotAgeMs)}</span></div>
+            <div class="bp-nd-row"><span>tick applied/render</span><span>${d.appliedTick ?? '--'} / ${d.renderTick ?? '--'}${lag != null ? ` (+${lag})` : ''}</span></div>
+            <div class="bp-nd-row"><span>prediction error</span><span>${d.lastPredictionError.toFixed(2)} avg ${d.avgPredictionError.toFixed(2)} max ${d.maxPredictionError.toFixed(2)}</span></div>
+        `;
+    }
+
+    // Hook into the main update loop
+    const origUpdate = window._breakpointUpdate;
+    window._breakpointUpdate = function (state) {
+        if (origUpdate) origUpdate(state);
+        if (state.netDiag) {
+            if (!panel) createPanel();
+            panel.style.display = 'block';
+            render(state.netDiag);
+        } else if (panel) {
+            panel.style.display = 'none';
+        }
+    };
+
+    // Toggle with F4
+    document.addEventListener('keydown', function (e) {
+        if (e.code === 'F4') {
+            e.preventDefault();
+            if (window._bpToggleNetDiag) window._bpToggleNetDiag();
+        }
+    });
+})();
@@ -0,0 +1,120 @@
+// Opt-in cross-device settings sync. Mirrors a few localStorage keys to
+// PUT api/v1/players/{friendId}/settings, authenticated with the identity
+// key the WASM client keeps in localStorage. The server merges and returns
+// the result, which is written back and re-applied.
+
+(function () {
+    "use strict";
+
+    // localStorage keys synced as plain values (last write wins)
+    const SYNCED_KEYS = ["audio_muted", "audio_master_volume", "audio_music_volume", "ui_language", "golf_drag_aim"];
+    const ENABLED_KEY = "settings_sync";
+    const FRIEND_ID_KEY = "friend_id";
+    const IDENTITY_KEY = "identity_key";
+    const UPDATED_AT_KEY = "settings_updated_at";
+    // Action -> {key, updated_at}; merged per action on the server
+    const KEYBINDS_KEY = "keybinds";
+    const CHECK_INTERVAL_MS = 30000;
+
+    function get(key) {
+        try { return localStorage.getItem(key); } catch (_) { return null; }
+    }
+
+    function set(key, value) {
+        try { localStorage.setItem(key, value); } catch (_) { /* storage unavailable */ }
+    }
+
+    function readValues() {
+        const values = {};
+        SYNCED_KEYS.forEach((key) => {
+            const value = get(key);
+            if (value !== null) values[key] = value;
+        });
+        return values;
+    }
+
+    function readKeybinds() {
+        try {
+            const binds = JSON.parse(get(KEYBINDS_KEY) || "{}");
+            return binds && typeof binds === "object" ? binds : {};
+        } catch (_) {
+            return {};
+        }
+    }
+
+    let lastValues = JSON.stringify(readValues());
+    let inFlight = false;
+
+    function enabled() {
+        return get(ENABLED_KEY) === "true";
+    }
+
+    // Stamp the values as changed if they differ from the last sync.
+    // Returns whether anything changed.
+    function noteLocalChanges() {
+        const values = JSON.stringify(readValues());
+        if (values === lastValues) return false;
+        lastValues = values;
+        set(UPDATED_AT_KEY, String(Date.now()));
+        return true;
+    }
+
+    function apply(merged) {
+        const values = merged.values || {};
+        SYNCED_KEYS.forEach((key) => {
+            if (typeof values[key] === "string") set(key, values[key]);
+        });
+        if (merged.keybinds && Object.keys(merged.keybinds).length) {
+            set(KEYBINDS_KEY, JSON.stringify(merged.keybinds));
+        }
+        set(UPDATED_AT_KEY, String(merged.updated_at || 0));
+        lastValues = JSON.stringify(readValues());
+
+        if (window._bpReloadSettings) window._bpReloadSettings();
+        const i18n = window.bpI18n;
+        if (values.ui_language && i18n && values.ui_language !== i18n.language()) {
+            i18n.setLanguage(values.ui_language);
+        }
+    }
+
+    function sync() {
+        const friendId = get(FRIEND_ID_KEY);
+        const identityKey = get(IDENTITY_KEY);
+        if (!enabled() || !friendId || !identityKey || inFlight) return;
+        noteLocalChanges();
+        inFlight = true;
+        fetch(`api/v1/players/${encodeURIComponent(friendId)}/settings`, {
+            method: "PUT",
+            headers: { "Content-Type": "application/json", "X-Identity-Key": identityKey },
+            body: JSON.stringify({
+                updated_at: Number(get(UPDATED_AT_KEY)) || 0,
+                values: readValues(),
+                keybinds: readKeybinds(),
+            }),
+        })
+            .then((r) => (r.ok ? r.json() : null))
+            .then((merged) => { if (merged) apply(merged); })
+            .catch(() => { /* offline; retried on the next change */ })
+            .finally(() => { inFlight = false; });
+    }
+
+    function setEnabled(on) {
+        set(ENABLED_KEY, on ? "true" : "false");
+        if (on) sync();
+    }
+
+    // The friend ID is only known once the server has sent a social update
+    function setFriendId(id) {
+        if (!id || id === get(FRIEND_ID_KEY)) return;
+        set(FRIEND_ID_KEY, id);
+        sync();
+    }
+
+    window.bpSettingsSync = { enabled, setEnabled, setFriendId, sync };
+
+    setInterval(() => { if (noteLocalChanges()) sync(); }, CHECK_INTERVAL_MS);
+    document.addEventListener("visibilitychange", () => {
+        if (document.visibilityState === "hidden" && noteLocalChanges()) sync();
+    });
+    sync();
+})();
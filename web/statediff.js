@@ -0,0 +1,67 @@
+// Breakpoint State Diff Inspector
+// Shows the field-by-field diff between consecutive game state snapshots,
+// or against a pinned one, when WASM is compiled with the "state-diff"
+// feature. Toggle with F7; Shift+F7 pins the latest snapshot as the base
+// (again to unpin). Hidden by default.
+(function () {
+    'use strict';
+
+    let panel = null;
+
+    function createPanel() {
+        panel = document.createElement('div');
+        panel.id = 'bp-statediff';
+        document.body.appendChild(panel);
+    }
+
+    function escapeHtml(text) {
+        return text.replace(/[&<>"]/g, c => ({ '&': '&amp;', '<': '&lt;', '>': '&gt;', '"': '&quot;' }[c]));
+    }
+
+    function lineClass(line) {
+        if (line.startsWith('+ ')) return 'bp-sd-added';
+        if (line.startsWith('- ')) return 'bp-sd-removed';
+        return '';
+    }
+
+    function render(d) {
+        let body;
+        if (d.lines == null) {
+            body = '<div class="bp-sd-line">state not inspectable</div>';
+        } else if (d.lines.length === 0) {
+            body = '<div class="bp-sd-line">no differences</div>';
+        } else {
+            body = d.lines.map(line =>
+                `<div class="bp-sd-line ${lineClass(line)}">${escapeHtml(line)}</div>`
+            ).join('');
+            if (d.more > 0) body += `<div class="bp-sd-line">… ${d.more} more</div>`;
+        }
+        panel.innerHTML = `
+            <div class="bp-sd-title">State diff: tick ${d.baseTick}${d.pinned ? ' (pinned)' : ''} → ${d.tick}</div>
+            ${body}
+        `;
+    }
+
+    // Receive diffs from WASM; null hides the panel
+    window._breakpointStateDiffUpdate = function (d) {
+        if (d) {
+            if (!panel) createPanel();
+            panel.style.display = 'block';
+            render(d);
+        } else if (panel) {
+            panel.style.display = 'none';
+        }
+    };
+
+    // Toggle with F7, pin with Shift+F7
+    document.addEventListener('keydown', function (e) {
+        if (e.code === 'F7') {
+            e.preventDefault();
+            if (e.shiftKey) {
+                if (window._bpPinStateDiff) window._bpPinStateDiff();
+            } else if (window._bpToggleStateDiff) {
+                window._bpToggleStateDiff();
+            }
+        }
+    });
+})();
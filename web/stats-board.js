@@ -0,0 +1,150 @@
+// Breakpoint stats board — renders the display frames pushed by
+// /api/v1/public/stats-board/stream. The server decides what to show and
+// when, so the page only draws the latest frame; EventSource reconnects on
+// its own when the server restarts.
+
+(function () {
+    "use strict";
+
+    const STREAM_URL = "/api/v1/public/stats-board/stream";
+
+    const pageEl = document.getElementById("board-page");
+    const dotsEl = document.getElementById("board-dots");
+    let lastFrame = null;
+
+    function t(key, params) {
+        return window.bpI18n ? window.bpI18n.t(key, params) : key;
+    }
+
+    function el(tag, className, text) {
+        const node = document.createElement(tag);
+        if (className) node.className = className;
+        if (text != null) node.textContent = String(text);
+        return node;
+    }
+
+    function heading(key) {
+        return el("h2", "board-heading", t(key));
+    }
+
+    function table(headers, rows) {
+        const tbl = el("table", "board-table");
+        const head = el("tr");
+        headers.forEach(([key, cls]) => head.appendChild(el("th", cls, t(key))));
+        tbl.appendChild(el("thead")).appendChild(head);
+        const body = tbl.appendChild(el("tbody"));
+        rows.forEach((cells) => {
+            const tr = body.appendChild(el("tr"));
+            cells.forEach(([text, cls]) => tr.appendChild(el("td", cls, text)));
+        });
+        return tbl;
+    }
+
+    function gameName(id) {
+        return t("game." + id);
+    }
+
+    function clockTime(unixSecs) {
+        return new Date(unixSecs * 1000).toLocaleString(undefined, {
+            weekday: "short",
+            hour: "2-digit",
+            minute: "2-digit",
+        });
+    }
+
+    function renderLeaderboard(frame) {
+        return [
+            heading("board.leaderboard"),
+            table(
+                [["board.player"], ["board.points", "num"], ["board.wins", "num"], ["board.matches", "num"]],
+                frame.rows.map((r) => [[r.name], [r.points, "num"], [r.wins, "num"], [r.matches, "num"]]),
+            ),
+        ];
+    }
+
+    function renderHighlights(frame) {
+        return [
+            heading("board.highlights"),
+            table(
+                [["board.game"], ["board.winner"], ["board.score", "num"], ["board.players", "num"]],
+                frame.matches.map((m) => [
+                    [gameName(m.game)],
+                    [m.winners.join(", ")],
+                    [m.winning_score, "num"],
+                    [m.players, "num"],
+                ]),
+            ),
+        ];
+    }
+
+    function renderAlerts(frame) {
+        const counts = el("ul", "board-counts");
+        [
+            ["board.pending_actions", frame.pending_actions, ""],
+            ["board.urgent_actions", frame.urgent_actions, frame.urgent_actions > 0 ? "urgent" : ""],
+            ["board.failing_sources", frame.failing_sources, frame.failing_sources > 0 ? "urgent" : ""],
+        ].forEach(([key, n, cls]) => {
+            const li = counts.appendChild(el("li", cls));
+            li.appendChild(el("strong", null, n));
+            li.appendChild(document.createTextNode(t(key)));
+        });
+        return [
+            heading("board.alerts"),
+            el("p", "board-status " + frame.status, t("board.status_" + frame.status)),
+            counts,
+        ];
+    }
+
+    function renderSchedule(frame) {
+        return [
+            heading("board.schedule"),
+            table(
+                [["board.when"], ["board.session"], ["board.games"]],
+                frame.upcoming.map((s) => [
+                    [clockTime(s.start_at)],
+                    [s.title],
+                    [s.games.map(gameName).join(", ")],
+                ]),
+            ),
+        ];
+    }
+
+    const RENDERERS = {
+        leaderboard: renderLeaderboard,
+        highlights: renderHighlights,
+        alerts: renderAlerts,
+        schedule: renderSchedule,
+    };
+
+    function renderDots(frame) {
+        dotsEl.replaceChildren();
+        for (let i = 0; i < frame.pages; i++) {
+            dotsEl.appendChild(el("span", i === frame.index ? "active" : null));
+        }
+    }
+
+    function render(frame) {
+        const renderer = RENDERERS[frame.page];
+        if (!renderer) return;
+        lastFrame = frame;
+        renderDots(frame);
+        // Restart the fade-in for each new page
+        pageEl.classList.add("fade");
+        void pageEl.offsetWidth;
+        pageEl.classList.remove("fade");
+        pageEl.replaceChildren(...renderer(frame));
+    }
+
+    const source = new EventSource(STREAM_URL);
+    source.addEventListener("frame", (e) => {
+        try {
+            render(JSON.parse(e.data));
+        } catch (err) {
+            console.warn("stats board: bad frame", err);
+        }
+    });
+
+    if (window.bpI18n) {
+        window.bpI18n.onChange(() => { if (lastFrame) render(lastFrame); });
+    }
+})();
@@ -7,16 +7,28 @@
     // ── DOM refs ────────────────────────────────────────
     const $ = (id) => document.getElementById(id);
 
+    // Localization (web/i18n.js); falls back to keys if it failed to load.
+    const i18n = window.bpI18n || { t: (k) => k, localize: (x) => x || "", onChange: () => {} };
+    const t = i18n.t;
+
     const lobbyScreen    = $("lobby-screen");
     const gameHud        = $("game-hud");
     const betweenRounds  = $("between-rounds");
+    const gameLoading    = $("game-loading");
+    const gameLoadingFill = $("game-loading-fill");
+    const gameLoadingDetail = $("game-loading-detail");
     const gameOver       = $("game-over");
     const playerNameInput = $("player-name");
     const joinCodeInput  = $("join-code");
+    const roomAliasInput = $("room-alias");
     const lobbyStatus    = $("lobby-status");
     const lobbyError     = $("lobby-error");
     const roomInfo       = $("room-info");
     const roomCodeValue  = $("room-code-value");
+    const roomAliasDisplay  = $("room-alias-display");
+    const roomAliasValue    = $("room-alias-value");
+    const roomSecretDisplay = $("room-secret-display");
+    const roomSecretValue   = $("room-secret-value");
     const playerList     = $("player-list");
     const btnCreate      = $("btn-create");
     const btnJoin        = $("btn-join");
@@ -25,13 +37,21 @@
     const btnReturnLobby = $("btn-return-lobby");
     const btnPlayAgain   = $("btn-play-again");
     const roundCountdown = $("round-countdown");
+    const btnNextRound   = $("btn-next-round");
+    const btnStartMatch  = $("btn-start-match");
     const gameOverCountdown = $("game-over-countdown");
     const hudGameName    = $("hud-game-name");
     const hudRound       = $("hud-round");
     const hudControls    = $("hud-controls");
+    const scoreboardEl   = $("scoreboard");
     const roundScores    = $("round-scores");
     const roundInfoEl    = $("round-info");
+    const roundHeatmap   = $("round-heatmap");
+    const roundHeatmapCanvas = $("round-heatmap-canvas");
+    const roundHeatmapCtx = roundHeatmapCanvas ? roundHeatmapCanvas.getContext("2d") : null;
     const finalScores    = $("final-scores");
+    const roundAnnouncer = $("round-announcer");
+    const gameOverAnnouncer = $("game-over-announcer");
     const tickerBar      = $("ticker-bar");
     const tickerText     = $("ticker-text");
     const toastContainer = $("toast-container");
@@ -44,37 +64,104 @@
     const gameSettings   = $("game-settings");
     const settPlatformer = $("settings-platformer");
     const settLasertag   = $("settings-lasertag");
+    const settGolf       = $("settings-golf");
+    const settTron       = $("settings-tron");
     let selectedGame = "mini-golf";
 
-    // ── Game descriptions ───────────────────────────────
-    const GAME_DESCS = {
-        "mini-golf": "2-8 players \u00b7 Turn-based \u00b7 10 courses",
-        "platform-racer": "2-6 players \u00b7 Race or Survive",
-        "laser-tag": "2-8 players \u00b7 FFA or Teams",
-        "tron": "2-8 players \u00b7 Light Cycles \u00b7 Bots available",
-    };
-
-    // Inject game descriptions into buttons
-    gameBtns.forEach((btn) => {
-        const desc = GAME_DESCS[btn.dataset.game];
-        if (desc) {
+    // ── Game names and descriptions ─────────────────────
+    // Rebuilt on language change; descriptions live under game_desc.<id>.
+    function renderGameButtons() {
+        gameBtns.forEach((btn) => {
+            const id = btn.dataset.game;
+            btn.textContent = t(`game.${id}`);
             const descEl = document.createElement("div");
             descEl.className = "game-desc";
-            descEl.textContent = desc;
+            descEl.textContent = t(`game_desc.${id}`);
             btn.appendChild(descEl);
+        });
+    }
+    renderGameButtons();
+    i18n.onChange(renderGameButtons);
+
+    // ── Language selector ───────────────────────────────
+    const langSelect = $("lang-select");
+    if (langSelect && i18n.LANGUAGES) {
+        for (const [code, name] of Object.entries(i18n.LANGUAGES)) {
+            const opt = document.createElement("option");
+            opt.value = code;
+            opt.textContent = name;
+            langSelect.appendChild(opt);
         }
+        i18n.onChange((lang) => { langSelect.value = lang; });
+        langSelect.addEventListener("change", () => i18n.setLanguage(langSelect.value));
+    }
+
+    // ── Crash report opt-in ─────────────────────────────
+    const settingsSync = window.bpSettingsSync;
+    const settingsSyncOptIn = $("settings-sync-opt-in");
+    if (settingsSyncOptIn && settingsSync) {
+        settingsSyncOptIn.checked = settingsSync.enabled();
+        settingsSyncOptIn.addEventListener("change", () => {
+            settingsSync.setEnabled(settingsSyncOptIn.checked);
+        });
+    }
+
+    const telemetryOptIn = $("telemetry-opt-in");
+    if (telemetryOptIn) {
+        telemetryOptIn.addEventListener("change", () => {
+            if (window._bpSetTelemetry) window._bpSetTelemetry(telemetryOptIn.checked);
+        });
+    }
+
+    // ── Replay viewer ───────────────────────────────────
+    const replayHud = $("replay-hud");
+    const replayLabel = $("replay-label");
+    const replayProgress = $("replay-progress");
+    const btnReplayPause = $("btn-replay-pause");
+    const btnDownloadReplay = $("btn-download-replay");
+    const btnDownloadInputs = $("btn-download-inputs");
+    const replayFile = $("replay-file");
+    $("btn-open-replay").addEventListener("click", () => replayFile.click());
+    replayFile.addEventListener("change", async () => {
+        const file = replayFile.files[0];
+        replayFile.value = "";
+        if (!file || !window._bpLoadReplay) return;
+        const buf = await file.arrayBuffer();
+        window._bpLoadReplay(new Uint8Array(buf));
+    });
+    btnDownloadInputs.addEventListener("click", () => {
+        const data = window._bpExportInputReplay ? window._bpExportInputReplay() : null;
+        if (!data) return;
+        const url = URL.createObjectURL(new Blob([data], { type: "application/octet-stream" }));
+        const link = document.createElement("a");
+        link.href = url;
+        link.download = "match.bpinputs";
+        link.click();
+        setTimeout(() => URL.revokeObjectURL(url), 0);
+    });
+    btnReplayPause.addEventListener("click", () => {
+        if (window._bpToggleReplayPause) window._bpToggleReplayPause();
+    });
+    $("btn-replay-exit").addEventListener("click", () => {
+        if (window._bpReturnToLobby) window._bpReturnToLobby();
     });
 
     function updateGameSettingsPanel() {
-        const panels = [settPlatformer, settLasertag];
+        const panels = [settPlatformer, settLasertag, settGolf, settTron];
         panels.forEach((p) => p && p.classList.add("hidden"));
 
         if (selectedGame === "platform-racer" && settPlatformer) {
             gameSettings.classList.remove("hidden");
             settPlatformer.classList.remove("hidden");
+        } else if (selectedGame === "mini-golf" && settGolf) {
+            gameSettings.classList.remove("hidden");
+            settGolf.classList.remove("hidden");
         } else if (selectedGame === "laser-tag" && settLasertag) {
             gameSettings.classList.remove("hidden");
             settLasertag.classList.remove("hidden");
+        } else if (selectedGame === "tron" && settTron) {
+            gameSettings.classList.remove("hidden");
+            settTron.classList.remove("hidden");
         } else {
             gameSettings.classList.add("hidden");
         }
@@ -106,8 +193,148 @@
     }
 
     bindSettingSelect("setting-platformer-mode", "mode");
+    bindSettingSelect("setting-golf-mode", "mode");
     bindSettingSelect("setting-lasertag-team-mode", "team_mode");
+    bindSettingSelect("setting-lasertag-team-select", "team_select");
     bindSettingSelect("setting-lasertag-arena-size", "arena_size");
+    bindSettingSelect("setting-tron-movement", "movement");
+    bindSettingSelect("setting-lasertag-late-join", "late_join");
+    bindSettingSelect("setting-tron-late-join", "late_join");
+
+    const courseVoteSelect = $("setting-platformer-course-vote");
+    if (courseVoteSelect) {
+        courseVoteSelect.addEventListener("change", () => {
+            if (window._bpSetGameSetting) {
+                window._bpSetGameSetting("course_vote", JSON.stringify(courseVoteSelect.value === "on"));
+            }
+        });
+    }
+
+    const lasertagFogSelect = $("setting-lasertag-fog");
+    if (lasertagFogSelect) {
+        lasertagFogSelect.addEventListener("change", () => {
+            if (window._bpSetGameSetting) {
+                window._bpSetGameSetting("fog_of_war", JSON.stringify(lasertagFogSelect.value === "on"));
+            }
+        });
+    }
+
+    const warmupSelect = $("setting-warmup");
+    if (warmupSelect) {
+        warmupSelect.addEventListener("change", () => {
+            if (window._bpSetGameSetting) {
+                window._bpSetGameSetting("warmup", JSON.stringify(warmupSelect.value === "on"));
+            }
+        });
+    }
+
+    // Only rooms this browser hosts record; the server keeps its own replays
+    const recordReplaySelect = $("setting-record-replay");
+    if (recordReplaySelect) {
+        recordReplaySelect.addEventListener("change", () => {
+            if (window._bpSetGameSetting) {
+                window._bpSetGameSetting(
+                    "record_replay",
+                    JSON.stringify(recordReplaySelect.value === "on"),
+                );
+            }
+        });
+    }
+
+    // Short rounds with near-instant restarts; "off" sends false
+    const microRoundsSelect = $("setting-micro-rounds");
+    if (microRoundsSelect) {
+        microRoundsSelect.addEventListener("change", () => {
+            if (window._bpSetGameSetting) {
+                const value = microRoundsSelect.value;
+                window._bpSetGameSetting("micro_rounds", JSON.stringify(value === "off" ? false : Number(value)));
+            }
+        });
+    }
+
+    const tronOvertimeSelect = $("setting-tron-overtime");
+    if (tronOvertimeSelect) {
+        tronOvertimeSelect.addEventListener("change", () => {
+            if (window._bpSetGameSetting) {
+                window._bpSetGameSetting("overtime", JSON.stringify(tronOvertimeSelect.value === "on"));
+            }
+        });
+    }
+
+    const tronDangerMapSelect = $("setting-tron-danger-map");
+    if (tronDangerMapSelect) {
+        tronDangerMapSelect.addEventListener("change", () => {
+            if (window._bpSetGameSetting) {
+                window._bpSetGameSetting("danger_map", JSON.stringify(tronDangerMapSelect.value === "on"));
+            }
+        });
+    }
+
+    const golfIntroSelect = $("setting-golf-course-intro");
+    if (golfIntroSelect) {
+        golfIntroSelect.addEventListener("change", () => {
+            if (window._bpSetGameSetting) {
+                window._bpSetGameSetting("course_intro", JSON.stringify(golfIntroSelect.value === "on"));
+            }
+        });
+    }
+
+    // Competitive lobbies can turn off the blind-shot aim indicator
+    const golfHintsSelect = $("setting-golf-shot-hints");
+    if (golfHintsSelect) {
+        golfHintsSelect.addEventListener("change", () => {
+            if (window._bpSetGameSetting) {
+                window._bpSetGameSetting("shot_hints", JSON.stringify(golfHintsSelect.value === "on"));
+            }
+        });
+    }
+
+    // Night holes can go without the blind-shot indicator
+    const golfNightHintsSelect = $("setting-golf-night-hints");
+    if (golfNightHintsSelect) {
+        golfNightHintsSelect.addEventListener("change", () => {
+            if (window._bpSetGameSetting) {
+                window._bpSetGameSetting("night_hints", JSON.stringify(golfNightHintsSelect.value === "on"));
+            }
+        });
+    }
+
+    // Casual lobbies can allow taking back a misclicked stroke
+    const golfUndoSelect = $("setting-golf-undo");
+    if (golfUndoSelect) {
+        golfUndoSelect.addEventListener("change", () => {
+            if (window._bpSetGameSetting) {
+                window._bpSetGameSetting("undo_window", JSON.stringify(Number(golfUndoSelect.value)));
+            }
+        });
+    }
+
+    // Stroke suggestions per hole, for lobbies with new players
+    const golfCaddieSelect = $("setting-golf-caddie");
+    if (golfCaddieSelect) {
+        golfCaddieSelect.addEventListener("change", () => {
+            if (window._bpSetGameSetting) {
+                window._bpSetGameSetting("caddie_hints", JSON.stringify(Number(golfCaddieSelect.value)));
+            }
+        });
+    }
+
+    // Round pacing goes out with the next game start
+    const pacingIntermission = $("setting-pacing-intermission");
+    const pacingAutoAdvance = $("setting-pacing-auto-advance");
+    const pacingMinResults = $("setting-pacing-min-results");
+    function sendPacing() {
+        if (window._bpSetPacing) {
+            window._bpSetPacing(
+                Number(pacingIntermission.value),
+                pacingAutoAdvance.value === "on",
+                Number(pacingMinResults.value),
+            );
+        }
+    }
+    [pacingIntermission, pacingAutoAdvance, pacingMinResults].forEach(el => {
+        if (el) el.addEventListener("change", sendPacing);
+    });
 
     // ── Button debounce utility ─────────────────────────
     function debounceBtn(btn, fn, ms) {
@@ -125,19 +352,163 @@
     debounceBtn(btnCreate, () => {
         syncPlayerName();
         if (window._bpSelectGame) window._bpSelectGame(selectedGame);
-        if (window._bpCreateRoom) window._bpCreateRoom();
+        if (window._bpCreateRoom) window._bpCreateRoom(roomAliasInput.value.trim());
     });
 
     debounceBtn(btnJoin, () => {
         syncPlayerName();
         const code = joinCodeInput.value.trim().toUpperCase();
         if (!code) {
-            lobbyError.textContent = "Enter a room code first";
+            lobbyError.textContent = t("status.enter_room_code");
             return;
         }
         if (window._bpJoinRoom) window._bpJoinRoom(code);
     });
 
+    // ── LAN and offline play ────────────────────────────
+    // The host's client runs the room; others reach it through a relay.
+    const relayUrlInput = $("relay-url");
+    const roomSecretInput = $("room-secret");
+    const lanEncrypt    = $("lan-encrypt");
+    const btnHostLan    = $("btn-host-lan");
+    const btnJoinLan    = $("btn-join-lan");
+    const btnOffline    = $("btn-play-offline");
+
+    debounceBtn(btnHostLan, () => {
+        const url = relayUrlInput.value.trim();
+        if (!url) {
+            lobbyError.textContent = t("ui.enter_relay_url");
+            return;
+        }
+        syncPlayerName();
+        if (window._bpSelectGame) window._bpSelectGame(selectedGame);
+        if (window._bpHostLocal) window._bpHostLocal(url, lanEncrypt.checked, roomAliasInput.value.trim());
+    });
+
+    debounceBtn(btnJoinLan, () => {
+        const url = relayUrlInput.value.trim();
+        const code = joinCodeInput.value.trim().toUpperCase();
+        if (!url) {
+            lobbyError.textContent = t("ui.enter_relay_url");
+            return;
+        }
+        if (!code) {
+            lobbyError.textContent = t("status.enter_room_code");
+            return;
+        }
+        syncPlayerName();
+        if (window._bpUseRelay) window._bpUseRelay(url, roomSecretInput.value.trim());
+        if (window._bpJoinRoom) window._bpJoinRoom(code);
+    });
+
+    debounceBtn(btnOffline, () => {
+        syncPlayerName();
+        if (window._bpSelectGame) window._bpSelectGame(selectedGame);
+        if (window._bpHostLocal) window._bpHostLocal("", false, "");
+    });
+
+    // ── Scheduled game nights ───────────────────────────
+    // Public, read-only listing; polled while the lobby is open.
+    const scheduleSection = $("schedule-section");
+    const scheduleList    = $("schedule-list");
+    let schedules = [];
+
+    function renderSchedules() {
+        scheduleSection.classList.toggle("hidden", schedules.length === 0);
+        scheduleList.innerHTML = "";
+        for (const s of schedules) {
+            const li = document.createElement("li");
+            li.className = "schedule-item";
+            const when = new Date(s.start_at * 1000).toLocaleString(i18n.language(), {
+                weekday: "short", hour: "2-digit", minute: "2-digit",
+            });
+            const games = s.games.map((g) => t(`game.${g}`)).join(", ");
+            li.innerHTML =
+                `<div class="schedule-title">${escapeHtml(s.title)}</div>` +
+                `<div class="schedule-meta">${escapeHtml(when)}${games ? " \u00b7 " + escapeHtml(games) : ""}</div>`;
+
+            const actions = document.createElement("div");
+            actions.className = "schedule-actions";
+            if (s.room_code) {
+                const join = document.createElement("button");
+                join.className = "btn btn-secondary";
+                join.textContent = t("ui.join");
+                join.addEventListener("click", () => {
+                    joinCodeInput.value = s.room_code;
+                    btnJoin.click();
+                });
+                actions.appendChild(join);
+            }
+            const ics = document.createElement("a");
+            ics.className = "schedule-ics";
+            ics.href = `api/v1/public/schedules/${encodeURIComponent(s.id)}/ics`;
+            ics.textContent = t("ui.add_to_calendar");
+            actions.appendChild(ics);
+            li.appendChild(actions);
+            scheduleList.appendChild(li);
+        }
+    }
+
+    function fetchSchedules() {
+        if (lobbyScreen.classList.contains("hidden")) return;
+        fetch("api/v1/public/schedules")
+            .then((r) => r.ok ? r.json() : { schedules: [] })
+            .then((data) => { schedules = data.schedules || []; renderSchedules(); })
+            .catch(() => { /* server without schedules — leave hidden */ });
+    }
+    fetchSchedules();
+    setInterval(fetchSchedules, 60000);
+    i18n.onChange(renderSchedules);
+
+    // ── Season banner ───────────────────────────────────
+    // The week's featured game and standings, polled like schedules.
+    // Leaders can opt their room into featured mode.
+    const seasonBanner    = $("season-banner");
+    const seasonFeatured  = $("season-featured");
+    const seasonStandings = $("season-standings");
+    const featuredToggle  = $("setting-featured");
+    let season = null;
+
+    function renderSeason() {
+        seasonBanner.classList.toggle("hidden", !season);
+        if (!season) return;
+        $("season-name").textContent = season.name;
+        const week = season.featured;
+        seasonFeatured.classList.toggle("hidden", !week);
+        if (week) {
+            $("season-week").textContent = t("ui.season_week", {
+                week: week.week, weeks: week.weeks, title: week.title, game: t(`game.${week.game}`),
+            });
+            const ends = new Date(week.ends_at * 1000).toLocaleString(i18n.language(), {
+                weekday: "short", hour: "2-digit", minute: "2-digit",
+            });
+            $("season-ends").textContent = t("ui.season_ends", { when: ends });
+        } else if (featuredToggle.checked) {
+            featuredToggle.checked = false;
+            if (window._bpSetFeatured) window._bpSetFeatured(false);
+        }
+        seasonStandings.innerHTML = "";
+        for (const s of season.standings.slice(0, 5)) {
+            const li = document.createElement("li");
+            li.textContent = `${s.name} \u00b7 ${t("ui.season_points", { points: s.points })}`;
+            seasonStandings.appendChild(li);
+        }
+    }
+
+    function fetchSeason() {
+        if (lobbyScreen.classList.contains("hidden")) return;
+        fetch("api/v1/public/season")
+            .then((r) => r.ok ? r.json() : null)
+            .then((data) => { season = data; renderSeason(); })
+            .catch(() => { /* server without a season — leave hidden */ });
+    }
+    featuredToggle.addEventListener("change", () => {
+        if (window._bpSetFeatured) window._bpSetFeatured(featuredToggle.checked);
+    });
+    fetchSeason();
+    setInterval(fetchSeason, 60000);
+    i18n.onChange(renderSeason);
+
     // Allow pressing Enter on join code input
     joinCodeInput.addEventListener("keydown", (e) => {
         if (e.key === "Enter") btnJoin.click();
@@ -159,6 +530,15 @@
         if (window._bpReturnToLobby) window._bpReturnToLobby();
     });
 
+    btnNextRound.addEventListener("click", () => {
+        if (window._bpAdvanceRound) window._bpAdvanceRound();
+    });
+
+    // Ends the warmup: round 0 advances to the first real round
+    btnStartMatch.addEventListener("click", () => {
+        if (window._bpAdvanceRound) window._bpAdvanceRound();
+    });
+
     btnDashboard.addEventListener("click", () => {
         if (window._bpToggleDashboard) window._bpToggleDashboard();
     });
@@ -201,24 +581,18 @@
         }
     });
 
-    // ── Controls hints per game ─────────────────────────
-    const CONTROLS = {
-        "mini-golf": "Click to aim & shoot | Power = distance from ball",
-        "platform-racer": "WASD / Arrows = Move | Space = Jump | E = Use Power-Up",
-        "laser-tag": "WASD = Move | Mouse = Aim | Click = Fire | E = Power-Up",
-        "tron": "A/D or Left/Right = Turn | Space = Brake",
-    };
-
-    // ── Game name display ───────────────────────────────
-    const GAME_NAMES = {
-        "mini-golf": "Mini Golf",
-        "Golf": "Mini Golf",
-        "platform-racer": "Platform Racer",
-        "Platformer": "Platform Racer",
-        "laser-tag": "Laser Tag",
-        "LaserTag": "Laser Tag",
-        "tron": "Tron",
-        "Tron": "Tron",
+    // ── Game ID normalization ───────────────────────────
+    // Rust reports either the lobby ID or the GameId variant name; locale
+    // keys (game.*, controls.*) use the lobby ID.
+    const GAME_KEYS = {
+        "mini-golf": "mini-golf",
+        "Golf": "mini-golf",
+        "platform-racer": "platform-racer",
+        "Platformer": "platform-racer",
+        "laser-tag": "laser-tag",
+        "LaserTag": "laser-tag",
+        "tron": "tron",
+        "Tron": "tron",
     };
 
     // ── State update from WASM ──────────────────────────
@@ -227,14 +601,22 @@
     window._breakpointUpdate = function (state) {
         updateScreens(state);
         updateLobby(state);
+        updateSocial(state);
+        updateTutorials(state);
+        updateAnnouncements(state);
         updateHud(state);
         updateGolfHud(state);
         updatePlatformerHud(state);
         updateLasertagHud(state);
         updateTronHud(state);
+        updateReplayHud(state);
+        updateCourseVote(state);
+        updateTeamDraft(state);
         updateScoreScreens(state);
+        updateGameLoading(state);
         updateOverlay(state);
         updateMuteBtn(state);
+        if (telemetryOptIn) telemetryOptIn.checked = !!state.telemetryOptIn;
         prevState = state;
     };
 
@@ -261,25 +643,27 @@
         const nextRetrySecs = info && info.nextRetrySecs;
         if (typeof attempt === "number" && typeof maxAttempts === "number") {
             if (attempt >= maxAttempts) {
-                disconnectBanner.textContent = "Connection lost. ";
+                disconnectBanner.textContent = t("ui.connection_lost");
                 const rejoinBtn = document.createElement("button");
                 rejoinBtn.id = "dc-rejoin";
                 rejoinBtn.className = "dc-rejoin-btn";
-                rejoinBtn.textContent = "Return to Lobby";
+                rejoinBtn.textContent = t("ui.return_to_lobby");
                 rejoinBtn.addEventListener("click", () => {
                     if (window._bpReturnToLobby) window._bpReturnToLobby();
                     disconnectBanner.classList.add("hidden");
                 });
                 disconnectBanner.appendChild(rejoinBtn);
             } else {
-                const retryText = typeof nextRetrySecs === "number" && nextRetrySecs > 0
-                    ? `, retrying in ${Math.ceil(nextRetrySecs)}s`
-                    : "";
-                disconnectBanner.textContent =
-                    `Connection lost. Reconnecting (attempt ${attempt + 1}/${maxAttempts}${retryText})...`;
+                const params = { attempt: attempt + 1, max: maxAttempts };
+                if (typeof nextRetrySecs === "number" && nextRetrySecs > 0) {
+                    params.secs = Math.ceil(nextRetrySecs);
+                    disconnectBanner.textContent = t("ui.reconnecting_attempt_retry", params);
+                } else {
+                    disconnectBanner.textContent = t("ui.reconnecting_attempt", params);
+                }
             }
         } else {
-            disconnectBanner.textContent = "Connection lost. Reconnecting...";
+            disconnectBanner.textContent = t("ui.reconnecting");
         }
     };
 
@@ -293,8 +677,10 @@
 
         lobbyScreen.classList.toggle("hidden", s !== "Lobby");
         gameHud.classList.toggle("hidden", s !== "InGame");
+        gameLoading.classList.toggle("hidden", s !== "Loading");
         betweenRounds.classList.toggle("hidden", s !== "BetweenRounds");
         gameOver.classList.toggle("hidden", s !== "GameOver");
+        replayHud.classList.toggle("hidden", s !== "Replay");
 
         // Focus trap: move focus into visible modal
         if (s === "BetweenRounds") {
@@ -313,7 +699,34 @@
         }
     }
 
+    // Progress of the game assets fetched before play starts
+    function updateGameLoading(state) {
+        const loading = state.loading;
+        if (!loading) return;
+        const pct = loading.total > 0 ? Math.round((loading.loaded / loading.total) * 100) : 100;
+        gameLoadingFill.style.width = `${pct}%`;
+        gameLoadingDetail.textContent = t("ui.loading_assets", {
+            loaded: loading.loaded,
+            total: loading.total,
+        });
+    }
+
     // ── Lobby ───────────────────────────────────────────
+    const BOT_DIFFICULTIES = ["easy", "normal", "hard", "mirror"];
+    let lastPlayerListKey = "";
+
+    // Leaders pick each bot's difficulty; everyone else sees it as a badge
+    function botDifficultyHtml(p, isLeader) {
+        const label = (d) => escapeHtml(t(`ui.bot_difficulty_${d}`));
+        if (!isLeader) {
+            return `<span class="bot-badge">${label(p.botDifficulty)}</span>`;
+        }
+        const options = BOT_DIFFICULTIES.map((d) =>
+            `<option value="${d}"${d === p.botDifficulty ? " selected" : ""}>${label(d)}</option>`
+        ).join("");
+        return `<select class="bot-difficulty" data-bot-id="${p.id}" aria-label="${escapeHtml(t("ui.bot_difficulty"))}">${options}</select>`;
+    }
+
     function updateLobby(state) {
         if (state.appState !== "Lobby") return;
         const lobby = state.lobby;
@@ -329,8 +742,8 @@
         }
 
         // Status/error messages
-        lobbyStatus.textContent = lobby.statusMessage || "";
-        lobbyError.textContent = lobby.errorMessage || "";
+        lobbyStatus.textContent = i18n.localize(lobby.statusMessage);
+        lobbyError.textContent = i18n.localize(lobby.errorMessage);
 
         // Room info visibility
         if (lobby.connected && lobby.roomCode) {
@@ -340,29 +753,87 @@
             // Add copy button if not present
             ensureCopyButton(lobby.roomCode);
 
-            // Player list
-            let html = "";
-            for (const p of lobby.players) {
-                const botTag = p.isBot ? '<span class="bot-badge">[BOT]</span>' : "";
-                const removeBtn = (lobby.isLeader && p.isBot)
-                    ? `<button class="bot-remove-btn" data-bot-id="${p.id}">Remove</button>`
-                    : "";
-                html += `<div class="player-item">
-                    <span>${escapeHtml(p.name)}</span>
-                    ${botTag}
-                    ${p.isLeader ? '<span class="leader-badge">Leader</span>' : ""}
-                    ${removeBtn}
-                </div>`;
-            }
-            playerList.innerHTML = html;
+            // Vanity alias others can join with instead of the code
+            roomAliasDisplay.classList.toggle("hidden", !lobby.roomAlias);
+            roomAliasValue.textContent = lobby.roomAlias || "";
+
+            // Encrypted relay rooms: players need the secret to join
+            roomSecretDisplay.classList.toggle("hidden", !lobby.roomSecret);
+            roomSecretValue.textContent = lobby.roomSecret || "";
+
+            // Player list, rebuilt only when it changes so an open
+            // difficulty dropdown isn't replaced under the pointer
+            const playerListKey = JSON.stringify([i18n.language, lobby.isLeader, lobby.players]);
+            if (playerListKey !== lastPlayerListKey) {
+                lastPlayerListKey = playerListKey;
+                let html = "";
+                for (const p of lobby.players) {
+                    const botTag = p.isBot ? `<span class="bot-badge">${escapeHtml(t("ui.bot_tag"))}</span>`
+                        : p.externalBot ? `<span class="bot-badge">${escapeHtml(t("ui.external_bot_tag"))}</span>` : "";
+                    const removeBtn = (lobby.isLeader && p.isBot)
+                        ? `<button class="bot-remove-btn" data-bot-id="${p.id}">${escapeHtml(t("ui.remove"))}</button>`
+                        : "";
+                    // Hot-seat guests on this keyboard show their keys
+                    const isMyGuest = p.hotSeatOf != null && p.hotSeatOf === lobby.localPlayerId;
+                    const seatTag = p.hotSeatOf != null
+                        ? `<span class="seat-badge">${escapeHtml(p.seat ? t(`ui.seat_keys_${p.seat}`) : t("ui.hot_seat_tag"))}</span>`
+                        : "";
+                    const removeGuestBtn = isMyGuest
+                        ? `<button class="guest-remove-btn" data-guest-id="${p.id}">${escapeHtml(t("ui.remove"))}</button>`
+                        : "";
+                    // Players can swap their name (or a guest's) for another suggestion
+                    const rerollBtn = (p.id === lobby.localPlayerId || isMyGuest)
+                        ? `<button class="name-reroll-btn" data-player-id="${p.id}">${escapeHtml(t("ui.reroll_name"))}</button>`
+                        : "";
+                    const sessionTag = p.sessionScore != null
+                        ? `<span class="session-score">${escapeHtml(t("ui.session_score", { score: p.sessionScore }))}</span>`
+                        : "";
+                    html += `<div class="player-item">
+                        <span>${escapeHtml(p.name)}</span>
+                        ${sessionTag}
+                        ${botTag}
+                        ${seatTag}
+                        ${p.isBot ? botDifficultyHtml(p, lobby.isLeader) : ""}
+                        ${p.isLeader ? `<span class="leader-badge">${escapeHtml(t("ui.leader"))}</span>` : ""}
+                        ${removeBtn}
+                        ${rerollBtn}
+                        ${removeGuestBtn}
+                    </div>`;
+                }
+                playerList.innerHTML = html;
 
-            // Bind remove-bot buttons
-            playerList.querySelectorAll(".bot-remove-btn").forEach((btn) => {
-                btn.addEventListener("click", () => {
-                    const botId = Number(btn.dataset.botId);
-                    if (window._bpRemoveBot) window._bpRemoveBot(botId);
+                // Bind remove-bot buttons
+                playerList.querySelectorAll(".bot-remove-btn").forEach((btn) => {
+                    btn.addEventListener("click", () => {
+                        const botId = Number(btn.dataset.botId);
+                        if (window._bpRemoveBot) window._bpRemoveBot(botId);
+                    });
                 });
-            });
+                playerList.querySelectorAll(".guest-remove-btn").forEach((btn) => {
+                    btn.addEventListener("click", () => {
+                        const guestId = Number(btn.dataset.guestId);
+                        if (window._bpRemoveLocalPlayer) window._bpRemoveLocalPlayer(guestId);
+                    });
+                });
+                playerList.querySelectorAll(".name-reroll-btn").forEach((btn) => {
+                    btn.addEventListener("click", () => {
+                        const playerId = Number(btn.dataset.playerId);
+                        if (window._bpRerollName) window._bpRerollName(playerId);
+                    });
+                });
+                playerList.querySelectorAll(".bot-difficulty").forEach((sel) => {
+                    sel.addEventListener("change", () => {
+                        const botId = Number(sel.dataset.botId);
+                        if (window._bpSetBotDifficulty) window._bpSetBotDifficulty(botId, sel.value);
+                    });
+                });
+            }
+
+            updateJoinQueue(lobby);
+            updateRoomTransfer(lobby);
+            updateEventFilter(lobby);
+            updateRoomConfig(lobby);
+            updateLobbyToy(state.lobbyToy);
 
             // Add Bot button (leader only)
             let addBotBtn = $("btn-add-bot");
@@ -371,27 +842,57 @@
                     addBotBtn = document.createElement("button");
                     addBotBtn.id = "btn-add-bot";
                     addBotBtn.className = "btn-secondary";
-                    addBotBtn.textContent = "Add Bot";
                     addBotBtn.addEventListener("click", () => {
                         if (window._bpAddBot) window._bpAddBot();
                     });
                     btnStart.parentNode.insertBefore(addBotBtn, btnStart);
                 }
+                addBotBtn.textContent = t("ui.add_bot");
                 addBotBtn.classList.remove("hidden");
             } else if (addBotBtn) {
                 addBotBtn.classList.add("hidden");
             }
 
+            // Add Local Player button: another player on this keyboard
+            let addLocalBtn = $("btn-add-local-player");
+            if (lobby.canAddLocalPlayer) {
+                if (!addLocalBtn) {
+                    addLocalBtn = document.createElement("button");
+                    addLocalBtn.id = "btn-add-local-player";
+                    addLocalBtn.className = "btn-secondary";
+                    addLocalBtn.addEventListener("click", () => {
+                        if (window._bpAddLocalPlayer) window._bpAddLocalPlayer();
+                    });
+                    btnStart.parentNode.insertBefore(addLocalBtn, btnStart);
+                }
+                addLocalBtn.textContent = t("ui.add_local_player");
+                addLocalBtn.classList.remove("hidden");
+            } else if (addLocalBtn) {
+                addLocalBtn.classList.add("hidden");
+            }
+
             // Start button (leader only)
             btnStart.classList.toggle("hidden", !lobby.isLeader);
 
             // Disable create/join after connected (use CSS :disabled)
             btnCreate.disabled = true;
             btnJoin.disabled = true;
+            btnHostLan.disabled = true;
+            btnJoinLan.disabled = true;
+            btnOffline.disabled = true;
         } else {
             roomInfo.classList.add("hidden");
             btnCreate.disabled = false;
             btnJoin.disabled = false;
+            btnHostLan.disabled = false;
+            btnJoinLan.disabled = false;
+            btnOffline.disabled = false;
+        }
+
+        // The server can line up a game, e.g. from a scheduled playlist
+        if (lobby.selectedGame && lobby.selectedGame !== selectedGame) {
+            selectedGame = lobby.selectedGame;
+            updateGameSettingsPanel();
         }
 
         // Highlight selected game button
@@ -403,6 +904,461 @@
         });
     }
 
+    // ── Friends, recent players, and invites ────────────
+    const friendsSection = $("friends-section");
+    const friendIdValue  = $("friend-id-value");
+    const friendIdInput  = $("friend-id-input");
+    const sharePresence  = $("share-presence");
+    const btnAddFriend   = $("btn-add-friend");
+    const friendsList    = $("friends-list");
+    const recentList     = $("recent-list");
+    const invitePanel    = $("invite-panel");
+    let lastSocialKey = "";
+    let lastInvitesKey = "";
+    const announcementBanner = $("announcement-banner");
+    // Dismissed per page load, keyed by id and text so an edited MOTD reappears
+    const dismissedAnnouncements = new Set();
+    let lastAnnouncementsKey = "";
+    let socialInRoom = false;
+    let socialRoomCode = null;
+
+    sharePresence.addEventListener("change", () => {
+        if (window._bpSetPresenceSharing) window._bpSetPresenceSharing(sharePresence.checked);
+    });
+
+    btnAddFriend.addEventListener("click", () => {
+        const id = friendIdInput.value.trim().toLowerCase();
+        if (!id || !window._bpSetFriend) return;
+        window._bpSetFriend(id, true);
+        friendIdInput.value = "";
+    });
+
+    // Where a friend who shares their presence is playing, if anywhere.
+    function presenceText(entry) {
+        if (!entry.roomCode) return "";
+        return entry.game
+            ? t("ui.friend_playing", { code: entry.roomCode, game: t("game." + entry.game) })
+            : t("ui.friend_in_room", { code: entry.roomCode });
+    }
+
+    function socialRow(entry, actions) {
+        const li = document.createElement("li");
+        li.className = "social-item";
+        const dot = `<span class="presence-dot${entry.online ? " online" : ""}" title="${escapeHtml(t(entry.online ? "ui.online" : "ui.offline"))}"></span>`;
+        const where = presenceText(entry);
+        const detail = where ? `<span class="social-presence">${escapeHtml(where)}</span>` : "";
+        li.innerHTML = `${dot}<span class="social-name">${escapeHtml(entry.name || entry.id)}${detail}</span>`;
+        for (const [label, fn] of actions) {
+            const btn = document.createElement("button");
+            btn.className = "social-btn";
+            btn.textContent = label;
+            btn.addEventListener("click", fn);
+            li.appendChild(btn);
+        }
+        return li;
+    }
+
+    function renderSocial(social) {
+        friendIdValue.textContent = social.yourId;
+        sharePresence.checked = !!social.sharingPresence;
+        friendsList.innerHTML = "";
+        const friendIds = new Set();
+        for (const f of social.friends) {
+            friendIds.add(f.id);
+            const actions = [];
+            // Follow a friend into their room; mid-game, that means watching
+            if (f.roomCode && f.roomCode !== socialRoomCode) {
+                actions.push([t(f.game ? "ui.watch" : "ui.join"), () => joinRoomCode(f.roomCode)]);
+            }
+            if (socialInRoom && f.online) {
+                actions.push([t("ui.invite"), () => window._bpInviteFriend && window._bpInviteFriend(f.id)]);
+            }
+            actions.push([t("ui.remove"), () => window._bpSetFriend && window._bpSetFriend(f.id, false)]);
+            friendsList.appendChild(socialRow(f, actions));
+        }
+        if (social.friends.length === 0) {
+            const li = document.createElement("li");
+            li.className = "social-empty";
+            li.textContent = t("ui.no_friends");
+            friendsList.appendChild(li);
+        }
+        recentList.innerHTML = "";
+        for (const r of social.recent) {
+            const actions = friendIds.has(r.id)
+                ? []
+                : [[t("ui.add_friend"), () => window._bpSetFriend && window._bpSetFriend(r.id, true)]];
+            recentList.appendChild(socialRow(r, actions));
+        }
+    }
+
+    function joinRoomCode(roomCode) {
+        if (prevState && prevState.lobby.connected) {
+            // Already in a room: a fresh page load joins the other one
+            window.location.href = `?room=${encodeURIComponent(roomCode)}`;
+        } else if (window._bpJoinRoom) {
+            syncPlayerName();
+            window._bpJoinRoom(roomCode);
+        }
+    }
+
+    function joinInvite(invite) {
+        if (window._bpDismissInvite) window._bpDismissInvite(invite.fromId);
+        joinRoomCode(invite.roomCode);
+    }
+
+    function renderInvites(invites) {
+        invitePanel.classList.toggle("hidden", invites.length === 0);
+        invitePanel.innerHTML = "";
+        for (const inv of invites) {
+            const div = document.createElement("div");
+            div.className = "invite-item";
+            div.innerHTML = `<span>${escapeHtml(t("ui.invite_from", { name: inv.fromName, code: inv.roomCode }))}</span>`;
+            const join = document.createElement("button");
+            join.className = "social-btn";
+            join.textContent = t("ui.join");
+            join.addEventListener("click", () => joinInvite(inv));
+            const dismiss = document.createElement("button");
+            dismiss.className = "social-btn";
+            dismiss.textContent = t("ui.dismiss");
+            dismiss.setAttribute("aria-label", t("ui.dismiss"));
+            dismiss.addEventListener("click", () => {
+                if (window._bpDismissInvite) window._bpDismissInvite(inv.fromId);
+            });
+            div.appendChild(join);
+            div.appendChild(dismiss);
+            invitePanel.appendChild(div);
+        }
+    }
+
+    function renderAnnouncements(announcements) {
+        const shown = announcements.filter(
+            (a) => !dismissedAnnouncements.has(`${a.id}:${a.message}`)
+        );
+        announcementBanner.classList.toggle("hidden", shown.length === 0);
+        announcementBanner.innerHTML = "";
+        for (const a of shown) {
+            const div = document.createElement("div");
+            div.className = `announcement-item severity-${a.severity}`;
+            div.setAttribute("role", a.severity === "critical" ? "alert" : "status");
+            const text = document.createElement("span");
+            text.textContent = a.message;
+            const dismiss = document.createElement("button");
+            dismiss.className = "social-btn";
+            dismiss.textContent = t("ui.dismiss");
+            dismiss.setAttribute("aria-label", t("ui.dismiss_announcement"));
+            dismiss.addEventListener("click", () => {
+                dismissedAnnouncements.add(`${a.id}:${a.message}`);
+                renderAnnouncements(announcements);
+            });
+            div.appendChild(text);
+            div.appendChild(dismiss);
+            announcementBanner.appendChild(div);
+        }
+    }
+
+    function updateAnnouncements(state) {
+        const announcements = state.lobby.announcements || [];
+        const key = JSON.stringify([announcements, i18n.language && i18n.language()]);
+        if (key !== lastAnnouncementsKey) {
+            lastAnnouncementsKey = key;
+            renderAnnouncements(announcements);
+        }
+    }
+
+    // Rebuilt only when the data changes, so buttons stay clickable.
+    function updateSocial(state) {
+        const lobby = state.lobby;
+        const social = lobby.social;
+        socialInRoom = lobby.connected && !!lobby.roomCode;
+        socialRoomCode = socialInRoom ? lobby.roomCode : null;
+        friendsSection.classList.toggle("hidden", !social);
+        if (social && settingsSync) settingsSync.setFriendId(social.yourId);
+        const socialKey = JSON.stringify([social, socialInRoom, i18n.language && i18n.language()]);
+        if (social && socialKey !== lastSocialKey) {
+            lastSocialKey = socialKey;
+            renderSocial(social);
+        }
+        const invitesKey = JSON.stringify([lobby.invites, i18n.language && i18n.language()]);
+        if (invitesKey !== lastInvitesKey) {
+            lastInvitesKey = invitesKey;
+            renderInvites(lobby.invites || []);
+        }
+    }
+
+    // ── Tutorial checklists ──────────────────────────────
+    const TUTORIAL_GAMES = ["mini-golf", "platform-racer", "laser-tag", "tron"];
+    const tutorialList = $("tutorial-list");
+    let lastTutorialKey = "";
+
+    // One row per game: its "Play tutorial" button while outside a room,
+    // and the server's checklist once one has arrived.
+    function renderTutorials(checklists, inRoom) {
+        tutorialList.innerHTML = "";
+        for (const game of TUTORIAL_GAMES) {
+            const checklist = checklists.find((c) => c.game === game);
+            const li = document.createElement("li");
+            li.className = "social-item tutorial-item";
+            const done = checklist && checklist.steps.every((s) => s.done);
+            li.innerHTML = `<span class="social-name">${escapeHtml(t("game." + game))}${done ? " ✓" : ""}</span>`;
+            if (!inRoom) {
+                const play = document.createElement("button");
+                play.className = "social-btn";
+                play.dataset.testid = `btn-tutorial-${game}`;
+                play.textContent = t("ui.play_tutorial");
+                play.addEventListener("click", () => {
+                    if (!window._bpStartTutorial) return;
+                    syncPlayerName();
+                    window._bpStartTutorial(game);
+                });
+                li.appendChild(play);
+            }
+            if (checklist) {
+                const steps = document.createElement("ul");
+                steps.className = "tutorial-steps";
+                for (const step of checklist.steps) {
+                    const item = document.createElement("li");
+                    item.className = step.done ? "tutorial-step done" : "tutorial-step";
+                    item.textContent = `${step.done ? "☑" : "☐"} ${t(step.text)}`;
+                    steps.appendChild(item);
+                }
+                li.appendChild(steps);
+            }
+            tutorialList.appendChild(li);
+        }
+    }
+
+    function updateTutorials(state) {
+        const lobby = state.lobby;
+        const inRoom = lobby.connected && !!lobby.roomCode;
+        const checklists = lobby.tutorials || [];
+        const key = JSON.stringify([checklists, inRoom, i18n.language && i18n.language()]);
+        if (key !== lastTutorialKey) {
+            lastTutorialKey = key;
+            renderTutorials(checklists, inRoom);
+        }
+    }
+
+    // ── Join queue ───────────────────────────────────────
+    const joinQueueEl    = $("join-queue");
+    const joinApprovalRow = $("join-approval-row");
+    const joinApproval   = $("join-approval");
+    const joinQueueTitle = $("join-queue-title");
+    const joinQueueList  = $("join-queue-list");
+    let lastJoinQueueKey = "";
+
+    joinApproval.addEventListener("change", () => {
+        if (window._bpSetJoinApproval) window._bpSetJoinApproval(joinApproval.checked);
+    });
+
+    joinQueueList.addEventListener("click", (e) => {
+        const btn = e.target.closest("[data-ticket]");
+        if (!btn || !window._bpAnswerJoin) return;
+        window._bpAnswerJoin(Number(btn.dataset.ticket), btn.dataset.admit === "true");
+    });
+
+    function updateJoinQueue(lobby) {
+        const queue = lobby.joinQueue || { requireApproval: false, entries: [] };
+        joinApprovalRow.classList.toggle("hidden", !lobby.isLeader);
+        joinApproval.checked = queue.requireApproval;
+        joinQueueEl.classList.toggle("hidden", !lobby.isLeader && queue.entries.length === 0);
+
+        const key = JSON.stringify([queue.entries, lobby.isLeader, i18n.language && i18n.language()]);
+        if (key === lastJoinQueueKey) return;
+        lastJoinQueueKey = key;
+
+        joinQueueTitle.textContent = queue.entries.length
+            ? t("ui.join_queue_waiting", { count: queue.entries.length })
+            : "";
+        if (!lobby.isLeader) {
+            joinQueueList.innerHTML = "";
+            return;
+        }
+        joinQueueList.innerHTML = queue.entries.map((e) => {
+            const admit = e.approved
+                ? `<span class="join-queue-approved">${escapeHtml(t("ui.join_queue_approved"))}</span>`
+                : `<button class="join-queue-btn" data-ticket="${e.ticket}" data-admit="true">${escapeHtml(t("ui.admit"))}</button>`;
+            return `<div class="player-item">
+                <span>${escapeHtml(e.name)}</span>
+                ${admit}
+                <button class="join-queue-btn" data-ticket="${e.ticket}" data-admit="false">${escapeHtml(t("ui.deny"))}</button>
+            </div>`;
+        }).join("");
+    }
+
+    // ── Room merges and splits ───────────────────────────
+    const roomTransferEl   = $("room-transfer");
+    const roomTransferText = $("room-transfer-text");
+    const roomReshapeEl    = $("room-reshape");
+    const mergeRoomCode    = $("merge-room-code");
+
+    $("btn-accept-transfer").addEventListener("click", () => {
+        if (window._bpAcceptTransfer) window._bpAcceptTransfer();
+    });
+    $("btn-decline-transfer").addEventListener("click", () => {
+        if (window._bpDeclineTransfer) window._bpDeclineTransfer();
+    });
+    $("btn-merge-room").addEventListener("click", () => {
+        if (window._bpMergeRoom && mergeRoomCode.value.trim()) {
+            window._bpMergeRoom(mergeRoomCode.value);
+            mergeRoomCode.value = "";
+        }
+    });
+    $("btn-split-room").addEventListener("click", () => {
+        if (window._bpSplitRoom) window._bpSplitRoom();
+    });
+
+    function updateRoomTransfer(lobby) {
+        const offer = lobby.transferOffer;
+        roomTransferEl.classList.toggle("hidden", !offer);
+        if (offer) {
+            const key = offer.reason === "Split" ? "ui.transfer_split" : "ui.transfer_merge";
+            roomTransferText.textContent = t(key, { code: offer.roomCode, secs: offer.secsLeft });
+        }
+        roomReshapeEl.classList.toggle("hidden", !lobby.isLeader || !lobby.connected);
+    }
+
+    // ── Lobby toy ────────────────────────────────────────
+    const lobbyToyEl     = $("lobby-toy");
+    const lobbyToyArea   = $("lobby-toy-area");
+    const lobbyToyTarget = $("lobby-toy-target");
+    const lobbyToyHits   = $("lobby-toy-hits");
+    const lobbyToyBoard  = $("lobby-toy-board");
+    let lastLobbyToyKey = "";
+
+    lobbyToyArea.addEventListener("pointerdown", (e) => {
+        if (!window._bpLobbyToyClick) return;
+        const rect = lobbyToyArea.getBoundingClientRect();
+        window._bpLobbyToyClick(
+            (e.clientX - rect.left) / rect.width,
+            (e.clientY - rect.top) / rect.height,
+        );
+    });
+
+    function updateLobbyToy(toy) {
+        lobbyToyEl.classList.toggle("hidden", !toy);
+        if (!toy) return;
+
+        const key = JSON.stringify([toy, i18n.language && i18n.language()]);
+        if (key === lastLobbyToyKey) return;
+        lastLobbyToyKey = key;
+
+        lobbyToyTarget.style.left = `${(toy.x - toy.radius) * 100}%`;
+        lobbyToyTarget.style.top = `${(toy.y - toy.radius) * 100}%`;
+        lobbyToyTarget.style.width = `${toy.radius * 200}%`;
+        lobbyToyTarget.style.height = `${toy.radius * 200}%`;
+        lobbyToyHits.textContent = t("ui.lobby_toy_hits", { hits: toy.myHits });
+        lobbyToyBoard.innerHTML = toy.board.slice(0, 5).map((e) =>
+            `<li><span>${escapeHtml(e.name)}</span><span>${e.hits}</span></li>`
+        ).join("");
+    }
+
+    // ── Event filter (leader only) ───────────────────────
+    const eventFilterEl      = $("event-filter");
+    const eventFilterSources = $("event-filter-sources");
+    const eventFilterTags    = $("event-filter-tags");
+    const eventFilterTypes   = $("event-filter-types");
+    const eventFilterPrio    = $("event-filter-priority");
+    let lastEventFilterKey = "";
+
+    // Empty lists mean "everything", so blank fields let all alerts through.
+    function splitList(value) {
+        return value.split(",").map((s) => s.trim()).filter(Boolean);
+    }
+
+    $("event-filter-apply").addEventListener("click", () => {
+        if (!window._bpSetEventFilter) return;
+        window._bpSetEventFilter(JSON.stringify({
+            sources: splitList(eventFilterSources.value),
+            tags: splitList(eventFilterTags.value),
+            types: splitList(eventFilterTypes.value),
+            minPriority: eventFilterPrio.value,
+        }));
+    });
+
+    function updateEventFilter(lobby) {
+        eventFilterEl.classList.toggle("hidden", !lobby.isLeader);
+        const filter = lobby.eventFilter;
+        if (!filter) return;
+        // Only overwrite the inputs when the room's filter changes, so a
+        // half-typed edit survives the per-frame refresh.
+        const key = JSON.stringify(filter);
+        if (key === lastEventFilterKey) return;
+        lastEventFilterKey = key;
+        eventFilterSources.value = filter.sources.join(", ");
+        eventFilterTags.value = filter.tags.join(", ");
+        eventFilterTypes.value = filter.types.join(", ");
+        eventFilterPrio.value = filter.minPriority;
+    }
+
+    // ── Room config import/export (leader only) ──────────
+    const roomConfigEl   = $("room-config");
+    const roomConfigText = $("room-config-text");
+    // Setting controls by the lobby setting they edit, so an imported
+    // config can be shown in them.
+    const SETTING_CONTROLS = [
+        ["setting-platformer-mode", "mode"],
+        ["setting-golf-mode", "mode"],
+        ["setting-lasertag-team-mode", "team_mode"],
+        ["setting-lasertag-team-select", "team_select"],
+        ["setting-lasertag-arena-size", "arena_size"],
+        ["setting-tron-movement", "movement"],
+        ["setting-platformer-course-vote", "course_vote"],
+        ["setting-lasertag-fog", "fog_of_war"],
+        ["setting-warmup", "warmup"],
+        ["setting-record-replay", "record_replay"],
+        ["setting-micro-rounds", "micro_rounds"],
+        ["setting-tron-overtime", "overtime"],
+        ["setting-tron-danger-map", "danger_map"],
+        ["setting-lasertag-late-join", "late_join"],
+        ["setting-tron-late-join", "late_join"],
+        ["setting-golf-course-intro", "course_intro"],
+        ["setting-golf-shot-hints", "shot_hints"],
+        ["setting-golf-night-hints", "night_hints"],
+        ["setting-golf-undo", "undo_window"],
+        ["setting-golf-caddie", "caddie_hints"],
+    ];
+    let roomConfigImported = false;
+
+    // Booleans are "on"/"off" options; everything else is its own value.
+    function setControl(el, value) {
+        if (!el || value == null) return;
+        const option = value === true ? "on" : value === false ? "off" : String(value);
+        if (el.querySelector(`option[value="${CSS.escape(option)}"]`)) el.value = option;
+    }
+
+    $("room-config-export").addEventListener("click", () => {
+        if (!window._bpExportRoomConfig) return;
+        roomConfigText.value = window._bpExportRoomConfig();
+        roomConfigText.select();
+        if (navigator.clipboard && navigator.clipboard.writeText) {
+            navigator.clipboard.writeText(roomConfigText.value).catch(() => {});
+        }
+    });
+
+    $("room-config-import").addEventListener("click", () => {
+        if (!window._bpImportRoomConfig) return;
+        window._bpImportRoomConfig(roomConfigText.value);
+        roomConfigImported = true;
+    });
+
+    function updateRoomConfig(lobby) {
+        roomConfigEl.classList.toggle("hidden", !lobby.isLeader);
+        if (!roomConfigImported) return;
+        roomConfigImported = false;
+        // Show what the import changed; a refused import changed nothing
+        if (lobby.selectedGame && lobby.selectedGame !== selectedGame) {
+            selectedGame = lobby.selectedGame;
+            updateGameSettingsPanel();
+        }
+        const settings = lobby.gameSettings || {};
+        SETTING_CONTROLS.forEach(([id, key]) => setControl($(id), settings[key]));
+        if (lobby.pacing) {
+            setControl(pacingIntermission, lobby.pacing.intermissionSecs);
+            setControl(pacingAutoAdvance, lobby.pacing.autoAdvance);
+            setControl(pacingMinResults, lobby.pacing.minResultsSecs);
+        }
+    }
+
     // ── Copy room code button ────────────────────────────
     let copyBtnCreated = false;
     function ensureCopyButton(roomCode) {
@@ -412,15 +1368,17 @@
 
         const btn = document.createElement("button");
         btn.className = "room-code-copy";
-        btn.textContent = "Copy";
-        btn.setAttribute("aria-label", "Copy room code");
+        btn.textContent = t("ui.copy");
+        btn.setAttribute("aria-label", t("ui.copy_room_code"));
+        btn.dataset.i18n = "ui.copy";
+        btn.dataset.i18nAriaLabel = "ui.copy_room_code";
         btn.addEventListener("click", () => {
             if (navigator.clipboard && navigator.clipboard.writeText) {
                 navigator.clipboard.writeText(roomCode).then(() => {
-                    btn.textContent = "Copied!";
+                    btn.textContent = t("ui.copied");
                     btn.classList.add("copied");
                     setTimeout(() => {
-                        btn.textContent = "Copy";
+                        btn.textContent = t("ui.copy");
                         btn.classList.remove("copied");
                     }, 2000);
                 });
@@ -431,8 +1389,8 @@
                 const sel = window.getSelection();
                 sel.removeAllRanges();
                 sel.addRange(range);
-                btn.textContent = "Selected!";
-                setTimeout(() => { btn.textContent = "Copy"; }, 2000);
+                btn.textContent = t("ui.selected");
+                setTimeout(() => { btn.textContent = t("ui.copy"); }, 2000);
             }
         });
         codeDisplay.appendChild(btn);
@@ -444,11 +1402,17 @@
         if (state.appState !== "InGame") return;
 
         const gameId = state.game ? state.game.gameId : selectedGame;
-        hudGameName.textContent = GAME_NAMES[gameId] || gameId || "";
-        hudControls.textContent = CONTROLS[gameId] || CONTROLS[selectedGame] || "";
+        const gameKey = GAME_KEYS[gameId] || GAME_KEYS[selectedGame];
+        hudGameName.textContent = gameKey ? t(`game.${gameKey}`) : (gameId || "");
+        hudControls.textContent = gameKey
+            ? `${t(`controls.${gameKey}`)} · ${t("ui.scoreboard_hint")}`
+            : "";
 
         if (state.roundTracker) {
-            hudRound.textContent = `Round ${state.roundTracker.currentRound} / ${state.roundTracker.totalRounds}`;
+            hudRound.textContent = t("ui.hud_round", {
+                current: state.roundTracker.currentRound,
+                total: state.roundTracker.totalRounds,
+            });
             hudRound.classList.remove("hidden");
         } else {
             hudRound.classList.add("hidden");
@@ -456,6 +1420,229 @@
 
         // Spectator badge
         updateSpectatorBadge(state);
+
+        updateRoundPhase(state);
+        updateScoreboard(state.scoreboard);
+    }
+
+    // ── Hold-to-view scoreboard ──────────────────────────
+    let lastScoreboardKey = "";
+
+    function updateScoreboard(board) {
+        scoreboardEl.classList.toggle("hidden", !board);
+        if (!board) {
+            lastScoreboardKey = "";
+            return;
+        }
+        const key = JSON.stringify([board, i18n.language && i18n.language()]);
+        if (key === lastScoreboardKey) return;
+        lastScoreboardKey = key;
+
+        if (board.waiting) {
+            scoreboardEl.innerHTML = `<p>${escapeHtml(t("ui.scoreboard_waiting"))}</p>`;
+            return;
+        }
+        const teamName = (team) => t("ui.scoreboard_team", { team: team + 1 });
+        const title = t("ui.hud_round", { current: board.round, total: board.roundCount });
+        const teams = board.teams.map((s) =>
+            `<span>${escapeHtml(teamName(s.team))}: ${s.score}</span>`
+        ).join("");
+        const rows = board.players.map((p) => {
+            const name = p.team == null
+                ? escapeHtml(p.name)
+                : `${escapeHtml(p.name)} <small>${escapeHtml(teamName(p.team))}</small>`;
+            const ping = p.ping == null ? "–" : `${p.ping} ms`;
+            return `<tr${p.isLocal ? ' class="local"' : ""}>` +
+                `<td>${name}</td><td>${p.roundScore}</td><td>${p.matchScore}</td><td>${ping}</td></tr>`;
+        }).join("");
+        scoreboardEl.innerHTML =
+            `<div class="scoreboard-title">${escapeHtml(title)}</div>` +
+            (teams ? `<div class="scoreboard-teams">${teams}</div>` : "") +
+            `<table><thead><tr><th>${escapeHtml(t("ui.player"))}</th>` +
+            `<th>${escapeHtml(t("ui.scoreboard_round"))}</th>` +
+            `<th>${escapeHtml(t("ui.scoreboard_total"))}</th>` +
+            `<th>${escapeHtml(t("ui.scoreboard_ping"))}</th></tr></thead>` +
+            `<tbody>${rows}</tbody></table>`;
+    }
+
+    function updateReplayHud(state) {
+        if (state.appState === "GameOver") {
+            btnDownloadReplay.classList.toggle("hidden", !state.replayUrl);
+            if (state.replayUrl) btnDownloadReplay.href = state.replayUrl;
+            btnDownloadInputs.classList.toggle("hidden", !state.inputReplay);
+        }
+        if (state.appState !== "Replay" || !state.replay) return;
+        const r = state.replay;
+        const gameKey = GAME_KEYS[r.gameId];
+        const gameName = gameKey ? t(`game.${gameKey}`) : r.gameId;
+        const round = t("ui.hud_round", { current: r.round, total: r.totalRounds });
+        replayLabel.textContent = `${gameName} · ${round}`;
+        replayProgress.style.width = `${Math.round(r.progress * 100)}%`;
+        btnReplayPause.textContent = t(r.paused || r.finished ? "ui.replay_play" : "ui.replay_pause");
+        btnReplayPause.disabled = r.finished;
+    }
+
+    // ── Round countdown / freeze frame / warmup ─────────
+    const roundPhaseEl = $("round-phase");
+    const warmupBanner = $("warmup-banner");
+    const GO_DISPLAY_MS = 800;
+    let goUntil = 0;
+
+    function updateRoundPhase(state) {
+        const rp = state.roundPhase;
+        const warmingUp = !!rp && rp.phase === "Warmup";
+        warmupBanner.classList.toggle("hidden", !warmingUp);
+        btnStartMatch.classList.toggle("hidden", !(warmingUp && state.lobby && state.lobby.isLeader));
+        if (!rp) {
+            roundPhaseEl.classList.add("hidden");
+            return;
+        }
+        const prevPhase = prevState && prevState.roundPhase && prevState.roundPhase.phase;
+        if (rp.phase === "Playing" && prevPhase === "Countdown") {
+            goUntil = performance.now() + GO_DISPLAY_MS;
+        }
+
+        let text = "";
+        if (rp.phase === "Countdown") {
+            text = String(Math.max(1, Math.ceil(rp.remaining || 0)));
+        } else if (rp.phase === "Playing" && performance.now() < goUntil) {
+            text = t("ui.round_go");
+        } else if (rp.phase === "FreezeFrame") {
+            text = t("ui.round_finish");
+        }
+        roundPhaseEl.textContent = text;
+        roundPhaseEl.classList.toggle("hidden", !text);
+    }
+
+    // ── Platformer course vote ───────────────────────────
+    const courseVoteEl      = $("course-vote");
+    const courseVoteOptions = $("course-vote-options");
+    const courseVoteStatus  = $("course-vote-status");
+    const THUMB_CELL_PX = 4;
+    // Indexed by minimap cell kind: open, solid, water, hazard, checkpoint, finish
+    const THUMB_COLORS = ["#12122a", "#667", "#38a", "#c44", "#6c6", "#fc6"];
+    let courseVoteRound = null;
+
+    function drawCourseThumbnail(canvas, candidate) {
+        canvas.width = candidate.cols * THUMB_CELL_PX;
+        canvas.height = candidate.rows * THUMB_CELL_PX;
+        const ctx = canvas.getContext("2d");
+        if (!ctx) return;
+        candidate.cells.forEach((cell, i) => {
+            ctx.fillStyle = THUMB_COLORS[cell] || THUMB_COLORS[0];
+            ctx.fillRect(
+                (i % candidate.cols) * THUMB_CELL_PX,
+                Math.floor(i / candidate.cols) * THUMB_CELL_PX,
+                THUMB_CELL_PX,
+                THUMB_CELL_PX,
+            );
+        });
+    }
+
+    function updateCourseVote(state) {
+        const vote = state.courseVote;
+        courseVoteEl.classList.toggle("hidden", !vote);
+        if (!vote) {
+            courseVoteRound = null;
+            return;
+        }
+
+        // Thumbnails are drawn once, when the vote opens
+        if (courseVoteRound !== vote.round) {
+            courseVoteRound = vote.round;
+            courseVoteOptions.textContent = "";
+            vote.candidates.forEach((candidate, i) => {
+                const btn = document.createElement("button");
+                btn.className = "course-vote-option";
+                btn.dataset.testid = `course-vote-option-${i}`;
+                const canvas = document.createElement("canvas");
+                drawCourseThumbnail(canvas, candidate);
+                const count = document.createElement("span");
+                count.className = "course-vote-count";
+                btn.append(canvas, count);
+                btn.addEventListener("click", () => {
+                    if (window._bpCastCourseVote) window._bpCastCourseVote(i);
+                });
+                courseVoteOptions.appendChild(btn);
+            });
+        }
+
+        const closed = vote.winner != null;
+        Array.from(courseVoteOptions.children).forEach((btn, i) => {
+            btn.classList.toggle("mine", vote.myChoice === i);
+            btn.classList.toggle("winner", vote.winner === i);
+            btn.disabled = closed;
+            btn.querySelector(".course-vote-count").textContent =
+                t("ui.course_vote_count", { n: vote.votes[i] || 0 });
+        });
+        courseVoteStatus.textContent = closed
+            ? t("ui.course_vote_chosen", { n: vote.winner + 1 })
+            : t("ui.course_vote_closes", { secs: Math.ceil(vote.remaining) });
+    }
+
+    // ── Laser tag captain draft ──────────────────────────
+    const teamDraftEl     = $("team-draft");
+    const teamDraftTeams  = $("team-draft-teams");
+    const teamDraftPool   = $("team-draft-pool");
+    const teamDraftStatus = $("team-draft-status");
+    let teamDraftKey = null;
+
+    function updateTeamDraft(state) {
+        const draft = state.teamDraft;
+        teamDraftEl.classList.toggle("hidden", !draft);
+        if (!draft) {
+            teamDraftKey = null;
+            return;
+        }
+
+        // Rebuild only after a pick, so buttons survive between frames
+        const key = JSON.stringify([draft.teams, draft.pool, draft.turn, draft.myTurn]);
+        if (key !== teamDraftKey) {
+            teamDraftKey = key;
+            renderTeamDraft(draft);
+        }
+
+        const secs = Math.ceil(draft.remaining);
+        if (draft.turn == null) {
+            teamDraftStatus.textContent = t("ui.team_draft_done");
+        } else if (draft.myTurn) {
+            teamDraftStatus.textContent = t("ui.team_draft_your_pick", { secs });
+        } else {
+            teamDraftStatus.textContent = t("ui.team_draft_picking", { name: draft.picker, secs });
+        }
+    }
+
+    function renderTeamDraft(draft) {
+        teamDraftTeams.textContent = "";
+        draft.teams.forEach((team, i) => {
+            const col = document.createElement("div");
+            col.className = "team-draft-team";
+            col.classList.toggle("picking", draft.turn === i);
+            const title = document.createElement("h3");
+            title.textContent = t("ui.scoreboard_team", { team: i + 1 });
+            const captain = document.createElement("div");
+            captain.className = "captain";
+            captain.textContent = t("ui.team_draft_captain", { name: team.captain });
+            col.append(title, captain);
+            team.members.forEach((name) => {
+                const member = document.createElement("div");
+                member.textContent = name;
+                col.appendChild(member);
+            });
+            teamDraftTeams.appendChild(col);
+        });
+
+        teamDraftPool.textContent = "";
+        draft.pool.forEach((player) => {
+            const btn = document.createElement("button");
+            btn.dataset.testid = `team-draft-pick-${player.id}`;
+            btn.textContent = player.name;
+            btn.disabled = !draft.myTurn;
+            btn.addEventListener("click", () => {
+                if (window._bpDraftPick) window._bpDraftPick(player.id);
+            });
+            teamDraftPool.appendChild(btn);
+        });
     }
 
     // ── Spectator badge ──────────────────────────────────
@@ -467,7 +1654,8 @@
                 badge = document.createElement("div");
                 badge.id = "spectator-badge";
                 badge.className = "spectator-badge";
-                badge.textContent = "SPECTATOR";
+                badge.dataset.i18n = "ui.spectator";
+                badge.textContent = t("ui.spectator");
                 gameHud.appendChild(badge);
             }
             badge.classList.remove("hidden");
@@ -483,7 +1671,21 @@
     const golfHudEl     = $("golf-hud");
     const golfHoleName  = $("golf-hole-name");
     const golfPar       = $("golf-par");
+    const golfLength    = $("golf-length");
+    const golfIntro     = $("golf-intro");
     const golfStrokes   = $("golf-player-strokes");
+    const golfUndo      = $("golf-undo");
+    if (golfUndo) {
+        golfUndo.addEventListener("click", () => {
+            if (window._bpGolfUndo) window._bpGolfUndo();
+        });
+    }
+    const golfCaddie    = $("golf-caddie");
+    if (golfCaddie) {
+        golfCaddie.addEventListener("click", () => {
+            if (window._bpGolfCaddie) window._bpGolfCaddie();
+        });
+    }
 
     function updateGolfHud(state) {
         const hud = state.golfHud;
@@ -492,8 +1694,30 @@
             return;
         }
         golfHudEl.classList.remove("hidden");
-        golfHoleName.textContent = hud.holeName || `Hole ${(hud.holeIndex || 0) + 1}`;
-        golfPar.textContent = `Par ${hud.par}`;
+        golfHoleName.textContent = hud.holeName || t("golf.hole", { n: (hud.holeIndex || 0) + 1 });
+        golfPar.textContent = hud.closestToPin ? t("golf.closest_to_pin") : t("golf.par", { par: hud.par });
+        if (hud.night) golfPar.textContent += ` · ${t("golf.night")}`;
+        golfLength.textContent = hud.holeLength ? t("golf.length", { m: hud.holeLength }) : "";
+
+        const intro = hud.intro;
+        golfIntro.classList.toggle("hidden", !intro);
+        if (intro) {
+            const votes = t("golf.skip_votes", { votes: intro.votes, players: intro.players });
+            golfIntro.textContent = intro.voted ? votes : `${t("golf.skip_intro")} ${votes}`;
+        }
+
+        const canUndo = hud.undoRemaining != null;
+        golfUndo.classList.toggle("hidden", !canUndo);
+        if (canUndo) {
+            golfUndo.textContent = t("golf.undo", { s: Math.ceil(hud.undoRemaining * 10) / 10 });
+        }
+
+        const hasCaddie = hud.caddieLeft != null;
+        golfCaddie.classList.toggle("hidden", !hasCaddie);
+        if (hasCaddie) {
+            golfCaddie.disabled = hud.caddieShowing || hud.caddieLeft === 0;
+            golfCaddie.textContent = t("golf.caddie", { n: hud.caddieLeft });
+        }
 
         let html = "";
         for (const p of hud.players) {
@@ -501,7 +1725,7 @@
             const sunkLabel = p.isSunk ? (p.sunkRank ? ` (#${p.sunkRank})` : " \u2713") : "";
             html += `<div class="hud-player-row${sunkClass}">
                 <span class="name">${escapeHtml(p.name)}${sunkLabel}</span>
-                <span class="value">${p.strokes}</span>
+                <span class="value">${p.distance != null ? t("golf.length", { m: p.distance }) : p.strokes}</span>
             </div>`;
         }
         golfStrokes.innerHTML = html;
@@ -540,7 +1764,8 @@
             return;
         }
         platformerHudEl.classList.remove("hidden");
-        platformerMode.textContent = hud.mode || "Race";
+        const modeKeys = { Survival: "ui.mode_survival", Coop: "ui.mode_coop" };
+        platformerMode.textContent = t(modeKeys[hud.mode] || "ui.mode_race");
 
         // Race position
         if (hud.racePosition && hud.totalRacers) {
@@ -570,6 +1795,22 @@
         if (hud.totalCheckpoints > 0) {
             statusParts.push(`CP: ${hud.localCheckpoint}/${hud.totalCheckpoints}`);
         }
+        if (hud.localStumbling) statusParts.push(t("platformer.stumbled"));
+        else if (hud.localHoldsBanana) statusParts.push(t("platformer.banana_ready"));
+        if (hud.mode === "Survival") {
+            statusParts.push(t("platformer.alive", { n: hud.aliveCount }));
+            statusParts.push(hud.hazardPaused
+                ? t("platformer.hazard_paused")
+                : t("platformer.hazard_speed", { speed: hud.hazardSpeed.toFixed(1) }));
+        }
+        if (hud.mode === "Coop") {
+            statusParts.push(t("platformer.coop_lives", { n: hud.coopLives }));
+            if (hud.localDowned) {
+                statusParts.push(hud.localReviveProgress > 0
+                    ? t("platformer.reviving", { pct: Math.round(hud.localReviveProgress * 100) })
+                    : t("platformer.downed"));
+            }
+        }
         platformerStatus.textContent = statusParts.join(" | ");
 
         // Checkpoint toast
@@ -780,6 +2021,9 @@
     const lasertagTimer  = $("lasertag-timer");
     const lasertagScores = $("lasertag-scores");
     const lasertagStun   = $("lasertag-stun");
+    const lasertagEnergy = $("lasertag-energy");
+    const lasertagEnergyLabel = $("lasertag-energy-label");
+    const lasertagEnergyFill = $("lasertag-energy-fill");
 
     // Kill feed tracking
     let prevLasertagTags = {};
@@ -802,6 +2046,18 @@
             lasertagStun.classList.toggle("hidden", !(hud.localStunRemaining > 0));
         }
 
+        // Energy bar (only when the energy system is enabled)
+        const hasEnergy = hud.energyMax > 0;
+        lasertagEnergy.classList.toggle("hidden", !hasEnergy);
+        if (hasEnergy) {
+            const pct = Math.max(0, Math.min(100, (hud.localEnergy / hud.energyMax) * 100));
+            lasertagEnergyFill.style.width = `${pct}%`;
+            lasertagEnergy.classList.toggle("overheated", !!hud.localOverheated);
+            lasertagEnergyLabel.textContent = hud.localOverheated
+                ? t("lasertag.overheated")
+                : t("lasertag.energy");
+        }
+
         // Detect tag changes for kill feed
         for (const p of hud.players) {
             const prevTags = prevLasertagTags[p.id] || 0;
@@ -828,9 +2084,12 @@
 
         for (const p of sorted) {
             const stunnedClass = p.stunned ? " stunned" : "";
+            const assists = p.assists
+                ? ` <span class="assists">${escapeHtml(t("lasertag.assists", { n: p.assists }))}</span>`
+                : "";
             html += `<div class="hud-player-row${stunnedClass}">
                 <span class="name">${escapeHtml(p.name)}</span>
-                <span class="value">${p.tags || 0}</span>
+                <span class="value">${p.tags || 0}${assists}</span>
             </div>`;
         }
         lasertagScores.innerHTML = html;
@@ -898,7 +2157,7 @@
 
         updateTronPlayerNames(hud.players);
         updateTronGauges(hud.players);
-        updateTronEliminatedOverlay(hud.players);
+        updateTronEliminatedOverlay(hud);
 
         // Minimap — update every 5th frame for performance
         tronMinimapFrame++;
@@ -966,15 +2225,21 @@
         tronBrakeFill.style.width = brakePct + "%";
     }
 
-    function updateTronEliminatedOverlay(players) {
-        const local = players.find((p) => p.isLocal);
-        if (local && !local.alive) {
+    function updateTronEliminatedOverlay(hud) {
+        const local = hud.players.find((p) => p.isLocal);
+        // A draw outranks elimination; sudden death shows as a smaller banner
+        let text = null;
+        if (hud.draw) text = t("tron.draw");
+        else if (local && !local.alive) text = "ELIMINATED";
+        else if (hud.overtime) text = t("tron.sudden_death");
+        if (text) {
             if (!tronEliminatedEl) {
                 tronEliminatedEl = document.createElement("div");
                 tronEliminatedEl.className = "tron-eliminated";
-                tronEliminatedEl.textContent = "ELIMINATED";
                 gameHud.appendChild(tronEliminatedEl);
             }
+            tronEliminatedEl.textContent = text;
+            tronEliminatedEl.classList.toggle("overtime", text !== "ELIMINATED" && !hud.draw);
         } else if (tronEliminatedEl) {
             tronEliminatedEl.remove();
             tronEliminatedEl = null;
@@ -1034,10 +2299,10 @@
 
     // ── Score screens ───────────────────────────────────
     const SCORE_LABELS = {
-        "mini-golf": "Strokes", "Golf": "Strokes",
-        "laser-tag": "Tags", "LaserTag": "Tags",
-        "platform-racer": "Score", "Platformer": "Score",
-        "tron": "Score", "Tron": "Score",
+        "mini-golf": "ui.strokes", "Golf": "ui.strokes",
+        "laser-tag": "ui.tags", "LaserTag": "ui.tags",
+        "platform-racer": "ui.score", "Platformer": "ui.score",
+        "tron": "ui.score", "Tron": "ui.score",
     };
 
     function getScoreOpts(state, isGameOver) {
@@ -1045,7 +2310,8 @@
         const isGolf = gameId === "mini-golf" || gameId === "Golf";
         return {
             roundHistory: state.roundTracker.roundScoresHistory || null,
-            scoreLabel: SCORE_LABELS[gameId] || "Score",
+            disconnected: state.roundTracker.disconnected || [],
+            scoreLabel: t(SCORE_LABELS[gameId] || "ui.score"),
             isGameOver,
             isGolf,
         };
@@ -1054,42 +2320,108 @@
     function updateScoreScreens(state) {
         if (state.appState === "BetweenRounds" && state.roundTracker) {
             renderScores(roundScores, state.roundTracker.scores, state.lobby.players, getScoreOpts(state, false));
-            roundInfoEl.textContent = `Round ${state.roundTracker.currentRound} of ${state.roundTracker.totalRounds}`;
+            roundInfoEl.textContent = t("ui.round_of", {
+                current: state.roundTracker.currentRound,
+                total: state.roundTracker.totalRounds,
+            });
             // Between-round countdown with progress bar
-            if (roundCountdown && state.betweenRoundCountdown != null) {
+            if (roundCountdown && state.waitingForHost) {
+                roundCountdown.textContent = t(state.lobby.isLeader ? "ui.start_next_round_when_ready" : "ui.waiting_for_host");
+            } else if (roundCountdown && state.betweenRoundCountdown != null) {
                 const secs = Math.ceil(state.betweenRoundCountdown);
-                const totalSecs = 8; // typical between-round duration
+                const totalSecs = state.betweenRoundTotal || secs || 1;
                 const pct = Math.max(0, Math.min(100, (secs / totalSecs) * 100));
                 roundCountdown.innerHTML = secs > 0
-                    ? `Next round in ${secs}s...<div class="countdown-bar"><div class="countdown-bar-fill" style="width:${pct}%"></div></div>`
+                    ? `${escapeHtml(t("ui.next_round_in", { secs }))}<div class="countdown-bar"><div class="countdown-bar-fill" style="width:${pct}%"></div></div>`
                     : "";
             } else if (roundCountdown) {
                 roundCountdown.innerHTML = "";
             }
+            if (btnNextRound) btnNextRound.classList.toggle("hidden", !state.lobby.isLeader);
+            drawRoundHeatmap(state.roundHeatmap);
+            showAnnouncer(roundAnnouncer, state.victory);
         }
 
         if (state.appState === "GameOver" && state.roundTracker) {
             renderScores(finalScores, state.roundTracker.scores, state.lobby.players, getScoreOpts(state, true));
+            showAnnouncer(gameOverAnnouncer, state.victory);
             // Game-over auto-return countdown
             if (gameOverCountdown && state.gameOverCountdown != null) {
                 const secs = Math.ceil(state.gameOverCountdown);
-                gameOverCountdown.textContent = secs > 0 ? `Returning to lobby in ${secs}s...` : "";
+                gameOverCountdown.textContent = secs > 0 ? t("ui.returning_to_lobby_in", { secs }) : "";
             } else if (gameOverCountdown) {
                 gameOverCountdown.textContent = "";
             }
         }
     }
 
+    // Announcer banner: the game's victory line with winners' names.
+    function showAnnouncer(el, victory) {
+        if (!el) return;
+        el.classList.toggle("hidden", !victory);
+        el.textContent = victory ? i18n.localize(victory.message) : "";
+    }
+
+    // Presence as a blue-to-red tint per cell, tags as dots sized by count,
+    // arena walls on top.
+    function drawRoundHeatmap(map) {
+        if (!roundHeatmap) return;
+        roundHeatmap.classList.toggle("hidden", !map);
+        if (!map || !roundHeatmapCtx) return;
+
+        const ctx = roundHeatmapCtx;
+        const canvas = roundHeatmapCanvas;
+        const aspect = (map.depth || 1) / (map.width || 1);
+        canvas.height = Math.round(canvas.width * aspect);
+        const w = canvas.width;
+        const h = canvas.height;
+        const cw = w / map.cols;
+        const ch = h / map.rows;
+        ctx.clearRect(0, 0, w, h);
+
+        for (let r = 0; r < map.rows; r++) {
+            for (let c = 0; c < map.cols; c++) {
+                const heat = map.presence[r * map.cols + c] / 255;
+                if (heat <= 0) continue;
+                ctx.fillStyle = `hsla(${Math.round(240 - heat * 240)}, 90%, 50%, ${0.25 + heat * 0.6})`;
+                ctx.fillRect(c * cw, r * ch, cw + 0.5, ch + 0.5);
+            }
+        }
+
+        const sx = (x) => (x / map.width) * w;
+        const sy = (z) => (z / map.depth) * h;
+        ctx.strokeStyle = "rgba(255,255,255,0.5)";
+        ctx.lineWidth = 1;
+        for (const wall of map.walls || []) {
+            ctx.beginPath();
+            ctx.moveTo(sx(wall[0]), sy(wall[1]));
+            ctx.lineTo(sx(wall[2]), sy(wall[3]));
+            ctx.stroke();
+        }
+
+        ctx.fillStyle = "#fff";
+        for (let i = 0; i < map.tags.length; i++) {
+            const tags = map.tags[i];
+            if (!tags) continue;
+            const x = (i % map.cols + 0.5) * cw;
+            const y = (Math.floor(i / map.cols) + 0.5) * ch;
+            ctx.beginPath();
+            ctx.arc(x, y, Math.min(2 + tags, Math.min(cw, ch) / 2 + 2), 0, Math.PI * 2);
+            ctx.fill();
+        }
+    }
+
     function renderScores(container, scores, players, opts) {
         if (!scores) {
-            container.innerHTML = "<p>Waiting for scores...</p>";
+            container.innerHTML = `<p>${escapeHtml(t("ui.waiting_for_scores"))}</p>`;
             return;
         }
 
         const roundHistory = (opts && opts.roundHistory) || null;
-        const scoreLabel = (opts && opts.scoreLabel) || "Score";
+        const scoreLabel = (opts && opts.scoreLabel) || t("ui.score");
         const isGameOver = (opts && opts.isGameOver) || false;
         const isGolf = (opts && opts.isGolf) || false;
+        const disconnected = (opts && opts.disconnected) || [];
 
         // Convert scores object to sorted array
         const entries = Object.entries(scores)
@@ -1109,7 +2441,7 @@
         if (roundHistory && roundHistory.length > 1) {
             html += `<div class="score-row score-header">
                 <span class="rank"></span>
-                <span class="name">Player</span>`;
+                <span class="name">${escapeHtml(t("ui.player"))}</span>`;
             for (let r = 0; r < roundHistory.length; r++) {
                 html += `<span class="round-col">R${r + 1}</span>`;
             }
@@ -1122,9 +2454,13 @@
             const colorIdx = entries.findIndex((x) => x.pid === e.pid);
             const dotColor = PLAYER_COLORS[colorIdx % PLAYER_COLORS.length];
 
+            const leftTag = disconnected.includes(e.pid)
+                ? ` <span class="score-left">${escapeHtml(t("ui.player_left"))}</span>`
+                : "";
+
             html += `<div class="score-row${winnerClass}">
                 <span class="rank">${medal || (i + 1) + "."}</span>
-                <span class="name"><span class="player-dot" style="background:${dotColor}"></span>${escapeHtml(e.name)}</span>`;
+                <span class="name"><span class="player-dot" style="background:${dotColor}"></span>${escapeHtml(e.name)}${leftTag}</span>`;
 
             // Per-round columns
             if (roundHistory && roundHistory.length > 1) {
@@ -1148,16 +2484,15 @@
 
         // Winner announcement
         if (isGameOver && entries.length > 0) {
-            html += `<div class="winner-announce">${escapeHtml(entries[0].name)} wins!</div>`;
+            html += `<div class="winner-announce">${escapeHtml(t("ui.wins", { name: entries[0].name }))}</div>`;
         }
 
         container.innerHTML = html;
     }
 
     function findPlayerName(pid, players) {
-        if (!players) return `Player ${pid}`;
-        const p = players.find((p) => p.id === pid);
-        return p ? p.name : `Player ${pid}`;
+        const p = players && players.find((p) => p.id === pid);
+        return p ? p.name : t("ui.player_fallback", { id: pid });
     }
 
     // ── Overlay (ticker, toasts, badge) ─────────────────
@@ -1225,7 +2560,7 @@
                 const el = activeToasts.get(toast.id);
                 const actions = el.querySelector(".toast-actions");
                 if (toast.claimedBy && actions) {
-                    actions.innerHTML = `<span class="toast-claimed" data-testid="toast-claimed">Claimed by ${escapeHtml(toast.claimedBy)}</span>`;
+                    actions.innerHTML = `<span class="toast-claimed" data-testid="toast-claimed">${escapeHtml(t("ui.claimed_by", { name: toast.claimedBy }))}</span>`;
                 }
             } else {
                 // Create new toast
@@ -1234,10 +2569,10 @@
                 el.dataset.testid = `toast-${toast.id}`;
                 el.innerHTML = `
                     <div class="toast-title" data-testid="toast-title">${escapeHtml(toast.title)}</div>
-                    <div class="toast-meta" data-testid="toast-meta">${escapeHtml(toast.source || "")} ${toast.actor ? "by " + escapeHtml(toast.actor) : ""}</div>
+                    <div class="toast-meta" data-testid="toast-meta">${escapeHtml(toast.source || "")} ${toast.actor ? escapeHtml(t("ui.by_actor", { actor: toast.actor })) : ""}</div>
                     <div class="toast-actions" data-testid="toast-actions">
                         ${toast.claimedBy
-                            ? `<span class="toast-claimed" data-testid="toast-claimed">Claimed by ${escapeHtml(toast.claimedBy)}</span>`
+                            ? `<span class="toast-claimed" data-testid="toast-claimed">${escapeHtml(t("ui.claimed_by", { name: toast.claimedBy }))}</span>`
                             : `<button class="toast-claim-btn" data-testid="toast-claim-btn" data-event-id="${escapeHtml(toast.id)}">Claim</button>`
                         }
                     </div>`;
@@ -1299,11 +2634,11 @@
         if (state.muted) {
             btnMute.classList.add("muted");
             btnMute.innerHTML = "&#x1f507;";
-            btnMute.setAttribute("aria-label", "Unmute audio");
+            btnMute.setAttribute("aria-label", t("ui.unmute_audio"));
         } else {
             btnMute.classList.remove("muted");
             btnMute.innerHTML = "&#x1f50a;";
-            btnMute.setAttribute("aria-label", "Mute audio");
+            btnMute.setAttribute("aria-label", t("ui.mute_audio"));
         }
     }
 